@@ -14,6 +14,7 @@ no_std_packages=(
 #   reth-primitives
 #   reth-primitives-traits
 #   reth-revm
+  reth-trie-common
 )
 
 for package in "${no_std_packages[@]}"; do
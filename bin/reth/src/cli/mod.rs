@@ -6,9 +6,10 @@ use crate::{
         LogArgs,
     },
     commands::{
-        config_cmd, db, debug_cmd, dump_genesis, import, init_cmd, init_state,
+        bootnode, config_cmd, db, debug_cmd, dump_genesis, export, import, init_cmd, init_state,
+        migrate,
         node::{self, NoArgs},
-        p2p, recover, stage, test_vectors,
+        p2p, recover, space, stage, test_vectors,
     },
     version::{LONG_VERSION, SHORT_VERSION},
 };
@@ -64,6 +65,8 @@ pub struct Cli<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// - `AUTH_PORT`: default + `instance` * 100 - 100
     /// - `HTTP_RPC_PORT`: default - `instance` + 1
     /// - `WS_RPC_PORT`: default + `instance` * 2 - 2
+    /// - `GRAPHQL_PORT`: default + `instance` * 100 - 100
+    /// - `GRPC_PORT`: default + `instance` * 100 - 100
     #[arg(long, value_name = "INSTANCE", global = true, default_value_t = 1, value_parser = value_parser!(u16).range(..=200))]
     instance: u16,
 
@@ -150,6 +153,7 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Init(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::InitState(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Import(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Migrate(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
             Commands::ImportOp(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             #[cfg(feature = "optimism")]
@@ -160,10 +164,15 @@ impl<Ext: clap::Args + fmt::Debug> Cli<Ext> {
             Commands::Db(command) => runner.run_blocking_until_ctrl_c(command.execute()),
             Commands::Stage(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::P2P(command) => runner.run_until_ctrl_c(command.execute()),
+            Commands::Bootnode(command) => {
+                runner.run_command_until_exit(|ctx| command.execute(ctx))
+            }
             Commands::TestVectors(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Config(command) => runner.run_until_ctrl_c(command.execute()),
             Commands::Debug(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
             Commands::Recover(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+            Commands::Space(command) => runner.run_blocking_until_ctrl_c(command.execute()),
+            Commands::Export(command) => runner.run_blocking_until_ctrl_c(command.execute()),
         }
     }
 
@@ -192,6 +201,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// This syncs RLP encoded blocks from a file.
     #[command(name = "import")]
     Import(import::ImportCommand),
+    /// Migrates the chain data of another client's database.
+    #[command(name = "migrate")]
+    Migrate(migrate::MigrateCommand),
     /// This syncs RLP encoded OP blocks below Bedrock from a file, without executing.
     #[cfg(feature = "optimism")]
     #[command(name = "import-op")]
@@ -211,6 +223,9 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// P2P Debugging utilities
     #[command(name = "p2p")]
     P2P(p2p::Command),
+    /// Run only the discovery services, as a bootnode
+    #[command(name = "bootnode")]
+    Bootnode(bootnode::Command),
     /// Generate Test Vectors
     #[command(name = "test-vectors")]
     TestVectors(test_vectors::Command),
@@ -223,6 +238,12 @@ pub enum Commands<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// Scripts for node recovery
     #[command(name = "recover")]
     Recover(recover::Command),
+    /// Disk usage reports of the data directory
+    #[command(name = "space")]
+    Space(space::Command),
+    /// Export state from the database
+    #[command(name = "export")]
+    Export(export::Command),
 }
 
 #[cfg(test)]
@@ -0,0 +1,192 @@
+//! Command that runs a discovery-only bootnode.
+
+use crate::{
+    args::{
+        get_secret_key,
+        utils::{chain_help, chain_value_parser, SUPPORTED_CHAINS},
+        DatadirArgs, DiscoveryArgs,
+    },
+    prometheus_exporter,
+};
+use clap::Parser;
+use discv5::ListenConfig;
+use reth_chainspec::ChainSpec;
+use reth_cli_runner::CliContext;
+use reth_discv4::{Discv4, Discv4Config};
+use reth_discv5::{Discv5, NetworkStackId};
+use reth_net_nat::NatResolver;
+use reth_network_peers::NodeRecord;
+use reth_primitives::EnrForkIdEntry;
+use std::{
+    net::{IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6},
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// Runs only the discovery services of a node, so that it can be used as a bootnode.
+///
+/// No `RLPx` sessions are accepted and no database is opened.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = chain_help(),
+        default_value = SUPPORTED_CHAINS[0],
+        value_parser = chain_value_parser
+    )]
+    chain: Arc<ChainSpec>,
+
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// Secret key to use for this node.
+    ///
+    /// This will also deterministically set the peer ID. If not specified, it will be set in the
+    /// data dir for the chain being used.
+    #[arg(long, value_name = "PATH")]
+    p2p_secret_key: Option<PathBuf>,
+
+    /// NAT resolution method (any|none|upnp|publicip|extip:\<IP\>)
+    #[arg(long, default_value = "any")]
+    nat: NatResolver,
+
+    /// Comma separated enode URLs of other bootnodes to connect to.
+    #[arg(long, value_delimiter = ',')]
+    bootnodes: Vec<NodeRecord>,
+
+    #[command(flatten)]
+    discovery: DiscoveryArgs,
+
+    /// Enable Prometheus metrics.
+    ///
+    /// The metrics will be served at the given interface and port.
+    #[arg(long, value_name = "SOCKET")]
+    metrics: Option<SocketAddr>,
+}
+
+impl Command {
+    /// Execute `bootnode` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let DiscoveryArgs {
+            disable_discovery,
+            disable_discv4_discovery,
+            enable_discv5_discovery,
+            addr,
+            port,
+            discv5_addr,
+            discv5_addr_ipv6,
+            discv5_port,
+            discv5_port_ipv6,
+            discv5_lookup_interval,
+            discv5_bootstrap_lookup_interval,
+            discv5_bootstrap_lookup_countdown,
+            ..
+        } = self.discovery;
+        let enable_discv4 = !disable_discovery && !disable_discv4_discovery;
+        let enable_discv5 = !disable_discovery && enable_discv5_discovery;
+        if !enable_discv4 && !enable_discv5 {
+            eyre::bail!("No discovery service enabled")
+        }
+
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain);
+        let secret_key_path = self.p2p_secret_key.clone().unwrap_or_else(|| data_dir.p2p_secret());
+        let secret_key = get_secret_key(&secret_key_path)?;
+
+        if let Some(listen_addr) = self.metrics {
+            info!(target: "reth::cli", "Starting metrics endpoint at {}", listen_addr);
+            prometheus_exporter::serve_process_metrics(
+                listen_addr,
+                prometheus_exporter::install_recorder()?,
+                metrics_process::Collector::default(),
+                ctx.task_executor,
+            )
+            .await?;
+        }
+
+        let fork_id = self.chain.latest_fork_id();
+
+        let _discv4 = if enable_discv4 {
+            let external_ip = self.nat.external_addr().await.unwrap_or(addr);
+            let local_node_record =
+                NodeRecord::from_secret_key(SocketAddr::new(external_ip, port), &secret_key);
+            let config = Discv4Config::builder()
+                .external_ip_resolver(Some(self.nat))
+                .add_boot_nodes(self.bootnodes.clone())
+                .add_eip868_pair("eth", EnrForkIdEntry::from(fork_id))
+                .build();
+
+            let discv4 =
+                Discv4::spawn((addr, port).into(), local_node_record, secret_key, config).await?;
+            info!(target: "reth::cli", enode = %discv4.node_record(), "Started discv4");
+            Some(discv4)
+        } else {
+            None
+        };
+
+        if !enable_discv5 {
+            futures::future::pending::<()>().await;
+            return Ok(())
+        }
+
+        // Use discovery address if none given
+        let discv5_addr_ipv4 = discv5_addr.or(match addr {
+            IpAddr::V4(ip) => Some(ip),
+            IpAddr::V6(_) => None,
+        });
+        let discv5_addr_ipv6 = discv5_addr_ipv6.or(match addr {
+            IpAddr::V4(_) => None,
+            IpAddr::V6(ip) => Some(ip),
+        });
+
+        // there is no `RLPx` listener, so the node record doesn't advertise a TCP port
+        let mut config = reth_discv5::Config::builder((addr, 0).into())
+            .discv5_config(
+                discv5::ConfigBuilder::new(ListenConfig::from_two_sockets(
+                    discv5_addr_ipv4.map(|addr| SocketAddrV4::new(addr, discv5_port)),
+                    discv5_addr_ipv6.map(|addr| SocketAddrV6::new(addr, discv5_port_ipv6, 0, 0)),
+                ))
+                .build(),
+            )
+            .add_unsigned_boot_nodes(self.bootnodes.into_iter())
+            .lookup_interval(discv5_lookup_interval)
+            .bootstrap_lookup_interval(discv5_bootstrap_lookup_interval)
+            .bootstrap_lookup_countdown(discv5_bootstrap_lookup_countdown);
+        if let Some(network_stack_id) = NetworkStackId::id(&self.chain) {
+            config = config.fork(network_stack_id, fork_id);
+        }
+
+        let (discv5, mut updates, _) = Discv5::start(&secret_key, config.build()).await?;
+        let enr = discv5.with_discv5(|discv5| discv5.local_enr());
+        info!(target: "reth::cli", enr = %enr.to_base64(), "Started discv5");
+
+        while let Some(update) = updates.recv().await {
+            // keeps the kbuckets and metrics up to date
+            discv5.on_discv5_update(update);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bootnode_command() {
+        let cmd = Command::try_parse_from([
+            "reth",
+            "--enable-discv5-discovery",
+            "--bootnodes",
+            "enode://6f8a80d14311c39f35f516fa664deaaaa13e85b2f7493f37f6144d86991ec012937307647bd3b9a82abe2974e1407241d54947bbb39763a4cac9f77166ad92a0@10.3.58.6:30303?discport=30301",
+        ])
+        .unwrap();
+        assert!(cmd.discovery.enable_discv5_discovery);
+        assert_eq!(cmd.bootnodes.len(), 1);
+    }
+}
@@ -2,51 +2,189 @@ use crate::{
     commands::db::get::{maybe_json_value_parser, table_key},
     utils::DbTool,
 };
-use ahash::RandomState;
 use clap::Parser;
+use eyre::WrapErr;
 use reth_db::{DatabaseEnv, RawKey, RawTable, RawValue, TableViewer, Tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, table::Table, transaction::DbTx};
+use reth_fs_util as fs;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_primitives::{alloy_primitives::Keccak256, BlockNumber, B256};
+use reth_provider::BlockNumReader;
+use serde::{Deserialize, Serialize};
 use std::{
-    hash::{BuildHasher, Hasher},
+    collections::BTreeMap,
+    path::Path,
     sync::Arc,
     time::{Duration, Instant},
 };
 use tracing::{info, warn};
 
+/// Name of the file inside the database directory that stores the table checksums.
+const CHECKSUMS_FILE_NAME: &str = "checksums.json";
+
 #[derive(Parser, Debug)]
 /// The arguments for the `reth db checksum` command
 pub struct Command {
-    /// The table name
-    table: Tables,
+    /// The table name.
+    ///
+    /// Can be omitted together with `--store` or `--verify` to operate on all tables.
+    #[arg(required_unless_present_any = ["store", "verify"])]
+    table: Option<Tables>,
 
     /// The start of the range to checksum.
-    #[arg(long, value_parser = maybe_json_value_parser)]
+    #[arg(long, value_parser = maybe_json_value_parser, conflicts_with_all = ["store", "verify"])]
     start_key: Option<String>,
 
     /// The end of the range to checksum.
-    #[arg(long, value_parser = maybe_json_value_parser)]
+    #[arg(long, value_parser = maybe_json_value_parser, conflicts_with_all = ["store", "verify"])]
     end_key: Option<String>,
 
     /// The maximum number of records that are queried and used to compute the
     /// checksum.
-    #[arg(long)]
+    #[arg(long, conflicts_with_all = ["store", "verify"])]
     limit: Option<usize>,
+
+    /// Store the checksums of the tables together with the current block height, so they can be
+    /// checked later with `--verify`.
+    #[arg(long, conflicts_with = "verify")]
+    store: bool,
+
+    /// Compare the checksums of the tables against the stored ones.
+    ///
+    /// Tables whose checksum was stored at a different block height than the current one are
+    /// skipped, because their contents are expected to have changed.
+    #[arg(long)]
+    verify: bool,
 }
 
 impl Command {
     /// Execute `db checksum` command
-    pub fn execute(self, tool: &DbTool<Arc<DatabaseEnv>>) -> eyre::Result<()> {
+    pub fn execute(
+        self,
+        data_dir: ChainPath<DataDirPath>,
+        tool: &DbTool<Arc<DatabaseEnv>>,
+    ) -> eyre::Result<()> {
         warn!("This command should be run without the node running!");
-        self.table.view(&ChecksumViewer {
-            tool,
-            start_key: self.start_key,
-            end_key: self.end_key,
-            limit: self.limit,
-        })?;
+
+        if !self.store && !self.verify {
+            let table = self.table.expect("required by clap");
+            table.view(&ChecksumViewer {
+                tool,
+                start_key: self.start_key,
+                end_key: self.end_key,
+                limit: self.limit,
+            })?;
+            return Ok(())
+        }
+
+        let tables = self.table.map(|table| vec![table]).unwrap_or_else(|| Tables::ALL.to_vec());
+
+        if self.store {
+            return store_checksums(&data_dir, tool, &tables)
+        }
+
+        let path = data_dir.db().join(CHECKSUMS_FILE_NAME);
+        let block_number = tool.provider_factory.provider()?.best_block_number()?;
+        let Some(stored) = StoredChecksums::load(&path)? else {
+            eyre::bail!("No stored checksums found at {}", path.display())
+        };
+
+        let mut mismatches = Vec::new();
+        for table in tables {
+            let Some(expected) = stored.tables.get(table.name()) else {
+                warn!(target: "reth::cli", table = table.name(), "No stored checksum, skipping");
+                continue
+            };
+            if expected.block_number != block_number {
+                warn!(
+                    target: "reth::cli",
+                    table = table.name(),
+                    stored_block_number = expected.block_number,
+                    block_number,
+                    "Checksum was stored at a different block height, skipping"
+                );
+                continue
+            }
+
+            let (checksum, _) = ChecksumViewer::new(tool).view_rt(table)?;
+            if checksum == expected.checksum {
+                info!(target: "reth::cli", table = table.name(), "Checksum matches");
+            } else {
+                warn!(
+                    target: "reth::cli",
+                    table = table.name(),
+                    expected = %expected.checksum,
+                    got = %checksum,
+                    "Checksum mismatch"
+                );
+                mismatches.push(table.name());
+            }
+        }
+
+        if !mismatches.is_empty() {
+            eyre::bail!("Checksum mismatch for tables: {}", mismatches.join(", "))
+        }
+
+        Ok(())
+    }
+}
+
+/// Computes the checksums of the tables and stores them together with the current block height,
+/// so they can be checked later with `reth db checksum --verify`.
+pub(crate) fn store_checksums<DB: Database>(
+    data_dir: &ChainPath<DataDirPath>,
+    tool: &DbTool<DB>,
+    tables: &[Tables],
+) -> eyre::Result<()> {
+    let path = data_dir.db().join(CHECKSUMS_FILE_NAME);
+    let block_number = tool.provider_factory.provider()?.best_block_number()?;
+
+    let mut stored = StoredChecksums::load(&path)?.unwrap_or_default();
+    for table in tables {
+        let (checksum, _) = ChecksumViewer::new(tool).view_rt(*table)?;
+        stored.tables.insert(table.name().to_string(), TableChecksum { checksum, block_number });
+    }
+    stored.save(&path)?;
+    info!(target: "reth::cli", ?path, block_number, "Stored table checksums");
+
+    Ok(())
+}
+
+/// Table checksums persisted by `reth db checksum --store`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredChecksums {
+    /// Checksums keyed by table name.
+    tables: BTreeMap<String, TableChecksum>,
+}
+
+impl StoredChecksums {
+    /// Loads the stored checksums, returning `None` if the file doesn't exist.
+    fn load(path: &Path) -> eyre::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None)
+        }
+        let contents = fs::read_to_string(path)?;
+        let stored = serde_json::from_str(&contents)
+            .wrap_err_with(|| format!("Could not parse checksums file {}", path.display()))?;
+        Ok(Some(stored))
+    }
+
+    /// Writes the checksums to the file.
+    fn save(&self, path: &Path) -> eyre::Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
         Ok(())
     }
 }
 
+/// Checksum of a table at a given block height.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct TableChecksum {
+    /// Checksum of all entries of the table.
+    checksum: B256,
+    /// Best block number at the time the checksum was computed.
+    block_number: BlockNumber,
+}
+
 pub(crate) struct ChecksumViewer<'a, DB: Database> {
     tool: &'a DbTool<DB>,
     start_key: Option<String>,
@@ -60,10 +198,10 @@ impl<DB: Database> ChecksumViewer<'_, DB> {
     }
 }
 
-impl<DB: Database> TableViewer<(u64, Duration)> for ChecksumViewer<'_, DB> {
+impl<DB: Database> TableViewer<(B256, Duration)> for ChecksumViewer<'_, DB> {
     type Error = eyre::Report;
 
-    fn view<T: Table>(&self) -> Result<(u64, Duration), Self::Error> {
+    fn view<T: Table>(&self) -> Result<(B256, Duration), Self::Error> {
         let provider =
             self.tool.provider_factory.provider()?.disable_long_read_transaction_safety();
         let tx = provider.tx_ref();
@@ -92,7 +230,9 @@ impl<DB: Database> TableViewer<(u64, Duration)> for ChecksumViewer<'_, DB> {
         };
 
         let start_time = Instant::now();
-        let mut hasher = RandomState::with_seeds(1, 2, 3, 4).build_hasher();
+        // keccak instead of a faster, seeded hasher, because its output doesn't depend on the
+        // platform or the version of the node, which the stored checksums rely on
+        let mut hasher = Keccak256::new();
         let mut total = 0;
 
         let limit = self.limit.unwrap_or(usize::MAX);
@@ -105,8 +245,8 @@ impl<DB: Database> TableViewer<(u64, Duration)> for ChecksumViewer<'_, DB> {
                 info!("Hashed {index} entries.");
             }
 
-            hasher.write(k.raw_key());
-            hasher.write(v.raw_value());
+            hasher.update(k.raw_key());
+            hasher.update(v.raw_value());
 
             if enumerate_start_key.is_none() {
                 enumerate_start_key = Some(k.clone());
@@ -125,7 +265,7 @@ impl<DB: Database> TableViewer<(u64, Duration)> for ChecksumViewer<'_, DB> {
             info!("end-key: {}", serde_json::to_string(&e.key()?).unwrap_or_default());
         }
 
-        let checksum = hasher.finish();
+        let checksum = hasher.finalize();
         let elapsed = start_time.elapsed();
 
         info!("Checksum for table `{}`: {:#x} (elapsed: {:?})", T::NAME, checksum, elapsed);
@@ -0,0 +1,234 @@
+use clap::Parser;
+use reth_db::{tables, BlockNumberList};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    models::{
+        sharded_key::NUM_OF_INDICES_IN_SHARD, storage_sharded_key::StorageShardedKey, ShardedKey,
+    },
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_primitives::BlockNumber;
+use reth_provider::ProviderFactory;
+use tracing::info;
+
+/// The arguments for the `reth db compact-changesets` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Number of shards to write or delete before committing the transaction.
+    #[arg(long, default_value_t = 100_000)]
+    commit_threshold: usize,
+}
+
+/// Summary of a re-sharded history table.
+#[derive(Debug, Default)]
+struct ReshardOutcome {
+    /// Number of keys whose shards were rewritten.
+    keys: usize,
+    /// Number of shards before re-sharding the rewritten keys.
+    shards_before: usize,
+    /// Number of shards after re-sharding the rewritten keys.
+    shards_after: usize,
+}
+
+impl Command {
+    /// Execute `db compact-changesets` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        // The changesets themselves are keyed by block number, the history tables index them by
+        // account and storage slot, sharded into lists of `NUM_OF_INDICES_IN_SHARD` blocks.
+        let accounts = self.reshard::<DB, tables::AccountsHistory, _>(
+            &provider_factory,
+            |key| key.key,
+            ShardedKey::new,
+        )?;
+        info!(target: "reth::cli", table = tables::AccountsHistory::NAME, ?accounts, "Re-sharded history table");
+
+        let storages = self.reshard::<DB, tables::StoragesHistory, _>(
+            &provider_factory,
+            |key| (key.address, key.sharded_key.key),
+            |(address, storage_key), highest_block_number| {
+                StorageShardedKey::new(address, storage_key, highest_block_number)
+            },
+        )?;
+        info!(target: "reth::cli", table = tables::StoragesHistory::NAME, ?storages, "Re-sharded history table");
+
+        Ok(())
+    }
+
+    /// Merges the shards of every partial key of the history table and rewrites them into full
+    /// shards, if that reduces the number of shards. The last shard of a key always has
+    /// [`BlockNumber::MAX`] as its highest block number.
+    ///
+    /// The transaction is committed every `commit_threshold` written shards, the pages of the
+    /// replaced shards are returned to the freelist of the database and reused by later writes.
+    fn reshard<DB, T, P>(
+        &self,
+        provider_factory: &ProviderFactory<DB>,
+        partial_key: impl Fn(&T::Key) -> P,
+        sharded_key: impl Fn(P, BlockNumber) -> T::Key,
+    ) -> eyre::Result<ReshardOutcome>
+    where
+        DB: Database,
+        T: Table<Value = BlockNumberList>,
+        P: Copy + Eq,
+    {
+        let mut outcome = ReshardOutcome::default();
+        let mut provider = provider_factory.provider_rw()?;
+        let mut next = provider.tx_ref().cursor_read::<T>()?.first()?.map(|(key, _)| key);
+        let mut pending_writes = 0;
+
+        let total_entries = provider.tx_ref().entries::<T>()?;
+        let interval = (total_entries / 100).max(1);
+        let mut visited_entries = 0;
+
+        while let Some(first_key) = next {
+            let partial = partial_key(&first_key);
+            let mut shard_keys = Vec::new();
+            let mut indices = Vec::new();
+
+            let mut cursor = provider.tx_ref().cursor_read::<T>()?;
+            let mut entry = cursor.seek_exact(first_key)?;
+            next = loop {
+                match entry {
+                    Some((key, list)) if partial_key(&key) == partial => {
+                        indices.extend(list.iter());
+                        shard_keys.push(key);
+                        entry = cursor.next()?;
+                    }
+                    other => break other.map(|(key, _)| key),
+                }
+            };
+            drop(cursor);
+
+            let previous_progress = visited_entries / interval;
+            visited_entries += shard_keys.len();
+            if visited_entries / interval > previous_progress && total_entries > 100 {
+                info!(target: "reth::cli", table = T::NAME, progress = %format!("{:.2}%", (visited_entries as f64 / total_entries as f64) * 100.0), "Re-sharding history");
+            }
+
+            let chunks = indices.chunks(NUM_OF_INDICES_IN_SHARD);
+            if shard_keys.len() <= chunks.len() {
+                continue
+            }
+
+            outcome.keys += 1;
+            outcome.shards_before += shard_keys.len();
+            outcome.shards_after += chunks.len();
+            pending_writes += shard_keys.len() + chunks.len();
+
+            for key in shard_keys {
+                provider.tx_ref().delete::<T>(key, None)?;
+            }
+            // shards with empty lists index nothing, they're deleted without a replacement
+            let last_chunk = chunks.len().saturating_sub(1);
+            for (index, chunk) in chunks.enumerate() {
+                let highest_block_number =
+                    if index == last_chunk { BlockNumber::MAX } else { *chunk.last().unwrap() };
+                provider.tx_ref().put::<T>(
+                    sharded_key(partial, highest_block_number),
+                    BlockNumberList::new_pre_sorted(chunk),
+                )?;
+            }
+
+            if pending_writes >= self.commit_threshold {
+                provider.commit()?;
+                provider = provider_factory.provider_rw()?;
+                pending_writes = 0;
+            }
+        }
+
+        provider.commit()?;
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_provider::test_utils::create_test_provider_factory;
+
+    #[test]
+    fn reshard_account_history() {
+        let factory = create_test_provider_factory();
+        let address = reth_primitives::Address::with_last_byte(1);
+        let other = reth_primitives::Address::with_last_byte(2);
+        let shard = NUM_OF_INDICES_IN_SHARD as u64;
+
+        // shards left behind by pruning, each only partially filled
+        let lists = [
+            (0..shard / 2, shard / 2 - 1),
+            (shard..shard + shard / 4, shard + shard / 4 - 1),
+            (shard * 2..shard * 2 + shard / 4, u64::MAX),
+        ];
+        let provider = factory.provider_rw().unwrap();
+        for (range, highest) in lists.clone() {
+            provider
+                .tx_ref()
+                .put::<tables::AccountsHistory>(
+                    ShardedKey::new(address, highest),
+                    BlockNumberList::new_pre_sorted(range.collect::<Vec<_>>()),
+                )
+                .unwrap();
+        }
+        provider
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::new(other, u64::MAX),
+                BlockNumberList::new_pre_sorted([1, 2, 3]),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let outcome = Command { commit_threshold: 1 }
+            .reshard::<_, tables::AccountsHistory, _>(&factory, |key| key.key, ShardedKey::new)
+            .unwrap();
+        assert_eq!(outcome.keys, 1);
+        assert_eq!(outcome.shards_before, 3);
+        assert_eq!(outcome.shards_after, 1);
+
+        let provider = factory.provider().unwrap();
+        let shards = provider
+            .tx_ref()
+            .cursor_read::<tables::AccountsHistory>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let merged = lists.into_iter().flat_map(|(range, _)| range).collect::<Vec<_>>();
+        assert_eq!(
+            shards,
+            vec![
+                (ShardedKey::new(address, u64::MAX), BlockNumberList::new_pre_sorted(merged)),
+                (ShardedKey::new(other, u64::MAX), BlockNumberList::new_pre_sorted([1, 2, 3])),
+            ]
+        );
+    }
+
+    #[test]
+    fn reshard_empty_account_history() {
+        let factory = create_test_provider_factory();
+        let address = reth_primitives::Address::with_last_byte(1);
+
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::AccountsHistory>(
+                ShardedKey::new(address, u64::MAX),
+                BlockNumberList::new_pre_sorted(Vec::<u64>::new()),
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let outcome = Command { commit_threshold: 1 }
+            .reshard::<_, tables::AccountsHistory, _>(&factory, |key| key.key, ShardedKey::new)
+            .unwrap();
+        assert_eq!(outcome.keys, 1);
+        assert_eq!(outcome.shards_before, 1);
+        assert_eq!(outcome.shards_after, 0);
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::AccountsHistory>().unwrap(), 0);
+    }
+}
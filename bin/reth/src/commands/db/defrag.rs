@@ -0,0 +1,102 @@
+use clap::Parser;
+use human_bytes::human_bytes;
+use reth_db::{mdbx::DatabaseArguments, open_db};
+use reth_fs_util as fs;
+use std::{
+    path::Path,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+use tracing::info;
+
+/// Name of the MDBX data file.
+const DATA_FILE: &str = "mdbx.dat";
+/// Name of the MDBX lock file.
+const LOCK_FILE: &str = "mdbx.lck";
+/// Name of the compacted copy of the data file, while it's being written.
+const DEFRAG_FILE: &str = "mdbx.dat.defrag";
+/// Name of the original data file, if it's kept.
+const BACKUP_FILE: &str = "mdbx.dat.bak";
+
+/// Interval at which the progress of the copy is reported.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The arguments for the `reth db defrag` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Keep the original database file as `mdbx.dat.bak` after it was replaced by the compacted
+    /// copy.
+    #[arg(long)]
+    keep_backup: bool,
+}
+
+impl Command {
+    /// Execute `db defrag` command
+    pub fn execute(self, db_path: &Path, db_args: DatabaseArguments) -> eyre::Result<()> {
+        let defrag_path = db_path.join(DEFRAG_FILE);
+        if defrag_path.exists() {
+            // left behind by an interrupted run
+            fs::remove_file(&defrag_path)?;
+        }
+
+        // Opening the database read-write takes the storage lock, so no node can use it while the
+        // data file is replaced. The lock is held until the data file was replaced, after the
+        // database is closed.
+        let db = open_db(db_path, db_args)?;
+        let _lock = db.storage_lock();
+
+        let page_size = db.stat()?.page_size() as u64;
+        let freelist = db.freelist()? as u64;
+        let used_pages = db.info()?.last_pgno() as u64 + 1;
+        let expected_size = used_pages.saturating_sub(freelist) * page_size;
+        info!(
+            target: "reth::cli",
+            size = %human_bytes((used_pages * page_size) as f64),
+            free = %human_bytes((freelist * page_size) as f64),
+            "Copying database without free pages"
+        );
+
+        // The copy doesn't report its progress, so the size of the compacted file is reported
+        // instead.
+        let (done_tx, done_rx) = mpsc::channel::<()>();
+        let copy = thread::scope(|scope| {
+            let defrag_path = &defrag_path;
+            scope.spawn(move || {
+                while let Err(RecvTimeoutError::Timeout) = done_rx.recv_timeout(PROGRESS_INTERVAL) {
+                    let Ok(metadata) = fs::metadata(defrag_path) else { continue };
+                    info!(
+                        target: "reth::cli",
+                        progress = %format!("{:.2}%", (metadata.len() as f64 / expected_size.max(1) as f64) * 100.0),
+                        copied = %human_bytes(metadata.len() as f64),
+                        "Copying database"
+                    );
+                }
+            });
+
+            let copy = db.copy(defrag_path, true);
+            drop(done_tx);
+            copy
+        });
+        drop(db);
+        copy?;
+
+        let data_path = db_path.join(DATA_FILE);
+        let previous_size = fs::metadata(&data_path)?.len();
+        if self.keep_backup {
+            fs::rename(&data_path, db_path.join(BACKUP_FILE))?;
+        }
+        fs::rename(&defrag_path, &data_path)?;
+        // The lock file is sized for the previous data file and is recreated on the next open.
+        fs::remove_file(db_path.join(LOCK_FILE))?;
+
+        info!(
+            target: "reth::cli",
+            previous_size = %human_bytes(previous_size as f64),
+            size = %human_bytes(fs::metadata(&data_path)?.len() as f64),
+            "Database defragmented"
+        );
+
+        Ok(())
+    }
+}
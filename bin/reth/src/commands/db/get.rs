@@ -1,14 +1,18 @@
+use super::OutputFormat;
 use crate::utils::DbTool;
 use clap::Parser;
 use reth_db::{
-    static_file::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask, TransactionMask},
-    tables, RawKey, RawTable, Receipts, TableViewer, Transactions,
+    static_file::{
+        AccessListMask, ColumnSelectorOne, ColumnSelectorTwo, HeaderMask, ReceiptMask,
+        TransactionMask,
+    },
+    tables, RawDupSort, RawKey, RawTable, Receipts, TableViewer, Transactions,
 };
 use reth_db_api::{
     database::Database,
-    table::{Decompress, DupSort, Table},
+    table::{Decompress, DupSort, Encode, Table},
 };
-use reth_primitives::{BlockHash, Header, StaticFileSegment};
+use reth_primitives::{AccessList, BlockHash, Header, StaticFileSegment, TxNumber};
 use reth_provider::StaticFileProviderFactory;
 use tracing::error;
 
@@ -29,13 +33,18 @@ enum Subcommand {
         #[arg(value_parser = maybe_json_value_parser)]
         key: String,
 
-        /// The subkey to get content for
+        /// The subkey to get content for. If omitted for a `DupSort` table, all values of the key
+        /// are returned.
         #[arg(value_parser = maybe_json_value_parser)]
         subkey: Option<String>,
 
         /// Output bytes instead of human-readable decoded value
         #[arg(long)]
         raw: bool,
+
+        /// Print the content in the given format
+        #[arg(long, value_enum, conflicts_with = "raw")]
+        output: Option<OutputFormat>,
     },
     /// Gets the content of a static file segment for the given key
     StaticFile {
@@ -55,8 +64,8 @@ impl Command {
     /// Execute `db get` command
     pub fn execute<DB: Database>(self, tool: &DbTool<DB>) -> eyre::Result<()> {
         match self.subcommand {
-            Subcommand::Mdbx { table, key, subkey, raw } => {
-                table.view(&GetValueViewer { tool, key, subkey, raw })?
+            Subcommand::Mdbx { table, key, subkey, raw, output } => {
+                table.view(&GetValueViewer { tool, key, subkey, raw, output })?
             }
             Subcommand::StaticFile { segment, key, raw } => {
                 let (key, mask): (u64, _) = match segment {
@@ -71,6 +80,10 @@ impl Command {
                         table_key::<tables::Receipts>(&key)?,
                         <ReceiptMask<<Receipts as Table>::Value>>::MASK,
                     ),
+                    StaticFileSegment::AccessLists => (
+                        serde_json::from_str::<TxNumber>(&key).map_err(|e| eyre::eyre!(e))?,
+                        <AccessListMask<AccessList>>::MASK,
+                    ),
                 };
 
                 let content = tool.provider_factory.static_file_provider().find_static_file(
@@ -112,6 +125,11 @@ impl Command {
                                     )?;
                                     println!("{}", serde_json::to_string_pretty(&receipt)?);
                                 }
+                                StaticFileSegment::AccessLists => {
+                                    let access_list =
+                                        AccessList::decompress(content[0].as_slice())?;
+                                    println!("{}", serde_json::to_string_pretty(&access_list)?);
+                                }
                             }
                         }
                     }
@@ -132,7 +150,7 @@ pub(crate) fn table_key<T: Table>(key: &str) -> Result<T::Key, eyre::Error> {
 }
 
 /// Get an instance of subkey for given dupsort table
-fn table_subkey<T: DupSort>(subkey: &Option<String>) -> Result<T::SubKey, eyre::Error> {
+pub(crate) fn table_subkey<T: DupSort>(subkey: &Option<String>) -> Result<T::SubKey, eyre::Error> {
     serde_json::from_str::<T::SubKey>(&subkey.clone().unwrap_or_default())
         .map_err(|e| eyre::eyre!(e))
 }
@@ -142,6 +160,14 @@ struct GetValueViewer<'a, DB: Database> {
     key: String,
     subkey: Option<String>,
     raw: bool,
+    output: Option<OutputFormat>,
+}
+
+impl<DB: Database> GetValueViewer<'_, DB> {
+    /// Returns the output format if the content has to be printed as raw entries.
+    fn raw_output(&self) -> Option<OutputFormat> {
+        self.output.filter(|output| *output != OutputFormat::Json)
+    }
 }
 
 impl<DB: Database> TableViewer<()> for GetValueViewer<'_, DB> {
@@ -150,6 +176,15 @@ impl<DB: Database> TableViewer<()> for GetValueViewer<'_, DB> {
     fn view<T: Table>(&self) -> Result<(), Self::Error> {
         let key = table_key::<T>(&self.key)?;
 
+        if let Some(output) = self.raw_output() {
+            let key = RawKey::new(key);
+            match self.tool.get::<RawTable<T>>(key.clone())? {
+                Some(value) => output.print_raw_rows([(key.raw_key(), value.raw_value())])?,
+                None => error!(target: "reth::cli", "No content for the given table key."),
+            }
+            return Ok(())
+        }
+
         let content = if self.raw {
             self.tool
                 .get::<RawTable<T>>(RawKey::from(key))?
@@ -174,9 +209,32 @@ impl<DB: Database> TableViewer<()> for GetValueViewer<'_, DB> {
         // get a key for given table
         let key = table_key::<T>(&self.key)?;
 
+        // without a subkey, return all values of the key
+        if self.subkey.is_none() {
+            if let Some(output) = self.raw_output() {
+                let raw_key = key.clone().encode();
+                let values = self.tool.get_dups::<RawDupSort<T>>(RawKey::new(key))?;
+                let rows = values.iter().map(|value| (raw_key.as_ref(), value.raw_value()));
+                output.print_raw_rows(rows)?;
+            } else {
+                let values = self.tool.get_dups::<T>(key)?;
+                println!("{}", serde_json::to_string_pretty(&values)?);
+            }
+            return Ok(())
+        }
+
         // process dupsort table
         let subkey = table_subkey::<T>(&self.subkey)?;
 
+        if let Some(output) = self.raw_output() {
+            let key = RawKey::new(key);
+            match self.tool.get_dup::<RawDupSort<T>>(key.clone(), RawKey::new(subkey))? {
+                Some(value) => output.print_raw_rows([(key.raw_key(), value.raw_value())])?,
+                None => error!(target: "reth::cli", "No content for the given table subkey."),
+            }
+            return Ok(())
+        }
+
         match self.tool.get_dup::<T>(key, subkey)? {
             Some(content) => {
                 println!("{}", serde_json::to_string_pretty(&content)?);
@@ -1,10 +1,17 @@
-use super::tui::DbListTUI;
+use super::{
+    get::{maybe_json_value_parser, table_subkey},
+    tui::DbListTUI,
+    OutputFormat,
+};
 use crate::utils::{DbTool, ListFilter};
 use clap::Parser;
 use eyre::WrapErr;
-use reth_db::{DatabaseEnv, RawValue, TableViewer, Tables};
-use reth_db_api::{database::Database, table::Table};
-use reth_primitives::hex;
+use reth_db::{DatabaseEnv, RawTable, RawValue, TableViewer, Tables};
+use reth_db_api::{
+    database::Database,
+    table::{DupSort, Encode, Table},
+};
+use reth_primitives::{hex, Bytes};
 use std::{cell::RefCell, sync::Arc};
 use tracing::error;
 
@@ -38,6 +45,13 @@ pub struct Command {
     /// Minimum size of value in bytes
     #[arg(long, default_value_t = 0)]
     min_value_size: usize,
+    /// Only list entries whose raw key starts with the given hex-encoded prefix, e.g. an address
+    /// for `PlainStorageState`.
+    #[arg(long, value_name = "HEX")]
+    key_prefix: Option<Bytes>,
+    /// Only list entries with the given subkey, only supported for `DupSort` tables.
+    #[arg(long, value_parser = maybe_json_value_parser)]
+    subkey: Option<String>,
     /// Returns the number of rows found.
     #[arg(long, short)]
     count: bool,
@@ -47,6 +61,9 @@ pub struct Command {
     /// Output bytes instead of human-readable decoded value
     #[arg(long)]
     raw: bool,
+    /// Print the entries in the given format instead of using TUI.
+    #[arg(long, value_enum, conflicts_with_all = ["json", "raw"])]
+    output: Option<OutputFormat>,
 }
 
 impl Command {
@@ -77,6 +94,8 @@ impl Command {
             min_value_size: self.min_value_size,
             reverse: self.reverse,
             only_count: self.count,
+            key_prefix: self.key_prefix.clone().map(Into::into).unwrap_or_default(),
+            value_prefix: Vec::new(),
         }
     }
 }
@@ -86,10 +105,9 @@ struct ListTableViewer<'a> {
     args: &'a Command,
 }
 
-impl TableViewer<()> for ListTableViewer<'_> {
-    type Error = eyre::Report;
-
-    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+impl ListTableViewer<'_> {
+    /// Lists the entries of the table whose raw value starts with the given prefix.
+    fn list<T: Table>(&self, value_prefix: Vec<u8>) -> eyre::Result<()> {
         self.tool.provider_factory.db_ref().view(|tx| {
             let table_db = tx.inner.open_db(Some(self.args.table.name())).wrap_err("Could not open db.")?;
             let stats = tx.inner.db_stat(&table_db).wrap_err(format!("Could not find table: {}", stringify!($table)))?;
@@ -107,9 +125,20 @@ impl TableViewer<()> for ListTableViewer<'_> {
             }
 
 
-            let list_filter = self.args.list_filter();
+            let mut list_filter = self.args.list_filter();
+            list_filter.value_prefix = value_prefix;
 
-            if self.args.json || self.args.count {
+            if let Some(output) = self.args.output.filter(|_| !self.args.count) {
+                if output == OutputFormat::Json {
+                    let (list, _) = self.tool.list::<T>(&list_filter)?;
+                    println!("{}", serde_json::to_string_pretty(&list)?);
+                } else {
+                    let (list, _) = self.tool.list::<RawTable<T>>(&list_filter)?;
+                    let rows = list.iter().map(|(key, value)| (key.raw_key(), value.raw_value()));
+                    output.print_raw_rows(rows)?;
+                }
+                Ok(())
+            } else if self.args.json || self.args.count {
                 let (list, count) = self.tool.list::<T>(&list_filter)?;
 
                 if self.args.count {
@@ -133,3 +162,23 @@ impl TableViewer<()> for ListTableViewer<'_> {
         Ok(())
     }
 }
+
+impl TableViewer<()> for ListTableViewer<'_> {
+    type Error = eyre::Report;
+
+    fn view<T: Table>(&self) -> Result<(), Self::Error> {
+        if self.args.subkey.is_some() {
+            eyre::bail!("Subkeys are only supported for DupSort tables.")
+        }
+        self.list::<T>(Vec::new())
+    }
+
+    fn view_dupsort<T: DupSort>(&self) -> Result<(), Self::Error> {
+        // values of dupsort tables start with the encoded subkey
+        let value_prefix = match &self.args.subkey {
+            Some(_) => table_subkey::<T>(&self.args.subkey)?.encode().as_ref().to_vec(),
+            None => Vec::new(),
+        };
+        self.list::<T>(value_prefix)
+    }
+}
@@ -0,0 +1,80 @@
+use clap::{Parser, Subcommand};
+use reth_db_api::database::Database;
+use reth_primitives::{BlockNumber, Receipts};
+use reth_provider::{
+    LogIndexProvider, ProviderFactory, ProviderResult, ReceiptProvider, StageCheckpointReader,
+};
+use reth_stages::StageId;
+use tracing::info;
+
+/// The arguments for the `reth db log-index` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// Number of blocks to index before committing the transaction.
+    #[arg(long, default_value_t = 10_000)]
+    commit_threshold: u64,
+
+    #[clap(subcommand)]
+    subcommand: Subcommands,
+}
+
+#[derive(Subcommand, Debug)]
+enum Subcommands {
+    /// Clears the log index and indexes the logs of the blocks from the given block to the
+    /// highest executed block
+    Build {
+        /// The first block to index.
+        #[arg(long, value_name = "BLOCK", default_value_t = 0)]
+        from: BlockNumber,
+    },
+    /// Indexes the logs of the blocks that were executed after the highest indexed block, e.g.
+    /// while the node was running without `--db.log-index`
+    Repair,
+}
+
+impl Command {
+    /// Execute `db log-index` command
+    pub fn execute<DB: Database>(self, provider_factory: ProviderFactory<DB>) -> eyre::Result<()> {
+        let provider_factory = provider_factory.with_log_index();
+        let provider = provider_factory.provider_rw()?;
+        // receipts only exist for executed blocks
+        let tip = provider
+            .get_stage_checkpoint(StageId::Execution)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+
+        let from = match self.subcommand {
+            Subcommands::Build { from } => {
+                provider.clear_log_index()?;
+                from
+            }
+            Subcommands::Repair => match provider.log_index_range()? {
+                Some(range) => range.end() + 1,
+                None => eyre::bail!(
+                    "The log index doesn't cover any blocks, use `reth db log-index build` instead"
+                ),
+            },
+        };
+        provider.commit()?;
+
+        let step = self.commit_threshold.max(1);
+        for start in (from..=tip).step_by(step as usize) {
+            let end = start.saturating_add(step - 1).min(tip);
+            let provider = provider_factory.provider_rw()?;
+            let receipts = (start..=end)
+                .map(|block_number| {
+                    let receipts = provider.receipts_by_block(block_number.into())?;
+                    Ok(receipts.unwrap_or_default().into_iter().map(Some).collect())
+                })
+                .collect::<ProviderResult<Receipts>>()?;
+            provider.insert_log_index(start, &receipts)?;
+            provider.commit()?;
+            info!(target: "reth::cli", from = start, to = end, tip, "Indexed logs");
+        }
+
+        if let Some(range) = provider_factory.log_index_range()? {
+            info!(target: "reth::cli", from = range.start(), to = range.end(), "Log index is up to date");
+        }
+        Ok(())
+    }
+}
@@ -4,15 +4,20 @@ use crate::{
     commands::common::{AccessRights, Environment, EnvironmentArgs},
     utils::DbTool,
 };
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use reth_db::version::{get_db_version, DatabaseVersionError, DB_VERSION};
+use reth_primitives::{hex, Bytes};
+use serde::Serialize;
 use std::io::{self, Write};
 
-mod checksum;
+pub(crate) mod checksum;
 mod clear;
+mod compact_changesets;
+mod defrag;
 mod diff;
 mod get;
 mod list;
+mod log_index;
 mod stats;
 /// DB List TUI
 mod tui;
@@ -48,6 +53,14 @@ pub enum Subcommands {
     },
     /// Deletes all table entries
     Clear(clear::Command),
+    /// Rewrites the account and storage history shards, which index the changesets, into full
+    /// shards
+    CompactChangesets(compact_changesets::Command),
+    /// Copies the database without its free pages and replaces it with the copy
+    Defrag(defrag::Command),
+    /// Builds or repairs the index of the blocks by the addresses and topics of their logs, which
+    /// is used by `eth_getLogs` if the node runs with `--db.log-index`
+    LogIndex(log_index::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -85,7 +98,7 @@ impl Command {
             }
             Subcommands::Checksum(command) => {
                 db_ro_exec!(self.env, tool, {
-                    command.execute(&tool)?;
+                    command.execute(data_dir, &tool)?;
                 });
             }
             Subcommands::Diff(command) => {
@@ -122,6 +135,17 @@ impl Command {
                 let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
                 command.execute(provider_factory)?;
             }
+            Subcommands::CompactChangesets(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
+            Subcommands::Defrag(command) => {
+                command.execute(&db_path, self.env.db.database_args())?;
+            }
+            Subcommands::LogIndex(command) => {
+                let Environment { provider_factory, .. } = self.env.init(AccessRights::RW)?;
+                command.execute(provider_factory)?;
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
@@ -146,6 +170,59 @@ impl Command {
     }
 }
 
+/// Output format of table entries printed by the `db` commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Decoded keys and values as JSON.
+    Json,
+    /// RLP-encoded `[key, value]` list of the raw bytes of each entry, hex-encoded, one per line.
+    Rlp,
+    /// Raw bytes of keys and values as hex, in a JSON array.
+    Raw,
+}
+
+impl OutputFormat {
+    /// Prints the raw entries in the [`OutputFormat::Rlp`] or [`OutputFormat::Raw`] format.
+    ///
+    /// Decoded entries are table specific and have to be printed by the caller.
+    fn print_raw_rows<K, V>(self, rows: impl IntoIterator<Item = (K, V)>) -> eyre::Result<()>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        /// A raw table entry.
+        #[derive(Serialize)]
+        struct RawRow {
+            key: Bytes,
+            value: Bytes,
+        }
+
+        match self {
+            Self::Json => eyre::bail!("decoded entries can't be printed from raw entries"),
+            Self::Rlp => {
+                for (key, value) in rows {
+                    let row = vec![
+                        Bytes::copy_from_slice(key.as_ref()),
+                        Bytes::copy_from_slice(value.as_ref()),
+                    ];
+                    println!("{}", hex::encode_prefixed(alloy_rlp::encode(row)));
+                }
+            }
+            Self::Raw => {
+                let rows = rows
+                    .into_iter()
+                    .map(|(key, value)| RawRow {
+                        key: Bytes::copy_from_slice(key.as_ref()),
+                        value: Bytes::copy_from_slice(value.as_ref()),
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", serde_json::to_string_pretty(&rows)?);
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,270 @@
+//! Command for comparing the execution of a block with another client.
+
+use crate::{
+    args::utils::hash_or_num_value_parser,
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    macros::block_executor,
+};
+use clap::Parser;
+use jsonrpsee::http_client::HttpClientBuilder;
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_primitives::{BlockHashOrNumber, Log, TransactionVariant, TxHash};
+use reth_provider::{BlockReader, ChainSpecProvider, HeaderProvider, StateRootProvider};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::clients::{DebugApiClient, EthApiClient};
+use reth_rpc_types::trace::{
+    common::TraceResult,
+    geth::{GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions, GethTrace},
+};
+use std::fmt;
+use tracing::*;
+
+/// `reth debug compare-block` command
+///
+/// Executes a block of the local chain on top of its parent state, and compares the status, gas
+/// used and logs of every transaction, and the state root, with the execution of the same block
+/// by another client. The other client's execution is fetched with `debug_traceBlockByHash`,
+/// `eth_getBlockReceipts` and `eth_getBlockByHash`, so it must have the block as well.
+///
+/// The first transaction whose execution diverges is reported, since the differences of the
+/// following transactions usually follow from it. The command fails if the execution or the state
+/// root diverges.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The number or hash of the block to compare.
+    #[arg(long, value_name = "BLOCK", value_parser = hash_or_num_value_parser)]
+    block: BlockHashOrNumber,
+
+    /// The RPC URL of the client to compare with.
+    #[arg(long, value_name = "RPC_URL")]
+    against: String,
+}
+
+impl Command {
+    /// Execute `debug compare-block` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let block = provider
+            .block_with_senders(self.block, TransactionVariant::WithHash)?
+            .ok_or_else(|| eyre::eyre!("Block {} not found", self.block))?;
+        eyre::ensure!(block.number > 0, "The genesis block can't be executed");
+        let block_hash = block.header.hash_slow();
+        let td = provider
+            .header_td_by_number(block.number)?
+            .ok_or_else(|| eyre::eyre!("Total difficulty of block {} not found", block.number))?;
+
+        // execute the block on top of its parent state
+        let state_provider = provider_factory.history_by_block_number(block.number - 1)?;
+        let executor = block_executor!(provider_factory.chain_spec())
+            .executor(StateProviderDatabase::new(&state_provider));
+        let BlockExecutionOutput { state, receipts, .. } = executor.execute((&block, td).into())?;
+        let local_state_root = state_provider.state_root(&state)?;
+
+        let mut previous_cumulative_gas_used = 0;
+        let local = block
+            .body
+            .iter()
+            .zip(receipts)
+            .map(|(tx, receipt)| {
+                let gas_used = receipt.cumulative_gas_used - previous_cumulative_gas_used;
+                previous_cumulative_gas_used = receipt.cumulative_gas_used;
+                TransactionExecution {
+                    hash: tx.hash(),
+                    success: receipt.success,
+                    gas_used,
+                    trace_gas_used: None,
+                    logs: receipt.logs,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // fetch the execution of the other client
+        info!(target: "reth::cli", number = block.number, hash = %block_hash, against = %self.against, "Fetching execution of the other client");
+        let client = HttpClientBuilder::default().build(&self.against)?;
+        let remote_block = EthApiClient::block_by_hash(&client, block_hash, false)
+            .await?
+            .ok_or_else(|| eyre::eyre!("Block {block_hash} not found on {}", self.against))?;
+        let remote_receipts = EthApiClient::block_receipts(&client, block_hash.into())
+            .await?
+            .ok_or_else(|| eyre::eyre!("Receipts of {block_hash} not found on {}", self.against))?;
+        let tracing_options = GethDebugTracingOptions::default().with_tracer(
+            GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::CallTracer),
+        );
+        let traces =
+            DebugApiClient::debug_trace_block_by_hash(&client, block_hash, Some(tracing_options))
+                .await?;
+        eyre::ensure!(
+            remote_receipts.len() == local.len() && traces.len() == local.len(),
+            "The other client returned {} receipts and {} traces for {} transactions",
+            remote_receipts.len(),
+            traces.len(),
+            local.len()
+        );
+
+        let remote = remote_receipts
+            .into_iter()
+            .zip(traces)
+            .map(|(receipt, trace)| {
+                let trace_gas_used = match trace {
+                    TraceResult::Success { result: GethTrace::CallTracer(frame), .. } => {
+                        Some(frame.gas_used.saturating_to())
+                    }
+                    _ => None,
+                };
+                TransactionExecution {
+                    hash: receipt.transaction_hash,
+                    success: receipt.inner.inner.status(),
+                    gas_used: receipt.gas_used as u64,
+                    trace_gas_used,
+                    logs: receipt.inner.inner.logs().iter().map(|log| log.inner.clone()).collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // compare
+        let remote_state_root = remote_block.header.state_root;
+        let state_root_matches = local_state_root == remote_state_root;
+        if state_root_matches {
+            info!(target: "reth::cli", state_root = %local_state_root, "State root matches");
+        } else {
+            warn!(target: "reth::cli", local = %local_state_root, remote = %remote_state_root, header = %block.state_root, "State root differs");
+        }
+
+        let divergences = local
+            .iter()
+            .zip(&remote)
+            .enumerate()
+            .filter_map(|(index, (local, remote))| {
+                let fields = local.diff(remote);
+                (!fields.is_empty()).then_some((index, local.hash, fields))
+            })
+            .collect::<Vec<_>>();
+        if let Some((index, hash, fields)) = divergences.first() {
+            warn!(target: "reth::cli", index, %hash, divergent_transactions = divergences.len(), "First divergent transaction");
+            for field in fields {
+                warn!(target: "reth::cli", index, %hash, "{field}");
+            }
+        } else {
+            info!(target: "reth::cli", transactions = local.len(), "Execution of all transactions matches");
+        }
+
+        // exit with an error on any divergence, so that the command can be used in scripts
+        eyre::ensure!(
+            state_root_matches && divergences.is_empty(),
+            "Execution of block {} diverges from {}: {} divergent transactions, state root {}",
+            block.number,
+            self.against,
+            divergences.len(),
+            if state_root_matches { "matches" } else { "differs" }
+        );
+        Ok(())
+    }
+}
+
+/// The execution of a transaction by a client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TransactionExecution {
+    hash: TxHash,
+    success: bool,
+    gas_used: u64,
+    /// The gas used by the top-level call of the transaction trace, only known for the other
+    /// client.
+    trace_gas_used: Option<u64>,
+    logs: Vec<Log>,
+}
+
+impl TransactionExecution {
+    /// Returns the fields that differ from the execution by the other client.
+    fn diff(&self, remote: &Self) -> Vec<FieldDiff> {
+        let mut fields = Vec::new();
+        if self.hash != remote.hash {
+            fields.push(FieldDiff::new("hash", self.hash, remote.hash));
+        }
+        if self.success != remote.success {
+            fields.push(FieldDiff::new("success", self.success, remote.success));
+        }
+        if self.gas_used != remote.gas_used {
+            fields.push(FieldDiff::new("gas used", self.gas_used, remote.gas_used));
+        }
+        if let Some(trace_gas_used) = remote.trace_gas_used.filter(|gas| *gas != self.gas_used) {
+            fields.push(FieldDiff::new("trace gas used", self.gas_used, trace_gas_used));
+        }
+        if self.logs.len() != remote.logs.len() {
+            fields.push(FieldDiff::new("log count", self.logs.len(), remote.logs.len()));
+        }
+        if let Some((index, (local, remote))) = self
+            .logs
+            .iter()
+            .zip(&remote.logs)
+            .enumerate()
+            .find(|(_, (local, remote))| local != remote)
+        {
+            fields.push(FieldDiff {
+                field: format!("log {index}"),
+                local: format!("{local:?}"),
+                remote: format!("{remote:?}"),
+            });
+        }
+        fields
+    }
+}
+
+/// A field of a transaction execution that differs between the clients.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FieldDiff {
+    field: String,
+    local: String,
+    remote: String,
+}
+
+impl FieldDiff {
+    fn new(field: &str, local: impl fmt::Display, remote: impl fmt::Display) -> Self {
+        Self { field: field.to_string(), local: local.to_string(), remote: remote.to_string() }
+    }
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} differs: local {}, remote {}", self.field, self.local, self.remote)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, Bytes, B256};
+
+    fn execution(gas_used: u64, logs: Vec<Log>) -> TransactionExecution {
+        TransactionExecution {
+            hash: B256::ZERO,
+            success: true,
+            gas_used,
+            trace_gas_used: None,
+            logs,
+        }
+    }
+
+    #[test]
+    fn transaction_execution_diff() {
+        let log = Log::new_unchecked(Address::ZERO, vec![], Bytes::new());
+        let local = execution(21_000, vec![log.clone()]);
+        assert!(local.diff(&local).is_empty());
+
+        let remote = TransactionExecution { trace_gas_used: Some(21_000), ..local.clone() };
+        assert!(local.diff(&remote).is_empty());
+
+        let remote =
+            TransactionExecution { trace_gas_used: Some(22_000), ..execution(22_000, vec![]) };
+        let fields = local.diff(&remote).into_iter().map(|diff| diff.field).collect::<Vec<_>>();
+        assert_eq!(fields, ["gas used", "trace gas used", "log count"]);
+
+        let other_log = Log::new_unchecked(Address::repeat_byte(1), vec![], Bytes::new());
+        let remote = execution(21_000, vec![other_log]);
+        assert_eq!(local.diff(&remote)[0].field, "log 0");
+    }
+}
@@ -0,0 +1,301 @@
+//! Command for exporting the inputs and outputs of historical block execution.
+
+use crate::{
+    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    macros::block_executor,
+};
+use alloy_rlp::Encodable;
+use clap::Parser;
+use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
+use reth_primitives::{
+    revm_primitives::{db::Database, AccountInfo, Bytecode},
+    Address, BlockHashOrNumber, Bytes, TransactionVariant, TxHash, B256, U256,
+};
+use reth_provider::{BlockReader, HeaderProvider, ProviderError, StateRootProvider};
+use reth_revm::database::StateProviderDatabase;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::*;
+
+/// `reth debug export-checkpoints` command
+///
+/// Re-executes a range of historical blocks and writes, for every block, the state read during
+/// execution, the block inputs and the post-state root as a line of JSON. The output contains
+/// everything needed to re-execute the block without access to the database, e.g. in a zkEVM
+/// prover.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The first block to export.
+    #[arg(long)]
+    from: u64,
+
+    /// The last block to export, inclusive.
+    #[arg(long)]
+    to: u64,
+
+    /// The file to write the checkpoints to, one JSON object per line.
+    #[arg(long, value_name = "FILE")]
+    output: PathBuf,
+
+    /// Include the outcome of every transaction in the block.
+    #[arg(long)]
+    per_tx: bool,
+
+    /// Recompute the post-state root of every block from the database, instead of using the root
+    /// of the block header after checking the block's gas used.
+    ///
+    /// This is slow for blocks far behind the tip, as the state is reverted from the tip.
+    #[arg(long)]
+    verify_state_root: bool,
+}
+
+impl Command {
+    /// Execute `debug export-checkpoints` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        if self.from == 0 {
+            eyre::bail!("The genesis block is not executed, start at block 1")
+        }
+        if self.from > self.to {
+            eyre::bail!("Invalid block range {}..={}", self.from, self.to)
+        }
+
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+        let executor_provider = block_executor!(provider_factory.chain_spec());
+
+        let mut output = BufWriter::new(File::create(&self.output)?);
+        for number in self.from..=self.to {
+            let block = provider
+                .block_with_senders(BlockHashOrNumber::Number(number), TransactionVariant::WithHash)?
+                .ok_or_else(|| eyre::eyre!("Block {number} not found"))?;
+            let total_difficulty = provider
+                .header_td_by_number(number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(number))?;
+
+            // the state after the parent block is the state the block is executed on
+            let state_provider = provider_factory.history_by_block_number(number - 1)?;
+            let mut db = RecordingDatabase::new(StateProviderDatabase::new(&state_provider));
+            let BlockExecutionOutput { state, receipts, gas_used, .. } = executor_provider
+                .executor(&mut db)
+                .execute((&block, total_difficulty).into())?;
+
+            if gas_used != block.gas_used {
+                eyre::bail!(
+                    "Block gas used mismatch at block {number}. Expected: {}. Got: {gas_used}",
+                    block.gas_used
+                );
+            }
+
+            let post_state_root = if self.verify_state_root {
+                let state_root = state_provider.state_root(&state)?;
+                if state_root != block.state_root {
+                    eyre::bail!(
+                        "Computed state root mismatch at block {number}. Expected: {:?}. Got: {:?}",
+                        block.state_root,
+                        state_root
+                    );
+                }
+                state_root
+            } else {
+                block.state_root
+            };
+
+            let transactions = self.per_tx.then(|| {
+                let mut previous_cumulative_gas_used = 0;
+                block
+                    .transactions_with_sender()
+                    .zip(&receipts)
+                    .map(|((sender, transaction), receipt)| {
+                        let outcome = TransactionOutcome {
+                            hash: transaction.hash(),
+                            sender: *sender,
+                            success: receipt.success,
+                            gas_used: receipt.cumulative_gas_used - previous_cumulative_gas_used,
+                            logs: receipt.logs.len(),
+                        };
+                        previous_cumulative_gas_used = receipt.cumulative_gas_used;
+                        outcome
+                    })
+                    .collect()
+            });
+
+            let mut encoded_block = Vec::with_capacity(block.block.length());
+            block.block.encode(&mut encoded_block);
+
+            let checkpoint = Checkpoint {
+                number,
+                hash: block.hash_slow(),
+                parent_state_root: provider
+                    .header_by_number(number - 1)?
+                    .ok_or(ProviderError::HeaderNotFound((number - 1).into()))?
+                    .state_root,
+                post_state_root,
+                block: encoded_block.into(),
+                senders: block.senders.clone(),
+                total_difficulty,
+                pre_state: db.reads,
+                transactions,
+            };
+            serde_json::to_writer(&mut output, &checkpoint)?;
+            output.write_all(b"\n")?;
+
+            debug!(target: "reth::cli", number, "Exported block checkpoint");
+            if number % 1000 == 0 {
+                info!(target: "reth::cli", number, to = self.to, "Exporting block checkpoints");
+            }
+        }
+        output.flush()?;
+
+        info!(target: "reth::cli", from = self.from, to = self.to, output = %self.output.display(), "Exported block checkpoints");
+        Ok(())
+    }
+}
+
+/// The inputs and outputs of a block's execution.
+///
+/// All maps are ordered, so the serialization of a block is stable.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Checkpoint {
+    /// The number of the block.
+    number: u64,
+    /// The hash of the block.
+    hash: B256,
+    /// The state root of the parent block, the root of the state the block is executed on.
+    parent_state_root: B256,
+    /// The state root after executing the block.
+    post_state_root: B256,
+    /// The RLP encoded block.
+    block: Bytes,
+    /// The senders of the block's transactions.
+    senders: Vec<Address>,
+    /// The total difficulty of the chain up to and including the block.
+    total_difficulty: U256,
+    /// The state read while executing the block.
+    pre_state: StateReads,
+    /// The outcome of every transaction, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transactions: Option<Vec<TransactionOutcome>>,
+}
+
+/// The outcome of a transaction's execution.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionOutcome {
+    /// The hash of the transaction.
+    hash: TxHash,
+    /// The sender of the transaction.
+    sender: Address,
+    /// Whether the transaction was successful.
+    success: bool,
+    /// Gas used by the transaction.
+    gas_used: u64,
+    /// Number of logs emitted by the transaction.
+    logs: usize,
+}
+
+/// State read from the database while executing a block, before any of the block's changes.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct StateReads {
+    /// Accounts that were read, `None` if the account doesn't exist.
+    pub(super) accounts: BTreeMap<Address, Option<AccountRead>>,
+    /// Storage slots that were read, by account.
+    pub(super) storage: BTreeMap<Address, BTreeMap<U256, U256>>,
+    /// Bytecode that was read, by code hash.
+    pub(super) codes: BTreeMap<B256, Bytes>,
+    /// Hashes of previous blocks that were read, by block number.
+    pub(super) block_hashes: BTreeMap<u64, B256>,
+}
+
+/// An account as read from the database.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct AccountRead {
+    pub(super) nonce: u64,
+    pub(super) balance: U256,
+    pub(super) code_hash: B256,
+}
+
+/// A [Database] that records all reads from the underlying database.
+///
+/// The executor caches reads for the duration of a block, so every value is read at most once.
+#[derive(Debug)]
+pub(super) struct RecordingDatabase<DB> {
+    db: DB,
+    pub(super) reads: StateReads,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    pub(super) fn new(db: DB) -> Self {
+        Self { db, reads: StateReads::default() }
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let info = self.db.basic(address)?;
+        self.reads.accounts.insert(
+            address,
+            info.as_ref().map(|info| AccountRead {
+                nonce: info.nonce,
+                balance: info.balance,
+                code_hash: info.code_hash,
+            }),
+        );
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.reads.codes.insert(code_hash, code.original_bytes());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let value = self.db.storage(address, index)?;
+        self.reads.storage.entry(address).or_default().insert(index, value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.reads.block_hashes.insert(number.saturating_to(), hash);
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::db::{CacheDB, EmptyDB};
+
+    #[test]
+    fn records_reads() {
+        let address = Address::with_last_byte(1);
+        let missing = Address::with_last_byte(2);
+        let mut cache = CacheDB::new(EmptyDB::default());
+        cache.insert_account_info(address, AccountInfo { nonce: 1, ..Default::default() });
+        cache.insert_account_storage(address, U256::from(1), U256::from(2)).unwrap();
+
+        let mut db = RecordingDatabase::new(cache);
+        db.basic(address).unwrap();
+        db.basic(missing).unwrap();
+        db.storage(address, U256::from(1)).unwrap();
+
+        assert_eq!(db.reads.accounts.len(), 2);
+        assert_eq!(db.reads.accounts[&address].as_ref().map(|account| account.nonce), Some(1));
+        assert!(db.reads.accounts[&missing].is_none());
+        assert_eq!(db.reads.storage[&address][&U256::from(1)], U256::from(2));
+    }
+}
@@ -0,0 +1,430 @@
+//! Command for turning a transaction of the chain into a state test.
+
+use super::export_checkpoints::{RecordingDatabase, StateReads};
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_primitives::{
+    keccak256,
+    revm_primitives::{db::Database, EnvWithHandlerCfg, EvmState, ResultAndState, SpecId},
+    AccessList, Address, BlockHashOrNumber, Bytes, GenesisAccount, TransactionVariant, TxHash,
+    B256, KECCAK_EMPTY, U256,
+};
+use reth_provider::{BlockReader, EvmEnvProvider, TransactionsProvider};
+use reth_revm::{
+    database::StateProviderDatabase, db::CacheDB, state_change::apply_beacon_root_contract_call,
+};
+use reth_trie::root::state_root_ref_unhashed;
+use serde::Serialize;
+use std::{collections::BTreeMap, fs::File, io::Write, path::PathBuf};
+use tracing::*;
+
+#[cfg(not(feature = "optimism"))]
+type EvmConfig = reth_node_ethereum::EthEvmConfig;
+#[cfg(feature = "optimism")]
+type EvmConfig = reth_node_optimism::OptimismEvmConfig;
+
+/// `reth debug make-test` command
+///
+/// Re-executes a transaction of the chain and writes the state it read, the transaction and the
+/// state after its execution as a state test fixture in the format of the execution spec tests.
+///
+/// The state before the transaction is the state after the preceding transactions of its block.
+/// Fixtures don't contain the secret key of the sender, but the sender and the signed transaction.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The hash of the transaction to turn into a state test.
+    #[arg(long, value_name = "HASH")]
+    tx: TxHash,
+
+    /// The file to write the state test to. Printed to stdout if not set.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `debug make-test` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+        let chain_spec = provider_factory.chain_spec();
+
+        let (transaction, meta) = provider
+            .transaction_by_hash_with_meta(self.tx)?
+            .ok_or_else(|| eyre::eyre!("Transaction {} not found", self.tx))?;
+        let block = provider
+            .block_with_senders(
+                BlockHashOrNumber::Number(meta.block_number),
+                TransactionVariant::WithHash,
+            )?
+            .ok_or_else(|| eyre::eyre!("Block {} not found", meta.block_number))?;
+        let sender = block.senders[meta.index as usize];
+
+        let evm_config = EvmConfig::default();
+        let (cfg, block_env) = provider.env_with_header(&block.header, evm_config.clone())?;
+        let spec_id = cfg.handler_cfg.spec_id;
+
+        // the state before the transaction, after the pre-block changes and the preceding
+        // transactions of the block
+        let state_provider = provider_factory.history_by_block_number(meta.block_number - 1)?;
+        let mut db = CacheDB::new(StateProviderDatabase::new(&state_provider));
+        let mut evm = evm_config.evm_with_env(
+            &mut db,
+            EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), Default::default()),
+        );
+        apply_beacon_root_contract_call(
+            &chain_spec,
+            block.timestamp,
+            block.number,
+            block.parent_beacon_block_root,
+            &mut evm,
+        )?;
+        for (sender, transaction) in block.transactions_with_sender().take(meta.index as usize) {
+            EvmConfig::fill_tx_env(evm.tx_mut(), transaction, *sender);
+            evm.transact_commit()?;
+        }
+        drop(evm);
+
+        let mut evm = evm_config.evm_with_env(
+            RecordingDatabase::new(&mut db),
+            EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env.clone(), Default::default()),
+        );
+        EvmConfig::fill_tx_env(evm.tx_mut(), &transaction, sender);
+        let ResultAndState { result, state } = evm.transact()?;
+        let (mut db, _) = evm.into_db_and_env_with_handler_cfg();
+
+        // the code of accounts whose code wasn't executed is needed for their code hash
+        let code_hashes = db
+            .reads
+            .accounts
+            .values()
+            .flatten()
+            .map(|account| account.code_hash)
+            .filter(|code_hash| {
+                *code_hash != KECCAK_EMPTY && !db.reads.codes.contains_key(code_hash)
+            })
+            .collect::<Vec<_>>();
+        for code_hash in code_hashes {
+            db.code_by_hash(code_hash)?;
+        }
+
+        let pre = pre_state(&db.reads);
+        let post = post_state(&pre, state, spec_id);
+        let mut block_hashes = db.reads.block_hashes;
+        block_hashes.insert(block.number - 1, block.parent_hash);
+
+        let test = StateTest {
+            info: StateTestInfo {
+                comment: format!(
+                    "Transaction {} at index {} of block {}",
+                    self.tx, meta.index, meta.block_number
+                ),
+                filling_tool: "reth debug make-test",
+            },
+            env: StateTestEnv {
+                current_coinbase: block_env.coinbase,
+                current_gas_limit: block_env.gas_limit,
+                current_number: block_env.number,
+                current_timestamp: block_env.timestamp,
+                current_difficulty: block_env.difficulty,
+                current_random: block_env.prevrandao,
+                current_base_fee: block_env.basefee,
+                current_excess_blob_gas: block_env
+                    .blob_excess_gas_and_price
+                    .map(|blob| U256::from(blob.excess_blob_gas)),
+                previous_hash: block.parent_hash,
+                block_hashes,
+            },
+            transaction: StateTestTransaction {
+                nonce: U256::from(transaction.nonce()),
+                gas_price: (!transaction.is_dynamic_fee())
+                    .then(|| U256::from(transaction.max_fee_per_gas())),
+                max_fee_per_gas: transaction
+                    .is_dynamic_fee()
+                    .then(|| U256::from(transaction.max_fee_per_gas())),
+                max_priority_fee_per_gas: transaction.max_priority_fee_per_gas().map(U256::from),
+                gas_limit: vec![U256::from(transaction.gas_limit())],
+                to: transaction.to().map(|to| to.to_string()).unwrap_or_default(),
+                value: vec![transaction.value()],
+                data: vec![transaction.input().clone()],
+                access_lists: transaction
+                    .access_list()
+                    .map(|access_list| vec![access_list.clone()]),
+                max_fee_per_blob_gas: transaction.max_fee_per_blob_gas().map(U256::from),
+                blob_versioned_hashes: transaction.blob_versioned_hashes(),
+                sender,
+            },
+            post: BTreeMap::from([(
+                fork_name(spec_id),
+                vec![StateTestPost {
+                    hash: state_root_ref_unhashed(&post),
+                    logs: keccak256(alloy_rlp::encode(result.logs())),
+                    txbytes: transaction.envelope_encoded(),
+                    indexes: StateTestIndexes::default(),
+                    state: post,
+                }],
+            )]),
+            pre,
+        };
+
+        let fixture = BTreeMap::from([(self.tx.to_string(), test)]);
+        match &self.output {
+            Some(output) => {
+                serde_json::to_writer_pretty(File::create(output)?, &fixture)?;
+                info!(target: "reth::cli", tx = %self.tx, output = %output.display(), "Wrote state test");
+            }
+            None => {
+                let mut stdout = std::io::stdout().lock();
+                serde_json::to_writer_pretty(&mut stdout, &fixture)?;
+                stdout.write_all(b"\n")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the accounts that were read as the pre-state of a state test.
+///
+/// Accounts that don't exist are omitted, as are storage slots with a zero value.
+fn pre_state(reads: &StateReads) -> BTreeMap<Address, GenesisAccount> {
+    reads
+        .accounts
+        .iter()
+        .filter_map(|(address, account)| {
+            let account = account.as_ref()?;
+            let storage = reads.storage.get(address).map(|storage| {
+                storage
+                    .iter()
+                    .filter(|(_, value)| !value.is_zero())
+                    .map(|(slot, value)| (B256::from(*slot), B256::from(*value)))
+                    .collect::<BTreeMap<_, _>>()
+            });
+            Some((
+                *address,
+                GenesisAccount {
+                    nonce: Some(account.nonce),
+                    balance: account.balance,
+                    code: reads.codes.get(&account.code_hash).cloned(),
+                    storage: storage.filter(|storage| !storage.is_empty()),
+                    private_key: None,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Applies the changes of a transaction to the pre-state of a state test.
+fn post_state(
+    pre: &BTreeMap<Address, GenesisAccount>,
+    changes: EvmState,
+    spec_id: SpecId,
+) -> BTreeMap<Address, GenesisAccount> {
+    let mut post = pre.clone();
+    for (address, account) in changes {
+        if !account.is_touched() {
+            continue
+        }
+        // EIP-161 removes touched empty accounts
+        if account.is_selfdestructed() ||
+            (account.is_empty() && spec_id.is_enabled_in(SpecId::SPURIOUS_DRAGON))
+        {
+            post.remove(&address);
+            continue
+        }
+
+        let entry = post.entry(address).or_default();
+        if account.is_created() {
+            entry.storage = None;
+        }
+        entry.nonce = Some(account.info.nonce);
+        entry.balance = account.info.balance;
+        if account.info.code_hash == KECCAK_EMPTY {
+            entry.code = None;
+        } else if let Some(code) = &account.info.code {
+            entry.code = Some(code.original_bytes());
+        }
+
+        let mut storage = entry.storage.take().unwrap_or_default();
+        for (slot, value) in account.storage {
+            if value.present_value.is_zero() {
+                storage.remove(&B256::from(slot));
+            } else {
+                storage.insert(B256::from(slot), B256::from(value.present_value));
+            }
+        }
+        entry.storage = (!storage.is_empty()).then_some(storage);
+    }
+    post
+}
+
+/// Returns the name of the fork in state tests.
+fn fork_name(spec_id: SpecId) -> String {
+    match spec_id {
+        SpecId::FRONTIER | SpecId::FRONTIER_THAWING => "Frontier",
+        SpecId::HOMESTEAD | SpecId::DAO_FORK => "Homestead",
+        SpecId::TANGERINE => "EIP150",
+        SpecId::SPURIOUS_DRAGON => "EIP158",
+        SpecId::BYZANTIUM => "Byzantium",
+        SpecId::CONSTANTINOPLE => "Constantinople",
+        SpecId::PETERSBURG => "ConstantinopleFix",
+        SpecId::ISTANBUL | SpecId::MUIR_GLACIER => "Istanbul",
+        SpecId::BERLIN => "Berlin",
+        SpecId::LONDON | SpecId::ARROW_GLACIER | SpecId::GRAY_GLACIER => "London",
+        SpecId::MERGE => "Paris",
+        SpecId::SHANGHAI => "Shanghai",
+        SpecId::CANCUN => "Cancun",
+        SpecId::PRAGUE => "Prague",
+        other => return format!("{other:?}"),
+    }
+    .to_string()
+}
+
+/// A state test fixture, in the format of the execution spec tests.
+#[derive(Debug, Serialize)]
+struct StateTest {
+    #[serde(rename = "_info")]
+    info: StateTestInfo,
+    env: StateTestEnv,
+    pre: BTreeMap<Address, GenesisAccount>,
+    transaction: StateTestTransaction,
+    /// The expected outcome, by fork.
+    post: BTreeMap<String, Vec<StateTestPost>>,
+}
+
+/// Information about the origin of a state test.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct StateTestInfo {
+    comment: String,
+    filling_tool: &'static str,
+}
+
+/// The block environment of a state test.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StateTestEnv {
+    current_coinbase: Address,
+    current_gas_limit: U256,
+    current_number: U256,
+    current_timestamp: U256,
+    current_difficulty: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_random: Option<B256>,
+    current_base_fee: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_excess_blob_gas: Option<U256>,
+    previous_hash: B256,
+    /// Hashes of previous blocks, by block number, including the ones the transaction read.
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+/// The transaction of a state test.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StateTestTransaction {
+    nonce: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gas_price: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_fee_per_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_priority_fee_per_gas: Option<U256>,
+    gas_limit: Vec<U256>,
+    /// The recipient, empty for contract creations.
+    to: String,
+    value: Vec<U256>,
+    data: Vec<Bytes>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    access_lists: Option<Vec<AccessList>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_fee_per_blob_gas: Option<U256>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blob_versioned_hashes: Option<Vec<B256>>,
+    sender: Address,
+}
+
+/// The expected outcome of a state test.
+#[derive(Debug, Serialize)]
+struct StateTestPost {
+    /// The state root after the transaction.
+    hash: B256,
+    /// The hash of the RLP encoded logs of the transaction.
+    logs: B256,
+    /// The signed transaction.
+    txbytes: Bytes,
+    indexes: StateTestIndexes,
+    /// The state after the transaction.
+    state: BTreeMap<Address, GenesisAccount>,
+}
+
+/// The indexes of the data, gas limit and value of the transaction, always `0`.
+#[derive(Debug, Default, Serialize)]
+struct StateTestIndexes {
+    data: usize,
+    gas: usize,
+    value: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::revm_primitives::{Account, AccountInfo, AccountStatus, EvmStorageSlot};
+
+    #[test]
+    fn applies_post_state() {
+        let sender = Address::with_last_byte(0x10);
+        let destroyed = Address::with_last_byte(0x11);
+        let pre = BTreeMap::from([
+            (
+                sender,
+                GenesisAccount {
+                    nonce: Some(1),
+                    balance: U256::from(10),
+                    storage: Some(BTreeMap::from([(
+                        B256::with_last_byte(1),
+                        B256::with_last_byte(1),
+                    )])),
+                    ..Default::default()
+                },
+            ),
+            (destroyed, GenesisAccount { balance: U256::from(1), ..Default::default() }),
+        ]);
+
+        let changes = EvmState::from_iter([
+            (
+                sender,
+                Account {
+                    info: AccountInfo { nonce: 2, balance: U256::from(5), ..Default::default() },
+                    storage: [
+                        (U256::from(1), EvmStorageSlot::new_changed(U256::from(1), U256::ZERO)),
+                        (U256::from(2), EvmStorageSlot::new_changed(U256::ZERO, U256::from(3))),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    status: AccountStatus::Touched,
+                },
+            ),
+            (
+                destroyed,
+                Account {
+                    info: AccountInfo::default(),
+                    storage: Default::default(),
+                    status: AccountStatus::Touched | AccountStatus::SelfDestructed,
+                },
+            ),
+        ]);
+
+        let post = post_state(&pre, changes, SpecId::CANCUN);
+        assert_eq!(post.len(), 1);
+        assert_eq!(post[&sender].nonce, Some(2));
+        assert_eq!(post[&sender].balance, U256::from(5));
+        assert_eq!(
+            post[&sender].storage,
+            Some(BTreeMap::from([(B256::with_last_byte(2), B256::with_last_byte(3))]))
+        );
+    }
+}
@@ -4,8 +4,11 @@ use clap::{Parser, Subcommand};
 use reth_cli_runner::CliContext;
 
 mod build_block;
+mod compare_block;
 mod execution;
+mod export_checkpoints;
 mod in_memory_merkle;
+mod make_test;
 mod merkle;
 mod replay_engine;
 
@@ -29,6 +32,12 @@ pub enum Subcommands {
     BuildBlock(build_block::Command),
     /// Debug engine API by replaying stored messages.
     ReplayEngine(replay_engine::Command),
+    /// Export the state reads, inputs and post-state roots of historical blocks.
+    ExportCheckpoints(export_checkpoints::Command),
+    /// Turn a transaction of the chain into a state test.
+    MakeTest(make_test::Command),
+    /// Compare the execution of a block with another client.
+    CompareBlock(compare_block::Command),
 }
 
 impl Command {
@@ -40,6 +49,9 @@ impl Command {
             Subcommands::InMemoryMerkle(command) => command.execute(ctx).await,
             Subcommands::BuildBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayEngine(command) => command.execute(ctx).await,
+            Subcommands::ExportCheckpoints(command) => command.execute().await,
+            Subcommands::MakeTest(command) => command.execute().await,
+            Subcommands::CompareBlock(command) => command.execute().await,
         }
     }
 }
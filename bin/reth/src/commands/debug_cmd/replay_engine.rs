@@ -17,7 +17,9 @@ use reth_db::DatabaseEnv;
 use reth_fs_util as fs;
 use reth_network::NetworkHandle;
 use reth_network_api::NetworkInfo;
-use reth_node_core::engine::engine_store::{EngineMessageStore, StoredEngineApiMessage};
+use reth_node_core::engine::engine_store::{
+    EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse,
+};
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::{
     providers::BlockchainProvider, CanonStateSubscriptions, ChainSpecProvider, ProviderFactory,
@@ -33,6 +35,7 @@ use tracing::*;
 
 /// `reth debug replay-engine` command
 /// This script will read stored engine API messages and replay them by the timestamp.
+/// The responses of the engine are compared with the recorded responses, if any.
 /// It does not require
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -171,29 +174,41 @@ impl Command {
         });
 
         let engine_api_store = EngineMessageStore::new(self.engine_api_store.clone());
+        let mut mismatches = 0;
         for filepath in engine_api_store.engine_messages_iter()? {
             let contents =
                 fs::read(&filepath).wrap_err(format!("failed to read: {}", filepath.display()))?;
             let message = serde_json::from_slice(&contents)
                 .wrap_err(format!("failed to parse: {}", filepath.display()))?;
             debug!(target: "reth::cli", filepath = %filepath.display(), ?message, "Forwarding Engine API message");
-            match message {
+            let response = match message {
                 StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
                     let response =
-                        beacon_engine_handle.fork_choice_updated(state, payload_attrs).await?;
+                        beacon_engine_handle.fork_choice_updated(state, payload_attrs).await;
                     debug!(target: "reth::cli", ?response, "Received for forkchoice updated");
+                    StoredEngineApiResponse::from_forkchoice_updated(&response)
                 }
                 StoredEngineApiMessage::NewPayload { payload, cancun_fields } => {
-                    let response = beacon_engine_handle.new_payload(payload, cancun_fields).await?;
+                    let response = beacon_engine_handle.new_payload(payload, cancun_fields).await;
                     debug!(target: "reth::cli", ?response, "Received for new payload");
+                    StoredEngineApiResponse::from_new_payload(&response)
                 }
             };
 
+            // Compare with the response of the node that recorded the message
+            let recorded = engine_api_store
+                .response(&filepath)
+                .wrap_err(format!("failed to read response: {}", filepath.display()))?;
+            if let Some(recorded) = recorded.filter(|recorded| !recorded.same_status(&response)) {
+                mismatches += 1;
+                warn!(target: "reth::cli", filepath = %filepath.display(), ?recorded, replayed = ?response, "Engine API response differs from the recorded response");
+            }
+
             // Pause before next message
             tokio::time::sleep(Duration::from_millis(self.interval)).await;
         }
 
-        info!(target: "reth::cli", "Finished replaying engine API messages");
+        info!(target: "reth::cli", mismatches, "Finished replaying engine API messages");
 
         match rx.await? {
             Ok(()) => info!("Beacon consensus engine exited successfully"),
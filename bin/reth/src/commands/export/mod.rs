@@ -0,0 +1,28 @@
+//! `reth export` command.
+
+use clap::{Parser, Subcommand};
+
+mod storage;
+
+/// `reth export` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth export` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Exports all storage slots of a contract at a block, for state surgery and audits.
+    Storage(storage::Command),
+}
+
+impl Command {
+    /// Execute `export` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Storage(command) => command.execute().await,
+        }
+    }
+}
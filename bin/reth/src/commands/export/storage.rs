@@ -0,0 +1,265 @@
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::{Parser, ValueEnum};
+use reth_db::tables;
+use reth_db_api::{cursor::DbDupCursorRO, models::BlockNumberAddress, transaction::DbTx};
+use reth_primitives::{hex, keccak256, Address, BlockNumber, Bytes, B256, U256};
+use reth_provider::{BlockNumReader, PruneCheckpointReader};
+use reth_prune_types::PruneSegment;
+use reth_trie::proof::Proof;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+use tracing::info;
+
+/// Output format of the exported storage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum StorageFormat {
+    /// A JSON object with the slots of the contract and, if requested, its storage root and
+    /// account proof.
+    #[default]
+    Json,
+    /// One line per slot, with the proof nodes separated by `;`.
+    Csv,
+}
+
+/// `reth export storage` command
+///
+/// Dumps the storage slots of a contract from the hashed storage. At historical blocks, the
+/// storage of the latest block is reverted with the storage changesets of the later blocks.
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The address of the contract to export the storage of.
+    #[arg(long, value_name = "ADDRESS")]
+    address: Address,
+
+    /// The block to export the storage at, defaults to the latest block.
+    #[arg(long, value_name = "BLOCK")]
+    block: Option<BlockNumber>,
+
+    /// The format to write the storage in.
+    #[arg(long, value_enum, default_value_t = StorageFormat::Json)]
+    format: StorageFormat,
+
+    /// The file to write the storage to, defaults to stdout.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+
+    /// Include the unhashed storage keys of the slots, read from the plain storage and the
+    /// storage changesets.
+    #[arg(long)]
+    preimages: bool,
+
+    /// Annotate every slot with its Merkle proof against the storage root of the contract, and
+    /// include the storage root and the account proof in the JSON output.
+    ///
+    /// Proofs are only available at the latest block.
+    #[arg(long)]
+    proofs: bool,
+}
+
+impl Command {
+    /// Execute `export storage` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+        let tip = provider.best_block_number()?;
+        let block = self.block.unwrap_or(tip);
+
+        if block > tip {
+            eyre::bail!("Block {block} is ahead of the latest block {tip}")
+        }
+        if self.proofs && block != tip {
+            eyre::bail!("Proofs are only available at the latest block {tip}")
+        }
+        if let Some(pruned) = provider
+            .get_prune_checkpoint(PruneSegment::StorageHistory)?
+            .and_then(|checkpoint| checkpoint.block_number)
+        {
+            if block < tip && block <= pruned {
+                eyre::bail!("Storage history was pruned up to block {pruned}")
+            }
+        }
+
+        let tx = provider.tx_ref();
+        let hashed_address = keccak256(self.address);
+
+        // the slots at the latest block
+        let mut slots = BTreeMap::new();
+        let mut hashed_storage = tx.cursor_dup_read::<tables::HashedStorages>()?;
+        for entry in hashed_storage.walk_dup(Some(hashed_address), None)? {
+            let (_, entry) = entry?;
+            slots.insert(entry.key, entry.value);
+        }
+
+        // the unhashed keys of all slots that were ever set after the block or are set at the
+        // latest block, which covers every slot that is set at the block
+        let mut preimages = HashMap::new();
+        if self.preimages || self.proofs {
+            let mut plain_storage = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+            for entry in plain_storage.walk_dup(Some(self.address), None)? {
+                let (_, entry) = entry?;
+                preimages.insert(keccak256(entry.key), entry.key);
+            }
+        }
+
+        // revert the slots to the block, the first change after the block holds the value at
+        // the block
+        let mut reverted = HashMap::new();
+        let mut changesets = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+        for number in block + 1..=tip {
+            let key = BlockNumberAddress((number, self.address));
+            for entry in changesets.walk_dup(Some(key), None)? {
+                let (_, entry) = entry?;
+                let hashed_slot = keccak256(entry.key);
+                reverted.entry(hashed_slot).or_insert(entry.value);
+                preimages.insert(hashed_slot, entry.key);
+            }
+        }
+        for (hashed_slot, value) in reverted {
+            if value.is_zero() {
+                slots.remove(&hashed_slot);
+            } else {
+                slots.insert(hashed_slot, value);
+            }
+        }
+
+        let mut export = StorageExport {
+            address: self.address,
+            block,
+            storage_root: None,
+            account_proof: None,
+            slots: slots
+                .into_iter()
+                .map(|(hashed_slot, value)| SlotExport {
+                    hashed_slot,
+                    slot: self.preimages.then(|| preimages.get(&hashed_slot).copied()).flatten(),
+                    value,
+                    proof: None,
+                })
+                .collect(),
+        };
+
+        if self.proofs {
+            let keys = export
+                .slots
+                .iter()
+                .filter_map(|slot| preimages.get(&slot.hashed_slot).copied())
+                .collect::<Vec<_>>();
+            let account_proof = Proof::new(tx).account_proof(self.address, &keys)?;
+            let mut storage_proofs = account_proof
+                .storage_proofs
+                .into_iter()
+                .map(|proof| (keccak256(proof.key), proof.proof))
+                .collect::<HashMap<_, _>>();
+            for slot in &mut export.slots {
+                slot.proof = storage_proofs.remove(&slot.hashed_slot);
+            }
+            export.storage_root = Some(account_proof.storage_root);
+            export.account_proof = Some(account_proof.proof);
+        }
+
+        match &self.output {
+            Some(path) => {
+                self.write(&export, BufWriter::new(File::create(path)?))?;
+                info!(target: "reth::cli", address = %self.address, block, slots = export.slots.len(), output = %path.display(), "Exported storage");
+            }
+            None => self.write(&export, io::stdout().lock())?,
+        }
+        Ok(())
+    }
+
+    /// Writes the storage in the configured format.
+    fn write(&self, export: &StorageExport, mut writer: impl Write) -> eyre::Result<()> {
+        match self.format {
+            StorageFormat::Json => {
+                serde_json::to_writer_pretty(&mut writer, export)?;
+                writeln!(writer)?;
+            }
+            StorageFormat::Csv => {
+                writeln!(writer, "hashed_slot,slot,value,proof")?;
+                for slot in &export.slots {
+                    writeln!(writer, "{}", slot.csv_row())?;
+                }
+            }
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// The storage of a contract at a block.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageExport {
+    /// The address of the contract.
+    address: Address,
+    /// The block the storage was exported at.
+    block: BlockNumber,
+    /// The storage root of the contract, if proofs were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    storage_root: Option<B256>,
+    /// The proof of the account against the state root, if proofs were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_proof: Option<Vec<Bytes>>,
+    /// The slots of the contract, ordered by their hashed key.
+    slots: Vec<SlotExport>,
+}
+
+/// A storage slot of a contract.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SlotExport {
+    /// The hashed key of the slot.
+    hashed_slot: B256,
+    /// The unhashed key of the slot, if preimages were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    slot: Option<B256>,
+    /// The value of the slot.
+    value: U256,
+    /// The proof of the slot against the storage root, if proofs were requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    proof: Option<Vec<Bytes>>,
+}
+
+impl SlotExport {
+    /// Returns the slot as a CSV row, with empty columns for the missing preimage and proof.
+    fn csv_row(&self) -> String {
+        let slot = self.slot.map(|slot| slot.to_string()).unwrap_or_default();
+        let proof = self
+            .proof
+            .as_ref()
+            .map(|proof| proof.iter().map(hex::encode_prefixed).collect::<Vec<_>>().join(";"))
+            .unwrap_or_default();
+        format!("{},{slot},{:#x},{proof}", self.hashed_slot, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_row() {
+        let mut slot = SlotExport {
+            hashed_slot: B256::with_last_byte(1),
+            slot: None,
+            value: U256::from(255),
+            proof: None,
+        };
+        assert_eq!(slot.csv_row(), format!("{},,0xff,", B256::with_last_byte(1)));
+
+        slot.slot = Some(B256::with_last_byte(2));
+        slot.proof = Some(vec![Bytes::from_static(&[1]), Bytes::from_static(&[2, 3])]);
+        assert_eq!(
+            slot.csv_row(),
+            format!("{},{},0xff,0x01;0x0203", B256::with_last_byte(1), B256::with_last_byte(2)),
+        );
+    }
+}
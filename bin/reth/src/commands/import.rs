@@ -1,8 +1,12 @@
 //! Command that initializes the node by importing a chain from a file.
 
 use crate::{
-    commands::common::{AccessRights, Environment, EnvironmentArgs},
+    commands::{
+        common::{AccessRights, Environment, EnvironmentArgs},
+        db::checksum::store_checksums,
+    },
     macros::block_executor,
+    utils::DbTool,
     version::SHORT_VERSION,
 };
 use clap::Parser;
@@ -10,7 +14,7 @@ use futures::{Stream, StreamExt};
 use reth_beacon_consensus::EthBeaconConsensus;
 use reth_config::Config;
 use reth_consensus::Consensus;
-use reth_db::tables;
+use reth_db::{tables, Tables};
 use reth_db_api::{database::Database, transaction::DbTx};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
@@ -48,6 +52,13 @@ pub struct ImportCommand {
     #[arg(long, value_name = "CHUNK_LEN", verbatim_doc_comment)]
     chunk_len: Option<u64>,
 
+    /// Stores the checksums of all tables once the chain is imported, so they can be checked
+    /// later with `reth db checksum --verify`.
+    ///
+    /// Hashing all tables of a large chain takes a long time.
+    #[arg(long, verbatim_doc_comment)]
+    store_checksums: bool,
+
     /// The path to a block file for import.
     ///
     /// The online stages (headers and bodies) are replaced by a file import, after which the
@@ -70,7 +81,7 @@ impl ImportCommand {
             "Chunking chain import"
         );
 
-        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+        let Environment { provider_factory, config, data_dir } = self.env.init(AccessRights::RW)?;
 
         let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
         info!(target: "reth::cli", "Consensus engine initialized");
@@ -149,6 +160,10 @@ impl ImportCommand {
             "Chain file imported"
         );
 
+        if self.store_checksums {
+            store_checksums(&data_dir, &DbTool::new(provider_factory)?, Tables::ALL)?;
+        }
+
         Ok(())
     }
 }
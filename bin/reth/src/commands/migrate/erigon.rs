@@ -0,0 +1,692 @@
+//! Reader for the erigon block snapshots.
+//!
+//! Erigon freezes blocks into segment files named `<version>-<from>-<to>-<type>.seg`, where the
+//! block range is given in thousands of blocks and the type is `headers`, `bodies` or
+//! `transactions`. Every segment starts with the number of words, the number of empty words and
+//! two dictionaries: the patterns that are cut out of the words and the positions they are
+//! inserted at, both Huffman coded. The words follow, each one being the Huffman coded word length
+//! and pattern positions, and the bytes that are not covered by patterns.
+//!
+//! - A header word is the first byte of the block hash, followed by the RLP encoded header.
+//! - A body word is the RLP encoded list of the id of the first transaction, the number of
+//!   transactions, the ommers and the withdrawals.
+//! - A transaction word is the first byte of the transaction hash and the sender, followed by the
+//!   encoded transaction. The first and the last transaction of every block are system
+//!   transactions.
+
+use alloy_rlp::Decodable;
+use memmap2::Mmap;
+use reth_primitives::{BlockBody, BlockNumber, Header, TransactionSigned, Withdrawals};
+use std::{
+    cmp::Reverse,
+    collections::BTreeMap,
+    fs::File,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+/// The number of blocks the ranges of the segment file names are given in.
+const SEGMENT_RANGE_UNIT: u64 = 1_000;
+
+/// The length of the prefix of a header word: the first byte of the block hash.
+const HEADER_PREFIX_LEN: usize = 1;
+
+/// The length of the prefix of a transaction word: the first byte of the transaction hash and the
+/// sender.
+const TRANSACTION_PREFIX_LEN: usize = 21;
+
+/// The maximum depth of a Huffman tree of a segment.
+const MAX_HUFFMAN_DEPTH: u64 = 64;
+
+/// A node of a Huffman tree. The children of a branch are indices into the tree.
+#[derive(Debug, Clone, Copy)]
+enum Node<T> {
+    Leaf(T),
+    Branch(usize, usize),
+}
+
+/// A Huffman tree, with its root at index `0`.
+#[derive(Debug)]
+struct HuffmanTree<T> {
+    nodes: Vec<Node<T>>,
+}
+
+impl<T: Copy> HuffmanTree<T> {
+    /// Builds the tree from the depths of its leaves, in the order of a depth-first traversal that
+    /// visits the `0` branch first.
+    fn new(leaves: &[(u64, T)]) -> eyre::Result<Self> {
+        let mut nodes = Vec::with_capacity(leaves.len() * 2);
+        let consumed = Self::build(&mut nodes, leaves, 0)?;
+        if consumed != leaves.len() {
+            eyre::bail!("segment dictionary is not a Huffman tree")
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Adds the subtree at the given depth and returns the number of leaves it consumed.
+    fn build(nodes: &mut Vec<Node<T>>, leaves: &[(u64, T)], depth: u64) -> eyre::Result<usize> {
+        let Some(&(leaf_depth, value)) = leaves.first() else {
+            eyre::bail!("segment dictionary is not a Huffman tree")
+        };
+        if leaf_depth == depth {
+            nodes.push(Node::Leaf(value));
+            return Ok(1)
+        }
+        if depth >= MAX_HUFFMAN_DEPTH {
+            eyre::bail!("segment dictionary is too deep")
+        }
+
+        let index = nodes.len();
+        nodes.push(Node::Branch(0, 0));
+        let zero = nodes.len();
+        let consumed = Self::build(nodes, leaves, depth + 1)?;
+        let one = nodes.len();
+        let consumed = consumed + Self::build(nodes, &leaves[consumed..], depth + 1)?;
+        nodes[index] = Node::Branch(zero, one);
+        Ok(consumed)
+    }
+}
+
+/// Reads the bits of the words of a segment, least significant bit first.
+#[derive(Debug)]
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    bit: u8,
+}
+
+impl<'a> BitReader<'a> {
+    /// Decodes the next value.
+    fn decode<T: Copy>(&mut self, tree: &HuffmanTree<T>) -> eyre::Result<T> {
+        let mut node = 0;
+        loop {
+            match tree.nodes[node] {
+                Node::Leaf(value) => return Ok(value),
+                Node::Branch(zero, one) => {
+                    let byte = self.data.get(self.pos).ok_or_else(truncated)?;
+                    node = if (byte >> self.bit) & 1 == 0 { zero } else { one };
+                    self.bit += 1;
+                    if self.bit == 8 {
+                        self.pos += 1;
+                        self.bit = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Skips to the start of the next byte, unless at the start of a byte already.
+    fn align(&mut self) {
+        if self.bit > 0 {
+            self.pos += 1;
+            self.bit = 0;
+        }
+    }
+
+    /// Returns the given number of bytes, starting at the given position.
+    fn bytes(&self, pos: usize, len: usize) -> eyre::Result<&'a [u8]> {
+        self.data.get(pos..pos + len).ok_or_else(truncated)
+    }
+}
+
+fn truncated() -> eyre::Report {
+    eyre::eyre!("segment is truncated")
+}
+
+/// A compressed segment file, which is read word by word.
+#[derive(Debug)]
+struct Segment {
+    path: PathBuf,
+    data: Mmap,
+    /// The patterns that are cut out of the words.
+    patterns: Vec<Vec<u8>>,
+    /// The Huffman tree of the pattern indices, if there are any patterns.
+    pattern_tree: Option<HuffmanTree<usize>>,
+    /// The Huffman tree of the word lengths and pattern positions.
+    position_tree: HuffmanTree<u64>,
+    /// The position of the next word.
+    next_word: usize,
+}
+
+impl Segment {
+    /// Opens the segment and reads its dictionaries.
+    fn open(path: &Path) -> eyre::Result<Self> {
+        let file = File::open(path)
+            .map_err(|err| eyre::eyre!("failed to open segment {path:?}: {err}"))?;
+        // SAFETY: the segments are frozen, they are never modified while they are read.
+        let data = unsafe { Mmap::map(&file)? };
+
+        let read_u64 = |pos: usize| -> eyre::Result<u64> {
+            let bytes = data.get(pos..pos + 8).ok_or_else(truncated)?;
+            Ok(u64::from_be_bytes(bytes.try_into()?))
+        };
+
+        // the number of words and of empty words is not needed to read the words sequentially
+        let mut pos = 16;
+        let dict_len = read_u64(pos)? as usize;
+        let mut dict = data.get(pos + 8..pos + 8 + dict_len).ok_or_else(truncated)?;
+        pos += 8 + dict_len;
+        let mut patterns = Vec::new();
+        let mut pattern_leaves = Vec::new();
+        while !dict.is_empty() {
+            let depth = read_uvarint(&mut dict)?;
+            let len = read_uvarint(&mut dict)? as usize;
+            let pattern = dict.get(..len).ok_or_else(truncated)?;
+            dict = &dict[len..];
+            pattern_leaves.push((depth, patterns.len()));
+            patterns.push(pattern.to_vec());
+        }
+        let pattern_tree =
+            (!pattern_leaves.is_empty()).then(|| HuffmanTree::new(&pattern_leaves)).transpose()?;
+
+        let dict_len = read_u64(pos)? as usize;
+        let mut dict = data.get(pos + 8..pos + 8 + dict_len).ok_or_else(truncated)?;
+        pos += 8 + dict_len;
+        let mut position_leaves = Vec::new();
+        while !dict.is_empty() {
+            let depth = read_uvarint(&mut dict)?;
+            let position = read_uvarint(&mut dict)?;
+            position_leaves.push((depth, position));
+        }
+        let position_tree = HuffmanTree::new(&position_leaves)?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            data,
+            patterns,
+            pattern_tree,
+            position_tree,
+            next_word: pos,
+        })
+    }
+
+    /// Decodes the next pattern.
+    fn pattern(&self, reader: &mut BitReader<'_>) -> eyre::Result<&[u8]> {
+        let tree = self
+            .pattern_tree
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("segment {:?} has no patterns", self.path))?;
+        Ok(&self.patterns[reader.decode(tree)?])
+    }
+
+    /// Reads the next word into the buffer.
+    fn next(&mut self, buf: &mut Vec<u8>) -> eyre::Result<()> {
+        buf.clear();
+        let mut reader = BitReader { data: &self.data, pos: self.next_word, bit: 0 };
+        let word_len = reader
+            .decode(&self.position_tree)?
+            .checked_sub(1)
+            .ok_or_else(|| eyre::eyre!("segment {:?} is corrupted", self.path))?
+            as usize;
+        if word_len == 0 {
+            reader.align();
+            self.next_word = reader.pos;
+            return Ok(())
+        }
+        buf.resize(word_len, 0);
+
+        // the patterns are inserted at their positions, which are relative to each other
+        let mut buf_pos = 0;
+        loop {
+            let position = reader.decode(&self.position_tree)? as usize;
+            if position == 0 {
+                break
+            }
+            buf_pos += position - 1;
+            let pattern = self.pattern(&mut reader)?;
+            buf.get_mut(buf_pos..buf_pos + pattern.len())
+                .ok_or_else(|| eyre::eyre!("segment {:?} is corrupted", self.path))?
+                .copy_from_slice(pattern);
+        }
+        reader.align();
+
+        // the uncovered bytes follow the positions, so the positions are read again to fill the
+        // gaps between the patterns
+        let mut data_pos = reader.pos;
+        let mut reader = BitReader { data: &self.data, pos: self.next_word, bit: 0 };
+        reader.decode(&self.position_tree)?;
+        let mut buf_pos = 0;
+        let mut uncovered = 0;
+        loop {
+            let position = reader.decode(&self.position_tree)? as usize;
+            if position == 0 {
+                break
+            }
+            buf_pos += position - 1;
+            if buf_pos > uncovered {
+                let len = buf_pos - uncovered;
+                buf[uncovered..buf_pos].copy_from_slice(reader.bytes(data_pos, len)?);
+                data_pos += len;
+            }
+            uncovered = buf_pos + self.pattern(&mut reader)?.len();
+        }
+        if word_len > uncovered {
+            let len = word_len - uncovered;
+            buf[uncovered..].copy_from_slice(reader.bytes(data_pos, len)?);
+            data_pos += len;
+        }
+
+        self.next_word = data_pos;
+        Ok(())
+    }
+}
+
+/// Reads an unsigned LEB128 integer.
+fn read_uvarint(buf: &mut &[u8]) -> eyre::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = buf.split_first().ok_or_else(truncated)?;
+        *buf = rest;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value)
+        }
+    }
+    eyre::bail!("varint overflows u64")
+}
+
+/// The block body as stored by erigon.
+#[derive(Debug)]
+struct StoredBody {
+    /// The id of the first transaction of the block, which is a system transaction.
+    base_tx_id: u64,
+    /// The number of transactions, including the two system transactions.
+    tx_count: u32,
+    ommers: Vec<Header>,
+    withdrawals: Option<Withdrawals>,
+}
+
+impl Decodable for StoredBody {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = alloy_rlp::Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        if buf.len() < header.payload_length {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let (mut payload, rest) = buf.split_at(header.payload_length);
+        let body = Self {
+            base_tx_id: u64::decode(&mut payload)?,
+            tx_count: u32::decode(&mut payload)?,
+            ommers: Vec::decode(&mut payload)?,
+            withdrawals: (!payload.is_empty())
+                .then(|| Withdrawals::decode(&mut payload))
+                .transpose()?,
+        };
+        if !payload.is_empty() {
+            return Err(alloy_rlp::Error::UnexpectedLength)
+        }
+        *buf = rest;
+        Ok(body)
+    }
+}
+
+/// The headers, bodies and transactions segments of a block range.
+#[derive(Debug, Clone)]
+struct SegmentFiles {
+    headers: PathBuf,
+    bodies: PathBuf,
+    transactions: PathBuf,
+}
+
+/// The open segments of a block range, which are read block by block.
+#[derive(Debug)]
+struct OpenSegments {
+    range: Range<BlockNumber>,
+    headers: Segment,
+    bodies: Segment,
+    transactions: Segment,
+    /// The number of the next block in the segments.
+    next_block: BlockNumber,
+    /// The id of the next transaction in the transactions segment, known once the first body was
+    /// read.
+    next_tx_id: Option<u64>,
+    buf: Vec<u8>,
+}
+
+impl OpenSegments {
+    fn open(range: Range<BlockNumber>, files: &SegmentFiles) -> eyre::Result<Self> {
+        Ok(Self {
+            next_block: range.start,
+            range,
+            headers: Segment::open(&files.headers)?,
+            bodies: Segment::open(&files.bodies)?,
+            transactions: Segment::open(&files.transactions)?,
+            next_tx_id: None,
+            buf: Vec::new(),
+        })
+    }
+
+    /// Reads the header and the stored body of the next block.
+    fn next_block(&mut self) -> eyre::Result<(Header, StoredBody)> {
+        self.headers.next(&mut self.buf)?;
+        let mut header_rlp = self.buf.get(HEADER_PREFIX_LEN..).ok_or_else(truncated)?;
+        let header = Header::decode(&mut header_rlp)?;
+        if header.number != self.next_block {
+            eyre::bail!(
+                "segment {:?} contains block {} instead of block {}",
+                self.headers.path,
+                header.number,
+                self.next_block
+            )
+        }
+
+        self.bodies.next(&mut self.buf)?;
+        let body = StoredBody::decode(&mut self.buf.as_slice())?;
+        self.next_tx_id.get_or_insert(body.base_tx_id);
+        self.next_block += 1;
+
+        Ok((header, body))
+    }
+
+    /// Reads the transactions with the given ids.
+    fn transactions(&mut self, ids: Range<u64>) -> eyre::Result<Vec<TransactionSigned>> {
+        let next_tx_id = self.next_tx_id.as_mut().expect("a body was read");
+        if ids.start < *next_tx_id {
+            eyre::bail!(
+                "transaction {} precedes the segment {:?}",
+                ids.start,
+                self.transactions.path
+            )
+        }
+
+        let mut transactions = Vec::with_capacity((ids.end - ids.start) as usize);
+        while *next_tx_id < ids.end {
+            self.transactions.next(&mut self.buf)?;
+            if *next_tx_id >= ids.start {
+                let mut encoded = self.buf.get(TRANSACTION_PREFIX_LEN..).ok_or_else(truncated)?;
+                transactions.push(decode_transaction(&mut encoded)?);
+            }
+            *next_tx_id += 1;
+        }
+        Ok(transactions)
+    }
+}
+
+/// Decodes a transaction, which erigon stores either in its canonical encoding, or wrapped in an
+/// RLP string as in block bodies.
+fn decode_transaction(buf: &mut &[u8]) -> alloy_rlp::Result<TransactionSigned> {
+    match buf.first() {
+        Some(&byte) if byte < alloy_rlp::EMPTY_STRING_CODE => {
+            TransactionSigned::decode_enveloped(buf)
+        }
+        _ => TransactionSigned::decode(buf),
+    }
+}
+
+/// Reader of the blocks stored in the erigon block snapshots.
+///
+/// The segments are read sequentially, so reading blocks in ascending order is cheap, while
+/// reading an earlier block reopens its segments.
+#[derive(Debug)]
+pub struct ErigonSnapshots {
+    /// The segment files by block range.
+    segments: BTreeMap<BlockNumber, (Range<BlockNumber>, SegmentFiles)>,
+    current: Option<OpenSegments>,
+}
+
+impl ErigonSnapshots {
+    /// Opens the block snapshots in the given `snapshots` directory.
+    pub fn open(path: &Path) -> eyre::Result<Self> {
+        let mut files = BTreeMap::<(u64, Reverse<u64>), [Option<PathBuf>; 3]>::new();
+        for entry in std::fs::read_dir(path)
+            .map_err(|err| eyre::eyre!("failed to read snapshots directory {path:?}: {err}"))?
+        {
+            let path = entry?.path();
+            let Some((range, kind)) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".seg"))
+                .and_then(parse_segment_name)
+            else {
+                continue
+            };
+            files.entry((range.0, Reverse(range.1))).or_default()[kind] = Some(path);
+        }
+
+        // merged segments are kept next to the segments they were merged from until those are
+        // deleted, so the largest segments are used and the ones they cover are skipped
+        let mut segments = BTreeMap::new();
+        let mut next_block = None;
+        for ((from, Reverse(to)), [headers, bodies, transactions]) in files {
+            let range = from * SEGMENT_RANGE_UNIT..to * SEGMENT_RANGE_UNIT;
+            if let Some(next_block) = next_block {
+                if range.end <= next_block {
+                    continue
+                }
+                if range.start != next_block {
+                    eyre::bail!("block snapshots are missing blocks {next_block}..{}", range.start)
+                }
+            }
+            let (Some(headers), Some(bodies), Some(transactions)) = (headers, bodies, transactions)
+            else {
+                eyre::bail!("block snapshots of blocks {range:?} are incomplete")
+            };
+            next_block = Some(range.end);
+            segments.insert(range.start, (range, SegmentFiles { headers, bodies, transactions }));
+        }
+
+        Ok(Self { segments, current: None })
+    }
+
+    /// Returns the number of the first block in the snapshots.
+    pub fn first_block(&self) -> BlockNumber {
+        self.segments.values().next().map_or(0, |(range, _)| range.start)
+    }
+
+    /// Returns the number of the last block in the snapshots, or `None` if there are no
+    /// snapshots.
+    pub fn last_block(&self) -> Option<BlockNumber> {
+        self.segments.values().next_back().map(|(range, _)| range.end - 1)
+    }
+
+    /// Reads the header and body of the given block.
+    pub fn block(&mut self, number: BlockNumber) -> eyre::Result<(Header, BlockBody)> {
+        let reopen = self.current.as_ref().map_or(true, |current| {
+            !current.range.contains(&number) || number < current.next_block
+        });
+        if reopen {
+            let (range, files) = self
+                .segments
+                .range(..=number)
+                .next_back()
+                .map(|(_, segment)| segment)
+                .filter(|(range, _)| range.contains(&number))
+                .ok_or_else(|| eyre::eyre!("block {number} is not in the block snapshots"))?;
+            self.current = Some(OpenSegments::open(range.clone(), files)?);
+        }
+        let current = self.current.as_mut().expect("segments are open");
+
+        loop {
+            let (header, body) = current.next_block()?;
+            if header.number < number {
+                continue
+            }
+
+            // the first and the last transaction of the block are system transactions
+            let first_tx_id = body.base_tx_id + 1;
+            let last_tx_id = (body.base_tx_id + body.tx_count as u64).saturating_sub(1);
+            let transactions = current.transactions(first_tx_id..last_tx_id.max(first_tx_id))?;
+
+            let body = BlockBody {
+                transactions,
+                ommers: body.ommers,
+                withdrawals: body.withdrawals,
+                requests: None,
+            };
+            return Ok((header, body))
+        }
+    }
+}
+
+/// Parses the name of a segment file without its extension, e.g. `v1-000000-000500-headers`, into
+/// its range in thousands of blocks and the index of its type.
+fn parse_segment_name(name: &str) -> Option<((u64, u64), usize)> {
+    let mut parts = name.splitn(4, '-');
+    let _version = parts.next()?;
+    let from = parts.next()?.parse().ok()?;
+    let to = parts.next()?.parse().ok()?;
+    let kind = match parts.next()? {
+        "headers" => 0,
+        "bodies" => 1,
+        "transactions" => 2,
+        _ => return None,
+    };
+    (from < to).then_some(((from, to), kind))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::{Signature, Transaction, TxLegacy, U256};
+    use std::io::Write;
+
+    fn write_uvarint(buf: &mut Vec<u8>, mut value: u64) {
+        while value >= 0x80 {
+            buf.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        buf.push(value as u8);
+    }
+
+    /// Writes a segment without patterns, whose word lengths are coded by a degenerate Huffman
+    /// tree.
+    fn write_segment(path: &Path, words: &[Vec<u8>]) {
+        let mut positions = words.iter().map(|word| word.len() as u64 + 1).collect::<Vec<_>>();
+        positions.push(0);
+        positions.sort_unstable();
+        positions.dedup();
+
+        // the position at index `i` is coded as `i` one bits followed by a zero bit, the last one
+        // without the zero bit
+        let mut dict = Vec::new();
+        for (index, position) in positions.iter().enumerate() {
+            let depth = (index + 1).min(positions.len() - 1);
+            write_uvarint(&mut dict, depth as u64);
+            write_uvarint(&mut dict, *position);
+        }
+        let write_code = |bits: &mut Vec<bool>, position: u64| {
+            let index = positions.iter().position(|p| *p == position).unwrap();
+            bits.extend(std::iter::repeat(true).take(index));
+            if index < positions.len() - 1 {
+                bits.push(false);
+            }
+        };
+
+        let mut data = Vec::new();
+        for word in words {
+            let mut bits = Vec::new();
+            write_code(&mut bits, word.len() as u64 + 1);
+            if !word.is_empty() {
+                write_code(&mut bits, 0);
+            }
+            for chunk in bits.chunks(8) {
+                data.push(
+                    chunk.iter().enumerate().fold(0u8, |byte, (i, bit)| byte | (*bit as u8) << i),
+                );
+            }
+            data.extend_from_slice(word);
+        }
+
+        let mut file = File::create(path).unwrap();
+        file.write_all(&(words.len() as u64).to_be_bytes()).unwrap();
+        file.write_all(&0u64.to_be_bytes()).unwrap();
+        file.write_all(&0u64.to_be_bytes()).unwrap();
+        file.write_all(&(dict.len() as u64).to_be_bytes()).unwrap();
+        file.write_all(&dict).unwrap();
+        file.write_all(&data).unwrap();
+    }
+
+    #[test]
+    fn read_words_with_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("words.seg");
+
+        let mut file = Vec::new();
+        file.extend(2u64.to_be_bytes());
+        file.extend(1u64.to_be_bytes());
+        // a single pattern, which is coded without any bits
+        file.extend(4u64.to_be_bytes());
+        file.extend([0, 2, b'b', b'c']);
+        // the positions 0, 1, 2 and 5, coded as the bits 00, 01, 10 and 11
+        file.extend(8u64.to_be_bytes());
+        file.extend([2, 0, 2, 1, 2, 2, 2, 5]);
+        // "abcd": length 5, pattern at position 2, terminator, followed by the uncovered bytes
+        file.extend([0b000111, b'a', b'd']);
+        // "": length 1
+        file.push(0b10);
+        std::fs::write(&path, file).unwrap();
+
+        let mut segment = Segment::open(&path).unwrap();
+        let mut buf = Vec::new();
+        segment.next(&mut buf).unwrap();
+        assert_eq!(buf, b"abcd");
+        segment.next(&mut buf).unwrap();
+        assert!(buf.is_empty());
+        assert!(segment.next(&mut buf).is_err());
+    }
+
+    #[test]
+    fn read_snapshot_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let headers = (0..2u64).map(|number| Header { number, ..Default::default() });
+        let header_words = headers
+            .clone()
+            .map(|header| {
+                let mut word = vec![header.hash_slow()[0]];
+                header.encode(&mut word);
+                word
+            })
+            .collect::<Vec<_>>();
+
+        let encode_body = |base_tx_id: u64, tx_count: u32| {
+            let ommers = Vec::<Header>::new();
+            let payload_length = base_tx_id.length() + tx_count.length() + ommers.length();
+            let mut word = Vec::new();
+            alloy_rlp::Header { list: true, payload_length }.encode(&mut word);
+            base_tx_id.encode(&mut word);
+            tx_count.encode(&mut word);
+            ommers.encode(&mut word);
+            word
+        };
+        let body_words = vec![encode_body(0, 2), encode_body(2, 3)];
+
+        let transaction = TransactionSigned::from_transaction_and_signature(
+            Transaction::Legacy(TxLegacy { nonce: 1, ..Default::default() }),
+            Signature { r: U256::from(1), s: U256::from(1), odd_y_parity: false },
+        );
+        let mut transaction_word = vec![transaction.hash()[0]];
+        transaction_word.extend([0; 20]);
+        transaction.encode_enveloped(&mut transaction_word);
+        let transaction_words = vec![vec![], vec![], vec![], transaction_word, vec![]];
+
+        write_segment(&dir.path().join("v1-000000-000001-headers.seg"), &header_words);
+        write_segment(&dir.path().join("v1-000000-000001-bodies.seg"), &body_words);
+        write_segment(&dir.path().join("v1-000000-000001-transactions.seg"), &transaction_words);
+        std::fs::write(dir.path().join("v1-000000-000001-headers.idx"), []).unwrap();
+
+        let mut snapshots = ErigonSnapshots::open(dir.path()).unwrap();
+        assert_eq!(snapshots.first_block(), 0);
+        assert_eq!(snapshots.last_block(), Some(999));
+
+        let (header, body) = snapshots.block(1).unwrap();
+        assert_eq!(header.number, 1);
+        assert_eq!(body.transactions, vec![transaction]);
+
+        // reading an earlier block reopens the segments
+        let (header, body) = snapshots.block(0).unwrap();
+        assert_eq!(header.number, 0);
+        assert!(body.transactions.is_empty());
+    }
+
+    #[test]
+    fn parse_segment_names() {
+        assert_eq!(parse_segment_name("v1-000000-000500-headers"), Some(((0, 500), 0)));
+        assert_eq!(parse_segment_name("v1-019000-019100-transactions"), Some(((19000, 19100), 2)));
+        assert_eq!(parse_segment_name("v1-000000-000500-borevents"), None);
+        assert_eq!(parse_segment_name("salt-blocks"), None);
+    }
+}
@@ -0,0 +1,381 @@
+//! Reader for the geth freezer (a.k.a. ancient store).
+//!
+//! The freezer stores each table as an index file and a set of data files. Every index entry is
+//! 6 bytes long: a big-endian `u16` data file number followed by a big-endian `u32` offset of the
+//! item end within that file. The first entry is special and stores the number of the first data
+//! file and the number of items that were removed from the tail.
+
+use alloy_rlp::Decodable;
+use reth_primitives::{BlockBody, BlockNumber, Bytes, Header, Log, Receipt};
+use std::{
+    fs::File,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+/// Size of a single freezer index entry.
+const INDEX_ENTRY_SIZE: u64 = 6;
+
+/// Name of the freezer table with RLP encoded headers.
+const HEADERS_TABLE: &str = "headers";
+
+/// Name of the freezer table with RLP encoded block bodies.
+const BODIES_TABLE: &str = "bodies";
+
+/// Name of the freezer table with RLP encoded block receipts.
+const RECEIPTS_TABLE: &str = "receipts";
+
+/// Entry of the freezer table index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IndexEntry {
+    /// Number of the data file.
+    file_number: u16,
+    /// Offset within the data file.
+    offset: u32,
+}
+
+impl IndexEntry {
+    fn decode(bytes: [u8; INDEX_ENTRY_SIZE as usize]) -> Self {
+        Self {
+            file_number: u16::from_be_bytes([bytes[0], bytes[1]]),
+            offset: u32::from_be_bytes([bytes[2], bytes[3], bytes[4], bytes[5]]),
+        }
+    }
+}
+
+/// A single table of the geth freezer.
+#[derive(Debug)]
+struct FreezerTable {
+    /// Directory of the freezer.
+    dir: PathBuf,
+    /// Name of the table.
+    name: &'static str,
+    /// Whether the items are snappy compressed.
+    compressed: bool,
+    /// Open index file.
+    index: File,
+    /// Number of items removed from the tail of the table.
+    item_offset: u64,
+    /// Number of items in the index, including the removed ones.
+    items: u64,
+}
+
+impl FreezerTable {
+    /// Opens the table, preferring the compressed variant if present.
+    fn open(dir: &Path, name: &'static str) -> eyre::Result<Self> {
+        let (path, compressed) = {
+            let compressed = dir.join(format!("{name}.cidx"));
+            if compressed.exists() {
+                (compressed, true)
+            } else {
+                (dir.join(format!("{name}.ridx")), false)
+            }
+        };
+        let index = File::open(&path)
+            .map_err(|err| eyre::eyre!("failed to open freezer index {path:?}: {err}"))?;
+
+        let len = index.metadata()?.len();
+        if len < INDEX_ENTRY_SIZE || len % INDEX_ENTRY_SIZE != 0 {
+            eyre::bail!("freezer index {path:?} is corrupted")
+        }
+
+        let mut table =
+            Self { dir: dir.to_path_buf(), name, compressed, index, item_offset: 0, items: 0 };
+        table.item_offset = table.read_index_entry(0)?.offset as u64;
+        table.items = table.item_offset + len / INDEX_ENTRY_SIZE - 1;
+
+        Ok(table)
+    }
+
+    /// Returns the number of the first item stored in the table.
+    const fn first_item(&self) -> u64 {
+        self.item_offset
+    }
+
+    /// Reads the index entry at the given position.
+    fn read_index_entry(&mut self, position: u64) -> eyre::Result<IndexEntry> {
+        let mut buf = [0u8; INDEX_ENTRY_SIZE as usize];
+        self.index.seek(SeekFrom::Start(position * INDEX_ENTRY_SIZE))?;
+        self.index.read_exact(&mut buf)?;
+        Ok(IndexEntry::decode(buf))
+    }
+
+    /// Reads the raw (decompressed) item with the given number.
+    fn item(&mut self, number: u64) -> eyre::Result<Vec<u8>> {
+        if number < self.item_offset || number >= self.items {
+            eyre::bail!("item {number} is out of bounds of freezer table {}", self.name)
+        }
+
+        let position = number - self.item_offset;
+        let mut start = self.read_index_entry(position)?;
+        let end = self.read_index_entry(position + 1)?;
+        // Items never span multiple files, so if the end is in the next file, the item starts at
+        // the beginning of that file. The first entry carries the tail metadata instead of an
+        // offset, so the first item always starts at the beginning of its file as well.
+        if position == 0 || start.file_number != end.file_number {
+            start = IndexEntry { file_number: end.file_number, offset: 0 };
+        }
+
+        let extension = if self.compressed { "cdat" } else { "rdat" };
+        let path = self.dir.join(format!("{}.{:04}.{extension}", self.name, end.file_number));
+        let mut file = File::open(&path)
+            .map_err(|err| eyre::eyre!("failed to open freezer data file {path:?}: {err}"))?;
+        file.seek(SeekFrom::Start(start.offset as u64))?;
+        let mut buf = vec![0u8; (end.offset - start.offset) as usize];
+        file.read_exact(&mut buf)?;
+
+        if self.compressed {
+            buf = snap::raw::Decoder::new().decompress_vec(&buf)?;
+        }
+
+        Ok(buf)
+    }
+}
+
+/// Reader of the blocks stored in the geth chain freezer.
+#[derive(Debug)]
+pub struct GethFreezer {
+    headers: FreezerTable,
+    bodies: FreezerTable,
+    receipts: FreezerTable,
+}
+
+impl GethFreezer {
+    /// Opens the chain freezer.
+    ///
+    /// The path can either point to the `ancient` directory or to the `ancient/chain` directory
+    /// used by newer geth versions.
+    pub fn open(path: &Path) -> eyre::Result<Self> {
+        let chain = path.join("chain");
+        let dir = if chain.is_dir() { chain.as_path() } else { path };
+
+        Ok(Self {
+            headers: FreezerTable::open(dir, HEADERS_TABLE)?,
+            bodies: FreezerTable::open(dir, BODIES_TABLE)?,
+            receipts: FreezerTable::open(dir, RECEIPTS_TABLE)?,
+        })
+    }
+
+    /// Returns the number of the first block that has both header and body in the freezer.
+    pub fn first_block(&self) -> BlockNumber {
+        self.headers.first_item().max(self.bodies.first_item())
+    }
+
+    /// Returns the number of the last block that has both header and body in the freezer, or
+    /// `None` if the freezer is empty.
+    pub fn last_block(&self) -> Option<BlockNumber> {
+        self.headers.items.min(self.bodies.items).checked_sub(1)
+    }
+
+    /// Reads the header of the given block.
+    pub fn header(&mut self, number: BlockNumber) -> eyre::Result<Header> {
+        let raw = self.headers.item(number)?;
+        Ok(Header::decode(&mut raw.as_slice())?)
+    }
+
+    /// Reads the body of the given block.
+    pub fn body(&mut self, number: BlockNumber) -> eyre::Result<BlockBody> {
+        let raw = self.bodies.item(number)?;
+        Ok(BlockBody::decode(&mut raw.as_slice())?)
+    }
+
+    /// Reads the receipts of the given block, whose body is needed for the transaction types.
+    pub fn receipts(
+        &mut self,
+        number: BlockNumber,
+        body: &BlockBody,
+    ) -> eyre::Result<Vec<Receipt>> {
+        let raw = self.receipts.item(number)?;
+        decode_receipts(&mut raw.as_slice(), body)
+            .map_err(|err| eyre::eyre!("failed to decode receipts of block {number}: {err}"))
+    }
+}
+
+/// Decodes the receipts of a block.
+///
+/// Geth stores every receipt as the list of its status, cumulative gas used and logs, without its
+/// transaction type. The status is empty for failed transactions, `1` for successful ones and the
+/// post state root before Byzantium, which doesn't tell whether the transaction succeeded, so
+/// these receipts are considered successful.
+fn decode_receipts(buf: &mut &[u8], body: &BlockBody) -> eyre::Result<Vec<Receipt>> {
+    let mut payload = decode_list(buf)?;
+    let mut receipts = Vec::with_capacity(body.transactions.len());
+    while !payload.is_empty() {
+        let transaction = body
+            .transactions
+            .get(receipts.len())
+            .ok_or_else(|| eyre::eyre!("block has more receipts than transactions"))?;
+
+        let mut fields = decode_list(&mut payload)?;
+        let status = Bytes::decode(&mut fields)?;
+        let cumulative_gas_used = u64::decode(&mut fields)?;
+        let logs = Vec::<Log>::decode(&mut fields)?;
+        if !fields.is_empty() {
+            eyre::bail!("receipt has unexpected fields")
+        }
+
+        receipts.push(Receipt {
+            tx_type: transaction.tx_type(),
+            success: !status.is_empty(),
+            cumulative_gas_used,
+            logs,
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
+        });
+    }
+
+    if receipts.len() != body.transactions.len() {
+        eyre::bail!(
+            "block has {} receipts for {} transactions",
+            receipts.len(),
+            body.transactions.len()
+        )
+    }
+    Ok(receipts)
+}
+
+/// Decodes the header of an RLP list and returns its payload.
+fn decode_list<'a>(buf: &mut &'a [u8]) -> alloy_rlp::Result<&'a [u8]> {
+    let header = alloy_rlp::Header::decode(buf)?;
+    if !header.list {
+        return Err(alloy_rlp::Error::UnexpectedString)
+    }
+    if buf.len() < header.payload_length {
+        return Err(alloy_rlp::Error::InputTooShort)
+    }
+    let (payload, rest) = buf.split_at(header.payload_length);
+    *buf = rest;
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use reth_primitives::{Address, Transaction, TransactionSigned, TxType, B256};
+    use std::io::Write;
+
+    /// Writes a table with the given items into a single data file.
+    fn write_table(dir: &Path, name: &str, item_offset: u32, items: &[Vec<u8>]) {
+        let mut index = File::create(dir.join(format!("{name}.cidx"))).unwrap();
+        let mut data = File::create(dir.join(format!("{name}.0000.cdat"))).unwrap();
+
+        index.write_all(&[0, 0]).unwrap();
+        index.write_all(&item_offset.to_be_bytes()).unwrap();
+
+        let mut offset = 0u32;
+        for item in items {
+            let compressed = snap::raw::Encoder::new().compress_vec(item).unwrap();
+            data.write_all(&compressed).unwrap();
+            offset += compressed.len() as u32;
+            index.write_all(&[0, 0]).unwrap();
+            index.write_all(&offset.to_be_bytes()).unwrap();
+        }
+    }
+
+    #[test]
+    fn read_freezer_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        let chain = dir.path().join("chain");
+        std::fs::create_dir(&chain).unwrap();
+
+        let headers = (5..8u64).map(|number| Header { number, ..Default::default() });
+        let headers = headers.collect::<Vec<_>>();
+        let encoded_headers = headers
+            .iter()
+            .map(|header| {
+                let mut buf = Vec::new();
+                header.encode(&mut buf);
+                buf
+            })
+            .collect::<Vec<_>>();
+        let encoded_bodies = headers
+            .iter()
+            .map(|_| {
+                let mut buf = Vec::new();
+                BlockBody::default().encode(&mut buf);
+                buf
+            })
+            .collect::<Vec<_>>();
+        write_table(&chain, HEADERS_TABLE, 5, &encoded_headers);
+        write_table(&chain, BODIES_TABLE, 5, &encoded_bodies);
+        write_table(&chain, RECEIPTS_TABLE, 5, &vec![vec![alloy_rlp::EMPTY_LIST_CODE]; 3]);
+
+        let mut freezer = GethFreezer::open(dir.path()).unwrap();
+        assert_eq!(freezer.first_block(), 5);
+        assert_eq!(freezer.last_block(), Some(7));
+        for header in headers {
+            assert_eq!(freezer.header(header.number).unwrap(), header);
+            let body = freezer.body(header.number).unwrap();
+            assert_eq!(body, BlockBody::default());
+            assert_eq!(freezer.receipts(header.number, &body).unwrap(), vec![]);
+        }
+        assert!(freezer.header(4).is_err());
+        assert!(freezer.header(8).is_err());
+    }
+
+    #[test]
+    fn decode_stored_receipts() {
+        let body = BlockBody {
+            transactions: vec![
+                TransactionSigned::default(),
+                TransactionSigned::from_transaction_and_signature(
+                    Transaction::Eip1559(Default::default()),
+                    Default::default(),
+                ),
+            ],
+            ..Default::default()
+        };
+        let log = Log::new_unchecked(Address::with_last_byte(1), vec![B256::ZERO], Bytes::new());
+
+        let encode_receipt = |status: &[u8], cumulative_gas_used: u64, logs: &[Log]| {
+            let status = Bytes::copy_from_slice(status);
+            let payload_length = status.length() + cumulative_gas_used.length() + logs.length();
+            let mut buf = Vec::new();
+            alloy_rlp::Header { list: true, payload_length }.encode(&mut buf);
+            status.encode(&mut buf);
+            cumulative_gas_used.encode(&mut buf);
+            logs.encode(&mut buf);
+            buf
+        };
+        let encoded_receipts =
+            [encode_receipt(&[1], 21000, &[]), encode_receipt(&[], 42000, &[log.clone()])];
+        let mut encoded = Vec::new();
+        let payload_length = encoded_receipts.iter().map(Vec::len).sum();
+        alloy_rlp::Header { list: true, payload_length }.encode(&mut encoded);
+        encoded_receipts.iter().for_each(|receipt| encoded.extend_from_slice(receipt));
+
+        let receipts = decode_receipts(&mut encoded.as_slice(), &body).unwrap();
+        assert_eq!(
+            receipts,
+            vec![
+                Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: 21000,
+                    logs: vec![],
+                    #[cfg(feature = "optimism")]
+                    deposit_nonce: None,
+                    #[cfg(feature = "optimism")]
+                    deposit_receipt_version: None,
+                },
+                Receipt {
+                    tx_type: TxType::Eip1559,
+                    success: false,
+                    cumulative_gas_used: 42000,
+                    logs: vec![log],
+                    #[cfg(feature = "optimism")]
+                    deposit_nonce: None,
+                    #[cfg(feature = "optimism")]
+                    deposit_receipt_version: None,
+                },
+            ]
+        );
+
+        // the number of receipts must match the number of transactions
+        let body = BlockBody { transactions: vec![TransactionSigned::default()], ..body };
+        assert!(decode_receipts(&mut encoded.as_slice(), &body).is_err());
+    }
+}
@@ -0,0 +1,275 @@
+//! Command that migrates the chain data of another client's database into reth.
+
+use crate::{
+    commands::{
+        common::{AccessRights, Environment, EnvironmentArgs},
+        import::build_import_pipeline,
+    },
+    version::SHORT_VERSION,
+};
+use clap::{Parser, ValueEnum};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_db::tables;
+use reth_db_api::{database::Database, transaction::DbTx};
+use reth_downloaders::file_client::FileClient;
+use reth_execution_types::ExecutionOutcome;
+use reth_primitives::{BlockBody, BlockNumber, Header, Receipt, Receipts, StaticFileSegment};
+use reth_provider::{
+    BlockNumReader, OriginalValuesKnown, ProviderFactory, StageCheckpointReader, StateWriter,
+    StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_prune_types::PruneModes;
+use reth_stages::StageId;
+use reth_static_file::StaticFileProducer;
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+use tracing::{debug, info, warn};
+
+mod erigon;
+pub use erigon::ErigonSnapshots;
+
+mod geth;
+pub use geth::GethFreezer;
+
+/// Default number of blocks read from the source database per pipeline run.
+const DEFAULT_CHUNK_BLOCKS: u64 = 100_000;
+
+/// Client whose database is migrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SourceClient {
+    /// Go-ethereum. The source path must point to the freezer (`chaindata/ancient`) directory.
+    Geth,
+    /// Erigon. The source path must point to the `snapshots` directory. Erigon doesn't store
+    /// receipts.
+    Erigon,
+}
+
+/// The frozen blocks of the client whose database is migrated.
+trait SourceDatabase {
+    /// Returns the number of the first block.
+    fn first_block(&self) -> BlockNumber;
+
+    /// Returns the number of the last block, or `None` if there are no blocks.
+    fn last_block(&self) -> Option<BlockNumber>;
+
+    /// Returns whether the receipts of the blocks are stored.
+    fn stores_receipts(&self) -> bool;
+
+    /// Reads the header and body of the given block.
+    fn block(&mut self, number: BlockNumber) -> eyre::Result<(Header, BlockBody)>;
+
+    /// Reads the receipts of the given block.
+    fn receipts(&mut self, number: BlockNumber, body: &BlockBody) -> eyre::Result<Vec<Receipt>>;
+}
+
+impl SourceDatabase for GethFreezer {
+    fn first_block(&self) -> BlockNumber {
+        self.first_block()
+    }
+
+    fn last_block(&self) -> Option<BlockNumber> {
+        self.last_block()
+    }
+
+    fn stores_receipts(&self) -> bool {
+        true
+    }
+
+    fn block(&mut self, number: BlockNumber) -> eyre::Result<(Header, BlockBody)> {
+        Ok((self.header(number)?, self.body(number)?))
+    }
+
+    fn receipts(&mut self, number: BlockNumber, body: &BlockBody) -> eyre::Result<Vec<Receipt>> {
+        self.receipts(number, body)
+    }
+}
+
+impl SourceDatabase for ErigonSnapshots {
+    fn first_block(&self) -> BlockNumber {
+        self.first_block()
+    }
+
+    fn last_block(&self) -> Option<BlockNumber> {
+        self.last_block()
+    }
+
+    fn stores_receipts(&self) -> bool {
+        false
+    }
+
+    fn block(&mut self, number: BlockNumber) -> eyre::Result<(Header, BlockBody)> {
+        self.block(number)
+    }
+
+    fn receipts(&mut self, _number: BlockNumber, _body: &BlockBody) -> eyre::Result<Vec<Receipt>> {
+        eyre::bail!("erigon doesn't store receipts")
+    }
+}
+
+/// Migrates headers, bodies and receipts from the frozen data of another client.
+///
+/// The blocks are read directly from the source database and fed to the sync pipeline, which
+/// re-derives all indices. Unless `--no-state` is set, the blocks are also executed, which
+/// regenerates receipts and state locally. Otherwise, the receipts are imported from the source
+/// database, if it stores them.
+#[derive(Debug, Parser)]
+pub struct MigrateCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// The client whose database is migrated.
+    #[arg(long, value_enum)]
+    from: SourceClient,
+
+    /// The path to the source database.
+    #[arg(long, value_name = "PATH", verbatim_doc_comment)]
+    src: PathBuf,
+
+    /// Disables stages that require state, and imports the receipts from the source database
+    /// instead.
+    #[arg(long, verbatim_doc_comment)]
+    no_state: bool,
+
+    /// Number of blocks read from the source database per pipeline run.
+    #[arg(long, value_name = "BLOCKS", default_value_t = DEFAULT_CHUNK_BLOCKS)]
+    chunk_blocks: u64,
+}
+
+impl MigrateCommand {
+    /// Execute `migrate` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        info!(target: "reth::cli", "reth {} starting", SHORT_VERSION);
+
+        let mut source: Box<dyn SourceDatabase> = match self.from {
+            SourceClient::Geth => Box::new(GethFreezer::open(&self.src)?),
+            SourceClient::Erigon => Box::new(ErigonSnapshots::open(&self.src)?),
+        };
+        let Some(last_block) = source.last_block() else {
+            info!(target: "reth::cli", "Source database has no frozen blocks");
+            return Ok(())
+        };
+
+        // the receipts are regenerated if the blocks are executed
+        let import_receipts = self.no_state && source.stores_receipts();
+        if self.no_state && !import_receipts {
+            warn!(target: "reth::cli", from = ?self.from, "Source database doesn't store receipts, receipts are not migrated");
+        }
+
+        let Environment { provider_factory, config, .. } = self.env.init(AccessRights::RW)?;
+
+        let consensus = Arc::new(EthBeaconConsensus::new(self.env.chain.clone()));
+        info!(target: "reth::cli", "Consensus engine initialized");
+
+        let first_block = provider_factory.last_block_number()? + 1;
+        if first_block < source.first_block() {
+            eyre::bail!(
+                "source database starts at block {}, next block to migrate is {first_block}",
+                source.first_block()
+            )
+        }
+        info!(target: "reth::cli", first_block, last_block, "Migrating blocks");
+
+        let mut chunk_start = first_block;
+        while chunk_start <= last_block {
+            let chunk_end = last_block.min(chunk_start + self.chunk_blocks.max(1) - 1);
+
+            let mut headers = HashMap::new();
+            let mut bodies = HashMap::new();
+            let mut receipts = import_receipts.then(Receipts::default);
+            for number in chunk_start..=chunk_end {
+                let (header, body) = source.block(number)?;
+                if let Some(receipts) = &mut receipts {
+                    receipts.push(source.receipts(number, &body)?.into_iter().map(Some).collect());
+                }
+                bodies.insert(header.hash_slow(), body);
+                headers.insert(number, header);
+            }
+            debug!(target: "reth::cli", blocks=?chunk_start..=chunk_end, "Read blocks from source database");
+
+            let file_client = FileClient::default().with_headers(headers).with_bodies(bodies);
+            let tip = file_client.tip().ok_or(eyre::eyre!("file client has no tip"))?;
+
+            let (mut pipeline, events) = build_import_pipeline(
+                &config,
+                provider_factory.clone(),
+                &consensus,
+                Arc::new(file_client),
+                StaticFileProducer::new(provider_factory.clone(), PruneModes::default()),
+                self.no_state,
+            )
+            .await?;
+
+            pipeline.set_tip(tip);
+
+            let latest_block_number = provider_factory
+                .provider()?
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|ch| ch.block_number);
+            tokio::spawn(reth_node_events::node::handle_events(
+                None,
+                latest_block_number,
+                events,
+                provider_factory.db_ref().clone(),
+            ));
+
+            info!(target: "reth::cli", blocks=?chunk_start..=chunk_end, "Starting sync pipeline");
+            tokio::select! {
+                res = pipeline.run() => res?,
+                _ = tokio::signal::ctrl_c() => return Ok(()),
+            }
+
+            if let Some(receipts) = receipts {
+                write_receipts(&provider_factory, chunk_start, receipts)?;
+                debug!(target: "reth::cli", blocks=?chunk_start..=chunk_end, "Imported receipts");
+            }
+
+            chunk_start = chunk_end + 1;
+        }
+
+        let total_imported_blocks =
+            provider_factory.provider()?.tx_ref().entries::<tables::HeaderNumbers>()?;
+        info!(target: "reth::cli", total_imported_blocks, "Migration finished");
+
+        Ok(())
+    }
+}
+
+/// Writes the receipts of the blocks starting at the given block to the static files.
+fn write_receipts<DB: Database>(
+    provider_factory: &ProviderFactory<DB>,
+    first_block: BlockNumber,
+    receipts: Receipts,
+) -> eyre::Result<()> {
+    let tx = provider_factory.provider_rw()?.into_tx();
+    let static_file_provider = provider_factory.static_file_provider();
+
+    // the receipt writing of `ExecutionOutcome::write_to_storage` is reused, without any state
+    let execution_outcome =
+        ExecutionOutcome::new(Default::default(), receipts, first_block, Default::default());
+    execution_outcome.write_to_storage::<DB::TXMut>(
+        &tx,
+        Some(static_file_provider.get_writer(first_block, StaticFileSegment::Receipts)?),
+        OriginalValuesKnown::Yes,
+    )?;
+
+    tx.commit()?;
+    static_file_provider.commit()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_migrate_command() {
+        let args: MigrateCommand =
+            MigrateCommand::parse_from(["reth", "--from", "geth", "--src", "ancient"]);
+        assert_eq!(args.from, SourceClient::Geth);
+        assert_eq!(args.src, PathBuf::from("ancient"));
+        assert_eq!(args.chunk_blocks, DEFAULT_CHUNK_BLOCKS);
+
+        let args: MigrateCommand =
+            MigrateCommand::parse_from(["reth", "--from", "erigon", "--src", "snapshots"]);
+        assert_eq!(args.from, SourceClient::Erigon);
+    }
+}
@@ -1,19 +1,23 @@
 //! This contains all of the `reth` commands
 
+pub mod bootnode;
 pub mod config_cmd;
 pub mod db;
 pub mod debug_cmd;
 pub mod dump_genesis;
+pub mod export;
 pub mod import;
 pub mod import_op;
 pub mod import_receipts_op;
 
 pub mod init_cmd;
 pub mod init_state;
+pub mod migrate;
 
 pub mod node;
 pub mod p2p;
 pub mod recover;
+pub mod space;
 pub mod stage;
 pub mod test_vectors;
 
@@ -2,16 +2,30 @@
 
 use crate::args::{
     utils::{chain_help, chain_value_parser, parse_socket_address, SUPPORTED_CHAINS},
-    DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs, PruningArgs,
-    RpcServerArgs, TxPoolArgs,
+    AlertArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, ForkArgs, HealthArgs,
+    NetworkArgs, PayloadBuilderArgs, PruningArgs, ReorgGuardArgs, RpcServerArgs, SpaceReportArgs,
+    TxPoolArgs,
 };
 use clap::{value_parser, Args, Parser};
+use eyre::WrapErr;
 use reth_chainspec::ChainSpec;
 use reth_cli_runner::CliContext;
+use reth_config::Config;
 use reth_db::{init_db, DatabaseEnv};
 use reth_node_builder::{NodeBuilder, WithLaunchContext};
-use reth_node_core::{node_config::NodeConfig, version};
-use std::{ffi::OsString, fmt, future::Future, net::SocketAddr, path::PathBuf, sync::Arc};
+use reth_node_core::{dirs::MaybePlatformPath, node_config::NodeConfig, version};
+use reth_provider::{providers::StaticFileProvider, ProviderFactory};
+use std::{
+    ffi::OsString,
+    fmt,
+    future::Future,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tempfile::TempDir;
+
+mod safe_mode;
 
 /// Start the node
 #[derive(Debug, Parser)]
@@ -53,6 +67,8 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     /// - `AUTH_PORT`: default + `instance` * 100 - 100
     /// - `HTTP_RPC_PORT`: default - `instance` + 1
     /// - `WS_RPC_PORT`: default + `instance` * 2 - 2
+    /// - `GRAPHQL_PORT`: default + `instance` * 100 - 100
+    /// - `GRPC_PORT`: default + `instance` * 100 - 100
     #[arg(long, value_name = "INSTANCE", global = true, default_value_t = 1, value_parser = value_parser!(u16).range(..=200))]
     pub instance: u16,
 
@@ -63,6 +79,28 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[arg(long, conflicts_with = "instance", global = true)]
     pub with_unused_ports: bool,
 
+    /// Store all data in memory, in a temporary data directory that is removed on shutdown.
+    ///
+    /// This is experimental and intended for ephemeral test networks, e.g. together with `--dev`.
+    /// On Linux, the data directory is created in tmpfs (`/dev/shm`), elsewhere in the temporary
+    /// directory of the OS.
+    #[arg(long = "experimental.in-memory", conflicts_with_all = ["datadir", "static_files_path"])]
+    pub in_memory: bool,
+
+    /// Open the data directory read-only and check the consistency of the storage before starting
+    /// the node, e.g. after a crash or power loss.
+    ///
+    /// The stage checkpoints, the block body indices, the static files and the state root of the
+    /// trie are checked, and a plan to repair the inconsistencies is printed. If the storage is
+    /// consistent the node starts, otherwise it fails without modifying the data directory unless
+    /// `--apply-repairs` is set.
+    #[arg(long, conflicts_with = "in_memory")]
+    pub safe_mode: bool,
+
+    /// Apply the repairs planned by `--safe-mode` and start the node.
+    #[arg(long, requires = "safe_mode")]
+    pub apply_repairs: bool,
+
     /// All datadir related arguments
     #[command(flatten)]
     pub datadir: DatadirArgs,
@@ -99,6 +137,30 @@ pub struct NodeCommand<Ext: clap::Args + fmt::Debug = NoArgs> {
     #[command(flatten)]
     pub pruning: PruningArgs,
 
+    /// All sync health related arguments with --health prefix
+    #[command(flatten)]
+    pub health: HealthArgs,
+
+    /// All alert related arguments with --alerts prefix
+    #[command(flatten)]
+    pub alerts: AlertArgs,
+
+    /// All space report related arguments with --space-report prefix
+    #[command(flatten)]
+    pub space_report: SpaceReportArgs,
+
+    /// All reorg guard related arguments with --reorg-guard prefix
+    #[command(flatten)]
+    pub reorg_guard: ReorgGuardArgs,
+
+    /// All consensus engine related arguments with --engine prefix
+    #[command(flatten)]
+    pub engine: EngineArgs,
+
+    /// All fork related arguments with --fork prefix
+    #[command(flatten)]
+    pub fork: ForkArgs,
+
     /// Additional cli arguments
     #[command(flatten, next_help_heading = "Extension")]
     pub ext: Ext,
@@ -139,6 +201,9 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             metrics,
             instance,
             with_unused_ports,
+            in_memory,
+            safe_mode,
+            apply_repairs,
             network,
             rpc,
             txpool,
@@ -147,6 +212,12 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            health,
+            alerts,
+            space_report,
+            reorg_guard,
+            engine,
+            fork,
             ext,
         } = self;
 
@@ -165,18 +236,67 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
             db,
             dev,
             pruning,
+            health,
+            alerts,
+            space_report,
+            reorg_guard,
+            engine,
+            fork,
         };
 
         // Register the prometheus recorder before creating the database,
         // because database init needs it to register metrics.
         let _ = node_config.install_prometheus_recorder()?;
 
+        // the temporary data directory is removed when the node exits
+        let _in_memory_datadir = if in_memory {
+            let datadir = in_memory_datadir()?;
+            tracing::info!(target: "reth::cli", path = ?datadir.path(), "Storing data in memory");
+            node_config.datadir.datadir = MaybePlatformPath::from(datadir.path().to_path_buf());
+            Some(datadir)
+        } else {
+            None
+        };
+
         let data_dir = node_config.datadir();
         let db_path = data_dir.db();
 
+        let mut repairs = None;
+        if safe_mode {
+            let config_path = node_config.config.clone().unwrap_or_else(|| data_dir.config());
+            // loading a config file that doesn't exist would create it
+            let config: Config = if config_path.exists() {
+                confy::load_path(&config_path)
+                    .wrap_err_with(|| format!("Could not load config file {config_path:?}"))?
+            } else {
+                Config::default()
+            };
+            let report = safe_mode::ConsistencyReport::from_datadir(&node_config, &config)?;
+            if report.is_consistent() {
+                tracing::info!(target: "reth::cli", "Storage is consistent");
+            } else {
+                println!("{}\n", report.table());
+                if !apply_repairs {
+                    eyre::bail!("Inconsistent storage. Run with --apply-repairs to repair.")
+                }
+                repairs = Some((config, report));
+            }
+        }
+
         tracing::info!(target: "reth::cli", path = ?db_path, "Opening database");
         let database = Arc::new(init_db(db_path.clone(), self.db.database_args())?.with_metrics());
 
+        if let Some((config, report)) = repairs {
+            tracing::info!(target: "reth::cli", "Applying repairs");
+            let factory = ProviderFactory::new(
+                database.clone(),
+                node_config.chain.clone(),
+                StaticFileProvider::read_write(data_dir.static_files())?,
+            );
+            let has_receipt_pruning = safe_mode::has_receipt_pruning(&node_config, &config);
+            safe_mode::apply_repairs(factory, &config, has_receipt_pruning, &report)?;
+        }
+
         if with_unused_ports {
             node_config = node_config.with_unused_ports();
         }
@@ -189,6 +309,18 @@ impl<Ext: clap::Args + fmt::Debug> NodeCommand<Ext> {
     }
 }
 
+/// Creates the temporary data directory for `--experimental.in-memory`, in tmpfs if available.
+fn in_memory_datadir() -> std::io::Result<TempDir> {
+    let tmpfs = Path::new("/dev/shm");
+    let mut builder = tempfile::Builder::new();
+    builder.prefix("reth-");
+    if tmpfs.is_dir() {
+        builder.tempdir_in(tmpfs)
+    } else {
+        builder.tempdir()
+    }
+}
+
 /// No Additional arguments
 #[derive(Debug, Clone, Copy, Default, Args)]
 #[non_exhaustive]
@@ -198,10 +330,7 @@ pub struct NoArgs;
 mod tests {
     use super::*;
     use reth_discv4::DEFAULT_DISCOVERY_PORT;
-    use std::{
-        net::{IpAddr, Ipv4Addr},
-        path::Path,
-    };
+    use std::net::{IpAddr, Ipv4Addr};
 
     #[test]
     fn parse_help_node_command() {
@@ -266,6 +395,20 @@ mod tests {
         assert_eq!(cmd.metrics, Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 9001)));
     }
 
+    #[test]
+    fn parse_in_memory() {
+        let cmd = NodeCommand::try_parse_args_from(["reth", "--experimental.in-memory"]).unwrap();
+        assert!(cmd.in_memory);
+
+        let cmd = NodeCommand::try_parse_args_from([
+            "reth",
+            "--experimental.in-memory",
+            "--datadir",
+            "my/custom/path",
+        ]);
+        assert!(cmd.is_err());
+    }
+
     #[test]
     fn parse_config_path() {
         let cmd =
@@ -329,6 +472,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 8551);
         assert_eq!(cmd.rpc.http_port, 8545);
         assert_eq!(cmd.rpc.ws_port, 8546);
+        assert_eq!(cmd.rpc.grpc_port, 8549);
         // check network listening port number
         assert_eq!(cmd.network.port, 30303);
 
@@ -339,6 +483,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 8651);
         assert_eq!(cmd.rpc.http_port, 8544);
         assert_eq!(cmd.rpc.ws_port, 8548);
+        assert_eq!(cmd.rpc.grpc_port, 8649);
         // check network listening port number
         assert_eq!(cmd.network.port, 30304);
 
@@ -349,6 +494,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 8751);
         assert_eq!(cmd.rpc.http_port, 8543);
         assert_eq!(cmd.rpc.ws_port, 8550);
+        assert_eq!(cmd.rpc.grpc_port, 8749);
         // check network listening port number
         assert_eq!(cmd.network.port, 30305);
     }
@@ -367,6 +513,24 @@ mod tests {
         assert_eq!(err.kind(), clap::error::ErrorKind::ArgumentConflict);
     }
 
+    #[test]
+    fn graphql_requires_http() {
+        let cmd = NodeCommand::<NoArgs>::parse_from(["reth", "--http", "--graphql"]);
+        assert!(cmd.rpc.http && cmd.rpc.graphql);
+
+        let err = NodeCommand::try_parse_args_from(["reth", "--graphql"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
+    #[test]
+    fn apply_repairs_requires_safe_mode() {
+        let cmd = NodeCommand::<NoArgs>::parse_from(["reth", "--safe-mode", "--apply-repairs"]);
+        assert!(cmd.safe_mode && cmd.apply_repairs);
+
+        let err = NodeCommand::try_parse_args_from(["reth", "--apply-repairs"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+    }
+
     #[test]
     fn with_unused_ports_check_zero() {
         let mut cmd = NodeCommand::<NoArgs>::parse_from(["reth"]);
@@ -377,6 +541,7 @@ mod tests {
         assert_eq!(cmd.rpc.auth_port, 0);
         assert_eq!(cmd.rpc.http_port, 0);
         assert_eq!(cmd.rpc.ws_port, 0);
+        assert_eq!(cmd.rpc.grpc_port, 0);
 
         // make sure the network ports are zero
         assert_eq!(cmd.network.port, 0);
@@ -0,0 +1,639 @@
+//! Consistency checks of the storage for `reth node --safe-mode`
+
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use reth_beacon_consensus::EthBeaconConsensus;
+use reth_config::Config;
+use reth_db::{open_db_read_only, tables};
+use reth_db_api::{
+    cursor::DbCursorRO,
+    database::Database,
+    table::Table,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_downloaders::{bodies::noop::NoopBodiesDownloader, headers::noop::NoopHeaderDownloader};
+use reth_evm::noop::NoopBlockExecutorProvider;
+use reth_node_core::node_config::NodeConfig;
+use reth_primitives::{BlockNumber, StaticFileSegment, B256};
+use reth_provider::{
+    providers::StaticFileProvider, BlockReader, ChainSpecProvider, DatabaseProvider,
+    HeaderProvider, ProviderError, ProviderFactory, StageCheckpointReader,
+    StaticFileProviderFactory, StaticFileWriter,
+};
+use reth_stages::{sets::DefaultStages, Pipeline, StageId};
+use reth_static_file::StaticFileProducer;
+use reth_trie::StateRoot;
+use std::{cmp::Ordering, fmt, sync::Arc};
+use tokio::sync::watch;
+use tracing::info;
+
+/// A group of consistency checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Check {
+    /// Every stage checkpoint is at most the checkpoint of the previous stage.
+    Checkpoints,
+    /// The block body indices end at the Bodies checkpoint.
+    Indices,
+    /// The static files were committed completely and match the database and stage checkpoints.
+    StaticFiles,
+    /// The state root of the trie matches the header at the `MerkleExecute` checkpoint.
+    Trie,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Checkpoints => "Checkpoints",
+            Self::Indices => "Indices",
+            Self::StaticFiles => "Static Files",
+            Self::Trie => "Trie",
+        })
+    }
+}
+
+/// The repair of an [`Inconsistency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Repair {
+    /// Truncate the latest static file of the segment to its last committed row.
+    ///
+    /// Healing may unwind the stages further, which is only known once the file is healed.
+    HealStaticFile(StaticFileSegment),
+    /// Remove the rows of the static file segment above the block.
+    PruneStaticFile { segment: StaticFileSegment, block: BlockNumber },
+    /// Unwind all stages to the block.
+    Unwind(BlockNumber),
+    /// Drop the state trie, so that it's rebuilt from the hashed state.
+    RebuildTrie,
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::HealStaticFile(segment) => {
+                write!(f, "truncate the latest {segment} static file to its last committed row")
+            }
+            Self::PruneStaticFile { segment, block } => {
+                write!(f, "remove the {segment} static file rows above block {block}")
+            }
+            Self::Unwind(block) => write!(f, "unwind all stages to block {block}"),
+            Self::RebuildTrie => f.write_str("drop the state trie and rebuild it"),
+        }
+    }
+}
+
+/// An inconsistency of the storage and its repair.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Inconsistency {
+    pub(crate) check: Check,
+    pub(crate) description: String,
+    pub(crate) repair: Repair,
+}
+
+/// The inconsistencies found by the checks of the storage.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ConsistencyReport {
+    pub(crate) inconsistencies: Vec<Inconsistency>,
+}
+
+impl ConsistencyReport {
+    /// Opens the data directory of the node read-only and checks the consistency of its storage.
+    ///
+    /// Returns an empty report if there's no database yet.
+    pub(crate) fn from_datadir(node_config: &NodeConfig, config: &Config) -> eyre::Result<Self> {
+        let data_dir = node_config.datadir();
+        let db_path = data_dir.db();
+        if !db_path.exists() {
+            info!(target: "reth::cli", path = ?db_path, "No database to check");
+            return Ok(Self::default())
+        }
+
+        info!(target: "reth::cli", path = ?db_path, "Opening storage read-only");
+        let factory = ProviderFactory::new(
+            open_db_read_only(&db_path, node_config.db.database_args())?,
+            node_config.chain.clone(),
+            StaticFileProvider::read_only(data_dir.static_files())?,
+        );
+
+        info!(target: "reth::cli", "Checking storage consistency");
+        Self::new(&factory, has_receipt_pruning(node_config, config))
+    }
+
+    /// Checks the consistency of the storage without modifying it.
+    pub(crate) fn new<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        has_receipt_pruning: bool,
+    ) -> eyre::Result<Self> {
+        let provider = factory.provider()?;
+        let mut report = Self::default();
+
+        let checkpoints = StageId::ALL
+            .into_iter()
+            // the checkpoint of the unwind step of the merkle stage isn't kept in sync
+            .filter(|id| *id != StageId::MerkleUnwind)
+            .map(|id| Ok((id, checkpoint(&provider, id)?)))
+            .collect::<Result<Vec<_>, ProviderError>>()?;
+        report.check_checkpoints(&checkpoints);
+        report.check_indices(&provider)?;
+        report.check_static_files(
+            factory.static_file_provider(),
+            &provider,
+            has_receipt_pruning,
+        )?;
+        report.check_trie(&provider)?;
+
+        Ok(report)
+    }
+
+    /// Returns `true` if no inconsistency was found.
+    pub(crate) fn is_consistent(&self) -> bool {
+        self.inconsistencies.is_empty()
+    }
+
+    /// Returns the lowest block any repair unwinds to.
+    pub(crate) fn unwind_target(&self) -> Option<BlockNumber> {
+        self.repairs()
+            .filter_map(|repair| match repair {
+                Repair::Unwind(block) => Some(block),
+                _ => None,
+            })
+            .min()
+    }
+
+    /// Returns `true` if the state trie has to be rebuilt.
+    pub(crate) fn rebuild_trie(&self) -> bool {
+        self.repairs().any(|repair| repair == Repair::RebuildTrie)
+    }
+
+    /// Returns a table of the inconsistencies and their repairs.
+    pub(crate) fn table(&self) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Check", "Inconsistency", "Repair"]);
+        for inconsistency in &self.inconsistencies {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(inconsistency.check))
+                .add_cell(Cell::new(&inconsistency.description))
+                .add_cell(Cell::new(inconsistency.repair));
+            table.add_row(row);
+        }
+        table
+    }
+
+    fn repairs(&self) -> impl Iterator<Item = Repair> + '_ {
+        self.inconsistencies.iter().map(|inconsistency| inconsistency.repair)
+    }
+
+    fn push(&mut self, check: Check, description: String, repair: Repair) {
+        self.inconsistencies.push(Inconsistency { check, description, repair });
+    }
+
+    /// Checks that no stage is ahead of the stage before it, in the order of the pipeline.
+    fn check_checkpoints(&mut self, checkpoints: &[(StageId, BlockNumber)]) {
+        for ((previous, previous_block), (stage, block)) in
+            checkpoints.iter().zip(checkpoints.iter().skip(1))
+        {
+            if block > previous_block {
+                self.push(
+                    Check::Checkpoints,
+                    format!("{stage} checkpoint {block} is ahead of {previous} ({previous_block})"),
+                    Repair::Unwind(*previous_block),
+                );
+            }
+        }
+    }
+
+    /// Checks that the last block body indices are the ones of the Bodies checkpoint.
+    fn check_indices<TX: DbTx>(&mut self, provider: &DatabaseProvider<TX>) -> eyre::Result<()> {
+        let bodies = checkpoint(provider, StageId::Bodies)?;
+        let Some((_, last)) = key_range::<_, tables::BlockBodyIndices>(provider.tx_ref())? else {
+            return Ok(())
+        };
+        if last != bodies {
+            self.push(
+                Check::Indices,
+                format!(
+                    "last block body indices are of block {last}, Bodies checkpoint is {bodies}"
+                ),
+                Repair::Unwind(last.min(bodies)),
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks the static files like [`StaticFileProvider::check_consistency`], without healing
+    /// them.
+    fn check_static_files<TX: DbTx>(
+        &mut self,
+        static_file_provider: StaticFileProvider,
+        provider: &DatabaseProvider<TX>,
+        has_receipt_pruning: bool,
+    ) -> eyre::Result<()> {
+        for segment in [
+            StaticFileSegment::Headers,
+            StaticFileSegment::Transactions,
+            StaticFileSegment::Receipts,
+            StaticFileSegment::AccessLists,
+        ] {
+            if has_receipt_pruning && segment.is_receipts() {
+                // Pruned nodes (including full node) do not store receipts as static files.
+                continue
+            }
+
+            let Some(mut highest_block) =
+                static_file_provider.get_highest_static_file_block(segment)
+            else {
+                // Access lists are only stored as static files if they're collected during
+                // execution, and without static files there's no file to check.
+                if !segment.is_access_lists() {
+                    self.check_static_file_invariants(segment, provider, None, None)?;
+                }
+                continue
+            };
+
+            // With read-only access this fails instead of healing the file.
+            match static_file_provider.ensure_file_consistency(segment) {
+                Ok(()) => {}
+                Err(ProviderError::NippyJar(_)) => {
+                    self.push(
+                        Check::StaticFiles,
+                        format!("latest {segment} static file wasn't committed completely"),
+                        Repair::HealStaticFile(segment),
+                    );
+                    // the rows that are left are only known once the file is healed
+                    continue
+                }
+                Err(err) => return Err(err.into()),
+            }
+
+            // The last transaction of the static files has to be the last one of their last block.
+            let highest_tx = static_file_provider.get_highest_static_file_tx(segment);
+            if let Some(highest_tx) = highest_tx {
+                let mut last_block = highest_block;
+                while let Some(indices) = provider.block_body_indices(last_block)? {
+                    if indices.last_tx_num() <= highest_tx || last_block == 0 {
+                        break
+                    }
+                    last_block -= 1;
+                }
+                if last_block != highest_block {
+                    self.push(
+                        Check::StaticFiles,
+                        format!("{segment} static files end within block {highest_block}"),
+                        Repair::Unwind(last_block),
+                    );
+                    highest_block = last_block;
+                }
+            }
+
+            if segment.is_access_lists() {
+                let execution = checkpoint(provider, StageId::Execution)?;
+                if execution < highest_block {
+                    self.push(
+                        Check::StaticFiles,
+                        format!("{segment} static files are ahead of Execution ({execution})"),
+                        Repair::PruneStaticFile { segment, block: execution },
+                    );
+                }
+                continue
+            }
+
+            let highest_entry = if segment.is_headers() { Some(highest_block) } else { highest_tx };
+            self.check_static_file_invariants(
+                segment,
+                provider,
+                highest_entry,
+                Some(highest_block),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the static files of the segment continue in the database, and end at the
+    /// checkpoint of their stage if the database doesn't continue them.
+    fn check_static_file_invariants<TX: DbTx>(
+        &mut self,
+        segment: StaticFileSegment,
+        provider: &DatabaseProvider<TX>,
+        highest_entry: Option<u64>,
+        highest_block: Option<BlockNumber>,
+    ) -> eyre::Result<()> {
+        let highest_entry = highest_entry.unwrap_or_default();
+        let highest_block = highest_block.unwrap_or_default();
+        let tx = provider.tx_ref();
+        let table_range = match segment {
+            StaticFileSegment::Headers => key_range::<_, tables::Headers>(tx)?,
+            StaticFileSegment::Transactions => key_range::<_, tables::Transactions>(tx)?,
+            StaticFileSegment::Receipts => key_range::<_, tables::Receipts>(tx)?,
+            StaticFileSegment::AccessLists => return Ok(()),
+        };
+
+        if let Some((first, last)) = table_range {
+            if first > highest_entry + 1 {
+                self.push(
+                    Check::StaticFiles,
+                    format!("{segment} static files end before database entry {first}"),
+                    Repair::Unwind(highest_block),
+                );
+                return Ok(())
+            }
+            if last > highest_entry {
+                return Ok(())
+            }
+        }
+
+        let stage = match segment {
+            StaticFileSegment::Headers => StageId::Headers,
+            StaticFileSegment::Transactions => StageId::Bodies,
+            StaticFileSegment::Receipts | StaticFileSegment::AccessLists => StageId::Execution,
+        };
+        let checkpoint = checkpoint(provider, stage)?;
+        match checkpoint.cmp(&highest_block) {
+            Ordering::Greater => self.push(
+                Check::StaticFiles,
+                format!("{segment} static files are behind {stage} ({checkpoint})"),
+                Repair::Unwind(highest_block),
+            ),
+            Ordering::Less => self.push(
+                Check::StaticFiles,
+                format!("{segment} static files are ahead of {stage} ({checkpoint})"),
+                Repair::PruneStaticFile { segment, block: checkpoint },
+            ),
+            Ordering::Equal => {}
+        }
+
+        Ok(())
+    }
+
+    /// Checks the state root of the trie against the header of the `MerkleExecute` checkpoint, if
+    /// the trie is complete.
+    fn check_trie<TX: DbTx>(&mut self, provider: &DatabaseProvider<TX>) -> eyre::Result<()> {
+        let block = checkpoint(provider, StageId::MerkleExecute)?;
+
+        // The trie is only complete if the hashed state is at the same block and the merkle stage
+        // isn't in progress.
+        if checkpoint(provider, StageId::AccountHashing)? != block ||
+            checkpoint(provider, StageId::StorageHashing)? != block ||
+            provider
+                .get_stage_checkpoint_progress(StageId::MerkleExecute)?
+                .is_some_and(|progress| !progress.is_empty())
+        {
+            return Ok(())
+        }
+        let Some(header) = provider.header_by_number(block)? else { return Ok(()) };
+
+        // Without changes, only the stored branch nodes of the trie are hashed, which is cheap.
+        let state_root = StateRoot::from_tx(provider.tx_ref()).root()?;
+        if state_root != header.state_root {
+            self.push(
+                Check::Trie,
+                format!("trie root {state_root} differs from state root of block {block}"),
+                Repair::RebuildTrie,
+            );
+        }
+
+        Ok(())
+    }
+}
+
+/// Applies the repairs of the report to the storage.
+///
+/// The state trie is dropped first, so that it isn't unwound. The static files are then healed
+/// like on every start of the node, and all stages are unwound to the lowest target of the report
+/// and of the healing.
+pub(crate) fn apply_repairs<DB: Database + 'static>(
+    factory: ProviderFactory<DB>,
+    config: &Config,
+    has_receipt_pruning: bool,
+    report: &ConsistencyReport,
+) -> eyre::Result<()> {
+    if report.rebuild_trie() {
+        info!(target: "reth::cli", "Dropping the state trie");
+        let provider_rw = factory.provider_rw()?;
+        let tx = provider_rw.tx_ref();
+        tx.clear::<tables::AccountsTrie>()?;
+        tx.clear::<tables::StoragesTrie>()?;
+        tx.put::<tables::StageCheckpoints>(StageId::MerkleExecute.to_string(), Default::default())?;
+        tx.put::<tables::StageCheckpoints>(StageId::MerkleUnwind.to_string(), Default::default())?;
+        tx.delete::<tables::StageCheckpointProgresses>(StageId::MerkleExecute.to_string(), None)?;
+        provider_rw.commit()?;
+    }
+
+    info!(target: "reth::cli", "Healing static files");
+    let static_files_target = factory
+        .static_file_provider()
+        .check_consistency(&factory.provider()?, has_receipt_pruning)?
+        .and_then(|target| target.unwind_target());
+
+    let Some(unwind_target) = static_files_target.into_iter().chain(report.unwind_target()).min()
+    else {
+        return Ok(())
+    };
+    eyre::ensure!(
+        unwind_target > 0,
+        "the repairs would unwind to block 0, remove the data directory and sync again instead"
+    );
+
+    info!(target: "reth::cli", unwind_target, "Unwinding all stages");
+    let prune_modes = config.prune.clone().map(|prune| prune.segments).unwrap_or_default();
+    let (_tip_tx, tip_rx) = watch::channel(B256::ZERO);
+    let mut pipeline = Pipeline::builder()
+        .add_stages(DefaultStages::new(
+            factory.clone(),
+            tip_rx,
+            Arc::new(EthBeaconConsensus::new(factory.chain_spec())),
+            NoopHeaderDownloader::default(),
+            NoopBodiesDownloader::default(),
+            NoopBlockExecutorProvider::default(),
+            config.stages.clone(),
+            prune_modes.clone(),
+        ))
+        .build(factory.clone(), StaticFileProducer::new(factory, prune_modes));
+
+    // Move all applicable data from database to static files.
+    pipeline.move_to_static_files()?;
+    pipeline.unwind(unwind_target, None)?;
+
+    Ok(())
+}
+
+/// Returns `true` if the receipts are pruned, in which case they aren't stored as static files.
+pub(crate) fn has_receipt_pruning(node_config: &NodeConfig, config: &Config) -> bool {
+    config
+        .prune
+        .clone()
+        .or_else(|| node_config.prune_config())
+        .map_or(false, |prune| prune.has_receipts_pruning())
+}
+
+/// Returns the block number of the stage checkpoint, 0 if there's none.
+fn checkpoint<TX: DbTx>(
+    provider: &DatabaseProvider<TX>,
+    id: StageId,
+) -> Result<BlockNumber, ProviderError> {
+    Ok(provider.get_stage_checkpoint(id)?.unwrap_or_default().block_number)
+}
+
+/// Returns the first and last key of the table, if it isn't empty.
+fn key_range<TX: DbTx, T: Table<Key = u64>>(tx: &TX) -> eyre::Result<Option<(u64, u64)>> {
+    let mut cursor = tx.cursor_read::<T>()?;
+    let first = cursor.first()?.map(|(key, _)| key);
+    let last = cursor.last()?.map(|(key, _)| key);
+    Ok(first.zip(last))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_primitives::{constants::EMPTY_ROOT_HASH, Header};
+    use reth_provider::{test_utils::create_test_provider_factory, StageCheckpointWriter};
+    use reth_stages::StageCheckpoint;
+    use reth_trie::{BranchNodeCompact, StoredBranchNode};
+
+    fn save_checkpoints<DB: Database>(
+        factory: &ProviderFactory<DB>,
+        checkpoints: &[(StageId, u64)],
+    ) {
+        let provider_rw = factory.provider_rw().unwrap();
+        for (id, block) in checkpoints {
+            provider_rw.save_stage_checkpoint(*id, StageCheckpoint::new(*block)).unwrap();
+        }
+        provider_rw.commit().unwrap();
+    }
+
+    #[test]
+    fn checkpoints_ahead_of_previous_stage() {
+        let mut report = ConsistencyReport::default();
+        report.check_checkpoints(&[
+            (StageId::Headers, 100),
+            (StageId::Bodies, 90),
+            (StageId::SenderRecovery, 95),
+            (StageId::Execution, 80),
+            (StageId::AccountHashing, 85),
+        ]);
+
+        assert_eq!(report.repairs().collect::<Vec<_>>(), [Repair::Unwind(90), Repair::Unwind(80)]);
+        assert_eq!(report.unwind_target(), Some(80));
+        assert!(!report.rebuild_trie());
+
+        let mut report = ConsistencyReport::default();
+        report.check_checkpoints(&[(StageId::Headers, 100), (StageId::Bodies, 100)]);
+        assert!(report.is_consistent());
+    }
+
+    #[test]
+    fn indices_ahead_of_bodies_checkpoint() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 0..=5 {
+            provider_rw
+                .tx_ref()
+                .put::<tables::BlockBodyIndices>(block, StoredBlockBodyIndices::default())
+                .unwrap();
+        }
+        provider_rw.commit().unwrap();
+        save_checkpoints(&factory, &[(StageId::Bodies, 3)]);
+
+        let mut report = ConsistencyReport::default();
+        report.check_indices(&factory.provider().unwrap()).unwrap();
+        assert_eq!(report.repairs().collect::<Vec<_>>(), [Repair::Unwind(3)]);
+
+        save_checkpoints(&factory, &[(StageId::Bodies, 5)]);
+        let mut report = ConsistencyReport::default();
+        report.check_indices(&factory.provider().unwrap()).unwrap();
+        assert!(report.is_consistent());
+    }
+
+    #[test]
+    fn static_files_behind_checkpoint() {
+        let factory = create_test_provider_factory();
+        save_checkpoints(&factory, &[(StageId::Headers, 3)]);
+
+        let mut report = ConsistencyReport::default();
+        report
+            .check_static_files(factory.static_file_provider(), &factory.provider().unwrap(), false)
+            .unwrap();
+        assert_eq!(report.repairs().collect::<Vec<_>>(), [Repair::Unwind(0)]);
+        assert_eq!(report.inconsistencies[0].check, Check::StaticFiles);
+    }
+
+    #[test]
+    fn static_files_not_continued_by_database() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in 5..=6 {
+            provider_rw.tx_ref().put::<tables::Headers>(block, Header::default()).unwrap();
+        }
+        provider_rw.commit().unwrap();
+        save_checkpoints(&factory, &[(StageId::Headers, 6)]);
+
+        let mut report = ConsistencyReport::default();
+        report
+            .check_static_files(factory.static_file_provider(), &factory.provider().unwrap(), false)
+            .unwrap();
+        assert_eq!(report.repairs().collect::<Vec<_>>(), [Repair::Unwind(0)]);
+        assert!(report.inconsistencies[0].description.contains("database entry 5"));
+    }
+
+    #[test]
+    fn trie_root_differs_from_header() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        let header = Header { state_root: B256::with_last_byte(1), ..Default::default() };
+        provider_rw.tx_ref().put::<tables::Headers>(0, header).unwrap();
+        provider_rw.commit().unwrap();
+
+        let mut report = ConsistencyReport::default();
+        report.check_trie(&factory.provider().unwrap()).unwrap();
+        assert_eq!(report.repairs().collect::<Vec<_>>(), [Repair::RebuildTrie]);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let header = Header { state_root: EMPTY_ROOT_HASH, ..Default::default() };
+        provider_rw.tx_ref().put::<tables::Headers>(0, header).unwrap();
+        provider_rw.commit().unwrap();
+
+        let report = ConsistencyReport::new(&factory, false).unwrap();
+        assert!(report.is_consistent());
+    }
+
+    #[test]
+    fn apply_rebuild_trie() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::AccountsTrie>(
+                vec![0x5u8].into(),
+                StoredBranchNode(BranchNodeCompact::new(0b0110, 0b0100, 0, vec![], None)),
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+        save_checkpoints(&factory, &[(StageId::MerkleExecute, 10)]);
+
+        let report = ConsistencyReport {
+            inconsistencies: vec![Inconsistency {
+                check: Check::Trie,
+                description: String::new(),
+                repair: Repair::RebuildTrie,
+            }],
+        };
+        apply_repairs(factory.clone(), &Config::default(), false, &report).unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.tx_ref().entries::<tables::AccountsTrie>().unwrap(), 0);
+        assert_eq!(checkpoint(&provider, StageId::MerkleExecute).unwrap(), 0);
+    }
+
+    #[test]
+    fn refuse_unwind_to_genesis() {
+        let factory = create_test_provider_factory();
+        let report = ConsistencyReport {
+            inconsistencies: vec![Inconsistency {
+                check: Check::Indices,
+                description: String::new(),
+                repair: Repair::Unwind(0),
+            }],
+        };
+        assert!(apply_repairs(factory, &Config::default(), false, &report).is_err());
+    }
+}
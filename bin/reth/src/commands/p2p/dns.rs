@@ -0,0 +1,133 @@
+//! `reth p2p dns` command
+
+use crate::args::get_secret_key;
+use clap::{Parser, Subcommand};
+use discv5::enr::EnrKey;
+use reth_chainspec::ChainSpec;
+use reth_discv5::{enr::EnrCombinedKeyWrapper, NetworkStackId};
+use reth_dns_discovery::{tree::LinkEntry, DnsTree};
+use reth_fs_util as fs;
+use reth_network::NetworkHandle;
+use secp256k1::SecretKey;
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::info;
+
+/// `reth p2p dns` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth p2p dns` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Build and sign an EIP-1459 DNS tree from the peers found by the node's discv5 service
+    Publish(PublishCommand),
+}
+
+/// Arguments for the `reth p2p dns publish` command
+#[derive(Debug, Parser)]
+pub struct PublishCommand {
+    /// The domain the tree is published at.
+    #[arg(long)]
+    domain: String,
+
+    /// Secret key to sign the tree with. Defaults to the node's p2p secret key.
+    #[arg(long, value_name = "PATH")]
+    signing_key: Option<PathBuf>,
+
+    /// Sequence number of the tree. Defaults to the current unix timestamp, so that every
+    /// publish supersedes the previous one.
+    #[arg(long)]
+    seq: Option<u64>,
+
+    /// Link to another tree to include, e.g. `enrtree://<key>@<domain>`. Can be repeated.
+    #[arg(long = "link", value_name = "ENRTREE")]
+    links: Vec<LinkEntry>,
+
+    /// How long to run the network's discv5 discovery for, in seconds, to collect node records.
+    #[arg(long, value_name = "SECONDS", default_value_t = 60)]
+    crawl_duration: u64,
+
+    /// Maximum number of node records to include in the tree.
+    #[arg(long, default_value_t = 200)]
+    max_nodes: usize,
+
+    /// File to write the TXT records of the tree to, as JSON. Printed to stdout if not set.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+impl Command {
+    /// Execute `p2p dns` command
+    pub async fn execute(
+        &self,
+        chain: &ChainSpec,
+        network: &NetworkHandle,
+        p2p_secret_key: SecretKey,
+    ) -> eyre::Result<()> {
+        match &self.command {
+            Subcommands::Publish(command) => command.execute(chain, network, p2p_secret_key).await,
+        }
+    }
+}
+
+impl PublishCommand {
+    /// Execute `p2p dns publish` command
+    pub async fn execute(
+        &self,
+        chain: &ChainSpec,
+        network: &NetworkHandle,
+        p2p_secret_key: SecretKey,
+    ) -> eyre::Result<()> {
+        let signing_key = match &self.signing_key {
+            Some(path) => get_secret_key(path)?,
+            None => p2p_secret_key,
+        };
+
+        let Some(discv5) = network.discv5() else {
+            eyre::bail!("DNS trees are built from discv5 peers, enable discv5 with `--enable-discv5-discovery`")
+        };
+
+        // the network's discv5 service fills its kbuckets while the network is running
+        info!(target: "reth::cli", crawl_duration = self.crawl_duration, "Collecting node records");
+        tokio::time::sleep(Duration::from_secs(self.crawl_duration)).await;
+
+        let network_stack_id = NetworkStackId::id(chain);
+        let latest_fork_id = chain.latest_fork_id();
+        let nodes = discv5
+            .with_discv5(|discv5| discv5.table_entries_enr())
+            .into_iter()
+            .filter(|enr| discv5.filter_discovered_peer(enr).is_ok())
+            // only publish peers that are on the chain's latest fork
+            .filter(|enr| {
+                network_stack_id.is_none() ||
+                    discv5.get_fork_id(enr).is_ok_and(|fork_id| fork_id == latest_fork_id)
+            })
+            .take(self.max_nodes)
+            .map(|enr| EnrCombinedKeyWrapper(enr).into())
+            .collect::<Vec<_>>();
+        info!(target: "reth::cli", nodes = nodes.len(), "Building DNS tree");
+
+        let seq = match self.seq {
+            Some(seq) => seq,
+            None => SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+        };
+        let tree = DnsTree::new(nodes, self.links.clone(), seq, &signing_key)?;
+        let records = serde_json::to_string_pretty(&tree.txt_records(&self.domain))?;
+
+        let link = LinkEntry { domain: self.domain.clone(), pubkey: signing_key.public() };
+        info!(target: "reth::cli", %link, seq, "Built DNS tree");
+
+        match &self.output {
+            Some(path) => fs::write(path, records)?,
+            None => println!("{records}"),
+        }
+
+        Ok(())
+    }
+}
@@ -25,6 +25,8 @@ use std::{
     sync::Arc,
 };
 
+mod dns;
+
 /// `reth p2p` command
 #[derive(Debug, Parser)]
 pub struct Command {
@@ -76,15 +78,18 @@ pub enum Subcommands {
         #[arg(value_parser = hash_or_num_value_parser)]
         id: BlockHashOrNumber,
     },
+    /// EIP-1459 DNS discovery tree commands
+    Dns(dns::Command),
 }
 impl Command {
     /// Execute `p2p` command
     pub async fn execute(&self) -> eyre::Result<()> {
+        // add network name to data dir
+        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain);
+
         let tempdir = tempfile::TempDir::new()?;
         let noop_db = Arc::new(create_db(tempdir.into_path(), self.db.database_args())?);
 
-        // add network name to data dir
-        let data_dir = self.datadir.clone().resolve_datadir(self.chain.chain);
         let config_path = self.config.clone().unwrap_or_else(|| data_dir.config());
 
         let mut config: Config = confy::load_path(&config_path).unwrap_or_default();
@@ -207,6 +212,9 @@ impl Command {
                 let body = result.into_iter().next().unwrap();
                 println!("Successfully downloaded body: {body:?}")
             }
+            Subcommands::Dns(ref command) => {
+                command.execute(&self.chain, &network, p2p_secret_key).await?
+            }
         }
 
         Ok(())
@@ -0,0 +1,29 @@
+//! `reth space` command.
+
+use clap::{Parser, Subcommand};
+
+mod report;
+
+/// `reth space` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+/// `reth space` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands {
+    /// Breaks down the disk usage of the data directory by table, static file segment and prune
+    /// segment, projects its growth and suggests configuration changes that reclaim space.
+    Report(report::Command),
+}
+
+impl Command {
+    /// Execute `space` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Report(command) => command.execute().await,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_node_events::space::{SpaceReport, SpaceUsage};
+use reth_provider::BlockNumReader;
+use std::fmt::Display;
+
+/// `reth space report` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// Number of blocks to project the growth of the data directory over.
+    ///
+    /// The default of 216000 blocks is roughly 30 days on mainnet.
+    #[arg(long, default_value_t = 216_000)]
+    blocks: u64,
+}
+
+impl Command {
+    /// Execute `space report` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { config, provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        // Only the prune configuration of the config file is known here, the one derived from the
+        // `--full` flag of the node isn't
+        let prune_modes = config.prune.map(|prune| prune.segments).unwrap_or_default();
+        let tip = provider_factory.best_block_number()?;
+        let report = SpaceReport::new(&provider_factory, prune_modes, tip)?;
+        let total_size = report.total_size();
+
+        let mut tables = report.tables.clone();
+        tables.sort_by_key(|usage| std::cmp::Reverse(usage.size));
+        let mut table = self.usage_table("Table", &tables, total_size);
+        add_total_row(&mut table, "Freelist", report.freelist, total_size, "");
+        add_total_row(
+            &mut table,
+            "Database",
+            report.database_size(),
+            total_size,
+            &human_bytes(self.projected_growth(&tables) as f64),
+        );
+        println!("{table}\n");
+
+        let mut table = self.usage_table("Static File Segment", &report.static_files, total_size);
+        add_total_row(
+            &mut table,
+            "Static Files",
+            report.static_files_size(),
+            total_size,
+            &human_bytes(self.projected_growth(&report.static_files) as f64),
+        );
+        println!("{table}\n");
+
+        let prune_segments = report
+            .prune_segments
+            .iter()
+            .map(|usage| SpaceUsage {
+                part: match report.prune_mode(usage.part) {
+                    Some(mode) => format!("{} ({mode:?})", usage.part),
+                    None => format!("{} (not pruned)", usage.part),
+                },
+                size: usage.size,
+                growth_per_block: usage.growth_per_block,
+            })
+            .collect::<Vec<_>>();
+        println!("{}\n", self.usage_table("Prune Segment", &prune_segments, total_size));
+
+        println!(
+            "Total size at block {tip}: {}, projected growth over {} blocks: {}",
+            human_bytes(total_size as f64),
+            self.blocks,
+            human_bytes(report.projected_growth(self.blocks) as f64)
+        );
+
+        let suggestions = report.suggestions();
+        if !suggestions.is_empty() {
+            println!("\nSuggestions:");
+            for suggestion in suggestions {
+                println!("- {suggestion}");
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns a table with the size, the share of the total size and the growth of every part of
+    /// the data directory.
+    fn usage_table<T: Display>(
+        &self,
+        name: &str,
+        usages: &[SpaceUsage<T>],
+        total_size: u64,
+    ) -> ComfyTable {
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header([
+            name,
+            "Size",
+            "Share",
+            "Growth per Block",
+            format!("Growth over {} Blocks", self.blocks).as_str(),
+        ]);
+
+        for usage in usages {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(&usage.part))
+                .add_cell(Cell::new(human_bytes(usage.size as f64)))
+                .add_cell(Cell::new(share(usage.size, total_size)))
+                .add_cell(Cell::new(human_bytes(usage.growth_per_block as f64)))
+                .add_cell(Cell::new(human_bytes((usage.growth_per_block * self.blocks) as f64)));
+            table.add_row(row);
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
+
+        table
+    }
+
+    /// Returns the projected growth of the given parts of the data directory, in bytes.
+    fn projected_growth<T>(&self, usages: &[SpaceUsage<T>]) -> u64 {
+        usages.iter().map(|usage| usage.growth_per_block * self.blocks).sum()
+    }
+}
+
+fn add_total_row(table: &mut ComfyTable, name: &str, size: u64, total_size: u64, growth: &str) {
+    let mut row = Row::new();
+    row.add_cell(Cell::new(name))
+        .add_cell(Cell::new(human_bytes(size as f64)))
+        .add_cell(Cell::new(share(size, total_size)))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(growth));
+    table.add_row(row);
+}
+
+/// Formats the share of the size in the total size as a percentage.
+fn share(size: u64, total_size: u64) -> String {
+    format!("{:.2}%", size as f64 * 100.0 / total_size.max(1) as f64)
+}
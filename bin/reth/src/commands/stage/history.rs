@@ -0,0 +1,190 @@
+//! `reth stage history` command
+
+use crate::commands::common::{AccessRights, Environment, EnvironmentArgs};
+use clap::Parser;
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_db::tables;
+use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+use reth_stages::StageRun;
+use std::{
+    collections::HashMap,
+    time::{Duration, UNIX_EPOCH},
+};
+
+/// The number of runs that are listed if no limit is set, unless printing JSON.
+const DEFAULT_LIMIT: usize = 50;
+
+/// `reth stage history` command
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(flatten)]
+    env: EnvironmentArgs,
+
+    /// Only show the runs of the stage with the given ID, e.g. `Execution`.
+    #[arg(long)]
+    stage: Option<String>,
+
+    /// The number of most recent runs to list.
+    ///
+    /// Defaults to 50 runs, or all runs with `--json`.
+    #[arg(long)]
+    limit: Option<usize>,
+
+    /// Print the runs as JSON, one run per line, instead of tables.
+    #[arg(long)]
+    json: bool,
+}
+
+impl Command {
+    /// Execute `stage history` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init(AccessRights::RO)?;
+
+        let provider = provider_factory.provider()?;
+        let mut runs = provider
+            .tx_ref()
+            .cursor_read::<tables::StageRuns>()?
+            .walk(None)?
+            .collect::<Result<Vec<_>, _>>()?;
+        if let Some(stage) = &self.stage {
+            runs.retain(|(_, run)| run.stage.eq_ignore_ascii_case(stage));
+        }
+
+        let limit = self.limit.unwrap_or(if self.json { usize::MAX } else { DEFAULT_LIMIT });
+        let listed = &runs[runs.len().saturating_sub(limit)..];
+
+        if self.json {
+            for (_, run) in listed {
+                println!("{}", serde_json::to_string(run)?);
+            }
+            return Ok(())
+        }
+
+        if runs.is_empty() {
+            println!("No stage runs were recorded.");
+            return Ok(())
+        }
+
+        println!("{}\n", runs_table(listed));
+        println!("{}\n", summary_table(&runs));
+
+        let (_, first) = &runs[0];
+        let (_, last) = &runs[runs.len() - 1];
+        println!(
+            "{} runs between {} and {}, {} of which spent running stages",
+            runs.len(),
+            timestamp(first.started_at),
+            timestamp(last.started_at + last.duration_ms),
+            duration(runs.iter().map(|(_, run)| run.duration_ms).sum()),
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns a table of the given runs.
+fn runs_table(runs: &[(u64, StageRun)]) -> ComfyTable {
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Run", "Stage", "Kind", "From", "To", "Started", "Duration", "Entities", "Read", "Written",
+    ]);
+
+    for (number, run) in runs {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(number))
+            .add_cell(Cell::new(&run.stage))
+            .add_cell(Cell::new(if run.unwind { "Unwind" } else { "Execute" }))
+            .add_cell(Cell::new(run.from_block))
+            .add_cell(Cell::new(run.to_block))
+            .add_cell(Cell::new(timestamp(run.started_at)))
+            .add_cell(Cell::new(duration(run.duration_ms)))
+            .add_cell(Cell::new(optional(run.entities_processed, |entities| entities.to_string())))
+            .add_cell(Cell::new(optional(run.read_bytes, bytes)))
+            .add_cell(Cell::new(optional(run.write_bytes, bytes)));
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// Returns a table of the totals of the runs of every stage, sorted by the time spent running the
+/// stage.
+fn summary_table(runs: &[(u64, StageRun)]) -> ComfyTable {
+    let mut totals = HashMap::<_, StageTotals>::new();
+    for (_, run) in runs {
+        let totals = totals.entry(run.stage.as_str()).or_default();
+        totals.runs += 1;
+        totals.unwinds += run.unwind as u64;
+        totals.blocks += run.blocks();
+        totals.duration_ms += run.duration_ms;
+        totals.entities += run.entities_processed.unwrap_or_default();
+        totals.read_bytes += run.read_bytes.unwrap_or_default();
+        totals.write_bytes += run.write_bytes.unwrap_or_default();
+    }
+    let total_duration_ms = totals.values().map(|totals| totals.duration_ms).sum::<u64>();
+    let mut totals = totals.into_iter().collect::<Vec<_>>();
+    totals.sort_by_key(|(_, totals)| std::cmp::Reverse(totals.duration_ms));
+
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header([
+        "Stage", "Runs", "Unwinds", "Blocks", "Duration", "Share", "Entities", "Read", "Written",
+    ]);
+
+    for (stage, totals) in totals {
+        let share = if total_duration_ms == 0 {
+            0.0
+        } else {
+            totals.duration_ms as f64 / total_duration_ms as f64 * 100.0
+        };
+        let mut row = Row::new();
+        row.add_cell(Cell::new(stage))
+            .add_cell(Cell::new(totals.runs))
+            .add_cell(Cell::new(totals.unwinds))
+            .add_cell(Cell::new(totals.blocks))
+            .add_cell(Cell::new(duration(totals.duration_ms)))
+            .add_cell(Cell::new(format!("{share:.2}%")))
+            .add_cell(Cell::new(totals.entities))
+            .add_cell(Cell::new(bytes(totals.read_bytes)))
+            .add_cell(Cell::new(bytes(totals.write_bytes)));
+        table.add_row(row);
+    }
+
+    table
+}
+
+/// The totals of the runs of a stage.
+#[derive(Debug, Default)]
+struct StageTotals {
+    runs: u64,
+    unwinds: u64,
+    blocks: u64,
+    duration_ms: u64,
+    entities: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+}
+
+fn timestamp(unix_millis: u64) -> String {
+    humantime::format_rfc3339_seconds(UNIX_EPOCH + Duration::from_millis(unix_millis)).to_string()
+}
+
+/// Formats a duration in milliseconds, with millisecond precision below one second.
+fn duration(millis: u64) -> String {
+    let duration = if millis < 1000 {
+        Duration::from_millis(millis)
+    } else {
+        Duration::from_secs(millis / 1000)
+    };
+    humantime::format_duration(duration).to_string()
+}
+
+fn bytes(bytes: u64) -> String {
+    human_bytes(bytes as f64)
+}
+
+fn optional(value: Option<u64>, f: impl FnOnce(u64) -> String) -> String {
+    value.map(f).unwrap_or_else(|| "-".to_string())
+}
@@ -5,6 +5,7 @@ use reth_cli_runner::CliContext;
 
 pub mod drop;
 pub mod dump;
+pub mod history;
 pub mod run;
 pub mod unwind;
 
@@ -31,6 +32,8 @@ pub enum Subcommands {
     Dump(dump::Command),
     /// Unwinds a certain block range, deleting it from the database.
     Unwind(unwind::Command),
+    /// Shows the runs of the stages by the pipeline, and the time and resources every stage took.
+    History(history::Command),
 }
 
 impl Command {
@@ -41,6 +44,7 @@ impl Command {
             Subcommands::Drop(command) => command.execute().await,
             Subcommands::Dump(command) => command.execute().await,
             Subcommands::Unwind(command) => command.execute().await,
+            Subcommands::History(command) => command.execute().await,
         }
     }
 }
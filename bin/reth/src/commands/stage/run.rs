@@ -100,6 +100,7 @@ impl Command {
                 provider_factory.db_ref().clone(),
                 provider_factory.static_file_provider(),
                 metrics_process::Collector::default(),
+                Vec::new(),
                 ctx.task_executor,
             )
             .await?;
@@ -76,13 +76,18 @@ impl Command {
                 .take_block_and_execution_range(range.clone())
                 .map_err(|err| eyre::eyre!("Transaction error on unwind: {err}"))?;
 
-            // update finalized block if needed
-            let last_saved_finalized_block_number = provider.last_finalized_block_number()?;
+            // update finalized and safe blocks if needed
             let range_min =
                 range.clone().min().ok_or(eyre::eyre!("Could not fetch lower range end"))?;
-            if range_min < last_saved_finalized_block_number {
+            if provider
+                .last_finalized_block_number()?
+                .is_some_and(|finalized| range_min < finalized)
+            {
                 provider.save_finalized_block_number(BlockNumber::from(range_min))?;
             }
+            if provider.last_safe_block_number()?.is_some_and(|safe| range_min < safe) {
+                provider.save_safe_block_number(BlockNumber::from(range_min))?;
+            }
 
             provider.commit()?;
         }
@@ -20,10 +20,30 @@ fn main() {
         std::env::set_var("RUST_BACKTRACE", "1");
     }
 
-    if let Err(err) = Cli::parse_args().run(|builder, _| async {
+    #[cfg(not(feature = "wasm-exex"))]
+    let result = Cli::parse_args().run(|builder, _| async {
         let handle = builder.launch_node(EthereumNode::default()).await?;
         handle.node_exit_future.await
-    }) {
+    });
+
+    // installs an `ExEx` for every module passed with `--exex.wasm.module`
+    #[cfg(feature = "wasm-exex")]
+    let result = {
+        use clap::Parser;
+        use reth_exex_wasm::WasmExExArgs;
+
+        Cli::<WasmExExArgs>::parse().run(|builder, args| async move {
+            let mut builder = builder.node(EthereumNode::default());
+            for module in args.modules() {
+                builder =
+                    builder.install_exex(module.name().to_string(), move |ctx| module.init(ctx));
+            }
+            let handle = builder.launch().await?;
+            handle.node_exit_future.await
+        })
+    };
+
+    if let Err(err) = result {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
@@ -3,7 +3,7 @@
 use boyer_moore_magiclen::BMByte;
 use eyre::Result;
 use reth_chainspec::ChainSpec;
-use reth_db::{RawTable, TableRawRow};
+use reth_db::{RawKey, RawTable, TableRawRow};
 use reth_db_api::{
     cursor::{DbCursorRO, DbDupCursorRO},
     database::Database,
@@ -76,6 +76,9 @@ impl<DB: Database> DbTool<DB> {
                     if value.len() < filter.min_value_size {
                         return None
                     }
+                    if !value.starts_with(&filter.value_prefix) {
+                        return None
+                    }
 
                     let result = || {
                         if filter.only_count {
@@ -105,16 +108,31 @@ impl<DB: Database> DbTool<DB> {
                 None
             };
 
+            // errors are passed through to `map_filter`
+            let has_key_prefix = |row: &Result<TableRawRow<T>, _>| {
+                row.as_ref().map_or(true, |(k, _)| filter.has_key_prefix(k.raw_key()))
+            };
+
             if filter.reverse {
+                // start right after the last key with the prefix, or at the end of the table
+                let start = match filter.key_prefix_upper_bound().map(RawKey::from_vec) {
+                    Some(bound) if cursor.seek(bound.clone())?.is_some() => Some(bound),
+                    _ => None,
+                };
                 Ok(cursor
-                    .walk_back(None)?
+                    .walk_back(start)?
+                    .skip_while(|row| !has_key_prefix(row))
+                    .take_while(has_key_prefix)
                     .skip(filter.skip)
                     .filter_map(map_filter)
                     .take(filter.len)
                     .collect::<Vec<(_, _)>>())
             } else {
+                let start = (!filter.key_prefix.is_empty())
+                    .then(|| RawKey::from_vec(filter.key_prefix.clone()));
                 Ok(cursor
-                    .walk(None)?
+                    .walk(start)?
+                    .take_while(has_key_prefix)
                     .skip(filter.skip)
                     .filter_map(map_filter)
                     .take(filter.len)
@@ -138,6 +156,21 @@ impl<DB: Database> DbTool<DB> {
             .map_err(|e| eyre::eyre!(e))
     }
 
+    /// Grabs all values of the `DupSort` table for the given key
+    pub fn get_dups<T: DupSort>(&self, key: T::Key) -> Result<Vec<T::Value>> {
+        self.provider_factory
+            .db_ref()
+            .view(|tx| {
+                let mut cursor = tx.cursor_dup_read::<T>()?;
+                let values = cursor
+                    .walk_dup(Some(key), None)?
+                    .map(|row| row.map(|(_, value)| value))
+                    .collect::<Result<Vec<_>, _>>();
+                values
+            })?
+            .map_err(|e| eyre::eyre!(e))
+    }
+
     /// Drops the database and the static files at the given path.
     pub fn drop(
         &self,
@@ -182,6 +215,11 @@ pub struct ListFilter {
     pub reverse: bool,
     /// Only counts the number of filtered entries without decoding and returning them.
     pub only_count: bool,
+    /// Only return entries whose raw key starts with this prefix.
+    pub key_prefix: Vec<u8>,
+    /// Only return entries whose raw value starts with this prefix. For `DupSort` tables, this is
+    /// the encoded subkey.
+    pub value_prefix: Vec<u8>,
 }
 
 impl ListFilter {
@@ -195,4 +233,22 @@ impl ListFilter {
         self.skip = skip;
         self.len = len;
     }
+
+    /// Returns `true` if the raw key starts with the key prefix.
+    pub fn has_key_prefix(&self, key: &[u8]) -> bool {
+        key.starts_with(&self.key_prefix)
+    }
+
+    /// Returns the smallest key that is greater than all keys starting with the key prefix, if
+    /// any.
+    fn key_prefix_upper_bound(&self) -> Option<Vec<u8>> {
+        let mut bound = self.key_prefix.clone();
+        while let Some(last) = bound.pop() {
+            if last < u8::MAX {
+                bound.push(last + 1);
+                return Some(bound)
+            }
+        }
+        None
+    }
 }
@@ -70,6 +70,17 @@ pub enum CanonicalError {
     /// Error indicating that a previous optimistic sync target was re-orged
     #[error("transaction error on revert: {0}")]
     OptimisticTargetRevert(BlockNumber),
+    /// Error indicating that making the block canonical would revert more canonical blocks than
+    /// allowed without a confirmation, see [`ReorgGuard`](crate::ReorgGuard).
+    #[error("unconfirmed reorg to {head} reverts {depth} blocks, more than {max_depth}")]
+    ReorgNotConfirmed {
+        /// The hash of the new head.
+        head: BlockHash,
+        /// The number of canonical blocks that would be reverted.
+        depth: u64,
+        /// The maximum number of canonical blocks that can be reverted without a confirmation.
+        max_depth: u64,
+    },
 }
 
 impl CanonicalError {
@@ -84,6 +95,11 @@ impl CanonicalError {
         matches!(self, Self::BlockchainTree(BlockchainTreeError::BlockHashNotFoundInChain { .. }))
     }
 
+    /// Returns `true` if the error is a [`CanonicalError::ReorgNotConfirmed`].
+    pub const fn is_reorg_not_confirmed(&self) -> bool {
+        matches!(self, Self::ReorgNotConfirmed { .. })
+    }
+
     /// Returns `Some(BlockNumber)` if the underlying error matches
     /// [`CanonicalError::OptimisticTargetRevert`].
     pub const fn optimistic_revert_block_number(&self) -> Option<BlockNumber> {
@@ -323,6 +339,7 @@ impl InsertBlockErrorKind {
                 CanonicalError::CanonicalCommit(_) |
                 CanonicalError::CanonicalRevert(_) |
                 CanonicalError::OptimisticTargetRevert(_) |
+                CanonicalError::ReorgNotConfirmed { .. } |
                 CanonicalError::Provider(_) => false,
                 CanonicalError::Validation(_) => true,
             },
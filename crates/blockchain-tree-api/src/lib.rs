@@ -10,14 +10,17 @@
 use self::error::CanonicalError;
 use crate::error::InsertBlockError;
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader,
+    BlockHash, BlockNumHash, BlockNumber, Receipt, ReceiptEncoding, SealedBlock,
+    SealedBlockWithSenders, SealedHeader,
 };
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
 pub mod error;
 
+mod reorg_guard;
+pub use reorg_guard::{PendingReorg, ReorgGuard};
+
 /// * [`BlockchainTreeEngine::insert_block`]: Connect block to chain, execute it and if valid insert
 ///   block inside tree.
 /// * [`BlockchainTreeEngine::finalize_block`]: Remove chains that join to now finalized block, as
@@ -336,6 +339,12 @@ pub trait BlockchainTreeViewer: Send + Sync {
     /// Returns the pending receipts if there is one.
     fn receipts_by_block_hash(&self, block_hash: BlockHash) -> Option<Vec<Receipt>>;
 
+    /// Returns the bloom filters and encodings of the receipts of a recently validated block, one
+    /// per receipt, if they're cached.
+    ///
+    /// The block may have become canonical since it was validated.
+    fn receipt_encodings(&self, block_hash: BlockHash) -> Option<Arc<[ReceiptEncoding]>>;
+
     /// Returns the pending block if there is one.
     fn pending_header(&self) -> Option<SealedHeader> {
         self.header_by_hash(self.pending_block_num_hash()?.hash)
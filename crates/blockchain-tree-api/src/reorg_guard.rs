@@ -0,0 +1,112 @@
+//! Guard against deep reorgs of the canonical chain.
+
+use crate::error::CanonicalError;
+use reth_primitives::BlockHash;
+use std::sync::{Arc, Mutex};
+
+/// A reorg that is waiting for a confirmation, see [`ReorgGuard`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PendingReorg {
+    /// The hash of the new head.
+    pub head: BlockHash,
+    /// The number of canonical blocks that would be reverted.
+    pub depth: u64,
+}
+
+/// Guards the canonical chain against reorgs that revert more than a maximum number of blocks.
+///
+/// Such a reorg is rejected with [`CanonicalError::ReorgNotConfirmed`] and becomes pending, until
+/// an operator confirms it with [`ReorgGuard::confirm`]. The next attempt to make the confirmed
+/// head canonical is then allowed.
+///
+/// The guard is cheap to clone, all clones share the same state.
+#[derive(Debug, Clone)]
+pub struct ReorgGuard {
+    inner: Arc<ReorgGuardInner>,
+}
+
+impl ReorgGuard {
+    /// Creates a new guard that rejects unconfirmed reorgs deeper than `max_depth` blocks.
+    pub fn new(max_depth: u64) -> Self {
+        Self {
+            inner: Arc::new(ReorgGuardInner { max_depth, state: Mutex::new(Default::default()) }),
+        }
+    }
+
+    /// Returns the maximum number of canonical blocks that can be reverted without a confirmation.
+    pub fn max_depth(&self) -> u64 {
+        self.inner.max_depth
+    }
+
+    /// Returns the reorg that is waiting for a confirmation, if any.
+    pub fn pending(&self) -> Option<PendingReorg> {
+        self.inner.state.lock().unwrap().pending
+    }
+
+    /// Confirms the pending reorg to the given head.
+    ///
+    /// Returns `false` if there is no pending reorg to the given head.
+    pub fn confirm(&self, head: BlockHash) -> bool {
+        let mut state = self.inner.state.lock().unwrap();
+        if state.pending.map(|pending| pending.head) != Some(head) {
+            return false
+        }
+        state.pending = None;
+        state.confirmed = Some(head);
+        true
+    }
+
+    /// Checks whether a reorg to the given head that reverts `depth` canonical blocks is allowed.
+    ///
+    /// If the reorg is too deep and not confirmed, it becomes the pending reorg.
+    pub fn check(&self, head: BlockHash, depth: u64) -> Result<(), CanonicalError> {
+        if depth <= self.inner.max_depth {
+            return Ok(())
+        }
+        let mut state = self.inner.state.lock().unwrap();
+        if state.confirmed == Some(head) {
+            state.confirmed = None;
+            return Ok(())
+        }
+        state.pending = Some(PendingReorg { head, depth });
+        Err(CanonicalError::ReorgNotConfirmed { head, depth, max_depth: self.inner.max_depth })
+    }
+}
+
+#[derive(Debug)]
+struct ReorgGuardInner {
+    max_depth: u64,
+    state: Mutex<ReorgGuardState>,
+}
+
+#[derive(Debug, Default)]
+struct ReorgGuardState {
+    /// The last rejected reorg.
+    pending: Option<PendingReorg>,
+    /// The head of the last confirmed reorg, that is allowed once.
+    confirmed: Option<BlockHash>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirm_deep_reorg() {
+        let guard = ReorgGuard::new(2);
+        let head = BlockHash::with_last_byte(1);
+
+        assert!(guard.check(head, 2).is_ok());
+        assert!(!guard.confirm(head));
+
+        assert!(guard.check(head, 3).unwrap_err().is_reorg_not_confirmed());
+        assert_eq!(guard.pending(), Some(PendingReorg { head, depth: 3 }));
+        assert!(!guard.confirm(BlockHash::with_last_byte(2)));
+        assert!(guard.confirm(head));
+        assert_eq!(guard.pending(), None);
+
+        // the confirmation is used only once
+        assert!(guard.check(head, 3).is_ok());
+        assert!(guard.check(head, 3).is_err());
+    }
+}
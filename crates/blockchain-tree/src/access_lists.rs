@@ -0,0 +1,63 @@
+//! Access lists of executed blocks that aren't committed to the database yet.
+//!
+//! If the executor collects access lists, the access lists of the blocks validated by the tree are
+//! kept until the blocks are committed to the database, at which point they're appended to the
+//! static files. The access lists of reverted canonical blocks are kept as well, so that they're
+//! written again if the blocks become canonical again.
+
+use parking_lot::Mutex;
+use reth_primitives::{AccessList, BlockHash, BlockNumber};
+use std::{collections::HashMap, fmt};
+
+/// The access lists of the executed blocks that aren't committed yet, by block hash.
+#[derive(Default)]
+pub(crate) struct PendingAccessLists {
+    blocks: Mutex<HashMap<BlockHash, (BlockNumber, Vec<AccessList>)>>,
+}
+
+impl PendingAccessLists {
+    /// Keeps the access lists of the executed block until it's committed.
+    pub(crate) fn insert(
+        &self,
+        block_hash: BlockHash,
+        block_number: BlockNumber,
+        access_lists: Vec<AccessList>,
+    ) {
+        self.blocks.lock().insert(block_hash, (block_number, access_lists));
+    }
+
+    /// Takes the access lists of the block, if they're kept.
+    pub(crate) fn take(&self, block_hash: &BlockHash) -> Option<Vec<AccessList>> {
+        self.blocks.lock().remove(block_hash).map(|(_, access_lists)| access_lists)
+    }
+
+    /// Discards the access lists of the blocks at or below the finalized block, they can't become
+    /// canonical anymore.
+    pub(crate) fn finalize(&self, finalized_block: BlockNumber) {
+        self.blocks.lock().retain(|_, (number, _)| *number > finalized_block);
+    }
+}
+
+impl fmt::Debug for PendingAccessLists {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingAccessLists").field("blocks", &self.blocks.lock().len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discards_finalized_blocks() {
+        let pending = PendingAccessLists::default();
+        for number in 1..=3 {
+            pending.insert(BlockHash::with_last_byte(number as u8), number, vec![]);
+        }
+
+        pending.finalize(2);
+        assert!(pending.take(&BlockHash::with_last_byte(2)).is_none());
+        assert_eq!(pending.take(&BlockHash::with_last_byte(3)), Some(vec![]));
+        assert!(pending.take(&BlockHash::with_last_byte(3)).is_none());
+    }
+}
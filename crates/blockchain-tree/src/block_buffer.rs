@@ -1,8 +1,13 @@
 use crate::metrics::BlockBufferMetrics;
 use reth_network::cache::LruCache;
-use reth_primitives::{BlockHash, BlockNumber, SealedBlockWithSenders};
+use reth_primitives::{Address, BlockHash, BlockNumber, SealedBlockWithSenders};
 use std::collections::{btree_map, hash_map, BTreeMap, HashMap, HashSet};
 
+/// Returns the estimated in-memory size of the block and its senders in bytes.
+pub(crate) fn estimated_block_size(block: &SealedBlockWithSenders) -> usize {
+    block.block.size() + block.senders.len() * std::mem::size_of::<Address>()
+}
+
 /// Contains the tree of pending blocks that cannot be executed due to missing parent.
 /// It allows to store unconnected blocks for potential future inclusion.
 ///
@@ -12,8 +17,8 @@ use std::collections::{btree_map, hash_map, BTreeMap, HashMap, HashSet};
 ///   and inserted.
 /// * [`BlockBuffer::remove_old_blocks`] to remove old blocks that precede the finalized number.
 ///
-/// Note: Buffer is limited by number of blocks that it can contain and eviction of the block
-/// is done by last recently used block.
+/// Note: Buffer is limited by number of blocks that it can contain and, optionally, by their
+/// estimated size in bytes. Eviction of the block is done by last recently used block.
 #[derive(Debug)]
 pub struct BlockBuffer {
     /// All blocks in the buffer stored by their block hash.
@@ -30,6 +35,10 @@ pub struct BlockBuffer {
     ///
     /// Used as counter of amount of blocks inside buffer.
     pub(crate) lru: LruCache<BlockHash>,
+    /// Estimated size of all blocks in the buffer in bytes.
+    size: usize,
+    /// Maximum estimated size of all blocks in the buffer in bytes, if limited.
+    max_bytes: Option<usize>,
     /// Various metrics for the block buffer.
     pub(crate) metrics: BlockBufferMetrics,
 }
@@ -42,10 +51,26 @@ impl BlockBuffer {
             parent_to_child: Default::default(),
             earliest_blocks: Default::default(),
             lru: LruCache::new(limit),
+            size: 0,
+            max_bytes: None,
             metrics: Default::default(),
         }
     }
 
+    /// Limits the estimated size of all blocks in the buffer to the given number of bytes.
+    ///
+    /// Once exceeded, the least recently inserted blocks are evicted. The last inserted block is
+    /// always kept.
+    pub const fn with_max_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Return the estimated size of all blocks in the buffer in bytes.
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
     /// Return reference to buffered blocks
     pub const fn blocks(&self) -> &HashMap<BlockHash, SealedBlockWithSenders> {
         &self.blocks
@@ -71,16 +96,27 @@ impl BlockBuffer {
 
         self.parent_to_child.entry(block.parent_hash).or_default().insert(hash);
         self.earliest_blocks.entry(block.number).or_default().insert(hash);
-        self.blocks.insert(hash, block);
+        self.size += estimated_block_size(&block);
+        if let Some(previous) = self.blocks.insert(hash, block) {
+            self.size -= estimated_block_size(&previous);
+        }
 
         if let (_, Some(evicted_hash)) = self.lru.insert_and_get_evicted(hash) {
             // evict the block if limit is hit
-            if let Some(evicted_block) = self.remove_block(&evicted_hash) {
-                // evict the block if limit is hit
-                self.remove_from_parent(evicted_block.parent_hash, &evicted_hash);
+            self.evict_block(&evicted_hash);
+        }
+
+        // evict the least recently inserted blocks if the size limit is hit
+        while self.max_bytes.is_some_and(|max_bytes| self.size > max_bytes) {
+            let Some(evicted_hash) = self.lru.iter().last().copied() else { break };
+            if evicted_hash == hash {
+                break
             }
+            self.evict_block(&evicted_hash);
         }
+
         self.metrics.blocks.set(self.blocks.len() as f64);
+        self.metrics.bytes.set(self.size as f64);
     }
     /// Removes the given block from the buffer and also all the children of the block.
     ///
@@ -97,6 +133,7 @@ impl BlockBuffer {
 
         removed.extend(self.remove_children(vec![*parent_hash]));
         self.metrics.blocks.set(self.blocks.len() as f64);
+        self.metrics.bytes.set(self.size as f64);
         removed
     }
 
@@ -121,6 +158,15 @@ impl BlockBuffer {
 
         self.remove_children(block_hashes_to_remove);
         self.metrics.blocks.set(self.blocks.len() as f64);
+        self.metrics.bytes.set(self.size as f64);
+    }
+
+    /// Evicts the block from the buffer, keeping its children.
+    fn evict_block(&mut self, hash: &BlockHash) {
+        if let Some(evicted_block) = self.remove_block(hash) {
+            self.remove_from_parent(evicted_block.parent_hash, hash);
+            self.metrics.evicted_blocks.increment(1);
+        }
     }
 
     /// Remove block entry
@@ -151,6 +197,7 @@ impl BlockBuffer {
     /// been removed.
     fn remove_block(&mut self, hash: &BlockHash) -> Option<SealedBlockWithSenders> {
         let block = self.blocks.remove(hash)?;
+        self.size -= estimated_block_size(&block);
         self.remove_from_earliest_blocks(block.number, hash);
         self.remove_from_parent(block.parent_hash, hash);
         self.lru.remove(hash);
@@ -181,6 +228,7 @@ impl BlockBuffer {
 
 #[cfg(test)]
 mod tests {
+    use super::estimated_block_size;
     use crate::BlockBuffer;
     use reth_primitives::{BlockHash, BlockNumHash, SealedBlockWithSenders};
     use reth_testing_utils::{
@@ -486,4 +534,38 @@ mod tests {
 
         assert_buffer_lengths(&buffer, 3);
     }
+
+    #[test]
+    fn size_eviction() {
+        let mut rng = generators::rng();
+
+        let parent1 = rng.gen();
+        let block1 = create_block(&mut rng, 10, parent1);
+        let block2 = create_block(&mut rng, 11, block1.hash());
+        let parent3 = rng.gen();
+        let block3 = create_block(&mut rng, 12, parent3);
+        let max_bytes = estimated_block_size(&block2) + estimated_block_size(&block3);
+
+        let mut buffer = BlockBuffer::new(10).with_max_bytes(max_bytes);
+
+        buffer.insert_block(block1.clone());
+        buffer.insert_block(block2.clone());
+        buffer.insert_block(block3.clone());
+
+        // block1 gets evicted
+        assert_block_removal(&buffer, &block1);
+        assert_buffer_lengths(&buffer, 2);
+        assert_eq!(buffer.size(), max_bytes);
+
+        // the last inserted block is kept even if it exceeds the limit on its own
+        let mut buffer = BlockBuffer::new(10).with_max_bytes(0);
+        buffer.insert_block(block1.clone());
+        buffer.insert_block(block2.clone());
+        assert_block_removal(&buffer, &block1);
+        assert_eq!(buffer.block(&block2.hash()), Some(&block2));
+
+        buffer.remove_block_with_children(&block1.hash());
+        assert_buffer_lengths(&buffer, 0);
+        assert_eq!(buffer.size(), 0);
+    }
 }
@@ -213,6 +213,20 @@ impl BlockIndices {
             .collect()
     }
 
+    /// Removes the child block from the children of the block it forks off.
+    ///
+    /// This disconnects a chain that is removed from the indices from its fork block.
+    pub(crate) fn remove_fork_child(&mut self, fork_hash: BlockHash, child_hash: BlockHash) {
+        if let hash_map::Entry::Occupied(mut entry) = self.fork_to_child.entry(fork_hash) {
+            let set = entry.get_mut();
+            set.remove(&child_hash);
+            // remove set if empty
+            if set.is_empty() {
+                entry.remove();
+            }
+        }
+    }
+
     /// Remove Blocks from indices.
     fn remove_block(
         &mut self,
@@ -2,12 +2,14 @@
 
 use crate::{
     metrics::{MakeCanonicalAction, MakeCanonicalDurationsRecorder, TreeMetrics},
+    receipts_cache::ReceiptEncodingsCache,
     state::{BlockchainId, TreeState},
     AppendableChain, BlockIndices, BlockchainTreeConfig, ExecutionData, TreeExternals,
 };
 use reth_blockchain_tree_api::{
     error::{BlockchainTreeError, CanonicalError, InsertBlockError, InsertBlockErrorKind},
     BlockAttachment, BlockStatus, BlockValidationKind, CanonicalOutcome, InsertPayloadOk,
+    ReorgGuard,
 };
 use reth_consensus::{Consensus, ConsensusError};
 use reth_db_api::database::Database;
@@ -19,15 +21,16 @@ use reth_primitives::{
     SealedBlockWithSenders, SealedHeader, StaticFileSegment, B256, U256,
 };
 use reth_provider::{
-    BlockExecutionWriter, BlockNumReader, BlockWriter, CanonStateNotification,
-    CanonStateNotificationSender, CanonStateNotifications, ChainSpecProvider, ChainSplit,
-    ChainSplitTarget, DisplayBlocksChain, HeaderProvider, ProviderError, StaticFileProviderFactory,
+    providers::StaticFileWriter, AccessListProvider, BlockExecutionWriter, BlockHashReader,
+    BlockNumReader, BlockWriter, CanonStateNotification, CanonStateNotificationSender,
+    CanonStateNotifications, ChainSpecProvider, ChainSplit, ChainSplitTarget, DisplayBlocksChain,
+    HeaderProvider, ProviderError, StaticFileProviderFactory,
 };
 use reth_prune_types::PruneModes;
 use reth_stages_api::{MetricEvent, MetricEventsSender};
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use std::{
-    collections::{btree_map::Entry, BTreeMap, HashSet},
+    collections::{btree_map::Entry, BTreeMap, BTreeSet, HashSet},
     sync::Arc,
 };
 use tracing::{debug, error, info, instrument, trace, warn};
@@ -71,6 +74,8 @@ pub struct BlockchainTree<DB, E> {
     canon_state_notification_sender: CanonStateNotificationSender,
     /// Metrics for sync stages.
     sync_metrics_tx: Option<MetricEventsSender>,
+    /// Guard against deep reorgs, if configured.
+    reorg_guard: Option<ReorgGuard>,
     /// Metrics for the blockchain tree.
     metrics: TreeMetrics,
 }
@@ -136,11 +141,14 @@ where
                 last_finalized_block_number,
                 last_canonical_hashes,
                 config.max_unconnected_blocks(),
+                config.max_unconnected_block_bytes(),
+                config.max_sidechain_state_bytes(),
             ),
             config,
             prune_modes,
             canon_state_notification_sender,
             sync_metrics_tx: None,
+            reorg_guard: None,
             metrics: Default::default(),
         })
     }
@@ -166,6 +174,20 @@ where
         self
     }
 
+    /// Set the guard against deep reorgs.
+    ///
+    /// Reorgs deeper than the guard's maximum depth are rejected with
+    /// [`CanonicalError::ReorgNotConfirmed`] until they are confirmed.
+    pub fn with_reorg_guard(mut self, reorg_guard: ReorgGuard) -> Self {
+        self.reorg_guard = Some(reorg_guard);
+        self
+    }
+
+    /// Returns the receipt encodings of the most recently validated blocks.
+    pub(crate) fn receipt_encodings(&self) -> Arc<ReceiptEncodingsCache> {
+        self.externals.receipt_encodings.clone()
+    }
+
     /// Check if the block is known to blockchain tree or database and return its status.
     ///
     /// Function will check:
@@ -278,7 +300,8 @@ where
                 return None;
             };
             let block_number = chain.block_number(block_hash)?;
-            let execution_outcome = chain.execution_outcome_at_block(block_number)?;
+            let execution_outcome =
+                self.state.execution_outcome_at_block(chain_id, block_number)?;
 
             // get parent hashes
             let mut parent_block_hashes = self.all_chain_hashes(chain_id);
@@ -432,6 +455,7 @@ where
             &self.externals,
             block_attachment,
             block_validation_kind,
+            self.config.prewarm_threads(),
         )?;
 
         self.insert_chain(chain);
@@ -462,6 +486,7 @@ where
         })?;
 
         // get chain that block needs to join to.
+        self.state.restore_chain(chain_id)?;
         let parent_chain = self.state.chains.get_mut(&chain_id).ok_or_else(|| {
             BlockchainTreeError::BlockSideChainIdConsistency { chain_id: chain_id.into() }
         })?;
@@ -489,9 +514,11 @@ where
                 canonical_fork,
                 block_attachment,
                 block_validation_kind,
+                self.config.prewarm_threads(),
             )?;
 
             self.state.block_indices.insert_non_fork_block(block_number, block_hash, chain_id);
+            self.state.update_appended_chain_size(chain_id);
             block_attachment
         } else {
             debug!(target: "blockchain_tree", ?canonical_fork, "Starting new fork from side chain");
@@ -503,6 +530,7 @@ where
                 canonical_fork,
                 &self.externals,
                 block_validation_kind,
+                self.config.prewarm_threads(),
             )?;
             self.insert_chain(chain);
             BlockAttachment::HistoricalFork
@@ -620,7 +648,10 @@ where
     /// plain state during the unwind.
     /// Returns the result of inserting the chain or None if any of the dependent chains is not
     /// in the tree.
-    fn insert_unwound_chain(&mut self, chain: AppendableChain) -> Option<BlockchainId> {
+    fn insert_unwound_chain(
+        &mut self,
+        chain: AppendableChain,
+    ) -> ProviderResult<Option<BlockchainId>> {
         // iterate over all blocks in chain and find any fork blocks that are in tree.
         for (number, block) in chain.blocks() {
             let hash = block.hash();
@@ -634,9 +665,10 @@ where
 
                 // prepend state to all chains that fork from this block.
                 for chain_id in chains_to_bump {
+                    self.state.restore_chain(chain_id)?;
                     let Some(chain) = self.state.chains.get_mut(&chain_id) else {
                         debug!(target: "blockchain_tree", ?chain_id, "Chain not in tree");
-                        return None;
+                        return Ok(None);
                     };
 
                     debug!(target: "blockchain_tree",
@@ -645,12 +677,13 @@ where
                         chain_tip = ?chain.tip().num_hash(),
                         "Prepend unwound block state to blockchain tree chain");
 
-                    chain.prepend_state(cloned_execution_outcome.state().clone())
+                    chain.prepend_state(cloned_execution_outcome.state().clone());
+                    self.state.update_chain_size(chain_id);
                 }
             }
         }
         // Insert unwound chain to the tree.
-        self.insert_chain(chain)
+        Ok(self.insert_chain(chain))
     }
 
     /// Checks the block buffer for the given block.
@@ -781,12 +814,67 @@ where
             return Err(InsertBlockError::consensus_error(err, block.block))
         }
 
+        let block_hash = block.hash();
         let status = self
             .try_insert_validated_block(block.clone(), block_validation_kind)
             .map_err(|kind| InsertBlockError::new(block.block, kind))?;
+
+        // keep the state of the chain that was just extended in memory
+        if let Some(chain_id) = self.block_indices().get_block_chain_id(&block_hash) {
+            self.state.touch_chain(chain_id);
+            if let Err(err) = self.state.spill_states(chain_id) {
+                warn!(target: "blockchain_tree", %err, "Failed to spill sidechain states to disk");
+            }
+            self.evict_sidechains(chain_id);
+        }
         Ok(InsertPayloadOk::Inserted(status))
     }
 
+    /// Evicts the least recently used sidechains until the estimated size of the sidechains fits
+    /// into the configured budget.
+    ///
+    /// Chains that fork off an evicted chain are evicted with it. The chain with the `keep` ID and
+    /// the chains it forks off are never evicted.
+    fn evict_sidechains(&mut self, keep: BlockchainId) {
+        let Some(max_bytes) = self.config.max_sidechain_bytes() else { return };
+        if self.state.chains_size() <= max_bytes {
+            return
+        }
+
+        // the chains that `keep` depends on
+        let mut protected = HashSet::from([keep]);
+        let mut next = self.state.chains.get(&keep).map(|chain| chain.fork_block().hash);
+        while let Some(chain_id) =
+            next.and_then(|fork_hash| self.block_indices().get_block_chain_id(&fork_hash))
+        {
+            if !protected.insert(chain_id) {
+                break
+            }
+            next = self.state.chains.get(&chain_id).map(|chain| chain.fork_block().hash);
+        }
+
+        for chain_id in self.state.least_recently_used_chains() {
+            if self.state.chains_size() <= max_bytes {
+                break
+            }
+            if protected.contains(&chain_id) {
+                continue
+            }
+            let Some(chain) = self.state.chains.get(&chain_id) else { continue };
+            let (fork_hash, first_hash) = (chain.fork_block().hash, chain.first().hash());
+            self.state.block_indices.remove_fork_child(fork_hash, first_hash);
+
+            let mut remove_chains = BTreeSet::from([chain_id]);
+            while let Some(chain_id) = remove_chains.pop_first() {
+                if let Some(chain) = self.state.discard_chain(chain_id) {
+                    debug!(target: "blockchain_tree", ?chain_id, tip = ?chain.tip().num_hash(), "Evicted sidechain");
+                    self.metrics.evicted_sidechains.increment(1);
+                    remove_chains.extend(self.state.block_indices.remove_chain(&chain));
+                }
+            }
+        }
+    }
+
     /// Discard all blocks that precede block number from the buffer.
     pub fn remove_old_blocks(&mut self, block: BlockNumber) {
         self.state.buffered_blocks.remove_old_blocks(block);
@@ -801,12 +889,14 @@ where
         );
         // remove chains of removed blocks
         while let Some(chain_id) = remove_chains.pop_first() {
-            if let Some(chain) = self.state.chains.remove(&chain_id) {
+            if let Some(chain) = self.state.discard_chain(chain_id) {
                 remove_chains.extend(self.state.block_indices.remove_chain(&chain));
             }
         }
         // clean block buffer.
         self.remove_old_blocks(finalized_block);
+        // finalized blocks are never committed again
+        self.externals.access_lists.finalize(finalized_block);
 
         // save finalized block in db.
         self.externals.save_finalized_block_number(finalized_block)?;
@@ -850,7 +940,7 @@ where
 
         // remove all chains that got discarded
         while let Some(chain_id) = remove_chains.first() {
-            if let Some(chain) = self.state.chains.remove(chain_id) {
+            if let Some(chain) = self.state.discard_chain(*chain_id) {
                 remove_chains.extend(self.state.block_indices.remove_chain(&chain));
             }
         }
@@ -942,19 +1032,20 @@ where
         &mut self,
         chain_id: BlockchainId,
         split_at: ChainSplitTarget,
-    ) -> Option<Chain> {
-        let chain = self.state.chains.remove(&chain_id)?;
+    ) -> ProviderResult<Option<Chain>> {
+        let Some(chain) = self.state.remove_chain(chain_id)? else { return Ok(None) };
         match chain.into_inner().split(split_at) {
             ChainSplit::Split { canonical, pending } => {
                 trace!(target: "blockchain_tree", ?canonical, ?pending, "Split chain");
                 // rest of split chain is inserted back with same chain_id.
                 self.state.block_indices.insert_chain(chain_id, &pending);
                 self.state.chains.insert(chain_id, AppendableChain::new(pending));
-                Some(canonical)
+                self.state.update_chain_size(chain_id);
+                Ok(Some(canonical))
             }
             ChainSplit::NoSplitCanonical(canonical) => {
                 trace!(target: "blockchain_tree", "No split on canonical chain");
-                Some(canonical)
+                Ok(Some(canonical))
             }
             ChainSplit::NoSplitPending(_) => {
                 unreachable!("Should not happen as block indices guarantee structure of blocks")
@@ -1062,8 +1153,22 @@ where
             }))
         };
 
+        // check the reorg depth before the tree is modified
+        if let Some(reorg_guard) = &self.reorg_guard {
+            if let Some(canonical_fork) = self.canonical_fork(chain_id) {
+                let depth = self
+                    .block_indices()
+                    .canonical_tip()
+                    .number
+                    .saturating_sub(canonical_fork.number);
+                reorg_guard.check(block_hash, depth).inspect_err(|_| {
+                    warn!(target: "blockchain_tree", ?block_hash, depth, "Rejected unconfirmed deep reorg");
+                })?;
+            }
+        }
+
         // we are splitting chain at the block hash that we want to make canonical
-        let Some(canonical) = self.remove_and_split_chain(chain_id, block_hash.into()) else {
+        let Some(canonical) = self.remove_and_split_chain(chain_id, block_hash.into())? else {
             debug!(target: "blockchain_tree", ?block_hash, ?chain_id, "Chain not present");
             return Err(CanonicalError::from(BlockchainTreeError::BlockSideChainIdConsistency {
                 chain_id: chain_id.into(),
@@ -1079,7 +1184,7 @@ where
         while let Some(chain_id) = self.block_indices().get_block_chain_id(&fork_block.hash) {
             // canonical chain is lower part of the chain.
             let Some(canonical) =
-                self.remove_and_split_chain(chain_id, ChainSplitTarget::Number(fork_block.number))
+                self.remove_and_split_chain(chain_id, ChainSplitTarget::Number(fork_block.number))?
             else {
                 debug!(target: "blockchain_tree", ?fork_block, ?chain_id, "Fork not present");
                 return Err(CanonicalError::from(
@@ -1163,7 +1268,7 @@ where
                 self.update_reorg_metrics(old_canon_chain.len() as f64);
 
                 // Insert old canonical chain back into tree.
-                self.insert_unwound_chain(AppendableChain::new(old_canon_chain.clone()));
+                self.insert_unwound_chain(AppendableChain::new(old_canon_chain.clone()))?;
                 durations_recorder.record_relative(MakeCanonicalAction::InsertOldCanonicalChain);
 
                 CanonStateNotification::Reorg {
@@ -1251,6 +1356,15 @@ where
         };
         recorder.record_relative(MakeCanonicalAction::RetrieveStateTrieUpdates);
 
+        // The access lists segment is contiguous, so the access lists are only appended up to the
+        // first block they weren't collected for.
+        let first_block = blocks.first().number;
+        let access_lists = block_hash_numbers
+            .iter()
+            .map_while(|(_, hash)| self.externals.access_lists.take(hash))
+            .collect::<Vec<_>>();
+        let has_access_lists = !access_lists.is_empty();
+
         let provider_rw = self.externals.provider_factory.provider_rw()?;
         provider_rw
             .append_blocks_with_state(
@@ -1261,7 +1375,19 @@ where
                 self.prune_modes.as_ref(),
             )
             .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
+        provider_rw
+            .append_access_lists(first_block, access_lists)
+            .map_err(|e| CanonicalError::CanonicalCommit(e.to_string()))?;
 
+        // The static files are committed first, so that they're truncated according to the
+        // database on the next start-up if the database commit is interrupted.
+        if has_access_lists {
+            self.externals
+                .provider_factory
+                .static_file_provider()
+                .latest_writer(StaticFileSegment::AccessLists)?
+                .commit()?;
+        }
         provider_rw.commit()?;
         recorder.record_relative(MakeCanonicalAction::CommitCanonicalChainToDatabase);
 
@@ -1281,7 +1407,7 @@ where
         if let Some(old_canon_chain) = old_canon_chain {
             self.state.block_indices.unwind_canonical_chain(unwind_to);
             // insert old canonical chain to BlockchainTree.
-            self.insert_unwound_chain(AppendableChain::new(old_canon_chain));
+            self.insert_unwound_chain(AppendableChain::new(old_canon_chain))?;
         }
 
         Ok(())
@@ -1323,12 +1449,38 @@ where
         let tip = provider_rw.last_block_number()?;
         let revert_range = (revert_until + 1)..=tip;
         info!(target: "blockchain_tree", "REORG: revert canonical from database by unwinding chain blocks {:?}", revert_range);
+
+        // Keep the access lists of the reverted blocks, so that they're appended again if the
+        // blocks become canonical again.
+        let static_file_provider = self.externals.provider_factory.static_file_provider();
+        let unwind_access_lists = static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccessLists)
+            .is_some_and(|highest_block| highest_block > revert_until);
+        if unwind_access_lists {
+            for block_number in revert_range.clone() {
+                if let (Some(block_hash), Some(access_lists)) = (
+                    provider_rw.block_hash(block_number)?,
+                    provider_rw.access_lists_by_block(block_number.into())?,
+                ) {
+                    self.externals.access_lists.insert(block_hash, block_number, access_lists);
+                }
+            }
+            provider_rw
+                .unwind_access_lists(revert_until)
+                .map_err(|e| CanonicalError::CanonicalRevert(e.to_string()))?;
+        }
+
         // read block and execution result from database. and remove traces of block from tables.
         let blocks_and_execution = provider_rw
             .take_block_and_execution_range(revert_range)
             .map_err(|e| CanonicalError::CanonicalRevert(e.to_string()))?;
 
+        // The database is committed first, so that the static files are truncated according to
+        // the database on the next start-up if the static files commit is interrupted.
         provider_rw.commit()?;
+        if unwind_access_lists {
+            static_file_provider.latest_writer(StaticFileSegment::AccessLists)?.commit()?;
+        }
 
         if blocks_and_execution.is_empty() {
             Ok(None)
@@ -1357,6 +1509,8 @@ where
         }
 
         self.metrics.sidechains.set(self.state.chains.len() as f64);
+        self.metrics.spilled_sidechains.set(self.state.spilled_chains() as f64);
+        self.metrics.sidechain_bytes.set(self.state.chains_size() as f64);
         self.metrics.canonical_chain_height.set(height as f64);
         if let Some(metrics_tx) = self.sync_metrics_tx.as_mut() {
             let _ = metrics_tx.send(MetricEvent::SyncHeight { height });
@@ -1971,6 +2125,94 @@ mod tests {
         assert_eq!(chain1.first_block(), block2.number);
     }
 
+    #[test]
+    fn test_side_chain_state_spill() {
+        let data = BlockchainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec2.clone(), exec2, exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        // spill the states of all chains except the last extended one
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2).with_max_sidechain_state_bytes(0);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+        tree.make_canonical(B256::ZERO).unwrap();
+        tree.finalize_block(10).unwrap();
+
+        tree.insert_block(block1, BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block2.clone(), BlockValidationKind::Exhaustive).unwrap();
+        assert!(!tree.state.is_spilled(&0.into()));
+        let chain0 = tree.state.chains.get(&0.into()).unwrap().execution_outcome().clone();
+
+        // forking off chain 0 spills its state
+        let mut block2a = block2.clone();
+        block2a.set_hash(B256::new([0x34; 32]));
+        tree.insert_block(block2a, BlockValidationKind::Exhaustive).unwrap();
+        assert!(tree.state.is_spilled(&0.into()));
+        assert!(!tree.state.is_spilled(&1.into()));
+        assert!(tree.state.chains.get(&0.into()).unwrap().execution_outcome().is_empty());
+        assert_eq!(tree.receipts_by_block_hash(block2.hash()), None);
+        assert_eq!(tree.state.chains_size(), tree.state.recompute_chains_size());
+
+        // the spilled state is still served to the pending state
+        let post_state = tree.post_state_data(block2.hash()).unwrap();
+        assert_eq!(post_state.execution_outcome, chain0);
+
+        // and restored when the chain is used again
+        tree.state.restore_chain(0.into()).unwrap();
+        assert!(!tree.state.is_spilled(&0.into()));
+        assert_eq!(tree.state.chains.get(&0.into()).unwrap().execution_outcome(), &chain0);
+        assert_eq!(tree.state.chains_size(), tree.state.recompute_chains_size());
+    }
+
+    #[test]
+    fn test_side_chain_eviction() {
+        let data = BlockchainTestData::default_from_number(11);
+        let (block1, exec1) = data.blocks[0].clone();
+        let (block2, exec2) = data.blocks[1].clone();
+        let genesis = data.genesis;
+
+        // test pops execution results from vector, so order is from last to first.
+        let externals = setup_externals(vec![exec1.clone(), exec2.clone(), exec2, exec1]);
+        setup_genesis(&externals.provider_factory, genesis);
+
+        // evict all chains except the last extended one and the chains it forks off
+        let config = BlockchainTreeConfig::new(1, 2, 3, 2).with_max_sidechain_bytes(0);
+        let mut tree = BlockchainTree::new(externals, config, None).expect("failed to create tree");
+        tree.make_canonical(B256::ZERO).unwrap();
+        tree.finalize_block(10).unwrap();
+
+        tree.insert_block(block1.clone(), BlockValidationKind::Exhaustive).unwrap();
+        tree.insert_block(block2.clone(), BlockValidationKind::Exhaustive).unwrap();
+        assert_eq!(tree.state.chains_size(), tree.state.recompute_chains_size());
+
+        // chain 1 forks off chain 0, so chain 0 is kept
+        let mut block2a = block2.clone();
+        block2a.set_hash(B256::new([0x34; 32]));
+        tree.insert_block(block2a.clone(), BlockValidationKind::Exhaustive).unwrap();
+        assert_eq!(tree.state.chains.len(), 2);
+        assert_eq!(tree.state.chains_size(), tree.state.recompute_chains_size());
+
+        // a chain forking off the canonical chain evicts both
+        let mut block1a = block1.clone();
+        block1a.set_hash(B256::new([0x12; 32]));
+        tree.insert_block(block1a.clone(), BlockValidationKind::Exhaustive).unwrap();
+
+        TreeTester::default()
+            .with_chain_num(1)
+            .with_block_to_chain(HashMap::from([(block1a.hash(), 2.into())]))
+            .with_fork_to_child(HashMap::from([(
+                block1a.parent_hash,
+                HashSet::from([block1a.hash()]),
+            )]))
+            .assert(&tree);
+        assert!(tree.sidechain_block_by_hash(block2a.hash()).is_none());
+        assert_eq!(tree.state.chains_size(), tree.state.recompute_chains_size());
+    }
+
     #[test]
     fn sanity_path() {
         let data = BlockchainTestData::default_from_number(11);
@@ -2367,11 +2609,15 @@ mod tests {
             provider_factory: externals.provider_factory.clone(),
             executor_factory: externals.executor_factory.clone(),
             consensus: externals.consensus.clone(),
+            receipt_encodings: externals.receipt_encodings.clone(),
+            access_lists: Default::default(),
         };
         let cloned_externals_2 = TreeExternals {
             provider_factory: externals.provider_factory.clone(),
             executor_factory: externals.executor_factory.clone(),
             consensus: externals.consensus.clone(),
+            receipt_encodings: externals.receipt_encodings.clone(),
+            access_lists: Default::default(),
         };
 
         // last finalized block would be number 9.
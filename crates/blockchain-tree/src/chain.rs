@@ -4,7 +4,11 @@
 //! blocks, as well as a list of the blocks the chain is composed of.
 
 use super::externals::TreeExternals;
-use crate::BundleStateDataRef;
+use crate::{
+    prewarm::{PrewarmedState, PrewarmedStateProvider},
+    BundleStateDataRef,
+};
+use rayon::prelude::*;
 use reth_blockchain_tree_api::{
     error::{BlockchainTreeError, InsertBlockErrorKind},
     BlockAttachment, BlockValidationKind,
@@ -15,7 +19,8 @@ use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::{Chain, ExecutionOutcome};
 use reth_primitives::{
-    BlockHash, BlockNumber, ForkBlock, GotExpected, SealedBlockWithSenders, SealedHeader, U256,
+    BlockHash, BlockNumber, ForkBlock, GotExpected, ReceiptEncoding, SealedBlockWithSenders,
+    SealedHeader, U256,
 };
 use reth_provider::{
     providers::{BundleStateProvider, ConsistentDbView},
@@ -27,6 +32,7 @@ use reth_trie_parallel::parallel_root::ParallelStateRoot;
 use std::{
     collections::BTreeMap,
     ops::{Deref, DerefMut},
+    sync::Arc,
     time::Instant,
 };
 
@@ -66,6 +72,7 @@ impl AppendableChain {
     ///
     /// if [`BlockValidationKind::Exhaustive`] is specified, the method will verify the state root
     /// of the block.
+    #[allow(clippy::too_many_arguments)]
     pub fn new_canonical_fork<DB, E>(
         block: SealedBlockWithSenders,
         parent_header: &SealedHeader,
@@ -74,6 +81,7 @@ impl AppendableChain {
         externals: &TreeExternals<DB, E>,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
+        prewarm_threads: usize,
     ) -> Result<Self, InsertBlockErrorKind>
     where
         DB: Database + Clone,
@@ -96,6 +104,7 @@ impl AppendableChain {
             externals,
             block_attachment,
             block_validation_kind,
+            prewarm_threads,
         )?;
 
         Ok(Self { chain: Chain::new(vec![block], bundle_state, trie_updates) })
@@ -104,6 +113,7 @@ impl AppendableChain {
     /// Create a new chain that forks off of an existing sidechain.
     ///
     /// This differs from [`AppendableChain::new_canonical_fork`] in that this starts a new fork.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new_chain_fork<DB, E>(
         &self,
         block: SealedBlockWithSenders,
@@ -112,6 +122,7 @@ impl AppendableChain {
         canonical_fork: ForkBlock,
         externals: &TreeExternals<DB, E>,
         block_validation_kind: BlockValidationKind,
+        prewarm_threads: usize,
     ) -> Result<Self, InsertBlockErrorKind>
     where
         DB: Database + Clone,
@@ -142,6 +153,7 @@ impl AppendableChain {
             externals,
             BlockAttachment::HistoricalFork,
             block_validation_kind,
+            prewarm_threads,
         )?;
         // extending will also optimize few things, mostly related to selfdestruct and wiping of
         // storage.
@@ -167,6 +179,9 @@ impl AppendableChain {
     ///   - [`BlockAttachment`] represents if the block extends the canonical chain, and thus we can
     ///     cache the trie state updates.
     ///   - [`BlockValidationKind`] determines if the state root __should__ be validated.
+    ///
+    /// While the block is executed, `prewarm_threads` threads read the state it's likely going to
+    /// touch ahead of the execution, see [`PrewarmedState`].
     fn validate_and_execute<EDP, DB, E>(
         block: SealedBlockWithSenders,
         parent_block: &SealedHeader,
@@ -174,6 +189,7 @@ impl AppendableChain {
         externals: &TreeExternals<DB, E>,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
+        prewarm_threads: usize,
     ) -> Result<(ExecutionOutcome, Option<TrieUpdates>), BlockExecutionError>
     where
         EDP: FullExecutionDataProvider,
@@ -202,58 +218,101 @@ impl AppendableChain {
             .disable_long_read_transaction_safety()
             .state_provider_by_block_number(canonical_fork.number)?;
 
-        let provider = BundleStateProvider::new(state_provider, bundle_state_data_provider);
+        // The canonical state doesn't change while the block is executed, so the state read ahead
+        // of the execution is valid underneath the state of the sidechain.
+        let prewarmed_state = PrewarmedState::default();
+        let provider = BundleStateProvider::new(
+            PrewarmedStateProvider::new(state_provider, &prewarmed_state),
+            bundle_state_data_provider,
+        );
 
         let db = StateProviderDatabase::new(&provider);
         let executor = externals.executor_factory.executor(db);
         let block_hash = block.hash();
         let block = block.unseal();
 
-        let state = executor.execute((&block, U256::MAX).into())?;
-        let BlockExecutionOutput { state, receipts, requests, .. } = state;
-        externals
-            .consensus
-            .validate_block_post_execution(&block, PostExecutionInput::new(&receipts, &requests))?;
+        let state = prewarmed_state.prewarm_while(
+            &consistent_view,
+            canonical_fork.number,
+            &block,
+            prewarm_threads,
+            || executor.execute((&block, U256::MAX).into()),
+        )?;
+        let BlockExecutionOutput { state, receipts, requests, access_lists, .. } = state;
 
-        let initial_execution_outcome =
-            ExecutionOutcome::new(state, receipts.into(), block.number, vec![requests.into()]);
+        // The receipts root, logs bloom and requests root are validated on the rayon pool while
+        // the state root is computed. The blooms and encodings of the receipts are computed in
+        // parallel once, shared with the consensus validation, and cached for the block.
+        let mut receipt_encodings: Arc<[ReceiptEncoding]> = Arc::new([]);
+        let mut post_execution_result = Ok(());
+        let state_root_result = rayon::in_place_scope(|scope| {
+            scope.spawn(|_| {
+                receipt_encodings =
+                    receipts.par_iter().map(ReceiptEncoding::new).collect::<Vec<_>>().into();
+                post_execution_result = externals.consensus.validate_block_post_execution(
+                    &block,
+                    PostExecutionInput::new(&receipts, &requests)
+                        .with_receipt_encodings(&receipt_encodings),
+                );
+            });
+
+            // check state root if the block extends the canonical chain __and__ if state root
+            // validation was requested.
+            if !block_validation_kind.is_exhaustive() {
+                return Ok(None)
+            }
 
-        // check state root if the block extends the canonical chain __and__ if state root
-        // validation was requested.
-        if block_validation_kind.is_exhaustive() {
             // calculate and check state root
             let start = Instant::now();
             let (state_root, trie_updates) = if block_attachment.is_canonical() {
                 let mut execution_outcome =
                     provider.block_execution_data_provider.execution_outcome().clone();
-                execution_outcome.extend(initial_execution_outcome.clone());
+                execution_outcome.extend(ExecutionOutcome::new(
+                    state.clone(),
+                    Default::default(),
+                    block.number,
+                    Vec::new(),
+                ));
                 let hashed_state = execution_outcome.hash_state_slow();
                 ParallelStateRoot::new(consistent_view, hashed_state)
                     .incremental_root_with_updates()
                     .map(|(root, updates)| (root, Some(updates)))
                     .map_err(ProviderError::from)?
             } else {
-                (provider.state_root(initial_execution_outcome.state())?, None)
+                (provider.state_root(&state)?, None)
             };
-            if block.state_root != state_root {
-                return Err(ConsensusError::BodyStateRootDiff(
-                    GotExpected { got: state_root, expected: block.state_root }.into(),
-                )
-                .into())
-            }
-
             tracing::debug!(
                 target: "blockchain_tree::chain",
                 number = block.number,
                 hash = %block_hash,
                 elapsed = ?start.elapsed(),
-                "Validated state root"
+                "Computed state root"
             );
+            Ok::<_, BlockExecutionError>(Some((state_root, trie_updates)))
+        });
+        post_execution_result?;
+
+        let trie_updates = match state_root_result? {
+            Some((state_root, trie_updates)) => {
+                if block.state_root != state_root {
+                    return Err(ConsensusError::BodyStateRootDiff(
+                        GotExpected { got: state_root, expected: block.state_root }.into(),
+                    )
+                    .into())
+                }
+                trie_updates
+            }
+            None => None,
+        };
 
-            Ok((initial_execution_outcome, trie_updates))
-        } else {
-            Ok((initial_execution_outcome, None))
+        externals.receipt_encodings.insert(block_hash, receipt_encodings);
+        if let Some(access_lists) = access_lists {
+            externals.access_lists.insert(block_hash, block.number, access_lists);
         }
+
+        let initial_execution_outcome =
+            ExecutionOutcome::new(state, receipts.into(), block.number, vec![requests.into()]);
+        Ok((initial_execution_outcome, trie_updates))
     }
 
     /// Validate and execute the given block, and append it to this chain.
@@ -278,6 +337,7 @@ impl AppendableChain {
         canonical_fork: ForkBlock,
         block_attachment: BlockAttachment,
         block_validation_kind: BlockValidationKind,
+        prewarm_threads: usize,
     ) -> Result<(), InsertBlockErrorKind>
     where
         DB: Database + Clone,
@@ -299,6 +359,7 @@ impl AppendableChain {
             externals,
             block_attachment,
             block_validation_kind,
+            prewarm_threads,
         )?;
         // extend the state.
         self.chain.append_block(block, block_state);
@@ -19,6 +19,19 @@ pub struct BlockchainTreeConfig {
     /// be 256. It covers both number of blocks required for reorg, and number of blocks
     /// required for `BLOCKHASH` EVM opcode.
     num_of_additional_canonical_block_hashes: u64,
+    /// The number of threads that read the state of a block ahead of its execution, `0` disables
+    /// prewarming.
+    prewarm_threads: usize,
+    /// The estimated number of bytes of sidechain execution outcomes that are held in memory
+    /// before they are spilled to disk, `None` keeps all of them in memory.
+    max_sidechain_state_bytes: Option<usize>,
+    /// The estimated number of bytes of unconnected blocks that are buffered, `None` only limits
+    /// the number of buffered blocks.
+    max_unconnected_block_bytes: Option<usize>,
+    /// The estimated number of bytes of sidechain blocks and their in-memory execution outcomes
+    /// that are held before sidechains are evicted, `None` keeps all sidechains until they are
+    /// finalized away.
+    max_sidechain_bytes: Option<usize>,
 }
 
 impl Default for BlockchainTreeConfig {
@@ -33,6 +46,14 @@ impl Default for BlockchainTreeConfig {
             num_of_additional_canonical_block_hashes: 256,
             // max unconnected blocks.
             max_unconnected_blocks: 200,
+            // enough to keep ahead of the serial execution for most blocks.
+            prewarm_threads: 4,
+            // keep all sidechain states in memory.
+            max_sidechain_state_bytes: None,
+            // only limit the number of unconnected blocks.
+            max_unconnected_block_bytes: None,
+            // keep all sidechains.
+            max_sidechain_bytes: None,
         }
     }
 }
@@ -54,9 +75,47 @@ impl BlockchainTreeConfig {
             max_reorg_depth,
             num_of_additional_canonical_block_hashes,
             max_unconnected_blocks,
+            ..Self::default()
         }
     }
 
+    /// Set the number of threads that read the state of a block ahead of its execution.
+    ///
+    /// `0` disables prewarming.
+    pub const fn with_prewarm_threads(mut self, prewarm_threads: usize) -> Self {
+        self.prewarm_threads = prewarm_threads;
+        self
+    }
+
+    /// Set the estimated number of bytes of sidechain execution outcomes that are held in memory.
+    ///
+    /// Once exceeded, the execution outcomes of the sidechains least likely to become canonical are
+    /// spilled to a temporary directory, and read back when the sidechain is extended or
+    /// canonicalized.
+    pub const fn with_max_sidechain_state_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_sidechain_state_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Set the estimated number of bytes of unconnected blocks that are buffered.
+    ///
+    /// Once exceeded, the least recently buffered blocks are evicted.
+    pub const fn with_max_unconnected_block_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_unconnected_block_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Set the estimated number of bytes of sidechain blocks and their in-memory execution outcomes
+    /// that are held.
+    ///
+    /// Once exceeded, the least recently extended sidechains are evicted together with the chains
+    /// that fork off them. Execution outcomes that were spilled to disk don't count towards the
+    /// budget, see [`BlockchainTreeConfig::with_max_sidechain_state_bytes`].
+    pub const fn with_max_sidechain_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_sidechain_bytes = Some(max_bytes);
+        self
+    }
+
     /// Return the maximum reorg depth.
     pub const fn max_reorg_depth(&self) -> u64 {
         self.max_reorg_depth
@@ -88,4 +147,26 @@ impl BlockchainTreeConfig {
     pub const fn max_unconnected_blocks(&self) -> u32 {
         self.max_unconnected_blocks
     }
+
+    /// Return the number of threads that read the state of a block ahead of its execution.
+    pub const fn prewarm_threads(&self) -> usize {
+        self.prewarm_threads
+    }
+
+    /// Return the estimated number of bytes of sidechain execution outcomes that are held in
+    /// memory, if limited.
+    pub const fn max_sidechain_state_bytes(&self) -> Option<usize> {
+        self.max_sidechain_state_bytes
+    }
+
+    /// Return the estimated number of bytes of unconnected blocks that are buffered, if limited.
+    pub const fn max_unconnected_block_bytes(&self) -> Option<usize> {
+        self.max_unconnected_block_bytes
+    }
+
+    /// Return the estimated number of bytes of sidechain blocks and their in-memory execution
+    /// outcomes that are held, if limited.
+    pub const fn max_sidechain_bytes(&self) -> Option<usize> {
+        self.max_sidechain_bytes
+    }
 }
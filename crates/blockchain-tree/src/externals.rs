@@ -1,5 +1,6 @@
 //! Blockchain tree externals.
 
+use crate::{access_lists::PendingAccessLists, receipts_cache::ReceiptEncodingsCache};
 use reth_consensus::Consensus;
 use reth_db::{static_file::HeaderMask, tables};
 use reth_db_api::{cursor::DbCursorRO, database::Database, transaction::DbTx};
@@ -28,6 +29,11 @@ pub struct TreeExternals<DB, E> {
     pub(crate) consensus: Arc<dyn Consensus>,
     /// The executor factory to execute blocks with.
     pub(crate) executor_factory: E,
+    /// The receipt encodings of the most recently validated blocks.
+    pub(crate) receipt_encodings: Arc<ReceiptEncodingsCache>,
+    /// The access lists of the executed blocks that aren't committed yet, if the executor
+    /// collects them.
+    pub(crate) access_lists: PendingAccessLists,
 }
 
 impl<DB, E> TreeExternals<DB, E> {
@@ -37,7 +43,13 @@ impl<DB, E> TreeExternals<DB, E> {
         consensus: Arc<dyn Consensus>,
         executor_factory: E,
     ) -> Self {
-        Self { provider_factory, consensus, executor_factory }
+        Self {
+            provider_factory,
+            consensus,
+            executor_factory,
+            receipt_encodings: Default::default(),
+            access_lists: Default::default(),
+        }
     }
 }
 
@@ -86,7 +98,7 @@ impl<DB: Database, E> TreeExternals<DB, E> {
     }
 
     pub(crate) fn fetch_latest_finalized_block_number(&self) -> ProviderResult<BlockNumber> {
-        self.provider_factory.provider()?.last_finalized_block_number()
+        Ok(self.provider_factory.provider()?.last_finalized_block_number()?.unwrap_or_default())
     }
 
     pub(crate) fn save_finalized_block_number(
@@ -54,6 +54,14 @@ pub use block_buffer::BlockBuffer;
 /// Implementation of Tree traits that does nothing.
 pub mod noop;
 
+mod access_lists;
+
+mod prewarm;
+
+mod receipts_cache;
+
+mod spill;
+
 mod state;
 
 use aquamarine as _;
@@ -19,6 +19,12 @@ pub struct TreeMetrics {
     pub latest_reorg_depth: Gauge,
     /// Longest sidechain height
     pub longest_sidechain_height: Gauge,
+    /// The number of sidechains whose state was spilled to disk
+    pub spilled_sidechains: Gauge,
+    /// Estimated size of the blocks and the in-memory state of all sidechains in bytes
+    pub sidechain_bytes: Gauge,
+    /// The number of sidechains evicted because the sidechains exceeded their memory budget
+    pub evicted_sidechains: Counter,
     /// The number of times cached trie updates were used for insert.
     pub trie_updates_insert_cached: Counter,
     /// The number of times trie updates were recomputed for insert.
@@ -31,6 +37,20 @@ pub struct TreeMetrics {
 pub struct BlockBufferMetrics {
     /// Total blocks in the block buffer
     pub blocks: Gauge,
+    /// Estimated size of the blocks in the block buffer in bytes
+    pub bytes: Gauge,
+    /// The number of blocks evicted from the block buffer because it was full
+    pub evicted_blocks: Counter,
+}
+
+/// Metrics for the state prefetched ahead of block execution
+#[derive(Metrics)]
+#[metrics(scope = "blockchain_tree.prewarm")]
+pub(crate) struct PrewarmMetrics {
+    /// The number of state reads during execution that were served from the prefetched state
+    pub(crate) hits: Counter,
+    /// The number of state reads during execution that went to the database
+    pub(crate) misses: Counter,
 }
 
 #[derive(Debug)]
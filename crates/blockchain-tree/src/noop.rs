@@ -5,15 +5,15 @@ use reth_blockchain_tree_api::{
     InsertPayloadOk,
 };
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader,
+    BlockHash, BlockNumHash, BlockNumber, Receipt, ReceiptEncoding, SealedBlock,
+    SealedBlockWithSenders, SealedHeader,
 };
 use reth_provider::{
     BlockchainTreePendingStateProvider, CanonStateNotificationSender, CanonStateNotifications,
     CanonStateSubscriptions, FullExecutionDataProvider,
 };
 use reth_storage_errors::provider::ProviderResult;
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, sync::Arc};
 
 /// A `BlockchainTree` that does nothing.
 ///
@@ -116,6 +116,10 @@ impl BlockchainTreeViewer for NoopBlockchainTree {
     fn receipts_by_block_hash(&self, _block_hash: BlockHash) -> Option<Vec<Receipt>> {
         None
     }
+
+    fn receipt_encodings(&self, _block_hash: BlockHash) -> Option<Arc<[ReceiptEncoding]>> {
+        None
+    }
 }
 
 impl BlockchainTreePendingStateProvider for NoopBlockchainTree {
@@ -0,0 +1,296 @@
+//! Speculative prefetching of the state a block is going to read.
+//!
+//! Block execution is serial and spends much of its time waiting on the database. A large part of
+//! the state a block reads is known before it's executed: the beneficiary, the senders and
+//! recipients of its transactions, the accounts and storage slots of their access lists and the
+//! recipients of its withdrawals. While the block is executed, this state is read on separate
+//! threads, each with its own database transaction, and the executor finds it in the
+//! [`PrewarmedState`] instead of reading it from the database.
+
+use crate::metrics::PrewarmMetrics;
+use parking_lot::RwLock;
+use reth_db_api::database::Database;
+use reth_primitives::{
+    Account, Address, BlockNumber, BlockWithSenders, Bytecode, StorageKey, StorageValue, B256,
+    KECCAK_EMPTY,
+};
+use reth_provider::{
+    providers::ConsistentDbView, AccountReader, BlockHashReader, ProviderFactory, StateProvider,
+    StateRootProvider,
+};
+use reth_revm::db::BundleState;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{updates::TrieUpdates, AccountProof};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+};
+use tracing::debug;
+
+/// State of the canonical chain that was read ahead of a block's execution.
+///
+/// The state is read at the block the executed block forks off of the canonical chain, which
+/// doesn't change while the block is executed.
+#[derive(Debug, Default)]
+pub(crate) struct PrewarmedState {
+    /// Accounts by address, `None` if the account doesn't exist.
+    accounts: RwLock<HashMap<Address, Option<Account>>>,
+    /// Storage values by account and slot.
+    storage: RwLock<HashMap<(Address, StorageKey), Option<StorageValue>>>,
+    /// Bytecode by code hash.
+    bytecodes: RwLock<HashMap<B256, Option<Bytecode>>>,
+}
+
+impl PrewarmedState {
+    /// Calls `f` while `threads` threads read the state `block` is likely going to touch at
+    /// `block_number` into this state.
+    ///
+    /// The threads stop once `f` returns, whether or not all the state was read.
+    pub(crate) fn prewarm_while<DB, R>(
+        &self,
+        consistent_view: &ConsistentDbView<DB, ProviderFactory<DB>>,
+        block_number: BlockNumber,
+        block: &BlockWithSenders,
+        threads: usize,
+        f: impl FnOnce() -> R,
+    ) -> R
+    where
+        DB: Database,
+    {
+        let targets = prewarm_targets(block);
+        let threads = threads.min(targets.len());
+        if threads == 0 {
+            return f()
+        }
+
+        let next = AtomicUsize::new(0);
+        let done = AtomicBool::new(false);
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    if let Err(err) =
+                        self.prewarm(consistent_view, block_number, &targets, &next, &done)
+                    {
+                        debug!(target: "blockchain_tree::prewarm", %err, "Failed to prewarm state");
+                    }
+                });
+            }
+
+            let result = f();
+            done.store(true, Ordering::Relaxed);
+            result
+        })
+    }
+
+    /// Reads targets until all targets were taken or `done` is set.
+    ///
+    /// Targets are taken in order, so the state read first by execution is also prewarmed first.
+    fn prewarm<DB: Database>(
+        &self,
+        consistent_view: &ConsistentDbView<DB, ProviderFactory<DB>>,
+        block_number: BlockNumber,
+        targets: &[PrewarmTarget],
+        next: &AtomicUsize,
+        done: &AtomicBool,
+    ) -> ProviderResult<()> {
+        let state_provider =
+            consistent_view.provider_ro()?.state_provider_by_block_number(block_number)?;
+
+        while !done.load(Ordering::Relaxed) {
+            let Some(target) = targets.get(next.fetch_add(1, Ordering::Relaxed)) else { break };
+            match *target {
+                PrewarmTarget::Account(address) => {
+                    let account = state_provider.basic_account(address)?;
+                    self.accounts.write().insert(address, account);
+
+                    let Some(code_hash) = account
+                        .and_then(|account| account.bytecode_hash)
+                        .filter(|code_hash| *code_hash != KECCAK_EMPTY)
+                    else {
+                        continue
+                    };
+                    if !self.bytecodes.read().contains_key(&code_hash) {
+                        let bytecode = state_provider.bytecode_by_hash(code_hash)?;
+                        self.bytecodes.write().insert(code_hash, bytecode);
+                    }
+                }
+                PrewarmTarget::Storage(address, key) => {
+                    let value = state_provider.storage(address, key)?;
+                    self.storage.write().insert((address, key), value);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// State that is read ahead of a block's execution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PrewarmTarget {
+    /// An account and its bytecode.
+    Account(Address),
+    /// A storage slot of an account.
+    Storage(Address, StorageKey),
+}
+
+/// Returns the state the block is likely going to read, in the order execution reads it.
+fn prewarm_targets(block: &BlockWithSenders) -> Vec<PrewarmTarget> {
+    let mut seen = HashSet::new();
+    let mut targets = Vec::new();
+    let mut push = |target| {
+        if seen.insert(target) {
+            targets.push(target);
+        }
+    };
+
+    push(PrewarmTarget::Account(block.beneficiary));
+    for (sender, transaction) in block.transactions_with_sender() {
+        push(PrewarmTarget::Account(*sender));
+        if let Some(to) = transaction.to() {
+            push(PrewarmTarget::Account(to));
+        }
+        for item in transaction.access_list().iter().flat_map(|access_list| access_list.0.iter()) {
+            push(PrewarmTarget::Account(item.address));
+            for key in &item.storage_keys {
+                push(PrewarmTarget::Storage(item.address, *key));
+            }
+        }
+    }
+    for withdrawal in block.withdrawals.iter().flat_map(|withdrawals| withdrawals.iter()) {
+        push(PrewarmTarget::Account(withdrawal.address));
+    }
+
+    targets
+}
+
+/// A state provider that serves reads from the [`PrewarmedState`], if the state was prewarmed,
+/// and from the wrapped state provider otherwise.
+///
+/// The wrapped state provider must provide the state the [`PrewarmedState`] was read at.
+#[derive(Debug)]
+pub(crate) struct PrewarmedStateProvider<'a, SP> {
+    /// The inner state provider.
+    state_provider: SP,
+    /// The state read ahead of execution.
+    prewarmed: &'a PrewarmedState,
+    /// Prewarm metrics.
+    metrics: PrewarmMetrics,
+}
+
+impl<'a, SP> PrewarmedStateProvider<'a, SP> {
+    /// Creates a new state provider over the given prewarmed state.
+    pub(crate) fn new(state_provider: SP, prewarmed: &'a PrewarmedState) -> Self {
+        Self { state_provider, prewarmed, metrics: PrewarmMetrics::default() }
+    }
+
+    /// Returns the prewarmed value, and records whether the value was prewarmed.
+    fn lookup<K, V>(&self, map: &RwLock<HashMap<K, V>>, key: &K) -> Option<V>
+    where
+        K: Eq + std::hash::Hash,
+        V: Clone,
+    {
+        let value = map.read().get(key).cloned();
+        if value.is_some() {
+            self.metrics.hits.increment(1);
+        } else {
+            self.metrics.misses.increment(1);
+        }
+        value
+    }
+}
+
+impl<'a, SP: StateProvider> BlockHashReader for PrewarmedStateProvider<'a, SP> {
+    fn block_hash(&self, block_number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.state_provider.block_hash(block_number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.state_provider.canonical_hashes_range(start, end)
+    }
+}
+
+impl<'a, SP: StateProvider> AccountReader for PrewarmedStateProvider<'a, SP> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.lookup(&self.prewarmed.accounts, &address) {
+            return Ok(account)
+        }
+        self.state_provider.basic_account(address)
+    }
+}
+
+impl<'a, SP: StateProvider> StateRootProvider for PrewarmedStateProvider<'a, SP> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.state_provider.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.state_provider.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<'a, SP: StateProvider> StateProvider for PrewarmedStateProvider<'a, SP> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.lookup(&self.prewarmed.storage, &(account, storage_key)) {
+            return Ok(value)
+        }
+        self.state_provider.storage(account, storage_key)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.lookup(&self.prewarmed.bytecodes, &code_hash) {
+            return Ok(bytecode)
+        }
+        self.state_provider.bytecode_by_hash(code_hash)
+    }
+
+    fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof> {
+        self.state_provider.proof(address, keys)
+    }
+
+    fn multiproof(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        self.state_provider.multiproof(targets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Block, Header, Withdrawal, Withdrawals};
+
+    #[test]
+    fn prewarm_targets_deduplicated() {
+        let beneficiary = Address::with_last_byte(1);
+        let block = BlockWithSenders {
+            block: Block {
+                header: Header { beneficiary, ..Default::default() },
+                withdrawals: Some(Withdrawals::new(vec![
+                    Withdrawal { address: beneficiary, ..Default::default() },
+                    Withdrawal { address: Address::with_last_byte(2), ..Default::default() },
+                ])),
+                ..Default::default()
+            },
+            senders: Vec::new(),
+        };
+
+        assert_eq!(
+            prewarm_targets(&block),
+            vec![
+                PrewarmTarget::Account(beneficiary),
+                PrewarmTarget::Account(Address::with_last_byte(2))
+            ]
+        );
+    }
+}
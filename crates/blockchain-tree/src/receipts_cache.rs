@@ -0,0 +1,66 @@
+//! Cache of the receipt encodings of validated blocks.
+//!
+//! The bloom filters and encodings of the receipts of a block are computed to validate its
+//! receipts root. They are kept for the most recently validated blocks, so that they aren't
+//! computed again when the receipts of the block are served once it's canonical.
+
+use parking_lot::Mutex;
+use reth_primitives::{BlockHash, ReceiptEncoding};
+use schnellru::{ByLength, LruMap};
+use std::{fmt, sync::Arc};
+
+/// The number of most recently validated blocks the receipt encodings are cached for.
+pub(crate) const RECEIPT_ENCODINGS_CACHE_BLOCKS: u32 = 128;
+
+/// The receipt encodings of the most recently validated blocks, by block hash.
+pub(crate) struct ReceiptEncodingsCache {
+    blocks: Mutex<LruMap<BlockHash, Arc<[ReceiptEncoding]>, ByLength>>,
+}
+
+impl ReceiptEncodingsCache {
+    /// Creates a new cache that holds the receipt encodings of the given number of blocks.
+    pub(crate) fn new(max_blocks: u32) -> Self {
+        Self { blocks: Mutex::new(LruMap::new(ByLength::new(max_blocks))) }
+    }
+
+    /// Returns the receipt encodings of the block, if they're cached.
+    pub(crate) fn get(&self, block_hash: &BlockHash) -> Option<Arc<[ReceiptEncoding]>> {
+        self.blocks.lock().get(block_hash).cloned()
+    }
+
+    /// Caches the receipt encodings of the validated block.
+    pub(crate) fn insert(&self, block_hash: BlockHash, encodings: Arc<[ReceiptEncoding]>) {
+        self.blocks.lock().insert(block_hash, encodings);
+    }
+}
+
+impl Default for ReceiptEncodingsCache {
+    fn default() -> Self {
+        Self::new(RECEIPT_ENCODINGS_CACHE_BLOCKS)
+    }
+}
+
+impl fmt::Debug for ReceiptEncodingsCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReceiptEncodingsCache").field("blocks", &self.blocks.lock().len()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Receipt;
+
+    #[test]
+    fn evicts_least_recently_validated_blocks() {
+        let cache = ReceiptEncodingsCache::new(2);
+        let encodings: Arc<[ReceiptEncoding]> =
+            vec![ReceiptEncoding::new(&Receipt::default())].into();
+        for number in 0..3 {
+            cache.insert(BlockHash::with_last_byte(number), encodings.clone());
+        }
+
+        assert!(cache.get(&BlockHash::with_last_byte(0)).is_none());
+        assert_eq!(cache.get(&BlockHash::with_last_byte(2)), Some(encodings));
+    }
+}
@@ -1,6 +1,7 @@
 //! Wrapper around `BlockchainTree` that allows for it to be shared.
 
 use super::BlockchainTree;
+use crate::receipts_cache::ReceiptEncodingsCache;
 use parking_lot::RwLock;
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -10,8 +11,8 @@ use reth_blockchain_tree_api::{
 use reth_db_api::database::Database;
 use reth_evm::execute::BlockExecutorProvider;
 use reth_primitives::{
-    BlockHash, BlockNumHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader,
+    BlockHash, BlockNumHash, BlockNumber, Receipt, ReceiptEncoding, SealedBlock,
+    SealedBlockWithSenders, SealedHeader,
 };
 use reth_provider::{
     BlockchainTreePendingStateProvider, CanonStateSubscriptions, FullExecutionDataProvider,
@@ -26,12 +27,16 @@ use tracing::trace;
 pub struct ShareableBlockchainTree<DB, E> {
     /// `BlockchainTree`
     pub tree: Arc<RwLock<BlockchainTree<DB, E>>>,
+    /// The receipt encodings of the most recently validated blocks, which are read without
+    /// locking the tree.
+    receipt_encodings: Arc<ReceiptEncodingsCache>,
 }
 
 impl<DB, E> ShareableBlockchainTree<DB, E> {
     /// Create a new shareable database.
     pub fn new(tree: BlockchainTree<DB, E>) -> Self {
-        Self { tree: Arc::new(RwLock::new(tree)) }
+        let receipt_encodings = tree.receipt_encodings();
+        Self { tree: Arc::new(RwLock::new(tree)), receipt_encodings }
     }
 }
 
@@ -167,6 +172,10 @@ where
         let tree = self.tree.read();
         Some(tree.receipts_by_block_hash(block_hash)?.into_iter().cloned().collect())
     }
+
+    fn receipt_encodings(&self, block_hash: BlockHash) -> Option<Arc<[ReceiptEncoding]>> {
+        self.receipt_encodings.get(&block_hash)
+    }
 }
 
 impl<DB, E> BlockchainTreePendingStateProvider for ShareableBlockchainTree<DB, E>
@@ -0,0 +1,215 @@
+//! Spilling of sidechain states to disk.
+
+use crate::{state::BlockchainId, AppendableChain};
+use reth_codecs::Compact;
+use reth_execution_types::ExecutionOutcome;
+use reth_fs_util as fs;
+use reth_primitives::{BlockNumber, Receipt, Receipts, Requests};
+use reth_revm::db::BundleState;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{collections::HashSet, path::PathBuf};
+use tempfile::TempDir;
+use tracing::debug;
+
+/// Approximate in-memory size of an account or storage slot of a bundle state, including its
+/// reverts.
+const STATE_ENTRY_SIZE: usize = 128;
+
+/// Approximate in-memory size of a receipt without its logs.
+const RECEIPT_SIZE: usize = 64;
+
+/// Keeps the execution outcomes of sidechains on disk once the outcomes held in memory exceed a
+/// byte budget.
+///
+/// The outcome of a spilled chain is replaced with an empty one, and its cached trie updates are
+/// dropped. The outcome has to be restored before the chain is extended or canonicalized, see
+/// [`StateSpill::restore`].
+#[derive(Debug)]
+pub(crate) struct StateSpill {
+    /// Maximum estimated size of the execution outcomes held in memory.
+    max_bytes: usize,
+    /// Temporary directory holding the spilled outcomes, created on the first spill and removed on
+    /// drop.
+    directory: Option<TempDir>,
+    /// The chains whose execution outcome is spilled.
+    spilled: HashSet<BlockchainId>,
+}
+
+impl StateSpill {
+    /// Creates a new spill with the given byte budget for the execution outcomes held in memory.
+    pub(crate) fn new(max_bytes: usize) -> Self {
+        Self { max_bytes, directory: None, spilled: HashSet::new() }
+    }
+
+    /// Returns the byte budget for the execution outcomes held in memory.
+    pub(crate) const fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Returns the number of spilled chains.
+    pub(crate) fn len(&self) -> usize {
+        self.spilled.len()
+    }
+
+    /// Returns `true` if the execution outcome of the chain is spilled.
+    pub(crate) fn is_spilled(&self, chain_id: &BlockchainId) -> bool {
+        self.spilled.contains(chain_id)
+    }
+
+    /// Writes the execution outcome of the chain to disk and removes it from memory.
+    pub(crate) fn spill(
+        &mut self,
+        chain_id: BlockchainId,
+        chain: &mut AppendableChain,
+    ) -> ProviderResult<()> {
+        if self.is_spilled(&chain_id) {
+            return Ok(())
+        }
+        if self.directory.is_none() {
+            let directory = tempfile::Builder::new()
+                .prefix("reth-tree-state")
+                .tempdir()
+                .map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+            self.directory = Some(directory);
+        }
+
+        let path = self.path(chain_id).expect("directory exists");
+        let outcome = encode_outcome(chain.execution_outcome())
+            .map_err(|err| ProviderError::FsPathError(err.to_string()))?;
+        fs::write(&path, outcome)?;
+
+        debug!(target: "blockchain_tree", ?chain_id, tip = ?chain.tip().num_hash(), ?path, "Spilled sidechain state to disk");
+        *chain.execution_outcome_mut() = ExecutionOutcome::default();
+        chain.clear_trie_updates();
+        self.spilled.insert(chain_id);
+        Ok(())
+    }
+
+    /// Reads the spilled execution outcome of the chain, without restoring it.
+    ///
+    /// Returns `None` if the chain is not spilled.
+    pub(crate) fn read(&self, chain_id: BlockchainId) -> ProviderResult<Option<ExecutionOutcome>> {
+        if !self.is_spilled(&chain_id) {
+            return Ok(None)
+        }
+        let path = self.path(chain_id).expect("directory exists");
+        let outcome = decode_outcome(&fs::read(&path)?)
+            .map_err(|err| ProviderError::FsPathError(format!("{}: {err}", path.display())))?;
+        Ok(Some(outcome))
+    }
+
+    /// Moves the spilled execution outcome of the chain back into memory, if the chain is spilled.
+    ///
+    /// Returns `true` if the execution outcome was restored.
+    pub(crate) fn restore(
+        &mut self,
+        chain_id: BlockchainId,
+        chain: &mut AppendableChain,
+    ) -> ProviderResult<bool> {
+        let Some(outcome) = self.read(chain_id)? else { return Ok(false) };
+        *chain.execution_outcome_mut() = outcome;
+        self.discard(chain_id);
+        debug!(target: "blockchain_tree", ?chain_id, tip = ?chain.tip().num_hash(), "Restored sidechain state from disk");
+        Ok(true)
+    }
+
+    /// Removes the spilled execution outcome of the chain, if any.
+    pub(crate) fn discard(&mut self, chain_id: BlockchainId) {
+        if self.spilled.remove(&chain_id) {
+            if let Some(path) = self.path(chain_id) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    fn path(&self, chain_id: BlockchainId) -> Option<PathBuf> {
+        let directory = self.directory.as_ref()?;
+        Some(directory.path().join(format!("{}.bin", u64::from(chain_id))))
+    }
+}
+
+/// An execution outcome as it's spilled to disk: its bundle state, receipts and first block, and
+/// the compact encoded requests of each block.
+///
+/// The requests are encoded separately, since their serde encoding can't be decoded by bincode.
+type SpilledOutcome = (BundleState, Receipts, BlockNumber, Vec<Vec<u8>>);
+
+/// Encodes the execution outcome with bincode, see [`SpilledOutcome`].
+fn encode_outcome(outcome: &ExecutionOutcome) -> bincode::Result<Vec<u8>> {
+    let requests = outcome
+        .requests
+        .iter()
+        .map(|requests| {
+            let mut buf = Vec::new();
+            requests.clone().to_compact(&mut buf);
+            buf
+        })
+        .collect::<Vec<_>>();
+    bincode::serialize(&(&outcome.bundle, &outcome.receipts, outcome.first_block, requests))
+}
+
+/// Decodes an execution outcome encoded with [`encode_outcome`].
+fn decode_outcome(bytes: &[u8]) -> bincode::Result<ExecutionOutcome> {
+    let (bundle, receipts, first_block, requests): SpilledOutcome = bincode::deserialize(bytes)?;
+    let requests =
+        requests.iter().map(|buf| Requests::from_compact(buf, buf.len()).0).collect::<Vec<_>>();
+    Ok(ExecutionOutcome::new(bundle, receipts, first_block, requests))
+}
+
+/// Returns the estimated in-memory size of the bundle state of the execution outcome in bytes.
+pub(crate) fn estimated_bundle_size(outcome: &ExecutionOutcome) -> usize {
+    let state = outcome.state();
+    let contracts = state.contracts.values().map(|bytecode| bytecode.len()).sum::<usize>();
+    state.size_hint() * STATE_ENTRY_SIZE + contracts
+}
+
+/// Returns the estimated in-memory size of the receipts in bytes.
+pub(crate) fn estimated_receipts_size<'a>(
+    receipts: impl IntoIterator<Item = &'a Option<Receipt>>,
+) -> usize {
+    receipts
+        .into_iter()
+        .flatten()
+        .map(|receipt| {
+            let logs = receipt
+                .logs
+                .iter()
+                .map(|log| STATE_ENTRY_SIZE + log.data.data.len())
+                .sum::<usize>();
+            RECEIPT_SIZE + logs
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, Bytes, Log, Request, TxType, B256};
+
+    #[test]
+    fn outcome_encoding_roundtrip() {
+        let receipt = Receipt {
+            tx_type: TxType::Eip1559,
+            success: true,
+            cumulative_gas_used: 21_000,
+            logs: vec![Log::new_unchecked(
+                Address::repeat_byte(0x01),
+                vec![B256::repeat_byte(0x02)],
+                Bytes::from_static(&[0x03]),
+            )],
+            #[cfg(feature = "optimism")]
+            deposit_nonce: None,
+            #[cfg(feature = "optimism")]
+            deposit_receipt_version: None,
+        };
+        let outcome = ExecutionOutcome::new(
+            BundleState::default(),
+            Receipts::from(vec![vec![Some(receipt), None]]),
+            1,
+            vec![Requests(vec![Request::DepositRequest(Default::default())])],
+        );
+
+        let encoded = encode_outcome(&outcome).unwrap();
+        assert_eq!(decode_outcome(&encoded).unwrap(), outcome);
+    }
+}
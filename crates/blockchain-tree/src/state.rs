@@ -1,8 +1,15 @@
 //! Blockchain tree state.
 
-use crate::{AppendableChain, BlockBuffer, BlockIndices};
+use crate::{
+    block_buffer::estimated_block_size,
+    spill::{estimated_bundle_size, estimated_receipts_size, StateSpill},
+    AppendableChain, BlockBuffer, BlockIndices,
+};
+use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{BlockHash, BlockNumber, Receipt, SealedBlock, SealedBlockWithSenders};
+use reth_storage_errors::provider::ProviderResult;
 use std::collections::{BTreeMap, HashMap};
+use tracing::debug;
 
 /// Container to hold the state of the blockchain tree.
 #[derive(Debug)]
@@ -18,6 +25,18 @@ pub(crate) struct TreeState {
     pub(crate) block_indices: BlockIndices,
     /// Unconnected block buffer.
     pub(crate) buffered_blocks: BlockBuffer,
+    /// Spilled sidechain states, if a budget for the sidechain states held in memory is set.
+    state_spill: Option<StateSpill>,
+    /// Counter that orders the uses of the chains.
+    use_counter: u64,
+    /// The value of the use counter at the last insertion or extension of each chain.
+    last_used: HashMap<BlockchainId, u64>,
+    /// Estimated size of each chain.
+    chain_sizes: HashMap<BlockchainId, ChainSize>,
+    /// Estimated size of the blocks of all chains in bytes.
+    blocks_size: usize,
+    /// Estimated size of the execution outcomes of all chains held in memory in bytes.
+    states_size: usize,
 }
 
 impl TreeState {
@@ -27,7 +46,13 @@ impl TreeState {
         last_finalized_block_number: BlockNumber,
         last_canonical_hashes: impl IntoIterator<Item = (BlockNumber, BlockHash)>,
         buffer_limit: u32,
+        max_buffer_bytes: Option<usize>,
+        max_sidechain_state_bytes: Option<usize>,
     ) -> Self {
+        let mut buffered_blocks = BlockBuffer::new(buffer_limit);
+        if let Some(max_bytes) = max_buffer_bytes {
+            buffered_blocks = buffered_blocks.with_max_bytes(max_bytes);
+        }
         Self {
             block_chain_id_generator: 0,
             chains: Default::default(),
@@ -35,7 +60,13 @@ impl TreeState {
                 last_finalized_block_number,
                 BTreeMap::from_iter(last_canonical_hashes),
             ),
-            buffered_blocks: BlockBuffer::new(buffer_limit),
+            buffered_blocks,
+            state_spill: max_sidechain_state_bytes.map(StateSpill::new),
+            use_counter: 0,
+            last_used: HashMap::new(),
+            chain_sizes: HashMap::new(),
+            blocks_size: 0,
+            states_size: 0,
         }
     }
 
@@ -75,13 +106,201 @@ impl TreeState {
 
     /// Returns the block's receipts with matching hash from any side-chain.
     ///
-    /// Caution: This will not return blocks from the canonical chain.
+    /// Caution: This will not return blocks from the canonical chain, nor from chains whose state
+    /// was spilled to disk.
     pub(crate) fn receipts_by_block_hash(&self, block_hash: BlockHash) -> Option<Vec<&Receipt>> {
         let id = self.block_indices.get_block_chain_id(&block_hash)?;
+        if self.is_spilled(&id) {
+            return None
+        }
         let chain = self.chains.get(&id)?;
         chain.receipts_by_block_hash(block_hash)
     }
 
+    /// Returns the execution outcome of the chain at the given block, reading it from disk if the
+    /// state of the chain was spilled.
+    pub(crate) fn execution_outcome_at_block(
+        &self,
+        chain_id: BlockchainId,
+        block_number: BlockNumber,
+    ) -> Option<ExecutionOutcome> {
+        let chain = self.chains.get(&chain_id)?;
+        let Some(state_spill) =
+            self.state_spill.as_ref().filter(|spill| spill.is_spilled(&chain_id))
+        else {
+            return chain.execution_outcome_at_block(block_number)
+        };
+
+        if !chain.blocks().contains_key(&block_number) {
+            return None
+        }
+        let mut execution_outcome = state_spill
+            .read(chain_id)
+            .inspect_err(|err| {
+                debug!(target: "blockchain_tree", ?chain_id, %err, "Failed to read spilled state");
+            })
+            .ok()??;
+        if chain.tip().number != block_number {
+            execution_outcome.revert_to(block_number);
+        }
+        Some(execution_outcome)
+    }
+
+    /// Returns `true` if the state of the chain was spilled to disk.
+    pub(crate) fn is_spilled(&self, chain_id: &BlockchainId) -> bool {
+        self.state_spill.as_ref().map_or(false, |spill| spill.is_spilled(chain_id))
+    }
+
+    /// Returns the number of chains whose state was spilled to disk.
+    pub(crate) fn spilled_chains(&self) -> usize {
+        self.state_spill.as_ref().map_or(0, StateSpill::len)
+    }
+
+    /// Moves the state of the chain with the given ID back into memory if it was spilled to disk.
+    ///
+    /// This must be called before the state of the chain is used or modified.
+    pub(crate) fn restore_chain(&mut self, chain_id: BlockchainId) -> ProviderResult<()> {
+        let (Some(state_spill), Some(chain)) =
+            (self.state_spill.as_mut(), self.chains.get_mut(&chain_id))
+        else {
+            return Ok(())
+        };
+        if state_spill.restore(chain_id, chain)? {
+            self.update_chain_size(chain_id);
+        }
+        Ok(())
+    }
+
+    /// Removes the chain with the given ID from the tree, restoring its state if it was spilled to
+    /// disk.
+    ///
+    /// Note: this does not update the block indices.
+    pub(crate) fn remove_chain(
+        &mut self,
+        chain_id: BlockchainId,
+    ) -> ProviderResult<Option<AppendableChain>> {
+        self.restore_chain(chain_id)?;
+        self.set_chain_size(chain_id, None);
+        Ok(self.chains.remove(&chain_id))
+    }
+
+    /// Removes the chain with the given ID from the tree, discarding its state if it was spilled
+    /// to disk.
+    ///
+    /// Note: this does not update the block indices.
+    pub(crate) fn discard_chain(&mut self, chain_id: BlockchainId) -> Option<AppendableChain> {
+        if let Some(state_spill) = self.state_spill.as_mut() {
+            state_spill.discard(chain_id);
+        }
+        self.set_chain_size(chain_id, None);
+        self.chains.remove(&chain_id)
+    }
+
+    /// Spills the states of the chains to disk until the states held in memory fit into the
+    /// configured budget.
+    ///
+    /// The chains with the lowest tips are spilled first, since they are the least likely to
+    /// become canonical. The state of the chain with the `keep` ID is never spilled.
+    pub(crate) fn spill_states(&mut self, keep: BlockchainId) -> ProviderResult<()> {
+        let Some(state_spill) = self.state_spill.as_mut() else { return Ok(()) };
+        if self.states_size <= state_spill.max_bytes() {
+            return Ok(())
+        }
+
+        let mut in_memory = self
+            .chains
+            .iter()
+            .filter(|(id, _)| **id != keep && !state_spill.is_spilled(id))
+            .map(|(id, chain)| (chain.tip().number, *id))
+            .collect::<Vec<_>>();
+        in_memory.sort_unstable();
+        for (_, chain_id) in in_memory {
+            if self.states_size <= state_spill.max_bytes() {
+                break
+            }
+            let chain = self.chains.get_mut(&chain_id).expect("chain exists");
+            state_spill.spill(chain_id, chain)?;
+            if let Some(size) = self.chain_sizes.get_mut(&chain_id) {
+                self.states_size -= size.state();
+                size.clear_state();
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the estimated size of the blocks and the in-memory execution outcomes of all chains
+    /// in bytes.
+    pub(crate) const fn chains_size(&self) -> usize {
+        self.blocks_size + self.states_size
+    }
+
+    /// Recomputes the estimated size of the chain with the given ID.
+    ///
+    /// This has to be called whenever the blocks or the in-memory execution outcome of the chain
+    /// change, see [`TreeState::update_appended_chain_size`] for chains that were extended.
+    pub(crate) fn update_chain_size(&mut self, chain_id: BlockchainId) {
+        let size = self.chains.get(&chain_id).map(ChainSize::new);
+        self.set_chain_size(chain_id, size);
+    }
+
+    /// Updates the estimated size of the chain with the given ID after a block was appended to it.
+    ///
+    /// Unlike [`TreeState::update_chain_size`], this only adds the size of the appended block and
+    /// its receipts to the size of the chain.
+    pub(crate) fn update_appended_chain_size(&mut self, chain_id: BlockchainId) {
+        let Some(chain) = self.chains.get(&chain_id) else { return };
+        let mut size = self.chain_sizes.get(&chain_id).copied().unwrap_or_default();
+        size.blocks += estimated_block_size(chain.tip());
+        size.receipts +=
+            chain.execution_outcome().receipts().last().map_or(0, estimated_receipts_size);
+        size.bundle = estimated_bundle_size(chain.execution_outcome());
+        self.set_chain_size(chain_id, Some(size));
+    }
+
+    /// Returns the estimated size of all chains in bytes, recomputed from their blocks and
+    /// execution outcomes.
+    #[cfg(test)]
+    pub(crate) fn recompute_chains_size(&self) -> usize {
+        self.chains
+            .values()
+            .map(|chain| {
+                let size = ChainSize::new(chain);
+                size.blocks + size.state()
+            })
+            .sum()
+    }
+
+    /// Replaces the estimated size of the chain with the given ID, removing it if `None`.
+    fn set_chain_size(&mut self, chain_id: BlockchainId, size: Option<ChainSize>) {
+        let previous = match size {
+            Some(size) => self.chain_sizes.insert(chain_id, size),
+            None => self.chain_sizes.remove(&chain_id),
+        };
+        if let Some(previous) = previous {
+            self.blocks_size -= previous.blocks;
+            self.states_size -= previous.state();
+        }
+        if let Some(size) = size {
+            self.blocks_size += size.blocks;
+            self.states_size += size.state();
+        }
+    }
+
+    /// Marks the chain as the most recently used one.
+    pub(crate) fn touch_chain(&mut self, chain_id: BlockchainId) {
+        self.use_counter += 1;
+        self.last_used.insert(chain_id, self.use_counter);
+    }
+
+    /// Returns the IDs of all chains, from the least to the most recently used one.
+    pub(crate) fn least_recently_used_chains(&mut self) -> Vec<BlockchainId> {
+        self.last_used.retain(|id, _| self.chains.contains_key(id));
+        let mut chains = self.chains.keys().copied().collect::<Vec<_>>();
+        chains
+            .sort_unstable_by_key(|id| (self.last_used.get(id).copied().unwrap_or_default(), *id));
+        chains
+    }
+
     /// Insert a chain into the tree.
     ///
     /// Inserts a chain into the tree and builds the block indices.
@@ -94,6 +313,8 @@ impl TreeState {
         self.block_indices.insert_chain(chain_id, &chain);
         // add chain_id -> chain index
         self.chains.insert(chain_id, chain);
+        self.update_chain_size(chain_id);
+        self.touch_chain(chain_id);
         Some(chain_id)
     }
 
@@ -111,6 +332,40 @@ impl TreeState {
     }
 }
 
+/// Estimated in-memory size of a chain in bytes.
+#[derive(Debug, Clone, Copy, Default)]
+struct ChainSize {
+    /// Size of the blocks of the chain.
+    blocks: usize,
+    /// Size of the receipts of the execution outcome held in memory.
+    receipts: usize,
+    /// Size of the bundle state of the execution outcome held in memory.
+    bundle: usize,
+}
+
+impl ChainSize {
+    /// Estimates the size of the chain.
+    fn new(chain: &AppendableChain) -> Self {
+        let outcome = chain.execution_outcome();
+        Self {
+            blocks: chain.blocks().values().map(estimated_block_size).sum(),
+            receipts: estimated_receipts_size(outcome.receipts().iter().flatten()),
+            bundle: estimated_bundle_size(outcome),
+        }
+    }
+
+    /// Returns the size of the execution outcome held in memory.
+    const fn state(&self) -> usize {
+        self.receipts + self.bundle
+    }
+
+    /// Drops the size of the execution outcome, once it's no longer held in memory.
+    fn clear_state(&mut self) {
+        self.receipts = 0;
+        self.bundle = 0;
+    }
+}
+
 /// The ID of a sidechain internally in a [`BlockchainTree`][super::BlockchainTree].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub struct BlockchainId(u64);
@@ -20,8 +20,8 @@ use reth_ethereum_forks::{
 use reth_network_peers::NodeRecord;
 use reth_primitives_traits::{
     constants::{
-        EIP1559_INITIAL_BASE_FEE, EMPTY_OMMER_ROOT_HASH, EMPTY_RECEIPTS, EMPTY_TRANSACTIONS,
-        EMPTY_WITHDRAWALS,
+        ALLOWED_FUTURE_BLOCK_TIME_SECONDS, EIP1559_INITIAL_BASE_FEE, EMPTY_OMMER_ROOT_HASH,
+        EMPTY_RECEIPTS, EMPTY_TRANSACTIONS, EMPTY_WITHDRAWALS, MAXIMUM_EXTRA_DATA_SIZE,
     },
     Header, SealedHeader,
 };
@@ -65,6 +65,8 @@ pub static MAINNET: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 3500,
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
+        max_future_timestamp_drift: ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
     }
     .into()
 });
@@ -89,6 +91,8 @@ pub static GOERLI: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
+        max_future_timestamp_drift: ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
     }
     .into()
 });
@@ -113,6 +117,8 @@ pub static SEPOLIA: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
+        max_future_timestamp_drift: ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
     }
     .into()
 });
@@ -135,6 +141,8 @@ pub static HOLESKY: Lazy<Arc<ChainSpec>> = Lazy::new(|| {
         )),
         base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 1700,
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
+        max_future_timestamp_drift: ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
     }
     .into()
 });
@@ -353,6 +361,15 @@ pub struct ChainSpec {
     /// the amount of blocks between pruner runs to account for the difference in amount of new
     /// data coming in.
     pub prune_delete_limit: usize,
+
+    /// The maximum size of the extra data of a header, in bytes.
+    ///
+    /// Ethereum allows at most [`MAXIMUM_EXTRA_DATA_SIZE`] bytes, but some custom chains use
+    /// larger extra data.
+    pub max_extra_data_size: usize,
+
+    /// How many seconds the timestamp of a pre-merge header may be ahead of the system clock.
+    pub max_future_timestamp_drift: u64,
 }
 
 impl Default for ChainSpec {
@@ -366,6 +383,8 @@ impl Default for ChainSpec {
             deposit_contract: Default::default(),
             base_fee_params: BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
             prune_delete_limit: MAINNET.prune_delete_limit,
+            max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
+            max_future_timestamp_drift: ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
         }
     }
 }
@@ -646,6 +665,12 @@ impl ChainSpec {
         self.is_fork_active_at_timestamp(Hardfork::Prague, timestamp)
     }
 
+    /// Convenience method to check if [`Hardfork::Osaka`] is active at a given timestamp.
+    #[inline]
+    pub fn is_osaka_active_at_timestamp(&self, timestamp: u64) -> bool {
+        self.is_fork_active_at_timestamp(Hardfork::Osaka, timestamp)
+    }
+
     /// Convenience method to check if [`Hardfork::Byzantium`] is active at a given block number.
     #[inline]
     pub fn is_byzantium_active_at_block(&self, block_number: u64) -> bool {
@@ -875,6 +900,11 @@ impl From<Genesis> for ChainSpec {
             (Hardfork::Shanghai, genesis.config.shanghai_time),
             (Hardfork::Cancun, genesis.config.cancun_time),
             (Hardfork::Prague, genesis.config.prague_time),
+            // not part of the genesis config yet, as the fork is experimental
+            (
+                Hardfork::Osaka,
+                genesis.config.extra_fields.get("osakaTime").and_then(|value| value.as_u64()),
+            ),
             #[cfg(feature = "optimism")]
             (Hardfork::Regolith, optimism_genesis_info.regolith_time),
             #[cfg(feature = "optimism")]
@@ -902,6 +932,20 @@ impl From<Genesis> for ChainSpec {
             DepositContract { address, block: 0, topic: MAINNET_DEPOSIT_CONTRACT.topic }
         });
 
+        // not part of the genesis config, custom chains can relax the header validation rules
+        let max_extra_data_size = genesis
+            .config
+            .extra_fields
+            .get("maxExtraDataSize")
+            .and_then(|value| value.as_u64())
+            .map_or(MAXIMUM_EXTRA_DATA_SIZE, |size| size as usize);
+        let max_future_timestamp_drift = genesis
+            .config
+            .extra_fields
+            .get("maxFutureTimestampDrift")
+            .and_then(|value| value.as_u64())
+            .unwrap_or(ALLOWED_FUTURE_BLOCK_TIME_SECONDS);
+
         Self {
             chain: genesis.config.chain_id.into(),
             genesis,
@@ -911,6 +955,8 @@ impl From<Genesis> for ChainSpec {
             deposit_contract,
             #[cfg(feature = "optimism")]
             base_fee_params: optimism_genesis_info.base_fee_params,
+            max_extra_data_size,
+            max_future_timestamp_drift,
             ..Default::default()
         }
     }
@@ -1061,6 +1107,20 @@ impl ChainSpecBuilder {
         self
     }
 
+    /// Enable Prague at genesis.
+    pub fn prague_activated(mut self) -> Self {
+        self = self.cancun_activated();
+        self.hardforks.insert(Hardfork::Prague, ForkCondition::Timestamp(0));
+        self
+    }
+
+    /// Enable Osaka at genesis.
+    pub fn osaka_activated(mut self) -> Self {
+        self = self.prague_activated();
+        self.hardforks.insert(Hardfork::Osaka, ForkCondition::Timestamp(0));
+        self
+    }
+
     /// Enable Bedrock at genesis
     #[cfg(feature = "optimism")]
     pub fn bedrock_activated(mut self) -> Self {
@@ -2629,6 +2689,35 @@ Post-merge hard forks (timestamp based):
         assert_eq!(genesis.config.prague_time, Some(4662));
     }
 
+    #[test]
+    fn test_parse_osaka_genesis() {
+        let s = r#"{"config":{"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":0,"pragueTime":0,"osakaTime":4663},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let chainspec = ChainSpec::from(genesis);
+
+        assert_eq!(
+            chainspec.hardforks.get(&Hardfork::Osaka).unwrap(),
+            &ForkCondition::Timestamp(4663)
+        );
+        assert!(chainspec.is_prague_active_at_timestamp(4662));
+        assert!(!chainspec.is_osaka_active_at_timestamp(4662));
+        assert!(chainspec.is_osaka_active_at_timestamp(4663));
+    }
+
+    #[test]
+    fn test_parse_header_validation_extras_genesis() {
+        let s = r#"{"config":{"chainId":1337,"homesteadBlock":0,"londonBlock":0,"maxExtraDataSize":97,"maxFutureTimestampDrift":60},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
+        let genesis: Genesis = serde_json::from_str(s).unwrap();
+        let chainspec = ChainSpec::from(genesis);
+        assert_eq!(chainspec.max_extra_data_size, 97);
+        assert_eq!(chainspec.max_future_timestamp_drift, 60);
+
+        // defaults to the ethereum rules
+        let chainspec = ChainSpec::from(Genesis::default());
+        assert_eq!(chainspec.max_extra_data_size, MAXIMUM_EXTRA_DATA_SIZE);
+        assert_eq!(chainspec.max_future_timestamp_drift, ALLOWED_FUTURE_BLOCK_TIME_SECONDS);
+    }
+
     #[test]
     fn test_parse_cancun_genesis_all_formats() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0,"cancunTime":4661},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x3b9aca00"}"#;
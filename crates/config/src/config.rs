@@ -2,6 +2,7 @@
 
 use reth_network::{PeersConfig, SessionsConfig};
 use reth_prune_types::PruneModes;
+use reth_rpc_server_types::GasPriceOracleConfig;
 use serde::{Deserialize, Deserializer, Serialize};
 use std::{
     ffi::OsStr,
@@ -25,6 +26,10 @@ pub struct Config {
     pub peers: PeersConfig,
     /// Configuration for peer sessions.
     pub sessions: SessionsConfig,
+    /// Configuration for the gas price oracle of the RPC servers, the `--gpo` arguments that are
+    /// set take precedence.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_price_oracle: Option<GasPriceOracleConfig>,
 }
 
 impl Config {
@@ -114,6 +119,12 @@ pub struct HeadersConfig {
     /// Maximum amount of responses to buffer internally.
     /// The response contains multiple headers.
     pub downloader_max_buffered_responses: usize,
+    /// The maximum size of the temporary file that buffered responses are written to once
+    /// `downloader_max_buffered_responses` is reached, instead of waiting for buffered responses
+    /// to be processed.
+    ///
+    /// Default: 0, nothing is written to disk
+    pub downloader_max_spilled_size_bytes: usize,
     /// The maximum number of headers to request from a peer at a time.
     pub downloader_request_limit: u64,
     /// The maximum number of headers to download before committing progress to the database.
@@ -128,6 +139,7 @@ impl Default for HeadersConfig {
             downloader_max_concurrent_requests: 100,
             downloader_min_concurrent_requests: 5,
             downloader_max_buffered_responses: 100,
+            downloader_max_spilled_size_bytes: 0,
         }
     }
 }
@@ -148,6 +160,12 @@ pub struct BodiesConfig {
     ///
     /// Default: 2GB
     pub downloader_max_buffered_blocks_size_bytes: usize,
+    /// The maximum size of the temporary file that buffered blocks are written to once
+    /// `downloader_max_buffered_blocks_size_bytes` is reached, instead of waiting for buffered
+    /// blocks to be processed.
+    ///
+    /// Default: 0, nothing is written to disk
+    pub downloader_max_spilled_size_bytes: usize,
     /// The minimum number of requests to send concurrently.
     ///
     /// Default: 5
@@ -165,6 +183,7 @@ impl Default for BodiesConfig {
             downloader_request_limit: 200,
             downloader_stream_batch_size: 1_000,
             downloader_max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
+            downloader_max_spilled_size_bytes: 0,
             downloader_min_concurrent_requests: 5,
             downloader_max_concurrent_requests: 100,
         }
@@ -355,6 +374,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::{Config, EXTENSION};
+    use reth_rpc_server_types::{gas_oracle::PriorityFeeStrategy, GasPriceOracleConfig};
     use std::time::Duration;
 
     fn with_tempdir(filename: &str, proc: fn(&std::path::Path)) {
@@ -723,4 +743,24 @@ connect_trusted_nodes_only = true
         let conf: Config = toml::from_str(trusted_nodes_only).unwrap();
         assert!(conf.peers.trusted_nodes_only);
     }
+
+    #[test]
+    fn test_gas_price_oracle() {
+        let conf: Config = toml::from_str("").unwrap();
+        assert!(conf.gas_price_oracle.is_none());
+
+        let gas_price_oracle = r#"
+[gas_price_oracle]
+strategy = "gasWeighted"
+blobFeeBlocks = 5
+"#;
+        let conf: Config = toml::from_str(gas_price_oracle).unwrap();
+        let gas_price_oracle = conf.gas_price_oracle.clone().unwrap();
+        assert_eq!(gas_price_oracle.strategy, PriorityFeeStrategy::GasWeighted);
+        assert_eq!(gas_price_oracle.blob_fee_blocks, 5);
+        assert_eq!(gas_price_oracle.percentile, GasPriceOracleConfig::default().percentile);
+
+        let loaded: Config = toml::from_str(&toml::to_string(&conf).unwrap()).unwrap();
+        assert_eq!(loaded, conf);
+    }
 }
@@ -0,0 +1,343 @@
+//! A local consensus client that drives the engine with realistic slot timing.
+
+use reth_beacon_consensus::{
+    BeaconConsensusEngineHandle, BeaconForkChoiceUpdateError, BeaconOnNewPayloadError,
+};
+use reth_chainspec::ChainSpec;
+use reth_engine_primitives::{BuiltPayload, EngineTypes, PayloadAttributes};
+use reth_payload_builder::{error::PayloadBuilderError, PayloadId, PayloadStore};
+use reth_primitives::{Address, SealedBlock, SealedHeader, B256};
+use reth_provider::{BlockReaderIdExt, ProviderError};
+use reth_rpc_types::engine::{
+    CancunPayloadFields, ForkchoiceState, PayloadAttributes as EthPayloadAttributes,
+    PayloadStatusEnum,
+};
+use reth_rpc_types_compat::engine::payload::block_to_payload;
+use reth_transaction_pool::TransactionPool;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::{Instant, MissedTickBehavior};
+use tracing::{debug, info, warn};
+
+/// The number of blocks the safe block lags behind the head.
+const SAFE_BLOCK_DISTANCE: u64 = 32;
+
+/// The number of blocks the finalized block lags behind the head.
+const FINALIZED_BLOCK_DISTANCE: u64 = 64;
+
+/// Configures the reorgs injected by the [`LocalConsensusClient`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgConfig {
+    /// The number of blocks between two reorgs.
+    pub every: u64,
+    /// The number of blocks that are reverted by a reorg.
+    ///
+    /// This must not exceed the distance of the safe block to the head, which is 32 blocks.
+    pub depth: u64,
+}
+
+/// A consensus client for local development that proposes a block every slot.
+///
+/// Unlike the [`MiningTask`](crate::MiningTask), which seals blocks itself, this drives the
+/// consensus engine like a beacon node would: at the start of every slot it resolves the payload
+/// that was requested in the previous slot and sends it to the engine as a new payload. It then makes
+/// the block canonical with a forkchoice update, which also requests the payload for the next slot.
+///
+/// If configured, reorgs are injected by building a competing block on the same parent, and making
+/// it canonical after [`ReorgConfig::depth`] blocks have been built on top of the original block.
+#[derive(Debug)]
+pub struct LocalConsensusClient<Engine: EngineTypes, Provider, Pool> {
+    chain_spec: Arc<ChainSpec>,
+    provider: Provider,
+    pool: Pool,
+    engine: BeaconConsensusEngineHandle<Engine>,
+    payload_store: PayloadStore<Engine>,
+    block_time: Duration,
+    empty_blocks: bool,
+    reorg: Option<ReorgConfig>,
+}
+
+impl<Engine, Provider, Pool> LocalConsensusClient<Engine, Provider, Pool>
+where
+    Engine: EngineTypes + 'static,
+    Provider: BlockReaderIdExt,
+    Pool: TransactionPool,
+{
+    /// Creates a new [`LocalConsensusClient`] that proposes a block every `block_time`, including
+    /// empty blocks.
+    pub const fn new(
+        chain_spec: Arc<ChainSpec>,
+        provider: Provider,
+        pool: Pool,
+        engine: BeaconConsensusEngineHandle<Engine>,
+        payload_store: PayloadStore<Engine>,
+        block_time: Duration,
+    ) -> Self {
+        Self {
+            chain_spec,
+            provider,
+            pool,
+            engine,
+            payload_store,
+            block_time,
+            empty_blocks: true,
+            reorg: None,
+        }
+    }
+
+    /// Sets whether blocks are proposed in slots without pending transactions.
+    pub const fn with_empty_blocks(mut self, empty_blocks: bool) -> Self {
+        self.empty_blocks = empty_blocks;
+        self
+    }
+
+    /// Sets the reorgs to inject, if any.
+    pub const fn with_reorgs(mut self, reorg: Option<ReorgConfig>) -> Self {
+        self.reorg = reorg;
+        self
+    }
+
+    /// Runs the client, proposing a block every slot.
+    pub async fn run(self) {
+        let mut head = match self.provider.latest_header() {
+            Ok(Some(head)) => head,
+            Ok(None) => {
+                warn!(target: "consensus::local", "No head block, not proposing blocks");
+                return
+            }
+            Err(err) => {
+                warn!(target: "consensus::local", %err, "Failed to read head block, not proposing blocks");
+                return
+            }
+        };
+        info!(target: "consensus::local", block_time = ?self.block_time, reorg = ?self.reorg, "Proposing blocks");
+
+        let mut slots = tokio::time::interval_at(Instant::now() + self.block_time, self.block_time);
+        slots.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let mut state = SlotState::new(self.reorg);
+        let mut payload = self.request_payload(&head, B256::ZERO).await;
+
+        loop {
+            slots.tick().await;
+
+            // make the competing block canonical once enough blocks were built on top of the
+            // original block
+            if let Some((fork, 0)) = state.reorg {
+                state.reorg = None;
+                info!(target: "consensus::local", number = fork.number, hash = %fork.hash(), old_tip = head.number, "Injecting reorg");
+                head = fork;
+                payload = self.request_payload(&head, B256::ZERO).await;
+                continue
+            }
+
+            if !self.empty_blocks && self.pool.pool_size().pending == 0 {
+                debug!(target: "consensus::local", "No pending transactions, skipping slot");
+                payload = self.request_payload(&head, B256::ZERO).await;
+                continue
+            }
+
+            match self.propose(payload).await {
+                Ok(block) => {
+                    let fork = match state.fork.take() {
+                        Some(fork) => match self.propose(fork).await {
+                            Ok(fork) => Some(fork.header),
+                            Err(err) => {
+                                warn!(target: "consensus::local", %err, "Failed to propose competing block");
+                                None
+                            }
+                        },
+                        None => None,
+                    };
+                    head = block.header;
+                    state.on_block(fork);
+                }
+                Err(err) => {
+                    warn!(target: "consensus::local", %err, "Failed to propose block");
+                    state.fork = None;
+                }
+            }
+
+            // make the new block canonical and request the block for the next slot
+            payload = self.request_payload(&head, B256::ZERO).await;
+            if state.should_fork() {
+                // the competing block only differs in the randomness
+                state.fork = Some(self.request_payload(&head, B256::with_last_byte(1)).await);
+            }
+        }
+    }
+
+    /// Resolves the requested payload and sends it to the engine.
+    async fn propose(
+        &self,
+        payload: Result<PayloadId, LocalConsensusError>,
+    ) -> Result<SealedBlock, LocalConsensusError> {
+        let id = payload?;
+        let payload = self
+            .payload_store
+            .resolve(id)
+            .await
+            .ok_or(LocalConsensusError::MissingPayload(id))??;
+        let block = payload.block().clone();
+
+        let cancun_fields =
+            block.parent_beacon_block_root.map(|parent_beacon_block_root| CancunPayloadFields {
+                parent_beacon_block_root,
+                versioned_hashes: block.blob_versioned_hashes().into_iter().copied().collect(),
+            });
+        let (payload, _) = block_to_payload(block.clone());
+        let status = self.engine.new_payload(payload, cancun_fields).await?;
+        if !status.is_valid() {
+            return Err(LocalConsensusError::Rejected(block.hash(), status.status))
+        }
+        Ok(block)
+    }
+
+    /// Sends a forkchoice update for the head with attributes for the next slot.
+    async fn request_payload(
+        &self,
+        head: &SealedHeader,
+        prev_randao: B256,
+    ) -> Result<PayloadId, LocalConsensusError> {
+        let state = self.forkchoice_state(head)?;
+        let attributes = self
+            .payload_attributes(head, prev_randao)
+            .ok_or(LocalConsensusError::UnsupportedPayloadAttributes)?;
+        let updated = self.engine.fork_choice_updated(state, Some(attributes)).await?;
+        updated
+            .payload_id
+            .ok_or(LocalConsensusError::Rejected(head.hash(), updated.payload_status.status))
+    }
+
+    /// Returns the forkchoice state for the head, with the safe and finalized blocks lagging behind
+    /// like on mainnet.
+    fn forkchoice_state(&self, head: &SealedHeader) -> Result<ForkchoiceState, ProviderError> {
+        let safe = head.number.saturating_sub(SAFE_BLOCK_DISTANCE);
+        let finalized = head.number.saturating_sub(FINALIZED_BLOCK_DISTANCE);
+        Ok(ForkchoiceState {
+            head_block_hash: head.hash(),
+            safe_block_hash: self.provider.block_hash(safe)?.unwrap_or_default(),
+            finalized_block_hash: self.provider.block_hash(finalized)?.unwrap_or_default(),
+        })
+    }
+
+    /// Returns the payload attributes for the block of the next slot.
+    fn payload_attributes(
+        &self,
+        parent: &SealedHeader,
+        prev_randao: B256,
+    ) -> Option<Engine::PayloadAttributes> {
+        let slot = SystemTime::now() + self.block_time;
+        let timestamp = slot.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let timestamp = timestamp.max(parent.timestamp + 1);
+
+        let attributes = EthPayloadAttributes {
+            timestamp,
+            prev_randao,
+            suggested_fee_recipient: Address::ZERO,
+            withdrawals: self.chain_spec.is_shanghai_active_at_timestamp(timestamp).then(Vec::new),
+            parent_beacon_block_root: self
+                .chain_spec
+                .is_cancun_active_at_timestamp(timestamp)
+                .then_some(B256::ZERO),
+        };
+
+        Engine::PayloadAttributes::local(attributes, parent)
+    }
+}
+
+/// Tracks the reorg injection across slots.
+#[derive(Debug)]
+struct SlotState {
+    config: Option<ReorgConfig>,
+    /// The number of blocks until the next competing block is requested.
+    blocks_until_fork: u64,
+    /// The requested competing block.
+    fork: Option<Result<PayloadId, LocalConsensusError>>,
+    /// The competing block and the number of blocks that still have to be built before it is
+    /// made canonical.
+    reorg: Option<(SealedHeader, u64)>,
+}
+
+impl SlotState {
+    fn new(config: Option<ReorgConfig>) -> Self {
+        Self {
+            blocks_until_fork: config.map(|config| config.every).unwrap_or_default(),
+            config,
+            fork: None,
+            reorg: None,
+        }
+    }
+
+    /// Records a new canonical block and the competing block built in the same slot.
+    fn on_block(&mut self, fork: Option<SealedHeader>) {
+        let Some(config) = self.config else { return };
+        if let Some(fork) = fork {
+            self.reorg = Some((fork, config.depth.saturating_sub(1)));
+        } else if let Some((_, remaining)) = &mut self.reorg {
+            *remaining = remaining.saturating_sub(1);
+        } else {
+            self.blocks_until_fork = self.blocks_until_fork.saturating_sub(1);
+        }
+    }
+
+    /// Returns true if a competing block should be requested for the next slot.
+    fn should_fork(&mut self) -> bool {
+        let Some(config) = self.config else { return false };
+        if self.reorg.is_some() || self.blocks_until_fork > 0 {
+            return false
+        }
+        self.blocks_until_fork = config.every;
+        true
+    }
+}
+
+/// Errors of the [`LocalConsensusClient`].
+#[derive(Debug, thiserror::Error)]
+enum LocalConsensusError {
+    #[error(transparent)]
+    ForkchoiceUpdated(#[from] BeaconForkChoiceUpdateError),
+    #[error(transparent)]
+    NewPayload(#[from] BeaconOnNewPayloadError),
+    #[error(transparent)]
+    PayloadBuilder(#[from] PayloadBuilderError),
+    #[error(transparent)]
+    Provider(#[from] ProviderError),
+    #[error("payload attributes of the engine can't be built locally")]
+    UnsupportedPayloadAttributes,
+    #[error("missing payload {0}")]
+    MissingPayload(PayloadId),
+    #[error("block {0} was rejected by the engine: {1:?}")]
+    Rejected(B256, PayloadStatusEnum),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reorg_injection() {
+        let mut state = SlotState::new(Some(ReorgConfig { every: 2, depth: 2 }));
+
+        state.on_block(None);
+        assert!(!state.should_fork());
+        state.on_block(None);
+        assert!(state.should_fork());
+
+        // the competing block is built in the same slot as the first reverted block
+        state.on_block(Some(SealedHeader::default()));
+        assert!(!state.should_fork());
+        assert_eq!(state.reorg.as_ref().map(|(_, remaining)| *remaining), Some(1));
+        state.on_block(None);
+        assert_eq!(state.reorg.as_ref().map(|(_, remaining)| *remaining), Some(0));
+    }
+
+    #[test]
+    fn no_reorg_injection() {
+        let mut state = SlotState::new(None);
+        state.on_block(None);
+        assert!(!state.should_fork());
+    }
+}
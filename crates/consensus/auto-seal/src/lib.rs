@@ -6,6 +6,9 @@
 //!
 //! These downloaders poll the miner, assemble the block, and return transactions that are ready to
 //! be mined.
+//!
+//! Alternatively, the [`LocalConsensusClient`] drives the consensus engine with forkchoice updates
+//! and new payloads every slot, like a beacon node would.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -38,10 +41,12 @@ use tokio::sync::{mpsc::UnboundedSender, RwLock, RwLockReadGuard, RwLockWriteGua
 use tracing::trace;
 
 mod client;
+mod consensus_client;
 mod mode;
 mod task;
 
 pub use crate::client::AutoSealClient;
+pub use consensus_client::{LocalConsensusClient, ReorgConfig};
 pub use mode::{FixedBlockTimeMiner, MiningMode, ReadyTransactionMiner};
 use reth_evm::execute::{BlockExecutionOutput, BlockExecutorProvider, Executor};
 pub use task::MiningTask;
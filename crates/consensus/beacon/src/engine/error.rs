@@ -1,5 +1,6 @@
 use crate::engine::hooks::EngineHookError;
 use reth_errors::{DatabaseError, RethError};
+use reth_primitives::{BlockNumber, B256};
 use reth_rpc_types::engine::ForkchoiceUpdateError;
 use reth_stages_api::PipelineError;
 
@@ -101,3 +102,13 @@ impl BeaconOnNewPayloadError {
         Self::Internal(Box::new(e))
     }
 }
+
+/// Thrown when the engine refuses to advance the canonical chain past the block it's frozen at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("chain is frozen at block {frozen_at}, refusing to advance to block {block_hash}")]
+pub struct ChainFrozenError {
+    /// The block the chain is frozen at.
+    pub frozen_at: BlockNumber,
+    /// The hash of the rejected block.
+    pub block_hash: B256,
+}
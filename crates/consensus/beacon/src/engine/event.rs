@@ -16,6 +16,15 @@ pub enum BeaconConsensusEngineEvent {
     LiveSyncProgress(ConsensusEngineLiveSyncProgress),
     /// A block was added to the fork chain.
     ForkBlockAdded(Arc<SealedBlock>),
+    /// A block failed validation and was marked as invalid.
+    InvalidBlock(Box<SealedHeader>),
+    /// A reorg deeper than the configured maximum depth was rejected until it is confirmed.
+    ReorgNotConfirmed {
+        /// The hash of the new head.
+        head: B256,
+        /// The number of canonical blocks that would be reverted.
+        depth: u64,
+    },
 }
 
 /// Progress of the consensus engine during live sync.
@@ -1,5 +1,6 @@
 use reth_primitives::B256;
 use reth_rpc_types::engine::{ForkchoiceState, PayloadStatusEnum};
+use serde::{Deserialize, Serialize};
 
 /// The struct that keeps track of the received forkchoice state and their status.
 #[derive(Debug, Clone, Default)]
@@ -95,7 +96,8 @@ pub(crate) struct ReceivedForkchoiceState {
 }
 
 /// A simplified representation of [`PayloadStatusEnum`] specifically for FCU.
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ForkchoiceStatus {
     /// The forkchoice state is valid.
     Valid,
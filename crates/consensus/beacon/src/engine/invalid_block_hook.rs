@@ -0,0 +1,21 @@
+use reth_blockchain_tree_api::error::InsertBlockErrorKind;
+use reth_primitives::SealedBlock;
+use std::fmt;
+
+/// A hook that is called when the [`BeaconConsensusEngine`](crate::BeaconConsensusEngine) marks a
+/// block as invalid, e.g. to dump debugging information about the block.
+///
+/// The hook is called synchronously from the engine's main loop, which is blocked until it returns.
+pub trait InvalidBlockHook: Send + Sync + fmt::Debug {
+    /// Called with the block that failed validation and the error it failed with.
+    fn on_invalid_block(&self, block: &SealedBlock, error: &InsertBlockErrorKind);
+}
+
+/// An [`InvalidBlockHook`] that does nothing.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct NoopInvalidBlockHook;
+
+impl InvalidBlockHook for NoopInvalidBlockHook {
+    fn on_invalid_block(&self, _block: &SealedBlock, _error: &InsertBlockErrorKind) {}
+}
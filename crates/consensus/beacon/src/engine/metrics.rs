@@ -13,6 +13,8 @@ pub(crate) struct EngineMetrics {
     pub(crate) forkchoice_updated_messages: Counter,
     /// The total count of new payload messages received.
     pub(crate) new_payload_messages: Counter,
+    /// The total count of forkchoice updates whose head was made canonical in a later batch.
+    pub(crate) deferred_forkchoice_updates: Counter,
     /// Latency for making canonical already canonical block
     pub(crate) make_canonical_already_canonical_latency: Histogram,
     /// Latency for making canonical committed block
@@ -20,8 +20,8 @@ use reth_primitives::{
     B256,
 };
 use reth_provider::{
-    BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider, ProviderError,
-    StageCheckpointReader,
+    BlockIdReader, BlockReader, BlockSource, CanonChainTracker, ChainSpecProvider,
+    FinalizedBlockWriter, ProviderError, StageCheckpointReader,
 };
 use reth_rpc_types::engine::{
     CancunPayloadFields, ExecutionPayload, ForkchoiceState, PayloadStatus, PayloadStatusEnum,
@@ -49,12 +49,15 @@ pub use message::{BeaconEngineMessage, OnForkChoiceUpdated};
 mod error;
 pub use error::{
     BeaconConsensusEngineError, BeaconEngineResult, BeaconForkChoiceUpdateError,
-    BeaconOnNewPayloadError,
+    BeaconOnNewPayloadError, ChainFrozenError,
 };
 
 mod invalid_headers;
 use invalid_headers::InvalidHeaderCache;
 
+mod invalid_block_hook;
+pub use invalid_block_hook::{InvalidBlockHook, NoopInvalidBlockHook};
+
 mod event;
 pub use event::{BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress};
 
@@ -68,6 +71,10 @@ use forkchoice::{ForkchoiceStateHash, ForkchoiceStateTracker};
 mod metrics;
 use metrics::EngineMetrics;
 
+mod persistence;
+use persistence::DeferredHead;
+pub use persistence::PersistenceBatchConfig;
+
 pub(crate) mod sync;
 use sync::{EngineSyncController, EngineSyncEvent};
 
@@ -165,7 +172,8 @@ where
         + BlockReader
         + BlockIdReader
         + CanonChainTracker
-        + StageCheckpointReader,
+        + StageCheckpointReader
+        + FinalizedBlockWriter,
     EngineT: EngineTypes,
 {
     /// Controls syncing triggered by engine updates.
@@ -199,6 +207,14 @@ where
     /// Tracks the header of invalid payloads that were rejected by the engine because they're
     /// invalid.
     invalid_headers: InvalidHeaderCache,
+    /// Called with every block that fails validation.
+    invalid_block_hook: Box<dyn InvalidBlockHook>,
+    /// Limits for batching the persistence of consecutive canonical blocks.
+    persistence_batch: PersistenceBatchConfig,
+    /// The forkchoice update whose head is made canonical once the engine messages are drained.
+    deferred_head: Option<DeferredHead>,
+    /// The block the canonical chain is frozen at, if any.
+    freeze_block: Option<BlockNumber>,
     /// After downloading a block corresponding to a recent forkchoice update, the engine will
     /// check whether or not we can connect the block to the current canonical chain. If we can't,
     /// we need to download and execute the missing parents of that block.
@@ -226,6 +242,7 @@ where
         + BlockIdReader
         + CanonChainTracker
         + StageCheckpointReader
+        + FinalizedBlockWriter
         + ChainSpecProvider
         + 'static,
     Client: HeadersClient + BodiesClient + Clone + Unpin + 'static,
@@ -310,6 +327,10 @@ where
             forkchoice_state_tracker: Default::default(),
             payload_builder,
             invalid_headers: InvalidHeaderCache::new(MAX_INVALID_HEADERS),
+            invalid_block_hook: Box::new(NoopInvalidBlockHook),
+            persistence_batch: PersistenceBatchConfig::default(),
+            deferred_head: None,
+            freeze_block: None,
             blockchain_tree_action: None,
             pending_forkchoice_update: None,
             pipeline_run_threshold,
@@ -331,6 +352,50 @@ where
         Ok((this, handle))
     }
 
+    /// Sets the hook that is called with every block that fails validation.
+    pub fn with_invalid_block_hook(mut self, hook: Box<dyn InvalidBlockHook>) -> Self {
+        self.invalid_block_hook = hook;
+        self
+    }
+
+    /// Sets the limits for batching the persistence of consecutive canonical blocks.
+    ///
+    /// Batching is disabled by default.
+    pub const fn with_persistence_batch(
+        mut self,
+        persistence_batch: PersistenceBatchConfig,
+    ) -> Self {
+        self.persistence_batch = persistence_batch;
+        self
+    }
+
+    /// Freezes the canonical chain at the given block.
+    ///
+    /// Once the chain has reached the block, the engine keeps running without advancing it:
+    /// forkchoice updates and payloads past the block are rejected with a [`ChainFrozenError`].
+    /// Reaching the block doesn't terminate the engine, even if it's the configured max block.
+    pub const fn with_freeze_block(mut self, block: BlockNumber) -> Self {
+        self.freeze_block = Some(block);
+        self
+    }
+
+    /// Returns an error if the chain is frozen and making the block canonical would advance it
+    /// past the block it's frozen at.
+    ///
+    /// Unknown blocks are only rejected once the canonical chain has reached the frozen block,
+    /// since the chain is synced up to it until then.
+    fn ensure_not_frozen(&self, block_hash: B256) -> RethResult<()> {
+        let Some(frozen_at) = self.freeze_block else { return Ok(()) };
+        let past_frozen = match self.blockchain.find_block_by_hash(block_hash, BlockSource::Any)? {
+            Some(block) => block.number > frozen_at,
+            None => self.blockchain.canonical_tip().number >= frozen_at,
+        };
+        if past_frozen {
+            return Err(RethError::other(ChainFrozenError { frozen_at, block_hash }))
+        }
+        Ok(())
+    }
+
     /// Returns current [`EngineHookContext`] that's used for polling engine hooks.
     fn current_engine_hook_context(&self) -> RethResult<EngineHookContext> {
         Ok(EngineHookContext {
@@ -500,6 +565,12 @@ where
         self.blockchain.on_forkchoice_update_received(&state);
         trace!(target: "consensus::engine", ?state, "Received new forkchoice state update");
 
+        if let Err(error) = self.ensure_not_frozen(state.head_block_hash) {
+            warn!(target: "consensus::engine", %error, ?state, "Rejecting forkchoice update");
+            let _ = tx.send(Err(error));
+            return
+        }
+
         match self.pre_validate_forkchoice_update(state) {
             Ok(on_updated_result) => {
                 if let Some(on_updated) = on_updated_result {
@@ -547,6 +618,11 @@ where
         let status = on_updated.forkchoice_status();
         let _ = tx.send(Ok(on_updated));
 
+        self.on_forkchoice_status(state, status);
+    }
+
+    /// Records the status of a forkchoice update that was answered.
+    fn on_forkchoice_status(&mut self, state: ForkchoiceState, status: ForkchoiceStatus) {
         // update the forkchoice state tracker
         self.forkchoice_state_tracker.set_latest(state, status);
 
@@ -811,6 +887,68 @@ where
         Ok(Some(self.prepare_invalid_response(header.parent_hash)?))
     }
 
+    /// Defers making the head of the forkchoice state canonical, if its blocks can be persisted in
+    /// a single batch with the blocks of the currently deferred head.
+    ///
+    /// Returns `true` if the head was deferred, in which case the forkchoice update can be answered
+    /// right away.
+    fn try_defer_canonical_head(&mut self, state: ForkchoiceState) -> ProviderResult<bool> {
+        if !self.persistence_batch.is_enabled() {
+            return Ok(false)
+        }
+
+        // the safe and finalized blocks are only updated once the head is made canonical, so they
+        // must already be canonical for the update to be valid
+        for hash in [state.safe_block_hash, state.finalized_block_hash] {
+            if !hash.is_zero() && !self.blockchain.is_canonical(hash)? {
+                return Ok(false)
+            }
+        }
+
+        // the new head is batched with the deferred head, if the canonical chain didn't change
+        // since it was deferred
+        let canonical_tip = self.blockchain.canonical_tip();
+        let (base, mut blocks, mut bytes) = match self.deferred_head {
+            Some(deferred) if deferred.canonical_tip == canonical_tip => {
+                (deferred.head, deferred.blocks, deferred.bytes)
+            }
+            _ => (canonical_tip, 0, 0),
+        };
+
+        // walk back from the new head, which must be a descendant of the base
+        let mut head = base;
+        let mut hash = state.head_block_hash;
+        while hash != base.hash {
+            let Some(block) = self.blockchain.find_block_by_hash(hash, BlockSource::Pending)?
+            else {
+                return Ok(false)
+            };
+            if block.number <= base.number {
+                return Ok(false)
+            }
+            if hash == state.head_block_hash {
+                head = BlockNumHash::new(block.number, hash);
+            }
+
+            blocks += 1;
+            bytes += block.size();
+            if !self.persistence_batch.fits(blocks, bytes) {
+                return Ok(false)
+            }
+            hash = block.parent_hash;
+        }
+
+        // the head is already canonical
+        if blocks == 0 {
+            return Ok(false)
+        }
+
+        trace!(target: "consensus::engine", ?head, blocks, bytes, "Deferring canonicalization of new head");
+        self.deferred_head = Some(DeferredHead { state, canonical_tip, head, blocks, bytes });
+        self.metrics.deferred_forkchoice_updates.increment(1);
+        Ok(true)
+    }
+
     /// Record latency metrics for one call to make a block canonical
     /// Takes start time of the call and result of the make canonical call
     ///
@@ -936,6 +1074,7 @@ where
                 .blockchain
                 .find_block_by_hash(safe_block_hash, BlockSource::Any)?
                 .ok_or_else(|| ProviderError::UnknownBlockHash(safe_block_hash))?;
+            self.blockchain.save_safe_block_number(safe.number)?;
             self.blockchain.set_safe(safe.header.seal(safe_block_hash));
         }
         Ok(())
@@ -1003,6 +1142,16 @@ where
                 self.sync.set_pipeline_sync_target(PipelineTarget::Unwind(*block_number));
                 return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
             }
+            CanonicalError::ReorgNotConfirmed { head, depth, .. } => {
+                // the head is known, but the reorg to it has to be confirmed first, so there's
+                // nothing to sync
+                warn!(target: "consensus::engine", %error, ?state, "Deep reorg needs to be confirmed");
+                self.event_sender.notify(BeaconConsensusEngineEvent::ReorgNotConfirmed {
+                    head: *head,
+                    depth: *depth,
+                });
+                return Ok(PayloadStatus::from_status(PayloadStatusEnum::Syncing))
+            }
             _ => {
                 warn!(target: "consensus::engine", %error, ?state, "Failed to canonicalize the head hash");
                 // TODO(mattsse) better error handling before attempting to sync (FCU could be
@@ -1078,6 +1227,12 @@ where
     ) -> Result<Either<PayloadStatus, SealedBlock>, BeaconOnNewPayloadError> {
         self.metrics.new_payload_messages.increment(1);
 
+        if let Some(frozen_at) = self.freeze_block.filter(|block| payload.block_number() > *block) {
+            let error = ChainFrozenError { frozen_at, block_hash: payload.block_hash() };
+            warn!(target: "consensus::engine", %error, "Rejecting new payload");
+            return Err(BeaconOnNewPayloadError::internal(error))
+        }
+
         // Ensures that the given payload does not violate any consensus rules that concern the
         // block's layout, like:
         //    - missing or invalid base fee
@@ -1327,6 +1482,10 @@ where
         inserted: BlockNumHash,
     ) -> Result<(), (B256, CanonicalError)> {
         let Some(target) = self.forkchoice_state_tracker.sync_target_state() else { return Ok(()) };
+        if self.ensure_not_frozen(target.head_block_hash).is_err() {
+            // the downloaded chain goes past the frozen block
+            return Ok(())
+        }
 
         // optimistically try to make the head of the current FCU target canonical, the sync
         // target might have changed since the block download request was issued
@@ -1414,7 +1573,10 @@ where
                 trace!(target: "consensus::engine", ?result, ?reached_max_block, "Pipeline finished");
                 // Any pipeline error at this point is fatal.
                 let ctrl = result?;
-                if reached_max_block {
+                if reached_max_block && self.freeze_block.is_some() {
+                    self.on_frozen_pipeline_outcome(ctrl)?;
+                    EngineEventOutcome::Processed
+                } else if reached_max_block {
                     // Terminate the sync early if it's reached the maximum user-configured block.
                     EngineEventOutcome::ReachedMaxBlock
                 } else {
@@ -1450,6 +1612,14 @@ where
                 // This is only possible if the node was run with `debug.tip`
                 // argument and without CL.
                 warn!(target: "consensus::engine", "No fork choice state available");
+
+                // there won't be any forkchoice updates that set the finalized and safe blocks,
+                // so the tip the pipeline synced to is used for both
+                let tip = ctrl.block_number().map(|number| self.blockchain.block_hash(number));
+                if let Some(tip) = tip.transpose()?.flatten() {
+                    self.update_finalized_block(tip)?;
+                    self.update_safe_block(tip)?;
+                }
                 return Ok(())
             }
         };
@@ -1535,6 +1705,20 @@ where
         Ok(())
     }
 
+    /// Invoked when the pipeline has synced up to the block the chain is frozen at.
+    ///
+    /// Makes the block the canonical head, without scheduling further pipeline runs.
+    fn on_frozen_pipeline_outcome(&mut self, ctrl: ControlFlow) -> RethResult<()> {
+        // unwinds are handled as usual
+        let Some(block_number) = ctrl.block_number() else { return self.on_pipeline_outcome(ctrl) };
+
+        info!(target: "consensus::engine", block_number, "Chain frozen, not advancing past the block");
+        self.set_canonical_head(block_number)?;
+        self.blockchain.update_block_hashes_and_clear_buffered()?;
+        self.sync_state_updater.update_sync_state(SyncState::Idle);
+        Ok(())
+    }
+
     fn set_canonical_head(&self, max_block: BlockNumber) -> RethResult<()> {
         let max_header = self.blockchain.sealed_header(max_block)
         .inspect_err(|error| {
@@ -1594,6 +1778,25 @@ where
     ) -> RethResult<EngineEventOutcome> {
         match action {
             BlockchainTreeAction::MakeForkchoiceHeadCanonical { state, attrs, tx } => {
+                // payloads are built on top of the canonical head, so it can't be deferred
+                let deferred = attrs.is_none() &&
+                    self.try_defer_canonical_head(state).unwrap_or_else(|err| {
+                        warn!(target: "consensus::engine", %err, "Failed to defer new head, making it canonical right away");
+                        false
+                    });
+                if deferred {
+                    let on_updated = OnForkChoiceUpdated::valid(PayloadStatus::new(
+                        PayloadStatusEnum::Valid,
+                        Some(state.head_block_hash),
+                    ));
+                    self.on_forkchoice_updated_status(state, on_updated, tx);
+                    return Ok(EngineEventOutcome::Processed)
+                }
+
+                // the deferred head is either made canonical along with this head, or superseded
+                // by it
+                self.deferred_head = None;
+
                 let start = Instant::now();
                 let result = self.blockchain.make_canonical(state.head_block_hash);
                 let elapsed = self.record_make_canonical_latency(start, &result);
@@ -1607,7 +1810,9 @@ where
 
                         if fcu_status.is_valid() {
                             let tip_number = self.blockchain.canonical_tip().number;
-                            if self.sync.has_reached_max_block(tip_number) {
+                            if self.freeze_block.is_none() &&
+                                self.sync.has_reached_max_block(tip_number)
+                            {
                                 // Terminate the sync early if it's reached
                                 // the maximum user configured block.
                                 return Ok(EngineEventOutcome::ReachedMaxBlock)
@@ -1656,6 +1861,10 @@ where
                         } else {
                             self.latest_valid_hash_for_invalid_payload(block.parent_hash)?
                         };
+                        self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                            Box::new(block.header.clone()),
+                        ));
+                        self.invalid_block_hook.on_invalid_block(&block, &error);
                         // keep track of the invalid header
                         self.invalid_headers.insert(block.header);
                         PayloadStatus::new(
@@ -1688,6 +1897,27 @@ where
                 trace!(target: "consensus::engine", ?status, "Returning payload status");
                 let _ = tx.send(Ok(status));
             }
+            BlockchainTreeAction::MakeDeferredHeadCanonical { state } => {
+                let start = Instant::now();
+                let result = self.blockchain.make_canonical(state.head_block_hash);
+                let elapsed = self.record_make_canonical_latency(start, &result);
+                let on_updated = self
+                    .on_forkchoice_updated_make_canonical_result(state, None, result, elapsed)
+                    .map_err(RethError::Canonical)?;
+
+                // the forkchoice update was already answered as valid when it was deferred, so if
+                // the head turns out to be invalid or unknown, the status is only recorded
+                let status = on_updated.forkchoice_status();
+                if !status.is_valid() {
+                    warn!(target: "consensus::engine", ?state, ?status, "Failed to make deferred head canonical");
+                    self.on_forkchoice_status(state, status);
+                }
+
+                let tip_number = self.blockchain.canonical_tip().number;
+                if self.freeze_block.is_none() && self.sync.has_reached_max_block(tip_number) {
+                    return Ok(EngineEventOutcome::ReachedMaxBlock)
+                }
+            }
             BlockchainTreeAction::MakeNewPayloadCanonical { payload_num_hash, status, tx } => {
                 let status = match self.try_make_sync_target_canonical(payload_num_hash) {
                     Ok(()) => status,
@@ -1766,6 +1996,10 @@ where
                             let (block, err) = err.split();
                             warn!(target: "consensus::engine", invalid_number=?block.number, invalid_hash=?block.hash(), %err, "Marking block as invalid");
 
+                            self.event_sender.notify(BeaconConsensusEngineEvent::InvalidBlock(
+                                Box::new(block.header.clone()),
+                            ));
+                            self.invalid_block_hook.on_invalid_block(&block, &err);
                             self.invalid_headers.insert(block.header);
                         }
                     }
@@ -1792,6 +2026,7 @@ where
         + BlockIdReader
         + CanonChainTracker
         + StageCheckpointReader
+        + FinalizedBlockWriter
         + ChainSpecProvider
         + Unpin
         + 'static,
@@ -1875,6 +2110,19 @@ where
                     continue
                 }
 
+                // The burst of engine messages is over, so the deferred head is made canonical,
+                // persisting all of its blocks in a single commit.
+                if this.hooks.active_db_write_hook().is_none() {
+                    if let Some(deferred) = this.deferred_head.take() {
+                        this.set_blockchain_tree_action(
+                            BlockchainTreeAction::MakeDeferredHeadCanonical {
+                                state: deferred.state,
+                            },
+                        );
+                        continue
+                    }
+                }
+
                 // Both running hook with db write access and engine messages are pending,
                 // proceed to other polls
                 break
@@ -1926,6 +2174,9 @@ enum BlockchainTreeAction<EngineT: EngineTypes> {
         attrs: Option<EngineT::PayloadAttributes>,
         tx: oneshot::Sender<RethResult<OnForkChoiceUpdated>>,
     },
+    /// Action to make the head of a forkchoice update canonical that was already answered, see
+    /// [`PersistenceBatchConfig`].
+    MakeDeferredHeadCanonical { state: ForkchoiceState },
     InsertNewPayload {
         block: SealedBlock,
         tx: oneshot::Sender<Result<PayloadStatus, BeaconOnNewPayloadError>>,
@@ -1981,7 +2232,7 @@ mod tests {
     use reth_rpc_types_compat::engine::payload::block_to_payload_v1;
     use reth_stages::{ExecOutput, PipelineError, StageError};
     use reth_stages_api::StageCheckpoint;
-    use reth_testing_utils::generators::{self, Rng};
+    use reth_testing_utils::generators::{self, random_block, Rng};
     use std::{collections::VecDeque, sync::Arc};
     use tokio::sync::oneshot::error::TryRecvError;
 
@@ -2150,6 +2401,42 @@ mod tests {
         assert_matches!(rx.await, Ok(Ok(())));
     }
 
+    #[tokio::test]
+    async fn rejects_blocks_past_frozen_block() {
+        let mut rng = generators::rng();
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .paris_activated()
+                .build(),
+        );
+
+        let (consensus_engine, env) = TestConsensusEngineBuilder::new(chain_spec.clone()).build();
+
+        let mut rx = spawn_consensus_engine(consensus_engine.with_freeze_block(0));
+
+        let block = random_block(&mut rng, 1, None, None, Some(0));
+        let res = env.send_new_payload(block_to_payload_v1(block.clone()), None).await;
+        assert_matches!(res, Err(BeaconOnNewPayloadError::Internal(err)) => {
+            assert_eq!(
+                err.downcast_ref::<ChainFrozenError>(),
+                Some(&ChainFrozenError { frozen_at: 0, block_hash: block.hash() })
+            );
+        });
+
+        let res = env
+            .send_forkchoice_updated(ForkchoiceState {
+                head_block_hash: block.hash(),
+                ..Default::default()
+            })
+            .await;
+        assert_matches!(res, Err(BeaconForkChoiceUpdateError::Internal(_)));
+
+        // the engine keeps running
+        assert_matches!(rx.try_recv(), Err(TryRecvError::Empty));
+    }
+
     fn insert_blocks<'a, DB: Database>(
         provider_factory: ProviderFactory<DB>,
         mut blocks: impl Iterator<Item = &'a SealedBlock>,
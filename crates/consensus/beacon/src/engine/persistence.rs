@@ -0,0 +1,52 @@
+use reth_primitives::BlockNumHash;
+use reth_rpc_types::engine::ForkchoiceState;
+
+/// Limits for batching the persistence of consecutive canonical blocks.
+///
+/// While the engine receives a burst of messages, e.g. when the CL catches up after a short
+/// downtime, forkchoice updates that extend the canonical chain are answered right away, but their
+/// head is only made canonical once the burst is over or a limit is reached. This writes all blocks
+/// of the burst in a single database commit, instead of one commit per block.
+///
+/// Until then, the deferred blocks are kept in the blockchain tree and the canonical head reported
+/// by the node lags behind the forkchoice head.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PersistenceBatchConfig {
+    /// The maximum number of blocks that are persisted in a single commit, `0` disables batching.
+    max_blocks: u64,
+    /// The maximum estimated size of the blocks that are persisted in a single commit.
+    max_bytes: usize,
+}
+
+impl PersistenceBatchConfig {
+    /// Creates a new config that batches up to `max_blocks` blocks, with an estimated size of up to
+    /// `max_bytes`, in a single commit.
+    pub const fn new(max_blocks: u64, max_bytes: usize) -> Self {
+        Self { max_blocks, max_bytes }
+    }
+
+    /// Returns `true` if the persistence of canonical blocks is batched.
+    pub const fn is_enabled(&self) -> bool {
+        self.max_blocks > 0
+    }
+
+    /// Returns `true` if the given number of blocks and their size fit into a single batch.
+    pub(crate) const fn fits(&self, blocks: u64, bytes: usize) -> bool {
+        blocks <= self.max_blocks && bytes <= self.max_bytes
+    }
+}
+
+/// A forkchoice update that was answered, but whose head hasn't been made canonical yet.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DeferredHead {
+    /// The forkchoice state to make canonical.
+    pub(crate) state: ForkchoiceState,
+    /// The canonical tip at the time the first head was deferred.
+    pub(crate) canonical_tip: BlockNumHash,
+    /// The deferred head.
+    pub(crate) head: BlockNumHash,
+    /// The number of blocks between the canonical tip and the head.
+    pub(crate) blocks: u64,
+    /// The estimated size of the blocks between the canonical tip and the head.
+    pub(crate) bytes: usize,
+}
@@ -3,10 +3,7 @@
 use reth_chainspec::ChainSpec;
 use reth_consensus::ConsensusError;
 use reth_primitives::{
-    constants::{
-        eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
-        MAXIMUM_EXTRA_DATA_SIZE,
-    },
+    constants::eip4844::{DATA_GAS_PER_BLOB, MAX_DATA_GAS_PER_BLOCK},
     eip4844::calculate_excess_blob_gas,
     GotExpected, Hardfork, Header, SealedBlock, SealedHeader,
 };
@@ -150,15 +147,41 @@ pub fn validate_4844_header_standalone(header: &SealedHeader) -> Result<(), Cons
 ///
 /// From yellow paper: extraData: An arbitrary byte array containing data relevant to this block.
 /// This must be 32 bytes or fewer; formally Hx.
+///
+/// The limit is [`ChainSpec::max_extra_data_size`], which custom chains can raise.
 #[inline]
-pub fn validate_header_extradata(header: &Header) -> Result<(), ConsensusError> {
-    if header.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
+pub fn validate_header_extradata(
+    header: &Header,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if header.extra_data.len() > chain_spec.max_extra_data_size {
         Err(ConsensusError::ExtraDataExceedsMax { len: header.extra_data.len() })
     } else {
         Ok(())
     }
 }
 
+/// Validates that the header's timestamp is not too far ahead of the given present timestamp.
+///
+/// The header may be at most [`ChainSpec::max_future_timestamp_drift`] seconds in the future.
+/// Clock can drift but this can be consensus issue.
+///
+/// Note: This check is relevant only pre-merge.
+#[inline]
+pub fn validate_header_timestamp_drift(
+    header: &Header,
+    present_timestamp: u64,
+    chain_spec: &ChainSpec,
+) -> Result<(), ConsensusError> {
+    if header.timestamp > present_timestamp.saturating_add(chain_spec.max_future_timestamp_drift) {
+        return Err(ConsensusError::TimestampIsInFuture {
+            timestamp: header.timestamp,
+            present_timestamp,
+        })
+    }
+    Ok(())
+}
+
 /// Validates against the parent hash and number.
 ///
 /// This function ensures that the header block number is sequential and that the hash of the parent
@@ -531,4 +554,32 @@ mod tests {
             }))
         );
     }
+
+    #[test]
+    fn custom_max_extra_data_size() {
+        let header = Header { extra_data: Bytes::from(vec![0; 97]), ..Default::default() };
+
+        let mut chain_spec = ChainSpecBuilder::mainnet().build();
+        assert_eq!(
+            validate_header_extradata(&header, &chain_spec),
+            Err(ConsensusError::ExtraDataExceedsMax { len: 97 })
+        );
+
+        chain_spec.max_extra_data_size = 97;
+        assert_eq!(validate_header_extradata(&header, &chain_spec), Ok(()));
+    }
+
+    #[test]
+    fn custom_max_future_timestamp_drift() {
+        let header = Header { timestamp: 1_060, ..Default::default() };
+
+        let mut chain_spec = ChainSpecBuilder::mainnet().build();
+        assert_eq!(
+            validate_header_timestamp_drift(&header, 1_000, &chain_spec),
+            Err(ConsensusError::TimestampIsInFuture { timestamp: 1_060, present_timestamp: 1_000 })
+        );
+
+        chain_spec.max_future_timestamp_drift = 60;
+        assert_eq!(validate_header_timestamp_drift(&header, 1_000, &chain_spec), Ok(()));
+    }
 }
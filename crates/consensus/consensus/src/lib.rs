@@ -11,8 +11,8 @@
 
 use reth_primitives::{
     constants::MINIMUM_GAS_LIMIT, BlockHash, BlockNumber, BlockWithSenders, Bloom, GotExpected,
-    GotExpectedBoxed, Header, InvalidTransactionError, Receipt, Request, SealedBlock, SealedHeader,
-    B256, U256,
+    GotExpectedBoxed, Header, InvalidTransactionError, Receipt, ReceiptEncoding, Request,
+    SealedBlock, SealedHeader, B256, U256,
 };
 
 #[cfg(feature = "std")]
@@ -38,12 +38,32 @@ pub struct PostExecutionInput<'a> {
     pub receipts: &'a [Receipt],
     /// EIP-7685 requests of the block.
     pub requests: &'a [Request],
+    /// Blooms and encodings of the receipts, one per receipt, if they were already computed.
+    pub receipt_encodings: Option<&'a [ReceiptEncoding]>,
 }
 
 impl<'a> PostExecutionInput<'a> {
     /// Creates a new instance of `PostExecutionInput`.
     pub const fn new(receipts: &'a [Receipt], requests: &'a [Request]) -> Self {
-        Self { receipts, requests }
+        Self { receipts, requests, receipt_encodings: None }
+    }
+
+    /// Sets the blooms and encodings of the receipts, so they aren't computed again during
+    /// validation.
+    ///
+    /// # Panics
+    ///
+    /// If there isn't exactly one encoding per receipt.
+    pub const fn with_receipt_encodings(
+        mut self,
+        receipt_encodings: &'a [ReceiptEncoding],
+    ) -> Self {
+        assert!(
+            receipt_encodings.len() == self.receipts.len(),
+            "one encoding per receipt is required"
+        );
+        self.receipt_encodings = Some(receipt_encodings);
+        self
     }
 }
 
@@ -3,6 +3,9 @@
 //! This is a worker that sends FCUs and new payloads by fetching recent blocks from an external
 //! provider like Etherscan or an RPC endpoint. This allows to quickly test the execution client
 //! without running a consensus node.
+//!
+//! It also provides a shadow validator that compares locally computed canonical blocks with the
+//! blocks of an external node.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -14,6 +17,8 @@
 
 mod client;
 mod providers;
+mod shadow;
 
 pub use client::{BlockProvider, DebugConsensusClient};
 pub use providers::{EtherscanBlockProvider, RpcBlockProvider};
+pub use shadow::{compare_headers, ShadowMismatch, ShadowMismatchKind, ShadowValidator};
@@ -0,0 +1,238 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_provider::{Provider, ProviderBuilder};
+use futures::{Future, Stream, StreamExt};
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_node_core::primitives::{SealedHeader, B256};
+use reth_tokio_util::{EventSender, EventStream};
+use reth_tracing::tracing::{debug, warn};
+use std::time::Duration;
+
+/// Delay before asking the external node again for a block it doesn't have yet.
+const MIN_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Upper bound of the delay between attempts to fetch a block the external node doesn't have yet.
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(32);
+
+/// Field of a block header that differs between the local and the external node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShadowMismatchKind {
+    /// Block hash differs.
+    Hash,
+    /// State root differs.
+    StateRoot,
+    /// Receipts root differs.
+    ReceiptsRoot,
+}
+
+/// A mismatch between a locally computed canonical block and the block of the external node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShadowMismatch {
+    /// Number of the compared block.
+    pub block_number: u64,
+    /// Mismatching header field.
+    pub kind: ShadowMismatchKind,
+    /// Locally computed value.
+    pub local: B256,
+    /// Value reported by the external node.
+    pub remote: B256,
+}
+
+/// Compares the header of a locally computed canonical block with the header of the same block
+/// reported by the external node.
+pub fn compare_headers(
+    local: &SealedHeader,
+    remote_hash: B256,
+    remote_state_root: B256,
+    remote_receipts_root: B256,
+) -> Vec<ShadowMismatch> {
+    [
+        (ShadowMismatchKind::Hash, local.hash(), remote_hash),
+        (ShadowMismatchKind::StateRoot, local.state_root, remote_state_root),
+        (ShadowMismatchKind::ReceiptsRoot, local.receipts_root, remote_receipts_root),
+    ]
+    .into_iter()
+    .filter(|(_, local, remote)| local != remote)
+    .map(|(kind, local_value, remote)| ShadowMismatch {
+        block_number: local.number,
+        kind,
+        local: local_value,
+        remote,
+    })
+    .collect()
+}
+
+/// Shadow validator metrics.
+#[derive(Metrics)]
+#[metrics(scope = "consensus.debug_client.shadow")]
+struct ShadowMetrics {
+    /// Number of canonical blocks compared with the external node.
+    blocks_compared: Counter,
+    /// Number of blocks with a hash mismatch.
+    hash_mismatches: Counter,
+    /// Number of blocks with a state root mismatch.
+    state_root_mismatches: Counter,
+    /// Number of blocks with a receipts root mismatch.
+    receipts_root_mismatches: Counter,
+    /// Number of blocks that couldn't be fetched from the external node.
+    fetch_errors: Counter,
+    /// Number of retries of blocks the external node didn't have yet.
+    fetch_retries: Counter,
+}
+
+impl ShadowMetrics {
+    fn record_mismatch(&self, kind: ShadowMismatchKind) {
+        match kind {
+            ShadowMismatchKind::Hash => self.hash_mismatches.increment(1),
+            ShadowMismatchKind::StateRoot => self.state_root_mismatches.increment(1),
+            ShadowMismatchKind::ReceiptsRoot => self.receipts_root_mismatches.increment(1),
+        }
+    }
+}
+
+/// Fetches a block from the external node, retrying with exponential backoff while the external
+/// node doesn't have the block yet.
+///
+/// Returns `None` if the fetch failed.
+async fn fetch_when_available<T, E, F, Fut>(
+    block_number: u64,
+    metrics: &ShadowMetrics,
+    mut fetch: F,
+) -> Option<T>
+where
+    E: std::fmt::Display,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<Option<T>, E>>,
+{
+    let mut retry_delay = MIN_RETRY_DELAY;
+    loop {
+        match fetch().await {
+            Ok(Some(block)) => return Some(block),
+            Ok(None) => {
+                metrics.fetch_retries.increment(1);
+                debug!(target: "consensus::debug-client::shadow", block_number, ?retry_delay, "block not found on external node, retrying");
+                tokio::time::sleep(retry_delay).await;
+                retry_delay = (retry_delay * 2).min(MAX_RETRY_DELAY);
+            }
+            Err(err) => {
+                metrics.fetch_errors.increment(1);
+                warn!(target: "consensus::debug-client::shadow", %err, block_number, "failed to fetch block from external node");
+                return None
+            }
+        }
+    }
+}
+
+/// Shadow validator that compares every locally computed canonical block with the same block
+/// of an external node, reporting mismatches via metrics and [`ShadowValidator::events`].
+///
+/// This is useful for operators evaluating reth alongside their current client.
+#[derive(Debug, Clone)]
+pub struct ShadowValidator {
+    /// RPC URL of the external node.
+    rpc_url: String,
+    /// Sender of the found mismatches.
+    event_sender: EventSender<ShadowMismatch>,
+}
+
+impl ShadowValidator {
+    /// Create a new shadow validator comparing blocks with the node at the given RPC URL.
+    pub fn new(rpc_url: String) -> Self {
+        Self { rpc_url, event_sender: Default::default() }
+    }
+
+    /// Returns a new stream of the mismatches found by the validator.
+    pub fn events(&self) -> EventStream<ShadowMismatch> {
+        self.event_sender.new_listener()
+    }
+
+    /// Runs the validator, comparing every header yielded by the stream of new canonical headers.
+    pub async fn run<S>(self, mut canonical_headers: S)
+    where
+        S: Stream<Item = SealedHeader> + Unpin,
+    {
+        let metrics = ShadowMetrics::default();
+        let provider = match ProviderBuilder::new().on_builtin(&self.rpc_url).await {
+            Ok(provider) => provider,
+            Err(err) => {
+                warn!(target: "consensus::debug-client::shadow", %err, "failed to connect to external node");
+                return
+            }
+        };
+
+        while let Some(local) = canonical_headers.next().await {
+            let block_number = local.number;
+            let Some(remote) = fetch_when_available(block_number, &metrics, || {
+                provider.get_block_by_number(BlockNumberOrTag::Number(block_number), false)
+            })
+            .await
+            else {
+                continue
+            };
+
+            metrics.blocks_compared.increment(1);
+            let mismatches = compare_headers(
+                &local,
+                remote.header.hash.unwrap_or_default(),
+                remote.header.state_root,
+                remote.header.receipts_root,
+            );
+            for mismatch in mismatches {
+                metrics.record_mismatch(mismatch.kind);
+                self.event_sender.notify(mismatch);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_node_core::primitives::Header;
+
+    #[test]
+    fn compare_matching_and_mismatching_headers() {
+        let header = Header { number: 1, state_root: B256::with_last_byte(1), ..Default::default() }
+            .seal_slow();
+
+        assert!(compare_headers(&header, header.hash(), header.state_root, header.receipts_root)
+            .is_empty());
+
+        let mismatches = compare_headers(
+            &header,
+            header.hash(),
+            B256::with_last_byte(2),
+            header.receipts_root,
+        );
+        assert_eq!(
+            mismatches,
+            vec![ShadowMismatch {
+                block_number: 1,
+                kind: ShadowMismatchKind::StateRoot,
+                local: B256::with_last_byte(1),
+                remote: B256::with_last_byte(2),
+            }]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retry_until_block_is_available() {
+        let metrics = ShadowMetrics::default();
+        let mut attempts = 0;
+        let block = fetch_when_available(1, &metrics, || {
+            attempts += 1;
+            let result: Result<_, String> = Ok((attempts == 4).then_some(attempts));
+            futures::future::ready(result)
+        })
+        .await;
+        assert_eq!(block, Some(4));
+
+        let mut attempts = 0;
+        let block = fetch_when_available(1, &metrics, || {
+            attempts += 1;
+            futures::future::ready(Err::<Option<()>, _>("unavailable"))
+        })
+        .await;
+        assert_eq!(block, None);
+        assert_eq!(attempts, 1);
+    }
+}
@@ -0,0 +1,371 @@
+//! Dumps the execution witness, pre-state, receipts and traces of invalid blocks.
+
+use eyre::OptionExt;
+use reth_beacon_consensus::InvalidBlockHook;
+use reth_blockchain_tree_api::error::InsertBlockErrorKind;
+use reth_evm::ConfigureEvm;
+use reth_primitives::{
+    keccak256, proofs::calculate_receipt_root_no_memo, Address, Bloom, Bytes, Receipt, SealedBlock,
+    B256, U256,
+};
+use reth_provider::{
+    AccountReader, ChainSpecProvider, HeaderProvider, StateProvider, StateProviderFactory,
+};
+use reth_revm::{database::StateProviderDatabase, state_change::apply_beacon_root_contract_call};
+use reth_rpc_types::trace::geth::{CallConfig, CallFrame};
+use revm::{
+    db::CacheDB,
+    primitives::{
+        AccountInfo, BlockEnv, Bytecode, CfgEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg,
+        ResultAndState, SpecId, TxEnv,
+    },
+    Database, DatabaseCommit,
+};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    path::PathBuf,
+};
+use tracing::{info, warn};
+
+/// An [`InvalidBlockHook`] that re-executes invalid blocks on top of their parent state and writes
+/// everything needed to debug the block to a directory, so that the block can be compared against
+/// other clients without re-running it manually.
+///
+/// For every invalid block, the following files are written, prefixed with `{number}_{hash}`:
+/// - `block.json`: the block and the validation error.
+/// - `witness.json`: the trie nodes, bytecodes and key preimages of the parent state accessed by
+///   the block.
+/// - `pre_state.json`: the accounts and storage slots accessed by the block, as of the parent
+///   state.
+/// - `receipts.json`: the re-computed receipts, and the header fields that don't match them.
+/// - `traces.json`: the call traces of the transactions, up to the first transaction that failed
+///   to execute.
+///
+/// The block is re-executed synchronously, which blocks the engine for the duration of the dump.
+pub struct InvalidBlockWitnessHook<P, EvmConfig> {
+    provider: P,
+    evm_config: EvmConfig,
+    output_directory: PathBuf,
+}
+
+impl<P, EvmConfig> fmt::Debug for InvalidBlockWitnessHook<P, EvmConfig> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InvalidBlockWitnessHook")
+            .field("output_directory", &self.output_directory)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<P, EvmConfig> InvalidBlockWitnessHook<P, EvmConfig> {
+    /// Creates a new hook that writes the dumps of invalid blocks to the given directory.
+    pub const fn new(provider: P, evm_config: EvmConfig, output_directory: PathBuf) -> Self {
+        Self { provider, evm_config, output_directory }
+    }
+}
+
+impl<P, EvmConfig> InvalidBlockWitnessHook<P, EvmConfig>
+where
+    P: StateProviderFactory + ChainSpecProvider + HeaderProvider,
+    EvmConfig: ConfigureEvm,
+{
+    fn dump(&self, block: &SealedBlock, error: &InsertBlockErrorKind) -> eyre::Result<()> {
+        reth_fs_util::create_dir_all(&self.output_directory)?;
+        let prefix = format!("{}_{}", block.number, block.hash());
+        self.write_json(&prefix, "block", &InvalidBlock { block, error: error.to_string() })?;
+
+        let chain_spec = self.provider.chain_spec();
+        let parent_td = self
+            .provider
+            .header_td(&block.parent_hash)?
+            .ok_or_eyre("missing total difficulty of the parent block")?;
+        let mut cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LATEST);
+        let mut block_env = BlockEnv::default();
+        EvmConfig::fill_cfg_and_block_env(
+            &mut cfg,
+            &mut block_env,
+            &chain_spec,
+            &block.header,
+            parent_td + block.difficulty,
+        );
+
+        let block = block.clone().seal_with_senders().ok_or_eyre("failed to recover senders")?;
+        let state = self.provider.state_by_block_hash(block.parent_hash)?;
+        let mut db = CacheDB::new(RecordingDatabase::new(StateProviderDatabase::new(state)));
+
+        {
+            let env = EnvWithHandlerCfg::new_with_cfg_env(
+                cfg.clone(),
+                block_env.clone(),
+                TxEnv::default(),
+            );
+            let mut evm = self.evm_config.evm_with_env(&mut db, env);
+            apply_beacon_root_contract_call(
+                &chain_spec,
+                block.timestamp,
+                block.number,
+                block.parent_beacon_block_root,
+                &mut evm,
+            )?;
+        }
+
+        // replay the transactions and trace them, until the first one that fails to execute
+        let call_config = CallConfig::default().with_log();
+        let mut receipts = Vec::with_capacity(block.body.len());
+        let mut traces = Vec::with_capacity(block.body.len());
+        let mut execution_error = None;
+        let mut cumulative_gas_used = 0;
+        for (sender, tx) in block.transactions_with_sender() {
+            let mut tx_env = TxEnv::default();
+            EvmConfig::fill_tx_env(&mut tx_env, tx, *sender);
+            let env = EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx_env);
+
+            let mut inspector =
+                TracingInspector::new(TracingInspectorConfig::from_geth_call_config(&call_config));
+            let result =
+                self.evm_config.evm_with_env_and_inspector(&mut db, env, &mut inspector).transact();
+            let ResultAndState { result, state } = match result {
+                Ok(result) => result,
+                Err(err) => {
+                    execution_error =
+                        Some(TransactionError { hash: tx.hash(), error: err.to_string() });
+                    break
+                }
+            };
+            db.commit(state);
+
+            cumulative_gas_used += result.gas_used();
+            traces.push(TransactionTrace {
+                hash: tx.hash(),
+                trace: inspector
+                    .into_geth_builder()
+                    .geth_call_traces(call_config, result.gas_used()),
+            });
+            receipts.push(Receipt {
+                tx_type: tx.tx_type(),
+                success: result.is_success(),
+                cumulative_gas_used,
+                logs: result.into_logs(),
+                ..Default::default()
+            });
+        }
+        self.write_json(&prefix, "traces", &Traces { traces, error: execution_error.as_ref() })?;
+
+        let mut mismatches = Vec::new();
+        if execution_error.is_none() {
+            let receipts_root =
+                calculate_receipt_root_no_memo(&receipts.iter().collect::<Vec<_>>());
+            let logs_bloom =
+                receipts.iter().fold(Bloom::ZERO, |bloom, receipt| bloom | receipt.bloom_slow());
+            mismatches.extend(
+                [
+                    FieldMismatch::new("receipts_root", block.receipts_root, receipts_root),
+                    FieldMismatch::new("logs_bloom", block.logs_bloom, logs_bloom),
+                    FieldMismatch::new("gas_used", block.gas_used, cumulative_gas_used),
+                ]
+                .into_iter()
+                .flatten(),
+            );
+        }
+        self.write_json(&prefix, "receipts", &Receipts { receipts, mismatches })?;
+
+        // the withdrawals are applied after the transactions, and are part of the witness as well
+        let mut recorder = db.db;
+        if let Some(withdrawals) = &block.withdrawals {
+            for withdrawal in withdrawals.iter() {
+                recorder.accounts.entry(withdrawal.address).or_default();
+            }
+        }
+        let RecordingDatabase { db: state, accounts, codes, block_hashes } = recorder;
+        let state = state.into_inner();
+
+        let mut pre_state = BTreeMap::new();
+        for (address, slots) in &accounts {
+            let account = state.basic_account(*address)?.map(|account| {
+                let storage = slots
+                    .iter()
+                    .map(|slot| Ok((*slot, state.storage(*address, *slot)?.unwrap_or_default())))
+                    .collect::<eyre::Result<_>>()?;
+                eyre::Ok(PreStateAccount {
+                    balance: account.balance,
+                    nonce: account.nonce,
+                    code_hash: account.bytecode_hash,
+                    storage,
+                })
+            });
+            pre_state.insert(*address, account.transpose()?);
+        }
+        self.write_json(&prefix, "pre_state", &pre_state)?;
+
+        let mut witness = Witness { codes, block_hashes, ..Default::default() };
+        for (address, slots) in &accounts {
+            witness.keys.insert(keccak256(address), Bytes::copy_from_slice(address.as_slice()));
+            for slot in slots {
+                witness.keys.insert(keccak256(slot), Bytes::copy_from_slice(slot.as_slice()));
+            }
+
+            // proofs are only available if the parent block is the canonical tip
+            let proof = match state.proof(*address, &slots.iter().copied().collect::<Vec<_>>()) {
+                Ok(proof) => proof,
+                Err(err) => {
+                    witness.proof_error = Some(err.to_string());
+                    continue
+                }
+            };
+            let nodes = proof
+                .proof
+                .into_iter()
+                .chain(proof.storage_proofs.into_iter().flat_map(|proof| proof.proof));
+            witness.state.extend(nodes.map(|node| (keccak256(&node), node)));
+        }
+        self.write_json(&prefix, "witness", &witness)?;
+
+        info!(target: "consensus::engine", number = block.number, hash = %block.hash(), directory = %self.output_directory.display(), "Dumped invalid block");
+        Ok(())
+    }
+
+    fn write_json(&self, prefix: &str, name: &str, value: &impl Serialize) -> eyre::Result<()> {
+        let path = self.output_directory.join(format!("{prefix}.{name}.json"));
+        reth_fs_util::write(path, serde_json::to_vec_pretty(value)?)?;
+        Ok(())
+    }
+}
+
+impl<P, EvmConfig> InvalidBlockHook for InvalidBlockWitnessHook<P, EvmConfig>
+where
+    P: StateProviderFactory + ChainSpecProvider + HeaderProvider + Send + Sync,
+    EvmConfig: ConfigureEvm,
+{
+    fn on_invalid_block(&self, block: &SealedBlock, error: &InsertBlockErrorKind) {
+        if let Err(err) = self.dump(block, error) {
+            warn!(target: "consensus::engine", number = block.number, hash = %block.hash(), %err, "Failed to dump invalid block");
+        }
+    }
+}
+
+/// A [`Database`] that records the state accessed through it.
+///
+/// Wrapped in a [`CacheDB`], it records the state of the parent block that the executed block
+/// depends on.
+#[derive(Debug)]
+struct RecordingDatabase<DB> {
+    db: DB,
+    /// The accessed accounts, and their accessed storage slots.
+    accounts: BTreeMap<Address, BTreeSet<B256>>,
+    /// The accessed bytecodes, by code hash.
+    codes: BTreeMap<B256, Bytes>,
+    /// The accessed block hashes, by block number.
+    block_hashes: BTreeMap<u64, B256>,
+}
+
+impl<DB> RecordingDatabase<DB> {
+    const fn new(db: DB) -> Self {
+        Self {
+            db,
+            accounts: BTreeMap::new(),
+            codes: BTreeMap::new(),
+            block_hashes: BTreeMap::new(),
+        }
+    }
+}
+
+impl<DB: Database> Database for RecordingDatabase<DB> {
+    type Error = DB::Error;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        self.accounts.entry(address).or_default();
+        self.db.basic(address)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let code = self.db.code_by_hash(code_hash)?;
+        self.codes.insert(code_hash, code.original_bytes());
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        self.accounts.entry(address).or_default().insert(B256::from(index));
+        self.db.storage(address, index)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let hash = self.db.block_hash(number)?;
+        self.block_hashes.insert(number.saturating_to(), hash);
+        Ok(hash)
+    }
+}
+
+#[derive(Serialize)]
+struct InvalidBlock<'a> {
+    block: &'a SealedBlock,
+    error: String,
+}
+
+#[derive(Serialize)]
+struct PreStateAccount {
+    balance: U256,
+    nonce: u64,
+    code_hash: Option<B256>,
+    storage: BTreeMap<B256, U256>,
+}
+
+#[derive(Serialize, Default)]
+struct Witness {
+    /// The trie nodes of the accessed accounts and storage slots, by hash.
+    state: BTreeMap<B256, Bytes>,
+    codes: BTreeMap<B256, Bytes>,
+    /// The preimages of the hashed addresses and storage slots.
+    keys: BTreeMap<B256, Bytes>,
+    block_hashes: BTreeMap<u64, B256>,
+    /// The error the proofs failed with, e.g. if the parent state is historical.
+    proof_error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Receipts {
+    receipts: Vec<Receipt>,
+    /// The header fields that don't match the re-computed receipts.
+    mismatches: Vec<FieldMismatch>,
+}
+
+#[derive(Serialize)]
+struct FieldMismatch {
+    field: &'static str,
+    header: String,
+    computed: String,
+}
+
+impl FieldMismatch {
+    fn new<T: PartialEq + fmt::Display>(
+        field: &'static str,
+        header: T,
+        computed: T,
+    ) -> Option<Self> {
+        (header != computed).then(|| Self {
+            field,
+            header: header.to_string(),
+            computed: computed.to_string(),
+        })
+    }
+}
+
+#[derive(Serialize)]
+struct Traces<'a> {
+    traces: Vec<TransactionTrace>,
+    /// The transaction that failed to execute, if any.
+    error: Option<&'a TransactionError>,
+}
+
+#[derive(Serialize)]
+struct TransactionTrace {
+    hash: B256,
+    trace: CallFrame,
+}
+
+#[derive(Serialize)]
+struct TransactionError {
+    hash: B256,
+    error: String,
+}
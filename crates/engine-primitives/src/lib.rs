@@ -10,8 +10,8 @@
 
 use reth_chainspec::ChainSpec;
 pub use reth_payload_primitives::{
-    BuiltPayload, EngineApiMessageVersion, EngineObjectValidationError, PayloadOrAttributes,
-    PayloadTypes,
+    BuiltPayload, EngineApiMessageVersion, EngineObjectValidationError, PayloadAttributes,
+    PayloadOrAttributes, PayloadTypes,
 };
 use serde::{de::DeserializeOwned, ser::Serialize};
 
@@ -80,6 +80,11 @@ pub enum Hardfork {
     // Upcoming
     /// Prague: <https://github.com/ethereum/execution-specs/blob/master/network-upgrades/mainnet-upgrades/prague.md>
     Prague,
+    /// Osaka: <https://github.com/ethereum/execution-specs/blob/master/network-upgrades/mainnet-upgrades/osaka.md>
+    ///
+    /// Experimental, activates the EVM Object Format (EOF) until its inclusion in a mainnet
+    /// upgrade is settled.
+    Osaka,
     /// Fjord: <https://github.com/ethereum-optimism/specs/blob/main/specs/protocol/superchain-upgrades.md#fjord>
     #[cfg(feature = "optimism")]
     Fjord,
@@ -561,6 +566,7 @@ impl FromStr for Hardfork {
             #[cfg(feature = "optimism")]
             "fjord" => Self::Fjord,
             "prague" => Self::Prague,
+            "osaka" => Self::Osaka,
             // "arbos11" => Hardfork::ArbOS11,
             // "arbos20atlas" => Hardfork::ArbOS20Atlas,
             _ => return Err(format!("Unknown hardfork: {s}")),
@@ -599,6 +605,7 @@ mod tests {
             "ShAnGhAI",
             "CaNcUn",
             "PrAguE",
+            "oSaKa",
         ];
         let expected_hardforks = [
             Hardfork::Frontier,
@@ -619,6 +626,7 @@ mod tests {
             Hardfork::Shanghai,
             Hardfork::Cancun,
             Hardfork::Prague,
+            Hardfork::Osaka,
         ];
 
         let hardforks: Vec<Hardfork> =
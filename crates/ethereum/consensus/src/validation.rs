@@ -1,27 +1,39 @@
 use reth_chainspec::ChainSpec;
 use reth_consensus::ConsensusError;
 use reth_primitives::{
-    gas_spent_by_transactions, BlockWithSenders, Bloom, GotExpected, Receipt, Request, B256,
+    gas_spent_by_transactions, proofs::calculate_receipt_root_encoded, BlockWithSenders, Bloom,
+    GotExpected, Receipt, ReceiptEncoding, Request, B256,
 };
 
 /// Validate a block with regard to execution results:
 ///
 /// - Compares the receipts root in the block header to the block body
 /// - Compares the gas used in the block header to the actual gas usage after execution
+///
+/// The blooms and encodings of the receipts are computed, unless they're given in
+/// `receipt_encodings`.
+///
+/// # Panics
+///
+/// If `receipt_encodings` doesn't contain exactly one encoding per receipt.
 pub fn validate_block_post_execution(
     block: &BlockWithSenders,
     chain_spec: &ChainSpec,
     receipts: &[Receipt],
     requests: &[Request],
+    receipt_encodings: Option<&[ReceiptEncoding]>,
 ) -> Result<(), ConsensusError> {
     // Before Byzantium, receipts contained state root that would mean that expensive
     // operation as hashing that is required for state root got calculated in every
     // transaction This was replaced with is_success flag.
     // See more about EIP here: https://eips.ethereum.org/EIPS/eip-658
     if chain_spec.is_byzantium_active_at_block(block.header.number) {
-        if let Err(error) =
-            verify_receipts(block.header.receipts_root, block.header.logs_bloom, receipts)
-        {
+        if let Err(error) = verify_receipts(
+            block.header.receipts_root,
+            block.header.logs_bloom,
+            receipts,
+            receipt_encodings,
+        ) {
             tracing::debug!(%error, ?receipts, "receipts verification failed");
             return Err(error)
         }
@@ -59,13 +71,25 @@ fn verify_receipts(
     expected_receipts_root: B256,
     expected_logs_bloom: Bloom,
     receipts: &[Receipt],
+    receipt_encodings: Option<&[ReceiptEncoding]>,
 ) -> Result<(), ConsensusError> {
+    let computed;
+    let receipt_encodings = match receipt_encodings {
+        Some(receipt_encodings) => {
+            assert_eq!(receipt_encodings.len(), receipts.len(), "one encoding per receipt");
+            receipt_encodings
+        }
+        None => {
+            computed = receipts.iter().map(ReceiptEncoding::new).collect::<Vec<_>>();
+            &computed
+        }
+    };
+
     // Calculate receipts root.
-    let receipts_with_bloom = receipts.iter().map(Receipt::with_bloom_ref).collect::<Vec<_>>();
-    let receipts_root = reth_primitives::proofs::calculate_receipt_root_ref(&receipts_with_bloom);
+    let receipts_root = calculate_receipt_root_encoded(receipt_encodings);
 
     // Calculate header logs bloom.
-    let logs_bloom = receipts_with_bloom.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom);
+    let logs_bloom = receipt_encodings.iter().fold(Bloom::ZERO, |bloom, r| bloom | r.bloom);
 
     compare_receipts_root_and_logs_bloom(
         receipts_root,
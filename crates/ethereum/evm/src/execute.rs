@@ -4,18 +4,22 @@ use crate::{
     dao_fork::{DAO_HARDFORK_BENEFICIARY, DAO_HARDKFORK_ACCOUNTS},
     EthEvmConfig,
 };
+use core::num::NonZeroUsize;
 use reth_chainspec::{ChainSpec, MAINNET};
 use reth_ethereum_consensus::validate_block_post_execution;
 use reth_evm::{
+    access_list::transaction_access_list,
     execute::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
     },
+    hook::{BlockExecutionHook, HookInspector},
     ConfigureEvm,
 };
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives::{
-    BlockNumber, BlockWithSenders, Hardfork, Header, Receipt, Request, Withdrawals, U256,
+    AccessList, BlockNumber, BlockWithSenders, Hardfork, Header, Receipt, Request, Withdrawals,
+    U256,
 };
 use reth_prune_types::PruneModes;
 use reth_revm::{
@@ -43,6 +47,10 @@ use std::sync::Arc;
 pub struct EthExecutorProvider<EvmConfig = EthEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     evm_config: EvmConfig,
+    /// Hook that can inspect the execution of every block.
+    hook: Option<Arc<dyn BlockExecutionHook>>,
+    /// Whether the executors collect the access lists of the executed transactions.
+    collect_access_lists: bool,
 }
 
 impl EthExecutorProvider {
@@ -60,7 +68,27 @@ impl EthExecutorProvider {
 impl<EvmConfig> EthExecutorProvider<EvmConfig> {
     /// Creates a new executor provider.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self { chain_spec, evm_config, hook: None, collect_access_lists: false }
+    }
+
+    /// Registers a hook that can attach an inspector to the execution of every block executed by
+    /// the executors of this provider.
+    ///
+    /// Blocks that are inspected are executed one after another, even if parallel execution is
+    /// configured.
+    pub fn with_execution_hook(mut self, hook: Arc<dyn BlockExecutionHook>) -> Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Configures the executors of this provider to collect the access lists of the transactions
+    /// they execute, see [`reth_evm::access_list`].
+    ///
+    /// Transactions are executed one after another if access lists are collected, even if
+    /// parallel execution is configured.
+    pub const fn with_access_lists(mut self) -> Self {
+        self.collect_access_lists = true;
+        self
     }
 }
 
@@ -72,11 +100,14 @@ where
     where
         DB: Database<Error = ProviderError>,
     {
-        EthBlockExecutor::new(
+        let mut executor = EthBlockExecutor::new(
             self.chain_spec.clone(),
             self.evm_config.clone(),
             State::builder().with_database(db).with_bundle_update().without_state_clear().build(),
-        )
+        );
+        executor.hook.clone_from(&self.hook);
+        executor.executor.collect_access_lists = self.collect_access_lists;
+        executor
     }
 }
 
@@ -104,6 +135,7 @@ where
             executor,
             batch_record: BlockBatchRecord::new(prune_modes),
             stats: BlockExecutorStats::default(),
+            access_lists: Vec::new(),
         }
     }
 }
@@ -114,6 +146,7 @@ struct EthExecuteOutput {
     receipts: Vec<Receipt>,
     requests: Vec<Request>,
     gas_used: u64,
+    access_lists: Vec<AccessList>,
 }
 
 /// Helper container type for EVM with chain spec.
@@ -123,6 +156,8 @@ struct EthEvmExecutor<EvmConfig> {
     chain_spec: Arc<ChainSpec>,
     /// How to create an EVM.
     evm_config: EvmConfig,
+    /// Whether to collect the access lists of the executed transactions.
+    collect_access_lists: bool,
 }
 
 impl<EvmConfig> EthEvmExecutor<EvmConfig>
@@ -139,10 +174,14 @@ where
     ///
     /// It does __not__ apply post-execution changes that do not require an [EVM](Evm), for that see
     /// [`EthBlockExecutor::post_execution`].
+    ///
+    /// Transactions are executed one after another if `inspected` is set or access lists are
+    /// collected, even if parallel execution is configured.
     fn execute_state_transitions<Ext, DB>(
         &self,
         block: &BlockWithSenders,
         mut evm: Evm<'_, Ext, &mut State<DB>>,
+        inspected: bool,
     ) -> Result<EthExecuteOutput, BlockExecutionError>
     where
         DB: Database<Error = ProviderError>,
@@ -164,8 +203,67 @@ where
         )?;
 
         // execute transactions
+        let threads = if inspected { None } else { self.parallel_execution_threads(block) };
+        let (receipts, cumulative_gas_used, access_lists) = match threads {
+            #[cfg(feature = "std")]
+            Some(threads) => {
+                let (receipts, cumulative_gas_used) = crate::parallel::execute_transactions(
+                    &self.evm_config,
+                    block,
+                    &mut evm,
+                    threads,
+                )?;
+                (receipts, cumulative_gas_used, Vec::new())
+            }
+            _ => self.execute_transactions(block, &mut evm)?,
+        };
+
+        let requests = if self.chain_spec.is_prague_active_at_timestamp(block.timestamp) {
+            // Collect all EIP-6110 deposits
+            let deposit_requests =
+                crate::eip6110::parse_deposits_from_receipts(&self.chain_spec, &receipts)?;
+
+            // Collect all EIP-7685 requests
+            let withdrawal_requests = apply_withdrawal_requests_contract_call(&mut evm)?;
+
+            [deposit_requests, withdrawal_requests].concat()
+        } else {
+            vec![]
+        };
+
+        Ok(EthExecuteOutput { receipts, requests, gas_used: cumulative_gas_used, access_lists })
+    }
+
+    /// Returns the number of threads to execute the transactions of the block with, if they
+    /// should be executed in parallel.
+    ///
+    /// Parallel execution relies on the EIP-161 state clearing rules for the beneficiary.
+    fn parallel_execution_threads(&self, block: &BlockWithSenders) -> Option<NonZeroUsize> {
+        if !cfg!(feature = "std") ||
+            self.collect_access_lists ||
+            block.body.len() < 2 ||
+            !self.chain_spec.is_spurious_dragon_active_at_block(block.number)
+        {
+            return None
+        }
+        self.evm_config.parallel_execution_threads().filter(|threads| threads.get() > 1)
+    }
+
+    /// Executes the transactions of the block one after another.
+    ///
+    /// Returns the receipts of the transactions, the total gas used and the access lists of the
+    /// transactions if they're collected.
+    fn execute_transactions<Ext, DB>(
+        &self,
+        block: &BlockWithSenders,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+    ) -> Result<(Vec<Receipt>, u64, Vec<AccessList>), BlockExecutionError>
+    where
+        DB: Database<Error = ProviderError>,
+    {
         let mut cumulative_gas_used = 0;
         let mut receipts = Vec::with_capacity(block.body.len());
+        let mut access_lists = Vec::new();
         for (sender, transaction) in block.transactions_with_sender() {
             // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
             // must be no greater than the block’s gasLimit.
@@ -188,6 +286,9 @@ where
                     error: err.into(),
                 }
             })?;
+            if self.collect_access_lists {
+                access_lists.push(transaction_access_list(&state));
+            }
             evm.db_mut().commit(state);
 
             // append gas used
@@ -209,20 +310,7 @@ where
             );
         }
 
-        let requests = if self.chain_spec.is_prague_active_at_timestamp(block.timestamp) {
-            // Collect all EIP-6110 deposits
-            let deposit_requests =
-                crate::eip6110::parse_deposits_from_receipts(&self.chain_spec, &receipts)?;
-
-            // Collect all EIP-7685 requests
-            let withdrawal_requests = apply_withdrawal_requests_contract_call(&mut evm)?;
-
-            [deposit_requests, withdrawal_requests].concat()
-        } else {
-            vec![]
-        };
-
-        Ok(EthExecuteOutput { receipts, requests, gas_used: cumulative_gas_used })
+        Ok((receipts, cumulative_gas_used, access_lists))
     }
 }
 
@@ -237,12 +325,18 @@ pub struct EthBlockExecutor<EvmConfig, DB> {
     executor: EthEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// Hook that can inspect the execution of the block.
+    hook: Option<Arc<dyn BlockExecutionHook>>,
 }
 
 impl<EvmConfig, DB> EthBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: EthEvmExecutor { chain_spec, evm_config }, state }
+        Self {
+            executor: EthEvmExecutor { chain_spec, evm_config, collect_access_lists: false },
+            state,
+            hook: None,
+        }
     }
 
     #[inline]
@@ -297,10 +391,24 @@ where
 
         // 2. configure the evm and execute
         let env = self.evm_env_for_block(&block.header, total_difficulty);
-        let output = {
-            let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_state_transitions(block, evm)
-        }?;
+        let output = match self.hook.as_ref().and_then(|hook| hook.block_inspector(block)) {
+            Some(mut inspector) => {
+                let output = {
+                    let evm = self.executor.evm_config.evm_with_env_and_inspector(
+                        &mut self.state,
+                        env,
+                        HookInspector::new(inspector.as_mut()),
+                    );
+                    self.executor.execute_state_transitions(block, evm, true)
+                }?;
+                inspector.block_end(&output.receipts);
+                output
+            }
+            None => {
+                let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
+                self.executor.execute_state_transitions(block, evm, false)?
+            }
+        };
 
         // 3. apply post execution changes
         self.post_execution(block, total_difficulty)?;
@@ -373,13 +481,19 @@ where
     /// State changes are committed to the database.
     fn execute(mut self, input: Self::Input<'_>) -> Result<Self::Output, Self::Error> {
         let BlockExecutionInput { block, total_difficulty } = input;
-        let EthExecuteOutput { receipts, requests, gas_used } =
+        let EthExecuteOutput { receipts, requests, gas_used, access_lists } =
             self.execute_without_verification(block, total_difficulty)?;
 
         // NOTE: we need to merge keep the reverts for the bundle retention
         self.state.merge_transitions(BundleRetention::Reverts);
 
-        Ok(BlockExecutionOutput { state: self.state.take_bundle(), receipts, requests, gas_used })
+        Ok(BlockExecutionOutput {
+            state: self.state.take_bundle(),
+            receipts,
+            requests,
+            gas_used,
+            access_lists: self.executor.collect_access_lists.then_some(access_lists),
+        })
     }
 }
 
@@ -395,6 +509,8 @@ pub struct EthBatchExecutor<EvmConfig, DB> {
     /// Keeps track of the batch and records receipts based on the configured prune mode
     batch_record: BlockBatchRecord,
     stats: BlockExecutorStats,
+    /// The access lists of the executed blocks, if they're collected.
+    access_lists: Vec<Vec<AccessList>>,
 }
 
 impl<EvmConfig, DB> EthBatchExecutor<EvmConfig, DB> {
@@ -416,10 +532,16 @@ where
 
     fn execute_and_verify_one(&mut self, input: Self::Input<'_>) -> Result<(), Self::Error> {
         let BlockExecutionInput { block, total_difficulty } = input;
-        let EthExecuteOutput { receipts, requests, gas_used: _ } =
+        let EthExecuteOutput { receipts, requests, gas_used: _, access_lists } =
             self.executor.execute_without_verification(block, total_difficulty)?;
 
-        validate_block_post_execution(block, self.executor.chain_spec(), &receipts, &requests)?;
+        validate_block_post_execution(
+            block,
+            self.executor.chain_spec(),
+            &receipts,
+            &requests,
+            None,
+        )?;
 
         // prepare the state according to the prune mode
         let retention = self.batch_record.bundle_retention(block.number);
@@ -431,6 +553,10 @@ where
         // store requests in the set
         self.batch_record.save_requests(requests);
 
+        if self.executor.executor.collect_access_lists {
+            self.access_lists.push(access_lists);
+        }
+
         if self.batch_record.first_block().is_none() {
             self.batch_record.set_first_block(block.number);
         }
@@ -456,6 +582,10 @@ where
     fn size_hint(&self) -> Option<usize> {
         Some(self.executor.state.bundle_state.size_hint())
     }
+
+    fn take_access_lists(&mut self) -> Vec<Vec<AccessList>> {
+        core::mem::take(&mut self.access_lists)
+    }
 }
 
 #[cfg(test)]
@@ -467,9 +597,11 @@ mod tests {
         eip7002::{WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, WITHDRAWAL_REQUEST_PREDEPLOY_CODE},
     };
     use reth_chainspec::{ChainSpecBuilder, ForkCondition};
+    use reth_evm::hook::{ExecutionEvent, ExecutionEventsHook};
     use reth_primitives::{
         constants::{EMPTY_ROOT_HASH, ETH_TO_WEI},
-        keccak256, public_key_to_address, Account, Block, Transaction, TxKind, TxLegacy, B256,
+        keccak256, public_key_to_address, Account, Address, Block, Transaction, TxKind, TxLegacy,
+        B256,
     };
     use reth_revm::{
         database::StateProviderDatabase, test_utils::StateProviderTest, TransitionState,
@@ -1409,4 +1541,189 @@ mod tests {
             ),
         }
     }
+
+    #[test]
+    fn parallel_execution_matches_serial() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut db = StateProviderTest::default();
+        let secp = Secp256k1::new();
+        let key_pairs = [
+            Keypair::new(&secp, &mut generators::rng()),
+            Keypair::new(&secp, &mut generators::rng()),
+        ];
+        for key_pair in key_pairs {
+            db.insert_account(
+                public_key_to_address(key_pair.public_key()),
+                Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+                None,
+                HashMap::new(),
+            );
+        }
+
+        let mut header = chain_spec.genesis_header();
+        header.gas_limit = 1_000_000;
+        header.beneficiary = Address::with_last_byte(0xcb);
+        let gas_price = header.base_fee_per_gas.unwrap() as u128 + 1;
+
+        // the third transaction depends on the first one and is re-executed
+        let transfers = [(0, 0, 1u8), (1, 0, 2), (0, 1, 3)];
+        let body = transfers
+            .into_iter()
+            .map(|(sender, nonce, recipient)| {
+                sign_tx_with_key_pair(
+                    key_pairs[sender],
+                    Transaction::Legacy(TxLegacy {
+                        chain_id: Some(chain_spec.chain.id()),
+                        nonce,
+                        gas_price,
+                        gas_limit: 21_000,
+                        to: TxKind::Call(Address::with_last_byte(0x10 + recipient)),
+                        value: U256::from(recipient),
+                        input: Bytes::default(),
+                    }),
+                )
+            })
+            .collect();
+        let block = Block { header, body, ommers: vec![], withdrawals: None, requests: None }
+            .with_recovered_senders()
+            .unwrap();
+
+        let serial = executor_provider(chain_spec.clone())
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        let parallel = EthExecutorProvider::new(
+            chain_spec,
+            EthEvmConfig::default().with_parallel_execution(NonZeroUsize::new(4).unwrap()),
+        )
+        .executor(StateProviderDatabase::new(&db))
+        .execute((&block, U256::ZERO).into())
+        .unwrap();
+
+        assert_eq!(parallel.receipts, serial.receipts);
+        assert_eq!(parallel.gas_used, serial.gas_used);
+        assert_eq!(parallel.state, serial.state);
+        assert_eq!(
+            parallel
+                .state
+                .account(&block.beneficiary)
+                .and_then(|account| account.info.as_ref())
+                .map(|info| info.balance),
+            Some(U256::from(3 * 21_000))
+        );
+    }
+
+    #[test]
+    fn execution_hook_streams_events() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut db = StateProviderTest::default();
+        let key_pair = Keypair::new(&Secp256k1::new(), &mut generators::rng());
+        let sender = public_key_to_address(key_pair.public_key());
+        db.insert_account(
+            sender,
+            Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let mut header = chain_spec.genesis_header();
+        header.gas_limit = 1_000_000;
+        let gas_price = header.base_fee_per_gas.unwrap() as u128;
+        let recipient = Address::with_last_byte(0x10);
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price,
+                gas_limit: 21_000,
+                to: TxKind::Call(recipient),
+                value: U256::from(1),
+                input: Bytes::default(),
+            }),
+        );
+        let tx_hash = tx.hash();
+        let block =
+            Block { header, body: vec![tx], ommers: vec![], withdrawals: None, requests: None }
+                .with_recovered_senders()
+                .unwrap();
+
+        let (hook, events) = ExecutionEventsHook::new();
+        let output = executor_provider(chain_spec)
+            .with_execution_hook(Arc::new(hook))
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+
+        let events = events.try_recv().unwrap();
+        assert_eq!(events.number, block.number);
+        assert_eq!(events.receipts, output.receipts);
+        assert!(matches!(
+            events.events.as_slice(),
+            [
+                ExecutionEvent::TransactionStart { index: 0, hash },
+                ExecutionEvent::Call { depth: 0, from, to, .. },
+                ExecutionEvent::CallEnd { depth: 0, success: true, .. },
+                ExecutionEvent::TransactionEnd { index: 0 },
+            ] if *hash == tx_hash && *from == sender && *to == recipient
+        ));
+    }
+
+    #[test]
+    fn collects_access_lists() {
+        let chain_spec = Arc::new(ChainSpecBuilder::from(&*MAINNET).shanghai_activated().build());
+
+        let mut db = StateProviderTest::default();
+        let key_pair = Keypair::new(&Secp256k1::new(), &mut generators::rng());
+        let sender = public_key_to_address(key_pair.public_key());
+        db.insert_account(
+            sender,
+            Account { nonce: 0, balance: U256::from(ETH_TO_WEI), bytecode_hash: None },
+            None,
+            HashMap::new(),
+        );
+
+        let mut header = chain_spec.genesis_header();
+        header.gas_limit = 1_000_000;
+        let gas_price = header.base_fee_per_gas.unwrap() as u128;
+        let recipient = Address::with_last_byte(0x10);
+        let tx = sign_tx_with_key_pair(
+            key_pair,
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(chain_spec.chain.id()),
+                nonce: 0,
+                gas_price,
+                gas_limit: 21_000,
+                to: TxKind::Call(recipient),
+                value: U256::from(1),
+                input: Bytes::default(),
+            }),
+        );
+        let block =
+            Block { header, body: vec![tx], ommers: vec![], withdrawals: None, requests: None }
+                .with_recovered_senders()
+                .unwrap();
+
+        // access lists aren't collected by default
+        let output = executor_provider(chain_spec.clone())
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        assert!(output.access_lists.is_none());
+
+        let output = executor_provider(chain_spec)
+            .with_access_lists()
+            .executor(StateProviderDatabase::new(&db))
+            .execute((&block, U256::ZERO).into())
+            .unwrap();
+        let Some([access_list]) = output.access_lists.as_deref() else {
+            panic!("expected one access list, got {:?}", output.access_lists)
+        };
+        let addresses = access_list.0.iter().map(|item| item.address).collect::<Vec<_>>();
+        assert!(addresses.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(addresses.contains(&sender));
+        assert!(addresses.contains(&recipient));
+    }
 }
@@ -12,17 +12,24 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+use core::num::NonZeroUsize;
 use reth_chainspec::ChainSpec;
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{
+    precompile::{PrecompileMetricsRecorder, PrecompileOverrides},
+    ConfigureEvm, ConfigureEvmEnv,
+};
 use reth_primitives::{
     revm::{config::revm_spec, env::fill_tx_env},
     revm_primitives::{AnalysisKind, CfgEnvWithHandlerCfg, TxEnv},
     Address, Head, Header, TransactionSigned, U256,
 };
-use reth_revm::{Database, EvmBuilder};
+use reth_revm::{inspector_handle_register, Database, EvmBuilder, GetInspector};
 
 pub mod execute;
 
+#[cfg(feature = "std")]
+mod parallel;
+
 /// Ethereum DAO hardfork state change data.
 pub mod dao_fork;
 
@@ -30,9 +37,35 @@ pub mod dao_fork;
 pub mod eip6110;
 
 /// Ethereum-related EVM configuration.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Default)]
 #[non_exhaustive]
-pub struct EthEvmConfig;
+pub struct EthEvmConfig {
+    /// Number of threads to execute the transactions of a block with, if in parallel.
+    parallel_execution_threads: Option<NonZeroUsize>,
+    /// Precompiles installed on top of the precompiles of the hardfork.
+    precompiles: PrecompileOverrides,
+    /// Records the calls to the precompiles, shared by all EVMs of the config.
+    precompile_metrics: PrecompileMetricsRecorder,
+}
+
+impl EthEvmConfig {
+    /// Executes the transactions of a block optimistically in parallel, on the given number of
+    /// threads.
+    ///
+    /// Transactions are executed on the state before the block and re-executed one after another
+    /// if they read state that was changed by a previous transaction in the block.
+    pub const fn with_parallel_execution(mut self, threads: NonZeroUsize) -> Self {
+        self.parallel_execution_threads = Some(threads);
+        self
+    }
+
+    /// Installs the given precompiles on top of the precompiles of the hardfork, adding new
+    /// precompiles or replacing existing ones.
+    pub fn with_precompiles(mut self, precompiles: PrecompileOverrides) -> Self {
+        self.precompiles = precompiles;
+        self
+    }
+}
 
 impl ConfigureEvmEnv for EthEvmConfig {
     fn fill_tx_env(tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
@@ -70,7 +103,29 @@ impl ConfigureEvm for EthEvmConfig {
         &self,
         db: DB,
     ) -> reth_revm::Evm<'a, Self::DefaultExternalContext<'a>, DB> {
-        EvmBuilder::default().with_db(db).build()
+        EvmBuilder::default()
+            .with_db(db)
+            .append_handler_register_box(self.precompiles.handler_register())
+            .append_handler_register_box(self.precompile_metrics.handler_register())
+            .build()
+    }
+
+    fn evm_with_inspector<'a, DB, I>(&self, db: DB, inspector: I) -> reth_revm::Evm<'a, I, DB>
+    where
+        DB: Database + 'a,
+        I: GetInspector<DB>,
+    {
+        EvmBuilder::default()
+            .with_db(db)
+            .with_external_context(inspector)
+            .append_handler_register_box(self.precompiles.handler_register())
+            .append_handler_register_box(self.precompile_metrics.handler_register())
+            .append_handler_register(inspector_handle_register)
+            .build()
+    }
+
+    fn parallel_execution_threads(&self) -> Option<NonZeroUsize> {
+        self.parallel_execution_threads
     }
 }
 
@@ -0,0 +1,489 @@
+//! Optimistic parallel execution of the transactions of a block.
+//!
+//! Every transaction is first executed speculatively on a worker thread, without the changes of
+//! the transactions before it in the block that haven't been committed yet. Workers don't access
+//! the database directly, their reads are served by the executing thread from the block's
+//! [State], and each worker records the account and storage values its transaction read.
+//!
+//! The speculative results are committed in block order. Before a result is committed, all values
+//! the transaction read are compared to the current state. If they still match, the transaction
+//! would have had the same result when executed in order and its changes are committed as they
+//! are. Otherwise, or if the speculative execution failed, the transaction is re-executed on the
+//! current state.
+//!
+//! Every transaction pays its fee to the block's beneficiary, which would make every transaction
+//! conflict with all previous ones. If the beneficiary's account exists and isn't empty, and a
+//! transaction doesn't observe it other than by paying the fee, the account is left out of the
+//! comparison. Its balance change is then added to the current balance instead of committing the
+//! speculatively written account.
+
+use reth_evm::{
+    execute::{BlockExecutionError, BlockValidationError, ProviderError},
+    ConfigureEvm,
+};
+use reth_primitives::{Address, BlockWithSenders, Receipt, TransactionSigned, B256, U256};
+use reth_revm::{
+    interpreter::{opcode, CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Evm, EvmContext, Inspector, State,
+};
+use revm_primitives::{
+    db::{Database, DatabaseCommit},
+    AccountInfo, Bytecode, EnvWithHandlerCfg, ResultAndState,
+};
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc::{self, Receiver, Sender},
+    },
+    thread,
+};
+
+/// Executes the transactions of the block optimistically in parallel on the given number of
+/// threads and commits their changes to the state of the given EVM, in block order.
+///
+/// Returns the receipts of the transactions and the total gas used.
+pub(crate) fn execute_transactions<EvmConfig, Ext, DB>(
+    evm_config: &EvmConfig,
+    block: &BlockWithSenders,
+    evm: &mut Evm<'_, Ext, &mut State<DB>>,
+    threads: NonZeroUsize,
+) -> Result<(Vec<Receipt>, u64), BlockExecutionError>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error = ProviderError>,
+{
+    let coinbase = block.beneficiary;
+    // Under EIP-161, paying a zero fee to an existing empty beneficiary removes it, which isn't
+    // the same as adding the fee later.
+    let defer_coinbase_fees = evm.db_mut().basic(coinbase)?.map_or(true, |info| !info.is_empty());
+
+    let env = EnvWithHandlerCfg::new_with_spec_id(evm.context.evm.env.clone(), evm.spec_id());
+    let transactions = block.body.len();
+    let next_transaction = AtomicUsize::new(0);
+    let aborted = AtomicBool::new(false);
+
+    thread::scope(|scope| {
+        let (messages_tx, messages) = mpsc::channel();
+        let mut replies = Vec::new();
+        for worker in 0..threads.get().min(transactions) {
+            let (reply_tx, reply_rx) = mpsc::channel();
+            replies.push(reply_tx);
+            let db = SpeculativeDb {
+                worker,
+                requests: messages_tx.clone(),
+                replies: reply_rx,
+                reads: ReadSet::default(),
+            };
+            let env = env.clone();
+            let (next_transaction, aborted) = (&next_transaction, &aborted);
+            scope.spawn(move || {
+                speculate(evm_config, block, db, env, coinbase, next_transaction, aborted)
+            });
+        }
+        drop(messages_tx);
+
+        let mut committer = Committer {
+            block,
+            coinbase,
+            defer_coinbase_fees,
+            outcomes: (0..transactions).map(|_| None).collect(),
+            receipts: Vec::with_capacity(transactions),
+            cumulative_gas_used: 0,
+        };
+        let result = (|| {
+            while committer.receipts.len() < transactions {
+                match messages.recv() {
+                    Ok(WorkerMessage::Read(worker, request)) => {
+                        let _ = replies[worker].send(serve_read(evm.db_mut(), request));
+                    }
+                    Ok(WorkerMessage::Done(index, outcome)) => {
+                        committer.outcomes[index] = Some(outcome);
+                        committer.commit_ready::<EvmConfig, _, _>(evm)?;
+                    }
+                    // all workers are gone, execute the remaining transactions in order
+                    Err(_) => {
+                        for outcome in &mut committer.outcomes[committer.receipts.len()..] {
+                            outcome.get_or_insert(None);
+                        }
+                        committer.commit_ready::<EvmConfig, _, _>(evm)?;
+                    }
+                }
+            }
+            Ok((committer.receipts, committer.cumulative_gas_used))
+        })();
+
+        // unblocks workers waiting for a read and stops them before their next transaction
+        aborted.store(true, Ordering::Relaxed);
+        result
+    })
+}
+
+/// Executes transactions speculatively until there are none left.
+fn speculate<EvmConfig: ConfigureEvm>(
+    evm_config: &EvmConfig,
+    block: &BlockWithSenders,
+    db: SpeculativeDb,
+    env: EnvWithHandlerCfg,
+    coinbase: Address,
+    next_transaction: &AtomicUsize,
+    aborted: &AtomicBool,
+) {
+    let mut evm = evm_config.evm_with_env_and_inspector(db, env, CoinbaseObserver::new(coinbase));
+    while !aborted.load(Ordering::Relaxed) {
+        let index = next_transaction.fetch_add(1, Ordering::Relaxed);
+        let (Some(sender), Some(transaction)) = (block.senders.get(index), block.body.get(index))
+        else {
+            break
+        };
+
+        EvmConfig::fill_tx_env(evm.tx_mut(), transaction, *sender);
+        evm.context.external.observed = *sender == coinbase;
+        let result = evm.transact().ok();
+        let reads = std::mem::take(&mut evm.db_mut().reads);
+        let outcome = result.map(|result| SpeculativeOutcome {
+            result,
+            reads,
+            coinbase_observed: evm.context.external.observed,
+        });
+
+        let requests = &evm.db_mut().requests;
+        if requests.send(WorkerMessage::Done(index, outcome)).is_err() {
+            break
+        }
+    }
+}
+
+/// Commits speculative outcomes in block order.
+#[derive(Debug)]
+struct Committer<'a> {
+    block: &'a BlockWithSenders,
+    coinbase: Address,
+    defer_coinbase_fees: bool,
+    /// Outcomes of transactions, `Some(None)` if the speculative execution failed.
+    outcomes: Vec<Option<Option<SpeculativeOutcome>>>,
+    receipts: Vec<Receipt>,
+    cumulative_gas_used: u64,
+}
+
+impl<'a> Committer<'a> {
+    /// Commits the outcomes of all transactions that are next in block order.
+    fn commit_ready<EvmConfig, Ext, DB>(
+        &mut self,
+        evm: &mut Evm<'_, Ext, &mut State<DB>>,
+    ) -> Result<(), BlockExecutionError>
+    where
+        EvmConfig: ConfigureEvm,
+        DB: Database<Error = ProviderError>,
+    {
+        while let Some(outcome) = self.outcomes.get_mut(self.receipts.len()).and_then(Option::take)
+        {
+            let index = self.receipts.len();
+            let (sender, transaction) = (self.block.senders[index], &self.block.body[index]);
+
+            // The sum of the transaction’s gas limit, Tg, and the gas utilized in this block prior,
+            // must be no greater than the block’s gasLimit.
+            let block_available_gas = self.block.header.gas_limit - self.cumulative_gas_used;
+            if transaction.gas_limit() > block_available_gas {
+                return Err(BlockValidationError::TransactionGasLimitMoreThanAvailableBlockGas {
+                    transaction_gas_limit: transaction.gas_limit(),
+                    block_available_gas,
+                }
+                .into())
+            }
+
+            let defer_fee = self.defer_coinbase_fees &&
+                outcome.as_ref().is_some_and(|outcome| !outcome.coinbase_observed);
+            let skip = defer_fee.then_some(self.coinbase);
+            let speculative = match outcome {
+                Some(outcome) if outcome.reads.matches(evm.db_mut(), skip)? => Some(outcome),
+                _ => None,
+            };
+
+            let result = match speculative {
+                Some(mut outcome) => {
+                    let fee = if defer_fee {
+                        outcome.take_coinbase_fee(self.coinbase)
+                    } else {
+                        U256::ZERO
+                    };
+                    let ResultAndState { result, state } = outcome.result;
+
+                    let db = evm.db_mut();
+                    db.commit(state);
+                    if !fee.is_zero() {
+                        db.increment_balances([(self.coinbase, fee.to::<u128>())])
+                            .map_err(|_| BlockValidationError::IncrementBalanceFailed)?;
+                    }
+                    result
+                }
+                None => execute_transaction::<EvmConfig, _, _>(evm, transaction, sender)?,
+            };
+
+            // append gas used
+            self.cumulative_gas_used += result.gas_used();
+
+            // Push transaction changeset and calculate header bloom filter for receipt.
+            self.receipts.push(
+                #[allow(clippy::needless_update)] // side-effect of optimism fields
+                Receipt {
+                    tx_type: transaction.tx_type(),
+                    // Success flag was added in `EIP-658: Embedding transaction status code in
+                    // receipts`.
+                    success: result.is_success(),
+                    cumulative_gas_used: self.cumulative_gas_used,
+                    // convert to reth log
+                    logs: result.into_logs(),
+                    ..Default::default()
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Executes the transaction on the current state and commits its changes.
+fn execute_transaction<EvmConfig, Ext, DB>(
+    evm: &mut Evm<'_, Ext, &mut State<DB>>,
+    transaction: &TransactionSigned,
+    sender: Address,
+) -> Result<revm_primitives::ExecutionResult, BlockExecutionError>
+where
+    EvmConfig: ConfigureEvm,
+    DB: Database<Error = ProviderError>,
+{
+    EvmConfig::fill_tx_env(evm.tx_mut(), transaction, sender);
+
+    let ResultAndState { result, state } = evm.transact().map_err(move |err| {
+        // Ensure hash is calculated for error log, if not already done
+        BlockValidationError::EVM { hash: transaction.recalculate_hash(), error: err.into() }
+    })?;
+    evm.db_mut().commit(state);
+    Ok(result)
+}
+
+/// Serves a read of a worker from the state.
+fn serve_read<DB: Database>(db: &mut DB, request: ReadRequest) -> ReadResponse {
+    let response = match request {
+        ReadRequest::Account(address) => db.basic(address).map(ReadResponse::Account),
+        ReadRequest::Storage(address, index) => db.storage(address, index).map(ReadResponse::Value),
+        ReadRequest::Code(code_hash) => db.code_by_hash(code_hash).map(ReadResponse::Code),
+        ReadRequest::BlockHash(number) => db.block_hash(number).map(ReadResponse::Hash),
+    };
+    response.unwrap_or(ReadResponse::Failed)
+}
+
+/// The result of a speculatively executed transaction.
+#[derive(Debug)]
+struct SpeculativeOutcome {
+    result: ResultAndState,
+    reads: ReadSet,
+    /// Whether the transaction observed the state of the block's beneficiary.
+    coinbase_observed: bool,
+}
+
+impl SpeculativeOutcome {
+    /// Removes the beneficiary's account from the changes and returns the fee paid to it.
+    fn take_coinbase_fee(&mut self, coinbase: Address) -> U256 {
+        let observed = self
+            .reads
+            .accounts
+            .get(&coinbase)
+            .and_then(Option::as_ref)
+            .map_or(U256::ZERO, |info| info.balance);
+        self.result
+            .state
+            .remove(&coinbase)
+            .map_or(U256::ZERO, |account| account.info.balance.saturating_sub(observed))
+    }
+}
+
+/// Account and storage values read by a transaction.
+///
+/// Code and block hashes can't change during a block and aren't recorded.
+#[derive(Debug, Default)]
+struct ReadSet {
+    accounts: HashMap<Address, Option<AccountInfo>>,
+    storage: HashMap<(Address, U256), U256>,
+}
+
+impl ReadSet {
+    /// Returns whether all read values still match the current state, except for the account of
+    /// the skipped address.
+    fn matches<DB: Database>(&self, db: &mut DB, skip: Option<Address>) -> Result<bool, DB::Error> {
+        for (address, observed) in &self.accounts {
+            if skip == Some(*address) {
+                continue
+            }
+            let current = db.basic(*address)?;
+            let matches = match (observed, &current) {
+                (None, None) => true,
+                (Some(observed), Some(current)) => {
+                    observed.balance == current.balance &&
+                        observed.nonce == current.nonce &&
+                        observed.code_hash == current.code_hash
+                }
+                _ => false,
+            };
+            if !matches {
+                return Ok(false)
+            }
+        }
+
+        for (&(address, index), observed) in &self.storage {
+            if db.storage(address, index)? != *observed {
+                return Ok(false)
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+/// A message sent from a worker to the executing thread.
+#[derive(Debug)]
+enum WorkerMessage {
+    /// A read of the worker with the given index.
+    Read(usize, ReadRequest),
+    /// The outcome of the transaction with the given index, `None` if it failed.
+    Done(usize, Option<SpeculativeOutcome>),
+}
+
+/// A read from the state.
+#[derive(Debug)]
+enum ReadRequest {
+    Account(Address),
+    Storage(Address, U256),
+    Code(B256),
+    BlockHash(U256),
+}
+
+/// The value read from the state.
+#[derive(Debug)]
+enum ReadResponse {
+    Account(Option<AccountInfo>),
+    Value(U256),
+    Code(Bytecode),
+    Hash(B256),
+    /// The read failed, the transaction is re-executed in order.
+    Failed,
+}
+
+/// Error of a speculative read, the transaction is re-executed in order.
+#[derive(Debug)]
+struct SpeculationFailed;
+
+/// A [Database] of a worker that reads from the executing thread and records the read values.
+#[derive(Debug)]
+struct SpeculativeDb {
+    worker: usize,
+    requests: Sender<WorkerMessage>,
+    replies: Receiver<ReadResponse>,
+    reads: ReadSet,
+}
+
+impl SpeculativeDb {
+    fn read(&self, request: ReadRequest) -> Result<ReadResponse, SpeculationFailed> {
+        self.requests
+            .send(WorkerMessage::Read(self.worker, request))
+            .map_err(|_| SpeculationFailed)?;
+        self.replies.recv().map_err(|_| SpeculationFailed)
+    }
+}
+
+impl Database for SpeculativeDb {
+    type Error = SpeculationFailed;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let ReadResponse::Account(info) = self.read(ReadRequest::Account(address))? else {
+            return Err(SpeculationFailed)
+        };
+        self.reads.accounts.entry(address).or_insert_with(|| info.clone());
+        Ok(info)
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        let ReadResponse::Code(code) = self.read(ReadRequest::Code(code_hash))? else {
+            return Err(SpeculationFailed)
+        };
+        Ok(code)
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let ReadResponse::Value(value) = self.read(ReadRequest::Storage(address, index))? else {
+            return Err(SpeculationFailed)
+        };
+        self.reads.storage.entry((address, index)).or_insert(value);
+        Ok(value)
+    }
+
+    fn block_hash(&mut self, number: U256) -> Result<B256, Self::Error> {
+        let ReadResponse::Hash(hash) = self.read(ReadRequest::BlockHash(number))? else {
+            return Err(SpeculationFailed)
+        };
+        Ok(hash)
+    }
+}
+
+/// An [Inspector] that detects whether a transaction observes the account of the block's
+/// beneficiary, other than by paying its fee to it.
+#[derive(Debug)]
+struct CoinbaseObserver {
+    coinbase: Address,
+    observed: bool,
+}
+
+impl CoinbaseObserver {
+    const fn new(coinbase: Address) -> Self {
+        Self { coinbase, observed: false }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for CoinbaseObserver {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if interp.contract.target_address == self.coinbase {
+            self.observed = true;
+            return
+        }
+        if matches!(
+            interp.current_opcode(),
+            opcode::BALANCE | opcode::EXTCODESIZE | opcode::EXTCODECOPY | opcode::EXTCODEHASH
+        ) {
+            if let Ok(address) = interp.stack().peek(0) {
+                if Address::from_word(B256::from(address)) == self.coinbase {
+                    self.observed = true;
+                }
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        if inputs.target_address == self.coinbase || inputs.bytecode_address == self.coinbase {
+            self.observed = true;
+        }
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        if outcome.address == Some(self.coinbase) {
+            self.observed = true;
+        }
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, _value: U256) {
+        if contract == self.coinbase || target == self.coinbase {
+            self.observed = true;
+        }
+    }
+}
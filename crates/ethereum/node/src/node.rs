@@ -19,10 +19,12 @@ use reth_node_builder::{
 };
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
+use reth_tasks::RestartPolicy;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, EthTransactionPool, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, validate::AddressDenyList, CoinbaseTipOrdering,
+    EthPooledTransaction, ScreenedEthTransactionPool, TransactionPool, TransactionScreen,
+    TransactionScreens, TransactionValidationTaskExecutor, ValidatorStack,
 };
 use std::sync::Arc;
 
@@ -100,7 +102,10 @@ where
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
         let evm_config = EthEvmConfig::default();
-        let executor = EthExecutorProvider::new(chain_spec, evm_config);
+        let mut executor = EthExecutorProvider::new(chain_spec, evm_config.clone());
+        if ctx.config().db.access_lists {
+            executor = executor.with_access_lists();
+        }
 
         Ok((evm_config, executor))
     }
@@ -110,22 +115,53 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
 pub struct EthereumPoolBuilder {
-    // TODO add options for txpool args
+    /// Screens that run before and after the validation of the transactions, see
+    /// [`ValidatorStack`].
+    screens: TransactionScreens<EthPooledTransaction>,
+}
+
+impl EthereumPoolBuilder {
+    /// Adds a screen that runs before the transactions are validated, e.g. to reject them early.
+    pub fn with_screen_before(
+        mut self,
+        screen: impl TransactionScreen<EthPooledTransaction> + 'static,
+    ) -> Self {
+        self.screens = self.screens.with_screen_before(screen);
+        self
+    }
+
+    /// Adds a screen that runs after the transactions were validated, only for the valid ones.
+    pub fn with_screen_after(
+        mut self,
+        screen: impl TransactionScreen<EthPooledTransaction> + 'static,
+    ) -> Self {
+        self.screens = self.screens.with_screen_after(screen);
+        self
+    }
 }
 
 impl<Node> PoolBuilder<Node> for EthereumPoolBuilder
 where
     Node: FullNodeTypes,
 {
-    type Pool = EthTransactionPool<Node::Provider, DiskFileBlobStore>;
+    type Pool = ScreenedEthTransactionPool<Node::Provider, DiskFileBlobStore>;
 
     async fn build_pool(self, ctx: &BuilderContext<Node>) -> eyre::Result<Self::Pool> {
         let data_dir = ctx.config().datadir();
         let pool_config = ctx.pool_config();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
+
+        // the deny list of the node's config screens the transactions before any other screen
+        let mut screens = TransactionScreens::default();
+        let deny_list = &ctx.config().txpool.deny_list;
+        if !deny_list.is_empty() {
+            screens = screens.with_screen_before(AddressDenyList::new(deny_list.iter().copied()));
+        }
+        let screens = screens.with_screens(self.screens);
+
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -135,20 +171,28 @@ where
                 ctx.provider().clone(),
                 ctx.task_executor().clone(),
                 blob_store.clone(),
-            );
+            )
+            .map(|validator| ValidatorStack::new(validator).with_screens(screens.clone()));
 
-        let transaction_pool =
-            reth_transaction_pool::Pool::eth_pool(validator, blob_store, pool_config);
+        let transaction_pool = reth_transaction_pool::Pool::new(
+            validator,
+            CoinbaseTipOrdering::default(),
+            blob_store,
+            pool_config,
+        );
         info!(target: "reth::cli", "Transaction pool initialized");
         let transactions_path = data_dir.txpool_transactions();
 
         // spawn txpool maintenance task
         {
             let pool = transaction_pool.clone();
-            let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
+            let mut transactions_backup_config =
                 reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            if ctx.config().txpool.persist {
+                transactions_backup_config = transactions_backup_config
+                    .with_pool_transactions(data_dir.txpool_persisted_transactions());
+            }
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -161,16 +205,47 @@ where
                 },
             );
 
-            // spawn the maintenance task
-            ctx.task_executor().spawn_critical(
-                "txpool maintenance task",
-                reth_transaction_pool::maintain::maintain_transaction_pool_future(
-                    client,
-                    pool,
-                    chain_events,
+            // spawn the trie prefetcher of the best pending transactions, if enabled
+            if let Some(max_transactions) = ctx.config().txpool.trie_prefetch_transactions {
+                ctx.task_executor().spawn(reth_transaction_pool::prefetch::prefetch_trie_future(
+                    client.clone(),
+                    pool.clone(),
+                    client.canonical_state_stream(),
                     ctx.task_executor().clone(),
-                    Default::default(),
-                ),
+                    reth_transaction_pool::prefetch::TriePrefetchConfig::new(max_transactions),
+                ));
+                debug!(target: "reth::cli", "Spawned txpool trie prefetch task");
+            }
+
+            // spawn the rebroadcast of the tracked local transactions, if enabled
+            if ctx.config().txpool.track_locals {
+                let interval = ctx.config().txpool.locals_rebroadcast_interval;
+                ctx.task_executor().spawn(
+                    reth_transaction_pool::locals::rebroadcast_local_transactions_future(
+                        client.clone(),
+                        pool.clone(),
+                        client.canonical_state_stream(),
+                        reth_transaction_pool::locals::LocalRebroadcastConfig::new(interval),
+                    ),
+                );
+                debug!(target: "reth::cli", "Spawned txpool local transactions rebroadcast task");
+            }
+
+            // spawn the maintenance task, which is restarted with a fresh subscription to the
+            // canonical state if it panics
+            let executor = ctx.task_executor().clone();
+            ctx.task_executor().spawn_critical_with_restart_policy(
+                "txpool maintenance task",
+                RestartPolicy::restart(3),
+                move |_| {
+                    reth_transaction_pool::maintain::maintain_transaction_pool_future(
+                        client.clone(),
+                        pool.clone(),
+                        client.canonical_state_stream(),
+                        executor.clone(),
+                        Default::default(),
+                    )
+                },
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
@@ -202,11 +277,17 @@ where
         let payload_builder = reth_ethereum_payload_builder::EthereumPayloadBuilder::default();
         let conf = ctx.payload_builder_config();
 
+        let extradata = conf.extradata_bytes();
+        let max_extra_data_size = ctx.chain_spec().max_extra_data_size;
+        if extradata.len() > max_extra_data_size {
+            eyre::bail!("payload builder extradata size exceeds {max_extra_data_size}-byte limit")
+        }
+
         let payload_job_config = BasicPayloadJobGeneratorConfig::default()
             .interval(conf.interval())
             .deadline(conf.deadline())
             .max_payload_tasks(conf.max_payload_tasks())
-            .extradata(conf.extradata_bytes());
+            .extradata(extradata);
 
         let payload_generator = BasicPayloadJobGenerator::with_builder(
             ctx.provider().clone(),
@@ -9,6 +9,9 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![allow(clippy::useless_let_if_seq)]
 
+mod ordering;
+pub use ordering::{PayloadTransactions, PayloadTransactionsOrdering, PoolTransactionsOrdering};
+
 use reth_basic_payload_builder::{
     commit_withdrawals, is_better_payload, post_block_withdrawal_requests_contract_call,
     pre_block_beacon_root_contract_call, BuildArguments, BuildOutcome, PayloadBuilder,
@@ -32,25 +35,38 @@ use reth_primitives::{
 };
 use reth_provider::StateProviderFactory;
 use reth_revm::{database::StateProviderDatabase, state_change::apply_blockhashes_update};
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_transaction_pool::{
+    validate::{ensure_conditional_block, ensure_known_accounts},
+    BestTransactionsAttributes, PoolTransaction, TransactionPool,
+};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
     DatabaseCommit, State,
 };
+use std::collections::HashSet;
 use tracing::{debug, trace, warn};
 
 /// Ethereum payload builder
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig> {
+pub struct EthereumPayloadBuilder<EvmConfig = EthEvmConfig, Ordering = PoolTransactionsOrdering> {
     /// The type responsible for creating the evm.
     evm_config: EvmConfig,
+    /// The ordering of the transactions in the payload.
+    ordering: Ordering,
 }
 
 impl<EvmConfig> EthereumPayloadBuilder<EvmConfig> {
     /// `EthereumPayloadBuilder` constructor.
     pub const fn new(evm_config: EvmConfig) -> Self {
-        Self { evm_config }
+        Self { evm_config, ordering: PoolTransactionsOrdering }
+    }
+}
+
+impl<EvmConfig, Ordering> EthereumPayloadBuilder<EvmConfig, Ordering> {
+    /// Sets the ordering of the transactions in the payload, see [`PayloadTransactionsOrdering`].
+    pub fn with_ordering<O>(self, ordering: O) -> EthereumPayloadBuilder<EvmConfig, O> {
+        EthereumPayloadBuilder { evm_config: self.evm_config, ordering }
     }
 }
 
@@ -61,9 +77,11 @@ impl Default for EthereumPayloadBuilder {
 }
 
 // Default implementation of [PayloadBuilder] for unit type
-impl<EvmConfig, Pool, Client> PayloadBuilder<Pool, Client> for EthereumPayloadBuilder<EvmConfig>
+impl<EvmConfig, Ordering, Pool, Client> PayloadBuilder<Pool, Client>
+    for EthereumPayloadBuilder<EvmConfig, Ordering>
 where
     EvmConfig: ConfigureEvm,
+    Ordering: PayloadTransactionsOrdering<Pool> + Clone,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -74,7 +92,7 @@ where
         &self,
         args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
     ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError> {
-        default_ethereum_payload_builder(self.evm_config.clone(), args)
+        default_ethereum_payload_builder(self.evm_config.clone(), &self.ordering, args)
     }
 
     fn build_empty_payload(
@@ -242,13 +260,17 @@ where
 /// Given build arguments including an Ethereum client, transaction pool,
 /// and configuration, this function creates a transaction payload. Returns
 /// a result indicating success with the payload or an error in case of failure.
+///
+/// The transactions are tried in the order of the given [`PayloadTransactionsOrdering`].
 #[inline]
-pub fn default_ethereum_payload_builder<EvmConfig, Pool, Client>(
+pub fn default_ethereum_payload_builder<EvmConfig, Ordering, Pool, Client>(
     evm_config: EvmConfig,
+    ordering: &Ordering,
     args: BuildArguments<Pool, Client, EthPayloadBuilderAttributes, EthBuiltPayload>,
 ) -> Result<BuildOutcome<EthBuiltPayload>, PayloadBuilderError>
 where
     EvmConfig: ConfigureEvm,
+    Ordering: PayloadTransactionsOrdering<Pool>,
     Client: StateProviderFactory,
     Pool: TransactionPool,
 {
@@ -276,10 +298,13 @@ where
 
     let mut executed_txs = Vec::new();
 
-    let mut best_txs = pool.best_transactions_with_attributes(BestTransactionsAttributes::new(
-        base_fee,
-        initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
-    ));
+    let mut best_txs = ordering.best_transactions(
+        &pool,
+        BestTransactionsAttributes::new(
+            base_fee,
+            initialized_block_env.get_blob_gasprice().map(|gasprice| gasprice as u64),
+        ),
+    );
 
     let mut total_fees = U256::ZERO;
 
@@ -305,6 +330,9 @@ where
     )
     .map_err(|err| PayloadBuilderError::Internal(err.into()))?;
 
+    // accounts whose storage was changed by the transactions of the block
+    let mut changed_storage = HashSet::new();
+
     let mut receipts = Vec::new();
     while let Some(pool_tx) = best_txs.next() {
         // ensure we still have capacity for this transaction
@@ -339,6 +367,25 @@ where
             }
         }
 
+        // Conditional transactions are only included if the block meets their conditions. The
+        // known accounts are checked against the state of the parent block, so they aren't met if
+        // a previous transaction of the block changed the storage of any of the accounts.
+        if let Some(conditional) = pool_tx.transaction.conditional() {
+            let is_block_met =
+                ensure_conditional_block(conditional, block_number, attributes.timestamp).is_ok();
+            let is_storage_changed =
+                conditional.known_accounts.keys().any(|address| changed_storage.contains(address));
+            let is_met = is_block_met && !is_storage_changed && {
+                let parent_state = db.database.0.inner.borrow();
+                ensure_known_accounts(conditional, &**parent_state.db)?.is_ok()
+            };
+            if !is_met {
+                trace!(target: "payload_builder", tx=?tx.hash, "skipping conditional transaction because its conditions are not met");
+                best_txs.mark_invalid(&pool_tx);
+                continue
+            }
+        }
+
         let env = EnvWithHandlerCfg::new_with_cfg_env(
             initialized_cfg.clone(),
             initialized_block_env.clone(),
@@ -374,6 +421,13 @@ where
         };
         // drop evm so db is released.
         drop(evm);
+        // keep track of the changed storage for the conditions of the following transactions
+        changed_storage.extend(
+            state
+                .iter()
+                .filter(|(_, account)| account.storage.values().any(|slot| slot.is_changed()))
+                .map(|(address, _)| *address),
+        );
         // commit changes
         db.commit(state);
 
@@ -0,0 +1,71 @@
+//! Ordering of the pool transactions in a payload.
+
+use reth_transaction_pool::{
+    BestTransactions, BestTransactionsAttributes, TransactionPool, ValidPoolTransaction,
+};
+use std::{fmt, sync::Arc};
+
+/// The transactions the payload builder tries to include, in the order they're executed.
+pub type PayloadTransactions<Pool> = Box<
+    dyn BestTransactions<Item = Arc<ValidPoolTransaction<<Pool as TransactionPool>::Transaction>>>,
+>;
+
+/// Determines which transactions the payload builder tries to include in a payload, and in which
+/// order.
+///
+/// This allows custom orderings, e.g. merging bundles or prioritizing certain senders, without
+/// reimplementing the payload builder. The builder executes the transactions in the returned order
+/// and keeps track of the block gas and blob gas limits: transactions that don't fit into the
+/// block or fail to execute are passed to [`BestTransactions::mark_invalid`], after which the
+/// returned iterator must not yield any transactions that depend on them.
+pub trait PayloadTransactionsOrdering<Pool: TransactionPool>: fmt::Debug + Send + Sync {
+    /// Returns the transactions for a payload with the given base fee and blob fee.
+    fn best_transactions(
+        &self,
+        pool: &Pool,
+        attributes: BestTransactionsAttributes,
+    ) -> PayloadTransactions<Pool>;
+}
+
+/// Orders the transactions by their priority in the pool, see
+/// [`TransactionPool::best_transactions_with_attributes`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PoolTransactionsOrdering;
+
+impl<Pool: TransactionPool> PayloadTransactionsOrdering<Pool> for PoolTransactionsOrdering {
+    fn best_transactions(
+        &self,
+        pool: &Pool,
+        attributes: BestTransactionsAttributes,
+    ) -> PayloadTransactions<Pool> {
+        pool.best_transactions_with_attributes(attributes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        PoolTransaction, TransactionOrigin,
+    };
+
+    #[tokio::test]
+    async fn pool_ordering() {
+        let pool = testing_pool();
+        let cheap = MockTransaction::eip1559();
+        let tip = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(50);
+        let expensive = MockTransaction::eip1559().with_max_fee(200).with_priority_fee(100);
+        for tx in [cheap, tip.clone(), expensive.clone()] {
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        // transactions are ordered by their tip, and those below the base fee are skipped
+        let best = PoolTransactionsOrdering
+            .best_transactions(&pool, BestTransactionsAttributes::new(50, None))
+            .map(|tx| *tx.hash())
+            .collect::<Vec<_>>();
+        assert_eq!(best, vec![*expensive.hash(), *tip.hash()]);
+    }
+}
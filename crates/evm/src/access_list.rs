@@ -0,0 +1,63 @@
+//! Access lists of the accounts and storage slots touched by the transactions of a block.
+//!
+//! The access list of a transaction is built from the state the transaction loaded during its
+//! execution. It contains every account the transaction loaded, including the sender and the
+//! block's beneficiary, and every storage slot it read or wrote, even if they weren't modified.
+//!
+//! Access lists are canonical: accounts are ordered by address, and the storage slots of an
+//! account are ordered by key.
+
+use reth_primitives::{AccessList, AccessListItem, B256};
+use revm_primitives::EvmState;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Returns the canonical access list of the state loaded by a transaction, see the
+/// [module documentation](self).
+pub fn transaction_access_list(state: &EvmState) -> AccessList {
+    let mut items = state
+        .iter()
+        .map(|(address, account)| {
+            let mut storage_keys =
+                account.storage.keys().map(|slot| B256::from(*slot)).collect::<Vec<_>>();
+            storage_keys.sort_unstable();
+            AccessListItem { address: *address, storage_keys }
+        })
+        .collect::<Vec<_>>();
+    items.sort_unstable_by_key(|item| item.address);
+    AccessList(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, U256};
+    use revm_primitives::{Account, EvmStorageSlot};
+
+    #[test]
+    fn canonical_access_list() {
+        let slot = |slot: u64| (U256::from(slot), EvmStorageSlot::new(U256::ZERO));
+        let mut contract = Account::default();
+        contract.storage.extend([slot(3), slot(1), slot(2)]);
+        let state = EvmState::from_iter([
+            (Address::with_last_byte(2), contract),
+            (Address::with_last_byte(1), Account::default()),
+        ]);
+
+        assert_eq!(
+            transaction_access_list(&state),
+            AccessList(vec![
+                AccessListItem { address: Address::with_last_byte(1), storage_keys: vec![] },
+                AccessListItem {
+                    address: Address::with_last_byte(2),
+                    storage_keys: vec![
+                        B256::from(U256::from(1)),
+                        B256::from(U256::from(2)),
+                        B256::from(U256::from(3)),
+                    ],
+                },
+            ])
+        );
+    }
+}
@@ -5,11 +5,14 @@ use crate::execute::{
 };
 use reth_execution_errors::BlockExecutionError;
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Receipt};
+use reth_primitives::{AccessList, BlockNumber, BlockWithSenders, Receipt};
 use reth_prune_types::PruneModes;
 use reth_storage_errors::provider::ProviderError;
 use revm_primitives::db::Database;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 // re-export Either
 pub use futures_util::future::Either;
 
@@ -118,4 +121,11 @@ where
             Self::Right(b) => b.size_hint(),
         }
     }
+
+    fn take_access_lists(&mut self) -> Vec<Vec<AccessList>> {
+        match self {
+            Self::Left(a) => a.take_access_lists(),
+            Self::Right(b) => b.take_access_lists(),
+        }
+    }
 }
@@ -1,7 +1,7 @@
 //! Traits for execution.
 
 use reth_execution_types::ExecutionOutcome;
-use reth_primitives::{BlockNumber, BlockWithSenders, Receipt, Request, U256};
+use reth_primitives::{AccessList, BlockNumber, BlockWithSenders, Receipt, Request, U256};
 use reth_prune_types::PruneModes;
 use revm::db::BundleState;
 use revm_primitives::db::Database;
@@ -88,6 +88,15 @@ pub trait BatchExecutor<DB> {
     ///
     /// This is used to optimize DB commits depending on the size of the state.
     fn size_hint(&self) -> Option<usize>;
+
+    /// Takes the access lists of the blocks executed since the last call, one entry per block
+    /// that contains the access lists of the block's transactions, see
+    /// [`access_list`](crate::access_list).
+    ///
+    /// Returns an empty list if the executor doesn't collect access lists.
+    fn take_access_lists(&mut self) -> Vec<Vec<AccessList>> {
+        Vec::new()
+    }
 }
 
 /// The output of an ethereum block.
@@ -105,6 +114,10 @@ pub struct BlockExecutionOutput<T> {
     pub requests: Vec<Request>,
     /// The total gas used by the block.
     pub gas_used: u64,
+    /// The access lists of the transactions in the block, see [`access_list`](crate::access_list).
+    ///
+    /// `None` if the executor doesn't collect access lists.
+    pub access_lists: Option<Vec<AccessList>>,
 }
 
 /// A helper type for ethereum block inputs that consists of a block and the total difficulty.
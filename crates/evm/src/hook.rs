@@ -0,0 +1,390 @@
+//! Hooks that inspect the execution of the blocks the node executes.
+//!
+//! A [`BlockExecutionHook`] is registered with a block executor and is asked for a
+//! [`BlockInspector`] for every block the executor executes, whether the block is executed by the
+//! pipeline or by the engine. The inspector is attached to the EVM while the block's transactions
+//! are executed, and is notified when the block's execution finished.
+
+use core::fmt;
+use reth_primitives::{address, Address, BlockWithSenders, Receipt, U256};
+use revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, Interpreter},
+    Database, EvmContext, Inspector,
+};
+use revm_primitives::TxEnv;
+
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+/// The address system calls are made from, see [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788)
+/// and [EIP-7002](https://eips.ethereum.org/EIPS/eip-7002).
+const SYSTEM_ADDRESS: Address = address!("fffffffffffffffffffffffffffffffffffffffe");
+
+/// A hook that can attach a [`BlockInspector`] to the execution of blocks.
+///
+/// The hook is called for every block that is executed, including blocks that are executed but
+/// never become canonical, e.g. blocks of side chains or invalid blocks.
+pub trait BlockExecutionHook: Send + Sync + fmt::Debug {
+    /// Returns the inspector to attach to the execution of the given block, or `None` if the
+    /// block should not be inspected.
+    fn block_inspector(&self, block: &BlockWithSenders) -> Option<Box<dyn BlockInspector>>;
+}
+
+/// Inspects the execution of a block's transactions.
+///
+/// Unlike an [`Inspector`], a block inspector is independent of the database the block is executed
+/// on. System calls, e.g. the [EIP-4788](https://eips.ethereum.org/EIPS/eip-4788) beacon root
+/// contract call, are not inspected.
+///
+/// All methods do nothing by default.
+#[allow(unused_variables)]
+pub trait BlockInspector: Send {
+    /// Called before the transaction at the given index in the block is executed.
+    fn transaction_start(&mut self, index: usize, tx: &TxEnv) {}
+
+    /// Called before every instruction of the interpreter is executed.
+    ///
+    /// This is called for every instruction executed by the block's transactions, implementing it
+    /// slows down execution considerably.
+    fn step(&mut self, interp: &Interpreter) {}
+
+    /// Called when a call is made at the given depth, the transaction's call is made at depth 0.
+    fn call(&mut self, depth: u64, inputs: &CallInputs) {}
+
+    /// Called when a call at the given depth returned.
+    fn call_end(&mut self, depth: u64, inputs: &CallInputs, outcome: &CallOutcome) {}
+
+    /// Called when a contract is created at the given depth.
+    fn create(&mut self, depth: u64, inputs: &CreateInputs) {}
+
+    /// Called when the creation of a contract at the given depth returned.
+    fn create_end(&mut self, depth: u64, inputs: &CreateInputs, outcome: &CreateOutcome) {}
+
+    /// Called when a contract self destructs, sending its balance to the target.
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {}
+
+    /// Called after the transaction at the given index in the block was executed.
+    fn transaction_end(&mut self, index: usize) {}
+
+    /// Called after all transactions of the block were executed, with the receipts of the block.
+    fn block_end(&mut self, receipts: &[Receipt]) {}
+}
+
+/// An [`Inspector`] that forwards the execution of a block's transactions to a
+/// [`BlockInspector`].
+#[derive(Debug)]
+pub struct HookInspector<'a> {
+    /// The block inspector.
+    inspector: &'a mut dyn BlockInspector,
+    /// The index of the transaction that is executed next.
+    index: usize,
+    /// Whether a system call is executed.
+    system_call: bool,
+}
+
+impl<'a> HookInspector<'a> {
+    /// Creates a new inspector that forwards to the given block inspector.
+    pub fn new(inspector: &'a mut dyn BlockInspector) -> Self {
+        Self { inspector, index: 0, system_call: false }
+    }
+
+    /// Called when a frame starts at the given depth.
+    ///
+    /// Returns `false` if the frame is part of a system call.
+    fn frame_start(&mut self, depth: u64, tx: &TxEnv) -> bool {
+        if depth == 0 {
+            self.system_call = tx.caller == SYSTEM_ADDRESS;
+            if !self.system_call {
+                self.inspector.transaction_start(self.index, tx);
+            }
+        }
+        !self.system_call
+    }
+
+    /// Called when a frame at the given depth ended, after the frame's end was inspected.
+    fn frame_end(&mut self, depth: u64) {
+        if depth != 0 {
+            return
+        }
+        if self.system_call {
+            self.system_call = false;
+        } else {
+            self.inspector.transaction_end(self.index);
+            self.index += 1;
+        }
+    }
+}
+
+impl fmt::Debug for dyn BlockInspector + '_ {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BlockInspector").finish_non_exhaustive()
+    }
+}
+
+impl<'a, DB: Database> Inspector<DB> for HookInspector<'a> {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        if !self.system_call {
+            self.inspector.step(interp);
+        }
+    }
+
+    fn call(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let depth = context.journaled_state.depth();
+        if self.frame_start(depth, &context.env.tx) {
+            self.inspector.call(depth, inputs);
+        }
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        let depth = context.journaled_state.depth();
+        if !self.system_call {
+            self.inspector.call_end(depth, inputs, &outcome);
+        }
+        self.frame_end(depth);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        let depth = context.journaled_state.depth();
+        if self.frame_start(depth, &context.env.tx) {
+            self.inspector.create(depth, inputs);
+        }
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let depth = context.journaled_state.depth();
+        if !self.system_call {
+            self.inspector.create_end(depth, inputs, &outcome);
+        }
+        self.frame_end(depth);
+        outcome
+    }
+
+    fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+        if !self.system_call {
+            self.inspector.selfdestruct(contract, target, value);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use events::{BlockExecutionEvents, ExecutionEvent, ExecutionEventsHook};
+
+#[cfg(feature = "std")]
+mod events {
+    use super::{BlockExecutionHook, BlockInspector};
+    use reth_primitives::{
+        Address, BlockNumber, BlockWithSenders, Bytes, Receipt, TxHash, B256, U256,
+    };
+    use revm::interpreter::{CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome};
+    use revm_primitives::TxEnv;
+    use std::sync::mpsc::{self, Receiver, Sender};
+
+    /// A structured event of a block's execution.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum ExecutionEvent {
+        /// A transaction started executing.
+        TransactionStart {
+            /// The index of the transaction in the block.
+            index: usize,
+            /// The hash of the transaction.
+            hash: TxHash,
+        },
+        /// A call was made.
+        Call {
+            /// The depth of the call.
+            depth: u64,
+            /// The kind of call.
+            scheme: CallScheme,
+            /// The caller.
+            from: Address,
+            /// The account whose storage the call executes on.
+            to: Address,
+            /// The value transferred by the call.
+            value: U256,
+            /// The input of the call.
+            input: Bytes,
+        },
+        /// A call returned.
+        CallEnd {
+            /// The depth of the call.
+            depth: u64,
+            /// Whether the call was successful.
+            success: bool,
+            /// The gas spent by the call.
+            gas_used: u64,
+            /// The output of the call.
+            output: Bytes,
+        },
+        /// A contract creation started.
+        Create {
+            /// The depth of the creation.
+            depth: u64,
+            /// The creator.
+            from: Address,
+            /// The value transferred to the created contract.
+            value: U256,
+            /// The init code of the contract.
+            init_code: Bytes,
+        },
+        /// A contract creation returned.
+        CreateEnd {
+            /// The depth of the creation.
+            depth: u64,
+            /// Whether the creation was successful.
+            success: bool,
+            /// The address of the created contract, if any.
+            address: Option<Address>,
+        },
+        /// A contract self destructed.
+        SelfDestruct {
+            /// The contract that self destructed.
+            contract: Address,
+            /// The account the contract's balance was sent to.
+            target: Address,
+            /// The balance of the contract.
+            value: U256,
+        },
+        /// A transaction finished executing.
+        TransactionEnd {
+            /// The index of the transaction in the block.
+            index: usize,
+        },
+    }
+
+    /// The execution events of a block.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct BlockExecutionEvents {
+        /// The number of the block.
+        pub number: BlockNumber,
+        /// The hash of the block.
+        pub hash: B256,
+        /// The events, in the order they occurred.
+        pub events: Vec<ExecutionEvent>,
+        /// The receipts of the block's transactions.
+        pub receipts: Vec<Receipt>,
+    }
+
+    /// A [`BlockExecutionHook`] that streams the execution events of every executed block to a
+    /// channel.
+    ///
+    /// Blocks are only sent once their execution finished, blocks that failed to execute are not
+    /// sent.
+    #[derive(Debug, Clone)]
+    pub struct ExecutionEventsHook {
+        sender: Sender<BlockExecutionEvents>,
+    }
+
+    impl ExecutionEventsHook {
+        /// Creates a new hook and the receiving end of its channel.
+        pub fn new() -> (Self, Receiver<BlockExecutionEvents>) {
+            let (sender, receiver) = mpsc::channel();
+            (Self { sender }, receiver)
+        }
+    }
+
+    impl BlockExecutionHook for ExecutionEventsHook {
+        fn block_inspector(&self, block: &BlockWithSenders) -> Option<Box<dyn BlockInspector>> {
+            Some(Box::new(EventsRecorder {
+                sender: self.sender.clone(),
+                hashes: block.body.iter().map(|tx| tx.hash()).collect(),
+                events: BlockExecutionEvents {
+                    number: block.number,
+                    hash: block.hash_slow(),
+                    events: Vec::new(),
+                    receipts: Vec::new(),
+                },
+            }))
+        }
+    }
+
+    /// Records the execution events of a block and sends them once the block was executed.
+    #[derive(Debug)]
+    struct EventsRecorder {
+        sender: Sender<BlockExecutionEvents>,
+        hashes: Vec<TxHash>,
+        events: BlockExecutionEvents,
+    }
+
+    impl BlockInspector for EventsRecorder {
+        fn transaction_start(&mut self, index: usize, _tx: &TxEnv) {
+            let hash = self.hashes.get(index).copied().unwrap_or_default();
+            self.events.events.push(ExecutionEvent::TransactionStart { index, hash });
+        }
+
+        fn call(&mut self, depth: u64, inputs: &CallInputs) {
+            self.events.events.push(ExecutionEvent::Call {
+                depth,
+                scheme: inputs.scheme,
+                from: inputs.caller,
+                to: inputs.target_address,
+                value: inputs.value.get(),
+                input: inputs.input.clone(),
+            });
+        }
+
+        fn call_end(&mut self, depth: u64, _inputs: &CallInputs, outcome: &CallOutcome) {
+            self.events.events.push(ExecutionEvent::CallEnd {
+                depth,
+                success: outcome.result.is_ok(),
+                gas_used: outcome.result.gas.spent(),
+                output: outcome.result.output.clone(),
+            });
+        }
+
+        fn create(&mut self, depth: u64, inputs: &CreateInputs) {
+            self.events.events.push(ExecutionEvent::Create {
+                depth,
+                from: inputs.caller,
+                value: inputs.value,
+                init_code: inputs.init_code.clone(),
+            });
+        }
+
+        fn create_end(&mut self, depth: u64, _inputs: &CreateInputs, outcome: &CreateOutcome) {
+            self.events.events.push(ExecutionEvent::CreateEnd {
+                depth,
+                success: outcome.result.is_ok(),
+                address: outcome.address,
+            });
+        }
+
+        fn selfdestruct(&mut self, contract: Address, target: Address, value: U256) {
+            self.events.events.push(ExecutionEvent::SelfDestruct { contract, target, value });
+        }
+
+        fn transaction_end(&mut self, index: usize) {
+            self.events.events.push(ExecutionEvent::TransactionEnd { index });
+        }
+
+        fn block_end(&mut self, receipts: &[Receipt]) {
+            let events = BlockExecutionEvents {
+                events: std::mem::take(&mut self.events.events),
+                receipts: receipts.to_vec(),
+                ..self.events
+            };
+            // the receiver may have been dropped, in which case the events are discarded
+            let _ = self.sender.send(events);
+        }
+    }
+}
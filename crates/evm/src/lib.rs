@@ -12,14 +12,18 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+use core::num::NonZeroUsize;
 use reth_chainspec::ChainSpec;
 use reth_primitives::{revm::env::fill_block_env, Address, Header, TransactionSigned, U256};
 use revm::{inspector_handle_register, Database, Evm, EvmBuilder, GetInspector};
 use revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, SpecId, TxEnv};
 
+pub mod access_list;
 pub mod either;
 pub mod execute;
+pub mod hook;
 pub mod noop;
+pub mod precompile;
 pub mod provider;
 
 #[cfg(any(test, feature = "test-utils"))]
@@ -94,6 +98,14 @@ pub trait ConfigureEvm: ConfigureEvmEnv {
             .append_handler_register(inspector_handle_register)
             .build()
     }
+
+    /// Returns the number of threads used to execute the transactions of a block optimistically
+    /// in parallel, or `None` if they are executed one after another.
+    ///
+    /// Block executors that don't support parallel execution ignore this.
+    fn parallel_execution_threads(&self) -> Option<NonZeroUsize> {
+        None
+    }
 }
 
 /// This represents the set of methods used to configure the EVM's environment before block
@@ -0,0 +1,403 @@
+//! Precompiles that are installed on top of the precompiles of a hardfork, and wrappers that
+//! meter the calls to the precompiles of the EVM.
+
+use core::{
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use reth_primitives::{Address, Bytes};
+use revm::{
+    handler::register::{EvmHandler, HandleRegisterBox},
+    precompile::Precompile,
+    ContextPrecompile, Database,
+};
+use revm_primitives::{Env, PrecompileErrors, PrecompileResult, SpecId, StatefulPrecompile};
+
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, collections::BTreeMap, format, sync::Arc, vec::Vec};
+
+#[cfg(feature = "std")]
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
+#[cfg(feature = "std")]
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, RwLock},
+    time::Instant,
+};
+
+/// Precompiles that are added to, or replace, the precompiles of the EVM from a hardfork on.
+///
+/// A precompile is registered for the [`SpecId`] of the hardfork that activates it, and is
+/// installed in every EVM of that hardfork and all later hardforks. If multiple precompiles are
+/// active at the same address, the one registered for the latest hardfork is installed.
+///
+/// This allows chains to add custom precompiles without implementing their own
+/// [`ConfigureEvm`](crate::ConfigureEvm).
+#[derive(Clone, Default)]
+pub struct PrecompileOverrides {
+    /// Registered precompiles, by the hardfork that activates them.
+    precompiles: Arc<BTreeMap<SpecId, Vec<(Address, Precompile)>>>,
+}
+
+impl PrecompileOverrides {
+    /// Creates an empty set of overrides.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the precompile at the given address, from the given hardfork on.
+    pub fn register(
+        &mut self,
+        spec_id: SpecId,
+        address: Address,
+        precompile: impl Into<Precompile>,
+    ) -> &mut Self {
+        Arc::make_mut(&mut self.precompiles)
+            .entry(spec_id)
+            .or_default()
+            .push((address, precompile.into()));
+        self
+    }
+
+    /// Registers the precompile at the given address, from the given hardfork on.
+    ///
+    /// See also [`PrecompileOverrides::register`].
+    pub fn with_precompile(
+        mut self,
+        spec_id: SpecId,
+        address: Address,
+        precompile: impl Into<Precompile>,
+    ) -> Self {
+        self.register(spec_id, address, precompile);
+        self
+    }
+
+    /// Returns `true` if no precompiles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+
+    /// Returns the precompiles that are active at the given hardfork, in the order they're
+    /// installed.
+    pub fn active(&self, spec_id: SpecId) -> impl Iterator<Item = (Address, &Precompile)> + '_ {
+        self.precompiles
+            .range(..=spec_id)
+            .flat_map(|(_, precompiles)| precompiles.iter())
+            .map(|(address, precompile)| (*address, precompile))
+    }
+
+    /// Installs the precompiles that are active at the hardfork of the handler, on top of the
+    /// precompiles the handler already loads.
+    pub fn install<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>) {
+        let precompiles = self
+            .active(handler.cfg.spec_id)
+            .map(|(address, precompile)| (address, precompile.clone()))
+            .collect::<Vec<_>>();
+        if precompiles.is_empty() {
+            return
+        }
+
+        let load_precompiles = handler.pre_execution.load_precompiles.clone();
+        handler.pre_execution.load_precompiles = Arc::new(move || {
+            let mut loaded = load_precompiles();
+            loaded.extend(
+                precompiles
+                    .iter()
+                    .map(|(address, precompile)| (*address, precompile.clone().into())),
+            );
+            loaded
+        });
+    }
+
+    /// Returns a handler register that installs the precompiles, see
+    /// [`PrecompileOverrides::install`].
+    ///
+    /// The register is re-applied whenever the spec of the EVM changes.
+    pub fn handler_register<EXT, DB: Database>(&self) -> HandleRegisterBox<EXT, DB> {
+        let overrides = self.clone();
+        Box::new(move |handler| overrides.install(handler))
+    }
+}
+
+impl fmt::Debug for PrecompileOverrides {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.precompiles.iter().map(|(spec_id, precompiles)| {
+                (spec_id, precompiles.iter().map(|(address, _)| address).collect::<Vec<_>>())
+            }))
+            .finish()
+    }
+}
+
+/// A budget of gas that the precompiles may use in a single transaction.
+///
+/// Once the precompiles called by a transaction used more gas than the budget, the transaction
+/// is aborted with a fatal precompile error. Failed precompile calls use all the gas they were
+/// given, like in the EVM.
+///
+/// This protects shared RPC nodes from calls that spend their gas on expensive precompiles, e.g.
+/// pairing checks or point evaluations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrecompileGasBudget {
+    /// The gas the precompiles may use in a single transaction.
+    limit: u64,
+}
+
+impl PrecompileGasBudget {
+    /// Creates a budget of the given amount of gas.
+    pub const fn new(limit: u64) -> Self {
+        Self { limit }
+    }
+
+    /// Returns the gas the precompiles may use in a single transaction.
+    pub const fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    /// Tracks the gas used by the precompiles the handler loads against the budget.
+    ///
+    /// Only precompiles that can be called through a shared reference are tracked.
+    pub fn install<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>) {
+        let limit = self.limit;
+        let load_precompiles = handler.pre_execution.load_precompiles.clone();
+        handler.pre_execution.load_precompiles = Arc::new(move || {
+            // the precompiles are loaded for every transaction, so the used gas is per transaction
+            let used = Arc::new(AtomicU64::new(0));
+            let mut loaded = load_precompiles();
+            for precompile in loaded.to_mut().values_mut() {
+                wrap_precompile(precompile, |inner| BudgetedPrecompile {
+                    inner,
+                    limit,
+                    used: used.clone(),
+                });
+            }
+            loaded
+        });
+    }
+
+    /// Returns a handler register that installs the budget, see
+    /// [`PrecompileGasBudget::install`].
+    pub fn handler_register<EXT, DB: Database>(&self) -> HandleRegisterBox<EXT, DB> {
+        let budget = *self;
+        Box::new(move |handler| budget.install(handler))
+    }
+}
+
+/// A precompile that tracks the gas it uses against a [`PrecompileGasBudget`].
+struct BudgetedPrecompile {
+    inner: Precompile,
+    limit: u64,
+    /// The gas used by all precompiles in the current transaction.
+    used: Arc<AtomicU64>,
+}
+
+impl StatefulPrecompile for BudgetedPrecompile {
+    fn call(&self, bytes: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+        let result = self.inner.clone().call(bytes, gas_limit, env);
+        let gas_used = match &result {
+            Ok(output) => output.gas_used,
+            Err(PrecompileErrors::Error(_)) => gas_limit,
+            Err(PrecompileErrors::Fatal { .. }) => return result,
+        };
+
+        let used = self.used.fetch_add(gas_used, Ordering::Relaxed).saturating_add(gas_used);
+        if used > self.limit {
+            return Err(PrecompileErrors::Fatal {
+                msg: format!("precompiles used more than the gas budget of {}", self.limit),
+            })
+        }
+        result
+    }
+}
+
+/// Records the number and the duration of the calls to each precompile of the EVMs it's installed
+/// in, see [`PrecompileMetrics`].
+///
+/// The metrics of a precompile are registered once, when it's first loaded, and are shared by
+/// all clones of the recorder. Building an EVM only wraps its precompiles.
+#[cfg(feature = "std")]
+#[derive(Clone, Default)]
+pub struct PrecompileMetricsRecorder {
+    /// The registered metrics, by the address of the precompile.
+    metrics: Arc<RwLock<HashMap<Address, PrecompileMetrics>>>,
+}
+
+#[cfg(feature = "std")]
+impl PrecompileMetricsRecorder {
+    /// Creates a new recorder without registered metrics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the metrics of the precompile at the given address, registering them if necessary.
+    fn metrics(&self, address: Address) -> PrecompileMetrics {
+        if let Some(metrics) =
+            self.metrics.read().unwrap_or_else(|err| err.into_inner()).get(&address)
+        {
+            return metrics.clone()
+        }
+        self.metrics
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .entry(address)
+            .or_insert_with(|| PrecompileMetrics::for_address(address))
+            .clone()
+    }
+
+    /// Meters the precompiles the handler loads.
+    ///
+    /// Only precompiles that can be called through a shared reference are metered.
+    pub fn install<EXT, DB: Database>(&self, handler: &mut EvmHandler<'_, EXT, DB>) {
+        let recorder = self.clone();
+        let load_precompiles = handler.pre_execution.load_precompiles.clone();
+        handler.pre_execution.load_precompiles = Arc::new(move || {
+            let mut loaded = load_precompiles();
+            for (address, precompile) in loaded.to_mut().iter_mut() {
+                wrap_precompile(precompile, |inner| MeteredPrecompile {
+                    inner,
+                    metrics: recorder.metrics(*address),
+                });
+            }
+            loaded
+        });
+    }
+
+    /// Returns a handler register that meters the precompiles, see
+    /// [`PrecompileMetricsRecorder::install`].
+    pub fn handler_register<EXT, DB: Database>(&self) -> HandleRegisterBox<EXT, DB> {
+        let recorder = self.clone();
+        Box::new(move |handler| recorder.install(handler))
+    }
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for PrecompileMetricsRecorder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrecompileMetricsRecorder").finish_non_exhaustive()
+    }
+}
+
+/// Metrics of the calls to a precompile.
+#[cfg(feature = "std")]
+#[derive(Metrics, Clone)]
+#[metrics(scope = "evm.precompile")]
+pub struct PrecompileMetrics {
+    /// The number of calls to the precompile
+    calls_total: Counter,
+    /// The number of calls to the precompile that failed
+    failed_calls_total: Counter,
+    /// The duration of a call to the precompile
+    call_duration_seconds: Histogram,
+}
+
+#[cfg(feature = "std")]
+impl PrecompileMetrics {
+    /// Returns the metrics of the precompile at the given address.
+    pub fn for_address(address: Address) -> Self {
+        Self::new_with_labels(&[("address", address.to_string())])
+    }
+}
+
+/// A precompile that records its calls in [`PrecompileMetrics`].
+#[cfg(feature = "std")]
+struct MeteredPrecompile {
+    inner: Precompile,
+    metrics: PrecompileMetrics,
+}
+
+#[cfg(feature = "std")]
+impl StatefulPrecompile for MeteredPrecompile {
+    fn call(&self, bytes: &Bytes, gas_limit: u64, env: &Env) -> PrecompileResult {
+        let start = Instant::now();
+        let result = self.inner.clone().call(bytes, gas_limit, env);
+        self.metrics.call_duration_seconds.record(start.elapsed());
+        self.metrics.calls_total.increment(1);
+        if result.is_err() {
+            self.metrics.failed_calls_total.increment(1);
+        }
+        result
+    }
+}
+
+/// Replaces the precompile with the wrapper returned by `wrap` if it's an ordinary precompile that
+/// can be called through a shared reference. Other precompiles are kept as they are.
+fn wrap_precompile<DB, P>(
+    precompile: &mut ContextPrecompile<DB>,
+    wrap: impl FnOnce(Precompile) -> P,
+) where
+    DB: Database,
+    P: StatefulPrecompile + 'static,
+{
+    if let ContextPrecompile::Ordinary(
+        inner @ (Precompile::Standard(_) | Precompile::Env(_) | Precompile::Stateful(_)),
+    ) = precompile
+    {
+        let wrapped = wrap(inner.clone());
+        *precompile = ContextPrecompile::Ordinary(Precompile::Stateful(Arc::new(wrapped)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use revm_primitives::{PrecompileError, PrecompileOutput};
+
+    fn noop(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+        Ok(PrecompileOutput::new(0, Bytes::new()))
+    }
+
+    fn hundred_gas(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+        Ok(PrecompileOutput::new(100, Bytes::new()))
+    }
+
+    fn fail(_input: &Bytes, _gas_limit: u64) -> PrecompileResult {
+        Err(PrecompileError::OutOfGas.into())
+    }
+
+    #[test]
+    fn active_precompiles() {
+        let first = Address::with_last_byte(0x10);
+        let second = Address::with_last_byte(0x11);
+        let overrides = PrecompileOverrides::new()
+            .with_precompile(SpecId::SHANGHAI, first, Precompile::Standard(noop))
+            .with_precompile(SpecId::CANCUN, second, Precompile::Standard(noop));
+
+        let active =
+            |spec_id| overrides.active(spec_id).map(|(address, _)| address).collect::<Vec<_>>();
+        assert!(active(SpecId::MERGE).is_empty());
+        assert_eq!(active(SpecId::SHANGHAI), vec![first]);
+        assert_eq!(active(SpecId::CANCUN), vec![first, second]);
+    }
+
+    #[test]
+    fn precompile_metrics_are_registered_once() {
+        let recorder = PrecompileMetricsRecorder::new();
+        let address = Address::with_last_byte(0x01);
+        recorder.metrics(address);
+        recorder.clone().metrics(address);
+        assert_eq!(recorder.metrics.read().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn precompile_gas_budget() {
+        let used = Arc::new(AtomicU64::new(0));
+        let budgeted = |inner| BudgetedPrecompile { inner, limit: 250, used: used.clone() };
+        let cheap = budgeted(Precompile::Standard(hundred_gas));
+        let failing = budgeted(Precompile::Standard(fail));
+        let env = Env::default();
+
+        assert!(cheap.call(&Bytes::new(), 1_000, &env).is_ok());
+        assert!(cheap.call(&Bytes::new(), 1_000, &env).is_ok());
+        assert_eq!(used.load(Ordering::Relaxed), 200);
+
+        // failed calls use all the gas they were given
+        assert!(matches!(failing.call(&Bytes::new(), 50, &env), Err(PrecompileErrors::Error(_))));
+        assert!(matches!(
+            cheap.call(&Bytes::new(), 1_000, &env),
+            Err(PrecompileErrors::Fatal { .. })
+        ));
+    }
+}
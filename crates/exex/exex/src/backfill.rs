@@ -0,0 +1,293 @@
+use crate::ExExNotification;
+use futures::{stream::FuturesOrdered, Stream, StreamExt};
+use reth_evm::execute::{BatchExecutor, BlockExecutionError, BlockExecutorProvider};
+use reth_primitives::{BlockNumHash, BlockNumber, SealedBlockWithSenders};
+use reth_provider::{BlockReader, Chain, ExecutionOutcome, ProviderError, StateProviderFactory};
+use reth_prune_types::PruneModes;
+use reth_revm::database::StateProviderDatabase;
+use std::{
+    ops::RangeInclusive,
+    pin::Pin,
+    sync::Arc,
+    task::{ready, Context, Poll},
+};
+use tokio::task::JoinHandle;
+
+/// The default number of blocks in a single backfilled chain.
+pub const DEFAULT_BACKFILL_BATCH_SIZE: u64 = 100;
+
+/// The default number of batches that are replayed in parallel.
+pub const DEFAULT_BACKFILL_PARALLELISM: usize = 4;
+
+/// How the execution outcomes of backfilled blocks are obtained.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BackfillMode {
+    /// Re-execute the blocks on top of the historical state of their parent.
+    ///
+    /// The execution outcomes contain both the state changes and the receipts of the blocks.
+    #[default]
+    Execute,
+    /// Read the stored receipts of the blocks instead of re-executing them.
+    ///
+    /// This is considerably cheaper, but the execution outcomes contain no state changes, and the
+    /// receipts of the blocks must not have been pruned.
+    Receipts,
+}
+
+/// Factory for [`BackfillJob`]s, which replay historical blocks as [`ExExNotification`]s.
+///
+/// Can be obtained from [`ExExContext::backfill_job_factory`](crate::ExExContext::backfill_job_factory).
+#[derive(Debug, Clone)]
+pub struct BackfillJobFactory<E, P> {
+    executor: E,
+    provider: P,
+    mode: BackfillMode,
+    batch_size: u64,
+    parallelism: usize,
+}
+
+impl<E, P> BackfillJobFactory<E, P> {
+    /// Creates a new factory that replays blocks with the given executor and provider.
+    pub const fn new(executor: E, provider: P) -> Self {
+        Self {
+            executor,
+            provider,
+            mode: BackfillMode::Execute,
+            batch_size: DEFAULT_BACKFILL_BATCH_SIZE,
+            parallelism: DEFAULT_BACKFILL_PARALLELISM,
+        }
+    }
+
+    /// Sets how the execution outcomes of backfilled blocks are obtained.
+    pub const fn with_mode(mut self, mode: BackfillMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the maximum number of blocks in a single backfilled chain.
+    pub fn with_batch_size(mut self, batch_size: u64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the number of batches that are replayed in parallel.
+    pub fn with_parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism.max(1);
+        self
+    }
+}
+
+impl<E, P> BackfillJobFactory<E, P>
+where
+    E: BlockExecutorProvider,
+    P: BlockReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    /// Creates a job that replays the given range of canonical blocks.
+    ///
+    /// The genesis block is never replayed, since it is not executed.
+    pub fn backfill(&self, range: RangeInclusive<BlockNumber>) -> BackfillJob<E, P> {
+        BackfillJob {
+            factory: self.clone(),
+            batches: BatchRanges::new((*range.start()).max(1)..=*range.end(), self.batch_size),
+            tasks: FuturesOrdered::new(),
+            tip: None,
+        }
+    }
+
+    /// Replays the given range of blocks into a single chain.
+    fn replay(&self, range: RangeInclusive<BlockNumber>) -> Result<Chain, BlockExecutionError> {
+        let blocks = self.provider.sealed_block_with_senders_range(range.clone())?;
+        if blocks.len() as u64 != range.end() - range.start() + 1 {
+            let missing = range.start() + blocks.len() as u64;
+            return Err(ProviderError::HeaderNotFound(missing.into()).into())
+        }
+
+        let outcome = match self.mode {
+            BackfillMode::Execute => self.execute(&blocks)?,
+            BackfillMode::Receipts => self.read_receipts(&blocks)?,
+        };
+        Ok(Chain::new(blocks, outcome, None))
+    }
+
+    /// Executes the blocks on top of the historical state of the parent of the first block.
+    fn execute(
+        &self,
+        blocks: &[SealedBlockWithSenders],
+    ) -> Result<ExecutionOutcome, BlockExecutionError> {
+        let (Some(first), Some(last)) = (blocks.first(), blocks.last()) else {
+            return Ok(ExecutionOutcome::default())
+        };
+
+        let state = self.provider.history_by_block_number(first.number - 1)?;
+        let mut executor =
+            self.executor.batch_executor(StateProviderDatabase::new(state), PruneModes::none());
+        executor.set_tip(last.number);
+
+        for block in blocks {
+            let td = self
+                .provider
+                .header_td_by_number(block.number)?
+                .ok_or(ProviderError::TotalDifficultyNotFound(block.number))?;
+            let block = block.clone().unseal();
+            executor.execute_and_verify_one((&block, td).into())?;
+        }
+
+        Ok(executor.finalize())
+    }
+
+    /// Reads the stored receipts of the blocks.
+    fn read_receipts(
+        &self,
+        blocks: &[SealedBlockWithSenders],
+    ) -> Result<ExecutionOutcome, BlockExecutionError> {
+        let Some(first) = blocks.first() else { return Ok(ExecutionOutcome::default()) };
+
+        let mut receipts = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let block_receipts = self
+                .provider
+                .receipts_by_block(block.number.into())?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block.number))?;
+            if block_receipts.len() != block.body.len() {
+                return Err(BlockExecutionError::msg(format!(
+                    "receipts of block {} are not available, they may have been pruned",
+                    block.number
+                )))
+            }
+            receipts.push(block_receipts.into_iter().map(Some).collect::<Vec<_>>());
+        }
+
+        Ok(ExecutionOutcome::new(Default::default(), receipts.into(), first.number, Vec::new()))
+    }
+}
+
+/// A job that replays a range of historical blocks, created by [`BackfillJobFactory::backfill`].
+///
+/// The range is split into batches that are replayed in parallel on blocking tasks, and yielded in
+/// order as [`ExExNotification::ChainCommitted`] notifications.
+///
+/// The job is independent of the live notifications of the `ExEx`, which can interleave both by
+/// polling them concurrently. Since historical state is read, the `ExEx` should not emit a
+/// [`ExExEvent::FinishedHeight`](crate::ExExEvent) at or above the start of the range until the job
+/// is done, so that the state isn't pruned in the meantime. If the replayed blocks don't connect,
+/// because the canonical chain was reorged during the backfill, the job yields an error and ends.
+#[must_use = "streams do nothing unless polled"]
+pub struct BackfillJob<E, P> {
+    factory: BackfillJobFactory<E, P>,
+    /// The batches that haven't been spawned yet.
+    batches: BatchRanges,
+    /// The batches that are being replayed.
+    tasks: FuturesOrdered<JoinHandle<Result<Chain, BlockExecutionError>>>,
+    /// The tip of the last yielded chain.
+    tip: Option<BlockNumHash>,
+}
+
+impl<E, P> BackfillJob<E, P>
+where
+    E: BlockExecutorProvider,
+    P: BlockReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    /// Spawns batches until the configured parallelism is reached.
+    fn spawn_batches(&mut self) {
+        while self.tasks.len() < self.factory.parallelism {
+            let Some(range) = self.batches.next() else { break };
+            let factory = self.factory.clone();
+            self.tasks.push_back(tokio::task::spawn_blocking(move || factory.replay(range)));
+        }
+    }
+
+    /// Ensures that the chain connects to the previously yielded chain.
+    fn connect(&mut self, chain: Chain) -> Result<Chain, BlockExecutionError> {
+        if let Some(tip) = self.tip.filter(|tip| *tip != chain.fork_block()) {
+            return Err(BlockExecutionError::AppendChainDoesntConnect {
+                chain_tip: Box::new(tip),
+                other_chain_fork: Box::new(chain.fork_block()),
+            })
+        }
+        self.tip = Some(chain.tip().num_hash());
+        Ok(chain)
+    }
+}
+
+impl<E, P> Stream for BackfillJob<E, P>
+where
+    E: BlockExecutorProvider,
+    P: BlockReader + StateProviderFactory + Clone + Unpin + 'static,
+{
+    type Item = Result<ExExNotification, BlockExecutionError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        this.spawn_batches();
+        let Some(result) = ready!(this.tasks.poll_next_unpin(cx)) else { return Poll::Ready(None) };
+
+        let result = result
+            .map_err(BlockExecutionError::other)
+            .and_then(|result| result)
+            .and_then(|chain| this.connect(chain));
+        if result.is_err() {
+            // the remaining batches can't be delivered in order anymore
+            this.batches = BatchRanges::default();
+            this.tasks = FuturesOrdered::new();
+        }
+
+        let notification =
+            result.map(|chain| ExExNotification::ChainCommitted { new: Arc::new(chain) });
+        Poll::Ready(Some(notification))
+    }
+}
+
+impl<E, P> std::fmt::Debug for BackfillJob<E, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BackfillJob")
+            .field("batches", &self.batches)
+            .field("tasks", &self.tasks.len())
+            .field("tip", &self.tip)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Splits a range of blocks into consecutive batches of at most `batch_size` blocks.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct BatchRanges {
+    /// The remaining range, `None` if exhausted.
+    remaining: Option<RangeInclusive<BlockNumber>>,
+    batch_size: u64,
+}
+
+impl BatchRanges {
+    fn new(range: RangeInclusive<BlockNumber>, batch_size: u64) -> Self {
+        Self { remaining: (!range.is_empty()).then_some(range), batch_size: batch_size.max(1) }
+    }
+}
+
+impl Iterator for BatchRanges {
+    type Item = RangeInclusive<BlockNumber>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self.remaining.take()?.into_inner();
+        let batch_end = start.saturating_add(self.batch_size - 1).min(end);
+        if batch_end < end {
+            self.remaining = Some(batch_end + 1..=end);
+        }
+        Some(start..=batch_end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_ranges() {
+        assert_eq!(BatchRanges::new(1..=0, 10).collect::<Vec<_>>(), []);
+        assert_eq!(BatchRanges::new(1..=10, 10).collect::<Vec<_>>(), [1..=10]);
+        assert_eq!(BatchRanges::new(1..=25, 10).collect::<Vec<_>>(), [1..=10, 11..=20, 21..=25]);
+        assert_eq!(BatchRanges::new(5..=7, 0).collect::<Vec<_>>(), [5..=5, 6..=6, 7..=7]);
+        assert_eq!(
+            BatchRanges::new(u64::MAX - 1..=u64::MAX, 10).collect::<Vec<_>>(),
+            [u64::MAX - 1..=u64::MAX]
+        );
+    }
+}
@@ -1,13 +1,16 @@
-use crate::{ExExEvent, ExExNotification};
+use crate::{BackfillJobFactory, ExExEvent, ExExNotification};
 use reth_node_api::FullNodeComponents;
 use reth_node_core::node_config::NodeConfig;
-use reth_primitives::Head;
+use reth_primitives::{BlockNumber, Head};
+use reth_provider::{ExExCheckpointReader, ExExCheckpointWriter, ProviderResult};
 use reth_tasks::TaskExecutor;
 use std::fmt::Debug;
 use tokio::sync::mpsc::{Receiver, UnboundedSender};
 
 /// Captures the context that an `ExEx` has access to.
 pub struct ExExContext<Node: FullNodeComponents> {
+    /// The ID of the `ExEx`.
+    pub id: String,
     /// The current head of the blockchain at launch.
     pub head: Head,
     /// The config of the node
@@ -37,6 +40,7 @@ pub struct ExExContext<Node: FullNodeComponents> {
 impl<Node: FullNodeComponents> Debug for ExExContext<Node> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ExExContext")
+            .field("id", &self.id)
             .field("head", &self.head)
             .field("config", &self.config)
             .field("reth_config", &self.reth_config)
@@ -82,4 +86,30 @@ impl<Node: FullNodeComponents> ExExContext<Node> {
     pub fn task_executor(&self) -> &TaskExecutor {
         self.components.task_executor()
     }
+
+    /// Returns a factory for jobs that replay historical blocks as [`ExExNotification`]s.
+    pub fn backfill_job_factory(&self) -> BackfillJobFactory<Node::Executor, Node::Provider> {
+        BackfillJobFactory::new(self.block_executor().clone(), self.provider().clone())
+    }
+
+    /// Returns the last checkpoint saved with [`Self::save_checkpoint`], if any.
+    pub fn checkpoint(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.provider().exex_checkpoint(&self.id)
+    }
+
+    /// Saves the highest block the `ExEx` has processed and persisted its state for in the node's
+    /// database, and emits a [`ExExEvent::FinishedHeight`] event for it.
+    ///
+    /// After a restart, the manager skips the blocks up to the checkpoint, and replays the blocks
+    /// between the checkpoint and the head of the node at launch before any new notifications.
+    ///
+    /// The checkpoint is written on a blocking task, since committing the database transaction
+    /// waits for any other write in progress.
+    pub async fn save_checkpoint(&self, height: BlockNumber) -> eyre::Result<()> {
+        let provider = self.provider().clone();
+        let id = self.id.clone();
+        tokio::task::spawn_blocking(move || provider.save_exex_checkpoint(&id, height)).await??;
+        self.events.send(ExExEvent::FinishedHeight(height))?;
+        Ok(())
+    }
 }
@@ -0,0 +1,220 @@
+use crate::ExExNotification;
+use reth_primitives::{Address, Log, Receipt, TxType, B256};
+use reth_provider::{Chain, ExecutionOutcome};
+use reth_revm::db::{states::reverts::Reverts, BundleState};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+
+/// A filter that reduces the state delivered to an `ExEx` in [`ExExNotification`]s.
+///
+/// The blocks of the notified chains are always delivered in full, while their
+/// [`ExecutionOutcome`] only retains the state and receipts the `ExEx` is interested in:
+///
+/// - [`Self::with_addresses`]: only the state changes of these accounts are retained, and only
+///   logs emitted by these accounts.
+/// - [`Self::with_topics`]: only logs with at least one of these topics are retained.
+/// - [`Self::with_tx_types`]: only receipts of transactions of these types are retained.
+///
+/// Receipts that are filtered out, or that have no matching logs if a log filter is set, are
+/// replaced with `None`, so that the remaining receipts still line up with the transactions of
+/// their block. Cached trie updates are never delivered to filtered `ExEx`'s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExExNotificationFilter {
+    /// The accounts to retain the state and logs of, if set.
+    addresses: Option<HashSet<Address>>,
+    /// The log topics to retain logs with, if set.
+    topics: Option<HashSet<B256>>,
+    /// The transaction types to retain receipts of, if set.
+    tx_types: Option<HashSet<TxType>>,
+}
+
+impl ExExNotificationFilter {
+    /// Only retain the state changes and logs of the given accounts.
+    pub fn with_addresses(mut self, addresses: impl IntoIterator<Item = Address>) -> Self {
+        self.addresses = Some(addresses.into_iter().collect());
+        self
+    }
+
+    /// Only retain logs with at least one of the given topics.
+    pub fn with_topics(mut self, topics: impl IntoIterator<Item = B256>) -> Self {
+        self.topics = Some(topics.into_iter().collect());
+        self
+    }
+
+    /// Only retain receipts of transactions of the given types.
+    pub fn with_tx_types(mut self, tx_types: impl IntoIterator<Item = TxType>) -> Self {
+        self.tx_types = Some(tx_types.into_iter().collect());
+        self
+    }
+
+    /// Returns the notification with the execution outcomes of its chains filtered.
+    pub fn filter_notification(&self, notification: &ExExNotification) -> ExExNotification {
+        match notification {
+            ExExNotification::ChainCommitted { new } => {
+                ExExNotification::ChainCommitted { new: self.filter_chain(new) }
+            }
+            ExExNotification::ChainReorged { old, new } => ExExNotification::ChainReorged {
+                old: self.filter_chain(old),
+                new: self.filter_chain(new),
+            },
+            ExExNotification::ChainReverted { old } => {
+                ExExNotification::ChainReverted { old: self.filter_chain(old) }
+            }
+        }
+    }
+
+    /// Returns a copy of the chain with its execution outcome filtered and without trie updates.
+    pub fn filter_chain(&self, chain: &Chain) -> Arc<Chain> {
+        let blocks = chain.blocks().values().cloned();
+        Arc::new(Chain::new(blocks, self.filter_execution_outcome(chain.execution_outcome()), None))
+    }
+
+    /// Returns a copy of the execution outcome that only retains the matching state and receipts.
+    ///
+    /// Only the retained state and receipts are copied.
+    pub fn filter_execution_outcome(&self, outcome: &ExecutionOutcome) -> ExecutionOutcome {
+        let bundle = match &self.addresses {
+            Some(addresses) => filter_bundle(&outcome.bundle, addresses),
+            None => outcome.bundle.clone(),
+        };
+        let receipts = outcome
+            .receipts
+            .iter()
+            .map(|receipts| {
+                receipts
+                    .iter()
+                    .map(|receipt| receipt.as_ref().and_then(|r| self.filter_receipt(r)))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        ExecutionOutcome::new(bundle, receipts, outcome.first_block, outcome.requests.clone())
+    }
+
+    /// Returns a copy of the receipt with only the matching logs, or `None` if the receipt is
+    /// filtered out.
+    fn filter_receipt(&self, receipt: &Receipt) -> Option<Receipt> {
+        if self.tx_types.as_ref().is_some_and(|tx_types| !tx_types.contains(&receipt.tx_type)) {
+            return None
+        }
+        if self.addresses.is_none() && self.topics.is_none() {
+            return Some(receipt.clone())
+        }
+        if !receipt.logs.iter().any(|log| self.matches_log(log)) {
+            return None
+        }
+        let mut receipt = receipt.clone();
+        receipt.logs.retain(|log| self.matches_log(log));
+        Some(receipt)
+    }
+
+    fn matches_log(&self, log: &Log) -> bool {
+        self.addresses.as_ref().map_or(true, |addresses| addresses.contains(&log.address)) &&
+            self.topics
+                .as_ref()
+                .map_or(true, |topics| log.topics().iter().any(|topic| topics.contains(topic)))
+    }
+}
+
+/// Returns a copy of the bundle state that only retains the state changes of the given accounts.
+fn filter_bundle(bundle: &BundleState, addresses: &HashSet<Address>) -> BundleState {
+    let state = bundle
+        .state
+        .iter()
+        .filter(|(address, _)| addresses.contains(*address))
+        .map(|(address, account)| (*address, account.clone()))
+        .collect::<HashMap<_, _>>();
+    let reverts = Reverts::new(
+        bundle
+            .reverts
+            .iter()
+            .map(|block_reverts| {
+                block_reverts
+                    .iter()
+                    .filter(|(address, _)| addresses.contains(address))
+                    .cloned()
+                    .collect()
+            })
+            .collect(),
+    );
+    let code_hashes = state
+        .values()
+        .filter_map(|account| account.info.as_ref().map(|info| info.code_hash))
+        .collect::<HashSet<_>>();
+    let contracts = bundle
+        .contracts
+        .iter()
+        .filter(|(code_hash, _)| code_hashes.contains(*code_hash))
+        .map(|(code_hash, code)| (*code_hash, code.clone()))
+        .collect();
+
+    BundleState {
+        state_size: state.values().map(|account| account.size_hint()).sum(),
+        reverts_size: reverts.iter().flatten().map(|(_, revert)| revert.size_hint()).sum(),
+        state,
+        contracts,
+        reverts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Account, Receipts, SealedBlockWithSenders};
+
+    #[test]
+    fn filter_execution_outcome() {
+        let indexed = Address::with_last_byte(1);
+        let other = Address::with_last_byte(2);
+        let topic = B256::with_last_byte(1);
+
+        let log = |address, topic| Log::new_unchecked(address, vec![topic], Default::default());
+        let receipt =
+            |tx_type, logs| Some(Receipt { tx_type, success: true, logs, ..Default::default() });
+        let outcome = ExecutionOutcome::new_init(
+            HashMap::from([
+                (indexed, (None, Some(Account::default()), HashMap::default())),
+                (other, (None, Some(Account::default()), HashMap::default())),
+            ]),
+            HashMap::from([(
+                1,
+                HashMap::from([(indexed, (Some(None), vec![])), (other, (Some(None), vec![]))]),
+            )]),
+            vec![],
+            Receipts {
+                receipt_vec: vec![vec![
+                    receipt(TxType::Eip1559, vec![log(indexed, topic), log(other, topic)]),
+                    receipt(TxType::Eip1559, vec![log(indexed, B256::ZERO)]),
+                    receipt(TxType::Legacy, vec![log(indexed, topic)]),
+                ]],
+            },
+            1,
+            vec![],
+        );
+        let chain = Chain::new([SealedBlockWithSenders::default()], outcome, None);
+
+        // an empty filter retains everything
+        let unfiltered = ExExNotificationFilter::default().filter_chain(&chain);
+        assert_eq!(unfiltered.execution_outcome(), chain.execution_outcome());
+
+        let filter = ExExNotificationFilter::default()
+            .with_addresses([indexed])
+            .with_topics([topic])
+            .with_tx_types([TxType::Eip1559]);
+        let filtered = filter.filter_chain(&chain);
+        assert_eq!(filtered.blocks(), chain.blocks());
+
+        let filtered = filtered.execution_outcome();
+        assert_eq!(
+            filtered.bundle_accounts_iter().map(|(address, _)| address).collect::<Vec<_>>(),
+            [indexed]
+        );
+        assert_eq!(filtered.bundle.reverts.iter().flatten().count(), 1);
+        assert_eq!(
+            filtered.receipts.receipt_vec,
+            [[receipt(TxType::Eip1559, vec![log(indexed, topic)]), None, None]]
+        );
+    }
+}
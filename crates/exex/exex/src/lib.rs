@@ -14,6 +14,14 @@
 //! in [`ExExContext`]. A new notification is emitted whenever blocks are executed in live and
 //! historical sync.
 //!
+//! # Backfill
+//!
+//! `ExEx`'s that need to process blocks from before they were installed can replay them with a
+//! [`BackfillJob`], created by the factory returned from
+//! [`ExExContext::backfill_job_factory`]. The job yields [`ExExNotification`]s for the requested
+//! range, generated in parallel by re-executing the blocks or reading their stored receipts, which
+//! the `ExEx` can interleave with its live notifications.
+//!
 //! # Pruning
 //!
 //! `ExEx`'s **SHOULD** emit an `ExExEvent::FinishedHeight` event to signify what blocks have been
@@ -23,8 +31,20 @@
 //! event. To clarify: if the `ExEx` emits `ExExEvent::FinishedHeight(0)` it will receive
 //! notifications for any `block_number > 0`.
 //!
+//! # Checkpoints
+//!
+//! `ExEx`'s that persist their own state can save the height up to which they persisted it with
+//! [`ExExContext::save_checkpoint`], which stores it in the node's database and emits the
+//! corresponding `ExExEvent::FinishedHeight` event. When the node restarts, the `ExEx` is resumed
+//! from its checkpoint: the blocks between the checkpoint and the head of the node are replayed to
+//! it with a [`BackfillJob`], before any new notifications are delivered.
+//!
 //! [`Future`]: std::future::Future
 //! [`ExExContext`]: crate::ExExContext
+//! [`ExExContext::backfill_job_factory`]: crate::ExExContext::backfill_job_factory
+//! [`ExExContext::save_checkpoint`]: crate::ExExContext::save_checkpoint
+//! [`BackfillJob`]: crate::BackfillJob
+//! [`ExExNotification`]: crate::ExExNotification
 //! [`CanonStateNotification`]: reth_provider::CanonStateNotification
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -34,12 +54,18 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod backfill;
+pub use backfill::*;
+
 mod context;
 pub use context::*;
 
 mod event;
 pub use event::*;
 
+mod filter;
+pub use filter::*;
+
 mod manager;
 pub use manager::*;
 
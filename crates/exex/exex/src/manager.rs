@@ -1,10 +1,13 @@
-use crate::{ExExEvent, ExExNotification, FinishedExExHeight};
+use crate::{ExExEvent, ExExNotification, ExExNotificationFilter, FinishedExExHeight};
+use futures::{Stream, StreamExt};
 use metrics::Gauge;
+use reth_evm::execute::BlockExecutionError;
 use reth_metrics::{metrics::Counter, Metrics};
 use reth_primitives::BlockNumber;
 use reth_tracing::tracing::debug;
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
+    fmt,
     future::{poll_fn, Future},
     pin::Pin,
     sync::{
@@ -29,12 +32,15 @@ struct ExExMetrics {
     events_sent_total: Counter,
 }
 
+/// Notifications that are sent to an `ExEx` before any notifications of the [`ExExManager`].
+type BackfillStream =
+    Pin<Box<dyn Stream<Item = Result<ExExNotification, BlockExecutionError>> + Send>>;
+
 /// A handle to an `ExEx` used by the [`ExExManager`] to communicate with `ExEx`'s.
 ///
 /// A handle should be created for each `ExEx` with a unique ID. The channels returned by
 /// [`ExExHandle::new`] should be given to the `ExEx`, while the handle itself should be given to
 /// the manager in [`ExExManager::new`].
-#[derive(Debug)]
 pub struct ExExHandle {
     /// The execution extension's ID.
     id: String,
@@ -43,10 +49,16 @@ pub struct ExExHandle {
 
     /// Channel to send [`ExExNotification`]s to the `ExEx`.
     sender: PollSender<ExExNotification>,
+    /// Filter applied to the [`ExExNotification`]s before they are sent to the `ExEx`, if any.
+    filter: Option<ExExNotificationFilter>,
     /// Channel to receive [`ExExEvent`]s from the `ExEx`.
     receiver: UnboundedReceiver<ExExEvent>,
     /// The ID of the next notification to send to this `ExEx`.
     next_notification_id: usize,
+    /// Notifications that are sent to the `ExEx` before any notifications of the manager, if any.
+    backfill: Option<BackfillStream>,
+    /// The next notification of the backfill, waiting for a slot in the channel.
+    pending_backfill: Option<ExExNotification>,
 
     /// The finished block number of the `ExEx`.
     ///
@@ -68,8 +80,11 @@ impl ExExHandle {
                 id: id.clone(),
                 metrics: ExExMetrics::new_with_labels(&[("exex", id)]),
                 sender: PollSender::new(notification_tx),
+                filter: None,
                 receiver: event_rx,
                 next_notification_id: 0,
+                backfill: None,
+                pending_backfill: None,
                 finished_height: None,
             },
             event_tx,
@@ -77,6 +92,70 @@ impl ExExHandle {
         )
     }
 
+    /// Sets the filter applied to the [`ExExNotification`]s sent to the `ExEx`.
+    pub fn with_filter(mut self, filter: ExExNotificationFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Resumes the `ExEx` from the given checkpoint, which is treated like a `FinishedHeight`
+    /// event the `ExEx` emitted before it was launched.
+    pub const fn with_checkpoint(mut self, checkpoint: BlockNumber) -> Self {
+        self.finished_height = Some(checkpoint);
+        self
+    }
+
+    /// Sets the notifications that are sent to the `ExEx` before any notifications of the
+    /// manager, e.g. a [`BackfillJob`](crate::BackfillJob) for the blocks it missed while the node
+    /// was offline.
+    ///
+    /// The notifications of the manager are buffered until the backfill is done, and an error of
+    /// the backfill is an error of the manager.
+    pub fn with_backfill<S>(mut self, backfill: S) -> Self
+    where
+        S: Stream<Item = Result<ExExNotification, BlockExecutionError>> + Send + 'static,
+    {
+        self.backfill = Some(Box::pin(backfill));
+        self
+    }
+
+    /// Returns the backfilled notification to send to the `ExEx`, with its filter applied.
+    fn filter_notification(&self, notification: &ExExNotification) -> ExExNotification {
+        match &self.filter {
+            Some(filter) => filter.filter_notification(notification),
+            None => notification.clone(),
+        }
+    }
+
+    /// Sends the notifications of the backfill to the `ExEx`.
+    ///
+    /// Returns [`Poll::Ready`] once there's no backfill left.
+    fn poll_backfill(&mut self, cx: &mut Context<'_>) -> Poll<eyre::Result<()>> {
+        loop {
+            if self.pending_backfill.is_none() {
+                let Some(backfill) = &mut self.backfill else { return Poll::Ready(Ok(())) };
+                match ready!(backfill.poll_next_unpin(cx)) {
+                    Some(notification) => self.pending_backfill = Some(notification?),
+                    None => {
+                        debug!(exex_id = %self.id, "Backfill finished");
+                        self.backfill = None;
+                        return Poll::Ready(Ok(()))
+                    }
+                }
+            }
+
+            ready!(self.sender.poll_reserve(cx))?;
+            let notification = self.pending_backfill.take().expect("pending backfill notification");
+            debug!(
+                exex_id = %self.id,
+                committed_tip = ?notification.committed_chain().map(|chain| chain.tip().number),
+                "Sending backfill notification"
+            );
+            self.sender.send_item(self.filter_notification(&notification))?;
+            self.metrics.notifications_sent_total.increment(1);
+        }
+    }
+
     /// Reserves a slot in the `PollSender` channel and sends the notification if the slot was
     /// successfully reserved.
     ///
@@ -85,6 +164,7 @@ impl ExExHandle {
         &mut self,
         cx: &mut Context<'_>,
         (notification_id, notification): &(usize, ExExNotification),
+        filtered: &mut FilteredNotifications,
     ) -> Poll<Result<(), PollSendError<ExExNotification>>> {
         if let Some(finished_height) = self.finished_height {
             match notification {
@@ -128,7 +208,11 @@ impl ExExHandle {
             %notification_id,
             "Sending notification"
         );
-        match self.sender.send_item(notification.clone()) {
+        let notification = match &self.filter {
+            Some(filter) => filtered.get_or_filter(*notification_id, notification, filter),
+            None => notification.clone(),
+        };
+        match self.sender.send_item(notification) {
             Ok(()) => {
                 self.next_notification_id = notification_id + 1;
                 self.metrics.notifications_sent_total.increment(1);
@@ -139,6 +223,49 @@ impl ExExHandle {
     }
 }
 
+impl fmt::Debug for ExExHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExExHandle")
+            .field("id", &self.id)
+            .field("filter", &self.filter)
+            .field("next_notification_id", &self.next_notification_id)
+            .field("backfill", &self.backfill.is_some())
+            .field("finished_height", &self.finished_height)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The filtered copies of the buffered notifications, by notification ID.
+///
+/// A notification is filtered once per distinct filter, and the filtered copy is shared by all
+/// `ExEx`'s with that filter.
+#[derive(Debug, Default)]
+struct FilteredNotifications(HashMap<usize, Vec<(ExExNotificationFilter, ExExNotification)>>);
+
+impl FilteredNotifications {
+    /// Returns the notification with the filter applied, filtering it if there's no filtered copy
+    /// yet.
+    fn get_or_filter(
+        &mut self,
+        notification_id: usize,
+        notification: &ExExNotification,
+        filter: &ExExNotificationFilter,
+    ) -> ExExNotification {
+        let filtered = self.0.entry(notification_id).or_default();
+        if let Some((_, notification)) = filtered.iter().find(|(other, _)| other == filter) {
+            return notification.clone()
+        }
+        let notification = filter.filter_notification(notification);
+        filtered.push((filter.clone(), notification.clone()));
+        notification
+    }
+
+    /// Removes the filtered copies of the notifications with IDs lower than the given ID.
+    fn remove_below(&mut self, min_id: usize) {
+        self.0.retain(|&notification_id, _| notification_id >= min_id);
+    }
+}
+
 /// Metrics for the `ExEx` manager.
 #[derive(Metrics)]
 #[metrics(scope = "exex_manager")]
@@ -181,6 +308,8 @@ pub struct ExExManager {
     /// The first element of the tuple is a monotonically increasing ID unique to the notification
     /// (the second element of the tuple).
     buffer: VecDeque<(usize, ExExNotification)>,
+    /// The filtered copies of the buffered notifications.
+    filtered: FilteredNotifications,
     /// Max size of the internal state notifications buffer.
     max_capacity: usize,
     /// Current state notifications buffer capacity.
@@ -233,6 +362,7 @@ impl ExExManager {
             min_id: 0,
             next_id: 0,
             buffer: VecDeque::with_capacity(max_capacity),
+            filtered: FilteredNotifications::default(),
             max_capacity,
             current_capacity: Arc::clone(&current_capacity),
 
@@ -304,14 +434,24 @@ impl Future for ExExManager {
         for idx in (0..self.exex_handles.len()).rev() {
             let mut exex = self.exex_handles.swap_remove(idx);
 
+            // the notifications of the manager are only sent once the backfill is done
+            let backfill_done = match exex.poll_backfill(cx) {
+                Poll::Ready(Ok(())) => true,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => false,
+            };
+
             // it is a logic error for this to ever underflow since the manager manages the
             // notification IDs
             let notification_index = exex
                 .next_notification_id
                 .checked_sub(self.min_id)
                 .expect("exex expected notification ID outside the manager's range");
-            if let Some(notification) = self.buffer.get(notification_index) {
-                if let Poll::Ready(Err(err)) = exex.send(cx, notification) {
+            let this = &mut *self;
+            if let Some(notification) =
+                this.buffer.get(notification_index).filter(|_| backfill_done)
+            {
+                if let Poll::Ready(Err(err)) = exex.send(cx, notification, &mut this.filtered) {
                     // the channel was closed, which is irrecoverable for the manager
                     return Poll::Ready(Err(err.into()))
                 }
@@ -323,6 +463,7 @@ impl Future for ExExManager {
         // remove processed buffered notifications
         debug!(%min_id, "Updating lowest notification id in buffer");
         self.buffer.retain(|&(id, _)| id >= min_id);
+        self.filtered.remove_below(min_id);
         self.min_id = min_id;
 
         // update capacity
@@ -481,9 +622,64 @@ impl Clone for ExExManagerHandle {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use futures::FutureExt;
+    use reth_primitives::SealedBlockWithSenders;
+    use reth_provider::{Chain, ExecutionOutcome};
+
     #[tokio::test]
     async fn delivers_events() {}
 
+    #[tokio::test]
+    async fn delivers_backfill_first() {
+        let chain = Arc::new(Chain::from_block(
+            SealedBlockWithSenders::default(),
+            ExecutionOutcome::default(),
+            None,
+        ));
+        let backfilled = ExExNotification::ChainCommitted { new: chain.clone() };
+        let live = ExExNotification::ChainReverted { old: chain };
+
+        let (handle, _events, mut notifications) = ExExHandle::new("test".to_string());
+        let handle = handle.with_backfill(futures::stream::iter([Ok(backfilled.clone())]));
+        let mut manager = ExExManager::new(vec![handle], 10);
+        manager.handle().send(live.clone()).unwrap();
+
+        // the channel only has room for the backfilled notification
+        assert!(poll_fn(|cx| Poll::Ready(manager.poll_unpin(cx).is_pending())).await);
+        assert_eq!(notifications.recv().await, Some(backfilled));
+
+        assert!(poll_fn(|cx| Poll::Ready(manager.poll_unpin(cx).is_pending())).await);
+        assert_eq!(notifications.recv().await, Some(live));
+    }
+
+    #[tokio::test]
+    async fn shares_filtered_notifications() {
+        let chain = Arc::new(Chain::from_block(
+            SealedBlockWithSenders::default(),
+            ExecutionOutcome::default(),
+            None,
+        ));
+        let notification = ExExNotification::ChainCommitted { new: chain };
+
+        let filter = ExExNotificationFilter::default().with_topics([Default::default()]);
+        let (first, _first_events, mut first_notifications) = ExExHandle::new("first".to_string());
+        let (second, _second_events, mut second_notifications) =
+            ExExHandle::new("second".to_string());
+        let mut manager = ExExManager::new(
+            vec![first.with_filter(filter.clone()), second.with_filter(filter)],
+            10,
+        );
+        manager.handle().send(notification).unwrap();
+        assert!(poll_fn(|cx| Poll::Ready(manager.poll_unpin(cx).is_pending())).await);
+
+        // the notification is filtered once for both `ExEx`'s
+        let first = first_notifications.recv().await.unwrap();
+        let second = second_notifications.recv().await.unwrap();
+        assert!(Arc::ptr_eq(&first.committed_chain().unwrap(), &second.committed_chain().unwrap()));
+        assert!(manager.filtered.0.is_empty());
+    }
+
     #[tokio::test]
     async fn capacity() {}
 
@@ -278,6 +278,7 @@ pub async fn test_exex_context_with_chain_spec(
     let (notifications_tx, notifications_rx) = tokio::sync::mpsc::channel(1);
 
     let ctx = ExExContext {
+        id: "test".to_string(),
         head,
         config: NodeConfig::test(),
         reth_config: reth_config::Config::default(),
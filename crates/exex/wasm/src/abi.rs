@@ -0,0 +1,121 @@
+//! The types of the notifications that are passed to WASM `ExEx`'s.
+//!
+//! Notifications are encoded as JSON, so that guests don't depend on the memory layout of reth's
+//! types. Changes to these types that aren't backwards compatible must bump [`ABI_VERSION`].
+
+use reth_execution_types::Chain;
+use reth_exex::ExExNotification;
+use reth_primitives::{Address, Bytes, B256};
+use serde::Serialize;
+
+/// The version of the ABI between the host and the guests, which guests return from their
+/// `reth_exex_abi_version` export.
+pub const ABI_VERSION: u32 = 1;
+
+/// A notification, tagged by its `type`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WasmNotification {
+    /// A chain was committed.
+    ChainCommitted {
+        /// The committed chain.
+        new: WasmChain,
+    },
+    /// A chain was reorged.
+    ChainReorged {
+        /// The reverted chain.
+        old: WasmChain,
+        /// The committed chain.
+        new: WasmChain,
+    },
+    /// A chain was reverted.
+    ChainReverted {
+        /// The reverted chain.
+        old: WasmChain,
+    },
+}
+
+impl From<&ExExNotification> for WasmNotification {
+    fn from(notification: &ExExNotification) -> Self {
+        match notification {
+            ExExNotification::ChainCommitted { new } => {
+                Self::ChainCommitted { new: new.as_ref().into() }
+            }
+            ExExNotification::ChainReorged { old, new } => {
+                Self::ChainReorged { old: old.as_ref().into(), new: new.as_ref().into() }
+            }
+            ExExNotification::ChainReverted { old } => {
+                Self::ChainReverted { old: old.as_ref().into() }
+            }
+        }
+    }
+}
+
+/// The blocks of a chain, in ascending order.
+#[derive(Debug, Serialize)]
+pub struct WasmChain {
+    /// The blocks of the chain.
+    pub blocks: Vec<WasmBlock>,
+}
+
+impl From<&Chain> for WasmChain {
+    fn from(chain: &Chain) -> Self {
+        let blocks = chain
+            .blocks_and_receipts()
+            .map(|(block, receipts)| WasmBlock {
+                number: block.number,
+                hash: block.hash(),
+                parent_hash: block.parent_hash,
+                timestamp: block.timestamp,
+                gas_used: block.gas_used,
+                transactions: block.body.iter().map(|tx| tx.hash()).collect(),
+                logs: receipts
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, receipt)| Some((index, receipt.as_ref()?)))
+                    .flat_map(|(transaction_index, receipt)| {
+                        receipt.logs.iter().map(move |log| WasmLog {
+                            transaction_index,
+                            address: log.address,
+                            topics: log.topics().to_vec(),
+                            data: log.data.data.clone(),
+                        })
+                    })
+                    .collect(),
+            })
+            .collect();
+        Self { blocks }
+    }
+}
+
+/// A block with the hashes of its transactions and the logs they emitted.
+#[derive(Debug, Serialize)]
+pub struct WasmBlock {
+    /// The block number.
+    pub number: u64,
+    /// The block hash.
+    pub hash: B256,
+    /// The hash of the parent block.
+    pub parent_hash: B256,
+    /// The block timestamp.
+    pub timestamp: u64,
+    /// The gas used by the transactions of the block.
+    pub gas_used: u64,
+    /// The hashes of the transactions of the block.
+    pub transactions: Vec<B256>,
+    /// The logs emitted by the transactions of the block.
+    pub logs: Vec<WasmLog>,
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug, Serialize)]
+pub struct WasmLog {
+    /// The index of the emitting transaction in its block.
+    pub transaction_index: usize,
+    /// The emitting account.
+    pub address: Address,
+    /// The topics of the log.
+    pub topics: Vec<B256>,
+    /// The data of the log.
+    pub data: Bytes,
+}
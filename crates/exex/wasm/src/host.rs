@@ -0,0 +1,225 @@
+//! The host that runs the logic of an `ExEx` compiled to WASM.
+
+use crate::abi::{WasmNotification, ABI_VERSION};
+use reth_exex::ExExNotification;
+use reth_primitives::{Address, BlockNumber};
+use reth_provider::{BlockHashReader, BlockNumReader, StateProvider, StateProviderFactory};
+use reth_tracing::tracing::info;
+use std::path::Path;
+use wasmtime::{Caller, Config, Engine, Extern, Linker, Memory, Module, Store, TypedFunc};
+use wasmtime_wasi::{preview1::WasiP1Ctx, WasiCtxBuilder};
+
+/// The data of the [`Store`] of a guest, available to the host functions.
+struct HostState<P> {
+    /// The name of the `ExEx`, used in its logs.
+    name: String,
+    /// The provider backing the host functions.
+    provider: P,
+    /// The WASI context of the guest.
+    wasi: WasiP1Ctx,
+}
+
+/// An `ExEx` that forwards notifications to a WASM guest.
+///
+/// Guests are WASI preview 1 modules that import the host functions of the `reth` module and
+/// export:
+///
+/// - `memory`: the memory of the guest.
+/// - `reth_exex_abi_version() -> u32`: the [`ABI_VERSION`] the guest was built against.
+/// - `reth_exex_alloc(len: u32) -> u32`: allocates `len` bytes the host writes a notification to.
+///   The guest takes ownership of the allocation.
+/// - `reth_exex_notification(ptr: u32, len: u32) -> i64`: handles the JSON encoded
+///   [`WasmNotification`] at `ptr`, and returns the height up to which the guest is done with the
+///   blocks, `-1` if it didn't make progress, or any other negative value if it failed.
+///
+/// The host functions give the guests read-only access to a small part of the node's state:
+///
+/// - `log(ptr: u32, len: u32)`: logs the UTF-8 message at `ptr`.
+/// - `latest_block_number() -> u64`: returns the number of the latest block.
+/// - `block_hash(number: u64, out: u32) -> u32`: writes the hash of the canonical block with the
+///   given number to `out`, and returns `1`, or `0` if the block doesn't exist.
+/// - `balance(address: u32, out: u32) -> u32`: writes the big endian balance of the 20 byte
+///   address at `address` in the latest state to `out`, and returns `1`, or `0` if the account
+///   doesn't exist.
+///
+/// Guests have no access to the filesystem, network or environment of the node, and the fuel they
+/// can consume to handle a single notification is limited.
+pub struct WasmExEx<P> {
+    store: Store<HostState<P>>,
+    memory: Memory,
+    alloc: TypedFunc<u32, u32>,
+    notification: TypedFunc<(u32, u32), i64>,
+    /// The fuel available to handle a single notification.
+    fuel: u64,
+}
+
+impl<P> WasmExEx<P>
+where
+    P: BlockHashReader + BlockNumReader + StateProviderFactory + Send + 'static,
+{
+    /// Loads the WASM module at the given path.
+    pub fn load(name: String, path: &Path, provider: P, fuel: u64) -> eyre::Result<Self> {
+        let module = std::fs::read(path)?;
+        Self::new(name, &module, provider, fuel)
+            .map_err(|err| eyre::eyre!("failed to load {}: {err:?}", path.display()))
+    }
+
+    /// Instantiates the given WASM module, in binary or text format.
+    pub fn new(name: String, module: &[u8], provider: P, fuel: u64) -> wasmtime::Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config)?;
+        let module = Module::new(&engine, module)?;
+
+        let mut linker = Linker::new(&engine);
+        wasmtime_wasi::preview1::add_to_linker_sync(&mut linker, |state: &mut HostState<P>| {
+            &mut state.wasi
+        })?;
+        add_host_functions(&mut linker)?;
+
+        // only forward the output of the guest, it doesn't get any other access to the node
+        let wasi = WasiCtxBuilder::new().inherit_stdout().inherit_stderr().build_p1();
+        let mut store = Store::new(&engine, HostState { name, provider, wasi });
+        store.set_fuel(fuel)?;
+
+        let instance = linker.instantiate(&mut store, &module)?;
+        // reactor modules must be initialized before their exports are called
+        if let Some(initialize) = instance.get_func(&mut store, "_initialize") {
+            initialize.typed::<(), ()>(&store)?.call(&mut store, ())?;
+        }
+
+        let version = instance
+            .get_typed_func::<(), u32>(&mut store, "reth_exex_abi_version")?
+            .call(&mut store, ())?;
+        if version != ABI_VERSION {
+            return Err(wasmtime::Error::msg(format!(
+                "unsupported ABI version {version}, expected {ABI_VERSION}"
+            )))
+        }
+
+        let memory = instance
+            .get_memory(&mut store, "memory")
+            .ok_or_else(|| wasmtime::Error::msg("module doesn't export its memory"))?;
+        let alloc = instance.get_typed_func(&mut store, "reth_exex_alloc")?;
+        let notification = instance.get_typed_func(&mut store, "reth_exex_notification")?;
+
+        Ok(Self { store, memory, alloc, notification, fuel })
+    }
+
+    /// Passes the notification to the guest, and returns the height up to which it's done with the
+    /// blocks, if it made progress.
+    ///
+    /// This blocks until the guest handled the notification.
+    pub fn on_notification(
+        &mut self,
+        notification: &ExExNotification,
+    ) -> eyre::Result<Option<BlockNumber>> {
+        let payload = serde_json::to_vec(&WasmNotification::from(notification))?;
+        self.call_notification(&payload)
+            .map_err(|err| eyre::eyre!("{} failed to handle notification: {err:?}", self.name()))
+    }
+
+    /// Returns the name of the `ExEx`.
+    pub fn name(&self) -> &str {
+        &self.store.data().name
+    }
+
+    fn call_notification(&mut self, payload: &[u8]) -> wasmtime::Result<Option<BlockNumber>> {
+        self.store.set_fuel(self.fuel)?;
+
+        let len = u32::try_from(payload.len())?;
+        let ptr = self.alloc.call(&mut self.store, len)?;
+        self.memory.write(&mut self.store, ptr as usize, payload)?;
+
+        match self.notification.call(&mut self.store, (ptr, len))? {
+            -1 => Ok(None),
+            height if height >= 0 => Ok(Some(height as BlockNumber)),
+            code => Err(wasmtime::Error::msg(format!("guest failed with code {code}"))),
+        }
+    }
+}
+
+impl<P> std::fmt::Debug for WasmExEx<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WasmExEx")
+            .field("name", &self.store.data().name)
+            .field("fuel", &self.fuel)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Adds the host functions to the `reth` module of the linker.
+///
+/// Provider errors trap the guest, which fails the handling of the current notification.
+fn add_host_functions<P>(linker: &mut Linker<HostState<P>>) -> wasmtime::Result<()>
+where
+    P: BlockHashReader + BlockNumReader + StateProviderFactory + Send + 'static,
+{
+    linker.func_wrap(
+        "reth",
+        "log",
+        |mut caller: Caller<'_, HostState<P>>, ptr: u32, len: u32| -> wasmtime::Result<()> {
+            let message = read_bytes(&mut caller, ptr, len)?;
+            info!(target: "exex::wasm", exex = %caller.data().name, "{}", String::from_utf8_lossy(&message));
+            Ok(())
+        },
+    )?;
+
+    linker.func_wrap(
+        "reth",
+        "latest_block_number",
+        |caller: Caller<'_, HostState<P>>| -> wasmtime::Result<u64> {
+            Ok(caller.data().provider.best_block_number()?)
+        },
+    )?;
+
+    linker.func_wrap(
+        "reth",
+        "block_hash",
+        |mut caller: Caller<'_, HostState<P>>, number: u64, out: u32| -> wasmtime::Result<u32> {
+            let Some(hash) = caller.data().provider.block_hash(number)? else { return Ok(0) };
+            write_bytes(&mut caller, out, hash.as_slice())?;
+            Ok(1)
+        },
+    )?;
+
+    linker.func_wrap(
+        "reth",
+        "balance",
+        |mut caller: Caller<'_, HostState<P>>, address: u32, out: u32| -> wasmtime::Result<u32> {
+            let address = Address::from_slice(&read_bytes(&mut caller, address, 20)?);
+            let state = caller.data().provider.latest()?;
+            let Some(balance) = state.account_balance(address)? else { return Ok(0) };
+            write_bytes(&mut caller, out, &balance.to_be_bytes::<32>())?;
+            Ok(1)
+        },
+    )?;
+
+    Ok(())
+}
+
+/// Returns the memory exported by the guest.
+fn guest_memory<T>(caller: &mut Caller<'_, T>) -> wasmtime::Result<Memory> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or_else(|| wasmtime::Error::msg("module doesn't export its memory"))
+}
+
+/// Reads `len` bytes at `ptr` from the memory of the guest.
+fn read_bytes<T>(caller: &mut Caller<'_, T>, ptr: u32, len: u32) -> wasmtime::Result<Vec<u8>> {
+    let memory = guest_memory(caller)?;
+    if ptr as usize + len as usize > memory.data_size(&*caller) {
+        return Err(wasmtime::Error::msg("out of bounds memory access"))
+    }
+    let mut bytes = vec![0; len as usize];
+    memory.read(&*caller, ptr as usize, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Writes the bytes to `ptr` in the memory of the guest.
+fn write_bytes<T>(caller: &mut Caller<'_, T>, ptr: u32, bytes: &[u8]) -> wasmtime::Result<()> {
+    let memory = guest_memory(caller)?;
+    memory.write(&mut *caller, ptr as usize, bytes)?;
+    Ok(())
+}
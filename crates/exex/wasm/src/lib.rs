@@ -0,0 +1,191 @@
+//! Host for execution extensions compiled to WASM.
+//!
+//! The logic of an `ExEx` can be compiled to a WASI module and loaded by the node at runtime, with
+//! `--exex.wasm.module <PATH>`, so that it can be deployed without recompiling the node. One `ExEx`
+//! is installed per module. See [`WasmExEx`] for the ABI between the node and the modules.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use reth_exex::{ExExContext, ExExEvent};
+use reth_node_api::FullNodeComponents;
+use std::{future::Future, path::PathBuf};
+
+mod abi;
+pub use abi::{WasmBlock, WasmChain, WasmLog, WasmNotification, ABI_VERSION};
+
+mod host;
+pub use host::WasmExEx;
+
+/// The default fuel a module can consume to handle a single notification.
+pub const DEFAULT_FUEL: u64 = 10_000_000_000;
+
+/// Parameters for loading `ExEx`'s from WASM modules.
+#[derive(Debug, Clone, PartialEq, Eq, clap::Args)]
+#[command(next_help_heading = "WASM ExEx")]
+pub struct WasmExExArgs {
+    /// The path of a WASM module to run as an `ExEx`, can be passed multiple times.
+    #[arg(long = "exex.wasm.module", value_name = "PATH")]
+    pub modules: Vec<PathBuf>,
+
+    /// The maximum fuel a module can consume to handle a single notification.
+    #[arg(long = "exex.wasm.fuel", default_value_t = DEFAULT_FUEL)]
+    pub fuel: u64,
+}
+
+impl Default for WasmExExArgs {
+    fn default() -> Self {
+        Self { modules: Vec::new(), fuel: DEFAULT_FUEL }
+    }
+}
+
+impl WasmExExArgs {
+    /// Returns the configured modules, in the order they were passed.
+    pub fn modules(&self) -> impl Iterator<Item = WasmModule> + '_ {
+        self.modules.iter().map(|path| WasmModule::new(path.clone(), self.fuel))
+    }
+}
+
+/// A WASM module to install as an `ExEx`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmModule {
+    /// The ID of the `ExEx`.
+    name: String,
+    /// The path of the module.
+    path: PathBuf,
+    /// The fuel the module can consume to handle a single notification.
+    fuel: u64,
+}
+
+impl WasmModule {
+    /// Creates a new module at the given path, named after its file.
+    pub fn new(path: PathBuf, fuel: u64) -> Self {
+        let stem = path.file_stem().unwrap_or(path.as_os_str()).to_string_lossy();
+        Self { name: format!("wasm-{stem}"), path, fuel }
+    }
+
+    /// Returns the ID of the `ExEx`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Loads the module on a blocking task, and returns the `ExEx` that forwards all notifications
+    /// to it.
+    pub async fn init<Node: FullNodeComponents>(
+        self,
+        ctx: ExExContext<Node>,
+    ) -> eyre::Result<impl Future<Output = eyre::Result<()>>> {
+        let provider = ctx.provider().clone();
+        let Self { name, path, fuel } = self;
+        let exex = tokio::task::spawn_blocking(move || WasmExEx::load(name, &path, provider, fuel))
+            .await??;
+        Ok(wasm_exex(ctx, exex))
+    }
+}
+
+/// Forwards all notifications to the WASM module.
+///
+/// The module is called on a blocking task, since it runs synchronously and can take a while.
+async fn wasm_exex<Node: FullNodeComponents>(
+    mut ctx: ExExContext<Node>,
+    mut exex: WasmExEx<Node::Provider>,
+) -> eyre::Result<()> {
+    while let Some(notification) = ctx.notifications.recv().await {
+        let (returned, finished_height) = tokio::task::spawn_blocking(move || {
+            let finished_height = exex.on_notification(&notification);
+            (exex, finished_height)
+        })
+        .await?;
+        exex = returned;
+
+        if let Some(height) = finished_height? {
+            ctx.events.send(ExExEvent::FinishedHeight(height))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use reth_execution_types::{Chain, ExecutionOutcome};
+    use reth_exex::ExExNotification;
+    use reth_exex_test_utils::test_exex_context;
+    use std::sync::Arc;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: clap::Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    /// A module that is done with all blocks up to the latest block.
+    const LATEST_BLOCK_MODULE: &str = r#"
+        (module
+            (import "reth" "latest_block_number" (func $latest_block_number (result i64)))
+            (memory (export "memory") 1)
+            (func (export "reth_exex_abi_version") (result i32) i32.const 1)
+            (func (export "reth_exex_alloc") (param i32) (result i32) i32.const 0)
+            (func (export "reth_exex_notification") (param i32 i32) (result i64)
+                call $latest_block_number))
+    "#;
+
+    #[test]
+    fn parse_wasm_exex_args() {
+        let args = CommandParser::<WasmExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, WasmExExArgs::default());
+        assert_eq!(args.modules().count(), 0);
+
+        let args = CommandParser::<WasmExExArgs>::parse_from([
+            "reth",
+            "--exex.wasm.module",
+            "indexer.wasm",
+            "--exex.wasm.module",
+            "modules/bridge.wasm",
+            "--exex.wasm.fuel",
+            "1000",
+        ])
+        .args;
+        let modules = args.modules().collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec![
+                WasmModule::new("indexer.wasm".into(), 1000),
+                WasmModule::new("modules/bridge.wasm".into(), 1000)
+            ]
+        );
+        assert_eq!(modules[1].name(), "wasm-bridge");
+    }
+
+    #[tokio::test]
+    async fn test_wasm_exex() -> eyre::Result<()> {
+        let (ctx, handle) = test_exex_context().await?;
+
+        let mut exex = WasmExEx::new(
+            "test".to_string(),
+            LATEST_BLOCK_MODULE.as_bytes(),
+            ctx.provider().clone(),
+            DEFAULT_FUEL,
+        )
+        .map_err(|err| eyre::eyre!("{err:?}"))?;
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::from_block(
+                handle.genesis.clone(),
+                ExecutionOutcome::default(),
+                None,
+            )),
+        };
+        assert_eq!(exex.on_notification(&notification)?, Some(ctx.head.number));
+
+        Ok(())
+    }
+}
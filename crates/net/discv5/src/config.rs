@@ -14,7 +14,11 @@ use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
 use reth_network_peers::NodeRecord;
 use tracing::warn;
 
-use crate::{enr::discv4_id_to_multiaddr_id, filter::MustNotIncludeKeys, NetworkStackId};
+use crate::{
+    enr::discv4_id_to_multiaddr_id,
+    filter::{MustIncludeKeys, MustNotIncludeKeys},
+    NetworkStackId,
+};
 
 /// The default address for discv5 via UDP is IPv4.
 ///
@@ -59,7 +63,8 @@ pub struct ConfigBuilder {
     ///
     /// Defaults to L1 mainnet if not set.
     fork: Option<(&'static [u8], ForkId)>,
-    /// `RLPx` TCP socket to advertise.
+    /// `RLPx` TCP socket to advertise. No TCP port is advertised if the port is zero, e.g. for a
+    /// discovery-only node.
     ///
     /// NOTE: IP address of `RLPx` socket overwrites IP address of same IP version in
     /// [`discv5::ListenConfig`].
@@ -77,6 +82,8 @@ pub struct ConfigBuilder {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     discovered_peer_filter: Option<MustNotIncludeKeys>,
+    /// Keys a discovered peer must advertise in its node record in order to be passed up to rlpx.
+    discovered_peer_required_keys: MustIncludeKeys,
 }
 
 impl ConfigBuilder {
@@ -92,6 +99,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            discovered_peer_required_keys,
         } = discv5_config;
 
         Self {
@@ -104,6 +112,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval: Some(bootstrap_lookup_interval),
             bootstrap_lookup_countdown: Some(bootstrap_lookup_countdown),
             discovered_peer_filter: Some(discovered_peer_filter),
+            discovered_peer_required_keys,
         }
     }
 
@@ -163,7 +172,7 @@ impl ConfigBuilder {
 
     /// Sets the tcp socket to advertise in the local [`Enr`](discv5::enr::Enr). The IP address of
     /// this socket will overwrite the discovery address of the same IP version, if one is
-    /// configured.
+    /// configured. The TCP port isn't advertised if it's zero.
     pub const fn tcp_socket(mut self, socket: SocketAddr) -> Self {
         self.tcp_socket = socket;
         self
@@ -206,6 +215,20 @@ impl ConfigBuilder {
         self
     }
 
+    /// Adds keys that a discovered peer must advertise in its node record, in order to be passed to
+    /// rlpx. This allows finding peers of a specific network, e.g. a rollup, rather than filtering
+    /// all discovered peers by fork id alone.
+    pub fn must_include_keys(mut self, keys: &[&'static [u8]]) -> Self {
+        self.discovered_peer_required_keys.add_required_keys(keys);
+        self
+    }
+
+    /// Advertises the topic `(key, rlp-encoded-value)` in the local node record, and only passes
+    /// discovered peers to rlpx that advertise the same key.
+    pub fn topic(self, key: &'static [u8], value: Bytes) -> Self {
+        self.add_enr_kv_pair(key, value).must_include_keys(&[key])
+    }
+
     /// Returns a new [`Config`].
     pub fn build(self) -> Config {
         let Self {
@@ -218,6 +241,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            discovered_peer_required_keys,
         } = self;
 
         let mut discv5_config = discv5_config
@@ -247,6 +271,7 @@ impl ConfigBuilder {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            discovered_peer_required_keys,
         }
     }
 }
@@ -262,7 +287,8 @@ pub struct Config {
     /// Fork kv-pair to set in local node record. Identifies which network/chain/fork the node
     /// belongs, e.g. `(b"opstack", ChainId)` or `(b"eth", [ForkId])`.
     pub(super) fork: Option<(&'static [u8], EnrForkIdEntry)>,
-    /// `RLPx` TCP socket to advertise.
+    /// `RLPx` TCP socket to advertise. No TCP port is advertised if the port is zero, e.g. for a
+    /// discovery-only node.
     ///
     /// NOTE: IP address of `RLPx` socket overwrites IP address of same IP version in
     /// [`discv5::ListenConfig`].
@@ -280,6 +306,8 @@ pub struct Config {
     /// Custom filter rules to apply to a discovered peer in order to determine if it should be
     /// passed up to rlpx or dropped.
     pub(super) discovered_peer_filter: MustNotIncludeKeys,
+    /// Keys a discovered peer must advertise in its node record in order to be passed up to rlpx.
+    pub(super) discovered_peer_required_keys: MustIncludeKeys,
 }
 
 impl Config {
@@ -296,6 +324,7 @@ impl Config {
             bootstrap_lookup_interval: None,
             bootstrap_lookup_countdown: None,
             discovered_peer_filter: None,
+            discovered_peer_required_keys: MustIncludeKeys::default(),
         }
     }
 
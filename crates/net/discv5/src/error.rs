@@ -35,6 +35,9 @@ pub enum Error {
     /// An error from underlying [`discv5::Discv5`] node.
     #[error("sigp/discv5 error, {0}")]
     Discv5Error(discv5::Error),
+    /// A lookup query of the underlying [`discv5::Discv5`] node failed.
+    #[error("lookup query failed, {0:?}")]
+    QueryFailed(discv5::QueryError),
     /// The [`ListenConfig`](discv5::ListenConfig) has been misconfigured.
     #[error("misconfigured listen config, RLPx TCP address must also be supported by discv5")]
     ListenConfigMisconfigured,
@@ -87,6 +87,42 @@ impl MustNotIncludeKeys {
     }
 }
 
+/// Filter requiring that peers advertise kv-pairs using all of certain keys, e.g. a topic
+/// identifying a rollup or custom chain.
+#[derive(Debug, Clone, Default)]
+pub struct MustIncludeKeys {
+    keys: HashSet<MustIncludeKey>,
+}
+
+impl MustIncludeKeys {
+    /// Returns a new instance that only allows node records with a kv-pair for each of the given
+    /// keys.
+    pub fn new(required_keys: &[&'static [u8]]) -> Self {
+        let mut filter = Self::default();
+        filter.add_required_keys(required_keys);
+        filter
+    }
+
+    /// Returns `true` if [`Enr`](discv5::Enr) passes filtering rules.
+    pub fn filter(&self, enr: &discv5::Enr) -> FilterOutcome {
+        for key in &self.keys {
+            let outcome = key.filter(enr);
+            if !outcome.is_ok() {
+                return outcome
+            }
+        }
+
+        FilterOutcome::Ok
+    }
+
+    /// Adds keys that must be present for a kv-pair in a node record.
+    pub fn add_required_keys(&mut self, keys: &[&'static [u8]]) {
+        for key in keys {
+            self.keys.insert(MustIncludeKey::new(key));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use alloy_rlp::Bytes;
@@ -121,4 +157,25 @@ mod tests {
         assert!(matches!(filter.filter(&enr_1), FilterOutcome::Ignore { .. }));
         assert!(matches!(filter.filter(&enr_2), FilterOutcome::Ignore { .. }));
     }
+
+    #[test]
+    fn must_include_keys_filter() {
+        let filter = MustIncludeKeys::new(&[b"myrollup"]);
+
+        let sk = CombinedKey::generate_secp256k1();
+        let enr_1 = Enr::builder()
+            .add_value_rlp(b"myrollup" as &[u8], Bytes::from("mainnet"))
+            .build(&sk)
+            .unwrap();
+
+        let sk = CombinedKey::generate_secp256k1();
+        let enr_2 = Enr::builder()
+            .add_value_rlp(NetworkStackId::ETH, Bytes::from("cancun"))
+            .build(&sk)
+            .unwrap();
+
+        assert!(filter.filter(&enr_1).is_ok());
+        assert!(matches!(filter.filter(&enr_2), FilterOutcome::Ignore { .. }));
+        assert!(MustIncludeKeys::default().filter(&enr_2).is_ok());
+    }
 }
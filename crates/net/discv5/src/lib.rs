@@ -45,7 +45,7 @@ pub use config::{
 };
 pub use enr::enr_to_discv4_id;
 pub use error::Error;
-pub use filter::{FilterOutcome, MustNotIncludeKeys};
+pub use filter::{FilterOutcome, MustIncludeKeys, MustNotIncludeKeys};
 pub use network_stack_id::NetworkStackId;
 
 use metrics::{DiscoveredPeersMetrics, Discv5Metrics};
@@ -73,6 +73,8 @@ pub struct Discv5 {
     fork_key: Option<&'static [u8]>,
     /// Filter applied to a discovered peers before passing it up to app.
     discovered_peer_filter: MustNotIncludeKeys,
+    /// Keys a discovered peer must advertise before passing it up to app.
+    discovered_peer_required_keys: MustIncludeKeys,
     /// Metrics for underlying [`discv5::Discv5`] node and filtered discovered peers.
     metrics: Discv5Metrics,
 }
@@ -180,6 +182,7 @@ impl Discv5 {
             bootstrap_lookup_interval,
             bootstrap_lookup_countdown,
             discovered_peer_filter,
+            discovered_peer_required_keys,
             ..
         } = discv5_config;
 
@@ -215,7 +218,14 @@ impl Discv5 {
         );
 
         Ok((
-            Self { discv5, rlpx_ip_mode, fork_key, discovered_peer_filter, metrics },
+            Self {
+                discv5,
+                rlpx_ip_mode,
+                fork_key,
+                discovered_peer_filter,
+                discovered_peer_required_keys,
+                metrics,
+            },
             discv5_updates,
             bc_enr,
         ))
@@ -357,7 +367,27 @@ impl Discv5 {
     /// Applies filtering rules on an ENR. Returns [`Ok`](FilterOutcome::Ok) if peer should be
     /// passed up to app, and [`Ignore`](FilterOutcome::Ignore) if peer should instead be dropped.
     pub fn filter_discovered_peer(&self, enr: &discv5::Enr) -> FilterOutcome {
-        self.discovered_peer_filter.filter(enr)
+        let outcome = self.discovered_peer_filter.filter(enr);
+        if !outcome.is_ok() {
+            return outcome
+        }
+        self.discovered_peer_required_keys.filter(enr)
+    }
+
+    /// Runs a lookup query for peers that advertise a kv-pair with the given key in their node
+    /// record, e.g. a topic set with [`ConfigBuilder::topic`]. Returns at most `count` node
+    /// records.
+    ///
+    /// Peers found by the query are also reported as discovered peers, like for any other query.
+    pub async fn find_peers_with_key(
+        &self,
+        key: &'static [u8],
+        count: usize,
+    ) -> Result<Vec<discv5::Enr>, Error> {
+        let target = discv5::enr::NodeId::random();
+        let predicate = Box::new(move |enr: &discv5::Enr| enr.get_raw_rlp(key).is_some());
+
+        self.discv5.find_node_predicate(target, predicate, count).await.map_err(Error::QueryFailed)
     }
 
     /// Returns the [`ForkId`] of the given [`Enr`](discv5::Enr) w.r.t. the local node's network
@@ -426,13 +456,18 @@ pub fn build_local_enr(
 
     let Config { discv5_config, fork, tcp_socket, other_enr_kv_pairs, .. } = config;
 
+    // a node without `RLPx` listener, e.g. a bootnode, has no TCP port to advertise
+    let tcp_port = (tcp_socket.port() != 0).then_some(tcp_socket.port());
+
     let socket = match discv5_config.listen_config {
         ListenConfig::Ipv4 { ip, port } => {
             if ip != Ipv4Addr::UNSPECIFIED {
                 builder.ip4(ip);
             }
             builder.udp4(port);
-            builder.tcp4(tcp_socket.port());
+            if let Some(tcp_port) = tcp_port {
+                builder.tcp4(tcp_port);
+            }
 
             (ip, port).into()
         }
@@ -441,7 +476,9 @@ pub fn build_local_enr(
                 builder.ip6(ip);
             }
             builder.udp6(port);
-            builder.tcp6(tcp_socket.port());
+            if let Some(tcp_port) = tcp_port {
+                builder.tcp6(tcp_port);
+            }
 
             (ip, port).into()
         }
@@ -450,7 +487,9 @@ pub fn build_local_enr(
                 builder.ip4(ipv4);
             }
             builder.udp4(ipv4_port);
-            builder.tcp4(tcp_socket.port());
+            if let Some(tcp_port) = tcp_port {
+                builder.tcp4(tcp_port);
+            }
 
             if ipv6 != Ipv6Addr::UNSPECIFIED {
                 builder.ip6(ipv6);
@@ -671,6 +710,7 @@ mod test {
             rlpx_ip_mode: IpMode::Ip4,
             fork_key: None,
             discovered_peer_filter: MustNotIncludeKeys::default(),
+            discovered_peer_required_keys: MustIncludeKeys::default(),
             metrics: Discv5Metrics::default(),
         }
     }
@@ -892,4 +932,15 @@ mod test {
         assert_eq!(fork_id, decoded_fork_id);
         assert_eq!(TCP_PORT, enr.tcp4().unwrap()); // listen config is defaulting to ip mode ipv4
     }
+
+    #[test]
+    fn build_enr_without_rlpx() {
+        let config = Config::builder((Ipv4Addr::UNSPECIFIED, 0).into()).build();
+
+        let sk = SecretKey::new(&mut thread_rng());
+        let (enr, _, _, _) = build_local_enr(&sk, &config);
+
+        assert_eq!(enr.tcp4(), None);
+        assert!(enr.udp4().is_some());
+    }
 }
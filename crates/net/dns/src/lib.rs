@@ -20,6 +20,7 @@ use crate::{
     tree::{DnsEntry, LinkEntry},
 };
 pub use config::DnsDiscoveryConfig;
+pub use publish::DnsTree;
 use enr::Enr;
 use error::ParseDnsEntryError;
 use reth_ethereum_forks::{EnrForkIdEntry, ForkId};
@@ -51,6 +52,7 @@ use tracing::{debug, trace};
 
 mod config;
 mod error;
+pub mod publish;
 mod query;
 pub mod resolver;
 mod sync;
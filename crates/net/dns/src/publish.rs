@@ -0,0 +1,209 @@
+//! Construction of [EIP-1459](https://eips.ethereum.org/EIPS/eip-1459) trees for publishing.
+//!
+//! A tree is built from a set of node records and links to other trees. Both sets are split into
+//! subtrees of branch entries, the root entry references the roots of both subtrees and is signed
+//! with the key of the tree's operator. Every entry is published as a TXT record at
+//! `<hash>.<domain>`, where `hash` is the base32 encoding of the first 16 bytes of the keccak256
+//! hash of the entry's text.
+
+use crate::tree::{BranchEntry, DnsEntry, LinkEntry, NodeEntry, TreeRootEntry};
+use alloy_primitives::keccak256;
+use data_encoding::BASE32_NOPAD;
+use enr::{Enr, EnrKey, EnrKeyUnambiguous, Error as EnrError};
+use secp256k1::SecretKey;
+use std::collections::BTreeMap;
+
+/// Length of an abbreviated entry hash, in bytes.
+const HASH_ABBREV_LEN: usize = 16;
+
+/// Maximum number of children of a branch entry, chosen so that the entry fits into a single TXT
+/// record of 370 bytes, each child being an encoded hash followed by a comma.
+const MAX_CHILDREN: usize = 370 / (1 + HASH_ABBREV_LEN * 13 / 8);
+
+/// A signed tree of node records and links, ready to be published.
+#[derive(Debug, Clone)]
+pub struct DnsTree<K: EnrKeyUnambiguous = SecretKey> {
+    /// The signed root entry.
+    root: TreeRootEntry,
+    /// All entries of the tree, keyed by their hash.
+    entries: BTreeMap<String, DnsEntry<K>>,
+}
+
+// === impl DnsTree ===
+
+impl<K: EnrKeyUnambiguous> DnsTree<K> {
+    /// Builds the tree from the given node records and links and signs its root with the given
+    /// key.
+    pub fn new<S: EnrKey>(
+        mut nodes: Vec<Enr<K>>,
+        mut links: Vec<LinkEntry<K>>,
+        sequence_number: u64,
+        key: &S,
+    ) -> Result<Self, EnrError> {
+        // sort the input, so that the same set of records always results in the same tree
+        nodes.sort_by_key(|enr| enr.node_id().raw());
+        links.sort_by_key(|link| link.to_string());
+
+        let mut entries = BTreeMap::new();
+        let enr_root = build_subtree(
+            nodes.into_iter().map(|enr| DnsEntry::Node(NodeEntry { enr })).collect(),
+            &mut entries,
+        );
+        let link_root =
+            build_subtree(links.into_iter().map(DnsEntry::Link).collect(), &mut entries);
+
+        let mut root = TreeRootEntry {
+            enr_root: insert_entry(enr_root, &mut entries),
+            link_root: insert_entry(link_root, &mut entries),
+            sequence_number,
+            signature: Default::default(),
+        };
+        root.sign(key)?;
+
+        Ok(Self { root, entries })
+    }
+
+    /// Returns the signed root entry of the tree.
+    pub const fn root(&self) -> &TreeRootEntry {
+        &self.root
+    }
+
+    /// Returns all entries of the tree, excluding the root, keyed by their hash.
+    pub const fn entries(&self) -> &BTreeMap<String, DnsEntry<K>> {
+        &self.entries
+    }
+
+    /// Returns the TXT records to publish the tree at the given domain, keyed by the fully
+    /// qualified record name.
+    pub fn txt_records(&self, domain: &str) -> BTreeMap<String, String> {
+        let mut records = self
+            .entries
+            .iter()
+            .map(|(hash, entry)| (format!("{hash}.{domain}"), entry.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        records.insert(domain.to_string(), self.root.to_string());
+        records
+    }
+}
+
+/// Returns the abbreviated hash of the entry, which is the subdomain it's published at.
+fn entry_hash<K: EnrKeyUnambiguous>(entry: &DnsEntry<K>) -> String {
+    BASE32_NOPAD.encode(&keccak256(entry.to_string().as_bytes())[..HASH_ABBREV_LEN])
+}
+
+/// Inserts the entry into the tree and returns its hash.
+fn insert_entry<K: EnrKeyUnambiguous>(
+    entry: DnsEntry<K>,
+    entries: &mut BTreeMap<String, DnsEntry<K>>,
+) -> String {
+    let hash = entry_hash(&entry);
+    entries.insert(hash.clone(), entry);
+    hash
+}
+
+/// Builds a subtree of the given entries and returns its root entry, which is _not_ inserted into
+/// the tree.
+fn build_subtree<K: EnrKeyUnambiguous>(
+    mut leaves: Vec<DnsEntry<K>>,
+    entries: &mut BTreeMap<String, DnsEntry<K>>,
+) -> DnsEntry<K> {
+    if leaves.len() == 1 {
+        return leaves.remove(0)
+    }
+
+    if leaves.len() <= MAX_CHILDREN {
+        let children = leaves.into_iter().map(|leaf| insert_entry(leaf, entries)).collect();
+        return DnsEntry::Branch(BranchEntry { children })
+    }
+
+    let mut subtrees = Vec::with_capacity(leaves.len().div_ceil(MAX_CHILDREN));
+    while !leaves.is_empty() {
+        let rest = leaves.split_off(leaves.len().min(MAX_CHILDREN));
+        subtrees.push(build_subtree(std::mem::replace(&mut leaves, rest), entries));
+    }
+    build_subtree(subtrees, entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secp256k1::rand::thread_rng;
+    use std::net::Ipv4Addr;
+
+    fn random_enr() -> Enr<SecretKey> {
+        let secret_key = SecretKey::new(&mut thread_rng());
+        Enr::builder().ip4(Ipv4Addr::LOCALHOST).tcp4(30303).udp4(30303).build(&secret_key).unwrap()
+    }
+
+    /// Walks the subtree with the given root hash and collects its leaves.
+    fn collect_leaves(
+        records: &BTreeMap<String, String>,
+        hash: &str,
+        domain: &str,
+        leaves: &mut Vec<DnsEntry<SecretKey>>,
+    ) {
+        let entry: DnsEntry<SecretKey> = records[&format!("{hash}.{domain}")].parse().unwrap();
+        match entry {
+            DnsEntry::Branch(branch) => {
+                for child in &branch.children {
+                    collect_leaves(records, child, domain, leaves);
+                }
+            }
+            DnsEntry::Root(_) => unreachable!(),
+            leaf => leaves.push(leaf),
+        }
+    }
+
+    #[test]
+    fn build_and_walk_tree() {
+        let domain = "nodes.example.org";
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let nodes = (0..40).map(|_| random_enr()).collect::<Vec<_>>();
+        let link = LinkEntry::<SecretKey> {
+            domain: "other.example.org".to_string(),
+            pubkey: SecretKey::new(&mut thread_rng()).public(),
+        };
+
+        let tree = DnsTree::new(nodes.clone(), vec![link.clone()], 7, &secret_key).unwrap();
+        let records = tree.txt_records(domain);
+
+        let root: TreeRootEntry = records[domain].parse().unwrap();
+        assert_eq!(&root, tree.root());
+        assert_eq!(root.sequence_number, 7);
+        assert!(root.verify::<SecretKey>(&secret_key.public()));
+
+        let mut leaves = Vec::new();
+        collect_leaves(&records, &root.enr_root, domain, &mut leaves);
+        let mut found = leaves
+            .into_iter()
+            .map(|leaf| match leaf {
+                DnsEntry::Node(node) => node.enr,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+        found.sort_by_key(|enr| enr.node_id().raw());
+        let mut expected = nodes;
+        expected.sort_by_key(|enr| enr.node_id().raw());
+        assert_eq!(found, expected);
+
+        let mut leaves = Vec::new();
+        collect_leaves(&records, &root.link_root, domain, &mut leaves);
+        match leaves.as_slice() {
+            [DnsEntry::Link(found)] => assert_eq!(*found, link),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn build_empty_tree() {
+        let secret_key = SecretKey::new(&mut thread_rng());
+        let tree = DnsTree::<SecretKey>::new(vec![], vec![], 1, &secret_key).unwrap();
+        let records = tree.txt_records("nodes.example.org");
+
+        // root and the empty branch shared by both subtrees
+        assert_eq!(records.len(), 2);
+        let branch = &records[&format!("{}.nodes.example.org", tree.root().enr_root)];
+        assert_eq!(branch, "enrtree-branch:");
+        assert!(branch.parse::<BranchEntry>().unwrap().children.is_empty());
+    }
+}
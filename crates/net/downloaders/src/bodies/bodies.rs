@@ -1,6 +1,10 @@
 use super::queue::BodiesRequestQueue;
-use crate::{bodies::task::TaskDownloader, metrics::BodyDownloaderMetrics};
-use futures::Stream;
+use crate::{
+    bodies::task::TaskDownloader,
+    metrics::BodyDownloaderMetrics,
+    spill::{MaybeSpilled, ResponseSpill, SpillRead},
+};
+use futures::{FutureExt, Stream};
 use futures_util::StreamExt;
 use reth_config::BodiesConfig;
 use reth_consensus::Consensus;
@@ -20,11 +24,12 @@ use std::{
     collections::BinaryHeap,
     mem,
     ops::RangeInclusive,
+    path::PathBuf,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
 };
-use tracing::info;
+use tracing::{info, warn};
 
 /// Downloads bodies in batches.
 ///
@@ -48,6 +53,10 @@ pub struct BodiesDownloader<B: BodiesClient, Provider> {
     max_buffered_blocks_size_bytes: usize,
     /// Current estimated size of buffered blocks in bytes.
     buffered_blocks_size_bytes: usize,
+    /// Temporary file that buffered blocks are written to once the buffer is full.
+    spill: ResponseSpill,
+    /// The read of the next expected response from the temporary file, with its block range.
+    pending_read: Option<(SpillRead<BlockResponse>, RangeInclusive<BlockNumber>)>,
     /// The range of block numbers for body download.
     download_range: RangeInclusive<BlockNumber>,
     /// The latest block number returned.
@@ -168,6 +177,7 @@ where
         nothing_to_request &&
             self.in_progress_queue.is_empty() &&
             self.buffered_responses.is_empty() &&
+            self.pending_read.is_none() &&
             self.queued_bodies.is_empty()
     }
 
@@ -181,12 +191,16 @@ where
         self.queued_bodies = Vec::new();
         self.buffered_responses = BinaryHeap::new();
         self.buffered_blocks_size_bytes = 0;
+        self.spill.clear();
+        self.pending_read = None;
 
         // reset metrics
         self.metrics.in_flight_requests.set(0.);
         self.metrics.buffered_responses.set(0.);
         self.metrics.buffered_blocks.set(0.);
         self.metrics.buffered_blocks_size_bytes.set(0.);
+        self.metrics.spilled_responses.set(0.);
+        self.metrics.spilled_blocks_size_bytes.set(0.);
         self.metrics.queued_blocks.set(0.);
     }
 
@@ -204,41 +218,106 @@ where
         self.buffered_blocks_size_bytes -= resp.size();
         self.metrics.buffered_blocks.decrement(resp.len() as f64);
         self.metrics.buffered_blocks_size_bytes.set(self.buffered_blocks_size_bytes as f64);
+        if let MaybeSpilled::Spilled(spilled) = &resp.resp {
+            self.spill.release(spilled);
+            self.metrics.spilled_responses.decrement(1.);
+            self.metrics.spilled_blocks_size_bytes.set(self.spill.size_bytes() as f64);
+        }
         Some(resp)
     }
 
     /// Adds a new response to the internal buffer
+    ///
+    /// If the buffer is full, the response is written to a temporary file instead, unless it's the
+    /// next expected response.
     fn buffer_bodies_response(&mut self, response: Vec<BlockResponse>) {
+        let block_range = response.first().expect("is not empty").block_number()..=
+            response.last().expect("is not empty").block_number();
+        let len = response.len();
+
+        let should_spill = !self.has_buffer_capacity() &&
+            self.spill.has_capacity() &&
+            *block_range.start() > self.next_expected_block_number();
+        if should_spill {
+            let spilled = self.spill.spill(&response);
+            self.buffered_responses.push(OrderedBodiesResponse {
+                resp: MaybeSpilled::Spilled(spilled),
+                block_range,
+                len,
+                size: 0,
+            });
+
+            self.metrics.buffered_blocks.increment(len as f64);
+            self.metrics.buffered_responses.set(self.buffered_responses.len() as f64);
+            self.metrics.spilled_responses.increment(1.);
+            self.metrics.spilled_blocks_size_bytes.set(self.spill.size_bytes() as f64);
+            return
+        }
+
         // take into account capacity
         let size = response.iter().map(BlockResponse::size).sum::<usize>() +
             response.capacity() * mem::size_of::<BlockResponse>();
 
-        let response = OrderedBodiesResponse { resp: response, size };
-        let response_len = response.len();
+        let response = OrderedBodiesResponse {
+            resp: MaybeSpilled::InMemory(response),
+            block_range,
+            len,
+            size,
+        };
 
         self.buffered_blocks_size_bytes += size;
         self.buffered_responses.push(response);
 
-        self.metrics.buffered_blocks.increment(response_len as f64);
+        self.metrics.buffered_blocks.increment(len as f64);
         self.metrics.buffered_blocks_size_bytes.set(self.buffered_blocks_size_bytes as f64);
         self.metrics.buffered_responses.set(self.buffered_responses.len() as f64);
     }
 
     /// Returns a response if it's first block number matches the next expected.
-    fn try_next_buffered(&mut self) -> Option<Vec<BlockResponse>> {
+    ///
+    /// If the response was written to disk, it's read back in the background first. If it can't
+    /// be read back, its blocks are requested again and no more responses are written to disk.
+    fn try_next_buffered(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> DownloadResult<Option<Vec<BlockResponse>>> {
+        if let Some((read, block_range)) = &mut self.pending_read {
+            let Poll::Ready(result) = read.poll_unpin(cx) else { return Ok(None) };
+            let block_range = block_range.clone();
+            self.pending_read = None;
+
+            return match result {
+                Ok(blocks) => Ok(Some(self.trim_buffered_response(blocks))),
+                Err(error) => {
+                    warn!(target: "downloaders::bodies", %error, range = ?block_range, "Failed to read blocks from disk, requesting them again");
+                    self.spill.set_failed();
+                    let headers = self.provider.sealed_headers_range(block_range)?;
+                    self.metrics.in_flight_requests.increment(1.);
+                    self.in_progress_queue.push_new_request(
+                        Arc::clone(&self.client),
+                        Arc::clone(&self.consensus),
+                        headers,
+                    );
+                    // make sure the new request is polled
+                    cx.waker().wake_by_ref();
+                    Ok(None)
+                }
+            }
+        }
+
         if let Some(next) = self.buffered_responses.peek() {
             let expected = self.next_expected_block_number();
             let next_block_range = next.block_range();
 
             if next_block_range.contains(&expected) {
-                return self.pop_buffered_response().map(|buffered| {
-                    buffered
-                        .resp
-                        .into_iter()
-                        .skip_while(|b| b.block_number() < expected)
-                        .take_while(|b| self.download_range.contains(&b.block_number()))
-                        .collect()
-                })
+                let buffered = self.pop_buffered_response().expect("is not empty");
+                return match buffered.resp {
+                    MaybeSpilled::InMemory(blocks) => Ok(Some(self.trim_buffered_response(blocks))),
+                    MaybeSpilled::Spilled(spilled) => {
+                        self.pending_read = Some((self.spill.read(&spilled), next_block_range));
+                        self.try_next_buffered(cx)
+                    }
+                }
             }
 
             // Drop buffered response since we passed that range
@@ -246,7 +325,17 @@ where
                 self.pop_buffered_response();
             }
         }
-        None
+        Ok(None)
+    }
+
+    /// Returns the blocks of the next expected response that are within the download range.
+    fn trim_buffered_response(&self, blocks: Vec<BlockResponse>) -> Vec<BlockResponse> {
+        let expected = self.next_expected_block_number();
+        blocks
+            .into_iter()
+            .skip_while(|b| b.block_number() < expected)
+            .take_while(|b| self.download_range.contains(&b.block_number()))
+            .collect()
     }
 
     /// Returns the next batch of block bodies that can be returned if we have enough buffered
@@ -271,7 +360,7 @@ where
         // can grow large if a certain request is slow, so we limit the followup requests if the
         // queued bodies grew too large
         self.queued_bodies.len() < 4 * self.stream_batch_size &&
-            self.has_buffer_capacity() &&
+            (self.has_buffer_capacity() || self.spill.has_capacity()) &&
             self.in_progress_queue.len() < self.concurrent_request_limit()
     }
 }
@@ -401,8 +490,16 @@ where
                 };
             }
 
-            while let Some(buf_response) = this.try_next_buffered() {
-                this.queue_bodies(buf_response);
+            loop {
+                match this.try_next_buffered(cx) {
+                    Ok(Some(buf_response)) => this.queue_bodies(buf_response),
+                    Ok(None) => break,
+                    Err(error) => {
+                        tracing::error!(target: "downloaders::bodies", %error, "Failed to request blocks again");
+                        this.clear();
+                        return Poll::Ready(Some(Err(error)))
+                    }
+                }
             }
 
             // shrink the buffer so that it doesn't grow indefinitely
@@ -414,7 +511,7 @@ where
         }
 
         // All requests are handled, stream is finished
-        if this.in_progress_queue.is_empty() {
+        if this.in_progress_queue.is_empty() && this.pending_read.is_none() {
             if this.queued_bodies.is_empty() {
                 return Poll::Ready(None)
             }
@@ -432,34 +529,32 @@ where
 
 #[derive(Debug)]
 struct OrderedBodiesResponse {
-    resp: Vec<BlockResponse>,
-    /// The total size of the response in bytes
+    resp: MaybeSpilled<Vec<BlockResponse>>,
+    /// The range of the block numbers in the response
+    block_range: RangeInclusive<u64>,
+    /// The number of blocks in the response
+    len: usize,
+    /// The total size of the response in memory in bytes, zero if the response was spilled
     size: usize,
 }
 
 impl OrderedBodiesResponse {
     /// Returns the block number of the first element
-    ///
-    /// # Panics
-    /// If the response vec is empty.
-    fn first_block_number(&self) -> u64 {
-        self.resp.first().expect("is not empty").block_number()
+    const fn first_block_number(&self) -> u64 {
+        *self.block_range.start()
     }
 
     /// Returns the range of the block numbers in the response
-    ///
-    /// # Panics
-    /// If the response vec is empty.
     fn block_range(&self) -> RangeInclusive<u64> {
-        self.first_block_number()..=self.resp.last().expect("is not empty").block_number()
+        self.block_range.clone()
     }
 
     #[inline]
-    fn len(&self) -> usize {
-        self.resp.len()
+    const fn len(&self) -> usize {
+        self.len
     }
 
-    /// Returns the size of the response in bytes
+    /// Returns the size of the response in memory in bytes
     ///
     /// See [`BlockResponse::size`]
     #[inline]
@@ -497,6 +592,11 @@ pub struct BodiesDownloaderBuilder {
     pub stream_batch_size: usize,
     /// Maximum number of bytes of received bodies to buffer internally.
     pub max_buffered_blocks_size_bytes: usize,
+    /// Maximum size of the temporary file received bodies are written to once the internal
+    /// buffer is full.
+    pub max_spilled_blocks_size_bytes: usize,
+    /// The directory of the temporary file, the temporary directory of the OS if unset.
+    pub spill_dir: Option<PathBuf>,
     /// The maximum number of requests to send concurrently.
     pub concurrent_requests_range: RangeInclusive<usize>,
 }
@@ -509,6 +609,7 @@ impl BodiesDownloaderBuilder {
             .with_stream_batch_size(config.downloader_stream_batch_size)
             .with_request_limit(config.downloader_request_limit)
             .with_max_buffered_blocks_size_bytes(config.downloader_max_buffered_blocks_size_bytes)
+            .with_max_spilled_blocks_size_bytes(config.downloader_max_spilled_size_bytes)
             .with_concurrent_requests_range(
                 config.downloader_min_concurrent_requests..=
                     config.downloader_max_concurrent_requests,
@@ -522,6 +623,8 @@ impl Default for BodiesDownloaderBuilder {
             request_limit: 200,
             stream_batch_size: 1_000,
             max_buffered_blocks_size_bytes: 2 * 1024 * 1024 * 1024, // ~2GB
+            max_spilled_blocks_size_bytes: 0,
+            spill_dir: None,
             concurrent_requests_range: 5..=100,
         }
     }
@@ -558,6 +661,23 @@ impl BodiesDownloaderBuilder {
         self
     }
 
+    /// Set max block bytes the downloader writes to temporary files once its buffer is full.
+    ///
+    /// Blocks are only written to disk if this is not zero.
+    pub const fn with_max_spilled_blocks_size_bytes(
+        mut self,
+        max_spilled_blocks_size_bytes: usize,
+    ) -> Self {
+        self.max_spilled_blocks_size_bytes = max_spilled_blocks_size_bytes;
+        self
+    }
+
+    /// Set the directory of the temporary file the downloader writes blocks to.
+    pub fn with_spill_dir(mut self, spill_dir: Option<PathBuf>) -> Self {
+        self.spill_dir = spill_dir;
+        self
+    }
+
     /// Consume self and return the concurrent downloader.
     pub fn build<B, Provider>(
         self,
@@ -574,6 +694,8 @@ impl BodiesDownloaderBuilder {
             stream_batch_size,
             concurrent_requests_range,
             max_buffered_blocks_size_bytes,
+            max_spilled_blocks_size_bytes,
+            spill_dir,
         } = self;
         let metrics = BodyDownloaderMetrics::default();
         let in_progress_queue = BodiesRequestQueue::new(metrics.clone());
@@ -592,6 +714,8 @@ impl BodiesDownloaderBuilder {
             buffered_responses: Default::default(),
             queued_bodies: Default::default(),
             buffered_blocks_size_bytes: 0,
+            spill: ResponseSpill::new(spill_dir, max_spilled_blocks_size_bytes),
+            pending_read: None,
         }
     }
 }
@@ -812,6 +936,94 @@ mod tests {
         }
     }
 
+    // Check that the downloader writes out-of-order responses to disk once the size limit is
+    // reached, and streams them in order.
+    #[tokio::test]
+    async fn can_download_with_spilled_blocks() {
+        // Generate some random blocks
+        let db = create_test_rw_db();
+        let (headers, mut bodies) = generate_bodies(0..=199);
+
+        insert_headers(db.db(), &headers);
+
+        // delay responses so they arrive out of order
+        let client = Arc::new(
+            TestBodiesClient::default().with_bodies(bodies.clone()).with_should_delay(true),
+        );
+
+        let (_static_dir, static_dir_path) = create_test_static_files_dir();
+        let spill_dir = tempfile::tempdir().unwrap();
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_stream_batch_size(10)
+            .with_request_limit(2)
+            .with_max_buffered_blocks_size_bytes(1)
+            .with_max_spilled_blocks_size_bytes(1024 * 1024 * 1024)
+            .with_spill_dir(Some(spill_dir.path().to_path_buf()))
+            .build(
+                client.clone(),
+                Arc::new(TestConsensus::default()),
+                ProviderFactory::new(
+                    db,
+                    MAINNET.clone(),
+                    StaticFileProvider::read_write(static_dir_path).unwrap(),
+                ),
+            );
+
+        // Set and download the entire range
+        downloader.set_download_range(0..=199).expect("failed to set download range");
+        let mut header = 0;
+        while let Some(Ok(resp)) = downloader.next().await {
+            assert_eq!(resp, zip_blocks(headers.iter().skip(header).take(resp.len()), &mut bodies));
+            header += resp.len();
+        }
+        assert_eq!(header, headers.len());
+        assert_eq!(downloader.spill.size_bytes(), 0);
+    }
+
+    // Check that the downloader requests the blocks again and holds back requests once the size
+    // limit is reached if the blocks can't be written to disk.
+    #[tokio::test]
+    async fn can_download_if_spilling_fails() {
+        // Generate some random blocks
+        let db = create_test_rw_db();
+        let (headers, mut bodies) = generate_bodies(0..=199);
+
+        insert_headers(db.db(), &headers);
+
+        // delay responses so they arrive out of order
+        let client = Arc::new(
+            TestBodiesClient::default().with_bodies(bodies.clone()).with_should_delay(true),
+        );
+
+        let (_static_dir, static_dir_path) = create_test_static_files_dir();
+        // the file can't be created in a directory below a regular file
+        let spill_file = tempfile::NamedTempFile::new().unwrap();
+        let mut downloader = BodiesDownloaderBuilder::default()
+            .with_stream_batch_size(10)
+            .with_request_limit(2)
+            .with_max_buffered_blocks_size_bytes(1)
+            .with_max_spilled_blocks_size_bytes(1024 * 1024 * 1024)
+            .with_spill_dir(Some(spill_file.path().join("spill")))
+            .build(
+                client.clone(),
+                Arc::new(TestConsensus::default()),
+                ProviderFactory::new(
+                    db,
+                    MAINNET.clone(),
+                    StaticFileProvider::read_write(static_dir_path).unwrap(),
+                ),
+            );
+
+        // Set and download the entire range
+        downloader.set_download_range(0..=199).expect("failed to set download range");
+        let mut header = 0;
+        while let Some(Ok(resp)) = downloader.next().await {
+            assert_eq!(resp, zip_blocks(headers.iter().skip(header).take(resp.len()), &mut bodies));
+            header += resp.len();
+        }
+        assert_eq!(header, headers.len());
+    }
+
     // Check that the downloader can tolerate a few completely empty responses
     #[tokio::test]
     async fn can_tolerate_empty_responses() {
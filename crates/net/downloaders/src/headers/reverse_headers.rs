@@ -1,7 +1,10 @@
 //! A headers downloader that can handle multiple requests concurrently.
 
 use super::task::TaskDownloader;
-use crate::metrics::HeaderDownloaderMetrics;
+use crate::{
+    metrics::HeaderDownloaderMetrics,
+    spill::{MaybeSpilled, ResponseSpill, SpillRead},
+};
 use futures::{stream::Stream, FutureExt};
 use futures_util::{stream::FuturesUnordered, StreamExt};
 use rayon::prelude::*;
@@ -25,12 +28,13 @@ use std::{
     cmp::{Ordering, Reverse},
     collections::{binary_heap::PeekMut, BinaryHeap},
     future::Future,
+    path::PathBuf,
     pin::Pin,
     sync::Arc,
     task::{ready, Context, Poll},
 };
 use thiserror::Error;
-use tracing::{error, trace};
+use tracing::{error, trace, warn};
 
 /// A heuristic that is used to determine the number of requests that should be prepared for a peer.
 /// This should ensure that there are always requests lined up for peers to handle while the
@@ -98,6 +102,11 @@ pub struct ReverseHeadersDownloader<H: HeadersClient> {
     in_progress_queue: FuturesUnordered<HeadersRequestFuture<H::Output>>,
     /// Buffered, unvalidated responses
     buffered_responses: BinaryHeap<OrderedHeadersResponse>,
+    /// Temporary file that buffered responses are written to once `max_buffered_responses` is
+    /// reached.
+    spill: ResponseSpill,
+    /// The read of the next expected response from the temporary file.
+    pending_read: Option<PendingSpillRead>,
     /// Buffered, _sorted_ and validated headers ready to be returned.
     ///
     /// Note: headers are sorted from high to low
@@ -495,6 +504,7 @@ where
                 } else if highest.number > self.existing_local_block_number() {
                     self.metrics.buffered_responses.increment(1.);
                     // can't validate yet
+                    let headers = self.maybe_spill(headers);
                     self.buffered_responses.push(OrderedHeadersResponse {
                         headers,
                         request,
@@ -513,6 +523,62 @@ where
         }
     }
 
+    /// Writes the headers of a response that is buffered to a temporary file, if the buffer is
+    /// full and there is disk space left.
+    fn maybe_spill(&mut self, headers: Vec<Header>) -> MaybeSpilled<Vec<Header>> {
+        if self.buffered_responses.len() < self.max_buffered_responses || !self.spill.has_capacity()
+        {
+            return MaybeSpilled::InMemory(headers)
+        }
+
+        let spilled = self.spill.spill(&headers);
+        self.metrics.spilled_responses.increment(1.);
+        self.metrics.spilled_responses_size_bytes.set(self.spill.size_bytes() as f64);
+        MaybeSpilled::Spilled(spilled)
+    }
+
+    /// Polls the read of the next expected response from disk and validates it.
+    ///
+    /// Returns true if the read completed. If the response can't be read back, it's requested
+    /// again and no more responses are written to disk.
+    fn poll_pending_read(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Result<bool, ReverseHeadersDownloaderError> {
+        let Some(pending) = &mut self.pending_read else { return Ok(false) };
+        let Poll::Ready(result) = pending.read.poll_unpin(cx) else { return Ok(false) };
+        let PendingSpillRead { request, peer_id, .. } =
+            self.pending_read.take().expect("is pending");
+
+        match result {
+            // the response might have been received again in the meantime
+            Ok(headers) if request.start.as_number() == Some(self.next_chain_tip_block_number) => {
+                self.process_next_headers(request, headers, peer_id)?;
+                // try to validate all buffered responses blocked by this successful response
+                self.try_validate_buffered()
+                    .map(Err::<(), ReverseHeadersDownloaderError>)
+                    .transpose()?;
+            }
+            Ok(_) => {}
+            Err(error) => {
+                warn!(target: "downloaders::headers", %error, ?request, "Failed to read headers from disk, requesting them again");
+                self.spill.set_failed();
+                self.submit_request(request, Priority::High);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Releases the disk space of a buffered response that was removed from the buffer, if it was
+    /// written to disk.
+    fn release_spilled(&mut self, response: &OrderedHeadersResponse) {
+        if let MaybeSpilled::Spilled(spilled) = &response.headers {
+            self.spill.release(spilled);
+            self.metrics.spilled_responses.decrement(1.);
+            self.metrics.spilled_responses_size_bytes.set(self.spill.size_bytes() as f64);
+        }
+    }
+
     fn penalize_peer(&self, peer_id: Option<PeerId>, error: &DownloadError) {
         // Penalize the peer for bad response
         if let Some(peer_id) = peer_id {
@@ -539,7 +605,14 @@ where
     /// Attempts to validate the buffered responses
     ///
     /// Returns an error if the next expected response was popped, but failed validation.
+    ///
+    /// If the next expected response was written to disk, it's read back in the background first,
+    /// see [`Self::poll_pending_read`].
     fn try_validate_buffered(&mut self) -> Option<ReverseHeadersDownloaderError> {
+        if self.pending_read.is_some() {
+            return None
+        }
+
         loop {
             // Check to see if we've already received the next value
             let next_response = self.buffered_responses.peek_mut()?;
@@ -547,9 +620,19 @@ where
             match next_block_number.cmp(&self.next_chain_tip_block_number) {
                 Ordering::Less => return None,
                 Ordering::Equal => {
-                    let OrderedHeadersResponse { headers, request, peer_id } =
-                        PeekMut::pop(next_response);
+                    let response = PeekMut::pop(next_response);
                     self.metrics.buffered_responses.decrement(1.);
+                    self.release_spilled(&response);
+
+                    let OrderedHeadersResponse { headers, request, peer_id } = response;
+                    let headers = match headers {
+                        MaybeSpilled::InMemory(headers) => headers,
+                        MaybeSpilled::Spilled(spilled) => {
+                            let read = self.spill.read(&spilled);
+                            self.pending_read = Some(PendingSpillRead { read, request, peer_id });
+                            return None
+                        }
+                    };
 
                     if let Err(err) = self.process_next_headers(request, headers, peer_id) {
                         return Some(err)
@@ -557,7 +640,8 @@ where
                 }
                 Ordering::Greater => {
                     self.metrics.buffered_responses.decrement(1.);
-                    PeekMut::pop(next_response);
+                    let response = PeekMut::pop(next_response);
+                    self.release_spilled(&response);
                 }
             }
         }
@@ -597,10 +681,14 @@ where
         self.lowest_validated_header.take();
         self.queued_validated_headers = Vec::new();
         self.buffered_responses = BinaryHeap::new();
+        self.spill.clear();
+        self.pending_read = None;
         self.in_progress_queue.clear();
 
         self.metrics.in_flight_requests.set(0.);
         self.metrics.buffered_responses.set(0.);
+        self.metrics.spilled_responses.set(0.);
+        self.metrics.spilled_responses_size_bytes.set(0.);
     }
 
     /// Splits off the next batch of headers
@@ -821,13 +909,31 @@ where
             // shrink the buffer after handling headers outcomes
             this.buffered_responses.shrink_to_fit();
 
-            // marks the loop's exit condition: exit if no requests submitted
-            let mut progress = false;
+            // validate the next response once it was read back from disk, this can submit a
+            // request or start the next read.
+            //
+            // marks the loop's exit condition: exit if no requests submitted and no response read
+            let mut progress = match this.poll_pending_read(cx) {
+                Ok(read) => read,
+                Err(ReverseHeadersDownloaderError::Response(error)) => {
+                    if error.is_channel_closed() {
+                        // download channel closed which means the network was dropped
+                        return Poll::Ready(None)
+                    }
+                    this.on_headers_error(error);
+                    true
+                }
+                Err(ReverseHeadersDownloaderError::Downloader(error)) => {
+                    this.clear();
+                    return Poll::Ready(Some(Err(error)))
+                }
+            };
 
             let concurrent_request_limit = this.concurrent_request_limit();
             // populate requests
             while this.in_progress_queue.len() < concurrent_request_limit &&
-                this.buffered_responses.len() < this.max_buffered_responses
+                (this.buffered_responses.len() < this.max_buffered_responses ||
+                    this.spill.has_capacity())
             {
                 if let Some(request) = this.next_request() {
                     trace!(
@@ -864,7 +970,7 @@ where
         }
 
         // all requests are handled, stream is finished
-        if this.in_progress_queue.is_empty() {
+        if this.in_progress_queue.is_empty() && this.pending_read.is_none() {
             let next_batch = this.split_next_batch();
             if next_batch.is_empty() {
                 this.clear();
@@ -914,10 +1020,19 @@ impl HeadersRequestOutcome {
     }
 }
 
+/// The read of a buffered response that was written to disk, see
+/// [`ReverseHeadersDownloader::poll_pending_read`].
+#[derive(Debug)]
+struct PendingSpillRead {
+    read: SpillRead<Header>,
+    request: HeadersRequest,
+    peer_id: PeerId,
+}
+
 /// Wrapper type to order responses
 #[derive(Debug)]
 struct OrderedHeadersResponse {
-    headers: Vec<Header>,
+    headers: MaybeSpilled<Vec<Header>>,
     request: HeadersRequest,
     peer_id: PeerId,
 }
@@ -1072,6 +1187,10 @@ pub struct ReverseHeadersDownloaderBuilder {
     max_concurrent_requests: usize,
     /// How many responses to buffer
     max_buffered_responses: usize,
+    /// The maximum size of the temporary file responses are written to once the buffer is full
+    max_spilled_size_bytes: usize,
+    /// The directory of the temporary file
+    spill_dir: Option<PathBuf>,
 }
 
 impl ReverseHeadersDownloaderBuilder {
@@ -1083,6 +1202,7 @@ impl ReverseHeadersDownloaderBuilder {
             .min_concurrent_requests(config.downloader_min_concurrent_requests)
             .max_concurrent_requests(config.downloader_max_concurrent_requests)
             .max_buffered_responses(config.downloader_max_buffered_responses)
+            .max_spilled_size_bytes(config.downloader_max_spilled_size_bytes)
             .stream_batch_size(config.commit_threshold as usize)
     }
 }
@@ -1097,6 +1217,8 @@ impl Default for ReverseHeadersDownloaderBuilder {
             max_concurrent_requests: 100,
             min_concurrent_requests: 5,
             max_buffered_responses: 100,
+            max_spilled_size_bytes: 0,
+            spill_dir: None,
         }
     }
 }
@@ -1149,6 +1271,24 @@ impl ReverseHeadersDownloaderBuilder {
         self
     }
 
+    /// The maximum size of the temporary file responses are written to once the buffer is full.
+    ///
+    /// Instead of waiting for buffered responses to be processed, the [`ReverseHeadersDownloader`]
+    /// keeps sending requests and writes the headers of responses that can't be validated yet to
+    /// disk, until they reach this size. Nothing is written to disk if this is zero.
+    pub const fn max_spilled_size_bytes(mut self, max_spilled_size_bytes: usize) -> Self {
+        self.max_spilled_size_bytes = max_spilled_size_bytes;
+        self
+    }
+
+    /// Set the directory of the temporary file that responses are written to.
+    ///
+    /// Defaults to the temporary directory of the OS.
+    pub fn spill_dir(mut self, spill_dir: Option<PathBuf>) -> Self {
+        self.spill_dir = spill_dir;
+        self
+    }
+
     /// Build [`ReverseHeadersDownloader`] with provided consensus
     /// and header client implementations
     pub fn build<H>(self, client: H, consensus: Arc<dyn Consensus>) -> ReverseHeadersDownloader<H>
@@ -1161,6 +1301,8 @@ impl ReverseHeadersDownloaderBuilder {
             min_concurrent_requests,
             max_concurrent_requests,
             max_buffered_responses,
+            max_spilled_size_bytes,
+            spill_dir,
         } = self;
         ReverseHeadersDownloader {
             consensus,
@@ -1180,6 +1322,8 @@ impl ReverseHeadersDownloaderBuilder {
             sync_target_request: None,
             in_progress_queue: Default::default(),
             buffered_responses: Default::default(),
+            spill: ResponseSpill::new(spill_dir, max_spilled_size_bytes),
+            pending_read: None,
             queued_validated_headers: Default::default(),
             metrics: Default::default(),
         }
@@ -1369,14 +1513,14 @@ mod tests {
         let mut heap = BinaryHeap::new();
         let hi = 1u64;
         heap.push(OrderedHeadersResponse {
-            headers: vec![],
+            headers: MaybeSpilled::InMemory(vec![]),
             request: HeadersRequest { start: hi.into(), limit: 0, direction: Default::default() },
             peer_id: Default::default(),
         });
 
         let lo = 0u64;
         heap.push(OrderedHeadersResponse {
-            headers: vec![],
+            headers: MaybeSpilled::InMemory(vec![]),
             request: HeadersRequest { start: lo.into(), limit: 0, direction: Default::default() },
             peer_id: Default::default(),
         });
@@ -21,6 +21,9 @@ pub mod headers;
 /// Common downloader metrics.
 pub mod metrics;
 
+/// Spilling of buffered responses to a temporary file.
+mod spill;
+
 /// Module managing file-based data retrieval and buffering.
 ///
 /// Contains [`FileClient`](file_client::FileClient) to read block data from files,
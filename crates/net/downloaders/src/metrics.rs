@@ -35,6 +35,11 @@ pub struct BodyDownloaderMetrics {
     pub buffered_blocks: Gauge,
     /// Total amount of memory used by the buffered blocks in bytes
     pub buffered_blocks_size_bytes: Gauge,
+    /// The number of buffered responses that were written to a temporary file because the
+    /// internal buffer was full.
+    pub spilled_responses: Gauge,
+    /// Total size of the temporary files of the buffered blocks in bytes
+    pub spilled_blocks_size_bytes: Gauge,
     /// The number blocks that are contiguous and are queued for insertion into the db.
     pub queued_blocks: Gauge,
     /// The number of out-of-order requests sent by the downloader.
@@ -106,6 +111,11 @@ pub struct HeaderDownloaderMetrics {
     pub buffered_blocks: Gauge,
     /// Total amount of memory used by the buffered blocks in bytes
     pub buffered_blocks_size_bytes: Gauge,
+    /// The number of buffered responses that were written to a temporary file because the
+    /// internal buffer was full.
+    pub spilled_responses: Gauge,
+    /// Total size of the temporary files of the buffered responses in bytes
+    pub spilled_responses_size_bytes: Gauge,
     /// The number blocks that are contiguous and are queued for insertion into the db.
     pub queued_blocks: Gauge,
     /// The number of out-of-order requests sent by the downloader.
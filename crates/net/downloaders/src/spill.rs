@@ -0,0 +1,428 @@
+use alloy_rlp::{Decodable, Encodable};
+use futures::{channel::oneshot, FutureExt};
+use reth_network_p2p::bodies::response::BlockResponse;
+use reth_primitives::{Header, SealedBlock, SealedHeader, B256};
+use std::{
+    fs::File,
+    future::Future,
+    io::{self, Read, Seek, SeekFrom, Write},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    task::{ready, Context, Poll},
+};
+use tracing::warn;
+
+/// Writes buffered responses of a downloader to a temporary file once the memory budget of the
+/// downloader is exhausted, up to a budget of disk space.
+///
+/// Responses are appended to a single unnamed temporary file, which is removed by the OS once the
+/// spill is dropped. The file is owned by a dedicated thread, so writing and reading responses
+/// doesn't block the task that polls the downloader: responses are encoded and sent to the thread,
+/// and are read back with a [`SpillRead`] future.
+///
+/// The disk space of a response has to be released once it's removed from the buffer of the
+/// downloader. The file is truncated once all spilled responses were released, so the budget
+/// limits the size of the file rather than the size of the responses in it.
+///
+/// If a response can't be written or read back, no more responses are spilled until the
+/// downloader is reset, so the downloader falls back to holding back requests once its buffer is
+/// full.
+#[derive(Debug)]
+pub(crate) struct ResponseSpill {
+    /// The directory the file is created in, the temporary directory of the OS if unset.
+    dir: Option<PathBuf>,
+    /// The maximum number of bytes of the file.
+    max_size_bytes: usize,
+    /// The number of bytes of the currently spilled responses.
+    size_bytes: usize,
+    /// The number of bytes written to the file since it was last truncated.
+    len_bytes: usize,
+    /// The thread that owns the file, spawned once the first response is spilled.
+    worker: Option<SpillWorker>,
+    /// Set if a response couldn't be written to or read from the file.
+    failed: Arc<AtomicBool>,
+}
+
+impl ResponseSpill {
+    /// Creates a new spill that writes up to `max_size_bytes` to a file in the given directory.
+    ///
+    /// Nothing is spilled if `max_size_bytes` is zero.
+    pub(crate) fn new(dir: Option<PathBuf>, max_size_bytes: usize) -> Self {
+        Self {
+            dir,
+            max_size_bytes,
+            size_bytes: 0,
+            len_bytes: 0,
+            worker: None,
+            failed: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns true if there is disk space left to spill responses to.
+    ///
+    /// Returns false if spilling a response failed before.
+    pub(crate) fn has_capacity(&self) -> bool {
+        // the file is truncated before the next response is written if all responses were released
+        self.max_size_bytes > 0 &&
+            !self.failed.load(Ordering::Relaxed) &&
+            (self.size_bytes == 0 || self.len_bytes < self.max_size_bytes)
+    }
+
+    /// Returns the number of bytes of the currently spilled responses.
+    pub(crate) const fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
+
+    /// Stops spilling responses, e.g. because a response couldn't be read back.
+    pub(crate) fn set_failed(&self) {
+        self.failed.store(true, Ordering::Relaxed);
+    }
+
+    /// Appends the items of a response to the file.
+    ///
+    /// The items are written by the thread that owns the file, errors are reported when the
+    /// response is read back.
+    pub(crate) fn spill<T: Spill>(&mut self, items: &[T]) -> SpilledResponse {
+        let mut buf = Vec::new();
+        for item in items {
+            item.encode_spilled(&mut buf);
+        }
+
+        // all previously spilled responses were released, so the file can be reused
+        if self.size_bytes == 0 && self.len_bytes > 0 {
+            self.send(SpillOp::Truncate);
+            self.len_bytes = 0;
+        }
+
+        let response =
+            SpilledResponse { offset: self.len_bytes as u64, len: items.len(), size: buf.len() };
+        self.size_bytes += buf.len();
+        self.len_bytes += buf.len();
+        self.send(SpillOp::Write { offset: response.offset, buf });
+        response
+    }
+
+    /// Returns a future that reads the items of a spilled response back from the file.
+    ///
+    /// The read is queued after all writes, so it can be started right after the response was
+    /// spilled. The disk space of the response has to be released separately.
+    pub(crate) fn read<T: Spill>(&mut self, response: &SpilledResponse) -> SpillRead<T> {
+        let (tx, rx) = oneshot::channel();
+        self.send(SpillOp::Read { offset: response.offset, size: response.size, tx });
+        SpillRead { rx, len: response.len, _marker: PhantomData }
+    }
+
+    /// Releases the disk space of a spilled response that was removed from the buffer.
+    pub(crate) fn release(&mut self, response: &SpilledResponse) {
+        self.size_bytes -= response.size;
+    }
+
+    /// Releases the disk space of all spilled responses, which are dropped by the downloader.
+    ///
+    /// This truncates the file and spills responses again if spilling failed before.
+    pub(crate) fn clear(&mut self) {
+        self.size_bytes = 0;
+        if self.len_bytes > 0 {
+            self.send(SpillOp::Truncate);
+            self.len_bytes = 0;
+        }
+        self.failed.store(false, Ordering::Relaxed);
+    }
+
+    /// Sends an operation to the thread that owns the file, spawning it if necessary.
+    fn send(&mut self, op: SpillOp) {
+        if self.worker.is_none() {
+            match SpillWorker::spawn(self.dir.clone(), Arc::clone(&self.failed)) {
+                Ok(worker) => self.worker = Some(worker),
+                Err(error) => {
+                    warn!(target: "downloaders", %error, "Failed to spawn thread for writing responses to disk");
+                    self.set_failed();
+                    // dropping the operation fails a pending read
+                    return
+                }
+            }
+        }
+
+        if let Some(worker) = &self.worker {
+            if worker.tx.send(op).is_err() {
+                // the thread panicked, the dropped operation fails a pending read
+                self.set_failed();
+                self.worker = None;
+            }
+        }
+    }
+}
+
+/// The handle of the thread that owns the file of a [`ResponseSpill`].
+///
+/// The thread exits once the handle is dropped, which closes and removes the file.
+#[derive(Debug)]
+struct SpillWorker {
+    tx: mpsc::Sender<SpillOp>,
+}
+
+impl SpillWorker {
+    /// Spawns the thread, which creates the file in the given directory on the first write.
+    fn spawn(dir: Option<PathBuf>, failed: Arc<AtomicBool>) -> io::Result<Self> {
+        let (tx, rx) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("downloader-spill".to_string())
+            .spawn(move || Self::run(dir, &rx, &failed))?;
+        Ok(Self { tx })
+    }
+
+    /// Executes the operations in order until the handle is dropped.
+    fn run(dir: Option<PathBuf>, rx: &mpsc::Receiver<SpillOp>, failed: &AtomicBool) {
+        let mut file = None;
+        while let Ok(op) = rx.recv() {
+            match op {
+                SpillOp::Write { offset, buf } => {
+                    let result = Self::file(&mut file, dir.as_deref()).and_then(|file| {
+                        file.seek(SeekFrom::Start(offset))?;
+                        file.write_all(&buf)
+                    });
+                    if let Err(error) = result {
+                        warn!(target: "downloaders", %error, "Failed to write response to disk");
+                        failed.store(true, Ordering::Relaxed);
+                    }
+                }
+                SpillOp::Read { offset, size, tx } => {
+                    let result = Self::file(&mut file, dir.as_deref()).and_then(|file| {
+                        let mut buf = vec![0; size];
+                        file.seek(SeekFrom::Start(offset))?;
+                        file.read_exact(&mut buf)?;
+                        Ok(buf)
+                    });
+                    let _ = tx.send(result);
+                }
+                SpillOp::Truncate => {
+                    if let Some(file) = &file {
+                        if let Err(error) = file.set_len(0) {
+                            warn!(target: "downloaders", %error, "Failed to truncate file of responses");
+                            failed.store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the file, which is created if necessary.
+    fn file<'a>(file: &'a mut Option<File>, dir: Option<&Path>) -> io::Result<&'a mut File> {
+        if file.is_none() {
+            *file = Some(match dir {
+                Some(dir) => {
+                    std::fs::create_dir_all(dir)?;
+                    tempfile::tempfile_in(dir)?
+                }
+                None => tempfile::tempfile()?,
+            });
+        }
+        Ok(file.as_mut().expect("is set"))
+    }
+}
+
+/// An operation on the file of a [`ResponseSpill`].
+#[derive(Debug)]
+enum SpillOp {
+    /// Writes the encoded items of a response at the offset.
+    Write { offset: u64, buf: Vec<u8> },
+    /// Reads the encoded items of a response at the offset.
+    Read { offset: u64, size: usize, tx: oneshot::Sender<io::Result<Vec<u8>>> },
+    /// Truncates the file, once all responses were released.
+    Truncate,
+}
+
+/// A response that was written to the file of a [`ResponseSpill`].
+#[derive(Debug)]
+pub(crate) struct SpilledResponse {
+    /// The offset of the response in the file.
+    offset: u64,
+    /// The number of items in the response.
+    len: usize,
+    /// The size of the response in bytes.
+    size: usize,
+}
+
+/// A future that resolves to the items of a spilled response, see [`ResponseSpill::read`].
+#[must_use = "futures do nothing unless polled"]
+#[derive(Debug)]
+pub(crate) struct SpillRead<T> {
+    rx: oneshot::Receiver<io::Result<Vec<u8>>>,
+    /// The number of items in the response.
+    len: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Spill> Future for SpillRead<T> {
+    type Output = io::Result<Vec<T>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let buf = ready!(this.rx.poll_unpin(cx)).unwrap_or_else(|_| {
+            Err(io::Error::new(io::ErrorKind::Other, "spilled response can't be read"))
+        })?;
+
+        let mut slice = buf.as_slice();
+        Poll::Ready(
+            (0..this.len)
+                .map(|_| {
+                    T::decode_spilled(&mut slice)
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+                })
+                .collect(),
+        )
+    }
+}
+
+/// A buffered response of a downloader, in memory or spilled to disk.
+#[derive(Debug)]
+pub(crate) enum MaybeSpilled<T> {
+    /// The response is held in memory.
+    InMemory(T),
+    /// The response was written to a temporary file.
+    Spilled(SpilledResponse),
+}
+
+/// The encoding of the items of a response that can be spilled to disk.
+pub(crate) trait Spill: Sized {
+    /// Encodes the item and appends it to the buffer.
+    fn encode_spilled(&self, out: &mut Vec<u8>);
+
+    /// Decodes the next item of the buffer.
+    fn decode_spilled(buf: &mut &[u8]) -> alloy_rlp::Result<Self>;
+}
+
+impl Spill for Header {
+    fn encode_spilled(&self, out: &mut Vec<u8>) {
+        self.encode(out);
+    }
+
+    fn decode_spilled(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        Self::decode(buf)
+    }
+}
+
+/// Blocks are spilled with the hash of their header, so the hash is not recomputed when they are
+/// read back.
+impl Spill for BlockResponse {
+    fn encode_spilled(&self, out: &mut Vec<u8>) {
+        match self {
+            Self::Full(block) => {
+                out.push(1);
+                out.extend_from_slice(block.hash().as_slice());
+                block.encode(out);
+            }
+            Self::Empty(header) => {
+                out.push(0);
+                out.extend_from_slice(header.hash().as_slice());
+                header.header().encode(out);
+            }
+        }
+    }
+
+    fn decode_spilled(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        if buf.len() < 1 + B256::len_bytes() {
+            return Err(alloy_rlp::Error::InputTooShort)
+        }
+        let full = buf[0] == 1;
+        let hash = B256::from_slice(&buf[1..1 + B256::len_bytes()]);
+        *buf = &buf[1 + B256::len_bytes()..];
+
+        if full {
+            let mut block = SealedBlock::decode(buf)?;
+            block.header = SealedHeader::new(block.header.unseal(), hash);
+            Ok(Self::Full(block))
+        } else {
+            Ok(Self::Empty(SealedHeader::new(Header::decode(buf)?, hash)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_testing_utils::{generators, generators::random_block_range};
+
+    #[tokio::test]
+    async fn spill_and_restore_blocks() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(&mut rng, 0..=9, B256::ZERO, 0..3)
+            .into_iter()
+            .map(|block| {
+                if block.body.is_empty() {
+                    BlockResponse::Empty(block.header)
+                } else {
+                    BlockResponse::Full(block)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut spill = ResponseSpill::new(Some(dir.path().to_path_buf()), 1024 * 1024);
+        let first = spill.spill(&blocks[..5]);
+        let second = spill.spill(&blocks[5..]);
+        assert!(spill.size_bytes() > 0);
+
+        // responses can be read back in any order
+        assert_eq!(spill.read::<BlockResponse>(&second).await.unwrap(), blocks[5..]);
+        assert_eq!(spill.read::<BlockResponse>(&first).await.unwrap(), blocks[..5]);
+
+        spill.release(&first);
+        spill.release(&second);
+        assert_eq!(spill.size_bytes(), 0);
+    }
+
+    #[tokio::test]
+    async fn spill_and_restore_headers() {
+        let headers =
+            (0..10).map(|number| Header { number, ..Default::default() }).collect::<Vec<_>>();
+
+        let mut spill = ResponseSpill::new(None, 1);
+        assert!(spill.has_capacity());
+        let spilled = spill.spill(&headers);
+        assert!(!spill.has_capacity());
+
+        let read = spill.read::<Header>(&spilled);
+        spill.release(&spilled);
+        assert!(spill.has_capacity());
+        assert_eq!(read.await.unwrap(), headers);
+
+        // the file is reused once all responses were released
+        let spilled = spill.spill(&headers[..1]);
+        assert_eq!(spilled.offset, 0);
+        assert_eq!(spill.read::<Header>(&spilled).await.unwrap(), headers[..1]);
+    }
+
+    #[tokio::test]
+    async fn stops_spilling_after_failure() {
+        let headers = vec![Header::default()];
+
+        // the file can't be created in a directory below a regular file
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let mut spill = ResponseSpill::new(Some(file.path().join("spill")), 1024);
+        assert!(spill.has_capacity());
+
+        let spilled = spill.spill(&headers);
+        assert!(spill.read::<Header>(&spilled).await.is_err());
+        assert!(!spill.has_capacity());
+
+        spill.release(&spilled);
+        assert!(!spill.has_capacity());
+
+        // spilling is enabled again once the downloader is reset
+        spill.clear();
+        assert!(spill.has_capacity());
+    }
+
+    #[test]
+    fn disabled_without_budget() {
+        let spill = ResponseSpill::new(None, 0);
+        assert!(!spill.has_capacity());
+    }
+}
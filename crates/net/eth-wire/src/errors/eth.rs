@@ -22,9 +22,14 @@ pub enum EthStreamError {
     /// Thrown when decoding a message message failed.
     #[error(transparent)]
     InvalidMessage(#[from] MessageError),
-    #[error("message size ({0}) exceeds max length (10MB)")]
-    /// Received a message whose size exceeds the standard limit.
-    MessageTooBig(usize),
+    #[error("message size ({message_size}) exceeds max length ({max_size})")]
+    /// Received a message whose size exceeds the configured limit.
+    MessageTooBig {
+        /// The actual size of the message received.
+        message_size: usize,
+        /// The maximum allowed size for the message.
+        max_size: usize,
+    },
     #[error("TransactionHashes invalid len of fields: hashes_len={hashes_len} types_len={types_len} sizes_len={sizes_len}")]
     /// Received malformed transaction hashes message with discrepancies in field lengths.
     TransactionHashesInvalidLenOfFields {
@@ -2,7 +2,8 @@ use crate::{
     errors::{EthHandshakeError, EthStreamError},
     message::{EthBroadcastMessage, ProtocolBroadcastMessage},
     p2pstream::HANDSHAKE_TIMEOUT,
-    CanDisconnect, DisconnectReason, EthMessage, EthVersion, ProtocolMessage, Status,
+    CanDisconnect, DisconnectReason, EthMessage, EthMessageID, EthVersion, ProtocolMessage,
+    Status,
 };
 use futures::{ready, Sink, SinkExt, StreamExt};
 use pin_project::pin_project;
@@ -23,6 +24,79 @@ use tracing::{debug, trace};
 // https://github.com/ethereum/go-ethereum/blob/30602163d5d8321fbc68afdcbbaf2362b2641bde/eth/protocols/eth/protocol.go#L50
 pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 
+/// Maximum sizes of received eth protocol messages, per message type.
+///
+/// The sizes apply to the uncompressed message and default to [`MAX_MESSAGE_SIZE`]. Lowering them
+/// bounds the memory a single peer can make us allocate for a message of the given type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct EthMessageSizeLimits {
+    /// Maximum size of [`EthMessageID::BlockHeaders`] messages.
+    pub block_headers: usize,
+    /// Maximum size of [`EthMessageID::BlockBodies`] messages.
+    pub block_bodies: usize,
+    /// Maximum size of [`EthMessageID::NewBlock`] messages.
+    pub new_block: usize,
+    /// Maximum size of [`EthMessageID::Transactions`] messages.
+    pub transactions: usize,
+    /// Maximum size of [`EthMessageID::PooledTransactions`] messages.
+    pub pooled_transactions: usize,
+    /// Maximum size of [`EthMessageID::Receipts`] messages.
+    pub receipts: usize,
+    /// Maximum size of [`EthMessageID::NodeData`] messages.
+    pub node_data: usize,
+    /// Maximum size of all other messages.
+    pub other: usize,
+}
+
+impl EthMessageSizeLimits {
+    /// Creates limits that allow every message type up to the given size.
+    pub const fn uniform(max_size: usize) -> Self {
+        Self {
+            block_headers: max_size,
+            block_bodies: max_size,
+            new_block: max_size,
+            transactions: max_size,
+            pooled_transactions: max_size,
+            receipts: max_size,
+            node_data: max_size,
+            other: max_size,
+        }
+    }
+
+    /// Returns the maximum size of messages with the given message id.
+    pub const fn max_size(&self, id: u8) -> usize {
+        match id {
+            id if id == EthMessageID::BlockHeaders as u8 => self.block_headers,
+            id if id == EthMessageID::BlockBodies as u8 => self.block_bodies,
+            id if id == EthMessageID::NewBlock as u8 => self.new_block,
+            id if id == EthMessageID::Transactions as u8 => self.transactions,
+            id if id == EthMessageID::PooledTransactions as u8 => self.pooled_transactions,
+            id if id == EthMessageID::Receipts as u8 => self.receipts,
+            id if id == EthMessageID::NodeData as u8 => self.node_data,
+            _ => self.other,
+        }
+    }
+
+    /// Returns an error if the message exceeds the maximum size of its message type.
+    ///
+    /// The first byte of the message is its message id.
+    fn ensure_within_limit(&self, msg: &[u8]) -> Result<(), EthStreamError> {
+        let max_size = self.max_size(msg.first().copied().unwrap_or_default());
+        if msg.len() > max_size {
+            return Err(EthStreamError::MessageTooBig { message_size: msg.len(), max_size })
+        }
+        Ok(())
+    }
+}
+
+impl Default for EthMessageSizeLimits {
+    fn default() -> Self {
+        Self::uniform(MAX_MESSAGE_SIZE)
+    }
+}
+
 /// An un-authenticated [`EthStream`]. This is consumed and returns a [`EthStream`] after the
 /// `Status` handshake is completed.
 #[pin_project]
@@ -30,12 +104,21 @@ pub const MAX_MESSAGE_SIZE: usize = 10 * 1024 * 1024;
 pub struct UnauthedEthStream<S> {
     #[pin]
     inner: S,
+    /// Maximum sizes of received messages.
+    max_message_sizes: EthMessageSizeLimits,
 }
 
 impl<S> UnauthedEthStream<S> {
     /// Create a new `UnauthedEthStream` from a type `S` which implements `Stream` and `Sink`.
     pub const fn new(inner: S) -> Self {
-        Self { inner }
+        Self { inner, max_message_sizes: EthMessageSizeLimits::uniform(MAX_MESSAGE_SIZE) }
+    }
+
+    /// Sets the maximum sizes of received messages, which are also applied to the authenticated
+    /// [`EthStream`].
+    pub const fn with_max_message_sizes(mut self, max_message_sizes: EthMessageSizeLimits) -> Self {
+        self.max_message_sizes = max_message_sizes;
+        self
     }
 
     /// Consumes the type and returns the wrapped stream
@@ -99,9 +182,9 @@ where
             }
         }?;
 
-        if their_msg.len() > MAX_MESSAGE_SIZE {
+        if let Err(err) = self.max_message_sizes.ensure_within_limit(&their_msg) {
             self.inner.disconnect(DisconnectReason::ProtocolBreach).await?;
-            return Err(EthStreamError::MessageTooBig(their_msg.len()))
+            return Err(err)
         }
 
         let version = EthVersion::try_from(status.version)?;
@@ -168,7 +251,8 @@ where
 
                 // now we can create the `EthStream` because the peer has successfully completed
                 // the handshake
-                let stream = EthStream::new(version, self.inner);
+                let stream = EthStream::new(version, self.inner)
+                    .with_max_message_sizes(self.max_message_sizes);
 
                 Ok((stream, resp))
             }
@@ -191,6 +275,8 @@ pub struct EthStream<S> {
     version: EthVersion,
     #[pin]
     inner: S,
+    /// Maximum sizes of received messages.
+    max_message_sizes: EthMessageSizeLimits,
 }
 
 impl<S> EthStream<S> {
@@ -198,7 +284,14 @@ impl<S> EthStream<S> {
     /// to manually handshake a peer.
     #[inline]
     pub const fn new(version: EthVersion, inner: S) -> Self {
-        Self { version, inner }
+        Self { version, inner, max_message_sizes: EthMessageSizeLimits::uniform(MAX_MESSAGE_SIZE) }
+    }
+
+    /// Sets the maximum sizes of received messages.
+    #[inline]
+    pub const fn with_max_message_sizes(mut self, max_message_sizes: EthMessageSizeLimits) -> Self {
+        self.max_message_sizes = max_message_sizes;
+        self
     }
 
     /// Returns the eth version.
@@ -260,8 +353,8 @@ where
             None => return Poll::Ready(None),
         };
 
-        if bytes.len() > MAX_MESSAGE_SIZE {
-            return Poll::Ready(Some(Err(EthStreamError::MessageTooBig(bytes.len()))))
+        if let Err(err) = this.max_message_sizes.ensure_within_limit(&bytes) {
+            return Poll::Ready(Some(Err(err)))
         }
 
         let msg = match ProtocolMessage::decode_message(*this.version, &mut bytes.as_ref()) {
@@ -344,7 +437,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::UnauthedEthStream;
+    use super::{EthMessageSizeLimits, UnauthedEthStream};
     use crate::{
         broadcast::BlockHashNumber,
         errors::{EthHandshakeError, EthStreamError},
@@ -544,6 +637,34 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn rejects_messages_exceeding_size_limit() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+        let test_msg = EthMessage::NewBlockHashes(
+            vec![BlockHashNumber { hash: B256::random(), number: 5 }].into(),
+        );
+
+        let handle = tokio::spawn(async move {
+            let (incoming, _) = listener.accept().await.unwrap();
+            let stream = PassthroughCodec::default().framed(incoming);
+            let limits = EthMessageSizeLimits { other: 16, ..Default::default() };
+            let mut stream =
+                EthStream::new(EthVersion::Eth67, stream).with_max_message_sizes(limits);
+
+            let err = stream.next().await.unwrap().unwrap_err();
+            assert!(matches!(err, EthStreamError::MessageTooBig { max_size: 16, .. }));
+        });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = PassthroughCodec::default().framed(outgoing);
+        let mut client_stream = EthStream::new(EthVersion::Eth67, sink);
+
+        client_stream.send(test_msg).await.unwrap();
+
+        handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn can_write_and_read_ecies() {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
@@ -36,11 +36,11 @@ pub use tokio_util::codec::{
 pub use crate::{
     capability::Capability,
     disconnect::{CanDisconnect, DisconnectReason},
-    ethstream::{EthStream, UnauthedEthStream, MAX_MESSAGE_SIZE},
+    ethstream::{EthMessageSizeLimits, EthStream, UnauthedEthStream, MAX_MESSAGE_SIZE},
     hello::{HelloMessage, HelloMessageBuilder, HelloMessageWithProtocols},
     p2pstream::{
         DisconnectP2P, P2PMessage, P2PMessageID, P2PStream, ProtocolVersion, UnauthedP2PStream,
-        MAX_RESERVED_MESSAGE_ID,
+        HANDSHAKE_TIMEOUT, MAX_PAYLOAD_SIZE, MAX_RESERVED_MESSAGE_ID, PING_INTERVAL, PING_TIMEOUT,
     },
 };
 
@@ -20,7 +20,8 @@ use crate::{
     capability::{Capability, SharedCapabilities, SharedCapability, UnsupportedCapabilityError},
     errors::{EthStreamError, P2PStreamError},
     p2pstream::DisconnectP2P,
-    CanDisconnect, DisconnectReason, EthStream, P2PStream, Status, UnauthedEthStream,
+    CanDisconnect, DisconnectReason, EthMessageSizeLimits, EthStream, P2PStream, Status,
+    UnauthedEthStream,
 };
 use bytes::{Bytes, BytesMut};
 use futures::{Sink, SinkExt, Stream, StreamExt, TryStream, TryStreamExt};
@@ -209,6 +210,25 @@ impl<St> RlpxProtocolMultiplexer<St> {
         status: Status,
         fork_filter: ForkFilter,
     ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
+    where
+        St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
+    {
+        self.into_eth_satellite_stream_with_max_message_sizes(
+            status,
+            fork_filter,
+            EthMessageSizeLimits::default(),
+        )
+        .await
+    }
+
+    /// Same as [`Self::into_eth_satellite_stream`], but enforces the given maximum sizes of
+    /// received eth messages.
+    pub async fn into_eth_satellite_stream_with_max_message_sizes(
+        self,
+        status: Status,
+        fork_filter: ForkFilter,
+        max_message_sizes: EthMessageSizeLimits,
+    ) -> Result<(RlpxSatelliteStream<St, EthStream<ProtocolProxy>>, Status), EthStreamError>
     where
         St: Stream<Item = io::Result<BytesMut>> + Sink<Bytes, Error = io::Error> + Unpin,
     {
@@ -216,7 +236,10 @@ impl<St> RlpxProtocolMultiplexer<St> {
         self.into_satellite_stream_with_tuple_handshake(
             &Capability::eth(eth_cap),
             move |proxy| async move {
-                UnauthedEthStream::new(proxy).handshake(status, fork_filter).await
+                UnauthedEthStream::new(proxy)
+                    .with_max_message_sizes(max_message_sizes)
+                    .handshake(status, fork_filter)
+                    .await
             },
         )
         .await
@@ -29,7 +29,7 @@ use serde::{Deserialize, Serialize};
 
 /// [`MAX_PAYLOAD_SIZE`] is the maximum size of an uncompressed message payload.
 /// This is defined in [EIP-706](https://eips.ethereum.org/EIPS/eip-706).
-const MAX_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
+pub const MAX_PAYLOAD_SIZE: usize = 16 * 1024 * 1024;
 
 /// [`MAX_RESERVED_MESSAGE_ID`] is the maximum message ID reserved for the `p2p` subprotocol. If
 /// there are any incoming messages with an ID greater than this, they are subprotocol messages.
@@ -40,15 +40,15 @@ const MAX_P2P_MESSAGE_ID: u8 = P2PMessageID::Pong as u8;
 
 /// [`HANDSHAKE_TIMEOUT`] determines the amount of time to wait before determining that a `p2p`
 /// handshake has timed out.
-pub(crate) const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+pub const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
 
 /// [`PING_TIMEOUT`] determines the amount of time to wait before determining that a `p2p` ping has
 /// timed out.
-const PING_TIMEOUT: Duration = Duration::from_secs(15);
+pub const PING_TIMEOUT: Duration = Duration::from_secs(15);
 
 /// [`PING_INTERVAL`] determines the amount of time to wait between sending `p2p` ping messages
 /// when the peer is responsive.
-const PING_INTERVAL: Duration = Duration::from_secs(60);
+pub const PING_INTERVAL: Duration = Duration::from_secs(60);
 
 /// [`MAX_P2P_CAPACITY`] is the maximum number of messages that can be buffered to be sent in the
 /// `p2p` stream.
@@ -65,12 +65,58 @@ const MAX_P2P_CAPACITY: usize = 2;
 pub struct UnauthedP2PStream<S> {
     #[pin]
     inner: S,
+    /// Maximum size of a received uncompressed message payload.
+    max_payload_size: usize,
+    /// Maximum duration of the `Hello` exchange.
+    handshake_timeout: Duration,
+    /// Interval between pings sent by the authenticated [`P2PStream`].
+    ping_interval: Duration,
+    /// Time the peer has to answer a ping sent by the authenticated [`P2PStream`].
+    ping_timeout: Duration,
 }
 
 impl<S> UnauthedP2PStream<S> {
     /// Create a new `UnauthedP2PStream` from a type `S` which implements `Stream` and `Sink`.
     pub const fn new(inner: S) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            max_payload_size: MAX_PAYLOAD_SIZE,
+            handshake_timeout: HANDSHAKE_TIMEOUT,
+            ping_interval: PING_INTERVAL,
+            ping_timeout: PING_TIMEOUT,
+        }
+    }
+
+    /// Sets the maximum size of received uncompressed message payloads, which is also applied to
+    /// the authenticated [`P2PStream`].
+    ///
+    /// Defaults to [`MAX_PAYLOAD_SIZE`].
+    pub const fn with_max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = max_payload_size;
+        self
+    }
+
+    /// Sets the maximum duration of the `Hello` exchange, which covers both sending our `Hello`
+    /// and receiving the peer's.
+    ///
+    /// Defaults to [`HANDSHAKE_TIMEOUT`].
+    pub const fn with_handshake_timeout(mut self, handshake_timeout: Duration) -> Self {
+        self.handshake_timeout = handshake_timeout;
+        self
+    }
+
+    /// Sets the interval between pings and the time the peer has to answer them, which are
+    /// applied to the authenticated [`P2PStream`].
+    ///
+    /// Defaults to [`PING_INTERVAL`] and [`PING_TIMEOUT`].
+    pub const fn with_ping_config(
+        mut self,
+        ping_interval: Duration,
+        ping_timeout: Duration,
+    ) -> Self {
+        self.ping_interval = ping_interval;
+        self.ping_timeout = ping_timeout;
+        self
     }
 
     /// Returns a reference to the inner stream.
@@ -91,20 +137,25 @@ where
     ) -> Result<(P2PStream<S>, HelloMessage), P2PStreamError> {
         trace!(?hello, "sending p2p hello to peer");
 
-        // send our hello message with the Sink
-        self.inner.send(alloy_rlp::encode(P2PMessage::Hello(hello.message())).into()).await?;
-
-        let first_message_bytes = tokio::time::timeout(HANDSHAKE_TIMEOUT, self.inner.next())
-            .await
-            .or(Err(P2PStreamError::HandshakeError(P2PHandshakeError::Timeout)))?
-            .ok_or(P2PStreamError::HandshakeError(P2PHandshakeError::NoResponse))??;
+        // Sending is covered by the timeout as well: a half-open connection, where the peer
+        // completed the ECIES handshake but stopped reading, would otherwise stall here.
+        let our_hello = alloy_rlp::encode(P2PMessage::Hello(hello.message()));
+        let inner = &mut self.inner;
+        let first_message_bytes = tokio::time::timeout(self.handshake_timeout, async move {
+            // send our hello message with the Sink
+            inner.send(our_hello.into()).await?;
+            Ok::<_, P2PStreamError>(inner.next().await)
+        })
+        .await
+        .or(Err(P2PStreamError::HandshakeError(P2PHandshakeError::Timeout)))??
+        .ok_or(P2PStreamError::HandshakeError(P2PHandshakeError::NoResponse))??;
 
         // let's check the compressed length first, we will need to check again once confirming
         // that it contains snappy-compressed data (this will be the case for all non-p2p messages).
-        if first_message_bytes.len() > MAX_PAYLOAD_SIZE {
+        if first_message_bytes.len() > self.max_payload_size {
             return Err(P2PStreamError::MessageTooBig {
                 message_size: first_message_bytes.len(),
-                max_size: MAX_PAYLOAD_SIZE,
+                max_size: self.max_payload_size,
             })
         }
 
@@ -163,7 +214,9 @@ where
             Ok(cap) => Ok(cap),
         }?;
 
-        let stream = P2PStream::new(self.inner, shared_capability);
+        let mut stream = P2PStream::new(self.inner, shared_capability);
+        stream.set_max_payload_size(self.max_payload_size);
+        stream.set_ping_config(self.ping_interval, self.ping_timeout);
 
         Ok((stream, their_hello))
     }
@@ -250,6 +303,9 @@ pub struct P2PStream<S> {
     /// Whether this stream is currently in the process of disconnecting by sending a disconnect
     /// message.
     disconnecting: bool,
+
+    /// Maximum size of a received uncompressed message payload.
+    max_payload_size: usize,
 }
 
 impl<S> P2PStream<S> {
@@ -266,6 +322,7 @@ impl<S> P2PStream<S> {
             outgoing_messages: VecDeque::new(),
             outgoing_message_buffer_capacity: MAX_P2P_CAPACITY,
             disconnecting: false,
+            max_payload_size: MAX_PAYLOAD_SIZE,
         }
     }
 
@@ -283,6 +340,21 @@ impl<S> P2PStream<S> {
         self.outgoing_message_buffer_capacity = capacity;
     }
 
+    /// Sets the maximum size of received uncompressed message payloads.
+    ///
+    /// Messages that declare a larger uncompressed size are rejected before any memory is
+    /// allocated for their decompression.
+    pub fn set_max_payload_size(&mut self, max_payload_size: usize) {
+        self.max_payload_size = max_payload_size;
+    }
+
+    /// Sets the interval between pings and the time the peer has to answer them.
+    ///
+    /// This restarts the ping interval.
+    pub fn set_ping_config(&mut self, ping_interval: Duration, ping_timeout: Duration) {
+        self.pinger = Pinger::new(ping_interval, ping_timeout);
+    }
+
     /// Returns the shared capabilities for this stream.
     ///
     /// This includes all the shared capabilities that were negotiated during the handshake and
@@ -428,13 +500,26 @@ where
                 }
             }
 
-            // first check that the compressed message length does not exceed the max
-            // payload size
+            // reject compressed payloads that can't possibly decompress to an allowed size
+            // before looking at their content, `max_compress_len` is 0 if the size is too large
+            // for snappy
+            let max_compressed_len = snap::raw::max_compress_len(this.max_payload_size);
+            if max_compressed_len != 0 && bytes.len() - 1 > max_compressed_len {
+                return Poll::Ready(Some(Err(P2PStreamError::MessageTooBig {
+                    message_size: bytes.len() - 1,
+                    max_size: this.max_payload_size,
+                })))
+            }
+
+            // check that the uncompressed length declared in the snappy header does not exceed
+            // the max payload size. The decompression below writes into a buffer of exactly the
+            // declared length and fails if the payload decompresses to anything else, so this is a
+            // hard cap on the memory a single message can make us allocate.
             let decompressed_len = snap::raw::decompress_len(&bytes[1..])?;
-            if decompressed_len > MAX_PAYLOAD_SIZE {
+            if decompressed_len > this.max_payload_size {
                 return Poll::Ready(Some(Err(P2PStreamError::MessageTooBig {
                     message_size: decompressed_len,
-                    max_size: MAX_PAYLOAD_SIZE,
+                    max_size: this.max_payload_size,
                 })))
             }
 
@@ -1016,6 +1101,29 @@ mod tests {
         handle.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_handshake_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        // accept the connection but never answer the hello
+        let handle = tokio::spawn(async move { listener.accept().await.unwrap() });
+
+        let outgoing = TcpStream::connect(local_addr).await.unwrap();
+        let sink = crate::PassthroughCodec::default().framed(outgoing);
+        let (client_hello, _) = eth_hello();
+
+        let unauthed_stream =
+            UnauthedP2PStream::new(sink).with_handshake_timeout(Duration::from_millis(100));
+        match unauthed_stream.handshake(client_hello).await {
+            Ok((_, hello)) => panic!("expected handshake to time out, got a Hello: {hello:?}"),
+            Err(P2PStreamError::HandshakeError(P2PHandshakeError::Timeout)) => {}
+            Err(other_err) => panic!("expected handshake timeout, got {other_err:?}"),
+        }
+
+        let _incoming = handle.await.unwrap();
+    }
+
     #[test]
     fn snappy_decode_encode_ping() {
         let snappy_ping = b"\x02\x01\0\xc0";
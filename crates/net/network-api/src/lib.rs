@@ -139,6 +139,11 @@ pub trait Peers: PeersInfo {
         &self,
         peer_id: PeerId,
     ) -> impl Future<Output = Result<Option<Reputation>, NetworkError>> + Send;
+
+    /// Returns all peers of the peer set that are not banned, with their reputation.
+    ///
+    /// Unlike [`Peers::get_all_peers`], this includes peers that are not connected.
+    fn get_known_peers(&self) -> impl Future<Output = Result<Vec<KnownPeer>, NetworkError>> + Send;
 }
 
 /// Represents the kind of peer
@@ -186,6 +191,19 @@ pub struct PeerInfo {
     pub session_established: Instant,
 }
 
+/// A peer of the peer set with its reputation.
+///
+/// This is the format the known peers are persisted in, so a node can be bootstrapped from the
+/// peer set of another node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KnownPeer {
+    /// The record of the peer.
+    pub record: NodeRecord,
+    /// The reputation of the peer.
+    pub reputation: Reputation,
+}
+
 /// The direction of the connection.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Direction {
@@ -4,7 +4,7 @@
 //! generic over it.
 
 use crate::{
-    NetworkError, NetworkInfo, PeerId, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
+    KnownPeer, NetworkError, NetworkInfo, PeerId, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
     ReputationChangeKind,
 };
 use enr::{secp256k1::SecretKey, Enr};
@@ -100,4 +100,8 @@ impl Peers for NoopNetwork {
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
         Ok(None)
     }
+
+    async fn get_known_peers(&self) -> Result<Vec<KnownPeer>, NetworkError> {
+        Ok(vec![])
+    }
 }
@@ -61,7 +61,8 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
         let (tx, rx) = mpsc::unbounded_channel();
         network.set_transactions(tx);
         let handle = network.handle().clone();
-        let transactions = TransactionsManager::new(handle, pool, rx, transactions_manager_config);
+        let transactions = TransactionsManager::new(handle, pool, rx, transactions_manager_config)
+            .with_request_quotas(network.eth_request_quotas());
         NetworkBuilder { network, request_handler, transactions }
     }
 
@@ -74,7 +75,8 @@ impl<C, Tx, Eth> NetworkBuilder<C, Tx, Eth> {
         let (tx, rx) = mpsc::channel(ETH_REQUEST_CHANNEL_CAPACITY);
         network.set_eth_request_handler(tx);
         let peers = network.handle().peers_handle().clone();
-        let request_handler = EthRequestHandler::new(client, peers, rx);
+        let request_handler =
+            EthRequestHandler::new(client, peers, rx).with_quotas(network.eth_request_quotas());
         NetworkBuilder { network, request_handler, transactions }
     }
 }
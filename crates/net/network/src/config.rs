@@ -2,9 +2,11 @@
 
 use crate::{
     error::NetworkError,
+    eth_requests::EthRequestQuotas,
     import::{BlockImport, ProofOfStakeBlockImport},
     peers::PeersConfig,
     session::SessionsConfig,
+    sim,
     transactions::TransactionsManagerConfig,
     NetworkHandle, NetworkManager,
 };
@@ -17,7 +19,7 @@ use reth_discv5::NetworkStackId;
 use reth_dns_discovery::DnsDiscoveryConfig;
 use reth_eth_wire::{HelloMessage, HelloMessageWithProtocols, Status};
 use reth_network_peers::{pk2id, PeerId};
-use reth_primitives::{ForkFilter, Head};
+use reth_primitives::{Bytes, ForkFilter, Head};
 use reth_provider::{BlockReader, HeaderProvider};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
 use secp256k1::SECP256K1;
@@ -29,7 +31,7 @@ pub use secp256k1::SecretKey;
 
 /// Convenience function to create a new random [`SecretKey`]
 pub fn rng_secret_key() -> SecretKey {
-    SecretKey::new(&mut rand::thread_rng())
+    sim::with_rng(|rng| SecretKey::new(rng))
 }
 
 /// All network related initialization settings.
@@ -83,6 +85,8 @@ pub struct NetworkConfig<C> {
     pub tx_gossip_disabled: bool,
     /// How to instantiate transactions manager.
     pub transactions_manager_config: TransactionsManagerConfig,
+    /// Limits for serving incoming eth requests.
+    pub eth_request_quotas: EthRequestQuotas,
 }
 
 // === impl NetworkConfig ===
@@ -180,6 +184,8 @@ pub struct NetworkConfigBuilder {
     block_import: Option<Box<dyn BlockImport>>,
     /// How to instantiate transactions manager.
     transactions_manager_config: TransactionsManagerConfig,
+    /// Limits for serving incoming eth requests.
+    eth_request_quotas: EthRequestQuotas,
 }
 
 // === impl NetworkConfigBuilder ===
@@ -206,6 +212,7 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled: false,
             block_import: None,
             transactions_manager_config: Default::default(),
+            eth_request_quotas: Default::default(),
         }
     }
 
@@ -285,6 +292,12 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Sets the limits for serving incoming eth requests.
+    pub const fn eth_request_quotas(mut self, quotas: EthRequestQuotas) -> Self {
+        self.eth_request_quotas = quotas;
+        self
+    }
+
     /// Sets the discovery and listener address
     ///
     /// This is a convenience function for both [`NetworkConfigBuilder::listener_addr`] and
@@ -447,6 +460,15 @@ impl NetworkConfigBuilder {
         self
     }
 
+    /// Advertises the topic `(key, rlp-encoded-value)` in the local discv5 node record, and only
+    /// connects to peers discovered over discv5 that advertise the same key.
+    ///
+    /// This lets chains built on reth, e.g. rollups, find their own peers instead of filtering
+    /// mainnet discovery traffic by fork id alone. Has no effect if discv5 is not enabled.
+    pub fn discv5_topic(self, key: &'static [u8], value: Bytes) -> Self {
+        self.map_discv5_config_builder(|builder| builder.topic(key, value))
+    }
+
     /// Adds a new additional protocol to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(mut self, protocol: impl IntoRlpxSubProtocol) -> Self {
         self.extra_protocols.push(protocol);
@@ -501,6 +523,7 @@ impl NetworkConfigBuilder {
             tx_gossip_disabled,
             block_import,
             transactions_manager_config,
+            eth_request_quotas,
         } = self;
 
         let listener_addr = listener_addr.unwrap_or(DEFAULT_DISCOVERY_ADDRESS);
@@ -555,6 +578,7 @@ impl NetworkConfigBuilder {
             fork_filter,
             tx_gossip_disabled,
             transactions_manager_config,
+            eth_request_quotas,
         }
     }
 }
@@ -181,6 +181,11 @@ impl Discovery {
         self.discv4.clone()
     }
 
+    /// Returns a shared reference to the discv5.
+    pub fn discv5(&self) -> Option<Discv5> {
+        self.discv5.clone()
+    }
+
     /// Returns the id with which the local node identifies itself in the network
     pub(crate) const fn local_id(&self) -> PeerId {
         self.local_enr.id // local discv4 and discv5 have same id, since signed with same secret key
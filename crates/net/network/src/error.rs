@@ -174,10 +174,12 @@ impl SessionError for EthStreamError {
                             DisconnectReason::IncompatibleP2PProtocolVersion
                         ) |
                         P2PStreamError::Disconnected(DisconnectReason::ProtocolBreach) |
-                        P2PStreamError::MismatchedProtocolVersion { .. }
+                        P2PStreamError::MismatchedProtocolVersion { .. } |
+                        P2PStreamError::MessageTooBig { .. }
                 )
             }
             Self::EthHandshakeError(err) => !matches!(err, EthHandshakeError::NoResponse),
+            Self::MessageTooBig { .. } => true,
             _ => false,
         }
     }
@@ -217,6 +219,10 @@ impl SessionError for EthStreamError {
             Self::EthHandshakeError(EthHandshakeError::NoResponse) |
             Self::P2PStreamError(P2PStreamError::HandshakeError(P2PHandshakeError::NoResponse)) |
             Self::P2PStreamError(P2PStreamError::PingTimeout) => Some(BackoffKind::Low),
+            // stalled in the hello exchange, e.g. a half-open connection
+            Self::P2PStreamError(P2PStreamError::HandshakeError(P2PHandshakeError::Timeout)) => {
+                Some(BackoffKind::Medium)
+            }
             // malformed messages
             Self::P2PStreamError(P2PStreamError::Rlp(_)) |
             Self::P2PStreamError(P2PStreamError::UnknownReservedMessageId(_)) |
@@ -296,6 +302,28 @@ mod tests {
         assert!(err.is_fatal_protocol_error());
     }
 
+    #[test]
+    fn test_stalled_hello_is_backed_off() {
+        let err = PendingSessionHandshakeError::Eth(EthStreamError::P2PStreamError(
+            P2PStreamError::HandshakeError(P2PHandshakeError::Timeout),
+        ));
+
+        assert!(!err.is_fatal_protocol_error());
+        assert_eq!(err.should_backoff(), Some(BackoffKind::Medium));
+    }
+
+    #[test]
+    fn test_oversized_message_is_fatal() {
+        let err = EthStreamError::MessageTooBig { message_size: 2, max_size: 1 };
+        assert!(err.is_fatal_protocol_error());
+
+        let err = EthStreamError::P2PStreamError(P2PStreamError::MessageTooBig {
+            message_size: 2,
+            max_size: 1,
+        });
+        assert!(err.is_fatal_protocol_error());
+    }
+
     #[test]
     fn test_should_backoff() {
         let err = EthStreamError::P2PStreamError(P2PStreamError::HandshakeError(
@@ -2,10 +2,10 @@
 
 use crate::{
     budget::DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS, metered_poll_nested_stream_with_budget,
-    metrics::EthRequestHandlerMetrics, peers::PeersHandle,
+    metrics::EthRequestHandlerMetrics, peers::PeersHandle, sim,
 };
 use alloy_rlp::Encodable;
-use futures::StreamExt;
+use futures::{stream::FuturesUnordered, StreamExt};
 use reth_eth_wire::{
     BlockBodies, BlockHeaders, GetBlockBodies, GetBlockHeaders, GetNodeData, GetReceipts, NodeData,
     Receipts,
@@ -15,10 +15,12 @@ use reth_network_peers::PeerId;
 use reth_primitives::{BlockBody, BlockHashOrNumber, Header, HeadersDirection};
 use reth_provider::{BlockReader, HeaderProvider, ReceiptProvider};
 use std::{
+    collections::VecDeque,
     future::Future,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::Receiver, oneshot};
 use tokio_stream::wrappers::ReceiverStream;
@@ -44,6 +46,58 @@ const MAX_BODIES_SERVE: usize = 1024;
 /// Maximum size of replies to data retrievals.
 const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 
+/// Default maximum number of concurrently served `GetBlockHeaders` requests.
+pub const DEFAULT_MAX_CONCURRENT_HEADERS_REQUESTS: usize = 8;
+
+/// Default maximum number of concurrently served `GetBlockBodies` requests.
+pub const DEFAULT_MAX_CONCURRENT_BODIES_REQUESTS: usize = 4;
+
+/// Default maximum number of concurrently served `GetReceipts` requests.
+pub const DEFAULT_MAX_CONCURRENT_RECEIPTS_REQUESTS: usize = 2;
+
+/// Default maximum number of concurrently served `GetPooledTransactions` requests.
+pub const DEFAULT_MAX_CONCURRENT_POOLED_TRANSACTIONS_REQUESTS: usize = 4;
+
+/// Default maximum number of requests of a type that wait to be served.
+pub const DEFAULT_MAX_QUEUED_REQUESTS: usize = 256;
+
+/// Limits for serving incoming eth requests, per request type.
+///
+/// Requests are served on blocking worker threads. Each request type has its own quota of
+/// concurrently served requests, so that expensive requests, like receipts, can't delay serving
+/// cheap ones, like headers.
+///
+/// Block requests are served by the [`EthRequestHandler`], `GetPooledTransactions` requests by the
+/// [`TransactionsManager`](crate::transactions::TransactionsManager).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EthRequestQuotas {
+    /// Maximum number of concurrently served `GetBlockHeaders` requests.
+    pub max_concurrent_headers_requests: usize,
+    /// Maximum number of concurrently served `GetBlockBodies` requests.
+    pub max_concurrent_bodies_requests: usize,
+    /// Maximum number of concurrently served `GetReceipts` requests.
+    pub max_concurrent_receipts_requests: usize,
+    /// Maximum number of concurrently served `GetPooledTransactions` requests.
+    pub max_concurrent_pooled_transactions_requests: usize,
+    /// Maximum number of requests of a type that wait to be served. Further requests of that type
+    /// are dropped.
+    pub max_queued_requests: usize,
+}
+
+impl Default for EthRequestQuotas {
+    fn default() -> Self {
+        Self {
+            max_concurrent_headers_requests: DEFAULT_MAX_CONCURRENT_HEADERS_REQUESTS,
+            max_concurrent_bodies_requests: DEFAULT_MAX_CONCURRENT_BODIES_REQUESTS,
+            max_concurrent_receipts_requests: DEFAULT_MAX_CONCURRENT_RECEIPTS_REQUESTS,
+            max_concurrent_pooled_transactions_requests:
+                DEFAULT_MAX_CONCURRENT_POOLED_TRANSACTIONS_REQUESTS,
+            max_queued_requests: DEFAULT_MAX_QUEUED_REQUESTS,
+        }
+    }
+}
+
 /// Manages eth related requests on top of the p2p network.
 ///
 /// This can be spawned to another task and is supposed to be run as background service.
@@ -51,13 +105,23 @@ const SOFT_RESPONSE_LIMIT: usize = 2 * 1024 * 1024;
 #[must_use = "Manager does nothing unless polled."]
 pub struct EthRequestHandler<C> {
     /// The client type that can interact with the chain.
-    client: C,
+    client: Arc<C>,
     /// Used for reporting peers.
     // TODO use to report spammers
     #[allow(dead_code)]
     peers: PeersHandle,
     /// Incoming request from the [`NetworkManager`](crate::NetworkManager).
     incoming_requests: ReceiverStream<IncomingEthRequest>,
+    /// Limits for serving requests.
+    quotas: EthRequestQuotas,
+    /// `GetBlockHeaders` requests waiting to be served.
+    headers_requests: RequestQueue,
+    /// `GetBlockBodies` requests waiting to be served.
+    bodies_requests: RequestQueue,
+    /// `GetReceipts` requests waiting to be served.
+    receipts_requests: RequestQueue,
+    /// Requests that are currently being served, resolving to their type once done.
+    workers: FuturesUnordered<Pin<Box<dyn Future<Output = RequestKind> + Send>>>,
     /// Metrics for the eth request handler.
     metrics: EthRequestHandlerMetrics,
 }
@@ -67,164 +131,111 @@ impl<C> EthRequestHandler<C> {
     /// Create a new instance
     pub fn new(client: C, peers: PeersHandle, incoming: Receiver<IncomingEthRequest>) -> Self {
         let metrics = Default::default();
-        Self { client, peers, incoming_requests: ReceiverStream::new(incoming), metrics }
-    }
-}
-
-impl<C> EthRequestHandler<C>
-where
-    C: BlockReader + HeaderProvider + ReceiptProvider,
-{
-    /// Returns the list of requested headers
-    fn get_headers_response(&self, request: GetBlockHeaders) -> Vec<Header> {
-        let GetBlockHeaders { start_block, limit, skip, direction } = request;
-
-        let mut headers = Vec::new();
-
-        let mut block: BlockHashOrNumber = match start_block {
-            BlockHashOrNumber::Hash(start) => start.into(),
-            BlockHashOrNumber::Number(num) => {
-                let Some(hash) = self.client.block_hash(num).unwrap_or_default() else {
-                    return headers
-                };
-                hash.into()
-            }
-        };
-
-        let skip = skip as u64;
-        let mut total_bytes = 0;
-
-        for _ in 0..limit {
-            if let Some(header) = self.client.header_by_hash_or_number(block).unwrap_or_default() {
-                match direction {
-                    HeadersDirection::Rising => {
-                        if let Some(next) = (header.number + 1).checked_add(skip) {
-                            block = next.into()
-                        } else {
-                            break
-                        }
-                    }
-                    HeadersDirection::Falling => {
-                        if skip > 0 {
-                            // prevent under flows for block.number == 0 and `block.number - skip <
-                            // 0`
-                            if let Some(next) =
-                                header.number.checked_sub(1).and_then(|num| num.checked_sub(skip))
-                            {
-                                block = next.into()
-                            } else {
-                                break
-                            }
-                        } else {
-                            block = header.parent_hash.into()
-                        }
-                    }
-                }
-
-                total_bytes += header.length();
-                headers.push(header);
-
-                if headers.len() >= MAX_HEADERS_SERVE {
-                    break
-                }
-
-                if total_bytes > SOFT_RESPONSE_LIMIT {
-                    break
-                }
-            } else {
-                break
-            }
+        Self {
+            client: Arc::new(client),
+            peers,
+            incoming_requests: ReceiverStream::new(incoming),
+            quotas: Default::default(),
+            headers_requests: Default::default(),
+            bodies_requests: Default::default(),
+            receipts_requests: Default::default(),
+            workers: Default::default(),
+            metrics,
         }
-
-        headers
     }
 
-    fn on_headers_request(
-        &self,
-        _peer_id: PeerId,
-        request: GetBlockHeaders,
-        response: oneshot::Sender<RequestResult<BlockHeaders>>,
-    ) {
-        self.metrics.eth_headers_requests_received_total.increment(1);
-        let headers = self.get_headers_response(request);
-        let _ = response.send(Ok(BlockHeaders(headers)));
+    /// Sets the limits for serving requests.
+    pub const fn with_quotas(mut self, quotas: EthRequestQuotas) -> Self {
+        self.quotas = quotas;
+        self
     }
 
-    fn on_bodies_request(
-        &self,
-        _peer_id: PeerId,
-        request: GetBlockBodies,
-        response: oneshot::Sender<RequestResult<BlockBodies>>,
-    ) {
-        self.metrics.eth_bodies_requests_received_total.increment(1);
-        let mut bodies = Vec::new();
-
-        let mut total_bytes = 0;
-
-        for hash in request.0 {
-            if let Some(block) = self.client.block_by_hash(hash).unwrap_or_default() {
-                let body = BlockBody {
-                    transactions: block.body,
-                    ommers: block.ommers,
-                    withdrawals: block.withdrawals,
-                    requests: block.requests,
-                };
-
-                total_bytes += body.length();
-                bodies.push(body);
-
-                if bodies.len() >= MAX_BODIES_SERVE {
-                    break
-                }
-
-                if total_bytes > SOFT_RESPONSE_LIMIT {
-                    break
-                }
-            } else {
-                break
+    /// Queues an incoming request, or drops it if too many requests of its type are queued.
+    fn on_incoming_request(&mut self, request: IncomingEthRequest) {
+        let queue = match &request {
+            IncomingEthRequest::GetBlockHeaders { .. } => {
+                self.metrics.eth_headers_requests_received_total.increment(1);
+                &mut self.headers_requests
             }
-        }
+            IncomingEthRequest::GetBlockBodies { .. } => {
+                self.metrics.eth_bodies_requests_received_total.increment(1);
+                &mut self.bodies_requests
+            }
+            IncomingEthRequest::GetNodeData { .. } => {
+                self.metrics.eth_node_data_requests_received_total.increment(1);
+                return
+            }
+            IncomingEthRequest::GetReceipts { .. } => {
+                self.metrics.eth_receipts_requests_received_total.increment(1);
+                &mut self.receipts_requests
+            }
+        };
 
-        let _ = response.send(Ok(BlockBodies(bodies)));
+        if queue.queued.len() >= self.quotas.max_queued_requests {
+            // dropping the response channel lets the peer's request time out
+            self.metrics.eth_requests_dropped_total.increment(1);
+            return
+        }
+        queue.queued.push_back((sim::now(), request));
     }
 
-    fn on_receipts_request(
-        &self,
-        _peer_id: PeerId,
-        request: GetReceipts,
-        response: oneshot::Sender<RequestResult<Receipts>>,
-    ) {
-        self.metrics.eth_receipts_requests_received_total.increment(1);
-
-        let mut receipts = Vec::new();
-
-        let mut total_bytes = 0;
-
-        for hash in request.0 {
-            if let Some(receipts_by_block) =
-                self.client.receipts_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
-            {
-                let receipt = receipts_by_block
-                    .into_iter()
-                    .map(|receipt| receipt.with_bloom())
-                    .collect::<Vec<_>>();
-
-                total_bytes += receipt.length();
-                receipts.push(receipt);
-
-                if receipts.len() >= MAX_RECEIPTS_SERVE {
-                    break
-                }
+    /// Marks a request of the given type as served.
+    fn on_request_served(&mut self, kind: RequestKind) {
+        let queue = match kind {
+            RequestKind::Headers => &mut self.headers_requests,
+            RequestKind::Bodies => &mut self.bodies_requests,
+            RequestKind::Receipts => &mut self.receipts_requests,
+        };
+        queue.in_flight -= 1;
+    }
+}
 
-                if total_bytes > SOFT_RESPONSE_LIMIT {
-                    break
-                }
-            } else {
-                break
+impl<C> EthRequestHandler<C>
+where
+    C: BlockReader + 'static,
+{
+    /// Starts serving queued requests, as long as their type's quota allows it.
+    ///
+    /// Returns `true` if any request was dispatched.
+    fn dispatch_queued_requests(&mut self) -> bool {
+        let mut dispatched = false;
+        for (kind, max_concurrent) in [
+            (RequestKind::Headers, self.quotas.max_concurrent_headers_requests),
+            (RequestKind::Bodies, self.quotas.max_concurrent_bodies_requests),
+            (RequestKind::Receipts, self.quotas.max_concurrent_receipts_requests),
+        ] {
+            let (queue, queue_duration) = match kind {
+                RequestKind::Headers => (
+                    &mut self.headers_requests,
+                    &self.metrics.eth_headers_requests_queue_duration_seconds,
+                ),
+                RequestKind::Bodies => (
+                    &mut self.bodies_requests,
+                    &self.metrics.eth_bodies_requests_queue_duration_seconds,
+                ),
+                RequestKind::Receipts => (
+                    &mut self.receipts_requests,
+                    &self.metrics.eth_receipts_requests_queue_duration_seconds,
+                ),
+            };
+
+            while queue.in_flight < max_concurrent {
+                let Some((queued_at, request)) = queue.queued.pop_front() else { break };
+                queue_duration.record(queued_at.elapsed().as_secs_f64());
+                queue.in_flight += 1;
+                dispatched = true;
+
+                let client = Arc::clone(&self.client);
+                let worker =
+                    tokio::task::spawn_blocking(move || serve_request(client.as_ref(), request));
+                self.workers.push(Box::pin(async move {
+                    // a panicking worker has dropped the response channel already
+                    let _ = worker.await;
+                    kind
+                }));
             }
         }
-
-        let _ = response.send(Ok(Receipts(receipts)));
+        dispatched
     }
 }
 
@@ -233,7 +244,7 @@ where
 /// This should be spawned or used as part of `tokio::select!`.
 impl<C> Future for EthRequestHandler<C>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + Unpin + 'static,
 {
     type Output = ();
 
@@ -247,24 +258,22 @@ where
             "Incoming eth requests stream",
             DEFAULT_BUDGET_TRY_DRAIN_DOWNLOADERS,
             this.incoming_requests.poll_next_unpin(cx),
-            |incoming| {
-                match incoming {
-                    IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
-                        this.on_headers_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
-                        this.on_bodies_request(peer_id, request, response)
-                    }
-                    IncomingEthRequest::GetNodeData { .. } => {
-                        this.metrics.eth_node_data_requests_received_total.increment(1);
-                    }
-                    IncomingEthRequest::GetReceipts { peer_id, request, response } => {
-                        this.on_receipts_request(peer_id, request, response)
-                    }
-                }
-            },
+            |incoming| this.on_incoming_request(incoming),
         );
 
+        loop {
+            // free the quotas of served requests
+            while let Poll::Ready(Some(kind)) = this.workers.poll_next_unpin(cx) {
+                this.on_request_served(kind);
+            }
+
+            // newly dispatched workers are polled on the next iteration, so that they wake the
+            // handler once they're done
+            if !this.dispatch_queued_requests() {
+                break
+            }
+        }
+
         this.metrics.acc_duration_poll_eth_req_handler.set(acc.as_secs_f64());
 
         // stream is fully drained and import futures pending
@@ -278,6 +287,186 @@ where
     }
 }
 
+/// The type of a served request, each type has its own quota.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RequestKind {
+    Headers,
+    Bodies,
+    Receipts,
+}
+
+/// Requests of a single type that wait to be served.
+#[derive(Debug, Default)]
+struct RequestQueue {
+    /// Queued requests with the time they were received.
+    queued: VecDeque<(Instant, IncomingEthRequest)>,
+    /// Number of requests that are currently being served.
+    in_flight: usize,
+}
+
+/// Serves the request and sends the response. This is blocking.
+fn serve_request<C>(client: &C, request: IncomingEthRequest)
+where
+    C: BlockReader,
+{
+    match request {
+        IncomingEthRequest::GetBlockHeaders { peer_id, request, response } => {
+            on_headers_request(client, peer_id, request, response)
+        }
+        IncomingEthRequest::GetBlockBodies { peer_id, request, response } => {
+            on_bodies_request(client, peer_id, request, response)
+        }
+        IncomingEthRequest::GetReceipts { peer_id, request, response } => {
+            on_receipts_request(client, peer_id, request, response)
+        }
+        // not queued
+        IncomingEthRequest::GetNodeData { .. } => {}
+    }
+}
+
+/// Returns the list of requested headers
+fn get_headers_response<C: HeaderProvider>(client: &C, request: GetBlockHeaders) -> Vec<Header> {
+    let GetBlockHeaders { start_block, limit, skip, direction } = request;
+
+    let mut headers = Vec::new();
+
+    let mut block: BlockHashOrNumber = match start_block {
+        BlockHashOrNumber::Hash(start) => start.into(),
+        BlockHashOrNumber::Number(num) => {
+            let Some(hash) = client.block_hash(num).unwrap_or_default() else {
+                return headers
+            };
+            hash.into()
+        }
+    };
+
+    let skip = skip as u64;
+    let mut total_bytes = 0;
+
+    for _ in 0..limit {
+        if let Some(header) = client.header_by_hash_or_number(block).unwrap_or_default() {
+            match direction {
+                HeadersDirection::Rising => {
+                    if let Some(next) = (header.number + 1).checked_add(skip) {
+                        block = next.into()
+                    } else {
+                        break
+                    }
+                }
+                HeadersDirection::Falling => {
+                    if skip > 0 {
+                        // prevent under flows for block.number == 0 and `block.number - skip <
+                        // 0`
+                        if let Some(next) =
+                            header.number.checked_sub(1).and_then(|num| num.checked_sub(skip))
+                        {
+                            block = next.into()
+                        } else {
+                            break
+                        }
+                    } else {
+                        block = header.parent_hash.into()
+                    }
+                }
+            }
+
+            total_bytes += header.length();
+            headers.push(header);
+
+            if headers.len() >= MAX_HEADERS_SERVE {
+                break
+            }
+
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+        } else {
+            break
+        }
+    }
+
+    headers
+}
+
+fn on_headers_request<C: HeaderProvider>(
+    client: &C,
+    _peer_id: PeerId,
+    request: GetBlockHeaders,
+    response: oneshot::Sender<RequestResult<BlockHeaders>>,
+) {
+    let headers = get_headers_response(client, request);
+    let _ = response.send(Ok(BlockHeaders(headers)));
+}
+
+fn on_bodies_request<C: BlockReader>(
+    client: &C,
+    _peer_id: PeerId,
+    request: GetBlockBodies,
+    response: oneshot::Sender<RequestResult<BlockBodies>>,
+) {
+    let mut bodies = Vec::new();
+
+    let mut total_bytes = 0;
+
+    for hash in request.0 {
+        if let Some(block) = client.block_by_hash(hash).unwrap_or_default() {
+            let body = BlockBody {
+                transactions: block.body,
+                ommers: block.ommers,
+                withdrawals: block.withdrawals,
+                requests: block.requests,
+            };
+
+            total_bytes += body.length();
+            bodies.push(body);
+
+            if bodies.len() >= MAX_BODIES_SERVE {
+                break
+            }
+
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+        } else {
+            break
+        }
+    }
+
+    let _ = response.send(Ok(BlockBodies(bodies)));
+}
+
+fn on_receipts_request<C: ReceiptProvider>(
+    client: &C,
+    _peer_id: PeerId,
+    request: GetReceipts,
+    response: oneshot::Sender<RequestResult<Receipts>>,
+) {
+    let mut receipts = Vec::new();
+
+    let mut total_bytes = 0;
+
+    for hash in request.0 {
+        if let Some(receipt) =
+            client.receipts_with_bloom_by_block(BlockHashOrNumber::Hash(hash)).unwrap_or_default()
+        {
+            total_bytes += receipt.length();
+            receipts.push(receipt);
+
+            if receipts.len() >= MAX_RECEIPTS_SERVE {
+                break
+            }
+
+            if total_bytes > SOFT_RESPONSE_LIMIT {
+                break
+            }
+        } else {
+            break
+        }
+    }
+
+    let _ = response.send(Ok(Receipts(receipts)));
+}
+
 /// All `eth` request related to blocks delegated by the network.
 #[derive(Debug)]
 pub enum IncomingEthRequest {
@@ -326,3 +515,69 @@ pub enum IncomingEthRequest {
         response: oneshot::Sender<RequestResult<Receipts>>,
     },
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peers::PeersManager;
+    use reth_provider::test_utils::NoopProvider;
+    use tokio::sync::mpsc;
+
+    fn headers_request() -> (IncomingEthRequest, oneshot::Receiver<RequestResult<BlockHeaders>>) {
+        let (response, rx) = oneshot::channel();
+        let request = IncomingEthRequest::GetBlockHeaders {
+            peer_id: PeerId::random(),
+            request: GetBlockHeaders {
+                start_block: BlockHashOrNumber::Number(0),
+                limit: 1,
+                skip: 0,
+                direction: HeadersDirection::Rising,
+            },
+            response,
+        };
+        (request, rx)
+    }
+
+    #[test]
+    fn drop_requests_over_queue_quota() {
+        let (_tx, rx) = mpsc::channel(1);
+        let mut handler =
+            EthRequestHandler::new(NoopProvider::default(), PeersManager::default().handle(), rx)
+                .with_quotas(EthRequestQuotas { max_queued_requests: 1, ..Default::default() });
+
+        let (first, mut first_rx) = headers_request();
+        let (second, mut second_rx) = headers_request();
+        handler.on_incoming_request(first);
+        handler.on_incoming_request(second);
+
+        assert_eq!(handler.headers_requests.queued.len(), 1);
+        assert!(matches!(first_rx.try_recv(), Err(oneshot::error::TryRecvError::Empty)));
+        assert!(matches!(second_rx.try_recv(), Err(oneshot::error::TryRecvError::Closed)));
+    }
+
+    #[tokio::test]
+    async fn serve_queued_requests_once_quota_frees_up() {
+        let (tx, rx) = mpsc::channel(2);
+        let handler =
+            EthRequestHandler::new(NoopProvider::default(), PeersManager::default().handle(), rx)
+                .with_quotas(EthRequestQuotas {
+                    max_concurrent_headers_requests: 1,
+                    ..Default::default()
+                });
+        tokio::spawn(handler);
+
+        let (first, first_rx) = headers_request();
+        let (second, second_rx) = headers_request();
+        tx.send(first).await.unwrap();
+        tx.send(second).await.unwrap();
+
+        // the second request is served once the first one is done, without further requests
+        // waking the handler
+        let responses = tokio::time::timeout(Duration::from_secs(5), async {
+            (first_rx.await.unwrap(), second_rx.await.unwrap())
+        })
+        .await
+        .unwrap();
+        assert!(responses.0.is_ok() && responses.1.is_ok());
+    }
+}
@@ -100,6 +100,8 @@
 //!
 //! - `serde` (default): Enable serde support for configuration types.
 //! - `test-utils`: Various utilities helpful for writing tests
+//! - `simulation`: Replaces the sockets, the clock and the rng of the network to run it in a
+//!   deterministic [`turmoil`](https://docs.rs/turmoil) simulation, see [`sim`].
 //! - `geth-tests`: Runs tests that require Geth to be installed locally.
 
 #![doc(
@@ -132,6 +134,7 @@ mod network;
 pub mod peers;
 pub mod protocol;
 mod session;
+pub mod sim;
 mod state;
 mod swarm;
 pub mod transactions;
@@ -1,5 +1,6 @@
 //! Contains connection-oriented interfaces.
 
+use crate::sim::{TcpListener, TcpStream};
 use futures::{ready, Stream};
 use std::{
     io,
@@ -7,7 +8,6 @@ use std::{
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::net::{TcpListener, TcpStream};
 
 /// A tcp connection listener.
 ///
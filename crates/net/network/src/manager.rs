@@ -20,7 +20,7 @@ use crate::{
     config::NetworkConfig,
     discovery::Discovery,
     error::{NetworkError, ServiceKind},
-    eth_requests::IncomingEthRequest,
+    eth_requests::{EthRequestQuotas, IncomingEthRequest},
     import::{BlockImport, BlockImportOutcome, BlockValidation},
     listener::ConnectionListener,
     message::{NewBlockMessage, PeerMessage, PeerRequest, PeerRequestSender},
@@ -42,7 +42,7 @@ use reth_eth_wire::{
     DisconnectReason, EthVersion, Status,
 };
 use reth_metrics::common::mpsc::UnboundedMeteredSender;
-use reth_network_api::ReputationChangeKind;
+use reth_network_api::{KnownPeer, ReputationChangeKind};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_primitives::ForkId;
 use reth_provider::{BlockNumReader, BlockReader};
@@ -102,6 +102,8 @@ pub struct NetworkManager<C> {
     /// requests. This channel size is set at
     /// [`ETH_REQUEST_CHANNEL_CAPACITY`](crate::builder::ETH_REQUEST_CHANNEL_CAPACITY)
     to_eth_request_handler: Option<mpsc::Sender<IncomingEthRequest>>,
+    /// Limits for serving incoming eth requests, applied to the handlers of the requests.
+    eth_request_quotas: EthRequestQuotas,
     /// Tracks the number of active session (connected peers).
     ///
     /// This is updated via internal events and shared via `Arc` with the [`NetworkHandle`]
@@ -128,6 +130,11 @@ impl<C> NetworkManager<C> {
         self.to_eth_request_handler = Some(tx);
     }
 
+    /// Returns the configured limits for serving incoming eth requests.
+    pub const fn eth_request_quotas(&self) -> EthRequestQuotas {
+        self.eth_request_quotas
+    }
+
     /// Adds an additional protocol handler to the `RLPx` sub-protocol list.
     pub fn add_rlpx_sub_protocol(&mut self, protocol: impl IntoRlpxSubProtocol) {
         self.swarm.add_rlpx_sub_protocol(protocol)
@@ -189,6 +196,7 @@ where
             extra_protocols,
             tx_gossip_disabled,
             transactions_manager_config: _,
+            eth_request_quotas,
         } = config;
 
         let peers_manager = PeersManager::new(peers_config);
@@ -227,6 +235,7 @@ where
         // need to retrieve the addr here since provided port could be `0`
         let local_peer_id = discovery.local_id();
         let discv4 = discovery.discv4();
+        let discv5 = discovery.discv5();
 
         let num_active_peers = Arc::new(AtomicUsize::new(0));
 
@@ -260,6 +269,7 @@ where
             Arc::new(AtomicU64::new(chain_spec.chain.id())),
             tx_gossip_disabled,
             discv4,
+            discv5,
             event_sender.clone(),
         );
 
@@ -271,6 +281,7 @@ where
             event_sender,
             to_transactions_manager: None,
             to_eth_request_handler: None,
+            eth_request_quotas,
             num_active_peers,
             metrics: Default::default(),
             disconnect_metrics: Default::default(),
@@ -337,6 +348,12 @@ where
         self.swarm.state().peers().iter_peers()
     }
 
+    /// Returns an iterator over all peers in the peer set that are not banned, with their
+    /// reputation.
+    pub fn known_peers(&self) -> impl Iterator<Item = KnownPeer> + '_ {
+        self.swarm.state().peers().iter_known_peers()
+    }
+
     /// Returns a new [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -593,6 +610,9 @@ where
             NetworkHandleMessage::GetReputationById(peer_id, tx) => {
                 let _ = tx.send(self.swarm.state_mut().peers().get_reputation(&peer_id));
             }
+            NetworkHandleMessage::GetKnownPeers(tx) => {
+                let _ = tx.send(self.known_peers().collect());
+            }
             NetworkHandleMessage::FetchClient(tx) => {
                 let _ = tx.send(self.fetch_client());
             }
@@ -128,6 +128,13 @@ pub struct TransactionsManagerMetrics {
     /// measure.
     pub(crate) capacity_pending_pool_imports: Counter,
 
+    /* ================ SERVE REQUESTS ================ */
+    /// Total number of `GetPooledTransactions` requests dropped because too many of them were
+    /// queued.
+    pub(crate) pooled_transactions_requests_dropped_total: Counter,
+    /// Time in seconds `GetPooledTransactions` requests were queued before being served.
+    pub(crate) pooled_transactions_requests_queue_duration_seconds: Histogram,
+
     /* ================ POLL DURATION ================ */
 
     /* -- Total poll duration of `TransactionsManager` future -- */
@@ -222,6 +229,30 @@ pub struct TransactionFetcherMetrics {
     pub(crate) duration_fill_request_from_hashes_pending_fetch: Gauge,
 }
 
+/// Metrics for fetching announced blob transactions from peers.
+///
+/// Blob transactions are never broadcast in full, so they can only be pulled with
+/// [`GetPooledTransactions`](reth_eth_wire::GetPooledTransactions) requests.
+#[derive(Metrics)]
+#[metrics(scope = "network.transaction_fetcher.blobs")]
+pub struct BlobFetchMetrics {
+    /// Total number of requests that include announced blob transactions.
+    pub(crate) requests: Counter,
+    /// Total number of requests that include announced blob transactions and failed, e.g.
+    /// because they timed out.
+    pub(crate) failed_requests: Counter,
+    /// Total number of requested blob transactions that were served by the peer.
+    pub(crate) fetched_transactions: Counter,
+    /// Total number of requested blob transactions that weren't served by the peer, because the
+    /// request failed or the peer left them out of the response.
+    pub(crate) failed_transactions: Counter,
+    /// Time between sending a request that includes announced blob transactions and receiving
+    /// the response.
+    ///
+    /// Duration in seconds.
+    pub(crate) request_duration_seconds: Histogram,
+}
+
 /// Measures the duration of executing the given code block. The duration is added to the given
 /// accumulator value passed as a mutable reference.
 #[macro_export]
@@ -322,6 +353,18 @@ pub struct EthRequestHandlerMetrics {
     /// Number of `GetNodeData` requests received
     pub(crate) eth_node_data_requests_received_total: Counter,
 
+    /// Number of requests dropped because too many requests of their type were queued
+    pub(crate) eth_requests_dropped_total: Counter,
+
+    /// Time in seconds `GetBlockHeaders` requests were queued before being served
+    pub(crate) eth_headers_requests_queue_duration_seconds: Histogram,
+
+    /// Time in seconds `GetBlockBodies` requests were queued before being served
+    pub(crate) eth_bodies_requests_queue_duration_seconds: Histogram,
+
+    /// Time in seconds `GetReceipts` requests were queued before being served
+    pub(crate) eth_receipts_requests_queue_duration_seconds: Histogram,
+
     /// Duration in seconds of call to poll
     /// [`EthRequestHandler`](crate::eth_requests::EthRequestHandler).
     pub(crate) acc_duration_poll_eth_req_handler: Gauge,
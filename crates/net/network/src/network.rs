@@ -6,9 +6,10 @@ use crate::{
 use enr::Enr;
 use parking_lot::Mutex;
 use reth_discv4::Discv4;
+use reth_discv5::Discv5;
 use reth_eth_wire::{DisconnectReason, NewBlock, NewPooledTransactionHashes, SharedTransactions};
 use reth_network_api::{
-    NetworkError, NetworkInfo, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
+    KnownPeer, NetworkError, NetworkInfo, PeerInfo, PeerKind, Peers, PeersInfo, Reputation,
     ReputationChangeKind,
 };
 use reth_network_p2p::sync::{NetworkSyncUpdater, SyncState, SyncStateProvider};
@@ -55,6 +56,7 @@ impl NetworkHandle {
         chain_id: Arc<AtomicU64>,
         tx_gossip_disabled: bool,
         discv4: Option<Discv4>,
+        discv5: Option<Discv5>,
         event_sender: EventSender<NetworkEvent>,
     ) -> Self {
         let inner = NetworkInner {
@@ -70,6 +72,7 @@ impl NetworkHandle {
             chain_id,
             tx_gossip_disabled,
             discv4,
+            discv5,
             event_sender,
         };
         Self { inner: Arc::new(inner) }
@@ -80,6 +83,11 @@ impl NetworkHandle {
         &self.inner.local_peer_id
     }
 
+    /// Returns the discv5 service of the network, if discv5 discovery is enabled.
+    pub fn discv5(&self) -> Option<&Discv5> {
+        self.inner.discv5.as_ref()
+    }
+
     /// Returns the [`PeersHandle`] that can be cloned and shared.
     ///
     /// The [`PeersHandle`] can be used to interact with the network's peer set.
@@ -314,6 +322,12 @@ impl Peers for NetworkHandle {
         let _ = self.manager().send(NetworkHandleMessage::GetReputationById(peer_id, tx));
         Ok(rx.await?)
     }
+
+    async fn get_known_peers(&self) -> Result<Vec<KnownPeer>, NetworkError> {
+        let (tx, rx) = oneshot::channel();
+        let _ = self.manager().send(NetworkHandleMessage::GetKnownPeers(tx));
+        Ok(rx.await?)
+    }
 }
 
 impl NetworkInfo for NetworkHandle {
@@ -395,6 +409,8 @@ struct NetworkInner {
     tx_gossip_disabled: bool,
     /// The instance of the discv4 service
     discv4: Option<Discv4>,
+    /// The instance of the discv5 service
+    discv5: Option<Discv5>,
     /// Sender for high level network events.
     event_sender: EventSender<NetworkEvent>,
 }
@@ -470,6 +486,8 @@ pub(crate) enum NetworkHandleMessage {
     GetPeerInfosByPeerKind(PeerKind, oneshot::Sender<Vec<PeerInfo>>),
     /// Gets the reputation for a specific peer via a oneshot sender.
     GetReputationById(PeerId, oneshot::Sender<Option<Reputation>>),
+    /// Gets all peers of the peer set that are not banned, with their reputation.
+    GetKnownPeers(oneshot::Sender<Vec<KnownPeer>>),
     /// Retrieves the `TransactionsHandle` via a oneshot sender.
     GetTransactionsHandle(oneshot::Sender<Option<TransactionsHandle>>),
     /// Initiates a graceful shutdown of the network via a oneshot sender.
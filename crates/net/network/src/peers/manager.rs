@@ -8,12 +8,13 @@ use crate::{
         DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND,
     },
     session::{Direction, PendingSessionHandshakeError},
+    sim,
     swarm::NetworkConnectionState,
 };
 use futures::StreamExt;
 use reth_eth_wire::{errors::EthStreamError, DisconnectReason};
 use reth_net_banlist::BanList;
-use reth_network_api::{PeerKind, ReputationChangeKind};
+use reth_network_api::{KnownPeer, PeerKind, Reputation, ReputationChangeKind};
 use reth_network_peers::{NodeRecord, PeerId};
 use reth_primitives::ForkId;
 use std::{
@@ -88,7 +89,7 @@ impl PeersHandle {
 #[derive(Debug)]
 pub struct PeersManager {
     /// All peers known to the network
-    peers: HashMap<PeerId, Peer>,
+    peers: sim::HashMap<PeerId, Peer>,
     /// The set of trusted peer ids.
     ///
     /// This tracks peer ids that are considered trusted, but for which we don't necessarily have
@@ -141,6 +142,7 @@ impl PeersManager {
             trusted_nodes,
             trusted_nodes_only,
             basic_nodes,
+            basic_node_reputations,
             max_backoff_count,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
@@ -149,7 +151,10 @@ impl PeersManager {
         // We use half of the interval to decrease the max duration to `150%` in worst case
         let unban_interval = ban_duration.min(backoff_durations.low) / 2;
 
-        let mut peers = HashMap::with_capacity(trusted_nodes.len() + basic_nodes.len());
+        let mut peers = sim::HashMap::with_capacity_and_hasher(
+            trusted_nodes.len() + basic_nodes.len(),
+            Default::default(),
+        );
         let mut trusted_peer_ids = HashSet::with_capacity(trusted_nodes.len());
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in trusted_nodes {
@@ -158,7 +163,13 @@ impl PeersManager {
         }
 
         for NodeRecord { address, tcp_port, udp_port: _, id } in basic_nodes {
-            peers.entry(id).or_insert_with(|| Peer::new(SocketAddr::from((address, tcp_port))));
+            peers.entry(id).or_insert_with(|| {
+                let mut peer = Peer::new(SocketAddr::from((address, tcp_port)));
+                if let Some(reputation) = basic_node_reputations.get(&id) {
+                    peer.reputation = *reputation;
+                }
+                peer
+            });
         }
 
         Self {
@@ -198,6 +209,14 @@ impl PeersManager {
         self.peers.iter().map(|(peer_id, v)| NodeRecord::new(v.addr, *peer_id))
     }
 
+    /// Returns an iterator over all peers that are not banned, with their reputation.
+    pub(crate) fn iter_known_peers(&self) -> impl Iterator<Item = KnownPeer> + '_ {
+        self.peers.iter().filter(|(_, peer)| !peer.is_banned()).map(|(peer_id, peer)| KnownPeer {
+            record: NodeRecord::new(peer.addr, *peer_id),
+            reputation: peer.reputation,
+        })
+    }
+
     /// Returns an iterator over all peer ids for peers with the given kind
     pub(crate) fn peers_by_kind(&self, kind: PeerKind) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(move |(peer_id, peer)| (peer.kind == kind).then_some(*peer_id))
@@ -363,13 +382,13 @@ impl PeersManager {
             }
         }
 
-        self.ban_list.ban_peer_until(peer_id, std::time::Instant::now() + ban_duration);
+        self.ban_list.ban_peer_until(peer_id, sim::now() + ban_duration);
         self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
     }
 
     /// Bans the IP temporarily with the configured ban timeout
     fn ban_ip(&mut self, ip: IpAddr) {
-        self.ban_list.ban_ip_until(ip, std::time::Instant::now() + self.ban_duration);
+        self.ban_list.ban_ip_until(ip, sim::now() + self.ban_duration);
     }
 
     /// Temporarily puts the peer in timeout by inserting it into the backedoff peers set
@@ -877,7 +896,7 @@ impl PeersManager {
             }
 
             if self.release_interval.poll_tick(cx).is_ready() {
-                let now = std::time::Instant::now();
+                let now = sim::now();
                 let (_, unbanned_peers) = self.ban_list.evict(now);
 
                 for peer_id in unbanned_peers {
@@ -1289,6 +1308,11 @@ pub struct PeersConfig {
     /// Basic nodes to connect to.
     #[cfg_attr(feature = "serde", serde(skip))]
     pub basic_nodes: HashSet<NodeRecord>,
+    /// The initial reputations of the basic nodes, e.g. as persisted in the peers file.
+    ///
+    /// Basic nodes without a reputation start with the default reputation.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub basic_node_reputations: HashMap<PeerId, Reputation>,
     /// How long to ban bad peers.
     #[cfg_attr(feature = "serde", serde(with = "humantime_serde"))]
     pub ban_duration: Duration,
@@ -1319,6 +1343,7 @@ impl Default for PeersConfig {
             trusted_nodes: Default::default(),
             trusted_nodes_only: false,
             basic_nodes: Default::default(),
+            basic_node_reputations: Default::default(),
             max_backoff_count: 5,
         }
     }
@@ -1401,6 +1426,12 @@ impl PeersConfig {
         self
     }
 
+    /// Initial reputations of the basic nodes.
+    pub fn with_basic_node_reputations(mut self, reputations: HashMap<PeerId, Reputation>) -> Self {
+        self.basic_node_reputations = reputations;
+        self
+    }
+
     /// Configures the max allowed backoff count.
     pub const fn with_max_backoff_count(mut self, max_backoff_count: u8) -> Self {
         self.max_backoff_count = max_backoff_count;
@@ -1428,6 +1459,9 @@ impl PeersConfig {
     }
 
     /// Read from file nodes available at launch. Ignored if None.
+    ///
+    /// The file contains either plain node records or [`KnownPeer`]s, whose reputations are
+    /// restored. Peers that were persisted with a banned reputation are skipped.
     pub fn with_basic_nodes_from_file(
         self,
         optional_file: Option<impl AsRef<Path>>,
@@ -1439,8 +1473,24 @@ impl PeersConfig {
             Err(e) => Err(e)?,
         };
         info!(target: "net::peers", file = %file_path.as_ref().display(), "Loading saved peers");
-        let nodes: HashSet<NodeRecord> = serde_json::from_reader(reader)?;
-        Ok(self.with_basic_nodes(nodes))
+        let nodes: Vec<PersistedNode> = serde_json::from_reader(reader)?;
+        let mut basic_nodes = HashSet::with_capacity(nodes.len());
+        let mut reputations = HashMap::new();
+        for node in nodes {
+            match node {
+                PersistedNode::Record(record) => {
+                    basic_nodes.insert(record);
+                }
+                PersistedNode::Known(KnownPeer { record, reputation }) => {
+                    if is_banned_reputation(reputation) {
+                        continue
+                    }
+                    reputations.insert(record.id, reputation);
+                    basic_nodes.insert(record);
+                }
+            }
+        }
+        Ok(self.with_basic_nodes(basic_nodes).with_basic_node_reputations(reputations))
     }
 
     /// Returns settings for testing
@@ -1454,6 +1504,15 @@ impl PeersConfig {
     }
 }
 
+/// An entry of the peers file, which is either a plain node record, as written by older versions,
+/// or a [`KnownPeer`] with its reputation.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(untagged))]
+enum PersistedNode {
+    Record(NodeRecord),
+    Known(KnownPeer),
+}
+
 /// The durations to use when a backoff should be applied to a peer.
 ///
 /// See also [`BackoffKind`].
@@ -1492,7 +1551,7 @@ impl PeerBackoffDurations {
     pub fn backoff_until(&self, kind: BackoffKind, backoff_counter: u8) -> std::time::Instant {
         let backoff_time = self.backoff(kind);
         let backoff_time = backoff_time + backoff_time * backoff_counter as u32;
-        let now = std::time::Instant::now();
+        let now = sim::now();
         now + backoff_time.min(self.max)
     }
 
@@ -1544,7 +1603,7 @@ mod tests {
         error::BackoffKind,
         peers::{
             manager::{ConnectionInfo, PeerBackoffDurations, PeerConnectionState},
-            reputation::DEFAULT_REPUTATION,
+            reputation::{BANNED_REPUTATION, DEFAULT_REPUTATION},
             InboundConnectionError, PeerAction,
         },
         session::PendingSessionHandshakeError,
@@ -1556,7 +1615,7 @@ mod tests {
         DisconnectReason,
     };
     use reth_net_banlist::BanList;
-    use reth_network_api::{Direction, ReputationChangeKind};
+    use reth_network_api::{Direction, KnownPeer, ReputationChangeKind};
     use reth_network_peers::PeerId;
     use reth_primitives::B512;
     use std::{
@@ -2263,6 +2322,39 @@ mod tests {
         assert_eq!(peers.get_reputation(&peer), Some(0));
     }
 
+    #[tokio::test]
+    async fn test_known_peers_from_file() {
+        let socket_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(127, 0, 1, 2)), 8008);
+        let plain = NodeRecord::new(socket_addr, PeerId::random());
+        let known = NodeRecord::new(socket_addr, PeerId::random());
+        let banned = NodeRecord::new(socket_addr, PeerId::random());
+
+        // plain node records and known peers with their reputation can be mixed
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let peers = serde_json::json!([
+            plain.to_string(),
+            { "record": known.to_string(), "reputation": 1024 },
+            { "record": banned.to_string(), "reputation": BANNED_REPUTATION - 1 },
+        ]);
+        std::fs::write(file.path(), peers.to_string()).unwrap();
+
+        let config = PeersConfig::test().with_basic_nodes_from_file(Some(file.path())).unwrap();
+        let peers = PeersManager::new(config);
+        assert_eq!(peers.get_reputation(&plain.id), Some(DEFAULT_REPUTATION));
+        assert_eq!(peers.get_reputation(&known.id), Some(1024));
+        assert_eq!(peers.get_reputation(&banned.id), None);
+
+        let mut known_peers = peers.iter_known_peers().collect::<Vec<_>>();
+        known_peers.sort_by_key(|peer| peer.reputation);
+        assert_eq!(
+            known_peers,
+            vec![
+                KnownPeer { record: plain, reputation: DEFAULT_REPUTATION },
+                KnownPeer { record: known, reputation: 1024 },
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn test_remove_discovered_active() {
         let peer = PeerId::random();
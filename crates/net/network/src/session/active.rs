@@ -8,6 +8,7 @@ use crate::{
         handle::{ActiveSessionMessage, SessionCommand},
         SessionId,
     },
+    sim,
 };
 use core::sync::atomic::Ordering;
 use fnv::FnvHashMap;
@@ -93,6 +94,11 @@ pub(crate) struct ActiveSession {
     /// If an [`ActiveSession`] does not receive a response at all within this duration then it is
     /// considered a protocol violation and the session will initiate a drop.
     pub(crate) protocol_breach_request_timeout: Duration,
+    /// If set, the session is disconnected when no message was received from the peer within
+    /// this duration.
+    pub(crate) idle_timeout: Option<Duration>,
+    /// When the last message was received from the peer.
+    pub(crate) last_message_received: Instant,
     /// Used to reserve a slot to guarantee that the termination message is delivered
     pub(crate) terminate_message: Option<(PollSender<ActiveSessionMessage>, ActiveSessionMessage)>,
 }
@@ -130,7 +136,7 @@ impl ActiveSession {
                 let received = ReceivedRequest {
                     request_id,
                     rx: PeerResponse::$resp_item { response },
-                    received: Instant::now(),
+                    received: sim::now(),
                 };
                 self.received_requests_from_remote.push(received);
                 self.try_emit_request(PeerMessage::EthRequest(PeerRequest::$req_item {
@@ -150,14 +156,14 @@ impl ActiveSession {
                     match req.request {
                         RequestState::Waiting(PeerRequest::$item { response, .. }) => {
                             let _ = response.send(Ok(message));
-                            self.update_request_timeout(req.timestamp, Instant::now());
+                            self.update_request_timeout(req.timestamp, sim::now());
                         }
                         RequestState::Waiting(request) => {
                             request.send_bad_response();
                         }
                         RequestState::TimedOut => {
                             // request was already timed out internally
-                            self.update_request_timeout(req.timestamp, Instant::now());
+                            self.update_request_timeout(req.timestamp, sim::now());
                         }
                     }
                 } else {
@@ -241,7 +247,7 @@ impl ActiveSession {
         self.queued_outgoing.push_back(msg.into());
         let req = InflightRequest {
             request: RequestState::Waiting(request),
-            timestamp: Instant::now(),
+            timestamp: sim::now(),
             deadline,
         };
         self.inflight_requests.insert(request_id, req);
@@ -279,7 +285,7 @@ impl ActiveSession {
 
     /// Returns the deadline timestamp at which the request times out
     fn request_deadline(&self) -> Instant {
-        Instant::now() +
+        sim::now() +
             Duration::from_millis(self.internal_request_timeout.load(Ordering::Relaxed))
     }
 
@@ -437,6 +443,11 @@ impl ActiveSession {
         false
     }
 
+    /// Returns `true` if no message was received from the peer within the idle timeout.
+    fn is_idle(&self, now: Instant) -> bool {
+        self.idle_timeout.is_some_and(|timeout| now - self.last_message_received > timeout)
+    }
+
     /// Updates the request timeout with a request's timestamps
     fn update_request_timeout(&mut self, sent: Instant, received: Instant) {
         let elapsed = received.saturating_duration_since(sent);
@@ -606,6 +617,7 @@ impl Future for ActiveSession {
                         match res {
                             Ok(msg) => {
                                 trace!(target: "net::session", msg_id=?msg.message_id(), remote_peer_id=?this.remote_peer_id, "received eth message");
+                                this.last_message_received = sim::now();
                                 // decode and handle message
                                 match this.on_incoming_message(msg) {
                                     OnIncomingMessageOutcome::Ok => {
@@ -638,8 +650,15 @@ impl Future for ActiveSession {
         }
 
         while this.internal_request_timeout_interval.poll_tick(cx).is_ready() {
+            let now = sim::now();
+
+            if this.is_idle(now) {
+                debug!(target: "net::session", remote_peer_id=?this.remote_peer_id, "disconnecting idle session");
+                return this.try_disconnect(DisconnectReason::DisconnectRequested, cx)
+            }
+
             // check for timed out requests
-            if this.check_timed_out_requests(Instant::now()) {
+            if this.check_timed_out_requests(now) {
                 if let Poll::Ready(Ok(_)) = this.to_session_manager.poll_reserve(cx) {
                     let msg = ActiveSessionMessage::ProtocolBreach { peer_id: this.remote_peer_id };
                     this.pending_message_to_session = Some(msg);
@@ -766,8 +785,9 @@ mod tests {
     use reth_chainspec::MAINNET;
     use reth_ecies::stream::ECIESStream;
     use reth_eth_wire::{
-        EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream, Status, StatusBuilder,
-        UnauthedEthStream, UnauthedP2PStream,
+        EthMessageSizeLimits, EthStream, GetBlockBodies, HelloMessageWithProtocols, P2PStream,
+        Status, StatusBuilder, UnauthedEthStream, UnauthedP2PStream, HANDSHAKE_TIMEOUT,
+        MAX_PAYLOAD_SIZE, PING_INTERVAL, PING_TIMEOUT,
     };
     use reth_network_peers::pk2id;
     use reth_primitives::{ForkFilter, Hardfork};
@@ -849,6 +869,11 @@ mod tests {
                 self.status,
                 self.fork_filter.clone(),
                 Default::default(),
+                MAX_PAYLOAD_SIZE,
+                HANDSHAKE_TIMEOUT,
+                PING_INTERVAL,
+                PING_TIMEOUT,
+                EthMessageSizeLimits::default(),
             ));
 
             let mut stream = ReceiverStream::new(pending_sessions_rx);
@@ -892,6 +917,8 @@ mod tests {
                             INITIAL_REQUEST_TIMEOUT.as_millis() as u64,
                         )),
                         protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
+                        idle_timeout: None,
+                        last_message_received: Instant::now(),
                         terminate_message: None,
                     }
                 }
@@ -1054,6 +1081,38 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_idle_session_disconnect() {
+        reth_tracing::init_test_tracing();
+
+        let mut builder = SessionBuilder::default();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let local_addr = listener.local_addr().unwrap();
+
+        let idle_timeout = Duration::from_millis(100);
+
+        // the client never sends a message
+        let fut = builder.with_client_stream(local_addr, move |client_stream| async move {
+            let _client_stream = client_stream;
+            tokio::time::sleep(idle_timeout * 600).await;
+        });
+        tokio::task::spawn(fut);
+
+        let (incoming, _) = listener.accept().await.unwrap();
+        let mut session = builder.connect_incoming(incoming).await;
+        session.idle_timeout = Some(idle_timeout);
+        session.internal_request_timeout_interval =
+            tokio::time::interval_at(tokio::time::Instant::now() + idle_timeout * 2, idle_timeout);
+        tokio::spawn(session);
+
+        let msg = builder.active_session_rx.next().await.unwrap();
+        match msg {
+            ActiveSessionMessage::Disconnected { .. } => {}
+            ev => unreachable!("{ev:?}"),
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_keep_alive() {
         let mut builder = SessionBuilder::default();
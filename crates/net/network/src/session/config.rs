@@ -4,6 +4,9 @@ use crate::{
     peers::{DEFAULT_MAX_COUNT_PEERS_INBOUND, DEFAULT_MAX_COUNT_PEERS_OUTBOUND},
     session::{Direction, ExceedsSessionLimit},
 };
+use reth_eth_wire::{
+    EthMessageSizeLimits, HANDSHAKE_TIMEOUT, MAX_PAYLOAD_SIZE, PING_INTERVAL, PING_TIMEOUT,
+};
 use std::time::Duration;
 
 /// Default request timeout for a single request.
@@ -53,6 +56,28 @@ pub struct SessionsConfig {
     pub protocol_breach_request_timeout: Duration,
     /// The timeout after which a pending session attempt is considered failed.
     pub pending_session_timeout: Duration,
+    /// The timeout for the `Hello` exchange, which starts once the ECIES handshake completed.
+    ///
+    /// Peers that stall in the `Hello` exchange, for example because the connection is half-open,
+    /// are disconnected and backed off once it elapses.
+    pub hello_timeout: Duration,
+    /// The interval at which pings are sent to the peer of an active session.
+    pub ping_interval: Duration,
+    /// The time the peer has to answer a ping, before the session is dropped.
+    pub ping_timeout: Duration,
+    /// The time after which an active session that didn't receive any message from the peer is
+    /// disconnected.
+    ///
+    /// Pings and pongs don't count as messages. By default, idle sessions are kept.
+    pub idle_session_timeout: Option<Duration>,
+    /// Maximum size of received uncompressed `RLPx` message payloads.
+    ///
+    /// Peers sending larger messages are disconnected and banned.
+    pub max_payload_size: usize,
+    /// Maximum sizes of received eth messages, per message type.
+    ///
+    /// Peers sending larger messages are disconnected and banned.
+    pub max_eth_message_sizes: EthMessageSizeLimits,
 }
 
 impl Default for SessionsConfig {
@@ -72,6 +97,12 @@ impl Default for SessionsConfig {
             initial_internal_request_timeout: INITIAL_REQUEST_TIMEOUT,
             protocol_breach_request_timeout: PROTOCOL_BREACH_REQUEST_TIMEOUT,
             pending_session_timeout: PENDING_SESSION_TIMEOUT,
+            hello_timeout: HANDSHAKE_TIMEOUT,
+            ping_interval: PING_INTERVAL,
+            ping_timeout: PING_TIMEOUT,
+            idle_session_timeout: None,
+            max_payload_size: MAX_PAYLOAD_SIZE,
+            max_eth_message_sizes: EthMessageSizeLimits::default(),
         }
     }
 }
@@ -87,6 +118,38 @@ impl SessionsConfig {
         self
     }
 
+    /// Sets the timeout for the `Hello` exchange, which starts once the ECIES handshake completed.
+    pub const fn with_hello_timeout(mut self, hello_timeout: Duration) -> Self {
+        self.hello_timeout = hello_timeout;
+        self
+    }
+
+    /// Sets the interval at which pings are sent to peers and the time they have to answer them.
+    pub const fn with_ping_interval(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.ping_interval = interval;
+        self.ping_timeout = timeout;
+        self
+    }
+
+    /// Sets the time after which an active session that didn't receive any message from the peer
+    /// is disconnected.
+    pub const fn with_idle_session_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.idle_session_timeout = timeout;
+        self
+    }
+
+    /// Sets the maximum size of received uncompressed `RLPx` message payloads.
+    pub const fn with_max_payload_size(mut self, max_payload_size: usize) -> Self {
+        self.max_payload_size = max_payload_size;
+        self
+    }
+
+    /// Sets the maximum sizes of received eth messages, per message type.
+    pub const fn with_max_eth_message_sizes(mut self, sizes: EthMessageSizeLimits) -> Self {
+        self.max_eth_message_sizes = sizes;
+        self
+    }
+
     /// Helper function to set the buffer size for the bounded communication channel between the
     /// manager and its sessions for events emitted by the sessions.
     ///
@@ -1,5 +1,6 @@
 //! Connection types for a session
 
+use crate::sim::TcpStream;
 use futures::{Sink, Stream};
 use reth_ecies::stream::ECIESStream;
 use reth_eth_wire::{
@@ -12,7 +13,6 @@ use std::{
     pin::Pin,
     task::{Context, Poll},
 };
-use tokio::net::TcpStream;
 
 /// The type of the underlying peer network connection.
 pub type EthPeerConnection = EthStream<P2PStream<ECIESStream<TcpStream>>>;
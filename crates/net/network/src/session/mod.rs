@@ -4,6 +4,7 @@ use crate::{
     message::PeerMessage,
     metrics::SessionManagerMetrics,
     session::{active::ActiveSession, config::SessionCounter},
+    sim::{self, TcpStream},
 };
 use fnv::FnvHashMap;
 use futures::{future::Either, io, FutureExt, StreamExt};
@@ -11,8 +12,8 @@ use reth_ecies::{stream::ECIESStream, ECIESError};
 use reth_eth_wire::{
     capability::{Capabilities, CapabilityMessage},
     errors::EthStreamError,
-    DisconnectReason, EthVersion, HelloMessageWithProtocols, Status, UnauthedEthStream,
-    UnauthedP2PStream,
+    DisconnectReason, EthMessageSizeLimits, EthVersion, HelloMessageWithProtocols, Status,
+    UnauthedEthStream, UnauthedP2PStream,
 };
 use reth_metrics::common::mpsc::MeteredPollSender;
 use reth_network_peers::PeerId;
@@ -25,11 +26,10 @@ use std::{
     net::SocketAddr,
     sync::{atomic::AtomicU64, Arc},
     task::{Context, Poll},
-    time::{Duration, Instant},
+    time::Duration,
 };
 use tokio::{
     io::{AsyncRead, AsyncWrite},
-    net::TcpStream,
     sync::{mpsc, oneshot},
 };
 use tokio_stream::wrappers::ReceiverStream;
@@ -80,6 +80,18 @@ pub struct SessionManager {
     fork_filter: ForkFilter,
     /// Size of the command buffer per session.
     session_command_buffer: usize,
+    /// Maximum size of received uncompressed `RLPx` message payloads.
+    max_payload_size: usize,
+    /// The timeout for the `Hello` exchange of a pending session.
+    hello_timeout: Duration,
+    /// The interval at which pings are sent to the peer of an active session.
+    ping_interval: Duration,
+    /// The time the peer has to answer a ping.
+    ping_timeout: Duration,
+    /// The time after which an active session that didn't receive any message is disconnected.
+    idle_session_timeout: Option<Duration>,
+    /// Maximum sizes of received eth messages, per message type.
+    max_eth_message_sizes: EthMessageSizeLimits,
     /// The executor for spawned tasks.
     executor: Box<dyn TaskSpawner>,
     /// All pending session that are currently handshaking, exchanging `Hello`s.
@@ -138,6 +150,12 @@ impl SessionManager {
             hello_message,
             fork_filter,
             session_command_buffer: config.session_command_buffer,
+            max_payload_size: config.max_payload_size,
+            hello_timeout: config.hello_timeout,
+            ping_interval: config.ping_interval,
+            ping_timeout: config.ping_timeout,
+            idle_session_timeout: config.idle_session_timeout,
+            max_eth_message_sizes: config.max_eth_message_sizes,
             executor,
             pending_sessions: Default::default(),
             active_sessions: Default::default(),
@@ -237,6 +255,11 @@ impl SessionManager {
         let status = self.status;
         let fork_filter = self.fork_filter.clone();
         let extra_handlers = self.extra_protocols.on_incoming(remote_addr);
+        let max_payload_size = self.max_payload_size;
+        let hello_timeout = self.hello_timeout;
+        let ping_interval = self.ping_interval;
+        let ping_timeout = self.ping_timeout;
+        let max_eth_message_sizes = self.max_eth_message_sizes;
         self.spawn(pending_session_with_timeout(
             self.pending_session_timeout,
             session_id,
@@ -254,6 +277,11 @@ impl SessionManager {
                 status,
                 fork_filter,
                 extra_handlers,
+                max_payload_size,
+                hello_timeout,
+                ping_interval,
+                ping_timeout,
+                max_eth_message_sizes,
             ),
         ));
 
@@ -278,6 +306,11 @@ impl SessionManager {
             let fork_filter = self.fork_filter.clone();
             let status = self.status;
             let extra_handlers = self.extra_protocols.on_outgoing(remote_addr, remote_peer_id);
+            let max_payload_size = self.max_payload_size;
+            let hello_timeout = self.hello_timeout;
+            let ping_interval = self.ping_interval;
+            let ping_timeout = self.ping_timeout;
+            let max_eth_message_sizes = self.max_eth_message_sizes;
             self.spawn(pending_session_with_timeout(
                 self.pending_session_timeout,
                 session_id,
@@ -295,6 +328,11 @@ impl SessionManager {
                     status,
                     fork_filter,
                     extra_handlers,
+                    max_payload_size,
+                    hello_timeout,
+                    ping_interval,
+                    ping_timeout,
+                    max_eth_message_sizes,
                 ),
             ));
 
@@ -482,6 +520,8 @@ impl SessionManager {
                     ),
                     internal_request_timeout: Arc::clone(&timeout),
                     protocol_breach_request_timeout: self.protocol_breach_request_timeout,
+                    idle_timeout: self.idle_session_timeout,
+                    last_message_received: sim::now(),
                     terminate_message: None,
                 };
 
@@ -494,7 +534,7 @@ impl SessionManager {
                     session_id,
                     remote_id: peer_id,
                     version,
-                    established: Instant::now(),
+                    established: sim::now(),
                     capabilities: Arc::clone(&capabilities),
                     commands_to_session,
                     client_version: Arc::clone(&client_version),
@@ -794,6 +834,11 @@ pub(crate) async fn start_pending_incoming_session(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    max_payload_size: usize,
+    hello_timeout: Duration,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+    max_eth_message_sizes: EthMessageSizeLimits,
 ) {
     authenticate(
         disconnect_rx,
@@ -807,6 +852,11 @@ pub(crate) async fn start_pending_incoming_session(
         status,
         fork_filter,
         extra_handlers,
+        max_payload_size,
+        hello_timeout,
+        ping_interval,
+        ping_timeout,
+        max_eth_message_sizes,
     )
     .await
 }
@@ -825,6 +875,11 @@ async fn start_pending_outbound_session(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    max_payload_size: usize,
+    hello_timeout: Duration,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+    max_eth_message_sizes: EthMessageSizeLimits,
 ) {
     let stream = match TcpStream::connect(remote_addr).await {
         Ok(stream) => {
@@ -857,6 +912,11 @@ async fn start_pending_outbound_session(
         status,
         fork_filter,
         extra_handlers,
+        max_payload_size,
+        hello_timeout,
+        ping_interval,
+        ping_timeout,
+        max_eth_message_sizes,
     )
     .await
 }
@@ -875,6 +935,11 @@ async fn authenticate(
     status: Status,
     fork_filter: ForkFilter,
     extra_handlers: RlpxSubProtocolHandlers,
+    max_payload_size: usize,
+    hello_timeout: Duration,
+    ping_interval: Duration,
+    ping_timeout: Duration,
+    max_eth_message_sizes: EthMessageSizeLimits,
 ) {
     let local_addr = stream.local_addr().ok();
     let stream = match get_eciess_stream(stream, secret_key, direction).await {
@@ -892,7 +957,10 @@ async fn authenticate(
         }
     };
 
-    let unauthed = UnauthedP2PStream::new(stream);
+    let unauthed = UnauthedP2PStream::new(stream)
+        .with_max_payload_size(max_payload_size)
+        .with_handshake_timeout(hello_timeout)
+        .with_ping_config(ping_interval, ping_timeout);
 
     let auth = authenticate_stream(
         unauthed,
@@ -904,6 +972,7 @@ async fn authenticate(
         status,
         fork_filter,
         extra_handlers,
+        max_eth_message_sizes,
     )
     .boxed();
 
@@ -956,6 +1025,7 @@ async fn authenticate_stream(
     mut status: Status,
     fork_filter: ForkFilter,
     mut extra_handlers: RlpxSubProtocolHandlers,
+    max_eth_message_sizes: EthMessageSizeLimits,
 ) -> PendingSessionEvent {
     // Add extra protocols to the hello message
     extra_handlers.retain(|handler| hello.try_add_protocol(handler.protocol()).is_ok());
@@ -991,7 +1061,8 @@ async fn authenticate_stream(
         //
         // Before trying status handshake, set up the version to negotiated shared version
         status.set_eth_version(eth_version);
-        let eth_unauthed = UnauthedEthStream::new(p2p_stream);
+        let eth_unauthed =
+            UnauthedEthStream::new(p2p_stream).with_max_message_sizes(max_eth_message_sizes);
         let (eth_stream, their_status) = match eth_unauthed.handshake(status, fork_filter).await {
             Ok(stream_res) => stream_res,
             Err(err) => {
@@ -1007,7 +1078,11 @@ async fn authenticate_stream(
     } else {
         // Multiplex the stream with the extra protocols
         let (mut multiplex_stream, their_status) = RlpxProtocolMultiplexer::new(p2p_stream)
-            .into_eth_satellite_stream(status, fork_filter)
+            .into_eth_satellite_stream_with_max_message_sizes(
+                status,
+                fork_filter,
+                max_eth_message_sizes,
+            )
             .await
             .unwrap();
 
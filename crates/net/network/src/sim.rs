@@ -0,0 +1,131 @@
+//! Sockets, time and randomness the network depends on.
+//!
+//! By default these are the tokio sockets, the system clock and the thread local rng. With the
+//! `simulation` feature they're replaced by the [`turmoil`](https://docs.rs/turmoil) sockets, the
+//! virtual clock of the runtime and a seeded rng, so the network can be run in a deterministic
+//! simulation.
+//!
+//! In a simulation, all nodes must be spawned as turmoil hosts and discovery must be disabled,
+//! since the discovery services use their own UDP sockets.
+
+use rand::RngCore;
+use std::time::Instant;
+
+#[cfg(not(feature = "simulation"))]
+pub use tokio::net::{TcpListener, TcpStream};
+
+#[cfg(feature = "simulation")]
+pub use simulated::{set_rng_seed, TcpListener};
+#[cfg(feature = "simulation")]
+pub use turmoil::net::TcpStream;
+
+/// A [`HashMap`](std::collections::HashMap) that iterates in a deterministic order in
+/// simulations.
+#[cfg(not(feature = "simulation"))]
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V>;
+
+/// A [`HashMap`](std::collections::HashMap) that iterates in a deterministic order in
+/// simulations.
+#[cfg(feature = "simulation")]
+pub(crate) type HashMap<K, V> = std::collections::HashMap<K, V, fnv::FnvBuildHasher>;
+
+/// Returns the current time.
+///
+/// In simulations this is the virtual time of the runtime.
+pub(crate) fn now() -> Instant {
+    #[cfg(feature = "simulation")]
+    {
+        tokio::time::Instant::now().into_std()
+    }
+    #[cfg(not(feature = "simulation"))]
+    {
+        Instant::now()
+    }
+}
+
+/// Calls the closure with the rng of the network.
+///
+/// In simulations the rng is seeded with [`set_rng_seed`].
+pub(crate) fn with_rng<R>(f: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+    #[cfg(feature = "simulation")]
+    {
+        simulated::RNG.with(|rng| f(&mut *rng.borrow_mut()))
+    }
+    #[cfg(not(feature = "simulation"))]
+    {
+        f(&mut rand::thread_rng())
+    }
+}
+
+#[cfg(feature = "simulation")]
+mod simulated {
+    use super::TcpStream;
+    use futures::{future::BoxFuture, ready, FutureExt};
+    use rand::{rngs::StdRng, SeedableRng};
+    use std::{
+        cell::RefCell,
+        fmt, io,
+        net::SocketAddr,
+        sync::Arc,
+        task::{Context, Poll},
+    };
+    use turmoil::ToSocketAddrs;
+
+    thread_local! {
+        /// The rng of the network, turmoil runs all hosts of a simulation on the same thread.
+        pub(super) static RNG: RefCell<StdRng> = RefCell::new(StdRng::seed_from_u64(0));
+    }
+
+    /// Seeds the rng of the network on the current thread.
+    ///
+    /// This should be called before the nodes of a simulation are created, to run the simulation
+    /// with different randomness.
+    pub fn set_rng_seed(seed: u64) {
+        RNG.with(|rng| *rng.borrow_mut() = StdRng::seed_from_u64(seed));
+    }
+
+    /// A simulated tcp listener that can be polled for incoming connections, like the tokio
+    /// listener.
+    pub struct TcpListener {
+        inner: Arc<turmoil::net::TcpListener>,
+        accept: Option<BoxFuture<'static, io::Result<(TcpStream, SocketAddr)>>>,
+    }
+
+    impl TcpListener {
+        /// Creates a new listener bound to the given address.
+        pub async fn bind<A: ToSocketAddrs>(addr: A) -> io::Result<Self> {
+            let inner = turmoil::net::TcpListener::bind(addr).await?;
+            Ok(Self { inner: Arc::new(inner), accept: None })
+        }
+
+        /// Returns the local address of the listener.
+        pub fn local_addr(&self) -> io::Result<SocketAddr> {
+            self.inner.local_addr()
+        }
+
+        /// Accepts a new incoming connection.
+        pub async fn accept(&self) -> io::Result<(TcpStream, SocketAddr)> {
+            self.inner.accept().await
+        }
+
+        /// Polls to accept a new incoming connection.
+        pub fn poll_accept(
+            &mut self,
+            cx: &mut Context<'_>,
+        ) -> Poll<io::Result<(TcpStream, SocketAddr)>> {
+            let accept = self.accept.get_or_insert_with(|| {
+                let listener = self.inner.clone();
+                async move { listener.accept().await }.boxed()
+            });
+            let conn = ready!(accept.poll_unpin(cx));
+            self.accept = None;
+            Poll::Ready(conn)
+        }
+    }
+
+    impl fmt::Debug for TcpListener {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("TcpListener").field("local_addr", &self.local_addr()).finish()
+        }
+    }
+}
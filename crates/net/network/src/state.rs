@@ -10,7 +10,7 @@ use crate::{
         PeerResponseResult,
     },
     peers::{PeerAction, PeersManager},
-    FetchClient,
+    sim, FetchClient,
 };
 use rand::seq::SliceRandom;
 
@@ -22,7 +22,7 @@ use reth_network_peers::PeerId;
 use reth_primitives::{ForkId, B256};
 use reth_provider::BlockNumReader;
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::VecDeque,
     net::{IpAddr, SocketAddr},
     sync::{
         atomic::{AtomicU64, AtomicUsize},
@@ -49,7 +49,7 @@ const PEER_BLOCK_CACHE_LIMIT: u32 = 512;
 #[derive(Debug)]
 pub struct NetworkState<C> {
     /// All active peers and their state.
-    active_peers: HashMap<PeerId, ActivePeer>,
+    active_peers: sim::HashMap<PeerId, ActivePeer>,
     /// Manages connections to peers.
     peers_manager: PeersManager,
     /// Buffered messages until polled.
@@ -173,7 +173,7 @@ where
 
         // Shuffle to propagate to a random sample of peers on every block announcement
         let mut peers: Vec<_> = self.active_peers.iter_mut().collect();
-        peers.shuffle(&mut rand::thread_rng());
+        sim::with_rng(|rng| peers.shuffle(rng));
 
         for (peer_id, peer) in peers {
             if peer.blocks.contains(&msg.hash) {
@@ -253,7 +253,7 @@ impl<C, Pool> fmt::Debug for Testnet<C, Pool> {
 
 impl<C, Pool> Future for Testnet<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
 {
     type Output = ();
@@ -393,7 +393,8 @@ where
         let (tx, rx) = channel(ETH_REQUEST_CHANNEL_CAPACITY);
         self.network.set_eth_request_handler(tx);
         let peers = self.network.peers_handle();
-        let request_handler = EthRequestHandler::new(self.client.clone(), peers, rx);
+        let request_handler = EthRequestHandler::new(self.client.clone(), peers, rx)
+            .with_quotas(self.network.eth_request_quotas());
         self.request_handler = Some(request_handler);
     }
 
@@ -406,7 +407,8 @@ where
             pool.clone(),
             rx,
             TransactionsManagerConfig::default(),
-        );
+        )
+        .with_request_quotas(self.network.eth_request_quotas());
         self.transactions_manager = Some(transactions_manager);
         self.pool = Some(pool);
     }
@@ -424,7 +426,8 @@ where
             pool.clone(),
             rx,
             TransactionsManagerConfig::default(),
-        );
+        )
+        .with_request_quotas(network.eth_request_quotas());
         Peer {
             network,
             request_handler,
@@ -448,7 +451,7 @@ where
 
 impl<C, Pool> Future for Peer<C, Pool>
 where
-    C: BlockReader + HeaderProvider + Unpin,
+    C: BlockReader + HeaderProvider + Unpin + 'static,
     Pool: TransactionPool + Unpin + 'static,
 {
     type Output = ();
@@ -29,7 +29,7 @@ use crate::{
     cache::{LruCache, LruMap},
     duration_metered_exec,
     message::PeerRequest,
-    metrics::TransactionFetcherMetrics,
+    metrics::{BlobFetchMetrics, TransactionFetcherMetrics},
     transactions::{validation, PartiallyFilterMessage},
 };
 use derive_more::{Constructor, Deref};
@@ -42,7 +42,7 @@ use reth_eth_wire::{
 };
 use reth_network_p2p::error::{RequestError, RequestResult};
 use reth_network_peers::PeerId;
-use reth_primitives::{PooledTransactionsElement, TxHash};
+use reth_primitives::{PooledTransactionsElement, TxHash, EIP4844_TX_TYPE_ID};
 use schnellru::ByLength;
 #[cfg(debug_assertions)]
 use smallvec::{smallvec, SmallVec};
@@ -50,7 +50,7 @@ use std::{
     collections::HashMap,
     pin::Pin,
     task::{ready, Context, Poll},
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc::error::TrySendError, oneshot, oneshot::error::RecvError};
 use tracing::{debug, trace};
@@ -92,6 +92,8 @@ pub struct TransactionFetcher {
     pub info: TransactionFetcherInfo,
     #[doc(hidden)]
     metrics: TransactionFetcherMetrics,
+    /// Metrics for fetching announced blob transactions.
+    blob_metrics: BlobFetchMetrics,
 }
 
 // === impl TransactionFetcher ===
@@ -519,9 +521,9 @@ impl TransactionFetcher {
 
             // occupied entry
 
-            if let Some(TxFetchMetadata{ref mut fallback_peers, tx_encoded_length: ref mut previously_seen_size, ..}) = self.hashes_fetch_inflight_and_pending_fetch.peek_mut(hash) {
+            if let Some(TxFetchMetadata{ref mut fallback_peers, tx_encoded_length: ref mut previously_seen_size, ref mut is_blob_transaction, ..}) = self.hashes_fetch_inflight_and_pending_fetch.peek_mut(hash) {
                 // update size metadata if available
-                if let Some((_ty, size)) = metadata {
+                if let Some((ty, size)) = metadata {
                     if let Some(prev_size) = previously_seen_size {
                         // check if this peer is announcing a different size than a previous peer
                         if size != prev_size {
@@ -537,6 +539,7 @@ impl TransactionFetcher {
                     }
                     // believe the most recent peer to announce tx
                     *previously_seen_size = Some(*size);
+                    *is_blob_transaction = *ty == EIP4844_TX_TYPE_ID;
                 }
 
                 // hash has been seen but is not inflight
@@ -573,8 +576,9 @@ impl TransactionFetcher {
             #[cfg(debug_assertions)]
             previously_unseen_hashes.push(*hash);
 
+            let is_blob_transaction = metadata.is_some_and(|(ty, _size)| ty == EIP4844_TX_TYPE_ID);
             if self.hashes_fetch_inflight_and_pending_fetch.get_or_insert(*hash, ||
-                TxFetchMetadata{retries: 0, fallback_peers: LruCache::new(DEFAULT_MAX_COUNT_FALLBACK_PEERS as u32), tx_encoded_length: None}
+                TxFetchMetadata{retries: 0, fallback_peers: LruCache::new(DEFAULT_MAX_COUNT_FALLBACK_PEERS as u32), tx_encoded_length: None, is_blob_transaction}
             ).is_none() {
 
                 debug!(target: "net::tx",
@@ -675,6 +679,18 @@ impl TransactionFetcher {
             }
         }
 
+        // requests for announced blob transactions are measured separately
+        let blob_transactions = new_announced_hashes
+            .iter()
+            .filter(|&hash| {
+                self.hashes_fetch_inflight_and_pending_fetch
+                    .peek(hash)
+                    .is_some_and(|metadata| metadata.is_blob_transaction)
+            })
+            .count();
+        let blob_fetch =
+            (blob_transactions > 0).then(|| BlobFetch::new(blob_transactions, Instant::now()));
+
         let (response, rx) = oneshot::channel();
         let req: PeerRequest = PeerRequest::GetPooledTransactions {
             request: GetPooledTransactions(
@@ -693,10 +709,14 @@ impl TransactionFetcher {
                 }
             }
         } else {
+            if blob_fetch.is_some() {
+                self.blob_metrics.requests.increment(1);
+            }
             // stores a new request future for the request
             self.inflight_requests.push(GetPooledTxRequestFut::new(
                 peer_id,
                 new_announced_hashes,
+                blob_fetch,
                 rx,
             ))
         }
@@ -901,7 +921,7 @@ impl TransactionFetcher {
     ) -> FetchEvent {
         // update peer activity, requests for buffered hashes can only be made to idle
         // fallback peers
-        let GetPooledTxResponse { peer_id, mut requested_hashes, result } = response;
+        let GetPooledTxResponse { peer_id, mut requested_hashes, blob_fetch, result } = response;
 
         debug_assert!(
             self.active_peers.get(&peer_id).is_some(),
@@ -928,6 +948,9 @@ impl TransactionFetcher {
                         requested_hashes_len=requested_hashes.len(),
                         "received empty `PooledTransactions` response from peer, peer failed to serve hashes it announced"
                     );
+                    if let Some(blob_fetch) = blob_fetch {
+                        blob_fetch.on_response(&self.blob_metrics, Some(0));
+                    }
 
                     return FetchEvent::EmptyResponse { peer_id }
                 }
@@ -956,6 +979,9 @@ impl TransactionFetcher {
                 }
                 // peer has only sent hashes that we didn't request
                 if verified_payload.is_empty() {
+                    if let Some(blob_fetch) = blob_fetch {
+                        blob_fetch.on_response(&self.blob_metrics, Some(0));
+                    }
                     return FetchEvent::FetchError { peer_id, error: RequestError::BadResponse }
                 }
 
@@ -999,6 +1025,11 @@ impl TransactionFetcher {
                 });
                 fetched.shrink_to_fit();
                 self.metrics.fetched_transactions.increment(fetched.len() as u64);
+                if let Some(blob_fetch) = blob_fetch {
+                    let fetched_blob_transactions =
+                        valid_payload.values().filter(|tx| tx.is_eip4844()).count();
+                    blob_fetch.on_response(&self.blob_metrics, Some(fetched_blob_transactions));
+                }
 
                 if fetched.len() < requested_hashes_len {
                     trace!(target: "net::tx",
@@ -1020,10 +1051,16 @@ impl TransactionFetcher {
                 FetchEvent::TransactionsFetched { peer_id, transactions }
             }
             Ok(Err(req_err)) => {
+                if let Some(blob_fetch) = blob_fetch {
+                    blob_fetch.on_response(&self.blob_metrics, None);
+                }
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
                 FetchEvent::FetchError { peer_id, error: req_err }
             }
             Err(_) => {
+                if let Some(blob_fetch) = blob_fetch {
+                    blob_fetch.on_response(&self.blob_metrics, None);
+                }
                 self.try_buffer_hashes_for_retry(requested_hashes, &peer_id);
                 // request channel closed/dropped
                 FetchEvent::FetchError { peer_id, error: RequestError::ChannelClosed }
@@ -1063,6 +1100,7 @@ impl Default for TransactionFetcher {
             filter_valid_message: Default::default(),
             info: TransactionFetcherInfo::default(),
             metrics: Default::default(),
+            blob_metrics: Default::default(),
         }
     }
 }
@@ -1079,6 +1117,9 @@ pub struct TxFetchMetadata {
     // another size tx than they announced. alt enter in request (won't catch peers announcing
     // wrong size for requests assembled from hashes pending fetch if stored in request fut)
     tx_encoded_length: Option<usize>,
+    /// Whether the transaction has been announced as a blob transaction in an
+    /// [`Eth68`](reth_eth_wire::EthVersion::Eth68) announcement.
+    is_blob_transaction: bool,
 }
 
 impl TxFetchMetadata {
@@ -1094,6 +1135,12 @@ impl TxFetchMetadata {
     pub const fn tx_encoded_len(&self) -> Option<usize> {
         self.tx_encoded_length
     }
+
+    /// Returns `true` if the transaction has been announced as a blob transaction in an
+    /// [`Eth68`](reth_eth_wire::EthVersion::Eth68) announcement.
+    pub const fn is_blob_transaction(&self) -> bool {
+        self.is_blob_transaction
+    }
 }
 
 /// Represents possible events from fetching transactions.
@@ -1126,6 +1173,8 @@ pub struct GetPooledTxRequest {
     peer_id: PeerId,
     /// Transaction hashes that were requested, for cleanup purposes
     requested_hashes: RequestTxHashes,
+    /// Set if announced blob transactions were requested.
+    blob_fetch: Option<BlobFetch>,
     response: oneshot::Receiver<RequestResult<PooledTransactions>>,
 }
 
@@ -1137,9 +1186,37 @@ pub struct GetPooledTxResponse {
     /// Transaction hashes that were requested, for cleanup purposes, since peer may only return a
     /// subset of requested hashes.
     requested_hashes: RequestTxHashes,
+    /// Set if announced blob transactions were requested.
+    blob_fetch: Option<BlobFetch>,
     result: Result<RequestResult<PooledTransactions>, RecvError>,
 }
 
+/// Measures a [`GetPooledTransactions`] request that includes announced blob transactions.
+#[derive(Debug, Constructor)]
+struct BlobFetch {
+    /// The number of requested blob transactions.
+    blob_transactions: usize,
+    /// When the request was sent.
+    sent_at: Instant,
+}
+
+impl BlobFetch {
+    /// Records the outcome of the request, given the number of blob transactions the peer
+    /// served, or `None` if the request failed.
+    fn on_response(self, metrics: &BlobFetchMetrics, fetched_blob_transactions: Option<usize>) {
+        let Some(fetched) = fetched_blob_transactions else {
+            metrics.failed_requests.increment(1);
+            metrics.failed_transactions.increment(self.blob_transactions as u64);
+            return
+        };
+        metrics.request_duration_seconds.record(self.sent_at.elapsed().as_secs_f64());
+        metrics.fetched_transactions.increment(fetched as u64);
+        metrics
+            .failed_transactions
+            .increment(self.blob_transactions.saturating_sub(fetched) as u64);
+    }
+}
+
 /// Stores the response receiver made by sending a [`GetPooledTransactions`] request to a peer's
 /// session.
 #[must_use = "futures do nothing unless polled"]
@@ -1155,9 +1232,10 @@ impl GetPooledTxRequestFut {
     const fn new(
         peer_id: PeerId,
         requested_hashes: RequestTxHashes,
+        blob_fetch: Option<BlobFetch>,
         response: oneshot::Receiver<RequestResult<PooledTransactions>>,
     ) -> Self {
-        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, response }) }
+        Self { inner: Some(GetPooledTxRequest { peer_id, requested_hashes, blob_fetch, response }) }
     }
 }
 
@@ -1170,6 +1248,7 @@ impl Future for GetPooledTxRequestFut {
             Poll::Ready(result) => Poll::Ready(GetPooledTxResponse {
                 peer_id: req.peer_id,
                 requested_hashes: req.requested_hashes,
+                blob_fetch: req.blob_fetch,
                 result,
             }),
             Poll::Pending => {
@@ -1339,6 +1418,7 @@ mod test {
     use reth_primitives::{hex, TransactionSigned, B256};
 
     use crate::transactions::tests::{default_cache, new_mock_session};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
 
     use super::*;
 
@@ -1465,10 +1545,10 @@ mod test {
             // insert peer_2 as fallback peer for seen_hashes
             let mut backups = default_cache();
             backups.insert(peer_2);
-            let meta = TxFetchMetadata::new(0, backups, Some(seen_eth68_hashes_sizes[i]));
+            let meta = TxFetchMetadata::new(0, backups, Some(seen_eth68_hashes_sizes[i]), false);
             tx_fetcher.hashes_fetch_inflight_and_pending_fetch.insert(seen_hashes[i], meta);
         }
-        let meta = TxFetchMetadata::new(0, backups, None);
+        let meta = TxFetchMetadata::new(0, backups, None, false);
         tx_fetcher.hashes_fetch_inflight_and_pending_fetch.insert(seen_hashes[3], meta);
 
         let mut backups = default_cache();
@@ -1477,7 +1557,7 @@ mod test {
         let hash_other = B256::from_slice(&[5; 32]);
         tx_fetcher
             .hashes_fetch_inflight_and_pending_fetch
-            .insert(hash_other, TxFetchMetadata::new(0, backups, None));
+            .insert(hash_other, TxFetchMetadata::new(0, backups, None, false));
         tx_fetcher.hashes_pending_fetch.insert(hash_other);
 
         // add peer_1 as lru fallback peer for seen hashes
@@ -1553,4 +1633,65 @@ mod test {
         assert_eq!(1, verified_payload.len());
         assert!(verified_payload.contains(&signed_tx_1));
     }
+
+    #[tokio::test]
+    async fn test_blob_fetch_metrics() {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let counter = |name: &str| {
+            snapshotter
+                .snapshot()
+                .into_vec()
+                .into_iter()
+                .find_map(|(key, _, _, value)| match value {
+                    DebugValue::Counter(value) if key.key().name().ends_with(name) => Some(value),
+                    _ => None,
+                })
+                .unwrap_or_default()
+        };
+
+        let mut tx_fetcher = ::metrics::with_local_recorder(&recorder, TransactionFetcher::default);
+
+        let peer_id = PeerId::new([1; 64]);
+        let (peer, mut to_mock_session_rx) = new_mock_session(peer_id, EthVersion::Eth68);
+        let blob_hash = B256::from_slice(&[1; 32]);
+        let hash = B256::from_slice(&[2; 32]);
+        for (hash, is_blob_transaction) in [(blob_hash, true), (hash, false)] {
+            tx_fetcher
+                .hashes_fetch_inflight_and_pending_fetch
+                .insert(hash, TxFetchMetadata::new(0, default_cache(), None, is_blob_transaction));
+        }
+        let request_hashes = || RequestTxHashes::new([blob_hash, hash].into_iter().collect());
+
+        // the request fails
+        assert!(tx_fetcher.request_transactions_from_peer(request_hashes(), &peer).is_none());
+        let Some(PeerRequest::GetPooledTransactions { response, .. }) =
+            to_mock_session_rx.recv().await
+        else {
+            unreachable!()
+        };
+        response.send(Err(RequestError::Timeout)).unwrap();
+        let Some(FetchEvent::FetchError { .. }) = tx_fetcher.next().await else { unreachable!() };
+
+        assert_eq!(counter("blobs.requests"), 1);
+        assert_eq!(counter("blobs.failed_requests"), 1);
+        assert_eq!(counter("blobs.failed_transactions"), 1);
+
+        // the peer doesn't serve the blob transaction
+        assert!(tx_fetcher.request_transactions_from_peer(request_hashes(), &peer).is_none());
+        let Some(PeerRequest::GetPooledTransactions { response, .. }) =
+            to_mock_session_rx.recv().await
+        else {
+            unreachable!()
+        };
+        response.send(Ok(PooledTransactions(vec![]))).unwrap();
+        let Some(FetchEvent::EmptyResponse { .. }) = tx_fetcher.next().await else {
+            unreachable!()
+        };
+
+        assert_eq!(counter("blobs.requests"), 2);
+        assert_eq!(counter("blobs.failed_requests"), 1);
+        assert_eq!(counter("blobs.fetched_transactions"), 0);
+        assert_eq!(counter("blobs.failed_transactions"), 2);
+    }
 }
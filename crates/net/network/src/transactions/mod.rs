@@ -8,11 +8,12 @@ use crate::{
     },
     cache::LruCache,
     duration_metered_exec,
+    eth_requests::EthRequestQuotas,
     manager::NetworkEvent,
     message::{PeerRequest, PeerRequestSender},
     metered_poll_nested_stream_with_budget,
     metrics::{TransactionsManagerMetrics, NETWORK_POOL_TRANSACTIONS_SCOPE},
-    NetworkEvents, NetworkHandle,
+    sim, NetworkEvents, NetworkHandle,
 };
 use futures::{stream::FuturesUnordered, Future, StreamExt};
 use reth_eth_wire::{
@@ -37,7 +38,7 @@ use reth_transaction_pool::{
     TransactionPool, ValidPoolTransaction,
 };
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet},
+    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
     pin::Pin,
     sync::{
         atomic::{AtomicUsize, Ordering},
@@ -74,6 +75,11 @@ use self::constants::{tx_manager::*, DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_B
 /// Resolves with the result of each transaction import.
 pub type PoolImportFuture = Pin<Box<dyn Future<Output = Vec<PoolResult<TxHash>>> + Send + 'static>>;
 
+/// The future serving a `GetPooledTransactions` request.
+///
+/// Resolves with the requesting peer and the hashes of the transactions sent to it.
+type PooledTransactionsWorker = Pin<Box<dyn Future<Output = (PeerId, Vec<TxHash>)> + Send>>;
+
 /// Api to interact with [`TransactionsManager`] task.
 ///
 /// This can be obtained via [`TransactionsManager::handle`] and can be used to manually interact
@@ -244,6 +250,13 @@ pub struct TransactionsManager<Pool> {
     pending_transactions: ReceiverStream<TxHash>,
     /// Incoming events from the [`NetworkManager`](crate::NetworkManager).
     transaction_events: UnboundedMeteredReceiver<NetworkTransactionEvent>,
+    /// Limits for serving `GetPooledTransactions` requests.
+    request_quotas: EthRequestQuotas,
+    /// `GetPooledTransactions` requests waiting to be served.
+    queued_pooled_transactions_requests: VecDeque<QueuedPooledTransactionsRequest>,
+    /// `GetPooledTransactions` requests that are currently being served on blocking worker
+    /// threads.
+    pooled_transactions_workers: FuturesUnordered<PooledTransactionsWorker>,
     /// `TransactionsManager` metrics
     metrics: TransactionsManagerMetrics,
 }
@@ -294,9 +307,18 @@ impl<Pool: TransactionPool> TransactionsManager<Pool> {
                 from_network,
                 NETWORK_POOL_TRANSACTIONS_SCOPE,
             ),
+            request_quotas: Default::default(),
+            queued_pooled_transactions_requests: Default::default(),
+            pooled_transactions_workers: Default::default(),
             metrics,
         }
     }
+
+    /// Sets the limits for serving `GetPooledTransactions` requests.
+    pub const fn with_request_quotas(mut self, quotas: EthRequestQuotas) -> Self {
+        self.request_quotas = quotas;
+        self
+    }
 }
 
 // === impl TransactionsManager ===
@@ -348,24 +370,68 @@ where
         request: GetPooledTransactions,
         response: oneshot::Sender<RequestResult<PooledTransactions>>,
     ) {
-        if let Some(peer) = self.peers.get_mut(&peer_id) {
+        if self.peers.contains_key(&peer_id) {
             if self.network.tx_gossip_disabled() {
                 let _ = response.send(Ok(PooledTransactions::default()));
                 return
             }
-            let transactions = self.pool.get_pooled_transaction_elements(
-                request.0,
-                GetPooledTransactionLimit::ResponseSizeSoftLimit(
-                    self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response,
-                ),
-            );
+            if self.queued_pooled_transactions_requests.len() >=
+                self.request_quotas.max_queued_requests
+            {
+                // dropping the response channel lets the peer's request time out
+                self.metrics.pooled_transactions_requests_dropped_total.increment(1);
+                return
+            }
+            self.queued_pooled_transactions_requests.push_back(QueuedPooledTransactionsRequest {
+                queued_at: sim::now(),
+                peer_id,
+                request,
+                response,
+            });
+        }
+    }
 
-            // we sent a response at which point we assume that the peer is aware of the
-            // transactions
-            peer.seen_transactions.extend(transactions.iter().map(|tx| *tx.hash()));
+    /// Starts serving queued `GetPooledTransactions` requests, as long as the quota allows it.
+    ///
+    /// Returns `true` if any request was dispatched.
+    fn dispatch_queued_pooled_transactions_requests(&mut self) -> bool {
+        let mut dispatched = false;
+        while self.pooled_transactions_workers.len() <
+            self.request_quotas.max_concurrent_pooled_transactions_requests
+        {
+            let Some(QueuedPooledTransactionsRequest { queued_at, peer_id, request, response }) =
+                self.queued_pooled_transactions_requests.pop_front()
+            else {
+                break
+            };
+            self.metrics
+                .pooled_transactions_requests_queue_duration_seconds
+                .record(queued_at.elapsed().as_secs_f64());
+            dispatched = true;
 
-            let resp = PooledTransactions(transactions);
-            let _ = response.send(Ok(resp));
+            let pool = self.pool.clone();
+            let limit = GetPooledTransactionLimit::ResponseSizeSoftLimit(
+                self.transaction_fetcher.info.soft_limit_byte_size_pooled_transactions_response,
+            );
+            let worker = tokio::task::spawn_blocking(move || {
+                let transactions = pool.get_pooled_transaction_elements(request.0, limit);
+                let hashes: Vec<_> = transactions.iter().map(|tx| *tx.hash()).collect();
+                let _ = response.send(Ok(PooledTransactions(transactions)));
+                hashes
+            });
+            self.pooled_transactions_workers.push(Box::pin(async move {
+                // a panicking worker has dropped the response channel already
+                (peer_id, worker.await.unwrap_or_default())
+            }));
+        }
+        dispatched
+    }
+
+    /// Invoked when a `GetPooledTransactions` request of the peer was served.
+    fn on_pooled_transactions_served(&mut self, peer_id: PeerId, hashes: Vec<TxHash>) {
+        // we sent a response at which point we assume that the peer is aware of the transactions
+        if let Some(peer) = self.peers.get_mut(&peer_id) {
+            peer.seen_transactions.extend(hashes);
         }
     }
 
@@ -422,67 +488,8 @@ where
 
         // Note: Assuming ~random~ order due to random state of the peers map hasher
         for (peer_idx, (peer_id, peer)) in self.peers.iter_mut().enumerate() {
-            // filter all transactions unknown to the peer
-            let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
-            let mut full_transactions = FullTransactionsBuilder::default();
-
-            // Iterate through the transactions to propagate and fill the hashes and full
-            // transaction lists, before deciding whether or not to send full transactions to the
-            // peer.
-            for tx in &to_propagate {
-                if peer.seen_transactions.insert(tx.hash()) {
-                    hashes.push(tx);
-
-                    // Do not send full 4844 transaction hashes to peers.
-                    //
-                    //  Nodes MUST NOT automatically broadcast blob transactions to their peers.
-                    //  Instead, those transactions are only announced using
-                    //  `NewPooledTransactionHashes` messages, and can then be manually requested
-                    //  via `GetPooledTransactions`.
-                    //
-                    // From: <https://eips.ethereum.org/EIPS/eip-4844#networking>
-                    if !tx.transaction.is_eip4844() {
-                        full_transactions.push(tx);
-                    }
-                }
-            }
-            let mut new_pooled_hashes = hashes.build();
-
-            if !new_pooled_hashes.is_empty() {
-                // determine whether to send full tx objects or hashes. If there are no full
-                // transactions, try to send hashes.
-                if peer_idx > max_num_full || full_transactions.is_empty() {
-                    // enforce tx soft limit per message for the (unlikely) event the number of
-                    // hashes exceeds it
-                    new_pooled_hashes.truncate(
-                        SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE,
-                    );
-
-                    for hash in new_pooled_hashes.iter_hashes().copied() {
-                        propagated.0.entry(hash).or_default().push(PropagateKind::Hash(*peer_id));
-                    }
-
-                    trace!(target: "net::tx", ?peer_id, num_txs=?new_pooled_hashes.len(), "Propagating tx hashes to peer");
-
-                    // send hashes of transactions
-                    self.network.send_transactions_hashes(*peer_id, new_pooled_hashes);
-                } else {
-                    let new_full_transactions = full_transactions.build();
-
-                    for tx in &new_full_transactions {
-                        propagated
-                            .0
-                            .entry(tx.hash())
-                            .or_default()
-                            .push(PropagateKind::Full(*peer_id));
-                    }
-
-                    trace!(target: "net::tx", ?peer_id, num_txs=?new_full_transactions.len(), "Propagating full transactions to peer");
-
-                    // send full transactions
-                    self.network.send_transactions(*peer_id, new_full_transactions);
-                }
-            }
+            let messages = peer.propagation_messages(&to_propagate, peer_idx <= max_num_full);
+            messages.send(&self.network, *peer_id, &mut propagated);
         }
 
         // Update propagated transactions metrics
@@ -491,7 +498,8 @@ where
         propagated
     }
 
-    /// Propagate the full transactions to a specific peer
+    /// Propagate the full transactions to a specific peer. Blob transactions and transactions that
+    /// don't fit in the message are announced instead.
     ///
     /// Returns the propagated transactions
     fn propagate_full_transactions_to_peer(
@@ -504,34 +512,15 @@ where
         let peer = self.peers.get_mut(&peer_id)?;
         let mut propagated = PropagatedTransactions::default();
 
-        // filter all transactions unknown to the peer
-        let mut full_transactions = FullTransactionsBuilder::default();
-
-        let to_propagate = self
-            .pool
-            .get_all(txs)
-            .into_iter()
-            .filter(|tx| !tx.transaction.is_eip4844())
-            .map(PropagateTransaction::new);
-
-        // Iterate through the transactions to propagate and fill the hashes and full transaction
-        for tx in to_propagate {
-            if peer.seen_transactions.insert(tx.hash()) {
-                full_transactions.push(&tx);
-            }
-        }
+        let to_propagate =
+            self.pool.get_all(txs).into_iter().map(PropagateTransaction::new).collect::<Vec<_>>();
 
-        if full_transactions.transactions.is_empty() {
+        let messages = peer.propagation_messages(&to_propagate, true);
+        if messages.is_empty() {
             // nothing to propagate
             return None
         }
-
-        let new_full_transactions = full_transactions.build();
-        for tx in &new_full_transactions {
-            propagated.0.entry(tx.hash()).or_default().push(PropagateKind::Full(peer_id));
-        }
-        // send full transactions
-        self.network.send_transactions(peer_id, new_full_transactions);
+        messages.send(&self.network, peer_id, &mut propagated);
 
         // Update propagated transactions metrics
         self.metrics.propagated_transactions.increment(propagated.0.len() as u64);
@@ -562,7 +551,7 @@ where
             let mut hashes = PooledTransactionsHashesBuilder::new(peer.version);
 
             for tx in to_propagate {
-                if !peer.seen_transactions.insert(tx.hash()) {
+                if peer.seen_transactions.insert(tx.hash()) {
                     hashes.push(&tx);
                 }
             }
@@ -1336,6 +1325,21 @@ where
             |cmd| this.on_command(cmd)
         );
 
+        // Serve queued `GetPooledTransactions` requests, and free the quota of served requests.
+        loop {
+            while let Poll::Ready(Some((peer_id, hashes))) =
+                this.pooled_transactions_workers.poll_next_unpin(cx)
+            {
+                this.on_pooled_transactions_served(peer_id, hashes);
+            }
+
+            // newly dispatched workers are polled on the next iteration, so that they wake the
+            // manager once they're done
+            if !this.dispatch_queued_pooled_transactions_requests() {
+                break
+            }
+        }
+
         this.transaction_fetcher.update_metrics();
 
         // all channels are fully drained and import futures pending
@@ -1378,27 +1382,108 @@ impl PropagateTransaction {
     }
 }
 
+/// The messages that propagate transactions to a peer.
+struct PropagationMessages {
+    /// The transactions that are broadcast in full.
+    full: Vec<Arc<TransactionSigned>>,
+    /// The transactions that are announced.
+    pooled: NewPooledTransactionHashes,
+}
+
+// === impl PropagationMessages ===
+
+impl PropagationMessages {
+    /// Returns `true` if there's nothing to propagate.
+    fn is_empty(&self) -> bool {
+        self.full.is_empty() && self.pooled.is_empty()
+    }
+
+    /// Sends the messages to the peer and records the propagated transactions.
+    fn send(
+        self,
+        network: &NetworkHandle,
+        peer_id: PeerId,
+        propagated: &mut PropagatedTransactions,
+    ) {
+        let Self { full, mut pooled } = self;
+
+        if !full.is_empty() {
+            for tx in &full {
+                propagated.0.entry(tx.hash()).or_default().push(PropagateKind::Full(peer_id));
+            }
+
+            trace!(target: "net::tx", ?peer_id, num_txs=?full.len(), "Propagating full transactions to peer");
+
+            // send full transactions
+            network.send_transactions(peer_id, full);
+        }
+
+        if !pooled.is_empty() {
+            // enforce tx soft limit per message for the (unlikely) event the number of hashes
+            // exceeds it
+            pooled.truncate(SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE);
+
+            for hash in pooled.iter_hashes().copied() {
+                propagated.0.entry(hash).or_default().push(PropagateKind::Hash(peer_id));
+            }
+
+            trace!(target: "net::tx", ?peer_id, num_txs=?pooled.len(), "Propagating tx hashes to peer");
+
+            // send hashes of transactions
+            network.send_transactions_hashes(peer_id, pooled);
+        }
+    }
+}
+
 /// Helper type for constructing the full transaction message that enforces the
 /// [`DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE`].
-#[derive(Default)]
+///
+/// The transactions that can't be broadcast in full are announced instead.
 struct FullTransactionsBuilder {
     total_size: usize,
     transactions: Vec<Arc<TransactionSigned>>,
+    /// The transactions that are announced.
+    pooled: PooledTransactionsHashesBuilder,
 }
 
 // === impl FullTransactionsBuilder ===
 
 impl FullTransactionsBuilder {
+    /// Create a builder for the negotiated version of the peer's session
+    fn new(version: EthVersion) -> Self {
+        Self {
+            total_size: 0,
+            transactions: Vec::new(),
+            pooled: PooledTransactionsHashesBuilder::new(version),
+        }
+    }
+
     /// Append a transaction to the list if the total message bytes size doesn't exceed the soft
     /// maximum target byte size. The limit is soft, meaning if one single transaction goes over
     /// the limit, it will be broadcasted in its own [`Transactions`] message. The same pattern is
     /// followed in filling a [`GetPooledTransactions`] request in
     /// [`TransactionFetcher::fill_request_from_hashes_pending_fetch`].
+    ///
+    /// Blob transactions and the transactions that exceed the limit are announced.
     fn push(&mut self, transaction: &PropagateTransaction) {
+        // Do not send full 4844 transaction hashes to peers.
+        //
+        //  Nodes MUST NOT automatically broadcast blob transactions to their peers.
+        //  Instead, those transactions are only announced using
+        //  `NewPooledTransactionHashes` messages, and can then be manually requested
+        //  via `GetPooledTransactions`.
+        //
+        // From: <https://eips.ethereum.org/EIPS/eip-4844#networking>
+        if transaction.transaction.is_eip4844() {
+            self.pooled.push(transaction);
+            return
+        }
+
         let new_size = self.total_size + transaction.size;
         if new_size > DEFAULT_SOFT_LIMIT_BYTE_SIZE_TRANSACTIONS_BROADCAST_MESSAGE &&
             self.total_size > 0
         {
+            self.pooled.push(transaction);
             return
         }
 
@@ -1406,14 +1491,15 @@ impl FullTransactionsBuilder {
         self.transactions.push(Arc::clone(&transaction.transaction));
     }
 
-    /// Returns whether or not any transactions are in the [`FullTransactionsBuilder`].
+    /// Returns whether or not any transactions are broadcast in full by the
+    /// [`FullTransactionsBuilder`].
     fn is_empty(&self) -> bool {
         self.transactions.is_empty()
     }
 
-    /// returns the list of transactions.
-    fn build(self) -> Vec<Arc<TransactionSigned>> {
-        self.transactions
+    /// returns the full transactions and the announced transactions.
+    fn build(self) -> PropagationMessages {
+        PropagationMessages { full: self.transactions, pooled: self.pooled.build() }
     }
 }
 
@@ -1483,6 +1569,19 @@ impl TransactionSource {
     }
 }
 
+/// A `GetPooledTransactions` request waiting to be served.
+#[derive(Debug)]
+struct QueuedPooledTransactionsRequest {
+    /// When the request was queued.
+    queued_at: Instant,
+    /// The peer that sent the request.
+    peer_id: PeerId,
+    /// The received request.
+    request: GetPooledTransactions,
+    /// The channel to send the response to.
+    response: oneshot::Sender<RequestResult<PooledTransactions>>,
+}
+
 /// Tracks a single peer in the context of [`TransactionsManager`].
 #[derive(Debug)]
 pub struct PeerMetadata {
@@ -1508,6 +1607,35 @@ impl PeerMetadata {
             client_version,
         }
     }
+
+    /// Returns the messages that propagate the transactions unknown to the peer, and marks them
+    /// as seen by the peer.
+    ///
+    /// If `full` is `true`, the transactions are broadcast in full, except for blob transactions
+    /// and the transactions that don't fit in the message, which are announced. Otherwise, or if
+    /// none of the transactions can be broadcast in full, all transactions are announced.
+    fn propagation_messages(
+        &mut self,
+        to_propagate: &[PropagateTransaction],
+        full: bool,
+    ) -> PropagationMessages {
+        let mut hashes = PooledTransactionsHashesBuilder::new(self.version);
+        let mut full_transactions = FullTransactionsBuilder::new(self.version);
+
+        // Iterate through the transactions to propagate and fill the hashes and full transaction
+        // lists, before deciding whether or not to send full transactions to the peer.
+        for tx in to_propagate {
+            if self.seen_transactions.insert(tx.hash()) {
+                hashes.push(tx);
+                full_transactions.push(tx);
+            }
+        }
+
+        if full && !full_transactions.is_empty() {
+            return full_transactions.build()
+        }
+        PropagationMessages { full: Vec::new(), pooled: hashes.build() }
+    }
 }
 
 /// Commands to send to the [`TransactionsManager`]
@@ -1617,7 +1745,10 @@ mod tests {
     };
     use reth_primitives::hex;
     use reth_provider::test_utils::NoopProvider;
-    use reth_transaction_pool::test_utils::{testing_pool, MockTransaction};
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction, MockTransactionFactory},
+        TransactionEvent, TransactionOrigin,
+    };
     use secp256k1::SecretKey;
     use std::{fmt, future::poll_fn, hash};
     use tests::fetcher::TxFetchMetadata;
@@ -1929,6 +2060,37 @@ mod tests {
         handle.terminate().await;
     }
 
+    #[tokio::test]
+    async fn test_propagate_hashes_to_unknown_txs() {
+        let mut tx_manager = new_tx_manager().await;
+
+        let peer_id = PeerId::new([1; 64]);
+        let (peer, _to_mock_session_rx) = new_mock_session(peer_id, EthVersion::Eth68);
+        tx_manager.peers.insert(peer_id, peer);
+
+        let tx = MockTransaction::eip1559();
+        let hash = *tx.get_hash();
+        let mut events = tx_manager
+            .pool
+            .add_transaction_and_subscribe(TransactionOrigin::External, tx)
+            .await
+            .unwrap();
+        assert_eq!(events.next().await, Some(TransactionEvent::Pending));
+
+        tx_manager.propagate_hashes_to(vec![hash], peer_id);
+        match events.next().await {
+            Some(TransactionEvent::Propagated(kinds)) => {
+                assert_eq!(*kinds, vec![PropagateKind::Hash(peer_id)])
+            }
+            ev => panic!("unexpected event {ev:?}"),
+        }
+        assert!(tx_manager.peers[&peer_id].seen_transactions.contains(&hash));
+
+        // the hash is known to the peer now, so it's not announced again
+        tx_manager.propagate_hashes_to(vec![hash], peer_id);
+        assert!(events.next().now_or_never().is_none());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_on_get_pooled_transactions_network() {
         reth_tracing::init_test_tracing();
@@ -2010,6 +2172,9 @@ mod tests {
             response: send,
         });
 
+        // the request is served once the manager is polled
+        tokio::task::spawn(transactions);
+
         match receive.await.unwrap() {
             Ok(PooledTransactions(transactions)) => {
                 assert_eq!(transactions.len(), 1);
@@ -2049,10 +2214,10 @@ mod tests {
         backups1.insert(peer_id_1);
         tx_fetcher
             .hashes_fetch_inflight_and_pending_fetch
-            .insert(seen_hashes[1], TxFetchMetadata::new(retries, backups, None));
+            .insert(seen_hashes[1], TxFetchMetadata::new(retries, backups, None, false));
         tx_fetcher
             .hashes_fetch_inflight_and_pending_fetch
-            .insert(seen_hashes[0], TxFetchMetadata::new(retries, backups1, None));
+            .insert(seen_hashes[0], TxFetchMetadata::new(retries, backups1, None, false));
         tx_fetcher.hashes_pending_fetch.insert(seen_hashes[1]);
         tx_fetcher.hashes_pending_fetch.insert(seen_hashes[0]);
 
@@ -2132,4 +2297,51 @@ mod tests {
         assert!(tx_fetcher.hashes_pending_fetch.is_empty());
         assert_eq!(tx_fetcher.active_peers.len(), 0);
     }
+
+    #[test]
+    fn test_blob_heavy_propagation() {
+        let mut tx_factory = MockTransactionFactory::default();
+
+        // every other transaction to propagate is a blob transaction
+        let to_propagate = (0..64)
+            .map(|i| {
+                let tx = if i % 2 == 0 {
+                    MockTransaction::eip4844()
+                } else {
+                    MockTransaction::eip1559()
+                };
+                PropagateTransaction::new(tx_factory.validated_arc(tx))
+            })
+            .collect::<Vec<_>>();
+        let blob_hashes = to_propagate
+            .iter()
+            .filter(|tx| tx.transaction.is_eip4844())
+            .map(|tx| tx.hash())
+            .collect::<HashSet<_>>();
+
+        // a fraction of the peers receives full transactions, like in
+        // `TransactionsManager::propagate_transactions`
+        let num_peers = 16;
+        let max_num_full = (num_peers as f64).sqrt() as usize + 1;
+        for peer_idx in 0..num_peers {
+            let version = if peer_idx % 2 == 0 { EthVersion::Eth68 } else { EthVersion::Eth66 };
+            let (mut peer, _to_mock_session_rx) = new_mock_session(PeerId::random(), version);
+
+            let PropagationMessages { full, pooled } =
+                peer.propagation_messages(&to_propagate, peer_idx <= max_num_full);
+
+            // blob transactions are never broadcast in full, but always announced
+            assert!(full.iter().all(|tx| !tx.is_eip4844()));
+            assert_eq!(full.is_empty(), peer_idx > max_num_full);
+            let announced = pooled.iter_hashes().copied().collect::<HashSet<_>>();
+            assert!(blob_hashes.is_subset(&announced));
+
+            // every transaction is propagated to the peer exactly once
+            let propagated =
+                full.iter().map(|tx| tx.hash()).chain(announced).collect::<HashSet<_>>();
+            assert_eq!(propagated.len(), to_propagate.len());
+            assert_eq!(full.len() + pooled.len(), to_propagate.len());
+            assert!(peer.propagation_messages(&to_propagate, true).is_empty());
+        }
+    }
 }
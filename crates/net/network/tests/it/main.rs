@@ -3,6 +3,8 @@ mod connect;
 mod multiplex;
 mod requests;
 mod session;
+#[cfg(feature = "simulation")]
+mod simulation;
 mod startup;
 mod txgossip;
 
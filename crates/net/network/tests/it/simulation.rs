@@ -0,0 +1,57 @@
+//! Deterministic simulations of the network, see [`reth_network::sim`].
+
+use futures::StreamExt;
+use reth_network::{
+    config::rng_secret_key, sim::set_rng_seed, NetworkConfigBuilder, NetworkEvent, NetworkEvents,
+    NetworkHandle, NetworkManager,
+};
+use reth_network_api::Peers;
+use reth_network_peers::pk2id;
+use reth_provider::test_utils::NoopProvider;
+use secp256k1::{SecretKey, SECP256K1};
+use std::net::{Ipv4Addr, SocketAddr};
+
+const PORT: u16 = 30303;
+
+async fn spawn_network(secret_key: SecretKey) -> NetworkHandle {
+    let config = NetworkConfigBuilder::new(secret_key)
+        .listener_addr(SocketAddr::from((Ipv4Addr::UNSPECIFIED, PORT)))
+        .disable_discovery()
+        .build(NoopProvider::default());
+    let network = NetworkManager::new(config).await.unwrap();
+    let handle = network.handle().clone();
+    tokio::task::spawn(network);
+    handle
+}
+
+#[test]
+fn test_simulated_session() {
+    reth_tracing::init_test_tracing();
+    set_rng_seed(1);
+
+    let bootnode_key = rng_secret_key();
+    let bootnode_id = pk2id(&bootnode_key.public_key(SECP256K1));
+    let node_key = rng_secret_key();
+
+    let mut sim = turmoil::Builder::new().build();
+    sim.host("bootnode", move || async move {
+        let _handle = spawn_network(bootnode_key).await;
+        std::future::pending::<()>().await;
+        Ok(())
+    });
+    sim.client("node", async move {
+        let handle = spawn_network(node_key).await;
+        let mut events = handle.event_listener();
+        handle.add_peer(bootnode_id, SocketAddr::new(turmoil::lookup("bootnode"), PORT));
+
+        while let Some(event) = events.next().await {
+            if let NetworkEvent::SessionEstablished { peer_id, .. } = event {
+                assert_eq!(peer_id, bootnode_id);
+                break
+            }
+        }
+        Ok(())
+    });
+
+    sim.run().unwrap();
+}
@@ -4,10 +4,12 @@ use futures::StreamExt;
 use rand::thread_rng;
 use reth_network::{test_utils::Testnet, NetworkEvent, NetworkEvents};
 use reth_network_api::PeersInfo;
-use reth_primitives::{TransactionSigned, TxLegacy, U256};
+use reth_primitives::{TransactionSigned, TxLegacy, B256, U256};
 use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
-use reth_transaction_pool::{test_utils::TransactionGenerator, PoolTransaction, TransactionPool};
-use std::sync::Arc;
+use reth_transaction_pool::{
+    test_utils::TransactionGenerator, EthPooledTransaction, PoolTransaction, TransactionPool,
+};
+use std::{collections::HashSet, sync::Arc};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_tx_gossip() {
@@ -47,6 +49,58 @@ async fn test_tx_gossip() {
     assert_eq!(received, hash);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_large_txs_gossip() {
+    reth_tracing::init_test_tracing();
+
+    let provider = MockEthProvider::default();
+    let net = Testnet::create_with(2, provider.clone()).await;
+
+    // install request handlers
+    let net = net.with_eth_pool();
+    let handle = net.spawn();
+    // connect all the peers
+    handle.connect_peers().await;
+
+    let peer0 = &handle.peers()[0];
+    let peer1 = &handle.peers()[1];
+
+    let peer0_pool = peer0.pool().unwrap();
+    let mut peer1_tx_listener = peer1.pool().unwrap().pending_transactions_listener();
+
+    // together the transactions exceed the soft limit of a broadcast message, so the second one
+    // is announced instead of broadcast in full
+    let mut gen = TransactionGenerator::new(thread_rng());
+    let txs = (0..2)
+        .map(|_| {
+            let tx = gen
+                .transaction()
+                .signer(B256::random())
+                .gas_limit(2_000_000)
+                .input(vec![0u8; 100_000])
+                .into_eip1559()
+                .into_ecrecovered()
+                .unwrap();
+            provider.add_account(tx.signer(), ExtendedAccount::new(0, U256::from(100_000_000)));
+            let encoded_length = tx.length_without_header();
+            EthPooledTransaction::new(tx, encoded_length)
+        })
+        .collect::<Vec<_>>();
+    let hashes = txs.iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
+
+    // insert the pending txs in peer0's pool at once, so they're propagated together
+    for result in peer0_pool.add_external_transactions(txs).await {
+        result.unwrap();
+    }
+
+    // ensure both txs are gossiped to peer1
+    let mut received = HashSet::new();
+    while received.len() < hashes.len() {
+        received.insert(peer1_tx_listener.recv().await.unwrap());
+    }
+    assert_eq!(received, hashes);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_4844_tx_gossip_penalization() {
     reth_tracing::init_test_tracing();
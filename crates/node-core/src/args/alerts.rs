@@ -0,0 +1,92 @@
+//! clap [Args](clap::Args) for alerts on critical node events
+
+use clap::{Args, ValueEnum};
+use std::fmt;
+
+/// Parameters for alerting on critical node events
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Alerts")]
+pub struct AlertArgs {
+    /// URL that critical node events are posted to.
+    #[arg(long = "alerts.webhook-url", help_heading = "Alerts", value_name = "URL")]
+    pub webhook_url: Option<String>,
+
+    /// Format of the webhook request body.
+    #[arg(long = "alerts.format", help_heading = "Alerts", default_value_t = AlertFormat::Json)]
+    pub format: AlertFormat,
+
+    /// Minimum number of reverted blocks for a reorg to be alerted.
+    #[arg(long = "alerts.reorg-depth", help_heading = "Alerts", default_value_t = 3)]
+    pub reorg_depth: u64,
+
+    /// Free disk space of the data directory, in GB, below which an alert is sent.
+    #[arg(long = "alerts.min-free-disk", help_heading = "Alerts", default_value_t = 10)]
+    pub min_free_disk_gb: u64,
+}
+
+impl Default for AlertArgs {
+    fn default() -> Self {
+        Self { webhook_url: None, format: AlertFormat::Json, reorg_depth: 3, min_free_disk_gb: 10 }
+    }
+}
+
+/// The format of the alert webhook request body.
+#[derive(Debug, Copy, Clone, Default, ValueEnum, Eq, PartialEq)]
+pub enum AlertFormat {
+    /// A JSON object with the event and its fields
+    #[default]
+    Json,
+    /// A Slack incoming webhook message
+    Slack,
+    /// A Discord webhook message
+    Discord,
+}
+
+impl fmt::Display for AlertFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Json => write!(f, "json"),
+            Self::Slack => write!(f, "slack"),
+            Self::Discord => write!(f, "discord"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_alert_args() {
+        let args = CommandParser::<AlertArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, AlertArgs::default());
+
+        let args = CommandParser::<AlertArgs>::parse_from([
+            "reth",
+            "--alerts.webhook-url",
+            "https://hooks.slack.com/services/T0/B0/X",
+            "--alerts.format",
+            "slack",
+            "--alerts.reorg-depth",
+            "5",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            AlertArgs {
+                webhook_url: Some("https://hooks.slack.com/services/T0/B0/X".to_string()),
+                format: AlertFormat::Slack,
+                reorg_depth: 5,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -1,8 +1,8 @@
 //! clap [Args](clap::Args) for database configuration
 
-use crate::version::default_client_version;
+use crate::{args::utils::parse_byte_size, version::default_client_version};
 use clap::{
-    builder::{PossibleValue, TypedValueParser},
+    builder::{PossibleValue, RangedU64ValueParser, TypedValueParser},
     error::ErrorKind,
     Arg, Args, Command, Error,
 };
@@ -19,6 +19,40 @@ pub struct DatabaseArgs {
     /// NFS volume.
     #[arg(long = "db.exclusive")]
     pub exclusive: Option<bool>,
+    /// Step in which the database file grows (e.g. 4GB, 512MB).
+    #[arg(long = "db.growth-step", value_parser = parse_byte_size)]
+    pub growth_step: Option<usize>,
+    /// Amount of unused space at the end of the database file after which it is shrunk
+    /// (e.g. 8GB). By default the database never shrinks.
+    #[arg(long = "db.shrink-threshold", value_parser = parse_byte_size)]
+    pub shrink_threshold: Option<usize>,
+    /// Maximum number of concurrent database readers.
+    #[arg(long = "db.max-readers", value_parser = RangedU64ValueParser::<u64>::new().range(1..=32767))]
+    pub max_readers: Option<u64>,
+    /// Database page size (e.g. 4KB, 16KB). Must be a power of two between 256B and 64KB.
+    ///
+    /// Only applied when the database is created.
+    #[arg(long = "db.page-size", value_parser = parse_page_size)]
+    pub page_size: Option<usize>,
+    /// Keep an in-memory copy of the stage checkpoints, the last finalized and safe blocks and
+    /// recent canonical block hashes, which is invalidated whenever the database is written to.
+    #[arg(long = "db.hot-tables-cache")]
+    pub hot_tables_cache: bool,
+    /// Index the transactions by the 4-byte function selector they call while blocks are
+    /// executed, to serve `reth_getTransactionsBySelector`. Only covers the blocks executed while
+    /// the index is enabled.
+    #[arg(long = "db.selector-index")]
+    pub selector_index: bool,
+    /// Index the blocks by the addresses and topics of their logs while blocks are executed, so
+    /// that `eth_getLogs` skips blocks without matching logs. Only covers the blocks executed
+    /// while the index is enabled, use `reth db log-index build` to index earlier blocks.
+    #[arg(long = "db.log-index")]
+    pub log_index: bool,
+    /// Collect the access lists of the transactions, the accounts and storage slots they touched,
+    /// while blocks are executed, and store them in static files. Only covers the blocks executed
+    /// while collecting is enabled, starting with the next executed block.
+    #[arg(long = "db.access-lists")]
+    pub access_lists: bool,
 }
 
 impl DatabaseArgs {
@@ -27,9 +61,22 @@ impl DatabaseArgs {
         reth_db::mdbx::DatabaseArguments::new(default_client_version())
             .with_log_level(self.log_level)
             .with_exclusive(self.exclusive)
+            .with_growth_step(self.growth_step)
+            .with_shrink_threshold(self.shrink_threshold)
+            .with_max_readers(self.max_readers)
+            .with_page_size(self.page_size)
     }
 }
 
+/// Parses a database page size, ensuring it is a power of two within the range supported by MDBX.
+fn parse_page_size(arg: &str) -> eyre::Result<usize> {
+    let page_size = parse_byte_size(arg)?;
+    if !page_size.is_power_of_two() || !(256..=0x10000).contains(&page_size) {
+        eyre::bail!("page size must be a power of two between 256B and 64KB, got {page_size}")
+    }
+    Ok(page_size)
+}
+
 /// clap value parser for [`LogLevel`].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -132,6 +179,33 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_command_parser_with_geometry() {
+        let cmd = CommandParser::<DatabaseArgs>::try_parse_from([
+            "reth",
+            "--db.growth-step",
+            "1GB",
+            "--db.shrink-threshold",
+            "2GB",
+            "--db.max-readers",
+            "1024",
+            "--db.page-size",
+            "16KB",
+        ])
+        .unwrap();
+        assert_eq!(cmd.args.growth_step, Some(1024 * 1024 * 1024));
+        assert_eq!(cmd.args.shrink_threshold, Some(2 * 1024 * 1024 * 1024));
+        assert_eq!(cmd.args.max_readers, Some(1024));
+        assert_eq!(cmd.args.page_size, Some(16 * 1024));
+    }
+
+    #[test]
+    fn test_command_parser_with_invalid_page_size() {
+        let result =
+            CommandParser::<DatabaseArgs>::try_parse_from(["reth", "--db.page-size", "3000"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_command_parser_without_log_level() {
         let cmd = CommandParser::<DatabaseArgs>::try_parse_from(["reth"]).unwrap();
@@ -22,6 +22,13 @@ pub struct DebugArgs {
     #[arg(long = "debug.max-block", help_heading = "Debug")]
     pub max_block: Option<u64>,
 
+    /// Freezes the chain at the specified block.
+    ///
+    /// The node syncs up to the block and keeps serving RPC requests, but rejects forkchoice
+    /// updates and payloads that would advance the chain past it with an error.
+    #[arg(long = "debug.freeze-at-block", help_heading = "Debug", conflicts_with = "max_block")]
+    pub freeze_at_block: Option<u64>,
+
     /// Runs a fake consensus client that advances the chain using recent block hashes
     /// on Etherscan. If specified, requires an `ETHERSCAN_API_KEY` environment variable.
     #[arg(
@@ -42,6 +49,11 @@ pub struct DebugArgs {
     )]
     pub rpc_consensus_ws: Option<String>,
 
+    /// Compares every canonical block with the block of an external node at the given RPC URL
+    /// and reports hash, state root and receipts root mismatches via logs and metrics.
+    #[arg(long = "debug.shadow-rpc", help_heading = "Debug", value_name = "RPC_URL")]
+    pub shadow_rpc: Option<String>,
+
     /// If provided, the engine will skip `n` consecutive FCUs.
     #[arg(long = "debug.skip-fcu", help_heading = "Debug")]
     pub skip_fcu: Option<usize>,
@@ -51,10 +63,16 @@ pub struct DebugArgs {
     pub skip_new_payload: Option<usize>,
 
     /// The path to store engine API messages at.
-    /// If specified, all of the intercepted engine API messages
-    /// will be written to specified location.
+    /// If specified, all of the intercepted engine API messages and the responses of the engine
+    /// will be written to specified location, and can be replayed with `reth debug replay-engine`.
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
+
+    /// The directory to dump invalid blocks to.
+    /// If specified, every block that the engine marks as invalid is re-executed, and its
+    /// execution witness, pre-state, receipts and transaction traces are written to the directory.
+    #[arg(long = "debug.invalid-block-hook", help_heading = "Debug", value_name = "PATH")]
+    pub invalid_block_hook: Option<PathBuf>,
 }
 
 #[cfg(test)]
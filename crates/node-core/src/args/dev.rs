@@ -6,7 +6,7 @@ use clap::Args;
 use humantime::parse_duration;
 
 /// Parameters for Dev testnet configuration
-#[derive(Debug, Args, PartialEq, Eq, Default, Clone, Copy)]
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
 #[command(next_help_heading = "Dev testnet")]
 pub struct DevArgs {
     /// Start the node in dev mode
@@ -39,6 +39,56 @@ pub struct DevArgs {
         verbatim_doc_comment
     )]
     pub block_time: Option<Duration>,
+
+    /// Propose a block every `--dev.block-time` with a built-in consensus client.
+    ///
+    /// Instead of sealing blocks directly, the consensus client drives the engine like a beacon
+    /// node, by requesting payloads with forkchoice updates and sending them back as new payloads.
+    #[arg(long = "dev.consensus-client", help_heading = "Dev testnet", requires = "block_time")]
+    pub consensus_client: bool,
+
+    /// Skip slots without pending transactions instead of proposing empty blocks.
+    #[arg(
+        long = "dev.no-empty-blocks",
+        help_heading = "Dev testnet",
+        requires = "consensus_client"
+    )]
+    pub no_empty_blocks: bool,
+
+    /// Inject a reorg every N blocks proposed by the consensus client.
+    #[arg(
+        long = "dev.reorg-every",
+        help_heading = "Dev testnet",
+        value_name = "BLOCKS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        requires = "consensus_client"
+    )]
+    pub reorg_every: Option<u64>,
+
+    /// Number of blocks reverted by the injected reorgs.
+    #[arg(
+        long = "dev.reorg-depth",
+        help_heading = "Dev testnet",
+        value_name = "BLOCKS",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..=32),
+        requires = "reorg_every"
+    )]
+    pub reorg_depth: u64,
+}
+
+impl Default for DevArgs {
+    fn default() -> Self {
+        Self {
+            dev: false,
+            block_max_transactions: None,
+            block_time: None,
+            consensus_client: false,
+            no_empty_blocks: false,
+            reorg_every: None,
+            reorg_depth: 1,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -56,13 +106,13 @@ mod tests {
     #[test]
     fn test_parse_dev_args() {
         let args = CommandParser::<DevArgs>::parse_from(["reth"]).args;
-        assert_eq!(args, DevArgs { dev: false, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs::default());
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--dev"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from(["reth", "--auto-mine"]).args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: None, block_time: None });
+        assert_eq!(args, DevArgs { dev: true, ..Default::default() });
 
         let args = CommandParser::<DevArgs>::parse_from([
             "reth",
@@ -71,18 +121,78 @@ mod tests {
             "2",
         ])
         .args;
-        assert_eq!(args, DevArgs { dev: true, block_max_transactions: Some(2), block_time: None });
+        assert_eq!(
+            args,
+            DevArgs { dev: true, block_max_transactions: Some(2), ..Default::default() }
+        );
 
         let args =
             CommandParser::<DevArgs>::parse_from(["reth", "--dev", "--dev.block-time", "1s"]).args;
+        assert_eq!(
+            args,
+            DevArgs {
+                dev: true,
+                block_time: Some(std::time::Duration::from_secs(1)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_dev_consensus_client_args() {
+        let args = CommandParser::<DevArgs>::parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-time",
+            "12s",
+            "--dev.consensus-client",
+            "--dev.no-empty-blocks",
+            "--dev.reorg-every",
+            "10",
+            "--dev.reorg-depth",
+            "2",
+        ])
+        .args;
         assert_eq!(
             args,
             DevArgs {
                 dev: true,
                 block_max_transactions: None,
-                block_time: Some(std::time::Duration::from_secs(1))
+                block_time: Some(std::time::Duration::from_secs(12)),
+                consensus_client: true,
+                no_empty_blocks: true,
+                reorg_every: Some(10),
+                reorg_depth: 2,
             }
         );
+
+        // the consensus client proposes a block every slot
+        let args =
+            CommandParser::<DevArgs>::try_parse_from(["reth", "--dev", "--dev.consensus-client"]);
+        assert!(args.is_err());
+
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-time",
+            "12s",
+            "--dev.reorg-every",
+            "10",
+        ]);
+        assert!(args.is_err());
+
+        let args = CommandParser::<DevArgs>::try_parse_from([
+            "reth",
+            "--dev",
+            "--dev.block-time",
+            "12s",
+            "--dev.consensus-client",
+            "--dev.reorg-every",
+            "10",
+            "--dev.reorg-depth",
+            "33",
+        ]);
+        assert!(args.is_err());
     }
 
     #[test]
@@ -0,0 +1,104 @@
+//! clap [Args](clap::Args) for the consensus engine
+
+use clap::Args;
+
+/// The default maximum estimated size of the blocks persisted in a single commit, 256 MiB.
+pub const DEFAULT_PERSISTENCE_MAX_BYTES: usize = 256 * 1024 * 1024;
+
+/// Parameters for configuring the consensus engine.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Engine")]
+pub struct EngineArgs {
+    /// Maximum number of canonical blocks that are persisted in a single database commit.
+    ///
+    /// While the engine receives a burst of messages, e.g. when the consensus client catches up,
+    /// forkchoice updates are answered right away, and their blocks are only made canonical once
+    /// the burst is over or this limit is reached. Disabled by default.
+    #[arg(long = "engine.persistence-max-blocks", value_name = "BLOCKS", default_value_t = 0)]
+    pub persistence_max_blocks: u64,
+
+    /// Maximum estimated size in bytes of the canonical blocks that are persisted in a single
+    /// database commit.
+    #[arg(
+        long = "engine.persistence-max-bytes",
+        value_name = "BYTES",
+        default_value_t = DEFAULT_PERSISTENCE_MAX_BYTES
+    )]
+    pub persistence_max_bytes: usize,
+
+    /// Maximum estimated size in bytes of the blocks that are buffered until their parents are
+    /// known.
+    ///
+    /// Once exceeded, the least recently buffered blocks are evicted. By default only the number
+    /// of buffered blocks is limited.
+    #[arg(long = "engine.max-buffered-block-bytes", value_name = "BYTES")]
+    pub max_buffered_block_bytes: Option<usize>,
+
+    /// Maximum estimated size in bytes of the sidechain blocks and their execution outcomes held
+    /// in memory.
+    ///
+    /// Once exceeded, the least recently extended sidechains are evicted, together with the
+    /// sidechains that fork off them. By default sidechains are kept until they fall behind the
+    /// finalized block.
+    #[arg(long = "engine.max-sidechain-bytes", value_name = "BYTES")]
+    pub max_sidechain_bytes: Option<usize>,
+
+    /// Maximum estimated size in bytes of the sidechain execution outcomes held in memory.
+    ///
+    /// Once exceeded, the execution outcomes of the sidechains with the lowest tips are spilled to
+    /// a temporary directory, and read back when the sidechain is extended or made canonical. By
+    /// default all execution outcomes are held in memory.
+    #[arg(long = "engine.max-sidechain-state-bytes", value_name = "BYTES")]
+    pub max_sidechain_state_bytes: Option<usize>,
+}
+
+impl Default for EngineArgs {
+    fn default() -> Self {
+        Self {
+            persistence_max_blocks: 0,
+            persistence_max_bytes: DEFAULT_PERSISTENCE_MAX_BYTES,
+            max_buffered_block_bytes: None,
+            max_sidechain_bytes: None,
+            max_sidechain_state_bytes: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_engine_args() {
+        let args = CommandParser::<EngineArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, EngineArgs::default());
+
+        let args = CommandParser::<EngineArgs>::parse_from([
+            "reth",
+            "--engine.persistence-max-blocks",
+            "32",
+            "--engine.persistence-max-bytes",
+            "1024",
+            "--engine.max-buffered-block-bytes",
+            "2048",
+            "--engine.max-sidechain-bytes",
+            "4096",
+            "--engine.max-sidechain-state-bytes",
+            "8192",
+        ])
+        .args;
+        assert_eq!(args.persistence_max_blocks, 32);
+        assert_eq!(args.persistence_max_bytes, 1024);
+        assert_eq!(args.max_buffered_block_bytes, Some(2048));
+        assert_eq!(args.max_sidechain_bytes, Some(4096));
+        assert_eq!(args.max_sidechain_state_bytes, Some(8192));
+    }
+}
@@ -0,0 +1,54 @@
+//! clap [Args](clap::Args) for forking a remote network
+
+use clap::Args;
+
+/// Parameters for forking the state of a remote network.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Fork")]
+pub struct ForkArgs {
+    /// HTTP url of a node of the network to fork.
+    ///
+    /// The local dev chain falls back to the state of the remote node for all accounts and
+    /// storage slots that it didn't change itself. Remote state is fetched lazily and cached.
+    /// Requires `--dev`.
+    #[arg(long = "fork.url", value_name = "URL")]
+    pub url: Option<String>,
+
+    /// Block of the remote node to fork the state at.
+    ///
+    /// Defaults to the latest block of the remote node at startup.
+    #[arg(long = "fork.block", value_name = "BLOCK", requires = "url")]
+    pub block: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_fork_args() {
+        let args = CommandParser::<ForkArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ForkArgs::default());
+
+        let args = CommandParser::<ForkArgs>::parse_from([
+            "reth",
+            "--fork.url",
+            "http://localhost:8545",
+            "--fork.block",
+            "100",
+        ])
+        .args;
+        assert_eq!(args.url.as_deref(), Some("http://localhost:8545"));
+        assert_eq!(args.block, Some(100));
+
+        assert!(CommandParser::<ForkArgs>::try_parse_from(["reth", "--fork.block", "100"]).is_err());
+    }
+}
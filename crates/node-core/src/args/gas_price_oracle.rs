@@ -39,6 +39,28 @@ impl GasPriceOracleArgs {
             ..Default::default()
         }
     }
+
+    /// Returns the given [`GasPriceOracleConfig`], e.g. of the `[gas_price_oracle]` section of
+    /// `reth.toml`, with the arguments that differ from their defaults applied on top.
+    pub fn merge_gas_price_oracle_config(
+        &self,
+        mut config: GasPriceOracleConfig,
+    ) -> GasPriceOracleConfig {
+        let defaults = Self::default();
+        if self.blocks != defaults.blocks {
+            config.blocks = self.blocks;
+        }
+        if self.ignore_price != defaults.ignore_price {
+            config.ignore_price = Some(U256::from(self.ignore_price));
+        }
+        if self.max_price != defaults.max_price {
+            config.max_price = Some(U256::from(self.max_price));
+        }
+        if self.percentile != defaults.percentile {
+            config.percentile = self.percentile;
+        }
+        config
+    }
 }
 
 impl Default for GasPriceOracleArgs {
@@ -77,6 +99,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn merge_gpo_args_with_config() {
+        let config = GasPriceOracleConfig { blocks: 30, percentile: 80, ..Default::default() };
+
+        let args = CommandParser::<GasPriceOracleArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.merge_gas_price_oracle_config(config.clone()), config);
+
+        let args =
+            CommandParser::<GasPriceOracleArgs>::parse_from(["reth", "--gpo.percentile", "50"])
+                .args;
+        assert_eq!(
+            args.merge_gas_price_oracle_config(config.clone()),
+            GasPriceOracleConfig { percentile: 50, ..config }
+        );
+    }
+
     #[test]
     fn gpo_args_default_sanity_test() {
         let default_args = GasPriceOracleArgs::default();
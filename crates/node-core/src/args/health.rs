@@ -0,0 +1,87 @@
+//! clap [Args](clap::Args) for sync health monitoring
+
+use clap::Args;
+use humantime::parse_duration;
+use std::time::Duration;
+
+/// Parameters for monitoring the sync health of the node
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Sync health")]
+pub struct HealthArgs {
+    /// Period without sync progress, while the beacon client requests a head that is not synced
+    /// yet, after which the sync is reported as stalled.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --health.stall-threshold 5m
+    #[arg(
+        long = "health.stall-threshold",
+        help_heading = "Sync health",
+        default_value = "5m",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub stall_threshold: Duration,
+
+    /// Period without forkchoice updates from the beacon client after which a gap is reported.
+    ///
+    /// Parses strings using [`humantime::parse_duration`]
+    /// --health.forkchoice-gap-threshold 5m
+    #[arg(
+        long = "health.forkchoice-gap-threshold",
+        help_heading = "Sync health",
+        default_value = "5m",
+        value_parser = parse_duration,
+        verbatim_doc_comment
+    )]
+    pub forkchoice_gap_threshold: Duration,
+
+    /// URL that stalls and forkchoice gaps are posted to as JSON when they are detected.
+    #[arg(long = "health.webhook-url", help_heading = "Sync health", value_name = "URL")]
+    pub webhook_url: Option<String>,
+}
+
+impl Default for HealthArgs {
+    fn default() -> Self {
+        Self {
+            stall_threshold: Duration::from_secs(300),
+            forkchoice_gap_threshold: Duration::from_secs(300),
+            webhook_url: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_health_args() {
+        let args = CommandParser::<HealthArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, HealthArgs::default());
+
+        let args = CommandParser::<HealthArgs>::parse_from([
+            "reth",
+            "--health.stall-threshold",
+            "10m",
+            "--health.webhook-url",
+            "http://localhost:8080",
+        ])
+        .args;
+        assert_eq!(
+            args,
+            HealthArgs {
+                stall_threshold: Duration::from_secs(600),
+                webhook_url: Some("http://localhost:8080".to_string()),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -12,6 +12,18 @@ pub use rpc_server::RpcServerArgs;
 mod rpc_state_cache;
 pub use rpc_state_cache::RpcStateCacheArgs;
 
+/// `RpcAccessLogArgs` struct for configuring the RPC access log
+mod rpc_access_log;
+pub use rpc_access_log::RpcAccessLogArgs;
+
+/// `RpcUsageArgs` struct for configuring the RPC usage accounting
+mod rpc_usage;
+pub use rpc_usage::RpcUsageArgs;
+
+/// `RpcAuthArgs` struct for configuring the per-namespace authentication of the RPC servers
+mod rpc_auth;
+pub use rpc_auth::{RpcAuthArgs, RpcAuthTokens};
+
 /// DebugArgs struct for debugging purposes
 mod debug;
 pub use debug::DebugArgs;
@@ -47,6 +59,30 @@ pub use txpool::TxPoolArgs;
 mod dev;
 pub use dev::DevArgs;
 
+/// HealthArgs for configuring the sync health monitoring
+mod health;
+pub use health::HealthArgs;
+
+/// AlertArgs for configuring alerts on critical node events
+mod alerts;
+pub use alerts::{AlertArgs, AlertFormat};
+
+/// SpaceReportArgs for configuring the periodic reports of the disk space used by the node
+mod space_report;
+pub use space_report::SpaceReportArgs;
+
+/// ReorgGuardArgs for configuring the guard against deep reorgs
+mod reorg_guard;
+pub use reorg_guard::ReorgGuardArgs;
+
+/// ForkArgs for forking the state of a remote network
+mod fork;
+pub use fork::ForkArgs;
+
+/// EngineArgs for configuring the consensus engine
+mod engine;
+pub use engine::EngineArgs;
+
 /// PruneArgs for configuring the pruning and full node
 mod pruning;
 pub use pruning::PruningArgs;
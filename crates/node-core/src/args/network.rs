@@ -2,6 +2,7 @@
 
 use crate::version::P2P_CLIENT_VERSION;
 use clap::Args;
+use humantime::parse_duration;
 use reth_chainspec::{net::mainnet_nodes, ChainSpec};
 use reth_config::Config;
 use reth_discv4::{DEFAULT_DISCOVERY_ADDR, DEFAULT_DISCOVERY_PORT};
@@ -11,6 +12,12 @@ use reth_discv5::{
 };
 use reth_net_nat::NatResolver;
 use reth_network::{
+    eth_requests::{
+        EthRequestQuotas, DEFAULT_MAX_CONCURRENT_BODIES_REQUESTS,
+        DEFAULT_MAX_CONCURRENT_HEADERS_REQUESTS,
+        DEFAULT_MAX_CONCURRENT_POOLED_TRANSACTIONS_REQUESTS,
+        DEFAULT_MAX_CONCURRENT_RECEIPTS_REQUESTS, DEFAULT_MAX_QUEUED_REQUESTS,
+    },
     transactions::{
         TransactionFetcherConfig, TransactionsManagerConfig,
         DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
@@ -25,8 +32,12 @@ use std::{
     ops::Not,
     path::PathBuf,
     sync::Arc,
+    time::Duration,
 };
 
+/// The default interval at which the known peers are written to the peers file.
+const DEFAULT_PEERS_PERSIST_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 /// Parameters for configuring the network more granularity via CLI
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Networking")]
@@ -56,11 +67,27 @@ pub struct NetworkArgs {
     #[arg(long, default_value_t = 0)]
     pub dns_retries: usize,
 
-    /// The path to the known peers file. Connected peers are dumped to this file on nodes
-    /// shutdown, and read on startup. Cannot be used with `--no-persist-peers`.
-    #[arg(long, value_name = "FILE", verbatim_doc_comment, conflicts_with = "no_persist_peers")]
+    /// The path to the known peers file. Known peers are dumped to this file with their
+    /// reputation periodically and on nodes shutdown, and read on startup.
+    /// Cannot be used with `--no-persist-peers`.
+    ///
+    /// A copy of the file of another node, or the output of `admin_exportPeers`, can be used
+    /// to bootstrap a new node from the peers of that node.
+    #[arg(
+        long,
+        visible_alias = "network.peers-file",
+        value_name = "FILE",
+        verbatim_doc_comment,
+        conflicts_with = "no_persist_peers"
+    )]
     pub peers_file: Option<PathBuf>,
 
+    /// How often the known peers are written to the peers file, in addition to on shutdown.
+    ///
+    /// Set to 0 to only write the peers file on shutdown.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = "5m")]
+    pub peers_persist_interval: Duration,
+
     /// Custom node identity
     #[arg(long, value_name = "IDENTITY", default_value = P2P_CLIENT_VERSION)]
     pub identity: String,
@@ -115,6 +142,27 @@ pub struct NetworkArgs {
     /// Default is 128 KiB.
     #[arg(long = "pooled-tx-pack-soft-limit", value_name = "BYTES", default_value_t = DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ, verbatim_doc_comment)]
     pub soft_limit_byte_size_pooled_transactions_response_on_pack_request: usize,
+
+    /// Maximum number of concurrently served `GetBlockHeaders` requests.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_CONCURRENT_HEADERS_REQUESTS)]
+    pub max_concurrent_headers_requests: usize,
+
+    /// Maximum number of concurrently served `GetBlockBodies` requests.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_CONCURRENT_BODIES_REQUESTS)]
+    pub max_concurrent_bodies_requests: usize,
+
+    /// Maximum number of concurrently served `GetReceipts` requests.
+    #[arg(long, value_name = "COUNT", default_value_t = DEFAULT_MAX_CONCURRENT_RECEIPTS_REQUESTS)]
+    pub max_concurrent_receipts_requests: usize,
+
+    /// Maximum number of concurrently served `GetPooledTransactions` requests.
+    #[arg(long = "max-concurrent-pooled-tx-requests", value_name = "COUNT", default_value_t = DEFAULT_MAX_CONCURRENT_POOLED_TRANSACTIONS_REQUESTS)]
+    pub max_concurrent_pooled_transactions_requests: usize,
+
+    /// Maximum number of incoming eth requests of a type that wait to be served. Further requests
+    /// of that type are dropped.
+    #[arg(long = "max-queued-eth-requests", value_name = "COUNT", default_value_t = DEFAULT_MAX_QUEUED_REQUESTS)]
+    pub max_queued_eth_requests: usize,
 }
 
 impl NetworkArgs {
@@ -161,6 +209,14 @@ impl NetworkArgs {
             .boot_nodes(chain_bootnodes.clone())
             .chain_spec(chain_spec)
             .transactions_manager_config(transactions_manager_config)
+            .eth_request_quotas(EthRequestQuotas {
+                max_concurrent_headers_requests: self.max_concurrent_headers_requests,
+                max_concurrent_bodies_requests: self.max_concurrent_bodies_requests,
+                max_concurrent_receipts_requests: self.max_concurrent_receipts_requests,
+                max_concurrent_pooled_transactions_requests: self
+                    .max_concurrent_pooled_transactions_requests,
+                max_queued_requests: self.max_queued_eth_requests,
+            })
             // Configure node identity
             .apply(|builder| {
                 let peer_id = builder.get_peer_id();
@@ -222,6 +278,7 @@ impl Default for NetworkArgs {
             bootnodes: None,
             dns_retries: 0,
             peers_file: None,
+            peers_persist_interval: DEFAULT_PEERS_PERSIST_INTERVAL,
             identity: P2P_CLIENT_VERSION.to_string(),
             p2p_secret_key: None,
             no_persist_peers: false,
@@ -233,6 +290,12 @@ impl Default for NetworkArgs {
             soft_limit_byte_size_pooled_transactions_response:
                 SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESPONSE,
             soft_limit_byte_size_pooled_transactions_response_on_pack_request: DEFAULT_SOFT_LIMIT_BYTE_SIZE_POOLED_TRANSACTIONS_RESP_ON_PACK_GET_POOLED_TRANSACTIONS_REQ,
+            max_concurrent_headers_requests: DEFAULT_MAX_CONCURRENT_HEADERS_REQUESTS,
+            max_concurrent_bodies_requests: DEFAULT_MAX_CONCURRENT_BODIES_REQUESTS,
+            max_concurrent_receipts_requests: DEFAULT_MAX_CONCURRENT_RECEIPTS_REQUESTS,
+            max_concurrent_pooled_transactions_requests:
+                DEFAULT_MAX_CONCURRENT_POOLED_TRANSACTIONS_REQUESTS,
+            max_queued_eth_requests: DEFAULT_MAX_QUEUED_REQUESTS,
         }
     }
 }
@@ -429,6 +492,20 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_peers_file_args() {
+        let args = CommandParser::<NetworkArgs>::parse_from([
+            "reth",
+            "--network.peers-file",
+            "peers.json",
+            "--peers-persist-interval",
+            "30s",
+        ])
+        .args;
+        assert_eq!(args.peers_file, Some(PathBuf::from("peers.json")));
+        assert_eq!(args.peers_persist_interval, Duration::from_secs(30));
+    }
+
     #[cfg(not(feature = "optimism"))]
     #[test]
     fn network_args_default_sanity_test() {
@@ -2,21 +2,18 @@ use crate::{
     args::utils::parse_duration_from_secs, cli::config::PayloadBuilderConfig,
     version::default_extradata,
 };
-use clap::{
-    builder::{RangedU64ValueParser, TypedValueParser},
-    Arg, Args, Command,
-};
-use reth_primitives::constants::{
-    ETHEREUM_BLOCK_GAS_LIMIT, MAXIMUM_EXTRA_DATA_SIZE, SLOT_DURATION,
-};
-use std::{borrow::Cow, ffi::OsStr, time::Duration};
+use clap::{builder::RangedU64ValueParser, Args};
+use reth_primitives::constants::{ETHEREUM_BLOCK_GAS_LIMIT, SLOT_DURATION};
+use std::{borrow::Cow, time::Duration};
 
 /// Parameters for configuring the Payload Builder
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "Builder")]
 pub struct PayloadBuilderArgs {
     /// Block extra data set by the payload builder.
-    #[arg(long = "builder.extradata", value_parser = ExtradataValueParser::default(), default_value_t = default_extradata())]
+    ///
+    /// Must not exceed the maximum extra data size of the chain.
+    #[arg(long = "builder.extradata", default_value_t = default_extradata())]
     pub extradata: String,
 
     /// Target gas ceiling for built blocks.
@@ -70,37 +67,11 @@ impl PayloadBuilderConfig for PayloadBuilderArgs {
     }
 }
 
-#[derive(Clone, Debug, Default)]
-#[non_exhaustive]
-struct ExtradataValueParser;
-
-impl TypedValueParser for ExtradataValueParser {
-    type Value = String;
-
-    fn parse_ref(
-        &self,
-        _cmd: &Command,
-        _arg: Option<&Arg>,
-        value: &OsStr,
-    ) -> Result<Self::Value, clap::Error> {
-        let val =
-            value.to_str().ok_or_else(|| clap::Error::new(clap::error::ErrorKind::InvalidUtf8))?;
-        if val.as_bytes().len() > MAXIMUM_EXTRA_DATA_SIZE {
-            return Err(clap::Error::raw(
-                clap::error::ErrorKind::InvalidValue,
-                format!(
-                    "Payload builder extradata size exceeds {MAXIMUM_EXTRA_DATA_SIZE}-byte limit"
-                ),
-            ))
-        }
-        Ok(val.to_string())
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use clap::Parser;
+    use reth_primitives::constants::MAXIMUM_EXTRA_DATA_SIZE;
 
     /// A helper type to parse Args more easily
     #[derive(Parser)]
@@ -140,14 +111,16 @@ mod tests {
     }
 
     #[test]
-    fn test_invalid_extradata() {
+    fn test_extradata_exceeding_mainnet_limit() {
+        // the size is validated against the chain spec when the payload builder is launched
         let extradata = "x".repeat(MAXIMUM_EXTRA_DATA_SIZE + 1);
-        let args = CommandParser::<PayloadBuilderArgs>::try_parse_from([
+        let args = CommandParser::<PayloadBuilderArgs>::parse_from([
             "reth",
             "--builder.extradata",
             extradata.as_str(),
-        ]);
-        assert!(args.is_err());
+        ])
+        .args;
+        assert_eq!(args.extradata, extradata);
     }
 
     #[test]
@@ -0,0 +1,39 @@
+//! clap [Args](clap::Args) for the reorg guard
+
+use clap::Args;
+
+/// Parameters to guard the canonical chain against deep reorgs.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Reorg Guard")]
+pub struct ReorgGuardArgs {
+    /// Maximum number of canonical blocks a reorg can revert without a confirmation.
+    ///
+    /// Deeper reorgs are rejected and alerted until they are confirmed with the
+    /// `admin_confirmReorg` method, which is only served over IPC. Disabled by default.
+    #[arg(long = "reorg-guard.max-depth", value_name = "BLOCKS")]
+    pub max_depth: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_reorg_guard_args() {
+        let args = CommandParser::<ReorgGuardArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ReorgGuardArgs::default());
+
+        let args =
+            CommandParser::<ReorgGuardArgs>::parse_from(["reth", "--reorg-guard.max-depth", "64"])
+                .args;
+        assert_eq!(args.max_depth, Some(64));
+    }
+}
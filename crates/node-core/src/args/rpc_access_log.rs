@@ -0,0 +1,77 @@
+//! clap [Args](clap::Args) for the RPC access log
+
+use clap::Args;
+
+/// Parameters to configure the access log of the RPC servers.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "RPC Access Log")]
+pub struct RpcAccessLogArgs {
+    /// Log the method, params hash, latency, response size and error code of RPC requests.
+    ///
+    /// Requests are logged with the `rpc::access` target.
+    #[arg(long = "rpc.access-log")]
+    pub enabled: bool,
+
+    /// Log one out of every N requests. Failed requests are always logged.
+    #[arg(
+        long = "rpc.access-log.sample",
+        value_name = "N",
+        default_value_t = 1,
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub sample: u64,
+
+    /// Log the raw params of requests in addition to their hash.
+    #[arg(long = "rpc.access-log.params")]
+    pub params: bool,
+
+    /// Comma separated list of methods whose params, and params hash, are never logged.
+    #[arg(long = "rpc.access-log.redact", value_name = "METHODS", value_delimiter = ',')]
+    pub redact: Vec<String>,
+}
+
+impl Default for RpcAccessLogArgs {
+    fn default() -> Self {
+        Self { enabled: false, sample: 1, params: false, redact: Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_rpc_access_log_args() {
+        let default_args = RpcAccessLogArgs::default();
+        let args = CommandParser::<RpcAccessLogArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<RpcAccessLogArgs>::parse_from([
+            "reth",
+            "--rpc.access-log",
+            "--rpc.access-log.sample",
+            "10",
+            "--rpc.access-log.redact",
+            "eth_sendRawTransaction,eth_call",
+        ])
+        .args;
+        assert!(args.enabled);
+        assert_eq!(args.sample, 10);
+        assert_eq!(args.redact, ["eth_sendRawTransaction", "eth_call"]);
+
+        assert!(CommandParser::<RpcAccessLogArgs>::try_parse_from([
+            "reth",
+            "--rpc.access-log.sample",
+            "0"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! clap [Args](clap::Args) for the per-namespace authentication of the RPC servers
+
+use alloy_rpc_types_engine::JwtSecret;
+use clap::{ArgGroup, Args};
+use reth_rpc_server_types::RethRpcModule;
+use std::{fmt, fs, io};
+
+/// Parameters to configure the namespaces of the HTTP and WS servers that require
+/// authentication.
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(
+    next_help_heading = "RPC Namespace Auth",
+    group = ArgGroup::new("rpc_auth_credentials").args(["jwt_secret", "tokens"]).multiple(true)
+)]
+pub struct RpcAuthArgs {
+    /// Comma separated list of namespaces of the HTTP and WS servers that require
+    /// authentication, e.g. `admin,debug`.
+    ///
+    /// Requests to these namespaces must have an `Authorization: Bearer` header with a JWT signed
+    /// with `--rpc.auth.jwtsecret` or one of the tokens of `--rpc.auth.tokens`, all other
+    /// namespaces are open. WS connections are authenticated when they are established. IPC
+    /// requests never require authentication.
+    #[arg(
+        long = "rpc.auth.namespaces",
+        value_name = "NAMESPACES",
+        value_delimiter = ',',
+        value_parser = parse_namespace,
+        requires = "rpc_auth_credentials"
+    )]
+    pub namespaces: Vec<RethRpcModule>,
+
+    /// Hex encoded secret of the JWTs that authenticate requests to `--rpc.auth.namespaces`.
+    #[arg(long = "rpc.auth.jwtsecret", value_name = "HEX", requires = "namespaces")]
+    pub jwt_secret: Option<JwtSecret>,
+
+    /// Path to a file with the tokens that authenticate requests to `--rpc.auth.namespaces`, one
+    /// token per line.
+    #[arg(
+        long = "rpc.auth.tokens",
+        value_name = "FILE",
+        value_parser = parse_tokens,
+        requires = "namespaces"
+    )]
+    pub tokens: Option<RpcAuthTokens>,
+}
+
+/// The tokens of `--rpc.auth.tokens`.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RpcAuthTokens(pub Vec<String>);
+
+impl fmt::Debug for RpcAuthTokens {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // the tokens are secrets
+        write!(f, "RpcAuthTokens({} tokens)", self.0.len())
+    }
+}
+
+/// clap value parser for the namespaces of `--rpc.auth.namespaces`.
+///
+/// The methods of [`RethRpcModule::EthCallBundle`] are in the `eth` namespace, so it can't require
+/// authentication on its own.
+fn parse_namespace(value: &str) -> Result<RethRpcModule, String> {
+    match value.parse() {
+        Ok(RethRpcModule::EthCallBundle) => {
+            Err("the eth-call-bundle methods are in the eth namespace".to_string())
+        }
+        Ok(module) => Ok(module),
+        Err(_) => Err(format!(
+            "unknown namespace, possible values: {}",
+            RethRpcModule::all_variant_names().join(",")
+        )),
+    }
+}
+
+/// clap value parser for [`RpcAuthTokens`] that reads the tokens from the given file, ignoring
+/// empty lines.
+fn parse_tokens(path: &str) -> io::Result<RpcAuthTokens> {
+    let tokens = fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+        .map(ToString::to_string)
+        .collect();
+    Ok(RpcAuthTokens(tokens))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_rpc_auth_args() {
+        let default_args = RpcAuthArgs::default();
+        let args = CommandParser::<RpcAuthArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tokens");
+        fs::write(&path, "s3cr3t\n\n  t0k3n \n").unwrap();
+
+        let args = CommandParser::<RpcAuthArgs>::parse_from([
+            "reth",
+            "--rpc.auth.namespaces",
+            "admin,debug",
+            "--rpc.auth.tokens",
+            path.to_str().unwrap(),
+        ])
+        .args;
+        assert_eq!(args.namespaces, [RethRpcModule::Admin, RethRpcModule::Debug]);
+        assert_eq!(args.tokens, Some(RpcAuthTokens(vec!["s3cr3t".into(), "t0k3n".into()])));
+
+        // namespaces must be RPC modules
+        for namespace in ["admin,foo", "eth-call-bundle"] {
+            assert!(CommandParser::<RpcAuthArgs>::try_parse_from([
+                "reth",
+                "--rpc.auth.namespaces",
+                namespace,
+                "--rpc.auth.tokens",
+                path.to_str().unwrap(),
+            ])
+            .is_err());
+        }
+
+        // namespaces require credentials, and credentials require namespaces
+        assert!(CommandParser::<RpcAuthArgs>::try_parse_from([
+            "reth",
+            "--rpc.auth.namespaces",
+            "admin"
+        ])
+        .is_err());
+        assert!(CommandParser::<RpcAuthArgs>::try_parse_from([
+            "reth",
+            "--rpc.auth.tokens",
+            path.to_str().unwrap()
+        ])
+        .is_err());
+    }
+}
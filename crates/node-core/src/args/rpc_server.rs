@@ -2,7 +2,7 @@
 
 use crate::args::{
     types::{MaxU32, ZeroAsNoneU64},
-    GasPriceOracleArgs, RpcStateCacheArgs,
+    GasPriceOracleArgs, RpcAccessLogArgs, RpcAuthArgs, RpcStateCacheArgs, RpcUsageArgs,
 };
 use alloy_rpc_types_engine::JwtSecret;
 use clap::{
@@ -11,8 +11,12 @@ use clap::{
 };
 use rand::Rng;
 use reth_rpc::eth::RPC_DEFAULT_GAS_CAP;
+use reth_transaction_pool::DEFAULT_PRICE_BUMP;
 
-use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+use reth_rpc_server_types::{
+    constants, rate_limit::RpcRateLimitConfigError, RethRpcModule, RpcModuleSelection,
+    RpcRateLimitConfig,
+};
 use std::{
     ffi::OsStr,
     net::{IpAddr, Ipv4Addr},
@@ -77,14 +81,38 @@ pub struct RpcServerArgs {
     #[arg(long = "ws.api", value_parser = RpcModuleSelectionValueParser::default())]
     pub ws_api: Option<RpcModuleSelection>,
 
+    /// Enable GraphQL (EIP-1767) on the HTTP-RPC server, at the `/graphql` path
+    #[arg(long, requires = "http")]
+    pub graphql: bool,
+
+    /// Enable the gRPC gateway for the eth and debug namespaces
+    #[arg(long)]
+    pub grpc: bool,
+
+    /// gRPC server address to listen on
+    #[arg(long = "grpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
+    pub grpc_addr: IpAddr,
+
+    /// gRPC server port to listen on
+    #[arg(long = "grpc.port", default_value_t = constants::DEFAULT_GRPC_PORT)]
+    pub grpc_port: u16,
+
     /// Disable the IPC-RPC server
     #[arg(long)]
     pub ipcdisable: bool,
 
     /// Filename for IPC socket/pipe within the datadir
+    ///
+    /// On Windows, the IPC server listens on a named pipe. Paths that don't start with
+    /// `\\.\pipe\` are turned into the named pipe with the path as its name, with backslashes
+    /// replaced by slashes.
     #[arg(long, default_value_t = constants::DEFAULT_IPC_ENDPOINT.to_string())]
     pub ipcpath: String,
 
+    /// Rpc Modules to be configured for the IPC server, all modules by default
+    #[arg(long = "ipc.api", value_parser = RpcModuleSelectionValueParser::default())]
+    pub ipc_api: Option<RpcModuleSelection>,
+
     /// Auth server address to listen on
     #[arg(long = "authrpc.addr", default_value_t = IpAddr::V4(Ipv4Addr::LOCALHOST))]
     pub auth_addr: IpAddr,
@@ -134,6 +162,13 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-connections", alias = "rpc-max-connections", value_name = "COUNT", default_value_t = RPC_DEFAULT_MAX_CONNECTIONS.into())]
     pub rpc_max_connections: MaxU32,
 
+    /// Path to a TOML file with per-connection and per-method rate limits of the RPC servers.
+    ///
+    /// Requests with an `x-api-key` header are limited according to the tier of the key, all
+    /// other requests according to the default tier.
+    #[arg(long = "rpc.rate-limits", value_name = "FILE", value_parser = parse_rate_limits)]
+    pub rpc_rate_limits: Option<RpcRateLimitConfig>,
+
     /// Maximum number of concurrent tracing requests.
     #[arg(long = "rpc.max-tracing-requests", alias = "rpc-max-tracing-requests", value_name = "COUNT", default_value_t = constants::default_max_tracing_requests())]
     pub rpc_max_tracing_requests: usize,
@@ -146,6 +181,17 @@ pub struct RpcServerArgs {
     #[arg(long = "rpc.max-logs-per-response", alias = "rpc-max-logs-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64))]
     pub rpc_max_logs_per_response: ZeroAsNoneU64,
 
+    /// Maximum number of traces that `trace_filter` can return in a single response, larger
+    /// results must be paginated with `after` and `count`. (0 = no limit)
+    #[arg(long = "rpc.max-traces-per-response", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TRACES_PER_RESPONSE as u64))]
+    pub rpc_max_traces_per_response: ZeroAsNoneU64,
+
+    /// Maximum number of transactions that `txpool_content` and `txpool_inspect` can return in a
+    /// single response, larger pools must be paginated with `txpool_contentPage` and
+    /// `txpool_inspectPage`. (0 = no limit)
+    #[arg(long = "rpc.max-txpool-content", value_name = "COUNT", default_value_t = ZeroAsNoneU64::new(constants::DEFAULT_MAX_TXPOOL_CONTENT as u64))]
+    pub rpc_max_txpool_content: ZeroAsNoneU64,
+
     /// Maximum gas limit for `eth_call` and call tracing RPC methods.
     #[arg(
         long = "rpc.gascap",
@@ -156,6 +202,37 @@ pub struct RpcServerArgs {
     )]
     pub rpc_gas_cap: u64,
 
+    /// Maximum gas that precompiles may use in a single call of `eth_call`, `eth_callMany`,
+    /// `eth_estimateGas`, `eth_createAccessList`, `debug_traceCall`, `debug_traceCallMany`,
+    /// `trace_call` and `trace_callMany`.
+    ///
+    /// Calls whose precompile calls use more gas are rejected. Transactions of blocks that are
+    /// replayed, e.g. by `debug_traceTransaction`, are not limited. Unlimited by default.
+    #[arg(
+        long = "rpc.precompile-gas-budget",
+        value_name = "GAS",
+        value_parser = RangedU64ValueParser::<u64>::new().range(1..)
+    )]
+    pub rpc_precompile_gas_budget: Option<u64>,
+
+    /// Include hints about the failure in the error data of reverted `eth_call` and
+    /// `eth_estimateGas` calls.
+    ///
+    /// Reverted calls are executed again to find the call frame the revert originates from. The
+    /// error data then is an object with the revert output, the decoded revert reason, the
+    /// address, selector and gas of that frame, and the gas used until the call failed.
+    #[arg(long = "rpc.revert-hints")]
+    pub rpc_revert_hints: bool,
+
+    /// Percentage by which `reth_cancelTransaction` bumps the fees of the replacement of a
+    /// cancelled transaction.
+    #[arg(
+        long = "rpc.cancel-price-bump",
+        value_name = "PERCENT",
+        default_value_t = DEFAULT_PRICE_BUMP
+    )]
+    pub rpc_cancel_price_bump: u128,
+
     /// State cache configuration.
     #[command(flatten)]
     pub rpc_state_cache: RpcStateCacheArgs,
@@ -163,6 +240,18 @@ pub struct RpcServerArgs {
     /// Gas price oracle configuration.
     #[command(flatten)]
     pub gas_price_oracle: GasPriceOracleArgs,
+
+    /// Access log configuration.
+    #[command(flatten)]
+    pub rpc_access_log: RpcAccessLogArgs,
+
+    /// Usage accounting configuration.
+    #[command(flatten)]
+    pub rpc_usage: RpcUsageArgs,
+
+    /// Per-namespace authentication configuration.
+    #[command(flatten)]
+    pub rpc_auth: RpcAuthArgs,
 }
 
 impl RpcServerArgs {
@@ -188,6 +277,7 @@ impl RpcServerArgs {
     /// * The `auth_port` is scaled by a factor of `instance * 100`
     /// * The `http_port` is scaled by a factor of `-instance`
     /// * The `ws_port` is scaled by a factor of `instance * 2`
+    /// * The `grpc_port` is scaled by a factor of `instance * 100`
     /// * The `ipcpath` is appended with the instance number: `/tmp/reth.ipc-<instance>`
     ///
     /// # Panics
@@ -206,6 +296,8 @@ impl RpcServerArgs {
         self.http_port -= instance - 1;
         // ws port is scaled by a factor of instance * 2
         self.ws_port += instance * 2 - 2;
+        // grpc port is scaled by a factor of instance * 100
+        self.grpc_port += instance * 100 - 100;
 
         // if multiple instances are being run, append the instance number to the ipc path
         if instance > 1 {
@@ -227,6 +319,13 @@ impl RpcServerArgs {
         self
     }
 
+    /// Set the gRPC port to zero, to allow the OS to assign a random unused port when the gRPC
+    /// server binds to a socket.
+    pub const fn with_grpc_unused_port(mut self) -> Self {
+        self.grpc_port = 0;
+        self
+    }
+
     /// Set the auth port to zero, to allow the OS to assign a random unused port when the rpc
     /// server binds to a socket.
     pub const fn with_auth_unused_port(mut self) -> Self {
@@ -251,6 +350,7 @@ impl RpcServerArgs {
     pub fn with_unused_ports(mut self) -> Self {
         self = self.with_http_unused_port();
         self = self.with_ws_unused_port();
+        self = self.with_grpc_unused_port();
         self = self.with_auth_unused_port();
         self = self.with_ipc_random_path();
         self
@@ -270,8 +370,13 @@ impl Default for RpcServerArgs {
             ws_port: constants::DEFAULT_WS_RPC_PORT,
             ws_allowed_origins: None,
             ws_api: None,
+            graphql: false,
+            grpc: false,
+            grpc_addr: Ipv4Addr::LOCALHOST.into(),
+            grpc_port: constants::DEFAULT_GRPC_PORT,
             ipcdisable: false,
             ipcpath: constants::DEFAULT_IPC_ENDPOINT.to_string(),
+            ipc_api: None,
             auth_addr: Ipv4Addr::LOCALHOST.into(),
             auth_port: constants::DEFAULT_AUTH_PORT,
             auth_jwtsecret: None,
@@ -282,16 +387,30 @@ impl Default for RpcServerArgs {
             rpc_max_response_size: RPC_DEFAULT_MAX_RESPONSE_SIZE_MB.into(),
             rpc_max_subscriptions_per_connection: RPC_DEFAULT_MAX_SUBS_PER_CONN.into(),
             rpc_max_connections: RPC_DEFAULT_MAX_CONNECTIONS.into(),
+            rpc_rate_limits: None,
             rpc_max_tracing_requests: constants::default_max_tracing_requests(),
             rpc_max_blocks_per_filter: constants::DEFAULT_MAX_BLOCKS_PER_FILTER.into(),
             rpc_max_logs_per_response: (constants::DEFAULT_MAX_LOGS_PER_RESPONSE as u64).into(),
+            rpc_max_traces_per_response: (constants::DEFAULT_MAX_TRACES_PER_RESPONSE as u64).into(),
+            rpc_max_txpool_content: (constants::DEFAULT_MAX_TXPOOL_CONTENT as u64).into(),
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            rpc_precompile_gas_budget: None,
+            rpc_revert_hints: false,
+            rpc_cancel_price_bump: DEFAULT_PRICE_BUMP,
             gas_price_oracle: GasPriceOracleArgs::default(),
             rpc_state_cache: RpcStateCacheArgs::default(),
+            rpc_access_log: RpcAccessLogArgs::default(),
+            rpc_usage: RpcUsageArgs::default(),
+            rpc_auth: RpcAuthArgs::default(),
         }
     }
 }
 
+/// clap value parser for [`RpcRateLimitConfig`] that reads the config from the given file.
+fn parse_rate_limits(path: &str) -> Result<RpcRateLimitConfig, RpcRateLimitConfigError> {
+    RpcRateLimitConfig::from_path(path)
+}
+
 /// clap value parser for [`RpcModuleSelection`].
 #[derive(Clone, Debug, Default)]
 #[non_exhaustive]
@@ -35,6 +35,12 @@ pub struct RpcStateCacheArgs {
         default_value_t = DEFAULT_CONCURRENT_DB_REQUESTS,
     )]
     pub max_concurrent_db_requests: usize,
+
+    /// Max number of `eth_call` outputs in cache.
+    ///
+    /// Outputs are cached by block hash and call request. Disabled by default.
+    #[arg(long = "rpc-cache.max-calls", default_value_t = 0)]
+    pub max_calls: u32,
 }
 
 impl Default for RpcStateCacheArgs {
@@ -44,6 +50,7 @@ impl Default for RpcStateCacheArgs {
             max_receipts: DEFAULT_RECEIPT_CACHE_MAX_LEN,
             max_envs: DEFAULT_ENV_CACHE_MAX_LEN,
             max_concurrent_db_requests: DEFAULT_CONCURRENT_DB_REQUESTS,
+            max_calls: 0,
         }
     }
 }
@@ -0,0 +1,104 @@
+//! clap [Args](clap::Args) for the RPC usage accounting
+
+use clap::Args;
+use humantime::parse_duration;
+use std::{path::PathBuf, time::Duration};
+
+/// Parameters to configure the usage accounting of the RPC servers.
+#[derive(Debug, Clone, Args, PartialEq, Eq)]
+#[command(next_help_heading = "RPC Usage")]
+pub struct RpcUsageArgs {
+    /// Account the requests by method, the compute units and the response bytes of every API key
+    /// of the `--rpc.rate-limits` tiers.
+    ///
+    /// Requests without a known API key are accounted together. The usage is persisted to a file,
+    /// exported as metrics and can be queried with `admin_rpcUsage` on transports with the
+    /// `admin` namespace.
+    #[arg(long = "rpc.usage")]
+    pub enabled: bool,
+
+    /// The file the usage is persisted to. Defaults to `rpc-usage.json` in the datadir.
+    #[arg(long = "rpc.usage.file", value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// How often the usage is written to the file, in addition to on shutdown.
+    ///
+    /// Set to 0 to only write the file on shutdown.
+    #[arg(
+        long = "rpc.usage.persist-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "1m"
+    )]
+    pub persist_interval: Duration,
+
+    /// Comma separated list of `METHOD=UNITS` pairs that override the compute units of methods.
+    #[arg(
+        long = "rpc.usage.compute-units",
+        value_name = "METHOD=UNITS",
+        value_delimiter = ',',
+        value_parser = parse_compute_units
+    )]
+    pub compute_units: Vec<(String, u64)>,
+}
+
+impl Default for RpcUsageArgs {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            file: None,
+            persist_interval: Duration::from_secs(60),
+            compute_units: Vec::new(),
+        }
+    }
+}
+
+/// Parses a `METHOD=UNITS` pair.
+fn parse_compute_units(value: &str) -> eyre::Result<(String, u64)> {
+    let (method, units) =
+        value.split_once('=').ok_or_else(|| eyre::eyre!("expected METHOD=UNITS: {value}"))?;
+    Ok((method.to_string(), units.parse()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_rpc_usage_args() {
+        let default_args = RpcUsageArgs::default();
+        let args = CommandParser::<RpcUsageArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, default_args);
+
+        let args = CommandParser::<RpcUsageArgs>::parse_from([
+            "reth",
+            "--rpc.usage",
+            "--rpc.usage.persist-interval",
+            "5m",
+            "--rpc.usage.compute-units",
+            "eth_call=40,debug_traceCall=1000",
+        ])
+        .args;
+        assert!(args.enabled);
+        assert_eq!(args.persist_interval, Duration::from_secs(300));
+        assert_eq!(
+            args.compute_units,
+            [("eth_call".to_string(), 40), ("debug_traceCall".to_string(), 1000)]
+        );
+
+        assert!(CommandParser::<RpcUsageArgs>::try_parse_from([
+            "reth",
+            "--rpc.usage.compute-units",
+            "eth_call"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! clap [Args](clap::Args) for reports of the disk space used by the node
+
+use clap::Args;
+
+/// Parameters for the periodic reports of the disk space used by the node
+#[derive(Debug, Clone, Copy, Args, PartialEq, Eq)]
+#[command(next_help_heading = "Space report")]
+pub struct SpaceReportArgs {
+    /// Number of epochs of canonical blocks between reports of the disk space used by the node.
+    ///
+    /// A report is taken whenever the canonical chain crosses a multiple of this many epochs. The
+    /// default of 225 epochs is roughly a day on mainnet. Set to 0 to disable the reports.
+    #[arg(long = "space-report.epochs", help_heading = "Space report", default_value_t = 225)]
+    pub epochs: u64,
+}
+
+impl Default for SpaceReportArgs {
+    fn default() -> Self {
+        Self { epochs: 225 }
+    }
+}
+
+impl SpaceReportArgs {
+    /// Returns `true` if the periodic reports are enabled.
+    pub const fn is_enabled(&self) -> bool {
+        self.epochs > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_space_report_args() {
+        let args = CommandParser::<SpaceReportArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, SpaceReportArgs::default());
+        assert!(args.is_enabled());
+
+        let args =
+            CommandParser::<SpaceReportArgs>::parse_from(["reth", "--space-report.epochs", "0"])
+                .args;
+        assert_eq!(args, SpaceReportArgs { epochs: 0 });
+        assert!(!args.is_enabled());
+    }
+}
@@ -4,11 +4,13 @@ use crate::cli::config::RethTransactionPoolConfig;
 use clap::Args;
 use reth_primitives::Address;
 use reth_transaction_pool::{
-    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+    blobstore::disk::DEFAULT_MAX_CACHED_BLOBS, locals::DEFAULT_REBROADCAST_INTERVAL,
+    validate::DEFAULT_MAX_TX_INPUT_BYTES, EvictionPolicy, FeeEvictionPolicy,
+    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_EVICTION_FREE_SLOTS,
+    DEFAULT_PRICE_BUMP, REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
     TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
+use std::sync::Arc;
 /// Parameters for debugging purposes
 #[derive(Debug, Clone, Args, PartialEq, Eq)]
 #[command(next_help_heading = "TxPool")]
@@ -38,6 +40,26 @@ pub struct TxPoolArgs {
     #[arg(long = "txpool.max-account-slots", alias = "txpool.max_account_slots", default_value_t = TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER)]
     pub max_account_slots: usize,
 
+    /// Max combined size in bytes of the transactions of an account, unlimited by default
+    #[arg(long = "txpool.max-account-bytes", value_name = "BYTES")]
+    pub max_account_bytes: Option<usize>,
+
+    /// Evict the transactions of full sub-pools by their max fee per gas, lowered for senders
+    /// with many transactions in the pool and for old transactions.
+    ///
+    /// By default every sub-pool evicts by its own order.
+    #[arg(long = "txpool.fee-eviction")]
+    pub fee_eviction: bool,
+
+    /// Number of transactions of a sender before the fee eviction lowers their scores.
+    #[arg(
+        long = "txpool.fee-eviction-free-slots",
+        value_name = "COUNT",
+        default_value_t = DEFAULT_EVICTION_FREE_SLOTS,
+        requires = "fee_eviction"
+    )]
+    pub fee_eviction_free_slots: usize,
+
     /// Price bump (in %) for the transaction pool underpriced check.
     #[arg(long = "txpool.pricebump", default_value_t = DEFAULT_PRICE_BUMP)]
     pub price_bump: u128,
@@ -63,6 +85,47 @@ pub struct TxPoolArgs {
     /// Flag to toggle local transaction propagation.
     #[arg(long = "txpool.no-local-transactions-propagation")]
     pub no_local_transactions_propagation: bool,
+
+    /// Prefetch the state trie paths of up to this many of the best pending transactions after
+    /// every new block, to speed up computing the state root of the next block.
+    ///
+    /// The trie nodes on the paths of the senders, recipients and access lists of the transactions
+    /// are read into the page cache of the database. Disabled by default.
+    #[arg(long = "txpool.trie-prefetch-txs", value_name = "COUNT")]
+    pub trie_prefetch_transactions: Option<usize>,
+
+    /// Persist the transactions of all sub-pools on shutdown and reinsert them on startup.
+    ///
+    /// Local transactions and the transactions that are propagated to peers are persisted, with
+    /// the sidecars of blob transactions. All transactions are validated again when they are
+    /// reinserted. Without this flag only the local transactions are backed up.
+    #[arg(long = "txpool.persist")]
+    pub persist: bool,
+
+    /// Track the local transactions until they are mined, and reinsert the ones that were dropped
+    /// from the pool every `--txpool.locals-rebroadcast-interval` blocks.
+    ///
+    /// Senders whose local transactions are blocked by a nonce gap are reported. The tracked
+    /// transactions are listed with `admin_localTransactions` and can be removed with
+    /// `admin_removeLocalTransaction`.
+    #[arg(long = "txpool.track-locals")]
+    pub track_locals: bool,
+
+    /// Number of blocks between the rebroadcasts of the tracked local transactions.
+    #[arg(
+        long = "txpool.locals-rebroadcast-interval",
+        value_name = "BLOCKS",
+        default_value_t = DEFAULT_REBROADCAST_INTERVAL,
+        requires = "track_locals"
+    )]
+    pub locals_rebroadcast_interval: u64,
+
+    /// Reject the transactions from or to any of these addresses, comma separated.
+    ///
+    /// The addresses are checked before the transactions are validated, local transactions
+    /// included.
+    #[arg(long = "txpool.deny-list", value_name = "ADDRESSES", value_delimiter = ',')]
+    pub deny_list: Vec<Address>,
 }
 
 impl Default for TxPoolArgs {
@@ -75,6 +138,9 @@ impl Default for TxPoolArgs {
             queued_max_count: TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
             queued_max_size: TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_account_bytes: None,
+            fee_eviction: false,
+            fee_eviction_free_slots: DEFAULT_EVICTION_FREE_SLOTS,
             price_bump: DEFAULT_PRICE_BUMP,
             blob_transaction_price_bump: REPLACE_BLOB_PRICE_BUMP,
             max_tx_input_bytes: DEFAULT_MAX_TX_INPUT_BYTES,
@@ -82,6 +148,11 @@ impl Default for TxPoolArgs {
             no_locals: false,
             locals: Default::default(),
             no_local_transactions_propagation: false,
+            trie_prefetch_transactions: None,
+            persist: false,
+            track_locals: false,
+            locals_rebroadcast_interval: DEFAULT_REBROADCAST_INTERVAL,
+            deny_list: Vec::new(),
         }
     }
 }
@@ -94,6 +165,8 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 no_exemptions: self.no_locals,
                 local_addresses: self.locals.clone().into_iter().collect(),
                 propagate_local_transactions: !self.no_local_transactions_propagation,
+                journal: self.track_locals,
+                ..Default::default()
             },
             pending_limit: SubPoolLimit {
                 max_txs: self.pending_max_count,
@@ -112,10 +185,15 @@ impl RethTransactionPoolConfig for TxPoolArgs {
                 max_size: self.queued_max_size * 1024 * 1024,
             },
             max_account_slots: self.max_account_slots,
+            max_account_size: self.max_account_bytes,
             price_bumps: PriceBumpConfig {
                 default_price_bump: self.price_bump,
                 replace_blob_tx_price_bump: self.blob_transaction_price_bump,
             },
+            eviction_policy: self.fee_eviction.then(|| {
+                Arc::new(FeeEvictionPolicy::new(self.fee_eviction_free_slots))
+                    as Arc<dyn EvictionPolicy>
+            }),
         }
     }
 }
@@ -138,4 +216,49 @@ mod tests {
         let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
         assert_eq!(args, default_args);
     }
+
+    #[test]
+    fn txpool_args_track_locals() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.track-locals",
+            "--txpool.locals-rebroadcast-interval",
+            "5",
+        ])
+        .args;
+        assert!(args.pool_config().local_transactions_config.journal);
+        assert_eq!(args.locals_rebroadcast_interval, 5);
+
+        // the interval requires tracking the local transactions
+        assert!(CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.locals-rebroadcast-interval",
+            "5"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn txpool_args_fee_eviction() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.fee-eviction",
+            "--txpool.fee-eviction-free-slots",
+            "2",
+        ])
+        .args;
+        assert!(args.pool_config().eviction_policy.is_some());
+        assert!(TxPoolArgs::default().pool_config().eviction_policy.is_none());
+    }
+
+    #[test]
+    fn txpool_args_deny_list() {
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.deny-list",
+            "0x0000000000000000000000000000000000000001,0x0000000000000000000000000000000000000002",
+        ])
+        .args;
+        assert_eq!(args.deny_list, [Address::with_last_byte(1), Address::with_last_byte(2)]);
+    }
 }
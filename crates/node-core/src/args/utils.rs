@@ -33,6 +33,25 @@ pub fn parse_duration_from_secs(arg: &str) -> eyre::Result<Duration, std::num::P
     Ok(Duration::from_secs(seconds))
 }
 
+/// Parse a byte size from a `str`, e.g. `4096`, `64KB`, `4GB` or `1TB`.
+///
+/// Units are binary, i.e. `1KB` is 1024 bytes.
+pub fn parse_byte_size(arg: &str) -> eyre::Result<usize> {
+    let arg = arg.trim();
+    let split = arg.find(|c: char| !c.is_ascii_digit()).unwrap_or(arg.len());
+    let (number, unit) = arg.split_at(split);
+    let number: usize = number.parse()?;
+    let multiplier: usize = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "KB" => 1024,
+        "MB" => 1024 * 1024,
+        "GB" => 1024 * 1024 * 1024,
+        "TB" => 1024 * 1024 * 1024 * 1024,
+        unit => eyre::bail!("invalid byte size unit: {unit}"),
+    };
+    number.checked_mul(multiplier).ok_or_else(|| eyre::eyre!("byte size overflow: {arg}"))
+}
+
 /// The help info for the --chain flag
 pub fn chain_help() -> String {
     format!("The chain this node is running.\nPossible values are either a built-in chain or the path to a chain specification file.\n\nBuilt-in chains:\n    {}", SUPPORTED_CHAINS.join(", "))
@@ -149,6 +168,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_byte_sizes() {
+        assert_eq!(parse_byte_size("4096").unwrap(), 4096);
+        assert_eq!(parse_byte_size("64KB").unwrap(), 64 * 1024);
+        assert_eq!(parse_byte_size("4gb").unwrap(), 4 * 1024 * 1024 * 1024);
+        assert!(parse_byte_size("4XB").is_err());
+        assert!(parse_byte_size("GB").is_err());
+    }
+
     #[test]
     fn parse_socket_addresses() {
         for value in ["localhost:9000", ":9000", "9000"] {
@@ -315,6 +315,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("known-peers.json")
     }
 
+    /// Returns the path to the RPC usage file for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/rpc-usage.json`
+    pub fn rpc_usage(&self) -> PathBuf {
+        self.data_dir().join("rpc-usage.json")
+    }
+
     /// Returns the path to the blobstore directory for this chain where blobs of unfinalized
     /// transactions are stored.
     ///
@@ -330,6 +337,14 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the file the transactions of the pool are persisted to with
+    /// `--txpool.persist`
+    ///
+    /// `<DIR>/<CHAIN_ID>/txpool-transactions.rlp`
+    pub fn txpool_persisted_transactions(&self) -> PathBuf {
+        self.data_dir().join("txpool-transactions.rlp")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -1,23 +1,34 @@
-//! Stores engine API messages to disk for later inspection and replay.
+//! Stores engine API messages and the responses of the engine to disk for later inspection and
+//! replay.
 
-use futures::{Stream, StreamExt};
-use reth_beacon_consensus::BeaconEngineMessage;
+use futures::{future::BoxFuture, FutureExt, Stream};
+use reth_beacon_consensus::{
+    BeaconEngineMessage, BeaconForkChoiceUpdateError, BeaconOnNewPayloadError, ForkchoiceStatus,
+};
 use reth_engine_primitives::EngineTypes;
 use reth_fs_util as fs;
 use reth_rpc_types::{
-    engine::{CancunPayloadFields, ForkchoiceState},
+    engine::{
+        CancunPayloadFields, ForkchoiceState, ForkchoiceUpdateError, ForkchoiceUpdated,
+        PayloadStatus,
+    },
     ExecutionPayload,
 };
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    mem,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The extension of the files that store the responses to engine API messages.
+const RESPONSE_EXTENSION: &str = "response.json";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -38,8 +49,63 @@ pub enum StoredEngineApiMessage<Attributes> {
     },
 }
 
+/// The response of the engine to an engine API message that has been stored to disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The status the engine assigned to the forkchoice state of an `engine_forkchoiceUpdated`
+    /// call.
+    ForkchoiceUpdated(ForkchoiceStatus),
+    /// The payload status returned for an `engine_newPayload` call.
+    NewPayload(PayloadStatus),
+    /// The engine failed to process the call.
+    Error(String),
+}
+
+impl StoredEngineApiResponse {
+    /// Creates the stored response from the result of an `engine_forkchoiceUpdated` call.
+    pub fn from_forkchoice_updated(
+        result: &Result<ForkchoiceUpdated, BeaconForkChoiceUpdateError>,
+    ) -> Self {
+        match result {
+            Ok(updated) => Self::ForkchoiceUpdated(updated.payload_status.status.clone().into()),
+            // the forkchoice state itself is valid if only the payload attributes are invalid
+            Err(BeaconForkChoiceUpdateError::ForkchoiceUpdateError(
+                ForkchoiceUpdateError::UpdatedInvalidPayloadAttributes,
+            )) => Self::ForkchoiceUpdated(ForkchoiceStatus::Valid),
+            Err(BeaconForkChoiceUpdateError::ForkchoiceUpdateError(_)) => {
+                Self::ForkchoiceUpdated(ForkchoiceStatus::Invalid)
+            }
+            Err(err) => Self::Error(err.to_string()),
+        }
+    }
+
+    /// Creates the stored response from the result of an `engine_newPayload` call.
+    pub fn from_new_payload(result: &Result<PayloadStatus, BeaconOnNewPayloadError>) -> Self {
+        match result {
+            Ok(status) => Self::NewPayload(status.clone()),
+            Err(err) => Self::Error(err.to_string()),
+        }
+    }
+
+    /// Returns true if both responses have the same status.
+    ///
+    /// Validation and error messages are not compared, because they may differ between versions.
+    pub fn same_status(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::ForkchoiceUpdated(status), Self::ForkchoiceUpdated(other)) => status == other,
+            (Self::NewPayload(status), Self::NewPayload(other)) => {
+                mem::discriminant(&status.status) == mem::discriminant(&other.status) &&
+                    status.latest_valid_hash == other.latest_valid_hash
+            }
+            (Self::Error(_), Self::Error(_)) => true,
+            _ => false,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -55,51 +121,165 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the stored message, if it was stored.
     pub fn on_message<Engine>(
         &self,
         msg: &BeaconEngineMessage<Engine>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<Option<PathBuf>>
+    where
+        Engine: EngineTypes,
+    {
+        let Some((path, encoded)) = self.encode_message(msg, received_at)? else { return Ok(None) };
+        self.write_message(&path, &encoded)?;
+        Ok(Some(path))
+    }
+
+    /// Returns the path and the JSON encoding of the received [`BeaconEngineMessage`], if it's
+    /// stored.
+    fn encode_message<Engine>(
+        &self,
+        msg: &BeaconEngineMessage<Engine>,
+        received_at: SystemTime,
+    ) -> eyre::Result<Option<(PathBuf, Vec<u8>)>>
     where
         Engine: EngineTypes,
     {
-        fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
+        let encoded = match msg {
             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: _tx } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(&StoredEngineApiMessage::ForkchoiceUpdated {
-                        state: *state,
-                        payload_attrs: payload_attrs.clone(),
-                    })?,
-                )?;
+                let path =
+                    self.path.join(format!("{}-fcu-{}.json", timestamp, state.head_block_hash));
+                let encoded = serde_json::to_vec(&StoredEngineApiMessage::ForkchoiceUpdated {
+                    state: *state,
+                    payload_attrs: payload_attrs.clone(),
+                })?;
+                (path, encoded)
             }
             BeaconEngineMessage::NewPayload { payload, cancun_fields, tx: _tx } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
-                fs::write(
-                    self.path.join(filename),
-                    serde_json::to_vec(
-                        &StoredEngineApiMessage::<Engine::PayloadAttributes>::NewPayload {
-                            payload: payload.clone(),
-                            cancun_fields: cancun_fields.clone(),
-                        },
-                    )?,
-                )?;
+                let path = self.path.join(format!(
+                    "{}-new_payload-{}.json",
+                    timestamp,
+                    payload.block_hash()
+                ));
+                let stored = StoredEngineApiMessage::<Engine::PayloadAttributes>::NewPayload {
+                    payload: payload.clone(),
+                    cancun_fields: cancun_fields.clone(),
+                };
+                (path, serde_json::to_vec(&stored)?)
             }
             // noop
-            BeaconEngineMessage::TransitionConfigurationExchanged => (),
+            BeaconEngineMessage::TransitionConfigurationExchanged => return Ok(None),
         };
+        Ok(Some(encoded))
+    }
+
+    /// Writes the encoded message to the given path.
+    fn write_message(&self, path: &Path, encoded: &[u8]) -> eyre::Result<()> {
+        fs::create_dir_all(&self.path)?; // ensure that store path had been created
+        fs::write(path, encoded)?;
         Ok(())
     }
 
+    /// Stores the response of the engine to the message stored at the given path.
+    pub fn on_response(
+        &self,
+        message_path: &Path,
+        response: &StoredEngineApiResponse,
+    ) -> eyre::Result<()> {
+        fs::write(Self::response_path(message_path), serde_json::to_vec(response)?)?;
+        Ok(())
+    }
+
+    /// Reads the stored response of the engine to the message stored at the given path, if any.
+    pub fn response(&self, message_path: &Path) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let path = Self::response_path(message_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Returns the path of the response to the message stored at the given path.
+    fn response_path(message_path: &Path) -> PathBuf {
+        message_path.with_extension(RESPONSE_EXTENSION)
+    }
+
+    /// Replaces the response channel of the message. The returned future stores the message,
+    /// forwards the response of the engine to the original sender and then stores the response.
+    ///
+    /// All files are written on blocking tasks.
+    fn intercept_response<Engine>(
+        &self,
+        msg: BeaconEngineMessage<Engine>,
+        message_path: PathBuf,
+        encoded_message: Vec<u8>,
+    ) -> (BeaconEngineMessage<Engine>, BoxFuture<'static, ()>)
+    where
+        Engine: EngineTypes,
+    {
+        let store = self.clone();
+        let store_message = {
+            let store = store.clone();
+            let message_path = message_path.clone();
+            run_blocking(move || store.write_message(&message_path, &encoded_message))
+        };
+        let store_response = move |response: StoredEngineApiResponse| {
+            run_blocking(move || store.on_response(&message_path, &response))
+        };
+
+        match msg {
+            BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx } => {
+                let (intercepted_tx, rx) = oneshot::channel();
+                let forward = async move {
+                    store_message.await;
+                    let Ok(result) = rx.await else { return };
+                    let response = match &result {
+                        Ok(on_updated) => StoredEngineApiResponse::ForkchoiceUpdated(
+                            on_updated.forkchoice_status(),
+                        ),
+                        Err(err) => StoredEngineApiResponse::Error(err.to_string()),
+                    };
+                    let _ = tx.send(result);
+                    store_response(response).await;
+                };
+                let msg = BeaconEngineMessage::ForkchoiceUpdated {
+                    state,
+                    payload_attrs,
+                    tx: intercepted_tx,
+                };
+                (msg, forward.boxed())
+            }
+            BeaconEngineMessage::NewPayload { payload, cancun_fields, tx } => {
+                let (intercepted_tx, rx) = oneshot::channel();
+                let forward = async move {
+                    store_message.await;
+                    let Ok(result) = rx.await else { return };
+                    let response = StoredEngineApiResponse::from_new_payload(&result);
+                    let _ = tx.send(result);
+                    store_response(response).await;
+                };
+                let msg =
+                    BeaconEngineMessage::NewPayload { payload, cancun_fields, tx: intercepted_tx };
+                (msg, forward.boxed())
+            }
+            msg @ BeaconEngineMessage::TransitionConfigurationExchanged => {
+                (msg, store_message.boxed())
+            }
+        }
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if filename.to_str().is_some_and(|n| n.ends_with(RESPONSE_EXTENSION)) {
+                // responses are read together with their messages
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -115,8 +295,24 @@ impl EngineMessageStore {
     }
 }
 
-/// A wrapper stream that stores Engine API messages in
+/// Runs the write on a blocking task, and logs its error.
+async fn run_blocking(write: impl FnOnce() -> eyre::Result<()> + Send + 'static) {
+    match tokio::task::spawn_blocking(write).await {
+        Ok(Ok(())) => {}
+        Ok(Err(error)) => {
+            error!(target: "engine::intercept", %error, "Error storing Engine API message");
+        }
+        Err(error) => {
+            error!(target: "engine::intercept", %error, "Engine API message store task failed");
+        }
+    }
+}
+
+/// A wrapper stream that stores Engine API messages and the responses of the engine in
 /// the specified directory.
+///
+/// The messages and responses are stored by spawned tasks, so that the responses are forwarded to
+/// the original senders independently of the polling of the stream.
 #[derive(Debug)]
 #[pin_project::pin_project]
 pub struct EngineStoreStream<S> {
@@ -142,13 +338,58 @@ where
     type Item = S::Item;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next {
-            if let Err(error) = this.store.on_message(msg, SystemTime::now()) {
+        let this = self.project();
+
+        let Some(msg) = ready!(this.stream.poll_next(cx)) else { return Poll::Ready(None) };
+        let msg = match this.store.encode_message(&msg, SystemTime::now()) {
+            Ok(Some((path, encoded))) => {
+                let (msg, store) = this.store.intercept_response(msg, path, encoded);
+                tokio::spawn(store);
+                msg
+            }
+            Ok(None) => msg,
+            Err(error) => {
                 error!(target: "engine::intercept", ?msg, %error, "Error handling Engine API message");
+                msg
             }
-        }
-        Poll::Ready(next)
+        };
+        Poll::Ready(Some(msg))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::engine::PayloadStatusEnum;
+
+    #[test]
+    fn stored_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+        let message_path = dir.path().join("1-fcu-0x00.json");
+        fs::write(&message_path, b"{}").unwrap();
+        assert_eq!(store.response(&message_path).unwrap(), None);
+
+        let response = StoredEngineApiResponse::ForkchoiceUpdated(ForkchoiceStatus::Syncing);
+        store.on_response(&message_path, &response).unwrap();
+        assert_eq!(store.response(&message_path).unwrap(), Some(response));
+
+        // responses are not replayed as messages
+        assert_eq!(store.engine_messages_iter().unwrap().collect::<Vec<_>>(), vec![message_path]);
+    }
+
+    #[test]
+    fn response_status() {
+        let invalid = |error: &str| {
+            StoredEngineApiResponse::NewPayload(PayloadStatus::from_status(
+                PayloadStatusEnum::Invalid { validation_error: error.to_string() },
+            ))
+        };
+        assert!(invalid("gas used mismatch").same_status(&invalid("different message")));
+        assert!(!invalid("gas used mismatch").same_status(&StoredEngineApiResponse::NewPayload(
+            PayloadStatus::from_status(PayloadStatusEnum::Valid)
+        )));
+        assert!(!StoredEngineApiResponse::ForkchoiceUpdated(ForkchoiceStatus::Valid)
+            .same_status(&StoredEngineApiResponse::ForkchoiceUpdated(ForkchoiceStatus::Syncing)));
     }
 }
@@ -1,4 +1,5 @@
 //! Metrics utilities for the node.
 
 pub mod prometheus_exporter;
+pub mod recorder;
 pub mod version_metrics;
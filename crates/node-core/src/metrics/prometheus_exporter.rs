@@ -1,6 +1,6 @@
 //! Prometheus exporter
 
-use crate::metrics::version_metrics::register_version_metrics;
+use crate::metrics::{recorder::FanoutRecorder, version_metrics::register_version_metrics};
 use eyre::WrapErr;
 use futures::{future::FusedFuture, FutureExt};
 use http::Response;
@@ -10,19 +10,25 @@ use metrics_util::layers::{PrefixLayer, Stack};
 use reth_db_api::database_metrics::DatabaseMetrics;
 use reth_metrics::metrics::Unit;
 use reth_provider::providers::StaticFileProvider;
-use reth_tasks::TaskExecutor;
+use reth_tasks::{RestartPolicy, TaskExecutor};
 use std::{convert::Infallible, net::SocketAddr, sync::Arc};
 
 pub(crate) trait Hook: Fn() + Send + Sync {}
 impl<T: Fn() + Send + Sync> Hook for T {}
 
+/// A hook that records pull-style metrics, e.g. every time the metrics are requested.
+pub type MetricsHook = Box<dyn Fn() + Send + Sync>;
+
 /// Installs Prometheus as the metrics recorder.
+///
+/// All metrics are also forwarded to the recorders set with
+/// [`set_recorders`](crate::metrics::recorder::set_recorders).
 pub fn install_recorder() -> eyre::Result<PrometheusHandle> {
     let recorder = PrometheusBuilder::new().build_recorder();
     let handle = recorder.handle();
 
     // Build metrics stack
-    Stack::new(recorder)
+    Stack::new(FanoutRecorder::new(recorder))
         .push(PrefixLayer::new("reth"))
         .install()
         .wrap_err("Couldn't set metrics recorder.")?;
@@ -65,47 +71,69 @@ async fn start_endpoint<F: Hook + 'static>(
     let listener =
         tokio::net::TcpListener::bind(listen_addr).await.wrap_err("Could not bind to address")?;
 
-    task_executor.spawn_with_graceful_shutdown_signal(|signal| async move {
-        let mut shutdown = signal.ignore_guard().fuse();
-        loop {
-            let io = match listener.accept().await {
-                Ok((stream, _remote_addr)) => stream,
-                Err(err) => {
-                    tracing::error!(%err, "failed to accept connection");
-                    continue;
-                }
-            };
-
+    // the endpoint is restarted if it panics, e.g. in one of the hooks, but the node keeps running
+    // without it once the restarts are used up
+    let listener = Arc::new(listener);
+    task_executor.spawn_with_restart_policy(
+        "metrics server",
+        RestartPolicy::restart(5),
+        move |signal| {
+            let listener = listener.clone();
             let handle = handle.clone();
             let hook = hook.clone();
-            let service = tower::service_fn(move |_| {
-                (hook)();
-                let metrics = handle.render();
-                async move { Ok::<_, Infallible>(Response::new(metrics)) }
-            });
-
-            if let Err(error) =
-                jsonrpsee::server::serve_with_graceful_shutdown(io, service, &mut shutdown).await
-            {
-                tracing::debug!(%error, "failed to serve request")
-            }
+            async move {
+                let mut shutdown = signal.fuse();
+                let mut guard = None;
+                loop {
+                    let io = match listener.accept().await {
+                        Ok((stream, _remote_addr)) => stream,
+                        Err(err) => {
+                            tracing::error!(%err, "failed to accept connection");
+                            continue;
+                        }
+                    };
+
+                    let handle = handle.clone();
+                    let hook = hook.clone();
+                    let service = tower::service_fn(move |_| {
+                        (hook)();
+                        let metrics = handle.render();
+                        async move { Ok::<_, Infallible>(Response::new(metrics)) }
+                    });
+
+                    let stopped =
+                        (&mut shutdown).map(|shutdown_guard| guard = Some(shutdown_guard));
+                    if let Err(error) =
+                        jsonrpsee::server::serve_with_graceful_shutdown(io, service, stopped).await
+                    {
+                        tracing::debug!(%error, "failed to serve request")
+                    }
+
+                    if shutdown.is_terminated() {
+                        break;
+                    }
+                }
 
-            if shutdown.is_terminated() {
-                break;
+                // the node waits for the endpoint to stop serving until the guard is dropped
+                drop(guard);
             }
-        }
-    });
+        },
+    );
 
     Ok(())
 }
 
 /// Serves Prometheus metrics over HTTP with database and process metrics.
+///
+/// The additional hooks are called together with the hooks that record the database and process
+/// metrics.
 pub async fn serve<Metrics>(
     listen_addr: SocketAddr,
     handle: PrometheusHandle,
     db: Metrics,
     static_file_provider: StaticFileProvider,
     process: metrics_process::Collector,
+    additional_hooks: Vec<MetricsHook>,
     task_executor: TaskExecutor,
 ) -> eyre::Result<()>
 where
@@ -120,13 +148,14 @@ where
 
     // Clone `process` to move it into the hook and use the original `process` for describe below.
     let cloned_process = process.clone();
-    let hooks: Vec<Box<dyn Hook<Output = ()>>> = vec![
+    let mut hooks: Vec<MetricsHook> = vec![
         Box::new(db_metrics_hook),
         Box::new(static_file_metrics_hook),
         Box::new(move || cloned_process.collect()),
         Box::new(collect_memory_stats),
         Box::new(collect_io_stats),
     ];
+    hooks.extend(additional_hooks);
     serve_with_hooks(listen_addr, handle, hooks, task_executor).await?;
 
     // We describe the metrics after the recorder is installed, otherwise this information is not
@@ -156,6 +185,30 @@ where
     Ok(())
 }
 
+/// Serves Prometheus metrics over HTTP with process metrics only, for processes that don't open a
+/// database.
+pub async fn serve_process_metrics(
+    listen_addr: SocketAddr,
+    handle: PrometheusHandle,
+    process: metrics_process::Collector,
+    task_executor: TaskExecutor,
+) -> eyre::Result<()> {
+    let cloned_process = process.clone();
+    let hooks: Vec<Box<dyn Hook<Output = ()>>> = vec![
+        Box::new(move || cloned_process.collect()),
+        Box::new(collect_memory_stats),
+        Box::new(collect_io_stats),
+    ];
+    serve_with_hooks(listen_addr, handle, hooks, task_executor).await?;
+
+    process.describe();
+    describe_memory_stats();
+    describe_io_stats();
+    register_version_metrics();
+
+    Ok(())
+}
+
 #[cfg(all(feature = "jemalloc", unix))]
 fn collect_memory_stats() {
     use metrics::gauge;
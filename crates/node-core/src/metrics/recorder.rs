@@ -0,0 +1,287 @@
+//! Forwarding of metrics to additional recorders.
+//!
+//! The Prometheus recorder is installed as the global recorder before the node is built, so
+//! additional recorders, e.g. statsd or OTLP exporters, can't be installed globally anymore.
+//! Instead, they are set with [`set_recorders`] and all metrics are forwarded to them.
+
+use metrics::{
+    Counter, CounterFn, Gauge, GaugeFn, Histogram, HistogramFn, Key, KeyName, Level, Metadata,
+    Recorder, SharedString, Unit,
+};
+use metrics_util::MetricKind;
+use once_cell::sync::Lazy;
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex, OnceLock, PoisonError},
+};
+
+/// A recorder that metrics can be forwarded to.
+pub type SharedRecorder = Arc<dyn Recorder + Send + Sync>;
+
+/// The additional recorders of the global recorder.
+static FANOUT: Lazy<Arc<Fanout>> = Lazy::new(Default::default);
+
+/// The metadata that metrics are registered with in recorders that were set after the metric
+/// was registered.
+const LATE_METADATA: Metadata<'static> = Metadata::new(module_path!(), Level::INFO, None);
+
+/// Sets the recorders that all metrics are forwarded to, in addition to the global recorder.
+///
+/// The recorders can only be set once. All metric descriptions are replayed to the recorders.
+/// Metrics that were registered before the recorders were set are registered with them on their
+/// next update.
+pub fn set_recorders(recorders: Vec<SharedRecorder>) -> eyre::Result<()> {
+    FANOUT.set(recorders)
+}
+
+/// The recorders that a [`FanoutRecorder`] forwards to, in addition to its inner recorder.
+#[derive(Default)]
+pub(crate) struct Fanout {
+    /// The additional recorders, which are read without locking on every metric update.
+    recorders: OnceLock<Box<[SharedRecorder]>>,
+    /// The descriptions of all metrics, which are replayed to the recorders when they are set.
+    descriptions: Mutex<HashMap<(MetricKind, KeyName), (Option<Unit>, SharedString)>>,
+}
+
+impl Fanout {
+    /// Sets the recorders, and replays all descriptions to them.
+    fn set(&self, recorders: Vec<SharedRecorder>) -> eyre::Result<()> {
+        // holding the lock ensures that no description is missed
+        let descriptions = self.descriptions.lock().unwrap_or_else(PoisonError::into_inner);
+        if self.recorders.get().is_some() {
+            eyre::bail!("additional metrics recorders were already set")
+        }
+        for ((kind, key), (unit, description)) in descriptions.iter() {
+            for recorder in &recorders {
+                describe(recorder.as_ref(), *kind, key.clone(), *unit, description.clone());
+            }
+        }
+        let _ = self.recorders.set(recorders.into_boxed_slice());
+        Ok(())
+    }
+
+    fn recorders(&self) -> Option<&[SharedRecorder]> {
+        self.recorders.get().map(|recorders| &**recorders)
+    }
+}
+
+impl fmt::Debug for Fanout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fanout")
+            .field("recorders", &self.recorders().map_or(0, <[_]>::len))
+            .finish_non_exhaustive()
+    }
+}
+
+/// A recorder that forwards all metrics to the inner recorder and to the recorders set with
+/// [`set_recorders`].
+#[derive(Debug)]
+pub(crate) struct FanoutRecorder<R> {
+    inner: R,
+    fanout: Arc<Fanout>,
+}
+
+impl<R> FanoutRecorder<R> {
+    /// Creates a new recorder that forwards to the given recorder and the global additional
+    /// recorders.
+    pub(crate) fn new(inner: R) -> Self {
+        Self::with_fanout(inner, FANOUT.clone())
+    }
+
+    /// Creates a new recorder that forwards to the given recorder and the given additional
+    /// recorders.
+    const fn with_fanout(inner: R, fanout: Arc<Fanout>) -> Self {
+        Self { inner, fanout }
+    }
+}
+
+impl<R: Recorder> FanoutRecorder<R> {
+    fn describe(
+        &self,
+        kind: MetricKind,
+        key: KeyName,
+        unit: Option<Unit>,
+        description: SharedString,
+    ) {
+        let mut descriptions =
+            self.fanout.descriptions.lock().unwrap_or_else(PoisonError::into_inner);
+        describe(&self.inner, kind, key.clone(), unit, description.clone());
+        for recorder in self.fanout.recorders().unwrap_or_default() {
+            describe(recorder.as_ref(), kind, key.clone(), unit, description.clone());
+        }
+        descriptions.insert((kind, key), (unit, description));
+    }
+}
+
+impl<R: Recorder> Recorder for FanoutRecorder<R> {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(MetricKind::Counter, key, unit, description)
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(MetricKind::Gauge, key, unit, description)
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.describe(MetricKind::Histogram, key, unit, description)
+    }
+
+    fn register_counter(&self, key: &Key, metadata: &Metadata<'_>) -> Counter {
+        let inner = self.inner.register_counter(key, metadata);
+        Counter::from_arc(Arc::new(FanoutHandle::new(key, inner, metadata, self.fanout.clone())))
+    }
+
+    fn register_gauge(&self, key: &Key, metadata: &Metadata<'_>) -> Gauge {
+        let inner = self.inner.register_gauge(key, metadata);
+        Gauge::from_arc(Arc::new(FanoutHandle::new(key, inner, metadata, self.fanout.clone())))
+    }
+
+    fn register_histogram(&self, key: &Key, metadata: &Metadata<'_>) -> Histogram {
+        let inner = self.inner.register_histogram(key, metadata);
+        Histogram::from_arc(Arc::new(FanoutHandle::new(key, inner, metadata, self.fanout.clone())))
+    }
+}
+
+/// A metric handle that can be registered with a recorder.
+trait Register: Sized {
+    fn register(recorder: &dyn Recorder, key: &Key, metadata: &Metadata<'_>) -> Self;
+}
+
+impl Register for Counter {
+    fn register(recorder: &dyn Recorder, key: &Key, metadata: &Metadata<'_>) -> Self {
+        recorder.register_counter(key, metadata)
+    }
+}
+
+impl Register for Gauge {
+    fn register(recorder: &dyn Recorder, key: &Key, metadata: &Metadata<'_>) -> Self {
+        recorder.register_gauge(key, metadata)
+    }
+}
+
+impl Register for Histogram {
+    fn register(recorder: &dyn Recorder, key: &Key, metadata: &Metadata<'_>) -> Self {
+        recorder.register_histogram(key, metadata)
+    }
+}
+
+/// A metric handle that forwards all updates to the handles of the metric in all recorders.
+struct FanoutHandle<H> {
+    key: Key,
+    inner: H,
+    fanout: Arc<Fanout>,
+    /// The handles of the metric in the additional recorders, registered once they are set.
+    added: OnceLock<Box<[H]>>,
+}
+
+impl<H: Register> FanoutHandle<H> {
+    fn new(key: &Key, inner: H, metadata: &Metadata<'_>, fanout: Arc<Fanout>) -> Self {
+        let added = OnceLock::new();
+        if let Some(recorders) = fanout.recorders() {
+            let _ = added.set(register_all(recorders, key, metadata));
+        }
+        Self { key: key.clone(), inner, fanout, added }
+    }
+
+    /// Calls the function with the handles of the metric in all recorders.
+    fn for_each(&self, f: impl Fn(&H)) {
+        f(&self.inner);
+        if let Some(recorders) = self.fanout.recorders() {
+            // the metric is registered with the recorders on the first update after they were set
+            self.added
+                .get_or_init(|| register_all(recorders, &self.key, &LATE_METADATA))
+                .iter()
+                .for_each(f);
+        }
+    }
+}
+
+fn register_all<H: Register>(
+    recorders: &[SharedRecorder],
+    key: &Key,
+    metadata: &Metadata<'_>,
+) -> Box<[H]> {
+    recorders.iter().map(|recorder| H::register(recorder.as_ref(), key, metadata)).collect()
+}
+
+impl CounterFn for FanoutHandle<Counter> {
+    fn increment(&self, value: u64) {
+        self.for_each(|counter| counter.increment(value))
+    }
+
+    fn absolute(&self, value: u64) {
+        self.for_each(|counter| counter.absolute(value))
+    }
+}
+
+impl GaugeFn for FanoutHandle<Gauge> {
+    fn increment(&self, value: f64) {
+        self.for_each(|gauge| gauge.increment(value))
+    }
+
+    fn decrement(&self, value: f64) {
+        self.for_each(|gauge| gauge.decrement(value))
+    }
+
+    fn set(&self, value: f64) {
+        self.for_each(|gauge| gauge.set(value))
+    }
+}
+
+impl HistogramFn for FanoutHandle<Histogram> {
+    fn record(&self, value: f64) {
+        self.for_each(|histogram| histogram.record(value))
+    }
+}
+
+fn describe(
+    recorder: &dyn Recorder,
+    kind: MetricKind,
+    key: KeyName,
+    unit: Option<Unit>,
+    description: SharedString,
+) {
+    match kind {
+        MetricKind::Counter => recorder.describe_counter(key, unit, description),
+        MetricKind::Gauge => recorder.describe_gauge(key, unit, description),
+        MetricKind::Histogram => recorder.describe_histogram(key, unit, description),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use metrics::NoopRecorder;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    #[test]
+    fn forwards_to_additional_recorders() {
+        let fanout = Arc::new(Fanout::default());
+        let recorder = FanoutRecorder::with_fanout(NoopRecorder, fanout.clone());
+        recorder.describe_counter(
+            KeyName::from_const_str("fanout_test"),
+            None,
+            SharedString::const_str("A test counter"),
+        );
+        let counter =
+            recorder.register_counter(&Key::from_static_name("fanout_test"), &LATE_METADATA);
+        counter.increment(1);
+
+        // the counter is registered with the additional recorder on its next update
+        let debugging = DebuggingRecorder::new();
+        let snapshotter = debugging.snapshotter();
+        fanout.set(vec![Arc::new(debugging)]).unwrap();
+        assert!(fanout.set(Vec::new()).is_err());
+        counter.increment(2);
+
+        let (_, _, description, value) = snapshotter
+            .snapshot()
+            .into_vec()
+            .into_iter()
+            .find(|(key, ..)| key.key().name() == "fanout_test")
+            .unwrap();
+        assert_eq!(description, Some(SharedString::const_str("A test counter")));
+        assert_eq!(value, DebugValue::Counter(2));
+    }
+}
@@ -2,11 +2,12 @@
 
 use crate::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, NetworkArgs, PayloadBuilderArgs,
-        PruningArgs, RpcServerArgs, TxPoolArgs,
+        AlertArgs, DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, ForkArgs, HealthArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, ReorgGuardArgs, RpcServerArgs,
+        SpaceReportArgs, TxPoolArgs,
     },
     dirs::{ChainPath, DataDirPath},
-    metrics::prometheus_exporter,
+    metrics::prometheus_exporter::{self, MetricsHook},
     utils::get_single_header,
 };
 use metrics_exporter_prometheus::PrometheusHandle;
@@ -121,6 +122,8 @@ pub struct NodeConfig {
     /// - `AUTH_PORT`: default + `instance` * 100 - 100
     /// - `HTTP_RPC_PORT`: default - `instance` + 1
     /// - `WS_RPC_PORT`: default + `instance` * 2 - 2
+    /// - `GRAPHQL_PORT`: default + `instance` * 100 - 100
+    /// - `GRPC_PORT`: default + `instance` * 100 - 100
     pub instance: u16,
 
     /// All networking related arguments
@@ -146,6 +149,24 @@ pub struct NodeConfig {
 
     /// All pruning related arguments
     pub pruning: PruningArgs,
+
+    /// All sync health related arguments with --health prefix
+    pub health: HealthArgs,
+
+    /// All alert related arguments with --alerts prefix
+    pub alerts: AlertArgs,
+
+    /// All space report related arguments with --space-report prefix
+    pub space_report: SpaceReportArgs,
+
+    /// All reorg guard related arguments with --reorg-guard prefix
+    pub reorg_guard: ReorgGuardArgs,
+
+    /// All consensus engine related arguments with --engine prefix
+    pub engine: EngineArgs,
+
+    /// All fork related arguments with --fork prefix
+    pub fork: ForkArgs,
 }
 
 impl NodeConfig {
@@ -240,13 +261,51 @@ impl NodeConfig {
         self
     }
 
+    /// Set the sync health args for the node
+    pub fn with_health(mut self, health: HealthArgs) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// Set the alert args for the node
+    pub fn with_alerts(mut self, alerts: AlertArgs) -> Self {
+        self.alerts = alerts;
+        self
+    }
+
+    /// Set the space report args for the node
+    pub const fn with_space_report(mut self, space_report: SpaceReportArgs) -> Self {
+        self.space_report = space_report;
+        self
+    }
+
+    /// Set the reorg guard args for the node
+    pub const fn with_reorg_guard(mut self, reorg_guard: ReorgGuardArgs) -> Self {
+        self.reorg_guard = reorg_guard;
+        self
+    }
+
+    /// Set the engine args for the node
+    pub const fn with_engine(mut self, engine: EngineArgs) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// Set the fork args for the node
+    pub fn with_fork(mut self, fork: ForkArgs) -> Self {
+        self.fork = fork;
+        self
+    }
+
     /// Returns pruning configuration.
     pub fn prune_config(&self) -> Option<PruneConfig> {
         self.pruning.prune_config(&self.chain)
     }
 
     /// Returns the max block that the node should run to, looking it up from the network if
-    /// necessary
+    /// necessary.
+    ///
+    /// If the chain is frozen, this is the block it's frozen at.
     pub async fn max_block<Provider, Client>(
         &self,
         network_client: Client,
@@ -256,7 +315,7 @@ impl NodeConfig {
         Provider: HeaderProvider,
         Client: HeadersClient,
     {
-        let max_block = if let Some(block) = self.debug.max_block {
+        let max_block = if let Some(block) = self.debug.max_block.or(self.debug.freeze_at_block) {
             Some(block)
         } else if let Some(tip) = self.debug.tip {
             Some(self.lookup_or_fetch_tip(provider, network_client, tip).await?)
@@ -278,11 +337,14 @@ impl NodeConfig {
     }
 
     /// Serves the prometheus endpoint over HTTP with the given database and prometheus handle.
+    ///
+    /// The hooks are called every time the metrics are requested.
     pub async fn start_metrics_endpoint<Metrics>(
         &self,
         prometheus_handle: PrometheusHandle,
         db: Metrics,
         static_file_provider: StaticFileProvider,
+        hooks: Vec<MetricsHook>,
         task_executor: TaskExecutor,
     ) -> eyre::Result<()>
     where
@@ -296,6 +358,7 @@ impl NodeConfig {
                 db,
                 static_file_provider,
                 metrics_process::Collector::default(),
+                hooks,
                 task_executor,
             )
             .await?;
@@ -422,6 +485,12 @@ impl Default for NodeConfig {
             db: DatabaseArgs::default(),
             dev: DevArgs::default(),
             pruning: PruningArgs::default(),
+            health: HealthArgs::default(),
+            alerts: AlertArgs::default(),
+            space_report: SpaceReportArgs::default(),
+            reorg_guard: ReorgGuardArgs::default(),
+            engine: EngineArgs::default(),
+            fork: ForkArgs::default(),
             datadir: DatadirArgs::default(),
         }
     }
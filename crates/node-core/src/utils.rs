@@ -6,6 +6,7 @@ use reth_chainspec::ChainSpec;
 use reth_consensus_common::validation::validate_block_pre_execution;
 use reth_fs_util as fs;
 use reth_network::NetworkManager;
+use reth_network_api::{KnownPeer, Peers};
 use reth_network_p2p::{
     bodies::client::BodiesClient,
     headers::client::{HeadersClient, HeadersRequest},
@@ -18,7 +19,9 @@ use std::{
     env::VarError,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
+use tokio::time::Instant;
 use tracing::{debug, info, trace, warn};
 
 /// Parses a user-specified path with support for environment variables and common shorthands (e.g.
@@ -45,22 +48,45 @@ where
     C: BlockReader + Unpin,
 {
     if let Some(file_path) = persistent_peers_file {
-        let known_peers = network.all_peers().collect::<Vec<_>>();
-        if let Ok(known_peers) = serde_json::to_string_pretty(&known_peers) {
-            trace!(target: "reth::cli", peers_file =?file_path, num_peers=%known_peers.len(), "Saving current peers");
-            let parent_dir = file_path.parent().map(fs::create_dir_all).transpose();
-            match parent_dir.and_then(|_| fs::write(&file_path, known_peers)) {
-                Ok(_) => {
-                    info!(target: "reth::cli", peers_file=?file_path, "Wrote network peers to file");
-                }
-                Err(err) => {
-                    warn!(target: "reth::cli", %err, peers_file=?file_path, "Failed to write network peers to file");
-                }
+        let known_peers = network.known_peers().collect::<Vec<_>>();
+        write_known_peers_to_file(&known_peers, &file_path);
+    }
+}
+
+/// Writes the known peers, with their reputation, to the given file.
+///
+/// The peers are written to a temporary file that then replaces the file, so a node that reads
+/// the file concurrently never sees a partially written file.
+pub fn write_known_peers_to_file(known_peers: &[KnownPeer], file_path: &Path) {
+    if let Ok(json) = serde_json::to_string_pretty(known_peers) {
+        trace!(target: "reth::cli", peers_file =?file_path, num_peers=%known_peers.len(), "Saving current peers");
+        let tmp_path = file_path.with_extension("tmp");
+        let parent_dir = file_path.parent().map(fs::create_dir_all).transpose();
+        match parent_dir
+            .and_then(|_| fs::write(&tmp_path, json))
+            .and_then(|_| fs::rename(&tmp_path, file_path))
+        {
+            Ok(_) => {
+                info!(target: "reth::cli", peers_file=?file_path, "Wrote network peers to file");
+            }
+            Err(err) => {
+                warn!(target: "reth::cli", %err, peers_file=?file_path, "Failed to write network peers to file");
             }
         }
     }
 }
 
+/// Periodically writes the known peers of the network to the given file, until the network is
+/// shut down.
+pub async fn persist_peers_periodically<N: Peers>(network: N, file_path: PathBuf, every: Duration) {
+    let mut interval = tokio::time::interval_at(Instant::now() + every, every);
+    loop {
+        interval.tick().await;
+        let Ok(known_peers) = network.get_known_peers().await else { return };
+        write_known_peers_to_file(&known_peers, &file_path);
+    }
+}
+
 /// Get a single header from network
 pub async fn get_single_header<Client>(
     client: Client,
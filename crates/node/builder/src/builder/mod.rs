@@ -7,7 +7,7 @@ use crate::{
     components::NodeComponentsBuilder,
     node::FullNode,
     rpc::{RethRpcServerHandles, RpcContext},
-    DefaultNodeLauncher, Node, NodeHandle,
+    DefaultNodeLauncher, Node, NodeHandle, NodeMetrics,
 };
 use discv5::ListenConfig;
 use futures::Future;
@@ -20,7 +20,7 @@ use reth_db_api::{
     database::Database,
     database_metrics::{DatabaseMetadata, DatabaseMetrics},
 };
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, ExExNotificationFilter};
 use reth_network::{
     NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkHandle, NetworkManager,
 };
@@ -31,7 +31,7 @@ use reth_node_core::{
     dirs::{ChainPath, DataDirPath, MaybePlatformPath},
     node_config::NodeConfig,
     primitives::{kzg::KzgSettings, Head},
-    utils::write_peers_to_file,
+    utils::{persist_peers_periodically, write_peers_to_file},
 };
 use reth_primitives::constants::eip4844::MAINNET_KZG_TRUSTED_SETUP;
 use reth_provider::{providers::BlockchainProvider, ChainSpecProvider};
@@ -150,12 +150,14 @@ pub struct NodeBuilder<DB> {
     config: NodeConfig,
     /// The configured database for the node.
     database: DB,
+    /// Additional metrics recorders and hooks of the node.
+    metrics: NodeMetrics,
 }
 
 impl NodeBuilder<()> {
     /// Create a new [`NodeBuilder`].
     pub const fn new(config: NodeConfig) -> Self {
-        Self { config, database: () }
+        Self { config, database: (), metrics: NodeMetrics::new() }
     }
 }
 
@@ -167,7 +169,16 @@ impl<DB> NodeBuilder<DB> {
 
     /// Configures the underlying database that the node will use.
     pub fn with_database<D>(self, database: D) -> NodeBuilder<D> {
-        NodeBuilder { config: self.config, database }
+        NodeBuilder { config: self.config, database, metrics: self.metrics }
+    }
+
+    /// Configures additional metrics recorders and hooks of the node.
+    ///
+    /// The metrics of the node are forwarded to the recorders in addition to the Prometheus
+    /// endpoint, and the hooks are called every time the metrics are requested.
+    pub fn with_metrics(mut self, metrics: NodeMetrics) -> Self {
+        self.metrics = metrics;
+        self
     }
 
     /// Preconfigure the builder with the context to launch the node.
@@ -205,7 +216,7 @@ where
     where
         T: NodeTypes,
     {
-        NodeBuilderWithTypes::new(self.config, self.database)
+        NodeBuilderWithTypes::new(self.config, self.database).with_metrics(self.metrics)
     }
 
     /// Preconfigures the node with a specific node implementation.
@@ -247,6 +258,13 @@ where
         self.builder.config()
     }
 
+    /// Configures additional metrics recorders and hooks of the node.
+    ///
+    /// See also [`NodeBuilder::with_metrics`].
+    pub fn with_metrics(self, metrics: NodeMetrics) -> Self {
+        Self { builder: self.builder.with_metrics(metrics), task_executor: self.task_executor }
+    }
+
     /// Configures the types of the node.
     pub fn with_types<T>(self) -> WithLaunchContext<NodeBuilderWithTypes<RethFullAdapter<DB, T>>>
     where
@@ -386,6 +404,31 @@ where
         }
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node, that only receives the state matching
+    /// the given [`ExExNotificationFilter`] in its notifications.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_filter<F, R, E>(
+        self,
+        exex_id: impl Into<String>,
+        filter: ExExNotificationFilter,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<RethFullAdapter<DB, T>, CB::Components>>) -> R
+            + Send
+            + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        Self {
+            builder: self.builder.install_exex_with_filter(exex_id, filter, exex),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Launches the node and returns a handle to it.
     pub async fn launch(
         self,
@@ -508,6 +551,14 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
 
         let default_peers_path = self.config().datadir().known_peers();
         let known_peers_file = self.config().network.persistent_peers_file(default_peers_path);
+        let persist_interval = self.config().network.peers_persist_interval;
+        if let Some(file_path) = known_peers_file.clone().filter(|_| !persist_interval.is_zero()) {
+            self.executor.spawn(persist_peers_periodically(
+                handle.clone(),
+                file_path,
+                persist_interval,
+            ));
+        }
         self.executor.spawn_critical_with_graceful_shutdown_signal(
             "p2p network task",
             |shutdown| {
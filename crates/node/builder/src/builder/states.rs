@@ -10,16 +10,17 @@ use crate::{
     exex::BoxedLaunchExEx,
     hooks::NodeHooks,
     launch::LaunchNode,
+    metrics::NodeMetrics,
     rpc::{RethRpcServerHandles, RpcContext, RpcHooks},
     FullNode,
 };
-use reth_exex::ExExContext;
+use reth_exex::{ExExContext, ExExNotificationFilter};
 use reth_network::NetworkHandle;
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_tasks::TaskExecutor;
-use std::{fmt, future::Future};
+use std::{collections::HashMap, fmt, future::Future};
 
 /// A node builder that also has the configured types.
 pub struct NodeBuilderWithTypes<T: FullNodeTypes> {
@@ -27,12 +28,20 @@ pub struct NodeBuilderWithTypes<T: FullNodeTypes> {
     config: NodeConfig,
     /// The configured database for the node.
     adapter: NodeTypesAdapter<T>,
+    /// Additional metrics recorders and hooks of the node.
+    metrics: NodeMetrics,
 }
 
 impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
     /// Creates a new instance of the node builder with the given configuration and types.
     pub const fn new(config: NodeConfig, database: T::DB) -> Self {
-        Self { config, adapter: NodeTypesAdapter::new(database) }
+        Self { config, adapter: NodeTypesAdapter::new(database), metrics: NodeMetrics::new() }
+    }
+
+    /// Configures additional metrics recorders and hooks of the node.
+    pub(crate) fn with_metrics(mut self, metrics: NodeMetrics) -> Self {
+        self.metrics = metrics;
+        self
     }
 
     /// Advances the state of the node builder to the next state where all components are configured
@@ -40,7 +49,7 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
     where
         CB: NodeComponentsBuilder<T>,
     {
-        let Self { config, adapter } = self;
+        let Self { config, adapter, metrics } = self;
 
         NodeBuilderWithComponents {
             config,
@@ -50,6 +59,8 @@ impl<T: FullNodeTypes> NodeBuilderWithTypes<T> {
                 hooks: NodeHooks::default(),
                 rpc: RpcHooks::new(),
                 exexs: Vec::new(),
+                exex_filters: HashMap::new(),
+                metrics,
             },
         }
     }
@@ -212,6 +223,28 @@ impl<T: FullNodeTypes, CB: NodeComponentsBuilder<T>> NodeBuilderWithComponents<T
         self
     }
 
+    /// Installs an `ExEx` (Execution Extension) in the node, that only receives the state matching
+    /// the given [`ExExNotificationFilter`] in its notifications.
+    ///
+    /// # Note
+    ///
+    /// The `ExEx` ID must be unique.
+    pub fn install_exex_with_filter<F, R, E>(
+        mut self,
+        exex_id: impl Into<String>,
+        filter: ExExNotificationFilter,
+        exex: F,
+    ) -> Self
+    where
+        F: FnOnce(ExExContext<NodeAdapter<T, CB::Components>>) -> R + Send + 'static,
+        R: Future<Output = eyre::Result<E>> + Send,
+        E: Future<Output = eyre::Result<()>> + Send,
+    {
+        let exex_id = exex_id.into();
+        self.add_ons.exex_filters.insert(exex_id.clone(), filter);
+        self.install_exex(exex_id, exex)
+    }
+
     /// Launches the node with the given launcher.
     pub async fn launch_with<L>(self, launcher: L) -> eyre::Result<L::Node>
     where
@@ -244,4 +277,8 @@ pub(crate) struct NodeAddOns<Node: FullNodeComponents> {
     pub(crate) rpc: RpcHooks<Node>,
     /// The `ExExs` (execution extensions) of the node.
     pub(crate) exexs: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    /// The notification filters of the `ExExs`, by ID.
+    pub(crate) exex_filters: HashMap<String, ExExNotificationFilter>,
+    /// Additional metrics recorders and hooks.
+    pub(crate) metrics: NodeMetrics,
 }
@@ -15,6 +15,7 @@ use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_core::{
     dirs::{ChainPath, DataDirPath},
+    metrics::prometheus_exporter::MetricsHook,
     node_config::NodeConfig,
 };
 use reth_primitives::{BlockNumber, Head, B256};
@@ -23,6 +24,7 @@ use reth_provider::{
     CanonStateNotificationSender, ProviderFactory, StaticFileProviderFactory,
 };
 use reth_prune::{PruneModes, PrunerBuilder};
+use reth_remote_state::RpcStateFallback;
 use reth_rpc_builder::config::RethRpcServerConfig;
 use reth_rpc_layer::JwtSecret;
 use reth_stages::{sets::DefaultStages, MetricEvent, Pipeline, PipelineTarget};
@@ -349,12 +351,29 @@ where
     /// between the database and static files. **It may execute a pipeline unwind if it fails this
     /// check.**
     pub async fn create_provider_factory(&self) -> eyre::Result<ProviderFactory<DB>> {
-        let factory = ProviderFactory::new(
+        let mut factory = ProviderFactory::new(
             self.right().clone(),
             self.chain_spec(),
             StaticFileProvider::read_write(self.data_dir().static_files())?,
         )
         .with_static_files_metrics();
+        if self.node_config().db.hot_tables_cache {
+            factory = factory.with_hot_tables_cache();
+        }
+        if self.node_config().db.selector_index {
+            factory = factory.with_selector_index();
+        }
+        if self.node_config().db.log_index {
+            factory = factory.with_log_index();
+        }
+        if let Some(url) = &self.node_config().fork.url {
+            eyre::ensure!(self.node_config().dev.dev, "forking a remote network requires --dev");
+            let fallback = RpcStateFallback::connect(url, self.node_config().fork.block)
+                .await
+                .wrap_err_with(|| format!("failed to connect to fork url {url}"))?;
+            info!(target: "reth::cli", %url, block = fallback.block(), "Forking remote state");
+            factory = factory.with_state_fallback(Arc::new(fallback));
+        }
 
         let has_receipt_pruning =
             self.toml_config().prune.as_ref().map_or(false, |a| a.has_receipts_pruning());
@@ -446,19 +465,21 @@ where
     }
 
     /// Convenience function to [`Self::start_prometheus_endpoint`]
-    pub async fn with_prometheus(self) -> eyre::Result<Self> {
-        self.start_prometheus_endpoint().await?;
+    pub async fn with_prometheus(self, hooks: Vec<MetricsHook>) -> eyre::Result<Self> {
+        self.start_prometheus_endpoint(hooks).await?;
         Ok(self)
     }
 
-    /// Starts the prometheus endpoint.
-    pub async fn start_prometheus_endpoint(&self) -> eyre::Result<()> {
+    /// Starts the prometheus endpoint, which calls the given hooks every time the metrics are
+    /// requested.
+    pub async fn start_prometheus_endpoint(&self, hooks: Vec<MetricsHook>) -> eyre::Result<()> {
         let prometheus_handle = self.node_config().install_prometheus_recorder()?;
         self.node_config()
             .start_metrics_endpoint(
                 prometheus_handle,
                 self.database().clone(),
                 self.static_file_provider(),
+                hooks,
                 self.task_executor().clone(),
             )
             .await
@@ -512,7 +533,17 @@ where
     pub async fn with_blockchain_db(
         self,
     ) -> eyre::Result<LaunchContextWith<Attached<WithConfigs, WithMeteredProviders<DB>>>> {
-        let tree_config = BlockchainTreeConfig::default();
+        let engine = &self.node_config().engine;
+        let mut tree_config = BlockchainTreeConfig::default();
+        if let Some(max_bytes) = engine.max_buffered_block_bytes {
+            tree_config = tree_config.with_max_unconnected_block_bytes(max_bytes);
+        }
+        if let Some(max_bytes) = engine.max_sidechain_bytes {
+            tree_config = tree_config.with_max_sidechain_bytes(max_bytes);
+        }
+        if let Some(max_bytes) = engine.max_sidechain_state_bytes {
+            tree_config = tree_config.with_max_sidechain_state_bytes(max_bytes);
+        }
 
         // NOTE: This is a temporary workaround to provide the canon state notification sender to the components builder because there's a cyclic dependency between the blockchain provider and the tree component. This will be removed once the Blockchain provider no longer depends on an instance of the tree: <https://github.com/paradigmxyz/reth/issues/7154>
         let (canon_state_notification_sender, _receiver) =
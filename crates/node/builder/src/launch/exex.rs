@@ -2,17 +2,21 @@
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 use futures::future;
-use reth_exex::{ExExContext, ExExHandle, ExExManager, ExExManagerHandle};
+use reth_exex::{
+    BackfillJobFactory, ExExContext, ExExHandle, ExExManager, ExExManagerHandle,
+    ExExNotificationFilter,
+};
 use reth_node_api::FullNodeComponents;
 use reth_primitives::Head;
-use reth_provider::CanonStateSubscriptions;
+use reth_provider::{CanonStateSubscriptions, ExExCheckpointReader};
 use reth_tracing::tracing::{debug, info};
-use std::{fmt, fmt::Debug};
+use std::{collections::HashMap, fmt, fmt::Debug};
 
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
     extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
+    filters: HashMap<String, ExExNotificationFilter>,
     components: Node,
     config_container: WithConfigs,
 }
@@ -25,19 +29,28 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         extensions: Vec<(String, Box<dyn BoxedLaunchExEx<Node>>)>,
         config_container: WithConfigs,
     ) -> Self {
-        Self { head, extensions, components, config_container }
+        Self { head, extensions, filters: HashMap::new(), components, config_container }
+    }
+
+    /// Sets the notification filters of the execution extensions, by ID.
+    pub fn with_filters(mut self, filters: HashMap<String, ExExNotificationFilter>) -> Self {
+        self.filters = filters;
+        self
     }
 
     /// Launches all execution extensions.
     ///
     /// Spawns all extensions and returns the handle to the exex manager if any extensions are
     /// installed.
-    pub async fn launch(self) -> Option<ExExManagerHandle> {
-        let Self { head, extensions, components, config_container } = self;
+    ///
+    /// Extensions that saved a checkpoint are resumed from it, and the blocks between their
+    /// checkpoint and the head are replayed to them first.
+    pub async fn launch(self) -> eyre::Result<Option<ExExManagerHandle>> {
+        let Self { head, extensions, mut filters, components, config_container } = self;
 
         if extensions.is_empty() {
             // nothing to launch
-            return None
+            return Ok(None)
         }
 
         let mut exex_handles = Vec::with_capacity(extensions.len());
@@ -45,11 +58,28 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         for (id, exex) in extensions {
             // create a new exex handle
-            let (handle, events, notifications) = ExExHandle::new(id.clone());
+            let (mut handle, events, notifications) = ExExHandle::new(id.clone());
+            if let Some(filter) = filters.remove(&id) {
+                debug!(target: "reth::cli", id, "filtering exex notifications");
+                handle = handle.with_filter(filter);
+            }
+            if let Some(checkpoint) = components.provider().exex_checkpoint(&id)? {
+                info!(target: "reth::cli", id, checkpoint, head = head.number, "Resuming exex from checkpoint");
+                handle = handle.with_checkpoint(checkpoint);
+                if checkpoint < head.number {
+                    let backfill = BackfillJobFactory::new(
+                        components.block_executor().clone(),
+                        components.provider().clone(),
+                    )
+                    .backfill(checkpoint + 1..=head.number);
+                    handle = handle.with_backfill(backfill);
+                }
+            }
             exex_handles.push(handle);
 
             // create the launch context for the exex
             let context = ExExContext {
+                id: id.clone(),
                 head,
                 config: config_container.config.clone(),
                 reth_config: config_container.toml_config.clone(),
@@ -106,7 +136,7 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         info!(target: "reth::cli", "ExEx Manager started");
 
-        Some(exex_manager_handle)
+        Ok(Some(exex_manager_handle))
     }
 }
 
@@ -8,14 +8,18 @@ use crate::{
     BuilderContext, NodeBuilderWithComponents, NodeHandle,
 };
 use futures::{future::Either, stream, stream_select, StreamExt};
+use reth_auto_seal_consensus::{LocalConsensusClient, ReorgConfig};
 use reth_beacon_consensus::{
     hooks::{EngineHooks, PruneHook, StaticFileHook},
-    BeaconConsensusEngine,
+    BeaconConsensusEngine, PersistenceBatchConfig,
 };
-use reth_blockchain_tree::{BlockchainTree, ShareableBlockchainTree, TreeExternals};
+use reth_blockchain_tree::{BlockchainTree, ReorgGuard, ShareableBlockchainTree, TreeExternals};
 use reth_consensus::Consensus;
-use reth_consensus_debug_client::{DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider};
+use reth_consensus_debug_client::{
+    DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider, ShadowValidator,
+};
 use reth_exex::ExExManagerHandle;
+use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network::NetworkEvents;
 use reth_node_api::{FullNodeComponents, FullNodeTypes};
 use reth_node_core::{
@@ -24,10 +28,16 @@ use reth_node_core::{
     exit::NodeExitFuture,
     version::{CARGO_PKG_VERSION, CLIENT_CODE, NAME_CLIENT, VERGEN_GIT_SHA},
 };
-use reth_node_events::{cl::ConsensusLayerHealthEvents, node};
+use reth_node_events::{
+    alerts::{AlertConfig, AlertEvents},
+    cl::ConsensusLayerHealthEvents,
+    health::{SyncHealthConfig, SyncHealthEvents},
+    node,
+    space::SpaceReportEvents,
+};
 
 use reth_primitives::format_ether;
-use reth_provider::providers::BlockchainProvider;
+use reth_provider::{providers::BlockchainProvider, CanonStateSubscriptions};
 use reth_rpc_engine_api::EngineApi;
 use reth_rpc_types::engine::ClientVersionV1;
 use reth_tasks::TaskExecutor;
@@ -98,10 +108,12 @@ where
         let NodeBuilderWithComponents {
             adapter: NodeTypesAdapter { database },
             components_builder,
-            add_ons: NodeAddOns { hooks, rpc, exexs: installed_exex },
+            add_ons: NodeAddOns { hooks, rpc, exexs: installed_exex, exex_filters, metrics },
             config,
         } = target;
 
+        let metrics_hooks = metrics.install(&ctx.task_executor)?;
+
         // setup the launch context
         let ctx = ctx
             .with_configured_globals()
@@ -118,7 +130,7 @@ where
             .inspect(|_| {
                 info!(target: "reth::cli", "Database opened");
             })
-            .with_prometheus().await?
+            .with_prometheus(metrics_hooks).await?
             .inspect(|this| {
                 debug!(target: "reth::cli", chain=%this.chain_id(), genesis=?this.genesis_hash(), "Initializing genesis");
             })
@@ -149,13 +161,19 @@ where
             consensus.clone(),
             components.block_executor().clone(),
         );
-        let tree = BlockchainTree::new(tree_externals, *ctx.tree_config(), ctx.prune_modes())?
+        let mut tree = BlockchainTree::new(tree_externals, *ctx.tree_config(), ctx.prune_modes())?
             .with_sync_metrics_tx(ctx.sync_metrics_tx())
             // Note: This is required because we need to ensure that both the components and the
             // tree are using the same channel for canon state notifications. This will be removed
             // once the Blockchain provider no longer depends on an instance of the tree
             .with_canon_state_notification_sender(ctx.canon_state_notification_sender());
 
+        let reorg_guard = ctx.node_config().reorg_guard.max_depth.map(ReorgGuard::new);
+        if let Some(reorg_guard) = &reorg_guard {
+            info!(target: "reth::cli", max_depth = reorg_guard.max_depth(), "Reorg guard enabled");
+            tree = tree.with_reorg_guard(reorg_guard.clone());
+        }
+
         let blockchain_tree = Arc::new(ShareableBlockchainTree::new(tree));
 
         // Replace the tree component with the actual tree
@@ -177,8 +195,9 @@ where
         // spawn exexs
         let exex_manager_handle =
             ExExLauncher::new(head, node_adapter.clone(), installed_exex, ctx.configs().clone())
+                .with_filters(exex_filters)
                 .launch()
-                .await;
+                .await?;
 
         // create pipeline
         let network_client = node_adapter.network().fetch_client().await?;
@@ -207,13 +226,17 @@ where
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
-        let (pipeline, client) = if ctx.is_dev() {
+        if ctx.is_dev() {
             info!(target: "reth::cli", "Starting Reth in dev mode");
 
             for (idx, (address, alloc)) in ctx.chain_spec().genesis.alloc.iter().enumerate() {
                 info!(target: "reth::cli", "Allocated Genesis Account: {:02}. {} ({} ETH)", idx, address.to_string(), format_ether(alloc.balance));
             }
+        }
 
+        // with the dev consensus client, blocks are proposed through the engine instead of being
+        // sealed directly
+        let (pipeline, client) = if ctx.is_dev() && !ctx.node_config().dev.consensus_client {
             // install auto-seal
             let mining_mode =
                 ctx.dev_mining_mode(node_adapter.components.pool().pending_transactions_listener());
@@ -270,6 +293,7 @@ where
         };
 
         let pipeline_events = pipeline.events();
+        let pipeline_health_events = pipeline.events();
 
         let initial_target = ctx.node_config().debug.tip;
 
@@ -283,6 +307,7 @@ where
         let pruner = pruner_builder.build(ctx.provider_factory().clone());
 
         let pruner_events = pruner.events();
+        let pruner_alert_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config().unwrap_or_default(), "Pruner initialized");
         hooks.add(PruneHook::new(pruner, Box::new(ctx.task_executor().clone())));
 
@@ -301,8 +326,35 @@ where
             Box::pin(consensus_engine_stream),
             hooks,
         )?;
+        let beacon_consensus_engine = if let Some(invalid_block_hook_dir) =
+            ctx.node_config().debug.invalid_block_hook.clone()
+        {
+            info!(target: "reth::cli", directory = %invalid_block_hook_dir.display(), "Dumping invalid blocks");
+            beacon_consensus_engine.with_invalid_block_hook(Box::new(InvalidBlockWitnessHook::new(
+                blockchain_db.clone(),
+                node_adapter.components.evm_config().clone(),
+                invalid_block_hook_dir,
+            )))
+        } else {
+            beacon_consensus_engine
+        };
+        let persistence_batch = PersistenceBatchConfig::new(
+            ctx.node_config().engine.persistence_max_blocks,
+            ctx.node_config().engine.persistence_max_bytes,
+        );
+        if persistence_batch.is_enabled() {
+            info!(target: "reth::cli", ?persistence_batch, "Batching persistence of canonical blocks");
+        }
+        let mut beacon_consensus_engine =
+            beacon_consensus_engine.with_persistence_batch(persistence_batch);
+        if let Some(freeze_block) = ctx.node_config().debug.freeze_at_block {
+            info!(target: "reth::cli", freeze_block, "Freezing the chain");
+            beacon_consensus_engine = beacon_consensus_engine.with_freeze_block(freeze_block);
+        }
         info!(target: "reth::cli", "Consensus engine initialized");
 
+        let shadow_validator = ctx.node_config().debug.shadow_rpc.clone().map(ShadowValidator::new);
+
         let events = stream_select!(
             node_adapter.components.network().event_listener().map(Into::into),
             beacon_engine_handle.event_listener().map(Into::into),
@@ -315,8 +367,62 @@ where
             } else {
                 Either::Right(stream::empty())
             },
+            if ctx.node_config().debug.tip.is_none() && !ctx.is_dev() {
+                let health = &ctx.node_config().health;
+                let config = SyncHealthConfig {
+                    stall_threshold: health.stall_threshold,
+                    forkchoice_gap_threshold: health.forkchoice_gap_threshold,
+                    webhook_url: health.webhook_url.clone(),
+                };
+                Either::Left(
+                    SyncHealthEvents::new(
+                        config,
+                        beacon_engine_handle.event_listener(),
+                        pipeline_health_events,
+                    )
+                    .map(Into::into),
+                )
+            } else {
+                Either::Right(stream::empty())
+            },
             pruner_events.map(Into::into),
             static_file_producer_events.map(Into::into),
+            {
+                let alerts = &ctx.node_config().alerts;
+                let config = AlertConfig {
+                    webhook_url: alerts.webhook_url.clone(),
+                    format: alerts.format,
+                    reorg_depth: alerts.reorg_depth,
+                    min_free_disk_space: alerts.min_free_disk_gb * 1_000_000_000,
+                    datadir: ctx.data_dir().data_dir().to_path_buf(),
+                };
+                AlertEvents::new(
+                    config,
+                    beacon_engine_handle.event_listener(),
+                    blockchain_db.canonical_state_stream(),
+                    pruner_alert_events,
+                    ctx.task_executor().restarted_tasks(),
+                )
+                .map(Into::into)
+            },
+            if ctx.node_config().space_report.is_enabled() {
+                Either::Left(
+                    SpaceReportEvents::new(
+                        ctx.provider_factory().clone(),
+                        ctx.prune_modes().unwrap_or_default(),
+                        ctx.node_config().space_report.epochs,
+                        blockchain_db.canonical_state_stream(),
+                    )
+                    .map(Into::into),
+                )
+            } else {
+                Either::Right(stream::empty())
+            },
+            if let Some(shadow_validator) = &shadow_validator {
+                Either::Left(shadow_validator.events().map(Into::into))
+            } else {
+                Either::Right(stream::empty())
+            },
         );
         ctx.task_executor().spawn_critical(
             "events task",
@@ -337,10 +443,11 @@ where
         let engine_api = EngineApi::new(
             blockchain_db.clone(),
             ctx.chain_spec(),
-            beacon_engine_handle,
+            beacon_engine_handle.clone(),
             node_adapter.components.payload_builder().clone().into(),
             Box::new(ctx.task_executor().clone()),
             client,
+            node_adapter.components.pool().clone(),
         );
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -352,7 +459,9 @@ where
             node_adapter.clone(),
             engine_api,
             ctx.node_config(),
+            ctx.toml_config(),
             jwt_secret,
+            reorg_guard,
             rpc,
         )
         .await?;
@@ -370,6 +479,29 @@ where
             let _ = tx.send(res);
         });
 
+        if ctx.is_dev() && ctx.node_config().dev.consensus_client {
+            let dev = ctx.node_config().dev;
+            let block_time = dev.block_time.ok_or_else(|| {
+                eyre::eyre!("--dev.consensus-client requires --dev.block-time to be set")
+            })?;
+            info!(target: "reth::cli", ?block_time, "Using local consensus client");
+
+            let reorg = dev.reorg_every.map(|every| ReorgConfig { every, depth: dev.reorg_depth });
+            let local_consensus_client = LocalConsensusClient::new(
+                ctx.chain_spec(),
+                blockchain_db.clone(),
+                node_adapter.components.pool().clone(),
+                beacon_engine_handle,
+                node_adapter.components.payload_builder().clone().into(),
+                block_time,
+            )
+            .with_empty_blocks(!dev.no_empty_blocks)
+            .with_reorgs(reorg);
+            ctx.task_executor().spawn_critical("local consensus client", async move {
+                local_consensus_client.run().await
+            });
+        }
+
         if let Some(maybe_custom_etherscan_url) = ctx.node_config().debug.etherscan.clone() {
             info!(target: "reth::cli", "Using etherscan as consensus client");
 
@@ -412,6 +544,18 @@ where
             });
         }
 
+        if let Some(shadow_validator) = shadow_validator {
+            info!(target: "reth::cli", "Comparing canonical blocks with external node");
+
+            let canonical_headers =
+                blockchain_db.canonical_state_stream().flat_map(|notification| {
+                    stream::iter(notification.committed().headers().collect::<Vec<_>>())
+                });
+            ctx.task_executor().spawn(Box::pin(async move {
+                shadow_validator.run(Box::pin(canonical_headers)).await
+            }));
+        }
+
         let full_node = FullNode {
             evm_config: node_adapter.components.evm_config().clone(),
             block_executor: node_adapter.components.block_executor().clone(),
@@ -34,6 +34,9 @@ pub mod setup;
 /// Support for installing the ExExs (execution extensions) in a node.
 pub mod exex;
 
+pub mod metrics;
+pub use metrics::NodeMetrics;
+
 /// Re-export the core configuration traits.
 pub use reth_node_core::cli::config::{
     PayloadBuilderConfig, RethNetworkConfig, RethTransactionPoolConfig,
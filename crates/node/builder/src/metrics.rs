@@ -0,0 +1,108 @@
+//! Support for additional metrics recorders and metrics hooks of a node.
+
+use eyre::WrapErr;
+use metrics::Recorder;
+use reth_node_core::metrics::{
+    prometheus_exporter::MetricsHook,
+    recorder::{set_recorders, SharedRecorder},
+};
+use reth_tasks::TaskExecutor;
+use std::{fmt, sync::Arc, time::Duration};
+
+/// The default interval at which the metrics hooks are called.
+pub const DEFAULT_HOOK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Additional metrics recorders and hooks of a node.
+///
+/// The metrics of the node are always served by the Prometheus endpoint. Additional recorders,
+/// e.g. statsd or OTLP exporters, receive all metrics of the node as well.
+///
+/// See also [`NodeBuilder::with_metrics`](crate::NodeBuilder::with_metrics).
+pub struct NodeMetrics {
+    /// The recorders that are set with [`set_recorders`] when the node is launched.
+    recorders: Vec<SharedRecorder>,
+    /// The hooks that record pull-style metrics.
+    hooks: Vec<MetricsHook>,
+    /// The interval at which the hooks are called.
+    hook_interval: Duration,
+}
+
+impl NodeMetrics {
+    /// Creates a new instance without additional recorders and hooks.
+    pub const fn new() -> Self {
+        Self { recorders: Vec::new(), hooks: Vec::new(), hook_interval: DEFAULT_HOOK_INTERVAL }
+    }
+
+    /// Adds a recorder that all metrics of the node are forwarded to.
+    ///
+    /// The additional recorders can only be set once per process, so launching another node with
+    /// additional recorders fails.
+    pub fn with_recorder<R>(mut self, recorder: R) -> Self
+    where
+        R: Recorder + Send + Sync + 'static,
+    {
+        self.recorders.push(Arc::new(recorder));
+        self
+    }
+
+    /// Adds a hook that is called at the hook interval, and every time the metrics are requested
+    /// from the metrics endpoint.
+    ///
+    /// This can be used to record node-specific pull-style metrics, e.g. gauges that are not
+    /// updated automatically.
+    pub fn with_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Sets the interval at which the hooks are called, [`DEFAULT_HOOK_INTERVAL`] by default.
+    pub const fn with_hook_interval(mut self, interval: Duration) -> Self {
+        self.hook_interval = interval;
+        self
+    }
+
+    /// Sets the recorders, spawns the task that calls the hooks at the hook interval and returns
+    /// the hooks to call when the metrics are requested.
+    ///
+    /// The hooks are called periodically so that the additional recorders receive their metrics
+    /// even if the metrics endpoint is disabled or never requested.
+    pub(crate) fn install(self, task_executor: &TaskExecutor) -> eyre::Result<Vec<MetricsHook>> {
+        let Self { recorders, hooks, hook_interval } = self;
+        if !recorders.is_empty() {
+            set_recorders(recorders).wrap_err("Couldn't set additional metrics recorders.")?;
+        }
+        if hooks.is_empty() {
+            return Ok(Vec::new())
+        }
+
+        let hooks = Arc::new(hooks);
+        let periodic_hooks = hooks.clone();
+        task_executor.spawn(Box::pin(async move {
+            let mut interval = tokio::time::interval(hook_interval);
+            loop {
+                interval.tick().await;
+                periodic_hooks.iter().for_each(|hook| hook());
+            }
+        }));
+        Ok(vec![Box::new(move || hooks.iter().for_each(|hook| hook()))])
+    }
+}
+
+impl Default for NodeMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for NodeMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodeMetrics")
+            .field("recorders", &self.recorders.len())
+            .field("hooks", &self.hooks.len())
+            .field("hook_interval", &self.hook_interval)
+            .finish()
+    }
+}
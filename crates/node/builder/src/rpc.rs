@@ -1,21 +1,31 @@
 //! Builder support for rpc components.
 
 use futures::TryFutureExt;
+use reth_blockchain_tree::ReorgGuard;
 use reth_network::NetworkHandle;
 use reth_node_api::FullNodeComponents;
-use reth_node_core::{node_config::NodeConfig, rpc::api::EngineApiServer};
+use reth_node_core::{
+    node_config::NodeConfig,
+    rpc::api::{EngineApiServer, ReorgGuardApiServer, RpcUsageApiServer},
+};
 use reth_payload_builder::PayloadBuilderHandle;
+use reth_rpc::{ReorgGuardApi, RpcUsage, RpcUsageApi};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RethModuleRegistry, RpcModuleBuilder, RpcServerHandle, TransportRpcModules,
+    EthHandlers, RethModuleRegistry, RethRpcModule, RpcModuleBuilder, RpcServerHandle,
+    TransportRpcModules,
 };
+use reth_rpc_graphql::{build_schema, EthBackend, GRAPHQL_PATH};
+use reth_rpc_grpc::{DebugService, EthService, GrpcServices};
 use reth_rpc_layer::JwtSecret;
 use reth_tasks::TaskExecutor;
-use reth_tracing::tracing::{debug, info};
+use reth_tracing::tracing::{debug, info, warn};
 use std::{
     fmt,
+    net::SocketAddr,
     ops::{Deref, DerefMut},
+    sync::Arc,
 };
 
 /// Contains the handles to the spawned RPC servers.
@@ -27,6 +37,8 @@ pub struct RethRpcServerHandles {
     pub rpc: RpcServerHandle,
     /// The handle to the auth server (engine API)
     pub auth: AuthServerHandle,
+    /// The address the gRPC server listens on, if it's enabled.
+    pub grpc: Option<SocketAddr>,
 }
 
 /// Contains hooks that are called during the rpc setup.
@@ -251,7 +263,9 @@ pub(crate) async fn launch_rpc_servers<Node, Engine>(
     node: Node,
     engine_api: Engine,
     config: &NodeConfig,
+    toml_config: &reth_config::Config,
     jwt_secret: JwtSecret,
+    reorg_guard: Option<ReorgGuard>,
     hooks: RpcHooks<Node>,
 ) -> eyre::Result<(RethRpcServerHandles, RpcRegistry<Node>)>
 where
@@ -261,7 +275,15 @@ where
     let RpcHooks { on_rpc_started, extend_rpc_modules } = hooks;
 
     let auth_config = config.rpc.auth_server_config(jwt_secret)?;
-    let module_config = config.rpc.transport_rpc_module_config();
+    let mut module_config = config.rpc.transport_rpc_module_config();
+    // the `--gpo` arguments that are set take precedence over the gas price oracle section of the
+    // toml config
+    if let Some(gas_price_oracle) = &toml_config.gas_price_oracle {
+        if let Some(rpc_module_config) = module_config.config_mut() {
+            rpc_module_config.eth_mut().gas_oracle =
+                config.rpc.gas_price_oracle.merge_gas_price_oracle_config(gas_price_oracle.clone());
+        }
+    }
     debug!(target: "reth::cli", http=?module_config.http(), ws=?module_config.ws(), "Using RPC module config");
 
     let (mut modules, mut auth_module, registry) = RpcModuleBuilder::default()
@@ -273,6 +295,36 @@ where
         .with_evm_config(node.evm_config().clone())
         .build_with_auth_server(module_config, engine_api);
 
+    // confirming deep reorgs is restricted to the local IPC endpoint
+    if let Some(reorg_guard) = reorg_guard {
+        if !modules.merge_ipc(ReorgGuardApi::new(reorg_guard).into_rpc())? {
+            warn!(target: "reth::cli", "IPC is disabled, deep reorgs can't be confirmed");
+        }
+    }
+
+    let usage = if config.rpc.rpc_usage.enabled {
+        let args = &config.rpc.rpc_usage;
+        let file = args.file.clone().unwrap_or_else(|| config.datadir().rpc_usage());
+        let usage = RpcUsage::new(args.compute_units.iter().cloned(), Some(file))?;
+        // the usage of all API keys is only exposed with the admin namespace
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            RpcUsageApi::new(usage.clone()).into_rpc(),
+        )?;
+
+        let persist_interval = args.persist_interval;
+        let table = usage.clone();
+        node.task_executor().spawn_critical_with_graceful_shutdown_signal(
+            "rpc usage persistence",
+            |shutdown| async move {
+                let _guard = table.persist_periodically(persist_interval, shutdown).await;
+            },
+        );
+        Some(usage)
+    } else {
+        None
+    };
+
     let mut registry = RpcRegistry { registry };
     let ctx = RpcContext {
         node: node.clone(),
@@ -284,13 +336,24 @@ where
 
     extend_rpc_modules.extend_rpc_modules(ctx)?;
 
-    let server_config = config.rpc.rpc_server_config();
+    // GraphQL is served by the http server
+    let graphql = config.rpc.graphql.then(|| {
+        let EthHandlers { api, cache, filter, .. } = registry.eth_handlers();
+        let backend =
+            EthBackend::new(node.provider().clone(), node.pool().clone(), api, filter, cache);
+        build_schema(Arc::new(backend))
+    });
+    let server_config = config.rpc.rpc_server_config().with_usage(usage).with_graphql(graphql);
+    let grpc_middleware = server_config.grpc_middleware(modules.http_module());
     let launch_rpc = modules.clone().start_server(server_config).map_ok(|handle| {
         if let Some(path) = handle.ipc_endpoint() {
             info!(target: "reth::cli", %path, "RPC IPC server started");
         }
         if let Some(addr) = handle.http_local_addr() {
             info!(target: "reth::cli", url=%addr, "RPC HTTP server started");
+            if config.rpc.graphql {
+                info!(target: "reth::cli", url=%addr, path=GRAPHQL_PATH, "GraphQL server started");
+            }
         }
         if let Some(addr) = handle.ws_local_addr() {
             info!(target: "reth::cli", url=%addr, "RPC WS server started");
@@ -310,7 +373,33 @@ where
 
     // launch servers concurrently
     let (rpc, auth) = futures::future::try_join(launch_rpc, launch_auth).await?;
-    let handles = RethRpcServerHandles { rpc, auth };
+
+    let grpc = if config.rpc.grpc {
+        // the gRPC services and JSON-RPC over HTTP/2 are served like the modules of the http server
+        let http_configured = |module| {
+            modules.module_config().http().is_some_and(|selection| selection.contains(&module))
+        };
+        let eth = http_configured(RethRpcModule::Eth).then(|| {
+            let EthHandlers { api, cache, filter, .. } = registry.eth_handlers();
+            EthService::new(api, filter, cache)
+        });
+        let debug =
+            http_configured(RethRpcModule::Debug).then(|| DebugService::new(registry.debug_api()));
+        if modules.http_module().is_none() {
+            warn!(target: "reth::cli", "HTTP is disabled, the gRPC server serves no methods");
+        }
+        let services = GrpcServices::new(eth, debug)
+            .with_json_rpc(modules.http_module().cloned())
+            .with_middleware(grpc_middleware);
+        let addr = SocketAddr::new(config.rpc.grpc_addr, config.rpc.grpc_port);
+        let addr = reth_rpc_grpc::start_server(addr, services, node.task_executor()).await?;
+        info!(target: "reth::cli", url=%addr, "gRPC server started");
+        Some(addr)
+    } else {
+        None
+    };
+
+    let handles = RethRpcServerHandles { rpc, auth, grpc };
 
     let ctx = RpcContext {
         node,
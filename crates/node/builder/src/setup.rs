@@ -42,12 +42,15 @@ where
     Client: HeadersClient + BodiesClient + Clone + 'static,
     Executor: BlockExecutorProvider,
 {
-    // building network downloaders using the fetch client
+    // building network downloaders using the fetch client, responses that are written to disk
+    // once their buffers are full go to the ETL directory
     let header_downloader = ReverseHeadersDownloaderBuilder::new(config.headers)
+        .spill_dir(config.etl.dir.clone())
         .build(client.clone(), Arc::clone(&consensus))
         .into_task_with(task_executor);
 
     let body_downloader = BodiesDownloaderBuilder::new(config.bodies)
+        .with_spill_dir(config.etl.dir.clone())
         .build(client, Arc::clone(&consensus), provider_factory.clone())
         .into_task_with(task_executor);
 
@@ -0,0 +1,386 @@
+//! Alerts on critical node events.
+//!
+//! Critical events are reported to the node operator as they happen, by posting them to a webhook,
+//! e.g. a Slack or Discord channel. This includes panics of tasks that are recovered by restarting
+//! the task, see [`RestartPolicy`](reth_tasks::RestartPolicy).
+
+use crate::webhook::Webhook;
+use futures::{future, stream, stream::BoxStream, Stream, StreamExt};
+use reth_beacon_consensus::BeaconConsensusEngineEvent;
+use reth_node_core::args::AlertFormat;
+use reth_primitives::{BlockNumber, B256};
+use reth_provider::CanonStateNotification;
+use reth_prune::PrunerEvent;
+use reth_tasks::TaskRestarted;
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use sysinfo::Disks;
+
+/// Interval of checking the free disk space.
+const DISK_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Configuration of [`AlertEvents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AlertConfig {
+    /// URL that alerts are posted to.
+    pub webhook_url: Option<String>,
+    /// Format of the webhook request body.
+    pub format: AlertFormat,
+    /// Minimum number of reverted blocks for a reorg to be alerted.
+    pub reorg_depth: u64,
+    /// Free disk space of the data directory, in bytes, below which an alert is emitted.
+    pub min_free_disk_space: u64,
+    /// The data directory of the node.
+    pub datadir: PathBuf,
+}
+
+/// A Stream of [`Alert`]s.
+///
+/// Every alert is emitted once, and posted to the configured webhook in the background. A low disk
+/// space alert is emitted again only after the disk space recovered in between.
+pub struct AlertEvents {
+    config: AlertConfig,
+    engine_events: BoxStream<'static, BeaconConsensusEngineEvent>,
+    canon_state_notifications: BoxStream<'static, CanonStateNotification>,
+    pruner_events: BoxStream<'static, PrunerEvent>,
+    restarted_tasks: BoxStream<'static, TaskRestarted>,
+    /// The periodically checked free disk space of the data directory.
+    free_disk_space: BoxStream<'static, u64>,
+    /// Whether the free disk space is below the threshold.
+    low_disk_space: bool,
+    webhook: Option<Webhook>,
+}
+
+impl fmt::Debug for AlertEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlertEvents")
+            .field("config", &self.config)
+            .field("low_disk_space", &self.low_disk_space)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AlertEvents {
+    /// Creates a new [`AlertEvents`] that monitors the given consensus engine, canonical state,
+    /// pruner and task restart events.
+    pub fn new(
+        config: AlertConfig,
+        engine_events: impl Stream<Item = BeaconConsensusEngineEvent> + Send + 'static,
+        canon_state_notifications: impl Stream<Item = CanonStateNotification> + Send + 'static,
+        pruner_events: impl Stream<Item = PrunerEvent> + Send + 'static,
+        restarted_tasks: impl Stream<Item = TaskRestarted> + Send + 'static,
+    ) -> Self {
+        let free_disk_space = free_disk_space(config.datadir.clone()).boxed();
+        let webhook = config.webhook_url.clone().map(Webhook::new);
+        Self {
+            config,
+            engine_events: engine_events.boxed(),
+            canon_state_notifications: canon_state_notifications.boxed(),
+            pruner_events: pruner_events.boxed(),
+            restarted_tasks: restarted_tasks.boxed(),
+            free_disk_space,
+            low_disk_space: false,
+            webhook,
+        }
+    }
+
+    fn on_engine_event(&self, event: BeaconConsensusEngineEvent) -> Option<Alert> {
+        match event {
+            BeaconConsensusEngineEvent::InvalidBlock(header) => {
+                Some(Alert::InvalidBlock { number: header.number, hash: header.hash() })
+            }
+            BeaconConsensusEngineEvent::ReorgNotConfirmed { head, depth } => {
+                Some(Alert::ReorgNotConfirmed { head, depth })
+            }
+            _ => None,
+        }
+    }
+
+    fn on_canon_state_notification(&self, notification: CanonStateNotification) -> Option<Alert> {
+        let old = notification.reverted()?;
+        let depth = old.len() as u64;
+        (depth >= self.config.reorg_depth).then(|| Alert::Reorg {
+            depth,
+            old_tip: old.tip().number,
+            old_tip_hash: old.tip().hash(),
+        })
+    }
+
+    fn on_pruner_event(&self, event: PrunerEvent) -> Option<Alert> {
+        match event {
+            PrunerEvent::Failed { tip_block_number, error } => {
+                Some(Alert::PruneFailed { tip_block_number, error })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an alert if the free disk space of the data directory dropped below the threshold.
+    fn on_free_disk_space(&mut self, available: u64) -> Option<Alert> {
+        if available >= self.config.min_free_disk_space {
+            self.low_disk_space = false;
+            return None
+        }
+        if self.low_disk_space {
+            return None
+        }
+        self.low_disk_space = true;
+        Some(Alert::LowDiskSpace { path: self.config.datadir.clone(), available })
+    }
+
+    /// Posts the alert to the configured webhook in the background.
+    fn notify_webhook(&self, alert: &Alert) {
+        if let Some(webhook) = &self.webhook {
+            webhook.notify(&alert.to_body(self.config.format));
+        }
+    }
+}
+
+impl Stream for AlertEvents {
+    type Item = Alert;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let alert = loop {
+            if let Poll::Ready(Some(event)) = this.engine_events.poll_next_unpin(cx) {
+                if let Some(alert) = this.on_engine_event(event) {
+                    break alert
+                }
+                continue
+            }
+            if let Poll::Ready(Some(notification)) =
+                this.canon_state_notifications.poll_next_unpin(cx)
+            {
+                if let Some(alert) = this.on_canon_state_notification(notification) {
+                    break alert
+                }
+                continue
+            }
+            if let Poll::Ready(Some(event)) = this.pruner_events.poll_next_unpin(cx) {
+                if let Some(alert) = this.on_pruner_event(event) {
+                    break alert
+                }
+                continue
+            }
+            if let Poll::Ready(Some(event)) = this.restarted_tasks.poll_next_unpin(cx) {
+                let TaskRestarted { task_name, error, restart } = event;
+                break Alert::TaskRestarted { task_name, error, restart }
+            }
+            if let Poll::Ready(Some(available)) = this.free_disk_space.poll_next_unpin(cx) {
+                if let Some(alert) = this.on_free_disk_space(available) {
+                    break alert
+                }
+                continue
+            }
+            return Poll::Pending
+        };
+
+        this.notify_webhook(&alert);
+        Poll::Ready(Some(alert))
+    }
+}
+
+/// Returns a stream of the free disk space of the data directory, checked every
+/// [`DISK_CHECK_INTERVAL`].
+///
+/// Listing the disks can block on unresponsive mounts, so the disks are listed on a blocking task.
+fn free_disk_space(datadir: PathBuf) -> impl Stream<Item = u64> {
+    let state = (tokio::time::interval(DISK_CHECK_INTERVAL), Disks::new(), datadir);
+    stream::unfold(state, |(mut interval, disks, datadir)| async move {
+        interval.tick().await;
+        let (available, disks, datadir) =
+            tokio::task::spawn_blocking(move || refresh_free_disk_space(disks, datadir))
+                .await
+                .ok()?;
+        Some((available, (interval, disks, datadir)))
+    })
+    .filter_map(future::ready)
+}
+
+/// Lists the disks, and returns the free disk space of the data directory.
+fn refresh_free_disk_space(mut disks: Disks, datadir: PathBuf) -> (Option<u64>, Disks, PathBuf) {
+    disks.refresh_list();
+    let available = available_disk_space(
+        disks.list().iter().map(|disk| (disk.mount_point(), disk.available_space())),
+        &datadir,
+    );
+    (available, disks, datadir)
+}
+
+/// Returns the available space of the disk that the path is stored on, which is the disk with the
+/// longest mount point that the path starts with.
+fn available_disk_space<'a>(
+    disks: impl IntoIterator<Item = (&'a Path, u64)>,
+    path: &Path,
+) -> Option<u64> {
+    disks
+        .into_iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, available)| available)
+}
+
+/// A critical node event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Alert {
+    /// The canonical chain reorged at least the configured number of blocks.
+    Reorg {
+        /// The number of reverted blocks.
+        depth: u64,
+        /// The number of the tip before the reorg.
+        old_tip: BlockNumber,
+        /// The hash of the tip before the reorg.
+        old_tip_hash: B256,
+    },
+    /// A block failed validation.
+    InvalidBlock {
+        /// The number of the invalid block.
+        number: BlockNumber,
+        /// The hash of the invalid block.
+        hash: B256,
+    },
+    /// A reorg deeper than the reorg guard's maximum depth was rejected until it is confirmed.
+    ReorgNotConfirmed {
+        /// The hash of the new head.
+        head: B256,
+        /// The number of canonical blocks that would be reverted.
+        depth: u64,
+    },
+    /// The pruner failed.
+    PruneFailed {
+        /// The tip block number the pruner ran for.
+        tip_block_number: BlockNumber,
+        /// The error the pruner failed with.
+        error: String,
+    },
+    /// A task panicked and was restarted.
+    TaskRestarted {
+        /// The name of the task.
+        task_name: &'static str,
+        /// The panic message, if any.
+        error: Option<String>,
+        /// The number of the restart, starting at one.
+        restart: usize,
+    },
+    /// The free disk space of the data directory dropped below the configured threshold.
+    LowDiskSpace {
+        /// The data directory of the node.
+        path: PathBuf,
+        /// The available disk space, in bytes.
+        available: u64,
+    },
+}
+
+impl Alert {
+    /// Returns the webhook request body in the given format.
+    fn to_body(&self, format: AlertFormat) -> serde_json::Value {
+        match format {
+            AlertFormat::Json => self.to_json(),
+            AlertFormat::Slack => serde_json::json!({ "text": format!("reth: {self}") }),
+            AlertFormat::Discord => serde_json::json!({ "content": format!("reth: {self}") }),
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            Self::Reorg { depth, old_tip, old_tip_hash } => serde_json::json!({
+                "event": "reorg",
+                "depth": depth,
+                "old_tip": old_tip,
+                "old_tip_hash": old_tip_hash,
+            }),
+            Self::InvalidBlock { number, hash } => serde_json::json!({
+                "event": "invalid_block",
+                "number": number,
+                "hash": hash,
+            }),
+            Self::ReorgNotConfirmed { head, depth } => serde_json::json!({
+                "event": "reorg_not_confirmed",
+                "head": head,
+                "depth": depth,
+            }),
+            Self::PruneFailed { tip_block_number, error } => serde_json::json!({
+                "event": "prune_failed",
+                "tip_block_number": tip_block_number,
+                "error": error,
+            }),
+            Self::TaskRestarted { task_name, error, restart } => serde_json::json!({
+                "event": "task_restarted",
+                "task": task_name,
+                "error": error,
+                "restart": restart,
+            }),
+            Self::LowDiskSpace { path, available } => serde_json::json!({
+                "event": "low_disk_space",
+                "path": path,
+                "available_bytes": available,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for Alert {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Reorg { depth, old_tip, old_tip_hash } => {
+                write!(f, "reorg of {depth} blocks from tip {old_tip} ({old_tip_hash})")
+            }
+            Self::InvalidBlock { number, hash } => write!(f, "invalid block {number} ({hash})"),
+            Self::ReorgNotConfirmed { head, depth } => {
+                write!(f, "reorg of {depth} blocks to {head} is waiting for confirmation")
+            }
+            Self::PruneFailed { tip_block_number, error } => {
+                write!(f, "pruner failed at tip {tip_block_number}: {error}")
+            }
+            Self::TaskRestarted { task_name, error, restart } => {
+                write!(f, "task `{task_name}` panicked and was restarted ({restart})")?;
+                if let Some(error) = error {
+                    write!(f, ": {error}")?;
+                }
+                Ok(())
+            }
+            Self::LowDiskSpace { path, available } => write!(
+                f,
+                "low disk space for {}: {:.2} GB available",
+                path.display(),
+                *available as f64 / 1e9
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alert_body_formats() {
+        let alert = Alert::InvalidBlock { number: 1, hash: B256::ZERO };
+        assert_eq!(
+            alert.to_body(AlertFormat::Json),
+            serde_json::json!({ "event": "invalid_block", "number": 1, "hash": B256::ZERO })
+        );
+        assert_eq!(
+            alert.to_body(AlertFormat::Slack),
+            serde_json::json!({ "text": format!("reth: invalid block 1 ({})", B256::ZERO) })
+        );
+        assert_eq!(
+            alert.to_body(AlertFormat::Discord),
+            serde_json::json!({ "content": format!("reth: invalid block 1 ({})", B256::ZERO) })
+        );
+    }
+
+    #[test]
+    fn datadir_disk_space() {
+        let disks = [(Path::new("/"), 1), (Path::new("/data"), 2), (Path::new("/datadir"), 3)];
+        assert_eq!(available_disk_space(disks, Path::new("/data/reth/mainnet")), Some(2));
+        assert_eq!(available_disk_space(disks, Path::new("/home/reth")), Some(1));
+        assert_eq!(available_disk_space([], Path::new("/home/reth")), None);
+    }
+}
@@ -0,0 +1,320 @@
+//! Self-monitoring of the sync progress of the node.
+//!
+//! Detects when the node stops following the chain, so that it's reported right away instead of
+//! being noticed hours later.
+
+use crate::webhook::Webhook;
+use futures::{stream::BoxStream, Stream, StreamExt};
+use metrics::{Counter, Gauge};
+use reth_beacon_consensus::{BeaconConsensusEngineEvent, ForkchoiceStatus};
+use reth_metrics::Metrics;
+use reth_primitives::{BlockNumber, B256};
+use reth_stages::{ExecOutput, PipelineEvent};
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::{Instant, Interval};
+
+/// Interval of checking the sync health.
+const CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default period without sync progress, while behind the head requested by the Consensus Layer
+/// client, after which the head is considered stalled.
+pub const DEFAULT_STALL_THRESHOLD: Duration = Duration::from_secs(300);
+
+/// Default period without forkchoice updates from the Consensus Layer client after which a gap is
+/// reported.
+pub const DEFAULT_FORKCHOICE_GAP_THRESHOLD: Duration = Duration::from_secs(300);
+
+/// Configuration of [`SyncHealthEvents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncHealthConfig {
+    /// Period without sync progress, while behind the head requested by the Consensus Layer
+    /// client, after which [`SyncHealthEvent::HeadStalled`] is emitted.
+    pub stall_threshold: Duration,
+    /// Period without forkchoice updates after which [`SyncHealthEvent::ForkchoiceGap`] is
+    /// emitted.
+    pub forkchoice_gap_threshold: Duration,
+    /// URL that detected conditions are posted to as JSON.
+    pub webhook_url: Option<String>,
+}
+
+impl Default for SyncHealthConfig {
+    fn default() -> Self {
+        Self {
+            stall_threshold: DEFAULT_STALL_THRESHOLD,
+            forkchoice_gap_threshold: DEFAULT_FORKCHOICE_GAP_THRESHOLD,
+            webhook_url: None,
+        }
+    }
+}
+
+/// A Stream of [`SyncHealthEvent`].
+///
+/// The events are emitted on every check for as long as the condition holds, the webhook is only
+/// called once when the condition is detected.
+///
+/// There's no block number in the `eth` status of the peers after the merge, so the head requested
+/// by the forkchoice updates of the Consensus Layer client is the reference for the chain head.
+pub struct SyncHealthEvents {
+    config: SyncHealthConfig,
+    interval: Interval,
+    engine_events: BoxStream<'static, BeaconConsensusEngineEvent>,
+    pipeline_events: BoxStream<'static, PipelineEvent>,
+    /// The latest canonical head.
+    head: Option<BlockNumber>,
+    /// The time the node last made sync progress, either by committing a canonical head or by a
+    /// pipeline stage making progress.
+    last_progress: Instant,
+    /// The head requested by the last forkchoice update, if the node is still syncing to it.
+    syncing_target: Option<B256>,
+    /// The time the last forkchoice update was received.
+    last_forkchoice_update: Option<Instant>,
+    /// Whether the current stall was already detected.
+    stalled: bool,
+    /// Whether the current forkchoice gap was already detected.
+    forkchoice_gap: bool,
+    metrics: SyncHealthMetrics,
+    webhook: Option<Webhook>,
+}
+
+impl fmt::Debug for SyncHealthEvents {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SyncHealthEvents")
+            .field("config", &self.config)
+            .field("head", &self.head)
+            .field("syncing_target", &self.syncing_target)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SyncHealthEvents {
+    /// Creates a new [`SyncHealthEvents`] that monitors the given consensus engine and pipeline
+    /// events.
+    pub fn new(
+        config: SyncHealthConfig,
+        engine_events: impl Stream<Item = BeaconConsensusEngineEvent> + Send + 'static,
+        pipeline_events: impl Stream<Item = PipelineEvent> + Send + 'static,
+    ) -> Self {
+        let interval = tokio::time::interval_at(Instant::now() + CHECK_INTERVAL, CHECK_INTERVAL);
+        let webhook = config.webhook_url.clone().map(Webhook::new);
+        Self {
+            config,
+            interval,
+            engine_events: engine_events.boxed(),
+            pipeline_events: pipeline_events.boxed(),
+            head: None,
+            last_progress: Instant::now(),
+            syncing_target: None,
+            last_forkchoice_update: None,
+            stalled: false,
+            forkchoice_gap: false,
+            metrics: SyncHealthMetrics::default(),
+            webhook,
+        }
+    }
+
+    fn on_engine_event(&mut self, event: BeaconConsensusEngineEvent) {
+        match event {
+            BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status) => {
+                self.last_forkchoice_update = Some(Instant::now());
+                self.forkchoice_gap = false;
+                self.syncing_target =
+                    (status == ForkchoiceStatus::Syncing).then_some(state.head_block_hash);
+            }
+            BeaconConsensusEngineEvent::CanonicalChainCommitted(head, _) => {
+                self.head = Some(head.number);
+                self.on_progress();
+            }
+            _ => {}
+        }
+    }
+
+    fn on_pipeline_event(&mut self, event: PipelineEvent) {
+        if let PipelineEvent::Ran { stage_id, result: ExecOutput { checkpoint, .. }, .. } = event {
+            if stage_id.is_finish() {
+                self.head = Some(checkpoint.block_number);
+            }
+            self.on_progress();
+        }
+    }
+
+    fn on_progress(&mut self) {
+        self.last_progress = Instant::now();
+        self.stalled = false;
+    }
+
+    /// Checks the sync health, returns the detected condition if any.
+    fn check(&mut self) -> Option<SyncHealthEvent> {
+        let since_progress = self.last_progress.elapsed();
+        self.metrics.seconds_since_progress.set(since_progress.as_secs_f64());
+
+        if let Some(target) = self.syncing_target {
+            if since_progress >= self.config.stall_threshold {
+                let event = SyncHealthEvent::HeadStalled {
+                    head: self.head,
+                    target,
+                    period: since_progress,
+                };
+                if !self.stalled {
+                    self.stalled = true;
+                    self.metrics.head_stalls.increment(1);
+                    self.notify_webhook(&event);
+                }
+                return Some(event)
+            }
+        }
+
+        let since_update = self.last_forkchoice_update?.elapsed();
+        self.metrics.seconds_since_forkchoice_update.set(since_update.as_secs_f64());
+        if since_update >= self.config.forkchoice_gap_threshold {
+            let event = SyncHealthEvent::ForkchoiceGap(since_update);
+            if !self.forkchoice_gap {
+                self.forkchoice_gap = true;
+                self.metrics.forkchoice_gaps.increment(1);
+                self.notify_webhook(&event);
+            }
+            return Some(event)
+        }
+
+        None
+    }
+
+    /// Posts the event to the configured webhook in the background.
+    fn notify_webhook(&self, event: &SyncHealthEvent) {
+        if let Some(webhook) = &self.webhook {
+            webhook.notify(&event.to_json());
+        }
+    }
+}
+
+impl Stream for SyncHealthEvents {
+    type Item = SyncHealthEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while let Poll::Ready(Some(event)) = this.engine_events.poll_next_unpin(cx) {
+            this.on_engine_event(event);
+        }
+        while let Poll::Ready(Some(event)) = this.pipeline_events.poll_next_unpin(cx) {
+            this.on_pipeline_event(event);
+        }
+
+        while this.interval.poll_tick(cx).is_ready() {
+            if let Some(event) = this.check() {
+                return Poll::Ready(Some(event))
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Event that is triggered when the node stops following the chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncHealthEvent {
+    /// The node made no sync progress for a while, although the Consensus Layer client requested
+    /// a head that the node hasn't synced yet.
+    HeadStalled {
+        /// The latest canonical head, if any was committed since the node started.
+        head: Option<BlockNumber>,
+        /// The head requested by the last forkchoice update.
+        target: B256,
+        /// The period without sync progress.
+        period: Duration,
+    },
+    /// No forkchoice updates were received from the Consensus Layer client for a while.
+    ForkchoiceGap(Duration),
+}
+
+impl SyncHealthEvent {
+    /// Returns the payload that is posted to the webhook.
+    fn to_json(self) -> serde_json::Value {
+        match self {
+            Self::HeadStalled { head, target, period } => serde_json::json!({
+                "event": "head_stalled",
+                "head": head,
+                "target": target,
+                "period_secs": period.as_secs(),
+            }),
+            Self::ForkchoiceGap(period) => serde_json::json!({
+                "event": "forkchoice_gap",
+                "period_secs": period.as_secs(),
+            }),
+        }
+    }
+}
+
+/// Sync health metrics.
+#[derive(Metrics)]
+#[metrics(scope = "sync.health")]
+struct SyncHealthMetrics {
+    /// Number of detected head stalls
+    head_stalls: Counter,
+    /// Number of detected forkchoice update gaps
+    forkchoice_gaps: Counter,
+    /// Seconds since the node last made sync progress
+    seconds_since_progress: Gauge,
+    /// Seconds since the last forkchoice update was received
+    seconds_since_forkchoice_update: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::ForkchoiceState;
+    use futures::stream;
+    use reth_primitives::Header;
+
+    fn health_events(engine_events: Vec<BeaconConsensusEngineEvent>) -> SyncHealthEvents {
+        let config = SyncHealthConfig {
+            stall_threshold: Duration::from_secs(60),
+            forkchoice_gap_threshold: Duration::from_secs(120),
+            webhook_url: None,
+        };
+        SyncHealthEvents::new(
+            config,
+            stream::iter(engine_events).chain(stream::pending()),
+            stream::pending(),
+        )
+    }
+
+    fn forkchoice_updated(head: B256, status: ForkchoiceStatus) -> BeaconConsensusEngineEvent {
+        let state = ForkchoiceState { head_block_hash: head, ..Default::default() };
+        BeaconConsensusEngineEvent::ForkchoiceUpdated(state, status)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn detects_head_stall() {
+        let target = B256::random();
+        let head = Header { number: 10, ..Default::default() }.seal_slow();
+        let mut events = health_events(vec![
+            BeaconConsensusEngineEvent::CanonicalChainCommitted(Box::new(head), Duration::ZERO),
+            forkchoice_updated(target, ForkchoiceStatus::Syncing),
+        ]);
+
+        let event = events.next().await.unwrap();
+        assert_eq!(
+            event,
+            SyncHealthEvent::HeadStalled {
+                head: Some(10),
+                target,
+                period: Duration::from_secs(60)
+            }
+        );
+        assert!(events.stalled);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn detects_forkchoice_gap() {
+        let mut events =
+            health_events(vec![forkchoice_updated(B256::random(), ForkchoiceStatus::Valid)]);
+
+        let event = events.next().await.unwrap();
+        assert_eq!(event, SyncHealthEvent::ForkchoiceGap(Duration::from_secs(120)));
+    }
+}
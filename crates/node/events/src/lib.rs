@@ -8,5 +8,9 @@
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
+pub mod alerts;
 pub mod cl;
+pub mod health;
 pub mod node;
+pub mod space;
+mod webhook;
@@ -1,11 +1,14 @@
 //! Support for handling events emitted by node components.
 
-use crate::cl::ConsensusLayerHealthEvent;
+use crate::{
+    alerts::Alert, cl::ConsensusLayerHealthEvent, health::SyncHealthEvent, space::SpaceReportEvent,
+};
 use alloy_rpc_types_engine::ForkchoiceState;
 use futures::Stream;
 use reth_beacon_consensus::{
     BeaconConsensusEngineEvent, ConsensusEngineLiveSyncProgress, ForkchoiceStatus,
 };
+use reth_consensus_debug_client::ShadowMismatch;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetadata};
 use reth_network::{NetworkEvent, NetworkHandle};
 use reth_network_api::PeersInfo;
@@ -298,6 +301,12 @@ impl<DB> NodeState<DB> {
             BeaconConsensusEngineEvent::ForkBlockAdded(block) => {
                 info!(number=block.number, hash=?block.hash(), "Block added to fork chain");
             }
+            BeaconConsensusEngineEvent::InvalidBlock(header) => {
+                warn!(number=header.number, hash=?header.hash(), "Block marked as invalid");
+            }
+            BeaconConsensusEngineEvent::ReorgNotConfirmed { head, depth } => {
+                warn!(?head, depth, "Deep reorg rejected, waiting for confirmation");
+            }
         }
     }
 
@@ -322,6 +331,23 @@ impl<DB> NodeState<DB> {
         }
     }
 
+    fn handle_sync_health_event(&self, event: SyncHealthEvent) {
+        match event {
+            SyncHealthEvent::HeadStalled { head, target, period } => {
+                warn!(
+                    head = %OptionalField(head),
+                    ?target,
+                    ?period,
+                    peers = self.num_connected_peers(),
+                    "Sync stalled, the head did not advance for a while although the beacon client requested a newer head. Please investigate reth logs and the connected peers!"
+                )
+            }
+            SyncHealthEvent::ForkchoiceGap(period) => {
+                warn!(?period, "No forkchoice updates received from the beacon client for a while. Please investigate the beacon client logs!")
+            }
+        }
+    }
+
     fn handle_pruner_event(&self, event: PrunerEvent) {
         match event {
             PrunerEvent::Started { tip_block_number } => {
@@ -330,9 +356,30 @@ impl<DB> NodeState<DB> {
             PrunerEvent::Finished { tip_block_number, elapsed, stats } => {
                 info!(tip_block_number, ?elapsed, ?stats, "Pruner finished");
             }
+            PrunerEvent::Failed { tip_block_number, error } => {
+                warn!(tip_block_number, %error, "Pruner failed");
+            }
         }
     }
 
+    fn handle_alert(&self, alert: Alert) {
+        match alert {
+            Alert::Reorg { depth, old_tip, old_tip_hash } => {
+                warn!(depth, old_tip, ?old_tip_hash, "Deep reorg of the canonical chain");
+            }
+            Alert::LowDiskSpace { path, available } => {
+                warn!(path = %path.display(), available_gb = available / 1_000_000_000, "Low disk space in the data directory");
+            }
+            // already logged by the event the alert originates from
+            alert => debug!(%alert, "Alert triggered"),
+        }
+    }
+
+    fn handle_shadow_mismatch(&self, mismatch: ShadowMismatch) {
+        let ShadowMismatch { block_number, kind, local, remote } = mismatch;
+        warn!(block_number, ?kind, %local, %remote, "Canonical block differs from external node");
+    }
+
     fn handle_static_file_producer_event(&self, event: StaticFileProducerEvent) {
         match event {
             StaticFileProducerEvent::Started { targets } => {
@@ -343,6 +390,26 @@ impl<DB> NodeState<DB> {
             }
         }
     }
+
+    fn handle_space_report_event(&self, event: SpaceReportEvent) {
+        let SpaceReportEvent { report, growth, interval } = event;
+        let growth = OptionalField(growth.map(|growth| {
+            format!("{:.2} GB over {} blocks", growth.bytes as f64 / 1e9, growth.blocks)
+        }));
+        let projected_growth = report.projected_growth(interval) as f64 / 1e9;
+        info!(
+            tip = report.tip,
+            total = %format!("{:.2} GB", report.total_size() as f64 / 1e9),
+            database = %format!("{:.2} GB", report.database_size() as f64 / 1e9),
+            static_files = %format!("{:.2} GB", report.static_files_size() as f64 / 1e9),
+            %growth,
+            projected_growth = %format!("{projected_growth:.2} GB over {interval} blocks"),
+            "Space report"
+        );
+        for suggestion in report.suggestions() {
+            info!(%suggestion, "Space report suggestion");
+        }
+    }
 }
 
 impl<DB: DatabaseMetadata> NodeState<DB> {
@@ -389,10 +456,18 @@ pub enum NodeEvent {
     ConsensusEngine(BeaconConsensusEngineEvent),
     /// A Consensus Layer health event.
     ConsensusLayerHealth(ConsensusLayerHealthEvent),
+    /// A sync health event.
+    SyncHealth(SyncHealthEvent),
     /// A pruner event
     Pruner(PrunerEvent),
+    /// An alert on a critical node event.
+    Alert(Alert),
     /// A `static_file_producer` event
     StaticFileProducer(StaticFileProducerEvent),
+    /// A report of the disk space used by the node.
+    SpaceReport(SpaceReportEvent),
+    /// A mismatch between a canonical block and the block of the external node it's compared with.
+    ShadowMismatch(ShadowMismatch),
     /// Used to encapsulate various conditions or situations that do not
     /// naturally fit into the other more specific variants.
     Other(String),
@@ -422,18 +497,42 @@ impl From<ConsensusLayerHealthEvent> for NodeEvent {
     }
 }
 
+impl From<SyncHealthEvent> for NodeEvent {
+    fn from(event: SyncHealthEvent) -> Self {
+        Self::SyncHealth(event)
+    }
+}
+
 impl From<PrunerEvent> for NodeEvent {
     fn from(event: PrunerEvent) -> Self {
         Self::Pruner(event)
     }
 }
 
+impl From<Alert> for NodeEvent {
+    fn from(alert: Alert) -> Self {
+        Self::Alert(alert)
+    }
+}
+
 impl From<StaticFileProducerEvent> for NodeEvent {
     fn from(event: StaticFileProducerEvent) -> Self {
         Self::StaticFileProducer(event)
     }
 }
 
+impl From<SpaceReportEvent> for NodeEvent {
+    fn from(event: SpaceReportEvent) -> Self {
+        Self::SpaceReport(event)
+    }
+}
+
+impl From<ShadowMismatch> for NodeEvent {
+    fn from(mismatch: ShadowMismatch) -> Self {
+        Self::ShadowMismatch(mismatch)
+    }
+}
+
 /// Displays relevant information to the user from components of the node, and periodically
 /// displays the high-level status of the node.
 pub async fn handle_events<E, DB>(
@@ -573,12 +672,24 @@ where
                 NodeEvent::ConsensusLayerHealth(event) => {
                     this.state.handle_consensus_layer_health_event(event)
                 }
+                NodeEvent::SyncHealth(event) => {
+                    this.state.handle_sync_health_event(event);
+                }
                 NodeEvent::Pruner(event) => {
                     this.state.handle_pruner_event(event);
                 }
+                NodeEvent::Alert(alert) => {
+                    this.state.handle_alert(alert);
+                }
                 NodeEvent::StaticFileProducer(event) => {
                     this.state.handle_static_file_producer_event(event);
                 }
+                NodeEvent::SpaceReport(event) => {
+                    this.state.handle_space_report_event(event);
+                }
+                NodeEvent::ShadowMismatch(mismatch) => {
+                    this.state.handle_shadow_mismatch(mismatch);
+                }
                 NodeEvent::Other(event_description) => {
                     warn!("{event_description}");
                 }
@@ -0,0 +1,569 @@
+//! Reports of the disk space used by the node.
+//!
+//! A [`SpaceReport`] breaks down the size of the data directory by database table, static file
+//! segment and prune segment, projects its growth and suggests configuration changes that reclaim
+//! space. [`SpaceReportEvents`] takes a report whenever the canonical chain crosses a multiple of
+//! the configured number of epochs, and `reth space report` takes one on demand.
+
+use futures::{stream::BoxStream, Stream, StreamExt};
+use metrics::Gauge;
+use reth_db::Tables;
+use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
+use reth_metrics::Metrics;
+use reth_primitives::{BlockNumber, StaticFileSegment};
+use reth_provider::{
+    CanonStateNotification, ProviderFactory, ProviderResult, StaticFileProviderFactory,
+};
+use reth_prune::{PruneMode, PruneModes, PruneSegment, MINIMUM_PRUNING_DISTANCE};
+use std::{
+    collections::HashMap,
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tracing::debug;
+
+/// Number of blocks in an epoch.
+pub const EPOCH_BLOCKS: u64 = 32;
+
+/// Share of the total size above which pruning an unpruned segment is suggested.
+const PRUNE_SUGGESTION_SHARE: f64 = 0.05;
+
+/// Share of the database size above which reclaiming the free pages of the database is suggested.
+const DEFRAG_SUGGESTION_SHARE: f64 = 0.2;
+
+/// The prune segments that can be configured, with the database tables and the static file segment
+/// that hold their data.
+const PRUNE_SEGMENTS: [(PruneSegment, &[Tables], Option<StaticFileSegment>); 5] = [
+    (PruneSegment::SenderRecovery, &[Tables::TransactionSenders], None),
+    (PruneSegment::TransactionLookup, &[Tables::TransactionHashNumbers], None),
+    (PruneSegment::Receipts, &[Tables::Receipts], Some(StaticFileSegment::Receipts)),
+    (PruneSegment::AccountHistory, &[Tables::AccountChangeSets, Tables::AccountsHistory], None),
+    (PruneSegment::StorageHistory, &[Tables::StorageChangeSets, Tables::StoragesHistory], None),
+];
+
+/// Disk usage of a part of the data directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceUsage<T> {
+    /// The part of the data directory, e.g. a database table.
+    pub part: T,
+    /// Size of the part, in bytes.
+    pub size: u64,
+    /// Average growth of the part per block, in bytes. Zero for data that the pruner keeps at a
+    /// fixed distance from the tip.
+    pub growth_per_block: u64,
+}
+
+/// Disk usage of the data directory, broken down by database table, static file segment and prune
+/// segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceReport {
+    /// The block number of the tip the report was taken at.
+    pub tip: BlockNumber,
+    /// Usage of every database table.
+    pub tables: Vec<SpaceUsage<Tables>>,
+    /// Size of the free pages of the database, in bytes.
+    pub freelist: u64,
+    /// Usage of every static file segment.
+    pub static_files: Vec<SpaceUsage<StaticFileSegment>>,
+    /// Usage of every configurable prune segment, which is the usage of the tables and static
+    /// files holding its data.
+    pub prune_segments: Vec<SpaceUsage<PruneSegment>>,
+    /// The configured prune modes.
+    pub prune_modes: PruneModes,
+}
+
+impl SpaceReport {
+    /// Takes a report of the database and static files of the given provider factory at the given
+    /// tip.
+    pub fn new<DB: Database + DatabaseMetrics>(
+        provider_factory: &ProviderFactory<DB>,
+        prune_modes: PruneModes,
+        tip: BlockNumber,
+    ) -> ProviderResult<Self> {
+        let mut tables = Vec::with_capacity(Tables::COUNT);
+        let (mut freelist_pages, mut page_size) = (0, 0);
+        for (name, value, labels) in provider_factory.db_ref().gauge_metrics() {
+            match name {
+                "db.table_size" => {
+                    let table = labels
+                        .iter()
+                        .find(|label| label.key() == "table")
+                        .and_then(|label| label.value().parse::<Tables>().ok());
+                    if let Some(table) = table {
+                        tables.push((table, value as u64));
+                    }
+                }
+                "db.freelist" => freelist_pages = value as u64,
+                "db.page_size" => page_size = value as u64,
+                _ => {}
+            }
+        }
+
+        let static_files = provider_factory
+            .static_file_provider()
+            .segment_sizes()?
+            .into_iter()
+            .map(|(segment, block_range, size)| {
+                (segment, size, block_range.end() - block_range.start() + 1)
+            })
+            .collect();
+
+        Ok(Self::from_sizes(tip, tables, freelist_pages * page_size, static_files, prune_modes))
+    }
+
+    /// Creates a report from the sizes of the database tables and the static file segments, in
+    /// bytes. Static file segments are given with the number of blocks they contain.
+    pub fn from_sizes(
+        tip: BlockNumber,
+        tables: Vec<(Tables, u64)>,
+        freelist: u64,
+        static_files: Vec<(StaticFileSegment, u64, u64)>,
+        prune_modes: PruneModes,
+    ) -> Self {
+        // Pruned data that is kept at a fixed distance from the tip doesn't grow
+        let is_steady = |segment: Option<PruneSegment>| {
+            segment
+                .and_then(|segment| prune_mode(&prune_modes, segment))
+                .is_some_and(|mode| matches!(mode, PruneMode::Full | PruneMode::Distance(_)))
+        };
+
+        let tables = tables
+            .into_iter()
+            .map(|(table, size)| {
+                let segment = PRUNE_SEGMENTS
+                    .iter()
+                    .find(|(_, tables, _)| tables.contains(&table))
+                    .map(|(segment, _, _)| *segment);
+                let growth_per_block = if is_steady(segment) { 0 } else { size / (tip + 1) };
+                SpaceUsage { part: table, size, growth_per_block }
+            })
+            .collect::<Vec<_>>();
+
+        let static_files = static_files
+            .into_iter()
+            .map(|(segment, size, blocks)| {
+                let prune_segment = PRUNE_SEGMENTS
+                    .iter()
+                    .find(|(_, _, static_file)| *static_file == Some(segment))
+                    .map(|(segment, _, _)| *segment);
+                let growth_per_block =
+                    if is_steady(prune_segment) { 0 } else { size / blocks.max(1) };
+                SpaceUsage { part: segment, size, growth_per_block }
+            })
+            .collect::<Vec<_>>();
+
+        let prune_segments = PRUNE_SEGMENTS
+            .iter()
+            .map(|(segment, segment_tables, static_file)| {
+                let (size, growth_per_block) = tables
+                    .iter()
+                    .filter(|usage| segment_tables.contains(&usage.part))
+                    .map(|usage| (usage.size, usage.growth_per_block))
+                    .chain(
+                        static_files
+                            .iter()
+                            .filter(|usage| Some(usage.part) == *static_file)
+                            .map(|usage| (usage.size, usage.growth_per_block)),
+                    )
+                    .fold((0, 0), |acc, (size, growth)| (acc.0 + size, acc.1 + growth));
+                SpaceUsage { part: *segment, size, growth_per_block }
+            })
+            .collect();
+
+        Self { tip, tables, freelist, static_files, prune_segments, prune_modes }
+    }
+
+    /// Returns the size of the database, including its free pages, in bytes.
+    pub fn database_size(&self) -> u64 {
+        self.tables.iter().map(|usage| usage.size).sum::<u64>() + self.freelist
+    }
+
+    /// Returns the size of the static files, in bytes.
+    pub fn static_files_size(&self) -> u64 {
+        self.static_files.iter().map(|usage| usage.size).sum()
+    }
+
+    /// Returns the size of the database and the static files, in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.database_size() + self.static_files_size()
+    }
+
+    /// Returns the projected growth of the data directory over the given number of blocks, in
+    /// bytes.
+    ///
+    /// The growth of every table and static file segment is extrapolated from its average size per
+    /// block, so it's only a rough estimate for data that doesn't grow linearly, like the state.
+    pub fn projected_growth(&self, blocks: u64) -> u64 {
+        let growth_per_block = self
+            .tables
+            .iter()
+            .map(|usage| usage.growth_per_block)
+            .chain(self.static_files.iter().map(|usage| usage.growth_per_block))
+            .sum::<u64>();
+        growth_per_block * blocks
+    }
+
+    /// Returns the configured prune mode of the prune segment, `None` if it isn't pruned.
+    pub fn prune_mode(&self, segment: PruneSegment) -> Option<PruneMode> {
+        prune_mode(&self.prune_modes, segment)
+    }
+
+    /// Returns the configuration changes that would reclaim a large share of the data directory.
+    pub fn suggestions(&self) -> Vec<SpaceSuggestion> {
+        let total_size = self.total_size() as f64;
+        let mut suggestions = self
+            .prune_segments
+            .iter()
+            .filter(|usage| self.prune_mode(usage.part).is_none())
+            .filter(|usage| usage.size as f64 > total_size * PRUNE_SUGGESTION_SHARE)
+            .map(|usage| SpaceSuggestion::Prune {
+                segment: usage.part,
+                size: usage.size,
+                mode: suggested_prune_mode(usage.part),
+            })
+            .collect::<Vec<_>>();
+
+        if self.freelist as f64 > self.database_size() as f64 * DEFRAG_SUGGESTION_SHARE {
+            suggestions.push(SpaceSuggestion::Defrag { freelist: self.freelist });
+        }
+
+        suggestions
+    }
+}
+
+/// Returns the configured prune mode of the prune segment.
+fn prune_mode(prune_modes: &PruneModes, segment: PruneSegment) -> Option<PruneMode> {
+    match segment {
+        PruneSegment::SenderRecovery => prune_modes.sender_recovery,
+        PruneSegment::TransactionLookup => prune_modes.transaction_lookup,
+        PruneSegment::Receipts => prune_modes.receipts,
+        PruneSegment::AccountHistory => prune_modes.account_history,
+        PruneSegment::StorageHistory => prune_modes.storage_history,
+        PruneSegment::ContractLogs | PruneSegment::Headers | PruneSegment::Transactions => None,
+    }
+}
+
+/// Returns the most aggressive prune mode the prune segment can be configured with.
+const fn suggested_prune_mode(segment: PruneSegment) -> PruneMode {
+    match segment {
+        PruneSegment::SenderRecovery | PruneSegment::TransactionLookup => PruneMode::Full,
+        _ => PruneMode::Distance(MINIMUM_PRUNING_DISTANCE),
+    }
+}
+
+/// A configuration change that reclaims disk space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpaceSuggestion {
+    /// Prune a segment that isn't pruned and takes up a large share of the data directory.
+    Prune {
+        /// The prune segment.
+        segment: PruneSegment,
+        /// The size of the data of the segment, in bytes.
+        size: u64,
+        /// The suggested prune mode.
+        mode: PruneMode,
+    },
+    /// Reclaim the free pages of the database with `reth db defrag`, because they take up a large
+    /// share of the database.
+    Defrag {
+        /// The size of the free pages, in bytes.
+        freelist: u64,
+    },
+}
+
+impl fmt::Display for SpaceSuggestion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Prune { segment, size, mode } => {
+                let key = match segment {
+                    PruneSegment::SenderRecovery => "sender_recovery",
+                    PruneSegment::TransactionLookup => "transaction_lookup",
+                    PruneSegment::Receipts => "receipts",
+                    PruneSegment::AccountHistory => "account_history",
+                    PruneSegment::StorageHistory => "storage_history",
+                    _ => return write!(f, "prune {segment} data ({:.2} GB)", *size as f64 / 1e9),
+                };
+                let mode = match mode {
+                    PruneMode::Full => "\"full\"".to_string(),
+                    PruneMode::Distance(distance) => format!("{{ distance = {distance} }}"),
+                    PruneMode::Before(block) => format!("{{ before = {block} }}"),
+                };
+                write!(
+                    f,
+                    "{segment} data takes up {:.2} GB, set `{key} = {mode}` in the \
+                     `[prune.segments]` section of reth.toml to prune it",
+                    *size as f64 / 1e9
+                )
+            }
+            Self::Defrag { freelist } => write!(
+                f,
+                "free database pages take up {:.2} GB, run `reth db defrag` to reclaim them",
+                *freelist as f64 / 1e9
+            ),
+        }
+    }
+}
+
+/// Growth of the data directory between two reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpaceGrowth {
+    /// The number of blocks between the tips of the reports.
+    pub blocks: u64,
+    /// The change of the total size, in bytes.
+    pub bytes: i64,
+}
+
+/// Event that is triggered when a [`SpaceReport`] was taken.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceReportEvent {
+    /// The report.
+    pub report: SpaceReport,
+    /// The growth since the previous report, if any.
+    pub growth: Option<SpaceGrowth>,
+    /// The number of blocks until the next report.
+    pub interval: u64,
+}
+
+/// A Stream of [`SpaceReportEvent`]s.
+///
+/// A report is taken whenever the canonical chain crosses a multiple of the configured number of
+/// epochs, so that reports of nodes on the same chain are taken at the same blocks.
+pub struct SpaceReportEvents<DB> {
+    provider_factory: ProviderFactory<DB>,
+    prune_modes: PruneModes,
+    /// Number of blocks between reports.
+    interval: u64,
+    canon_state_notifications: BoxStream<'static, CanonStateNotification>,
+    /// The latest canonical tip.
+    tip: Option<BlockNumber>,
+    /// The block number and the total size of the previous report.
+    previous: Option<(BlockNumber, u64)>,
+    metrics: SpaceReportMetrics,
+    prune_segment_metrics: HashMap<PruneSegment, PruneSegmentSpaceMetrics>,
+}
+
+impl<DB> fmt::Debug for SpaceReportEvents<DB> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpaceReportEvents")
+            .field("prune_modes", &self.prune_modes)
+            .field("interval", &self.interval)
+            .field("tip", &self.tip)
+            .field("previous", &self.previous)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<DB> SpaceReportEvents<DB> {
+    /// Creates a new [`SpaceReportEvents`] that takes a report every `epochs` epochs of the given
+    /// canonical state notifications.
+    pub fn new(
+        provider_factory: ProviderFactory<DB>,
+        prune_modes: PruneModes,
+        epochs: u64,
+        canon_state_notifications: impl Stream<Item = CanonStateNotification> + Send + 'static,
+    ) -> Self {
+        let prune_segment_metrics = PRUNE_SEGMENTS
+            .iter()
+            .map(|(segment, _, _)| {
+                let labels = [("segment", segment.to_string())];
+                (*segment, PruneSegmentSpaceMetrics::new_with_labels(&labels))
+            })
+            .collect();
+        Self {
+            provider_factory,
+            prune_modes,
+            interval: (epochs * EPOCH_BLOCKS).max(1),
+            canon_state_notifications: canon_state_notifications.boxed(),
+            tip: None,
+            previous: None,
+            metrics: SpaceReportMetrics::default(),
+            prune_segment_metrics,
+        }
+    }
+
+    /// Returns `true` if the canonical chain crossed a multiple of the interval since the previous
+    /// tip.
+    const fn crossed_interval(&self, previous_tip: BlockNumber, tip: BlockNumber) -> bool {
+        tip / self.interval > previous_tip / self.interval
+    }
+}
+
+impl<DB: Database + DatabaseMetrics> SpaceReportEvents<DB> {
+    fn on_canon_state_notification(
+        &mut self,
+        notification: CanonStateNotification,
+    ) -> Option<SpaceReportEvent> {
+        let tip = notification.tip().number;
+        let previous_tip = self.tip.replace(tip)?;
+        if !self.crossed_interval(previous_tip, tip) {
+            return None
+        }
+
+        let report = match SpaceReport::new(&self.provider_factory, self.prune_modes.clone(), tip) {
+            Ok(report) => report,
+            Err(err) => {
+                debug!(target: "reth::cli", %err, "Failed to take space report");
+                return None
+            }
+        };
+
+        let total_size = report.total_size();
+        let growth = self.previous.replace((tip, total_size)).map(|(block, size)| SpaceGrowth {
+            blocks: tip.saturating_sub(block),
+            bytes: total_size as i64 - size as i64,
+        });
+
+        self.metrics.total_size.set(total_size as f64);
+        self.metrics.projected_growth.set(report.projected_growth(self.interval) as f64);
+        self.metrics.suggestions.set(report.suggestions().len() as f64);
+        if let Some(growth) = growth {
+            self.metrics.growth.set(growth.bytes as f64);
+        }
+        for usage in &report.prune_segments {
+            if let Some(metrics) = self.prune_segment_metrics.get(&usage.part) {
+                metrics.size.set(usage.size as f64);
+                metrics.growth_per_block.set(usage.growth_per_block as f64);
+            }
+        }
+
+        Some(SpaceReportEvent { report, growth, interval: self.interval })
+    }
+}
+
+impl<DB: Database + DatabaseMetrics + Unpin> Stream for SpaceReportEvents<DB> {
+    type Item = SpaceReportEvent;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        while let Poll::Ready(Some(notification)) =
+            this.canon_state_notifications.poll_next_unpin(cx)
+        {
+            if let Some(event) = this.on_canon_state_notification(notification) {
+                return Poll::Ready(Some(event))
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+/// Space report metrics.
+#[derive(Metrics)]
+#[metrics(scope = "space")]
+struct SpaceReportMetrics {
+    /// Size of the database and the static files, in bytes
+    total_size: Gauge,
+    /// Growth of the total size since the previous report, in bytes
+    growth: Gauge,
+    /// Projected growth of the total size until the next report, in bytes
+    projected_growth: Gauge,
+    /// Number of suggested configuration changes that reclaim disk space
+    suggestions: Gauge,
+}
+
+/// Space report metrics of a prune segment.
+#[derive(Metrics)]
+#[metrics(scope = "space.prune_segment")]
+struct PruneSegmentSpaceMetrics {
+    /// Size of the data of the prune segment, in bytes
+    size: Gauge,
+    /// Average growth of the data of the prune segment per block, in bytes
+    growth_per_block: Gauge,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GB: u64 = 1_000_000_000;
+
+    fn report(prune_modes: PruneModes) -> SpaceReport {
+        SpaceReport::from_sizes(
+            999,
+            vec![
+                (Tables::PlainAccountState, 10 * GB),
+                (Tables::TransactionSenders, 2 * GB),
+                (Tables::AccountChangeSets, 3 * GB),
+                (Tables::AccountsHistory, GB),
+                (Tables::Receipts, 0),
+            ],
+            5 * GB,
+            vec![(StaticFileSegment::Headers, GB, 1000), (StaticFileSegment::Receipts, GB, 500)],
+            prune_modes,
+        )
+    }
+
+    #[test]
+    fn space_report_sizes() {
+        let report = report(PruneModes::none());
+        assert_eq!(report.database_size(), 21 * GB);
+        assert_eq!(report.static_files_size(), 2 * GB);
+        assert_eq!(report.total_size(), 23 * GB);
+
+        let account_history = report
+            .prune_segments
+            .iter()
+            .find(|usage| usage.part == PruneSegment::AccountHistory)
+            .unwrap();
+        assert_eq!(account_history.size, 4 * GB);
+        assert_eq!(account_history.growth_per_block, 4 * GB / 1000);
+
+        let receipts = report
+            .prune_segments
+            .iter()
+            .find(|usage| usage.part == PruneSegment::Receipts)
+            .unwrap();
+        assert_eq!(receipts.size, GB);
+        assert_eq!(receipts.growth_per_block, GB / 500);
+
+        // 16 GB of tables and 1 GB of headers over 1000 blocks, 1 GB of receipts over 500 blocks
+        assert_eq!(report.projected_growth(1000), 16 * GB + GB + 2 * GB);
+    }
+
+    #[test]
+    fn space_report_pruned_segments() {
+        let prune_modes = PruneModes {
+            sender_recovery: Some(PruneMode::Full),
+            account_history: Some(PruneMode::Distance(MINIMUM_PRUNING_DISTANCE)),
+            receipts: Some(PruneMode::Before(100)),
+            ..Default::default()
+        };
+        let report = report(prune_modes);
+
+        // Only data pruned to a fixed distance from the tip stops growing
+        assert_eq!(report.projected_growth(1000), 10 * GB + GB + 2 * GB);
+    }
+
+    #[test]
+    fn space_report_suggestions() {
+        assert_eq!(
+            report(PruneModes::none()).suggestions(),
+            vec![
+                SpaceSuggestion::Prune {
+                    segment: PruneSegment::SenderRecovery,
+                    size: 2 * GB,
+                    mode: PruneMode::Full,
+                },
+                SpaceSuggestion::Prune {
+                    segment: PruneSegment::AccountHistory,
+                    size: 4 * GB,
+                    mode: PruneMode::Distance(MINIMUM_PRUNING_DISTANCE),
+                },
+                SpaceSuggestion::Defrag { freelist: 5 * GB },
+            ]
+        );
+
+        let suggestion = SpaceSuggestion::Prune {
+            segment: PruneSegment::AccountHistory,
+            size: 4 * GB,
+            mode: PruneMode::Distance(MINIMUM_PRUNING_DISTANCE),
+        };
+        assert_eq!(
+            suggestion.to_string(),
+            "AccountHistory data takes up 4.00 GB, set `account_history = { distance = 10064 }` \
+             in the `[prune.segments]` section of reth.toml to prune it"
+        );
+    }
+}
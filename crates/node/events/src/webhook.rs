@@ -0,0 +1,27 @@
+//! Webhook that node events are posted to.
+
+use tracing::debug;
+
+/// A webhook that events are posted to as JSON, e.g. a Slack or Discord channel.
+#[derive(Debug, Clone)]
+pub(crate) struct Webhook {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Webhook {
+    /// Creates a new webhook that posts to the given URL.
+    pub(crate) fn new(url: String) -> Self {
+        Self { client: reqwest::Client::new(), url }
+    }
+
+    /// Posts the body to the webhook in the background.
+    pub(crate) fn notify(&self, body: &serde_json::Value) {
+        let request = self.client.post(&self.url).json(body);
+        tokio::spawn(async move {
+            if let Err(err) = request.send().await.and_then(|res| res.error_for_status()) {
+                debug!(target: "reth::cli", %err, "Failed to notify webhook");
+            }
+        });
+    }
+}
@@ -15,7 +15,7 @@ use reth_consensus_common::validation::{
     validate_against_parent_4844, validate_against_parent_eip1559_base_fee,
     validate_against_parent_hash_number, validate_against_parent_timestamp,
     validate_block_pre_execution, validate_header_base_fee, validate_header_extradata,
-    validate_header_gas,
+    validate_header_gas, validate_header_timestamp_drift,
 };
 use reth_primitives::{
     BlockWithSenders, Header, SealedBlock, SealedHeader, EMPTY_OMMER_ROOT_HASH, U256,
@@ -99,7 +99,7 @@ impl Consensus for OptimismBeaconConsensus {
             // is greater than its parent timestamp.
 
             // validate header extradata for all networks post merge
-            validate_header_extradata(header)?;
+            validate_header_extradata(header, &self.chain_spec)?;
 
             // mixHash is used instead of difficulty inside EVM
             // https://eips.ethereum.org/EIPS/eip-4399#using-mixhash-field-instead-of-difficulty
@@ -108,12 +108,7 @@ impl Consensus for OptimismBeaconConsensus {
             let present_timestamp =
                 SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap().as_secs();
 
-            if header.exceeds_allowed_future_timestamp(present_timestamp) {
-                return Err(ConsensusError::TimestampIsInFuture {
-                    timestamp: header.timestamp,
-                    present_timestamp,
-                })
-            }
+            validate_header_timestamp_drift(header, present_timestamp, &self.chain_spec)?;
         }
 
         Ok(())
@@ -128,6 +123,11 @@ impl Consensus for OptimismBeaconConsensus {
         block: &BlockWithSenders,
         input: PostExecutionInput<'_>,
     ) -> Result<(), ConsensusError> {
-        validate_block_post_execution(block, &self.chain_spec, input.receipts)
+        validate_block_post_execution(
+            block,
+            &self.chain_spec,
+            input.receipts,
+            input.receipt_encodings,
+        )
     }
 }
@@ -2,17 +2,26 @@ use reth_chainspec::ChainSpec;
 use reth_consensus::ConsensusError;
 use reth_primitives::{
     gas_spent_by_transactions, proofs::calculate_receipt_root_optimism, BlockWithSenders, Bloom,
-    GotExpected, Receipt, B256,
+    GotExpected, Receipt, ReceiptEncoding, ReceiptWithBloom, B256,
 };
 
 /// Validate a block with regard to execution results:
 ///
 /// - Compares the receipts root in the block header to the block body
 /// - Compares the gas used in the block header to the actual gas usage after execution
+///
+/// The logs blooms of the receipts are computed, unless they're given in `receipt_encodings`. The
+/// encodings themselves aren't used, since the receipts root is computed from modified receipts
+/// in some hardforks.
+///
+/// # Panics
+///
+/// If `receipt_encodings` doesn't contain exactly one encoding per receipt.
 pub fn validate_block_post_execution(
     block: &BlockWithSenders,
     chain_spec: &ChainSpec,
     receipts: &[Receipt],
+    receipt_encodings: Option<&[ReceiptEncoding]>,
 ) -> Result<(), ConsensusError> {
     // Before Byzantium, receipts contained state root that would mean that expensive
     // operation as hashing that is required for state root got calculated in every
@@ -23,6 +32,7 @@ pub fn validate_block_post_execution(
             block.header.receipts_root,
             block.header.logs_bloom,
             receipts,
+            receipt_encodings,
             chain_spec,
             block.timestamp,
         ) {
@@ -49,11 +59,22 @@ fn verify_receipts(
     expected_receipts_root: B256,
     expected_logs_bloom: Bloom,
     receipts: &[Receipt],
+    receipt_encodings: Option<&[ReceiptEncoding]>,
     chain_spec: &ChainSpec,
     timestamp: u64,
 ) -> Result<(), ConsensusError> {
     // Calculate receipts root.
-    let receipts_with_bloom = receipts.iter().cloned().map(Receipt::with_bloom).collect::<Vec<_>>();
+    let receipts_with_bloom = match receipt_encodings {
+        Some(receipt_encodings) => {
+            assert_eq!(receipt_encodings.len(), receipts.len(), "one encoding per receipt");
+            receipts
+                .iter()
+                .zip(receipt_encodings)
+                .map(|(receipt, encoding)| ReceiptWithBloom::new(receipt.clone(), encoding.bloom))
+                .collect::<Vec<_>>()
+        }
+        None => receipts.iter().cloned().map(Receipt::with_bloom).collect(),
+    };
     let receipts_root =
         calculate_receipt_root_optimism(&receipts_with_bloom, chain_spec, timestamp);
 
@@ -7,6 +7,7 @@ use reth_evm::{
         BatchExecutor, BlockExecutionError, BlockExecutionInput, BlockExecutionOutput,
         BlockExecutorProvider, BlockValidationError, Executor, ProviderError,
     },
+    hook::{BlockExecutionHook, HookInspector},
     ConfigureEvm,
 };
 use reth_execution_types::ExecutionOutcome;
@@ -33,6 +34,8 @@ use tracing::trace;
 pub struct OpExecutorProvider<EvmConfig = OptimismEvmConfig> {
     chain_spec: Arc<ChainSpec>,
     evm_config: EvmConfig,
+    /// Hook that can inspect the execution of every block.
+    hook: Option<Arc<dyn BlockExecutionHook>>,
 }
 
 impl OpExecutorProvider {
@@ -45,7 +48,14 @@ impl OpExecutorProvider {
 impl<EvmConfig> OpExecutorProvider<EvmConfig> {
     /// Creates a new executor provider.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig) -> Self {
-        Self { chain_spec, evm_config }
+        Self { chain_spec, evm_config, hook: None }
+    }
+
+    /// Registers a hook that can attach an inspector to the execution of every block executed by
+    /// the executors of this provider.
+    pub fn with_execution_hook(mut self, hook: Arc<dyn BlockExecutionHook>) -> Self {
+        self.hook = Some(hook);
+        self
     }
 }
 
@@ -57,11 +67,13 @@ where
     where
         DB: Database<Error = ProviderError>,
     {
-        OpBlockExecutor::new(
+        let mut executor = OpBlockExecutor::new(
             self.chain_spec.clone(),
             self.evm_config.clone(),
             State::builder().with_database(db).with_bundle_update().without_state_clear().build(),
-        )
+        );
+        executor.hook.clone_from(&self.hook);
+        executor
     }
 }
 
@@ -232,12 +244,14 @@ pub struct OpBlockExecutor<EvmConfig, DB> {
     executor: OpEvmExecutor<EvmConfig>,
     /// The state to use for execution
     state: State<DB>,
+    /// Hook that can inspect the execution of the block.
+    hook: Option<Arc<dyn BlockExecutionHook>>,
 }
 
 impl<EvmConfig, DB> OpBlockExecutor<EvmConfig, DB> {
     /// Creates a new Ethereum block executor.
     pub const fn new(chain_spec: Arc<ChainSpec>, evm_config: EvmConfig, state: State<DB>) -> Self {
-        Self { executor: OpEvmExecutor { chain_spec, evm_config }, state }
+        Self { executor: OpEvmExecutor { chain_spec, evm_config }, state, hook: None }
     }
 
     #[inline]
@@ -290,10 +304,25 @@ where
         // 2. configure the evm and execute
         let env = self.evm_env_for_block(&block.header, total_difficulty);
 
-        let (receipts, gas_used) = {
-            let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
-            self.executor.execute_pre_and_transactions(block, evm)
-        }?;
+        let (receipts, gas_used) =
+            match self.hook.as_ref().and_then(|hook| hook.block_inspector(block)) {
+                Some(mut inspector) => {
+                    let output = {
+                        let evm = self.executor.evm_config.evm_with_env_and_inspector(
+                            &mut self.state,
+                            env,
+                            HookInspector::new(inspector.as_mut()),
+                        );
+                        self.executor.execute_pre_and_transactions(block, evm)
+                    }?;
+                    inspector.block_end(&output.0);
+                    output
+                }
+                None => {
+                    let evm = self.executor.evm_config.evm_with_env(&mut self.state, env);
+                    self.executor.execute_pre_and_transactions(block, evm)?
+                }
+            };
 
         // 3. apply post execution changes
         self.post_execution(block, total_difficulty)?;
@@ -362,6 +391,7 @@ where
             receipts,
             requests: vec![],
             gas_used,
+            access_lists: None,
         })
     }
 }
@@ -405,7 +435,7 @@ where
         let (receipts, _gas_used) =
             self.executor.execute_without_verification(block, total_difficulty)?;
 
-        validate_block_post_execution(block, self.executor.chain_spec(), &receipts)?;
+        validate_block_post_execution(block, self.executor.chain_spec(), &receipts, None)?;
 
         // prepare the state according to the prune mode
         let retention = self.batch_record.bundle_retention(block.number);
@@ -445,6 +475,7 @@ where
 mod tests {
     use super::*;
     use reth_chainspec::ChainSpecBuilder;
+    use reth_evm::hook::{ExecutionEvent, ExecutionEventsHook};
     use reth_primitives::{
         b256, Account, Address, Block, Signature, StorageKey, StorageValue, Transaction,
         TransactionSigned, TxEip1559, BASE_MAINNET,
@@ -482,7 +513,7 @@ mod tests {
     }
 
     fn executor_provider(chain_spec: Arc<ChainSpec>) -> OpExecutorProvider<OptimismEvmConfig> {
-        OpExecutorProvider { chain_spec, evm_config: Default::default() }
+        OpExecutorProvider::new(chain_spec, Default::default())
     }
 
     #[test]
@@ -647,4 +678,70 @@ mod tests {
         assert!(deposit_receipt.deposit_nonce.is_some());
         assert!(tx_receipt.deposit_nonce.is_none());
     }
+
+    #[test]
+    fn execution_hook_streams_events() {
+        let header = Header { timestamp: 1, number: 1, gas_limit: 1_000_000, ..Default::default() };
+
+        let mut db = create_op_state_provider();
+
+        let addr = Address::ZERO;
+        let account = Account { balance: U256::MAX, ..Account::default() };
+        db.insert_account(addr, account, None, HashMap::new());
+
+        let chain_spec =
+            Arc::new(ChainSpecBuilder::from(&*BASE_MAINNET).regolith_activated().build());
+
+        let tx = TransactionSigned::from_transaction_and_signature(
+            Transaction::Eip1559(TxEip1559 {
+                chain_id: chain_spec.chain.id(),
+                nonce: 0,
+                gas_limit: 21_000,
+                to: addr.into(),
+                ..Default::default()
+            }),
+            Signature::default(),
+        );
+
+        let tx_deposit = TransactionSigned::from_transaction_and_signature(
+            Transaction::Deposit(reth_primitives::TxDeposit {
+                from: addr,
+                to: addr.into(),
+                gas_limit: 21_000,
+                ..Default::default()
+            }),
+            Signature::default(),
+        );
+        let hashes = [tx.hash(), tx_deposit.hash()];
+
+        let (hook, events) = ExecutionEventsHook::new();
+        let provider = executor_provider(chain_spec).with_execution_hook(Arc::new(hook));
+        let mut executor = provider.executor(StateProviderDatabase::new(&db));
+        executor.state_mut().load_cache_account(L1_BLOCK_CONTRACT).unwrap();
+
+        let block = BlockWithSenders {
+            block: Block {
+                header,
+                body: vec![tx, tx_deposit],
+                ommers: vec![],
+                withdrawals: None,
+                requests: None,
+            },
+            senders: vec![addr, addr],
+        };
+        let output = executor.execute((&block, U256::ZERO).into()).unwrap();
+
+        let events = events.try_recv().unwrap();
+        assert_eq!(events.number, block.number);
+        assert_eq!(events.receipts, output.receipts);
+        let started = events
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                ExecutionEvent::TransactionStart { index, hash } => Some((*index, *hash)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(started, vec![(0, hashes[0]), (1, hashes[1])]);
+    }
 }
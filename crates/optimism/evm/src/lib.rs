@@ -10,7 +10,7 @@
 #![cfg(feature = "optimism")]
 
 use reth_chainspec::ChainSpec;
-use reth_evm::{ConfigureEvm, ConfigureEvmEnv};
+use reth_evm::{precompile::PrecompileMetricsRecorder, ConfigureEvm, ConfigureEvmEnv};
 use reth_primitives::{
     revm::{config::revm_spec, env::fill_op_tx_env},
     revm_primitives::{AnalysisKind, CfgEnvWithHandlerCfg, TxEnv},
@@ -27,9 +27,12 @@ mod error;
 pub use error::OptimismBlockExecutionError;
 
 /// Optimism-related EVM configuration.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct OptimismEvmConfig;
+pub struct OptimismEvmConfig {
+    /// Records the calls to the precompiles, shared by all EVMs of the config.
+    precompile_metrics: PrecompileMetricsRecorder,
+}
 
 impl ConfigureEvmEnv for OptimismEvmConfig {
     fn fill_tx_env(tx_env: &mut TxEnv, transaction: &TransactionSigned, sender: Address) {
@@ -67,7 +70,11 @@ impl ConfigureEvm for OptimismEvmConfig {
     type DefaultExternalContext<'a> = ();
 
     fn evm<'a, DB: Database + 'a>(&self, db: DB) -> Evm<'a, Self::DefaultExternalContext<'a>, DB> {
-        EvmBuilder::default().with_db(db).optimism().build()
+        EvmBuilder::default()
+            .with_db(db)
+            .optimism()
+            .append_handler_register_box(self.precompile_metrics.handler_register())
+            .build()
     }
 
     fn evm_with_inspector<'a, DB, I>(&self, db: DB, inspector: I) -> Evm<'a, I, DB>
@@ -79,6 +86,7 @@ impl ConfigureEvm for OptimismEvmConfig {
             .with_db(db)
             .with_external_context(inspector)
             .optimism()
+            .append_handler_register_box(self.precompile_metrics.handler_register())
             .append_handler_register(inspector_handle_register)
             .build()
     }
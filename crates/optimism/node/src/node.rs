@@ -20,10 +20,12 @@ use reth_node_builder::{
 use reth_optimism_consensus::OptimismBeaconConsensus;
 use reth_payload_builder::{PayloadBuilderHandle, PayloadBuilderService};
 use reth_provider::CanonStateSubscriptions;
+use reth_tasks::RestartPolicy;
 use reth_tracing::tracing::{debug, info};
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, CoinbaseTipOrdering, TransactionPool,
-    TransactionValidationTaskExecutor,
+    blobstore::DiskFileBlobStore, validate::AddressDenyList, CoinbaseTipOrdering,
+    EthPooledTransaction, TransactionPool, TransactionScreen, TransactionScreens,
+    TransactionValidationTaskExecutor, ValidatorStack,
 };
 use std::sync::Arc;
 
@@ -111,7 +113,7 @@ where
     ) -> eyre::Result<(Self::EVM, Self::Executor)> {
         let chain_spec = ctx.chain_spec();
         let evm_config = OptimismEvmConfig::default();
-        let executor = OpExecutorProvider::new(chain_spec, evm_config);
+        let executor = OpExecutorProvider::new(chain_spec, evm_config.clone());
 
         Ok((evm_config, executor))
     }
@@ -121,9 +123,33 @@ where
 ///
 /// This contains various settings that can be configured and take precedence over the node's
 /// config.
-#[derive(Debug, Default, Clone, Copy)]
+#[derive(Debug, Default, Clone)]
 #[non_exhaustive]
-pub struct OptimismPoolBuilder;
+pub struct OptimismPoolBuilder {
+    /// Screens that run before and after the validation of the transactions, see
+    /// [`ValidatorStack`].
+    screens: TransactionScreens<EthPooledTransaction>,
+}
+
+impl OptimismPoolBuilder {
+    /// Adds a screen that runs before the transactions are validated, e.g. to reject them early.
+    pub fn with_screen_before(
+        mut self,
+        screen: impl TransactionScreen<EthPooledTransaction> + 'static,
+    ) -> Self {
+        self.screens = self.screens.with_screen_before(screen);
+        self
+    }
+
+    /// Adds a screen that runs after the transactions were validated, only for the valid ones.
+    pub fn with_screen_after(
+        mut self,
+        screen: impl TransactionScreen<EthPooledTransaction> + 'static,
+    ) -> Self {
+        self.screens = self.screens.with_screen_after(screen);
+        self
+    }
+}
 
 impl<Node> PoolBuilder<Node> for OptimismPoolBuilder
 where
@@ -135,6 +161,14 @@ where
         let data_dir = ctx.config().datadir();
         let blob_store = DiskFileBlobStore::open(data_dir.blobstore(), Default::default())?;
 
+        // the deny list of the node's config screens the transactions before any other screen
+        let mut screens = TransactionScreens::default();
+        let deny_list = &ctx.config().txpool.deny_list;
+        if !deny_list.is_empty() {
+            screens = screens.with_screen_before(AddressDenyList::new(deny_list.iter().copied()));
+        }
+        let screens = screens.with_screens(self.screens);
+
         let validator = TransactionValidationTaskExecutor::eth_builder(ctx.chain_spec())
             .with_head_timestamp(ctx.head().timestamp)
             .kzg_settings(ctx.kzg_settings()?)
@@ -144,7 +178,10 @@ where
                 ctx.task_executor().clone(),
                 blob_store.clone(),
             )
-            .map(OpTransactionValidator::new);
+            .map(|validator| {
+                ValidatorStack::new(OpTransactionValidator::new(validator))
+                    .with_screens(screens.clone())
+            });
 
         let transaction_pool = reth_transaction_pool::Pool::new(
             validator,
@@ -158,10 +195,13 @@ where
         // spawn txpool maintenance task
         {
             let pool = transaction_pool.clone();
-            let chain_events = ctx.provider().canonical_state_stream();
             let client = ctx.provider().clone();
-            let transactions_backup_config =
+            let mut transactions_backup_config =
                 reth_transaction_pool::maintain::LocalTransactionBackupConfig::with_local_txs_backup(transactions_path);
+            if ctx.config().txpool.persist {
+                transactions_backup_config = transactions_backup_config
+                    .with_pool_transactions(data_dir.txpool_persisted_transactions());
+            }
 
             ctx.task_executor().spawn_critical_with_graceful_shutdown_signal(
                 "local transactions backup task",
@@ -174,16 +214,47 @@ where
                 },
             );
 
-            // spawn the maintenance task
-            ctx.task_executor().spawn_critical(
-                "txpool maintenance task",
-                reth_transaction_pool::maintain::maintain_transaction_pool_future(
-                    client,
-                    pool,
-                    chain_events,
+            // spawn the trie prefetcher of the best pending transactions, if enabled
+            if let Some(max_transactions) = ctx.config().txpool.trie_prefetch_transactions {
+                ctx.task_executor().spawn(reth_transaction_pool::prefetch::prefetch_trie_future(
+                    client.clone(),
+                    pool.clone(),
+                    client.canonical_state_stream(),
                     ctx.task_executor().clone(),
-                    Default::default(),
-                ),
+                    reth_transaction_pool::prefetch::TriePrefetchConfig::new(max_transactions),
+                ));
+                debug!(target: "reth::cli", "Spawned txpool trie prefetch task");
+            }
+
+            // spawn the rebroadcast of the tracked local transactions, if enabled
+            if ctx.config().txpool.track_locals {
+                let interval = ctx.config().txpool.locals_rebroadcast_interval;
+                ctx.task_executor().spawn(
+                    reth_transaction_pool::locals::rebroadcast_local_transactions_future(
+                        client.clone(),
+                        pool.clone(),
+                        client.canonical_state_stream(),
+                        reth_transaction_pool::locals::LocalRebroadcastConfig::new(interval),
+                    ),
+                );
+                debug!(target: "reth::cli", "Spawned txpool local transactions rebroadcast task");
+            }
+
+            // spawn the maintenance task, which is restarted with a fresh subscription to the
+            // canonical state if it panics
+            let executor = ctx.task_executor().clone();
+            ctx.task_executor().spawn_critical_with_restart_policy(
+                "txpool maintenance task",
+                RestartPolicy::restart(3),
+                move |_| {
+                    reth_transaction_pool::maintain::maintain_transaction_pool_future(
+                        client.clone(),
+                        pool.clone(),
+                        client.canonical_state_stream(),
+                        executor.clone(),
+                        Default::default(),
+                    )
+                },
             );
             debug!(target: "reth::cli", "Spawned txpool maintenance task");
         }
@@ -8,7 +8,7 @@ use reth_revm::L1BlockInfo;
 use reth_transaction_pool::{
     CoinbaseTipOrdering, EthPoolTransaction, EthPooledTransaction, EthTransactionValidator, Pool,
     TransactionOrigin, TransactionValidationOutcome, TransactionValidationTaskExecutor,
-    TransactionValidator,
+    TransactionValidator, ValidatorStack,
 };
 use std::sync::{
     atomic::{AtomicU64, Ordering},
@@ -17,7 +17,9 @@ use std::sync::{
 
 /// Type alias for default optimism transaction pool
 pub type OpTransactionPool<Client, S> = Pool<
-    TransactionValidationTaskExecutor<OpTransactionValidator<Client, EthPooledTransaction>>,
+    TransactionValidationTaskExecutor<
+        ValidatorStack<OpTransactionValidator<Client, EthPooledTransaction>>,
+    >,
     CoinbaseTipOrdering<EthPooledTransaction>,
     S,
 >;
@@ -19,13 +19,16 @@ use reth_primitives::{
 };
 use reth_provider::StateProviderFactory;
 use reth_revm::database::StateProviderDatabase;
-use reth_transaction_pool::{BestTransactionsAttributes, TransactionPool};
+use reth_transaction_pool::{
+    validate::{ensure_conditional_block, ensure_known_accounts},
+    BestTransactionsAttributes, PoolTransaction, TransactionPool,
+};
 use revm::{
     db::states::bundle_state::BundleRetention,
     primitives::{EVMError, EnvWithHandlerCfg, InvalidTransaction, ResultAndState},
     DatabaseCommit, State,
 };
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 use tracing::{debug, trace, warn};
 
 /// Optimism's payload builder
@@ -308,6 +311,9 @@ where
         PayloadBuilderError::other(OptimismPayloadBuilderError::ForceCreate2DeployerFail)
     })?;
 
+    // accounts whose storage was changed by the transactions of the block
+    let mut changed_storage = HashSet::new();
+
     let mut receipts = Vec::with_capacity(attributes.transactions.len());
     for sequencer_tx in &attributes.transactions {
         // Check if the job was cancelled, if so we can exit early.
@@ -373,6 +379,13 @@ where
 
         // to release the db reference drop evm.
         drop(evm);
+        // keep track of the changed storage for the conditions of the pool transactions
+        changed_storage.extend(
+            state
+                .iter()
+                .filter(|(_, account)| account.storage.values().any(|slot| slot.is_changed()))
+                .map(|(address, _)| *address),
+        );
         // commit changes
         db.commit(state);
 
@@ -426,6 +439,33 @@ where
 
             // convert tx to a signed transaction
             let tx = pool_tx.to_recovered_transaction();
+
+            // Conditional transactions are only included if the block meets their conditions.
+            // The known accounts are checked against the state of the parent block, so they aren't
+            // met if a previous transaction of the block changed the storage of any of the
+            // accounts.
+            if let Some(conditional) = pool_tx.transaction.conditional() {
+                let is_block_met = ensure_conditional_block(
+                    conditional,
+                    block_number,
+                    attributes.payload_attributes.timestamp,
+                )
+                .is_ok();
+                let is_storage_changed = conditional
+                    .known_accounts
+                    .keys()
+                    .any(|address| changed_storage.contains(address));
+                let is_met = is_block_met && !is_storage_changed && {
+                    let parent_state = db.database.0.inner.borrow();
+                    ensure_known_accounts(conditional, &**parent_state.db)?.is_ok()
+                };
+                if !is_met {
+                    trace!(target: "payload_builder", tx=?tx.hash, "skipping conditional transaction because its conditions are not met");
+                    best_txs.mark_invalid(&pool_tx);
+                    continue
+                }
+            }
+
             let env = EnvWithHandlerCfg::new_with_cfg_env(
                 initialized_cfg.clone(),
                 initialized_block_env.clone(),
@@ -461,6 +501,13 @@ where
             };
             // drop evm so db is released.
             drop(evm);
+            // keep track of the changed storage for the conditions of the following transactions
+            changed_storage.extend(
+                state
+                    .iter()
+                    .filter(|(_, account)| account.storage.values().any(|slot| slot.is_changed()))
+                    .map(|(address, _)| *address),
+            );
             // commit changes
             db.commit(state);
 
@@ -18,7 +18,7 @@ use reth_payload_builder::{
 };
 use reth_payload_primitives::{BuiltPayload, PayloadBuilderAttributes};
 use reth_primitives::{
-    constants::{EMPTY_WITHDRAWALS, RETH_CLIENT_VERSION, SLOT_DURATION},
+    constants::{EMPTY_WITHDRAWALS, GWEI_TO_WEI, RETH_CLIENT_VERSION, SLOT_DURATION},
     proofs, BlockNumberOrTag, Bytes, Request, SealedBlock, Withdrawals, B256, U256,
 };
 use reth_provider::{
@@ -41,7 +41,7 @@ use std::{
     pin::Pin,
     sync::{atomic::AtomicBool, Arc},
     task::{Context, Poll},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     sync::{oneshot, Semaphore},
@@ -190,6 +190,9 @@ where
             interval: tokio::time::interval(self.config.interval),
             best_payload: None,
             pending_block: None,
+            build_started: None,
+            last_build_duration: None,
+            iterations: 0,
             cached_reads,
             payload_task_guard: self.payload_task_guard.clone(),
             metrics: Default::default(),
@@ -338,6 +341,12 @@ where
     best_payload: Option<Builder::BuiltPayload>,
     /// Receiver for the block that is currently being built.
     pending_block: Option<PendingPayload<Builder::BuiltPayload>>,
+    /// When the block that is currently being built was started.
+    build_started: Option<Instant>,
+    /// How long the last finished build attempt took.
+    last_build_duration: Option<Duration>,
+    /// The number of finished build attempts.
+    iterations: u64,
     /// Restricts how many generator tasks can be executed at once.
     payload_task_guard: PayloadTaskGuard,
     /// Caches all disk reads for the state the new payloads builds on
@@ -392,6 +401,30 @@ where
         }));
 
         self.pending_block = Some(PendingPayload { _cancel, payload: rx });
+        self.build_started = Some(Instant::now());
+    }
+
+    /// Returns `true` if a new build attempt is expected to finish before the deadline of the job,
+    /// based on how long the last attempt took.
+    fn can_build_before_deadline(&self) -> bool {
+        can_build_before(
+            self.deadline.deadline().into_std(),
+            Instant::now(),
+            self.last_build_duration,
+        )
+    }
+
+    /// Records the duration and the fees of a finished build attempt.
+    fn on_build_finished(&mut self, fees: Option<U256>) {
+        self.iterations += 1;
+        if let Some(started) = self.build_started.take() {
+            let elapsed = started.elapsed();
+            self.last_build_duration = Some(elapsed);
+            self.metrics.payload_build_duration.record(elapsed);
+        }
+        if let Some(fees) = fees {
+            self.metrics.payload_fees.record(fees_in_gwei(fees));
+        }
     }
 }
 
@@ -417,9 +450,14 @@ where
 
         // check if the interval is reached
         while this.interval.poll_tick(cx).is_ready() {
-            // start a new job if there is no pending block and we haven't reached the deadline
+            // start a new job if there is no pending block and it can finish before the deadline
             if this.pending_block.is_none() {
-                this.spawn_build_job();
+                if this.can_build_before_deadline() {
+                    this.spawn_build_job();
+                } else {
+                    trace!(target: "payload_builder", "skipping payload build that would not finish before the deadline");
+                    this.metrics.inc_skipped_payload_builds();
+                }
             }
         }
 
@@ -431,12 +469,22 @@ where
                     match outcome {
                         BuildOutcome::Better { payload, cached_reads } => {
                             this.cached_reads = Some(cached_reads);
-                            debug!(target: "payload_builder", value = %payload.fees(), "built better payload");
+                            this.on_build_finished(Some(payload.fees()));
+                            let previous_fees = this
+                                .best_payload
+                                .as_ref()
+                                .map(|best| best.fees())
+                                .unwrap_or_default();
+                            this.metrics
+                                .payload_fees_increase
+                                .record(fees_in_gwei(payload.fees().saturating_sub(previous_fees)));
+                            debug!(target: "payload_builder", value = %payload.fees(), iteration = this.iterations, "built better payload");
                             this.best_payload = Some(payload);
                         }
                         BuildOutcome::Aborted { fees, cached_reads } => {
                             this.cached_reads = Some(cached_reads);
-                            trace!(target: "payload_builder", worse_fees = %fees, "skipped payload build of worse block");
+                            this.on_build_finished(Some(fees));
+                            trace!(target: "payload_builder", worse_fees = %fees, iteration = this.iterations, "skipped payload build of worse block");
                         }
                         BuildOutcome::Cancelled => {
                             unreachable!("the cancel signal never fired")
@@ -446,6 +494,7 @@ where
                 Poll::Ready(Err(error)) => {
                     // job failed, but we simply try again next interval
                     debug!(target: "payload_builder", %error, "payload build attempt failed");
+                    this.on_build_finished(None);
                     this.metrics.inc_failed_payload_builds();
                 }
                 Poll::Pending => {
@@ -491,6 +540,8 @@ where
 
     fn resolve(&mut self) -> (Self::ResolvePayloadFuture, KeepPayloadJobAlive) {
         let best_payload = self.best_payload.take();
+        self.metrics.payload_build_iterations.record(self.iterations as f64);
+        debug!(target: "payload_builder", id=%self.config.payload_id(), iterations = self.iterations, value = ?best_payload.as_ref().map(|payload| payload.fees()), "resolving payload");
 
         if best_payload.is_none() && self.pending_block.is_none() {
             // ensure we have a job scheduled if we don't have a best payload yet and none is active
@@ -996,6 +1047,23 @@ where
         .map_err(|err| PayloadBuilderError::Internal(err.into()))
 }
 
+/// Returns `true` if a build attempt that starts now is expected to finish before the deadline,
+/// i.e. if the remaining time is longer than the last attempt took.
+///
+/// This is always the case before the first attempt finished.
+fn can_build_before(
+    deadline: Instant,
+    now: Instant,
+    last_build_duration: Option<Duration>,
+) -> bool {
+    last_build_duration.map_or(true, |duration| deadline.saturating_duration_since(now) > duration)
+}
+
+/// Converts the fees of a payload from wei to gwei, for metrics.
+fn fees_in_gwei(fees: U256) -> f64 {
+    fees.saturating_to::<u128>() as f64 / GWEI_TO_WEI as f64
+}
+
 /// Checks if the new payload is better than the current best.
 ///
 /// This compares the total fees of the blocks, higher is better.
@@ -1016,3 +1084,28 @@ fn duration_until(unix_timestamp_secs: u64) -> Duration {
     let timestamp = Duration::from_secs(unix_timestamp_secs);
     timestamp.saturating_sub(unix_now)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_builds_that_would_miss_the_deadline() {
+        let now = Instant::now();
+        let deadline = now + Duration::from_millis(500);
+
+        assert!(can_build_before(deadline, now, None));
+        assert!(can_build_before(deadline, now, Some(Duration::from_millis(400))));
+        assert!(!can_build_before(deadline, now, Some(Duration::from_millis(500))));
+        // the deadline already passed
+        assert!(!can_build_before(now, deadline, Some(Duration::ZERO)));
+        assert!(can_build_before(now, deadline, None));
+    }
+
+    #[test]
+    fn converts_fees_to_gwei() {
+        assert_eq!(fees_in_gwei(U256::ZERO), 0.0);
+        assert_eq!(fees_in_gwei(U256::from(GWEI_TO_WEI * 3 / 2)), 1.5);
+        assert_eq!(fees_in_gwei(U256::MAX), u128::MAX as f64 / GWEI_TO_WEI as f64);
+    }
+}
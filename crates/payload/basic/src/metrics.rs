@@ -1,6 +1,9 @@
 //! Metrics for the payload builder impl
 
-use reth_metrics::{metrics::Counter, Metrics};
+use reth_metrics::{
+    metrics::{Counter, Histogram},
+    Metrics,
+};
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -12,6 +15,17 @@ pub(crate) struct PayloadBuilderMetrics {
     pub(crate) initiated_payload_builds: Counter,
     /// Total number of failed payload build attempts
     pub(crate) failed_payload_builds: Counter,
+    /// Total number of payload build attempts that were skipped because they would not finish
+    /// before the deadline
+    pub(crate) skipped_payload_builds: Counter,
+    /// Duration of a payload build attempt
+    pub(crate) payload_build_duration: Histogram,
+    /// Fees of the payload built by an attempt, in gwei
+    pub(crate) payload_fees: Histogram,
+    /// Fee increase of a better payload over the previous best payload of the job, in gwei
+    pub(crate) payload_fees_increase: Histogram,
+    /// Number of finished build attempts of a job when its payload is resolved
+    pub(crate) payload_build_iterations: Histogram,
 }
 
 impl PayloadBuilderMetrics {
@@ -26,4 +40,8 @@ impl PayloadBuilderMetrics {
     pub(crate) fn inc_failed_payload_builds(&self) {
         self.failed_payload_builds.increment(1);
     }
+
+    pub(crate) fn inc_skipped_payload_builds(&self) {
+        self.skipped_payload_builds.increment(1);
+    }
 }
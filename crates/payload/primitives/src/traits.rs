@@ -103,6 +103,12 @@ pub trait PayloadAttributes:
         chain_spec: &ChainSpec,
         version: EngineApiMessageVersion,
     ) -> Result<(), EngineObjectValidationError>;
+
+    /// Returns the payload attributes for a locally proposed block on top of the given parent, e.g.
+    /// by a consensus client for development, or `None` if they can't be built locally.
+    fn local(_attributes: EthPayloadAttributes, _parent: &Header) -> Option<Self> {
+        None
+    }
 }
 
 impl PayloadAttributes for EthPayloadAttributes {
@@ -125,6 +131,10 @@ impl PayloadAttributes for EthPayloadAttributes {
     ) -> Result<(), EngineObjectValidationError> {
         validate_version_specific_fields(chain_spec, version, self.into())
     }
+
+    fn local(attributes: EthPayloadAttributes, _parent: &Header) -> Option<Self> {
+        Some(attributes)
+    }
 }
 
 impl PayloadAttributes for OptimismPayloadAttributes {
@@ -155,4 +165,13 @@ impl PayloadAttributes for OptimismPayloadAttributes {
 
         Ok(())
     }
+
+    fn local(attributes: EthPayloadAttributes, parent: &Header) -> Option<Self> {
+        Some(Self {
+            payload_attributes: attributes,
+            transactions: None,
+            no_tx_pool: None,
+            gas_limit: Some(parent.gas_limit),
+        })
+    }
 }
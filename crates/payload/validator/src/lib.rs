@@ -45,6 +45,23 @@ impl ExecutionPayloadValidator {
         self.chain_spec().is_shanghai_active_at_timestamp(timestamp)
     }
 
+    /// Ensures that the extra data of the payload does not exceed the maximum size allowed by the
+    /// chain spec.
+    fn ensure_valid_extra_data(&self, payload: &ExecutionPayload) -> Result<(), PayloadError> {
+        let extra_data = match payload {
+            ExecutionPayload::V1(payload) => &payload.extra_data,
+            ExecutionPayload::V2(payload) => &payload.payload_inner.extra_data,
+            ExecutionPayload::V3(payload) => &payload.payload_inner.payload_inner.extra_data,
+            ExecutionPayload::V4(payload) => {
+                &payload.payload_inner.payload_inner.payload_inner.extra_data
+            }
+        };
+        if extra_data.len() > self.chain_spec.max_extra_data_size {
+            return Err(PayloadError::ExtraData(extra_data.clone()))
+        }
+        Ok(())
+    }
+
     /// Cancun specific checks for EIP-4844 blob transactions.
     ///
     /// Ensures that the number of blob versioned hashes matches the number hashes included in the
@@ -109,6 +126,8 @@ impl ExecutionPayloadValidator {
     ) -> Result<SealedBlock, PayloadError> {
         let expected_hash = payload.block_hash();
 
+        self.ensure_valid_extra_data(&payload)?;
+
         // First parse the block
         let sealed_block =
             try_into_block(payload, cancun_fields.parent_beacon_block_root())?.seal_slow();
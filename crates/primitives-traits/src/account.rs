@@ -1,13 +1,18 @@
 use alloy_consensus::constants::KECCAK_EMPTY;
 use alloy_genesis::GenesisAccount;
-use alloy_primitives::{keccak256, Bytes, B256, U256};
-use byteorder::{BigEndian, ReadBytesExt};
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use bytes::Buf;
 use derive_more::Deref;
 use reth_codecs::{main_codec, Compact};
-use revm_primitives::{AccountInfo, Bytecode as RevmBytecode, JumpTable};
+use revm_primitives::{AccountInfo, Bytecode as RevmBytecode, Eof, JumpTable};
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::sync::Arc;
+
+#[cfg(feature = "std")]
+use std::sync::Arc;
+
 /// An Ethereum account.
 #[main_codec]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -51,6 +56,10 @@ impl Account {
     }
 }
 
+/// The prefix of the code of an account that delegates to another account, followed by the
+/// address of the delegate, see [EIP-7702](https://eips.ethereum.org/EIPS/eip-7702).
+pub const DELEGATION_DESIGNATOR_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
 /// Bytecode for an account.
 ///
 /// A wrapper around [`revm::primitives::Bytecode`][RevmBytecode] with encoding/decoding support.
@@ -64,6 +73,13 @@ impl Bytecode {
     pub fn new_raw(bytes: Bytes) -> Self {
         Self(RevmBytecode::new_raw(bytes))
     }
+
+    /// Returns the address of the delegate if the bytecode is an EIP-7702 delegation designator,
+    /// see [`DELEGATION_DESIGNATOR_PREFIX`].
+    pub fn delegate(&self) -> Option<Address> {
+        let code = self.0.original_bytes();
+        Address::try_from(code.strip_prefix(&DELEGATION_DESIGNATOR_PREFIX)?).ok()
+    }
 }
 
 impl Compact for Bytecode {
@@ -71,7 +87,12 @@ impl Compact for Bytecode {
     where
         B: bytes::BufMut + AsMut<[u8]>,
     {
-        let bytecode = &self.0.bytecode()[..];
+        let bytecode = match &self.0 {
+            // EOF bytecode is stored as the raw container, as the code of the first section alone
+            // isn't enough to decode it again.
+            RevmBytecode::Eof(eof) => &eof.raw[..],
+            _ => &self.0.bytecode()[..],
+        };
         buf.put_u32(bytecode.len() as u32);
         buf.put_slice(bytecode);
         let len = match &self.0 {
@@ -88,9 +109,8 @@ impl Compact for Bytecode {
                 1 + 8 + map.len()
             }
             RevmBytecode::Eof(_) => {
-                // buf.put_u8(3);
-                // TODO(EOF)
-                todo!("EOF")
+                buf.put_u8(3);
+                1
             }
         };
         len + bytecode.len() + 4
@@ -98,24 +118,25 @@ impl Compact for Bytecode {
 
     // # Panics
     //
-    // A panic will be triggered if a bytecode variant of 1 or greater than 2 is passed from the
-    // database.
+    // A panic will be triggered if a bytecode variant of 1 or greater than 3 is passed from the
+    // database, or if an EOF bytecode is not a valid container.
     fn from_compact(mut buf: &[u8], _: usize) -> (Self, &[u8]) {
-        let len = buf.read_u32::<BigEndian>().expect("could not read bytecode length");
+        let len = buf.get_u32();
         let bytes = Bytes::from(buf.copy_to_bytes(len as usize));
-        let variant = buf.read_u8().expect("could not read bytecode variant");
+        let variant = buf.get_u8();
         let decoded = match variant {
             0 => Self(RevmBytecode::new_raw(bytes)),
             1 => unreachable!("Junk data in database: checked Bytecode variant was removed"),
             2 => Self(unsafe {
                 RevmBytecode::new_analyzed(
                     bytes,
-                    buf.read_u64::<BigEndian>().unwrap() as usize,
+                    buf.get_u64() as usize,
                     JumpTable::from_slice(buf),
                 )
             }),
-            // TODO(EOF)
-            3 => todo!("EOF"),
+            3 => Self(RevmBytecode::Eof(Arc::new(
+                Eof::decode(bytes).expect("Junk data in database: invalid EOF container"),
+            ))),
             _ => unreachable!("Junk data in database: unknown Bytecode variant"),
         };
         (decoded, &[])
@@ -215,5 +236,26 @@ mod tests {
         let (decoded, remainder) = Bytecode::from_compact(&buf, len);
         assert_eq!(decoded, bytecode);
         assert!(remainder.is_empty());
+
+        let mut buf = vec![];
+        let bytecode = Bytecode(RevmBytecode::Eof(Arc::new(Eof::default())));
+        let len = bytecode.clone().to_compact(&mut buf);
+        assert_eq!(len, 25);
+
+        let (decoded, remainder) = Bytecode::from_compact(&buf, len);
+        assert_eq!(decoded, bytecode);
+        assert!(remainder.is_empty());
+    }
+
+    #[test]
+    fn test_bytecode_delegate() {
+        let delegate = Address::repeat_byte(0x42);
+        let designator = [&DELEGATION_DESIGNATOR_PREFIX[..], delegate.as_slice()].concat();
+        assert_eq!(Bytecode::new_raw(designator.clone().into()).delegate(), Some(delegate));
+
+        // the designator must be followed by exactly one address
+        assert_eq!(Bytecode::new_raw(designator[..22].to_vec().into()).delegate(), None);
+        assert_eq!(Bytecode::new_raw([designator, vec![0]].concat().into()).delegate(), None);
+        assert_eq!(Bytecode::new_raw(Bytes::from_static(&hex!("6000"))).delegate(), None);
     }
 }
@@ -1,4 +1,3 @@
-use bytes::BufMut;
 use core::fmt;
 use derive_more::Deref;
 use roaring::RoaringTreemap;
@@ -8,6 +7,9 @@ use serde::{
     Deserialize, Deserializer, Serialize, Serializer,
 };
 
+#[cfg(feature = "std")]
+use bytes::BufMut;
+
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
@@ -49,6 +51,7 @@ impl IntegerList {
     }
 
     /// Serializes a [`IntegerList`] into a sequence of bytes.
+    #[cfg(feature = "std")]
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut vec = Vec::with_capacity(self.0.serialized_size());
         self.0.serialize_into(&mut vec).expect("not able to encode IntegerList");
@@ -56,11 +59,13 @@ impl IntegerList {
     }
 
     /// Serializes a [`IntegerList`] into a sequence of bytes.
+    #[cfg(feature = "std")]
     pub fn to_mut_bytes<B: bytes::BufMut>(&self, buf: &mut B) {
         self.0.serialize_into(buf.writer()).unwrap();
     }
 
     /// Deserializes a sequence of bytes into a proper [`IntegerList`].
+    #[cfg(feature = "std")]
     pub fn from_bytes(data: &[u8]) -> Result<Self, RoaringBitmapError> {
         Ok(Self(
             RoaringTreemap::deserialize_from(data)
@@ -6,4 +6,4 @@ pub use crate::revm_primitives::{
 };
 
 #[doc(inline)]
-pub use alloy_eips::eip4844::kzg_to_versioned_hash;
+pub use alloy_eips::eip4844::{kzg_to_versioned_hash, Blob, Bytes48};
@@ -53,7 +53,8 @@ pub use constants::{
 pub use genesis::{ChainConfig, Genesis, GenesisAccount};
 pub use header::{Header, HeadersDirection, SealedHeader};
 pub use receipt::{
-    gas_spent_by_transactions, Receipt, ReceiptWithBloom, ReceiptWithBloomRef, Receipts,
+    gas_spent_by_transactions, Receipt, ReceiptEncoding, ReceiptWithBloom, ReceiptWithBloomRef,
+    Receipts,
 };
 pub use reth_primitives_traits::{
     logs_bloom, Account, Bytecode, GotExpected, GotExpectedBoxed, Log, Request, Requests,
@@ -62,7 +63,7 @@ pub use reth_primitives_traits::{
 pub use static_file::StaticFileSegment;
 
 pub use transaction::{
-    BlobTransaction, BlobTransactionSidecar, FromRecoveredPooledTransaction,
+    BlobAndProofV1, BlobTransaction, BlobTransactionSidecar, FromRecoveredPooledTransaction,
     PooledTransactionsElement, PooledTransactionsElementEcRecovered,
 };
 
@@ -1,8 +1,8 @@
 //! Helper function for calculating Merkle proofs and hashes.
 
 use crate::{
-    constants::EMPTY_OMMER_ROOT_HASH, keccak256, Header, Receipt, ReceiptWithBloom,
-    ReceiptWithBloomRef, Request, TransactionSigned, Withdrawal, B256,
+    constants::EMPTY_OMMER_ROOT_HASH, keccak256, Header, Receipt, ReceiptEncoding,
+    ReceiptWithBloom, ReceiptWithBloomRef, Request, TransactionSigned, Withdrawal, B256,
 };
 use reth_trie_common::root::{ordered_trie_root, ordered_trie_root_with_encoder};
 
@@ -75,6 +75,11 @@ pub fn calculate_receipt_root_ref(receipts: &[ReceiptWithBloomRef<'_>]) -> B256
     ordered_trie_root_with_encoder(receipts, |r, buf| r.encode_inner(buf, false))
 }
 
+/// Calculates the receipt root for a header from the encoded receipts.
+pub fn calculate_receipt_root_encoded(receipts: &[ReceiptEncoding]) -> B256 {
+    ordered_trie_root_with_encoder(receipts, |r, buf| buf.extend_from_slice(&r.encoded))
+}
+
 /// Calculates the receipt root for a header for the reference type of [Receipt].
 ///
 /// NOTE: Prefer [`calculate_receipt_root`] if you have log blooms memoized.
@@ -461,9 +466,12 @@ mod tests {
             },
             bloom,
         };
+        let encoded = vec![ReceiptEncoding::new(&receipt.receipt)];
+        assert_eq!(encoded[0].bloom, bloom);
         let receipt = vec![receipt];
         let root = calculate_receipt_root(&receipt);
         assert_eq!(root, b256!("fe70ae4a136d98944951b2123859698d59ad251a381abc9960fa81cae3d0d4a0"));
+        assert_eq!(calculate_receipt_root_encoded(&encoded), root);
     }
 
     #[test]
@@ -355,6 +355,25 @@ impl Decodable for ReceiptWithBloom {
     }
 }
 
+/// The bloom filter and the EIP-2718 encoding of a [`Receipt`], as used for the receipts root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptEncoding {
+    /// Bloom filter build from logs.
+    pub bloom: Bloom,
+    /// The EIP-2718 encoding of the receipt with its bloom filter.
+    pub encoded: Bytes,
+}
+
+impl ReceiptEncoding {
+    /// Calculates the bloom filter of the receipt and encodes it.
+    pub fn new(receipt: &Receipt) -> Self {
+        let receipt = receipt.with_bloom_ref();
+        let mut encoded = Vec::new();
+        receipt.encode_inner(&mut encoded, false);
+        Self { bloom: receipt.bloom, encoded: encoded.into() }
+    }
+}
+
 /// [`Receipt`] reference type with calculated bloom filter.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ReceiptWithBloomRef<'a> {
@@ -24,7 +24,9 @@ pub fn revm_spec_by_timestamp_after_merge(
         }
     }
 
-    if chain_spec.is_prague_active_at_timestamp(timestamp) {
+    if chain_spec.is_osaka_active_at_timestamp(timestamp) {
+        revm_primitives::PRAGUE_EOF
+    } else if chain_spec.is_prague_active_at_timestamp(timestamp) {
         revm_primitives::PRAGUE
     } else if chain_spec.is_cancun_active_at_timestamp(timestamp) {
         revm_primitives::CANCUN
@@ -52,7 +54,9 @@ pub fn revm_spec(chain_spec: &ChainSpec, block: Head) -> revm_primitives::SpecId
         }
     }
 
-    if chain_spec.fork(Hardfork::Prague).active_at_head(&block) {
+    if chain_spec.fork(Hardfork::Osaka).active_at_head(&block) {
+        revm_primitives::PRAGUE_EOF
+    } else if chain_spec.fork(Hardfork::Prague).active_at_head(&block) {
         revm_primitives::PRAGUE
     } else if chain_spec.fork(Hardfork::Cancun).active_at_head(&block) {
         revm_primitives::CANCUN
@@ -94,6 +98,20 @@ mod tests {
 
     #[test]
     fn test_revm_spec_by_timestamp_after_merge() {
+        assert_eq!(
+            revm_spec_by_timestamp_after_merge(
+                &ChainSpecBuilder::mainnet().osaka_activated().build(),
+                0
+            ),
+            revm_primitives::PRAGUE_EOF
+        );
+        assert_eq!(
+            revm_spec_by_timestamp_after_merge(
+                &ChainSpecBuilder::mainnet().prague_activated().build(),
+                0
+            ),
+            revm_primitives::PRAGUE
+        );
         assert_eq!(
             revm_spec_by_timestamp_after_merge(
                 &ChainSpecBuilder::mainnet().cancun_activated().build(),
@@ -144,6 +162,14 @@ mod tests {
 
     #[test]
     fn test_to_revm_spec() {
+        assert_eq!(
+            revm_spec(&ChainSpecBuilder::mainnet().osaka_activated().build(), Head::default()),
+            revm_primitives::PRAGUE_EOF
+        );
+        assert_eq!(
+            revm_spec(&ChainSpecBuilder::mainnet().prague_activated().build(), Head::default()),
+            revm_primitives::PRAGUE
+        );
         assert_eq!(
             revm_spec(&ChainSpecBuilder::mainnet().cancun_activated().build(), Head::default()),
             revm_primitives::CANCUN
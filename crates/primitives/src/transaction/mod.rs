@@ -30,7 +30,7 @@ pub use pooled::{PooledTransactionsElement, PooledTransactionsElementEcRecovered
 pub use sidecar::generate_blob_sidecar;
 #[cfg(feature = "c-kzg")]
 pub use sidecar::BlobTransactionValidationError;
-pub use sidecar::{BlobTransaction, BlobTransactionSidecar};
+pub use sidecar::{BlobAndProofV1, BlobTransaction, BlobTransactionSidecar};
 
 pub use signature::{extract_chain_id, Signature};
 pub use tx_type::{
@@ -1,19 +1,32 @@
 #![cfg_attr(docsrs, doc(cfg(feature = "c-kzg")))]
 
 use crate::{
-    keccak256, Signature, Transaction, TransactionSigned, TxEip4844, TxHash, EIP4844_TX_TYPE_ID,
+    keccak256, Signature, Transaction, TransactionSigned, TxEip4844, TxHash, B256,
+    EIP4844_TX_TYPE_ID,
 };
 use alloy_rlp::{Decodable, Encodable, Error as RlpError, Header};
 use serde::{Deserialize, Serialize};
 
 #[doc(inline)]
 pub use alloy_eips::eip4844::BlobTransactionSidecar;
+use alloy_eips::eip4844::{Blob, Bytes48};
 
 #[cfg(feature = "c-kzg")]
 pub use alloy_eips::eip4844::BlobTransactionValidationError;
 
 #[cfg(not(feature = "std"))]
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
+
+/// A blob and its KZG proof, as served by `engine_getBlobsV1`.
+///
+/// See also <https://github.com/ethereum/execution-apis/blob/main/src/engine/cancun.md#blobandproofv1>
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobAndProofV1 {
+    /// The blob data.
+    pub blob: Box<Blob>,
+    /// The KZG proof of the blob.
+    pub proof: Bytes48,
+}
 
 /// A response to `GetPooledTransactions` that includes blob data, their commitments, and their
 /// corresponding proofs.
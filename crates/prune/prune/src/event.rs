@@ -13,4 +13,6 @@ pub enum PrunerEvent {
         elapsed: Duration,
         stats: BTreeMap<PruneSegment, (PruneProgress, usize)>,
     },
+    /// Emitted when pruner failed.
+    Failed { tip_block_number: BlockNumber, error: String },
 }
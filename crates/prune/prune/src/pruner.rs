@@ -127,10 +127,20 @@ impl<DB: Database> Pruner<DB> {
             limiter = limiter.set_time_limit(timeout);
         };
 
-        let provider = self.provider_factory.provider_rw()?;
-        let (stats, deleted_entries, progress) =
-            self.prune_segments(&provider, tip_block_number, &mut limiter)?;
-        provider.commit()?;
+        let result =
+            self.provider_factory.provider_rw().map_err(PrunerError::from).and_then(|provider| {
+                let output = self.prune_segments(&provider, tip_block_number, &mut limiter)?;
+                provider.commit()?;
+                Ok(output)
+            });
+        let (stats, deleted_entries, progress) = match result {
+            Ok(output) => output,
+            Err(err) => {
+                self.event_sender
+                    .notify(PrunerEvent::Failed { tip_block_number, error: err.to_string() });
+                return Err(err)
+            }
+        };
 
         self.previous_tip_block_number = Some(tip_block_number);
 
@@ -17,6 +17,8 @@ pub mod database;
 
 pub mod batch;
 
+pub mod requests;
+
 /// State changes that are not related to transactions.
 pub mod state_change;
 
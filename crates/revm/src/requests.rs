@@ -0,0 +1,210 @@
+//! Reads the requests queued in the [EIP-7002](https://eips.ethereum.org/EIPS/eip-7002) withdrawal
+//! requests contract from the state.
+//!
+//! Requests are added to the queue by transactions and are dequeued by the system call at the end
+//! of every block, see [`apply_withdrawal_requests_contract_call`]. The queue in the state after a
+//! block contains the requests that are included in the following blocks.
+//!
+//! [`apply_withdrawal_requests_contract_call`]: crate::state_change::apply_withdrawal_requests_contract_call
+
+use alloy_eips::eip7002::{WithdrawalRequest, WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS};
+use reth_primitives::{Address, FixedBytes, B256, U256};
+use reth_storage_api::StateProvider;
+use reth_storage_errors::provider::ProviderResult;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Storage slot of the excess withdrawal requests.
+const EXCESS_WITHDRAWAL_REQUESTS_STORAGE_SLOT: u64 = 0;
+
+/// Storage slot of the number of withdrawal requests added in the current block.
+const WITHDRAWAL_REQUEST_COUNT_STORAGE_SLOT: u64 = 1;
+
+/// Storage slot of the index of the first request in the queue.
+const WITHDRAWAL_REQUEST_QUEUE_HEAD_STORAGE_SLOT: u64 = 2;
+
+/// Storage slot of the index after the last request in the queue.
+const WITHDRAWAL_REQUEST_QUEUE_TAIL_STORAGE_SLOT: u64 = 3;
+
+/// Storage slot of the first request of the queue, each request takes three slots.
+const WITHDRAWAL_REQUEST_QUEUE_STORAGE_OFFSET: u64 = 4;
+
+/// Value of the excess withdrawal requests before the contract is activated by the first system
+/// call.
+const EXCESS_INHIBITOR: U256 = U256::MAX;
+
+/// Minimum fee to add a withdrawal request, in wei.
+const MIN_WITHDRAWAL_REQUEST_FEE: u64 = 1;
+
+/// Controls how fast the withdrawal request fee rises with the excess requests.
+const WITHDRAWAL_REQUEST_FEE_UPDATE_FRACTION: u64 = 17;
+
+/// The state of the EIP-7002 withdrawal requests contract.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WithdrawalRequestQueue {
+    /// The number of requests above the target that were added in previous blocks, which
+    /// determines the fee.
+    pub excess_requests: U256,
+    /// The number of requests added in the current block.
+    pub request_count: u64,
+    /// The number of queued requests, which can be more than the number of read requests.
+    pub queue_length: u64,
+    /// The queued requests that were read, in the order they are dequeued.
+    pub requests: Vec<WithdrawalRequest>,
+}
+
+impl WithdrawalRequestQueue {
+    /// Returns `true` if the contract was activated by a system call.
+    pub fn is_active(&self) -> bool {
+        self.excess_requests != EXCESS_INHIBITOR
+    }
+
+    /// Returns the fee to add a withdrawal request, or `None` if the contract isn't active yet.
+    pub fn fee(&self) -> Option<U256> {
+        self.is_active().then(|| {
+            fake_exponential(
+                U256::from(MIN_WITHDRAWAL_REQUEST_FEE),
+                self.excess_requests,
+                U256::from(WITHDRAWAL_REQUEST_FEE_UPDATE_FRACTION),
+            )
+        })
+    }
+}
+
+/// Reads the withdrawal request queue from the given state, with up to `max_requests` of the
+/// queued requests from the head of the queue.
+///
+/// Returns an empty queue if the contract isn't deployed.
+pub fn withdrawal_request_queue<SP>(
+    state: &SP,
+    max_requests: usize,
+) -> ProviderResult<WithdrawalRequestQueue>
+where
+    SP: StateProvider + ?Sized,
+{
+    let storage = |slot: U256| -> ProviderResult<U256> {
+        Ok(state
+            .storage(WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS, B256::from(slot))?
+            .unwrap_or_default())
+    };
+
+    let excess_requests = storage(U256::from(EXCESS_WITHDRAWAL_REQUESTS_STORAGE_SLOT))?;
+    let request_count = storage(U256::from(WITHDRAWAL_REQUEST_COUNT_STORAGE_SLOT))?;
+    let head = storage(U256::from(WITHDRAWAL_REQUEST_QUEUE_HEAD_STORAGE_SLOT))?;
+    let tail = storage(U256::from(WITHDRAWAL_REQUEST_QUEUE_TAIL_STORAGE_SLOT))?;
+
+    let queue_length = tail.saturating_sub(head);
+    let len = queue_length.min(U256::from(max_requests)).saturating_to::<usize>();
+    let mut requests = Vec::with_capacity(len);
+    for index in 0..len {
+        let index = head + U256::from(index);
+        let slot = U256::from(WITHDRAWAL_REQUEST_QUEUE_STORAGE_OFFSET) + index * U256::from(3);
+
+        // Requests are stored in three slots:
+        //
+        // +------+--------------+--------------------------+
+        // | addr | pubkey[0:32] | pubkey[32:48] ++ amount  |
+        // +------+--------------+--------------------------+
+        let source_address = Address::from_word(B256::from(storage(slot)?));
+
+        let mut validator_pubkey = FixedBytes::<48>::ZERO;
+        validator_pubkey[..32].copy_from_slice(&storage(slot + U256::from(1))?.to_be_bytes::<32>());
+        let last = storage(slot + U256::from(2))?.to_be_bytes::<32>();
+        validator_pubkey[32..].copy_from_slice(&last[..16]);
+
+        let mut amount = [0; 8];
+        amount.copy_from_slice(&last[16..24]);
+
+        requests.push(WithdrawalRequest {
+            source_address,
+            validator_pubkey,
+            amount: u64::from_be_bytes(amount),
+        });
+    }
+
+    Ok(WithdrawalRequestQueue {
+        excess_requests,
+        request_count: request_count.saturating_to(),
+        queue_length: queue_length.saturating_to(),
+        requests,
+    })
+}
+
+/// Approximates `factor * e ** (numerator / denominator)` using Taylor expansion, as specified by
+/// EIP-7002.
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+    let mut i = U256::from(1);
+    let mut output = U256::ZERO;
+    let mut numerator_accum = factor.saturating_mul(denominator);
+    while numerator_accum > U256::ZERO {
+        output = output.saturating_add(numerator_accum);
+        numerator_accum = numerator_accum.saturating_mul(numerator) / (denominator * i);
+        i += U256::from(1);
+    }
+    output / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::StateProviderTest;
+    use reth_primitives::{Account, StorageKey};
+    use std::collections::HashMap;
+
+    #[test]
+    fn reads_withdrawal_request_queue() {
+        let slot = |slot: u64| StorageKey::from(U256::from(slot));
+        let source_address = Address::with_last_byte(1);
+        let mut last = [0u8; 32];
+        last[..16].copy_from_slice(&[0xbb; 16]);
+        last[16..24].copy_from_slice(&42u64.to_be_bytes());
+
+        // the first request was already dequeued
+        let storage = HashMap::from([
+            (slot(0), U256::from(2)),
+            (slot(1), U256::from(1)),
+            (slot(2), U256::from(1)),
+            (slot(3), U256::from(2)),
+            (slot(7), U256::from_be_bytes(source_address.into_word().0)),
+            (slot(8), U256::from_be_bytes([0xaa; 32])),
+            (slot(9), U256::from_be_bytes(last)),
+        ]);
+        let mut state = StateProviderTest::default();
+        state.insert_account(
+            WITHDRAWAL_REQUEST_PREDEPLOY_ADDRESS,
+            Account::default(),
+            None,
+            storage,
+        );
+
+        let queue = withdrawal_request_queue(&state, 16).unwrap();
+        assert_eq!(queue.request_count, 1);
+        assert_eq!(queue.queue_length, 1);
+        assert_eq!(queue.fee(), Some(U256::from(1)));
+
+        let mut validator_pubkey = FixedBytes::<48>::ZERO;
+        validator_pubkey[..32].copy_from_slice(&[0xaa; 32]);
+        validator_pubkey[32..].copy_from_slice(&[0xbb; 16]);
+        assert_eq!(
+            queue.requests,
+            vec![WithdrawalRequest { source_address, validator_pubkey, amount: 42 }]
+        );
+
+        // only the given number of requests is read
+        let queue = withdrawal_request_queue(&state, 0).unwrap();
+        assert_eq!(queue.queue_length, 1);
+        assert!(queue.requests.is_empty());
+    }
+
+    #[test]
+    fn inactive_queue() {
+        let queue =
+            WithdrawalRequestQueue { excess_requests: EXCESS_INHIBITOR, ..Default::default() };
+        assert_eq!(queue.fee(), None);
+        assert_eq!(
+            WithdrawalRequestQueue { excess_requests: U256::from(17), ..Default::default() }.fee(),
+            Some(U256::from(2))
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! [`jsonrpsee`] transport adapter implementation for IPC.
 
-use crate::stream_codec::StreamCodec;
+use crate::{endpoint::ipc_endpoint_path, stream_codec::StreamCodec};
 use futures::{StreamExt, TryFutureExt};
 use interprocess::local_socket::{
     tokio::{prelude::*, RecvHalf, SendHalf},
@@ -64,7 +64,8 @@ pub(crate) struct IpcTransportClientBuilder;
 
 impl IpcTransportClientBuilder {
     pub(crate) async fn build(self, path: &str) -> Result<(Sender, Receiver), IpcError> {
-        let conn = async { path.to_fs_name::<GenericFilePath>() }
+        let path = ipc_endpoint_path(path);
+        let conn = async { path.as_str().to_fs_name::<GenericFilePath>() }
             .and_then(LocalSocketStream::connect)
             .await
             .map_err(|err| IpcError::FailedToConnect { path: path.to_string(), err })?;
@@ -84,7 +85,7 @@ impl IpcTransportClientBuilder {
 pub struct IpcClientBuilder;
 
 impl IpcClientBuilder {
-    /// Connects to a IPC socket
+    /// Connects to a IPC socket, or a named pipe on Windows
     ///
     /// ```
     /// use jsonrpsee::{core::client::ClientT, rpc_params};
@@ -0,0 +1,48 @@
+//! Paths of IPC endpoints.
+
+/// The prefix of the paths of Windows named pipes.
+pub const NAMED_PIPE_PREFIX: &str = r"\\.\pipe\";
+
+/// Returns the path of the IPC endpoint with the given path or name.
+///
+/// On Windows, IPC endpoints are named pipes, see [`named_pipe_path`]. On other platforms, IPC
+/// endpoints are Unix domain sockets at the given path.
+pub fn ipc_endpoint_path(path: &str) -> String {
+    if cfg!(windows) {
+        named_pipe_path(path)
+    } else {
+        path.to_string()
+    }
+}
+
+/// Returns the path of the named pipe with the given path or name.
+///
+/// Paths that don't start with `\\.\pipe\` are turned into the named pipe with the whole path as
+/// its name, with backslashes replaced by slashes since pipe names can't contain them, e.g.
+/// `C:\reth\reth.ipc` is `\\.\pipe\C:/reth/reth.ipc`. This way, different paths never resolve to
+/// the same pipe.
+pub fn named_pipe_path(path: &str) -> String {
+    let is_named_pipe = path
+        .get(..NAMED_PIPE_PREFIX.len())
+        .map_or(false, |prefix| prefix.eq_ignore_ascii_case(NAMED_PIPE_PREFIX));
+    if is_named_pipe {
+        return path.to_string()
+    }
+
+    format!("{NAMED_PIPE_PREFIX}{}", path.replace('\\', "/"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn named_pipe_paths() {
+        assert_eq!(named_pipe_path(r"\\.\pipe\reth.ipc"), r"\\.\pipe\reth.ipc");
+        assert_eq!(named_pipe_path(r"\\.\PIPE\reth.ipc"), r"\\.\PIPE\reth.ipc");
+        assert_eq!(named_pipe_path("reth.ipc"), r"\\.\pipe\reth.ipc");
+        assert_eq!(named_pipe_path(r"C:\reth\reth.ipc"), r"\\.\pipe\C:/reth/reth.ipc");
+        assert_eq!(named_pipe_path(r"C:\other\reth.ipc"), r"\\.\pipe\C:/other/reth.ipc");
+        assert_eq!(named_pipe_path("/tmp/reth.ipc"), r"\\.\pipe\/tmp/reth.ipc");
+    }
+}
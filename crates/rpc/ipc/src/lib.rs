@@ -13,6 +13,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 pub mod client;
+pub mod endpoint;
 pub mod server;
 
 /// Json codec implementation
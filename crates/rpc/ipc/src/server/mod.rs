@@ -35,6 +35,7 @@ use tower::{layer::util::Identity, Layer, Service};
 use tracing::{debug, instrument, trace, warn, Instrument};
 // re-export so can be used during builder setup
 use crate::{
+    endpoint::ipc_endpoint_path,
     server::{
         connection::IpcConnDriver,
         rpc_service::{RpcService, RpcServiceCfg},
@@ -784,9 +785,11 @@ impl<HttpMiddleware, RpcMiddleware> Builder<HttpMiddleware, RpcMiddleware> {
     }
 
     /// Finalize the configuration of the server. Consumes the [`Builder`].
+    ///
+    /// On Windows, the endpoint is a named pipe, see [`ipc_endpoint_path`].
     pub fn build(self, endpoint: String) -> IpcServer<HttpMiddleware, RpcMiddleware> {
         IpcServer {
-            endpoint,
+            endpoint: ipc_endpoint_path(&endpoint),
             cfg: self.settings,
             id_provider: self.id_provider,
             http_middleware: self.http_middleware,
@@ -1,6 +1,8 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_network_api::KnownPeer;
 use reth_network_peers::{AnyNode, NodeRecord};
-use reth_rpc_types::{admin::NodeInfo, PeerInfo};
+use reth_primitives::B256;
+use reth_rpc_types::{admin::NodeInfo, PeerInfo, Transaction};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
@@ -34,6 +36,13 @@ pub trait AdminApi {
     #[method(name = "peers")]
     async fn peers(&self) -> RpcResult<Vec<PeerInfo>>;
 
+    /// Returns all known peers that are not banned, connected or not, with their reputation.
+    ///
+    /// The result has the format of the peers file, so it can be used as the peers file of another
+    /// node to bootstrap it from the peers of this node.
+    #[method(name = "exportPeers")]
+    async fn export_peers(&self) -> RpcResult<Vec<KnownPeer>>;
+
     /// Creates an RPC subscription which serves events received from the network.
     #[subscription(
         name = "peerEvents",
@@ -45,4 +54,16 @@ pub trait AdminApi {
     /// Returns the ENR of the node.
     #[method(name = "nodeInfo")]
     async fn node_info(&self) -> RpcResult<NodeInfo>;
+
+    /// Returns the local transactions that are tracked until they are mined, including the ones
+    /// that were dropped from the pool.
+    #[method(name = "localTransactions")]
+    fn local_transactions(&self) -> RpcResult<Vec<Transaction>>;
+
+    /// Stops tracking the local transaction, so that it is no longer reinserted into the pool if it
+    /// is dropped. This does not remove the transaction from the pool.
+    ///
+    /// Returns true if the transaction was tracked.
+    #[method(name = "removeLocalTransaction")]
+    fn remove_local_transaction(&self, hash: B256) -> RpcResult<bool>;
 }
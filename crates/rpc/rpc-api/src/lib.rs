@@ -28,10 +28,12 @@ mod mev;
 mod net;
 mod optimism;
 mod otterscan;
+mod reorg_guard;
 mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+mod usage;
 mod validation;
 mod web3;
 
@@ -51,10 +53,12 @@ pub mod servers {
         mev::MevApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
+        reorg_guard::ReorgGuardApiServer,
         reth::RethApiServer,
         rpc::RpcApiServer,
         trace::TraceApiServer,
         txpool::TxPoolApiServer,
+        usage::RpcUsageApiServer,
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
     };
@@ -80,9 +84,12 @@ pub mod clients {
         mev::MevApiClient,
         net::NetApiClient,
         otterscan::OtterscanClient,
+        reorg_guard::ReorgGuardApiClient,
+        reth::RethApiClient,
         rpc::RpcApiServer,
         trace::TraceApiClient,
         txpool::TxPoolApiClient,
+        usage::RpcUsageApiClient,
         validation::BlockSubmissionValidationApiClient,
         web3::Web3ApiClient,
     };
@@ -0,0 +1,16 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_primitives::B256;
+
+/// Admin namespace rpc interface to confirm reorgs rejected by the reorg guard.
+///
+/// This should only be exposed over IPC.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait ReorgGuardApi {
+    /// Confirms the pending reorg to the given head, so that the next forkchoice update to the
+    /// head is applied.
+    ///
+    /// Returns false if there is no pending reorg to the given head.
+    #[method(name = "confirmReorg")]
+    fn confirm_reorg(&self, head: B256) -> RpcResult<bool>;
+}
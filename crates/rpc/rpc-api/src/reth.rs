@@ -1,5 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
-use reth_primitives::{Address, BlockId, U256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, Selector, B256, U256};
+use reth_rpc_types::{
+    AccountDelegation, AccountProofRequest, AccountState, AnyTransactionReceipt, ChainReorg,
+    EIP1186AccountProofResponse, WithdrawalRequestQueue,
+};
 use std::collections::HashMap;
 
 /// Reth API namespace for reth-specific methods
@@ -12,4 +16,107 @@ pub trait RethApi {
         &self,
         block_id: BlockId,
     ) -> RpcResult<HashMap<Address, U256>>;
+
+    /// Returns the nonce, balance and code hash of the given accounts at the given block, in the
+    /// order of the addresses.
+    ///
+    /// Defaults to the latest block if no block is given.
+    #[method(name = "getAccountStates")]
+    async fn reth_get_account_states(
+        &self,
+        addresses: Vec<Address>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<AccountState>>;
+
+    /// Returns the EIP-7702 delegations of the given accounts at the given block, in the order of
+    /// the addresses.
+    ///
+    /// The delegation of an account is read from the delegation designator in its code, and
+    /// includes the code hash of the delegate. Accounts that don't delegate have no delegation.
+    ///
+    /// Defaults to the latest block if no block is given.
+    #[method(name = "getDelegations")]
+    async fn reth_get_delegations(
+        &self,
+        addresses: Vec<Address>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<Option<AccountDelegation>>>;
+
+    /// Returns the account and storage proofs of the given accounts, like `eth_getProof`, in the
+    /// order of the requests.
+    ///
+    /// All accounts are proven in a single walk of the state trie, so the trie nodes the proofs
+    /// share are only read once. Only the latest block is supported, which is also the default.
+    #[method(name = "getProofs")]
+    async fn reth_get_proofs(
+        &self,
+        requests: Vec<AccountProofRequest>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<EIP1186AccountProofResponse>>;
+
+    /// Returns the withdrawal requests queued in the EIP-7002 withdrawal requests contract after
+    /// the given block, which are dequeued by the following blocks.
+    ///
+    /// At most 1024 requests from the head of the queue are returned, `queueLength` is the number
+    /// of all queued requests.
+    ///
+    /// Defaults to the latest block if no block is given.
+    #[method(name = "getWithdrawalRequestQueue")]
+    async fn reth_get_withdrawal_request_queue(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<WithdrawalRequestQueue>;
+
+    /// Returns the receipt of a transaction by transaction hash, like `eth_getTransactionReceipt`,
+    /// extended with the timestamp of the block and a breakdown of the fees paid.
+    ///
+    /// In addition to the standard fields, the receipt includes `blockTimestamp`,
+    /// `effectivePriorityFee` and `totalFee`.
+    #[method(name = "getTransactionReceipt")]
+    async fn reth_get_transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<AnyTransactionReceipt>>;
+
+    /// Returns the hashes of the transactions in the block range that call the function with the
+    /// given 4-byte selector, i.e. whose input starts with it, in the order of the chain.
+    ///
+    /// Requires the node to index transactions by selector, which only covers the blocks executed
+    /// since the index was enabled. Defaults to the range from the earliest to the latest block.
+    #[method(name = "getTransactionsBySelector")]
+    async fn reth_get_transactions_by_selector(
+        &self,
+        selector: Selector,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> RpcResult<Vec<B256>>;
+
+    /// Removes a locally submitted transaction from the pool, so that it is no longer propagated.
+    ///
+    /// `signature` must be the sender's EIP-191 signature of the transaction hash, as returned by
+    /// `eth_sign`, so that only the sender can cancel its transactions.
+    ///
+    /// If `replace` is true, a zero value transfer from the sender to itself is submitted at the
+    /// same nonce, with the fees of the cancelled transaction bumped by the configured percentage,
+    /// to also replace the transaction in the pools of peers it was already propagated to. This
+    /// requires a signer for the sender.
+    ///
+    /// Returns the hash of the replacement, if any.
+    #[method(name = "cancelTransaction")]
+    async fn reth_cancel_transaction(
+        &self,
+        hash: B256,
+        signature: Bytes,
+        replace: Option<bool>,
+    ) -> RpcResult<Option<B256>>;
+
+    /// Creates a subscription that emits the blocks that were removed from and added to the
+    /// canonical chain on every reorg, so that data derived from the removed blocks can be
+    /// invalidated.
+    #[subscription(
+        name = "subscribeChainReorgs",
+        unsubscribe = "unsubscribeChainReorgs",
+        item = ChainReorg
+    )]
+    async fn reth_subscribe_chain_reorgs(&self) -> jsonrpsee::core::SubscriptionResult;
 }
@@ -1,6 +1,9 @@
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use reth_primitives::Address;
-use reth_rpc_types::txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
+use reth_rpc_types::{
+    txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
+    Transaction, TxpoolPage,
+};
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -33,4 +36,25 @@ pub trait TxPoolApi {
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
     async fn txpool_content(&self) -> RpcResult<TxpoolContent>;
+
+    /// Returns a page of the transactions of `txpool_content`, for pools that are too large to
+    /// return at once.
+    ///
+    /// The page contains the transactions of the senders starting with the `cursor` address, in
+    /// the order of their addresses, up to `limit` transactions. All transactions of a sender are
+    /// on the same page. Pass the `next` address of the page as the `cursor` of the next request.
+    #[method(name = "contentPage")]
+    async fn txpool_content_page(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolPage<Transaction>>;
+
+    /// Returns a page of the summaries of `txpool_inspect`, like `txpool_contentPage`.
+    #[method(name = "inspectPage")]
+    async fn txpool_inspect_page(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolPage<TxpoolInspectSummary>>;
 }
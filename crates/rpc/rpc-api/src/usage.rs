@@ -0,0 +1,19 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_rpc_types::ApiKeyUsage;
+
+/// Admin namespace rpc interface to query the usage of the RPC servers by API key.
+///
+/// The usage of all API keys is exposed, so this should not be exposed to the clients of the
+/// servers.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait RpcUsageApi {
+    /// Returns the usage of the given API key, or of all API keys if none is given.
+    #[method(name = "rpcUsage")]
+    fn rpc_usage(&self, api_key: Option<String>) -> RpcResult<Vec<ApiKeyUsage>>;
+
+    /// Resets the usage of the given API key, or of all API keys if none is given, and returns
+    /// the usage before the reset.
+    #[method(name = "resetRpcUsage")]
+    fn reset_rpc_usage(&self, api_key: Option<String>) -> RpcResult<Vec<ApiKeyUsage>>;
+}
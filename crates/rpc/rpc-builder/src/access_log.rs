@@ -0,0 +1,275 @@
+//! Structured access log for the RPC servers.
+
+use crate::metrics::RpcTransport;
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, RpcModule};
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    future::Future,
+    hash::{DefaultHasher, Hash, Hasher},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    time::Instant,
+};
+use tower::Layer;
+use tracing::info;
+
+/// Method logged for failed requests that aren't sampled and call an unregistered method.
+const UNKNOWN_METHOD: &str = "unknown";
+
+/// Configuration of the RPC access log.
+///
+/// Every logged request includes its method, a hash of its params, its latency, the size of its
+/// response and its error code, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcAccessLogConfig {
+    /// Log one out of every `sample` requests.
+    sample: u64,
+    /// Whether to log the raw params in addition to their hash.
+    log_params: bool,
+    /// Methods whose params, and params hash, are never logged.
+    redacted_methods: HashSet<String>,
+}
+
+impl Default for RpcAccessLogConfig {
+    fn default() -> Self {
+        Self { sample: 1, log_params: false, redacted_methods: HashSet::new() }
+    }
+}
+
+impl RpcAccessLogConfig {
+    /// Log one out of every `sample` requests.
+    ///
+    /// Failed requests are always logged.
+    pub fn with_sample(mut self, sample: u64) -> Self {
+        self.sample = sample.max(1);
+        self
+    }
+
+    /// Log the raw params of requests in addition to their hash.
+    pub const fn with_params(mut self, log_params: bool) -> Self {
+        self.log_params = log_params;
+        self
+    }
+
+    /// Never log the params, nor the params hash, of the given methods.
+    pub fn with_redacted_methods(
+        mut self,
+        methods: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.redacted_methods = methods.into_iter().map(Into::into).collect();
+        self
+    }
+}
+
+/// A [`Layer`] that logs the requests of an RPC server, see [`RpcAccessLogConfig`].
+///
+/// Does nothing if no config is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RpcAccessLog {
+    inner: Option<Arc<RpcAccessLogInner>>,
+}
+
+impl RpcAccessLog {
+    /// Creates a new access log layer for the given transport, disabled if `config` is `None`.
+    pub(crate) fn new(
+        config: Option<&RpcAccessLogConfig>,
+        module: Option<&RpcModule<()>>,
+        transport: RpcTransport,
+    ) -> Self {
+        Self {
+            inner: config.map(|config| {
+                Arc::new(RpcAccessLogInner {
+                    config: config.clone(),
+                    transport,
+                    methods: module
+                        .map(|module| module.method_names().collect())
+                        .unwrap_or_default(),
+                    requests: AtomicU64::new(0),
+                })
+            }),
+        }
+    }
+
+    /// Returns the entry to log once the request with the given method and params completes.
+    ///
+    /// Requests that aren't sampled are only logged if they fail, so their entry doesn't allocate:
+    /// it refers to the registered method name and doesn't include the params.
+    pub(crate) fn entry(&self, method: &str, params: Option<&str>) -> Option<AccessLogEntry> {
+        let inner = self.inner.as_ref()?;
+        let config = &inner.config;
+
+        let registered = inner.methods.get(method).copied();
+        if inner.requests.fetch_add(1, Ordering::Relaxed) % config.sample != 0 {
+            return Some(AccessLogEntry {
+                transport: inner.transport,
+                method: Cow::Borrowed(registered.unwrap_or(UNKNOWN_METHOD)),
+                params_hash: None,
+                params: None,
+                sampled: false,
+                started_at: Instant::now(),
+            })
+        }
+
+        let redacted = config.redacted_methods.contains(method);
+        let params_hash = params.filter(|_| !redacted).map(|params| {
+            let mut hasher = DefaultHasher::new();
+            params.hash(&mut hasher);
+            hasher.finish()
+        });
+        let params = params.filter(|_| config.log_params && !redacted).map(ToString::to_string);
+
+        Some(AccessLogEntry {
+            transport: inner.transport,
+            method: registered.map_or_else(|| Cow::Owned(method.to_string()), Cow::Borrowed),
+            params_hash,
+            params,
+            sampled: true,
+            started_at: Instant::now(),
+        })
+    }
+}
+
+impl<S> Layer<S> for RpcAccessLog {
+    type Service = RpcAccessLogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAccessLogService { inner, log: self.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct RpcAccessLogInner {
+    config: RpcAccessLogConfig,
+    transport: RpcTransport,
+    /// Names of the registered methods.
+    methods: HashSet<&'static str>,
+    /// The number of requests seen, used for sampling.
+    requests: AtomicU64,
+}
+
+/// A [`RpcServiceT`] middleware that logs the requests of an RPC server.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcAccessLogService<S> {
+    inner: S,
+    log: RpcAccessLog,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcAccessLogService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = AccessLogFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let entry = self.log.entry(req.method_name(), req.params().as_str());
+        AccessLogFuture { fut: self.inner.call(req), entry }
+    }
+}
+
+/// A request to log once its response is ready.
+#[derive(Debug)]
+pub(crate) struct AccessLogEntry {
+    transport: RpcTransport,
+    method: Cow<'static, str>,
+    params_hash: Option<u64>,
+    params: Option<String>,
+    /// Whether the request is logged even if it succeeds.
+    sampled: bool,
+    started_at: Instant,
+}
+
+impl AccessLogEntry {
+    /// Logs the request with the size of its response and its error code, if it failed.
+    pub(crate) fn log(self, response_size: usize, error_code: Option<i32>) {
+        if !self.sampled && error_code.is_none() {
+            return
+        }
+        info!(
+            target: "rpc::access",
+            transport = self.transport.as_str(),
+            method = %self.method,
+            params_hash = ?self.params_hash.map(|hash| format!("{hash:016x}")),
+            params = ?self.params,
+            latency = ?self.started_at.elapsed(),
+            response_size,
+            ?error_code,
+            "RPC request"
+        );
+    }
+}
+
+/// Response future that logs a single request/response pair.
+#[pin_project::pin_project]
+pub(crate) struct AccessLogFuture<F> {
+    #[pin]
+    fut: F,
+    entry: Option<AccessLogEntry>,
+}
+
+impl<F> std::fmt::Debug for AccessLogFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("AccessLogFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for AccessLogFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let res = this.fut.poll(cx);
+        if let Poll::Ready(resp) = &res {
+            if let Some(entry) = this.entry.take() {
+                entry.log(resp.as_result().len(), resp.as_error_code());
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn access_log_entry() {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_call", |_, _, _| "0x").unwrap();
+
+        let log = RpcAccessLog::new(None, Some(&module), RpcTransport::Http);
+        assert!(log.entry("eth_call", None).is_none());
+
+        let config = RpcAccessLogConfig::default()
+            .with_sample(2)
+            .with_params(true)
+            .with_redacted_methods(["eth_sendRawTransaction"]);
+        let log = RpcAccessLog::new(Some(&config), Some(&module), RpcTransport::Http);
+
+        let first = log.entry("eth_call", Some(r#"[{"to":"0x00"}]"#)).unwrap();
+        assert!(first.sampled);
+        assert!(first.params_hash.is_some());
+        assert_eq!(first.params.as_deref(), Some(r#"[{"to":"0x00"}]"#));
+
+        let second = log.entry("eth_call", Some(r#"[{"to":"0x00"}]"#)).unwrap();
+        assert!(!second.sampled);
+        assert!(matches!(second.method, Cow::Borrowed("eth_call")));
+        assert_eq!(second.params_hash, None);
+        assert_eq!(second.params, None);
+
+        let redacted = log.entry("eth_sendRawTransaction", Some(r#"["0x00"]"#)).unwrap();
+        assert!(redacted.sampled);
+        assert_eq!(redacted.method, "eth_sendRawTransaction");
+        assert_eq!(redacted.params_hash, None);
+        assert_eq!(redacted.params, None);
+
+        let unknown = log.entry("eth_unknown", None).unwrap();
+        assert!(!unknown.sampled);
+        assert_eq!(unknown.method, UNKNOWN_METHOD);
+    }
+}
@@ -1,12 +1,12 @@
 use crate::{
-    auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcModuleConfig,
-    RpcServerConfig, TransportRpcModuleConfig,
+    auth::AuthServerConfig, error::RpcError, EthConfig, IpcServerBuilder, RpcAccessLogConfig,
+    RpcModuleConfig, RpcNamespaceAuthConfig, RpcServerConfig, TransportRpcModuleConfig,
 };
 use jsonrpsee::server::ServerBuilder;
 use reth_node_core::{args::RpcServerArgs, utils::get_or_create_jwt_secret_from_path};
 use reth_rpc::eth::{cache::EthStateCacheConfig, gas_oracle::GasPriceOracleConfig};
 use reth_rpc_layer::{JwtError, JwtSecret};
-use reth_rpc_server_types::RpcModuleSelection;
+use reth_rpc_server_types::{RethRpcModule, RpcModuleSelection};
 use std::{net::SocketAddr, path::PathBuf};
 use tower::layer::util::Identity;
 use tracing::debug;
@@ -52,6 +52,13 @@ pub trait RethRpcServerConfig {
     /// Creates the [`RpcServerConfig`] from cli args.
     fn rpc_server_config(&self) -> RpcServerConfig;
 
+    /// Creates the [`RpcAccessLogConfig`] from cli args, if the access log is enabled.
+    fn rpc_access_log_config(&self) -> Option<RpcAccessLogConfig>;
+
+    /// Creates the [`RpcNamespaceAuthConfig`] from cli args, if any namespace requires
+    /// authentication.
+    fn rpc_namespace_auth_config(&self) -> Option<RpcNamespaceAuthConfig>;
+
     /// Creates the [`AuthServerConfig`] from cli args.
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError>;
 
@@ -91,7 +98,13 @@ impl RethRpcServerConfig for RpcServerArgs {
             .max_tracing_requests(self.rpc_max_tracing_requests)
             .max_blocks_per_filter(self.rpc_max_blocks_per_filter.unwrap_or_max())
             .max_logs_per_response(self.rpc_max_logs_per_response.unwrap_or_max() as usize)
+            .max_traces_per_response(self.rpc_max_traces_per_response.unwrap_or_max() as usize)
+            .max_txpool_content(self.rpc_max_txpool_content.unwrap_or_max() as usize)
             .rpc_gas_cap(self.rpc_gas_cap)
+            .precompile_gas_budget(self.rpc_precompile_gas_budget)
+            .revert_hints(self.rpc_revert_hints)
+            .call_cache_max_len(self.rpc_state_cache.max_calls)
+            .cancel_price_bump(self.rpc_cancel_price_bump)
             .state_cache(self.state_cache_config())
             .gpo_config(self.gas_price_oracle_config())
     }
@@ -138,7 +151,11 @@ impl RethRpcServerConfig for RpcServerArgs {
         }
 
         if self.is_ipc_enabled() {
-            config = config.with_ipc(RpcModuleSelection::default_ipc_modules());
+            config = config.with_ipc(
+                self.ipc_api
+                    .clone()
+                    .unwrap_or_else(|| RpcModuleSelection::default_ipc_modules().into()),
+            );
         }
 
         config
@@ -161,7 +178,11 @@ impl RethRpcServerConfig for RpcServerArgs {
     }
 
     fn rpc_server_config(&self) -> RpcServerConfig {
-        let mut config = RpcServerConfig::default().with_jwt_secret(self.rpc_secret_key());
+        let mut config = RpcServerConfig::default()
+            .with_jwt_secret(self.rpc_secret_key())
+            .with_access_log(self.rpc_access_log_config())
+            .with_rate_limits(self.rpc_rate_limits.clone())
+            .with_namespace_auth(self.rpc_namespace_auth_config());
 
         if self.http {
             let socket_address = SocketAddr::new(self.http_addr, self.http_port);
@@ -185,6 +206,23 @@ impl RethRpcServerConfig for RpcServerArgs {
         config
     }
 
+    fn rpc_access_log_config(&self) -> Option<RpcAccessLogConfig> {
+        self.rpc_access_log.enabled.then(|| {
+            RpcAccessLogConfig::default()
+                .with_sample(self.rpc_access_log.sample)
+                .with_params(self.rpc_access_log.params)
+                .with_redacted_methods(self.rpc_access_log.redact.iter().cloned())
+        })
+    }
+
+    fn rpc_namespace_auth_config(&self) -> Option<RpcNamespaceAuthConfig> {
+        (!self.rpc_auth.namespaces.is_empty()).then(|| {
+            RpcNamespaceAuthConfig::new(self.rpc_auth.namespaces.iter().map(RethRpcModule::as_str))
+                .with_jwt_secret(self.rpc_auth.jwt_secret)
+                .with_tokens(self.rpc_auth.tokens.iter().flat_map(|tokens| tokens.0.clone()))
+        })
+    }
+
     fn auth_server_config(&self, jwt_secret: JwtSecret) -> Result<AuthServerConfig, RpcError> {
         let address = SocketAddr::new(self.auth_addr, self.auth_port);
 
@@ -301,6 +339,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ipc_rpc_modules() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        let config = args.transport_rpc_module_config();
+        assert_eq!(
+            config.ipc().cloned().unwrap().into_selection(),
+            RpcModuleSelection::default_ipc_modules()
+        );
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipc.api", "eth,net"]).args;
+        let config = args.transport_rpc_module_config();
+        let expected = [RethRpcModule::Eth, RethRpcModule::Net];
+        assert_eq!(config.ipc().cloned().unwrap().into_selection(), expected.into());
+
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth", "--ipcdisable"]).args;
+        assert!(args.transport_rpc_module_config().ipc().is_none());
+    }
+
     #[test]
     fn test_rpc_server_config() {
         let args = CommandParser::<RpcServerArgs>::parse_from([
@@ -361,4 +418,32 @@ mod tests {
         assert_eq!(config.max_blocks_per_filter, Some(100));
         assert_eq!(config.max_logs_per_response, Some(200));
     }
+
+    #[test]
+    fn test_trace_filter_limits() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(
+            args.eth_config().max_traces_per_response,
+            constants::DEFAULT_MAX_TRACES_PER_RESPONSE
+        );
+
+        let args = CommandParser::<RpcServerArgs>::parse_from([
+            "reth",
+            "--rpc.max-traces-per-response",
+            "0",
+        ])
+        .args;
+        assert_eq!(args.eth_config().max_traces_per_response, usize::MAX);
+    }
+
+    #[test]
+    fn test_txpool_content_limit() {
+        let args = CommandParser::<RpcServerArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.eth_config().max_txpool_content, constants::DEFAULT_MAX_TXPOOL_CONTENT);
+
+        let args =
+            CommandParser::<RpcServerArgs>::parse_from(["reth", "--rpc.max-txpool-content", "0"])
+                .args;
+        assert_eq!(args.eth_config().max_txpool_content, usize::MAX);
+    }
 }
@@ -8,8 +8,10 @@ use reth_rpc::{
 };
 use reth_rpc_server_types::constants::{
     default_max_tracing_requests, DEFAULT_MAX_BLOCKS_PER_FILTER, DEFAULT_MAX_LOGS_PER_RESPONSE,
+    DEFAULT_MAX_TRACES_PER_RESPONSE, DEFAULT_MAX_TXPOOL_CONTENT,
 };
 use reth_tasks::pool::BlockingTaskPool;
+use reth_transaction_pool::DEFAULT_PRICE_BUMP;
 use serde::{Deserialize, Serialize};
 
 /// All handlers for the `eth` namespace
@@ -40,15 +42,31 @@ pub struct EthConfig {
     pub max_blocks_per_filter: u64,
     /// Maximum number of logs that can be returned in a single response in `eth_getLogs` calls.
     pub max_logs_per_response: usize,
+    /// Maximum number of traces that can be returned in a single response in `trace_filter`
+    /// calls.
+    pub max_traces_per_response: usize,
     /// Gas limit for `eth_call` and call tracing RPC methods.
     ///
     /// Defaults to [`RPC_DEFAULT_GAS_CAP`]
     pub rpc_gas_cap: u64,
+    /// Gas that precompiles may use in a single call of `eth_call` and call tracing RPC methods,
+    /// unlimited if not set.
+    pub precompile_gas_budget: Option<u64>,
+    /// Whether the error data of reverted `eth_call` and `eth_estimateGas` calls includes hints
+    /// about the failing call frame.
+    pub revert_hints: bool,
     ///
     /// Sets TTL for stale filters
     pub stale_filter_ttl: std::time::Duration,
     /// Settings for the fee history cache
     pub fee_history_cache: FeeHistoryCacheConfig,
+    /// Max number of `eth_call` outputs in the call cache, `0` disables the cache.
+    pub call_cache_max_len: u32,
+    /// Percentage by which `reth_cancelTransaction` bumps the fees of replacement transactions.
+    pub cancel_price_bump: u128,
+    /// Maximum number of transactions that `txpool_content` and `txpool_inspect` can return,
+    /// larger pools must be paginated.
+    pub max_txpool_content: usize,
 }
 
 impl EthConfig {
@@ -72,9 +90,15 @@ impl Default for EthConfig {
             max_tracing_requests: default_max_tracing_requests(),
             max_blocks_per_filter: DEFAULT_MAX_BLOCKS_PER_FILTER,
             max_logs_per_response: DEFAULT_MAX_LOGS_PER_RESPONSE,
+            max_traces_per_response: DEFAULT_MAX_TRACES_PER_RESPONSE,
             rpc_gas_cap: RPC_DEFAULT_GAS_CAP.into(),
+            precompile_gas_budget: None,
+            revert_hints: false,
             stale_filter_ttl: DEFAULT_STALE_FILTER_TTL,
             fee_history_cache: FeeHistoryCacheConfig::default(),
+            call_cache_max_len: 0,
+            cancel_price_bump: DEFAULT_PRICE_BUMP,
+            max_txpool_content: DEFAULT_MAX_TXPOOL_CONTENT,
         }
     }
 }
@@ -110,9 +134,49 @@ impl EthConfig {
         self
     }
 
+    /// Configures the maximum number of traces per `trace_filter` response
+    pub const fn max_traces_per_response(mut self, max_traces: usize) -> Self {
+        self.max_traces_per_response = max_traces;
+        self
+    }
+
     /// Configures the maximum gas limit for `eth_call` and call tracing RPC methods
     pub const fn rpc_gas_cap(mut self, rpc_gas_cap: u64) -> Self {
         self.rpc_gas_cap = rpc_gas_cap;
         self
     }
+
+    /// Configures the gas that precompiles may use in a single call of `eth_call` and call tracing
+    /// RPC methods
+    pub const fn precompile_gas_budget(mut self, budget: Option<u64>) -> Self {
+        self.precompile_gas_budget = budget;
+        self
+    }
+
+    /// Configures whether the error data of reverted `eth_call` and `eth_estimateGas` calls
+    /// includes hints about the failing call frame
+    pub const fn revert_hints(mut self, enabled: bool) -> Self {
+        self.revert_hints = enabled;
+        self
+    }
+
+    /// Configures the maximum number of cached `eth_call` outputs, `0` disables the cache
+    pub const fn call_cache_max_len(mut self, max_len: u32) -> Self {
+        self.call_cache_max_len = max_len;
+        self
+    }
+
+    /// Configures the percentage by which `reth_cancelTransaction` bumps the fees of replacement
+    /// transactions
+    pub const fn cancel_price_bump(mut self, price_bump: u128) -> Self {
+        self.cancel_price_bump = price_bump;
+        self
+    }
+
+    /// Configures the maximum number of transactions per `txpool_content` and `txpool_inspect`
+    /// response
+    pub const fn max_txpool_content(mut self, max_txs: usize) -> Self {
+        self.max_txpool_content = max_txs;
+        self
+    }
 }
@@ -21,7 +21,8 @@
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
 //!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     ChangeSetReader, EvmEnvProvider, LogIndexProvider, StateProviderFactory,
+//!     TransactionSelectorProvider,
 //! };
 //! use reth_rpc_builder::{
 //!     RethRpcModule, RpcModuleBuilder, RpcServerConfig, ServerBuilder, TransportRpcModuleConfig,
@@ -39,6 +40,8 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + TransactionSelectorProvider
+//!         + LogIndexProvider
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -82,7 +85,8 @@
 //! use reth_network_api::{NetworkInfo, Peers};
 //! use reth_provider::{
 //!     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-//!     ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+//!     ChangeSetReader, EvmEnvProvider, LogIndexProvider, StateProviderFactory,
+//!     TransactionSelectorProvider,
 //! };
 //! use reth_rpc_api::EngineApiServer;
 //! use reth_rpc_builder::{
@@ -105,6 +109,8 @@
 //!         + BlockReaderIdExt
 //!         + ChainSpecProvider
 //!         + ChangeSetReader
+//!         + TransactionSelectorProvider
+//!         + LogIndexProvider
 //!         + StateProviderFactory
 //!         + EvmEnvProvider
 //!         + Clone
@@ -156,8 +162,14 @@
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
 use crate::{
-    auth::AuthRpcModule, cors::CorsDomainError, error::WsHttpSamePortError,
-    metrics::RpcRequestMetrics,
+    access_log::RpcAccessLog,
+    auth::AuthRpcModule,
+    cors::CorsDomainError,
+    error::WsHttpSamePortError,
+    metrics::{RpcRequestMetrics, RpcTransport},
+    namespace_auth::{RpcCredentialsLayer, RpcNamespaceAuth},
+    rate_limit::{RpcApiKeyLayer, RpcRateLimit},
+    usage::RpcUsageLayer,
 };
 use error::{ConflictingModules, RpcError, ServerKind};
 use http::{header::AUTHORIZATION, HeaderMap};
@@ -167,25 +179,27 @@ use jsonrpsee::{
     Methods, RpcModule,
 };
 use reth_engine_primitives::EngineTypes;
-use reth_evm::ConfigureEvm;
+use reth_evm::{precompile::PrecompileGasBudget, ConfigureEvm};
 use reth_ipc::server::IpcServer;
 use reth_network_api::{noop::NoopNetwork, NetworkInfo, Peers};
 use reth_provider::{
     AccountReader, BlockReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider,
-    ChangeSetReader, EvmEnvProvider, StateProviderFactory,
+    ChangeSetReader, EvmEnvProvider, LogIndexProvider, StateProviderFactory,
+    TransactionSelectorProvider,
 };
 use reth_rpc::{
     eth::{
-        cache::{cache_new_blocks_task, EthStateCache},
+        cache::{cache_new_blocks_task, EthCallCache, EthStateCache},
         fee_history_cache_new_blocks_task,
         gas_oracle::GasPriceOracle,
         traits::RawTransactionForwarder,
-        EthBundle, FeeHistoryCache,
+        EthBundle, EthSimBundle, FeeHistoryCache,
     },
     AdminApi, DebugApi, EngineEthApi, EthApi, EthFilter, EthPubSub, EthSubscriptionIdProvider,
-    NetApi, OtterscanApi, RPCApi, RethApi, TraceApi, TxPoolApi, Web3Api,
+    NetApi, OtterscanApi, RPCApi, RethApi, RpcUsage, TraceApi, TxPoolApi, Web3Api,
 };
 use reth_rpc_api::servers::*;
+use reth_rpc_graphql::{GraphQlLayer, GraphQlSchema};
 use reth_rpc_layer::{AuthLayer, Claims, JwtAuthValidator, JwtSecret};
 use reth_tasks::{
     pool::{BlockingTaskGuard, BlockingTaskPool},
@@ -208,9 +222,28 @@ pub use jsonrpsee::server::ServerBuilder;
 pub use reth_ipc::server::{
     Builder as IpcServerBuilder, RpcServiceBuilder as IpcRpcServiceBuilder,
 };
-pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection};
+pub use reth_rpc_server_types::{constants, RethRpcModule, RpcModuleSelection, RpcRateLimitConfig};
 pub use tower::layer::util::{Identity, Stack};
 
+/// Rpc server access log.
+mod access_log;
+pub use access_log::RpcAccessLogConfig;
+
+/// Rpc server rate limits.
+mod rate_limit;
+pub use rate_limit::RATE_LIMIT_EXCEEDED_CODE;
+
+/// Rpc server usage accounting.
+mod usage;
+
+/// Rpc server per-namespace authentication.
+mod namespace_auth;
+pub use namespace_auth::{RpcNamespaceAuthConfig, UNAUTHORIZED_CODE};
+
+/// Rpc server middleware for other transports.
+mod middleware;
+pub use middleware::{RpcRequest, RpcRequestGuard, RpcRequestMiddleware};
+
 /// Auth server utilities.
 pub mod auth;
 
@@ -249,6 +282,8 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + TransactionSelectorProvider
+        + LogIndexProvider
         + Clone
         + Unpin
         + 'static,
@@ -440,6 +475,8 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + TransactionSelectorProvider
+        + LogIndexProvider
         + Clone
         + Unpin
         + 'static,
@@ -729,11 +766,12 @@ impl<Provider, Pool, Network, Tasks, Events, EvmConfig>
 impl<Provider: ChainSpecProvider, Pool, Network, Tasks, Events, EvmConfig>
     RethModuleRegistry<Provider, Pool, Network, Tasks, Events, EvmConfig>
 where
+    Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
 {
     /// Instantiates `AdminApi`
-    pub fn admin_api(&self) -> AdminApi<Network> {
-        AdminApi::new(self.network.clone(), self.provider.chain_spec())
+    pub fn admin_api(&self) -> AdminApi<Network, Pool> {
+        AdminApi::new(self.network.clone(), self.provider.chain_spec(), self.pool.clone())
     }
 
     /// Instantiates `Web3Api`
@@ -765,6 +803,8 @@ where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + TransactionSelectorProvider
+        + LogIndexProvider
         + Clone
         + Unpin
         + 'static,
@@ -930,11 +970,13 @@ where
                 self.modules
                     .entry(namespace)
                     .or_insert_with(|| match namespace {
-                        RethRpcModule::Admin => {
-                            AdminApi::new(self.network.clone(), self.provider.chain_spec())
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Admin => AdminApi::new(
+                            self.network.clone(),
+                            self.provider.chain_spec(),
+                            self.pool.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::Debug => DebugApi::new(
                             self.provider.clone(),
                             eth_api.clone(),
@@ -957,12 +999,15 @@ where
                             self.provider.clone(),
                             eth_api.clone(),
                             self.blocking_pool_guard.clone(),
+                            self.config.eth.max_traces_per_response,
                         )
                         .into_rpc()
                         .into(),
                         RethRpcModule::Web3 => Web3Api::new(self.network.clone()).into_rpc().into(),
                         RethRpcModule::Txpool => {
-                            TxPoolApi::new(self.pool.clone()).into_rpc().into()
+                            TxPoolApi::new(self.pool.clone(), self.config.eth.max_txpool_content)
+                                .into_rpc()
+                                .into()
                         }
                         RethRpcModule::Rpc => RPCApi::new(
                             namespaces
@@ -973,16 +1018,27 @@ where
                         .into_rpc()
                         .into(),
                         RethRpcModule::Ots => OtterscanApi::new(eth_api.clone()).into_rpc().into(),
-                        RethRpcModule::Reth => {
-                            RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
-                                .into_rpc()
-                                .into()
-                        }
+                        RethRpcModule::Reth => RethApi::new(
+                            self.provider.clone(),
+                            eth_api.clone(),
+                            self.events.clone(),
+                            Box::new(self.executor.clone()),
+                            self.config.eth.cancel_price_bump,
+                        )
+                        .into_rpc()
+                        .into(),
                         RethRpcModule::EthCallBundle => {
                             EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone())
                                 .into_rpc()
                                 .into()
                         }
+                        RethRpcModule::Mev => EthSimBundle::new(
+                            eth_api.clone(),
+                            self.provider.chain_spec(),
+                            self.blocking_pool_guard.clone(),
+                        )
+                        .into_rpc()
+                        .into(),
                     })
                     .clone()
             })
@@ -1061,6 +1117,15 @@ where
             self.evm_config.clone(),
             self.eth_raw_transaction_forwarder.clone(),
         );
+        if self.config.eth.call_cache_max_len > 0 {
+            api.set_eth_call_cache(EthCallCache::new(self.config.eth.call_cache_max_len));
+        }
+        if let Some(budget) = self.config.eth.precompile_gas_budget {
+            api.set_precompile_gas_budget(PrecompileGasBudget::new(budget));
+        }
+        if self.config.eth.revert_hints {
+            api.set_revert_hints(true);
+        }
         let filter = EthFilter::new(
             self.provider.clone(),
             self.pool.clone(),
@@ -1107,7 +1172,12 @@ where
     /// If called outside of the tokio runtime. See also [`Self::eth_api`]
     pub fn trace_api(&mut self) -> TraceApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>> {
         let eth = self.eth_handlers();
-        TraceApi::new(self.provider.clone(), eth.api, self.blocking_pool_guard.clone())
+        TraceApi::new(
+            self.provider.clone(),
+            eth.api,
+            self.blocking_pool_guard.clone(),
+            self.config.eth.max_traces_per_response,
+        )
     }
 
     /// Instantiates [`EthBundle`] Api
@@ -1120,6 +1190,16 @@ where
         EthBundle::new(eth_api, self.blocking_pool_guard.clone())
     }
 
+    /// Instantiates [`EthSimBundle`] Api
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn sim_bundle_api(&mut self) -> EthSimBundle<EthApi<Provider, Pool, Network, EvmConfig>> {
+        let eth_api = self.eth_api();
+        EthSimBundle::new(eth_api, self.provider.chain_spec(), self.blocking_pool_guard.clone())
+    }
+
     /// Instantiates `OtterscanApi`
     ///
     /// # Panics
@@ -1151,8 +1231,21 @@ where
     }
 
     /// Instantiates `RethApi`
-    pub fn reth_api(&self) -> RethApi<Provider> {
-        RethApi::new(self.provider.clone(), Box::new(self.executor.clone()))
+    ///
+    /// # Panics
+    ///
+    /// If called outside of the tokio runtime. See also [`Self::eth_api`]
+    pub fn reth_api(
+        &mut self,
+    ) -> RethApi<Provider, EthApi<Provider, Pool, Network, EvmConfig>, Events> {
+        let eth_api = self.eth_api();
+        RethApi::new(
+            self.provider.clone(),
+            eth_api,
+            self.events.clone(),
+            Box::new(self.executor.clone()),
+            self.config.eth.cancel_price_bump,
+        )
     }
 }
 
@@ -1187,6 +1280,16 @@ pub struct RpcServerConfig {
     ipc_endpoint: Option<String>,
     /// JWT secret for authentication
     jwt_secret: Option<JwtSecret>,
+    /// Access log for all configured transports
+    access_log: Option<RpcAccessLogConfig>,
+    /// Rate limits for all configured transports
+    rate_limits: Option<RpcRateLimitConfig>,
+    /// Usage accounting for all configured transports
+    usage: Option<RpcUsage>,
+    /// Authentication of selected namespaces of the http and ws servers
+    namespace_auth: Option<RpcNamespaceAuthConfig>,
+    /// GraphQL endpoint of the http server
+    graphql: Option<GraphQlLayer>,
 }
 
 // === impl RpcServerConfig ===
@@ -1304,6 +1407,48 @@ impl RpcServerConfig {
         self
     }
 
+    /// Configures the access log for all transports.
+    pub fn with_access_log(mut self, access_log: Option<RpcAccessLogConfig>) -> Self {
+        self.access_log = access_log;
+        self
+    }
+
+    /// Configures the rate limits for all transports.
+    ///
+    /// Over HTTP and WS, clients send their API key in the [`RPC_API_KEY_HEADER`] header.
+    ///
+    /// [`RPC_API_KEY_HEADER`]: reth_rpc_server_types::rate_limit::RPC_API_KEY_HEADER
+    pub fn with_rate_limits(mut self, rate_limits: Option<RpcRateLimitConfig>) -> Self {
+        self.rate_limits = rate_limits;
+        self
+    }
+
+    /// Configures the usage accounting for all transports.
+    ///
+    /// Requests are accounted to their API key if the key belongs to a tier of the rate limits,
+    /// see [`Self::with_rate_limits`], all other requests are accounted together.
+    pub fn with_usage(mut self, usage: Option<RpcUsage>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Configures the namespaces of the http and ws servers that require authentication.
+    ///
+    /// The ipc server is local and never requires authentication.
+    pub fn with_namespace_auth(mut self, namespace_auth: Option<RpcNamespaceAuthConfig>) -> Self {
+        self.namespace_auth = namespace_auth;
+        self
+    }
+
+    /// Serves the GraphQL schema at [`GRAPHQL_PATH`](reth_rpc_graphql::GRAPHQL_PATH) of the http
+    /// server.
+    ///
+    /// The CORS policy and authentication of the http server apply to the GraphQL queries.
+    pub fn with_graphql(mut self, schema: Option<GraphQlSchema>) -> Self {
+        self.graphql = schema.map(GraphQlLayer::new);
+        self
+    }
+
     /// Returns true if any server is configured.
     ///
     /// If no server is configured, no server will be launched on [`RpcServerConfig::start`].
@@ -1343,6 +1488,41 @@ impl RpcServerConfig {
         self.jwt_secret.map(|secret| AuthLayer::new(JwtAuthValidator::new(secret)))
     }
 
+    /// Creates the [`RpcApiKeyLayer`] if rate limits are configured
+    fn maybe_api_key_layer(&self) -> Option<RpcApiKeyLayer> {
+        self.rate_limits.as_ref().map(|_| RpcApiKeyLayer)
+    }
+
+    /// Creates the [`RpcCredentialsLayer`] if namespace authentication is configured
+    fn maybe_credentials_layer(&self) -> Option<RpcCredentialsLayer> {
+        self.namespace_auth.as_ref().map(RpcCredentialsLayer::new)
+    }
+
+    /// Creates the [`RpcNamespaceAuth`] layer, which does nothing if no namespace authentication
+    /// is configured
+    fn namespace_auth_layer(&self) -> RpcNamespaceAuth {
+        RpcNamespaceAuth::new(self.namespace_auth.as_ref())
+    }
+
+    /// Creates the [`RpcUsageLayer`] for the methods of the given module, which does nothing if no
+    /// usage accounting is configured
+    fn usage_layer(&self, module: Option<&RpcModule<()>>) -> RpcUsageLayer {
+        RpcUsageLayer::new(self.usage.as_ref(), self.rate_limits.as_ref(), module)
+    }
+
+    /// Creates the [`RpcRequestMiddleware`] of the gRPC gateway, which applies the configured
+    /// access log, namespace authentication, rate limits and usage accounting to its requests.
+    ///
+    /// The access log refers to the methods of the given module without allocating.
+    pub fn grpc_middleware(&self, module: Option<&RpcModule<()>>) -> RpcRequestMiddleware {
+        RpcRequestMiddleware::new(
+            RpcAccessLog::new(self.access_log.as_ref(), module, RpcTransport::Grpc),
+            self.namespace_auth.as_ref(),
+            RpcRateLimit::new(self.rate_limits.as_ref()),
+            self.usage_layer(module),
+        )
+    }
+
     /// Builds the ws and http server(s).
     ///
     /// If both are on the same port, they are combined into one server.
@@ -1390,17 +1570,29 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(cors)?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_api_key_layer())
+                        .option_layer(self.maybe_credentials_layer())
+                        .option_layer(self.graphql.clone()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules
-                            .http
-                            .as_ref()
-                            .or(modules.ws.as_ref())
-                            .map(RpcRequestMetrics::same_port)
-                            .unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules
+                                .http
+                                .as_ref()
+                                .or(modules.ws.as_ref())
+                                .map(RpcRequestMetrics::same_port)
+                                .unwrap_or_default(),
+                        )
+                        .layer(RpcAccessLog::new(
+                            self.access_log.as_ref(),
+                            modules.http.as_ref().or(modules.ws.as_ref()),
+                            RpcTransport::Http,
+                        ))
+                        .layer(self.namespace_auth_layer())
+                        .layer(RpcRateLimit::new(self.rate_limits.as_ref()))
+                        .layer(self.usage_layer(modules.http.as_ref().or(modules.ws.as_ref()))),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1427,11 +1619,21 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_api_key_layer())
+                        .option_layer(self.maybe_credentials_layer()),
                 )
                 .set_rpc_middleware(
                     RpcServiceBuilder::new()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default()),
+                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
+                        .layer(RpcAccessLog::new(
+                            self.access_log.as_ref(),
+                            modules.ws.as_ref(),
+                            RpcTransport::WebSocket,
+                        ))
+                        .layer(self.namespace_auth_layer())
+                        .layer(RpcRateLimit::new(self.rate_limits.as_ref()))
+                        .layer(self.usage_layer(modules.ws.as_ref())),
                 )
                 .build(ws_socket_addr)
                 .await
@@ -1450,12 +1652,24 @@ impl RpcServerConfig {
                 .set_http_middleware(
                     tower::ServiceBuilder::new()
                         .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(self.maybe_jwt_layer()),
+                        .option_layer(self.maybe_jwt_layer())
+                        .option_layer(self.maybe_api_key_layer())
+                        .option_layer(self.maybe_credentials_layer())
+                        .option_layer(self.graphql.clone()),
                 )
                 .set_rpc_middleware(
-                    RpcServiceBuilder::new().layer(
-                        modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                    ),
+                    RpcServiceBuilder::new()
+                        .layer(
+                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
+                        )
+                        .layer(RpcAccessLog::new(
+                            self.access_log.as_ref(),
+                            modules.http.as_ref(),
+                            RpcTransport::Http,
+                        ))
+                        .layer(self.namespace_auth_layer())
+                        .layer(RpcRateLimit::new(self.rate_limits.as_ref()))
+                        .layer(self.usage_layer(modules.http.as_ref())),
                 )
                 .build(http_socket_addr)
                 .await
@@ -1487,10 +1701,23 @@ impl RpcServerConfig {
 
         if let Some(builder) = self.ipc_server_config {
             let metrics = modules.ipc.as_ref().map(RpcRequestMetrics::ipc).unwrap_or_default();
+            let access_log = RpcAccessLog::new(
+                self.access_log.as_ref(),
+                modules.ipc.as_ref(),
+                RpcTransport::Ipc,
+            );
+            let rate_limit = RpcRateLimit::new(self.rate_limits.as_ref());
+            let usage = self.usage_layer(modules.ipc.as_ref());
             let ipc_path =
                 self.ipc_endpoint.unwrap_or_else(|| constants::DEFAULT_IPC_ENDPOINT.into());
             let ipc = builder
-                .set_rpc_middleware(IpcRpcServiceBuilder::new().layer(metrics))
+                .set_rpc_middleware(
+                    IpcRpcServiceBuilder::new()
+                        .layer(metrics)
+                        .layer(access_log)
+                        .layer(rate_limit)
+                        .layer(usage),
+                )
                 .build(ipc_path);
             server.ipc = Some(ipc);
         }
@@ -1654,6 +1881,11 @@ impl TransportRpcModules {
         &self.config
     }
 
+    /// Returns the configured http methods, if the http transport is configured.
+    pub const fn http_module(&self) -> Option<&RpcModule<()>> {
+        self.http.as_ref()
+    }
+
     /// Merge the given [Methods] in the configured http methods.
     ///
     /// Fails if any of the methods in other is present already.
@@ -1704,6 +1936,31 @@ impl TransportRpcModules {
         Ok(())
     }
 
+    /// Merge the given [Methods] in the configured methods of all transports that have the given
+    /// module configured.
+    ///
+    /// Fails if any of the methods in other is present already.
+    pub fn merge_if_module_configured(
+        &mut self,
+        module: RethRpcModule,
+        other: impl Into<Methods>,
+    ) -> Result<(), RegisterMethodError> {
+        let other = other.into();
+        let configured = |selection: Option<&RpcModuleSelection>| {
+            selection.map_or(false, |selection| selection.contains(&module))
+        };
+        if configured(self.config.http()) {
+            self.merge_http(other.clone())?;
+        }
+        if configured(self.config.ws()) {
+            self.merge_ws(other.clone())?;
+        }
+        if configured(self.config.ipc()) {
+            self.merge_ipc(other)?;
+        }
+        Ok(())
+    }
+
     /// Convenience function for starting a server
     pub async fn start_server(self, builder: RpcServerConfig) -> Result<RpcServerHandle, RpcError> {
         builder.start(self).await
@@ -1726,10 +1983,25 @@ struct WsHttpServer {
 // Define the type alias with detailed type complexity
 type WsHttpServerKind = Server<
     Stack<
-        tower::util::Either<AuthLayer<JwtAuthValidator>, Identity>,
-        Stack<tower::util::Either<CorsLayer, Identity>, Identity>,
+        tower::util::Either<RpcCredentialsLayer, Identity>,
+        Stack<
+            tower::util::Either<RpcApiKeyLayer, Identity>,
+            Stack<
+                tower::util::Either<AuthLayer<JwtAuthValidator>, Identity>,
+                Stack<tower::util::Either<CorsLayer, Identity>, Identity>,
+            >,
+        >,
+    >,
+    RpcMiddleware,
+>;
+
+/// The RPC middleware of all servers.
+type RpcMiddleware = Stack<
+    RpcUsageLayer,
+    Stack<
+        RpcRateLimit,
+        Stack<RpcNamespaceAuth, Stack<RpcAccessLog, Stack<RpcRequestMetrics, Identity>>>,
     >,
-    Stack<RpcRequestMetrics, Identity>,
 >;
 
 /// Enum for holding the http and ws servers in all possible combinations.
@@ -1793,7 +2065,7 @@ pub struct RpcServer {
     /// Configured ws,http servers
     ws_http: WsHttpServer,
     /// ipc server
-    ipc: Option<IpcServer<Identity, Stack<RpcRequestMetrics, Identity>>>,
+    ipc: Option<IpcServer<Identity, RpcMiddleware>>,
 }
 
 // === impl RpcServer ===
@@ -2077,6 +2349,7 @@ mod tests {
                 "rpc" => RethRpcModule::Rpc,
                 "ots" => RethRpcModule::Ots,
                 "reth" => RethRpcModule::Reth,
+                "mev" => RethRpcModule::Mev,
             );
     }
 
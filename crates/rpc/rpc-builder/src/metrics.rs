@@ -177,8 +177,8 @@ impl<F: Future<Output = MethodResponse>> Future for MeteredRequestFuture<F> {
 pub(crate) enum RpcTransport {
     Http,
     WebSocket,
-    #[allow(unused)]
     Ipc,
+    Grpc,
 }
 
 impl RpcTransport {
@@ -188,6 +188,7 @@ impl RpcTransport {
             Self::Http => "http",
             Self::WebSocket => "ws",
             Self::Ipc => "ipc",
+            Self::Grpc => "grpc",
         }
     }
 
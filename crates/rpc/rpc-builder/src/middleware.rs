@@ -0,0 +1,138 @@
+//! The middleware of the RPC servers for transports that aren't served by jsonrpsee.
+
+use crate::{
+    access_log::{AccessLogEntry, RpcAccessLog},
+    namespace_auth::{RpcNamespaceAuthConfig, UNAUTHORIZED_CODE},
+    rate_limit::{RpcRateLimit, RATE_LIMIT_EXCEEDED_CODE},
+    usage::{RpcUsageLayer, UsageEntry},
+};
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use std::{net::IpAddr, sync::Arc};
+
+/// The access log, namespace authentication, rate limits and usage accounting of the RPC servers,
+/// for transports that dispatch their requests without jsonrpsee, like the gRPC gateway.
+///
+/// Created with [`RpcServerConfig::grpc_middleware`](crate::RpcServerConfig::grpc_middleware).
+/// Every request is started with [`RpcRequestMiddleware::start`], which applies the same checks, in
+/// the same order, as the middleware of the HTTP server.
+#[derive(Debug, Clone, Default)]
+pub struct RpcRequestMiddleware {
+    access_log: RpcAccessLog,
+    namespace_auth: Option<Arc<RpcNamespaceAuthConfig>>,
+    rate_limit: RpcRateLimit,
+    usage: RpcUsageLayer,
+}
+
+impl RpcRequestMiddleware {
+    /// Creates a new middleware with the given layers.
+    pub(crate) fn new(
+        access_log: RpcAccessLog,
+        namespace_auth: Option<&RpcNamespaceAuthConfig>,
+        rate_limit: RpcRateLimit,
+        usage: RpcUsageLayer,
+    ) -> Self {
+        Self {
+            access_log,
+            namespace_auth: namespace_auth.map(|config| Arc::new(config.clone())),
+            rate_limit,
+            usage,
+        }
+    }
+
+    /// Starts the request, or returns the error to respond with if it's rejected.
+    ///
+    /// Requests to namespaces that require authentication are rejected with
+    /// [`UNAUTHORIZED_CODE`] unless they have valid credentials, and requests that exceed the rate
+    /// limits with [`RATE_LIMIT_EXCEEDED_CODE`]. The returned guard must be finished with the
+    /// response to the request.
+    pub fn start(&self, request: RpcRequest<'_>) -> Result<RpcRequestGuard, ErrorObjectOwned> {
+        let access_log = self.access_log.entry(request.method, request.params);
+        let reject = |code, message| {
+            if let Some(entry) = access_log {
+                entry.log(0, Some(code));
+            }
+            Err(ErrorObject::owned(code, message, None::<()>))
+        };
+
+        let unauthorized = self.namespace_auth.as_ref().is_some_and(|config| {
+            config.requires_auth(request.method) &&
+                !request.authorization.is_some_and(|value| config.is_authorized(value))
+        });
+        if unauthorized {
+            return reject(UNAUTHORIZED_CODE, "unauthorized")
+        }
+        if !self.rate_limit.check_remote(request.remote_ip, request.method, request.api_key) {
+            return reject(RATE_LIMIT_EXCEEDED_CODE, "rate limit exceeded")
+        }
+
+        Ok(RpcRequestGuard { access_log, usage: self.usage.entry(request.method, request.api_key) })
+    }
+}
+
+/// A request to an RPC method, see [`RpcRequestMiddleware::start`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RpcRequest<'a> {
+    /// The JSON-RPC name of the method, e.g. `eth_call`.
+    pub method: &'a str,
+    /// The JSON encoded params, if any.
+    pub params: Option<&'a str>,
+    /// The API key of the request, from the
+    /// [`RPC_API_KEY_HEADER`](reth_rpc_server_types::rate_limit::RPC_API_KEY_HEADER) header.
+    pub api_key: Option<&'a str>,
+    /// The value of the `Authorization` header of the request.
+    pub authorization: Option<&'a str>,
+    /// The address of the client, the connection quotas of the rate limits apply to all requests
+    /// of an address.
+    pub remote_ip: Option<IpAddr>,
+}
+
+/// A started request, that is accounted and logged once it's finished.
+#[derive(Debug)]
+#[must_use = "the request is only accounted once it's finished"]
+pub struct RpcRequestGuard {
+    access_log: Option<AccessLogEntry>,
+    usage: Option<UsageEntry>,
+}
+
+impl RpcRequestGuard {
+    /// Finishes the request with the size of its response and its error code, if it failed.
+    pub fn finish(self, response_size: usize, error_code: Option<i32>) {
+        if let Some(entry) = self.access_log {
+            entry.log(response_size, error_code);
+        }
+        if let Some(entry) = self.usage {
+            entry.record(response_size as u64);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::RpcTransport;
+    use reth_rpc_server_types::RpcRateLimitConfig;
+
+    #[test]
+    fn rejects_requests() {
+        let rate_limits: RpcRateLimitConfig =
+            "default.methods.eth_call = { rate = 1 }".parse().unwrap();
+        let namespace_auth = RpcNamespaceAuthConfig::new(["admin"]).with_tokens(["s3cr3t"]);
+        let middleware = RpcRequestMiddleware::new(
+            RpcAccessLog::new(None, None, RpcTransport::Grpc),
+            Some(&namespace_auth),
+            RpcRateLimit::new(Some(&rate_limits)),
+            RpcUsageLayer::default(),
+        );
+
+        let request = RpcRequest { method: "admin_peers", ..Default::default() };
+        let err = middleware.start(request).unwrap_err();
+        assert_eq!(err.code(), UNAUTHORIZED_CODE);
+        let request = RpcRequest { authorization: Some("Bearer s3cr3t"), ..request };
+        middleware.start(request).unwrap().finish(0, None);
+
+        let request = RpcRequest { method: "eth_call", ..Default::default() };
+        middleware.start(request).unwrap().finish(0, None);
+        let err = middleware.start(request).unwrap_err();
+        assert_eq!(err.code(), RATE_LIMIT_EXCEEDED_CODE);
+    }
+}
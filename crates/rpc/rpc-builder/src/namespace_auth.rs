@@ -0,0 +1,259 @@
+//! Authentication of the requests to selected namespaces of the RPC servers.
+
+use http::header::AUTHORIZATION;
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use reth_rpc_layer::JwtSecret;
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// The error code of requests to a namespace that requires authentication without valid
+/// credentials, a code of the implementation defined server error range.
+pub const UNAUTHORIZED_CODE: i32 = -32010;
+
+/// Configuration of the namespaces of the HTTP and WS servers that require authentication.
+///
+/// Requests to these namespaces must have an `Authorization: Bearer <credential>` header, where
+/// the credential is either a JWT signed with the configured secret or one of the configured
+/// tokens. Requests to all other namespaces are served without credentials.
+///
+/// The credentials of a WS connection are checked once, when the connection is established.
+#[derive(Debug, Clone, Default)]
+pub struct RpcNamespaceAuthConfig {
+    /// The namespaces that require authentication, e.g. `admin`.
+    namespaces: HashSet<String>,
+    /// The secret of the accepted JWTs.
+    jwt_secret: Option<JwtSecret>,
+    /// The accepted static tokens.
+    tokens: HashSet<String>,
+}
+
+impl RpcNamespaceAuthConfig {
+    /// Creates a new config that requires authentication for the given namespaces.
+    ///
+    /// No credential is accepted until a JWT secret or tokens are configured.
+    pub fn new(namespaces: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { namespaces: namespaces.into_iter().map(Into::into).collect(), ..Default::default() }
+    }
+
+    /// Accepts JWTs signed with the given secret.
+    pub const fn with_jwt_secret(mut self, jwt_secret: Option<JwtSecret>) -> Self {
+        self.jwt_secret = jwt_secret;
+        self
+    }
+
+    /// Accepts the given static tokens.
+    pub fn with_tokens(mut self, tokens: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.tokens = tokens.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Returns the namespaces that require authentication.
+    pub const fn namespaces(&self) -> &HashSet<String> {
+        &self.namespaces
+    }
+
+    /// Returns true if requests to the method require authentication.
+    pub(crate) fn requires_auth(&self, method: &str) -> bool {
+        method.split_once('_').is_some_and(|(namespace, _)| self.namespaces.contains(namespace))
+    }
+
+    /// Returns true if the bearer credential is a valid JWT or one of the tokens.
+    fn is_valid(&self, credential: &str) -> bool {
+        self.tokens.contains(credential) ||
+            self.jwt_secret.is_some_and(|secret| secret.validate(credential).is_ok())
+    }
+
+    /// Returns true if the value of an `Authorization` header is a bearer credential that is
+    /// valid.
+    pub(crate) fn is_authorized(&self, authorization: &str) -> bool {
+        authorization
+            .strip_prefix("Bearer ")
+            .is_some_and(|credential| self.is_valid(credential.trim()))
+    }
+}
+
+/// Marks a request whose `Authorization` header has valid credentials, set by
+/// [`RpcCredentialsLayer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RpcAuthorized;
+
+/// A [`Layer`] that checks the credentials of HTTP requests, and of the requests that establish
+/// WS connections, and marks the requests with valid credentials as [`RpcAuthorized`].
+///
+/// Requests without valid credentials are passed on unmarked, so that they can still call the
+/// namespaces that don't require authentication.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcCredentialsLayer {
+    config: Arc<RpcNamespaceAuthConfig>,
+}
+
+impl RpcCredentialsLayer {
+    /// Creates a new layer that checks the credentials against the config.
+    pub(crate) fn new(config: &RpcNamespaceAuthConfig) -> Self {
+        Self { config: Arc::new(config.clone()) }
+    }
+}
+
+impl<S> Layer<S> for RpcCredentialsLayer {
+    type Service = RpcCredentialsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcCredentialsService { inner, config: self.config.clone() }
+    }
+}
+
+/// An HTTP middleware that marks the requests with valid credentials.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcCredentialsService<S> {
+    inner: S,
+    config: Arc<RpcNamespaceAuthConfig>,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for RpcCredentialsService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let authorized = req
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| self.config.is_authorized(value));
+        if authorized {
+            req.extensions_mut().insert(RpcAuthorized);
+        }
+        self.inner.call(req)
+    }
+}
+
+/// A [`Layer`] that rejects the requests to the namespaces of the [`RpcNamespaceAuthConfig`]
+/// that were not marked as [`RpcAuthorized`] by the [`RpcCredentialsLayer`].
+///
+/// Does nothing if no config is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RpcNamespaceAuth {
+    config: Option<Arc<RpcNamespaceAuthConfig>>,
+}
+
+impl RpcNamespaceAuth {
+    /// Creates a new namespace auth layer, disabled if `config` is `None`.
+    pub(crate) fn new(config: Option<&RpcNamespaceAuthConfig>) -> Self {
+        Self { config: config.map(|config| Arc::new(config.clone())) }
+    }
+}
+
+impl<S> Layer<S> for RpcNamespaceAuth {
+    type Service = RpcNamespaceAuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcNamespaceAuthService { inner, config: self.config.clone() }
+    }
+}
+
+/// A [`RpcServiceT`] middleware that rejects unauthorized requests to protected namespaces.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcNamespaceAuthService<S> {
+    inner: S,
+    config: Option<Arc<RpcNamespaceAuthConfig>>,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcNamespaceAuthService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = NamespaceAuthFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let unauthorized = self.config.as_ref().is_some_and(|config| {
+            config.requires_auth(req.method_name()) &&
+                req.extensions().get::<RpcAuthorized>().is_none()
+        });
+        if !unauthorized {
+            return NamespaceAuthFuture::Inner { fut: self.inner.call(req) }
+        }
+
+        let error = ErrorObject::owned(UNAUTHORIZED_CODE, "unauthorized", None::<()>);
+        NamespaceAuthFuture::Unauthorized { response: Some(MethodResponse::error(req.id(), error)) }
+    }
+}
+
+/// Response future of a request that is either rejected or passed on.
+#[pin_project::pin_project(project = NamespaceAuthFutureProj)]
+pub(crate) enum NamespaceAuthFuture<F> {
+    /// The request is authorized.
+    Inner {
+        #[pin]
+        fut: F,
+    },
+    /// The request is missing valid credentials.
+    Unauthorized { response: Option<MethodResponse> },
+}
+
+impl<F> std::fmt::Debug for NamespaceAuthFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("NamespaceAuthFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for NamespaceAuthFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            NamespaceAuthFutureProj::Inner { fut } => fut.poll(cx),
+            NamespaceAuthFutureProj::Unauthorized { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_layer::Claims;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn namespace_auth_config() {
+        let secret = JwtSecret::random();
+        let config = RpcNamespaceAuthConfig::new(["admin", "debug"])
+            .with_jwt_secret(Some(secret))
+            .with_tokens(["s3cr3t"]);
+
+        assert!(config.requires_auth("admin_peers"));
+        assert!(config.requires_auth("debug_traceTransaction"));
+        assert!(!config.requires_auth("eth_call"));
+        assert!(!config.requires_auth("admin"));
+
+        let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let jwt = secret.encode(&Claims { iat, exp: None }).unwrap();
+        assert!(config.is_valid(&jwt));
+        assert!(config.is_valid("s3cr3t"));
+        assert!(!config.is_valid("secret"));
+        assert!(!config.is_valid(&JwtSecret::random().encode(&Claims { iat, exp: None }).unwrap()));
+        assert!(config.is_authorized("Bearer s3cr3t"));
+        assert!(!config.is_authorized("s3cr3t"));
+
+        // without credentials nothing is valid
+        assert!(!RpcNamespaceAuthConfig::new(["admin"]).is_valid(""));
+    }
+}
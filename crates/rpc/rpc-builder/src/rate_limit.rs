@@ -0,0 +1,375 @@
+//! Rate limiting of the requests of the RPC servers.
+
+use jsonrpsee::{
+    server::middleware::rpc::RpcServiceT,
+    types::{ErrorObject, Request},
+    MethodResponse,
+};
+use reth_rpc_server_types::{
+    rate_limit::{RateLimitQuota, RateLimitTier, RPC_API_KEY_HEADER},
+    RpcRateLimitConfig,
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    net::IpAddr,
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+use tower::Layer;
+
+/// The error code of requests that exceed a rate limit, the "limit exceeded" code of EIP-1474.
+pub const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// The number of remote addresses whose buckets are kept before the idle ones are removed.
+const MAX_REMOTES: usize = 10_000;
+
+/// The time after which the buckets of a remote address without requests are removed, once there
+/// are [`MAX_REMOTES`] remote addresses.
+const REMOTE_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The API key of a request, read from the [`RPC_API_KEY_HEADER`] header by [`RpcApiKeyLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RpcApiKey(pub(crate) String);
+
+/// The token buckets of the HTTP connection of a request, added to its extensions by
+/// [`RpcApiKeyLayer`].
+#[derive(Debug, Clone)]
+struct RpcConnection(Arc<ClientBuckets>);
+
+/// A [`Layer`] that reads the API key of HTTP requests from the [`RPC_API_KEY_HEADER`] header, so
+/// that the requests are rate limited according to the tier of the key.
+///
+/// The layer is applied once per connection, so it also identifies the connection of the
+/// requests: the requests of a connection without a known API key share its quotas.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct RpcApiKeyLayer;
+
+impl<S> Layer<S> for RpcApiKeyLayer {
+    type Service = RpcApiKeyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcApiKeyService { inner, connection: RpcConnection(Default::default()) }
+    }
+}
+
+/// An HTTP middleware that adds the API key and the connection of a request to its extensions.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcApiKeyService<S> {
+    inner: S,
+    connection: RpcConnection,
+}
+
+impl<S, B> tower::Service<http::Request<B>> for RpcApiKeyService<S>
+where
+    S: tower::Service<http::Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<B>) -> Self::Future {
+        let api_key = req
+            .headers()
+            .get(RPC_API_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(|key| RpcApiKey(key.to_string()));
+        if let Some(api_key) = api_key {
+            req.extensions_mut().insert(api_key);
+        }
+        req.extensions_mut().insert(self.connection.clone());
+        self.inner.call(req)
+    }
+}
+
+/// A token bucket that refills at the rate of its quota.
+#[derive(Debug)]
+struct TokenBucket {
+    quota: RateLimitQuota,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    /// Creates a full bucket.
+    fn new(quota: RateLimitQuota, now: Instant) -> Self {
+        Self { quota, tokens: quota.burst() as f64, updated_at: now }
+    }
+
+    /// Takes a token from the bucket, returns false if it's empty.
+    fn try_take(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.quota.rate as f64).min(self.quota.burst() as f64);
+        self.updated_at = now;
+        if self.tokens < 1.0 {
+            return false
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+/// The token buckets of a client: an API key, or a connection or remote address without a known
+/// API key.
+#[derive(Debug, Default)]
+struct ClientBuckets(Mutex<ClientBucketsInner>);
+
+#[derive(Debug, Default)]
+struct ClientBucketsInner {
+    /// The bucket of the connection quota.
+    connection: Option<TokenBucket>,
+    /// The buckets of the method quotas, by method.
+    methods: HashMap<String, TokenBucket>,
+    /// When the client last made a request.
+    last_request: Option<Instant>,
+}
+
+impl ClientBuckets {
+    /// Returns true if a request to the method is within the quotas of the tier, and takes a token
+    /// from the buckets of the quotas.
+    fn try_take(&self, tier: &RateLimitTier, method: &str, now: Instant) -> bool {
+        let mut buckets = lock(&self.0);
+        buckets.last_request = Some(now);
+
+        if let Some(quota) = tier.connection {
+            let bucket = buckets.connection.get_or_insert_with(|| TokenBucket::new(quota, now));
+            if !bucket.try_take(now) {
+                return false
+            }
+        }
+        if let Some((method, quota)) = tier.methods.get_key_value(method) {
+            let bucket = match buckets.methods.get_mut(method.as_str()) {
+                Some(bucket) => bucket,
+                None => buckets
+                    .methods
+                    .entry(method.clone())
+                    .or_insert_with(|| TokenBucket::new(*quota, now)),
+            };
+            if !bucket.try_take(now) {
+                return false
+            }
+        }
+        true
+    }
+
+    /// Returns true if the client made no request since the given instant.
+    fn is_idle_since(&self, instant: Instant) -> bool {
+        lock(&self.0).last_request.map_or(true, |last_request| last_request < instant)
+    }
+}
+
+/// A [`Layer`] that rate limits the requests of an RPC server, see [`RpcRateLimitConfig`].
+///
+/// Does nothing if no config is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RpcRateLimit {
+    inner: Option<Arc<RpcRateLimitInner>>,
+}
+
+impl RpcRateLimit {
+    /// Creates a new rate limit layer, disabled if `config` is `None`.
+    pub(crate) fn new(config: Option<&RpcRateLimitConfig>) -> Self {
+        Self {
+            inner: config.map(|config| {
+                Arc::new(RpcRateLimitInner {
+                    config: config.clone(),
+                    api_keys: Default::default(),
+                    remotes: Default::default(),
+                })
+            }),
+        }
+    }
+
+    /// Returns true if a request of the connection to the method is within the quotas of the tier
+    /// of the API key.
+    ///
+    /// Requests with a known API key are limited by the quotas of the key, across all
+    /// connections. All other requests are limited by the quotas of their connection.
+    fn check(&self, connection: &ClientBuckets, method: &str, api_key: Option<&str>) -> bool {
+        let Some(inner) = &self.inner else { return true };
+        let (tier, api_key) = inner.config.tier(api_key);
+        let now = Instant::now();
+
+        match api_key {
+            Some(api_key) => {
+                // the API keys are known, so their buckets are bounded by the config
+                let buckets = lock(&inner.api_keys).entry(api_key.to_string()).or_default().clone();
+                buckets.try_take(tier, method, now)
+            }
+            None => connection.try_take(tier, method, now),
+        }
+    }
+
+    /// Returns true if a request from the remote address to the method is within the quotas of
+    /// the tier of the API key.
+    ///
+    /// This is used by transports without long-lived connections, where the quotas of requests
+    /// without a known API key apply to all requests of the remote address.
+    pub(crate) fn check_remote(
+        &self,
+        remote: Option<IpAddr>,
+        method: &str,
+        api_key: Option<&str>,
+    ) -> bool {
+        let Some(inner) = &self.inner else { return true };
+        let connection = {
+            let mut remotes = lock(&inner.remotes);
+            if remotes.len() >= MAX_REMOTES {
+                // the buckets of remotes that are idle for longer than their bursts take to
+                // refill are full again, so recreating them doesn't change their quotas
+                let idle_since = Instant::now() - REMOTE_IDLE_TIMEOUT;
+                remotes.retain(|_, buckets| !buckets.is_idle_since(idle_since));
+            }
+            remotes.entry(remote).or_default().clone()
+        };
+        self.check(&connection, method, api_key)
+    }
+}
+
+impl<S> Layer<S> for RpcRateLimit {
+    type Service = RpcRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        // the layer is applied once per ws and ipc connection, but once per request of http
+        // connections, whose buckets are added to the request by the `RpcApiKeyLayer`
+        RpcRateLimitService { inner, limit: self.clone(), connection: Default::default() }
+    }
+}
+
+#[derive(Debug)]
+struct RpcRateLimitInner {
+    config: RpcRateLimitConfig,
+    /// The buckets of the known API keys, shared by all connections.
+    api_keys: Mutex<HashMap<String, Arc<ClientBuckets>>>,
+    /// The buckets of the remote addresses, see [`RpcRateLimit::check_remote`].
+    remotes: Mutex<HashMap<Option<IpAddr>, Arc<ClientBuckets>>>,
+}
+
+/// A [`RpcServiceT`] middleware that rejects the requests of a connection that exceed the rate
+/// limits.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcRateLimitService<S> {
+    inner: S,
+    limit: RpcRateLimit,
+    /// The buckets of this connection.
+    connection: Arc<ClientBuckets>,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcRateLimitService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = RateLimitFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let api_key = req.extensions().get::<RpcApiKey>().map(|key| key.0.as_str());
+        let connection = req
+            .extensions()
+            .get::<RpcConnection>()
+            .map_or(&*self.connection, |connection| &*connection.0);
+        if self.limit.check(connection, req.method_name(), api_key) {
+            return RateLimitFuture::Inner { fut: self.inner.call(req) }
+        }
+
+        let error = ErrorObject::owned(RATE_LIMIT_EXCEEDED_CODE, "rate limit exceeded", None::<()>);
+        RateLimitFuture::Limited { response: Some(MethodResponse::error(req.id(), error)) }
+    }
+}
+
+/// Response future of a request that is either rate limited or passed on.
+#[pin_project::pin_project(project = RateLimitFutureProj)]
+pub(crate) enum RateLimitFuture<F> {
+    /// The request is within the rate limits.
+    Inner {
+        #[pin]
+        fut: F,
+    },
+    /// The request exceeded the rate limits.
+    Limited { response: Option<MethodResponse> },
+}
+
+impl<F> std::fmt::Debug for RateLimitFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("RateLimitFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for RateLimitFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.project() {
+            RateLimitFutureProj::Inner { fut } => fut.poll(cx),
+            RateLimitFutureProj::Limited { response } => {
+                Poll::Ready(response.take().expect("polled after completion"))
+            }
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(RateLimitQuota::new(2).with_burst(3), now);
+        assert!(bucket.try_take(now));
+        assert!(bucket.try_take(now));
+        assert!(bucket.try_take(now));
+        assert!(!bucket.try_take(now));
+
+        // refills at 2 tokens per second, up to the burst
+        assert!(bucket.try_take(now + Duration::from_millis(500)));
+        assert!(!bucket.try_take(now + Duration::from_millis(500)));
+        assert!(bucket.try_take(now + Duration::from_secs(10)));
+        assert_eq!(bucket.tokens, 2.0);
+    }
+
+    #[test]
+    fn rate_limit_tiers() {
+        let config: RpcRateLimitConfig = r#"
+            default.methods.debug_traceBlockByNumber = { rate = 1 }
+            tiers.premium.api_keys = ["a", "b"]
+            tiers.premium.connection = { rate = 2 }
+        "#
+        .parse()
+        .unwrap();
+        let limit = RpcRateLimit::new(Some(&config));
+        let connection = ClientBuckets::default();
+
+        // requests without a known key are limited by the quotas of their connection
+        assert!(limit.check(&connection, "debug_traceBlockByNumber", None));
+        assert!(!limit.check(&connection, "debug_traceBlockByNumber", Some("unknown")));
+        assert!(limit.check(&connection, "eth_call", None));
+        assert!(limit.check(&ClientBuckets::default(), "debug_traceBlockByNumber", None));
+
+        // every API key of a tier has its own quota, across all connections
+        assert!(limit.check(&connection, "debug_traceBlockByNumber", Some("a")));
+        assert!(limit.check(&connection, "eth_call", Some("a")));
+        assert!(!limit.check(&ClientBuckets::default(), "eth_call", Some("a")));
+        assert!(limit.check(&connection, "eth_call", Some("b")));
+
+        // or per remote address
+        let remote = Some(IpAddr::from([127, 0, 0, 1]));
+        assert!(limit.check_remote(remote, "debug_traceBlockByNumber", None));
+        assert!(!limit.check_remote(remote, "debug_traceBlockByNumber", None));
+        let other = Some(IpAddr::from([127, 0, 0, 2]));
+        assert!(limit.check_remote(other, "debug_traceBlockByNumber", None));
+        assert!(limit.check_remote(other, "eth_call", Some("b")));
+        assert!(!limit.check_remote(remote, "eth_call", Some("b")));
+    }
+}
@@ -0,0 +1,177 @@
+//! Usage accounting of the requests of the RPC servers.
+
+use crate::rate_limit::RpcApiKey;
+use jsonrpsee::{server::middleware::rpc::RpcServiceT, types::Request, MethodResponse, RpcModule};
+use reth_rpc::RpcUsage;
+use reth_rpc_server_types::RpcRateLimitConfig;
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::Layer;
+
+/// Method accounted for requests to methods that aren't registered.
+const UNKNOWN_METHOD: &str = "unknown";
+
+/// A [`Layer`] that accounts the requests of an RPC server in the [`RpcUsage`] table.
+///
+/// Requests are accounted to their API key if it belongs to a tier of the [`RpcRateLimitConfig`],
+/// all other requests are accounted together. Requests to methods that aren't registered are
+/// accounted as [`UNKNOWN_METHOD`], so the size of the table doesn't depend on client input.
+///
+/// Does nothing if no usage table is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RpcUsageLayer {
+    inner: Option<Arc<RpcUsageLayerInner>>,
+}
+
+impl RpcUsageLayer {
+    /// Creates a new usage layer for the methods of the given module, disabled if `usage` is
+    /// `None`.
+    pub(crate) fn new(
+        usage: Option<&RpcUsage>,
+        rate_limits: Option<&RpcRateLimitConfig>,
+        module: Option<&RpcModule<()>>,
+    ) -> Self {
+        Self {
+            inner: usage.map(|usage| {
+                Arc::new(RpcUsageLayerInner {
+                    usage: usage.clone(),
+                    rate_limits: rate_limits.cloned(),
+                    methods: module
+                        .map(|module| module.method_names().collect())
+                        .unwrap_or_default(),
+                })
+            }),
+        }
+    }
+
+    /// Returns the entry to account once the request with the given method and API key
+    /// completes.
+    pub(crate) fn entry(&self, method: &str, api_key: Option<&str>) -> Option<UsageEntry> {
+        let inner = self.inner.as_ref()?;
+        // only known API keys are accounted on their own
+        let api_key = inner.rate_limits.as_ref().and_then(|config| config.tier(api_key).1);
+        Some(UsageEntry {
+            usage: inner.usage.clone(),
+            api_key: api_key.map(ToString::to_string),
+            method: inner.methods.get(method).copied().unwrap_or(UNKNOWN_METHOD),
+        })
+    }
+}
+
+impl<S> Layer<S> for RpcUsageLayer {
+    type Service = RpcUsageService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcUsageService { inner, layer: self.clone() }
+    }
+}
+
+#[derive(Debug)]
+struct RpcUsageLayerInner {
+    usage: RpcUsage,
+    /// The tiers of the known API keys.
+    rate_limits: Option<RpcRateLimitConfig>,
+    /// The registered methods.
+    methods: HashSet<&'static str>,
+}
+
+/// A [`RpcServiceT`] middleware that accounts the requests of an RPC server.
+#[derive(Debug, Clone)]
+pub(crate) struct RpcUsageService<S> {
+    inner: S,
+    layer: RpcUsageLayer,
+}
+
+impl<'a, S> RpcServiceT<'a> for RpcUsageService<S>
+where
+    S: RpcServiceT<'a> + Send + Sync + Clone + 'static,
+{
+    type Future = UsageFuture<S::Future>;
+
+    fn call(&self, req: Request<'a>) -> Self::Future {
+        let api_key = req.extensions().get::<RpcApiKey>().map(|key| key.0.as_str());
+        let entry = self.layer.entry(req.method_name(), api_key);
+        UsageFuture { fut: self.inner.call(req), entry }
+    }
+}
+
+/// A request to account once its response is ready.
+#[derive(Debug)]
+pub(crate) struct UsageEntry {
+    usage: RpcUsage,
+    api_key: Option<String>,
+    method: &'static str,
+}
+
+impl UsageEntry {
+    /// Accounts the request with the size of its response.
+    pub(crate) fn record(self, egress_bytes: u64) {
+        self.usage.record(self.api_key.as_deref(), self.method, egress_bytes);
+    }
+}
+
+/// Response future that accounts a single request/response pair.
+#[pin_project::pin_project]
+pub(crate) struct UsageFuture<F> {
+    #[pin]
+    fut: F,
+    entry: Option<UsageEntry>,
+}
+
+impl<F> std::fmt::Debug for UsageFuture<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("UsageFuture")
+    }
+}
+
+impl<F: Future<Output = MethodResponse>> Future for UsageFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+
+        let res = this.fut.poll(cx);
+        if let Poll::Ready(resp) = &res {
+            if let Some(entry) = this.entry.take() {
+                entry.record(resp.as_result().len() as u64);
+            }
+        }
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn usage_entry() {
+        let mut module = RpcModule::new(());
+        module.register_method("eth_call", |_, _, _| "0x").unwrap();
+
+        let usage = RpcUsage::new([], None).unwrap();
+        let layer = RpcUsageLayer::new(None, None, Some(&module));
+        assert!(layer.entry("eth_call", Some("a")).is_none());
+
+        // without rate limits, no API key is known
+        let entry = RpcUsageLayer::new(Some(&usage), None, Some(&module))
+            .entry("eth_call", Some("a"))
+            .unwrap();
+        assert_eq!(entry.api_key, None);
+
+        let config: RpcRateLimitConfig = r#"tiers.premium.api_keys = ["a"]"#.parse().unwrap();
+        let layer = RpcUsageLayer::new(Some(&usage), Some(&config), Some(&module));
+        assert_eq!(layer.entry("eth_call", Some("a")).unwrap().api_key.as_deref(), Some("a"));
+        assert_eq!(layer.entry("eth_call", Some("b")).unwrap().api_key, None);
+
+        // methods that aren't registered are accounted together
+        assert_eq!(layer.entry("eth_call", None).unwrap().method, "eth_call");
+        assert_eq!(layer.entry("eth_foo", None).unwrap().method, UNKNOWN_METHOD);
+        assert_eq!(layer.entry(&"x".repeat(64), None).unwrap().method, UNKNOWN_METHOD);
+    }
+}
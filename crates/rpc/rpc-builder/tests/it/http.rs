@@ -1,7 +1,8 @@
 #![allow(unreachable_pub)]
 //! Standalone http tests
 
-use crate::utils::{launch_http, launch_http_ws, launch_ws};
+use crate::utils::{launch_http, launch_http_ws, launch_ws, test_address, test_rpc_builder};
+use futures::StreamExt;
 use jsonrpsee::{
     core::{
         client::{ClientT, SubscriptionClientT},
@@ -13,13 +14,16 @@ use jsonrpsee::{
 };
 use reth_chainspec::net::NodeRecord;
 use reth_primitives::{
-    hex_literal::hex, Address, BlockId, BlockNumberOrTag, Bytes, TxHash, B256, B64, U256, U64,
+    hex_literal::hex, Address, BlockId, BlockNumberOrTag, Bytes, Header, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, TxHash, B256, B64, U256, U64,
 };
+use reth_provider::{test_utils::TestCanonStateSubscriptions, Chain, ExecutionOutcome};
 use reth_rpc_api::{
-    clients::{AdminApiClient, EthApiClient},
+    clients::{AdminApiClient, EthApiClient, RethApiClient},
     DebugApiClient, EthFilterApiClient, NetApiClient, OtterscanClient, TraceApiClient,
     Web3ApiClient,
 };
+use reth_rpc_builder::{RpcServerConfig, TransportRpcModuleConfig};
 use reth_rpc_server_types::RethRpcModule;
 use reth_rpc_types::{
     trace::filter::TraceFilter, FeeHistory, Filter, Index, Log, PendingTransactionFilterKind,
@@ -27,7 +31,7 @@ use reth_rpc_types::{
 };
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc};
 
 fn is_unimplemented(err: jsonrpsee::core::client::Error) -> bool {
     match err {
@@ -144,6 +148,9 @@ where
     AdminApiClient::add_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::remove_trusted_peer(client, node.into()).await.unwrap();
     AdminApiClient::node_info(client).await.unwrap();
+    AdminApiClient::export_peers(client).await.unwrap();
+    assert!(AdminApiClient::local_transactions(client).await.unwrap().is_empty());
+    assert!(!AdminApiClient::remove_local_transaction(client, B256::random()).await.unwrap());
 }
 
 async fn test_basic_eth_calls<C>(client: &C)
@@ -309,9 +316,7 @@ where
 
     OtterscanClient::get_transaction_error(client, tx_hash).await.unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::trace_transaction(client, tx_hash).await.err().unwrap()
-    ));
+    OtterscanClient::trace_transaction(client, tx_hash).await.unwrap();
 
     OtterscanClient::get_block_details(client, block_number).await.unwrap();
 
@@ -322,27 +327,29 @@ where
         .err()
         .unwrap();
 
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_before(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
-    ));
-    assert!(is_unimplemented(
-        OtterscanClient::search_transactions_after(client, address, block_number, page_size,)
-            .await
-            .err()
-            .unwrap()
+    // the search starts at the latest block, which the noop provider doesn't have
+    let err = OtterscanClient::search_transactions_before(client, address, block_number, page_size)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        jsonrpsee::core::client::Error::Call(error_obj)
+            if error_obj.message() == "block not found"
     ));
+
+    // there are no blocks after the latest block
+    let page = OtterscanClient::search_transactions_after(client, address, block_number, page_size)
+        .await
+        .unwrap();
+    assert!(page.txs.is_empty());
+    assert!(page.first_page && page.last_page);
     assert!(is_unimplemented(
         OtterscanClient::get_transaction_by_sender_and_nonce(client, sender, nonce,)
             .await
             .err()
             .unwrap()
     ));
-    assert!(is_unimplemented(
-        OtterscanClient::get_contract_creator(client, address).await.err().unwrap()
-    ));
+    OtterscanClient::get_contract_creator(client, address).await.unwrap();
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -1442,3 +1449,40 @@ async fn test_eth_fee_history_raw() {
     )
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reth_subscribe_chain_reorgs_ws() {
+    reth_tracing::init_test_tracing();
+
+    let events = TestCanonStateSubscriptions::default();
+    let server = test_rpc_builder()
+        .with_events(events.clone())
+        .build(TransportRpcModuleConfig::set_ws(vec![RethRpcModule::Reth]));
+    let handle = server
+        .start_server(RpcServerConfig::ws(Default::default()).with_ws_address(test_address()))
+        .await
+        .unwrap();
+    let client = handle.ws_client().await.unwrap();
+    let mut reorgs = RethApiClient::reth_subscribe_chain_reorgs(&client).await.unwrap();
+
+    let chain = |hash| {
+        let header = SealedHeader::new(Header { number: 1, ..Default::default() }, hash);
+        let block = SealedBlock { header, ..Default::default() };
+        let block = SealedBlockWithSenders { block, senders: Vec::new() };
+        Arc::new(Chain::from_block(block, ExecutionOutcome::default(), None))
+    };
+
+    // commits are not emitted
+    events.add_next_commit(chain(B256::with_last_byte(1)));
+    events.add_next_reorg(chain(B256::with_last_byte(1)), chain(B256::with_last_byte(2)));
+
+    let reorg = reorgs.next().await.unwrap().unwrap();
+    assert_eq!(
+        reorg.old.iter().map(|header| header.hash).collect::<Vec<_>>(),
+        vec![Some(B256::with_last_byte(1))]
+    );
+    assert_eq!(
+        reorg.new.iter().map(|header| header.hash).collect::<Vec<_>>(),
+        vec![Some(B256::with_last_byte(2))]
+    );
+}
@@ -1,5 +1,7 @@
 mod auth;
 mod http;
+mod namespace_auth;
+mod rate_limit;
 mod serde;
 mod startup;
 pub mod utils;
@@ -0,0 +1,79 @@
+//! Tests of the namespaces that require authentication.
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::client::Error,
+    http_client::{HeaderMap, HttpClient, HttpClientBuilder},
+};
+use reth_rpc_api::clients::{NetApiClient, Web3ApiClient};
+use reth_rpc_builder::{
+    RpcNamespaceAuthConfig, RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig,
+    UNAUTHORIZED_CODE,
+};
+use reth_rpc_layer::{Claims, JwtSecret};
+use reth_rpc_server_types::RethRpcModule;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Launches an HTTP server that requires authentication for the `web3` namespace.
+async fn launch(config: RpcNamespaceAuthConfig) -> RpcServerHandle {
+    let server = test_rpc_builder()
+        .build(TransportRpcModuleConfig::set_http([RethRpcModule::Web3, RethRpcModule::Net]));
+    server
+        .start_server(
+            RpcServerConfig::http(Default::default())
+                .with_http_address(test_address())
+                .with_namespace_auth(Some(config)),
+        )
+        .await
+        .unwrap()
+}
+
+/// Returns a client that sends the given `Authorization` header.
+fn http_client(handle: &RpcServerHandle, authorization: Option<&str>) -> HttpClient {
+    let headers = authorization
+        .map(|value| HeaderMap::from_iter([(http::header::AUTHORIZATION, value.parse().unwrap())]))
+        .unwrap_or_default();
+    HttpClientBuilder::default().set_headers(headers).build(handle.http_url().unwrap()).unwrap()
+}
+
+/// Returns true if the error is the rejection of an unauthorized request.
+fn is_unauthorized(err: Error) -> bool {
+    matches!(err, Error::Call(error_obj) if error_obj.code() == UNAUTHORIZED_CODE)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_namespace_auth_tokens() {
+    reth_tracing::init_test_tracing();
+    let config = RpcNamespaceAuthConfig::new(["web3"]).with_tokens(["s3cr3t"]);
+    let handle = launch(config).await;
+
+    // other namespaces don't require authentication
+    let client = http_client(&handle, None);
+    NetApiClient::version(&client).await.unwrap();
+    assert!(is_unauthorized(Web3ApiClient::client_version(&client).await.unwrap_err()));
+
+    let client = http_client(&handle, Some("Bearer t0k3n"));
+    assert!(is_unauthorized(Web3ApiClient::client_version(&client).await.unwrap_err()));
+
+    let client = http_client(&handle, Some("Bearer s3cr3t"));
+    Web3ApiClient::client_version(&client).await.unwrap();
+    NetApiClient::version(&client).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_namespace_auth_jwt() {
+    reth_tracing::init_test_tracing();
+    let secret = JwtSecret::random();
+    let config = RpcNamespaceAuthConfig::new(["web3"]).with_jwt_secret(Some(secret));
+    let handle = launch(config).await;
+
+    let iat = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+    let jwt = secret.encode(&Claims { iat, exp: None }).unwrap();
+    let client = http_client(&handle, Some(&format!("Bearer {jwt}")));
+    Web3ApiClient::client_version(&client).await.unwrap();
+
+    // a JWT signed with another secret is rejected
+    let jwt = JwtSecret::random().encode(&Claims { iat, exp: None }).unwrap();
+    let client = http_client(&handle, Some(&format!("Bearer {jwt}")));
+    assert!(is_unauthorized(Web3ApiClient::client_version(&client).await.unwrap_err()));
+}
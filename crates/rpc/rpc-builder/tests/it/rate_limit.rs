@@ -0,0 +1,83 @@
+//! Tests of the rate limits of the HTTP server.
+
+use crate::utils::{test_address, test_rpc_builder};
+use jsonrpsee::{
+    core::client::Error,
+    http_client::{HeaderMap, HttpClient, HttpClientBuilder},
+};
+use reth_rpc_api::clients::{NetApiClient, Web3ApiClient};
+use reth_rpc_builder::{
+    RpcRateLimitConfig, RpcServerConfig, RpcServerHandle, TransportRpcModuleConfig,
+    RATE_LIMIT_EXCEEDED_CODE,
+};
+use reth_rpc_server_types::{rate_limit::RPC_API_KEY_HEADER, RethRpcModule};
+
+/// Launches an HTTP server with the given rate limits.
+async fn launch(config: &str) -> RpcServerHandle {
+    let config: RpcRateLimitConfig = config.parse().unwrap();
+    let server = test_rpc_builder()
+        .build(TransportRpcModuleConfig::set_http([RethRpcModule::Web3, RethRpcModule::Net]));
+    server
+        .start_server(
+            RpcServerConfig::http(Default::default())
+                .with_http_address(test_address())
+                .with_rate_limits(Some(config)),
+        )
+        .await
+        .unwrap()
+}
+
+/// Returns a client with its own connection, that sends the given API key.
+fn http_client(handle: &RpcServerHandle, api_key: Option<&str>) -> HttpClient {
+    let headers = api_key
+        .map(|key| {
+            HeaderMap::from_iter([(RPC_API_KEY_HEADER.parse().unwrap(), key.parse().unwrap())])
+        })
+        .unwrap_or_default();
+    HttpClientBuilder::default().set_headers(headers).build(handle.http_url().unwrap()).unwrap()
+}
+
+/// Returns true if the error is the rejection of a rate limited request.
+fn is_rate_limited(err: Error) -> bool {
+    matches!(err, Error::Call(error_obj) if error_obj.code() == RATE_LIMIT_EXCEEDED_CODE)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rate_limit_connection() {
+    reth_tracing::init_test_tracing();
+    let handle = launch("default.connection = { rate = 1, burst = 2 }").await;
+
+    // the quota applies to all requests of the connection, not to every request
+    let client = http_client(&handle, None);
+    NetApiClient::version(&client).await.unwrap();
+    Web3ApiClient::client_version(&client).await.unwrap();
+    assert!(is_rate_limited(NetApiClient::version(&client).await.unwrap_err()));
+
+    // other connections have their own quota
+    NetApiClient::version(&http_client(&handle, None)).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_rate_limit_methods() {
+    reth_tracing::init_test_tracing();
+    let handle = launch(
+        r#"
+        default.methods.web3_clientVersion = { rate = 1 }
+        tiers.premium.api_keys = ["a"]
+        tiers.premium.methods.web3_clientVersion = { rate = 1 }
+        "#,
+    )
+    .await;
+
+    // the method quotas of the default tier aren't shared by all connections
+    let client = http_client(&handle, None);
+    Web3ApiClient::client_version(&client).await.unwrap();
+    assert!(is_rate_limited(Web3ApiClient::client_version(&client).await.unwrap_err()));
+    NetApiClient::version(&client).await.unwrap();
+    Web3ApiClient::client_version(&http_client(&handle, None)).await.unwrap();
+
+    // but the quotas of an API key are shared by all its connections
+    Web3ApiClient::client_version(&http_client(&handle, Some("a"))).await.unwrap();
+    let client = http_client(&handle, Some("a"));
+    assert!(is_rate_limited(Web3ApiClient::client_version(&client).await.unwrap_err()));
+}
@@ -42,6 +42,12 @@ pub enum EngineApiError {
         /// The length that was requested.
         len: u64,
     },
+    /// The number of requested blobs is too large.
+    #[error("requested blob count too large: {len}")]
+    BlobRequestTooLarge {
+        /// The length that was requested.
+        len: usize,
+    },
     /// Thrown if `engine_getPayloadBodiesByRangeV1` contains an invalid range
     #[error("invalid start ({start}) or count ({count})")]
     InvalidBodiesRange {
@@ -147,7 +153,8 @@ impl From<EngineApiError> for jsonrpsee_types::error::ErrorObject<'static> {
                 error.to_string(),
                 None::<()>,
             ),
-            EngineApiError::PayloadRequestTooLarge { .. } => {
+            EngineApiError::PayloadRequestTooLarge { .. } |
+            EngineApiError::BlobRequestTooLarge { .. } => {
                 jsonrpsee_types::error::ErrorObject::owned(
                     REQUEST_TOO_LARGE_CODE,
                     REQUEST_TOO_LARGE_MESSAGE,
@@ -242,6 +249,12 @@ mod tests {
             EngineApiError::PayloadRequestTooLarge { len: 0 },
         );
 
+        ensure_engine_rpc_error(
+            REQUEST_TOO_LARGE_CODE,
+            "Too large request",
+            EngineApiError::BlobRequestTooLarge { len: 0 },
+        );
+
         ensure_engine_rpc_error(
             -38002,
             "Invalid forkchoice state",
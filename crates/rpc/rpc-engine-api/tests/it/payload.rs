@@ -2,18 +2,22 @@
 
 use alloy_rlp::{Decodable, Error as RlpError};
 use assert_matches::assert_matches;
+use reth_chainspec::{ChainSpec, MAINNET};
+use reth_payload_validator::ExecutionPayloadValidator;
 use reth_primitives::{
     bytes::{Bytes, BytesMut},
     proofs, Block, SealedBlock, TransactionSigned, Withdrawals, B256, U256,
 };
 use reth_rpc_types::engine::{
-    ExecutionPayload, ExecutionPayloadBodyV1, ExecutionPayloadV1, PayloadError,
+    ExecutionPayload, ExecutionPayloadBodyV1, ExecutionPayloadV1, MaybeCancunPayloadFields,
+    PayloadError,
 };
 use reth_rpc_types_compat::engine::payload::{
     block_to_payload, block_to_payload_v1, convert_to_payload_body_v1, try_into_sealed_block,
     try_payload_v1_to_block,
 };
 use reth_testing_utils::generators::{self, random_block, random_block_range, random_header, Rng};
+use std::sync::Arc;
 
 fn transform_block<F: FnOnce(Block) -> Block>(src: SealedBlock, f: F) -> ExecutionPayload {
     let unsealed = src.unseal();
@@ -56,6 +60,7 @@ fn payload_validation() {
     let mut rng = generators::rng();
     let parent = rng.gen();
     let block = random_block(&mut rng, 100, Some(parent), Some(3), Some(0));
+    let validator = ExecutionPayloadValidator::new(MAINNET.clone());
 
     // Valid extra data
     let block_with_valid_extra_data = transform_block(block.clone(), |mut b| {
@@ -72,11 +77,24 @@ fn payload_validation() {
         b
     });
     assert_matches!(
-
-        try_into_sealed_block(invalid_extra_data_block,None),
+        validator.ensure_well_formed_payload(
+            invalid_extra_data_block.clone(),
+            MaybeCancunPayloadFields::none()
+        ),
         Err(PayloadError::ExtraData(data)) if data == block_with_invalid_extra_data
     );
 
+    // Extra data within the limit of a custom chain
+    let custom_validator = ExecutionPayloadValidator::new(Arc::new(ChainSpec {
+        max_extra_data_size: 64,
+        ..(*MAINNET).clone()
+    }));
+    assert_matches!(
+        custom_validator
+            .ensure_well_formed_payload(invalid_extra_data_block, MaybeCancunPayloadFields::none()),
+        Ok(_)
+    );
+
     // Zero base fee
     let block_with_zero_base_fee = transform_block(block.clone(), |mut b| {
         b.header.base_fee_per_gas = Some(0);
@@ -0,0 +1,330 @@
+use async_graphql::{Error, Result};
+use async_trait::async_trait;
+use reth_primitives::{
+    revm_primitives::ExecutionResult, Address, BlockId, Bytes, Header, Receipt,
+    SealedBlockWithSenders, TransactionSignedEcRecovered, B256, U256,
+};
+use reth_provider::{BlockIdReader, ChainSpecProvider, HeaderProvider};
+use reth_rpc::eth::{cache::EthStateCache, EthTransactions, TransactionSource};
+use reth_rpc_api::{EthApiServer, EthFilterApiServer};
+use reth_rpc_types::{
+    serde_helpers::JsonStorageKey, state::EvmOverrides, Filter, Log, SyncStatus, TransactionRequest,
+};
+use reth_transaction_pool::{IntoRecoveredTransaction, TransactionPool};
+use std::sync::Arc;
+
+/// The data the GraphQL schema is resolved with.
+///
+/// This is object safe, so the schema is independent of the types of the node components.
+#[async_trait]
+pub trait GraphQlBackend: Send + Sync + 'static {
+    /// Returns the block with the given id.
+    async fn block(&self, id: BlockId) -> Result<Option<SealedBlockWithSenders>>;
+
+    /// Returns the total difficulty of the chain up to the block with the given number.
+    fn total_difficulty(&self, number: u64) -> Result<Option<U256>>;
+
+    /// Returns the base fee of the block after the given block.
+    fn next_block_base_fee(&self, header: &Header) -> Option<u64>;
+
+    /// Returns the receipts of the block with the given hash.
+    async fn receipts(&self, block_hash: B256) -> Result<Option<Arc<Vec<Receipt>>>>;
+
+    /// Returns the transaction with the given hash, from a block or the pool.
+    async fn transaction(&self, hash: B256) -> Result<Option<TransactionSource>>;
+
+    /// Returns the pending transactions of the pool.
+    fn pending_transactions(&self) -> Vec<TransactionSignedEcRecovered>;
+
+    /// Returns the balance of the account at the given block.
+    async fn balance(&self, address: Address, at: BlockId) -> Result<U256>;
+
+    /// Returns the nonce of the account at the given block.
+    async fn transaction_count(&self, address: Address, at: BlockId) -> Result<U256>;
+
+    /// Returns the code of the account at the given block.
+    async fn code(&self, address: Address, at: BlockId) -> Result<Bytes>;
+
+    /// Returns the value of the storage slot of the account at the given block.
+    async fn storage(&self, address: Address, slot: B256, at: BlockId) -> Result<B256>;
+
+    /// Executes the call on the state of the given block.
+    async fn call(&self, request: TransactionRequest, at: BlockId) -> Result<CallOutcome>;
+
+    /// Returns the gas the transaction needs on the state of the given block.
+    async fn estimate_gas(&self, request: TransactionRequest, at: BlockId) -> Result<U256>;
+
+    /// Returns the logs that match the filter.
+    async fn logs(&self, filter: Filter) -> Result<Vec<Log>>;
+
+    /// Returns the suggested gas price of legacy transactions.
+    async fn gas_price(&self) -> Result<U256>;
+
+    /// Returns the suggested priority fee of dynamic fee transactions.
+    async fn max_priority_fee_per_gas(&self) -> Result<U256>;
+
+    /// Returns the sync status of the node.
+    async fn syncing(&self) -> Result<SyncStatus>;
+
+    /// Returns the chain id.
+    async fn chain_id(&self) -> Result<U256>;
+
+    /// Submits a signed transaction to the pool and returns its hash.
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256>;
+}
+
+/// The outcome of a call, see [`GraphQlBackend::call`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallOutcome {
+    /// The return data of the call, or the revert data if it reverted.
+    pub data: Bytes,
+    /// The gas the call used.
+    pub gas_used: u64,
+    /// Whether the call succeeded.
+    pub success: bool,
+}
+
+/// A [`GraphQlBackend`] that resolves the schema with the handlers of the `eth` namespace.
+#[derive(Debug, Clone)]
+pub struct EthBackend<Provider, Pool, Eth, Filter> {
+    provider: Provider,
+    pool: Pool,
+    eth: Eth,
+    filter: Filter,
+    cache: EthStateCache,
+}
+
+impl<Provider, Pool, Eth, Filter> EthBackend<Provider, Pool, Eth, Filter> {
+    /// Creates a new backend with the `eth` API, the filter API and their cache.
+    pub const fn new(
+        provider: Provider,
+        pool: Pool,
+        eth: Eth,
+        filter: Filter,
+        cache: EthStateCache,
+    ) -> Self {
+        Self { provider, pool, eth, filter, cache }
+    }
+}
+
+#[async_trait]
+impl<Provider, Pool, Eth, Filter> GraphQlBackend for EthBackend<Provider, Pool, Eth, Filter>
+where
+    Provider: BlockIdReader + HeaderProvider + ChainSpecProvider + 'static,
+    Pool: TransactionPool + 'static,
+    Eth: EthApiServer + EthTransactions + 'static,
+    Filter: EthFilterApiServer + 'static,
+{
+    async fn block(&self, id: BlockId) -> Result<Option<SealedBlockWithSenders>> {
+        let Some(hash) = self.provider.block_hash_for_id(id)? else { return Ok(None) };
+        Ok(self.cache.get_sealed_block_with_senders(hash).await?)
+    }
+
+    fn total_difficulty(&self, number: u64) -> Result<Option<U256>> {
+        Ok(self.provider.header_td_by_number(number)?)
+    }
+
+    fn next_block_base_fee(&self, header: &Header) -> Option<u64> {
+        let chain_spec = self.provider.chain_spec();
+        header.next_block_base_fee(chain_spec.base_fee_params_at_timestamp(header.timestamp))
+    }
+
+    async fn receipts(&self, block_hash: B256) -> Result<Option<Arc<Vec<Receipt>>>> {
+        Ok(self.cache.get_receipts(block_hash).await?)
+    }
+
+    async fn transaction(&self, hash: B256) -> Result<Option<TransactionSource>> {
+        Ok(EthTransactions::transaction_by_hash(&self.eth, hash).await?)
+    }
+
+    fn pending_transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+        self.pool
+            .pending_transactions()
+            .into_iter()
+            .map(|tx| tx.to_recovered_transaction())
+            .collect()
+    }
+
+    async fn balance(&self, address: Address, at: BlockId) -> Result<U256> {
+        EthApiServer::balance(&self.eth, address, Some(at)).await.map_err(rpc_error)
+    }
+
+    async fn transaction_count(&self, address: Address, at: BlockId) -> Result<U256> {
+        EthApiServer::transaction_count(&self.eth, address, Some(at)).await.map_err(rpc_error)
+    }
+
+    async fn code(&self, address: Address, at: BlockId) -> Result<Bytes> {
+        EthApiServer::get_code(&self.eth, address, Some(at)).await.map_err(rpc_error)
+    }
+
+    async fn storage(&self, address: Address, slot: B256, at: BlockId) -> Result<B256> {
+        EthApiServer::storage_at(&self.eth, address, JsonStorageKey(slot), Some(at))
+            .await
+            .map_err(rpc_error)
+    }
+
+    async fn call(&self, request: TransactionRequest, at: BlockId) -> Result<CallOutcome> {
+        let (res, _) = self.eth.transact_call_at(request, at, EvmOverrides::default()).await?;
+        Ok(match res.result {
+            ExecutionResult::Success { gas_used, output, .. } => {
+                CallOutcome { data: output.into_data(), gas_used, success: true }
+            }
+            ExecutionResult::Revert { gas_used, output } => {
+                CallOutcome { data: output, gas_used, success: false }
+            }
+            ExecutionResult::Halt { gas_used, .. } => {
+                CallOutcome { data: Bytes::new(), gas_used, success: false }
+            }
+        })
+    }
+
+    async fn estimate_gas(&self, request: TransactionRequest, at: BlockId) -> Result<U256> {
+        EthApiServer::estimate_gas(&self.eth, request, Some(at), None).await.map_err(rpc_error)
+    }
+
+    async fn logs(&self, filter: Filter) -> Result<Vec<Log>> {
+        self.filter.logs(filter).await.map_err(rpc_error)
+    }
+
+    async fn gas_price(&self) -> Result<U256> {
+        EthApiServer::gas_price(&self.eth).await.map_err(rpc_error)
+    }
+
+    async fn max_priority_fee_per_gas(&self) -> Result<U256> {
+        EthApiServer::max_priority_fee_per_gas(&self.eth).await.map_err(rpc_error)
+    }
+
+    async fn syncing(&self) -> Result<SyncStatus> {
+        EthApiServer::syncing(&self.eth).map_err(rpc_error)
+    }
+
+    async fn chain_id(&self) -> Result<U256> {
+        let chain_id = EthApiServer::chain_id(&self.eth).await.map_err(rpc_error)?;
+        Ok(chain_id.map(|chain_id| U256::from(chain_id.to::<u64>())).unwrap_or_default())
+    }
+
+    async fn send_raw_transaction(&self, tx: Bytes) -> Result<B256> {
+        EthApiServer::send_raw_transaction(&self.eth, tx).await.map_err(rpc_error)
+    }
+}
+
+/// Converts the error of an RPC handler into a GraphQL error with the same message.
+fn rpc_error(err: jsonrpsee::types::ErrorObjectOwned) -> Error {
+    Error::new(err.message())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use reth_primitives::{BlockNumberOrTag, SealedBlock};
+
+    /// A [`GraphQlBackend`] with a chain of empty blocks, that counts the loaded blocks.
+    #[derive(Debug, Default)]
+    pub(crate) struct MockBackend {
+        blocks: Vec<SealedBlockWithSenders>,
+        pub(crate) loaded_blocks: std::sync::atomic::AtomicUsize,
+    }
+
+    impl MockBackend {
+        /// Creates a backend with the empty blocks up to the given tip.
+        pub(crate) fn new(tip: u64) -> Self {
+            let mut blocks: Vec<SealedBlockWithSenders> = Vec::new();
+            for number in 0..=tip {
+                let header = Header {
+                    number,
+                    parent_hash: blocks.last().map(|block| block.hash()).unwrap_or_default(),
+                    gas_limit: 30_000_000,
+                    ..Default::default()
+                };
+                let block = SealedBlock { header: header.seal_slow(), ..Default::default() };
+                blocks.push(SealedBlockWithSenders { block, senders: Vec::new() });
+            }
+            Self { blocks, ..Default::default() }
+        }
+    }
+
+    #[async_trait]
+    impl GraphQlBackend for MockBackend {
+        async fn block(&self, id: BlockId) -> Result<Option<SealedBlockWithSenders>> {
+            self.loaded_blocks.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            let block = match id {
+                BlockId::Hash(hash) => {
+                    self.blocks.iter().find(|block| block.hash() == hash.block_hash)
+                }
+                BlockId::Number(BlockNumberOrTag::Number(number)) => {
+                    self.blocks.get(number as usize)
+                }
+                BlockId::Number(_) => self.blocks.last(),
+            };
+            Ok(block.cloned())
+        }
+
+        fn total_difficulty(&self, _number: u64) -> Result<Option<U256>> {
+            Ok(Some(U256::ZERO))
+        }
+
+        fn next_block_base_fee(&self, _header: &Header) -> Option<u64> {
+            None
+        }
+
+        async fn receipts(&self, _block_hash: B256) -> Result<Option<Arc<Vec<Receipt>>>> {
+            Ok(Some(Arc::default()))
+        }
+
+        async fn transaction(&self, _hash: B256) -> Result<Option<TransactionSource>> {
+            Ok(None)
+        }
+
+        fn pending_transactions(&self) -> Vec<TransactionSignedEcRecovered> {
+            Vec::new()
+        }
+
+        async fn balance(&self, _address: Address, _at: BlockId) -> Result<U256> {
+            Ok(U256::from(1000))
+        }
+
+        async fn transaction_count(&self, _address: Address, _at: BlockId) -> Result<U256> {
+            Ok(U256::from(1))
+        }
+
+        async fn code(&self, _address: Address, _at: BlockId) -> Result<Bytes> {
+            Ok(Bytes::new())
+        }
+
+        async fn storage(&self, _address: Address, _slot: B256, _at: BlockId) -> Result<B256> {
+            Ok(B256::ZERO)
+        }
+
+        async fn call(&self, _request: TransactionRequest, _at: BlockId) -> Result<CallOutcome> {
+            Err(Error::new("calls are not supported"))
+        }
+
+        async fn estimate_gas(&self, _request: TransactionRequest, _at: BlockId) -> Result<U256> {
+            Err(Error::new("calls are not supported"))
+        }
+
+        async fn logs(&self, _filter: Filter) -> Result<Vec<Log>> {
+            Ok(Vec::new())
+        }
+
+        async fn gas_price(&self) -> Result<U256> {
+            Ok(U256::from(7))
+        }
+
+        async fn max_priority_fee_per_gas(&self) -> Result<U256> {
+            Ok(U256::from(1))
+        }
+
+        async fn syncing(&self) -> Result<SyncStatus> {
+            Ok(SyncStatus::None)
+        }
+
+        async fn chain_id(&self) -> Result<U256> {
+            Ok(U256::from(1))
+        }
+
+        async fn send_raw_transaction(&self, _tx: Bytes) -> Result<B256> {
+            Err(Error::new("transactions are not supported"))
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! GraphQL API of reth, see [EIP-1767](https://eips.ethereum.org/EIPS/eip-1767).
+//!
+//! The schema follows the GraphQL schema of geth, so tooling that is built against the GraphQL
+//! endpoint of geth works with reth. The queries are resolved with the handlers of the `eth`
+//! namespace, see [`EthBackend`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+mod backend;
+pub use backend::{CallOutcome, EthBackend, GraphQlBackend};
+
+/// Scalar types of the schema.
+pub mod scalars;
+
+mod schema;
+pub use schema::{
+    build_schema, GraphQlSchema, MutationRoot, QueryRoot, MAX_BLOCKS_RANGE, MAX_QUERY_COMPLEXITY,
+    MAX_QUERY_DEPTH,
+};
+
+mod server;
+pub use server::{GraphQlLayer, GraphQlService, GRAPHQL_PATH};
@@ -0,0 +1,133 @@
+//! The scalars of the schema.
+//!
+//! Numbers are accepted as JSON numbers or as decimal or `0x` prefixed hexadecimal strings, and are
+//! returned as `0x` prefixed hexadecimal strings, like geth does.
+
+use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value};
+use reth_primitives::{hex, Address as PrimitiveAddress, Bytes as PrimitiveBytes, B256, U256};
+use std::str::FromStr;
+
+/// A 64 bit unsigned integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Long(pub u64);
+
+#[Scalar]
+impl ScalarType for Long {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => number.as_u64().map(Self).ok_or_else(|| {
+                InputValueError::custom(format!("{number} is not an unsigned 64 bit integer"))
+            }),
+            Value::String(s) => {
+                let res = match s.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16),
+                    None => s.parse(),
+                };
+                res.map(Self).map_err(InputValueError::custom)
+            }
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// A 256 bit unsigned integer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BigInt(pub U256);
+
+#[Scalar]
+impl ScalarType for BigInt {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        match &value {
+            Value::Number(number) => {
+                number.as_u64().map(|number| Self(U256::from(number))).ok_or_else(|| {
+                    InputValueError::custom(format!("{number} is not an unsigned integer"))
+                })
+            }
+            Value::String(s) => U256::from_str(s).map(Self).map_err(InputValueError::custom),
+            _ => Err(InputValueError::expected_type(value)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(format!("{:#x}", self.0))
+    }
+}
+
+/// Implements a scalar of hex encoded bytes.
+macro_rules! hex_scalar {
+    ($(#[$attr:meta])* $name:ident($inner:ty), $gql_name:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(pub $inner);
+
+        #[Scalar(name = $gql_name)]
+        impl ScalarType for $name {
+            fn parse(value: Value) -> InputValueResult<Self> {
+                match &value {
+                    Value::String(s) => {
+                        <$inner>::from_str(s).map(Self).map_err(InputValueError::custom)
+                    }
+                    _ => Err(InputValueError::expected_type(value)),
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::String(hex::encode_prefixed(&self.0))
+            }
+        }
+    };
+}
+
+hex_scalar!(
+    /// A 32 byte hash.
+    Bytes32(B256),
+    "Bytes32"
+);
+
+hex_scalar!(
+    /// A 20 byte address.
+    Address(PrimitiveAddress),
+    "Address"
+);
+
+hex_scalar!(
+    /// Arbitrary length bytes.
+    Bytes(PrimitiveBytes),
+    "Bytes"
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_long() {
+        assert_eq!(Long::parse(Value::from(10)).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::from("10")).unwrap(), Long(10));
+        assert_eq!(Long::parse(Value::from("0x10")).unwrap(), Long(16));
+        assert!(Long::parse(Value::from(-1)).is_err());
+        assert!(Long::parse(Value::from("0xzz")).is_err());
+        assert_eq!(Long(16).to_value(), Value::from("0x10"));
+    }
+
+    #[test]
+    fn parse_big_int() {
+        assert_eq!(BigInt::parse(Value::from("1000")).unwrap(), BigInt(U256::from(1000)));
+        assert_eq!(BigInt::parse(Value::from("0x3e8")).unwrap(), BigInt(U256::from(1000)));
+        assert_eq!(BigInt(U256::from(1000)).to_value(), Value::from("0x3e8"));
+    }
+
+    #[test]
+    fn parse_hex_scalars() {
+        let address = PrimitiveAddress::with_last_byte(1);
+        let value = Value::from(hex::encode_prefixed(address));
+        assert_eq!(Address::parse(value.clone()).unwrap(), Address(address));
+        assert_eq!(Address(address).to_value(), value);
+        assert!(Bytes32::parse(value).is_err());
+        assert_eq!(Bytes(PrimitiveBytes::new()).to_value(), Value::from("0x"));
+    }
+}
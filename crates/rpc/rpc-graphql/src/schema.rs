@@ -0,0 +1,1050 @@
+//! The schema of the GraphQL API.
+//!
+//! See also <https://github.com/ethereum/go-ethereum/blob/master/graphql/schema.go>
+
+use crate::{
+    backend::GraphQlBackend,
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+};
+use async_graphql::{
+    Context, EmptySubscription, Error, InputObject, Object, Result, Schema, SimpleObject,
+};
+use reth_primitives::{
+    Address as PrimitiveAddress, BlockId, BlockNumberOrTag, Bytes as PrimitiveBytes,
+    Log as PrimitiveLog, Receipt, SealedBlockWithSenders, TransactionSignedEcRecovered, TxKind,
+    B256, U256,
+};
+use reth_rpc::eth::TransactionSource;
+use reth_rpc_types::{Filter, Log as RpcLog, SyncStatus, TransactionInput, TransactionRequest};
+use std::sync::Arc;
+
+/// The maximum number of blocks a `blocks` query returns.
+pub const MAX_BLOCKS_RANGE: u64 = 1000;
+
+/// The maximum depth of a query, deep enough for the nested queries of the schema like
+/// `block { transactions { logs { account { balance } } } }`, but not for arbitrarily long chains
+/// of `parent` blocks.
+pub const MAX_QUERY_DEPTH: usize = 16;
+
+/// The maximum complexity of a query, the number of fields it selects.
+pub const MAX_QUERY_COMPLEXITY: usize = 1000;
+
+/// The GraphQL schema.
+pub type GraphQlSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// Builds the GraphQL schema that is resolved with the given backend.
+///
+/// Queries are limited to [`MAX_QUERY_DEPTH`] and [`MAX_QUERY_COMPLEXITY`].
+pub fn build_schema(backend: Arc<dyn GraphQlBackend>) -> GraphQlSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(backend)
+        .limit_depth(MAX_QUERY_DEPTH)
+        .limit_complexity(MAX_QUERY_COMPLEXITY)
+        .finish()
+}
+
+/// Returns the backend of the schema.
+fn backend<'a>(ctx: &Context<'a>) -> &'a dyn GraphQlBackend {
+    ctx.data_unchecked::<Arc<dyn GraphQlBackend>>().as_ref()
+}
+
+/// Returns the block id of the optional `block` argument, the latest block by default.
+fn block_id_or_latest(block: Option<Long>) -> BlockId {
+    block.map_or(BlockId::Number(BlockNumberOrTag::Latest), |Long(number)| number.into())
+}
+
+/// The root of all queries.
+#[derive(Debug, Default)]
+pub struct QueryRoot;
+
+#[Object(name = "Query")]
+impl QueryRoot {
+    /// Returns the block with the given number or hash, the latest block if neither is given.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        let id = match (number, hash) {
+            (Some(_), Some(_)) => return Err(Error::new("only one of number or hash must be set")),
+            (Some(Long(number)), None) => number.into(),
+            (None, Some(Bytes32(hash))) => hash.into(),
+            (None, None) => BlockId::Number(BlockNumberOrTag::Latest),
+        };
+        Block::load(backend(ctx), id).await
+    }
+
+    /// Returns the blocks in the given range, up to the latest block by default.
+    ///
+    /// The range can include at most [`MAX_BLOCKS_RANGE`] blocks.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        from: Option<Long>,
+        to: Option<Long>,
+    ) -> Result<Vec<Block>> {
+        let Some(Long(from)) = from else {
+            return Err(Error::new("from block number must be specified"));
+        };
+        let to = match to {
+            Some(Long(to)) => to,
+            None => match Block::load(backend(ctx), BlockNumberOrTag::Latest.into()).await? {
+                Some(latest) => latest.block.number,
+                None => return Ok(Vec::new()),
+            },
+        };
+        if to.saturating_sub(from) >= MAX_BLOCKS_RANGE {
+            return Err(Error::new(format!(
+                "block range exceeds the maximum of {MAX_BLOCKS_RANGE} blocks"
+            )))
+        }
+
+        let mut blocks = Vec::new();
+        for number in from..=to {
+            match Block::load(backend(ctx), number.into()).await? {
+                Some(block) => blocks.push(block),
+                None => break,
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// Returns the state of the pending block.
+    async fn pending(&self) -> Pending {
+        Pending
+    }
+
+    /// Returns the transaction with the given hash, from a block or the pool.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        Ok(backend(ctx).transaction(hash.0).await?.map(Transaction::from))
+    }
+
+    /// Returns the logs that match the filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        let mut rpc_filter = Filter::new();
+        if let Some(Long(from)) = filter.from_block {
+            rpc_filter = rpc_filter.from_block(from);
+        }
+        if let Some(Long(to)) = filter.to_block {
+            rpc_filter = rpc_filter.to_block(to);
+        }
+        if let Some(addresses) = filter.addresses {
+            rpc_filter = rpc_filter
+                .address(addresses.into_iter().map(|Address(address)| address).collect::<Vec<_>>());
+        }
+        for (position, topics) in filter.topics.unwrap_or_default().into_iter().enumerate().take(4)
+        {
+            rpc_filter.topics[position] =
+                topics.into_iter().map(|Bytes32(topic)| topic).collect::<Vec<_>>().into();
+        }
+
+        Ok(backend(ctx).logs(rpc_filter).await?.into_iter().map(Log::from).collect())
+    }
+
+    /// Returns the suggested gas price of legacy transactions.
+    async fn gas_price(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).gas_price().await?))
+    }
+
+    /// Returns the suggested priority fee of dynamic fee transactions.
+    async fn max_priority_fee_per_gas(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).max_priority_fee_per_gas().await?))
+    }
+
+    /// Returns the sync status of the node, or null if the node is not syncing.
+    async fn syncing(&self, ctx: &Context<'_>) -> Result<Option<SyncState>> {
+        Ok(match backend(ctx).syncing().await? {
+            SyncStatus::Info(info) => Some(SyncState {
+                starting_block: Long(info.starting_block.saturating_to()),
+                current_block: Long(info.current_block.saturating_to()),
+                highest_block: Long(info.highest_block.saturating_to()),
+            }),
+            SyncStatus::None => None,
+        })
+    }
+
+    /// Returns the chain id.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).chain_id().await?))
+    }
+}
+
+/// The root of all mutations.
+#[derive(Debug, Default)]
+pub struct MutationRoot;
+
+#[Object(name = "Mutation")]
+impl MutationRoot {
+    /// Submits a signed transaction to the pool and returns its hash.
+    async fn send_raw_transaction(&self, ctx: &Context<'_>, data: Bytes) -> Result<Bytes32> {
+        Ok(Bytes32(backend(ctx).send_raw_transaction(data.0).await?))
+    }
+}
+
+/// An account at a block.
+#[derive(Debug, Clone)]
+pub struct Account {
+    address: PrimitiveAddress,
+    at: BlockId,
+}
+
+#[Object]
+impl Account {
+    /// The address of the account.
+    async fn address(&self) -> Address {
+        Address(self.address)
+    }
+
+    /// The balance of the account, in wei.
+    async fn balance(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        Ok(BigInt(backend(ctx).balance(self.address, self.at).await?))
+    }
+
+    /// The number of transactions sent from the account.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        let nonce = backend(ctx).transaction_count(self.address, self.at).await?;
+        Ok(Long(nonce.saturating_to()))
+    }
+
+    /// The code of the account, empty if it's not a contract.
+    async fn code(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        Ok(Bytes(backend(ctx).code(self.address, self.at).await?))
+    }
+
+    /// The value of a storage slot of the account.
+    async fn storage(&self, ctx: &Context<'_>, slot: Bytes32) -> Result<Bytes32> {
+        Ok(Bytes32(backend(ctx).storage(self.address, slot.0, self.at).await?))
+    }
+}
+
+/// A block.
+#[derive(Debug, Clone)]
+pub struct Block {
+    block: Arc<SealedBlockWithSenders>,
+}
+
+impl Block {
+    /// Loads the block with the given id.
+    async fn load(backend: &dyn GraphQlBackend, id: BlockId) -> Result<Option<Self>> {
+        Ok(backend.block(id).await?.map(|block| Self { block: Arc::new(block) }))
+    }
+
+    /// Returns the transaction at the given index.
+    fn transaction(&self, index: usize) -> Option<Transaction> {
+        let tx = self.block.body.get(index)?;
+        let sender = *self.block.senders.get(index)?;
+        Some(Transaction {
+            tx: tx.clone().with_signer(sender),
+            block: Some(TransactionBlock {
+                hash: self.block.hash(),
+                number: self.block.number,
+                index: index as u64,
+                base_fee: self.block.base_fee_per_gas,
+            }),
+        })
+    }
+
+    /// The id of the block.
+    fn id(&self) -> BlockId {
+        self.block.hash().into()
+    }
+}
+
+#[Object]
+impl Block {
+    /// The number of the block.
+    async fn number(&self) -> Long {
+        Long(self.block.number)
+    }
+
+    /// The hash of the block.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.block.hash())
+    }
+
+    /// The parent of the block, null for the genesis block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Self>> {
+        if self.block.number == 0 {
+            return Ok(None);
+        }
+        Self::load(backend(ctx), self.block.parent_hash.into()).await
+    }
+
+    /// The nonce of the block.
+    async fn nonce(&self) -> Bytes {
+        Bytes(PrimitiveBytes::copy_from_slice(&self.block.nonce.to_be_bytes()))
+    }
+
+    /// The root of the transaction trie of the block.
+    async fn transactions_root(&self) -> Bytes32 {
+        Bytes32(self.block.transactions_root)
+    }
+
+    /// The number of transactions of the block.
+    async fn transaction_count(&self) -> Option<Long> {
+        Some(Long(self.block.body.len() as u64))
+    }
+
+    /// The root of the state trie after the block.
+    async fn state_root(&self) -> Bytes32 {
+        Bytes32(self.block.state_root)
+    }
+
+    /// The root of the receipt trie of the block.
+    async fn receipts_root(&self) -> Bytes32 {
+        Bytes32(self.block.receipts_root)
+    }
+
+    /// The account that received the rewards of the block, at the given block or the latest.
+    async fn miner(&self, block: Option<Long>) -> Account {
+        Account { address: self.block.beneficiary, at: block_id_or_latest(block) }
+    }
+
+    /// The extra data of the block.
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.block.extra_data.clone())
+    }
+
+    /// The gas limit of the block.
+    async fn gas_limit(&self) -> Long {
+        Long(self.block.gas_limit)
+    }
+
+    /// The gas the transactions of the block used.
+    async fn gas_used(&self) -> Long {
+        Long(self.block.gas_used)
+    }
+
+    /// The base fee of the block, null before London.
+    async fn base_fee_per_gas(&self) -> Option<BigInt> {
+        self.block.base_fee_per_gas.map(|base_fee| BigInt(U256::from(base_fee)))
+    }
+
+    /// The base fee of the next block, null before London.
+    async fn next_base_fee_per_gas(&self, ctx: &Context<'_>) -> Option<BigInt> {
+        backend(ctx)
+            .next_block_base_fee(self.block.header.header())
+            .map(|base_fee| BigInt(U256::from(base_fee)))
+    }
+
+    /// The timestamp of the block, in seconds.
+    async fn timestamp(&self) -> Long {
+        Long(self.block.timestamp)
+    }
+
+    /// The bloom filter of the logs of the block.
+    async fn logs_bloom(&self) -> Bytes {
+        Bytes(PrimitiveBytes::copy_from_slice(self.block.logs_bloom.as_slice()))
+    }
+
+    /// The mix hash of the block.
+    async fn mix_hash(&self) -> Bytes32 {
+        Bytes32(self.block.mix_hash)
+    }
+
+    /// The difficulty of the block.
+    async fn difficulty(&self) -> BigInt {
+        BigInt(self.block.difficulty)
+    }
+
+    /// The total difficulty of the chain up to the block.
+    async fn total_difficulty(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        let total_difficulty = backend(ctx)
+            .total_difficulty(self.block.number)?
+            .ok_or_else(|| Error::new("total difficulty not found"))?;
+        Ok(BigInt(total_difficulty))
+    }
+
+    /// The number of ommers of the block.
+    async fn ommer_count(&self) -> Option<Long> {
+        Some(Long(self.block.ommers.len() as u64))
+    }
+
+    /// The hash of the ommers of the block.
+    async fn ommer_hash(&self) -> Bytes32 {
+        Bytes32(self.block.ommers_hash)
+    }
+
+    /// The transactions of the block.
+    async fn transactions(&self) -> Option<Vec<Transaction>> {
+        Some((0..self.block.body.len()).filter_map(|index| self.transaction(index)).collect())
+    }
+
+    /// The transaction at the given index of the block.
+    async fn transaction_at(&self, index: Long) -> Option<Transaction> {
+        self.transaction(usize::try_from(index.0).ok()?)
+    }
+
+    /// The logs of the block that match the filter.
+    async fn logs(&self, ctx: &Context<'_>, filter: BlockFilterCriteria) -> Result<Vec<Log>> {
+        let Some(receipts) = backend(ctx).receipts(self.block.hash()).await? else {
+            return Ok(Vec::new());
+        };
+
+        let mut logs = Vec::new();
+        let mut index = 0;
+        for (receipt, tx) in receipts.iter().zip(self.block.body.iter()) {
+            for log in &receipt.logs {
+                if filter.matches(log) {
+                    logs.push(Log { inner: log.clone(), index, transaction_hash: tx.hash() });
+                }
+                index += 1;
+            }
+        }
+        Ok(logs)
+    }
+
+    /// The account with the given address at the block.
+    async fn account(&self, address: Address) -> Account {
+        Account { address: address.0, at: self.id() }
+    }
+
+    /// Executes a call on the state after the block.
+    async fn call(&self, ctx: &Context<'_>, data: CallData) -> Result<Option<CallResult>> {
+        Ok(Some(CallResult::call(backend(ctx), data, self.id()).await?))
+    }
+
+    /// Returns the gas a transaction needs on the state after the block.
+    async fn estimate_gas(&self, ctx: &Context<'_>, data: CallData) -> Result<Long> {
+        let gas = backend(ctx).estimate_gas(data.into(), self.id()).await?;
+        Ok(Long(gas.saturating_to()))
+    }
+
+    /// The RLP encoding of the block.
+    async fn raw(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(&self.block.block).into())
+    }
+
+    /// The RLP encoding of the header of the block.
+    async fn raw_header(&self) -> Bytes {
+        Bytes(alloy_rlp::encode(self.block.header.header()).into())
+    }
+
+    /// The root of the withdrawal trie of the block, null before Shanghai.
+    async fn withdrawals_root(&self) -> Option<Bytes32> {
+        self.block.withdrawals_root.map(Bytes32)
+    }
+
+    /// The withdrawals of the block, null before Shanghai.
+    async fn withdrawals(&self) -> Option<Vec<Withdrawal>> {
+        let withdrawals = self.block.withdrawals.as_ref()?;
+        Some(
+            withdrawals
+                .iter()
+                .map(|withdrawal| Withdrawal {
+                    index: Long(withdrawal.index),
+                    validator: Long(withdrawal.validator_index),
+                    address: Address(withdrawal.address),
+                    amount: Long(withdrawal.amount),
+                })
+                .collect(),
+        )
+    }
+
+    /// The blob gas the transactions of the block used, null before Cancun.
+    async fn blob_gas_used(&self) -> Option<Long> {
+        self.block.blob_gas_used.map(Long)
+    }
+
+    /// The excess blob gas of the block, null before Cancun.
+    async fn excess_blob_gas(&self) -> Option<Long> {
+        self.block.excess_blob_gas.map(Long)
+    }
+}
+
+/// The block a transaction was included in.
+#[derive(Debug, Clone, Copy)]
+struct TransactionBlock {
+    hash: B256,
+    number: u64,
+    index: u64,
+    base_fee: Option<u64>,
+}
+
+/// A transaction, included in a block or pending in the pool.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    tx: TransactionSignedEcRecovered,
+    block: Option<TransactionBlock>,
+}
+
+impl From<TransactionSource> for Transaction {
+    fn from(source: TransactionSource) -> Self {
+        match source {
+            TransactionSource::Pool(tx) => Self { tx, block: None },
+            TransactionSource::Block { transaction, index, block_hash, block_number, base_fee } => {
+                Self {
+                    tx: transaction,
+                    block: Some(TransactionBlock {
+                        hash: block_hash,
+                        number: block_number,
+                        index,
+                        base_fee,
+                    }),
+                }
+            }
+        }
+    }
+}
+
+impl Transaction {
+    /// Returns the receipt of the transaction, together with the gas it used and the index of its
+    /// first log in the block, if the transaction was included in a block.
+    async fn receipt(&self, ctx: &Context<'_>) -> Result<Option<(Receipt, u64, u64)>> {
+        let Some(block) = self.block else { return Ok(None) };
+        let Some(receipts) = backend(ctx).receipts(block.hash).await? else { return Ok(None) };
+
+        let index = block.index as usize;
+        let Some(receipt) = receipts.get(index) else { return Ok(None) };
+        let previous = index.checked_sub(1).and_then(|previous| receipts.get(previous));
+        let gas_used = receipt.cumulative_gas_used -
+            previous.map_or(0, |previous| previous.cumulative_gas_used);
+        let first_log_index =
+            receipts[..index].iter().map(|receipt| receipt.logs.len() as u64).sum::<u64>();
+        Ok(Some((receipt.clone(), gas_used, first_log_index)))
+    }
+}
+
+#[Object]
+impl Transaction {
+    /// The hash of the transaction.
+    async fn hash(&self) -> Bytes32 {
+        Bytes32(self.tx.hash())
+    }
+
+    /// The nonce of the sender of the transaction.
+    async fn nonce(&self) -> Long {
+        Long(self.tx.nonce())
+    }
+
+    /// The index of the transaction in its block, null if it's pending.
+    async fn index(&self) -> Option<Long> {
+        self.block.map(|block| Long(block.index))
+    }
+
+    /// The sender of the transaction, at the given block or the latest.
+    #[graphql(name = "from")]
+    async fn sender(&self, block: Option<Long>) -> Account {
+        Account { address: self.tx.signer(), at: block_id_or_latest(block) }
+    }
+
+    /// The recipient of the transaction at the given block or the latest, null if it creates a
+    /// contract.
+    #[graphql(name = "to")]
+    async fn recipient(&self, block: Option<Long>) -> Option<Account> {
+        self.tx.to().map(|address| Account { address, at: block_id_or_latest(block) })
+    }
+
+    /// The value the transaction transfers, in wei.
+    async fn value(&self) -> BigInt {
+        BigInt(self.tx.value())
+    }
+
+    /// The price per gas the transaction paid if it was included in a block, its max fee per gas
+    /// otherwise.
+    async fn gas_price(&self) -> BigInt {
+        let gas_price = match self.block {
+            Some(block) => self.tx.effective_gas_price(block.base_fee),
+            None => self.tx.max_fee_per_gas(),
+        };
+        BigInt(U256::from(gas_price))
+    }
+
+    /// The max fee per gas of dynamic fee transactions.
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.is_dynamic_fee().then(|| BigInt(U256::from(self.tx.max_fee_per_gas())))
+    }
+
+    /// The max priority fee per gas of dynamic fee transactions.
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.tx.max_priority_fee_per_gas().map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The max fee per blob gas of blob transactions.
+    async fn max_fee_per_blob_gas(&self) -> Option<BigInt> {
+        self.tx.max_fee_per_blob_gas().map(|fee| BigInt(U256::from(fee)))
+    }
+
+    /// The tip per gas the block producer received, null if the transaction is pending.
+    async fn effective_tip(&self) -> Option<BigInt> {
+        let block = self.block?;
+        self.tx.effective_tip_per_gas(block.base_fee).map(|tip| BigInt(U256::from(tip)))
+    }
+
+    /// The gas limit of the transaction.
+    async fn gas(&self) -> Long {
+        Long(self.tx.gas_limit())
+    }
+
+    /// The input data of the transaction.
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.tx.input().clone())
+    }
+
+    /// The block the transaction was included in, null if it's pending.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let Some(block) = self.block else { return Ok(None) };
+        Block::load(backend(ctx), block.hash.into()).await
+    }
+
+    /// The status of the transaction, 1 if it succeeded and 0 if it failed, null if it's pending.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|(receipt, ..)| Long(receipt.success as u64)))
+    }
+
+    /// The gas the transaction used, null if it's pending.
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|(_, gas_used, _)| Long(gas_used)))
+    }
+
+    /// The gas the transactions of the block used up to and including the transaction, null if
+    /// it's pending.
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        Ok(self.receipt(ctx).await?.map(|(receipt, ..)| Long(receipt.cumulative_gas_used)))
+    }
+
+    /// The price per gas the transaction paid, null if it's pending.
+    async fn effective_gas_price(&self) -> Option<BigInt> {
+        let block = self.block?;
+        Some(BigInt(U256::from(self.tx.effective_gas_price(block.base_fee))))
+    }
+
+    /// The blob gas the transaction used, null if it's pending or not a blob transaction.
+    async fn blob_gas_used(&self) -> Option<Long> {
+        self.block?;
+        self.tx.blob_gas_used().map(Long)
+    }
+
+    /// The contract the transaction created at the given block or the latest, null if it's
+    /// pending or didn't create a contract.
+    async fn created_contract(&self, block: Option<Long>) -> Option<Account> {
+        if self.block.is_none() || self.tx.kind() != TxKind::Create {
+            return None;
+        }
+        Some(Account {
+            address: self.tx.signer().create(self.tx.nonce()),
+            at: block_id_or_latest(block),
+        })
+    }
+
+    /// The logs of the transaction, null if it's pending.
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Option<Vec<Log>>> {
+        Ok(self.receipt(ctx).await?.map(|(receipt, _, first_log_index)| {
+            receipt
+                .logs
+                .into_iter()
+                .zip(first_log_index..)
+                .map(|(inner, index)| Log { inner, index, transaction_hash: self.tx.hash() })
+                .collect()
+        }))
+    }
+
+    /// The R field of the signature.
+    async fn r(&self) -> BigInt {
+        BigInt(self.tx.signature().r)
+    }
+
+    /// The S field of the signature.
+    async fn s(&self) -> BigInt {
+        BigInt(self.tx.signature().s)
+    }
+
+    /// The V field of the signature.
+    async fn v(&self) -> BigInt {
+        let v = if self.tx.is_legacy() {
+            self.tx.signature().v(self.tx.chain_id())
+        } else {
+            self.tx.signature().odd_y_parity as u64
+        };
+        BigInt(U256::from(v))
+    }
+
+    /// The y parity of the signature, null for legacy transactions.
+    async fn y_parity(&self) -> Option<BigInt> {
+        (!self.tx.is_legacy()).then(|| BigInt(U256::from(self.tx.signature().odd_y_parity as u8)))
+    }
+
+    /// The type of the transaction.
+    #[graphql(name = "type")]
+    async fn tx_type(&self) -> Long {
+        Long(u8::from(self.tx.tx_type()) as u64)
+    }
+
+    /// The access list of the transaction, null for legacy transactions.
+    async fn access_list(&self) -> Option<Vec<AccessTuple>> {
+        Some(
+            self.tx
+                .access_list()?
+                .iter()
+                .map(|item| AccessTuple {
+                    address: Address(item.address),
+                    storage_keys: item.storage_keys.iter().copied().map(Bytes32).collect(),
+                })
+                .collect(),
+        )
+    }
+
+    /// The encoding of the transaction.
+    async fn raw(&self) -> Bytes {
+        Bytes(self.tx.envelope_encoded())
+    }
+
+    /// The versioned hashes of the blobs of blob transactions.
+    async fn blob_versioned_hashes(&self) -> Option<Vec<Bytes32>> {
+        Some(self.tx.blob_versioned_hashes()?.into_iter().map(Bytes32).collect())
+    }
+}
+
+/// A log emitted by a transaction.
+#[derive(Debug, Clone)]
+pub struct Log {
+    inner: PrimitiveLog,
+    index: u64,
+    transaction_hash: B256,
+}
+
+impl From<RpcLog> for Log {
+    fn from(log: RpcLog) -> Self {
+        Self {
+            inner: log.inner,
+            index: log.log_index.unwrap_or_default(),
+            transaction_hash: log.transaction_hash.unwrap_or_default(),
+        }
+    }
+}
+
+#[Object]
+impl Log {
+    /// The index of the log in its block.
+    async fn index(&self) -> Long {
+        Long(self.index)
+    }
+
+    /// The account that emitted the log, at the given block or the latest.
+    async fn account(&self, block: Option<Long>) -> Account {
+        Account { address: self.inner.address, at: block_id_or_latest(block) }
+    }
+
+    /// The topics of the log.
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.inner.data.topics().iter().copied().map(Bytes32).collect()
+    }
+
+    /// The data of the log.
+    async fn data(&self) -> Bytes {
+        Bytes(self.inner.data.data.clone())
+    }
+
+    /// The transaction that emitted the log.
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Transaction> {
+        backend(ctx)
+            .transaction(self.transaction_hash)
+            .await?
+            .map(Transaction::from)
+            .ok_or_else(|| Error::new("transaction not found"))
+    }
+}
+
+/// The pending block.
+#[derive(Debug, Clone, Copy)]
+pub struct Pending;
+
+#[Object]
+impl Pending {
+    /// The number of pending transactions in the pool.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Long {
+        Long(backend(ctx).pending_transactions().len() as u64)
+    }
+
+    /// The pending transactions of the pool.
+    async fn transactions(&self, ctx: &Context<'_>) -> Option<Vec<Transaction>> {
+        Some(
+            backend(ctx)
+                .pending_transactions()
+                .into_iter()
+                .map(|tx| Transaction { tx, block: None })
+                .collect(),
+        )
+    }
+
+    /// The account with the given address at the pending block.
+    async fn account(&self, address: Address) -> Account {
+        Account { address: address.0, at: BlockNumberOrTag::Pending.into() }
+    }
+
+    /// Executes a call on the pending state.
+    async fn call(&self, ctx: &Context<'_>, data: CallData) -> Result<Option<CallResult>> {
+        Ok(Some(CallResult::call(backend(ctx), data, BlockNumberOrTag::Pending.into()).await?))
+    }
+
+    /// Returns the gas a transaction needs on the pending state.
+    async fn estimate_gas(&self, ctx: &Context<'_>, data: CallData) -> Result<Long> {
+        let gas = backend(ctx).estimate_gas(data.into(), BlockNumberOrTag::Pending.into()).await?;
+        Ok(Long(gas.saturating_to()))
+    }
+}
+
+/// An entry of the access list of a transaction.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct AccessTuple {
+    /// The accessed account.
+    address: Address,
+    /// The accessed storage slots of the account.
+    storage_keys: Vec<Bytes32>,
+}
+
+/// A withdrawal of a validator.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct Withdrawal {
+    /// The index of the withdrawal.
+    index: Long,
+    /// The index of the validator.
+    validator: Long,
+    /// The recipient of the withdrawal.
+    address: Address,
+    /// The amount of the withdrawal, in gwei.
+    amount: Long,
+}
+
+/// The result of a call.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct CallResult {
+    /// The return data of the call, or the revert data if it reverted.
+    data: Bytes,
+    /// The gas the call used.
+    gas_used: Long,
+    /// The status of the call, 1 if it succeeded and 0 if it failed.
+    status: Long,
+}
+
+impl CallResult {
+    /// Executes the call on the state of the given block.
+    async fn call(backend: &dyn GraphQlBackend, data: CallData, at: BlockId) -> Result<Self> {
+        let outcome = backend.call(data.into(), at).await?;
+        Ok(Self {
+            data: Bytes(outcome.data),
+            gas_used: Long(outcome.gas_used),
+            status: Long(outcome.success as u64),
+        })
+    }
+}
+
+/// The sync status of the node.
+#[derive(Debug, Clone, SimpleObject)]
+pub struct SyncState {
+    /// The block the sync started at.
+    starting_block: Long,
+    /// The current block of the node.
+    current_block: Long,
+    /// The highest block of the peers of the node.
+    highest_block: Long,
+}
+
+/// The parameters of a call.
+#[derive(Debug, Clone, InputObject)]
+pub struct CallData {
+    /// The sender of the call, the zero address by default.
+    from: Option<Address>,
+    /// The recipient of the call, null to create a contract.
+    to: Option<Address>,
+    /// The gas limit of the call.
+    gas: Option<Long>,
+    /// The gas price of a legacy call.
+    gas_price: Option<BigInt>,
+    /// The max fee per gas of a dynamic fee call.
+    max_fee_per_gas: Option<BigInt>,
+    /// The max priority fee per gas of a dynamic fee call.
+    max_priority_fee_per_gas: Option<BigInt>,
+    /// The value the call transfers, in wei.
+    value: Option<BigInt>,
+    /// The input data of the call.
+    data: Option<Bytes>,
+}
+
+impl From<CallData> for TransactionRequest {
+    fn from(data: CallData) -> Self {
+        let fee = |fee: Option<BigInt>| fee.map(|BigInt(fee)| fee.saturating_to::<u128>());
+        Self {
+            from: data.from.map(|Address(from)| from),
+            to: Some(data.to.map_or(TxKind::Create, |Address(to)| TxKind::Call(to))),
+            gas: data.gas.map(|Long(gas)| gas as u128),
+            gas_price: fee(data.gas_price),
+            max_fee_per_gas: fee(data.max_fee_per_gas),
+            max_priority_fee_per_gas: fee(data.max_priority_fee_per_gas),
+            value: data.value.map(|BigInt(value)| value),
+            input: TransactionInput::new(data.data.map(|Bytes(data)| data).unwrap_or_default()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The filter of logs of a range of blocks.
+#[derive(Debug, Clone, InputObject)]
+pub struct FilterCriteria {
+    /// The first block of the range, the latest block by default.
+    from_block: Option<Long>,
+    /// The last block of the range, the latest block by default.
+    to_block: Option<Long>,
+    /// The accounts that emitted the logs, any account if empty.
+    addresses: Option<Vec<Address>>,
+    /// The topics of the logs by position, any topic at a position that is empty.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// The filter of logs of a block.
+#[derive(Debug, Clone, InputObject)]
+pub struct BlockFilterCriteria {
+    /// The accounts that emitted the logs, any account if empty.
+    addresses: Option<Vec<Address>>,
+    /// The topics of the logs by position, any topic at a position that is empty.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+impl BlockFilterCriteria {
+    /// Returns true if the log matches the filter.
+    fn matches(&self, log: &PrimitiveLog) -> bool {
+        if let Some(addresses) = self.addresses.as_ref().filter(|addresses| !addresses.is_empty()) {
+            if !addresses.iter().any(|address| address.0 == log.address) {
+                return false;
+            }
+        }
+
+        let topics = self.topics.as_deref().unwrap_or_default();
+        let log_topics = log.data.topics();
+        if topics.len() > log_topics.len() {
+            return false;
+        }
+        topics.iter().zip(log_topics).all(|(topics, log_topic)| {
+            topics.is_empty() || topics.iter().any(|topic| topic.0 == *log_topic)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::tests::MockBackend;
+    use async_graphql::value;
+    use reth_primitives::alloy_primitives::LogData;
+    use std::sync::atomic::Ordering;
+
+    async fn execute(backend: Arc<MockBackend>, query: &str) -> async_graphql::Response {
+        build_schema(backend).execute(query).await
+    }
+
+    #[tokio::test]
+    async fn query_blocks() {
+        let backend = Arc::new(MockBackend::new(3));
+        let res = execute(backend.clone(), "{ block { number parent { number } } }").await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data,
+            value!({ "block": { "number": "0x3", "parent": { "number": "0x2" } } })
+        );
+
+        let res = execute(backend.clone(), "{ blocks(from: 1) { number } }").await;
+        assert_eq!(
+            res.data,
+            value!({ "blocks": [{ "number": "0x1" }, { "number": "0x2" }, { "number": "0x3" }] })
+        );
+
+        // the range ends at the first missing block
+        let res = execute(backend.clone(), "{ blocks(from: 2, to: 10) { number } }").await;
+        assert_eq!(res.data, value!({ "blocks": [{ "number": "0x2" }, { "number": "0x3" }] }));
+
+        let query = format!("{{ block(number: 1, hash: \"{}\") {{ number }} }}", B256::ZERO);
+        let res = execute(backend, &query).await;
+        assert_eq!(res.errors[0].message, "only one of number or hash must be set");
+    }
+
+    #[tokio::test]
+    async fn query_blocks_range_is_capped() {
+        let backend = Arc::new(MockBackend::new(3));
+        let query = format!("{{ blocks(from: 0, to: {MAX_BLOCKS_RANGE}) {{ number }} }}");
+        let res = execute(backend.clone(), &query).await;
+        assert_eq!(
+            res.errors[0].message,
+            format!("block range exceeds the maximum of {MAX_BLOCKS_RANGE} blocks")
+        );
+        // no block was loaded for the rejected range
+        assert_eq!(backend.loaded_blocks.load(Ordering::Relaxed), 0);
+
+        let query = format!("{{ blocks(from: 1, to: {MAX_BLOCKS_RANGE}) {{ number }} }}");
+        assert!(execute(backend, &query).await.errors.is_empty());
+    }
+
+    #[tokio::test]
+    async fn query_depth_and_complexity_are_limited() {
+        let backend = Arc::new(MockBackend::new(3));
+
+        let mut query = "number".to_string();
+        for _ in 0..MAX_QUERY_DEPTH {
+            query = format!("parent {{ {query} }}");
+        }
+        let res = execute(backend.clone(), &format!("{{ block {{ {query} }} }}")).await;
+        assert_eq!(res.errors[0].message, "Query is nested too deep.");
+
+        let fields = (0..MAX_QUERY_COMPLEXITY)
+            .map(|i| format!("b{i}: block {{ number }}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let res = execute(backend.clone(), &format!("{{ {fields} }}")).await;
+        assert_eq!(res.errors[0].message, "Query is too complex.");
+        assert_eq!(backend.loaded_blocks.load(Ordering::Relaxed), 0);
+    }
+
+    #[tokio::test]
+    async fn query_accounts_and_fees() {
+        let backend = Arc::new(MockBackend::new(0));
+        let res = execute(
+            backend,
+            r#"{ chainID gasPrice maxPriorityFeePerGas syncing
+                 block { account(address: "0x0000000000000000000000000000000000000001") {
+                     balance transactionCount code } } }"#,
+        )
+        .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data,
+            value!({
+                "chainID": "0x1",
+                "gasPrice": "0x7",
+                "maxPriorityFeePerGas": "0x1",
+                "syncing": null,
+                "block": { "account": { "balance": "0x3e8", "transactionCount": "0x1", "code": "0x" } },
+            })
+        );
+    }
+
+    #[test]
+    fn block_filter_matches_logs() {
+        let topic = |byte| Bytes32(B256::with_last_byte(byte));
+        let log = PrimitiveLog {
+            address: PrimitiveAddress::with_last_byte(1),
+            data: LogData::new_unchecked(
+                vec![B256::with_last_byte(1), B256::with_last_byte(2)],
+                PrimitiveBytes::new(),
+            ),
+        };
+        let filter =
+            |addresses: Option<Vec<u8>>, topics: Option<Vec<Vec<Bytes32>>>| BlockFilterCriteria {
+                addresses: addresses.map(|addresses| {
+                    addresses
+                        .into_iter()
+                        .map(|byte| Address(PrimitiveAddress::with_last_byte(byte)))
+                        .collect()
+                }),
+                topics,
+            };
+
+        assert!(filter(None, None).matches(&log));
+        assert!(filter(Some(vec![]), Some(vec![])).matches(&log));
+        assert!(filter(Some(vec![2, 1]), None).matches(&log));
+        assert!(!filter(Some(vec![2]), None).matches(&log));
+        assert!(filter(None, Some(vec![vec![], vec![topic(3), topic(2)]])).matches(&log));
+        assert!(!filter(None, Some(vec![vec![topic(2)]])).matches(&log));
+        assert!(!filter(None, Some(vec![vec![], vec![], vec![]])).matches(&log));
+    }
+}
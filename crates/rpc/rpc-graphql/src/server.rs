@@ -0,0 +1,185 @@
+use crate::schema::GraphQlSchema;
+use async_graphql::{http::parse_query_string, BatchRequest};
+use http::{header, Method, StatusCode};
+use http_body_util::{BodyExt, Limited};
+use jsonrpsee::server::{HttpBody, HttpRequest, HttpResponse};
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// The path the GraphQL endpoint is served at, like geth does.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// The maximum size of a request body.
+const MAX_REQUEST_BODY_SIZE: usize = 5 * 1024 * 1024;
+
+/// A [`Layer`] that serves the schema at [`GRAPHQL_PATH`] of an HTTP server, all other requests are
+/// passed to the inner service.
+///
+/// Queries are accepted as the JSON body of `POST` requests or as the `query` parameter of `GET`
+/// requests. Since the layer is part of the HTTP middleware of the server, the CORS policy and
+/// authentication of the server apply to the queries as well.
+#[derive(Clone)]
+pub struct GraphQlLayer {
+    schema: GraphQlSchema,
+}
+
+impl GraphQlLayer {
+    /// Creates a new layer that serves the given schema.
+    pub const fn new(schema: GraphQlSchema) -> Self {
+        Self { schema }
+    }
+}
+
+impl fmt::Debug for GraphQlLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQlLayer").finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for GraphQlLayer {
+    type Service = GraphQlService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GraphQlService { schema: self.schema.clone(), inner }
+    }
+}
+
+/// An HTTP middleware that executes the queries sent to [`GRAPHQL_PATH`], see [`GraphQlLayer`].
+#[derive(Clone)]
+pub struct GraphQlService<S> {
+    schema: GraphQlSchema,
+    inner: S,
+}
+
+impl<S> fmt::Debug for GraphQlService<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GraphQlService").finish_non_exhaustive()
+    }
+}
+
+impl<S> Service<HttpRequest> for GraphQlService<S>
+where
+    S: Service<HttpRequest, Response = HttpResponse>,
+    S::Future: Send + 'static,
+{
+    type Response = HttpResponse;
+    type Error = S::Error;
+    type Future =
+        Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: HttpRequest) -> Self::Future {
+        if req.uri().path() != GRAPHQL_PATH {
+            return Box::pin(self.inner.call(req))
+        }
+
+        let schema = self.schema.clone();
+        Box::pin(async move { Ok(handle_request(&schema, req).await) })
+    }
+}
+
+/// Executes the queries of the request.
+async fn handle_request(schema: &GraphQlSchema, req: HttpRequest) -> HttpResponse {
+    let batch = match *req.method() {
+        Method::GET => match parse_query_string(req.uri().query().unwrap_or_default()) {
+            Ok(request) => BatchRequest::Single(request),
+            Err(err) => return response(StatusCode::BAD_REQUEST, err.to_string()),
+        },
+        Method::POST => {
+            let body = match Limited::new(req.into_body(), MAX_REQUEST_BODY_SIZE).collect().await {
+                Ok(body) => body.to_bytes(),
+                Err(err) => return response(StatusCode::PAYLOAD_TOO_LARGE, err.to_string()),
+            };
+            match serde_json::from_slice::<BatchRequest>(&body) {
+                Ok(batch) => batch,
+                Err(err) => return response(StatusCode::BAD_REQUEST, err.to_string()),
+            }
+        }
+        _ => return response(StatusCode::METHOD_NOT_ALLOWED, "method not allowed".to_string()),
+    };
+
+    match serde_json::to_string(&schema.execute_batch(batch).await) {
+        Ok(body) => response(StatusCode::OK, body),
+        Err(err) => response(StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
+
+/// Returns a response with the given status and JSON or error message body.
+fn response(status: StatusCode, body: String) -> HttpResponse {
+    let content_type = if status.is_success() { "application/json" } else { "text/plain" };
+    let mut response = HttpResponse::new(HttpBody::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, header::HeaderValue::from_static(content_type));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{backend::tests::MockBackend, build_schema};
+    use std::{convert::Infallible, sync::Arc};
+    use tower::ServiceExt;
+
+    fn service() -> GraphQlService<impl Service<HttpRequest, Response = HttpResponse> + Clone> {
+        let inner = tower::service_fn(|_req: HttpRequest| async {
+            Ok::<_, Infallible>(HttpResponse::new(HttpBody::from("rpc")))
+        });
+        GraphQlLayer::new(build_schema(Arc::new(MockBackend::new(3)))).layer(inner)
+    }
+
+    async fn body(response: HttpResponse) -> String {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn serves_graphql_path() {
+        let req = HttpRequest::builder()
+            .method(Method::POST)
+            .uri(GRAPHQL_PATH)
+            .body(HttpBody::from(r#"{"query": "{ block { number } }"}"#))
+            .unwrap();
+        let response = service().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(body(response).await, r#"{"data":{"block":{"number":"0x3"}}}"#);
+
+        // `{ block(number: 1) { number } }`
+        let query = "%7B%20block(number%3A%201)%20%7B%20number%20%7D%20%7D";
+        let req = HttpRequest::builder()
+            .method(Method::GET)
+            .uri(format!("{GRAPHQL_PATH}?query={query}"))
+            .body(HttpBody::empty())
+            .unwrap();
+        let response = service().oneshot(req).await.unwrap();
+        assert_eq!(body(response).await, r#"{"data":{"block":{"number":"0x1"}}}"#);
+
+        let req = HttpRequest::builder()
+            .method(Method::PUT)
+            .uri(GRAPHQL_PATH)
+            .body(HttpBody::empty())
+            .unwrap();
+        let response = service().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn passes_other_paths() {
+        let req = HttpRequest::builder()
+            .method(Method::POST)
+            .uri("/")
+            .body(HttpBody::from(r#"{"jsonrpc":"2.0","id":1,"method":"eth_blockNumber"}"#))
+            .unwrap();
+        let response = service().oneshot(req).await.unwrap();
+        assert_eq!(body(response).await, "rpc");
+    }
+}
@@ -0,0 +1,521 @@
+//! Conversions between the protobuf messages and the types of the handlers.
+
+use crate::proto;
+use reth_primitives::{
+    hex, AccessList, AccessListItem, Address, BlockId, BlockNumberOrTag, Receipt,
+    SealedBlockWithSenders, SealedHeader, TransactionSignedEcRecovered, TxKind, B256, U256,
+};
+use reth_rpc::eth::TransactionSource;
+use reth_rpc_types::{
+    trace::geth::{CallFrame, CallLogFrame, DefaultFrame, GethTrace, StructLog},
+    Filter, Log, TransactionInput, TransactionRequest,
+};
+use tonic::Status;
+
+/// Returns the error for a field with an invalid value.
+fn invalid(field: &str) -> Status {
+    Status::invalid_argument(format!("invalid {field}"))
+}
+
+/// Decodes an address.
+pub(crate) fn address(bytes: &[u8], field: &str) -> Result<Address, Status> {
+    if bytes.len() != 20 {
+        return Err(invalid(field))
+    }
+    Ok(Address::from_slice(bytes))
+}
+
+/// Decodes a hash.
+pub(crate) fn b256(bytes: &[u8], field: &str) -> Result<B256, Status> {
+    if bytes.len() != 32 {
+        return Err(invalid(field))
+    }
+    Ok(B256::from_slice(bytes))
+}
+
+/// Decodes a big-endian quantity of up to 32 bytes.
+fn u256(bytes: &[u8], field: &str) -> Result<U256, Status> {
+    U256::try_from_be_slice(bytes).ok_or_else(|| invalid(field))
+}
+
+/// Encodes a quantity as 32 bytes, big-endian.
+pub(crate) fn u256_to_proto(value: U256) -> Vec<u8> {
+    value.to_be_bytes::<32>().to_vec()
+}
+
+/// Decodes the block id, the latest block if it's missing.
+pub(crate) fn block_id(id: Option<proto::BlockId>) -> Result<BlockId, Status> {
+    let Some(id) = id.and_then(|id| id.id) else { return Ok(BlockNumberOrTag::Latest.into()) };
+    Ok(match id {
+        proto::block_id::Id::Number(number) => BlockNumberOrTag::Number(number).into(),
+        proto::block_id::Id::Hash(hash) => b256(&hash, "block hash")?.into(),
+        proto::block_id::Id::Tag(tag) => {
+            let tag = match proto::BlockTag::try_from(tag) {
+                Ok(proto::BlockTag::Latest) => BlockNumberOrTag::Latest,
+                Ok(proto::BlockTag::Earliest) => BlockNumberOrTag::Earliest,
+                Ok(proto::BlockTag::Pending) => BlockNumberOrTag::Pending,
+                Ok(proto::BlockTag::Safe) => BlockNumberOrTag::Safe,
+                Ok(proto::BlockTag::Finalized) => BlockNumberOrTag::Finalized,
+                Err(_) => return Err(invalid("block tag")),
+            };
+            tag.into()
+        }
+    })
+}
+
+/// Decodes the call into a transaction request.
+pub(crate) fn call_request(call: Option<proto::Call>) -> Result<TransactionRequest, Status> {
+    let call = call.ok_or_else(|| Status::invalid_argument("missing call"))?;
+    let fee = |fee: Option<Vec<u8>>, field| -> Result<Option<u128>, Status> {
+        fee.map(|fee| u256(&fee, field).map(|fee| fee.saturating_to())).transpose()
+    };
+    let access_list = call
+        .access_list
+        .into_iter()
+        .map(|item| {
+            Ok(AccessListItem {
+                address: address(&item.address, "access list address")?,
+                storage_keys: item
+                    .storage_keys
+                    .iter()
+                    .map(|key| b256(key, "access list storage key"))
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect::<Result<Vec<_>, Status>>()?;
+
+    Ok(TransactionRequest {
+        from: call.from.map(|from| address(&from, "from")).transpose()?,
+        to: Some(match call.to {
+            Some(to) => TxKind::Call(address(&to, "to")?),
+            None => TxKind::Create,
+        }),
+        gas: call.gas.map(u128::from),
+        gas_price: fee(call.gas_price, "gas price")?,
+        max_fee_per_gas: fee(call.max_fee_per_gas, "max fee per gas")?,
+        max_priority_fee_per_gas: fee(call.max_priority_fee_per_gas, "max priority fee per gas")?,
+        value: call.value.map(|value| u256(&value, "value")).transpose()?,
+        input: TransactionInput::new(call.input.into()),
+        nonce: call.nonce,
+        access_list: (!access_list.is_empty()).then(|| AccessList(access_list)),
+        ..Default::default()
+    })
+}
+
+/// Decodes the log filter.
+pub(crate) fn filter(request: proto::GetLogsRequest) -> Result<Filter, Status> {
+    let mut filter = Filter::new();
+    if let Some(hash) = request.block_hash {
+        filter = filter.at_block_hash(b256(&hash, "block hash")?);
+    } else {
+        if let Some(from) = request.from_block {
+            filter = filter.from_block(from);
+        }
+        if let Some(to) = request.to_block {
+            filter = filter.to_block(to);
+        }
+    }
+    if !request.addresses.is_empty() {
+        let addresses = request
+            .addresses
+            .iter()
+            .map(|addr| address(addr, "address"))
+            .collect::<Result<Vec<_>, _>>()?;
+        filter = filter.address(addresses);
+    }
+    if request.topics.len() > filter.topics.len() {
+        return Err(Status::invalid_argument("too many topics"))
+    }
+    for (position, topics) in request.topics.into_iter().enumerate() {
+        filter.topics[position] = topics
+            .topics
+            .iter()
+            .map(|topic| b256(topic, "topic"))
+            .collect::<Result<Vec<_>, _>>()?
+            .into();
+    }
+    Ok(filter)
+}
+
+/// Encodes the block, with its transactions if `full_transactions` is set.
+pub(crate) fn block_to_proto(
+    block: &SealedBlockWithSenders,
+    full_transactions: bool,
+) -> proto::Block {
+    let transactions = if full_transactions {
+        block
+            .body
+            .iter()
+            .zip(&block.senders)
+            .enumerate()
+            .map(|(index, (transaction, sender))| proto::Transaction {
+                hash: transaction.hash().to_vec(),
+                from: sender.to_vec(),
+                raw: transaction.envelope_encoded().to_vec(),
+                location: Some(proto::TransactionLocation {
+                    block_hash: block.hash().to_vec(),
+                    block_number: block.number,
+                    index: index as u64,
+                }),
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    proto::Block {
+        header: Some(header_to_proto(&block.header)),
+        transaction_hashes: block.body.iter().map(|tx| tx.hash().to_vec()).collect(),
+        transactions,
+        ommer_hashes: block.ommers.iter().map(|ommer| ommer.hash_slow().to_vec()).collect(),
+        withdrawals: block
+            .withdrawals
+            .iter()
+            .flat_map(|withdrawals| withdrawals.iter())
+            .map(|withdrawal| proto::Withdrawal {
+                index: withdrawal.index,
+                validator_index: withdrawal.validator_index,
+                address: withdrawal.address.to_vec(),
+                amount: withdrawal.amount,
+            })
+            .collect(),
+    }
+}
+
+fn header_to_proto(header: &SealedHeader) -> proto::Header {
+    proto::Header {
+        hash: header.hash().to_vec(),
+        parent_hash: header.parent_hash.to_vec(),
+        ommers_hash: header.ommers_hash.to_vec(),
+        beneficiary: header.beneficiary.to_vec(),
+        state_root: header.state_root.to_vec(),
+        transactions_root: header.transactions_root.to_vec(),
+        receipts_root: header.receipts_root.to_vec(),
+        logs_bloom: header.logs_bloom.to_vec(),
+        difficulty: u256_to_proto(header.difficulty),
+        number: header.number,
+        gas_limit: header.gas_limit,
+        gas_used: header.gas_used,
+        timestamp: header.timestamp,
+        extra_data: header.extra_data.to_vec(),
+        mix_hash: header.mix_hash.to_vec(),
+        nonce: header.nonce,
+        base_fee_per_gas: header.base_fee_per_gas,
+        withdrawals_root: header.withdrawals_root.map(|root| root.to_vec()),
+        blob_gas_used: header.blob_gas_used,
+        excess_blob_gas: header.excess_blob_gas,
+        parent_beacon_block_root: header.parent_beacon_block_root.map(|root| root.to_vec()),
+        requests_root: header.requests_root.map(|root| root.to_vec()),
+    }
+}
+
+impl From<TransactionSource> for proto::Transaction {
+    fn from(source: TransactionSource) -> Self {
+        let (transaction, location) = match source {
+            TransactionSource::Pool(transaction) => (transaction, None),
+            TransactionSource::Block { transaction, index, block_hash, block_number, .. } => {
+                let location = proto::TransactionLocation {
+                    block_hash: block_hash.to_vec(),
+                    block_number,
+                    index,
+                };
+                (transaction, Some(location))
+            }
+        };
+        Self {
+            hash: transaction.hash().to_vec(),
+            from: transaction.signer().to_vec(),
+            raw: transaction.envelope_encoded().to_vec(),
+            location,
+        }
+    }
+}
+
+/// The block of a transaction, for the encoding of its receipt.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ReceiptBlock {
+    pub(crate) hash: B256,
+    pub(crate) number: u64,
+    pub(crate) base_fee: Option<u64>,
+}
+
+/// Encodes the receipt of the transaction at the given index of the block, from the receipts of
+/// the block.
+///
+/// Returns `None` if there's no receipt for the index.
+pub(crate) fn receipt_to_proto(
+    transaction: &TransactionSignedEcRecovered,
+    index: usize,
+    block: ReceiptBlock,
+    receipts: &[Receipt],
+) -> Option<proto::Receipt> {
+    let receipt = receipts.get(index)?;
+    let previous = index.checked_sub(1).and_then(|previous| receipts.get(previous));
+    let gas_used = receipt.cumulative_gas_used -
+        previous.map(|previous| previous.cumulative_gas_used).unwrap_or_default();
+    let first_log_index = receipts[..index].iter().map(|receipt| receipt.logs.len()).sum::<usize>();
+
+    let from = transaction.signer();
+    let contract_address = match transaction.kind() {
+        TxKind::Create => Some(from.create(transaction.nonce()).to_vec()),
+        TxKind::Call(_) => None,
+    };
+    let logs = receipt
+        .logs
+        .iter()
+        .enumerate()
+        .map(|(log_index, log)| proto::Log {
+            address: log.address.to_vec(),
+            topics: log.topics().iter().map(|topic| topic.to_vec()).collect(),
+            data: log.data.data.to_vec(),
+            block_hash: Some(block.hash.to_vec()),
+            block_number: Some(block.number),
+            transaction_hash: Some(transaction.hash().to_vec()),
+            transaction_index: Some(index as u64),
+            log_index: Some((first_log_index + log_index) as u64),
+            removed: false,
+        })
+        .collect();
+
+    Some(proto::Receipt {
+        transaction_hash: transaction.hash().to_vec(),
+        location: Some(proto::TransactionLocation {
+            block_hash: block.hash.to_vec(),
+            block_number: block.number,
+            index: index as u64,
+        }),
+        from: from.to_vec(),
+        to: transaction.to().map(|to| to.to_vec()),
+        contract_address,
+        tx_type: u8::from(receipt.tx_type).into(),
+        success: receipt.success,
+        cumulative_gas_used: receipt.cumulative_gas_used,
+        gas_used,
+        effective_gas_price: u256_to_proto(U256::from(
+            transaction.effective_gas_price(block.base_fee),
+        )),
+        logs,
+    })
+}
+
+impl From<Log> for proto::Log {
+    fn from(log: Log) -> Self {
+        Self {
+            address: log.inner.address.to_vec(),
+            topics: log.inner.data.topics().iter().map(|topic| topic.to_vec()).collect(),
+            data: log.inner.data.data.to_vec(),
+            block_hash: log.block_hash.map(|hash| hash.to_vec()),
+            block_number: log.block_number,
+            transaction_hash: log.transaction_hash.map(|hash| hash.to_vec()),
+            transaction_index: log.transaction_index,
+            log_index: log.log_index,
+            removed: log.removed,
+        }
+    }
+}
+
+/// Converts a trace into its message, the trace of tracers without a message of their own is JSON
+/// encoded.
+pub(crate) fn trace_to_proto(trace: GethTrace) -> Result<proto::TraceResponse, Status> {
+    let trace = match trace {
+        GethTrace::Default(frame) => proto::trace_response::Trace::StructLogs(frame.try_into()?),
+        GethTrace::CallTracer(frame) => proto::trace_response::Trace::Call(frame.into()),
+        trace => proto::trace_response::Trace::Json(
+            serde_json::to_vec(&trace).map_err(|err| Status::internal(err.to_string()))?,
+        ),
+    };
+    Ok(proto::TraceResponse { trace: Some(trace) })
+}
+
+impl TryFrom<DefaultFrame> for proto::StructLogTrace {
+    type Error = Status;
+
+    fn try_from(frame: DefaultFrame) -> Result<Self, Self::Error> {
+        Ok(Self {
+            failed: frame.failed,
+            gas: frame.gas,
+            return_value: frame.return_value.to_vec(),
+            struct_logs: frame
+                .struct_logs
+                .into_iter()
+                .map(proto::StructLog::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl TryFrom<StructLog> for proto::StructLog {
+    type Error = Status;
+
+    fn try_from(log: StructLog) -> Result<Self, Self::Error> {
+        // the memory words are hex encoded by the tracer
+        let memory = log
+            .memory
+            .unwrap_or_default()
+            .into_iter()
+            .map(hex::decode)
+            .collect::<Result<_, _>>()
+            .map_err(|err| Status::internal(format!("invalid memory word: {err}")))?;
+        Ok(Self {
+            pc: log.pc,
+            op: log.op,
+            gas: log.gas,
+            gas_cost: log.gas_cost,
+            depth: log.depth,
+            error: log.error,
+            stack: log.stack.unwrap_or_default().into_iter().map(u256_to_proto).collect(),
+            return_data: log.return_data.map(|data| data.to_vec()),
+            memory,
+            memory_size: log.memory_size,
+            storage: log
+                .storage
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(slot, value)| proto::StorageSlot {
+                    slot: slot.to_vec(),
+                    value: value.to_vec(),
+                })
+                .collect(),
+            refund_counter: log.refund_counter,
+        })
+    }
+}
+
+impl From<CallFrame> for proto::CallFrame {
+    fn from(frame: CallFrame) -> Self {
+        Self {
+            r#type: frame.typ,
+            from: frame.from.to_vec(),
+            to: frame.to.map(|to| to.to_vec()),
+            value: frame.value.map(u256_to_proto),
+            gas: frame.gas.saturating_to(),
+            gas_used: frame.gas_used.saturating_to(),
+            input: frame.input.to_vec(),
+            output: frame.output.map(|output| output.to_vec()),
+            error: frame.error,
+            revert_reason: frame.revert_reason,
+            calls: frame.calls.into_iter().map(Self::from).collect(),
+            logs: frame.logs.into_iter().map(proto::CallLog::from).collect(),
+        }
+    }
+}
+
+impl From<CallLogFrame> for proto::CallLog {
+    fn from(log: CallLogFrame) -> Self {
+        Self {
+            address: log.address.map(|address| address.to_vec()),
+            topics: log.topics.unwrap_or_default().iter().map(|topic| topic.to_vec()).collect(),
+            data: log.data.map(|data| data.to_vec()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::trace::geth::NoopFrame;
+
+    #[test]
+    fn decode_block_id() {
+        assert_eq!(block_id(None).unwrap(), BlockNumberOrTag::Latest.into());
+
+        let id = |id| Some(proto::BlockId { id: Some(id) });
+        assert_eq!(
+            block_id(id(proto::block_id::Id::Number(5))).unwrap(),
+            BlockNumberOrTag::Number(5).into()
+        );
+        assert_eq!(
+            block_id(id(proto::block_id::Id::Tag(proto::BlockTag::Finalized.into()))).unwrap(),
+            BlockNumberOrTag::Finalized.into()
+        );
+        assert_eq!(
+            block_id(id(proto::block_id::Id::Hash(vec![1; 32]))).unwrap(),
+            B256::repeat_byte(1).into()
+        );
+        assert!(block_id(id(proto::block_id::Id::Hash(vec![1; 20]))).is_err());
+        assert!(block_id(id(proto::block_id::Id::Tag(10))).is_err());
+    }
+
+    #[test]
+    fn decode_call_request() {
+        let call = proto::Call {
+            to: Some(vec![2; 20]),
+            gas: Some(21_000),
+            value: Some(vec![1, 0]),
+            input: vec![1, 2, 3],
+            ..Default::default()
+        };
+        let request = call_request(Some(call)).unwrap();
+        assert_eq!(request.to, Some(TxKind::Call(Address::repeat_byte(2))));
+        assert_eq!(request.gas, Some(21_000));
+        assert_eq!(request.value, Some(U256::from(256)));
+        assert_eq!(request.input.input().unwrap().as_ref(), &[1, 2, 3]);
+        assert_eq!(request.access_list, None);
+
+        let call = proto::Call { from: Some(vec![1; 19]), ..Default::default() };
+        assert!(call_request(Some(call)).is_err());
+        assert!(call_request(None).is_err());
+    }
+
+    #[test]
+    fn decode_filter() {
+        let request = proto::GetLogsRequest {
+            from_block: Some(1),
+            to_block: Some(2),
+            topics: vec![proto::Topics { topics: vec![vec![3; 32]] }],
+            ..Default::default()
+        };
+        let filter = filter(request).unwrap();
+        assert_eq!(filter.get_from_block(), Some(1));
+        assert_eq!(filter.get_to_block(), Some(2));
+        assert!(filter.topics[0].matches(&B256::repeat_byte(3)));
+
+        let request = proto::GetLogsRequest {
+            topics: vec![proto::Topics::default(); 5],
+            ..Default::default()
+        };
+        assert!(filter(request).is_err());
+    }
+
+    #[test]
+    fn encode_trace() {
+        let log = StructLog {
+            pc: 1,
+            op: "SLOAD".to_string(),
+            stack: Some(vec![U256::from(2)]),
+            memory: Some(vec![format!("{:064x}", 3)]),
+            storage: Some([(B256::repeat_byte(4), B256::repeat_byte(5))].into()),
+            ..Default::default()
+        };
+        let frame = DefaultFrame { gas: 21_000, struct_logs: vec![log], ..Default::default() };
+        let Some(proto::trace_response::Trace::StructLogs(trace)) =
+            trace_to_proto(GethTrace::Default(frame)).unwrap().trace
+        else {
+            panic!("expected struct logs")
+        };
+        assert_eq!(trace.gas, 21_000);
+        let log = &trace.struct_logs[0];
+        assert_eq!(log.op, "SLOAD");
+        assert_eq!(log.stack, vec![u256_to_proto(U256::from(2))]);
+        assert_eq!(log.memory, vec![u256_to_proto(U256::from(3))]);
+        assert_eq!(log.storage[0].value, vec![5; 32]);
+
+        let frame = CallFrame {
+            typ: "CALL".to_string(),
+            gas: U256::MAX,
+            calls: vec![CallFrame { typ: "STATICCALL".to_string(), ..Default::default() }],
+            ..Default::default()
+        };
+        let Some(proto::trace_response::Trace::Call(frame)) =
+            trace_to_proto(GethTrace::CallTracer(frame)).unwrap().trace
+        else {
+            panic!("expected a call frame")
+        };
+        assert_eq!(frame.r#type, "CALL");
+        assert_eq!(frame.gas, u64::MAX);
+        assert_eq!(frame.calls[0].r#type, "STATICCALL");
+
+        let trace = trace_to_proto(GethTrace::NoopTracer(NoopFrame::default())).unwrap();
+        assert_eq!(trace.trace, Some(proto::trace_response::Trace::Json(b"{}".to_vec())));
+    }
+}
@@ -0,0 +1,106 @@
+//! gRPC gateway for the `eth` and `debug` namespaces.
+//!
+//! The gateway serves the most frequently used methods of the namespaces with protobuf messages
+//! over HTTP/2, for consumers that suffer from the overhead of the JSON encoding of JSON-RPC.
+//! Clients in any language can be generated from the schema in `proto/rpc.proto`.
+//!
+//! The requests are forwarded to the same handlers that serve JSON-RPC, see [`EthService`] and
+//! [`DebugService`], so both transports behave the same. The access log, namespace authentication,
+//! rate limits and usage accounting of the RPC servers apply to the gRPC requests as well, under
+//! the name of the JSON-RPC method that serves the same request.
+//!
+//! The server also serves JSON-RPC requests over HTTP/2, see [`GrpcServices::with_json_rpc`].
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+
+use jsonrpsee::RpcModule;
+use middleware::GrpcMiddlewareLayer;
+use reth_rpc_builder::RpcRequestMiddleware;
+use reth_tasks::TaskExecutor;
+use std::{io, net::SocketAddr};
+use tokio::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+use tracing::error;
+
+mod codec;
+mod middleware;
+mod service;
+pub use service::{DebugService, EthService};
+
+/// The generated protobuf types, and the gRPC servers and clients.
+#[allow(missing_docs, unreachable_pub, clippy::all)]
+pub mod proto {
+    tonic::include_proto!("rpc");
+}
+
+/// The services of the gRPC server.
+#[derive(Debug)]
+pub struct GrpcServices<E, D> {
+    eth: Option<E>,
+    debug: Option<D>,
+    json_rpc: Option<RpcModule<()>>,
+    middleware: RpcRequestMiddleware,
+}
+
+impl<E, D> GrpcServices<E, D> {
+    /// Creates the services with the given `EthApi` and `DebugApi` services, a service is disabled
+    /// if it's `None`.
+    pub fn new(eth: Option<E>, debug: Option<D>) -> Self {
+        Self { eth, debug, json_rpc: None, middleware: Default::default() }
+    }
+
+    /// Serves JSON-RPC requests over HTTP/2 with the methods of the module.
+    ///
+    /// JSON-RPC requests are `POST` requests with a JSON body. Batches and subscriptions aren't
+    /// supported.
+    pub fn with_json_rpc(mut self, module: Option<RpcModule<()>>) -> Self {
+        self.json_rpc = module;
+        self
+    }
+
+    /// Applies the middleware to all requests, see
+    /// [`RpcServerConfig::grpc_middleware`](reth_rpc_builder::RpcServerConfig::grpc_middleware).
+    pub fn with_middleware(mut self, middleware: RpcRequestMiddleware) -> Self {
+        self.middleware = middleware;
+        self
+    }
+}
+
+/// Starts the gRPC server with the services at the given address, and returns the address it
+/// listens on.
+///
+/// The server stops when the executor shuts down.
+pub async fn start_server<E, D>(
+    addr: SocketAddr,
+    services: GrpcServices<E, D>,
+    executor: &TaskExecutor,
+) -> io::Result<SocketAddr>
+where
+    E: proto::eth_api_server::EthApi,
+    D: proto::debug_api_server::DebugApi,
+{
+    let listener = TcpListener::bind(addr).await?;
+    let local_addr = listener.local_addr()?;
+
+    let GrpcServices { eth, debug, json_rpc, middleware } = services;
+    executor.spawn_critical_with_shutdown_signal("grpc server", |shutdown| async move {
+        let res = Server::builder()
+            .layer(GrpcMiddlewareLayer::new(middleware, json_rpc))
+            .add_optional_service(eth.map(proto::eth_api_server::EthApiServer::new))
+            .add_optional_service(debug.map(proto::debug_api_server::DebugApiServer::new))
+            .serve_with_incoming_shutdown(TcpListenerStream::new(listener), shutdown)
+            .await;
+        if let Err(err) = res {
+            error!(target: "rpc::grpc", %err, "gRPC server failed");
+        }
+    });
+
+    Ok(local_addr)
+}
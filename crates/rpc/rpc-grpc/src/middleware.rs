@@ -0,0 +1,382 @@
+//! The middleware of the gRPC server, which applies the [`RpcRequestMiddleware`] of the RPC
+//! servers to every request and serves JSON-RPC requests over HTTP/2.
+
+use crate::service::rpc_status;
+use jsonrpsee::{
+    server::MethodCallback,
+    types::{
+        error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE, METHOD_NOT_FOUND_CODE},
+        ErrorCode, ErrorObject, ErrorObjectOwned, Id, Request,
+    },
+    MethodResponse, RpcModule,
+};
+use reth_rpc_builder::{RpcRequest, RpcRequestGuard, RpcRequestMiddleware};
+use reth_rpc_server_types::rate_limit::RPC_API_KEY_HEADER;
+use serde::Deserialize;
+use std::{
+    net::IpAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tonic::{
+    body::BoxBody,
+    codegen::{
+        http::{
+            self,
+            header::{AUTHORIZATION, CONTENT_TYPE},
+            HeaderMap, HeaderValue,
+        },
+        Body as HttpBody, BoxFuture, Bytes,
+    },
+    transport::{server::TcpConnectInfo, Body},
+    Code, Status,
+};
+use tower::{Layer, Service};
+
+/// The max size of the body of a JSON-RPC request, the default of the HTTP server.
+const MAX_REQUEST_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// Returns the JSON-RPC method that serves the same request as the gRPC method of the path.
+fn rpc_method(path: &str) -> Option<&'static str> {
+    let method = match path.strip_prefix("/rpc.")? {
+        "EthApi/BlockNumber" => "eth_blockNumber",
+        "EthApi/ChainId" => "eth_chainId",
+        "EthApi/GetBlock" => "eth_getBlockByNumber",
+        "EthApi/GetBlockReceipts" => "eth_getBlockReceipts",
+        "EthApi/GetTransaction" => "eth_getTransactionByHash",
+        "EthApi/GetTransactionReceipt" => "eth_getTransactionReceipt",
+        "EthApi/GetBalance" => "eth_getBalance",
+        "EthApi/GetTransactionCount" => "eth_getTransactionCount",
+        "EthApi/GetCode" => "eth_getCode",
+        "EthApi/GetStorageAt" => "eth_getStorageAt",
+        "EthApi/Call" => "eth_call",
+        "EthApi/EstimateGas" => "eth_estimateGas",
+        "EthApi/GetLogs" => "eth_getLogs",
+        "EthApi/GasPrice" => "eth_gasPrice",
+        "EthApi/MaxPriorityFeePerGas" => "eth_maxPriorityFeePerGas",
+        "EthApi/SendRawTransaction" => "eth_sendRawTransaction",
+        "DebugApi/GetRawHeader" => "debug_getRawHeader",
+        "DebugApi/GetRawBlock" => "debug_getRawBlock",
+        "DebugApi/GetRawTransaction" => "debug_getRawTransaction",
+        "DebugApi/GetRawReceipts" => "debug_getRawReceipts",
+        "DebugApi/TraceTransaction" => "debug_traceTransaction",
+        "DebugApi/TraceCall" => "debug_traceCall",
+        _ => return None,
+    };
+    Some(method)
+}
+
+/// A [`Layer`] that applies the [`RpcRequestMiddleware`] to the gRPC requests, under the name of
+/// the JSON-RPC method that serves the same request, e.g. `eth_call` for `EthApi/Call`.
+///
+/// JSON-RPC requests, `POST` requests with a JSON body, are served with the methods of the
+/// JSON-RPC module, if any.
+#[derive(Debug, Clone)]
+pub(crate) struct GrpcMiddlewareLayer {
+    middleware: RpcRequestMiddleware,
+    json_rpc: Option<Arc<RpcModule<()>>>,
+}
+
+impl GrpcMiddlewareLayer {
+    /// Creates a new layer with the middleware and the JSON-RPC module.
+    pub(crate) fn new(middleware: RpcRequestMiddleware, json_rpc: Option<RpcModule<()>>) -> Self {
+        Self { middleware, json_rpc: json_rpc.map(Arc::new) }
+    }
+}
+
+impl<S> Layer<S> for GrpcMiddlewareLayer {
+    type Service = GrpcMiddleware<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcMiddleware {
+            inner,
+            middleware: self.middleware.clone(),
+            json_rpc: self.json_rpc.clone(),
+        }
+    }
+}
+
+/// The middleware of the gRPC server, see [`GrpcMiddlewareLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct GrpcMiddleware<S> {
+    inner: S,
+    middleware: RpcRequestMiddleware,
+    json_rpc: Option<Arc<RpcModule<()>>>,
+}
+
+impl<S> Service<http::Request<Body>> for GrpcMiddleware<S>
+where
+    S: Service<http::Request<Body>, Response = http::Response<BoxBody>>,
+    S::Future: Send + 'static,
+{
+    type Response = http::Response<BoxBody>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: http::Request<Body>) -> Self::Future {
+        let client = Client::new(&req);
+
+        if let Some(module) = self.json_rpc.clone().filter(|_| is_json_rpc(&req)) {
+            let middleware = self.middleware.clone();
+            return Box::pin(async move {
+                let response = match read_body(req.into_body()).await {
+                    Ok(body) => call_json_rpc(&module, &middleware, &client, &body).await,
+                    Err(err) => MethodResponse::error(Id::Null, err).into_result(),
+                };
+                Ok(json_response(response))
+            })
+        }
+
+        // unknown methods are rejected by the server
+        let Some(method) = rpc_method(req.uri().path()) else {
+            return Box::pin(self.inner.call(req))
+        };
+        let guard = match self.middleware.start(client.request(method, None)) {
+            Ok(guard) => guard,
+            Err(err) => {
+                let response = rpc_status(err).to_http();
+                return Box::pin(async move { Ok(response) })
+            }
+        };
+
+        let fut = self.inner.call(req);
+        Box::pin(async move {
+            let response = fut.await?;
+            // errors are returned in the headers, without a body
+            if let Some(error_code) = error_code(response.headers()) {
+                guard.finish(0, error_code);
+                return Ok(response)
+            }
+            let guarded = |inner| GuardedBody { inner, guard: Some(guard), size: 0 }.boxed_unsync();
+            Ok(response.map(guarded))
+        })
+    }
+}
+
+/// The client of a request, as seen by the [`RpcRequestMiddleware`].
+#[derive(Debug)]
+struct Client {
+    api_key: Option<String>,
+    authorization: Option<String>,
+    remote_ip: Option<IpAddr>,
+}
+
+impl Client {
+    fn new(req: &http::Request<Body>) -> Self {
+        let header = |name: &str| {
+            req.headers().get(name).and_then(|value| value.to_str().ok()).map(ToString::to_string)
+        };
+        Self {
+            api_key: header(RPC_API_KEY_HEADER),
+            authorization: header(AUTHORIZATION.as_str()),
+            remote_ip: req
+                .extensions()
+                .get::<TcpConnectInfo>()
+                .and_then(TcpConnectInfo::remote_addr)
+                .map(|addr| addr.ip()),
+        }
+    }
+
+    /// Returns the request of the client to the method.
+    fn request<'a>(&'a self, method: &'a str, params: Option<&'a str>) -> RpcRequest<'a> {
+        RpcRequest {
+            method,
+            params,
+            api_key: self.api_key.as_deref(),
+            authorization: self.authorization.as_deref(),
+            remote_ip: self.remote_ip,
+        }
+    }
+}
+
+/// Returns the JSON-RPC error code of a response whose headers include the gRPC status, which is
+/// `None` if the request succeeded.
+///
+/// Returns `None` if the status isn't included in the headers, but in the trailers.
+fn error_code(headers: &HeaderMap) -> Option<Option<i32>> {
+    let status = Status::from_header_map(headers)?;
+    if status.code() == Code::Ok {
+        return Some(None)
+    }
+    let code =
+        status.metadata().get("rpc-error-code").and_then(|code| code.to_str().ok()?.parse().ok());
+    Some(Some(code.unwrap_or(match status.code() {
+        Code::InvalidArgument => INVALID_PARAMS_CODE,
+        Code::Unimplemented => METHOD_NOT_FOUND_CODE,
+        _ => INTERNAL_ERROR_CODE,
+    })))
+}
+
+/// A response body that finishes the [`RpcRequestGuard`] of its request once it's sent.
+struct GuardedBody {
+    inner: BoxBody,
+    guard: Option<RpcRequestGuard>,
+    /// The size of the data sent so far.
+    size: usize,
+}
+
+impl GuardedBody {
+    fn finish(&mut self, error_code: Option<i32>) {
+        if let Some(guard) = self.guard.take() {
+            guard.finish(self.size, error_code);
+        }
+    }
+}
+
+impl HttpBody for GuardedBody {
+    type Data = Bytes;
+    type Error = Status;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let res = Pin::new(&mut self.inner).poll_data(cx);
+        if let Poll::Ready(Some(Ok(data))) = &res {
+            self.size += data.len();
+        }
+        res
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        let res = Pin::new(&mut self.inner).poll_trailers(cx);
+        if let Poll::Ready(res) = &res {
+            let error_code = match res {
+                Ok(trailers) => trailers.as_ref().and_then(error_code).flatten(),
+                Err(_) => Some(INTERNAL_ERROR_CODE),
+            };
+            self.finish(error_code);
+        }
+        res
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+}
+
+impl Drop for GuardedBody {
+    fn drop(&mut self) {
+        // the response wasn't sent completely
+        self.finish(Some(INTERNAL_ERROR_CODE));
+    }
+}
+
+/// Returns true if the request is a JSON-RPC request.
+fn is_json_rpc(req: &http::Request<Body>) -> bool {
+    req.method() == http::Method::POST &&
+        req.headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|content_type| content_type.starts_with("application/json"))
+}
+
+/// Reads the body of a JSON-RPC request.
+async fn read_body(mut body: Body) -> Result<String, ErrorObjectOwned> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|_| ErrorObject::from(ErrorCode::ParseError))?;
+        if bytes.len() + chunk.len() > MAX_REQUEST_BODY_SIZE {
+            return Err(ErrorCode::OversizedRequest.into())
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    String::from_utf8(bytes).map_err(|_| ErrorCode::ParseError.into())
+}
+
+/// Serves a JSON-RPC request with the methods of the module, and returns the JSON encoded
+/// response.
+///
+/// Batches aren't supported, and neither are subscriptions, like over HTTP/1.
+async fn call_json_rpc(
+    module: &RpcModule<()>,
+    middleware: &RpcRequestMiddleware,
+    client: &Client,
+    body: &str,
+) -> String {
+    let parse_error = |id| MethodResponse::error(id, ErrorObject::from(ErrorCode::ParseError));
+    let Ok(request) = serde_json::from_str::<Request<'_>>(body) else {
+        return parse_error(Id::Null).into_result()
+    };
+    let method = request.method_name();
+    let guard = match middleware.start(client.request(method, request.params().as_str())) {
+        Ok(guard) => guard,
+        Err(err) => return MethodResponse::error(request.id(), err).into_result(),
+    };
+
+    let response = match module.method(method) {
+        Some(MethodCallback::Subscription(_) | MethodCallback::Unsubscription(_)) => {
+            MethodResponse::error(request.id(), ErrorObject::from(ErrorCode::MethodNotFound))
+                .into_result()
+        }
+        _ => match module.raw_json_request(body, 1).await {
+            Ok((response, _)) => response,
+            Err(_) => parse_error(request.id()).into_result(),
+        },
+    };
+
+    guard.finish(response.len(), json_rpc_error_code(&response));
+    response
+}
+
+/// Returns the error code of a JSON-RPC response, if it's an error.
+fn json_rpc_error_code(response: &str) -> Option<i32> {
+    #[derive(Deserialize)]
+    struct ErrorResponse {
+        error: Option<ResponseError>,
+    }
+
+    #[derive(Deserialize)]
+    struct ResponseError {
+        code: i32,
+    }
+
+    serde_json::from_str::<ErrorResponse>(response).ok()?.error.map(|error| error.code)
+}
+
+/// Returns the HTTP response with the JSON-RPC response.
+fn json_response(body: String) -> http::Response<BoxBody> {
+    let body = Body::from(body).map_err(|err| Status::internal(err.to_string())).boxed_unsync();
+    let mut response = http::Response::new(body);
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grpc_methods() {
+        assert_eq!(rpc_method("/rpc.EthApi/Call"), Some("eth_call"));
+        assert_eq!(rpc_method("/rpc.DebugApi/TraceCall"), Some("debug_traceCall"));
+        assert_eq!(rpc_method("/rpc.EthApi/Unknown"), None);
+        assert_eq!(rpc_method("/"), None);
+    }
+
+    #[test]
+    fn grpc_error_code() {
+        let status = Status::invalid_argument("invalid hash");
+        assert_eq!(error_code(status.to_http().headers()), Some(Some(INVALID_PARAMS_CODE)));
+
+        let err = ErrorObject::owned(3, "execution reverted", None::<()>);
+        assert_eq!(error_code(rpc_status(err).to_http().headers()), Some(Some(3)));
+
+        assert_eq!(error_code(&HeaderMap::new()), None);
+    }
+
+    #[test]
+    fn json_rpc_response_error_code() {
+        let response = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"x"}}"#;
+        assert_eq!(json_rpc_error_code(response), Some(-32601));
+        assert_eq!(json_rpc_error_code(r#"{"jsonrpc":"2.0","id":1,"result":"0x1"}"#), None);
+    }
+}
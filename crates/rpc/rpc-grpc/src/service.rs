@@ -0,0 +1,396 @@
+//! The gRPC services, which forward the requests to the handlers of the `eth` and `debug`
+//! namespaces.
+
+use crate::{
+    codec::{
+        self, address, b256, block_id, block_to_proto, call_request, receipt_to_proto,
+        trace_to_proto, u256_to_proto, ReceiptBlock,
+    },
+    proto::{self, debug_api_server::DebugApi, eth_api_server::EthApi},
+};
+use jsonrpsee::types::{
+    error::{
+        INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE, INVALID_REQUEST_CODE, METHOD_NOT_FOUND_CODE,
+        PARSE_ERROR_CODE,
+    },
+    ErrorObject, ErrorObjectOwned,
+};
+use reth_rpc::eth::{cache::EthStateCache, error::EthApiError, EthTransactions, TransactionSource};
+use reth_rpc_api::{DebugApiServer, EthApiServer, EthFilterApiServer};
+use reth_rpc_builder::{RATE_LIMIT_EXCEEDED_CODE, UNAUTHORIZED_CODE};
+use reth_rpc_types::serde_helpers::JsonStorageKey;
+use serde::de::DeserializeOwned;
+use tonic::{Code, Request, Response, Status};
+
+/// Implements the `EthApi` gRPC service with the handlers of the `eth` namespace.
+#[derive(Debug, Clone)]
+pub struct EthService<Eth, Filter> {
+    eth: Eth,
+    filter: Filter,
+    cache: EthStateCache,
+}
+
+impl<Eth, Filter> EthService<Eth, Filter> {
+    /// Creates a new service with the `eth` API, the filter API and their cache.
+    pub const fn new(eth: Eth, filter: Filter, cache: EthStateCache) -> Self {
+        Self { eth, filter, cache }
+    }
+}
+
+#[tonic::async_trait]
+impl<Eth, Filter> EthApi for EthService<Eth, Filter>
+where
+    Eth: EthApiServer + EthTransactions + 'static,
+    Filter: EthFilterApiServer + 'static,
+{
+    async fn block_number(
+        &self,
+        _request: Request<proto::BlockNumberRequest>,
+    ) -> Result<Response<proto::BlockNumberResponse>, Status> {
+        let number = EthApiServer::block_number(&self.eth).map_err(rpc_status)?;
+        Ok(Response::new(proto::BlockNumberResponse { number: number.saturating_to() }))
+    }
+
+    async fn chain_id(
+        &self,
+        _request: Request<proto::ChainIdRequest>,
+    ) -> Result<Response<proto::ChainIdResponse>, Status> {
+        let chain_id = EthApiServer::chain_id(&self.eth).await.map_err(rpc_status)?;
+        let chain_id = chain_id.map(|chain_id| chain_id.to::<u64>()).unwrap_or_default();
+        Ok(Response::new(proto::ChainIdResponse { chain_id }))
+    }
+
+    async fn get_block(
+        &self,
+        request: Request<proto::GetBlockRequest>,
+    ) -> Result<Response<proto::GetBlockResponse>, Status> {
+        let request = request.into_inner();
+        let block = self
+            .eth
+            .block_by_id_with_senders(block_id(request.block)?)
+            .await
+            .map_err(eth_status)?
+            .map(|block| block_to_proto(&block, request.full_transactions));
+        Ok(Response::new(proto::GetBlockResponse { block }))
+    }
+
+    async fn get_block_receipts(
+        &self,
+        request: Request<proto::GetBlockReceiptsRequest>,
+    ) -> Result<Response<proto::GetBlockReceiptsResponse>, Status> {
+        let id = block_id(request.into_inner().block)?;
+        let Some(block) = self.eth.block_by_id_with_senders(id).await.map_err(eth_status)? else {
+            return Ok(Response::new(proto::GetBlockReceiptsResponse::default()))
+        };
+        let Some(receipts) = self.cache.get_receipts(block.hash()).await.map_err(eth_status)?
+        else {
+            return Ok(Response::new(proto::GetBlockReceiptsResponse::default()))
+        };
+
+        let receipt_block = ReceiptBlock {
+            hash: block.hash(),
+            number: block.number,
+            base_fee: block.base_fee_per_gas,
+        };
+        let receipts = block
+            .into_transactions_ecrecovered()
+            .enumerate()
+            .filter_map(|(index, tx)| receipt_to_proto(&tx, index, receipt_block, &receipts))
+            .collect();
+        Ok(Response::new(proto::GetBlockReceiptsResponse { receipts }))
+    }
+
+    async fn get_transaction(
+        &self,
+        request: Request<proto::GetTransactionRequest>,
+    ) -> Result<Response<proto::GetTransactionResponse>, Status> {
+        let hash = b256(&request.into_inner().hash, "hash")?;
+        let transaction = EthTransactions::transaction_by_hash(&self.eth, hash)
+            .await
+            .map_err(eth_status)?
+            .map(proto::Transaction::from);
+        Ok(Response::new(proto::GetTransactionResponse { transaction }))
+    }
+
+    async fn get_transaction_receipt(
+        &self,
+        request: Request<proto::GetTransactionReceiptRequest>,
+    ) -> Result<Response<proto::GetTransactionReceiptResponse>, Status> {
+        let hash = b256(&request.into_inner().hash, "hash")?;
+        let source = EthTransactions::transaction_by_hash(&self.eth, hash).await;
+        let Some(TransactionSource::Block {
+            transaction,
+            index,
+            block_hash,
+            block_number,
+            base_fee,
+        }) = source.map_err(eth_status)?
+        else {
+            return Ok(Response::new(proto::GetTransactionReceiptResponse::default()))
+        };
+
+        let receipts = self.cache.get_receipts(block_hash).await.map_err(eth_status)?;
+        let receipt = receipts.and_then(|receipts| {
+            let block = ReceiptBlock { hash: block_hash, number: block_number, base_fee };
+            receipt_to_proto(&transaction, index as usize, block, &receipts)
+        });
+        Ok(Response::new(proto::GetTransactionReceiptResponse { receipt }))
+    }
+
+    async fn get_balance(
+        &self,
+        request: Request<proto::AccountRequest>,
+    ) -> Result<Response<proto::GetBalanceResponse>, Status> {
+        let request = request.into_inner();
+        let (address, at) = (address(&request.address, "address")?, block_id(request.block)?);
+        let balance =
+            EthApiServer::balance(&self.eth, address, Some(at)).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetBalanceResponse { balance: u256_to_proto(balance) }))
+    }
+
+    async fn get_transaction_count(
+        &self,
+        request: Request<proto::AccountRequest>,
+    ) -> Result<Response<proto::GetTransactionCountResponse>, Status> {
+        let request = request.into_inner();
+        let (address, at) = (address(&request.address, "address")?, block_id(request.block)?);
+        let nonce = EthApiServer::transaction_count(&self.eth, address, Some(at))
+            .await
+            .map_err(rpc_status)?;
+        Ok(Response::new(proto::GetTransactionCountResponse { nonce: nonce.saturating_to() }))
+    }
+
+    async fn get_code(
+        &self,
+        request: Request<proto::AccountRequest>,
+    ) -> Result<Response<proto::GetCodeResponse>, Status> {
+        let request = request.into_inner();
+        let (address, at) = (address(&request.address, "address")?, block_id(request.block)?);
+        let code =
+            EthApiServer::get_code(&self.eth, address, Some(at)).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetCodeResponse { code: code.to_vec() }))
+    }
+
+    async fn get_storage_at(
+        &self,
+        request: Request<proto::GetStorageAtRequest>,
+    ) -> Result<Response<proto::GetStorageAtResponse>, Status> {
+        let request = request.into_inner();
+        let address = address(&request.address, "address")?;
+        let slot = JsonStorageKey(b256(&request.slot, "slot")?);
+        let value =
+            EthApiServer::storage_at(&self.eth, address, slot, Some(block_id(request.block)?))
+                .await
+                .map_err(rpc_status)?;
+        Ok(Response::new(proto::GetStorageAtResponse { value: value.to_vec() }))
+    }
+
+    async fn call(
+        &self,
+        request: Request<proto::CallRequest>,
+    ) -> Result<Response<proto::CallResponse>, Status> {
+        let request = request.into_inner();
+        let (call, at) = (call_request(request.call)?, block_id(request.block)?);
+        let output =
+            EthApiServer::call(&self.eth, call, Some(at), None, None).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::CallResponse { output: output.to_vec() }))
+    }
+
+    async fn estimate_gas(
+        &self,
+        request: Request<proto::CallRequest>,
+    ) -> Result<Response<proto::EstimateGasResponse>, Status> {
+        let request = request.into_inner();
+        let (call, at) = (call_request(request.call)?, block_id(request.block)?);
+        let gas = EthApiServer::estimate_gas(&self.eth, call, Some(at), None)
+            .await
+            .map_err(rpc_status)?;
+        Ok(Response::new(proto::EstimateGasResponse { gas: gas.saturating_to() }))
+    }
+
+    async fn get_logs(
+        &self,
+        request: Request<proto::GetLogsRequest>,
+    ) -> Result<Response<proto::GetLogsResponse>, Status> {
+        let filter = codec::filter(request.into_inner())?;
+        let logs = self.filter.logs(filter).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetLogsResponse {
+            logs: logs.into_iter().map(proto::Log::from).collect(),
+        }))
+    }
+
+    async fn gas_price(
+        &self,
+        _request: Request<proto::GasPriceRequest>,
+    ) -> Result<Response<proto::GasPriceResponse>, Status> {
+        let gas_price = EthApiServer::gas_price(&self.eth).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GasPriceResponse { gas_price: u256_to_proto(gas_price) }))
+    }
+
+    async fn max_priority_fee_per_gas(
+        &self,
+        _request: Request<proto::MaxPriorityFeePerGasRequest>,
+    ) -> Result<Response<proto::MaxPriorityFeePerGasResponse>, Status> {
+        let fee = EthApiServer::max_priority_fee_per_gas(&self.eth).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::MaxPriorityFeePerGasResponse {
+            max_priority_fee_per_gas: u256_to_proto(fee),
+        }))
+    }
+
+    async fn send_raw_transaction(
+        &self,
+        request: Request<proto::SendRawTransactionRequest>,
+    ) -> Result<Response<proto::SendRawTransactionResponse>, Status> {
+        let tx = request.into_inner().transaction.into();
+        let hash = EthApiServer::send_raw_transaction(&self.eth, tx).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::SendRawTransactionResponse { hash: hash.to_vec() }))
+    }
+}
+
+/// Implements the `DebugApi` gRPC service with the handlers of the `debug` namespace.
+#[derive(Debug, Clone)]
+pub struct DebugService<DebugHandler> {
+    debug: DebugHandler,
+}
+
+impl<DebugHandler> DebugService<DebugHandler> {
+    /// Creates a new service with the `debug` API.
+    pub const fn new(debug: DebugHandler) -> Self {
+        Self { debug }
+    }
+}
+
+#[tonic::async_trait]
+impl<DebugHandler> DebugApi for DebugService<DebugHandler>
+where
+    DebugHandler: DebugApiServer + 'static,
+{
+    async fn get_raw_header(
+        &self,
+        request: Request<proto::GetRawHeaderRequest>,
+    ) -> Result<Response<proto::GetRawHeaderResponse>, Status> {
+        let id = block_id(request.into_inner().block)?;
+        let header = self.debug.raw_header(id).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetRawHeaderResponse { header: header.to_vec() }))
+    }
+
+    async fn get_raw_block(
+        &self,
+        request: Request<proto::GetRawBlockRequest>,
+    ) -> Result<Response<proto::GetRawBlockResponse>, Status> {
+        let id = block_id(request.into_inner().block)?;
+        let block = self.debug.raw_block(id).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetRawBlockResponse { block: block.to_vec() }))
+    }
+
+    async fn get_raw_transaction(
+        &self,
+        request: Request<proto::GetRawTransactionRequest>,
+    ) -> Result<Response<proto::GetRawTransactionResponse>, Status> {
+        let hash = b256(&request.into_inner().hash, "hash")?;
+        let transaction = self.debug.raw_transaction(hash).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetRawTransactionResponse {
+            transaction: transaction.map(|tx| tx.to_vec()),
+        }))
+    }
+
+    async fn get_raw_receipts(
+        &self,
+        request: Request<proto::GetRawReceiptsRequest>,
+    ) -> Result<Response<proto::GetRawReceiptsResponse>, Status> {
+        let id = block_id(request.into_inner().block)?;
+        let receipts = self.debug.raw_receipts(id).await.map_err(rpc_status)?;
+        Ok(Response::new(proto::GetRawReceiptsResponse {
+            receipts: receipts.into_iter().map(|receipt| receipt.to_vec()).collect(),
+        }))
+    }
+
+    async fn trace_transaction(
+        &self,
+        request: Request<proto::TraceTransactionRequest>,
+    ) -> Result<Response<proto::TraceResponse>, Status> {
+        let request = request.into_inner();
+        let hash = b256(&request.hash, "hash")?;
+        let trace = self
+            .debug
+            .debug_trace_transaction(hash, tracing_options(&request.options)?)
+            .await
+            .map_err(rpc_status)?;
+        Ok(Response::new(trace_to_proto(trace)?))
+    }
+
+    async fn trace_call(
+        &self,
+        request: Request<proto::TraceCallRequest>,
+    ) -> Result<Response<proto::TraceResponse>, Status> {
+        let request = request.into_inner();
+        let (call, at) = (call_request(request.call)?, block_id(request.block)?);
+        let trace = self
+            .debug
+            .debug_trace_call(call, Some(at), tracing_options(&request.options)?)
+            .await
+            .map_err(rpc_status)?;
+        Ok(Response::new(trace_to_proto(trace)?))
+    }
+}
+
+/// Decodes the JSON encoded tracing options, the default options if they're empty.
+fn tracing_options<T: DeserializeOwned>(options: &str) -> Result<Option<T>, Status> {
+    if options.is_empty() {
+        return Ok(None)
+    }
+    serde_json::from_str(options)
+        .map(Some)
+        .map_err(|err| Status::invalid_argument(format!("invalid tracing options: {err}")))
+}
+
+/// Converts an error of the `eth` handlers into a gRPC status.
+fn eth_status(err: impl Into<EthApiError>) -> Status {
+    rpc_status(ErrorObject::from(err.into()))
+}
+
+/// Converts a JSON-RPC error into a gRPC status with the matching code.
+///
+/// The JSON-RPC code and data of the error are included in the `rpc-error-code` and
+/// `rpc-error-data` metadata.
+pub(crate) fn rpc_status(err: ErrorObjectOwned) -> Status {
+    let code = match err.code() {
+        INVALID_PARAMS_CODE | INVALID_REQUEST_CODE | PARSE_ERROR_CODE => Code::InvalidArgument,
+        METHOD_NOT_FOUND_CODE => Code::Unimplemented,
+        INTERNAL_ERROR_CODE => Code::Internal,
+        UNAUTHORIZED_CODE => Code::Unauthenticated,
+        RATE_LIMIT_EXCEEDED_CODE => Code::ResourceExhausted,
+        // execution errors, like reverts, and rejected transactions
+        _ => Code::FailedPrecondition,
+    };
+    let mut status = Status::new(code, err.message());
+    let metadata = status.metadata_mut();
+    metadata.insert("rpc-error-code", err.code().into());
+    if let Some(Ok(data)) = err.data().map(|data| data.get().parse()) {
+        metadata.insert("rpc-error-data", data);
+    }
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rpc_error_to_status() {
+        let err = ErrorObject::owned(3, "execution reverted", Some("0x01"));
+        let status = rpc_status(err);
+        assert_eq!(status.code(), Code::FailedPrecondition);
+        assert_eq!(status.message(), "execution reverted");
+        assert_eq!(status.metadata().get("rpc-error-code").unwrap(), "3");
+        assert_eq!(status.metadata().get("rpc-error-data").unwrap(), "\"0x01\"");
+
+        let err = ErrorObject::owned(INVALID_PARAMS_CODE, "invalid params", None::<()>);
+        let status = rpc_status(err);
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.metadata().get("rpc-error-data").is_none());
+
+        let err = ErrorObject::owned(RATE_LIMIT_EXCEEDED_CODE, "rate limit exceeded", None::<()>);
+        assert_eq!(rpc_status(err).code(), Code::ResourceExhausted);
+    }
+}
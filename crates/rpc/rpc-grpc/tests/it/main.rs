@@ -0,0 +1,3 @@
+mod server;
+
+const fn main() {}
@@ -0,0 +1,138 @@
+//! Tests of the gRPC server.
+
+use jsonrpsee::RpcModule;
+use reth_evm_ethereum::EthEvmConfig;
+use reth_network_api::noop::NoopNetwork;
+use reth_provider::test_utils::{NoopProvider, TestCanonStateSubscriptions};
+use reth_rpc_builder::{
+    EthHandlers, RpcModuleBuilder, RpcNamespaceAuthConfig, RpcServerConfig,
+    RATE_LIMIT_EXCEEDED_CODE,
+};
+use reth_rpc_grpc::{
+    proto::{
+        debug_api_client::DebugApiClient, eth_api_client::EthApiClient, BlockNumberRequest,
+        ChainIdRequest, GetRawHeaderRequest,
+    },
+    start_server, DebugService, EthService, GrpcServices,
+};
+use reth_tasks::{TaskManager, TokioTaskExecutor};
+use reth_transaction_pool::test_utils::TestPoolBuilder;
+use std::net::SocketAddr;
+use tonic::{
+    codegen::{http, Body as _},
+    transport::{Body, Channel},
+    Code, Request, Status,
+};
+use tower::ServiceExt;
+
+/// Starts a gRPC server with the debug service, if enabled, and the JSON-RPC module.
+async fn launch(
+    config: RpcServerConfig,
+    debug: bool,
+    json_rpc: Option<RpcModule<()>>,
+) -> (Channel, TaskManager) {
+    let mut registry = RpcModuleBuilder::default()
+        .with_provider(NoopProvider::default())
+        .with_pool(TestPoolBuilder::default().into())
+        .with_network(NoopNetwork::default())
+        .with_executor(TokioTaskExecutor::default())
+        .with_events(TestCanonStateSubscriptions::default())
+        .with_evm_config(EthEvmConfig::default())
+        .into_registry(Default::default());
+    let EthHandlers { api, cache, filter, .. } = registry.eth_handlers();
+    let services = GrpcServices::new(
+        Some(EthService::new(api, filter, cache)),
+        debug.then(|| DebugService::new(registry.debug_api())),
+    )
+    .with_json_rpc(json_rpc.clone())
+    .with_middleware(config.grpc_middleware(json_rpc.as_ref()));
+
+    let manager = TaskManager::current();
+    let addr = SocketAddr::from(([127, 0, 0, 1], 0));
+    let addr = start_server(addr, services, &manager.executor()).await.unwrap();
+    let channel = Channel::from_shared(format!("http://{addr}")).unwrap().connect().await.unwrap();
+    (channel, manager)
+}
+
+/// Returns the JSON-RPC error code of the status.
+fn rpc_error_code(status: &Status) -> Option<i32> {
+    status.metadata().get("rpc-error-code")?.to_str().ok()?.parse().ok()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn serves_enabled_services() {
+    let (channel, _manager) = launch(RpcServerConfig::default(), false, None).await;
+
+    let mut eth = EthApiClient::new(channel.clone());
+    let response = eth.block_number(BlockNumberRequest {}).await.unwrap();
+    assert_eq!(response.into_inner().number, 0);
+    let response = eth.chain_id(ChainIdRequest {}).await.unwrap();
+    assert_eq!(response.into_inner().chain_id, 1);
+
+    // the debug service is disabled
+    let mut debug = DebugApiClient::new(channel);
+    let status = debug.get_raw_header(GetRawHeaderRequest::default()).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unimplemented);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn applies_middleware() {
+    let rate_limits = "default.methods.eth_blockNumber = { rate = 1 }".parse().unwrap();
+    let namespace_auth = RpcNamespaceAuthConfig::new(["debug"]).with_tokens(["s3cr3t"]);
+    let config = RpcServerConfig::default()
+        .with_rate_limits(Some(rate_limits))
+        .with_namespace_auth(Some(namespace_auth));
+    let (channel, _manager) = launch(config, true, None).await;
+
+    let mut eth = EthApiClient::new(channel.clone());
+    eth.block_number(BlockNumberRequest {}).await.unwrap();
+    let status = eth.block_number(BlockNumberRequest {}).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+    assert_eq!(rpc_error_code(&status), Some(RATE_LIMIT_EXCEEDED_CODE));
+    // other methods aren't limited
+    eth.chain_id(ChainIdRequest {}).await.unwrap();
+
+    let mut debug = DebugApiClient::new(channel);
+    let status = debug.get_raw_header(GetRawHeaderRequest::default()).await.unwrap_err();
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    let mut request = Request::new(GetRawHeaderRequest::default());
+    request.metadata_mut().insert("authorization", "Bearer s3cr3t".parse().unwrap());
+    if let Err(status) = debug.get_raw_header(request).await {
+        assert_ne!(status.code(), Code::Unauthenticated);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn serves_json_rpc_over_http2() {
+    let mut module = RpcModule::new(());
+    module.register_method("web3_clientVersion", |_, _, _| "reth").unwrap();
+    let (channel, _manager) = launch(RpcServerConfig::default(), false, Some(module)).await;
+
+    let call = |body: &'static str| {
+        let channel = channel.clone();
+        async move {
+            let body = Body::from(body).map_err(|err| Status::internal(err.to_string()));
+            let request = http::Request::post("/")
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(body.boxed_unsync())
+                .unwrap();
+            let response = channel.oneshot(request).await.unwrap();
+            let mut body = response.into_body();
+            let mut bytes = Vec::new();
+            while let Some(chunk) = body.data().await {
+                bytes.extend_from_slice(&chunk.unwrap());
+            }
+            serde_json::from_slice::<serde_json::Value>(&bytes).unwrap()
+        }
+    };
+
+    let response = call(r#"{"jsonrpc":"2.0","id":1,"method":"web3_clientVersion"}"#).await;
+    assert_eq!(response["result"], "reth");
+
+    let response = call(r#"{"jsonrpc":"2.0","id":1,"method":"eth_unknown"}"#).await;
+    assert_eq!(response["error"]["code"], -32601);
+
+    let response = call("not json").await;
+    assert_eq!(response["error"]["code"], -32700);
+}
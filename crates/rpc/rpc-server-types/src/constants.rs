@@ -6,6 +6,9 @@ pub const DEFAULT_HTTP_RPC_PORT: u16 = 8545;
 /// The default port for the ws server
 pub const DEFAULT_WS_RPC_PORT: u16 = 8546;
 
+/// The default port for the gRPC server
+pub const DEFAULT_GRPC_PORT: u16 = 8549;
+
 /// The default port for the auth server.
 pub const DEFAULT_AUTH_PORT: u16 = 8551;
 
@@ -15,6 +18,12 @@ pub const DEFAULT_MAX_BLOCKS_PER_FILTER: u64 = 100_000;
 /// The default maximum of logs in a single response.
 pub const DEFAULT_MAX_LOGS_PER_RESPONSE: usize = 20_000;
 
+/// The default maximum of traces in a single `trace_filter` response.
+pub const DEFAULT_MAX_TRACES_PER_RESPONSE: usize = 10_000;
+
+/// The default maximum of transactions in a single `txpool_content` or `txpool_inspect` response.
+pub const DEFAULT_MAX_TXPOOL_CONTENT: usize = 50_000;
+
 /// The default maximum number tracing requests we're allowing concurrently.
 /// Tracing is mostly CPU bound so we're limiting the number of concurrent requests to something
 /// lower that the number of cores, in order to minimize the impact on the rest of the system.
@@ -64,6 +73,9 @@ pub mod gas_oracle {
 
     /// The default minimum gas price, under which the sample will be ignored
     pub const DEFAULT_IGNORE_GAS_PRICE: U256 = U256::from_limbs([2u64, 0, 0, 0]);
+
+    /// The default number of blocks the suggested max fee per blob gas covers
+    pub const DEFAULT_BLOB_FEE_BLOCKS: u64 = 3;
 }
 
 /// Cache specific constants
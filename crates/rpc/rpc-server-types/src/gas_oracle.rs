@@ -0,0 +1,115 @@
+//! Settings of the gas price oracle.
+
+use crate::constants::gas_oracle::*;
+use alloy_primitives::U256;
+use serde::{Deserialize, Serialize};
+
+/// How the gas price oracle samples the priority fees of recent blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PriorityFeeStrategy {
+    /// Samples the lowest priority fees of every block, see
+    /// [`GasPriceOracleConfig::sample_size`], and suggests the configured percentile of the
+    /// samples.
+    ///
+    /// This is the strategy of geth, which suggests the fee that is barely enough to be included.
+    #[default]
+    Lowest,
+    /// Samples the priority fees of all transactions of every block, weighted by the gas the
+    /// transactions used, and suggests the fee under which the configured percentile of the gas
+    /// was paid.
+    GasWeighted,
+}
+
+/// Settings for the gas price oracle.
+///
+/// These can be set in the `[gas_price_oracle]` section of `reth.toml`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GasPriceOracleConfig {
+    /// The number of populated blocks to produce the gas price estimate
+    pub blocks: u32,
+
+    /// The percentile of gas prices to use for the estimate
+    pub percentile: u32,
+
+    /// How the priority fees of recent blocks are sampled
+    pub strategy: PriorityFeeStrategy,
+
+    /// The number of the lowest priority fees that are sampled per block, with the
+    /// [`PriorityFeeStrategy::Lowest`] strategy
+    pub sample_size: usize,
+
+    /// The maximum number of headers to keep in the cache
+    pub max_header_history: u64,
+
+    /// The maximum number of blocks for estimating gas price
+    pub max_block_history: u64,
+
+    /// The default gas price to use if there are no blocks to use
+    pub default: Option<U256>,
+
+    /// The maximum gas price to use for the estimate
+    pub max_price: Option<U256>,
+
+    /// The minimum gas price, under which the sample will be ignored
+    pub ignore_price: Option<U256>,
+
+    /// Whether to raise the suggested priority fee to the fee the pending transactions of the
+    /// pool need to be included in the next block, if they don't all fit.
+    ///
+    /// This reacts to fee spikes before they show up in the blocks. Disabled by default.
+    pub txpool: bool,
+
+    /// The number of blocks the suggested max fee per blob gas covers, if the blob base fee
+    /// increases as much as possible in every block.
+    ///
+    /// With zero blocks, the blob base fee of the next block is suggested.
+    pub blob_fee_blocks: u64,
+}
+
+impl Default for GasPriceOracleConfig {
+    fn default() -> Self {
+        Self {
+            blocks: DEFAULT_GAS_PRICE_BLOCKS,
+            percentile: DEFAULT_GAS_PRICE_PERCENTILE,
+            strategy: PriorityFeeStrategy::default(),
+            sample_size: SAMPLE_NUMBER,
+            max_header_history: MAX_HEADER_HISTORY,
+            max_block_history: MAX_HEADER_HISTORY,
+            default: None,
+            max_price: Some(DEFAULT_MAX_GAS_PRICE),
+            ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
+            txpool: false,
+            blob_fee_blocks: DEFAULT_BLOB_FEE_BLOCKS,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_partial_toml() {
+        let config: GasPriceOracleConfig = toml::from_str(
+            r#"
+            strategy = "gasWeighted"
+            percentile = 80
+            sampleSize = 5
+            txpool = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            GasPriceOracleConfig {
+                strategy: PriorityFeeStrategy::GasWeighted,
+                percentile: 80,
+                sample_size: 5,
+                txpool: true,
+                ..Default::default()
+            }
+        );
+    }
+}
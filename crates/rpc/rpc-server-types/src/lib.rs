@@ -11,5 +11,13 @@
 /// Common RPC constants.
 pub mod constants;
 
+/// Settings of the gas price oracle.
+pub mod gas_oracle;
+pub use gas_oracle::GasPriceOracleConfig;
+
 mod module;
 pub use module::{RethRpcModule, RpcModuleSelection};
+
+/// Rate limits of RPC methods.
+pub mod rate_limit;
+pub use rate_limit::RpcRateLimitConfig;
@@ -107,6 +107,15 @@ impl RpcModuleSelection {
         }
     }
 
+    /// Returns true if the selection contains the given module.
+    pub fn contains(&self, module: &RethRpcModule) -> bool {
+        match self {
+            Self::All => true,
+            Self::Standard => Self::STANDARD_MODULES.contains(module),
+            Self::Selection(s) => s.contains(module),
+        }
+    }
+
     /// Clones the set of configured [`RethRpcModule`].
     pub fn to_selection(&self) -> HashSet<RethRpcModule> {
         match self {
@@ -260,6 +269,11 @@ pub enum RethRpcModule {
     /// This is separate from [`RethRpcModule::Eth`] because it is a non standardized call that
     /// should be opt-in.
     EthCallBundle,
+    /// `mev_` module for the simulation of bundles with `mev_simBundle`
+    ///
+    /// This is opt-in because it's only useful for searchers that simulate bundles against their
+    /// own node.
+    Mev,
 }
 
 // === impl RethRpcModule ===
@@ -309,6 +323,7 @@ impl FromStr for RethRpcModule {
             "reth" => Self::Reth,
             "ots" => Self::Ots,
             "eth-call-bundle" | "eth_callBundle" => Self::EthCallBundle,
+            "mev" => Self::Mev,
             _ => return Err(ParseError::VariantNotFound),
         })
     }
@@ -0,0 +1,198 @@
+//! Rate limits and quotas of RPC methods.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+/// The HTTP header that clients send their API key in, to be rate limited according to the tier of
+/// the key.
+pub const RPC_API_KEY_HEADER: &str = "x-api-key";
+
+/// Rate limits of the RPC servers, read from a TOML file.
+///
+/// Requests without an API key, or with an unknown one, are limited by the `default` tier.
+/// Requests with the API key of a tier are limited by that tier.
+///
+/// ```toml
+/// [default]
+/// connection = { rate = 100, burst = 200 }
+/// methods.debug_traceBlockByNumber = { rate = 1 }
+///
+/// [tiers.premium]
+/// api_keys = ["d1d8a2b0"]
+/// methods.debug_traceBlockByNumber = { rate = 10, burst = 20 }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RpcRateLimitConfig {
+    /// The limits of requests without a known API key.
+    pub default: RateLimitTier,
+    /// The limits of requests with an API key, by the name of the tier.
+    pub tiers: HashMap<String, RateLimitTier>,
+}
+
+impl RpcRateLimitConfig {
+    /// Reads the rate limits from the TOML file at the given path.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, RpcRateLimitConfigError> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .map_err(|err| RpcRateLimitConfigError::Io(path.display().to_string(), err))?;
+        raw.parse()
+    }
+
+    /// Returns the tier of requests with the given API key, and the key if it's known.
+    pub fn tier<'a>(&'a self, api_key: Option<&'a str>) -> (&'a RateLimitTier, Option<&'a str>) {
+        api_key
+            .and_then(|key| {
+                self.tiers
+                    .values()
+                    .find(|tier| tier.api_keys.iter().any(|k| k == key))
+                    .map(|tier| (tier, Some(key)))
+            })
+            .unwrap_or((&self.default, None))
+    }
+
+    /// Ensures that all quotas allow requests and that no API key belongs to multiple tiers.
+    fn validate(&self) -> Result<(), RpcRateLimitConfigError> {
+        let mut api_keys = HashSet::new();
+        for tier in std::iter::once(&self.default).chain(self.tiers.values()) {
+            for quota in tier.connection.iter().chain(tier.methods.values()) {
+                if quota.rate == 0 || quota.burst == Some(0) {
+                    return Err(RpcRateLimitConfigError::EmptyQuota)
+                }
+            }
+            for key in &tier.api_keys {
+                if !api_keys.insert(key) {
+                    return Err(RpcRateLimitConfigError::DuplicateApiKey(key.clone()))
+                }
+            }
+        }
+        if !self.default.api_keys.is_empty() {
+            return Err(RpcRateLimitConfigError::DefaultTierApiKeys)
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for RpcRateLimitConfig {
+    type Err = RpcRateLimitConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let config: Self = toml::from_str(s)?;
+        config.validate()?;
+        Ok(config)
+    }
+}
+
+/// The rate limits of a group of clients.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimitTier {
+    /// The API keys of the clients of the tier.
+    pub api_keys: Vec<String>,
+    /// The quota of a single client, across all methods.
+    ///
+    /// Every API key of the tier is a client, across all its connections. Requests without a known
+    /// API key are limited per connection, or per remote address for the gRPC gateway.
+    pub connection: Option<RateLimitQuota>,
+    /// The quotas of individual methods, by method name.
+    ///
+    /// Every client, see [`RateLimitTier::connection`], has its own quota of each method.
+    pub methods: HashMap<String, RateLimitQuota>,
+}
+
+/// A token bucket quota of `rate` requests per second, with bursts of up to `burst` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimitQuota {
+    /// The number of requests per second.
+    pub rate: u32,
+    /// The number of requests that can be made at once, defaults to `rate`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+impl RateLimitQuota {
+    /// Creates a quota of `rate` requests per second, with bursts of up to `rate` requests.
+    pub const fn new(rate: u32) -> Self {
+        Self { rate, burst: None }
+    }
+
+    /// Sets the number of requests that can be made at once.
+    pub const fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = Some(burst);
+        self
+    }
+
+    /// Returns the number of requests that can be made at once.
+    pub fn burst(&self) -> u32 {
+        self.burst.unwrap_or(self.rate)
+    }
+}
+
+/// Errors of reading [`RpcRateLimitConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum RpcRateLimitConfigError {
+    /// The file couldn't be read.
+    #[error("failed to read rate limits from {0}: {1}")]
+    Io(String, #[source] std::io::Error),
+    /// The file isn't valid TOML or doesn't match the format.
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    /// A quota doesn't allow any requests.
+    #[error("rate limit quotas must allow at least one request")]
+    EmptyQuota,
+    /// An API key belongs to multiple tiers.
+    #[error("API key {0} belongs to multiple tiers")]
+    DuplicateApiKey(String),
+    /// The default tier has API keys.
+    #[error("the default tier can't have API keys")]
+    DefaultTierApiKeys,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_rate_limit_config() {
+        let config: RpcRateLimitConfig = r#"
+            [default]
+            connection = { rate = 100, burst = 200 }
+            methods.debug_traceBlockByNumber = { rate = 1 }
+
+            [tiers.premium]
+            api_keys = ["key"]
+            methods.debug_traceBlockByNumber = { rate = 10, burst = 20 }
+        "#
+        .parse()
+        .unwrap();
+
+        let (tier, key) = config.tier(None);
+        assert_eq!(key, None);
+        assert_eq!(tier.connection, Some(RateLimitQuota::new(100).with_burst(200)));
+        assert_eq!(tier.methods["debug_traceBlockByNumber"].burst(), 1);
+
+        let (tier, key) = config.tier(Some("key"));
+        assert_eq!(key, Some("key"));
+        assert_eq!(tier.connection, None);
+        assert_eq!(tier.methods["debug_traceBlockByNumber"].burst(), 20);
+
+        assert_eq!(config.tier(Some("unknown")), (&config.default, None));
+    }
+
+    #[test]
+    fn invalid_rate_limit_config() {
+        let err = "default.methods.eth_call = { rate = 0 }".parse::<RpcRateLimitConfig>();
+        assert!(matches!(err, Err(RpcRateLimitConfigError::EmptyQuota)));
+
+        let err = r#"
+            tiers.a.api_keys = ["key"]
+            tiers.b.api_keys = ["key"]
+        "#
+        .parse::<RpcRateLimitConfig>();
+        assert!(matches!(err, Err(RpcRateLimitConfigError::DuplicateApiKey(_))));
+    }
+}
@@ -2,7 +2,7 @@
 //! Ethereum's Engine
 
 use reth_primitives::{
-    constants::{EMPTY_OMMER_ROOT_HASH, MAXIMUM_EXTRA_DATA_SIZE},
+    constants::EMPTY_OMMER_ROOT_HASH,
     proofs::{self},
     Block, Header, Request, SealedBlock, TransactionSigned, UintTryTo, Withdrawals, B256, U256,
 };
@@ -14,10 +14,6 @@ use reth_rpc_types::engine::{
 
 /// Converts [`ExecutionPayloadV1`] to [Block]
 pub fn try_payload_v1_to_block(payload: ExecutionPayloadV1) -> Result<Block, PayloadError> {
-    if payload.extra_data.len() > MAXIMUM_EXTRA_DATA_SIZE {
-        return Err(PayloadError::ExtraData(payload.extra_data))
-    }
-
     if payload.base_fee_per_gas.is_zero() {
         return Err(PayloadError::BaseFee(payload.base_fee_per_gas))
     }
@@ -292,7 +288,8 @@ pub fn convert_block_to_payload_input_v2(value: SealedBlock) -> ExecutionPayload
 
 /// Tries to create a new block (without a block hash) from the given payload and optional parent
 /// beacon block root.
-/// Performs additional validation of `extra_data` and `base_fee_per_gas` fields.
+/// Performs additional validation of the `base_fee_per_gas` field. The size of the `extra_data`
+/// field depends on the chain and is not validated here.
 ///
 /// NOTE: The log bloom is assumed to be validated during serialization.
 ///
@@ -0,0 +1,119 @@
+//! Preconditions of transactions submitted with `eth_sendRawTransactionConditional`.
+
+use alloy_primitives::{Address, B256};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The conditions under which a transaction submitted with `eth_sendRawTransactionConditional`
+/// may be included in a block.
+///
+/// See also <https://notes.ethereum.org/@yoav/SkaX2lS9j>
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionConditional {
+    /// The expected storage of accounts at the time the transaction is included.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub known_accounts: HashMap<Address, KnownAccount>,
+    /// The lowest block number the transaction may be included in.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub block_number_min: Option<u64>,
+    /// The highest block number the transaction may be included in.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub block_number_max: Option<u64>,
+    /// The lowest block timestamp the transaction may be included in.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub timestamp_min: Option<u64>,
+    /// The highest block timestamp the transaction may be included in.
+    #[serde(default, with = "alloy_serde::quantity::opt", skip_serializing_if = "Option::is_none")]
+    pub timestamp_max: Option<u64>,
+}
+
+impl TransactionConditional {
+    /// Returns true if a block with the given number is within the block number range.
+    pub fn is_block_number_valid(&self, number: u64) -> bool {
+        self.block_number_min.map_or(true, |min| number >= min) &&
+            !self.has_exceeded_block_number(number)
+    }
+
+    /// Returns true if a block with the given timestamp is within the timestamp range.
+    pub fn is_timestamp_valid(&self, timestamp: u64) -> bool {
+        self.timestamp_min.map_or(true, |min| timestamp >= min) &&
+            !self.has_exceeded_timestamp(timestamp)
+    }
+
+    /// Returns true if the block number range ends before the given block number.
+    ///
+    /// Once exceeded, the transaction can't be included in any later block.
+    pub fn has_exceeded_block_number(&self, number: u64) -> bool {
+        self.block_number_max.is_some_and(|max| number > max)
+    }
+
+    /// Returns true if the timestamp range ends before the given timestamp.
+    ///
+    /// Once exceeded, the transaction can't be included in any later block.
+    pub fn has_exceeded_timestamp(&self, timestamp: u64) -> bool {
+        self.timestamp_max.is_some_and(|max| timestamp > max)
+    }
+
+    /// Returns the number of storage lookups needed to check the known accounts: one per storage
+    /// root and one per storage slot.
+    pub fn cost(&self) -> usize {
+        self.known_accounts
+            .values()
+            .map(|account| match account {
+                KnownAccount::StorageRoot(_) => 1,
+                KnownAccount::Slots(slots) => slots.len(),
+            })
+            .sum()
+    }
+}
+
+/// The expected storage of an account in a [`TransactionConditional`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KnownAccount {
+    /// The expected storage root of the account.
+    StorageRoot(B256),
+    /// The expected values of storage slots of the account.
+    Slots(HashMap<B256, B256>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_transaction_conditional() {
+        let s = r#"{
+            "knownAccounts": {
+                "0x0000000000000000000000000000000000000001": "0x0000000000000000000000000000000000000000000000000000000000000002",
+                "0x0000000000000000000000000000000000000003": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000004": "0x0000000000000000000000000000000000000000000000000000000000000005"
+                }
+            },
+            "blockNumberMax": "0x10",
+            "timestampMin": "0x20"
+        }"#;
+        let conditional: TransactionConditional = serde_json::from_str(s).unwrap();
+        assert_eq!(
+            conditional.known_accounts[&Address::with_last_byte(1)],
+            KnownAccount::StorageRoot(B256::with_last_byte(2))
+        );
+        assert_eq!(
+            conditional.known_accounts[&Address::with_last_byte(3)],
+            KnownAccount::Slots(HashMap::from([(
+                B256::with_last_byte(4),
+                B256::with_last_byte(5)
+            )]))
+        );
+        assert_eq!(conditional.block_number_min, None);
+        assert_eq!(conditional.block_number_max, Some(16));
+        assert_eq!(conditional.timestamp_min, Some(32));
+        assert_eq!(conditional.cost(), 2);
+
+        assert!(conditional.is_block_number_valid(16));
+        assert!(conditional.has_exceeded_block_number(17));
+        assert!(!conditional.is_timestamp_valid(31));
+        assert!(!conditional.has_exceeded_timestamp(u64::MAX));
+    }
+}
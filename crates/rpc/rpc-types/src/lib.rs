@@ -14,7 +14,10 @@ mod eth;
 mod mev;
 mod net;
 mod peer;
+mod reth;
 mod rpc;
+mod txpool_page;
+mod usage;
 
 // re-export for convenience
 pub use alloy_rpc_types::serde_helpers;
@@ -49,10 +52,15 @@ pub use eth::{
         ExecutionPayload, ExecutionPayloadV1, ExecutionPayloadV2, ExecutionPayloadV3, PayloadError,
     },
     error::ToRpcError,
-    transaction::{self, TransactionRequest, TypedTransactionRequest},
+    transaction::{
+        self, KnownAccount, TransactionConditional, TransactionRequest, TypedTransactionRequest,
+    },
 };
 
 pub use mev::*;
 pub use net::*;
 pub use peer::*;
+pub use reth::*;
 pub use rpc::*;
+pub use txpool_page::*;
+pub use usage::*;
@@ -0,0 +1,118 @@
+use alloy_primitives::{Address, FixedBytes, B256, U256};
+use alloy_rpc_types::{serde_helpers::JsonStorageKey, Header};
+use serde::{Deserialize, Serialize};
+
+/// The state of an account, as returned by `reth_getAccountStates`.
+///
+/// Accounts that don't exist have a zero nonce and balance and the hash of empty bytecode.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    /// The nonce of the account.
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    /// The balance of the account.
+    pub balance: U256,
+    /// The hash of the account's bytecode.
+    pub code_hash: B256,
+}
+
+/// The EIP-7702 delegation of an account, as returned by `reth_getDelegations`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountDelegation {
+    /// The address the account delegates to, from the delegation designator in its code.
+    pub delegate: Address,
+    /// The hash of the bytecode of the delegate, i.e. of the code the account executes.
+    pub delegate_code_hash: B256,
+}
+
+/// An account and its storage slots to prove, as requested from `reth_getProofs`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountProofRequest {
+    /// The address of the account.
+    pub address: Address,
+    /// The storage slots of the account to prove, like the keys of `eth_getProof`.
+    #[serde(default)]
+    pub storage_keys: Vec<JsonStorageKey>,
+}
+
+/// The state of the [EIP-7002](https://eips.ethereum.org/EIPS/eip-7002) withdrawal requests
+/// contract, as returned by `reth_getWithdrawalRequestQueue`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalRequestQueue {
+    /// The number of requests above the target that were added in previous blocks.
+    pub excess_requests: U256,
+    /// The number of requests added in the block.
+    #[serde(with = "alloy_serde::quantity")]
+    pub request_count: u64,
+    /// The fee to add a withdrawal request, `None` if the contract isn't active yet.
+    pub fee: Option<U256>,
+    /// The number of queued requests, which can be more than the number of returned requests.
+    #[serde(with = "alloy_serde::quantity")]
+    pub queue_length: u64,
+    /// The queued requests from the head of the queue, in the order they are dequeued.
+    pub requests: Vec<QueuedWithdrawalRequest>,
+}
+
+/// A withdrawal request in the queue of the EIP-7002 withdrawal requests contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedWithdrawalRequest {
+    /// The address that added the request.
+    pub source_address: Address,
+    /// The public key of the validator to withdraw from.
+    pub validator_pubkey: FixedBytes<48>,
+    /// The amount to withdraw in gwei, zero for a full exit.
+    #[serde(with = "alloy_serde::quantity")]
+    pub amount: u64,
+}
+
+/// A reorg of the canonical chain, as emitted by the `reth_subscribeChainReorgs` subscription.
+///
+/// Only reorgs that replace canonical blocks with the blocks of another fork are emitted, so `new`
+/// always contains at least one block. Unwinds of the chain that don't add new blocks, e.g. of the
+/// pipeline, aren't emitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChainReorg {
+    /// The headers of the blocks that were removed from the canonical chain, in ascending order.
+    pub old: Vec<Header>,
+    /// The headers of the blocks that replaced them, in ascending order.
+    pub new: Vec<Header>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_account_state() {
+        let s = r#"{"nonce":"0x2","balance":"0x10","codeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"}"#;
+        let state: AccountState = serde_json::from_str(s).unwrap();
+        assert_eq!(state.nonce, 2);
+        assert_eq!(state.balance, U256::from(16));
+        assert_eq!(serde_json::to_string(&state).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_account_delegation() {
+        let s = r#"{"delegate":"0x4242424242424242424242424242424242424242","delegateCodeHash":"0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"}"#;
+        let delegation: AccountDelegation = serde_json::from_str(s).unwrap();
+        assert_eq!(delegation.delegate, Address::repeat_byte(0x42));
+        assert_eq!(serde_json::to_string(&delegation).unwrap(), s);
+    }
+
+    #[test]
+    fn serde_account_proof_request() {
+        let s =
+            r#"{"address":"0x4242424242424242424242424242424242424242","storageKeys":["0x22"]}"#;
+        let request: AccountProofRequest = serde_json::from_str(s).unwrap();
+        assert_eq!(request.storage_keys, vec![JsonStorageKey(B256::with_last_byte(0x22))]);
+
+        let s = r#"{"address":"0x4242424242424242424242424242424242424242"}"#;
+        let request: AccountProofRequest = serde_json::from_str(s).unwrap();
+        assert!(request.storage_keys.is_empty());
+    }
+}
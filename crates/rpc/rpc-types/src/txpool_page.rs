@@ -0,0 +1,54 @@
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A page of the transactions of the pool, as returned by `txpool_contentPage` and
+/// `txpool_inspectPage`, grouped by sender and nonce like `txpool_content`.
+///
+/// Senders are ordered by when they were first seen by the pool, and all transactions of a sender
+/// are on the same page.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolPage<T> {
+    /// The pending transactions of the senders of the page.
+    pub pending: BTreeMap<Address, BTreeMap<String, T>>,
+    /// The queued transactions of the senders of the page.
+    pub queued: BTreeMap<Address, BTreeMap<String, T>>,
+    /// The sender the next page starts with, `None` if this is the last page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Address>,
+}
+
+impl<T> Default for TxpoolPage<T> {
+    fn default() -> Self {
+        Self { pending: BTreeMap::new(), queued: BTreeMap::new(), next: None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_txpool_page() {
+        let sender = Address::repeat_byte(0x42);
+        let page = TxpoolPage {
+            pending: BTreeMap::from([(sender, BTreeMap::from([("0".to_string(), 1u64)]))]),
+            queued: BTreeMap::new(),
+            next: Some(Address::repeat_byte(0x43)),
+        };
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "pending": { "0x4242424242424242424242424242424242424242": { "0": 1 } },
+                "queued": {},
+                "next": "0x4343434343434343434343434343434343434343"
+            })
+        );
+        assert_eq!(serde_json::from_value::<TxpoolPage<u64>>(json).unwrap(), page);
+
+        let last = TxpoolPage::<u64>::default();
+        assert_eq!(serde_json::to_string(&last).unwrap(), r#"{"pending":{},"queued":{}}"#);
+    }
+}
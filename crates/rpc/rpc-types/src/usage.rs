@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The RPC usage of an API key, as returned by `admin_rpcUsage`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeyUsage {
+    /// The API key, `None` for the usage of all requests without a known API key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// The unix timestamp in seconds when the accounting of the usage started.
+    pub since: u64,
+    /// The number of requests by method.
+    pub methods: BTreeMap<String, u64>,
+    /// The compute units of the requests.
+    pub compute_units: u64,
+    /// The size of the responses in bytes.
+    pub egress_bytes: u64,
+}
+
+impl ApiKeyUsage {
+    /// Returns the number of requests to all methods.
+    pub fn requests(&self) -> u64 {
+        self.methods.values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_api_key_usage() {
+        let usage = ApiKeyUsage {
+            api_key: Some("d1d8a2b0".to_string()),
+            since: 1_700_000_000,
+            methods: BTreeMap::from([("eth_call".to_string(), 2), ("eth_chainId".to_string(), 1)]),
+            compute_units: 52,
+            egress_bytes: 1024,
+        };
+        let json = serde_json::to_value(&usage).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "apiKey": "d1d8a2b0",
+                "since": 1_700_000_000,
+                "methods": { "eth_call": 2, "eth_chainId": 1 },
+                "computeUnits": 52,
+                "egressBytes": 1024
+            })
+        );
+        assert_eq!(serde_json::from_value::<ApiKeyUsage>(json).unwrap(), usage);
+        assert_eq!(usage.requests(), 3);
+    }
+}
@@ -4,36 +4,41 @@ use alloy_primitives::B256;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_chainspec::ChainSpec;
-use reth_network_api::{NetworkInfo, PeerKind, Peers};
+use reth_network_api::{KnownPeer, NetworkInfo, PeerKind, Peers};
 use reth_network_peers::{AnyNode, NodeRecord};
 use reth_rpc_api::AdminApiServer;
 use reth_rpc_types::{
     admin::{EthProtocolInfo, NodeInfo, Ports, ProtocolInfo},
-    PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo,
+    PeerEthProtocolInfo, PeerInfo, PeerNetworkInfo, PeerProtocolsInfo, Transaction,
 };
+use reth_rpc_types_compat::transaction::from_recovered;
+use reth_transaction_pool::TransactionPool;
 use std::sync::Arc;
 
 /// `admin` API implementation.
 ///
 /// This type provides the functionality for handling `admin` related requests.
-pub struct AdminApi<N> {
+pub struct AdminApi<N, Pool> {
     /// An interface to interact with the network
     network: N,
     /// The specification of the blockchain's configuration.
     chain_spec: Arc<ChainSpec>,
+    /// The transaction pool that tracks the local transactions.
+    pool: Pool,
 }
 
-impl<N> AdminApi<N> {
+impl<N, Pool> AdminApi<N, Pool> {
     /// Creates a new instance of `AdminApi`.
-    pub const fn new(network: N, chain_spec: Arc<ChainSpec>) -> Self {
-        Self { network, chain_spec }
+    pub const fn new(network: N, chain_spec: Arc<ChainSpec>, pool: Pool) -> Self {
+        Self { network, chain_spec, pool }
     }
 }
 
 #[async_trait]
-impl<N> AdminApiServer for AdminApi<N>
+impl<N, Pool> AdminApiServer for AdminApi<N, Pool>
 where
     N: NetworkInfo + Peers + 'static,
+    Pool: TransactionPool + 'static,
 {
     /// Handler for `admin_addPeer`
     fn add_peer(&self, record: NodeRecord) -> RpcResult<bool> {
@@ -91,6 +96,14 @@ where
         Ok(peers)
     }
 
+    /// Handler for `admin_exportPeers`
+    async fn export_peers(&self) -> RpcResult<Vec<KnownPeer>> {
+        let mut peers = self.network.get_known_peers().await.to_rpc_result()?;
+        // best peers first
+        peers.sort_unstable_by(|a, b| b.reputation.cmp(&a.reputation));
+        Ok(peers)
+    }
+
     /// Handler for `admin_nodeInfo`
     async fn node_info(&self) -> RpcResult<NodeInfo> {
         let enode = self.network.local_node_record();
@@ -134,9 +147,24 @@ where
     ) -> jsonrpsee::core::SubscriptionResult {
         Err("admin_peerEvents is not implemented yet".into())
     }
+
+    /// Handler for `admin_localTransactions`
+    fn local_transactions(&self) -> RpcResult<Vec<Transaction>> {
+        Ok(self
+            .pool
+            .tracked_local_transactions()
+            .into_iter()
+            .map(|tx| from_recovered(tx.into_ecrecovered_transaction()))
+            .collect())
+    }
+
+    /// Handler for `admin_removeLocalTransaction`
+    fn remove_local_transaction(&self, hash: B256) -> RpcResult<bool> {
+        Ok(self.pool.untrack_local_transaction(hash).is_some())
+    }
 }
 
-impl<N> std::fmt::Debug for AdminApi<N> {
+impl<N, Pool> std::fmt::Debug for AdminApi<N, Pool> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AdminApi").finish_non_exhaustive()
     }
@@ -30,7 +30,10 @@ use reth_rpc_types::{
 use reth_tasks::pool::BlockingTaskGuard;
 use revm::{
     db::CacheDB,
-    primitives::{db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg},
+    primitives::{
+        db::DatabaseCommit, BlockEnv, CfgEnvWithHandlerCfg, Env, EnvWithHandlerCfg, ResultAndState,
+    },
+    Database, GetInspector,
 };
 use revm_inspectors::tracing::{
     js::{JsInspector, TransactionContext},
@@ -111,6 +114,7 @@ where
                             tx_hash: Some(tx_hash),
                             tx_index: Some(index),
                         }),
+                        false,
                     )?;
 
                     results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
@@ -251,6 +255,7 @@ where
                         tx_index: Some(index),
                         tx_hash: Some(tx.hash),
                     }),
+                    false,
                 )
                 .map(|(trace, _)| trace)
             })
@@ -262,6 +267,10 @@ where
     ///
     /// Differences compare to `eth_call`:
     ///  - `debug_traceCall` executes with __enabled__ basefee check, `eth_call` does not: <https://github.com/paradigmxyz/reth/issues/6240>
+    ///
+    /// Like in geth, the state overrides are applied before the call is traced, so they are part
+    /// of the pre-state reported by the prestate tracer, and the call is executed with the
+    /// hardfork that is active at the overridden block number and timestamp.
     pub async fn debug_trace_call(
         &self,
         call: TransactionRequest,
@@ -284,7 +293,7 @@ where
                             .inner
                             .eth_api
                             .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                this.eth_api().inspect(db, env, &mut inspector)?;
+                                this.eth_api().inspect_call(db, env, &mut inspector)?;
                                 Ok(inspector)
                             })
                             .await?;
@@ -303,7 +312,8 @@ where
                             .inner
                             .eth_api
                             .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                let (res, _) = this.eth_api().inspect(db, env, &mut inspector)?;
+                                let (res, _) =
+                                    this.eth_api().inspect_call(db, env, &mut inspector)?;
                                 let frame = inspector
                                     .into_geth_builder()
                                     .geth_call_traces(call_config, res.result.gas_used());
@@ -320,18 +330,20 @@ where
                             TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
                         );
 
-                        let frame =
-                            self.inner
-                                .eth_api
-                                .spawn_with_call_at(call, at, overrides, move |db, env| {
-                                    let (res, _) =
-                                        this.eth_api().inspect(&mut *db, env, &mut inspector)?;
-                                    let frame = inspector
-                                        .into_geth_builder()
-                                        .geth_prestate_traces(&res, prestate_config, db)?;
-                                    Ok(frame)
-                                })
-                                .await?;
+                        let frame = self
+                            .inner
+                            .eth_api
+                            .spawn_with_call_at(call, at, overrides, move |db, env| {
+                                let (res, _) =
+                                    this.eth_api().inspect_call(&mut *db, env, &mut inspector)?;
+                                let frame = inspector.into_geth_builder().geth_prestate_traces(
+                                    &res,
+                                    prestate_config,
+                                    db,
+                                )?;
+                                Ok(frame)
+                            })
+                            .await?;
                         return Ok(frame.into())
                     }
                     GethDebugBuiltInTracerType::NoopTracer => Ok(NoopFrame::default().into()),
@@ -347,7 +359,7 @@ where
                             .eth_api
                             .spawn_with_call_at(call, at, overrides, move |db, env| {
                                 let (res, _) =
-                                    this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                                    this.eth_api().inspect_call(&mut *db, env, &mut inspector)?;
                                 let frame = inspector.try_into_mux_frame(&res, db)?;
                                 Ok(frame.into())
                             })
@@ -365,8 +377,11 @@ where
                         .eth_api
                         .spawn_with_call_at(call, at, overrides, move |db, env| {
                             let mut inspector = JsInspector::new(code, config)?;
-                            let (res, _) =
-                                this.eth_api().inspect(&mut *db, env.clone(), &mut inspector)?;
+                            let (res, _) = this.eth_api().inspect_call(
+                                &mut *db,
+                                env.clone(),
+                                &mut inspector,
+                            )?;
                             Ok(inspector.json_result(res, &env, db)?)
                         })
                         .await?;
@@ -385,7 +400,7 @@ where
             .inner
             .eth_api
             .spawn_with_call_at(call, at, overrides, move |db, env| {
-                let (res, _) = this.eth_api().inspect(db, env, &mut inspector)?;
+                let (res, _) = this.eth_api().inspect_call(db, env, &mut inspector)?;
                 Ok((res, inspector))
             })
             .await?;
@@ -486,8 +501,13 @@ where
                             overrides,
                         )?;
 
-                        let (trace, state) =
-                            this.trace_transaction(tracing_options.clone(), env, &mut db, None)?;
+                        let (trace, state) = this.trace_transaction(
+                            tracing_options.clone(),
+                            env,
+                            &mut db,
+                            None,
+                            true,
+                        )?;
 
                         // If there is more transactions, commit the database
                         // If there is no transactions, but more bundles, commit to the database too
@@ -513,6 +533,9 @@ where
     ///
     /// Note: this does not apply any state overrides if they're configured in the `opts`.
     ///
+    /// If `is_call` is set, the transaction is a call, e.g. of `debug_traceCallMany`, and is
+    /// subject to the precompile gas budget, see [`EthTransactions::inspect_call`].
+    ///
     /// Caution: this is blocking and should be performed on a blocking task.
     fn trace_transaction(
         &self,
@@ -520,6 +543,7 @@ where
         env: EnvWithHandlerCfg,
         db: &mut CacheDB<StateProviderDatabase<StateProviderBox>>,
         transaction_context: Option<TransactionContext>,
+        is_call: bool,
     ) -> EthResult<(GethTrace, revm_primitives::EvmState)> {
         let GethDebugTracingOptions { config, tracer, tracer_config, .. } = opts;
 
@@ -528,7 +552,7 @@ where
                 GethDebugTracerType::BuiltInTracer(tracer) => match tracer {
                     GethDebugBuiltInTracerType::FourByteTracer => {
                         let mut inspector = FourByteInspector::default();
-                        let (res, _) = self.eth_api().inspect(db, env, &mut inspector)?;
+                        let (res, _) = self.inspect(db, env, &mut inspector, is_call)?;
                         return Ok((FourByteFrame::from(inspector).into(), res.state))
                     }
                     GethDebugBuiltInTracerType::CallTracer => {
@@ -540,7 +564,7 @@ where
                             TracingInspectorConfig::from_geth_call_config(&call_config),
                         );
 
-                        let (res, _) = self.eth_api().inspect(db, env, &mut inspector)?;
+                        let (res, _) = self.inspect(db, env, &mut inspector, is_call)?;
 
                         let frame = inspector
                             .into_geth_builder()
@@ -556,7 +580,7 @@ where
                         let mut inspector = TracingInspector::new(
                             TracingInspectorConfig::from_geth_prestate_config(&prestate_config),
                         );
-                        let (res, _) = self.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                        let (res, _) = self.inspect(&mut *db, env, &mut inspector, is_call)?;
 
                         let frame = inspector.into_geth_builder().geth_prestate_traces(
                             &res,
@@ -576,7 +600,7 @@ where
 
                         let mut inspector = MuxInspector::try_from_config(mux_config)?;
 
-                        let (res, _) = self.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                        let (res, _) = self.inspect(&mut *db, env, &mut inspector, is_call)?;
                         let frame = inspector.try_into_mux_frame(&res, db)?;
                         return Ok((frame.into(), res.state))
                     }
@@ -588,7 +612,7 @@ where
                         config,
                         transaction_context.unwrap_or_default(),
                     )?;
-                    let (res, env) = self.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                    let (res, env) = self.inspect(&mut *db, env, &mut inspector, is_call)?;
 
                     let state = res.state.clone();
                     let result = inspector.json_result(res, &env, db)?;
@@ -602,13 +626,34 @@ where
 
         let mut inspector = TracingInspector::new(inspector_config);
 
-        let (res, _) = self.eth_api().inspect(db, env, &mut inspector)?;
+        let (res, _) = self.inspect(db, env, &mut inspector, is_call)?;
         let gas_used = res.result.gas_used();
         let return_value = res.result.into_output().unwrap_or_default();
         let frame = inspector.into_geth_builder().geth_traces(gas_used, return_value, config);
 
         Ok((frame.into(), res.state))
     }
+
+    /// Executes the env with the inspector, as a call if `is_call` is set, see
+    /// [`EthTransactions::inspect_call`].
+    fn inspect<DB, I>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        inspector: I,
+        is_call: bool,
+    ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)>
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>,
+        I: GetInspector<DB>,
+    {
+        if is_call {
+            self.eth_api().inspect_call(db, env, inspector)
+        } else {
+            self.eth_api().inspect(db, env, inspector)
+        }
+    }
 }
 
 #[async_trait]
@@ -679,6 +724,16 @@ where
 
     /// Handler for `debug_getRawReceipts`
     async fn raw_receipts(&self, block_id: BlockId) -> RpcResult<Vec<Bytes>> {
+        if let BlockId::Hash(hash) = block_id {
+            // the encodings of the receipts of recently validated blocks can be cached
+            return Ok(self
+                .inner
+                .provider
+                .raw_receipts_by_block(hash.block_hash.into())
+                .to_rpc_result()?
+                .unwrap_or_default())
+        }
+
         Ok(self
             .inner
             .provider
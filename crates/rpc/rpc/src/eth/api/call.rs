@@ -2,7 +2,9 @@
 
 use crate::{
     eth::{
+        cache::EthCallCache,
         error::{ensure_success, EthApiError, EthResult, RevertError, RpcInvalidTransactionError},
+        revert_hints::RevertHintsInspector,
         revm_utils::{
             apply_state_overrides, build_call_evm_env, caller_gas_allowance,
             cap_tx_gas_limit_with_caller_allowance, get_precompiles, prepare_call_env,
@@ -26,7 +28,7 @@ use reth_transaction_pool::TransactionPool;
 use revm::{
     db::{CacheDB, DatabaseRef},
     primitives::{BlockEnv, CfgEnvWithHandlerCfg, EnvWithHandlerCfg, ExecutionResult, HaltReason},
-    DatabaseCommit,
+    Database, DatabaseCommit,
 };
 use revm_inspectors::access_list::AccessListInspector;
 use tracing::trace;
@@ -69,10 +71,50 @@ where
         block_number: Option<BlockId>,
         overrides: EvmOverrides,
     ) -> EthResult<Bytes> {
-        let (res, _env) =
-            self.transact_call_at(request, block_number.unwrap_or_default(), overrides).await?;
+        let at = block_number.unwrap_or_default();
+        // outputs are only cached for the unmodified state of a canonical block
+        let cacheable = !at.is_pending() && overrides.state.is_none() && overrides.block.is_none();
+        let Some(call_cache) = self.call_cache().filter(|_| cacheable) else {
+            return self.call_output(request, at, overrides).await
+        };
+
+        // resolve the block hash first and execute the call on it, so that the output is cached
+        // under the block it was executed on even if the chain reorgs in between
+        let block_hash =
+            self.provider().block_hash_for_id(at)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let key = EthCallCache::key(block_hash, &request);
+        if let Some(output) = key.as_ref().and_then(|key| call_cache.get(key)) {
+            return Ok(output)
+        }
 
-        ensure_success(res.result)
+        let output = self.call_output(request, block_hash.into(), overrides).await?;
+        if let Some(key) = key {
+            call_cache.insert(key, output.clone());
+        }
+        Ok(output)
+    }
+
+    /// Executes the call request at the given [`BlockId`] and returns the output if it succeeded.
+    async fn call_output(
+        &self,
+        request: TransactionRequest,
+        at: BlockId,
+        overrides: EvmOverrides,
+    ) -> EthResult<Bytes> {
+        if !self.revert_hints() {
+            let (res, _env) = self.transact_call_at(request, at, overrides).await?;
+            return ensure_success(res.result)
+        }
+
+        let this = self.clone();
+        self.spawn_with_call_at(request, at, overrides, move |db, env| {
+            let (res, env) = this.transact_call(&mut *db, env)?;
+            match res.result {
+                ExecutionResult::Revert { output, .. } => Err(this.revert_error(db, env, output)),
+                result => ensure_success(result),
+            }
+        })
+        .await
     }
 
     /// Simulate arbitrary number of transactions at an arbitrary blockchain index, with the
@@ -150,7 +192,7 @@ where
                     &mut db,
                     overrides,
                 )?;
-                let (res, _) = this.transact(&mut db, env)?;
+                let (res, _) = this.transact_call(&mut db, env)?;
 
                 match ensure_success(res.result) {
                     Ok(output) => {
@@ -228,7 +270,7 @@ where
                         // with the minimum gas limit to make sure.
                         let mut env = env.clone();
                         env.tx.gas_limit = MIN_TRANSACTION_GAS;
-                        if let Ok((res, _)) = self.transact(&mut db, env) {
+                        if let Ok((res, _)) = self.transact_call(&mut db, env) {
                             if res.result.is_success() {
                                 return Ok(U256::from(MIN_TRANSACTION_GAS))
                             }
@@ -255,7 +297,7 @@ where
         trace!(target: "rpc::eth::estimate", ?env, "Starting gas estimation");
 
         // Execute the transaction with the highest possible gas limit.
-        let (mut res, mut env) = match self.transact(&mut db, env.clone()) {
+        let (mut res, mut env) = match self.transact_call(&mut db, env.clone()) {
             // Handle the exceptional case where the transaction initialization uses too much gas.
             // If the gas price or gas limit was specified in the request, retry the transaction
             // with the block's gas limit to determine if the failure was due to
@@ -283,7 +325,7 @@ where
                     Err(self.map_out_of_gas_err(block_env_gas_limit, env, &mut db))
                 } else {
                     // the transaction did revert
-                    Err(RpcInvalidTransactionError::Revert(RevertError::new(output)).into())
+                    Err(self.revert_error(&mut db, env, output))
                 }
             }
         };
@@ -309,7 +351,7 @@ where
             env.tx.gas_limit = optimistic_gas_limit;
             // Re-execute the transaction with the new gas limit and update the result and
             // environment.
-            (res, env) = self.transact(&mut db, env)?;
+            (res, env) = self.transact_call(&mut db, env)?;
             // Update the gas used based on the new result.
             gas_used = res.result.gas_used();
             // Update the gas limit estimates (highest and lowest) based on the execution result.
@@ -344,7 +386,7 @@ where
             env.tx.gas_limit = mid_gas_limit;
 
             // Execute transaction and handle potential gas errors, adjusting limits accordingly.
-            match self.transact(&mut db, env.clone()) {
+            match self.transact_call(&mut db, env.clone()) {
                 // Check if the error is due to gas being too high.
                 Err(EthApiError::InvalidTransaction(RpcInvalidTransactionError::GasTooHigh)) => {
                     // Increase the lowest gas limit if gas is too high
@@ -423,7 +465,7 @@ where
 
         let precompiles = get_precompiles(env.handler_cfg.spec_id);
         let mut inspector = AccessListInspector::new(initial, from, to, precompiles);
-        let (result, env) = self.inspect(&mut db, env, &mut inspector)?;
+        let (result, env) = self.inspect_call(&mut db, env, &mut inspector)?;
 
         match result.result {
             ExecutionResult::Halt { reason, .. } => Err(match reason {
@@ -463,7 +505,7 @@ where
     {
         let req_gas_limit = env.tx.gas_limit;
         env.tx.gas_limit = env_gas_limit.try_into().unwrap_or(u64::MAX);
-        let (res, _) = match self.transact(db, env) {
+        let (res, env) = match self.transact_call(&mut *db, env) {
             Ok(res) => res,
             Err(err) => return err,
         };
@@ -475,13 +517,40 @@ where
             }
             ExecutionResult::Revert { output, .. } => {
                 // reverted again after bumping the limit
-                RpcInvalidTransactionError::Revert(RevertError::new(output)).into()
+                self.revert_error(db, env, output)
             }
             ExecutionResult::Halt { reason, .. } => {
                 RpcInvalidTransactionError::EvmHalt(reason).into()
             }
         }
     }
+
+    /// Returns the error for a call that reverted with the given output.
+    ///
+    /// If revert hints are enabled, the call is executed again with a [`RevertHintsInspector`] to
+    /// attach the hints to the error.
+    fn revert_error<DB>(&self, db: DB, env: EnvWithHandlerCfg, output: Bytes) -> EthApiError
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>,
+    {
+        let mut revert = RevertError::new(output);
+        if self.revert_hints() {
+            let mut inspector = RevertHintsInspector::default();
+            match self.inspect_call(db, env, &mut inspector) {
+                Ok((res, _)) => {
+                    if let ExecutionResult::Revert { output, gas_used } = res.result {
+                        revert = RevertError::new(output.clone())
+                            .with_hints(inspector.into_hints(output, gas_used));
+                    }
+                }
+                Err(err) => {
+                    trace!(target: "rpc::eth::call", %err, "Failed to collect revert hints");
+                }
+            }
+        }
+        RpcInvalidTransactionError::Revert(revert).into()
+    }
 }
 
 /// Updates the highest and lowest gas limits for binary search based on the execution result.
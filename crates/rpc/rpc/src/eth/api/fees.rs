@@ -27,10 +27,11 @@ where
     ///
     /// See also: <https://github.com/ethereum/pm/issues/328#issuecomment-853234014>
     pub(crate) async fn gas_price(&self) -> EthResult<U256> {
-        let header = self.block(BlockNumberOrTag::Latest);
-        let suggested_tip = self.suggested_priority_fee();
-        let (header, suggested_tip) = futures::try_join!(header, suggested_tip)?;
-        let base_fee = header.and_then(|h| h.base_fee_per_gas).unwrap_or_default();
+        // the latest header is tracked in memory by the provider, so there's no need to fetch the
+        // full block
+        let base_fee =
+            self.provider().latest_header()?.and_then(|h| h.base_fee_per_gas).unwrap_or_default();
+        let suggested_tip = self.suggested_priority_fee().await?;
         Ok(suggested_tip + U256::from(base_fee))
     }
 
@@ -43,9 +44,20 @@ where
             .map(U256::from)
     }
 
+    /// Returns a suggestion for the max fee per blob gas of blob transactions.
+    pub(crate) async fn suggested_blob_fee(&self) -> EthResult<U256> {
+        self.gas_oracle().suggest_blob_fee().await
+    }
+
     /// Returns a suggestion for the priority fee (the tip)
+    ///
+    /// While the node is syncing, the recent blocks aren't representative of the current fees and
+    /// the head changes too frequently to sample them, so the last suggestion is returned instead.
     pub(crate) async fn suggested_priority_fee(&self) -> EthResult<U256> {
-        self.gas_oracle().suggest_tip_cap().await
+        if self.network().is_syncing() {
+            return Ok(self.gas_oracle().last_tip_cap().await)
+        }
+        self.gas_oracle().suggest_tip_cap_with_pool(self.pool()).await
     }
 
     /// Reports the fee history, for the given amount of blocks, up until the given newest block.
@@ -6,7 +6,7 @@ use crate::eth::{
         fee_history::FeeHistoryCache,
         pending_block::{PendingBlock, PendingBlockEnv, PendingBlockEnvOrigin},
     },
-    cache::EthStateCache,
+    cache::{EthCallCache, EthStateCache},
     error::{EthApiError, EthResult},
     gas_oracle::GasPriceOracle,
     signer::EthSigner,
@@ -15,7 +15,7 @@ use crate::eth::{
 use async_trait::async_trait;
 use reth_chainspec::ChainInfo;
 use reth_errors::{RethError, RethResult};
-use reth_evm::ConfigureEvm;
+use reth_evm::{precompile::PrecompileGasBudget, ConfigureEvm};
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
     revm_primitives::{BlockEnv, CfgEnvWithHandlerCfg},
@@ -31,7 +31,10 @@ use revm_primitives::{CfgEnv, SpecId};
 use std::{
     fmt::Debug,
     future::Future,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 use tokio::sync::{oneshot, Mutex};
@@ -95,6 +98,44 @@ impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConf
     pub fn set_eth_raw_transaction_forwarder(&self, forwarder: Arc<dyn RawTransactionForwarder>) {
         self.inner.raw_transaction_forwarder.write().replace(forwarder);
     }
+
+    /// Sets a cache for the outputs of `eth_call` requests.
+    pub fn set_eth_call_cache(&self, call_cache: EthCallCache) {
+        self.inner.call_cache.write().replace(call_cache);
+    }
+
+    /// Returns the cache for the outputs of `eth_call` requests, if configured.
+    pub(crate) fn call_cache(&self) -> Option<EthCallCache> {
+        self.inner.call_cache.read().clone()
+    }
+
+    /// Sets the gas that precompiles may use in a single call.
+    ///
+    /// Calls whose precompile calls use more gas are rejected. This applies to the calls that are
+    /// executed with [`EthTransactions::transact_call`] and [`EthTransactions::inspect_call`], e.g.
+    /// by `eth_call`, `debug_traceCall` and `trace_call`, but not to transactions of blocks that
+    /// are replayed.
+    pub fn set_precompile_gas_budget(&self, budget: PrecompileGasBudget) {
+        self.inner.precompile_gas_budget.write().replace(budget);
+    }
+
+    /// Returns the gas that precompiles may use in a single call, if configured.
+    pub(crate) fn precompile_gas_budget(&self) -> Option<PrecompileGasBudget> {
+        *self.inner.precompile_gas_budget.read()
+    }
+
+    /// Sets whether the error data of reverted `eth_call` and `eth_estimateGas` calls includes
+    /// hints about the failure, see [`RevertHints`](crate::eth::revert_hints::RevertHints).
+    ///
+    /// Reverted calls are executed a second time with an inspector to collect the hints.
+    pub fn set_revert_hints(&self, enabled: bool) {
+        self.inner.revert_hints.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns whether revert hints are enabled.
+    pub(crate) fn revert_hints(&self) -> bool {
+        self.inner.revert_hints.load(Ordering::Relaxed)
+    }
 }
 
 impl<Provider, Pool, Network, EvmConfig> EthApi<Provider, Pool, Network, EvmConfig>
@@ -168,6 +209,9 @@ where
             fee_history_cache,
             evm_config,
             raw_transaction_forwarder: parking_lot::RwLock::new(raw_transaction_forwarder),
+            call_cache: Default::default(),
+            precompile_gas_budget: Default::default(),
+            revert_hints: Default::default(),
         };
 
         Self { inner: Arc::new(inner) }
@@ -500,4 +544,10 @@ struct EthApiInner<Provider, Pool, Network, EvmConfig> {
     evm_config: EvmConfig,
     /// Allows forwarding received raw transactions
     raw_transaction_forwarder: parking_lot::RwLock<Option<Arc<dyn RawTransactionForwarder>>>,
+    /// Cache for the outputs of `eth_call` requests, disabled if not set
+    call_cache: parking_lot::RwLock<Option<EthCallCache>>,
+    /// Gas that precompiles may use in a single call, unlimited if not set
+    precompile_gas_budget: parking_lot::RwLock<Option<PrecompileGasBudget>>,
+    /// Whether reverted calls are executed again to include hints in their error
+    revert_hints: AtomicBool,
 }
@@ -132,7 +132,8 @@ mod tests {
         let pool = testing_pool();
         let evm_config = EthEvmConfig::default();
 
-        let cache = EthStateCache::spawn(NoopProvider::default(), Default::default(), evm_config);
+        let cache =
+            EthStateCache::spawn(NoopProvider::default(), Default::default(), evm_config.clone());
         let eth_api = EthApi::new(
             NoopProvider::default(),
             pool.clone(),
@@ -142,7 +143,7 @@ mod tests {
             ETHEREUM_BLOCK_GAS_LIMIT,
             BlockingTaskPool::build().expect("failed to build tracing pool"),
             FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
-            evm_config,
+            evm_config.clone(),
             None,
         );
         let address = Address::random();
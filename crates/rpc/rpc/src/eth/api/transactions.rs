@@ -3,7 +3,7 @@ use crate::{
     eth::{
         api::pending_block::PendingBlockEnv,
         error::{EthApiError, EthResult, RpcInvalidTransactionError, SignError},
-        revm_utils::prepare_call_env,
+        revm_utils::{apply_block_spec, prepare_call_env},
         utils::recover_raw_transaction,
     },
     EthApi, EthApiSpec,
@@ -13,11 +13,12 @@ use async_trait::async_trait;
 use reth_evm::ConfigureEvm;
 use reth_network_api::NetworkInfo;
 use reth_primitives::{
+    eip191_hash_message,
     eip4844::calc_blob_gasprice,
     revm::env::{fill_block_env_with_coinbase, tx_env_with_recovered},
     Address, BlockId, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction, Header,
-    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders, TransactionMeta,
-    TransactionSigned, TransactionSignedEcRecovered,
+    IntoRecoveredTransaction, Receipt, SealedBlock, SealedBlockWithSenders, Signature,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered,
     TxKind::{Call, Create},
     B256, U256,
 };
@@ -32,11 +33,11 @@ use reth_rpc_types::{
         LegacyTransactionRequest,
     },
     AnyReceiptEnvelope, AnyTransactionReceipt, Index, Log, ReceiptWithBloom, Transaction,
-    TransactionInfo, TransactionReceipt, TransactionRequest, TypedTransactionRequest,
-    WithOtherFields,
+    TransactionConditional, TransactionInfo, TransactionReceipt, TransactionRequest,
+    TypedTransactionRequest, WithOtherFields,
 };
 use reth_rpc_types_compat::transaction::from_recovered_with_block_context;
-use reth_transaction_pool::{TransactionOrigin, TransactionPool};
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
 use revm::{
     db::CacheDB,
     primitives::{
@@ -104,6 +105,36 @@ pub trait EthTransactions: Send + Sync {
         <DB as Database>::Error: Into<EthApiError>,
         I: GetInspector<DB>;
 
+    /// Executes the [EnvWithHandlerCfg] of a call, e.g. of `eth_call`, against the given
+    /// [Database] without committing state changes.
+    ///
+    /// Unlike [Self::transact], the gas the precompiles may use is limited by the precompile gas
+    /// budget, if configured.
+    fn transact_call<DB>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+    ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)>
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>;
+
+    /// Executes the [EnvWithHandlerCfg] of a call, e.g. of `debug_traceCall`, against the given
+    /// [Database] without committing state changes.
+    ///
+    /// Unlike [Self::inspect], the gas the precompiles may use is limited by the precompile gas
+    /// budget, if configured.
+    fn inspect_call<DB, I>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        inspector: I,
+    ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)>
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>,
+        I: GetInspector<DB>;
+
     /// Same as [Self::inspect] but also returns the database again.
     ///
     /// Even though [Database] is also implemented on `&mut`
@@ -261,15 +292,45 @@ pub trait EthTransactions: Send + Sync {
     /// Returns the hash of the transaction.
     async fn send_raw_transaction(&self, tx: Bytes) -> EthResult<B256>;
 
+    /// Decodes and recovers the transaction and submits it to the pool together with the
+    /// conditions under which it may be included in a block.
+    ///
+    /// Returns the hash of the transaction.
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256>;
+
     /// Signs transaction with a matching signer, if any and submits the transaction to the pool.
     /// Returns the hash of the signed transaction.
     async fn send_transaction(&self, request: TransactionRequest) -> EthResult<B256>;
 
+    /// Removes a locally submitted transaction from the pool, so that it is no longer propagated
+    /// to peers.
+    ///
+    /// The signature must be the sender's EIP-191 signature of the transaction hash.
+    ///
+    /// If a price bump is given, a replacement is submitted at the same nonce: a transfer of zero
+    /// value from the sender to itself, with the fees of the cancelled transaction bumped by the
+    /// given percentage. This requires a signer for the sender.
+    ///
+    /// Returns the hash of the replacement, if any.
+    async fn cancel_transaction(
+        &self,
+        hash: B256,
+        signature: Bytes,
+        replacement_price_bump: Option<u128>,
+    ) -> EthResult<Option<B256>>;
+
     /// Prepares the state and env for the given [TransactionRequest] at the given [BlockId] and
     /// executes the closure on a new task returning the result of the closure.
     ///
     /// This returns the configured [EnvWithHandlerCfg] for the given [TransactionRequest] at the
     /// given [BlockId] and with configured call settings: `prepare_call_env`.
+    ///
+    /// If the block number or timestamp are overridden, the env is configured with the hardfork
+    /// that is active at the overridden block.
     async fn spawn_with_call_at<F, R>(
         &self,
         request: TransactionRequest,
@@ -565,6 +626,24 @@ where
         Ok((res, env))
     }
 
+    fn transact_call<DB>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+    ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)>
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>,
+    {
+        let mut evm = self.inner.evm_config.evm_with_env(db, env);
+        if let Some(budget) = self.precompile_gas_budget() {
+            evm = evm.modify().append_handler_register_box(budget.handler_register()).build();
+        }
+        let res = evm.transact()?;
+        let (_, env) = evm.into_db_and_env_with_handler_cfg();
+        Ok((res, env))
+    }
+
     fn inspect<DB, I>(
         &self,
         db: DB,
@@ -596,6 +675,26 @@ where
         Ok((res, env, db))
     }
 
+    fn inspect_call<DB, I>(
+        &self,
+        db: DB,
+        env: EnvWithHandlerCfg,
+        inspector: I,
+    ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)>
+    where
+        DB: Database,
+        <DB as Database>::Error: Into<EthApiError>,
+        I: GetInspector<DB>,
+    {
+        let mut evm = self.inner.evm_config.evm_with_env_and_inspector(db, env, inspector);
+        if let Some(budget) = self.precompile_gas_budget() {
+            evm = evm.modify().append_handler_register_box(budget.handler_register()).build();
+        }
+        let res = evm.transact()?;
+        let (_, env) = evm.into_db_and_env_with_handler_cfg();
+        Ok((res, env))
+    }
+
     fn replay_transactions_until<DB, I, Tx>(
         &self,
         db: &mut CacheDB<DB>,
@@ -865,6 +964,29 @@ where
         Ok(hash)
     }
 
+    async fn send_raw_transaction_conditional(
+        &self,
+        tx: Bytes,
+        conditional: TransactionConditional,
+    ) -> EthResult<B256> {
+        // the conditions would be lost when forwarding the transaction to the sequencer
+        if self.inner.raw_transaction_forwarder.read().is_some() {
+            return Err(EthApiError::Unsupported("conditional transactions can't be forwarded"))
+        }
+
+        let recovered = recover_raw_transaction(tx)?;
+        let pool_transaction = <Pool::Transaction>::from_recovered_pooled_transaction(recovered)
+            .with_conditional(conditional)
+            .ok_or(EthApiError::Unsupported("the pool doesn't support conditional transactions"))?;
+
+        // submit the transaction to the pool with a `Private` origin, because peers would receive
+        // it without its conditions, the conditions are validated by the pool
+        let hash =
+            self.pool().add_transaction(TransactionOrigin::Private, pool_transaction).await?;
+
+        Ok(hash)
+    }
+
     async fn send_transaction(&self, mut request: TransactionRequest) -> EthResult<B256> {
         let from = match request.from {
             Some(from) => from,
@@ -1060,6 +1182,64 @@ where
         Ok(hash)
     }
 
+    async fn cancel_transaction(
+        &self,
+        hash: B256,
+        signature: Bytes,
+        replacement_price_bump: Option<u128>,
+    ) -> EthResult<Option<B256>> {
+        let transaction = self.pool().get(&hash).ok_or(EthApiError::TransactionNotFound)?;
+        if !transaction.is_local() {
+            return Err(EthApiError::InvalidParams(
+                "only locally submitted transactions can be cancelled".to_string(),
+            ))
+        }
+        if recover_message_signer(hash.as_slice(), &signature) != Some(transaction.sender()) {
+            return Err(EthApiError::InvalidParams(
+                "signature is not the sender's signature of the transaction hash".to_string(),
+            ))
+        }
+
+        // prepare the replacement before removing the transaction, so that nothing is removed if
+        // the replacement can't be signed
+        let replacement = match replacement_price_bump {
+            Some(price_bump) => {
+                if transaction.is_eip4844() {
+                    return Err(EthApiError::InvalidParams(
+                        "blob transactions can't be replaced".to_string(),
+                    ))
+                }
+                let sender = transaction.sender();
+                self.find_signer(&sender)?;
+
+                let bump = |fee: u128| fee.saturating_mul(100 + price_bump).div_ceil(100);
+                let mut request = TransactionRequest {
+                    from: Some(sender),
+                    to: Some(RpcTransactionKind::Call(sender)),
+                    value: Some(U256::ZERO),
+                    nonce: Some(transaction.nonce()),
+                    ..Default::default()
+                };
+                match transaction.transaction.max_priority_fee_per_gas() {
+                    Some(max_priority_fee_per_gas) => {
+                        request.max_fee_per_gas = Some(bump(transaction.max_fee_per_gas()));
+                        request.max_priority_fee_per_gas = Some(bump(max_priority_fee_per_gas));
+                    }
+                    None => request.gas_price = Some(bump(transaction.max_fee_per_gas())),
+                }
+                Some(request)
+            }
+            None => None,
+        };
+
+        self.pool().remove_transactions(vec![hash]);
+
+        match replacement {
+            Some(request) => Ok(Some(self.send_transaction(request).await?)),
+            None => Ok(None),
+        }
+    }
+
     async fn spawn_with_call_at<F, R>(
         &self,
         request: TransactionRequest,
@@ -1072,6 +1252,10 @@ where
         R: Send + 'static,
     {
         let (cfg, block_env, at) = self.evm_env_at(at).await?;
+        let block_spec = overrides
+            .block
+            .as_ref()
+            .is_some_and(|overrides| overrides.number.is_some() || overrides.time.is_some());
         let this = self.clone();
         self.inner
             .blocking_task_pool
@@ -1079,7 +1263,7 @@ where
                 let state = this.state_at(at)?;
                 let mut db = CacheDB::new(StateProviderDatabase::new(state));
 
-                let env = prepare_call_env(
+                let mut env = prepare_call_env(
                     cfg,
                     block_env,
                     request,
@@ -1087,6 +1271,9 @@ where
                     &mut db,
                     overrides,
                 )?;
+                if block_spec {
+                    apply_block_spec(&this.inner.provider.chain_spec(), &mut env);
+                }
                 f(&mut db, env)
             })
             .await
@@ -1100,7 +1287,8 @@ where
         overrides: EvmOverrides,
     ) -> EthResult<(ResultAndState, EnvWithHandlerCfg)> {
         let this = self.clone();
-        self.spawn_with_call_at(request, at, overrides, move |db, env| this.transact(db, env)).await
+        self.spawn_with_call_at(request, at, overrides, move |db, env| this.transact_call(db, env))
+            .await
     }
 
     async fn spawn_inspect_call_at<I>(
@@ -1115,7 +1303,7 @@ where
     {
         let this = self.clone();
         self.spawn_with_call_at(request, at, overrides, move |db, env| {
-            this.inspect(db, env, inspector)
+            this.inspect_call(db, env, inspector)
         })
         .await
     }
@@ -1439,7 +1627,7 @@ where
     pub(crate) async fn eip4844_blob_fee(&self, blob_fee: Option<U256>) -> EthResult<U256> {
         match blob_fee {
             Some(blob_fee) => Ok(blob_fee),
-            None => self.blob_base_fee().await,
+            None => self.suggested_blob_fee().await,
         }
     }
 
@@ -1796,18 +1984,36 @@ pub(crate) fn build_transaction_receipt_with_block_receipts(
     Ok(res_receipt)
 }
 
+/// Recovers the signer of the EIP-191 signature of the given message, as returned by `eth_sign`.
+fn recover_message_signer(message: &[u8], signature: &[u8]) -> Option<Address> {
+    let signature: &[u8; 65] = signature.try_into().ok()?;
+    let odd_y_parity = match signature[64] {
+        0 | 27 => false,
+        1 | 28 => true,
+        _ => return None,
+    };
+    Signature {
+        r: U256::from_be_slice(&signature[..32]),
+        s: U256::from_be_slice(&signature[32..64]),
+        odd_y_parity,
+    }
+    .recover_signer(eip191_hash_message(message))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::eth::{
         cache::EthStateCache, gas_oracle::GasPriceOracle, FeeHistoryCache, FeeHistoryCacheConfig,
     };
+    use reth_evm::precompile::PrecompileGasBudget;
     use reth_evm_ethereum::EthEvmConfig;
     use reth_network_api::noop::NoopNetwork;
-    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex};
+    use reth_primitives::{constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, Address, TxKind};
     use reth_provider::test_utils::NoopProvider;
     use reth_tasks::pool::BlockingTaskPool;
     use reth_transaction_pool::test_utils::testing_pool;
+    use revm::primitives::{CfgEnv, TxEnv};
 
     #[tokio::test]
     async fn send_raw_transaction() {
@@ -1817,7 +2023,7 @@ mod tests {
         let pool = testing_pool();
 
         let evm_config = EthEvmConfig::default();
-        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config);
+        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config.clone());
         let fee_history_cache =
             FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
         let eth_api = EthApi::new(
@@ -1858,4 +2064,68 @@ mod tests {
         assert!(pool.get(&tx_1_result).is_some(), "tx1 not found in the pool");
         assert!(pool.get(&tx_2_result).is_some(), "tx2 not found in the pool");
     }
+
+    #[test]
+    fn recovers_message_signer() {
+        let hash = B256::random();
+        let signature = reth_primitives::sign_message(B256::random(), eip191_hash_message(hash))
+            .unwrap()
+            .to_bytes();
+        let signer = recover_message_signer(hash.as_slice(), &signature).unwrap();
+
+        // `v` may be either 27/28 or 0/1
+        let mut raw_parity = signature;
+        raw_parity[64] -= 27;
+        assert_eq!(recover_message_signer(hash.as_slice(), &raw_parity), Some(signer));
+
+        // a signature of another message recovers another signer
+        let other = B256::random();
+        assert_ne!(recover_message_signer(other.as_slice(), &signature), Some(signer));
+        assert_eq!(recover_message_signer(hash.as_slice(), &signature[..64]), None);
+    }
+
+    #[test]
+    fn precompile_gas_budget_applies_to_calls() {
+        let noop_provider = NoopProvider::default();
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(noop_provider, Default::default(), evm_config.clone());
+        let fee_history_cache =
+            FeeHistoryCache::new(cache.clone(), FeeHistoryCacheConfig::default());
+        let eth_api = EthApi::new(
+            noop_provider,
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(noop_provider, Default::default(), cache),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            fee_history_cache,
+            evm_config,
+            None,
+        );
+        eth_api.set_precompile_gas_budget(PrecompileGasBudget::new(20));
+
+        // a call to the identity precompile with 64 bytes of input uses 21 gas
+        let env = EnvWithHandlerCfg::new_with_cfg_env(
+            CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::CANCUN),
+            BlockEnv::default(),
+            TxEnv {
+                transact_to: TxKind::Call(Address::with_last_byte(4)),
+                data: Bytes::from(vec![1; 64]),
+                gas_limit: 100_000,
+                ..Default::default()
+            },
+        );
+
+        let db = || StateProviderDatabase::new(noop_provider);
+
+        // transactions of blocks that are replayed are not subject to the budget
+        let (res, _) = eth_api.transact(db(), env.clone()).unwrap();
+        assert!(res.result.is_success());
+        let (res, _) = eth_api.inspect(db(), env.clone(), TracingInspector::default()).unwrap();
+        assert!(res.result.is_success());
+
+        assert!(eth_api.transact_call(db(), env.clone()).is_err());
+        assert!(eth_api.inspect_call(db(), env, TracingInspector::default()).is_err());
+    }
 }
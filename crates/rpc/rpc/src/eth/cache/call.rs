@@ -0,0 +1,122 @@
+//! Cache for `eth_call` results.
+
+use super::metrics::CallCacheMetrics;
+use reth_primitives::{keccak256, Bytes, B256};
+use reth_rpc_types::TransactionRequest;
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// Caches the outputs of successful `eth_call` requests.
+///
+/// Entries are keyed by the hash of the block the call was executed on and the hash of the call
+/// request. Since the state of a block is identified by its hash, a cached output stays valid
+/// regardless of reorgs: callers must resolve tags and numbers to the canonical block hash
+/// _before_ executing the call and execute it on that hash, so that a reorg between resolving
+/// and executing can't store an output under the wrong block.
+///
+/// Calls on the pending block and calls with state or block overrides must not be cached.
+#[derive(Clone)]
+pub struct EthCallCache {
+    inner: Arc<EthCallCacheInner>,
+}
+
+impl EthCallCache {
+    /// Creates a new cache that holds up to `max_len` call outputs.
+    pub fn new(max_len: u32) -> Self {
+        Self {
+            inner: Arc::new(EthCallCacheInner {
+                outputs: parking_lot::Mutex::new(LruMap::new(ByLength::new(max_len))),
+                metrics: CallCacheMetrics::default(),
+            }),
+        }
+    }
+
+    /// Returns the key for the call request executed on the block with the given hash.
+    pub fn key(block_hash: B256, request: &TransactionRequest) -> Option<EthCallCacheKey> {
+        let request = serde_json::to_vec(request).ok()?;
+        Some(EthCallCacheKey { block_hash, request_hash: keccak256(request) })
+    }
+
+    /// Returns the cached output for the key, if any.
+    pub fn get(&self, key: &EthCallCacheKey) -> Option<Bytes> {
+        let output = self.inner.outputs.lock().get(key).cloned();
+        if output.is_some() {
+            self.inner.metrics.hits_total.increment(1);
+        } else {
+            self.inner.metrics.misses_total.increment(1);
+        }
+        output
+    }
+
+    /// Caches the output of the call.
+    pub fn insert(&self, key: EthCallCacheKey, output: Bytes) {
+        let mut outputs = self.inner.outputs.lock();
+        outputs.insert(key, output);
+        self.inner.metrics.cached_count.set(outputs.len() as f64);
+    }
+
+    /// Returns the number of cached outputs.
+    pub fn len(&self) -> usize {
+        self.inner.outputs.lock().len()
+    }
+
+    /// Returns `true` if no outputs are cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Debug for EthCallCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EthCallCache").field("cache_length", &self.len()).finish()
+    }
+}
+
+/// Key of a cached `eth_call` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EthCallCacheKey {
+    /// Hash of the block the call was executed on.
+    pub block_hash: B256,
+    /// Hash of the serialized call request.
+    pub request_hash: B256,
+}
+
+struct EthCallCacheInner {
+    outputs: parking_lot::Mutex<LruMap<EthCallCacheKey, Bytes, ByLength>>,
+    metrics: CallCacheMetrics,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Address;
+
+    #[test]
+    fn call_cache_is_keyed_by_block_and_request() {
+        let cache = EthCallCache::new(2);
+        let request =
+            TransactionRequest { from: Some(Address::repeat_byte(1)), ..Default::default() };
+        let other_request =
+            TransactionRequest { from: Some(Address::repeat_byte(2)), ..Default::default() };
+
+        let key = EthCallCache::key(B256::repeat_byte(1), &request).unwrap();
+        assert_eq!(cache.get(&key), None);
+        cache.insert(key, Bytes::from_static(&[1]));
+        assert_eq!(cache.get(&key), Some(Bytes::from_static(&[1])));
+        assert_eq!(EthCallCache::key(B256::repeat_byte(1), &request), Some(key));
+
+        // same request on another block, or another request on the same block
+        let other_block = EthCallCache::key(B256::repeat_byte(2), &request).unwrap();
+        assert_eq!(cache.get(&other_block), None);
+        let other_call = EthCallCache::key(B256::repeat_byte(1), &other_request).unwrap();
+        assert_eq!(cache.get(&other_call), None);
+
+        // bounded
+        cache.insert(other_block, Bytes::from_static(&[2]));
+        cache.insert(other_call, Bytes::from_static(&[3]));
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -13,3 +13,14 @@ pub(crate) struct CacheMetrics {
     /// The number of cache misses.
     pub(crate) misses_total: Counter,
 }
+
+#[derive(Metrics)]
+#[metrics(scope = "rpc.eth_call_cache")]
+pub(crate) struct CallCacheMetrics {
+    /// The number of cached call outputs.
+    pub(crate) cached_count: Gauge,
+    /// The number of cache hits.
+    pub(crate) hits_total: Counter,
+    /// The number of cache misses.
+    pub(crate) misses_total: Counter,
+}
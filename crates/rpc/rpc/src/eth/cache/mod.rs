@@ -26,6 +26,9 @@ use tokio::sync::{
 };
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
+mod call;
+pub use call::{EthCallCache, EthCallCacheKey};
+
 mod config;
 pub use config::*;
 
@@ -1,6 +1,9 @@
 //! Implementation specific Errors for the `eth_` namespace.
 
-use crate::result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code};
+use crate::{
+    eth::revert_hints::RevertHints,
+    result::{internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code},
+};
 use alloy_sol_types::decode_revert_reason;
 use jsonrpsee::types::{error::CALL_EXECUTION_FAILED_CODE, ErrorObject};
 use reth_errors::RethError;
@@ -9,8 +12,8 @@ use reth_rpc_types::{
     error::EthRpcErrorCode, request::TransactionInputError, BlockError, ToRpcError,
 };
 use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind,
-    PoolTransactionError,
+    ConditionalTransactionError, Eip4844PoolTransactionError, InvalidPoolTransactionError,
+    PoolError, PoolErrorKind, PoolTransactionError, ScreenedTransactionError,
 };
 use revm::primitives::{EVMError, ExecutionResult, HaltReason, OutOfGasError};
 use revm_inspectors::tracing::{js::JsInspectorError, MuxError};
@@ -353,6 +356,12 @@ pub enum RpcInvalidTransactionError {
     /// Blob transaction is a create transaction
     #[error("blob transaction is a create transaction")]
     BlobTransactionIsCreate,
+    /// EOF create transaction is missing the `to` address
+    #[error("EOF create transaction should have a to address")]
+    EofCreateShouldHaveToAddress,
+    /// Init code of a create transaction is not a valid EOF container
+    #[error("invalid EOF init code")]
+    InvalidEofInitCode,
     /// Optimism related error
     #[error(transparent)]
     #[cfg(feature = "optimism")]
@@ -409,6 +418,10 @@ impl From<RpcInvalidTransactionError> for ErrorObject<'static> {
     fn from(err: RpcInvalidTransactionError) -> Self {
         match err {
             RpcInvalidTransactionError::Revert(revert) => {
+                if let Some(hints) = &revert.hints {
+                    // include the hints instead of the out data
+                    return ErrorObject::owned(revert.error_code(), revert.to_string(), Some(hints))
+                }
                 // include out data if some
                 rpc_err(
                     revert.error_code(),
@@ -455,8 +468,9 @@ impl From<revm::primitives::InvalidTransaction> for RpcInvalidTransactionError {
             InvalidTransaction::HaltedDepositPostRegolith => {
                 Self::Optimism(OptimismInvalidTransactionError::HaltedDepositPostRegolith)
             }
-            // TODO(EOF)
-            InvalidTransaction::EofCrateShouldHaveToAddress => todo!("EOF"),
+            InvalidTransaction::EofCrateShouldHaveToAddress => {
+                Self::EofCreateShouldHaveToAddress
+            }
         }
     }
 }
@@ -497,6 +511,8 @@ pub struct RevertError {
     ///
     /// Note: this is `None` if output was empty
     output: Option<Bytes>,
+    /// Hints about the failure, if enabled
+    hints: Option<Box<RevertHints>>,
 }
 
 // === impl RevertError ==
@@ -507,12 +523,18 @@ impl RevertError {
     /// Note: this is intended to wrap an revm output
     pub fn new(output: Bytes) -> Self {
         if output.is_empty() {
-            Self { output: None }
+            Self { output: None, hints: None }
         } else {
-            Self { output: Some(output) }
+            Self { output: Some(output), hints: None }
         }
     }
 
+    /// Attaches hints about the failure, which replace the output as the data of the error
+    pub fn with_hints(mut self, hints: RevertHints) -> Self {
+        self.hints = Some(Box::new(hints));
+        self
+    }
+
     const fn error_code(&self) -> i32 {
         EthRpcErrorCode::ExecutionError.code()
     }
@@ -569,6 +591,12 @@ pub enum RpcPoolError {
     /// Eip-4844 related error
     #[error(transparent)]
     Eip4844(#[from] Eip4844PoolTransactionError),
+    /// The conditions of a conditional transaction aren't met
+    #[error(transparent)]
+    Conditional(#[from] ConditionalTransactionError),
+    /// The transaction was rejected by a screen of the pool
+    #[error(transparent)]
+    Screened(#[from] ScreenedTransactionError),
     /// Thrown if a conflicting transaction type is already in the pool
     ///
     /// In other words, thrown if a transaction with the same sender that violates the exclusivity
@@ -584,6 +612,12 @@ impl From<RpcPoolError> for ErrorObject<'static> {
     fn from(error: RpcPoolError) -> Self {
         match error {
             RpcPoolError::Invalid(err) => err.into(),
+            RpcPoolError::Conditional(err) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
+            }
+            RpcPoolError::Screened(err) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), err.to_string())
+            }
             error => internal_rpc_err(error.to_string()),
         }
     }
@@ -623,6 +657,11 @@ impl From<InvalidPoolTransactionError> for RpcPoolError {
             InvalidPoolTransactionError::Overdraft => {
                 Self::Invalid(RpcInvalidTransactionError::InsufficientFunds)
             }
+            InvalidPoolTransactionError::InvalidEofInitCode => {
+                Self::Invalid(RpcInvalidTransactionError::InvalidEofInitCode)
+            }
+            InvalidPoolTransactionError::Conditional(err) => Self::Conditional(err),
+            InvalidPoolTransactionError::Screened(err) => Self::Screened(err),
         }
     }
 }
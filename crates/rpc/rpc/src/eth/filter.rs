@@ -12,20 +12,21 @@ use core::fmt;
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, server::IdProvider};
 use reth_chainspec::ChainInfo;
-use reth_primitives::{IntoRecoveredTransaction, TxHash};
-use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, ProviderError};
+use reth_primitives::{Header, IntoRecoveredTransaction, TxHash, B256};
+use reth_provider::{BlockIdReader, BlockReader, EvmEnvProvider, LogIndexProvider, ProviderError};
 use reth_rpc_api::EthFilterApiServer;
 use reth_rpc_types::{
-    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilteredParams, Log,
-    PendingTransactionFilterKind,
+    BlockNumHash, Filter, FilterBlockOption, FilterChanges, FilterId, FilterSet, FilteredParams,
+    Log, PendingTransactionFilterKind, ValueOrArray,
 };
 
 use reth_tasks::TaskSpawner;
 use reth_transaction_pool::{NewSubpoolTransactionStream, PoolTransaction, TransactionPool};
 use std::{
     collections::HashMap,
+    hash::Hash,
     iter::StepBy,
-    ops::RangeInclusive,
+    ops::{Range, RangeInclusive},
     sync::Arc,
     time::{Duration, Instant},
 };
@@ -127,7 +128,7 @@ where
 
 impl<Provider, Pool> EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexProvider + 'static,
     Pool: TransactionPool + 'static,
     <Pool as TransactionPool>::Transaction: 'static,
 {
@@ -224,7 +225,7 @@ where
 #[async_trait]
 impl<Provider, Pool> EthFilterApiServer for EthFilter<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Handler for `eth_newFilter`
@@ -343,7 +344,7 @@ struct EthFilterInner<Provider, Pool> {
 
 impl<Provider, Pool> EthFilterInner<Provider, Pool>
 where
-    Provider: BlockReader + BlockIdReader + EvmEnvProvider + 'static,
+    Provider: BlockReader + BlockIdReader + EvmEnvProvider + LogIndexProvider + 'static,
     Pool: TransactionPool + 'static,
 {
     /// Returns logs matching given filter object.
@@ -467,19 +468,95 @@ where
         // derive bloom filters from filter input, so we can check headers for matching logs
         let address_filter = FilteredParams::address_filter(&filter.address);
         let topics_filter = FilteredParams::topics_filter(&filter.topics);
+        let matches_bloom = |header: &Header| {
+            FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
+                FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
+        };
+        // size check but only if range is multiple blocks, so we always return all logs of a
+        // single block
+        let is_multi_block_range = from_block != to_block;
+
+        // the blocks that are covered by the log index are looked up in the index, all other
+        // blocks are scanned for headers with a matching bloom filter
+        let addresses = filter_set_values(&filter.address);
+        let topics = filter.topics.iter().map(filter_set_values).collect::<Vec<_>>();
+        let indexed_range = if addresses.is_empty() && topics.iter().all(Vec::is_empty) {
+            None
+        } else {
+            self.provider.log_index_range()?.and_then(|index| {
+                let range = from_block.max(*index.start())..=to_block.min(*index.end());
+                (!range.is_empty()).then_some(range)
+            })
+        };
+        let (scanned_before, scanned_after) = match &indexed_range {
+            Some(range) => (from_block..*range.start(), range.end() + 1..to_block + 1),
+            None => (from_block..to_block + 1, 0..0),
+        };
+
+        self.append_logs_in_scanned_range(
+            &mut all_logs,
+            &filter_params,
+            scanned_before,
+            &matches_bloom,
+            is_multi_block_range,
+        )
+        .await?;
+
+        if let Some(range) = indexed_range {
+            for block_number in self.provider.blocks_with_logs(&addresses, &topics, range)? {
+                let header = self
+                    .provider
+                    .sealed_header(block_number)?
+                    .ok_or(ProviderError::HeaderNotFound(block_number.into()))?;
+                if matches_bloom(header.header()) {
+                    self.append_block_logs(
+                        &mut all_logs,
+                        &filter_params,
+                        header.header(),
+                        header.hash(),
+                        is_multi_block_range,
+                    )
+                    .await?;
+                }
+            }
+        }
+
+        self.append_logs_in_scanned_range(
+            &mut all_logs,
+            &filter_params,
+            scanned_after,
+            &matches_bloom,
+            is_multi_block_range,
+        )
+        .await?;
+
+        Ok(all_logs)
+    }
+
+    /// Appends the logs of the blocks in the range that match the filter, checking the bloom
+    /// filters of the headers of all blocks first.
+    async fn append_logs_in_scanned_range(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        range: Range<u64>,
+        matches_bloom: &impl Fn(&Header) -> bool,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
+        if range.is_empty() {
+            return Ok(())
+        }
 
         // loop over the range of new blocks and check logs if the filter matches the log's bloom
         // filter
         for (from, to) in
-            BlockRangeInclusiveIter::new(from_block..=to_block, self.max_headers_range)
+            BlockRangeInclusiveIter::new(range.start..=range.end - 1, self.max_headers_range)
         {
             let headers = self.provider.headers_range(from..=to)?;
 
             for (idx, header) in headers.iter().enumerate() {
                 // only if filter matches
-                if FilteredParams::matches_address(header.logs_bloom, &address_filter) &&
-                    FilteredParams::matches_topics(header.logs_bloom, &topics_filter)
-                {
+                if matches_bloom(header) {
                     // these are consecutive headers, so we can use the parent hash of the next
                     // block to get the current header's hash
                     let block_hash = match headers.get(idx + 1) {
@@ -490,31 +567,58 @@ where
                             .ok_or(ProviderError::HeaderNotFound(header.number.into()))?,
                     };
 
-                    if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
-                        append_matching_block_logs(
-                            &mut all_logs,
-                            &self.provider,
-                            &filter_params,
-                            BlockNumHash::new(header.number, block_hash),
-                            &receipts,
-                            false,
-                            header.timestamp,
-                        )?;
-
-                        // size check but only if range is multiple blocks, so we always return all
-                        // logs of a single block
-                        let is_multi_block_range = from_block != to_block;
-                        if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
-                            return Err(FilterError::QueryExceedsMaxResults(
-                                self.max_logs_per_response,
-                            ))
-                        }
-                    }
+                    self.append_block_logs(
+                        all_logs,
+                        filter_params,
+                        header,
+                        block_hash,
+                        is_multi_block_range,
+                    )
+                    .await?;
                 }
             }
         }
 
-        Ok(all_logs)
+        Ok(())
+    }
+
+    /// Appends the logs of the block that match the filter.
+    ///
+    /// Returns an error if the range has multiple blocks and the amount of matches exceeds the
+    /// configured limit.
+    async fn append_block_logs(
+        &self,
+        all_logs: &mut Vec<Log>,
+        filter_params: &FilteredParams,
+        header: &Header,
+        block_hash: B256,
+        is_multi_block_range: bool,
+    ) -> Result<(), FilterError> {
+        if let Some(receipts) = self.eth_cache.get_receipts(block_hash).await? {
+            append_matching_block_logs(
+                all_logs,
+                &self.provider,
+                filter_params,
+                BlockNumHash::new(header.number, block_hash),
+                &receipts,
+                false,
+                header.timestamp,
+            )?;
+
+            if is_multi_block_range && all_logs.len() > self.max_logs_per_response {
+                return Err(FilterError::QueryExceedsMaxResults(self.max_logs_per_response))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the values of the filter set, which are empty if the set matches all values.
+fn filter_set_values<T: Clone + Eq + Hash>(set: &FilterSet<T>) -> Vec<T> {
+    match set.to_value_or_array() {
+        Some(ValueOrArray::Value(value)) => vec![value],
+        Some(ValueOrArray::Array(values)) => values,
+        None => Vec::new(),
     }
 }
 
@@ -6,54 +6,22 @@ use crate::eth::{
     error::{EthApiError, EthResult, RpcInvalidTransactionError},
 };
 use derive_more::{Deref, DerefMut};
-use reth_primitives::{constants::GWEI_TO_WEI, BlockNumberOrTag, B256, U256};
+use reth_primitives::{
+    constants::{
+        eip4844::{MAX_DATA_GAS_PER_BLOCK, TARGET_DATA_GAS_PER_BLOCK},
+        GWEI_TO_WEI,
+    },
+    eip4844::calc_blob_gasprice,
+    BlockNumberOrTag, SealedHeader, B256, U256,
+};
 use reth_provider::BlockReaderIdExt;
-use reth_rpc_server_types::constants::gas_oracle::*;
+use reth_transaction_pool::TransactionPool;
 use schnellru::{ByLength, LruMap};
-use serde::{Deserialize, Serialize};
 use std::fmt::{self, Debug, Formatter};
 use tokio::sync::Mutex;
 use tracing::warn;
 
-/// Settings for the [`GasPriceOracle`]
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct GasPriceOracleConfig {
-    /// The number of populated blocks to produce the gas price estimate
-    pub blocks: u32,
-
-    /// The percentile of gas prices to use for the estimate
-    pub percentile: u32,
-
-    /// The maximum number of headers to keep in the cache
-    pub max_header_history: u64,
-
-    /// The maximum number of blocks for estimating gas price
-    pub max_block_history: u64,
-
-    /// The default gas price to use if there are no blocks to use
-    pub default: Option<U256>,
-
-    /// The maximum gas price to use for the estimate
-    pub max_price: Option<U256>,
-
-    /// The minimum gas price, under which the sample will be ignored
-    pub ignore_price: Option<U256>,
-}
-
-impl Default for GasPriceOracleConfig {
-    fn default() -> Self {
-        Self {
-            blocks: DEFAULT_GAS_PRICE_BLOCKS,
-            percentile: DEFAULT_GAS_PRICE_PERCENTILE,
-            max_header_history: MAX_HEADER_HISTORY,
-            max_block_history: MAX_HEADER_HISTORY,
-            default: None,
-            max_price: Some(DEFAULT_MAX_GAS_PRICE),
-            ignore_price: Some(DEFAULT_IGNORE_GAS_PRICE),
-        }
-    }
-}
+pub use reth_rpc_server_types::gas_oracle::{GasPriceOracleConfig, PriorityFeeStrategy};
 
 /// Calculates a gas price depending on recent blocks.
 #[derive(Debug)]
@@ -66,7 +34,7 @@ pub struct GasPriceOracle<Provider> {
     oracle_config: GasPriceOracleConfig,
     /// The price under which the sample will be ignored.
     ignore_price: Option<u128>,
-    /// Stores the latest calculated price and its block hash and Cache stores the lowest effective
+    /// Stores the latest calculated prices and their block hashes and Cache stores the sampled
     /// tip values of recent blocks
     inner: Mutex<GasPriceOracleInner>,
 }
@@ -90,8 +58,14 @@ where
 
         // this is the number of blocks that we will cache the values for
         let cached_values = (oracle_config.blocks * 5).max(oracle_config.max_block_history as u32);
+        let last_price = GasPriceOracleResult {
+            block_hash: B256::ZERO,
+            price: oracle_config.default.unwrap_or(U256::from(GWEI_TO_WEI)),
+        };
         let inner = Mutex::new(GasPriceOracleInner {
-            last_price: Default::default(),
+            last_price: last_price.clone(),
+            last_pool_price: last_price,
+            last_blob_fee: None,
             lowest_effective_tip_cache: EffectiveTipLruCache(LruMap::new(ByLength::new(
                 cached_values,
             ))),
@@ -107,13 +81,99 @@ where
 
     /// Suggests a gas price estimate based on recent blocks, using the configured percentile.
     pub async fn suggest_tip_cap(&self) -> EthResult<U256> {
-        let header = self
-            .provider
-            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
-            .ok_or(EthApiError::UnknownBlockNumber)?;
+        let header = self.latest_header()?;
+        let mut inner = self.inner.lock().await;
+        self.history_tip_cap(&mut inner, &header).await
+    }
+
+    /// Returns the last gas price estimate computed by [`Self::suggest_tip_cap`], or the configured
+    /// default if there's none yet.
+    pub async fn last_tip_cap(&self) -> U256 {
+        self.inner.lock().await.last_price.price
+    }
+
+    /// Suggests a gas price estimate like [`Self::suggest_tip_cap`], raised to the tip the pending
+    /// transactions of the pool need to be included in the next block if they don't all fit, see
+    /// [`GasPriceOracleConfig::txpool`].
+    ///
+    /// The suggestion is computed once per block.
+    pub async fn suggest_tip_cap_with_pool<Pool>(&self, pool: &Pool) -> EthResult<U256>
+    where
+        Pool: TransactionPool,
+    {
+        if !self.oracle_config.txpool {
+            return self.suggest_tip_cap().await
+        }
+
+        let header = self.latest_header()?;
+        let mut inner = self.inner.lock().await;
+        if inner.last_pool_price.block_hash == header.hash() {
+            return Ok(inner.last_pool_price.price)
+        }
+
+        let mut price = self.history_tip_cap(&mut inner, &header).await?;
+        let gas_usage = self.gas_usage(&header).await?;
+        if let Some(pending_tip) = pending_tip_cap(pool, header.gas_limit, gas_usage) {
+            price = self.constrain_price(price.max(pending_tip));
+        }
 
+        inner.last_pool_price = GasPriceOracleResult { block_hash: header.hash(), price };
+
+        Ok(price)
+    }
+
+    /// Suggests a max fee per blob gas, that covers the blob base fee of the next block and the
+    /// following [`GasPriceOracleConfig::blob_fee_blocks`] blocks, even if they are all full of
+    /// blobs.
+    ///
+    /// The suggestion is computed once per block.
+    pub async fn suggest_blob_fee(&self) -> EthResult<U256> {
+        let header = self.latest_header()?;
         let mut inner = self.inner.lock().await;
+        if let Some(last_blob_fee) =
+            inner.last_blob_fee.as_ref().filter(|result| result.block_hash == header.hash())
+        {
+            return Ok(last_blob_fee.price)
+        }
+
+        // every full block increases the excess blob gas by the blob gas above the target
+        let excess_blob_gas =
+            header.next_block_excess_blob_gas().ok_or(EthApiError::ExcessBlobGasNotSet)?;
+        let excess_blob_gas = excess_blob_gas.saturating_add(
+            self.oracle_config
+                .blob_fee_blocks
+                .saturating_mul(MAX_DATA_GAS_PER_BLOCK - TARGET_DATA_GAS_PER_BLOCK),
+        );
+        let price = U256::from(calc_blob_gasprice(excess_blob_gas));
 
+        inner.last_blob_fee = Some(GasPriceOracleResult { block_hash: header.hash(), price });
+
+        Ok(price)
+    }
+
+    /// Returns the gas the transactions of the block of the given header used, compared to their
+    /// gas limits.
+    async fn gas_usage(&self, header: &SealedHeader) -> EthResult<GasUsage> {
+        let block =
+            self.cache.get_block(header.hash()).await?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let gas_limit = block.body.iter().map(|tx| tx.gas_limit()).sum();
+        Ok(GasUsage { gas_used: header.gas_used, gas_limit })
+    }
+
+    /// Returns the latest header.
+    fn latest_header(&self) -> EthResult<SealedHeader> {
+        self.provider
+            .sealed_header_by_number_or_tag(BlockNumberOrTag::Latest)?
+            .ok_or(EthApiError::UnknownBlockNumber)
+    }
+
+    /// Returns the gas price estimate based on the blocks up to the given header, which is
+    /// computed once per block.
+    async fn history_tip_cap(
+        &self,
+        inner: &mut GasPriceOracleInner,
+        header: &SealedHeader,
+    ) -> EthResult<U256> {
         // if we have stored a last price, then we check whether or not it was for the same head
         if inner.last_price.block_hash == header.hash() {
             return Ok(inner.last_price.price)
@@ -143,7 +203,7 @@ where
                 } else {
                     // Otherwise we fetch it using get_block_values
                     let (parent_hash, block_values) = self
-                        .get_block_values(current_hash, SAMPLE_NUMBER)
+                        .get_block_values(current_hash)
                         .await?
                         .ok_or(EthApiError::UnknownBlockNumber)?;
                     inner
@@ -153,7 +213,10 @@ where
                 };
 
             if block_values.is_empty() {
-                results.push(U256::from(inner.last_price.price));
+                // empty blocks don't use any gas to weigh the last price with
+                if self.oracle_config.strategy == PriorityFeeStrategy::Lowest {
+                    results.push(TipSample { tip: inner.last_price.price, gas_used: 1 });
+                }
             } else {
                 results.extend(block_values);
                 populated_blocks += 1;
@@ -168,30 +231,37 @@ where
         }
 
         // sort results then take the configured percentile result
-        let mut price = if !results.is_empty() {
-            results.sort_unstable();
-            *results.get((results.len() - 1) * self.oracle_config.percentile as usize / 100).expect(
-                "gas price index is a percent of nonzero array length, so a value always exists",
-            )
-        } else {
+        let price = if results.is_empty() {
             inner.last_price.price
-        };
-
-        // constrain to the max price
-        if let Some(max_price) = self.oracle_config.max_price {
-            if price > max_price {
-                price = max_price;
+        } else {
+            results.sort_unstable_by_key(|sample| sample.tip);
+            match self.oracle_config.strategy {
+                PriorityFeeStrategy::Lowest => {
+                    results[(results.len() - 1) * self.oracle_config.percentile as usize / 100].tip
+                }
+                PriorityFeeStrategy::GasWeighted => {
+                    gas_weighted_percentile(&results, self.oracle_config.percentile)
+                }
             }
-        }
+        };
+        let price = self.constrain_price(price);
 
         inner.last_price = GasPriceOracleResult { block_hash: header.hash(), price };
 
         Ok(price)
     }
 
-    /// Get the `limit` lowest effective tip values for the given block. If the oracle has a
-    /// configured `ignore_price` threshold, then tip values under that threshold will be ignored
-    /// before returning a result.
+    /// Constrains the price to the configured max price.
+    fn constrain_price(&self, price: U256) -> U256 {
+        match self.oracle_config.max_price {
+            Some(max_price) => price.min(max_price),
+            None => price,
+        }
+    }
+
+    /// Get the sampled effective tip values for the given block, depending on the configured
+    /// [`PriorityFeeStrategy`]. If the oracle has a configured `ignore_price` threshold, then tip
+    /// values under that threshold will be ignored before returning a result.
     ///
     /// If the block cannot be found, then this will return `None`.
     ///
@@ -199,8 +269,23 @@ where
     async fn get_block_values(
         &self,
         block_hash: B256,
+    ) -> EthResult<Option<(B256, Vec<TipSample>)>> {
+        match self.oracle_config.strategy {
+            PriorityFeeStrategy::Lowest => {
+                self.get_lowest_block_values(block_hash, self.oracle_config.sample_size).await
+            }
+            PriorityFeeStrategy::GasWeighted => {
+                self.get_gas_weighted_block_values(block_hash).await
+            }
+        }
+    }
+
+    /// Get the `limit` lowest effective tip values for the given block.
+    async fn get_lowest_block_values(
+        &self,
+        block_hash: B256,
         limit: usize,
-    ) -> EthResult<Option<(B256, Vec<U256>)>> {
+    ) -> EthResult<Option<(B256, Vec<TipSample>)>> {
         // check the cache (this will hit the disk if the block is not cached)
         let mut block = match self.cache.get_block(block_hash).await? {
             Some(block) => block,
@@ -239,7 +324,7 @@ where
                 .unwrap_or_else(|| tx.effective_tip_per_gas(base_fee_per_gas))
                 .ok_or(RpcInvalidTransactionError::FeeCapTooLow)?;
 
-            prices.push(U256::from(effective_gas_tip));
+            prices.push(TipSample { tip: U256::from(effective_gas_tip), gas_used: 1 });
 
             // we have enough entries
             if prices.len() >= limit {
@@ -249,18 +334,127 @@ where
 
         Ok(Some((parent_hash, prices)))
     }
+
+    /// Get the effective tip values of all transactions of the given block, with the gas they
+    /// used.
+    async fn get_gas_weighted_block_values(
+        &self,
+        block_hash: B256,
+    ) -> EthResult<Option<(B256, Vec<TipSample>)>> {
+        let (block, receipts) = futures::try_join!(
+            self.cache.get_block_with_senders(block_hash),
+            self.cache.get_receipts(block_hash)
+        )?;
+        let (Some(block), Some(receipts)) = (block, receipts) else { return Ok(None) };
+
+        let base_fee_per_gas = block.base_fee_per_gas;
+        let mut prices = Vec::with_capacity(block.body.len());
+        let mut last_cumulative_gas_used = 0;
+
+        for ((tx, sender), receipt) in block.body.iter().zip(&block.senders).zip(receipts.iter()) {
+            let gas_used = receipt.cumulative_gas_used.saturating_sub(last_cumulative_gas_used);
+            last_cumulative_gas_used = receipt.cumulative_gas_used;
+
+            // a `None` effective_gas_tip represents a transaction where the max_fee_per_gas is
+            // less than the base fee which would be invalid
+            let effective_gas_tip = tx
+                .effective_tip_per_gas(base_fee_per_gas)
+                .ok_or(RpcInvalidTransactionError::FeeCapTooLow)?;
+
+            // ignore transactions with a tip under the configured threshold, or from the coinbase
+            if self.ignore_price.map_or(false, |ignore_under| effective_gas_tip < ignore_under) ||
+                *sender == block.beneficiary
+            {
+                continue
+            }
+
+            prices.push(TipSample { tip: U256::from(effective_gas_tip), gas_used });
+        }
+
+        Ok(Some((block.parent_hash, prices)))
+    }
+}
+
+/// Returns the effective tip of the first transaction that doesn't fit into a block with the given
+/// gas limit after the better pending transactions of the pool, or `None` if they all fit.
+///
+/// The gas the transactions use is estimated with the [`GasUsage`] of the latest block.
+fn pending_tip_cap<Pool: TransactionPool>(
+    pool: &Pool,
+    gas_limit: u64,
+    gas_usage: GasUsage,
+) -> Option<U256> {
+    let base_fee = pool.block_info().pending_basefee;
+    let mut cumulative_gas_used = 0u64;
+    for tx in pool.best_transactions() {
+        cumulative_gas_used =
+            cumulative_gas_used.saturating_add(gas_usage.estimate(tx.gas_limit()));
+        if cumulative_gas_used > gas_limit {
+            return tx.effective_tip_per_gas(base_fee).map(U256::from)
+        }
+    }
+    None
+}
+
+/// The gas the transactions of a block used, compared to the sum of their gas limits.
+#[derive(Debug, Clone, Copy)]
+struct GasUsage {
+    gas_used: u64,
+    gas_limit: u64,
+}
+
+impl GasUsage {
+    /// Estimates the gas a transaction with the given gas limit uses, assuming it uses the same
+    /// share of its gas limit as the transactions of the block did on average.
+    ///
+    /// The entire gas limit is assumed to be used if the block has no transactions.
+    fn estimate(&self, gas_limit: u64) -> u64 {
+        if self.gas_limit == 0 {
+            return gas_limit
+        }
+        (gas_limit as u128 * self.gas_used.min(self.gas_limit) as u128 / self.gas_limit as u128)
+            as u64
+    }
+}
+
+/// Returns the tip under which the given percentile of the gas of the samples was paid.
+///
+/// The samples must be sorted by tip and not be empty.
+fn gas_weighted_percentile(samples: &[TipSample], percentile: u32) -> U256 {
+    let total_gas_used = samples.iter().map(|sample| sample.gas_used as u128).sum::<u128>();
+    let threshold = total_gas_used * percentile as u128 / 100;
+    let mut cumulative_gas_used = 0u128;
+    for sample in samples {
+        cumulative_gas_used += sample.gas_used as u128;
+        if cumulative_gas_used >= threshold {
+            return sample.tip
+        }
+    }
+    samples[samples.len() - 1].tip
 }
 
 /// Container type for mutable inner state of the [`GasPriceOracle`]
 #[derive(Debug)]
 struct GasPriceOracleInner {
     last_price: GasPriceOracleResult,
+    /// The last price including the pending transactions of the pool
+    last_pool_price: GasPriceOracleResult,
+    last_blob_fee: Option<GasPriceOracleResult>,
     lowest_effective_tip_cache: EffectiveTipLruCache,
 }
 
+/// An effective tip sampled from a block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TipSample {
+    /// The effective tip per gas of the transaction
+    pub tip: U256,
+    /// The gas the transaction used, or one if the samples aren't weighted by gas
+    pub gas_used: u64,
+}
+
 /// Wrapper struct for `LruMap`
 #[derive(Deref, DerefMut)]
-pub struct EffectiveTipLruCache(LruMap<B256, (B256, Vec<U256>), ByLength>);
+pub struct EffectiveTipLruCache(LruMap<B256, (B256, Vec<TipSample>), ByLength>);
 
 impl Debug for EffectiveTipLruCache {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
@@ -289,6 +483,11 @@ impl Default for GasPriceOracleResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_rpc_server_types::constants::gas_oracle::*;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
 
     #[test]
     fn max_price_sanity() {
@@ -300,4 +499,48 @@ mod tests {
     fn ignore_price_sanity() {
         assert_eq!(DEFAULT_IGNORE_GAS_PRICE, U256::from(2u64));
     }
+
+    #[test]
+    fn gas_weighted_percentiles() {
+        let samples = [
+            TipSample { tip: U256::from(1), gas_used: 21_000 },
+            TipSample { tip: U256::from(2), gas_used: 100_000 },
+            TipSample { tip: U256::from(3), gas_used: 21_000 },
+        ];
+        assert_eq!(gas_weighted_percentile(&samples, 0), U256::from(1));
+        assert_eq!(gas_weighted_percentile(&samples, 10), U256::from(1));
+        assert_eq!(gas_weighted_percentile(&samples, 60), U256::from(2));
+        assert_eq!(gas_weighted_percentile(&samples, 90), U256::from(3));
+        assert_eq!(gas_weighted_percentile(&samples, 100), U256::from(3));
+    }
+
+    #[test]
+    fn gas_usage_estimates() {
+        let gas_usage = GasUsage { gas_used: 15_000_000, gas_limit: 30_000_000 };
+        assert_eq!(gas_usage.estimate(100_000), 50_000);
+
+        let empty_block = GasUsage { gas_used: 0, gas_limit: 0 };
+        assert_eq!(empty_block.estimate(100_000), 100_000);
+    }
+
+    #[tokio::test]
+    async fn pending_tip_caps() {
+        let pool = testing_pool();
+        for tip in [3, 2, 1] {
+            let tx = MockTransaction::eip1559()
+                .with_gas_limit(10_000)
+                .with_max_fee(100)
+                .with_priority_fee(tip);
+            pool.add_transaction(TransactionOrigin::External, tx).await.unwrap();
+        }
+
+        // the transaction with the lowest tip doesn't fit after the others
+        let full_usage = GasUsage { gas_used: 1, gas_limit: 1 };
+        assert_eq!(pending_tip_cap(&pool, 25_000, full_usage), Some(U256::from(1)));
+        assert_eq!(pending_tip_cap(&pool, 30_000, full_usage), None);
+
+        // the transactions fit if they only use half of their gas limits
+        let half_usage = GasUsage { gas_used: 1, gas_limit: 2 };
+        assert_eq!(pending_tip_cap(&pool, 25_000, half_usage), None);
+    }
 }
@@ -8,9 +8,11 @@ mod filter;
 pub mod gas_oracle;
 mod id_provider;
 mod logs_utils;
-mod pubsub;
+pub(crate) mod pubsub;
+pub mod revert_hints;
 pub mod revm_utils;
 mod signer;
+pub mod sim_bundle;
 pub mod traits;
 pub(crate) mod utils;
 
@@ -26,3 +28,4 @@ pub use bundle::EthBundle;
 pub use filter::{EthFilter, EthFilterConfig};
 pub use id_provider::EthSubscriptionIdProvider;
 pub use pubsub::EthPubSub;
+pub use sim_bundle::EthSimBundle;
@@ -0,0 +1,251 @@
+//! Hints about the failure of reverted calls.
+//!
+//! If enabled, reverted `eth_call` and `eth_estimateGas` calls are executed again with the
+//! [`RevertHintsInspector`], and the error data of the response is a [`RevertHints`] object
+//! instead of the plain revert output.
+
+use alloy_sol_types::decode_revert_reason;
+use reth_primitives::{alloy_primitives::Selector, Address, Bytes, U64};
+use reth_revm::{
+    interpreter::{CallInputs, CallOutcome, CreateInputs, CreateOutcome, InterpreterResult},
+    EvmContext, Inspector,
+};
+use revm::Database;
+use serde::Serialize;
+
+/// Hints about the failure of a reverted call.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertHints {
+    /// The revert output of the call.
+    pub output: Bytes,
+    /// The decoded revert reason, if the output is a revert string, a panic or a custom error
+    /// that can be decoded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The deepest call frame the revert originates from.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frame: Option<RevertFrame>,
+    /// The gas the call used until it failed.
+    pub gas_used: U64,
+}
+
+impl RevertHints {
+    /// Creates the hints for the revert output and the failing frame.
+    pub fn new(output: Bytes, frame: Option<RevertFrame>, gas_used: u64) -> Self {
+        let reason = decode_revert_reason(&output);
+        Self { output, reason, frame, gas_used: U64::from(gas_used) }
+    }
+}
+
+/// A call frame that failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RevertFrame {
+    /// The address of the account the frame was executed for.
+    ///
+    /// This is `None` for a create frame that failed before the account was created.
+    pub address: Option<Address>,
+    /// The address of the executed code, if it differs from the address of the account, for
+    /// example for a `DELEGATECALL`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_address: Option<Address>,
+    /// The first four bytes of the input of the frame.
+    ///
+    /// This is `None` for create frames and for calls with a shorter input.
+    pub selector: Option<Selector>,
+    /// The depth of the frame, `0` for the frame of the transaction.
+    pub depth: u64,
+    /// The gas limit of the frame.
+    pub gas: U64,
+    /// The gas the frame used until it failed.
+    pub gas_used: U64,
+}
+
+/// An [`Inspector`] that finds the deepest call frame a revert originates from.
+///
+/// A failed frame is attributed to its last child frame if that failed with the same output, so
+/// that reverts that are bubbled up by the callers are attributed to the frame that reverted
+/// first.
+#[derive(Debug, Default)]
+pub(crate) struct RevertHintsInspector {
+    /// The frames that are currently executed.
+    stack: Vec<PendingFrame>,
+    /// The frame the failure of the transaction originates from.
+    failed: Option<RevertFrame>,
+}
+
+/// A frame that is currently executed.
+#[derive(Debug)]
+struct PendingFrame {
+    address: Option<Address>,
+    code_address: Option<Address>,
+    selector: Option<Selector>,
+    gas: u64,
+    /// The failure of the last child frame and its output.
+    failed_child: Option<(RevertFrame, Bytes)>,
+}
+
+impl RevertHintsInspector {
+    /// Consumes the inspector and returns the hints for the revert output of the transaction.
+    pub(crate) fn into_hints(self, output: Bytes, gas_used: u64) -> RevertHints {
+        RevertHints::new(output, self.failed, gas_used)
+    }
+
+    fn enter(&mut self, frame: PendingFrame) {
+        // only the failure of the last child frame can cause the failure of a frame
+        if let Some(parent) = self.stack.last_mut() {
+            parent.failed_child = None;
+        }
+        self.stack.push(frame);
+    }
+
+    fn exit(&mut self, result: &InterpreterResult, created: Option<Address>) {
+        let Some(frame) = self.stack.pop() else { return };
+        if result.is_ok() {
+            return
+        }
+
+        let failed = match frame.failed_child {
+            // the frame bubbled up the failure of its last child frame
+            Some((child, output)) if output == result.output => child,
+            _ => RevertFrame {
+                address: frame.address.or(created),
+                code_address: frame.code_address,
+                selector: frame.selector,
+                depth: self.stack.len() as u64,
+                gas: U64::from(frame.gas),
+                gas_used: U64::from(result.gas.spent()),
+            },
+        };
+
+        match self.stack.last_mut() {
+            Some(parent) => parent.failed_child = Some((failed, result.output.clone())),
+            None => self.failed = Some(failed),
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for RevertHintsInspector {
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        self.enter(PendingFrame {
+            address: Some(inputs.target_address),
+            code_address: (inputs.bytecode_address != inputs.target_address)
+                .then_some(inputs.bytecode_address),
+            selector: inputs.input.get(..4).map(Selector::from_slice),
+            gas: inputs.gas_limit,
+            failed_child: None,
+        });
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        self.exit(&outcome.result, None);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        self.enter(PendingFrame {
+            address: None,
+            code_address: None,
+            selector: None,
+            gas: inputs.gas_limit,
+            failed_child: None,
+        });
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        self.exit(&outcome.result, outcome.address);
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::address;
+    use revm::interpreter::{Gas, InstructionResult};
+
+    fn call(inspector: &mut RevertHintsInspector, to: Address, input: &[u8]) {
+        inspector.enter(PendingFrame {
+            address: Some(to),
+            code_address: None,
+            selector: input.get(..4).map(Selector::from_slice),
+            gas: 100_000,
+            failed_child: None,
+        });
+    }
+
+    fn end(inspector: &mut RevertHintsInspector, result: InstructionResult, output: &[u8]) {
+        let mut gas = Gas::new(100_000);
+        assert!(gas.record_cost(1_000));
+        inspector.exit(&InterpreterResult::new(result, Bytes::copy_from_slice(output), gas), None);
+    }
+
+    #[test]
+    fn attributes_bubbled_up_revert_to_deepest_frame() {
+        let outer = address!("0000000000000000000000000000000000000001");
+        let inner = address!("0000000000000000000000000000000000000002");
+        let mut inspector = RevertHintsInspector::default();
+
+        call(&mut inspector, outer, &[1, 2, 3, 4, 5]);
+        call(&mut inspector, inner, &[6, 7, 8, 9]);
+        end(&mut inspector, InstructionResult::Revert, b"oops");
+        end(&mut inspector, InstructionResult::Revert, b"oops");
+
+        let hints = inspector.into_hints(Bytes::from_static(b"oops"), 30_000);
+        let frame = hints.frame.unwrap();
+        assert_eq!(frame.address, Some(inner));
+        assert_eq!(frame.selector, Some(Selector::new([6, 7, 8, 9])));
+        assert_eq!(frame.depth, 1);
+        assert_eq!(frame.gas_used, U64::from(1_000));
+        assert_eq!(hints.gas_used, U64::from(30_000));
+    }
+
+    #[test]
+    fn ignores_caught_revert() {
+        let outer = address!("0000000000000000000000000000000000000001");
+        let inner = address!("0000000000000000000000000000000000000002");
+        let mut inspector = RevertHintsInspector::default();
+
+        call(&mut inspector, outer, &[]);
+        call(&mut inspector, inner, &[6, 7, 8, 9]);
+        end(&mut inspector, InstructionResult::Revert, &[]);
+        call(&mut inspector, inner, &[6, 7, 8, 9]);
+        end(&mut inspector, InstructionResult::Return, &[]);
+        end(&mut inspector, InstructionResult::Revert, &[]);
+
+        let frame = inspector.into_hints(Bytes::new(), 30_000).frame.unwrap();
+        assert_eq!(frame.address, Some(outer));
+        assert_eq!(frame.selector, None);
+        assert_eq!(frame.depth, 0);
+    }
+
+    #[test]
+    fn serializes_hints() {
+        let hints = RevertHints::new(Bytes::new(), None, 21_000);
+        assert_eq!(
+            serde_json::to_value(hints).unwrap(),
+            serde_json::json!({ "output": "0x", "gasUsed": "0x5208" })
+        );
+    }
+}
@@ -1,13 +1,17 @@
 //! utilities for working with revm
 
 use crate::eth::error::{EthApiError, EthResult, RpcInvalidTransactionError};
+use reth_chainspec::ChainSpec;
 #[cfg(feature = "optimism")]
 use reth_primitives::revm::env::fill_op_tx_env;
 #[cfg(not(feature = "optimism"))]
 use reth_primitives::revm::env::fill_tx_env;
 use reth_primitives::{
-    revm::env::fill_tx_env_with_recovered, Address, TransactionSigned,
-    TransactionSignedEcRecovered, TxHash, TxKind, B256, U256,
+    revm::{
+        config::{revm_spec, revm_spec_by_timestamp_after_merge},
+        env::fill_tx_env_with_recovered,
+    },
+    Address, Head, TransactionSigned, TransactionSignedEcRecovered, TxHash, TxKind, B256, U256,
 };
 use reth_rpc_types::{
     state::{AccountOverride, EvmOverrides, StateOverride},
@@ -437,6 +441,30 @@ fn apply_block_overrides(overrides: BlockOverrides, env: &mut BlockEnv) {
     }
 }
 
+/// Sets the spec of the env to the hardfork that is active at the block of the env.
+///
+/// This is used after the block number or timestamp was overridden, so that the call is executed
+/// with the rules of the overridden block, like geth does.
+pub fn apply_block_spec(chain_spec: &ChainSpec, env: &mut EnvWithHandlerCfg) {
+    let number = env.block.number.saturating_to();
+    let timestamp = env.block.timestamp.saturating_to();
+    env.handler_cfg.spec_id = if chain_spec.get_final_paris_total_difficulty().is_some() {
+        // the total difficulty is only needed to tell whether the block is past the merge
+        let total_difficulty = chain_spec.final_paris_total_difficulty(number).unwrap_or_default();
+        revm_spec(chain_spec, Head { number, timestamp, total_difficulty, ..Default::default() })
+    } else if env.handler_cfg.spec_id >= SpecId::MERGE {
+        // if the merge block isn't known, the block stays on the same side of the merge
+        revm_spec_by_timestamp_after_merge(chain_spec, timestamp)
+    } else {
+        revm_spec(chain_spec, Head { number, timestamp, ..Default::default() })
+    };
+
+    // the blob gas price must be set once cancun is active
+    if env.handler_cfg.spec_id >= SpecId::CANCUN && env.block.blob_excess_gas_and_price.is_none() {
+        env.block.set_blob_excess_gas_and_price(0);
+    }
+}
+
 /// Applies the given state overrides (a set of [`AccountOverride`]) to the [`CacheDB`].
 pub fn apply_state_overrides<DB>(overrides: StateOverride, db: &mut CacheDB<DB>) -> EthResult<()>
 where
@@ -511,7 +539,45 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_chainspec::MAINNET;
     use reth_primitives::constants::GWEI_TO_WEI;
+    use revm::primitives::CfgEnv;
+
+    #[test]
+    fn test_apply_block_spec() {
+        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::SHANGHAI);
+        let mut env =
+            EnvWithHandlerCfg::new_with_cfg_env(cfg, BlockEnv::default(), TxEnv::default());
+
+        // mainnet cancun timestamp
+        env.block.timestamp = U256::from(1710338135);
+        apply_block_spec(&MAINNET, &mut env);
+        assert_eq!(env.handler_cfg.spec_id, SpecId::CANCUN);
+        assert!(env.block.blob_excess_gas_and_price.is_some());
+
+        env.block.timestamp = U256::from(1710338134);
+        apply_block_spec(&MAINNET, &mut env);
+        assert_eq!(env.handler_cfg.spec_id, SpecId::SHANGHAI);
+    }
+
+    #[test]
+    fn test_apply_block_spec_across_merge() {
+        let cfg = CfgEnvWithHandlerCfg::new_with_spec_id(CfgEnv::default(), SpecId::LONDON);
+        let mut env =
+            EnvWithHandlerCfg::new_with_cfg_env(cfg, BlockEnv::default(), TxEnv::default());
+
+        // mainnet shanghai block
+        env.block.number = U256::from(17034870);
+        env.block.timestamp = U256::from(1681338455);
+        apply_block_spec(&MAINNET, &mut env);
+        assert_eq!(env.handler_cfg.spec_id, SpecId::SHANGHAI);
+
+        // mainnet london block
+        env.block.number = U256::from(12965000);
+        env.block.timestamp = U256::from(1628166822);
+        apply_block_spec(&MAINNET, &mut env);
+        assert_eq!(env.handler_cfg.spec_id, SpecId::LONDON);
+    }
 
     #[test]
     fn test_ensure_0_fallback() {
@@ -0,0 +1,419 @@
+//! `mev_simBundle` implementation and helpers.
+
+use crate::eth::{
+    error::{EthApiError, EthResult},
+    revm_utils::{apply_block_spec, FillableTransaction},
+    utils::recover_raw_transaction,
+    EthTransactions,
+};
+use jsonrpsee::core::RpcResult;
+use reth_chainspec::ChainSpec;
+use reth_primitives::{
+    revm_primitives::db::{DatabaseCommit, DatabaseRef},
+    TransactionSignedEcRecovered, U256,
+};
+use reth_revm::database::StateProviderDatabase;
+use reth_rpc_api::MevApiServer;
+use reth_rpc_types::{
+    BlockId, BundleItem, Log, SendBundleRequest, SendBundleResponse, SimBundleLogs,
+    SimBundleOverrides, SimBundleResponse,
+};
+use reth_tasks::pool::BlockingTaskGuard;
+use revm::{db::CacheDB, primitives::TxEnv};
+use revm_primitives::EnvWithHandlerCfg;
+use std::{
+    collections::HashSet,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The default timeout of a bundle simulation.
+pub const DEFAULT_SIM_BUNDLE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum timeout a bundle simulation can request.
+pub const MAX_SIM_BUNDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// `mev` bundle simulation implementation.
+///
+/// The transactions of a bundle are executed in order on top of the state of a parent block, with
+/// the EVM of the node, like the payload builder executes the transactions of a block.
+pub struct EthSimBundle<Eth> {
+    /// All nested fields bundled together.
+    inner: Arc<EthSimBundleInner<Eth>>,
+}
+
+impl<Eth> EthSimBundle<Eth> {
+    /// Create a new `EthSimBundle` instance.
+    pub fn new(
+        eth_api: Eth,
+        chain_spec: Arc<ChainSpec>,
+        blocking_task_guard: BlockingTaskGuard,
+    ) -> Self {
+        Self { inner: Arc::new(EthSimBundleInner { eth_api, chain_spec, blocking_task_guard }) }
+    }
+}
+
+impl<Eth> EthSimBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Simulates a bundle of transactions on top of the parent block of the overrides, or the
+    /// latest block.
+    ///
+    /// The block the bundle is simulated in follows the parent block unless overridden, and its
+    /// transactions are executed with the rules of the hardfork that is active at that block. Only
+    /// bundles that consist of signed transactions can be simulated, transaction hashes of the
+    /// transactions a bundle backruns are rejected.
+    ///
+    /// The simulation stops before the next transaction once the timeout elapsed.
+    pub async fn sim_bundle(
+        &self,
+        request: SendBundleRequest,
+        overrides: SimBundleOverrides,
+    ) -> EthResult<SimBundleResponse> {
+        let SendBundleRequest { inclusion, bundle_body, validity, .. } = request;
+        if bundle_body.is_empty() {
+            return Err(EthApiError::InvalidParams(
+                EthSimBundleError::EmptyBundleTransactions.to_string(),
+            ))
+        }
+
+        let mut transactions = Vec::with_capacity(bundle_body.len());
+        for item in bundle_body {
+            match item {
+                BundleItem::Tx { tx, can_revert } => {
+                    let tx = recover_raw_transaction(tx)?.into_ecrecovered_transaction();
+                    transactions.push((tx, can_revert));
+                }
+                BundleItem::Hash { .. } => {
+                    return Err(EthApiError::InvalidParams(
+                        EthSimBundleError::UnmatchedBundle.to_string(),
+                    ))
+                }
+            }
+        }
+
+        // the transactions that receive refunds don't contribute to the refundable value
+        let refund_indices = validity
+            .and_then(|validity| validity.refund)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|refund| refund.body_idx as usize)
+            .collect::<HashSet<_>>();
+
+        let timeout = overrides
+            .timeout
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_SIM_BUNDLE_TIMEOUT)
+            .min(MAX_SIM_BUNDLE_TIMEOUT);
+        let deadline = Instant::now() + timeout;
+
+        let SimBundleOverrides { parent_block, block_number, coinbase, timestamp, .. } = overrides;
+        let parent_block = parent_block.unwrap_or(BlockId::latest());
+        let (cfg, mut block_env, at) = self.inner.eth_api.evm_env_at(parent_block).await?;
+
+        let state_block = block_env.number.saturating_to::<u64>();
+        let number = block_number.unwrap_or(state_block + 1);
+        block_env.number = U256::from(number);
+        block_env.timestamp = match timestamp {
+            Some(timestamp) => U256::from(timestamp),
+            None => block_env.timestamp + U256::from(12),
+        };
+        if let Some(coinbase) = coinbase {
+            block_env.coinbase = coinbase;
+        }
+        if let Some(gas_limit) = overrides.gas_limit {
+            block_env.gas_limit = U256::from(gas_limit);
+        }
+        if let Some(base_fee) = overrides.base_fee {
+            block_env.basefee = U256::from(base_fee);
+        }
+
+        let failed = move |error: String| SimBundleResponse {
+            success: false,
+            error: Some(error),
+            state_block,
+            mev_gas_price: 0,
+            profit: 0,
+            refundable_value: 0,
+            gas_used: 0,
+            logs: None,
+        };
+
+        if number < inclusion.block_number() ||
+            inclusion.max_block_number().is_some_and(|max| number > max)
+        {
+            return Ok(failed(EthSimBundleError::BlockNotIncluded(number).to_string()))
+        }
+
+        // the cfg of the parent block may be of an earlier hardfork than the simulated block
+        let mut env = EnvWithHandlerCfg::new_with_cfg_env(cfg, block_env, TxEnv::default());
+        apply_block_spec(&self.inner.chain_spec, &mut env);
+        let coinbase = env.block.coinbase;
+        let block_gas_limit = env.block.gas_limit.saturating_to::<u64>();
+
+        let _permit = self.inner.blocking_task_guard.clone().acquire_owned().await;
+        let this = self.clone();
+        let simulation = self.inner.eth_api.spawn_with_state_at_block(at, move |state| {
+            let mut db = CacheDB::new(StateProviderDatabase::new(state));
+
+            let initial_coinbase =
+                DatabaseRef::basic_ref(&db, coinbase)?.map(|acc| acc.balance).unwrap_or_default();
+            let mut coinbase_balance_before_tx = initial_coinbase;
+            let mut refundable_value = U256::ZERO;
+            let mut gas_used = 0u64;
+            let mut logs = Vec::with_capacity(transactions.len());
+
+            for (idx, (tx, can_revert)) in transactions.into_iter().enumerate() {
+                if Instant::now() >= deadline {
+                    return Err(EthApiError::ExecutionTimedOut(timeout))
+                }
+
+                tx.try_fill_tx_env(&mut env.tx)?;
+                let res = match this.inner.eth_api.transact_call(&mut db, env.clone()) {
+                    Ok((res, _)) => res,
+                    Err(err) => return Ok(failed(format!("transaction {idx} failed: {err}"))),
+                };
+                let result = res.result;
+
+                if !result.is_success() && !can_revert {
+                    return Ok(failed(EthSimBundleError::TransactionReverted(idx).to_string()))
+                }
+                gas_used += result.gas_used();
+                if gas_used > block_gas_limit {
+                    return Ok(failed(EthSimBundleError::BlockGasLimitExceeded.to_string()))
+                }
+
+                // coinbase is always present in the result state
+                let coinbase_balance_after_tx =
+                    res.state.get(&coinbase).map(|acc| acc.info.balance).unwrap_or_default();
+                if !refund_indices.contains(&idx) {
+                    refundable_value +=
+                        coinbase_balance_after_tx.saturating_sub(coinbase_balance_before_tx);
+                }
+                coinbase_balance_before_tx = coinbase_balance_after_tx;
+
+                logs.push(SimBundleLogs {
+                    tx_logs: Some(tx_logs(&tx, result.into_logs())),
+                    bundle_logs: None,
+                });
+
+                // need to apply the state changes of this transaction before executing the
+                // next one
+                db.commit(res.state);
+            }
+
+            let profit = coinbase_balance_before_tx.saturating_sub(initial_coinbase);
+            let mev_gas_price = profit.checked_div(U256::from(gas_used)).unwrap_or_default();
+
+            Ok(SimBundleResponse {
+                success: true,
+                error: None,
+                state_block,
+                mev_gas_price: mev_gas_price.saturating_to(),
+                profit: profit.saturating_to(),
+                refundable_value: refundable_value.saturating_to(),
+                gas_used,
+                logs: Some(logs),
+            })
+        });
+
+        simulation.await
+    }
+}
+
+/// Returns the logs of a simulated transaction, which isn't part of a block yet.
+fn tx_logs(tx: &TransactionSignedEcRecovered, logs: Vec<reth_primitives::Log>) -> Vec<Log> {
+    logs.into_iter()
+        .map(|inner| Log {
+            inner,
+            block_hash: None,
+            block_number: None,
+            block_timestamp: None,
+            transaction_hash: Some(tx.hash()),
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        })
+        .collect()
+}
+
+#[async_trait::async_trait]
+impl<Eth> MevApiServer for EthSimBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_bundle(&self, _request: SendBundleRequest) -> RpcResult<SendBundleResponse> {
+        Err(EthApiError::Unsupported(
+            "mev_sendBundle is not supported, bundles can only be simulated",
+        )
+        .into())
+    }
+
+    async fn sim_bundle(
+        &self,
+        request: SendBundleRequest,
+        sim_overrides: SimBundleOverrides,
+    ) -> RpcResult<SimBundleResponse> {
+        Ok(Self::sim_bundle(self, request, sim_overrides).await?)
+    }
+}
+
+/// Container type for `EthSimBundle` internals
+#[derive(Debug)]
+struct EthSimBundleInner<Eth> {
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// The chain spec, to determine the hardfork of the simulated block
+    chain_spec: Arc<ChainSpec>,
+    /// Restricts the number of concurrent simulations
+    blocking_task_guard: BlockingTaskGuard,
+}
+
+impl<Eth> std::fmt::Debug for EthSimBundle<Eth> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EthSimBundle").finish_non_exhaustive()
+    }
+}
+
+impl<Eth> Clone for EthSimBundle<Eth> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+/// [`EthSimBundle`] specific errors.
+#[derive(Debug, thiserror::Error)]
+pub enum EthSimBundleError {
+    /// Thrown if the bundle does not contain any transactions.
+    #[error("bundle missing body")]
+    EmptyBundleTransactions,
+    /// Thrown if the bundle contains transaction hashes instead of signed transactions.
+    #[error("only fully matched bundles can be simulated")]
+    UnmatchedBundle,
+    /// Thrown if the simulated block is outside the inclusion range of the bundle.
+    #[error("bundle can't be included in block {0}")]
+    BlockNotIncluded(u64),
+    /// Thrown if a transaction that may not revert reverted.
+    #[error("transaction {0} reverted")]
+    TransactionReverted(usize),
+    /// Thrown if the bundle uses more gas than the block gas limit.
+    #[error("bundle exceeds the block gas limit")]
+    BlockGasLimitExceeded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eth::{
+        cache::EthStateCache, gas_oracle::GasPriceOracle, EthApi, FeeHistoryCache,
+        FeeHistoryCacheConfig,
+    };
+    use reth_chainspec::{ChainSpecBuilder, ForkCondition, Hardfork};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_network_api::noop::NoopNetwork;
+    use reth_primitives::{
+        constants::ETHEREUM_BLOCK_GAS_LIMIT, hex_literal::hex, Address, Block, Bytes, B256,
+    };
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_rpc_types::Inclusion;
+    use reth_tasks::pool::BlockingTaskPool;
+    use reth_transaction_pool::test_utils::{testing_pool, TestPool, TransactionBuilder};
+
+    /// A contract that returns with `PUSH0`, which is only valid since Shanghai.
+    const PUSH0_CONTRACT: Address = Address::repeat_byte(0x55);
+
+    /// The timestamp Shanghai is activated at.
+    const SHANGHAI_TIMESTAMP: u64 = 100;
+
+    fn sim_bundle_api(
+        provider: MockEthProvider,
+    ) -> EthSimBundle<EthApi<MockEthProvider, TestPool, NoopNetwork, EthEvmConfig>> {
+        let evm_config = EthEvmConfig::default();
+        let cache = EthStateCache::spawn(provider.clone(), Default::default(), evm_config.clone());
+        let eth_api = EthApi::new(
+            provider.clone(),
+            testing_pool(),
+            NoopNetwork::default(),
+            cache.clone(),
+            GasPriceOracle::new(provider, Default::default(), cache.clone()),
+            ETHEREUM_BLOCK_GAS_LIMIT,
+            BlockingTaskPool::build().expect("failed to build tracing pool"),
+            FeeHistoryCache::new(cache, FeeHistoryCacheConfig::default()),
+            evm_config,
+            None,
+        );
+        let chain_spec = ChainSpecBuilder::mainnet()
+            .paris_activated()
+            .with_fork(Hardfork::Shanghai, ForkCondition::Timestamp(SHANGHAI_TIMESTAMP))
+            .build();
+        EthSimBundle::new(eth_api, Arc::new(chain_spec), BlockingTaskGuard::new(1))
+    }
+
+    /// Returns a provider with a parent block, and a bundle that calls the [`PUSH0_CONTRACT`].
+    fn push0_bundle() -> (MockEthProvider, SendBundleRequest) {
+        let provider = MockEthProvider::default();
+        provider.add_block(B256::random(), Block::default());
+        provider.add_account(
+            PUSH0_CONTRACT,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from(hex!("5f5ff3"))),
+        );
+
+        let tx = TransactionBuilder::default()
+            .signer(B256::random())
+            .gas_limit(100_000)
+            .to(PUSH0_CONTRACT)
+            .into_eip1559();
+        let sender = tx.recover_signer().unwrap();
+        provider.add_account(sender, ExtendedAccount::new(0, U256::from(1_000_000_000u64)));
+
+        let request = SendBundleRequest {
+            inclusion: Inclusion::at_block(1),
+            bundle_body: vec![BundleItem::Tx { tx: tx.envelope_encoded(), can_revert: false }],
+            ..Default::default()
+        };
+        (provider, request)
+    }
+
+    #[tokio::test]
+    async fn sim_bundle_with_spec_of_simulated_block() {
+        let (provider, request) = push0_bundle();
+        let api = sim_bundle_api(provider);
+
+        let before_shanghai =
+            SimBundleOverrides { timestamp: Some(SHANGHAI_TIMESTAMP - 1), ..Default::default() };
+        let response = api.sim_bundle(request.clone(), before_shanghai).await.unwrap();
+        assert!(!response.success);
+        assert_eq!(response.error, Some(EthSimBundleError::TransactionReverted(0).to_string()));
+
+        let after_shanghai =
+            SimBundleOverrides { timestamp: Some(SHANGHAI_TIMESTAMP), ..Default::default() };
+        let response = api.sim_bundle(request, after_shanghai).await.unwrap();
+        assert!(response.success, "{:?}", response.error);
+        assert_eq!(response.state_block, 0);
+        assert_eq!(response.logs.map(|logs| logs.len()), Some(1));
+    }
+
+    #[tokio::test]
+    async fn sim_bundle_not_included() {
+        let (provider, request) = push0_bundle();
+        let api = sim_bundle_api(provider);
+
+        let overrides = SimBundleOverrides { block_number: Some(2), ..Default::default() };
+        let request =
+            SendBundleRequest { inclusion: Inclusion { block: 1, max_block: Some(1) }, ..request };
+        let response = api.sim_bundle(request, overrides).await.unwrap();
+        assert_eq!(response.error, Some(EthSimBundleError::BlockNotIncluded(2).to_string()));
+    }
+
+    #[tokio::test]
+    async fn sim_bundle_timeout() {
+        let (provider, request) = push0_bundle();
+        let api = sim_bundle_api(provider);
+
+        // the simulation stops before executing the first transaction
+        let overrides = SimBundleOverrides { timeout: Some(0), ..Default::default() };
+        let err = api.sim_bundle(request, overrides).await.unwrap_err();
+        assert!(matches!(err, EthApiError::ExecutionTimedOut(timeout) if timeout.is_zero()));
+    }
+}
@@ -38,10 +38,12 @@ mod engine;
 pub mod eth;
 mod net;
 mod otterscan;
+mod reorg_guard;
 mod reth;
 mod rpc;
 mod trace;
 mod txpool;
+pub mod usage;
 mod web3;
 pub use admin::AdminApi;
 pub use debug::DebugApi;
@@ -49,9 +51,11 @@ pub use engine::{EngineApi, EngineEthApi};
 pub use eth::{EthApi, EthApiSpec, EthFilter, EthPubSub, EthSubscriptionIdProvider};
 pub use net::NetApi;
 pub use otterscan::OtterscanApi;
+pub use reorg_guard::ReorgGuardApi;
 pub use reth::RethApi;
 pub use rpc::RPCApi;
 pub use trace::TraceApi;
 pub use txpool::TxPoolApi;
+pub use usage::{RpcUsage, RpcUsageApi};
 pub use web3::Web3Api;
 pub mod result;
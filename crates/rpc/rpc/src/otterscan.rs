@@ -1,23 +1,39 @@
 use alloy_primitives::Bytes;
 use async_trait::async_trait;
+use futures::Future;
 use jsonrpsee::core::RpcResult;
-use revm_inspectors::transfer::{TransferInspector, TransferKind};
+use revm_inspectors::{
+    tracing::{types::CallTraceNode, TracingInspectorConfig},
+    transfer::{TransferInspector, TransferKind},
+};
 use revm_primitives::ExecutionResult;
 
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, TxHash, B256, U256};
 use reth_rpc_api::{EthApiServer, OtterscanServer};
 use reth_rpc_types::{
-    trace::otterscan::{
-        BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
-        OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
+    trace::{
+        otterscan::{
+            BlockDetails, ContractCreator, InternalOperation, OperationType, OtsBlockTransactions,
+            OtsReceipt, OtsTransactionReceipt, TraceEntry, TransactionsWithReceipts,
+        },
+        parity::{Action, CreateAction, CreateOutput, TraceOutput},
     },
-    BlockTransactions, Transaction,
+    AnyTransactionReceipt, BlockTransactions, RichBlock, Transaction,
 };
 
 use crate::{eth::EthTransactions, result::internal_rpc_err};
 
 const API_LEVEL: u64 = 8;
 
+/// The maximum number of blocks a transaction search scans in a single call.
+const MAX_SEARCH_BLOCKS: usize = 1_000;
+
+/// The maximum page size of a transaction search.
+const MAX_SEARCH_PAGE_SIZE: usize = 100;
+
+/// A transaction of an address, together with its receipt.
+type AddressTransaction = (Transaction, OtsTransactionReceipt);
+
 /// Otterscan API.
 #[derive(Debug)]
 pub struct OtterscanApi<Eth> {
@@ -31,6 +47,70 @@ impl<Eth> OtterscanApi<Eth> {
     }
 }
 
+impl<Eth> OtterscanApi<Eth>
+where
+    Eth: EthApiServer + EthTransactions,
+{
+    /// Returns the details of the block, including the fees paid by its transactions.
+    async fn block_details(&self, block_id: BlockId) -> RpcResult<Option<BlockDetails>> {
+        let block = async {
+            match block_id {
+                BlockId::Hash(hash) => self.eth.block_by_hash(hash.block_hash, true).await,
+                BlockId::Number(number) => self.eth.block_by_number(number, true).await,
+            }
+        };
+        let receipts = self.eth.block_receipts(block_id);
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let Some(block) = block else { return Ok(None) };
+        let receipts = receipts.ok_or_else(|| internal_rpc_err("receipts not found"))?;
+        Ok(Some(into_block_details(block, &receipts)))
+    }
+
+    /// Returns the latest block number.
+    fn latest_block_number(&self) -> RpcResult<u64> {
+        Ok(EthApiServer::block_number(&self.eth)?.saturating_to())
+    }
+
+    /// Returns true if the block activated Cancun, after which contracts can only be destroyed in
+    /// the transaction that created them ([EIP-6780](https://eips.ethereum.org/EIPS/eip-6780)).
+    async fn is_cancun(&self, block_number: u64) -> RpcResult<bool> {
+        let header = self.eth.header_by_number(block_number.into()).await?;
+        Ok(header.is_some_and(|header| header.excess_blob_gas.is_some()))
+    }
+
+    /// Returns the transactions of the block that were sent by the address, were sent to it or
+    /// created it, together with their receipts.
+    async fn address_transactions_in_block(
+        &self,
+        address: Address,
+        block_number: u64,
+    ) -> RpcResult<Vec<AddressTransaction>> {
+        let block_number = BlockNumberOrTag::Number(block_number);
+        let block = self.eth.block_by_number(block_number, true);
+        let receipts = self.eth.block_receipts(BlockId::Number(block_number));
+        let (block, receipts) = futures::try_join!(block, receipts)?;
+
+        let block = block.ok_or_else(|| internal_rpc_err("block not found"))?;
+        let receipts = receipts.ok_or_else(|| internal_rpc_err("receipts not found"))?;
+        let timestamp = block.header.timestamp;
+        let BlockTransactions::Full(transactions) = block.inner.transactions else {
+            return Err(internal_rpc_err("block is not full"));
+        };
+
+        Ok(transactions
+            .into_iter()
+            .zip(receipts)
+            .filter(|(tx, receipt)| {
+                tx.from == address ||
+                    tx.to == Some(address) ||
+                    receipt.contract_address == Some(address)
+            })
+            .map(|(tx, receipt)| (tx, into_ots_receipt(receipt, timestamp)))
+            .collect())
+    }
+}
+
 #[async_trait]
 impl<Eth> OtterscanServer for OtterscanApi<Eth>
 where
@@ -90,8 +170,29 @@ where
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, _tx_hash: TxHash) -> RpcResult<TraceEntry> {
-        Err(internal_rpc_err("unimplemented"))
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>> {
+        let traces = self
+            .eth
+            .spawn_trace_transaction_in_block(
+                tx_hash,
+                TracingInspectorConfig::default_parity(),
+                |_tx_info, inspector, _, _| Ok(inspector.into_traces().into_nodes()),
+            )
+            .await?
+            .map(|nodes| {
+                nodes
+                    .into_iter()
+                    .map(|CallTraceNode { trace, .. }| TraceEntry {
+                        r#type: trace.kind.to_string(),
+                        depth: trace.depth as u32,
+                        from: trace.caller,
+                        to: trace.address,
+                        value: trace.value,
+                        input: trace.data,
+                    })
+                    .collect()
+            });
+        Ok(traces)
     }
 
     /// Handler for `ots_getBlockDetails`
@@ -99,14 +200,12 @@ where
         &self,
         block_number: BlockNumberOrTag,
     ) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_number(block_number, true).await?;
-        Ok(block.map(Into::into))
+        self.block_details(BlockId::Number(block_number)).await
     }
 
     /// Handler for `getBlockDetailsByHash`
     async fn get_block_details_by_hash(&self, block_hash: B256) -> RpcResult<Option<BlockDetails>> {
-        let block = self.eth.block_by_hash(block_hash, true).await?;
-        Ok(block.map(Into::into))
+        self.block_details(BlockId::Hash(block_hash.into())).await
     }
 
     /// Handler for `getBlockTransactions`
@@ -153,47 +252,79 @@ where
         }
 
         // Crop receipts and transform them into OtsTransactionReceipt
-        let timestamp = Some(block.header.timestamp);
+        let timestamp = block.header.timestamp;
         let receipts = receipts
             .drain(page_start..page_end)
-            .map(|receipt| {
-                let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
-                    status: receipt
-                        .inner
-                        .receipt
-                        .status
-                        .as_eip658()
-                        .expect("ETH API returned pre-EIP-658 status"),
-                    cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
-                    logs: None,
-                    logs_bloom: None,
-                    r#type: receipt.r#type,
-                });
-
-                OtsTransactionReceipt { receipt, timestamp }
-            })
+            .map(|receipt| into_ots_receipt(receipt, timestamp))
             .collect();
         Ok(OtsBlockTransactions { fullblock: block.inner.into(), receipts })
     }
 
     /// Handler for `searchTransactionsBefore`
+    ///
+    /// Walks the blocks before the given block backwards, or from the latest block if the given
+    /// block is 0, and returns the transactions that were sent by the address, were sent to it or
+    /// created it, newest first. Blocks are always returned whole, so a page may contain more
+    /// than `page_size` transactions.
+    ///
+    /// A call scans at most [`MAX_SEARCH_BLOCKS`] blocks, so a page that isn't the last page may
+    /// contain fewer than `page_size` transactions, or none.
     async fn search_transactions_before(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = block_number.as_number().unwrap_or_default();
+        let latest = self.latest_block_number()?;
+        let first_page = block_number == 0 || block_number > latest;
+        let start = if first_page { Some(latest) } else { block_number.checked_sub(1) };
+
+        let blocks = start.into_iter().flat_map(|start| (0..=start).rev());
+        let (txs, exhausted) = search_blocks(blocks, page_size, |number| async move {
+            let mut txs = self.address_transactions_in_block(address, number).await?;
+            txs.reverse();
+            Ok(txs)
+        })
+        .await?;
+
+        let (txs, receipts) = txs.into_iter().unzip();
+        Ok(TransactionsWithReceipts { txs, receipts, first_page, last_page: exhausted })
     }
 
     /// Handler for `searchTransactionsAfter`
+    ///
+    /// Walks the blocks after the given block forwards up to the latest block, and returns the
+    /// transactions that were sent by the address, were sent to it or created it, newest first.
+    /// Blocks are always returned whole, so a page may contain more than `page_size`
+    /// transactions.
+    ///
+    /// A call scans at most [`MAX_SEARCH_BLOCKS`] blocks, so a page that isn't the first page may
+    /// contain fewer than `page_size` transactions, or none.
     async fn search_transactions_after(
         &self,
-        _address: Address,
-        _block_number: BlockNumberOrTag,
-        _page_size: usize,
+        address: Address,
+        block_number: BlockNumberOrTag,
+        page_size: usize,
     ) -> RpcResult<TransactionsWithReceipts> {
-        Err(internal_rpc_err("unimplemented"))
+        let block_number = block_number.as_number().unwrap_or_default();
+        let latest = self.latest_block_number()?;
+
+        let blocks = block_number.saturating_add(1)..=latest;
+        let (mut txs, exhausted) = search_blocks(blocks, page_size, |number| {
+            self.address_transactions_in_block(address, number)
+        })
+        .await?;
+
+        // pages are ordered from the newest to the oldest transaction
+        txs.reverse();
+        let (txs, receipts) = txs.into_iter().unzip();
+        Ok(TransactionsWithReceipts {
+            txs,
+            receipts,
+            first_page: exhausted,
+            last_page: block_number == 0,
+        })
     }
 
     /// Handler for `getTransactionBySenderAndNonce`
@@ -206,7 +337,176 @@ where
     }
 
     /// Handler for `getContractCreator`
-    async fn get_contract_creator(&self, _address: Address) -> RpcResult<Option<ContractCreator>> {
-        Err(internal_rpc_err("unimplemented"))
+    ///
+    /// Finds the block the contract was created in with a binary search over the code of the
+    /// address, and then traces the block to find the creating transaction.
+    ///
+    /// Since Cancun, a contract that has code at the end of a block can't be destroyed anymore, so
+    /// the contract has code in all blocks after its creation. Before Cancun, a contract could be
+    /// destroyed and created again at the same address, so for contracts that exist since before
+    /// Cancun, the search assumes that the address has had code ever since its first creation.
+    async fn get_contract_creator(&self, address: Address) -> RpcResult<Option<ContractCreator>> {
+        if !self.has_code(address, None).await? {
+            return Ok(None)
+        }
+        let latest = self.latest_block_number()?;
+        let has_code = |number: u64| self.has_code(address, Some(number.into()));
+
+        // only search the blocks since Cancun if the contract was created after its activation
+        let mut low = 0;
+        if self.is_cancun(latest).await? {
+            let cancun = first_block(0, latest, |number| self.is_cancun(number)).await?;
+            if cancun == 0 || !has_code(cancun - 1).await? {
+                low = cancun;
+            }
+        }
+        let low = first_block(low, latest, has_code).await?;
+
+        let creator = self
+            .eth
+            .trace_block_with(
+                low.into(),
+                TracingInspectorConfig::default_parity(),
+                move |tx_info, inspector, _, _, _| {
+                    let traces =
+                        inspector.into_parity_builder().into_localized_transaction_traces(tx_info);
+                    Ok(traces.into_iter().find_map(|tx_trace| {
+                        let trace = tx_trace.trace;
+                        match (trace.action, trace.result, trace.error) {
+                            (
+                                Action::Create(CreateAction { from: creator, .. }),
+                                Some(TraceOutput::Create(CreateOutput {
+                                    address: contract, ..
+                                })),
+                                None,
+                            ) if contract == address => tx_trace
+                                .transaction_hash
+                                .map(|hash| ContractCreator { hash, creator }),
+                            _ => None,
+                        }
+                    }))
+                },
+            )
+            .await?
+            .and_then(|creators| creators.into_iter().flatten().last());
+        Ok(creator)
+    }
+}
+
+/// Returns the first block in `low..=high` for which the predicate holds, with a binary search
+/// that expects the predicate to hold for all blocks after the first, and for `high`.
+async fn first_block<F, Fut>(mut low: u64, mut high: u64, predicate: F) -> RpcResult<u64>
+where
+    F: Fn(u64) -> Fut,
+    Fut: Future<Output = RpcResult<bool>>,
+{
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if predicate(mid).await? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Collects the items of the blocks, in the given order, until there are at least `page_size`
+/// items or [`MAX_SEARCH_BLOCKS`] blocks were scanned. The page size is capped at
+/// [`MAX_SEARCH_PAGE_SIZE`].
+///
+/// Returns the items and whether all blocks were scanned.
+async fn search_blocks<T, F, Fut>(
+    blocks: impl IntoIterator<Item = u64>,
+    page_size: usize,
+    mut fetch: F,
+) -> RpcResult<(Vec<T>, bool)>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = RpcResult<Vec<T>>>,
+{
+    let page_size = page_size.min(MAX_SEARCH_PAGE_SIZE);
+    let mut blocks = blocks.into_iter().peekable();
+    let mut items = Vec::new();
+    let mut scanned = 0;
+    while items.len() < page_size && scanned < MAX_SEARCH_BLOCKS {
+        let Some(number) = blocks.next() else { break };
+        items.extend(fetch(number).await?);
+        scanned += 1;
+    }
+    Ok((items, blocks.peek().is_none()))
+}
+
+/// Converts the receipt into an [`OtsTransactionReceipt`], which omits the logs.
+fn into_ots_receipt(receipt: AnyTransactionReceipt, timestamp: u64) -> OtsTransactionReceipt {
+    let receipt = receipt.inner.map_inner(|receipt| OtsReceipt {
+        status: receipt
+            .inner
+            .receipt
+            .status
+            .as_eip658()
+            .expect("ETH API returned pre-EIP-658 status"),
+        cumulative_gas_used: receipt.inner.receipt.cumulative_gas_used as u64,
+        logs: None,
+        logs_bloom: None,
+        r#type: receipt.r#type,
+    });
+
+    OtsTransactionReceipt { receipt, timestamp: Some(timestamp) }
+}
+
+/// Returns the details of the block, with the total fees paid by the transactions of the block
+/// computed from their receipts.
+fn into_block_details(block: RichBlock, receipts: &[AnyTransactionReceipt]) -> BlockDetails {
+    let total_fees = receipts
+        .iter()
+        .map(|receipt| U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price))
+        .sum();
+    BlockDetails { total_fees, ..BlockDetails::from(block) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn search_blocks_bounds() {
+        let fetch = |number: u64| async move { Ok(vec![number; (number % 3) as usize]) };
+
+        // the page is filled with whole blocks
+        let (items, exhausted) = search_blocks((0..=10).rev(), 4, fetch).await.unwrap();
+        assert_eq!(items, vec![10, 8, 8, 7]);
+        assert!(!exhausted);
+
+        let (items, exhausted) = search_blocks(0..=2, 10, fetch).await.unwrap();
+        assert_eq!(items, vec![1, 2, 2]);
+        assert!(exhausted);
+
+        // the page size is capped
+        let (items, _) = search_blocks(0.., usize::MAX, fetch).await.unwrap();
+        assert_eq!(items.len(), MAX_SEARCH_PAGE_SIZE);
+
+        // the number of scanned blocks is capped
+        let empty = |_| async { Ok(Vec::<u64>::new()) };
+        let mut scanned = 0;
+        let (items, exhausted) = search_blocks(0.., 1, |number| {
+            scanned += 1;
+            empty(number)
+        })
+        .await
+        .unwrap();
+        assert!(items.is_empty());
+        assert!(!exhausted);
+        assert_eq!(scanned, MAX_SEARCH_BLOCKS);
+    }
+
+    #[tokio::test]
+    async fn first_block_search() {
+        for first in [0, 1, 17, 100] {
+            let found = first_block(0, 100, |number| async move { Ok(number >= first) }).await;
+            assert_eq!(found.unwrap(), first);
+        }
+        let found = first_block(50, 100, |number| async move { Ok(number >= 10) }).await;
+        assert_eq!(found.unwrap(), 50);
     }
 }
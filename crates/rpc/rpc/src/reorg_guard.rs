@@ -0,0 +1,31 @@
+use jsonrpsee::core::RpcResult;
+use reth_blockchain_tree_api::ReorgGuard;
+use reth_primitives::B256;
+use reth_rpc_api::ReorgGuardApiServer;
+use tracing::info;
+
+/// `admin_confirmReorg` API implementation.
+///
+/// This type provides the functionality for confirming reorgs rejected by the [`ReorgGuard`].
+#[derive(Debug, Clone)]
+pub struct ReorgGuardApi {
+    guard: ReorgGuard,
+}
+
+impl ReorgGuardApi {
+    /// Creates a new instance of `ReorgGuardApi`.
+    pub const fn new(guard: ReorgGuard) -> Self {
+        Self { guard }
+    }
+}
+
+impl ReorgGuardApiServer for ReorgGuardApi {
+    /// Handler for `admin_confirmReorg`
+    fn confirm_reorg(&self, head: B256) -> RpcResult<bool> {
+        let confirmed = self.guard.confirm(head);
+        if confirmed {
+            info!(target: "rpc::admin", ?head, "Deep reorg confirmed");
+        }
+        Ok(confirmed)
+    }
+}
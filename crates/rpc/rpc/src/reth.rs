@@ -1,39 +1,101 @@
-use crate::eth::error::{EthApiError, EthResult};
+use crate::{
+    eth::{
+        error::{EthApiError, EthResult},
+        pubsub::pipe_from_stream,
+        EthTransactions,
+    },
+    result::internal_rpc_err,
+};
 use async_trait::async_trait;
-use jsonrpsee::core::RpcResult;
+use futures::StreamExt;
+use jsonrpsee::{core::RpcResult, PendingSubscriptionSink};
 use reth_errors::RethResult;
-use reth_primitives::{Address, BlockId, U256};
-use reth_provider::{BlockReaderIdExt, ChangeSetReader, StateProviderFactory};
+use reth_primitives::{
+    Address, BlockId, BlockNumberOrTag, Header, Selector, B256, KECCAK_EMPTY, U128, U256,
+};
+use reth_provider::{
+    AccountReader, BlockNumReader, BlockReaderIdExt, CanonStateNotification,
+    CanonStateSubscriptions, ChangeSetReader, HeaderProvider, ProviderError, StateProvider,
+    StateProviderFactory, TransactionSelectorProvider,
+};
+use reth_revm::requests::withdrawal_request_queue;
 use reth_rpc_api::RethApiServer;
+use reth_rpc_types::{
+    AccountDelegation, AccountProofRequest, AccountState, AnyTransactionReceipt, ChainReorg,
+    EIP1186AccountProofResponse, QueuedWithdrawalRequest, WithdrawalRequestQueue,
+};
+use reth_rpc_types_compat::{block::from_primitive_with_hash, proof::from_primitive_account_proof};
 use reth_tasks::TaskSpawner;
 use std::{collections::HashMap, future::Future, sync::Arc};
 use tokio::sync::oneshot;
 
+/// The maximum number of accounts that can be requested in a single `reth_getAccountStates` call.
+const MAX_ACCOUNT_STATES: usize = 1024;
+
+/// The maximum number of accounts that can be proven in a single `reth_getProofs` call.
+const MAX_PROOF_ACCOUNTS: usize = 1024;
+
+/// The maximum number of storage slots that can be proven in a single `reth_getProofs` call.
+const MAX_PROOF_STORAGE_KEYS: usize = 10_000;
+
+/// The maximum number of transactions that a single `reth_getTransactionsBySelector` call returns.
+const MAX_SELECTOR_TRANSACTIONS: usize = 10_000;
+
+/// The maximum number of queued requests that a single `reth_getWithdrawalRequestQueue` call
+/// returns.
+const MAX_QUEUED_WITHDRAWAL_REQUESTS: usize = 1024;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
-pub struct RethApi<Provider> {
-    inner: Arc<RethApiInner<Provider>>,
+pub struct RethApi<Provider, Eth, Events> {
+    inner: Arc<RethApiInner<Provider, Eth, Events>>,
 }
 
 // === impl RethApi ===
 
-impl<Provider> RethApi<Provider> {
+impl<Provider, Eth, Events> RethApi<Provider, Eth, Events> {
     /// The provider that can interact with the chain.
     pub fn provider(&self) -> &Provider {
         &self.inner.provider
     }
 
+    /// Access the underlying `Eth` API.
+    pub fn eth_api(&self) -> &Eth {
+        &self.inner.eth_api
+    }
+
     /// Create a new instance of the [`RethApi`]
-    pub fn new(provider: Provider, task_spawner: Box<dyn TaskSpawner>) -> Self {
-        let inner = Arc::new(RethApiInner { provider, task_spawner });
+    ///
+    /// `cancel_price_bump` is the percentage by which `reth_cancelTransaction` bumps the fees of
+    /// replacement transactions.
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        chain_events: Events,
+        task_spawner: Box<dyn TaskSpawner>,
+        cancel_price_bump: u128,
+    ) -> Self {
+        let inner = Arc::new(RethApiInner {
+            provider,
+            eth_api,
+            chain_events,
+            task_spawner,
+            cancel_price_bump,
+        });
         Self { inner }
     }
 }
 
-impl<Provider> RethApi<Provider>
+impl<Provider, Eth, Events> RethApi<Provider, Eth, Events>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + TransactionSelectorProvider
+        + 'static,
+    Eth: EthTransactions + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Executes the future on a new blocking task.
     async fn on_blocking_task<C, F, R>(&self, c: C) -> EthResult<R>
@@ -81,12 +143,264 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns the state of the given accounts at the given block, in the order of the addresses.
+    ///
+    /// All accounts are read from the same state provider.
+    pub async fn account_states(
+        &self,
+        addresses: Vec<Address>,
+        block_id: BlockId,
+    ) -> EthResult<Vec<AccountState>> {
+        if addresses.len() > MAX_ACCOUNT_STATES {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many addresses, at most {MAX_ACCOUNT_STATES} are allowed"
+            )))
+        }
+        self.on_blocking_task(|this| async move { this.try_account_states(&addresses, block_id) })
+            .await
+    }
+
+    fn try_account_states(
+        &self,
+        addresses: &[Address],
+        block_id: BlockId,
+    ) -> EthResult<Vec<AccountState>> {
+        let state = self.provider().state_by_block_id(block_id)?;
+        addresses
+            .iter()
+            .map(|address| -> EthResult<_> {
+                let account = state.basic_account(*address)?.unwrap_or_default();
+                Ok(AccountState {
+                    nonce: account.nonce,
+                    balance: account.balance,
+                    code_hash: account.bytecode_hash.unwrap_or(KECCAK_EMPTY),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the EIP-7702 delegations of the given accounts at the given block, in the order of
+    /// the addresses.
+    ///
+    /// All accounts are read from the same state provider.
+    pub async fn delegations(
+        &self,
+        addresses: Vec<Address>,
+        block_id: BlockId,
+    ) -> EthResult<Vec<Option<AccountDelegation>>> {
+        if addresses.len() > MAX_ACCOUNT_STATES {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many addresses, at most {MAX_ACCOUNT_STATES} are allowed"
+            )))
+        }
+        self.on_blocking_task(|this| async move { this.try_delegations(&addresses, block_id) })
+            .await
+    }
+
+    fn try_delegations(
+        &self,
+        addresses: &[Address],
+        block_id: BlockId,
+    ) -> EthResult<Vec<Option<AccountDelegation>>> {
+        let state = self.provider().state_by_block_id(block_id)?;
+        addresses
+            .iter()
+            .map(|address| -> EthResult<_> {
+                let Some(delegate) = state.account_code(*address)?.and_then(|code| code.delegate())
+                else {
+                    return Ok(None)
+                };
+                let delegate_code_hash = state
+                    .basic_account(delegate)?
+                    .map_or(KECCAK_EMPTY, |account| account.get_bytecode_hash());
+                Ok(Some(AccountDelegation { delegate, delegate_code_hash }))
+            })
+            .collect()
+    }
+
+    /// Returns the account and storage proofs of the given accounts at the given block, in the
+    /// order of the requests.
+    ///
+    /// All accounts are proven in a single walk of the state trie.
+    pub async fn proofs(
+        &self,
+        requests: Vec<AccountProofRequest>,
+        block_id: BlockId,
+    ) -> EthResult<Vec<EIP1186AccountProofResponse>> {
+        if requests.len() > MAX_PROOF_ACCOUNTS {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many accounts, at most {MAX_PROOF_ACCOUNTS} are allowed"
+            )))
+        }
+        if requests.iter().map(|request| request.storage_keys.len()).sum::<usize>() >
+            MAX_PROOF_STORAGE_KEYS
+        {
+            return Err(EthApiError::InvalidParams(format!(
+                "too many storage keys, at most {MAX_PROOF_STORAGE_KEYS} are allowed"
+            )))
+        }
+
+        // like `eth_getProof`, proofs can only be generated for the latest block
+        let chain_info = self.provider().chain_info()?;
+        let is_latest_block = match block_id {
+            BlockId::Number(BlockNumberOrTag::Number(num)) => num == chain_info.best_number,
+            BlockId::Hash(hash) => hash == chain_info.best_hash.into(),
+            BlockId::Number(BlockNumberOrTag::Latest) => true,
+            _ => false,
+        };
+        if !is_latest_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        self.on_blocking_task(|this| async move { this.try_proofs(requests, block_id) }).await
+    }
+
+    fn try_proofs(
+        &self,
+        requests: Vec<AccountProofRequest>,
+        block_id: BlockId,
+    ) -> EthResult<Vec<EIP1186AccountProofResponse>> {
+        let state = self.provider().state_by_block_id(block_id)?;
+        let targets = requests
+            .into_iter()
+            .map(|request| {
+                (request.address, request.storage_keys.into_iter().map(|key| key.0).collect())
+            })
+            .collect::<Vec<_>>();
+        Ok(state.multiproof(&targets)?.into_iter().map(from_primitive_account_proof).collect())
+    }
+
+    /// Returns the withdrawal requests queued in the EIP-7002 withdrawal requests contract after
+    /// the given block.
+    pub async fn withdrawal_request_queue(
+        &self,
+        block_id: BlockId,
+    ) -> EthResult<WithdrawalRequestQueue> {
+        self.on_blocking_task(|this| async move { this.try_withdrawal_request_queue(block_id) })
+            .await
+    }
+
+    fn try_withdrawal_request_queue(&self, block_id: BlockId) -> EthResult<WithdrawalRequestQueue> {
+        let state = self.provider().state_by_block_id(block_id)?;
+        let queue = withdrawal_request_queue(&*state, MAX_QUEUED_WITHDRAWAL_REQUESTS)?;
+        Ok(WithdrawalRequestQueue {
+            excess_requests: queue.excess_requests,
+            request_count: queue.request_count,
+            fee: queue.fee(),
+            queue_length: queue.queue_length,
+            requests: queue
+                .requests
+                .into_iter()
+                .map(|request| QueuedWithdrawalRequest {
+                    source_address: request.source_address,
+                    validator_pubkey: request.validator_pubkey,
+                    amount: request.amount,
+                })
+                .collect(),
+        })
+    }
+
+    /// Returns the receipt of the transaction, extended with the timestamp of its block and a
+    /// breakdown of the fees it paid.
+    pub async fn transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> EthResult<Option<AnyTransactionReceipt>> {
+        let Some(mut receipt) = self.eth_api().transaction_receipt(hash).await? else {
+            return Ok(None)
+        };
+        let Some(block_hash) = receipt.block_hash else { return Ok(Some(receipt)) };
+
+        let header = self
+            .on_blocking_task(|this| async move { Ok(this.provider().header(&block_hash)?) })
+            .await?
+            .ok_or(EthApiError::UnknownBlockNumber)?;
+        extend_receipt(&mut receipt, &header);
+
+        Ok(Some(receipt))
+    }
+
+    /// Returns the hashes of the transactions in the block range that call the function with the
+    /// given selector.
+    pub async fn transactions_by_selector(
+        &self,
+        selector: Selector,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<B256>> {
+        self.on_blocking_task(|this| async move {
+            this.try_transactions_by_selector(selector, from_block, to_block)
+        })
+        .await
+    }
+
+    fn try_transactions_by_selector(
+        &self,
+        selector: Selector,
+        from_block: BlockNumberOrTag,
+        to_block: BlockNumberOrTag,
+    ) -> EthResult<Vec<B256>> {
+        let provider = self.provider();
+        let from_block =
+            provider.convert_block_number(from_block)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        let to_block =
+            provider.convert_block_number(to_block)?.ok_or(EthApiError::UnknownBlockNumber)?;
+        if from_block > to_block {
+            return Err(EthApiError::InvalidBlockRange)
+        }
+
+        let first_tx_num = provider
+            .block_body_indices(from_block)?
+            .ok_or(EthApiError::UnknownBlockNumber)?
+            .first_tx_num();
+        let next_tx_num = provider
+            .block_body_indices(to_block)?
+            .ok_or(EthApiError::UnknownBlockNumber)?
+            .next_tx_num();
+        if next_tx_num <= first_tx_num {
+            return Ok(Vec::new())
+        }
+
+        let tx_numbers = match provider.transactions_by_selector(
+            selector,
+            first_tx_num..=next_tx_num - 1,
+            MAX_SELECTOR_TRANSACTIONS + 1,
+        ) {
+            Ok(tx_numbers) => tx_numbers,
+            Err(ProviderError::UnsupportedProvider) => {
+                return Err(EthApiError::Unsupported("transactions are not indexed by selector"))
+            }
+            Err(err) => return Err(err.into()),
+        };
+        if tx_numbers.len() > MAX_SELECTOR_TRANSACTIONS {
+            return Err(EthApiError::InvalidParams(format!(
+                "more than {MAX_SELECTOR_TRANSACTIONS} transactions match, narrow the block range"
+            )))
+        }
+
+        tx_numbers
+            .into_iter()
+            .map(|tx_num| {
+                let transaction = provider
+                    .transaction_by_id_no_hash(tx_num)?
+                    .ok_or(EthApiError::TransactionNotFound)?;
+                Ok(transaction.hash())
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
-impl<Provider> RethApiServer for RethApi<Provider>
+impl<Provider, Eth, Events> RethApiServer for RethApi<Provider, Eth, Events>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider: BlockReaderIdExt
+        + ChangeSetReader
+        + StateProviderFactory
+        + TransactionSelectorProvider
+        + 'static,
+    Eth: EthTransactions + 'static,
+    Events: CanonStateSubscriptions + 'static,
 {
     /// Handler for `reth_getBalanceChangesInBlock`
     async fn reth_get_balance_changes_in_block(
@@ -95,23 +409,221 @@ where
     ) -> RpcResult<HashMap<Address, U256>> {
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
+
+    /// Handler for `reth_getAccountStates`
+    async fn reth_get_account_states(
+        &self,
+        addresses: Vec<Address>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<AccountState>> {
+        Ok(Self::account_states(self, addresses, block_id.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_getDelegations`
+    async fn reth_get_delegations(
+        &self,
+        addresses: Vec<Address>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<Option<AccountDelegation>>> {
+        Ok(Self::delegations(self, addresses, block_id.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_getProofs`
+    async fn reth_get_proofs(
+        &self,
+        requests: Vec<AccountProofRequest>,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<Vec<EIP1186AccountProofResponse>> {
+        let res = Self::proofs(self, requests, block_id.unwrap_or_default()).await;
+        Ok(res.map_err(|e| match e {
+            EthApiError::InvalidBlockRange => {
+                internal_rpc_err("reth_getProofs is unimplemented for historical blocks")
+            }
+            _ => e.into(),
+        })?)
+    }
+
+    /// Handler for `reth_getWithdrawalRequestQueue`
+    async fn reth_get_withdrawal_request_queue(
+        &self,
+        block_id: Option<BlockId>,
+    ) -> RpcResult<WithdrawalRequestQueue> {
+        Ok(Self::withdrawal_request_queue(self, block_id.unwrap_or_default()).await?)
+    }
+
+    /// Handler for `reth_getTransactionReceipt`
+    async fn reth_get_transaction_receipt(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<AnyTransactionReceipt>> {
+        Ok(Self::transaction_receipt(self, hash).await?)
+    }
+
+    /// Handler for `reth_cancelTransaction`
+    async fn reth_cancel_transaction(
+        &self,
+        hash: B256,
+        signature: Bytes,
+        replace: Option<bool>,
+    ) -> RpcResult<Option<B256>> {
+        let price_bump = replace.unwrap_or_default().then_some(self.inner.cancel_price_bump);
+        Ok(self.eth_api().cancel_transaction(hash, signature, price_bump).await?)
+    }
+
+    /// Handler for `reth_getTransactionsBySelector`
+    async fn reth_get_transactions_by_selector(
+        &self,
+        selector: Selector,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+    ) -> RpcResult<Vec<B256>> {
+        Ok(Self::transactions_by_selector(
+            self,
+            selector,
+            from_block.unwrap_or(BlockNumberOrTag::Earliest),
+            to_block.unwrap_or_default(),
+        )
+        .await?)
+    }
+
+    /// Handler for `reth_subscribeChainReorgs`
+    async fn reth_subscribe_chain_reorgs(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> jsonrpsee::core::SubscriptionResult {
+        let sink = pending.accept().await?;
+        let stream = self.inner.chain_events.canonical_state_stream().filter_map(|notification| {
+            futures::future::ready(match notification {
+                CanonStateNotification::Reorg { old, new } => Some(ChainReorg {
+                    old: old.headers().map(from_primitive_with_hash).collect(),
+                    new: new.headers().map(from_primitive_with_hash).collect(),
+                }),
+                CanonStateNotification::Commit { .. } => None,
+            })
+        });
+        self.inner.task_spawner.spawn(Box::pin(async move {
+            let _ = pipe_from_stream(sink, stream).await;
+        }));
+        Ok(())
+    }
+}
+
+/// Adds the timestamp of the block and a breakdown of the fees paid to the receipt of a
+/// transaction included in the block with the given header:
+///
+///  - `blockTimestamp`: the timestamp of the block.
+///  - `effectivePriorityFee`: the fee per gas paid to the beneficiary of the block, the effective
+///    gas price minus the base fee of the block.
+///  - `totalFee`: the total fee paid by the transaction in wei, including the blob fee and, on
+///    optimism, the L1 data fee.
+///
+/// The optimism L1 fee fields are already part of the receipt.
+pub(crate) fn extend_receipt(receipt: &mut AnyTransactionReceipt, header: &Header) {
+    let effective_gas_price = receipt.effective_gas_price;
+    let effective_priority_fee =
+        effective_gas_price.saturating_sub(header.base_fee_per_gas.unwrap_or_default() as u128);
+
+    let blob_fee = receipt
+        .blob_gas_used
+        .zip(receipt.blob_gas_price)
+        .map(|(blob_gas_used, blob_gas_price)| blob_gas_used.saturating_mul(blob_gas_price))
+        .unwrap_or_default();
+    let l1_fee = receipt
+        .other
+        .get("l1Fee")
+        .and_then(|l1_fee| serde_json::from_value::<U128>(l1_fee.clone()).ok())
+        .map(|l1_fee| l1_fee.to::<u128>())
+        .unwrap_or_default();
+    let total_fee = receipt
+        .gas_used
+        .saturating_mul(effective_gas_price)
+        .saturating_add(blob_fee)
+        .saturating_add(l1_fee);
+
+    receipt.other.insert("blockTimestamp".to_string(), quantity(header.timestamp as u128));
+    receipt.other.insert("effectivePriorityFee".to_string(), quantity(effective_priority_fee));
+    receipt.other.insert("totalFee".to_string(), quantity(total_fee));
+}
+
+/// Encodes the value as a hex quantity.
+fn quantity(value: u128) -> serde_json::Value {
+    serde_json::Value::String(format!("{value:#x}"))
 }
 
-impl<Provider> std::fmt::Debug for RethApi<Provider> {
+impl<Provider, Eth, Events> std::fmt::Debug for RethApi<Provider, Eth, Events> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("RethApi").finish_non_exhaustive()
     }
 }
 
-impl<Provider> Clone for RethApi<Provider> {
+impl<Provider, Eth, Events> Clone for RethApi<Provider, Eth, Events> {
     fn clone(&self) -> Self {
         Self { inner: Arc::clone(&self.inner) }
     }
 }
 
-struct RethApiInner<Provider> {
+struct RethApiInner<Provider, Eth, Events> {
     /// The provider that can interact with the chain.
     provider: Provider,
+    /// Access to commonly used code of the `eth` namespace
+    eth_api: Eth,
+    /// A type that allows to create new event subscriptions.
+    chain_events: Events,
     /// The type that can spawn tasks which would otherwise block.
     task_spawner: Box<dyn TaskSpawner>,
+    /// Percentage by which the fees of replacements of cancelled transactions are bumped.
+    cancel_price_bump: u128,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_rpc_types::{
+        AnyReceiptEnvelope, Receipt, ReceiptWithBloom, TransactionReceipt, WithOtherFields,
+    };
+
+    fn test_receipt(gas_used: u128, effective_gas_price: u128) -> AnyTransactionReceipt {
+        WithOtherFields::new(TransactionReceipt {
+            inner: AnyReceiptEnvelope {
+                inner: ReceiptWithBloom {
+                    receipt: Receipt {
+                        status: true.into(),
+                        cumulative_gas_used: gas_used,
+                        logs: Vec::new(),
+                    },
+                    logs_bloom: Default::default(),
+                },
+                r#type: 2,
+            },
+            transaction_hash: B256::ZERO,
+            transaction_index: Some(0),
+            block_hash: Some(B256::ZERO),
+            block_number: Some(1),
+            gas_used,
+            effective_gas_price,
+            blob_gas_used: None,
+            blob_gas_price: None,
+            from: Address::ZERO,
+            to: None,
+            contract_address: None,
+            state_root: None,
+        })
+    }
+
+    #[test]
+    fn extends_receipt() {
+        let header = Header { timestamp: 0x10, base_fee_per_gas: Some(7), ..Default::default() };
+
+        let mut receipt = test_receipt(21_000, 10);
+        extend_receipt(&mut receipt, &header);
+        assert_eq!(receipt.other["blockTimestamp"], "0x10");
+        assert_eq!(receipt.other["effectivePriorityFee"], "0x3");
+        assert_eq!(receipt.other["totalFee"], quantity(210_000));
+
+        // the L1 fee is part of the total fee
+        let mut receipt = test_receipt(21_000, 10);
+        receipt.other.insert("l1Fee".to_string(), quantity(5));
+        extend_receipt(&mut receipt, &header);
+        assert_eq!(receipt.other["totalFee"], quantity(210_005));
+    }
 }
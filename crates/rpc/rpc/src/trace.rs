@@ -9,7 +9,9 @@ use jsonrpsee::core::RpcResult as Result;
 use reth_consensus_common::calc::{
     base_block_reward, base_block_reward_pre_merge, block_reward, ommer_reward,
 };
-use reth_primitives::{revm::env::tx_env_with_recovered, BlockId, Bytes, Header, B256, U256};
+use reth_primitives::{
+    keccak256, revm::env::tx_env_with_recovered, BlockId, Bytes, Header, B256, U256,
+};
 use reth_provider::{BlockReader, ChainSpecProvider, EvmEnvProvider, StateProviderFactory};
 use reth_revm::database::StateProviderDatabase;
 use reth_rpc_api::TraceApiServer;
@@ -32,6 +34,7 @@ use revm_inspectors::{
     opcode::OpcodeGasInspector,
     tracing::{parity::populate_state_diff, TracingInspector, TracingInspectorConfig},
 };
+use schnellru::{ByLength, LruMap};
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::{AcquireError, OwnedSemaphorePermit};
 
@@ -51,8 +54,22 @@ impl<Provider, Eth> TraceApi<Provider, Eth> {
     }
 
     /// Create a new instance of the [`TraceApi`]
-    pub fn new(provider: Provider, eth_api: Eth, blocking_task_guard: BlockingTaskGuard) -> Self {
-        let inner = Arc::new(TraceApiInner { provider, eth_api, blocking_task_guard });
+    ///
+    /// `max_traces_per_response` is the maximum number of traces that `trace_filter` returns in a
+    /// single response.
+    pub fn new(
+        provider: Provider,
+        eth_api: Eth,
+        blocking_task_guard: BlockingTaskGuard,
+        max_traces_per_response: usize,
+    ) -> Self {
+        let inner = Arc::new(TraceApiInner {
+            provider,
+            eth_api,
+            blocking_task_guard,
+            max_traces_per_response,
+            filter_trace_counts: FilterTraceCounts::new(FILTER_TRACE_COUNTS_CACHE_SIZE),
+        });
         Self { inner }
     }
 
@@ -86,7 +103,7 @@ where
         let this = self.clone();
         self.eth_api()
             .spawn_with_call_at(trace_request.call, at, overrides, move |db, env| {
-                let (res, _) = this.eth_api().inspect(&mut *db, env, &mut inspector)?;
+                let (res, _) = this.eth_api().inspect_call(&mut *db, env, &mut inspector)?;
                 let trace_res = inspector.into_parity_builder().into_trace_results_with_state(
                     &res,
                     &trace_request.trace_types,
@@ -157,7 +174,7 @@ where
                     )?;
                     let config = TracingInspectorConfig::from_parity_config(&trace_types);
                     let mut inspector = TracingInspector::new(config);
-                    let (res, _) = this.eth_api().inspect(&mut db, env, &mut inspector)?;
+                    let (res, _) = this.eth_api().inspect_call(&mut db, env, &mut inspector)?;
 
                     let trace_res = inspector.into_parity_builder().into_trace_results_with_state(
                         &res,
@@ -236,12 +253,21 @@ where
     ///
     /// This is similar to [`Self::trace_block`] but only returns traces for transactions that match
     /// the filter.
+    ///
+    /// The `after` and `count` fields of the filter select a page of the traces. Blocks are traced
+    /// one after another and tracing stops as soon as the page is complete, so only the traces of
+    /// the page are held in memory. Without `count`, the traces must fit into the configured
+    /// maximum number of traces per response.
+    ///
+    /// The number of traces of each traced block that match the filter is cached, so that the
+    /// blocks before the page are skipped without tracing them again when the next page is
+    /// requested.
     pub async fn trace_filter(
         &self,
         filter: TraceFilter,
     ) -> EthResult<Vec<LocalizedTransactionTrace>> {
         let matcher = filter.matcher();
-        let TraceFilter { from_block, to_block, .. } = filter;
+        let TraceFilter { from_block, to_block, after, count, .. } = filter;
         let start = from_block.unwrap_or(0);
         let end = if let Some(to_block) = to_block {
             to_block
@@ -263,75 +289,85 @@ where
             ))
         }
 
+        let mut page = TracePage::new(after, count, self.inner.max_traces_per_response)?;
+        let filter_hash = FilterTraceCounts::filter_hash(&filter);
+
         // fetch all blocks in that range
         let blocks = self.provider().block_range(start..=end)?;
 
-        // find relevant blocks to trace
-        let mut target_blocks = Vec::new();
+        // blocks are in ascending order, so once there is no block reward, we're past the Paris
+        // hardfork and don't expect any rewards
+        let mut has_rewards = true;
         for block in &blocks {
+            if page.is_complete() {
+                break
+            }
+
+            let block_hash = block.header.hash_slow();
+            if let Some(len) = self.inner.filter_trace_counts.get(block_hash, filter_hash) {
+                if page.skip_block(len) {
+                    continue
+                }
+            }
+
+            // find relevant transactions to trace
             let mut transaction_indices = HashSet::new();
-            let mut highest_matching_index = 0;
+            let mut highest_matching_index = None;
             for (tx_idx, tx) in block.body.iter().enumerate() {
                 let from = tx.recover_signer_unchecked().ok_or(BlockError::InvalidSignature)?;
                 let to = tx.to();
                 if matcher.matches(from, to) {
                     let idx = tx_idx as u64;
                     transaction_indices.insert(idx);
-                    highest_matching_index = idx;
+                    highest_matching_index = Some(idx);
                 }
             }
-            if !transaction_indices.is_empty() {
-                target_blocks.push((block.number, transaction_indices, highest_matching_index));
-            }
-        }
 
-        // trace all relevant blocks
-        let mut block_traces = Vec::with_capacity(target_blocks.len());
-        for (num, indices, highest_idx) in target_blocks {
-            let traces = self.inner.eth_api.trace_block_until(
-                num.into(),
-                Some(highest_idx),
-                TracingInspectorConfig::default_parity(),
-                move |tx_info, inspector, res, _, _| {
-                    if let Some(idx) = tx_info.index {
-                        if !indices.contains(&idx) {
-                            // only record traces for relevant transactions
-                            return Ok(None)
-                        }
-                    }
-                    let traces = inspector
-                        .with_transaction_gas_used(res.gas_used())
-                        .into_parity_builder()
-                        .into_localized_transaction_traces(tx_info);
-                    Ok(Some(traces))
-                },
-            );
-            block_traces.push(traces);
-        }
-
-        let block_traces = futures::future::try_join_all(block_traces).await?;
-        let mut all_traces = block_traces
-            .into_iter()
-            .flatten()
-            .flat_map(|traces| traces.into_iter().flatten().flat_map(|traces| traces.into_iter()))
-            .collect::<Vec<_>>();
+            let mut block_traces = Vec::new();
+            if let Some(highest_idx) = highest_matching_index {
+                let traces = self
+                    .inner
+                    .eth_api
+                    .trace_block_until(
+                        block.number.into(),
+                        Some(highest_idx),
+                        TracingInspectorConfig::default_parity(),
+                        move |tx_info, inspector, res, _, _| {
+                            if let Some(idx) = tx_info.index {
+                                if !transaction_indices.contains(&idx) {
+                                    // only record traces for relevant transactions
+                                    return Ok(None)
+                                }
+                            }
+                            let traces = inspector
+                                .with_transaction_gas_used(res.gas_used())
+                                .into_parity_builder()
+                                .into_localized_transaction_traces(tx_info);
+                            Ok(Some(traces))
+                        },
+                    )
+                    .await?;
+                block_traces.extend(traces.into_iter().flatten().flatten().flatten());
+            }
 
-        // add reward traces for all blocks
-        for block in &blocks {
-            if let Some(base_block_reward) = self.calculate_base_block_reward(&block.header)? {
-                all_traces.extend(self.extract_reward_traces(
-                    &block.header,
-                    &block.ommers,
-                    base_block_reward,
-                ));
-            } else {
-                // no block reward, means we're past the Paris hardfork and don't expect any rewards
-                // because the blocks in ascending order
-                break
+            // add reward traces of the block
+            if has_rewards {
+                if let Some(base_block_reward) = self.calculate_base_block_reward(&block.header)? {
+                    block_traces.extend(self.extract_reward_traces(
+                        &block.header,
+                        &block.ommers,
+                        base_block_reward,
+                    ));
+                } else {
+                    has_rewards = false;
+                }
             }
+
+            self.inner.filter_trace_counts.insert(block_hash, filter_hash, block_traces.len());
+            page.extend(block_traces)?;
         }
 
-        Ok(all_traces)
+        Ok(page.traces)
     }
 
     /// Returns all traces for the given transaction hash
@@ -624,6 +660,7 @@ where
     /// # Limitations
     /// This currently requires block filter fields, since reth does not have address indices yet.
     async fn trace_filter(&self, filter: TraceFilter) -> Result<Vec<LocalizedTransactionTrace>> {
+        let _permit = self.acquire_trace_permit().await;
         Ok(Self::trace_filter(self, filter).await?)
     }
 
@@ -681,6 +718,118 @@ struct TraceApiInner<Provider, Eth> {
     eth_api: Eth,
     // restrict the number of concurrent calls to `trace_*`
     blocking_task_guard: BlockingTaskGuard,
+    /// Maximum number of traces in a single `trace_filter` response.
+    max_traces_per_response: usize,
+    /// The number of traces of recently traced blocks that match `trace_filter` filters.
+    filter_trace_counts: FilterTraceCounts,
+}
+
+/// The number of blocks and filters the number of matching traces is cached for.
+const FILTER_TRACE_COUNTS_CACHE_SIZE: u32 = 10_000;
+
+/// Caches the number of traces of a block that match a `trace_filter` filter.
+///
+/// Entries are keyed by the hash of the block and the hash of the addresses and mode of the
+/// filter, which are all that determine the matching traces of a block.
+struct FilterTraceCounts {
+    counts: parking_lot::Mutex<LruMap<(B256, B256), usize, ByLength>>,
+}
+
+impl FilterTraceCounts {
+    /// Creates a new cache that holds up to `max_len` trace counts.
+    fn new(max_len: u32) -> Self {
+        Self { counts: parking_lot::Mutex::new(LruMap::new(ByLength::new(max_len))) }
+    }
+
+    /// Returns the hash of the parts of the filter that determine the matching traces of a block.
+    fn filter_hash(filter: &TraceFilter) -> B256 {
+        let TraceFilter { from_address, to_address, mode, .. } = filter;
+        let filter = TraceFilter {
+            from_address: from_address.clone(),
+            to_address: to_address.clone(),
+            mode: *mode,
+            ..Default::default()
+        };
+        keccak256(serde_json::to_vec(&filter).unwrap_or_default())
+    }
+
+    /// Returns the cached number of traces of the block that match the filter, if any.
+    fn get(&self, block_hash: B256, filter_hash: B256) -> Option<usize> {
+        self.counts.lock().get(&(block_hash, filter_hash)).copied()
+    }
+
+    /// Caches the number of traces of the block that match the filter.
+    fn insert(&self, block_hash: B256, filter_hash: B256, len: usize) {
+        self.counts.lock().insert((block_hash, filter_hash), len);
+    }
+}
+
+/// A page of the traces that match a `trace_filter` request.
+#[derive(Debug)]
+struct TracePage {
+    /// The number of matching traces that precede the page and are still to be skipped.
+    skip: u64,
+    /// The maximum number of traces of the page.
+    limit: usize,
+    /// Whether the size of the page was requested, rather than bounded by the server.
+    requested: bool,
+    traces: Vec<LocalizedTransactionTrace>,
+}
+
+impl TracePage {
+    /// Creates the page that starts after `after` traces and holds up to `count` traces, or all
+    /// remaining traces if `count` isn't set.
+    fn new(after: Option<u64>, count: Option<u64>, max_traces: usize) -> EthResult<Self> {
+        if count.is_some_and(|count| count > max_traces as u64) {
+            return Err(EthApiError::InvalidParams(format!(
+                "count exceeds the maximum of {max_traces} traces per response"
+            )))
+        }
+        Ok(Self {
+            skip: after.unwrap_or_default(),
+            limit: count.map_or(max_traces, |count| count as usize),
+            requested: count.is_some(),
+            traces: Vec::new(),
+        })
+    }
+
+    /// Returns `true` if the requested number of traces has been collected.
+    fn is_complete(&self) -> bool {
+        self.requested && self.traces.len() == self.limit
+    }
+
+    /// Skips the `len` matching traces of a block if they all precede the page.
+    ///
+    /// Returns `false` if the page starts within the block, which must then be traced.
+    fn skip_block(&mut self, len: usize) -> bool {
+        let Some(skip) = self.skip.checked_sub(len as u64) else { return false };
+        self.skip = skip;
+        true
+    }
+
+    /// Adds the matching traces of the next block to the page.
+    ///
+    /// Returns an error if the page wasn't requested and the traces exceed the maximum number of
+    /// traces per response.
+    fn extend(&mut self, traces: Vec<LocalizedTransactionTrace>) -> EthResult<()> {
+        for trace in traces {
+            if self.skip > 0 {
+                self.skip -= 1;
+                continue
+            }
+            if self.traces.len() == self.limit {
+                if self.requested {
+                    break
+                }
+                return Err(EthApiError::InvalidParams(format!(
+                    "query exceeds max results {}, use `after` and `count` to paginate",
+                    self.limit
+                )))
+            }
+            self.traces.push(trace);
+        }
+        Ok(())
+    }
 }
 
 /// Helper to construct a [`LocalizedTransactionTrace`] that describes a reward to the block
@@ -700,3 +849,123 @@ fn reward_trace(header: &Header, reward: RewardAction) -> LocalizedTransactionTr
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::Address;
+
+    /// Returns the matching traces of blocks with the given numbers of traces.
+    fn block_traces(lens: &[usize]) -> Vec<Vec<LocalizedTransactionTrace>> {
+        let mut value = 0;
+        lens.iter()
+            .enumerate()
+            .map(|(number, len)| {
+                let header = Header { number: number as u64, ..Default::default() };
+                (0..*len)
+                    .map(|_| {
+                        value += 1;
+                        reward_trace(
+                            &header,
+                            RewardAction {
+                                author: Address::ZERO,
+                                reward_type: RewardType::Block,
+                                value: U256::from(value),
+                            },
+                        )
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Collects a page of the traces like `trace_filter`, skipping the blocks with known counts.
+    fn collect_page(
+        blocks: &[Vec<LocalizedTransactionTrace>],
+        known_counts: bool,
+        after: Option<u64>,
+        count: Option<u64>,
+        max_traces: usize,
+    ) -> EthResult<Vec<LocalizedTransactionTrace>> {
+        let mut page = TracePage::new(after, count, max_traces)?;
+        for traces in blocks {
+            if page.is_complete() {
+                break
+            }
+            if known_counts && page.skip_block(traces.len()) {
+                continue
+            }
+            page.extend(traces.clone())?;
+        }
+        Ok(page.traces)
+    }
+
+    #[test]
+    fn trace_pages_cover_all_traces() {
+        let blocks = block_traces(&[3, 0, 4, 3]);
+        let all = blocks.iter().flatten().cloned().collect::<Vec<_>>();
+
+        for known_counts in [false, true] {
+            assert_eq!(collect_page(&blocks, known_counts, None, None, 10).unwrap(), all);
+
+            for count in 1..=4 {
+                let mut paged = Vec::new();
+                let mut after = 0;
+                loop {
+                    let page =
+                        collect_page(&blocks, known_counts, Some(after), Some(count), 10).unwrap();
+                    if page.is_empty() {
+                        break
+                    }
+                    assert!(page.len() <= count as usize);
+                    after += page.len() as u64;
+                    paged.extend(page);
+                }
+                assert_eq!(paged, all);
+            }
+        }
+    }
+
+    #[test]
+    fn trace_page_is_bounded() {
+        let blocks = block_traces(&[3, 4]);
+
+        // the requested count can't exceed the maximum
+        assert!(TracePage::new(None, Some(6), 5).is_err());
+        // without count, all traces after the offset must fit
+        assert!(collect_page(&blocks, false, None, None, 5).is_err());
+        assert_eq!(collect_page(&blocks, true, Some(2), None, 5).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn skips_blocks_before_the_page() {
+        let mut page = TracePage::new(Some(5), Some(2), 10).unwrap();
+        assert!(page.skip_block(3));
+        assert!(page.skip_block(0));
+        // the page starts within the block
+        assert!(!page.skip_block(3));
+        assert_eq!(page.skip, 2);
+    }
+
+    #[test]
+    fn filter_hash_ignores_range_and_pagination() {
+        let filter = TraceFilter { from_address: vec![Address::ZERO], ..Default::default() };
+        let paged = TraceFilter {
+            from_block: Some(1),
+            to_block: Some(2),
+            after: Some(3),
+            count: Some(4),
+            ..filter.clone()
+        };
+        assert_eq!(FilterTraceCounts::filter_hash(&filter), FilterTraceCounts::filter_hash(&paged));
+
+        let other = TraceFilter { to_address: vec![Address::ZERO], ..Default::default() };
+        assert_ne!(FilterTraceCounts::filter_hash(&filter), FilterTraceCounts::filter_hash(&other));
+
+        let cache = FilterTraceCounts::new(1);
+        let filter_hash = FilterTraceCounts::filter_hash(&filter);
+        cache.insert(B256::ZERO, filter_hash, 3);
+        assert_eq!(cache.get(B256::ZERO, filter_hash), Some(3));
+        assert_eq!(cache.get(B256::ZERO, FilterTraceCounts::filter_hash(&other)), None);
+    }
+}
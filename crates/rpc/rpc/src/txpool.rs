@@ -1,15 +1,25 @@
+use crate::result::invalid_params_rpc_err;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult as Result;
 use reth_primitives::Address;
 use reth_rpc_api::TxPoolApiServer;
 use reth_rpc_types::{
     txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus},
-    Transaction,
+    Transaction, TxpoolPage,
 };
-use reth_transaction_pool::{AllPoolTransactions, PoolTransaction, TransactionPool};
-use std::collections::BTreeMap;
+use reth_transaction_pool::{
+    AllPoolTransactions, PoolTransaction, TransactionPool, ValidPoolTransaction,
+};
+use std::{collections::BTreeMap, sync::Arc};
 use tracing::trace;
 
+/// The number of transactions of a `txpool_contentPage` or `txpool_inspectPage` page if no limit
+/// is requested.
+const DEFAULT_PAGE_SIZE: usize = 1_000;
+
+/// The maximum number of transactions of a `txpool_contentPage` or `txpool_inspectPage` page.
+const MAX_PAGE_SIZE: usize = 10_000;
+
 /// `txpool` API implementation.
 ///
 /// This type provides the functionality for handling `txpool` related requests.
@@ -17,12 +27,18 @@ use tracing::trace;
 pub struct TxPoolApi<Pool> {
     /// An interface to interact with the pool
     pool: Pool,
+    /// The maximum number of transactions `txpool_content` and `txpool_inspect` return.
+    max_content: usize,
 }
 
 impl<Pool> TxPoolApi<Pool> {
     /// Creates a new instance of `TxpoolApi`.
-    pub const fn new(pool: Pool) -> Self {
-        Self { pool }
+    ///
+    /// `txpool_content` and `txpool_inspect` fail if the pool has more than `max_content`
+    /// transactions, which then must be paginated with `txpool_contentPage` and
+    /// `txpool_inspectPage`.
+    pub const fn new(pool: Pool, max_content: usize) -> Self {
+        Self { pool, max_content }
     }
 }
 
@@ -30,29 +46,63 @@ impl<Pool> TxPoolApi<Pool>
 where
     Pool: TransactionPool + 'static,
 {
-    fn content(&self) -> TxpoolContent {
-        #[inline]
-        fn insert<T: PoolTransaction>(
-            tx: &T,
-            content: &mut BTreeMap<Address, BTreeMap<String, Transaction>>,
-        ) {
-            content.entry(tx.sender()).or_default().insert(
-                tx.nonce().to_string(),
-                reth_rpc_types_compat::transaction::from_recovered(tx.to_recovered_transaction()),
-            );
+    /// Returns all transactions of the pool, or an error if there are more than the configured
+    /// maximum.
+    fn all_transactions(&self, method: &str) -> Result<AllPoolTransactions<Pool::Transaction>> {
+        // the queued transactions are the ones of the basefee and queued sub-pools
+        let size = self.pool.pool_size();
+        if size.pending + size.basefee + size.queued > self.max_content {
+            return Err(invalid_params_rpc_err(format!(
+                "the pool has more than {} transactions, use {method}Page",
+                self.max_content
+            )))
         }
+        Ok(self.pool.all_transactions())
+    }
 
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
-
-        let mut content = TxpoolContent::default();
-        for pending in pending {
-            insert(&pending.transaction, &mut content.pending);
-        }
-        for queued in queued {
-            insert(&queued.transaction, &mut content.queued);
+    /// Returns the transactions of the senders starting with `cursor`, up to `limit`
+    /// transactions, converted with `f`.
+    fn page<T>(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+        f: impl Fn(&Pool::Transaction) -> T,
+    ) -> TxpoolPage<T> {
+        let limit = limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let (AllPoolTransactions { pending, queued }, next) =
+            self.pool.get_transactions_page(cursor, limit);
+        TxpoolPage {
+            pending: group_by_sender(&pending, &f),
+            queued: group_by_sender(&queued, &f),
+            next,
         }
+    }
+}
 
+/// Groups the transactions by sender and nonce, converted with `f`.
+fn group_by_sender<T, P: PoolTransaction>(
+    txs: &[Arc<ValidPoolTransaction<P>>],
+    f: impl Fn(&P) -> T,
+) -> BTreeMap<Address, BTreeMap<String, T>> {
+    txs.iter().fold(BTreeMap::new(), |mut content, tx| {
+        content.entry(tx.sender()).or_default().insert(tx.nonce().to_string(), f(&tx.transaction));
         content
+    })
+}
+
+/// Converts a pool transaction to its `txpool_content` representation.
+fn to_transaction<T: PoolTransaction>(tx: &T) -> Transaction {
+    reth_rpc_types_compat::transaction::from_recovered(tx.to_recovered_transaction())
+}
+
+/// Converts a pool transaction to its `txpool_inspect` summary.
+fn to_inspect_summary<T: PoolTransaction>(tx: &T) -> TxpoolInspectSummary {
+    let tx = tx.to_recovered_transaction();
+    TxpoolInspectSummary {
+        to: tx.to(),
+        value: tx.value(),
+        gas: tx.gas_limit() as u128,
+        gas_price: tx.transaction.max_fee_per_gas(),
     }
 }
 
@@ -68,8 +118,13 @@ where
     /// Handler for `txpool_status`
     async fn txpool_status(&self) -> Result<TxpoolStatus> {
         trace!(target: "rpc::eth", "Serving txpool_status");
-        let all = self.pool.all_transactions();
-        Ok(TxpoolStatus { pending: all.pending.len() as u64, queued: all.queued.len() as u64 })
+        // the queued transactions of `txpool_content` are the ones of the basefee and queued
+        // sub-pools
+        let size = self.pool.pool_size();
+        Ok(TxpoolStatus {
+            pending: size.pending as u64,
+            queued: (size.basefee + size.queued) as u64,
+        })
     }
 
     /// Returns a summary of all the transactions currently pending for inclusion in the next
@@ -80,36 +135,10 @@ where
     /// Handler for `txpool_inspect`
     async fn txpool_inspect(&self) -> Result<TxpoolInspect> {
         trace!(target: "rpc::eth", "Serving txpool_inspect");
-
-        #[inline]
-        fn insert<T: PoolTransaction>(
-            tx: &T,
-            inspect: &mut BTreeMap<Address, BTreeMap<String, TxpoolInspectSummary>>,
-        ) {
-            let entry = inspect.entry(tx.sender()).or_default();
-            let tx = tx.to_recovered_transaction();
-            entry.insert(
-                tx.nonce().to_string(),
-                TxpoolInspectSummary {
-                    to: tx.to(),
-                    value: tx.value(),
-                    gas: tx.gas_limit() as u128,
-                    gas_price: tx.transaction.max_fee_per_gas(),
-                },
-            );
-        }
-
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
-
+        let AllPoolTransactions { pending, queued } = self.all_transactions("txpool_inspect")?;
         Ok(TxpoolInspect {
-            pending: pending.iter().fold(Default::default(), |mut acc, tx| {
-                insert(&tx.transaction, &mut acc);
-                acc
-            }),
-            queued: queued.iter().fold(Default::default(), |mut acc, tx| {
-                insert(&tx.transaction, &mut acc);
-                acc
-            }),
+            pending: group_by_sender(&pending, to_inspect_summary),
+            queued: group_by_sender(&queued, to_inspect_summary),
         })
     }
 
@@ -120,7 +149,12 @@ where
     /// Handler for `txpool_contentFrom`
     async fn txpool_content_from(&self, from: Address) -> Result<TxpoolContentFrom> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().remove_from(&from))
+        let AllPoolTransactions { pending, queued } =
+            self.pool.get_all_transactions_by_sender(from);
+        Ok(TxpoolContentFrom {
+            pending: group_by_sender(&pending, to_transaction).remove(&from).unwrap_or_default(),
+            queued: group_by_sender(&queued, to_transaction).remove(&from).unwrap_or_default(),
+        })
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -130,7 +164,31 @@ where
     /// Handler for `txpool_content`
     async fn txpool_content(&self) -> Result<TxpoolContent> {
         trace!(target: "rpc::eth", "Serving txpool_content");
-        Ok(self.content())
+        let AllPoolTransactions { pending, queued } = self.all_transactions("txpool_content")?;
+        Ok(TxpoolContent {
+            pending: group_by_sender(&pending, to_transaction),
+            queued: group_by_sender(&queued, to_transaction),
+        })
+    }
+
+    /// Handler for `txpool_contentPage`
+    async fn txpool_content_page(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> Result<TxpoolPage<Transaction>> {
+        trace!(target: "rpc::eth", ?cursor, ?limit, "Serving txpool_contentPage");
+        Ok(self.page(cursor, limit, to_transaction))
+    }
+
+    /// Handler for `txpool_inspectPage`
+    async fn txpool_inspect_page(
+        &self,
+        cursor: Option<Address>,
+        limit: Option<usize>,
+    ) -> Result<TxpoolPage<TxpoolInspectSummary>> {
+        trace!(target: "rpc::eth", ?cursor, ?limit, "Serving txpool_inspectPage");
+        Ok(self.page(cursor, limit, to_inspect_summary))
     }
 }
 
@@ -139,3 +197,45 @@ impl<Pool> std::fmt::Debug for TxPoolApi<Pool> {
         f.debug_struct("TxpoolApi").finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_transaction_pool::{
+        test_utils::{testing_pool, MockTransaction},
+        TransactionOrigin,
+    };
+
+    /// Returns the senders of the page and their number of transactions.
+    fn senders<T>(page: &TxpoolPage<T>) -> BTreeMap<Address, usize> {
+        let mut senders = BTreeMap::new();
+        for (sender, txs) in page.pending.iter().chain(&page.queued) {
+            *senders.entry(*sender).or_default() += txs.len();
+        }
+        senders
+    }
+
+    #[tokio::test]
+    async fn paginates_content() {
+        let pool = testing_pool();
+        let mut sender_addrs = Vec::new();
+        for _ in 0..3 {
+            let tx = MockTransaction::eip1559();
+            sender_addrs.push(tx.sender());
+            pool.add_transaction(TransactionOrigin::External, tx.clone()).await.unwrap();
+            pool.add_transaction(TransactionOrigin::External, tx.next()).await.unwrap();
+        }
+
+        let api = TxPoolApi::new(pool, 5);
+        assert!(api.txpool_content().await.is_err());
+
+        // a sender is never split across pages
+        let page = api.txpool_inspect_page(None, Some(3)).await.unwrap();
+        assert_eq!(senders(&page), BTreeMap::from([(sender_addrs[0], 2)]));
+        assert_eq!(page.next, Some(sender_addrs[1]));
+
+        let page = api.txpool_inspect_page(page.next, Some(4)).await.unwrap();
+        assert_eq!(senders(&page), BTreeMap::from([(sender_addrs[1], 2), (sender_addrs[2], 2)]));
+        assert_eq!(page.next, None);
+    }
+}
@@ -0,0 +1,399 @@
+//! Accounting of the usage of the RPC servers by API key.
+
+use jsonrpsee::core::RpcResult;
+use parking_lot::RwLock;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_rpc_api::RpcUsageApiServer;
+use reth_rpc_types::ApiKeyUsage;
+use std::{
+    collections::HashMap,
+    fs,
+    future::Future,
+    io,
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+/// The compute units of a request to a method without its own compute units.
+pub const DEFAULT_COMPUTE_UNITS: u64 = 10;
+
+/// The compute units of methods that are considerably cheaper or more expensive to serve than the
+/// [`DEFAULT_COMPUTE_UNITS`].
+const METHOD_COMPUTE_UNITS: &[(&str, u64)] = &[
+    ("eth_chainId", 1),
+    ("net_version", 1),
+    ("web3_clientVersion", 1),
+    ("eth_blockNumber", 2),
+    ("eth_syncing", 2),
+    ("eth_feeHistory", 15),
+    ("eth_call", 25),
+    ("eth_getProof", 30),
+    ("eth_createAccessList", 50),
+    ("eth_getLogs", 75),
+    ("eth_estimateGas", 80),
+    ("eth_getBlockReceipts", 150),
+    ("eth_sendRawTransaction", 250),
+    ("trace_call", 100),
+    ("trace_transaction", 100),
+    ("trace_block", 300),
+    ("trace_replayTransaction", 300),
+    ("debug_traceCall", 300),
+    ("debug_traceTransaction", 300),
+    ("trace_filter", 500),
+    ("trace_replayBlockTransactions", 500),
+    ("debug_traceBlockByHash", 500),
+    ("debug_traceBlockByNumber", 500),
+];
+
+/// The usage of the RPC servers by API key: the number of requests to every method, their compute
+/// units and the size of their responses.
+///
+/// The usage of all requests without a known API key is accounted together. Requests are accounted
+/// with atomic counters, the table is only locked exclusively when an API key or a method is
+/// accounted for the first time, or when the usage is reset. The usage table can be persisted to a file, see [`RpcUsage::persist`], and the totals of every API key are exported
+/// as metrics.
+#[derive(Debug, Clone)]
+pub struct RpcUsage {
+    inner: Arc<RpcUsageInner>,
+}
+
+impl RpcUsage {
+    /// Creates a new usage table with the default compute units of the methods, overridden by the
+    /// given ones.
+    ///
+    /// If a file is given, the table is read from the file if it exists, and persisted to it.
+    pub fn new(
+        compute_units: impl IntoIterator<Item = (String, u64)>,
+        file: Option<PathBuf>,
+    ) -> io::Result<Self> {
+        let compute_units = METHOD_COMPUTE_UNITS
+            .iter()
+            .map(|(method, units)| (method.to_string(), *units))
+            .chain(compute_units)
+            .collect();
+
+        let mut table = HashMap::new();
+        if let Some(file) = &file {
+            match fs::read(file) {
+                Ok(raw) => {
+                    for usage in serde_json::from_slice::<Vec<ApiKeyUsage>>(&raw)? {
+                        table.insert(usage.api_key.clone(), UsageEntry::new(usage));
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(Self {
+            inner: Arc::new(RpcUsageInner { compute_units, file, table: RwLock::new(table) }),
+        })
+    }
+
+    /// Returns the compute units of a request to the method.
+    pub fn compute_units(&self, method: &str) -> u64 {
+        self.inner.compute_units.get(method).copied().unwrap_or(DEFAULT_COMPUTE_UNITS)
+    }
+
+    /// Accounts a request to the method with the API key, and the size of its response.
+    ///
+    /// The caller is responsible for bounding the API keys and methods, every distinct one is
+    /// kept in the table until it is reset.
+    pub fn record(&self, api_key: Option<&str>, method: &str, egress_bytes: u64) {
+        let compute_units = self.compute_units(method);
+
+        // the table is held until the request is accounted, so a concurrent reset can't drop it
+        let table = self.inner.table.read();
+        if let Some(entry) = table.get(&api_key.map(ToString::to_string)) {
+            entry.record(method, compute_units, egress_bytes);
+            return
+        }
+        drop(table);
+
+        let mut table = self.inner.table.write();
+        table
+            .entry(api_key.map(ToString::to_string))
+            .or_insert_with(|| {
+                UsageEntry::new(ApiKeyUsage {
+                    api_key: api_key.map(ToString::to_string),
+                    since: unix_timestamp(),
+                    ..Default::default()
+                })
+            })
+            .record(method, compute_units, egress_bytes);
+    }
+
+    /// Returns the usage of the API key, or of all API keys if `None`.
+    pub fn usage(&self, api_key: Option<&str>) -> Vec<ApiKeyUsage> {
+        let table = self.inner.table.read();
+        let mut usage = match api_key {
+            Some(api_key) => {
+                table.get(&Some(api_key.to_string())).map(UsageEntry::usage).into_iter().collect()
+            }
+            None => table.values().map(UsageEntry::usage).collect::<Vec<_>>(),
+        };
+        usage.sort_unstable_by(|a, b| a.api_key.cmp(&b.api_key));
+        usage
+    }
+
+    /// Removes the usage of the API key, or of all API keys if `None`, and returns it.
+    pub fn reset(&self, api_key: Option<&str>) -> Vec<ApiKeyUsage> {
+        let mut table = self.inner.table.write();
+        let mut usage = match api_key {
+            Some(api_key) => table
+                .remove(&Some(api_key.to_string()))
+                .map(|entry| entry.usage())
+                .into_iter()
+                .collect(),
+            None => table.drain().map(|(_, entry)| entry.usage()).collect::<Vec<_>>(),
+        };
+        usage.sort_unstable_by(|a, b| a.api_key.cmp(&b.api_key));
+        usage
+    }
+
+    /// Writes the usage table to its file, if it has one.
+    pub fn persist(&self) -> io::Result<()> {
+        let Some(file) = &self.inner.file else { return Ok(()) };
+        let json = serde_json::to_vec_pretty(&self.usage(None))?;
+        let tmp = file.with_extension("tmp");
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&tmp, json)?;
+        fs::rename(&tmp, file)?;
+        debug!(target: "rpc::usage", ?file, "Wrote RPC usage to file");
+        Ok(())
+    }
+
+    /// Writes the usage table to its file every `every`, and once more when `shutdown` resolves.
+    ///
+    /// If `every` is zero, the usage table is only written when `shutdown` resolves. Returns the
+    /// output of `shutdown` after the usage table was written.
+    pub async fn persist_periodically<F: Future>(self, every: Duration, shutdown: F) -> F::Output {
+        let persist = || {
+            if let Err(err) = self.persist() {
+                warn!(target: "rpc::usage", %err, "Failed to write RPC usage to file");
+            }
+        };
+
+        let output = if every.is_zero() {
+            shutdown.await
+        } else {
+            let mut interval = tokio::time::interval_at(Instant::now() + every, every);
+            let mut shutdown = std::pin::pin!(shutdown);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => persist(),
+                    output = &mut shutdown => break output,
+                }
+            }
+        };
+        persist();
+        output
+    }
+}
+
+#[derive(Debug)]
+struct RpcUsageInner {
+    /// The compute units of the methods that don't have the default compute units.
+    compute_units: HashMap<String, u64>,
+    /// The file the table is persisted to.
+    file: Option<PathBuf>,
+    /// The usage by API key.
+    table: RwLock<HashMap<Option<String>, UsageEntry>>,
+}
+
+/// The usage of an API key and its metrics.
+#[derive(Debug)]
+struct UsageEntry {
+    api_key: Option<String>,
+    since: u64,
+    /// The number of requests by method.
+    methods: RwLock<HashMap<String, AtomicU64>>,
+    compute_units: AtomicU64,
+    egress_bytes: AtomicU64,
+    metrics: RpcUsageMetrics,
+}
+
+impl UsageEntry {
+    fn new(usage: ApiKeyUsage) -> Self {
+        let label = usage.api_key.clone().unwrap_or_else(|| "none".to_string());
+        Self {
+            api_key: usage.api_key,
+            since: usage.since,
+            methods: RwLock::new(
+                usage
+                    .methods
+                    .into_iter()
+                    .map(|(method, requests)| (method, AtomicU64::new(requests)))
+                    .collect(),
+            ),
+            compute_units: AtomicU64::new(usage.compute_units),
+            egress_bytes: AtomicU64::new(usage.egress_bytes),
+            metrics: RpcUsageMetrics::new_with_labels(&[("api_key", label)]),
+        }
+    }
+
+    /// Accounts a request to the method.
+    fn record(&self, method: &str, compute_units: u64, egress_bytes: u64) {
+        let methods = self.methods.read();
+        match methods.get(method) {
+            Some(requests) => {
+                requests.fetch_add(1, Ordering::Relaxed);
+            }
+            None => {
+                drop(methods);
+                self.methods
+                    .write()
+                    .entry(method.to_string())
+                    .or_default()
+                    .fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.compute_units.fetch_add(compute_units, Ordering::Relaxed);
+        self.egress_bytes.fetch_add(egress_bytes, Ordering::Relaxed);
+
+        self.metrics.requests.increment(1);
+        self.metrics.compute_units.increment(compute_units);
+        self.metrics.egress_bytes.increment(egress_bytes);
+    }
+
+    /// Returns a snapshot of the usage.
+    fn usage(&self) -> ApiKeyUsage {
+        ApiKeyUsage {
+            api_key: self.api_key.clone(),
+            since: self.since,
+            methods: self
+                .methods
+                .read()
+                .iter()
+                .map(|(method, requests)| (method.clone(), requests.load(Ordering::Relaxed)))
+                .collect(),
+            compute_units: self.compute_units.load(Ordering::Relaxed),
+            egress_bytes: self.egress_bytes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// The usage of an API key since the node started.
+#[derive(Metrics)]
+#[metrics(scope = "rpc.usage")]
+struct RpcUsageMetrics {
+    /// The number of requests.
+    requests: Counter,
+    /// The compute units of the requests.
+    compute_units: Counter,
+    /// The size of the responses in bytes.
+    egress_bytes: Counter,
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+/// `admin_rpcUsage` API implementation.
+///
+/// This type provides the functionality for querying the [`RpcUsage`] table.
+#[derive(Debug, Clone)]
+pub struct RpcUsageApi {
+    usage: RpcUsage,
+}
+
+impl RpcUsageApi {
+    /// Creates a new instance of `RpcUsageApi`.
+    pub const fn new(usage: RpcUsage) -> Self {
+        Self { usage }
+    }
+}
+
+impl RpcUsageApiServer for RpcUsageApi {
+    /// Handler for `admin_rpcUsage`
+    fn rpc_usage(&self, api_key: Option<String>) -> RpcResult<Vec<ApiKeyUsage>> {
+        Ok(self.usage.usage(api_key.as_deref()))
+    }
+
+    /// Handler for `admin_resetRpcUsage`
+    fn reset_rpc_usage(&self, api_key: Option<String>) -> RpcResult<Vec<ApiKeyUsage>> {
+        Ok(self.usage.reset(api_key.as_deref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_usage() {
+        let usage = RpcUsage::new([("eth_call".to_string(), 40)], None).unwrap();
+        assert_eq!(usage.compute_units("eth_call"), 40);
+        assert_eq!(usage.compute_units("eth_chainId"), 1);
+        assert_eq!(usage.compute_units("eth_getBalance"), DEFAULT_COMPUTE_UNITS);
+
+        usage.record(Some("a"), "eth_call", 100);
+        usage.record(Some("a"), "eth_call", 50);
+        usage.record(Some("a"), "eth_chainId", 10);
+        usage.record(None, "eth_getBalance", 70);
+
+        let a = usage.usage(Some("a"));
+        assert_eq!(a.len(), 1);
+        assert_eq!(a[0].methods["eth_call"], 2);
+        assert_eq!(a[0].requests(), 3);
+        assert_eq!(a[0].compute_units, 81);
+        assert_eq!(a[0].egress_bytes, 160);
+
+        let all = usage.usage(None);
+        assert_eq!(
+            all.iter().map(|usage| usage.api_key.as_deref()).collect::<Vec<_>>(),
+            [None, Some("a")]
+        );
+        assert!(usage.usage(Some("b")).is_empty());
+
+        assert_eq!(usage.reset(Some("a")), a);
+        assert_eq!(usage.usage(None).len(), 1);
+    }
+
+    #[test]
+    fn record_usage_concurrently() {
+        let usage = RpcUsage::new([], None).unwrap();
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let usage = &usage;
+                scope.spawn(move || {
+                    for i in 0..1000 {
+                        let api_key = (i % 2 == 0).then_some("a");
+                        let method = if thread % 2 == 0 { "eth_call" } else { "eth_chainId" };
+                        usage.record(api_key, method, 1);
+                    }
+                });
+            }
+        });
+
+        let a = &usage.usage(Some("a"))[0];
+        assert_eq!(a.requests(), 2000);
+        assert_eq!(a.methods["eth_call"], 1000);
+        assert_eq!(a.methods["eth_chainId"], 1000);
+        assert_eq!(a.egress_bytes, 2000);
+        assert_eq!(usage.usage(None).iter().map(ApiKeyUsage::requests).sum::<u64>(), 4000);
+    }
+
+    #[test]
+    fn persist_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("rpc-usage.json");
+
+        let usage = RpcUsage::new([], Some(file.clone())).unwrap();
+        usage.record(Some("a"), "eth_call", 100);
+        usage.persist().unwrap();
+
+        let restored = RpcUsage::new([], Some(file)).unwrap();
+        assert_eq!(restored.usage(None), usage.usage(None));
+        restored.record(Some("a"), "eth_call", 100);
+        assert_eq!(restored.usage(Some("a"))[0].methods["eth_call"], 2);
+    }
+}
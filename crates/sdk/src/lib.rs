@@ -0,0 +1,112 @@
+//! Stable API for building nodes with reth.
+//!
+//! The reth crates are split by component and their APIs change frequently as the node evolves.
+//! This crate re-exports the subset of those APIs that is needed to configure, extend and launch
+//! a node, and to read the chain from its database, so node distributions built on top of reth
+//! only need to depend on a single crate whose API is stable.
+//!
+//! ## Stability
+//!
+//! Every item is re-exported explicitly, and the re-exported items follow semantic versioning:
+//! removing an item, or changing it in a way that breaks code using it, only happens in a new
+//! major version. Items that are reachable through a re-exported item, but are not re-exported
+//! here themselves, are not covered and may change in any release.
+//!
+//! Items are added here once their API settled, use the component crates directly for everything
+//! else.
+//!
+//! ## Feature Flags
+//!
+//! - `ethereum` (default): Re-exports the Ethereum node, see [`ethereum`].
+//!
+//! ## Example
+//!
+//! Launch an ephemeral Ethereum node and wait for it to exit:
+//!
+//! ```no_run
+//! use reth_sdk::{
+//!     ethereum::EthereumNode,
+//!     node::{NodeBuilder, NodeConfig},
+//!     tasks::TaskManager,
+//! };
+//!
+//! # async fn launch() -> eyre::Result<()> {
+//! let tasks = TaskManager::current();
+//! let handle = NodeBuilder::new(NodeConfig::test())
+//!     .testing_node(tasks.executor())
+//!     .node(EthereumNode::default())
+//!     .launch()
+//!     .await?;
+//!
+//! handle.wait_for_node_exit().await
+//! # }
+//! ```
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+/// Configuring, extending and launching a node.
+pub mod node {
+    pub use reth_node_builder::{
+        BuilderContext, FullNode, FullNodeComponents, FullNodeTypes, Node, NodeBuilder, NodeConfig,
+        NodeHandle, NodeTypes, WithLaunchContext,
+    };
+
+    /// Builders for the components of a node.
+    pub mod components {
+        pub use reth_node_builder::components::{
+            ComponentsBuilder, ConsensusBuilder, ExecutorBuilder, NetworkBuilder,
+            PayloadServiceBuilder, PoolBuilder,
+        };
+    }
+
+    /// Extending the RPC server of a node.
+    pub mod rpc {
+        pub use reth_node_builder::rpc::{RethRpcServerHandles, RpcContext};
+    }
+}
+
+/// Reading the chain and state from the database of a node.
+pub mod provider {
+    pub use reth_provider::{
+        providers::BlockchainProvider, AccountReader, BlockHashReader, BlockNumReader, BlockReader,
+        BlockReaderIdExt, CanonStateNotification, CanonStateNotifications, CanonStateSubscriptions,
+        ChainSpecProvider, HeaderProvider, ProviderError, ProviderFactory, ProviderResult,
+        ReceiptProvider, StateProvider, StateProviderBox, StateProviderFactory,
+        TransactionsProvider,
+    };
+}
+
+/// Chain specifications and hardforks.
+pub mod chainspec {
+    pub use reth_chainspec::{
+        Chain, ChainSpec, ChainSpecBuilder, ForkCondition, Hardfork, NamedChain, DEV, HOLESKY,
+        MAINNET, SEPOLIA,
+    };
+}
+
+/// Commonly used types of the chain.
+pub mod primitives {
+    pub use reth_primitives::{
+        Account, Address, Block, BlockBody, BlockHash, BlockHashOrNumber, BlockNumber,
+        BlockWithSenders, Bytecode, Bytes, ChainId, Genesis, Header, Log, Receipt, SealedBlock,
+        SealedBlockWithSenders, SealedHeader, StorageKey, StorageValue, Transaction,
+        TransactionSigned, TransactionSignedEcRecovered, TxHash, TxKind, Withdrawal, B256, U256,
+    };
+}
+
+/// Spawning and managing the tasks of a node.
+pub mod tasks {
+    pub use reth_tasks::{TaskExecutor, TaskManager, TaskSpawner};
+}
+
+/// The Ethereum node.
+#[cfg(feature = "ethereum")]
+pub mod ethereum {
+    pub use reth_node_ethereum::{EthEngineTypes, EthEvmConfig, EthereumNode};
+}
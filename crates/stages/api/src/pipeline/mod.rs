@@ -22,6 +22,7 @@ use tracing::*;
 mod builder;
 mod progress;
 mod set;
+mod telemetry;
 
 use crate::{
     BlockErrorKind, ExecInput, ExecOutput, MetricEvent, MetricEventsSender, PipelineError, Stage,
@@ -31,6 +32,7 @@ pub use builder::*;
 use progress::*;
 use reth_errors::RethResult;
 pub use set::*;
+use telemetry::StageRunTimer;
 
 /// A container for a queued stage.
 pub(crate) type BoxedStage<DB> = Box<dyn Stage<DB>>;
@@ -264,6 +266,7 @@ where
                 headers: stages_checkpoints[0],
                 receipts: stages_checkpoints[1],
                 transactions: stages_checkpoints[2],
+                access_lists: None,
             })?;
             static_file_producer.run(targets)?;
             stages_checkpoints.into_iter().min().expect("exists")
@@ -325,6 +328,7 @@ where
                 let input = UnwindInput { checkpoint, unwind_to: to, bad_block };
                 self.event_sender.notify(PipelineEvent::Unwind { stage_id, input });
 
+                let timer = StageRunTimer::start();
                 let output = stage.unwind(&provider_rw, input);
                 match output {
                     Ok(unwind_output) => {
@@ -347,18 +351,33 @@ where
                             });
                         }
                         provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+                        provider_rw.save_stage_run(timer.finish(
+                            stage_id,
+                            true,
+                            input.checkpoint,
+                            checkpoint,
+                        ))?;
 
                         self.event_sender
                             .notify(PipelineEvent::Unwound { stage_id, result: unwind_output });
 
-                        // update finalized block if needed
-                        let last_saved_finalized_block_number =
-                            provider_rw.last_finalized_block_number()?;
-                        if checkpoint.block_number < last_saved_finalized_block_number {
+                        // update finalized and safe blocks if needed
+                        if provider_rw
+                            .last_finalized_block_number()?
+                            .is_some_and(|finalized| checkpoint.block_number < finalized)
+                        {
                             provider_rw.save_finalized_block_number(BlockNumber::from(
                                 checkpoint.block_number,
                             ))?;
                         }
+                        if provider_rw
+                            .last_safe_block_number()?
+                            .is_some_and(|safe| checkpoint.block_number < safe)
+                        {
+                            provider_rw.save_safe_block_number(BlockNumber::from(
+                                checkpoint.block_number,
+                            ))?;
+                        }
 
                         // For unwinding it makes more sense to commit the database first, since if
                         // this function is interrupted before the static files commit, we can just
@@ -418,6 +437,7 @@ where
             }
 
             let exec_input = ExecInput { target, checkpoint: prev_checkpoint };
+            let timer = StageRunTimer::start();
 
             self.event_sender.notify(PipelineEvent::Prepare {
                 pipeline_stages_progress: PipelineStagesProgress {
@@ -462,6 +482,12 @@ where
                         });
                     }
                     provider_rw.save_stage_checkpoint(stage_id, checkpoint)?;
+                    provider_rw.save_stage_run(timer.finish(
+                        stage_id,
+                        false,
+                        prev_checkpoint.unwrap_or_default(),
+                        checkpoint,
+                    ))?;
 
                     self.event_sender.notify(PipelineEvent::Ran {
                         pipeline_stages_progress: PipelineStagesProgress {
@@ -608,6 +634,8 @@ mod tests {
     use crate::{test_utils::TestStage, UnwindOutput};
     use assert_matches::assert_matches;
     use reth_consensus::ConsensusError;
+    use reth_db::tables;
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
     use reth_errors::ProviderError;
     use reth_provider::test_utils::create_test_provider_factory;
     use reth_prune::PruneModes;
@@ -707,6 +735,25 @@ mod tests {
                 },
             ]
         );
+
+        // Check that the runs of the stages were saved
+        let runs = provider_factory
+            .provider()
+            .unwrap()
+            .tx_ref()
+            .cursor_read::<tables::StageRuns>()
+            .unwrap()
+            .walk(None)
+            .unwrap()
+            .map(|entry| {
+                let (number, run) = entry.unwrap();
+                (number, run.stage, run.unwind, run.from_block, run.to_block)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            runs,
+            vec![(0, "A".to_string(), false, 0, 20), (1, "B".to_string(), false, 0, 10)]
+        );
     }
 
     /// Unwinds a simple pipeline.
@@ -0,0 +1,59 @@
+use crate::{StageCheckpoint, StageId, StageRun};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+/// Measures a run of a stage, to save it as a [`StageRun`].
+#[derive(Debug)]
+pub(crate) struct StageRunTimer {
+    started_at: SystemTime,
+    start: Instant,
+    /// The bytes the node read and wrote from storage when the run started.
+    io: Option<(u64, u64)>,
+}
+
+impl StageRunTimer {
+    /// Starts measuring a run.
+    pub(crate) fn start() -> Self {
+        Self { started_at: SystemTime::now(), start: Instant::now(), io: io_counters() }
+    }
+
+    /// Finishes the run of the stage from the `from` checkpoint to the `to` checkpoint.
+    pub(crate) fn finish(
+        self,
+        stage_id: StageId,
+        unwind: bool,
+        from: StageCheckpoint,
+        to: StageCheckpoint,
+    ) -> StageRun {
+        let io = self.io.zip(io_counters());
+        let processed_before = from.entities().map_or(0, |entities| entities.processed);
+        StageRun {
+            stage: stage_id.to_string(),
+            unwind,
+            from_block: from.block_number,
+            to_block: to.block_number,
+            started_at: self
+                .started_at
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |started_at| started_at.as_millis() as u64),
+            duration_ms: self.start.elapsed().as_millis() as u64,
+            entities_processed: to
+                .entities()
+                .map(|entities| entities.processed.abs_diff(processed_before)),
+            read_bytes: io.map(|((read, _), (read_after, _))| read_after.saturating_sub(read)),
+            write_bytes: io.map(|((_, write), (_, write_after))| write_after.saturating_sub(write)),
+        }
+    }
+}
+
+/// Returns the number of bytes the node has read and written from storage, if available.
+#[cfg(target_os = "linux")]
+fn io_counters() -> Option<(u64, u64)> {
+    let io = procfs::process::Process::myself().and_then(|process| process.io()).ok()?;
+    Some((io.read_bytes, io.write_bytes))
+}
+
+/// Returns the number of bytes the node has read and written from storage, if available.
+#[cfg(not(target_os = "linux"))]
+const fn io_counters() -> Option<(u64, u64)> {
+    None
+}
@@ -300,6 +300,7 @@ where
 
         // prepare execution output for writing
         let time = Instant::now();
+        let access_lists = executor.take_access_lists();
         let ExecutionOutcome { bundle, receipts, requests, first_block } = executor.finalize();
         let state = ExecutionOutcome::new(bundle, receipts, first_block, requests);
         let write_preparation_duration = time.elapsed();
@@ -335,12 +336,16 @@ where
         }
 
         let time = Instant::now();
+        // index the logs before the output is consumed by writing it
+        provider.insert_log_index(state.first_block(), state.receipts())?;
         // write output
         state.write_to_storage(
             provider.tx_ref(),
             static_file_producer,
             OriginalValuesKnown::Yes,
         )?;
+        provider.append_access_lists(start_block, access_lists)?;
+        provider.insert_selector_index(start_block..=stage_progress)?;
         let db_write_duration = time.elapsed();
         debug!(
             target: "sync::stages::execution",
@@ -385,6 +390,10 @@ where
             })
         }
 
+        // Unwind the log index of the block range while the receipts still exist, if it's
+        // enabled.
+        provider.unwind_log_index(range.clone())?;
+
         // Unwind account and storage changesets, as well as receipts.
         //
         // This also updates `PlainStorageState` and `PlainAccountState`.
@@ -425,6 +434,12 @@ where
             // `unwind_or_peek_state`.
         }
 
+        // Unwind the access lists of the block range, if the executor collected them.
+        provider.unwind_access_lists(unwind_to)?;
+
+        // Unwind the selector index of the block range, if it's enabled.
+        provider.unwind_selector_index(range.clone())?;
+
         // Update the checkpoint.
         let mut stage_checkpoint = input.checkpoint.execution_stage_checkpoint();
         if let Some(stage_checkpoint) = stage_checkpoint.as_mut() {
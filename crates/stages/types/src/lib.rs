@@ -21,6 +21,9 @@ pub use checkpoints::{
     StageUnitCheckpoint, StorageHashingCheckpoint,
 };
 
+mod run;
+pub use run::{StageRun, MAX_STAGE_RUNS};
+
 /// Direction and target block for pipeline operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PipelineTarget {
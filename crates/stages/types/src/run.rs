@@ -0,0 +1,124 @@
+use alloy_primitives::BlockNumber;
+use bytes::Buf;
+use reth_codecs::Compact;
+use serde::{Deserialize, Serialize};
+
+/// The number of most recent stage runs that are kept in the database, older runs are removed.
+pub const MAX_STAGE_RUNS: u64 = 10_000;
+
+/// A single run of a stage by the pipeline, i.e. the execution or unwind of a block range that was
+/// committed to the database.
+///
+/// The runs are kept as sync telemetry, to find out after the fact which stages slowed the sync
+/// down.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageRun {
+    /// The ID of the stage.
+    pub stage: String,
+    /// Whether the stage was unwound, rather than executed.
+    pub unwind: bool,
+    /// The block number of the stage checkpoint before the run.
+    pub from_block: BlockNumber,
+    /// The block number of the stage checkpoint after the run.
+    pub to_block: BlockNumber,
+    /// The unix timestamp in milliseconds when the run started.
+    pub started_at: u64,
+    /// The duration of the run in milliseconds, including the time the stage waited to be ready,
+    /// e.g. for downloads.
+    pub duration_ms: u64,
+    /// The number of entities the run processed, if the stage reports entities.
+    pub entities_processed: Option<u64>,
+    /// The number of bytes the node read from storage during the run, if available.
+    pub read_bytes: Option<u64>,
+    /// The number of bytes the node wrote to storage during the run, if available.
+    pub write_bytes: Option<u64>,
+}
+
+impl StageRun {
+    /// The length of the encoding of all fields except the stage ID.
+    const FIXED_LEN: usize = 1 + 4 * 8 + 3 * 9;
+
+    /// Returns the number of blocks the run executed or unwound.
+    pub const fn blocks(&self) -> u64 {
+        self.to_block.abs_diff(self.from_block)
+    }
+}
+
+impl Compact for StageRun {
+    fn to_compact<B>(self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_u8(self.unwind as u8);
+        for value in [self.from_block, self.to_block, self.started_at, self.duration_ms] {
+            buf.put_u64(value);
+        }
+        for value in [self.entities_processed, self.read_bytes, self.write_bytes] {
+            buf.put_u8(value.is_some() as u8);
+            buf.put_u64(value.unwrap_or_default());
+        }
+
+        // the stage ID takes the rest of the value
+        buf.put_slice(self.stage.as_bytes());
+        Self::FIXED_LEN + self.stage.len()
+    }
+
+    fn from_compact(mut buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let unwind = buf.get_u8() != 0;
+        let from_block = buf.get_u64();
+        let to_block = buf.get_u64();
+        let started_at = buf.get_u64();
+        let duration_ms = buf.get_u64();
+
+        let mut optional = || {
+            let is_some = buf.get_u8() != 0;
+            let value = buf.get_u64();
+            is_some.then_some(value)
+        };
+        let entities_processed = optional();
+        let read_bytes = optional();
+        let write_bytes = optional();
+
+        let (stage, buf) = buf.split_at(len.saturating_sub(Self::FIXED_LEN));
+        let run = Self {
+            stage: String::from_utf8_lossy(stage).into_owned(),
+            unwind,
+            from_block,
+            to_block,
+            started_at,
+            duration_ms,
+            entities_processed,
+            read_bytes,
+            write_bytes,
+        };
+        (run, buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_run_roundtrip() {
+        let run = StageRun {
+            stage: "Execution".to_string(),
+            unwind: false,
+            from_block: 100,
+            to_block: 600,
+            started_at: 1_700_000_000_000,
+            duration_ms: 12_345,
+            entities_processed: Some(1_000_000),
+            read_bytes: None,
+            write_bytes: Some(4096),
+        };
+
+        let mut buf = Vec::new();
+        let encoded = run.clone().to_compact(&mut buf);
+        assert_eq!(encoded, buf.len());
+        let (decoded, rest) = StageRun::from_compact(&buf, encoded);
+        assert_eq!(decoded, run);
+        assert!(rest.is_empty());
+        assert_eq!(decoded.blocks(), 500);
+    }
+}
@@ -63,7 +63,9 @@ pub(crate) fn prepare_jar<DB: Database, const COLUMNS: usize>(
 ) -> ProviderResult<NippyJar<SegmentHeader>> {
     let tx_range = match segment {
         StaticFileSegment::Headers => None,
-        StaticFileSegment::Receipts | StaticFileSegment::Transactions => {
+        StaticFileSegment::Receipts |
+        StaticFileSegment::Transactions |
+        StaticFileSegment::AccessLists => {
             Some(provider.transaction_range_by_block_range(block_range.clone())?.into())
         }
     };
@@ -32,6 +32,9 @@ pub struct HighestStaticFiles {
     /// Highest static file block of transactions, inclusive.
     /// If [`None`], no static file is available.
     pub transactions: Option<BlockNumber>,
+    /// Highest static file block of access lists, inclusive.
+    /// If [`None`], no static file is available.
+    pub access_lists: Option<BlockNumber>,
 }
 
 impl HighestStaticFiles {
@@ -41,6 +44,7 @@ impl HighestStaticFiles {
             StaticFileSegment::Headers => self.headers,
             StaticFileSegment::Transactions => self.transactions,
             StaticFileSegment::Receipts => self.receipts,
+            StaticFileSegment::AccessLists => self.access_lists,
         }
     }
 
@@ -50,12 +54,16 @@ impl HighestStaticFiles {
             StaticFileSegment::Headers => &mut self.headers,
             StaticFileSegment::Transactions => &mut self.transactions,
             StaticFileSegment::Receipts => &mut self.receipts,
+            StaticFileSegment::AccessLists => &mut self.access_lists,
         }
     }
 
     /// Returns the maximum block of all segments.
     pub fn max(&self) -> Option<u64> {
-        [self.headers, self.transactions, self.receipts].iter().filter_map(|&option| option).max()
+        [self.headers, self.transactions, self.receipts, self.access_lists]
+            .iter()
+            .filter_map(|&option| option)
+            .max()
     }
 }
 
@@ -7,7 +7,7 @@ use crate::{
 use reth_codecs::{main_codec, Compact};
 use reth_primitives::{Address, B256, *};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
-use reth_stages_types::StageCheckpoint;
+use reth_stages_types::{StageCheckpoint, StageRun};
 use reth_trie_common::{StoredNibbles, StoredNibblesSubKey, *};
 
 pub mod accounts;
@@ -77,6 +77,20 @@ impl Decode for Address {
     }
 }
 
+impl Encode for Selector {
+    type Encoded = [u8; 4];
+
+    fn encode(self) -> Self::Encoded {
+        self.0
+    }
+}
+
+impl Decode for Selector {
+    fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, DatabaseError> {
+        Ok(Self::new(value.as_ref().try_into().map_err(|_| DatabaseError::Decode)?))
+    }
+}
+
 impl Encode for B256 {
     type Encoded = [u8; 32];
 
@@ -219,9 +233,11 @@ impl_compression_for_compact!(
     TransactionSignedNoHash,
     CompactU256,
     StageCheckpoint,
+    StageRun,
     PruneCheckpoint,
     ClientVersion,
     Requests,
+    AccessList,
     // Non-DB
     GenesisAccount
 );
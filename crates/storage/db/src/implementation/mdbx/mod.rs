@@ -39,6 +39,9 @@ const TERABYTE: usize = GIGABYTE * 1024;
 /// MDBX allows up to 32767 readers (`MDBX_READERS_LIMIT`), but we limit it to slightly below that
 const DEFAULT_MAX_READERS: u64 = 32_000;
 
+/// Default step in which the database grows.
+const DEFAULT_GROWTH_STEP: usize = 4 * GIGABYTE;
+
 /// Space that a read-only transaction can occupy until the warning is emitted.
 /// See [`reth_libmdbx::EnvironmentBuilder::set_handle_slow_readers`] for more information.
 const MAX_SAFE_READER_SPACE: usize = 10 * GIGABYTE;
@@ -89,6 +92,17 @@ pub struct DatabaseArguments {
     ///
     /// This flag affects only at environment opening but can't be changed after.
     exclusive: Option<bool>,
+    /// Step in which the database file grows, in bytes. If [None], the default value is used.
+    growth_step: Option<usize>,
+    /// Threshold of unused space at the end of the database file after which it is shrunk, in
+    /// bytes. If [None], the database never shrinks.
+    shrink_threshold: Option<usize>,
+    /// Maximum number of concurrent readers. If [None], the default value is used.
+    max_readers: Option<u64>,
+    /// Database page size, in bytes. If [None], the OS page size is used.
+    ///
+    /// The page size is only applied when the database is created and can't be changed after.
+    page_size: Option<usize>,
 }
 
 impl DatabaseArguments {
@@ -99,6 +113,10 @@ impl DatabaseArguments {
             log_level: None,
             max_read_transaction_duration: None,
             exclusive: None,
+            growth_step: None,
+            shrink_threshold: None,
+            max_readers: None,
+            page_size: None,
         }
     }
 
@@ -123,6 +141,32 @@ impl DatabaseArguments {
         self
     }
 
+    /// Set the database growth step, in bytes.
+    pub const fn with_growth_step(mut self, growth_step: Option<usize>) -> Self {
+        self.growth_step = growth_step;
+        self
+    }
+
+    /// Set the database shrink threshold, in bytes.
+    pub const fn with_shrink_threshold(mut self, shrink_threshold: Option<usize>) -> Self {
+        self.shrink_threshold = shrink_threshold;
+        self
+    }
+
+    /// Set the maximum number of concurrent readers.
+    pub const fn with_max_readers(mut self, max_readers: Option<u64>) -> Self {
+        self.max_readers = max_readers;
+        self
+    }
+
+    /// Set the database page size, in bytes.
+    ///
+    /// Only takes effect when the database is created.
+    pub const fn with_page_size(mut self, page_size: Option<usize>) -> Self {
+        self.page_size = page_size;
+        self
+    }
+
     /// Returns the client version if any.
     pub const fn client_version(&self) -> &ClientVersion {
         &self.client_version
@@ -137,7 +181,7 @@ pub struct DatabaseEnv {
     /// Cache for metric handles. If `None`, metrics are not recorded.
     metrics: Option<Arc<DatabaseEnvMetrics>>,
     /// Write lock for when dealing with a read-write environment.
-    _lock_file: Option<StorageLock>,
+    lock_file: Option<StorageLock>,
 }
 
 impl Database for DatabaseEnv {
@@ -255,7 +299,7 @@ impl DatabaseEnv {
         kind: DatabaseEnvKind,
         args: DatabaseArguments,
     ) -> Result<Self, DatabaseError> {
-        let _lock_file = if kind.is_rw() {
+        let lock_file = if kind.is_rw() {
             Some(
                 StorageLock::try_acquire(path)
                     .map_err(|err| DatabaseError::Other(err.to_string()))?,
@@ -282,11 +326,11 @@ impl DatabaseEnv {
         inner_env.set_geometry(Geometry {
             // Maximum database size of 4 terabytes
             size: Some(0..(4 * TERABYTE)),
-            // We grow the database in increments of 4 gigabytes
-            growth_step: Some(4 * GIGABYTE as isize),
-            // The database never shrinks
-            shrink_threshold: Some(0),
-            page_size: Some(PageSize::Set(default_page_size())),
+            // We grow the database in increments of 4 gigabytes by default
+            growth_step: Some(args.growth_step.unwrap_or(DEFAULT_GROWTH_STEP) as isize),
+            // The database never shrinks by default
+            shrink_threshold: Some(args.shrink_threshold.unwrap_or_default() as isize),
+            page_size: Some(PageSize::Set(args.page_size.unwrap_or_else(default_page_size))),
         });
 
         fn is_current_process(id: u32) -> bool {
@@ -344,7 +388,7 @@ impl DatabaseEnv {
             ..Default::default()
         });
         // Configure more readers
-        inner_env.set_max_readers(DEFAULT_MAX_READERS);
+        inner_env.set_max_readers(args.max_readers.unwrap_or(DEFAULT_MAX_READERS));
         // This parameter sets the maximum size of the "reclaimed list", and the unit of measurement
         // is "pages". Reclaimed list is the list of freed pages that's populated during the
         // lifetime of DB transaction, and through which MDBX searches when it needs to insert new
@@ -405,7 +449,7 @@ impl DatabaseEnv {
         let env = Self {
             inner: inner_env.open(path).map_err(|e| DatabaseError::Open(e.into()))?,
             metrics: None,
-            _lock_file,
+            lock_file,
         };
 
         Ok(env)
@@ -417,6 +461,13 @@ impl DatabaseEnv {
         self
     }
 
+    /// Returns the storage lock of a read-write environment.
+    ///
+    /// The lock is only released once the environment and all returned locks are dropped.
+    pub fn storage_lock(&self) -> Option<StorageLock> {
+        self.lock_file.clone()
+    }
+
     /// Creates all the defined tables, if necessary.
     pub fn create_tables(&self) -> Result<(), DatabaseError> {
         let tx = self.inner.begin_rw_txn().map_err(|e| DatabaseError::InitTx(e.into()))?;
@@ -520,6 +571,21 @@ mod tests {
         create_test_db(DatabaseEnvKind::RW);
     }
 
+    #[test]
+    #[cfg(not(feature = "disable-lock"))]
+    fn storage_lock_outlives_env() {
+        let dir = TempDir::new().expect(ERROR_TEMPDIR);
+        let env = create_test_db_with_path(DatabaseEnvKind::RW, dir.path());
+        let lock = env.storage_lock();
+        assert!(lock.is_some());
+
+        drop(env);
+        assert!(dir.path().join("lock").exists());
+
+        drop(lock);
+        assert!(!dir.path().join("lock").exists());
+    }
+
     #[test]
     fn db_manual_put_get() {
         let env = create_test_db(DatabaseEnvKind::RW);
@@ -1,11 +1,11 @@
-use super::{ReceiptMask, TransactionMask};
+use super::{AccessListMask, ReceiptMask, TransactionMask};
 use crate::{
     add_static_file_mask,
     static_file::mask::{ColumnSelectorOne, ColumnSelectorTwo, HeaderMask},
     HeaderTerminalDifficulties, RawValue, Receipts, Transactions,
 };
 use reth_db_api::table::Table;
-use reth_primitives::{BlockHash, Header};
+use reth_primitives::{AccessList, BlockHash, Header};
 
 // HEADER MASKS
 add_static_file_mask!(HeaderMask, Header, 0b001);
@@ -20,3 +20,6 @@ add_static_file_mask!(ReceiptMask, <Receipts as Table>::Value, 0b1);
 // TRANSACTION MASKS
 add_static_file_mask!(TransactionMask, <Transactions as Table>::Value, 0b1);
 add_static_file_mask!(TransactionMask, RawValue<<Transactions as Table>::Value>, 0b1);
+
+// ACCESS LIST MASKS
+add_static_file_mask!(AccessListMask, AccessList, 0b1);
@@ -32,12 +32,12 @@ use reth_db_api::{
     table::{Decode, DupSort, Encode, Table},
 };
 use reth_primitives::{
-    Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt, Requests, StorageEntry,
-    TransactionSignedNoHash, TxHash, TxNumber, B256,
+    Account, Address, BlockHash, BlockNumber, Bytecode, Header, Receipt, Requests, Selector,
+    StorageEntry, TransactionSignedNoHash, TxHash, TxNumber, B256,
 };
 use reth_primitives_traits::IntegerList;
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
-use reth_stages_types::StageCheckpoint;
+use reth_stages_types::{StageCheckpoint, StageRun};
 use reth_trie_common::{StorageTrieEntry, StoredBranchNode, StoredNibbles, StoredNibblesSubKey};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -400,6 +400,12 @@ tables! {
     /// Stores arbitrary data to keep track of a stage first-sync progress.
     table StageCheckpointProgresses<Key = StageId, Value = Vec<u8>>;
 
+    /// Stores the runs of the stages by the pipeline, keyed by a sequential run number.
+    ///
+    /// The runs are only kept as sync telemetry, see `reth stage history`. Only the most recent
+    /// `MAX_STAGE_RUNS` runs are kept.
+    table StageRuns<Key = u64, Value = StageRun>;
+
     /// Stores the highest pruned block number and prune mode of each prune segment.
     table PruneCheckpoints<Key = PruneSegment, Value = PruneCheckpoint>;
 
@@ -409,8 +415,33 @@ tables! {
     /// Stores EIP-7685 EL -> CL requests, indexed by block number.
     table BlockRequests<Key = BlockNumber, Value = Requests>;
 
-    /// Stores generic chain state info, like the last finalized block.
+    /// Stores generic chain state info, like the last finalized and safe blocks.
     table ChainState<Key = ChainStateKey, Value = BlockNumber>;
+
+    /// Stores the checkpoint of each execution extension, i.e. the highest block it has
+    /// processed and persisted its state for, keyed by its ID.
+    table ExExCheckpoints<Key = ExExId, Value = BlockNumber>;
+
+    /// Stores the numbers of the transactions whose input starts with a function selector, i.e.
+    /// the calls to that function, sharded like [`AccountsHistory`].
+    ///
+    /// The `highest_block_number` of a shard key is the highest transaction number in the shard.
+    /// The table is only maintained if the selector index is enabled on the provider factory.
+    table TransactionSelectors<Key = ShardedKey<Selector>, Value = BlockNumberList>;
+
+    /// Stores the numbers of the blocks that contain logs emitted by an address, sharded like
+    /// [`AccountsHistory`].
+    ///
+    /// The table is only maintained if the log index is enabled on the provider factory, the
+    /// covered blocks are stored in [`ChainState`].
+    table LogAddressIndex<Key = ShardedKey<Address>, Value = BlockNumberList>;
+
+    /// Stores the numbers of the blocks that contain logs with a topic at any position, sharded
+    /// like [`AccountsHistory`].
+    ///
+    /// The table is only maintained if the log index is enabled on the provider factory, the
+    /// covered blocks are stored in [`ChainState`].
+    table LogTopicIndex<Key = ShardedKey<B256>, Value = BlockNumberList>;
 }
 
 /// Keys for the `ChainState` table.
@@ -418,6 +449,12 @@ tables! {
 pub enum ChainStateKey {
     /// Last finalized block key
     LastFinalizedBlock,
+    /// Last safe block key
+    LastSafeBlock,
+    /// Lowest block covered by the log index key
+    LogIndexLowestBlock,
+    /// Highest block covered by the log index key
+    LogIndexHighestBlock,
 }
 
 impl Encode for ChainStateKey {
@@ -426,16 +463,21 @@ impl Encode for ChainStateKey {
     fn encode(self) -> Self::Encoded {
         match self {
             Self::LastFinalizedBlock => [0],
+            Self::LastSafeBlock => [1],
+            Self::LogIndexLowestBlock => [2],
+            Self::LogIndexHighestBlock => [3],
         }
     }
 }
 
 impl Decode for ChainStateKey {
     fn decode<B: AsRef<[u8]>>(value: B) -> Result<Self, reth_db_api::DatabaseError> {
-        if value.as_ref() == [0] {
-            Ok(Self::LastFinalizedBlock)
-        } else {
-            Err(reth_db_api::DatabaseError::Decode)
+        match value.as_ref() {
+            [0] => Ok(Self::LastFinalizedBlock),
+            [1] => Ok(Self::LastSafeBlock),
+            [2] => Ok(Self::LogIndexLowestBlock),
+            [3] => Ok(Self::LogIndexHighestBlock),
+            _ => Err(reth_db_api::DatabaseError::Decode),
         }
     }
 }
@@ -448,6 +490,9 @@ pub type BlockNumberList = IntegerList;
 /// Encoded stage id.
 pub type StageId = String;
 
+/// Encoded execution extension id.
+pub type ExExId = String;
+
 #[cfg(test)]
 mod tests {
     use super::*;
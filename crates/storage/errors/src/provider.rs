@@ -142,6 +142,9 @@ pub enum ProviderError {
     /// Storage lock error.
     #[error(transparent)]
     StorageLockError(#[from] crate::lockfile::StorageLockError),
+    /// Error when reading state from the state fallback, e.g. a remote node.
+    #[error("failed to read state from fallback: {0}")]
+    StateFallback(String),
 }
 
 impl From<reth_fs_util::FsPathError> for ProviderError {
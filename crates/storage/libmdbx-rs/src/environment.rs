@@ -223,6 +223,23 @@ impl Environment {
 
         Ok(freelist)
     }
+
+    /// Copies the environment into a new database file at the given path.
+    ///
+    /// The file must not exist yet, but its parent directory must be writable. If `compact` is
+    /// set, free pages are omitted and all pages are renumbered sequentially, so that the copy
+    /// has an empty freelist.
+    ///
+    /// Note: the copy is made in a read transaction, which is kept open until the copy completes.
+    pub fn copy(&self, dest: &Path, compact: bool) -> Result<()> {
+        let dest = dest.to_str().and_then(|dest| CString::new(dest).ok()).ok_or(Error::Invalid)?;
+        let mut flags = ffi::MDBX_CP_FORCE_DYNAMIC_SIZE;
+        if compact {
+            flags |= ffi::MDBX_CP_COMPACT;
+        }
+        mdbx_result(unsafe { ffi::mdbx_env_copy(self.env_ptr(), dest.as_ptr(), flags) })?;
+        Ok(())
+    }
 }
 
 /// Container type for Environment internals.
@@ -154,3 +154,39 @@ fn test_freelist() {
     freelist = env.freelist().unwrap();
     assert!(freelist > 0);
 }
+
+#[test]
+fn test_copy_compact() {
+    let dir = tempdir().unwrap();
+    let env = Environment::builder().open(dir.path()).unwrap();
+
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in 0..1024 {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        tx.put(db.dbi(), value, value, WriteFlags::default()).expect("tx.put");
+    }
+    tx.commit().expect("tx.commit");
+
+    // Delete every other value, which leaves pages on the freelist.
+    let tx = env.begin_rw_txn().expect("begin_rw_txn");
+    let db = tx.open_db(None).unwrap();
+    for i in (0..1024).step_by(2) {
+        let mut value = [0u8; 8];
+        LittleEndian::write_u64(&mut value, i);
+        tx.del(db.dbi(), value, None).expect("tx.del");
+    }
+    tx.commit().expect("tx.commit");
+
+    let copy_dir = tempdir().unwrap();
+    env.copy(&copy_dir.path().join("mdbx.dat"), true).unwrap();
+
+    let copy = Environment::builder().open(copy_dir.path()).unwrap();
+    assert_eq!(copy.freelist().unwrap(), 0);
+    assert!(copy.info().unwrap().last_pgno() <= env.info().unwrap().last_pgno());
+
+    let tx = copy.begin_ro_txn().unwrap();
+    let db = tx.open_db(None).unwrap();
+    assert_eq!(tx.db_stat(&db).unwrap().entries(), 512);
+}
@@ -1,31 +1,42 @@
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
-use reth_primitives::{BlockNumHash, BlockNumber, SealedHeader};
+use reth_primitives::{BlockNumHash, BlockNumber, SealedHeader, B256};
 use std::{
+    hint,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{fence, AtomicU64, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::sync::watch;
 
 /// Tracks the chain info: canonical head, safe block, finalized block.
+///
+/// The number and hash of the canonical head and the timestamps of the engine API calls are read
+/// without taking any locks, because RPC and network status handlers poll them far more often than
+/// the engine updates them.
 #[derive(Debug, Clone)]
 pub(crate) struct ChainInfoTracker {
     inner: Arc<ChainInfoInner>,
 }
 
 impl ChainInfoTracker {
-    /// Create a new chain info container for the given canonical head.
-    pub(crate) fn new(head: SealedHeader) -> Self {
-        let (finalized_block, _) = watch::channel(None);
-        let (safe_block, _) = watch::channel(None);
+    /// Create a new chain info container for the given canonical head and the finalized and safe
+    /// blocks, if known.
+    pub(crate) fn new(
+        head: SealedHeader,
+        finalized: Option<SealedHeader>,
+        safe: Option<SealedHeader>,
+    ) -> Self {
+        let (finalized_block, _) = watch::channel(finalized);
+        let (safe_block, _) = watch::channel(safe);
         Self {
             inner: Arc::new(ChainInfoInner {
-                last_forkchoice_update: RwLock::new(None),
-                last_transition_configuration_exchange: RwLock::new(None),
-                canonical_head_number: AtomicU64::new(head.number),
+                created_at: Instant::now(),
+                last_forkchoice_update: AtomicInstant::default(),
+                last_transition_configuration_exchange: AtomicInstant::default(),
+                canonical_head_num_hash: VersionedNumHash::new(head.num_hash()),
                 canonical_head: RwLock::new(head),
                 safe_block,
                 finalized_block,
@@ -35,28 +46,28 @@ impl ChainInfoTracker {
 
     /// Returns the [`ChainInfo`] for the canonical head.
     pub(crate) fn chain_info(&self) -> ChainInfo {
-        let inner = self.inner.canonical_head.read();
-        ChainInfo { best_hash: inner.hash(), best_number: inner.number }
+        let BlockNumHash { number, hash } = self.inner.canonical_head_num_hash.load();
+        ChainInfo { best_hash: hash, best_number: number }
     }
 
     /// Update the timestamp when we received a forkchoice update.
     pub(crate) fn on_forkchoice_update_received(&self) {
-        self.inner.last_forkchoice_update.write().replace(Instant::now());
+        self.inner.last_forkchoice_update.store_now(self.inner.created_at);
     }
 
     /// Returns the instant when we received the latest forkchoice update.
     pub(crate) fn last_forkchoice_update_received_at(&self) -> Option<Instant> {
-        *self.inner.last_forkchoice_update.read()
+        self.inner.last_forkchoice_update.load(self.inner.created_at)
     }
 
     /// Update the timestamp when we exchanged a transition configuration.
     pub(crate) fn on_transition_configuration_exchanged(&self) {
-        self.inner.last_transition_configuration_exchange.write().replace(Instant::now());
+        self.inner.last_transition_configuration_exchange.store_now(self.inner.created_at);
     }
 
     /// Returns the instant when we exchanged the transition configuration last time.
     pub(crate) fn last_transition_configuration_exchanged_at(&self) -> Option<Instant> {
-        *self.inner.last_transition_configuration_exchange.read()
+        self.inner.last_transition_configuration_exchange.load(self.inner.created_at)
     }
 
     /// Returns the canonical head of the chain.
@@ -77,12 +88,12 @@ impl ChainInfoTracker {
     /// Returns the canonical head of the chain.
     #[allow(dead_code)]
     pub(crate) fn get_canonical_num_hash(&self) -> BlockNumHash {
-        self.inner.canonical_head.read().num_hash()
+        self.inner.canonical_head_num_hash.load()
     }
 
     /// Returns the canonical head of the chain.
     pub(crate) fn get_canonical_block_number(&self) -> BlockNumber {
-        self.inner.canonical_head_number.load(Ordering::Relaxed)
+        self.inner.canonical_head_num_hash.number()
     }
 
     /// Returns the safe header of the chain.
@@ -101,11 +112,10 @@ impl ChainInfoTracker {
 
     /// Sets the canonical head of the chain.
     pub(crate) fn set_canonical_head(&self, header: SealedHeader) {
-        let number = header.number;
-        *self.inner.canonical_head.write() = header;
-
-        // also update the atomic number.
-        self.inner.canonical_head_number.store(number, Ordering::Relaxed);
+        let mut head = self.inner.canonical_head.write();
+        // the write lock serializes the writers of the versioned number and hash
+        self.inner.canonical_head_num_hash.store(header.num_hash());
+        *head = header;
     }
 
     /// Sets the safe header of the chain.
@@ -126,16 +136,18 @@ impl ChainInfoTracker {
 /// Container type for all chain info fields
 #[derive(Debug)]
 struct ChainInfoInner {
+    /// The instant the tracker was created, which the timestamps are relative to.
+    created_at: Instant,
     /// Timestamp when we received the last fork choice update.
     ///
     /// This is mainly used to track if we're connected to a beacon node.
-    last_forkchoice_update: RwLock<Option<Instant>>,
+    last_forkchoice_update: AtomicInstant,
     /// Timestamp when we exchanged the transition configuration last time.
     ///
     /// This is mainly used to track if we're connected to a beacon node.
-    last_transition_configuration_exchange: RwLock<Option<Instant>>,
-    /// Tracks the number of the `canonical_head`.
-    canonical_head_number: AtomicU64,
+    last_transition_configuration_exchange: AtomicInstant,
+    /// Tracks the number and hash of the `canonical_head`.
+    canonical_head_num_hash: VersionedNumHash,
     /// The canonical head of the chain.
     canonical_head: RwLock<SealedHeader>,
     /// The block that the beacon node considers safe.
@@ -143,3 +155,140 @@ struct ChainInfoInner {
     /// The block that the beacon node considers finalized.
     finalized_block: watch::Sender<Option<SealedHeader>>,
 }
+
+/// A block number and hash that is read without locks, like a seqlock.
+///
+/// The version is odd while the block is updated. Readers retry until they read the same even
+/// version before and after reading the block, so they never see a torn number and hash. Writers
+/// must not update the block concurrently.
+#[derive(Debug)]
+struct VersionedNumHash {
+    version: AtomicU64,
+    number: AtomicU64,
+    hash: [AtomicU64; 4],
+}
+
+impl VersionedNumHash {
+    fn new(num_hash: BlockNumHash) -> Self {
+        let words = hash_to_words(num_hash.hash);
+        Self {
+            version: AtomicU64::new(0),
+            number: AtomicU64::new(num_hash.number),
+            hash: words.map(AtomicU64::new),
+        }
+    }
+
+    /// Returns the block number, without waiting for an update to finish.
+    fn number(&self) -> BlockNumber {
+        self.number.load(Ordering::Relaxed)
+    }
+
+    /// Returns the block number and hash.
+    fn load(&self) -> BlockNumHash {
+        loop {
+            let version = self.version.load(Ordering::Acquire);
+            if version % 2 == 0 {
+                let number = self.number.load(Ordering::Relaxed);
+                let words = [0, 1, 2, 3].map(|i| self.hash[i].load(Ordering::Relaxed));
+                fence(Ordering::Acquire);
+                if self.version.load(Ordering::Relaxed) == version {
+                    return BlockNumHash::new(number, words_to_hash(words))
+                }
+            }
+            hint::spin_loop();
+        }
+    }
+
+    /// Updates the block number and hash.
+    ///
+    /// This must not be called concurrently.
+    fn store(&self, num_hash: BlockNumHash) {
+        let version = self.version.load(Ordering::Relaxed);
+        self.version.store(version.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        self.number.store(num_hash.number, Ordering::Relaxed);
+        for (word, value) in self.hash.iter().zip(hash_to_words(num_hash.hash)) {
+            word.store(value, Ordering::Relaxed);
+        }
+
+        self.version.store(version.wrapping_add(2), Ordering::Release);
+    }
+}
+
+fn hash_to_words(hash: B256) -> [u64; 4] {
+    [0, 1, 2, 3].map(|i| u64::from_ne_bytes(hash[i * 8..(i + 1) * 8].try_into().unwrap()))
+}
+
+fn words_to_hash(words: [u64; 4]) -> B256 {
+    let mut hash = B256::ZERO;
+    for (chunk, word) in hash.chunks_exact_mut(8).zip(words) {
+        chunk.copy_from_slice(&word.to_ne_bytes());
+    }
+    hash
+}
+
+/// An optional [`Instant`] that is read and updated without locks.
+///
+/// The instant is stored as the nanoseconds since the creation of the tracker, plus one so zero
+/// means no instant.
+#[derive(Debug, Default)]
+struct AtomicInstant(AtomicU64);
+
+impl AtomicInstant {
+    /// Returns the stored instant, relative to the given base.
+    fn load(&self, base: Instant) -> Option<Instant> {
+        match self.0.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(base + Duration::from_nanos(nanos - 1)),
+        }
+    }
+
+    /// Stores the current instant, relative to the given base.
+    fn store_now(&self, base: Instant) {
+        let nanos = base.elapsed().as_nanos().min(u64::MAX as u128 - 1) as u64;
+        self.0.store(nanos + 1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Header, U256};
+
+    fn header(number: BlockNumber) -> SealedHeader {
+        SealedHeader::new(Header { number, ..Default::default() }, B256::from(U256::from(number)))
+    }
+
+    #[test]
+    fn chain_info_is_never_torn() {
+        let tracker = ChainInfoTracker::new(header(0), None, None);
+        assert_eq!(tracker.last_forkchoice_update_received_at(), None);
+
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let mut last = 0;
+                    while last < 10_000 {
+                        let info = tracker.chain_info();
+                        assert_eq!(info.best_hash, B256::from(U256::from(info.best_number)));
+                        assert!(info.best_number >= last);
+                        last = info.best_number;
+                    }
+                });
+            }
+            for number in 1..=10_000 {
+                tracker.set_canonical_head(header(number));
+            }
+        });
+
+        assert_eq!(tracker.get_canonical_num_hash(), header(10_000).num_hash());
+        assert_eq!(tracker.get_canonical_block_number(), 10_000);
+
+        let before = Instant::now();
+        tracker.on_forkchoice_update_received();
+        let received_at = tracker.last_forkchoice_update_received_at().unwrap();
+        assert!(received_at + Duration::from_millis(1) >= before);
+        assert!(received_at <= Instant::now());
+    }
+}
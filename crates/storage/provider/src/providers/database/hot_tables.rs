@@ -0,0 +1,197 @@
+use parking_lot::RwLock;
+use reth_metrics::{metrics::Counter, Metrics};
+use reth_primitives::{BlockNumber, B256};
+use reth_stages_types::{StageCheckpoint, StageId};
+use reth_storage_errors::provider::ProviderResult;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// The maximum number of canonical hashes kept in the [`HotTablesCache`].
+const MAX_CANONICAL_HASHES: usize = 256;
+
+/// An in-memory copy of small tables that are read on almost every request, so that these reads
+/// don't need to open a database transaction.
+///
+/// The copy contains the stage checkpoints, the last finalized and safe block numbers, and the
+/// canonical hashes of the most recently requested blocks. Entries are loaded from the database on
+/// their first read, and all entries are dropped whenever a database transaction created by the
+/// [`ProviderFactory`](crate::ProviderFactory) is committed, so that reads never return values that
+/// are older than the last commit.
+///
+/// Writes that bypass the commit of the [`DatabaseProvider`](crate::DatabaseProvider), e.g. by
+/// committing its inner transaction directly, aren't reflected until the next commit.
+#[derive(Debug, Clone, Default)]
+pub struct HotTablesCache {
+    inner: Arc<RwLock<HotTables>>,
+    metrics: HotTablesCacheMetrics,
+}
+
+impl HotTablesCache {
+    /// Returns the cached checkpoint of the stage, loading it with the closure if it's missing.
+    pub(crate) fn stage_checkpoint(
+        &self,
+        id: StageId,
+        load: impl FnOnce() -> ProviderResult<Option<StageCheckpoint>>,
+    ) -> ProviderResult<Option<StageCheckpoint>> {
+        self.get_or_load(
+            |tables| tables.stage_checkpoints.get(&id).copied(),
+            |tables, checkpoint| {
+                tables.stage_checkpoints.insert(id, checkpoint);
+            },
+            load,
+        )
+    }
+
+    /// Returns the cached last finalized block number, loading it with the closure if it's
+    /// missing.
+    pub(crate) fn last_finalized_block_number(
+        &self,
+        load: impl FnOnce() -> ProviderResult<Option<BlockNumber>>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        self.get_or_load(
+            |tables| tables.last_finalized_block,
+            |tables, block| tables.last_finalized_block = Some(block),
+            load,
+        )
+    }
+
+    /// Returns the cached last safe block number, loading it with the closure if it's missing.
+    pub(crate) fn last_safe_block_number(
+        &self,
+        load: impl FnOnce() -> ProviderResult<Option<BlockNumber>>,
+    ) -> ProviderResult<Option<BlockNumber>> {
+        self.get_or_load(
+            |tables| tables.last_safe_block,
+            |tables, block| tables.last_safe_block = Some(block),
+            load,
+        )
+    }
+
+    /// Returns the cached canonical hash of the block, loading it with the closure if it's
+    /// missing.
+    ///
+    /// Blocks that don't exist yet aren't cached.
+    pub(crate) fn block_hash(
+        &self,
+        number: BlockNumber,
+        load: impl FnOnce() -> ProviderResult<Option<B256>>,
+    ) -> ProviderResult<Option<B256>> {
+        self.get_or_load(
+            |tables| tables.canonical_hashes.get(&number).copied().map(Some),
+            |tables, hash| {
+                let Some(hash) = hash else { return };
+                tables.canonical_hashes.insert(number, hash);
+                if tables.canonical_hashes.len() > MAX_CANONICAL_HASHES {
+                    tables.canonical_hashes.pop_first();
+                }
+            },
+            load,
+        )
+    }
+
+    /// Drops all entries, called after a database transaction was committed.
+    pub(crate) fn invalidate(&self) {
+        let mut tables = self.inner.write();
+        tables.generation += 1;
+        tables.stage_checkpoints.clear();
+        tables.last_finalized_block = None;
+        tables.last_safe_block = None;
+        tables.canonical_hashes.clear();
+        self.metrics.invalidations.increment(1);
+    }
+
+    /// Returns the cached value, or loads it and caches it if there was no commit in the meantime.
+    fn get_or_load<T: Clone>(
+        &self,
+        get: impl FnOnce(&HotTables) -> Option<T>,
+        insert: impl FnOnce(&mut HotTables, T),
+        load: impl FnOnce() -> ProviderResult<T>,
+    ) -> ProviderResult<T> {
+        let generation = {
+            let tables = self.inner.read();
+            if let Some(value) = get(&tables) {
+                self.metrics.hits.increment(1);
+                return Ok(value)
+            }
+            tables.generation
+        };
+        self.metrics.misses.increment(1);
+
+        let value = load()?;
+
+        // the value may have been read before a commit that invalidated the cache
+        let mut tables = self.inner.write();
+        if tables.generation == generation {
+            insert(&mut tables, value.clone());
+        }
+        Ok(value)
+    }
+}
+
+/// The cached entries of the [`HotTablesCache`].
+#[derive(Debug, Default)]
+struct HotTables {
+    /// Incremented whenever the entries are dropped.
+    generation: u64,
+    stage_checkpoints: HashMap<StageId, Option<StageCheckpoint>>,
+    last_finalized_block: Option<Option<BlockNumber>>,
+    last_safe_block: Option<Option<BlockNumber>>,
+    canonical_hashes: BTreeMap<BlockNumber, B256>,
+}
+
+/// Metrics for the [`HotTablesCache`].
+#[derive(Metrics, Clone)]
+#[metrics(scope = "database.hot_tables")]
+struct HotTablesCacheMetrics {
+    /// The number of reads served from the cache.
+    hits: Counter,
+    /// The number of reads that had to be loaded from the database.
+    misses: Counter,
+    /// The number of times the cache was invalidated by a commit.
+    invalidations: Counter,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_storage_errors::provider::ProviderError;
+
+    #[test]
+    fn invalidate_drops_entries() {
+        let cache = HotTablesCache::default();
+        let checkpoint = Some(StageCheckpoint::new(10));
+
+        assert_eq!(cache.stage_checkpoint(StageId::Finish, || Ok(checkpoint)), Ok(checkpoint));
+        // served from the cache
+        assert_eq!(
+            cache.stage_checkpoint(StageId::Finish, || Err(ProviderError::UnsupportedProvider)),
+            Ok(checkpoint)
+        );
+
+        // missing blocks aren't cached
+        assert_eq!(cache.block_hash(1, || Ok(None)), Ok(None));
+        assert_eq!(cache.block_hash(1, || Ok(Some(B256::ZERO))), Ok(Some(B256::ZERO)));
+
+        cache.invalidate();
+        assert_eq!(cache.stage_checkpoint(StageId::Finish, || Ok(None)), Ok(None));
+        let hash = B256::with_last_byte(1);
+        assert_eq!(cache.block_hash(1, || Ok(Some(hash))), Ok(Some(hash)));
+    }
+
+    #[test]
+    fn stale_loads_are_not_cached() {
+        let cache = HotTablesCache::default();
+
+        // a commit happens while the value is loaded
+        assert_eq!(
+            cache.last_finalized_block_number(|| {
+                cache.invalidate();
+                Ok(Some(1))
+            }),
+            Ok(Some(1))
+        );
+        assert_eq!(cache.last_finalized_block_number(|| Ok(Some(2))), Ok(Some(2)));
+    }
+}
@@ -1,11 +1,15 @@
 use crate::{
-    providers::{state::latest::LatestStateProvider, StaticFileProvider},
+    providers::{
+        state::{fork::with_state_fallback, latest::LatestStateProvider},
+        StateFallback, StaticFileProvider,
+    },
     to_range,
     traits::{BlockSource, ReceiptProvider},
-    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
-    EvmEnvProvider, HeaderProvider, HeaderSyncGap, HeaderSyncGapProvider, ProviderError,
-    PruneCheckpointReader, RequestsProvider, StageCheckpointReader, StateProviderBox,
-    StaticFileProviderFactory, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
+    AccessListProvider, BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider,
+    DatabaseProviderFactory, EvmEnvProvider, FinalizedBlockReader, HeaderProvider, HeaderSyncGap,
+    HeaderSyncGapProvider, LogIndexProvider, ProviderError, PruneCheckpointReader,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StaticFileProviderFactory,
+    TransactionSelectorProvider, TransactionVariant, TransactionsProvider, WithdrawalsProvider,
 };
 use reth_chainspec::{ChainInfo, ChainSpec};
 use reth_db::{init_db, mdbx::DatabaseArguments, DatabaseEnv};
@@ -13,10 +17,10 @@ use reth_db_api::{database::Database, models::StoredBlockBodyIndices};
 use reth_errors::{RethError, RethResult};
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    AccessList, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, Selector,
+    StaticFileSegment, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash,
+    TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -30,10 +34,15 @@ use std::{
 use tokio::sync::watch;
 use tracing::trace;
 
+mod hot_tables;
 mod metrics;
 mod provider;
+mod snapshot;
+
+pub(crate) use hot_tables::HotTablesCache;
 
 pub use provider::{DatabaseProvider, DatabaseProviderRO, DatabaseProviderRW};
+pub use snapshot::{DatabaseSnapshot, SnapshotTableIter};
 
 /// A common provider that fetches data from a database or static file.
 ///
@@ -46,6 +55,14 @@ pub struct ProviderFactory<DB> {
     chain_spec: Arc<ChainSpec>,
     /// Static File Provider
     static_file_provider: StaticFileProvider,
+    /// In-memory copy of hot tables, if enabled
+    hot_tables_cache: Option<HotTablesCache>,
+    /// State that the state providers fall back to, if any
+    state_fallback: Option<Arc<dyn StateFallback>>,
+    /// Whether the transactions are indexed by the function selector they call
+    selector_index: bool,
+    /// Whether the blocks are indexed by the addresses and topics of their logs
+    log_index: bool,
 }
 
 impl<DB> ProviderFactory<DB> {
@@ -55,7 +72,15 @@ impl<DB> ProviderFactory<DB> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { db: Arc::new(db), chain_spec, static_file_provider }
+        Self {
+            db: Arc::new(db),
+            chain_spec,
+            static_file_provider,
+            hot_tables_cache: None,
+            state_fallback: None,
+            selector_index: false,
+            log_index: false,
+        }
     }
 
     /// Enables metrics on the static file provider.
@@ -64,6 +89,46 @@ impl<DB> ProviderFactory<DB> {
         self
     }
 
+    /// Keeps an in-memory copy of small tables that are read on almost every request: the stage
+    /// checkpoints, the last finalized and safe block numbers, and recent canonical hashes.
+    ///
+    /// Reads of these are served without opening a database transaction, and the copy is dropped
+    /// whenever a transaction created by this factory is committed.
+    pub fn with_hot_tables_cache(mut self) -> Self {
+        self.hot_tables_cache = Some(HotTablesCache::default());
+        self
+    }
+
+    /// Makes all state providers fall back to the given state for accounts, storage slots and
+    /// bytecode that were never changed locally.
+    ///
+    /// This is used to fork a remote network: the local chain starts from the remote state at the
+    /// fork block, which is fetched lazily instead of being imported.
+    pub fn with_state_fallback(mut self, fallback: Arc<dyn StateFallback>) -> Self {
+        self.state_fallback = Some(fallback);
+        self
+    }
+
+    /// Indexes the transactions by the function selector they call, i.e. the first 4 bytes of
+    /// their input, while blocks are executed.
+    ///
+    /// The index only covers the blocks that are executed after it was enabled, and can be queried
+    /// with [`TransactionSelectorProvider`].
+    pub const fn with_selector_index(mut self) -> Self {
+        self.selector_index = true;
+        self
+    }
+
+    /// Indexes the blocks by the addresses and topics of their logs while blocks are executed, to
+    /// skip blocks without matching logs when logs are filtered.
+    ///
+    /// The index only covers the blocks that are executed after it was enabled, unless it's built
+    /// with `reth db log-index`, and can be queried with [`LogIndexProvider`].
+    pub const fn with_log_index(mut self) -> Self {
+        self.log_index = true;
+        self
+    }
+
     /// Returns reference to the underlying database.
     pub fn db_ref(&self) -> &DB {
         &self.db
@@ -89,6 +154,10 @@ impl ProviderFactory<DatabaseEnv> {
             db: Arc::new(init_db(path, args).map_err(RethError::msg)?),
             chain_spec,
             static_file_provider,
+            hot_tables_cache: None,
+            state_fallback: None,
+            selector_index: false,
+            log_index: false,
         })
     }
 }
@@ -103,7 +172,10 @@ impl<DB: Database> ProviderFactory<DB> {
             self.db.tx()?,
             self.chain_spec.clone(),
             self.static_file_provider.clone(),
-        ))
+        )
+        .with_state_fallback(self.state_fallback.clone())
+        .with_selector_index(self.selector_index)
+        .with_log_index(self.log_index))
     }
 
     /// Returns a provider with a created `DbTxMut` inside, which allows fetching and updating
@@ -112,18 +184,32 @@ impl<DB: Database> ProviderFactory<DB> {
     /// open.
     #[track_caller]
     pub fn provider_rw(&self) -> ProviderResult<DatabaseProviderRW<DB>> {
-        Ok(DatabaseProviderRW(DatabaseProvider::new_rw(
-            self.db.tx_mut()?,
-            self.chain_spec.clone(),
-            self.static_file_provider.clone(),
-        )))
+        Ok(DatabaseProviderRW(
+            DatabaseProvider::new_rw(
+                self.db.tx_mut()?,
+                self.chain_spec.clone(),
+                self.static_file_provider.clone(),
+            )
+            .with_hot_tables_cache(self.hot_tables_cache.clone())
+            .with_state_fallback(self.state_fallback.clone())
+            .with_selector_index(self.selector_index)
+            .with_log_index(self.log_index),
+        ))
+    }
+
+    /// Returns a consistent read-only [`DatabaseSnapshot`] of the database, pinned at the current
+    /// state. See [`DatabaseSnapshot`] for the caveats of holding it for a long time.
+    #[track_caller]
+    pub fn snapshot(&self) -> ProviderResult<DatabaseSnapshot<DB>> {
+        DatabaseSnapshot::new(self.provider()?)
     }
 
     /// State provider for latest block
     #[track_caller]
     pub fn latest(&self) -> ProviderResult<StateProviderBox> {
         trace!(target: "providers::db", "Returning latest state provider");
-        Ok(Box::new(LatestStateProvider::new(self.db.tx()?, self.static_file_provider())))
+        let state_provider = LatestStateProvider::new(self.db.tx()?, self.static_file_provider());
+        Ok(with_state_fallback(state_provider, self.state_fallback.clone()))
     }
 
     /// Storage provider for state at that given block
@@ -249,12 +335,18 @@ impl<DB: Database> HeaderProvider for ProviderFactory<DB> {
 
 impl<DB: Database> BlockHashReader for ProviderFactory<DB> {
     fn block_hash(&self, number: u64) -> ProviderResult<Option<B256>> {
-        self.static_file_provider.get_with_static_file_or_database(
-            StaticFileSegment::Headers,
-            number,
-            |static_file| static_file.block_hash(number),
-            || self.provider()?.block_hash(number),
-        )
+        let load = || {
+            self.static_file_provider.get_with_static_file_or_database(
+                StaticFileSegment::Headers,
+                number,
+                |static_file| static_file.block_hash(number),
+                || self.provider()?.block_hash(number),
+            )
+        };
+        match &self.hot_tables_cache {
+            Some(cache) => cache.block_hash(number, load),
+            None => load(),
+        }
     }
 
     fn canonical_hashes_range(
@@ -278,6 +370,12 @@ impl<DB: Database> BlockNumReader for ProviderFactory<DB> {
     }
 
     fn best_block_number(&self) -> ProviderResult<BlockNumber> {
+        if self.hot_tables_cache.is_some() {
+            return Ok(self
+                .get_stage_checkpoint(StageId::Finish)?
+                .map(|checkpoint| checkpoint.block_number)
+                .unwrap_or_default())
+        }
         self.provider()?.best_block_number()
     }
 
@@ -455,6 +553,52 @@ impl<DB: Database> ReceiptProvider for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> AccessListProvider for ProviderFactory<DB> {
+    fn access_list(&self, id: TxNumber) -> ProviderResult<Option<AccessList>> {
+        self.static_file_provider.access_list(id)
+    }
+
+    fn access_lists_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<AccessList>>> {
+        self.provider()?.access_lists_by_block(block)
+    }
+
+    fn access_lists_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<AccessList>> {
+        self.static_file_provider.access_lists_by_tx_range(range)
+    }
+}
+
+impl<DB: Database> TransactionSelectorProvider for ProviderFactory<DB> {
+    fn transactions_by_selector(
+        &self,
+        selector: Selector,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.provider()?.transactions_by_selector(selector, range, limit)
+    }
+}
+
+impl<DB: Database> LogIndexProvider for ProviderFactory<DB> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.provider()?.log_index_range()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.provider()?.blocks_with_logs(addresses, topics, range)
+    }
+}
+
 impl<DB: Database> WithdrawalsProvider for ProviderFactory<DB> {
     fn withdrawals_by_block(
         &self,
@@ -484,7 +628,10 @@ where
 
 impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
-        self.provider()?.get_stage_checkpoint(id)
+        match &self.hot_tables_cache {
+            Some(cache) => cache.stage_checkpoint(id, || self.provider()?.get_stage_checkpoint(id)),
+            None => self.provider()?.get_stage_checkpoint(id),
+        }
     }
 
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
@@ -492,6 +639,26 @@ impl<DB: Database> StageCheckpointReader for ProviderFactory<DB> {
     }
 }
 
+impl<DB: Database> FinalizedBlockReader for ProviderFactory<DB> {
+    fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        match &self.hot_tables_cache {
+            Some(cache) => {
+                cache.last_finalized_block_number(|| self.provider()?.last_finalized_block_number())
+            }
+            None => self.provider()?.last_finalized_block_number(),
+        }
+    }
+
+    fn last_safe_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        match &self.hot_tables_cache {
+            Some(cache) => {
+                cache.last_safe_block_number(|| self.provider()?.last_safe_block_number())
+            }
+            None => self.provider()?.last_safe_block_number(),
+        }
+    }
+}
+
 impl<DB: Database> EvmEnvProvider for ProviderFactory<DB> {
     fn fill_env_at<EvmConfig>(
         &self,
@@ -584,6 +751,10 @@ impl<DB> Clone for ProviderFactory<DB> {
             db: Arc::clone(&self.db),
             chain_spec: self.chain_spec.clone(),
             static_file_provider: self.static_file_provider.clone(),
+            hot_tables_cache: self.hot_tables_cache.clone(),
+            state_fallback: self.state_fallback.clone(),
+            selector_index: self.selector_index,
+            log_index: self.log_index,
         }
     }
 }
@@ -593,7 +764,8 @@ mod tests {
     use crate::{
         providers::{StaticFileProvider, StaticFileWriter},
         test_utils::create_test_provider_factory,
-        BlockHashReader, BlockNumReader, BlockWriter, HeaderSyncGapProvider, TransactionsProvider,
+        BlockHashReader, BlockNumReader, BlockWriter, FinalizedBlockReader, FinalizedBlockWriter,
+        HeaderSyncGapProvider, StageCheckpointWriter, TransactionsProvider,
     };
     use alloy_rlp::Decodable;
     use assert_matches::assert_matches;
@@ -604,12 +776,17 @@ mod tests {
         tables,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_primitives::{hex_literal::hex, SealedBlock, StaticFileSegment, TxNumber, B256, U256};
+    use reth_db_api::{cursor::DbCursorRO, transaction::DbTx};
+    use reth_primitives::{
+        hex_literal::hex, Address, Bytes, Log, Receipts, SealedBlock, Selector, StaticFileSegment,
+        Transaction, TxKind, TxLegacy, TxNumber, B256, U256,
+    };
     use reth_prune_types::{PruneMode, PruneModes};
+    use reth_stages_types::{StageRun, MAX_STAGE_RUNS};
     use reth_storage_errors::provider::ProviderError;
     use reth_testing_utils::{
         generators,
-        generators::{random_block, random_header},
+        generators::{random_block, random_header, sign_tx_with_random_key_pair},
     };
     use std::{ops::RangeInclusive, sync::Arc};
     use tokio::sync::watch;
@@ -630,6 +807,40 @@ mod tests {
         assert_eq!(chain_info.best_hash, B256::ZERO);
     }
 
+    #[test]
+    fn finalized_and_safe_blocks() {
+        let factory = create_test_provider_factory();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_finalized_block_number().unwrap(), None);
+        assert_eq!(provider.last_safe_block_number().unwrap(), None);
+        drop(provider);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.save_finalized_block_number(10).unwrap();
+        provider_rw.save_safe_block_number(20).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+        assert_eq!(provider.last_finalized_block_number().unwrap(), Some(10));
+        assert_eq!(provider.last_safe_block_number().unwrap(), Some(20));
+    }
+
+    #[test]
+    fn prunes_stage_runs() {
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        for _ in 0..=MAX_STAGE_RUNS {
+            provider_rw.save_stage_run(StageRun::default()).unwrap();
+        }
+
+        // the first run was removed
+        let tx = provider_rw.tx_ref();
+        assert_eq!(tx.entries::<tables::StageRuns>().unwrap() as u64, MAX_STAGE_RUNS);
+        let first = tx.cursor_read::<tables::StageRuns>().unwrap().first().unwrap();
+        assert_eq!(first.map(|(number, _)| number), Some(1));
+    }
+
     #[test]
     fn provider_flow() {
         let factory = create_test_provider_factory();
@@ -739,6 +950,91 @@ mod tests {
         }
     }
 
+    #[test]
+    fn selector_index() {
+        let mut rng = generators::rng();
+        let selector = Selector::new([0xa9, 0x05, 0x9c, 0xbb]);
+        let mut block = random_block(&mut rng, 0, None, Some(0), None);
+        block.body = [
+            Bytes::copy_from_slice(selector.as_slice()),
+            // too short to contain a selector
+            Bytes::from_static(&[0xa9, 0x05]),
+            Bytes::from([selector.as_slice(), &[1; 32]].concat()),
+        ]
+        .into_iter()
+        .map(|input| {
+            let transaction = Transaction::Legacy(TxLegacy {
+                to: TxKind::Call(Address::ZERO),
+                input,
+                ..Default::default()
+            });
+            sign_tx_with_random_key_pair(&mut rng, transaction)
+        })
+        .collect();
+
+        let factory = create_test_provider_factory();
+        assert_eq!(
+            factory.transactions_by_selector(selector, 0..=2, 10),
+            Err(ProviderError::UnsupportedProvider)
+        );
+
+        let factory = factory.with_selector_index();
+        let provider = factory.provider_rw().unwrap();
+        provider.insert_block(block.try_seal_with_senders().unwrap(), None).unwrap();
+        provider.insert_selector_index(0..=0).unwrap();
+        assert_eq!(provider.transactions_by_selector(selector, 0..=2, 10), Ok(vec![0, 2]));
+        assert_eq!(provider.transactions_by_selector(selector, 1..=2, 10), Ok(vec![2]));
+        assert_eq!(provider.transactions_by_selector(selector, 0..=2, 1), Ok(vec![0]));
+
+        assert_eq!(provider.unwind_selector_index(0..=0), Ok(1));
+        assert_eq!(provider.transactions_by_selector(selector, 0..=2, 10), Ok(vec![]));
+    }
+
+    #[test]
+    fn log_index() {
+        let (a, b) = (Address::with_last_byte(1), Address::with_last_byte(2));
+        let (t1, t2) = (B256::with_last_byte(1), B256::with_last_byte(2));
+        let receipts = |logs: Vec<Vec<Log>>| -> Receipts {
+            logs.into_iter()
+                .map(|logs| vec![Some(Receipt { success: true, logs, ..Default::default() })])
+                .collect()
+        };
+        let log = |address, topics| Log::new_unchecked(address, topics, Bytes::new());
+
+        let factory = create_test_provider_factory();
+        assert_eq!(factory.log_index_range(), Ok(None));
+        assert_eq!(
+            factory.blocks_with_logs(&[a], &[], 1..=3),
+            Err(ProviderError::UnsupportedProvider)
+        );
+
+        let factory = factory.with_log_index();
+        let provider = factory.provider_rw().unwrap();
+        let logs = vec![vec![log(a, vec![t1])], vec![], vec![log(b, vec![t1, t2])]];
+        provider.insert_log_index(1, &receipts(logs)).unwrap();
+        assert_eq!(provider.log_index_range(), Ok(Some(1..=3)));
+        assert_eq!(provider.blocks_with_logs(&[a], &[], 1..=3), Ok(vec![1]));
+        assert_eq!(provider.blocks_with_logs(&[], &[vec![t1]], 1..=3), Ok(vec![1, 3]));
+        assert_eq!(provider.blocks_with_logs(&[], &[vec![t1]], 2..=3), Ok(vec![3]));
+        assert_eq!(provider.blocks_with_logs(&[a, b], &[vec![], vec![t2]], 1..=3), Ok(vec![3]));
+        assert_eq!(provider.blocks_with_logs(&[a], &[vec![t2]], 1..=3), Ok(vec![]));
+        assert_eq!(provider.blocks_with_logs(&[], &[], 2..=3), Ok(vec![2, 3]));
+
+        // blocks that don't extend the index are skipped
+        provider.insert_log_index(5, &receipts(vec![vec![log(a, vec![])]])).unwrap();
+        assert_eq!(provider.log_index_range(), Ok(Some(1..=3)));
+
+        // the entries of unwound blocks are replaced when the blocks are indexed again
+        provider.unwind_log_index(3..=3).unwrap();
+        assert_eq!(provider.log_index_range(), Ok(Some(1..=2)));
+        provider.insert_log_index(3, &receipts(vec![vec![log(a, vec![t2])]])).unwrap();
+        assert_eq!(provider.log_index_range(), Ok(Some(1..=3)));
+        assert_eq!(provider.blocks_with_logs(&[a], &[vec![t2]], 1..=3), Ok(vec![3]));
+
+        provider.unwind_log_index(1..=3).unwrap();
+        assert_eq!(provider.log_index_range(), Ok(None));
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
@@ -1,17 +1,23 @@
 use crate::{
     bundle_state::{BundleStateInit, HashedStateChanges, RevertsInit},
-    providers::{database::metrics, static_file::StaticFileWriter, StaticFileProvider},
+    providers::{
+        database::{metrics, HotTablesCache},
+        state::fork::with_state_fallback,
+        static_file::StaticFileWriter,
+        StateFallback, StaticFileProvider,
+    },
     to_range,
     traits::{
         AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
     },
-    AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader, BlockReader, BlockWriter,
-    EvmEnvProvider, FinalizedBlockReader, FinalizedBlockWriter, HashingWriter, HeaderProvider,
-    HeaderSyncGap, HeaderSyncGapProvider, HistoricalStateProvider, HistoryWriter,
-    LatestStateProvider, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
+    AccessListProvider, AccountReader, BlockExecutionWriter, BlockHashReader, BlockNumReader,
+    BlockReader, BlockWriter, EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter,
+    FinalizedBlockReader, FinalizedBlockWriter, HashingWriter, HeaderProvider, HeaderSyncGap,
+    HeaderSyncGapProvider, HistoricalStateProvider, HistoryWriter, LatestStateProvider,
+    LogIndexProvider, OriginalValuesKnown, ProviderError, PruneCheckpointReader,
     PruneCheckpointWriter, RequestsProvider, StageCheckpointReader, StateProviderBox, StateWriter,
-    StatsReader, StorageReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
-    WithdrawalsProvider,
+    StatsReader, StorageReader, TransactionSelectorProvider, TransactionVariant,
+    TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
 };
 use itertools::{izip, Itertools};
 use reth_chainspec::{ChainInfo, ChainSpec};
@@ -34,14 +40,14 @@ use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_primitives::{
     keccak256,
     revm::{config::revm_spec, env::fill_block_env},
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
-    GotExpected, Head, Header, Receipt, Requests, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, StaticFileSegment, StorageEntry, TransactionMeta, TransactionSigned,
-    TransactionSignedEcRecovered, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
-    Withdrawals, B256, U256,
+    AccessList, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber,
+    BlockWithSenders, GotExpected, Head, Header, Receipt, Receipts, Requests, SealedBlock,
+    SealedBlockWithSenders, SealedHeader, Selector, StaticFileSegment, StorageEntry, Transaction,
+    TransactionMeta, TransactionSigned, TransactionSignedEcRecovered, TransactionSignedNoHash,
+    TxHash, TxNumber, Withdrawal, Withdrawals, B256, U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneLimiter, PruneModes, PruneSegment};
-use reth_stages_types::{StageCheckpoint, StageId};
+use reth_stages_types::{StageCheckpoint, StageId, StageRun, MAX_STAGE_RUNS};
 use reth_storage_errors::provider::{ProviderResult, RootMismatch};
 use reth_trie::{
     prefix_set::{PrefixSet, PrefixSetMut, TriePrefixSets},
@@ -60,6 +66,9 @@ use std::{
 use tokio::sync::watch;
 use tracing::{debug, error, warn};
 
+/// The number of transactions that are read at once to build the selector index.
+const SELECTOR_INDEX_BATCH_SIZE: u64 = 10_000;
+
 /// A [`DatabaseProvider`] that holds a read-only database transaction.
 pub type DatabaseProviderRO<DB> = DatabaseProvider<<DB as Database>::TX>;
 
@@ -106,6 +115,14 @@ pub struct DatabaseProvider<TX> {
     chain_spec: Arc<ChainSpec>,
     /// Static File provider
     static_file_provider: StaticFileProvider,
+    /// Cache of hot tables that is invalidated when the transaction is committed, if any.
+    hot_tables_cache: Option<HotTablesCache>,
+    /// State that the state providers fall back to for state that was never changed locally.
+    state_fallback: Option<Arc<dyn StateFallback>>,
+    /// Whether the transactions are indexed by the function selector they call.
+    selector_index: bool,
+    /// Whether the blocks are indexed by the addresses and topics of their logs.
+    log_index: bool,
 }
 
 impl<TX> DatabaseProvider<TX> {
@@ -113,6 +130,25 @@ impl<TX> DatabaseProvider<TX> {
     pub const fn static_file_provider(&self) -> &StaticFileProvider {
         &self.static_file_provider
     }
+
+    /// Sets the state that the state providers fall back to for state that was never changed
+    /// locally.
+    pub(crate) fn with_state_fallback(mut self, fallback: Option<Arc<dyn StateFallback>>) -> Self {
+        self.state_fallback = fallback;
+        self
+    }
+
+    /// Sets whether the transactions are indexed by the function selector they call.
+    pub(crate) const fn with_selector_index(mut self, enabled: bool) -> Self {
+        self.selector_index = enabled;
+        self
+    }
+
+    /// Sets whether the blocks are indexed by the addresses and topics of their logs.
+    pub(crate) const fn with_log_index(mut self, enabled: bool) -> Self {
+        self.log_index = enabled;
+        self
+    }
 }
 
 impl<TX: DbTxMut> DatabaseProvider<TX> {
@@ -122,7 +158,21 @@ impl<TX: DbTxMut> DatabaseProvider<TX> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            hot_tables_cache: None,
+            state_fallback: None,
+            selector_index: false,
+            log_index: false,
+        }
+    }
+
+    /// Sets the cache of hot tables that is invalidated when the transaction is committed.
+    pub(crate) fn with_hot_tables_cache(mut self, cache: Option<HotTablesCache>) -> Self {
+        self.hot_tables_cache = cache;
+        self
     }
 }
 
@@ -135,7 +185,8 @@ impl<TX: DbTx + 'static> DatabaseProvider<TX> {
         if block_number == self.best_block_number().unwrap_or_default() &&
             block_number == self.last_block_number().unwrap_or_default()
         {
-            return Ok(Box::new(LatestStateProvider::new(self.tx, self.static_file_provider)))
+            let state_provider = LatestStateProvider::new(self.tx, self.static_file_provider);
+            return Ok(with_state_fallback(state_provider, self.state_fallback))
         }
 
         // +1 as the changeset that we want is the one that was applied after this block.
@@ -166,7 +217,7 @@ impl<TX: DbTx + 'static> DatabaseProvider<TX> {
             );
         }
 
-        Ok(Box::new(state_provider))
+        Ok(with_state_fallback(state_provider, self.state_fallback))
     }
 }
 
@@ -259,7 +310,15 @@ impl<TX: DbTx> DatabaseProvider<TX> {
         chain_spec: Arc<ChainSpec>,
         static_file_provider: StaticFileProvider,
     ) -> Self {
-        Self { tx, chain_spec, static_file_provider }
+        Self {
+            tx,
+            chain_spec,
+            static_file_provider,
+            hot_tables_cache: None,
+            state_fallback: None,
+            selector_index: false,
+            log_index: false,
+        }
     }
 
     /// Consume `DbTx` or `DbTxMut`.
@@ -516,12 +575,61 @@ impl<TX: DbTx> DatabaseProvider<TX> {
             assemble_block(header, body, ommers, withdrawals, requests, senders)
         })
     }
+
+    /// Returns the range of blocks covered by the log index, regardless of whether the log index
+    /// is enabled.
+    fn log_index_coverage(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        let lowest =
+            self.tx.get::<tables::ChainState>(tables::ChainStateKey::LogIndexLowestBlock)?;
+        let highest =
+            self.tx.get::<tables::ChainState>(tables::ChainStateKey::LogIndexHighestBlock)?;
+        Ok(lowest.zip(highest).map(|(lowest, highest)| lowest..=highest))
+    }
+
+    /// Returns the numbers of the blocks in the range that are stored for any of the keys in the
+    /// log index table.
+    fn log_index_blocks<K, T>(
+        &self,
+        keys: &[K],
+        range: &RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeSet<BlockNumber>>
+    where
+        K: Copy + PartialEq,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut blocks = BTreeSet::new();
+        let mut cursor = self.tx.cursor_read::<T>()?;
+        for &key in keys {
+            // Shards are keyed by their highest block number, so the first shard that can contain
+            // the start of the range is the first one with a key greater or equal to it.
+            for entry in cursor.walk(Some(ShardedKey::new(key, *range.start())))? {
+                let (sharded_key, list) = entry?;
+                if sharded_key.key != key {
+                    break
+                }
+
+                blocks.extend(
+                    list.iter()
+                        .skip_while(|block_number| block_number < range.start())
+                        .take_while(|block_number| block_number <= range.end()),
+                );
+                if sharded_key.highest_block_number >= *range.end() {
+                    break
+                }
+            }
+        }
+        Ok(blocks)
+    }
 }
 
 impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
     /// Commit database transaction.
     pub fn commit(self) -> ProviderResult<bool> {
-        Ok(self.tx.commit()?)
+        let committed = self.tx.commit()?;
+        if let Some(cache) = &self.hot_tables_cache {
+            cache.invalidate();
+        }
+        Ok(committed)
     }
 
     // TODO(joshie) TEMPORARY should be moved to trait providers
@@ -1203,6 +1311,335 @@ impl<TX: DbTxMut + DbTx> DatabaseProvider<TX> {
         }
         Ok(())
     }
+
+    /// Adds the transactions of the block range to the [`tables::TransactionSelectors`] index, if
+    /// the selector index is enabled.
+    ///
+    /// Must be called once for every block range that is executed and made canonical.
+    pub fn insert_selector_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        if !self.selector_index {
+            return Ok(())
+        }
+
+        let selectors = self.transaction_selectors_with_range(range)?;
+        self.append_history_index::<_, tables::TransactionSelectors>(selectors, ShardedKey::new)
+    }
+
+    /// Removes the transactions of the block range from the [`tables::TransactionSelectors`]
+    /// index, if the selector index is enabled.
+    ///
+    /// Must be called before the transactions of the block range are removed. Returns the number
+    /// of unwound selectors.
+    pub fn unwind_selector_index(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        if !self.selector_index {
+            return Ok(0)
+        }
+
+        let selectors = self.transaction_selectors_with_range(range)?;
+        let mut cursor = self.tx.cursor_write::<tables::TransactionSelectors>()?;
+        for (&selector, tx_numbers) in &selectors {
+            let partial_shard = unwind_history_shards::<_, tables::TransactionSelectors, _>(
+                &mut cursor,
+                ShardedKey::last(selector),
+                tx_numbers[0],
+                |sharded_key| sharded_key.key == selector,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(selector),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+
+        Ok(selectors.len())
+    }
+
+    /// Returns the numbers of the transactions of the block range, grouped by the function
+    /// selector they call.
+    ///
+    /// The transactions are read in batches, so that only the numbers are kept in memory.
+    fn transaction_selectors_with_range(
+        &self,
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<BTreeMap<Selector, Vec<TxNumber>>> {
+        let first_tx_num = self
+            .block_body_indices(*range.start())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.start()))?
+            .first_tx_num();
+        let next_tx_num = self
+            .block_body_indices(*range.end())?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(*range.end()))?
+            .next_tx_num();
+
+        let mut selectors = BTreeMap::<_, Vec<_>>::new();
+        let mut cursor = self.tx.cursor_read::<tables::Transactions>()?;
+        for start in (first_tx_num..next_tx_num).step_by(SELECTOR_INDEX_BATCH_SIZE as usize) {
+            let end = next_tx_num.min(start + SELECTOR_INDEX_BATCH_SIZE);
+            let transactions =
+                self.transactions_by_tx_range_with_cursor(start..end, &mut cursor)?;
+            for (tx_number, transaction) in (start..).zip(transactions) {
+                if let Some(selector) = call_selector(&transaction.transaction) {
+                    selectors.entry(selector).or_default().push(tx_number);
+                }
+            }
+        }
+        Ok(selectors)
+    }
+
+    /// Appends the access lists collected by the executor to the static files. The access lists are
+    /// grouped by block, starting at `first_block`.
+    ///
+    /// The access lists segment has to be contiguous, so the access lists are skipped if the
+    /// segment doesn't continue at `first_block`, e.g. if they weren't collected for the previous
+    /// blocks. The static files have to be committed separately.
+    pub fn append_access_lists(
+        &self,
+        first_block: BlockNumber,
+        access_lists: Vec<Vec<AccessList>>,
+    ) -> ProviderResult<()> {
+        if access_lists.is_empty() {
+            return Ok(())
+        }
+
+        let next_block = self
+            .static_file_provider
+            .get_highest_static_file_block(StaticFileSegment::AccessLists)
+            .map_or(0, |block| block + 1);
+
+        // The genesis block isn't executed, but it has no transactions, so the segment can start
+        // at the first executed block.
+        let from_genesis = next_block == 0 && first_block == 1;
+        if next_block != first_block && !from_genesis {
+            warn!(target: "providers::db", first_block, next_block, "Access lists don't continue the static files, skipping");
+            return Ok(())
+        }
+
+        let mut writer =
+            self.static_file_provider.get_writer(next_block, StaticFileSegment::AccessLists)?;
+        if from_genesis {
+            writer.increment_block(StaticFileSegment::AccessLists, 0)?;
+        }
+
+        for (block_number, access_lists) in (first_block..).zip(access_lists) {
+            let first_tx_num = self
+                .block_body_indices(block_number)?
+                .ok_or(ProviderError::BlockBodyIndicesNotFound(block_number))?
+                .first_tx_num();
+
+            writer.increment_block(StaticFileSegment::AccessLists, block_number)?;
+            writer.append_access_lists((first_tx_num..).zip(access_lists))?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes the access lists of the blocks after `unwind_to` from the static files.
+    ///
+    /// Must be called before the block body indices of the unwound blocks are removed. The static
+    /// files have to be committed separately.
+    pub fn unwind_access_lists(&self, unwind_to: BlockNumber) -> ProviderResult<()> {
+        let Some(highest_block) =
+            self.static_file_provider.get_highest_static_file_block(StaticFileSegment::AccessLists)
+        else {
+            return Ok(())
+        };
+        if highest_block <= unwind_to {
+            return Ok(())
+        }
+
+        let next_tx_num = self
+            .block_body_indices(unwind_to)?
+            .ok_or(ProviderError::BlockBodyIndicesNotFound(unwind_to))?
+            .next_tx_num();
+        let to_delete = self
+            .static_file_provider
+            .get_highest_static_file_tx(StaticFileSegment::AccessLists)
+            .map_or(0, |tx| tx + 1)
+            .saturating_sub(next_tx_num);
+
+        self.static_file_provider
+            .latest_writer(StaticFileSegment::AccessLists)?
+            .prune_access_lists(to_delete, unwind_to)?;
+
+        Ok(())
+    }
+
+    /// Adds the logs of the receipts to the [`tables::LogAddressIndex`] and
+    /// [`tables::LogTopicIndex`] indices, if the log index is enabled. The receipts are grouped by
+    /// block, starting at `first_block`.
+    ///
+    /// The blocks are only indexed if they extend the range of blocks covered by the index, or if
+    /// the index doesn't cover any blocks yet. Entries of blocks that were unwound while the index
+    /// was disabled are replaced.
+    pub fn insert_log_index(
+        &self,
+        first_block: BlockNumber,
+        receipts: &Receipts,
+    ) -> ProviderResult<()> {
+        if !self.log_index || receipts.is_empty() {
+            return Ok(())
+        }
+
+        let lowest_block = match self.log_index_coverage()? {
+            Some(range) if first_block < *range.start() || first_block > range.end() + 1 => {
+                debug!(target: "providers::db", ?range, first_block, "Blocks don't extend the log index, skipping");
+                return Ok(())
+            }
+            Some(range) => *range.start(),
+            None => first_block,
+        };
+        let highest_block = first_block + receipts.len() as u64 - 1;
+
+        let (addresses, topics) = log_index_updates(first_block, receipts);
+        self.unwind_log_index_shards::<_, tables::LogAddressIndex>(
+            addresses.keys().copied(),
+            first_block,
+        )?;
+        self.unwind_log_index_shards::<_, tables::LogTopicIndex>(
+            topics.keys().copied(),
+            first_block,
+        )?;
+        self.append_history_index::<_, tables::LogAddressIndex>(addresses, ShardedKey::new)?;
+        self.append_history_index::<_, tables::LogTopicIndex>(topics, ShardedKey::new)?;
+
+        self.tx
+            .put::<tables::ChainState>(tables::ChainStateKey::LogIndexLowestBlock, lowest_block)?;
+        self.tx.put::<tables::ChainState>(
+            tables::ChainStateKey::LogIndexHighestBlock,
+            highest_block,
+        )?;
+        Ok(())
+    }
+
+    /// Removes the blocks of the block range from the [`tables::LogAddressIndex`] and
+    /// [`tables::LogTopicIndex`] indices, if the log index is enabled.
+    ///
+    /// Must be called before the receipts of the block range are removed. The range of blocks
+    /// covered by the index is updated even if the index is disabled, so that it never covers
+    /// blocks that are no longer canonical.
+    pub fn unwind_log_index(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()> {
+        let Some(coverage) = self.log_index_coverage()? else { return Ok(()) };
+        let first_block = *range.start();
+
+        // only the covered blocks were added to the index
+        let indexed = first_block.max(*coverage.start())..=(*range.end()).min(*coverage.end());
+        if self.log_index && !indexed.is_empty() {
+            let receipts = indexed
+                .clone()
+                .map(|block_number| {
+                    let receipts = self.receipts_by_block(block_number.into())?.unwrap_or_default();
+                    Ok(receipts.into_iter().map(Some).collect())
+                })
+                .collect::<ProviderResult<Receipts>>()?;
+            let (addresses, topics) = log_index_updates(*indexed.start(), &receipts);
+            self.unwind_log_index_shards::<_, tables::LogAddressIndex>(
+                addresses.into_keys(),
+                first_block,
+            )?;
+            self.unwind_log_index_shards::<_, tables::LogTopicIndex>(
+                topics.into_keys(),
+                first_block,
+            )?;
+        }
+
+        if first_block <= *coverage.start() {
+            self.clear_log_index_coverage()?;
+        } else if first_block <= *coverage.end() {
+            self.tx.put::<tables::ChainState>(
+                tables::ChainStateKey::LogIndexHighestBlock,
+                first_block - 1,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Clears the [`tables::LogAddressIndex`] and [`tables::LogTopicIndex`] indices and the range
+    /// of blocks they cover.
+    pub fn clear_log_index(&self) -> ProviderResult<()> {
+        self.tx.clear::<tables::LogAddressIndex>()?;
+        self.tx.clear::<tables::LogTopicIndex>()?;
+        self.clear_log_index_coverage()
+    }
+
+    /// Removes the range of blocks covered by the log index.
+    fn clear_log_index_coverage(&self) -> ProviderResult<()> {
+        self.tx.delete::<tables::ChainState>(tables::ChainStateKey::LogIndexLowestBlock, None)?;
+        self.tx.delete::<tables::ChainState>(tables::ChainStateKey::LogIndexHighestBlock, None)?;
+        Ok(())
+    }
+
+    /// Removes the blocks from the given block number onwards from the shards of the keys in the
+    /// log index table.
+    fn unwind_log_index_shards<K, T>(
+        &self,
+        keys: impl IntoIterator<Item = K>,
+        block_number: BlockNumber,
+    ) -> ProviderResult<()>
+    where
+        K: Copy + PartialEq,
+        T: Table<Key = ShardedKey<K>, Value = BlockNumberList>,
+    {
+        let mut cursor = self.tx.cursor_write::<T>()?;
+        for key in keys {
+            let partial_shard = unwind_history_shards::<_, T, _>(
+                &mut cursor,
+                ShardedKey::last(key),
+                block_number,
+                |sharded_key| sharded_key.key == key,
+            )?;
+
+            // Check the last returned partial shard.
+            // If it's not empty, the shard needs to be reinserted.
+            if !partial_shard.is_empty() {
+                cursor.insert(
+                    ShardedKey::last(key),
+                    BlockNumberList::new_pre_sorted(partial_shard),
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the numbers of the blocks with logs, grouped by the addresses that emitted the logs
+/// and by the topics of the logs. The receipts are grouped by block, starting at `first_block`.
+fn log_index_updates(
+    first_block: BlockNumber,
+    receipts: &Receipts,
+) -> (BTreeMap<Address, Vec<BlockNumber>>, BTreeMap<B256, Vec<BlockNumber>>) {
+    let mut addresses = BTreeMap::<_, Vec<_>>::new();
+    let mut topics = BTreeMap::<_, Vec<_>>::new();
+    for (block_number, block_receipts) in (first_block..).zip(&receipts.receipt_vec) {
+        for log in block_receipts.iter().flatten().flat_map(|receipt| &receipt.logs) {
+            let blocks = addresses.entry(log.address).or_default();
+            if blocks.last() != Some(&block_number) {
+                blocks.push(block_number);
+            }
+            for topic in log.topics() {
+                let blocks = topics.entry(*topic).or_default();
+                if blocks.last() != Some(&block_number) {
+                    blocks.push(block_number);
+                }
+            }
+        }
+    }
+    (addresses, topics)
+}
+
+/// Returns the function selector that the transaction calls, i.e. the first 4 bytes of the input
+/// of a transaction that isn't a contract creation.
+fn call_selector(transaction: &Transaction) -> Option<Selector> {
+    if transaction.kind().is_create() {
+        return None
+    }
+    transaction.input().get(..4).map(Selector::from_slice)
 }
 
 impl<TX: DbTx> AccountReader for DatabaseProvider<TX> {
@@ -1920,6 +2357,117 @@ impl<TX: DbTx> ReceiptProvider for DatabaseProvider<TX> {
     }
 }
 
+impl<TX: DbTx> AccessListProvider for DatabaseProvider<TX> {
+    fn access_list(&self, id: TxNumber) -> ProviderResult<Option<AccessList>> {
+        // Access lists are only stored in static files.
+        self.static_file_provider.access_list(id)
+    }
+
+    fn access_lists_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<AccessList>>> {
+        if let Some(number) = self.convert_hash_or_number(block)? {
+            let is_collected = self
+                .static_file_provider
+                .get_highest_static_file_block(StaticFileSegment::AccessLists)
+                .map_or(false, |highest_block| highest_block >= number);
+            if !is_collected {
+                return Ok(None)
+            }
+
+            if let Some(body) = self.block_body_indices(number)? {
+                let tx_range = body.tx_num_range();
+                return if tx_range.is_empty() {
+                    Ok(Some(Vec::new()))
+                } else {
+                    self.access_lists_by_tx_range(tx_range).map(Some)
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn access_lists_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<AccessList>> {
+        self.static_file_provider.access_lists_by_tx_range(range)
+    }
+}
+
+impl<TX: DbTx> TransactionSelectorProvider for DatabaseProvider<TX> {
+    fn transactions_by_selector(
+        &self,
+        selector: Selector,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        if !self.selector_index {
+            return Err(ProviderError::UnsupportedProvider)
+        }
+
+        let mut tx_numbers = Vec::new();
+        // Shards are keyed by their highest transaction number, so the first shard that can
+        // contain the start of the range is the first one with a key greater or equal to it.
+        let mut cursor = self.tx.cursor_read::<tables::TransactionSelectors>()?;
+        for entry in cursor.walk(Some(ShardedKey::new(selector, *range.start())))? {
+            let (sharded_key, list) = entry?;
+            if sharded_key.key != selector {
+                break
+            }
+
+            for tx_number in list.iter().skip_while(|tx_number| tx_number < range.start()) {
+                if tx_number > *range.end() || tx_numbers.len() == limit {
+                    return Ok(tx_numbers)
+                }
+                tx_numbers.push(tx_number);
+            }
+        }
+        Ok(tx_numbers)
+    }
+}
+
+impl<TX: DbTx> LogIndexProvider for DatabaseProvider<TX> {
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        if !self.log_index {
+            return Ok(None)
+        }
+        self.log_index_coverage()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        if !self.log_index {
+            return Err(ProviderError::UnsupportedProvider)
+        }
+
+        // A block has to match every non-empty set, and matches a set if it contains a log with
+        // any of its values.
+        let mut matching: Option<BTreeSet<BlockNumber>> = None;
+        if !addresses.is_empty() {
+            matching =
+                Some(self.log_index_blocks::<_, tables::LogAddressIndex>(addresses, &range)?);
+        }
+        for topics in topics.iter().filter(|topics| !topics.is_empty()) {
+            let blocks = self.log_index_blocks::<_, tables::LogTopicIndex>(topics, &range)?;
+            matching = Some(match matching {
+                Some(matching) => matching.intersection(&blocks).copied().collect(),
+                None => blocks,
+            });
+        }
+
+        Ok(match matching {
+            Some(matching) => matching.into_iter().collect(),
+            None => range.collect(),
+        })
+    }
+}
+
 impl<TX: DbTx> WithdrawalsProvider for DatabaseProvider<TX> {
     fn withdrawals_by_block(
         &self,
@@ -2101,6 +2649,23 @@ impl<TX: DbTxMut> StageCheckpointWriter for DatabaseProvider<TX> {
         Ok(self.tx.put::<tables::StageCheckpointProgresses>(id.to_string(), checkpoint)?)
     }
 
+    fn save_stage_run(&self, run: StageRun) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_write::<tables::StageRuns>()?;
+        let next = cursor.last()?.map_or(0, |(number, _)| number + 1);
+        cursor.append(next, run)?;
+
+        // remove the oldest runs, so that only the most recent ones are kept
+        let oldest_kept = (next + 1).saturating_sub(MAX_STAGE_RUNS);
+        let mut walker = cursor.walk(None)?;
+        while let Some((number, _)) = walker.next().transpose()? {
+            if number >= oldest_kept {
+                break
+            }
+            walker.delete_current()?;
+        }
+        Ok(())
+    }
+
     fn update_pipeline_stages(
         &self,
         block_number: BlockNumber,
@@ -2546,6 +3111,12 @@ impl<TX: DbTxMut + DbTx> BlockExecutionWriter for DatabaseProvider<TX> {
             // Unwind storage history indices.
             self.unwind_storage_history_indices(storage_range)?;
 
+            // Unwind the selector index, while the transactions of the range still exist.
+            self.unwind_selector_index(range.clone())?;
+
+            // Unwind the log index, while the receipts of the range still exist.
+            self.unwind_log_index(range.clone())?;
+
             // Calculate the reverted merkle root.
             // This is the same as `StateRoot::incremental_root_with_updates`, only the prefix sets
             // are pre-loaded.
@@ -2770,6 +3341,9 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
             durations_recorder.record_relative(metrics::Action::InsertBlock);
         }
 
+        // Index the logs before the execution outcome is consumed by writing it.
+        self.insert_log_index(execution_outcome.first_block(), execution_outcome.receipts())?;
+
         // Write state and changesets to the database.
         // Must be written after blocks because of the receipt lookup.
         execution_outcome.write_to_storage(self.tx_ref(), None, OriginalValuesKnown::No)?;
@@ -2783,6 +3357,7 @@ impl<TX: DbTxMut + DbTx> BlockWriter for DatabaseProvider<TX> {
         durations_recorder.record_relative(metrics::Action::InsertHashes);
 
         self.update_history_indices(first_number..=last_block_number)?;
+        self.insert_selector_index(first_number..=last_block_number)?;
         durations_recorder.record_relative(metrics::Action::InsertHistoryIndices);
 
         // Update pipeline progress
@@ -2828,18 +3403,12 @@ impl<TX: DbTx> StatsReader for DatabaseProvider<TX> {
 }
 
 impl<TX: DbTx> FinalizedBlockReader for DatabaseProvider<TX> {
-    fn last_finalized_block_number(&self) -> ProviderResult<BlockNumber> {
-        let mut finalized_blocks = self
-            .tx
-            .cursor_read::<tables::ChainState>()?
-            .walk(Some(tables::ChainStateKey::LastFinalizedBlock))?
-            .take(1)
-            .collect::<Result<BTreeMap<tables::ChainStateKey, BlockNumber>, _>>()?;
+    fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?)
+    }
 
-        let last_finalized_block_number = finalized_blocks
-            .pop_first()
-            .unwrap_or((tables::ChainStateKey::LastFinalizedBlock, 0_u64));
-        Ok(last_finalized_block_number.1)
+    fn last_safe_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ChainState>(tables::ChainStateKey::LastSafeBlock)?)
     }
 }
 
@@ -2849,6 +3418,22 @@ impl<TX: DbTxMut> FinalizedBlockWriter for DatabaseProvider<TX> {
             .tx
             .put::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock, block_number)?)
     }
+
+    fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ChainState>(tables::ChainStateKey::LastSafeBlock, block_number)?)
+    }
+}
+
+impl<TX: DbTx> ExExCheckpointReader for DatabaseProvider<TX> {
+    fn exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self.tx.get::<tables::ExExCheckpoints>(id.to_string())?)
+    }
+}
+
+impl<TX: DbTxMut> ExExCheckpointWriter for DatabaseProvider<TX> {
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        Ok(self.tx.put::<tables::ExExCheckpoints>(id.to_string(), block_number)?)
+    }
 }
 
 fn range_size_hint(range: &impl RangeBounds<TxNumber>) -> Option<usize> {
@@ -0,0 +1,145 @@
+use crate::{
+    providers::{state::latest::LatestStateProviderRef, StaticFileProvider},
+    BlockNumReader, DatabaseProviderRO,
+};
+use reth_db_api::{
+    common::KeyValue,
+    cursor::DbCursorRO,
+    database::Database,
+    table::Table,
+    transaction::DbTx,
+    DatabaseError,
+};
+use reth_primitives::BlockNumber;
+use reth_storage_errors::provider::ProviderResult;
+
+/// A consistent, read-only view of the database pinned at the moment of its creation.
+///
+/// The snapshot holds a single read transaction open for its whole lifetime, so all reads observe
+/// the same state regardless of blocks written in the meantime. MDBX readers never block the
+/// writer, which makes this suitable for long-running analytics or ETL jobs next to a running
+/// node.
+///
+/// CAUTION: Pages referenced by the pinned transaction can't be reused until the snapshot is
+/// dropped, so the database file grows while the node keeps writing. Drop snapshots as soon as
+/// they're no longer needed.
+///
+/// Static files are shared with the writer and are not pinned. Use
+/// [`DatabaseSnapshot::block_number`] as the upper bound when reading block data.
+#[derive(Debug)]
+pub struct DatabaseSnapshot<DB: Database> {
+    /// Provider with the pinned read transaction.
+    provider: DatabaseProviderRO<DB>,
+    /// Highest block available in the snapshot.
+    block_number: BlockNumber,
+}
+
+impl<DB: Database> DatabaseSnapshot<DB> {
+    /// Creates a new snapshot from the given read-only provider.
+    ///
+    /// Long-lived read transaction safety is disabled, so the transaction is not timed out.
+    pub(crate) fn new(provider: DatabaseProviderRO<DB>) -> ProviderResult<Self> {
+        let provider = provider.disable_long_read_transaction_safety();
+        let block_number = provider.best_block_number()?;
+        Ok(Self { provider, block_number })
+    }
+
+    /// Returns the highest block available in the snapshot.
+    pub const fn block_number(&self) -> BlockNumber {
+        self.block_number
+    }
+
+    /// Returns the provider with the pinned read transaction.
+    pub const fn provider(&self) -> &DatabaseProviderRO<DB> {
+        &self.provider
+    }
+
+    /// Returns the static file provider.
+    pub const fn static_file_provider(&self) -> &StaticFileProvider {
+        self.provider.static_file_provider()
+    }
+
+    /// Returns a state provider for the latest state as of the snapshot.
+    pub fn latest(&self) -> LatestStateProviderRef<'_, DB::TX> {
+        LatestStateProviderRef::new(self.provider.tx_ref(), self.static_file_provider().clone())
+    }
+
+    /// Returns an iterator over all entries of the table, in key order.
+    ///
+    /// To iterate over all tables, combine this with [`reth_db::TableViewer`] and
+    /// [`reth_db::Tables::ALL`].
+    pub fn table_iter<T: Table>(&self) -> Result<SnapshotTableIter<DB::TX, T>, DatabaseError> {
+        let cursor = self.provider.tx_ref().cursor_read::<T>()?;
+        Ok(SnapshotTableIter { cursor, started: false })
+    }
+}
+
+/// Iterator over all entries of a table in a [`DatabaseSnapshot`].
+pub struct SnapshotTableIter<TX: DbTx, T: Table> {
+    /// Cursor of the pinned read transaction.
+    cursor: TX::Cursor<T>,
+    /// Whether the cursor was positioned at the first entry.
+    started: bool,
+}
+
+impl<TX: DbTx, T: Table> std::fmt::Debug for SnapshotTableIter<TX, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotTableIter")
+            .field("table", &T::NAME)
+            .field("started", &self.started)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<TX: DbTx, T: Table> Iterator for SnapshotTableIter<TX, T> {
+    type Item = Result<KeyValue<T>, DatabaseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entry = if self.started {
+            self.cursor.next()
+        } else {
+            self.started = true;
+            self.cursor.first()
+        };
+        entry.transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{test_utils::create_test_provider_factory, AccountReader, StateProvider};
+    use reth_db::tables;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::{Account, Address};
+
+    #[test]
+    fn snapshot_is_isolated_from_writes() {
+        let factory = create_test_provider_factory();
+        let address = Address::with_last_byte(1);
+        let account = Account { nonce: 1, ..Default::default() };
+
+        let provider = factory.provider_rw().unwrap();
+        provider.tx_ref().put::<tables::PlainAccountState>(address, account).unwrap();
+        provider.commit().unwrap();
+
+        let snapshot = factory.snapshot().unwrap();
+
+        let provider = factory.provider_rw().unwrap();
+        provider
+            .tx_ref()
+            .put::<tables::PlainAccountState>(Address::with_last_byte(2), account)
+            .unwrap();
+        provider.tx_ref().delete::<tables::PlainAccountState>(address, None).unwrap();
+        provider.commit().unwrap();
+
+        assert_eq!(snapshot.latest().basic_account(address).unwrap(), Some(account));
+        assert_eq!(snapshot.latest().storage(address, Default::default()).unwrap(), None);
+
+        let entries = snapshot
+            .table_iter::<tables::PlainAccountState>()
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(entries, vec![(address, account)]);
+    }
+}
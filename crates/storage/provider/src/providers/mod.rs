@@ -1,11 +1,13 @@
 use crate::{
-    AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt,
-    BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory,
-    EvmEnvProvider, FullExecutionDataProvider, HeaderProvider, ProviderError,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, RequestsProvider,
-    StageCheckpointReader, StateProviderBox, StateProviderFactory, StaticFileProviderFactory,
-    TransactionVariant, TransactionsProvider, TreeViewer, WithdrawalsProvider,
+    AccessListProvider, AccountReader, BlockHashReader, BlockIdReader, BlockNumReader, BlockReader,
+    BlockReaderIdExt, BlockSource, BlockchainTreePendingStateProvider, CanonChainTracker,
+    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
+    DatabaseProviderFactory, EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter,
+    FinalizedBlockReader, FinalizedBlockWriter, FullExecutionDataProvider, HeaderProvider,
+    LogIndexProvider, ProviderError, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    RequestsProvider, StageCheckpointReader, StateProviderBox, StateProviderFactory,
+    StaticFileProviderFactory, TransactionSelectorProvider, TransactionVariant,
+    TransactionsProvider, TreeViewer, WithdrawalsProvider,
 };
 use reth_blockchain_tree_api::{
     error::{CanonicalError, InsertBlockError},
@@ -19,10 +21,11 @@ use reth_db_api::{
 };
 use reth_evm::ConfigureEvmEnv;
 use reth_primitives::{
-    Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash, BlockNumber,
-    BlockNumberOrTag, BlockWithSenders, Header, Receipt, SealedBlock, SealedBlockWithSenders,
-    SealedHeader, TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber,
-    Withdrawal, Withdrawals, B256, U256,
+    AccessList, Account, Address, Block, BlockHash, BlockHashOrNumber, BlockId, BlockNumHash,
+    BlockNumber, BlockNumberOrTag, BlockWithSenders, Bytes, Header, Receipt, ReceiptEncoding,
+    ReceiptWithBloom, SealedBlock, SealedBlockWithSenders, SealedHeader, Selector, TransactionMeta,
+    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
+    U256,
 };
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
@@ -47,6 +50,7 @@ pub use static_file::{
 
 mod state;
 pub use state::{
+    fork::StateFallback,
     historical::{HistoricalStateProvider, HistoricalStateProviderRef},
     latest::{LatestStateProvider, LatestStateProviderRef},
 };
@@ -94,7 +98,7 @@ impl<DB> BlockchainProvider<DB> {
         tree: Arc<dyn TreeViewer>,
         latest: SealedHeader,
     ) -> Self {
-        Self { database, tree, chain_info: ChainInfoTracker::new(latest) }
+        Self { database, tree, chain_info: ChainInfoTracker::new(latest, None, None) }
     }
 
     /// Sets the treeviewer for the provider.
@@ -111,16 +115,27 @@ where
 {
     /// Create a new provider using only the database and the tree, fetching the latest header from
     /// the database to initialize the provider.
+    ///
+    /// The last persisted finalized and safe blocks are restored as well, so they are available
+    /// before the first forkchoice update is received.
     pub fn new(database: ProviderFactory<DB>, tree: Arc<dyn TreeViewer>) -> ProviderResult<Self> {
         let provider = database.provider()?;
         let best: ChainInfo = provider.chain_info()?;
-        match provider.header_by_number(best.best_number)? {
-            Some(header) => {
-                drop(provider);
-                Ok(Self::with_latest(database, tree, header.seal(best.best_hash)))
-            }
-            None => Err(ProviderError::HeaderNotFound(best.best_number.into())),
-        }
+        let latest = provider
+            .header_by_number(best.best_number)?
+            .ok_or_else(|| ProviderError::HeaderNotFound(best.best_number.into()))?
+            .seal(best.best_hash);
+
+        // only restore blocks that are still part of the canonical chain
+        let sealed_header = |number: Option<BlockNumber>| match number {
+            Some(number) if number <= best.best_number => provider.sealed_header(number),
+            _ => Ok(None),
+        };
+        let finalized = sealed_header(provider.last_finalized_block_number()?)?;
+        let safe = sealed_header(provider.last_safe_block_number()?)?;
+        drop(provider);
+
+        Ok(Self { database, tree, chain_info: ChainInfoTracker::new(latest, finalized, safe) })
     }
 }
 
@@ -430,6 +445,44 @@ where
         self.database.receipts_by_block(block)
     }
 
+    fn receipts_with_bloom_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<ReceiptWithBloom>>> {
+        let BlockHashOrNumber::Hash(hash) = block else {
+            return self.database.receipts_with_bloom_by_block(block)
+        };
+        let Some(receipts) = self.database.receipts_by_block(block)? else { return Ok(None) };
+
+        // the bloom filters of the receipts of recently validated blocks are cached by the tree
+        let receipts = match self.tree.receipt_encodings(hash) {
+            Some(encodings) if encodings.len() == receipts.len() => receipts
+                .into_iter()
+                .zip(encodings.iter())
+                .map(|(receipt, encoding)| ReceiptWithBloom::new(receipt, encoding.bloom))
+                .collect(),
+            _ => receipts.into_iter().map(Receipt::with_bloom).collect(),
+        };
+        Ok(Some(receipts))
+    }
+
+    fn raw_receipts_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        if let BlockHashOrNumber::Hash(hash) = block {
+            // the receipts of recently validated blocks are encoded already
+            if let Some(encodings) = self.tree.receipt_encodings(hash) {
+                if self.database.block_number(hash)?.is_some() {
+                    return Ok(Some(
+                        encodings.iter().map(|encoding| encoding.encoded.clone()).collect(),
+                    ))
+                }
+            }
+        }
+        self.database.raw_receipts_by_block(block)
+    }
+
     fn receipts_by_tx_range(
         &self,
         range: impl RangeBounds<TxNumber>,
@@ -438,6 +491,61 @@ where
     }
 }
 
+impl<DB> AccessListProvider for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn access_list(&self, id: TxNumber) -> ProviderResult<Option<AccessList>> {
+        self.database.access_list(id)
+    }
+
+    fn access_lists_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<AccessList>>> {
+        self.database.access_lists_by_block(block)
+    }
+
+    fn access_lists_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<AccessList>> {
+        self.database.access_lists_by_tx_range(range)
+    }
+}
+
+impl<DB> TransactionSelectorProvider for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn transactions_by_selector(
+        &self,
+        selector: Selector,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>> {
+        self.database.transactions_by_selector(selector, range, limit)
+    }
+}
+
+impl<DB> LogIndexProvider for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>> {
+        self.database.log_index_range()
+    }
+
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>> {
+        self.database.blocks_with_logs(addresses, topics, range)
+    }
+}
+
 impl<DB> ReceiptProviderIdExt for BlockchainProvider<DB>
 where
     DB: Database,
@@ -500,7 +608,7 @@ where
     DB: Database,
 {
     fn get_stage_checkpoint(&self, id: StageId) -> ProviderResult<Option<StageCheckpoint>> {
-        self.database.provider()?.get_stage_checkpoint(id)
+        self.database.get_stage_checkpoint(id)
     }
 
     fn get_stage_checkpoint_progress(&self, id: StageId) -> ProviderResult<Option<Vec<u8>>> {
@@ -508,6 +616,59 @@ where
     }
 }
 
+impl<DB> FinalizedBlockReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.last_finalized_block_number()
+    }
+
+    fn last_safe_block_number(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.last_safe_block_number()
+    }
+}
+
+impl<DB> FinalizedBlockWriter for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn save_finalized_block_number(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let provider_rw = self.database.provider_rw()?;
+        provider_rw.save_finalized_block_number(block_number)?;
+        provider_rw.commit()?;
+        Ok(())
+    }
+
+    fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()> {
+        let provider_rw = self.database.provider_rw()?;
+        provider_rw.save_safe_block_number(block_number)?;
+        provider_rw.commit()?;
+        Ok(())
+    }
+}
+
+impl<DB> ExExCheckpointReader for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.exex_checkpoint(id)
+    }
+}
+
+impl<DB> ExExCheckpointWriter for BlockchainProvider<DB>
+where
+    DB: Database,
+{
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()> {
+        let provider_rw = self.database.provider_rw()?;
+        provider_rw.save_exex_checkpoint(id, block_number)?;
+        provider_rw.commit()?;
+        Ok(())
+    }
+}
+
 impl<DB> EvmEnvProvider for BlockchainProvider<DB>
 where
     DB: Database,
@@ -761,6 +922,10 @@ where
     fn receipts_by_block_hash(&self, block_hash: BlockHash) -> Option<Vec<Receipt>> {
         self.tree.receipts_by_block_hash(block_hash)
     }
+
+    fn receipt_encodings(&self, block_hash: BlockHash) -> Option<Arc<[ReceiptEncoding]>> {
+        self.tree.receipt_encodings(block_hash)
+    }
 }
 
 impl<DB> CanonChainTracker for BlockchainProvider<DB>
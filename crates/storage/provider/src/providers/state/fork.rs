@@ -0,0 +1,256 @@
+use crate::{
+    providers::{HistoricalStateProvider, LatestStateProvider},
+    AccountReader, BlockHashReader, StateProvider, StateProviderBox, StateRootProvider,
+};
+use reth_db::tables;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{storage_sharded_key::StorageShardedKey, ShardedKey},
+    transaction::DbTx,
+};
+use reth_primitives::{Account, Address, BlockNumber, Bytecode, StorageKey, StorageValue, B256};
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie::{updates::TrieUpdates, AccountProof};
+use revm::db::BundleState;
+use std::{fmt::Debug, sync::Arc};
+
+/// State that the local state falls back to for accounts, storage slots and bytecode that were
+/// never changed locally, e.g. the state of a remote network at the block the local chain was
+/// forked from.
+pub trait StateFallback: Send + Sync + Debug {
+    /// Get basic account information.
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>>;
+
+    /// Get the value of a storage slot.
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>>;
+
+    /// Get account code by its hash.
+    ///
+    /// Only called for code hashes that were returned by [`StateFallback::basic_account`].
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>>;
+}
+
+/// A state provider of the local database whose transaction can be used to check whether state
+/// was ever changed locally.
+pub(crate) trait LocalStateProvider: StateProvider {
+    /// The database transaction.
+    type Tx: DbTx;
+
+    /// Returns the database transaction.
+    fn tx(&self) -> &Self::Tx;
+}
+
+impl<TX: DbTx> LocalStateProvider for LatestStateProvider<TX> {
+    type Tx = TX;
+
+    fn tx(&self) -> &TX {
+        self.tx_ref()
+    }
+}
+
+impl<TX: DbTx> LocalStateProvider for HistoricalStateProvider<TX> {
+    type Tx = TX;
+
+    fn tx(&self) -> &TX {
+        self.tx_ref()
+    }
+}
+
+/// Returns the local state provider, falling back to the given state if any.
+pub(crate) fn with_state_fallback<P: LocalStateProvider + 'static>(
+    local: P,
+    fallback: Option<Arc<dyn StateFallback>>,
+) -> StateProviderBox {
+    match fallback {
+        Some(fallback) => Box::new(ForkStateProvider { local, fallback }),
+        None => Box::new(local),
+    }
+}
+
+/// State provider that reads accounts, storage slots and bytecode from the [`StateFallback`] if
+/// they were never changed locally.
+///
+/// Whether an account or a storage slot was changed locally is determined by the history tables,
+/// so that accounts and storage slots that were changed and then deleted locally aren't read from
+/// the fallback again. The changesets of the local blocks contain the values that were read from
+/// the fallback before the change, which makes historical state consistent with the fallback as
+/// well.
+///
+/// The state root and proofs only cover the local state.
+#[derive(Debug)]
+struct ForkStateProvider<P> {
+    local: P,
+    fallback: Arc<dyn StateFallback>,
+}
+
+impl<P: LocalStateProvider> ForkStateProvider<P> {
+    /// Returns true if the account was ever changed locally.
+    fn is_account_changed(&self, address: Address) -> ProviderResult<bool> {
+        let mut cursor = self.local.tx().cursor_read::<tables::AccountsHistory>()?;
+        Ok(cursor.seek(ShardedKey::new(address, 0))?.is_some_and(|(key, _)| key.key == address))
+    }
+
+    /// Returns true if the storage slot was ever changed locally.
+    fn is_storage_changed(
+        &self,
+        address: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<bool> {
+        let mut cursor = self.local.tx().cursor_read::<tables::StoragesHistory>()?;
+        Ok(cursor
+            .seek(StorageShardedKey::new(address, storage_key, 0))?
+            .is_some_and(|(key, _)| key.address == address && key.sharded_key.key == storage_key))
+    }
+}
+
+impl<P: LocalStateProvider> AccountReader for ForkStateProvider<P> {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.local.basic_account(address)? {
+            return Ok(Some(account))
+        }
+        if self.is_account_changed(address)? {
+            return Ok(None)
+        }
+        self.fallback.basic_account(address)
+    }
+}
+
+impl<P: LocalStateProvider> BlockHashReader for ForkStateProvider<P> {
+    fn block_hash(&self, number: BlockNumber) -> ProviderResult<Option<B256>> {
+        self.local.block_hash(number)
+    }
+
+    fn canonical_hashes_range(
+        &self,
+        start: BlockNumber,
+        end: BlockNumber,
+    ) -> ProviderResult<Vec<B256>> {
+        self.local.canonical_hashes_range(start, end)
+    }
+}
+
+impl<P: LocalStateProvider> StateRootProvider for ForkStateProvider<P> {
+    fn state_root(&self, bundle_state: &BundleState) -> ProviderResult<B256> {
+        self.local.state_root(bundle_state)
+    }
+
+    fn state_root_with_updates(
+        &self,
+        bundle_state: &BundleState,
+    ) -> ProviderResult<(B256, TrieUpdates)> {
+        self.local.state_root_with_updates(bundle_state)
+    }
+}
+
+impl<P: LocalStateProvider> StateProvider for ForkStateProvider<P> {
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.local.storage(account, storage_key)? {
+            return Ok(Some(value))
+        }
+        if self.is_storage_changed(account, storage_key)? {
+            return Ok(None)
+        }
+        self.fallback.storage(account, storage_key)
+    }
+
+    fn proof(&self, address: Address, slots: &[B256]) -> ProviderResult<AccountProof> {
+        self.local.proof(address, slots)
+    }
+
+    fn multiproof(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        self.local.multiproof(targets)
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        if let Some(bytecode) = self.local.bytecode_by_hash(code_hash)? {
+            return Ok(Some(bytecode))
+        }
+        self.fallback.bytecode_by_hash(code_hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_test_provider_factory;
+    use parking_lot::Mutex;
+    use reth_db::BlockNumberList;
+    use reth_db_api::transaction::DbTxMut;
+    use reth_primitives::U256;
+    use std::collections::HashMap;
+
+    /// A fallback that counts its reads.
+    #[derive(Debug, Default)]
+    struct TestFallback {
+        accounts: HashMap<Address, Account>,
+        reads: Mutex<usize>,
+    }
+
+    impl StateFallback for TestFallback {
+        fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+            *self.reads.lock() += 1;
+            Ok(self.accounts.get(&address).copied())
+        }
+
+        fn storage(&self, _: Address, _: StorageKey) -> ProviderResult<Option<StorageValue>> {
+            *self.reads.lock() += 1;
+            Ok(Some(U256::from(1)))
+        }
+
+        fn bytecode_by_hash(&self, _: B256) -> ProviderResult<Option<Bytecode>> {
+            Ok(None)
+        }
+    }
+
+    #[test]
+    fn falls_back_for_unchanged_state() {
+        let factory = create_test_provider_factory();
+        let remote = Address::with_last_byte(1);
+        let local = Address::with_last_byte(2);
+        let deleted = Address::with_last_byte(3);
+        let local_account = Account { nonce: 1, ..Default::default() };
+        let fallback = Arc::new(TestFallback {
+            accounts: HashMap::from([
+                (remote, Account { balance: U256::from(1), ..Default::default() }),
+                (deleted, Account { balance: U256::from(2), ..Default::default() }),
+            ]),
+            ..Default::default()
+        });
+
+        let tx = factory.provider_rw().unwrap().into_tx();
+        tx.put::<tables::PlainAccountState>(local, local_account).unwrap();
+        // the account was changed, and then deleted locally
+        tx.put::<tables::AccountsHistory>(
+            ShardedKey::new(deleted, u64::MAX),
+            BlockNumberList::new([1]).unwrap(),
+        )
+        .unwrap();
+        // the slot was changed, and then cleared locally
+        tx.put::<tables::StoragesHistory>(
+            StorageShardedKey::new(local, B256::ZERO, u64::MAX),
+            BlockNumberList::new([1]).unwrap(),
+        )
+        .unwrap();
+        tx.commit().unwrap();
+
+        let tx = factory.provider().unwrap().into_tx();
+        let state = with_state_fallback(
+            LatestStateProvider::new(tx, factory.static_file_provider()),
+            Some(fallback.clone()),
+        );
+        assert_eq!(state.basic_account(local).unwrap(), Some(local_account));
+        assert_eq!(state.basic_account(remote).unwrap(), fallback.accounts.get(&remote).copied());
+        assert_eq!(state.basic_account(deleted).unwrap(), None);
+        assert_eq!(state.storage(local, B256::ZERO).unwrap(), None);
+        assert_eq!(state.storage(local, B256::with_last_byte(1)).unwrap(), Some(U256::from(1)));
+        assert_eq!(*fallback.reads.lock(), 2);
+    }
+}
@@ -355,6 +355,11 @@ impl<TX: DbTx> HistoricalStateProvider<TX> {
         self
     }
 
+    /// Returns the database transaction.
+    pub(crate) const fn tx_ref(&self) -> &TX {
+        &self.tx
+    }
+
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> HistoricalStateProviderRef<'_, TX> {
@@ -116,6 +116,10 @@ impl<'b, TX: DbTx> StateProvider for LatestStateProviderRef<'b, TX> {
             .account_proof(address, slots)
             .map_err(Into::<reth_db::DatabaseError>::into)?)
     }
+
+    fn multiproof(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        Ok(Proof::new(self.tx).multiproof(targets).map_err(Into::<reth_db::DatabaseError>::into)?)
+    }
 }
 
 /// State provider for the latest state.
@@ -133,6 +137,11 @@ impl<TX: DbTx> LatestStateProvider<TX> {
         Self { db, static_file_provider }
     }
 
+    /// Returns the database transaction.
+    pub(crate) const fn tx_ref(&self) -> &TX {
+        &self.db
+    }
+
     /// Returns a new provider that takes the `TX` as reference
     #[inline(always)]
     fn as_ref(&self) -> LatestStateProviderRef<'_, TX> {
@@ -16,6 +16,7 @@ use reth_primitives::{
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
 use std::{
     ops::{Deref, RangeBounds},
+    path::Path,
     sync::Arc,
 };
 
@@ -72,6 +73,19 @@ impl<'a> StaticFileJarProvider<'a> {
         self.metrics = Some(metrics);
         self
     }
+
+    /// Returns the total size of the data, index, offsets and config files, in bytes.
+    ///
+    /// Files that are missing or can't be read are counted with a size of zero.
+    pub fn size(&self) -> u64 {
+        let file_size = |path: &Path| {
+            reth_fs_util::metadata(path).map(|metadata| metadata.len()).unwrap_or_default()
+        };
+        file_size(self.data_path()) +
+            file_size(&self.index_path()) +
+            file_size(&self.offsets_path()) +
+            file_size(&self.config_path())
+    }
 }
 
 impl<'a> HeaderProvider for StaticFileJarProvider<'a> {
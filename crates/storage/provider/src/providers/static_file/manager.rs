@@ -3,16 +3,20 @@ use super::{
     StaticFileProviderRWRefMut, BLOCKS_PER_STATIC_FILE,
 };
 use crate::{
-    to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, DatabaseProvider,
-    HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader, StatsReader,
-    TransactionVariant, TransactionsProvider, TransactionsProviderExt, WithdrawalsProvider,
+    to_range, AccessListProvider, BlockHashReader, BlockNumReader, BlockReader, BlockSource,
+    DatabaseProvider, HeaderProvider, ReceiptProvider, RequestsProvider, StageCheckpointReader,
+    StatsReader, TransactionVariant, TransactionsProvider, TransactionsProviderExt,
+    WithdrawalsProvider,
 };
 use dashmap::{mapref::entry::Entry as DashMapEntry, DashMap};
 use parking_lot::RwLock;
 use reth_chainspec::ChainInfo;
 use reth_db::{
     lockfile::StorageLock,
-    static_file::{iter_static_files, HeaderMask, ReceiptMask, StaticFileCursor, TransactionMask},
+    static_file::{
+        iter_static_files, AccessListMask, HeaderMask, ReceiptMask, StaticFileCursor,
+        TransactionMask,
+    },
     tables,
 };
 use reth_db_api::{
@@ -25,10 +29,10 @@ use reth_nippy_jar::NippyJar;
 use reth_primitives::{
     keccak256,
     static_file::{find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive},
-    Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders, Header, Receipt,
-    SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment, TransactionMeta,
-    TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal, Withdrawals, B256,
-    U256,
+    AccessList, Address, Block, BlockHash, BlockHashOrNumber, BlockNumber, BlockWithSenders,
+    Header, Receipt, SealedBlock, SealedBlockWithSenders, SealedHeader, StaticFileSegment,
+    TransactionMeta, TransactionSigned, TransactionSignedNoHash, TxHash, TxNumber, Withdrawal,
+    Withdrawals, B256, U256,
 };
 use reth_stages_types::{PipelineTarget, StageId};
 use reth_storage_errors::provider::{ProviderError, ProviderResult};
@@ -180,27 +184,9 @@ impl StaticFileProvider {
             let mut size = 0;
 
             for (block_range, _) in &ranges {
-                let fixed_block_range = find_fixed_range(block_range.start());
-                let jar_provider = self
-                    .get_segment_provider(segment, || Some(fixed_block_range), None)?
-                    .ok_or(ProviderError::MissingStaticFileBlock(segment, block_range.start()))?;
-
+                let jar_provider = self.get_segment_provider_for_range(segment, block_range)?;
                 entries += jar_provider.rows();
-
-                let data_size = reth_fs_util::metadata(jar_provider.data_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let index_size = reth_fs_util::metadata(jar_provider.index_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let offsets_size = reth_fs_util::metadata(jar_provider.offsets_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-                let config_size = reth_fs_util::metadata(jar_provider.config_path())
-                    .map(|metadata| metadata.len())
-                    .unwrap_or_default();
-
-                size += data_size + index_size + offsets_size + config_size;
+                size += jar_provider.size();
             }
 
             metrics.record_segment(segment, size, ranges.len(), entries);
@@ -209,6 +195,43 @@ impl StaticFileProvider {
         Ok(())
     }
 
+    /// Returns the block range and the size of all files, in bytes, of every static file segment.
+    pub fn segment_sizes(
+        &self,
+    ) -> ProviderResult<Vec<(StaticFileSegment, SegmentRangeInclusive, u64)>> {
+        let static_files =
+            iter_static_files(&self.path).map_err(|e| ProviderError::NippyJar(e.to_string()))?;
+
+        let mut sizes = Vec::with_capacity(static_files.len());
+        for (segment, ranges) in static_files {
+            let (Some((first, _)), Some((last, _))) = (ranges.first(), ranges.last()) else {
+                continue
+            };
+            let block_range = SegmentRangeInclusive::new(first.start(), last.end());
+
+            let mut size = 0;
+            for (block_range, _) in &ranges {
+                size += self.get_segment_provider_for_range(segment, block_range)?.size();
+            }
+
+            sizes.push((segment, block_range, size));
+        }
+        sizes.sort_unstable_by_key(|(segment, _, _)| *segment);
+
+        Ok(sizes)
+    }
+
+    /// Gets the [`StaticFileJarProvider`] of the static file containing the given block range.
+    fn get_segment_provider_for_range(
+        &self,
+        segment: StaticFileSegment,
+        block_range: &SegmentRangeInclusive,
+    ) -> ProviderResult<StaticFileJarProvider<'_>> {
+        let fixed_block_range = find_fixed_range(block_range.start());
+        self.get_segment_provider(segment, || Some(fixed_block_range), None)?
+            .ok_or(ProviderError::MissingStaticFileBlock(segment, block_range.start()))
+    }
+
     /// Gets the [`StaticFileJarProvider`] of the requested segment and block.
     pub fn get_segment_provider_from_block(
         &self,
@@ -446,7 +469,9 @@ impl StaticFileProvider {
                     if jar.user_header().expected_block_start() == 0 &&
                         matches!(
                             segment,
-                            StaticFileSegment::Receipts | StaticFileSegment::Transactions
+                            StaticFileSegment::Receipts |
+                                StaticFileSegment::Transactions |
+                                StaticFileSegment::AccessLists
                         )
                     {
                         tx_index.remove(&segment);
@@ -547,6 +572,12 @@ impl StaticFileProvider {
                 continue
             }
 
+            if segment.is_access_lists() && self.get_highest_static_file_block(segment).is_none() {
+                // Access lists are only stored as static files if they're collected during
+                // execution.
+                continue
+            }
+
             let initial_highest_block = self.get_highest_static_file_block(segment);
 
             //  File consistency is broken if:
@@ -631,6 +662,10 @@ impl StaticFileProvider {
                     highest_tx,
                     highest_block,
                 )?,
+                StaticFileSegment::AccessLists => {
+                    self.ensure_access_lists_invariants(provider, highest_tx, highest_block)?;
+                    None
+                }
             } {
                 update_unwind_target(unwind);
             }
@@ -690,7 +725,7 @@ impl StaticFileProvider {
             .get_stage_checkpoint(match segment {
                 StaticFileSegment::Headers => StageId::Headers,
                 StaticFileSegment::Transactions => StageId::Bodies,
-                StaticFileSegment::Receipts => StageId::Execution,
+                StaticFileSegment::Receipts | StaticFileSegment::AccessLists => StageId::Execution,
             })?
             .unwrap_or_default()
             .block_number;
@@ -735,6 +770,45 @@ impl StaticFileProvider {
         Ok(None)
     }
 
+    /// Check invariants of the [`StaticFileSegment::AccessLists`] segment, which has no
+    /// corresponding database table.
+    ///
+    /// Access lists are only collected if the executor is configured to do so, so the segment is
+    /// allowed to be behind the execution stage checkpoint. If it's ahead, then we failed to do a
+    /// database commit **but committed** to static files on executing the stage, or the reverse on
+    /// unwinding it, and the extra static file rows are pruned.
+    fn ensure_access_lists_invariants<TX: DbTx>(
+        &self,
+        provider: &DatabaseProvider<TX>,
+        highest_static_file_tx: Option<TxNumber>,
+        highest_static_file_block: Option<BlockNumber>,
+    ) -> ProviderResult<()> {
+        let segment = StaticFileSegment::AccessLists;
+        let Some(highest_static_file_block) = highest_static_file_block else { return Ok(()) };
+
+        let checkpoint_block_number =
+            provider.get_stage_checkpoint(StageId::Execution)?.unwrap_or_default().block_number;
+
+        if checkpoint_block_number < highest_static_file_block {
+            info!(
+                target: "reth::providers",
+                ?segment,
+                from = highest_static_file_block,
+                to = checkpoint_block_number,
+                "Unwinding static file segment."
+            );
+            let mut writer = self.latest_writer(segment)?;
+            if let Some(block) = provider.block_body_indices(checkpoint_block_number)? {
+                let number =
+                    highest_static_file_tx.unwrap_or_default().saturating_sub(block.last_tx_num());
+                writer.prune_access_lists(number, checkpoint_block_number)?;
+            }
+            writer.commit()?;
+        }
+
+        Ok(())
+    }
+
     /// Gets the highest static file block if it exists for a static file segment.
     pub fn get_highest_static_file_block(&self, segment: StaticFileSegment) -> Option<BlockNumber> {
         self.static_files_max_block.read().get(&segment).copied()
@@ -754,6 +828,7 @@ impl StaticFileProvider {
             headers: self.get_highest_static_file_block(StaticFileSegment::Headers),
             receipts: self.get_highest_static_file_block(StaticFileSegment::Receipts),
             transactions: self.get_highest_static_file_block(StaticFileSegment::Transactions),
+            access_lists: self.get_highest_static_file_block(StaticFileSegment::AccessLists),
         }
     }
 
@@ -800,7 +875,9 @@ impl StaticFileProvider {
             StaticFileSegment::Headers => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::AccessLists => {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -870,7 +947,9 @@ impl StaticFileProvider {
             StaticFileSegment::Headers => {
                 self.get_segment_provider_from_block(segment, start, None)
             }
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::AccessLists => {
                 self.get_segment_provider_from_transaction(segment, start, None)
             }
         };
@@ -915,9 +994,9 @@ impl StaticFileProvider {
         // If there is, check the maximum block or transaction number of the segment.
         let static_file_upper_bound = match segment {
             StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::AccessLists => self.get_highest_static_file_tx(segment),
         };
 
         if static_file_upper_bound
@@ -957,9 +1036,9 @@ impl StaticFileProvider {
         // If there is, check the maximum block or transaction number of the segment.
         if let Some(static_file_upper_bound) = match segment {
             StaticFileSegment::Headers => self.get_highest_static_file_block(segment),
-            StaticFileSegment::Transactions | StaticFileSegment::Receipts => {
-                self.get_highest_static_file_tx(segment)
-            }
+            StaticFileSegment::Transactions |
+            StaticFileSegment::Receipts |
+            StaticFileSegment::AccessLists => self.get_highest_static_file_tx(segment),
         } {
             if block_or_tx_range.start <= static_file_upper_bound {
                 let end = block_or_tx_range.end.min(static_file_upper_bound + 1);
@@ -1214,6 +1293,42 @@ impl ReceiptProvider for StaticFileProvider {
     }
 }
 
+impl AccessListProvider for StaticFileProvider {
+    fn access_list(&self, num: TxNumber) -> ProviderResult<Option<AccessList>> {
+        self.get_segment_provider_from_transaction(StaticFileSegment::AccessLists, num, None)
+            .and_then(|provider| {
+                provider.cursor()?.get_one::<AccessListMask<AccessList>>(num.into())
+            })
+            .or_else(|err| {
+                if let ProviderError::MissingStaticFileTx(_, _) = err {
+                    Ok(None)
+                } else {
+                    Err(err)
+                }
+            })
+    }
+
+    fn access_lists_by_block(
+        &self,
+        _block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<AccessList>>> {
+        // Required data not present in static_files
+        Err(ProviderError::UnsupportedProvider)
+    }
+
+    fn access_lists_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<AccessList>> {
+        self.fetch_range_with_predicate(
+            StaticFileSegment::AccessLists,
+            to_range(range),
+            |cursor, number| cursor.get_one::<AccessListMask<AccessList>>(number.into()),
+            |_| true,
+        )
+    }
+}
+
 impl TransactionsProviderExt for StaticFileProvider {
     fn transaction_hashes_by_range(
         &self,
@@ -0,0 +1,14 @@
+use reth_errors::ProviderResult;
+use reth_primitives::BlockNumber;
+
+/// Functionality to read the checkpoints of execution extensions from the database.
+pub trait ExExCheckpointReader: Send + Sync {
+    /// Returns the checkpoint of the execution extension with the given ID, if any.
+    fn exex_checkpoint(&self, id: &str) -> ProviderResult<Option<BlockNumber>>;
+}
+
+/// Functionality to write the checkpoints of execution extensions to the database.
+pub trait ExExCheckpointWriter: Send + Sync {
+    /// Saves the checkpoint of the execution extension with the given ID in the DB.
+    fn save_exex_checkpoint(&self, id: &str, block_number: BlockNumber) -> ProviderResult<()>;
+}
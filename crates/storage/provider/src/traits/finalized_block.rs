@@ -1,14 +1,20 @@
 use reth_errors::ProviderResult;
 use reth_primitives::BlockNumber;
 
-/// Functionality to read the last known finalized block from the database.
+/// Functionality to read the last known finalized and safe blocks from the database.
 pub trait FinalizedBlockReader: Send + Sync {
-    /// Returns the last finalized block number.
-    fn last_finalized_block_number(&self) -> ProviderResult<BlockNumber>;
+    /// Returns the last finalized block number, if any.
+    fn last_finalized_block_number(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns the last safe block number, if any.
+    fn last_safe_block_number(&self) -> ProviderResult<Option<BlockNumber>>;
 }
 
-/// Functionality to write the last known finalized block to the database.
+/// Functionality to write the last known finalized and safe blocks to the database.
 pub trait FinalizedBlockWriter: Send + Sync {
     /// Saves the given finalized block number in the DB.
     fn save_finalized_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
+
+    /// Saves the given safe block number in the DB.
+    fn save_safe_block_number(&self, block_number: BlockNumber) -> ProviderResult<()>;
 }
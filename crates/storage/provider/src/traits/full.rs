@@ -2,8 +2,9 @@
 
 use crate::{
     AccountReader, BlockReaderIdExt, CanonStateSubscriptions, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, EvmEnvProvider, StageCheckpointReader, StateProviderFactory,
-    StaticFileProviderFactory,
+    DatabaseProviderFactory, EvmEnvProvider, ExExCheckpointReader, ExExCheckpointWriter,
+    LogIndexProvider, StageCheckpointReader, StateProviderFactory, StaticFileProviderFactory,
+    TransactionSelectorProvider,
 };
 use reth_db_api::database::Database;
 
@@ -17,8 +18,12 @@ pub trait FullProvider<DB: Database>:
     + EvmEnvProvider
     + ChainSpecProvider
     + ChangeSetReader
+    + TransactionSelectorProvider
+    + LogIndexProvider
     + CanonStateSubscriptions
     + StageCheckpointReader
+    + ExExCheckpointReader
+    + ExExCheckpointWriter
     + Clone
     + Unpin
     + 'static
@@ -34,8 +39,12 @@ impl<T, DB: Database> FullProvider<DB> for T where
         + EvmEnvProvider
         + ChainSpecProvider
         + ChangeSetReader
+        + TransactionSelectorProvider
+        + LogIndexProvider
         + CanonStateSubscriptions
         + StageCheckpointReader
+        + ExExCheckpointReader
+        + ExExCheckpointWriter
         + Clone
         + Unpin
         + 'static
@@ -50,3 +50,6 @@ pub use tree_viewer::TreeViewer;
 
 mod finalized_block;
 pub use finalized_block::{FinalizedBlockReader, FinalizedBlockWriter};
+
+mod exex_checkpoint;
+pub use exex_checkpoint::{ExExCheckpointReader, ExExCheckpointWriter};
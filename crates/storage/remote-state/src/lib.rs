@@ -0,0 +1,239 @@
+//! A [`StateFallback`] that reads the state of a remote node over RPC.
+//!
+//! Used to fork a remote network: the local chain falls back to the state of the remote network
+//! at a pinned block for all accounts and storage slots that it never changed itself.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+
+use futures::Future;
+use jsonrpsee::{
+    core::client::Error as RpcError,
+    http_client::{HttpClient, HttpClientBuilder},
+};
+use parking_lot::Mutex;
+use reth_primitives::{
+    keccak256, Account, Address, BlockId, BlockNumber, BlockNumberOrTag, Bytecode, Bytes,
+    StorageKey, StorageValue, B256, U256,
+};
+use reth_provider::{ProviderError, ProviderResult, StateFallback};
+use reth_rpc_api::clients::EthApiClient;
+use reth_rpc_types::serde_helpers::JsonStorageKey;
+use schnellru::{ByLength, LruMap};
+use std::fmt;
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tracing::trace;
+
+/// The maximum number of cached accounts.
+const MAX_CACHED_ACCOUNTS: u32 = 100_000;
+
+/// The maximum number of cached storage slots.
+const MAX_CACHED_STORAGE_SLOTS: u32 = 1_000_000;
+
+/// The maximum number of cached bytecodes.
+const MAX_CACHED_BYTECODES: u32 = 10_000;
+
+/// Reads accounts, storage slots and bytecode from a remote node at a pinned block.
+///
+/// The state at the pinned block never changes, so the most recently fetched accounts, storage
+/// slots and bytecodes are cached.
+///
+/// The requests are driven by the given runtime and the calling thread blocks until they
+/// complete, because state providers are synchronous. On a worker thread of the runtime, its other
+/// tasks are moved to another thread while the request blocks.
+pub struct RpcStateFallback {
+    client: HttpClient,
+    block: BlockNumber,
+    handle: Handle,
+    accounts: Mutex<LruMap<Address, Option<Account>>>,
+    storage: Mutex<LruMap<(Address, StorageKey), StorageValue>>,
+    /// An account with each code hash, to fetch the bytecode again after it was evicted.
+    code_accounts: Mutex<LruMap<B256, Address>>,
+    bytecodes: Mutex<LruMap<B256, Bytecode>>,
+}
+
+impl RpcStateFallback {
+    /// Creates a new fallback that reads the state at the given block from the client.
+    pub fn new(client: HttpClient, block: BlockNumber, handle: Handle) -> Self {
+        Self {
+            client,
+            block,
+            handle,
+            accounts: Mutex::new(LruMap::new(ByLength::new(MAX_CACHED_ACCOUNTS))),
+            storage: Mutex::new(LruMap::new(ByLength::new(MAX_CACHED_STORAGE_SLOTS))),
+            code_accounts: Mutex::new(LruMap::new(ByLength::new(MAX_CACHED_ACCOUNTS))),
+            bytecodes: Mutex::new(LruMap::new(ByLength::new(MAX_CACHED_BYTECODES))),
+        }
+    }
+
+    /// Connects to the node at the given HTTP url and pins the state at the given block, or at
+    /// the latest block of the node if none is given.
+    ///
+    /// Must be called from within a tokio runtime, which is used for all following requests.
+    pub async fn connect(url: &str, block: Option<BlockNumber>) -> Result<Self, RpcError> {
+        let client = HttpClientBuilder::default().build(url)?;
+        let block = match block {
+            Some(block) => block,
+            None => EthApiClient::block_number(&client).await?.saturating_to(),
+        };
+        Ok(Self::new(client, block, Handle::current()))
+    }
+
+    /// Returns the block that the state is read at.
+    pub const fn block(&self) -> BlockNumber {
+        self.block
+    }
+
+    fn block_id(&self) -> BlockId {
+        BlockId::Number(BlockNumberOrTag::Number(self.block))
+    }
+
+    /// Drives the request on the runtime and blocks until it completes.
+    ///
+    /// Fails on a worker thread of a current thread runtime, which can't be blocked without
+    /// stalling the request itself.
+    fn block_on<T, F>(&self, request: F) -> ProviderResult<T>
+    where
+        F: Future<Output = Result<T, RpcError>>,
+    {
+        let response = match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+            // the other tasks of the worker are moved to another thread while it's blocked
+            Ok(RuntimeFlavor::MultiThread) => {
+                tokio::task::block_in_place(|| self.handle.block_on(request))
+            }
+            Ok(_) => {
+                return Err(ProviderError::StateFallback(
+                    "remote state can't be fetched on a current thread runtime".to_string(),
+                ))
+            }
+            Err(_) => self.handle.block_on(request),
+        };
+        response.map_err(|err| ProviderError::StateFallback(err.to_string()))
+    }
+
+    /// Fetches the code of the account.
+    fn fetch_code(&self, address: Address) -> ProviderResult<Bytes> {
+        let block = Some(self.block_id());
+        self.block_on(EthApiClient::get_code(&self.client, address, block))
+    }
+
+    /// Caches the bytecode, and the account it was fetched for.
+    fn cache_bytecode(&self, code_hash: B256, address: Address, code: Bytes) {
+        self.code_accounts.lock().insert(code_hash, address);
+        self.bytecodes.lock().insert(code_hash, Bytecode::new_raw(code));
+    }
+}
+
+impl fmt::Debug for RpcStateFallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RpcStateFallback")
+            .field("block", &self.block)
+            .field("accounts", &self.accounts.lock().len())
+            .field("storage", &self.storage.lock().len())
+            .field("bytecodes", &self.bytecodes.lock().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl StateFallback for RpcStateFallback {
+    fn basic_account(&self, address: Address) -> ProviderResult<Option<Account>> {
+        if let Some(account) = self.accounts.lock().get(&address) {
+            return Ok(*account)
+        }
+
+        trace!(target: "remote_state", %address, block = self.block, "Fetching account");
+        let block = Some(self.block_id());
+        let (balance, nonce, code) = self.block_on(async {
+            futures::try_join!(
+                EthApiClient::balance(&self.client, address, block),
+                EthApiClient::transaction_count(&self.client, address, block),
+                EthApiClient::get_code(&self.client, address, block),
+            )
+        })?;
+
+        let account = into_account(balance, nonce, &code);
+        if let Some(code_hash) = account.and_then(|account| account.bytecode_hash) {
+            self.cache_bytecode(code_hash, address, code);
+        }
+        self.accounts.lock().insert(address, account);
+        Ok(account)
+    }
+
+    fn storage(
+        &self,
+        account: Address,
+        storage_key: StorageKey,
+    ) -> ProviderResult<Option<StorageValue>> {
+        if let Some(value) = self.storage.lock().get(&(account, storage_key)) {
+            return Ok(Some(*value))
+        }
+
+        trace!(target: "remote_state", %account, %storage_key, block = self.block, "Fetching storage slot");
+        let block = Some(self.block_id());
+        let value = self.block_on(EthApiClient::storage_at(
+            &self.client,
+            account,
+            JsonStorageKey(storage_key),
+            block,
+        ))?;
+
+        let value = U256::from_be_bytes(value.0);
+        self.storage.lock().insert((account, storage_key), value);
+        Ok(Some(value))
+    }
+
+    fn bytecode_by_hash(&self, code_hash: B256) -> ProviderResult<Option<Bytecode>> {
+        // the code is fetched together with the account that the hash was returned for, and
+        // fetched again from the same account once it was evicted
+        if let Some(bytecode) = self.bytecodes.lock().get(&code_hash) {
+            return Ok(Some(bytecode.clone()))
+        }
+        let Some(address) = self.code_accounts.lock().get(&code_hash).copied() else {
+            return Ok(None)
+        };
+
+        trace!(target: "remote_state", %address, %code_hash, block = self.block, "Fetching bytecode");
+        let code = self.fetch_code(address)?;
+        if keccak256(&code) != code_hash {
+            return Ok(None)
+        }
+        let bytecode = Bytecode::new_raw(code.clone());
+        self.cache_bytecode(code_hash, address, code);
+        Ok(Some(bytecode))
+    }
+}
+
+/// Returns the account with the given balance, nonce and code, or `None` if the account is empty.
+fn into_account(balance: U256, nonce: U256, code: &Bytes) -> Option<Account> {
+    let account = Account {
+        balance,
+        nonce: nonce.saturating_to(),
+        bytecode_hash: (!code.is_empty()).then(|| keccak256(code)),
+    };
+    (!account.is_empty()).then_some(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_accounts_are_missing() {
+        assert_eq!(into_account(U256::ZERO, U256::ZERO, &Bytes::new()), None);
+
+        let code = Bytes::from_static(&[0x60, 0x00]);
+        assert_eq!(
+            into_account(U256::ZERO, U256::ZERO, &code),
+            Some(Account { bytecode_hash: Some(keccak256(&code)), ..Default::default() })
+        );
+        assert_eq!(
+            into_account(U256::from(1), U256::from(2), &Bytes::new()),
+            Some(Account { balance: U256::from(1), nonce: 2, bytecode_hash: None })
+        );
+    }
+}
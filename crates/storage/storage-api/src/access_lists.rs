@@ -0,0 +1,32 @@
+use reth_primitives::{AccessList, BlockHashOrNumber, TxNumber};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeBounds;
+
+/// Client trait for fetching the [`AccessList`]s of transactions that were collected while
+/// executing blocks.
+///
+/// The access list of a transaction contains every account and storage slot it touched, see
+/// `reth_evm::access_list`. Access lists are only available if the executor was configured to
+/// collect them.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait AccessListProvider: Send + Sync {
+    /// Get the access list of the transaction by transaction number.
+    ///
+    /// Returns `None` if the transaction is not found or its access list wasn't collected.
+    fn access_list(&self, id: TxNumber) -> ProviderResult<Option<AccessList>>;
+
+    /// Get the access lists of the block's transactions by block num or hash, in the order of the
+    /// transactions.
+    ///
+    /// Returns `None` if the block is not found or its access lists weren't collected.
+    fn access_lists_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<AccessList>>>;
+
+    /// Get access lists by tx range.
+    fn access_lists_by_tx_range(
+        &self,
+        range: impl RangeBounds<TxNumber>,
+    ) -> ProviderResult<Vec<AccessList>>;
+}
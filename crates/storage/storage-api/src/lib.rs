@@ -10,6 +10,9 @@
 
 // Re-export used error types.
 pub use reth_storage_errors as errors;
+mod access_lists;
+pub use access_lists::*;
+
 mod account;
 pub use account::*;
 
@@ -25,6 +28,9 @@ pub use block_hash::*;
 mod header;
 pub use header::*;
 
+mod logs;
+pub use logs::*;
+
 mod prune_checkpoint;
 pub use prune_checkpoint::*;
 
@@ -34,6 +40,9 @@ pub use receipts::*;
 mod requests;
 pub use requests::*;
 
+mod selectors;
+pub use selectors::*;
+
 mod stage_checkpoint;
 pub use stage_checkpoint::*;
 
@@ -0,0 +1,30 @@
+use reth_primitives::{Address, BlockNumber, B256};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Client trait for looking up the blocks that contain logs of addresses or with topics.
+///
+/// The lookup is served by an index that is maintained while blocks are executed, or built with
+/// `reth db log-index`, so it only covers the range of blocks returned by
+/// [`LogIndexProvider::log_index_range`].
+#[auto_impl::auto_impl(&, Arc)]
+pub trait LogIndexProvider: Send + Sync {
+    /// Get the contiguous range of blocks that are covered by the log index.
+    ///
+    /// Returns `None` if the log index is disabled or doesn't cover any blocks.
+    fn log_index_range(&self) -> ProviderResult<Option<RangeInclusive<BlockNumber>>>;
+
+    /// Get the numbers of the blocks in the range that may contain a log that is emitted by one
+    /// of the addresses and has one of the topics of every topic set, in ascending order.
+    ///
+    /// Empty sets match all logs. The index doesn't distinguish the positions of topics, so the
+    /// blocks can contain false positives, but it never misses a block with matching logs.
+    ///
+    /// Returns an `UnsupportedProvider` error if the log index is disabled.
+    fn blocks_with_logs(
+        &self,
+        addresses: &[Address],
+        topics: &[Vec<B256>],
+        range: RangeInclusive<BlockNumber>,
+    ) -> ProviderResult<Vec<BlockNumber>>;
+}
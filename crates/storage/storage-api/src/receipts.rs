@@ -1,5 +1,8 @@
 use crate::BlockIdReader;
-use reth_primitives::{BlockHashOrNumber, BlockId, BlockNumberOrTag, Receipt, TxHash, TxNumber};
+use reth_primitives::{
+    BlockHashOrNumber, BlockId, BlockNumberOrTag, Bytes, Receipt, ReceiptEncoding,
+    ReceiptWithBloom, TxHash, TxNumber,
+};
 use reth_storage_errors::provider::ProviderResult;
 use std::ops::RangeBounds;
 
@@ -21,6 +24,30 @@ pub trait ReceiptProvider: Send + Sync {
     /// Returns `None` if the block is not found.
     fn receipts_by_block(&self, block: BlockHashOrNumber) -> ProviderResult<Option<Vec<Receipt>>>;
 
+    /// Get receipts with their bloom filters by block num or hash.
+    ///
+    /// Returns `None` if the block is not found.
+    fn receipts_with_bloom_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<ReceiptWithBloom>>> {
+        Ok(self
+            .receipts_by_block(block)?
+            .map(|receipts| receipts.into_iter().map(Receipt::with_bloom).collect()))
+    }
+
+    /// Get the EIP-2718 encoded receipts, with their bloom filters, by block num or hash.
+    ///
+    /// Returns `None` if the block is not found.
+    fn raw_receipts_by_block(
+        &self,
+        block: BlockHashOrNumber,
+    ) -> ProviderResult<Option<Vec<Bytes>>> {
+        Ok(self.receipts_by_block(block)?.map(|receipts| {
+            receipts.iter().map(|receipt| ReceiptEncoding::new(receipt).encoded).collect()
+        }))
+    }
+
     /// Get receipts by tx range.
     fn receipts_by_tx_range(
         &self,
@@ -0,0 +1,22 @@
+use reth_primitives::{Selector, TxNumber};
+use reth_storage_errors::provider::ProviderResult;
+use std::ops::RangeInclusive;
+
+/// Client trait for looking up the calls to a function, i.e. the transactions whose input starts
+/// with its 4-byte selector.
+///
+/// The lookup is served by an index that is maintained while blocks are executed, so it only
+/// covers the blocks that were executed while the index was enabled.
+#[auto_impl::auto_impl(&, Arc)]
+pub trait TransactionSelectorProvider: Send + Sync {
+    /// Get the numbers of the transactions in the range whose input starts with the selector, in
+    /// ascending order, returning at most `limit` of them.
+    ///
+    /// Returns an `UnsupportedProvider` error if the selector index is disabled.
+    fn transactions_by_selector(
+        &self,
+        selector: Selector,
+        range: RangeInclusive<TxNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<TxNumber>>;
+}
@@ -1,5 +1,5 @@
 use reth_primitives::BlockNumber;
-use reth_stages_types::{StageCheckpoint, StageId};
+use reth_stages_types::{StageCheckpoint, StageId, StageRun};
 use reth_storage_errors::provider::ProviderResult;
 
 /// The trait for fetching stage checkpoint related data.
@@ -26,6 +26,10 @@ pub trait StageCheckpointWriter: Send + Sync {
         checkpoint: Vec<u8>,
     ) -> ProviderResult<()>;
 
+    /// Save a run of a stage, after the runs that were saved before, and remove the oldest runs
+    /// beyond [`MAX_STAGE_RUNS`](reth_stages_types::MAX_STAGE_RUNS).
+    fn save_stage_run(&self, run: StageRun) -> ProviderResult<()>;
+
     /// Update all pipeline sync stage progress.
     fn update_pipeline_stages(
         &self,
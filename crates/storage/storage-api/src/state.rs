@@ -27,6 +27,14 @@ pub trait StateProvider: BlockHashReader + AccountReader + StateRootProvider + S
     /// Get account and storage proofs.
     fn proof(&self, address: Address, keys: &[B256]) -> ProviderResult<AccountProof>;
 
+    /// Get the account and storage proofs of multiple accounts, in the order of the targets.
+    ///
+    /// Providers that can prove all accounts in a single walk of the state trie should override
+    /// this, by default every account is proven separately.
+    fn multiproof(&self, targets: &[(Address, Vec<B256>)]) -> ProviderResult<Vec<AccountProof>> {
+        targets.iter().map(|(address, keys)| self.proof(*address, keys)).collect()
+    }
+
     /// Get account code by its address.
     ///
     /// Returns `None` if the account doesn't exist or account is not a contract
@@ -18,9 +18,10 @@ use crate::{
 };
 use dyn_clone::DynClone;
 use futures_util::{
-    future::{select, BoxFuture},
+    future::{select, BoxFuture, Either},
     Future, FutureExt, TryFutureExt,
 };
+use reth_tokio_util::{EventSender, EventStream};
 use std::{
     any::Any,
     fmt::{Display, Formatter},
@@ -36,12 +37,15 @@ use tokio::{
     sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
     task::JoinHandle,
 };
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 use tracing_futures::Instrument;
 
 pub mod metrics;
 pub mod shutdown;
 
+mod restart;
+pub use restart::{RestartPolicy, TaskRestarted};
+
 #[cfg(feature = "rayon")]
 pub mod pool;
 
@@ -163,6 +167,8 @@ pub struct TaskManager {
     on_shutdown: Shutdown,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Notifies listeners about restarted critical tasks
+    restarted_tasks: EventSender<TaskRestarted>,
 }
 
 // === impl TaskManager ===
@@ -189,6 +195,7 @@ impl TaskManager {
             signal: Some(signal),
             on_shutdown,
             graceful_tasks: Arc::new(AtomicUsize::new(0)),
+            restarted_tasks: EventSender::default(),
         }
     }
 
@@ -201,6 +208,7 @@ impl TaskManager {
             panicked_tasks_tx: self.panicked_tasks_tx.clone(),
             metrics: Default::default(),
             graceful_tasks: Arc::clone(&self.graceful_tasks),
+            restarted_tasks: self.restarted_tasks.clone(),
         }
     }
 
@@ -291,6 +299,8 @@ pub struct TaskExecutor {
     metrics: TaskExecutorMetrics,
     /// How many [`GracefulShutdown`] tasks are currently active
     graceful_tasks: Arc<AtomicUsize>,
+    /// Notifies listeners about restarted critical tasks
+    restarted_tasks: EventSender<TaskRestarted>,
 }
 
 // === impl TaskExecutor ===
@@ -306,6 +316,12 @@ impl TaskExecutor {
         &self.on_shutdown
     }
 
+    /// Returns a stream of the tasks that panicked and were restarted according to their
+    /// [`RestartPolicy`].
+    pub fn restarted_tasks(&self) -> EventStream<TaskRestarted> {
+        self.restarted_tasks.new_listener()
+    }
+
     /// Spawns a future on the tokio runtime depending on the [`TaskKind`]
     fn spawn_on_rt<F>(&self, fut: F, task_kind: TaskKind) -> JoinHandle<()>
     where
@@ -441,6 +457,134 @@ impl TaskExecutor {
         self.spawn_critical_as(name, fut, TaskKind::Default)
     }
 
+    /// This spawns a critical task onto the runtime, that is restarted according to the given
+    /// [`RestartPolicy`] if it panics.
+    /// The task resolves as soon as the [Shutdown] signal is received.
+    ///
+    /// Every (re)start of the task creates a new future with the given closure. If the task panics
+    /// and must not be restarted again, the [`TaskManager`] is notified.
+    pub fn spawn_critical_with_restart_policy<F, Fut>(
+        &self,
+        name: &'static str,
+        policy: RestartPolicy,
+        f: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut(Shutdown) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let panicked_tasks_tx = self.panicked_tasks_tx.clone();
+        let on_shutdown = self.on_shutdown.clone();
+
+        let task_shutdown = on_shutdown.clone();
+        let restarts = self.run_with_restarts(name, policy, move || task_shutdown.clone(), f);
+        let task = async move {
+            if let Some(task_error) = restarts.await {
+                error!("{task_error}");
+                let _ = panicked_tasks_tx.send(task_error);
+            }
+        }
+        .in_current_span();
+
+        // Clone only the specific counter that we need.
+        let finished_critical_tasks_total_metrics =
+            self.metrics.finished_critical_tasks_total.clone();
+        let task = async move {
+            // Create an instance of IncCounterOnDrop with the counter to increment
+            let _inc_counter_on_drop = IncCounterOnDrop::new(finished_critical_tasks_total_metrics);
+            let task = pin!(task);
+            let _ = select(on_shutdown, task).await;
+        };
+
+        self.metrics.inc_critical_tasks();
+        self.spawn_on_rt(task, TaskKind::Default)
+    }
+
+    /// This spawns a regular task onto the runtime, that is restarted according to the given
+    /// [`RestartPolicy`] if it panics.
+    ///
+    /// Every (re)start of the task creates a new future with the given closure, which receives a
+    /// [`GracefulShutdown`] signal. The [`TaskManager`] waits for the guard of the signal before
+    /// shutting down. If the task panics and must not be restarted again, it is given up without
+    /// notifying the [`TaskManager`].
+    pub fn spawn_with_restart_policy<F, Fut>(
+        &self,
+        name: &'static str,
+        policy: RestartPolicy,
+        f: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut(GracefulShutdown) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let on_shutdown = self.on_shutdown.clone();
+        let graceful_tasks = Arc::clone(&self.graceful_tasks);
+        let signal = move || {
+            GracefulShutdown::new(
+                on_shutdown.clone(),
+                GracefulShutdownGuard::new(Arc::clone(&graceful_tasks)),
+            )
+        };
+
+        let restarts = self.run_with_restarts(name, policy, signal, f);
+        let task = async move {
+            if let Some(task_error) = restarts.await {
+                error!("{task_error}, giving up");
+            }
+        }
+        .in_current_span();
+
+        self.handle.spawn(task)
+    }
+
+    /// Returns a future that runs the futures created by `f` with a new signal each, restarting
+    /// them according to the [`RestartPolicy`] if they panic.
+    ///
+    /// Resolves with the error of the last panic if the task must not be restarted again. No
+    /// restarts happen once the [Shutdown] signal was received.
+    fn run_with_restarts<S, F, Fut>(
+        &self,
+        name: &'static str,
+        policy: RestartPolicy,
+        mut signal: impl FnMut() -> S + Send + 'static,
+        mut f: F,
+    ) -> impl Future<Output = Option<PanickedTaskError>> + Send + 'static
+    where
+        F: FnMut(S) -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let on_shutdown = self.on_shutdown.clone();
+        let restarted_tasks = self.restarted_tasks.clone();
+        let metrics = self.metrics.clone();
+
+        async move {
+            let mut restart = 0;
+            loop {
+                let fut = f(signal());
+                let Err(error) = std::panic::AssertUnwindSafe(fut).catch_unwind().await else {
+                    return None
+                };
+                let task_error = PanickedTaskError::new(name, error);
+
+                restart += 1;
+                let Some(backoff) = policy.backoff(restart) else { return Some(task_error) };
+
+                warn!(?backoff, restart, "{task_error}, restarting");
+                metrics.inc_restarted_tasks();
+                restarted_tasks.notify(TaskRestarted {
+                    task_name: name,
+                    error: task_error.error,
+                    restart,
+                });
+
+                let sleep = pin!(tokio::time::sleep(backoff));
+                if let Either::Right(_) = select(sleep, on_shutdown.clone()).await {
+                    return None
+                }
+            }
+        }
+    }
+
     /// This spawns a critical task onto the runtime.
     ///
     /// If this task panics, the [`TaskManager`] is notified.
@@ -638,7 +782,8 @@ enum TaskKind {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::{sync::atomic::AtomicBool, time::Duration};
+    use futures_util::StreamExt;
+    use std::{future::poll_fn, sync::atomic::AtomicBool, time::Duration};
 
     #[test]
     fn test_cloneable() {
@@ -710,6 +855,41 @@ mod tests {
         handle.block_on(shutdown);
     }
 
+    #[test]
+    fn test_critical_with_restart_policy() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let mut restarts = executor.restarted_tasks();
+        let starts = Arc::new(AtomicUsize::new(0));
+        let c = starts.clone();
+        let policy = RestartPolicy::Restart { max_restarts: 2, backoff: Duration::from_millis(1) };
+        executor.spawn_critical_with_restart_policy("restarted task", policy, move |_| {
+            c.fetch_add(1, Ordering::SeqCst);
+            async { panic!("intentionally panic") }
+        });
+
+        runtime.block_on(async move {
+            let err = manager.await;
+            assert_eq!(err.task_name, "restarted task");
+            assert_eq!(starts.load(Ordering::SeqCst), 3);
+
+            for restart in 1..=2 {
+                let event = restarts.next().await.unwrap();
+                assert_eq!(
+                    event,
+                    TaskRestarted {
+                        task_name: "restarted task",
+                        error: Some("intentionally panic".to_string()),
+                        restart,
+                    }
+                );
+            }
+        })
+    }
+
     #[test]
     fn test_manager_graceful_shutdown() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
@@ -729,6 +909,66 @@ mod tests {
         assert!(val.load(Ordering::Relaxed));
     }
 
+    #[test]
+    fn test_restart_policy_gives_up() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let mut manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let starts = Arc::new(AtomicUsize::new(0));
+        let c = starts.clone();
+        let policy = RestartPolicy::Restart { max_restarts: 1, backoff: Duration::from_millis(1) };
+        let task = executor.spawn_with_restart_policy("restarted task", policy, move |_| {
+            c.fetch_add(1, Ordering::SeqCst);
+            async { panic!("intentionally panic") }
+        });
+
+        runtime.block_on(async move {
+            task.await.unwrap();
+            assert_eq!(starts.load(Ordering::SeqCst), 2);
+
+            // the task is not critical, so the manager isn't notified
+            poll_fn(|cx| {
+                assert!(manager.poll_unpin(cx).is_pending());
+                Poll::Ready(())
+            })
+            .await;
+        })
+    }
+
+    #[test]
+    fn test_restart_policy_graceful_shutdown() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let handle = runtime.handle().clone();
+        let manager = TaskManager::new(handle);
+        let executor = manager.executor();
+
+        let starts = Arc::new(AtomicUsize::new(0));
+        let val = Arc::new(AtomicBool::new(false));
+        let (s, c) = (starts.clone(), val.clone());
+        let policy = RestartPolicy::Restart { max_restarts: 1, backoff: Duration::from_millis(1) };
+        executor.spawn_with_restart_policy("restarted task", policy, move |shutdown| {
+            let start = s.fetch_add(1, Ordering::SeqCst);
+            let c = c.clone();
+            async move {
+                if start == 0 {
+                    panic!("intentionally panic")
+                }
+                let _guard = shutdown.await;
+                tokio::time::sleep(Duration::from_millis(200)).await;
+                c.store(true, Ordering::Relaxed);
+            }
+        });
+
+        // the restarted task holds the guard of its signal
+        while starts.load(Ordering::SeqCst) < 2 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        manager.graceful_shutdown();
+        assert!(val.load(Ordering::Relaxed));
+    }
+
     #[test]
     fn test_manager_graceful_shutdown_many() {
         let runtime = tokio::runtime::Runtime::new().unwrap();
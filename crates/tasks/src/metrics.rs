@@ -16,6 +16,8 @@ pub struct TaskExecutorMetrics {
     pub(crate) regular_tasks_total: Counter,
     /// Number of finished spawned regular tasks
     pub(crate) finished_regular_tasks_total: Counter,
+    /// Number of restarts of panicked tasks
+    pub(crate) restarted_tasks_total: Counter,
 }
 
 impl TaskExecutorMetrics {
@@ -28,6 +30,11 @@ impl TaskExecutorMetrics {
     pub(crate) fn inc_regular_tasks(&self) {
         self.regular_tasks_total.increment(1);
     }
+
+    /// Increments the counter for restarted tasks.
+    pub(crate) fn inc_restarted_tasks(&self) {
+        self.restarted_tasks_total.increment(1);
+    }
 }
 
 /// Helper type for increasing counters even if a task fails
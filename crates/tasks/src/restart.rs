@@ -0,0 +1,68 @@
+//! Restart policies for tasks.
+
+use std::time::Duration;
+
+/// Determines what happens when a task panics.
+///
+/// See [`TaskExecutor::spawn_critical_with_restart_policy`](crate::TaskExecutor::spawn_critical_with_restart_policy)
+/// and [`TaskExecutor::spawn_with_restart_policy`](crate::TaskExecutor::spawn_with_restart_policy).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RestartPolicy {
+    /// The task is not restarted. If it's a critical task, the
+    /// [`TaskManager`](crate::TaskManager) is notified, which takes down the node.
+    #[default]
+    Shutdown,
+    /// The task is restarted up to `max_restarts` times before it's treated like
+    /// [`RestartPolicy::Shutdown`].
+    Restart {
+        /// The maximum number of restarts.
+        max_restarts: usize,
+        /// The delay before the first restart, which is doubled on every restart.
+        backoff: Duration,
+    },
+}
+
+impl RestartPolicy {
+    /// Restarts the task up to `max_restarts` times, starting with a backoff of one second.
+    pub const fn restart(max_restarts: usize) -> Self {
+        Self::Restart { max_restarts, backoff: Duration::from_secs(1) }
+    }
+
+    /// Returns the delay before the given restart, or `None` if the task must not be restarted
+    /// again.
+    pub(crate) fn backoff(&self, restart: usize) -> Option<Duration> {
+        match *self {
+            Self::Shutdown => None,
+            Self::Restart { max_restarts, backoff } => {
+                let exponent = restart.saturating_sub(1).min(16) as u32;
+                (restart <= max_restarts).then(|| backoff.saturating_mul(1 << exponent))
+            }
+        }
+    }
+}
+
+/// Event emitted when a task panicked and was restarted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskRestarted {
+    /// The name of the task.
+    pub task_name: &'static str,
+    /// The panic message, if it could be downcasted to a string.
+    pub error: Option<String>,
+    /// The number of the restart, starting at one.
+    pub restart: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_backoff() {
+        let policy = RestartPolicy::Restart { max_restarts: 3, backoff: Duration::from_secs(1) };
+        assert_eq!(policy.backoff(1), Some(Duration::from_secs(1)));
+        assert_eq!(policy.backoff(2), Some(Duration::from_secs(2)));
+        assert_eq!(policy.backoff(3), Some(Duration::from_secs(4)));
+        assert_eq!(policy.backoff(4), None);
+        assert_eq!(RestartPolicy::Shutdown.backoff(1), None);
+    }
+}
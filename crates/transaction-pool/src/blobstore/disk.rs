@@ -1,11 +1,20 @@
 //! A simple diskstore for blobs
 
-use crate::blobstore::{BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize};
+use crate::blobstore::{
+    fill_blobs_and_proofs, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize,
+};
 use alloy_rlp::{Decodable, Encodable};
 use parking_lot::{Mutex, RwLock};
-use reth_primitives::{BlobTransactionSidecar, TxHash, B256};
+use reth_primitives::{
+    eip4844::kzg_to_versioned_hash, BlobAndProofV1, BlobTransactionSidecar, TxHash, B256,
+};
 use schnellru::{ByLength, LruMap};
-use std::{collections::HashSet, fmt, fs, io, path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, io,
+    path::PathBuf,
+    sync::Arc,
+};
 use tracing::{debug, trace};
 
 /// How many [`BlobTransactionSidecar`] to cache in memory.
@@ -28,12 +37,20 @@ impl DiskFileBlobStore {
         opts: DiskFileBlobStoreConfig,
     ) -> Result<Self, DiskFileBlobStoreError> {
         let blob_dir = blob_dir.into();
-        let DiskFileBlobStoreConfig { max_cached_entries, .. } = opts;
+        let DiskFileBlobStoreConfig { max_cached_entries, open } = opts;
         let inner = DiskFileBlobStoreInner::new(blob_dir, max_cached_entries);
 
         // initialize the blob store
-        inner.delete_all()?;
-        inner.create_blob_dir()?;
+        match open {
+            OpenDiskFileBlobStore::Clear => {
+                inner.delete_all()?;
+                inner.create_blob_dir()?;
+            }
+            OpenDiskFileBlobStore::ReIndex => {
+                inner.create_blob_dir()?;
+                inner.reindex()?;
+            }
+        }
 
         Ok(Self { inner: Arc::new(inner) })
     }
@@ -79,6 +96,7 @@ impl BlobStore for DiskFileBlobStore {
         let mut stat = BlobStoreCleanupStat::default();
         let mut subsize = 0;
         debug!(target:"txpool::blob", num_blobs=%txs_to_delete.len(), "Removing blobs from disk");
+        self.inner.versioned_hashes_to_txhash.lock().retain(|_, tx| !txs_to_delete.contains(tx));
         for tx in txs_to_delete {
             let path = self.inner.blob_disk_file(tx);
             let filesize = fs::metadata(&path).map_or(0, |meta| meta.len());
@@ -124,6 +142,13 @@ impl BlobStore for DiskFileBlobStore {
         self.inner.get_exact(txs)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        self.inner.get_by_versioned_hashes(versioned_hashes)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.size_tracker.data_size())
     }
@@ -136,6 +161,8 @@ impl BlobStore for DiskFileBlobStore {
 struct DiskFileBlobStoreInner {
     blob_dir: PathBuf,
     blob_cache: Mutex<LruMap<TxHash, BlobTransactionSidecar, ByLength>>,
+    /// Maps the versioned hashes of the stored blobs to their transaction hash.
+    versioned_hashes_to_txhash: Mutex<HashMap<B256, TxHash>>,
     size_tracker: BlobStoreSize,
     file_lock: RwLock<()>,
     txs_to_delete: RwLock<HashSet<B256>>,
@@ -147,6 +174,7 @@ impl DiskFileBlobStoreInner {
         Self {
             blob_dir,
             blob_cache: Mutex::new(LruMap::new(ByLength::new(max_length))),
+            versioned_hashes_to_txhash: Default::default(),
             size_tracker: Default::default(),
             file_lock: Default::default(),
             txs_to_delete: Default::default(),
@@ -172,10 +200,50 @@ impl DiskFileBlobStoreInner {
         Ok(())
     }
 
+    /// Indexes the blobs that are already stored on disk and accounts for their size.
+    ///
+    /// Files that can't be read or decoded are skipped.
+    fn reindex(&self) -> Result<(), DiskFileBlobStoreError> {
+        let entries = fs::read_dir(&self.blob_dir)
+            .map_err(|e| DiskFileBlobStoreError::Open(self.blob_dir.clone(), e))?;
+        let mut size = 0;
+        let mut num = 0;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(tx) = path.file_name().and_then(|name| name.to_str()?.parse::<B256>().ok())
+            else {
+                debug!(target:"txpool::blob", ?path, "Skipping unknown file in blob store");
+                continue
+            };
+            let data = match fs::read(&path) {
+                Ok(data) => data,
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to read blob file");
+                    continue
+                }
+            };
+            match BlobTransactionSidecar::decode(&mut data.as_slice()) {
+                Ok(sidecar) => {
+                    self.index_versioned_hashes(tx, &sidecar);
+                    size += data.len();
+                    num += 1;
+                }
+                Err(err) => {
+                    debug!(target:"txpool::blob", %err, ?tx, "Failed to decode blob file");
+                }
+            }
+        }
+        self.size_tracker.add_size(size);
+        self.size_tracker.inc_len(num);
+        debug!(target:"txpool::blob", blob_dir = ?self.blob_dir, num_blobs=%num, "Reindexed blob store");
+        Ok(())
+    }
+
     /// Ensures blob is in the blob cache and written to the disk.
     fn insert_one(&self, tx: B256, data: BlobTransactionSidecar) -> Result<(), BlobStoreError> {
         let mut buf = Vec::with_capacity(data.fields_len());
         data.encode(&mut buf);
+        self.index_versioned_hashes(tx, &data);
         self.blob_cache.lock().insert(tx, data);
         let size = self.write_one_encoded(tx, &buf)?;
 
@@ -198,6 +266,7 @@ impl DiskFileBlobStoreInner {
         {
             let mut cache = self.blob_cache.lock();
             for (tx, data) in txs {
+                self.index_versioned_hashes(tx, &data);
                 cache.insert(tx, data);
             }
         }
@@ -222,6 +291,14 @@ impl DiskFileBlobStoreInner {
         Ok(())
     }
 
+    /// Maps the versioned hashes of the blobs to the given transaction hash.
+    fn index_versioned_hashes(&self, tx: B256, data: &BlobTransactionSidecar) {
+        let mut index = self.versioned_hashes_to_txhash.lock();
+        for commitment in &data.commitments {
+            index.insert(kzg_to_versioned_hash(commitment.as_slice()), tx);
+        }
+    }
+
     /// Returns true if the blob for the given transaction hash is in the blob cache or on disk.
     fn contains(&self, tx: B256) -> Result<bool, BlobStoreError> {
         if self.blob_cache.lock().get(&tx).is_some() {
@@ -370,6 +447,43 @@ impl DiskFileBlobStoreInner {
 
         Ok(res)
     }
+
+    /// Retrieves the blobs and proofs for the given versioned hashes from the blob cache or disk.
+    ///
+    /// The entries of versioned hashes that aren't stored are `None`.
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        let mut result = vec![None; versioned_hashes.len()];
+        let txs = {
+            let index = self.versioned_hashes_to_txhash.lock();
+            versioned_hashes
+                .iter()
+                .filter_map(|versioned_hash| index.get(versioned_hash).copied())
+                .collect::<HashSet<_>>()
+        };
+
+        let mut cache_miss = Vec::new();
+        {
+            let mut cache = self.blob_cache.lock();
+            for tx in txs {
+                if let Some(sidecar) = cache.get(&tx) {
+                    fill_blobs_and_proofs(sidecar, versioned_hashes, &mut result);
+                } else {
+                    cache_miss.push(tx);
+                }
+            }
+        }
+        if cache_miss.is_empty() {
+            return Ok(result)
+        }
+        for (_, sidecar) in self.read_many_decoded(cache_miss) {
+            fill_blobs_and_proofs(&sidecar, versioned_hashes, &mut result);
+        }
+
+        Ok(result)
+    }
 }
 
 impl fmt::Debug for DiskFileBlobStoreInner {
@@ -445,6 +559,7 @@ pub enum OpenDiskFileBlobStore {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use reth_primitives::eip4844::{Blob, Bytes48};
     use std::sync::atomic::Ordering;
 
     fn tmp_store() -> (DiskFileBlobStore, tempfile::TempDir) {
@@ -499,4 +614,72 @@ mod tests {
         assert_eq!(store.data_size_hint(), Some(0));
         assert_eq!(store.inner.size_tracker.num_blobs.load(Ordering::Relaxed), 0);
     }
+
+    fn blob(byte: u8) -> Blob {
+        let mut blob = Blob::default();
+        blob[0] = byte;
+        blob
+    }
+
+    fn sidecar_with_versioned_hashes() -> (BlobTransactionSidecar, Vec<B256>) {
+        let sidecar = BlobTransactionSidecar {
+            blobs: vec![blob(1), blob(2)],
+            commitments: vec![Bytes48::repeat_byte(1), Bytes48::repeat_byte(2)],
+            proofs: vec![Bytes48::repeat_byte(3), Bytes48::repeat_byte(4)],
+        };
+        let versioned_hashes = sidecar
+            .commitments
+            .iter()
+            .map(|commitment| kzg_to_versioned_hash(commitment.as_slice()))
+            .collect();
+        (sidecar, versioned_hashes)
+    }
+
+    #[test]
+    fn disk_get_by_versioned_hashes() {
+        let (store, _dir) = tmp_store();
+
+        let (sidecar, versioned_hashes) = sidecar_with_versioned_hashes();
+        store.insert(TxHash::random(), sidecar.clone()).unwrap();
+
+        let request = [versioned_hashes[1], B256::random(), versioned_hashes[0]];
+        let expected = vec![
+            Some(BlobAndProofV1 { blob: Box::new(blob(2)), proof: sidecar.proofs[1] }),
+            None,
+            Some(BlobAndProofV1 { blob: Box::new(blob(1)), proof: sidecar.proofs[0] }),
+        ];
+        assert_eq!(store.get_by_versioned_hashes(&request).unwrap(), expected);
+
+        // read from disk
+        store.clear_cache();
+        assert_eq!(store.get_by_versioned_hashes(&request).unwrap(), expected);
+    }
+
+    #[test]
+    fn disk_reindex_on_reopen() {
+        let (store, dir) = tmp_store();
+
+        let (sidecar, versioned_hashes) = sidecar_with_versioned_hashes();
+        let tx = TxHash::random();
+        store.insert(tx, sidecar.clone()).unwrap();
+        let size = store.data_size_hint();
+        drop(store);
+
+        let store = DiskFileBlobStore::open(
+            dir.path(),
+            DiskFileBlobStoreConfig { open: OpenDiskFileBlobStore::ReIndex, ..Default::default() },
+        )
+        .unwrap();
+        assert!(!store.is_cached(&tx));
+        assert_eq!(store.blobs_len(), 1);
+        assert_eq!(store.data_size_hint(), size);
+        assert_eq!(
+            store.get_by_versioned_hashes(&versioned_hashes[..1]).unwrap(),
+            vec![Some(BlobAndProofV1 { blob: Box::new(blob(1)), proof: sidecar.proofs[0] })]
+        );
+
+        store.delete(tx).unwrap();
+        store.cleanup();
+        assert_eq!(store.get_by_versioned_hashes(&versioned_hashes[..1]).unwrap(), vec![None]);
+    }
 }
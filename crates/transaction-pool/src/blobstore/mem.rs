@@ -1,8 +1,9 @@
 use crate::blobstore::{
-    BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize, BlobTransactionSidecar,
+    fill_blobs_and_proofs, BlobStore, BlobStoreCleanupStat, BlobStoreError, BlobStoreSize,
+    BlobTransactionSidecar,
 };
 use parking_lot::RwLock;
-use reth_primitives::B256;
+use reth_primitives::{BlobAndProofV1, B256};
 use std::{collections::HashMap, sync::Arc};
 
 /// An in-memory blob store.
@@ -113,6 +114,20 @@ impl BlobStore for InMemoryBlobStore {
         Ok(items)
     }
 
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        let mut result = vec![None; versioned_hashes.len()];
+        for sidecar in self.inner.store.read().values() {
+            fill_blobs_and_proofs(sidecar, versioned_hashes, &mut result);
+            if result.iter().all(Option::is_some) {
+                break
+            }
+        }
+        Ok(result)
+    }
+
     fn data_size_hint(&self) -> Option<usize> {
         Some(self.inner.size_tracker.data_size())
     }
@@ -3,7 +3,9 @@
 pub use disk::{DiskFileBlobStore, DiskFileBlobStoreConfig, OpenDiskFileBlobStore};
 pub use mem::InMemoryBlobStore;
 pub use noop::NoopBlobStore;
-use reth_primitives::{BlobTransactionSidecar, B256};
+use reth_primitives::{
+    eip4844::kzg_to_versioned_hash, BlobAndProofV1, BlobTransactionSidecar, B256,
+};
 use std::{
     fmt,
     sync::atomic::{AtomicUsize, Ordering},
@@ -64,6 +66,15 @@ pub trait BlobStore: fmt::Debug + Send + Sync + 'static {
     /// Returns an error if any of the blobs are not found in the blob store.
     fn get_exact(&self, txs: Vec<B256>) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
 
+    /// Returns the blobs and proofs for the given versioned hashes, in the order they were
+    /// requested.
+    ///
+    /// The entry of a versioned hash is `None` if no blob with that versioned hash is in the store.
+    fn get_by_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError>;
+
     /// Data size of all transactions in the blob store.
     fn data_size_hint(&self) -> Option<usize>;
 
@@ -85,6 +96,26 @@ pub enum BlobStoreError {
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
+/// Fills the empty entries of `result` with the blobs and proofs of the sidecar whose versioned
+/// hashes are requested at the same index of `versioned_hashes`.
+pub(crate) fn fill_blobs_and_proofs(
+    sidecar: &BlobTransactionSidecar,
+    versioned_hashes: &[B256],
+    result: &mut [Option<BlobAndProofV1>],
+) {
+    for (i, commitment) in sidecar.commitments.iter().enumerate() {
+        let versioned_hash = kzg_to_versioned_hash(commitment.as_slice());
+        for (requested, entry) in versioned_hashes.iter().zip(result.iter_mut()) {
+            if entry.is_none() && *requested == versioned_hash {
+                *entry = Some(BlobAndProofV1 {
+                    blob: Box::new(sidecar.blobs[i]),
+                    proof: sidecar.proofs[i],
+                });
+            }
+        }
+    }
+}
+
 /// Keeps track of the size of the blob store.
 #[derive(Debug, Default)]
 pub(crate) struct BlobStoreSize {
@@ -46,6 +46,21 @@ impl BlobStoreCanonTracker {
         self.add_blocks(blob_txs);
     }
 
+    /// Stops tracking the given blocks.
+    pub fn remove_blocks(&mut self, block_numbers: impl IntoIterator<Item = BlockNumber>) {
+        for block_number in block_numbers {
+            self.blob_txs_in_blocks.remove(&block_number);
+        }
+    }
+
+    /// Stops tracking the blocks of the given chain.
+    ///
+    /// This is expected to be called with the old chain of a reorg, before the new chain is added
+    /// with [`Self::add_new_chain_blocks`].
+    pub fn remove_chain_blocks(&mut self, blocks: &ChainBlocks<'_>) {
+        self.remove_blocks(blocks.iter().map(|(num, _)| *num));
+    }
+
     /// Invoked when a block is finalized.
     ///
     /// This returns all blob transactions that were included in blocks that are now finalized.
@@ -98,4 +113,18 @@ mod tests {
             BlobStoreUpdates::Finalized(block2.into_iter().chain(block3).collect::<Vec<_>>())
         );
     }
+
+    #[test]
+    fn test_remove_reorged_blocks() {
+        let mut tracker = BlobStoreCanonTracker::default();
+
+        let block1 = vec![B256::random()];
+        tracker.add_block(1, block1.clone());
+        tracker.add_block(2, vec![B256::random()]);
+        tracker.add_block(3, vec![B256::random()]);
+
+        // blocks 2 and 3 are reorged out
+        tracker.remove_blocks([2, 3]);
+        assert_eq!(tracker.on_finalized_block(3), BlobStoreUpdates::Finalized(block1));
+    }
 }
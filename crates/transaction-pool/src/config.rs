@@ -1,6 +1,6 @@
-use crate::{PoolSize, TransactionOrigin};
+use crate::{EvictionPolicy, PoolSize, TransactionOrigin};
 use reth_primitives::{Address, EIP4844_TX_TYPE_ID};
-use std::collections::HashSet;
+use std::{collections::HashSet, sync::Arc, time::Duration};
 /// Guarantees max transactions for one sender, compatible with geth/erigon
 pub const TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER: usize = 16;
 
@@ -10,6 +10,16 @@ pub const TXPOOL_SUBPOOL_MAX_TXS_DEFAULT: usize = 10_000;
 /// The default maximum allowed size of the given subpool.
 pub const TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT: usize = 20;
 
+/// The default maximum number of local transactions tracked by the journal.
+pub const TXPOOL_LOCAL_JOURNAL_MAX_TXS_DEFAULT: usize = 4_096;
+
+/// The default maximum combined size of the local transactions tracked by the journal.
+pub const TXPOOL_LOCAL_JOURNAL_MAX_SIZE_MB_DEFAULT: usize = 16;
+
+/// The default duration a local transaction is tracked by the journal, compatible with the
+/// lifetime of geth's local transactions.
+pub const TXPOOL_LOCAL_JOURNAL_LIFETIME_DEFAULT: Duration = Duration::from_secs(3 * 60 * 60);
+
 /// Default price bump (in %) for the transaction pool underpriced check.
 pub const DEFAULT_PRICE_BUMP: u128 = 10;
 
@@ -31,11 +41,20 @@ pub struct PoolConfig {
     pub blob_limit: SubPoolLimit,
     /// Max number of executable transaction slots guaranteed per account
     pub max_account_slots: usize,
+    /// Max combined size (in bytes) of the transactions of an account, unlimited if unset.
+    pub max_account_size: Option<usize>,
     /// Price bump (in %) for the transaction pool underpriced check.
     pub price_bumps: PriceBumpConfig,
     /// How to handle locally received transactions:
     /// [`TransactionOrigin::Local`](crate::TransactionOrigin).
     pub local_transactions_config: LocalTransactionConfig,
+    /// Decides which transactions are evicted first when a sub-pool exceeds its limit.
+    ///
+    /// If unset, every sub-pool evicts by its own order: the pending sub-pool evicts roughly the
+    /// same number of transactions of each sender, the blob sub-pool the transactions with the
+    /// lowest priority, the basefee and queued sub-pools the transactions of the senders whose
+    /// last submission is the oldest.
+    pub eviction_policy: Option<Arc<dyn EvictionPolicy>>,
 }
 
 impl PoolConfig {
@@ -57,8 +76,10 @@ impl Default for PoolConfig {
             queued_limit: Default::default(),
             blob_limit: Default::default(),
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_account_size: None,
             price_bumps: Default::default(),
             local_transactions_config: Default::default(),
+            eviction_policy: None,
         }
     }
 }
@@ -139,6 +160,15 @@ pub struct LocalTransactionConfig {
     pub local_addresses: HashSet<Address>,
     /// Flag indicating whether local transactions should be propagated.
     pub propagate_local_transactions: bool,
+    /// Track the local transactions until they are mined, even if they are dropped from the pool.
+    ///
+    /// See [`rebroadcast_local_transactions`](crate::locals::rebroadcast_local_transactions).
+    pub journal: bool,
+    /// Max number and combined size of the transactions tracked by the journal. Local
+    /// transactions are not tracked while the journal is full.
+    pub journal_limit: SubPoolLimit,
+    /// How long a local transaction is tracked by the journal if it isn't mined.
+    pub journal_lifetime: Duration,
 }
 
 impl Default for LocalTransactionConfig {
@@ -147,6 +177,12 @@ impl Default for LocalTransactionConfig {
             no_exemptions: false,
             local_addresses: HashSet::default(),
             propagate_local_transactions: true,
+            journal: false,
+            journal_limit: SubPoolLimit::new(
+                TXPOOL_LOCAL_JOURNAL_MAX_TXS_DEFAULT,
+                TXPOOL_LOCAL_JOURNAL_MAX_SIZE_MB_DEFAULT * 1024 * 1024,
+            ),
+            journal_lifetime: TXPOOL_LOCAL_JOURNAL_LIFETIME_DEFAULT,
         }
     }
 }
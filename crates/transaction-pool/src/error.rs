@@ -1,6 +1,8 @@
 //! Transaction pool errors
 
-use reth_primitives::{Address, BlobTransactionValidationError, InvalidTransactionError, TxHash};
+use reth_primitives::{
+    Address, BlobTransactionValidationError, InvalidTransactionError, TxHash, B256,
+};
 
 /// Transaction pool result type.
 pub type PoolResult<T> = Result<T, PoolError>;
@@ -168,6 +170,49 @@ pub enum Eip4844PoolTransactionError {
     Eip4844NonceGap,
 }
 
+/// Represents errors that can happen when validating the conditions of a transaction that was
+/// submitted with `eth_sendRawTransactionConditional`.
+///
+/// See [`TransactionConditional`](reth_rpc_types::TransactionConditional).
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ConditionalTransactionError {
+    /// Thrown if checking the known accounts requires too many storage lookups.
+    #[error("too many known account storage lookups: have {have}, permitted {permitted}")]
+    TooManyKnownAccountLookups {
+        /// Number of storage lookups the conditions require
+        have: usize,
+        /// Maximum number of storage lookups
+        permitted: usize,
+    },
+    /// Thrown if the block number is outside of the block number range of the conditions.
+    #[error("block number {0} out of range")]
+    BlockNumberOutOfRange(u64),
+    /// Thrown if the block timestamp is outside of the timestamp range of the conditions.
+    #[error("block timestamp {0} out of range")]
+    TimestampOutOfRange(u64),
+    /// Thrown if the storage root of a known account doesn't match.
+    #[error("storage root of {0} doesn't match")]
+    StorageRootMismatch(Address),
+    /// Thrown if the value of a storage slot of a known account doesn't match.
+    #[error("storage slot {1} of {0} doesn't match")]
+    StorageSlotMismatch(Address, B256),
+}
+
+/// Represents errors of transactions that were rejected by a
+/// [`TransactionScreen`](crate::validate::TransactionScreen).
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ScreenedTransactionError {
+    /// Thrown if the sender of the transaction is denied.
+    #[error("sender {0} is denied")]
+    DeniedSender(Address),
+    /// Thrown if the recipient of the transaction is denied.
+    #[error("recipient {0} is denied")]
+    DeniedRecipient(Address),
+    /// Thrown if the transaction was rejected for any other reason.
+    #[error("transaction rejected: {0}")]
+    Rejected(String),
+}
+
 /// Represents errors that can happen when validating transactions for the pool
 ///
 /// See [`TransactionValidator`](crate::TransactionValidator).
@@ -205,6 +250,17 @@ pub enum InvalidPoolTransactionError {
     /// invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGasTooLow,
+    /// Thrown if the init code of a create transaction starts with the EOF magic but isn't a
+    /// valid EOF container.
+    #[error("invalid EOF init code")]
+    InvalidEofInitCode,
+    /// Thrown if the conditions the transaction was submitted with aren't met.
+    #[error(transparent)]
+    Conditional(#[from] ConditionalTransactionError),
+    /// Thrown if the transaction was rejected by a
+    /// [`TransactionScreen`](crate::validate::TransactionScreen).
+    #[error(transparent)]
+    Screened(#[from] ScreenedTransactionError),
 }
 
 // === impl InvalidPoolTransactionError ===
@@ -260,7 +316,16 @@ impl InvalidPoolTransactionError {
                 false
             }
             Self::IntrinsicGasTooLow => true,
+            Self::InvalidEofInitCode => true,
             Self::Overdraft => false,
+            Self::Conditional(_) => {
+                // conditions depend on the current block and state
+                false
+            }
+            Self::Screened(_) => {
+                // local policy
+                false
+            }
             Self::Other(err) => err.is_bad_transaction(),
             Self::Eip4844(eip4844_err) => {
                 match eip4844_err {
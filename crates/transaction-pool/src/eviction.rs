@@ -0,0 +1,139 @@
+//! Eviction policies that decide which transactions are dropped when a sub-pool is full.
+
+use crate::TransactionOrigin;
+use reth_primitives::Address;
+use std::{fmt, time::Duration};
+
+/// The default number of transactions of a sender before [`FeeEvictionPolicy`] lowers their
+/// scores.
+pub const DEFAULT_EVICTION_FREE_SLOTS: usize = 4;
+
+/// The default duration after which [`FeeEvictionPolicy`] halves the score of a transaction.
+pub const DEFAULT_EVICTION_HALF_LIFE: Duration = Duration::from_secs(60 * 60);
+
+/// A transaction that can be evicted from a full sub-pool, scored by an [`EvictionPolicy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvictionCandidate {
+    /// The sender of the transaction.
+    pub sender: Address,
+    /// The nonce of the transaction.
+    pub nonce: u64,
+    /// The max fee per gas the sender is willing to pay, the gas price for legacy transactions.
+    pub max_fee_per_gas: u128,
+    /// The tip the transaction pays at the base fee of the pending block, `None` if its max fee
+    /// per gas is below the base fee.
+    pub effective_tip_per_gas: Option<u128>,
+    /// How long the transaction has been in the pool.
+    pub age: Duration,
+    /// The heap allocated size of the transaction.
+    pub size: usize,
+    /// The number of transactions of the sender in the pool, including this one.
+    pub sender_transactions: usize,
+    /// Where the transaction originated from.
+    pub origin: TransactionOrigin,
+}
+
+/// Decides which transactions are evicted first when a sub-pool exceeds its limit.
+///
+/// The candidates of a sub-pool are the transactions with the highest nonce of each sender in the
+/// sub-pool, so that an eviction never leaves a nonce gap. The candidate with the lowest score is
+/// evicted first, but the transactions of local senders only after all other candidates. Evicting
+/// a transaction also evicts the transactions of the sender with a higher nonce from all
+/// sub-pools, after which the transaction with the next lower nonce becomes a candidate.
+///
+/// The policy of the pool is set with [`PoolConfig::eviction_policy`](crate::PoolConfig).
+pub trait EvictionPolicy: fmt::Debug + Send + Sync {
+    /// Returns the score of the candidate, lower scores are evicted first.
+    fn score(&self, candidate: &EvictionCandidate) -> u128;
+}
+
+/// An [`EvictionPolicy`] that scores the transactions by their max fee per gas, lowered for
+/// senders with many transactions in the pool and for old transactions.
+///
+/// The fee of a sender with more than `free_slots` transactions is scaled by
+/// `free_slots / sender_transactions`, so that the cheap transactions of a spam wave are evicted
+/// before the well-paying transactions of other senders, even if the spam pays a bit more. The
+/// score is halved for every `half_life` the transaction spent in the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeEvictionPolicy {
+    /// The number of transactions of a sender before their scores are lowered.
+    free_slots: usize,
+    /// The duration after which the score of a transaction is halved.
+    half_life: Option<Duration>,
+}
+
+impl FeeEvictionPolicy {
+    /// Creates a new policy that lowers the scores of senders with more than `free_slots`
+    /// transactions, with the [`DEFAULT_EVICTION_HALF_LIFE`].
+    pub const fn new(free_slots: usize) -> Self {
+        Self { free_slots, half_life: Some(DEFAULT_EVICTION_HALF_LIFE) }
+    }
+
+    /// Sets the duration after which the score of a transaction is halved, `None` to ignore the
+    /// age of the transactions.
+    pub const fn with_half_life(mut self, half_life: Option<Duration>) -> Self {
+        self.half_life = half_life;
+        self
+    }
+}
+
+impl Default for FeeEvictionPolicy {
+    fn default() -> Self {
+        Self::new(DEFAULT_EVICTION_FREE_SLOTS)
+    }
+}
+
+impl EvictionPolicy for FeeEvictionPolicy {
+    fn score(&self, candidate: &EvictionCandidate) -> u128 {
+        let free_slots = self.free_slots.max(1) as u128;
+        let sender_transactions = candidate.sender_transactions as u128;
+        let mut score = candidate.max_fee_per_gas;
+        if sender_transactions > free_slots {
+            score = score.saturating_mul(free_slots) / sender_transactions;
+        }
+
+        if let Some(half_life) = self.half_life.filter(|half_life| !half_life.is_zero()) {
+            let halvings = candidate.age.as_nanos() / half_life.as_nanos();
+            score = score.checked_shr(halvings.try_into().unwrap_or(u32::MAX)).unwrap_or_default();
+        }
+        score
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(max_fee_per_gas: u128, sender_transactions: usize, age: u64) -> EvictionCandidate {
+        EvictionCandidate {
+            sender: Address::ZERO,
+            nonce: 0,
+            max_fee_per_gas,
+            effective_tip_per_gas: None,
+            age: Duration::from_secs(age),
+            size: 0,
+            sender_transactions,
+            origin: TransactionOrigin::External,
+        }
+    }
+
+    #[test]
+    fn fee_eviction_score() {
+        let policy = FeeEvictionPolicy::new(4).with_half_life(Some(Duration::from_secs(60)));
+
+        assert_eq!(policy.score(&candidate(1_000, 1, 0)), 1_000);
+        assert_eq!(policy.score(&candidate(1_000, 4, 59)), 1_000);
+
+        // the fee of a sender with more than the free slots is scaled down
+        assert_eq!(policy.score(&candidate(1_000, 8, 0)), 500);
+        assert!(policy.score(&candidate(1_500, 16, 0)) < policy.score(&candidate(1_000, 1, 0)));
+
+        // the score is halved for every half life
+        assert_eq!(policy.score(&candidate(1_000, 1, 60)), 500);
+        assert_eq!(policy.score(&candidate(1_000, 8, 125)), 125);
+        assert_eq!(policy.score(&candidate(1_000, 1, 60 * 200)), 0);
+
+        let policy = policy.with_half_life(None);
+        assert_eq!(policy.score(&candidate(1_000, 1, 60 * 200)), 1_000);
+    }
+}
@@ -153,7 +153,10 @@
 use crate::{identifier::TransactionId, pool::PoolInner};
 use aquamarine as _;
 use reth_eth_wire_types::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, PooledTransactionsElement, TxHash, U256};
+use reth_primitives::{
+    Address, BlobAndProofV1, BlobTransactionSidecar, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, TxHash, B256, U256,
+};
 use reth_provider::StateProviderFactory;
 use std::{collections::HashSet, sync::Arc};
 use tokio::sync::mpsc::Receiver;
@@ -163,31 +166,41 @@ pub use crate::{
     blobstore::{BlobStore, BlobStoreError},
     config::{
         LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-        REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-        TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+        REPLACE_BLOB_PRICE_BUMP, TXPOOL_LOCAL_JOURNAL_LIFETIME_DEFAULT,
+        TXPOOL_LOCAL_JOURNAL_MAX_SIZE_MB_DEFAULT, TXPOOL_LOCAL_JOURNAL_MAX_TXS_DEFAULT,
+        TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+        TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
+    eviction::{
+        EvictionCandidate, EvictionPolicy, FeeEvictionPolicy, DEFAULT_EVICTION_FREE_SLOTS,
+        DEFAULT_EVICTION_HALF_LIFE,
+    },
     ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AllTransactionsEvents, FullTransactionEvent,
-        TransactionEvent, TransactionEvents,
+        SequencedEvent, TransactionEvent, TransactionEvents,
     },
     traits::*,
     validate::{
-        EthTransactionValidator, TransactionValidationOutcome, TransactionValidationTaskExecutor,
-        TransactionValidator, ValidPoolTransaction,
+        EthTransactionValidator, TransactionScreen, TransactionScreens,
+        TransactionValidationOutcome, TransactionValidationTaskExecutor, TransactionValidator,
+        ValidPoolTransaction, ValidatorStack,
     },
 };
 
 pub mod error;
+pub mod locals;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
 pub mod pool;
+pub mod prefetch;
 pub mod validate;
 
 pub mod blobstore;
 mod config;
+mod eviction;
 pub mod identifier;
 mod ordering;
 mod traits;
@@ -203,6 +216,16 @@ pub type EthTransactionPool<Client, S> = Pool<
     S,
 >;
 
+/// Type alias for the default ethereum transaction pool that runs [`TransactionScreen`]s before
+/// and after the validation of the transactions.
+pub type ScreenedEthTransactionPool<Client, S> = Pool<
+    TransactionValidationTaskExecutor<
+        ValidatorStack<EthTransactionValidator<Client, EthPooledTransaction>>,
+    >,
+    CoinbaseTipOrdering<EthPooledTransaction>,
+    S,
+>;
+
 /// A shareable, generic, customizable `TransactionPool` implementation.
 #[derive(Debug)]
 pub struct Pool<V, T: TransactionOrdering, S> {
@@ -484,6 +507,21 @@ where
         self.pool.get_transactions_by_sender(sender)
     }
 
+    fn get_all_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> AllPoolTransactions<Self::Transaction> {
+        self.pool.get_all_transactions_by_sender(sender)
+    }
+
+    fn get_transactions_page(
+        &self,
+        start: Option<Address>,
+        limit: usize,
+    ) -> (AllPoolTransactions<Self::Transaction>, Option<Address>) {
+        self.pool.get_transactions_page(start, limit)
+    }
+
     fn get_transactions_by_sender_and_nonce(
         &self,
         sender: Address,
@@ -501,6 +539,21 @@ where
         self.pool.get_transactions_by_origin(origin)
     }
 
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        self.pool.get_conditional_transactions()
+    }
+
+    fn tracked_local_transactions(&self) -> Vec<PooledTransactionsElementEcRecovered> {
+        self.pool.tracked_local_transactions()
+    }
+
+    fn untrack_local_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<PooledTransactionsElementEcRecovered> {
+        self.pool.untrack_local_transaction(tx_hash)
+    }
+
     fn unique_senders(&self) -> HashSet<Address> {
         self.pool.unique_senders()
     }
@@ -522,6 +575,13 @@ where
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError> {
         self.pool.blob_store().get_exact(tx_hashes)
     }
+
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        self.pool.blob_store().get_by_versioned_hashes(versioned_hashes)
+    }
 }
 
 impl<V, T, S> TransactionPoolExt for Pool<V, T, S>
@@ -0,0 +1,270 @@
+//! Rebroadcasting of the tracked local transactions.
+//!
+//! If the journal of the [`LocalTransactionConfig`](crate::LocalTransactionConfig) is enabled, the
+//! pool tracks the local transactions until they are mined, even if they are dropped from the
+//! pool, e.g. because the pool is full. Every few blocks the tracked transactions that are no
+//! longer in the pool are reinserted, which also propagates them to the peers again.
+
+use crate::{
+    error::PoolErrorKind, metrics::LocalRebroadcastMetrics, traits::TransactionPool,
+    TransactionOrigin,
+};
+use futures_util::{future::BoxFuture, FutureExt, Stream, StreamExt};
+use reth_primitives::{
+    Address, BlockHash, FromRecoveredPooledTransaction, PooledTransactionsElementEcRecovered,
+    TxHash,
+};
+use reth_provider::{CanonStateNotification, ProviderResult, StateProviderFactory};
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use tracing::{debug, warn};
+
+/// The default number of blocks between rebroadcasts of the tracked local transactions.
+pub const DEFAULT_REBROADCAST_INTERVAL: u64 = 10;
+
+/// Settings for rebroadcasting the tracked local transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalRebroadcastConfig {
+    /// Number of blocks between rebroadcasts of the tracked local transactions.
+    pub interval: u64,
+}
+
+impl LocalRebroadcastConfig {
+    /// Creates a new config that rebroadcasts the tracked local transactions every `interval`
+    /// blocks.
+    pub const fn new(interval: u64) -> Self {
+        Self { interval }
+    }
+}
+
+impl Default for LocalRebroadcastConfig {
+    fn default() -> Self {
+        Self::new(DEFAULT_REBROADCAST_INTERVAL)
+    }
+}
+
+/// Returns a spawnable future that rebroadcasts the tracked local transactions, see
+/// [`rebroadcast_local_transactions`].
+pub fn rebroadcast_local_transactions_future<Client, P, St>(
+    client: Client,
+    pool: P,
+    events: St,
+    config: LocalRebroadcastConfig,
+) -> BoxFuture<'static, ()>
+where
+    Client: StateProviderFactory + 'static,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    async move {
+        rebroadcast_local_transactions(client, pool, events, config).await;
+    }
+    .boxed()
+}
+
+/// Checks the tracked local transactions every [`LocalRebroadcastConfig::interval`] blocks.
+///
+/// The transactions whose nonce is below the nonce of their sender were mined or replaced and are
+/// no longer tracked. The ones that are no longer in the pool are reinserted, unless they became
+/// invalid. Senders whose tracked transactions can't be executed because of a nonce gap are
+/// reported, and the transactions after the gap are no longer tracked.
+pub async fn rebroadcast_local_transactions<Client, P, St>(
+    client: Client,
+    pool: P,
+    mut events: St,
+    config: LocalRebroadcastConfig,
+) where
+    Client: StateProviderFactory + 'static,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+{
+    let metrics = LocalRebroadcastMetrics::default();
+    let mut last_rebroadcast = None;
+
+    while let Some(event) = events.next().await {
+        let tip = event.tip();
+        if last_rebroadcast.is_some_and(|last| tip.number < last + config.interval.max(1)) {
+            continue
+        }
+        last_rebroadcast = Some(tip.number);
+
+        let tracked = pool.tracked_local_transactions();
+        metrics.tracked_transactions.set(tracked.len() as f64);
+        if tracked.is_empty() {
+            continue
+        }
+
+        let state_nonces = match state_nonces(&client, tip.hash(), &tracked) {
+            Ok(state_nonces) => state_nonces,
+            Err(err) => {
+                debug!(target: "txpool::locals", %err, "Failed to load nonces of local senders");
+                continue
+            }
+        };
+        let in_pool = |hash: &TxHash| pool.contains(hash);
+        let has_nonce =
+            |sender, nonce| pool.get_transactions_by_sender_and_nonce(sender, nonce).is_some();
+        let plan = RebroadcastPlan::new(tracked, &state_nonces, in_pool, has_nonce);
+
+        for (sender, nonce) in &plan.nonce_gaps {
+            warn!(
+                target: "txpool::locals",
+                %sender,
+                missing_nonce = %nonce,
+                "Local transactions are blocked by a nonce gap"
+            );
+        }
+        metrics.senders_with_nonce_gap.set(plan.nonce_gaps.len() as f64);
+
+        let mut untracked = plan.mined.len() + plan.blocked.len();
+        for hash in plan.mined.into_iter().chain(plan.blocked) {
+            pool.untrack_local_transaction(hash);
+        }
+
+        let reinsert = plan
+            .reinsert
+            .into_iter()
+            .map(<P as TransactionPool>::Transaction::from_recovered_pooled_transaction)
+            .collect::<Vec<_>>();
+        let mut reinserted = 0;
+        for result in pool.add_transactions(TransactionOrigin::Local, reinsert).await {
+            match result {
+                Ok(_) => reinserted += 1,
+                Err(err) if matches!(err.kind, PoolErrorKind::InvalidTransaction(_)) => {
+                    debug!(target: "txpool::locals", %err, "Local transaction became invalid");
+                    pool.untrack_local_transaction(err.hash);
+                    untracked += 1;
+                }
+                Err(err) => {
+                    debug!(target: "txpool::locals", %err, "Failed to reinsert local transaction")
+                }
+            }
+        }
+        metrics.reinserted_transactions.increment(reinserted);
+        metrics.untracked_transactions.increment(untracked as u64);
+
+        debug!(
+            target: "txpool::locals",
+            block = tip.number,
+            reinserted,
+            untracked,
+            "Rebroadcast local transactions"
+        );
+    }
+}
+
+/// Loads the nonces of the senders of the transactions at the given block.
+fn state_nonces<Client: StateProviderFactory>(
+    client: &Client,
+    block_hash: BlockHash,
+    transactions: &[PooledTransactionsElementEcRecovered],
+) -> ProviderResult<HashMap<Address, u64>> {
+    let state = client.history_by_block_hash(block_hash)?;
+    let senders = transactions.iter().map(|tx| tx.signer()).collect::<HashSet<_>>();
+    senders
+        .into_iter()
+        .map(|sender| Ok((sender, state.account_nonce(sender)?.unwrap_or_default())))
+        .collect()
+}
+
+/// What to do with the tracked local transactions at the state of a block.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RebroadcastPlan {
+    /// The transactions whose nonce is below the nonce of their sender.
+    mined: Vec<TxHash>,
+    /// The transactions that are not in the pool.
+    reinsert: Vec<PooledTransactionsElementEcRecovered>,
+    /// The transactions that can't be executed because of a nonce gap of their sender.
+    blocked: Vec<TxHash>,
+    /// The senders whose tracked transactions have a nonce gap, with the first missing nonce.
+    nonce_gaps: Vec<(Address, u64)>,
+}
+
+impl RebroadcastPlan {
+    /// Creates the plan for the tracked transactions, sorted by sender and nonce.
+    ///
+    /// A nonce is missing if there's neither a tracked transaction nor a transaction in the pool
+    /// with that nonce.
+    fn new(
+        tracked: Vec<PooledTransactionsElementEcRecovered>,
+        state_nonces: &HashMap<Address, u64>,
+        in_pool: impl Fn(&TxHash) -> bool,
+        has_nonce: impl Fn(Address, u64) -> bool,
+    ) -> Self {
+        let mut plan = Self::default();
+        // the next nonce of each sender that doesn't leave a gap
+        let mut next_nonces = HashMap::new();
+        for tx in tracked {
+            let sender = tx.signer();
+            let state_nonce = state_nonces.get(&sender).copied().unwrap_or_default();
+            if tx.nonce() < state_nonce {
+                plan.mined.push(*tx.hash());
+                continue
+            }
+
+            let next_nonce = match next_nonces.entry(sender) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => entry.insert(Some(state_nonce)),
+            };
+            let Some(next) = next_nonce else {
+                plan.blocked.push(*tx.hash());
+                continue
+            };
+            while *next < tx.nonce() && has_nonce(sender, *next) {
+                *next += 1;
+            }
+            if *next < tx.nonce() {
+                // a sender is reported once, with its first gap
+                plan.nonce_gaps.push((sender, *next));
+                plan.blocked.push(*tx.hash());
+                *next_nonce = None;
+                continue
+            }
+            *next = tx.nonce() + 1;
+
+            if !in_pool(tx.hash()) {
+                plan.reinsert.push(tx);
+            }
+        }
+        plan
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TransactionGenerator;
+    use reth_primitives::B256;
+
+    #[test]
+    fn rebroadcast_plan() {
+        let mut gen = TransactionGenerator::new(rand::thread_rng());
+        let signers = [B256::random(), B256::random()];
+        let mut transaction = |signer, nonce| -> PooledTransactionsElementEcRecovered {
+            let tx = gen.transaction().signer(signer).nonce(nonce).into_eip1559();
+            tx.into_ecrecovered().unwrap().try_into().unwrap()
+        };
+
+        // the first sender has nonce 1, the second sender has nonce 0 and a gap at nonce 1
+        let mined = transaction(signers[0], 0);
+        let pending = transaction(signers[0], 1);
+        let dropped = transaction(signers[0], 2);
+        let first = transaction(signers[1], 0);
+        let blocked = transaction(signers[1], 2);
+        let state_nonces = HashMap::from([(pending.signer(), 1), (first.signer(), 0)]);
+        let mut tracked =
+            vec![mined.clone(), pending.clone(), dropped.clone(), first.clone(), blocked.clone()];
+        tracked.sort_by_key(|tx| (tx.signer(), tx.nonce()));
+
+        let in_pool = [*pending.hash(), *first.hash(), *blocked.hash()];
+        let plan = RebroadcastPlan::new(
+            tracked,
+            &state_nonces,
+            |hash| in_pool.contains(hash),
+            |_, _| false,
+        );
+        assert_eq!(plan.mined, vec![*mined.hash()]);
+        assert_eq!(plan.reinsert, vec![dropped]);
+        assert_eq!(plan.nonce_gaps, vec![(first.signer(), 1)]);
+        assert_eq!(plan.blocked, vec![*blocked.hash()]);
+    }
+}
@@ -2,11 +2,16 @@
 
 use crate::{
     blobstore::{BlobStoreCanonTracker, BlobStoreUpdates},
-    error::PoolError,
+    error::{PoolError, PoolErrorKind},
     metrics::MaintainPoolMetrics,
-    traits::{CanonicalStateUpdate, ChangedAccount, TransactionPool, TransactionPoolExt},
+    traits::{
+        CanonicalStateUpdate, ChangedAccount, PoolTransaction, TransactionOrigin, TransactionPool,
+        TransactionPoolExt,
+    },
+    validate::{ensure_conditional_not_expired, ensure_known_accounts},
     BlockInfo,
 };
+use alloy_rlp::{length_of_length, BufMut, Decodable, Encodable, Header};
 use futures_util::{
     future::{BoxFuture, Fuse, FusedFuture},
     FutureExt, Stream, StreamExt,
@@ -14,20 +19,23 @@ use futures_util::{
 use reth_execution_types::ExecutionOutcome;
 use reth_fs_util::FsPathError;
 use reth_primitives::{
-    Address, BlockHash, BlockNumber, BlockNumberOrTag, FromRecoveredPooledTransaction,
-    IntoRecoveredTransaction, PooledTransactionsElementEcRecovered, TransactionSigned,
-    TryFromRecoveredTransaction,
+    Address, BlockHash, BlockNumber, BlockNumberOrTag, Bytes, FromRecoveredPooledTransaction,
+    IntoRecoveredTransaction, PooledTransactionsElement, PooledTransactionsElementEcRecovered,
+    SealedBlock, TransactionSigned, TryFromRecoveredTransaction, TxHash,
 };
 use reth_provider::{
-    BlockReaderIdExt, CanonStateNotification, ChainSpecProvider, ProviderError,
+    BlockReaderIdExt, CanonStateNotification, ChainBlocks, ChainSpecProvider, ProviderError,
     StateProviderFactory,
 };
+use reth_rpc_types::TransactionConditional;
 use reth_tasks::TaskSpawner;
 use std::{
     borrow::Borrow,
-    collections::HashSet,
+    collections::{BTreeMap, HashMap, HashSet},
     hash::{Hash, Hasher},
     path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
 };
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
@@ -38,17 +46,25 @@ pub struct MaintainPoolConfig {
     /// Maximum (reorg) depth we handle when updating the transaction pool: `new.number -
     /// last_seen.number`
     ///
+    /// Deeper updates, and reorgs that revert more blocks, are not applied incrementally. Instead,
+    /// all accounts of the pool are revalidated in the background.
+    ///
     /// Default: 64 (2 epochs)
     pub max_update_depth: u64,
     /// Maximum number of accounts to reload from state at once when updating the transaction pool.
     ///
     /// Default: 100
     pub max_reload_accounts: usize,
+    /// Maximum number of queued canonical state changes that are merged into a single update of
+    /// the transaction pool.
+    ///
+    /// Default: 16
+    pub max_update_batch: usize,
 }
 
 impl Default for MaintainPoolConfig {
     fn default() -> Self {
-        Self { max_update_depth: 64, max_reload_accounts: 100 }
+        Self { max_update_depth: 64, max_reload_accounts: 100, max_update_batch: 16 }
     }
 }
 
@@ -57,12 +73,24 @@ impl Default for MaintainPoolConfig {
 pub struct LocalTransactionBackupConfig {
     /// Path to transactions backup file
     pub transactions_path: Option<PathBuf>,
+    /// Path to the file the local and propagated transactions of the pool are persisted to, with
+    /// the sidecars of blob transactions.
+    ///
+    /// If set, the local transactions are persisted to this file instead of the backup file.
+    pub pool_transactions_path: Option<PathBuf>,
 }
 
 impl LocalTransactionBackupConfig {
     /// Receive path to transactions backup and return initialized config
     pub const fn with_local_txs_backup(transactions_path: PathBuf) -> Self {
-        Self { transactions_path: Some(transactions_path) }
+        Self { transactions_path: Some(transactions_path), pool_transactions_path: None }
+    }
+
+    /// Persists the local and propagated transactions of the pool to the given file on shutdown,
+    /// and reinserts them on startup.
+    pub fn with_pool_transactions(mut self, pool_transactions_path: PathBuf) -> Self {
+        self.pool_transactions_path = Some(pool_transactions_path);
+        self
     }
 }
 
@@ -102,7 +130,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     Tasks: TaskSpawner + 'static,
 {
     let metrics = MaintainPoolMetrics::default();
-    let MaintainPoolConfig { max_update_depth, max_reload_accounts, .. } = config;
+    let MaintainPoolConfig { max_update_depth, max_reload_accounts, max_update_batch } = config;
     // ensure the pool points to latest state
     if let Ok(Some(latest)) = client.header_by_number_or_tag(BlockNumberOrTag::Latest) {
         let latest = latest.seal_slow();
@@ -121,6 +149,10 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     // keeps track of mined blob transaction so we can clean finalized transactions
     let mut blob_store_tracker = BlobStoreCanonTracker::default();
 
+    // keeps track of the conditions of mined conditional transactions, so they are reinjected with
+    // their conditions after a reorg
+    let mut conditional_tracker = MinedConditionalTransactions::default();
+
     // keeps track of the latest finalized block
     let mut last_finalized_block =
         FinalizedBlockTracker::new(client.finalized_block_number().ok().flatten());
@@ -134,6 +166,12 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
     // the future that reloads accounts from state
     let mut reload_accounts_fut = Fuse::terminated();
 
+    // a queued canonical state change that couldn't be merged into the previous update
+    let mut next_event = None;
+
+    // whether the stream of canonical state changes ended while draining it
+    let mut events_ended = false;
+
     // The update loop that waits for new blocks and reorgs and performs pool updated
     // Listen for new chain events and derive the update action for the pool
     loop {
@@ -188,6 +226,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         if let Some(finalized) =
             last_finalized_block.update(client.finalized_block_number().ok().flatten())
         {
+            conditional_tracker.on_finalized_block(finalized);
             match blob_store_tracker.on_finalized_block(finalized) {
                 BlobStoreUpdates::None => {}
                 BlobStoreUpdates::Finalized(blobs) => {
@@ -205,21 +244,27 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         }
 
         // outcomes of the futures we are waiting on
-        let mut event = None;
+        let mut event = next_event.take();
         let mut reloaded = None;
 
-        // select of account reloads and new canonical state updates which should arrive at the rate
-        // of the block time (12s)
-        tokio::select! {
-            res = &mut reload_accounts_fut =>  {
-                reloaded = Some(res);
+        if event.is_none() {
+            if events_ended {
+                break
             }
-            ev = events.next() =>  {
-                 if ev.is_none() {
-                    // the stream ended, we are done
-                    break;
+
+            // select of account reloads and new canonical state updates which should arrive at the
+            // rate of the block time (12s)
+            tokio::select! {
+                res = &mut reload_accounts_fut =>  {
+                    reloaded = Some(res);
+                }
+                ev = events.next() =>  {
+                     if ev.is_none() {
+                        // the stream ended, we are done
+                        break;
+                    }
+                    event = ev;
                 }
-                event = ev;
             }
         }
 
@@ -246,7 +291,29 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
         }
 
         // handle the new block or reorg
-        let Some(event) = event else { continue };
+        let Some(mut event) = event else { continue };
+
+        // merge the canonical state changes that are already queued into a single update, so the
+        // pool catches up at once instead of being updated block by block after a stall
+        let mut batched = 1;
+        while batched < max_update_batch {
+            let next = match events.next().now_or_never() {
+                Some(Some(next)) => next,
+                Some(None) => {
+                    events_ended = true;
+                    break
+                }
+                None => break,
+            };
+            if let Some(next) = merge_canonical_state_changes(&mut event, next) {
+                next_event = Some(next);
+                break
+            }
+            batched += 1;
+        }
+        metrics.inc_batched_updates(batched - 1);
+
+        let update_start = Instant::now();
         match event {
             CanonStateNotification::Reorg { old, new } => {
                 let (old_blocks, old_state) = old.inner();
@@ -263,6 +330,16 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     maintained_state = MaintainedPoolState::Drifted;
                 }
 
+                // deep reorgs are not applied incrementally, all accounts of the pool are reloaded
+                // in the background instead, so that the pool isn't blocked on loading the state
+                let depth = old.len() as u64;
+                let is_deep_reorg = depth > max_update_depth;
+                if is_deep_reorg {
+                    debug!(target: "txpool", ?depth, "revalidating pool after deep reorg");
+                    metrics.inc_deep_reorgs();
+                    maintained_state = MaintainedPoolState::Drifted;
+                }
+
                 let chain_spec = client.chain_spec();
 
                 // fees for the next block: `new_tip+1`
@@ -283,8 +360,12 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     .map(|(a, _)| a)
                     .filter(|addr| !new_changed_accounts.contains(addr));
 
-                // for these we need to fetch the nonce+balance from the db at the new tip
-                let mut changed_accounts =
+                // for these we need to fetch the nonce+balance from the db at the new tip, unless
+                // the whole pool is revalidated anyway
+                let mut changed_accounts = if is_deep_reorg {
+                    dirty_addresses.extend(missing_changed_acc);
+                    vec![]
+                } else {
                     match load_accounts(client.clone(), new_tip.hash(), missing_changed_acc) {
                         Ok(LoadedAccounts { accounts, failed_to_load }) => {
                             // extend accounts we failed to load from database
@@ -303,7 +384,8 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                             dirty_addresses.extend(addresses);
                             vec![]
                         }
-                    };
+                    }
+                };
 
                 // also include all accounts from new chain
                 // we can use extend here because they are unique
@@ -314,35 +396,54 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
 
                 // update the pool then re-inject the pruned transactions
                 // find all transactions that were mined in the old chain but not in the new chain
-                let pruned_old_transactions = old_blocks
+                let mut pruned_old_transactions = Vec::new();
+                let mut pruned_conditional_transactions = Vec::new();
+                let mut reinserted_blob_transactions = HashSet::new();
+                let mut missing_blob_sidecars = 0;
+                let mut old_conditions = conditional_tracker.remove_chain_blocks(&old_blocks);
+                for tx in old_blocks
                     .transactions_ecrecovered()
                     .filter(|tx| !new_mined_transactions.contains(&tx.hash))
-                    .filter_map(|tx| {
-                        if tx.is_eip4844() {
-                            // reorged blobs no longer include the blob, which is necessary for
-                            // validating the transaction. Even though the transaction could have
-                            // been validated previously, we still need the blob in order to
-                            // accurately set the transaction's
-                            // encoded-length which is propagated over the network.
-                            pool.get_blob(tx.hash)
-                                .ok()
-                                .flatten()
-                                .and_then(|sidecar| {
-                                    PooledTransactionsElementEcRecovered::try_from_blob_transaction(
-                                        tx, sidecar,
-                                    )
-                                    .ok()
-                                })
-                                .map(
-                                    <P as TransactionPool>::Transaction::from_recovered_pooled_transaction,
-                                )
-                        } else {
-                            <P as TransactionPool>::Transaction::try_from_recovered_transaction(
-                                tx,
-                            ).ok()
+                {
+                    let tx_hash = tx.hash;
+                    let tx = if tx.is_eip4844() {
+                        // reorged blobs no longer include the blob, which is necessary for
+                        // validating the transaction. Even though the transaction could have
+                        // been validated previously, we still need the blob in order to
+                        // accurately set the transaction's
+                        // encoded-length which is propagated over the network.
+                        let Some(sidecar) = pool.get_blob(tx_hash).ok().flatten() else {
+                            // the sidecar is unknown if the transaction was never in the pool
+                            missing_blob_sidecars += 1;
+                            trace!(target: "txpool", ?tx_hash, "missing sidecar of reorged blob transaction");
+                            continue
+                        };
+                        let Ok(tx) =
+                            PooledTransactionsElementEcRecovered::try_from_blob_transaction(
+                                tx, sidecar,
+                            )
+                        else {
+                            continue
+                        };
+                        reinserted_blob_transactions.insert(tx_hash);
+                        <P as TransactionPool>::Transaction::from_recovered_pooled_transaction(tx)
+                    } else if let Ok(tx) =
+                        <P as TransactionPool>::Transaction::try_from_recovered_transaction(tx)
+                    {
+                        tx
+                    } else {
+                        continue
+                    };
+
+                    // conditional transactions are reinjected with their conditions, which are
+                    // validated again
+                    match old_conditions.remove(&tx_hash) {
+                        Some(conditional) => {
+                            pruned_conditional_transactions.extend(tx.with_conditional(conditional))
                         }
-                    })
-                    .collect::<Vec<_>>();
+                        None => pruned_old_transactions.push(tx),
+                    }
+                }
 
                 // update the pool first
                 let update = CanonicalStateUpdate {
@@ -353,19 +454,44 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     // all transactions mined in the new chain need to be removed from the pool
                     mined_transactions: new_blocks.transaction_hashes().collect(),
                 };
+                // the conditions of the mined transactions must be tracked before they are removed
+                conditional_tracker.add_new_chain_blocks(&pool, &new_blocks);
                 pool.on_canonical_state_change(update);
 
+                metrics.inc_expired_conditional_transactions(
+                    remove_expired_conditional_transactions(&client, &pool, &new_tip.block),
+                );
+
                 // all transactions that were mined in the old chain but not in the new chain need
                 // to be re-injected
                 //
                 // Note: we no longer know if the tx was local or external
                 // Because the transactions are not finalized, the corresponding blobs are still in
                 // blob store (if we previously received them from the network)
-                metrics.inc_reinserted_transactions(pruned_old_transactions.len());
-                let _ = pool.add_external_transactions(pruned_old_transactions).await;
+                metrics.inc_reinserted_transactions(
+                    pruned_old_transactions.len() + pruned_conditional_transactions.len(),
+                );
+                metrics.inc_missing_reorged_blob_sidecars(missing_blob_sidecars);
+                let reinsert = reinsert_reorged_transactions(
+                    pool.clone(),
+                    pruned_old_transactions,
+                    pruned_conditional_transactions,
+                    reinserted_blob_transactions,
+                );
+                if is_deep_reorg {
+                    // validating all transactions of a deep reorg can take a while, so this
+                    // shouldn't block the next update
+                    task_spawner.spawn(reinsert.boxed());
+                } else {
+                    reinsert.await;
+                }
 
-                // keep track of new mined blob transactions
+                // the blob transactions of the old chain are either reinserted into the pool or
+                // mined in the new chain, so only the new mined blob transactions are tracked
+                blob_store_tracker.remove_chain_blocks(&old_blocks);
                 blob_store_tracker.add_new_chain_blocks(&new_blocks);
+
+                metrics.record_update_duration(update_start.elapsed());
             }
             CanonStateNotification::Commit { new } => {
                 let (blocks, state) = new.inner();
@@ -406,6 +532,7 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     // keep track of mined blob transactions
                     blob_store_tracker.add_new_chain_blocks(&blocks);
 
+                    metrics.record_update_duration(update_start.elapsed());
                     continue
                 }
 
@@ -434,13 +561,177 @@ pub async fn maintain_transaction_pool<Client, P, St, Tasks>(
                     changed_accounts,
                     mined_transactions,
                 };
+                // the conditions of the mined transactions must be tracked before they are removed
+                conditional_tracker.add_new_chain_blocks(&pool, &blocks);
                 pool.on_canonical_state_change(update);
 
+                metrics.inc_expired_conditional_transactions(
+                    remove_expired_conditional_transactions(&client, &pool, &tip.block),
+                );
+
                 // keep track of mined blob transactions
                 blob_store_tracker.add_new_chain_blocks(&blocks);
+
+                metrics.record_update_duration(update_start.elapsed());
+            }
+        }
+    }
+}
+
+/// Removes all transactions from the pool whose conditions can't be met anymore after the given
+/// tip, because their block number or timestamp range ended or their known accounts don't match
+/// the state at the tip.
+///
+/// Returns the number of removed transactions, not counting their removed descendants.
+fn remove_expired_conditional_transactions<Client, P>(
+    client: &Client,
+    pool: &P,
+    tip: &SealedBlock,
+) -> usize
+where
+    Client: StateProviderFactory,
+    P: TransactionPool,
+{
+    let conditional_transactions = pool.get_conditional_transactions();
+    if conditional_transactions.is_empty() {
+        return 0
+    }
+
+    let state = match client.state_by_block_hash(tip.hash()) {
+        Ok(state) => state,
+        Err(err) => {
+            debug!(target: "txpool", %err, "failed to load state for checking conditional transactions");
+            return 0
+        }
+    };
+
+    let expired = conditional_transactions
+        .iter()
+        .filter(|tx| {
+            let Some(conditional) = tx.transaction.conditional() else { return false };
+            // the transaction is kept if the state can't be read
+            ensure_conditional_not_expired(conditional, tip.number, tip.timestamp).is_err() ||
+                matches!(ensure_known_accounts(conditional, &state), Ok(Err(_)))
+        })
+        .map(|tx| *tx.hash())
+        .collect::<Vec<_>>();
+    let count = expired.len();
+    if count > 0 {
+        debug!(target: "txpool", count, "removing expired conditional transactions");
+        pool.remove_transactions(expired);
+    }
+    count
+}
+
+/// Reinserts the transactions of a reorged chain into the pool, conditional transactions as
+/// [`TransactionOrigin::Private`] transactions.
+///
+/// The sidecars of the given blob transactions are removed from the blob store if the transactions
+/// are no longer valid.
+async fn reinsert_reorged_transactions<P>(
+    pool: P,
+    transactions: Vec<<P as TransactionPool>::Transaction>,
+    conditional_transactions: Vec<<P as TransactionPool>::Transaction>,
+    blob_transactions: HashSet<TxHash>,
+) where
+    P: TransactionPool,
+{
+    let mut outcome = pool.add_external_transactions(transactions).await;
+    if !conditional_transactions.is_empty() {
+        outcome.extend(
+            pool.add_transactions(TransactionOrigin::Private, conditional_transactions).await,
+        );
+    }
+
+    // the blobs of transactions that are no longer valid on the new chain are not
+    // tracked anymore, so they need to be removed from the blob store
+    let discarded_blobs = outcome
+        .into_iter()
+        .filter_map(Result::err)
+        .filter(|err| {
+            !matches!(err.kind, PoolErrorKind::AlreadyImported) &&
+                blob_transactions.contains(&err.hash)
+        })
+        .map(|err| err.hash)
+        .collect::<Vec<_>>();
+    if !discarded_blobs.is_empty() {
+        debug!(target: "txpool", discarded = discarded_blobs.len(), "failed to reinsert reorged blob transactions");
+        pool.delete_blobs(discarded_blobs);
+    }
+}
+
+/// Merges the `next` canonical state change into `batch` if it extends the new chain of `batch`,
+/// so that both can be applied to the pool as a single update.
+///
+/// Returns `next` if it can't be merged.
+fn merge_canonical_state_changes(
+    batch: &mut CanonStateNotification,
+    next: CanonStateNotification,
+) -> Option<CanonStateNotification> {
+    // reorgs are never merged into a previous update
+    let CanonStateNotification::Commit { new: next_chain } = next else { return Some(next) };
+
+    let (CanonStateNotification::Commit { new } | CanonStateNotification::Reorg { new, .. }) =
+        batch;
+    if new.tip().hash() != next_chain.fork_block().hash {
+        return Some(CanonStateNotification::Commit { new: next_chain })
+    }
+
+    let mut chain = Arc::unwrap_or_clone(std::mem::take(new));
+    chain.append_chain(Arc::unwrap_or_clone(next_chain)).expect("chains connect");
+    *new = Arc::new(chain);
+    None
+}
+
+/// Keeps the conditions of the mined conditional transactions until their block is finalized, so
+/// that they are reinjected with their conditions if their block is reorged.
+#[derive(Debug, Default)]
+struct MinedConditionalTransactions {
+    /// The conditions of the mined conditional transactions, by the block they were mined in.
+    by_block: BTreeMap<BlockNumber, HashMap<TxHash, TransactionConditional>>,
+}
+
+impl MinedConditionalTransactions {
+    /// Tracks the conditions of the conditional transactions of the pool that are mined in the
+    /// given blocks.
+    ///
+    /// This must be called before the mined transactions are removed from the pool.
+    fn add_new_chain_blocks<P>(&mut self, pool: &P, blocks: &ChainBlocks<'_>)
+    where
+        P: TransactionPool,
+    {
+        let mut conditional = pool
+            .get_conditional_transactions()
+            .into_iter()
+            .filter_map(|tx| Some((*tx.hash(), tx.transaction.conditional()?.clone())))
+            .collect::<HashMap<_, _>>();
+        if conditional.is_empty() {
+            return
+        }
+        for (number, block) in blocks.iter() {
+            let mined = block
+                .body
+                .iter()
+                .filter_map(|tx| Some((tx.hash, conditional.remove(&tx.hash)?)))
+                .collect::<HashMap<_, _>>();
+            if !mined.is_empty() {
+                self.by_block.insert(*number, mined);
             }
         }
     }
+
+    /// Stops tracking the given reorged blocks and returns the conditions of their transactions.
+    fn remove_chain_blocks(
+        &mut self,
+        blocks: &ChainBlocks<'_>,
+    ) -> HashMap<TxHash, TransactionConditional> {
+        blocks.iter().filter_map(|(number, _)| self.by_block.remove(number)).flatten().collect()
+    }
+
+    /// Stops tracking the conditions of transactions that were mined in finalized blocks.
+    fn on_finalized_block(&mut self, finalized_block: BlockNumber) {
+        self.by_block = self.by_block.split_off(&(finalized_block + 1));
+    }
 }
 
 struct FinalizedBlockTracker {
@@ -608,17 +899,17 @@ fn save_local_txs_backup<P>(pool: P, file_path: &Path)
 where
     P: TransactionPool,
 {
-    let local_transactions = pool.get_local_transactions();
+    let local_transactions = pool
+        .get_local_transactions()
+        .into_iter()
+        .map(|tx| tx.to_recovered_transaction().into_signed())
+        .chain(dropped_local_transactions(&pool).map(|tx| tx.into_transaction().into_transaction()))
+        .collect::<Vec<_>>();
     if local_transactions.is_empty() {
         trace!(target: "txpool", "no local transactions to save");
         return
     }
 
-    let local_transactions = local_transactions
-        .into_iter()
-        .map(|tx| tx.to_recovered_transaction().into_signed())
-        .collect::<Vec<_>>();
-
     let num_txs = local_transactions.len();
     let mut buf = Vec::new();
     alloy_rlp::encode_list(&local_transactions, &mut buf);
@@ -635,6 +926,227 @@ where
     }
 }
 
+/// Returns the tracked local transactions that are no longer in the pool, see
+/// [`LocalTransactionConfig::journal`](crate::LocalTransactionConfig::journal).
+///
+/// They are persisted with the local transactions of the pool, so they are tracked again once they
+/// are reinserted after a restart.
+fn dropped_local_transactions<P>(
+    pool: &P,
+) -> impl Iterator<Item = PooledTransactionsElementEcRecovered> + '_
+where
+    P: TransactionPool,
+{
+    pool.tracked_local_transactions().into_iter().filter(|tx| !pool.contains(tx.hash()))
+}
+
+/// A transaction of the pool that is persisted across restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PersistedTransaction {
+    /// Whether the transaction was added with [`TransactionOrigin::Local`].
+    local: bool,
+    /// The transaction, with the sidecar of a blob transaction.
+    transaction: PooledTransactionsElement,
+    /// The conditions the transaction was submitted with, JSON encoded.
+    ///
+    /// This is omitted from the encoding if the transaction has no conditions.
+    conditional: Option<Bytes>,
+}
+
+impl PersistedTransaction {
+    /// Creates a persisted transaction with the given conditions.
+    fn new(
+        local: bool,
+        transaction: PooledTransactionsElement,
+        conditional: Option<&TransactionConditional>,
+    ) -> Self {
+        let conditional = conditional
+            .and_then(|conditional| serde_json::to_vec(conditional).ok())
+            .map(Into::into);
+        Self { local, transaction, conditional }
+    }
+
+    /// Returns the decoded conditions of the transaction, if it has any.
+    fn conditional(&self) -> alloy_rlp::Result<Option<TransactionConditional>> {
+        self.conditional
+            .as_ref()
+            .map(|conditional| serde_json::from_slice(conditional))
+            .transpose()
+            .map_err(|_| alloy_rlp::Error::Custom("invalid transaction conditions"))
+    }
+
+    fn payload_length(&self) -> usize {
+        self.local.length() +
+            self.transaction.length() +
+            self.conditional.as_ref().map_or(0, Encodable::length)
+    }
+}
+
+impl Encodable for PersistedTransaction {
+    fn encode(&self, out: &mut dyn BufMut) {
+        Header { list: true, payload_length: self.payload_length() }.encode(out);
+        self.local.encode(out);
+        self.transaction.encode(out);
+        if let Some(conditional) = &self.conditional {
+            conditional.encode(out);
+        }
+    }
+
+    fn length(&self) -> usize {
+        let payload_length = self.payload_length();
+        payload_length + length_of_length(payload_length)
+    }
+}
+
+impl Decodable for PersistedTransaction {
+    fn decode(buf: &mut &[u8]) -> alloy_rlp::Result<Self> {
+        let header = Header::decode(buf)?;
+        if !header.list {
+            return Err(alloy_rlp::Error::UnexpectedString)
+        }
+        let remaining = buf.len();
+        let local = Decodable::decode(buf)?;
+        let transaction = Decodable::decode(buf)?;
+        // the conditions are optional, files of earlier versions don't have them
+        let conditional = if remaining - buf.len() < header.payload_length {
+            Some(Decodable::decode(buf)?)
+        } else {
+            None
+        };
+        let this = Self { local, transaction, conditional };
+        if remaining - buf.len() != header.payload_length {
+            return Err(alloy_rlp::Error::ListLengthMismatch {
+                expected: header.payload_length,
+                got: remaining - buf.len(),
+            })
+        }
+        Ok(this)
+    }
+}
+
+/// Loads the persisted transactions of the pool from a file and reinserts them into the pool on
+/// node boot up: conditional transactions with their conditions as private, other local
+/// transactions as local and all others as external transactions.
+///
+/// The transactions are validated again, so transactions that were mined or became invalid in the
+/// meantime are discarded. The file is removed after the transactions have been processed.
+async fn load_and_reinsert_pool_transactions<P>(
+    pool: P,
+    file_path: &Path,
+) -> Result<(), TransactionsBackupError>
+where
+    P: TransactionPool,
+{
+    if !file_path.exists() {
+        return Ok(())
+    }
+
+    debug!(target: "txpool", txs_file =?file_path, "Check persistent storage for saved pool transactions");
+    let data = reth_fs_util::read(file_path)?;
+
+    if data.is_empty() {
+        return Ok(())
+    }
+
+    let persisted: Vec<PersistedTransaction> = Decodable::decode(&mut data.as_slice())?;
+
+    let mut local = Vec::new();
+    let mut external = Vec::new();
+    let mut conditional = Vec::new();
+    for persisted in persisted {
+        let conditions = persisted.conditional()?;
+        let Ok(transaction) = persisted.transaction.try_into_ecrecovered() else { continue };
+        let transaction =
+            <P as TransactionPool>::Transaction::from_recovered_pooled_transaction(transaction);
+        if let Some(conditions) = conditions {
+            conditional.extend(transaction.with_conditional(conditions));
+        } else if persisted.local {
+            local.push(transaction);
+        } else {
+            external.push(transaction);
+        }
+    }
+
+    let num_local = pool
+        .add_transactions(TransactionOrigin::Local, local)
+        .await
+        .into_iter()
+        .filter(Result::is_ok)
+        .count();
+    let num_external = pool
+        .add_transactions(TransactionOrigin::External, external)
+        .await
+        .into_iter()
+        .filter(Result::is_ok)
+        .count();
+    let num_conditional = pool
+        .add_transactions(TransactionOrigin::Private, conditional)
+        .await
+        .into_iter()
+        .filter(Result::is_ok)
+        .count();
+
+    info!(target: "txpool", txs_file =?file_path, num_local, num_external, num_conditional, "Reinserted persisted pool transactions from file");
+    reth_fs_util::remove_file(file_path)?;
+    Ok(())
+}
+
+/// Writes the transactions of all sub-pools that are local, propagated or conditional to the file,
+/// with the sidecars of blob transactions and the conditions of conditional transactions.
+///
+/// The file is written to a temporary file first and then renamed, so that an interrupted write
+/// doesn't leave a truncated file behind.
+fn save_pool_transactions<P>(pool: P, file_path: &Path)
+where
+    P: TransactionPool,
+{
+    let mut seen = HashSet::new();
+    let transactions = pool
+        .get_local_transactions()
+        .into_iter()
+        .chain(pool.pooled_transactions())
+        .chain(pool.get_conditional_transactions())
+        .filter(|tx| seen.insert(*tx.hash()))
+        .filter_map(|tx| {
+            let transaction = pool.get_pooled_transaction_element(*tx.hash())?;
+            Some(PersistedTransaction::new(
+                tx.is_local(),
+                transaction,
+                tx.transaction.conditional(),
+            ))
+        })
+        .chain(
+            dropped_local_transactions(&pool)
+                .map(|tx| PersistedTransaction::new(true, tx.into_transaction(), None)),
+        )
+        .collect::<Vec<_>>();
+    if transactions.is_empty() {
+        trace!(target: "txpool", "no pool transactions to save");
+        return
+    }
+
+    let num_txs = transactions.len();
+    let mut buf = Vec::new();
+    alloy_rlp::encode_list(&transactions, &mut buf);
+    info!(target: "txpool", txs_file =?file_path, num_txs=%num_txs, "Saving current pool transactions");
+    let tmp_path = file_path.with_extension("tmp");
+    let written = file_path
+        .parent()
+        .map(reth_fs_util::create_dir_all)
+        .transpose()
+        .and_then(|_| reth_fs_util::write(&tmp_path, buf))
+        .and_then(|_| reth_fs_util::rename(&tmp_path, file_path));
+
+    match written {
+        Ok(()) => {
+            info!(target: "txpool", txs_file=?file_path, "Wrote pool transactions to file");
+        }
+        Err(err) => {
+            warn!(target: "txpool", %err, txs_file=?file_path, "Failed to write pool transactions to file");
+        }
+    }
+}
+
 /// Errors possible during txs backup load and decode
 #[derive(thiserror::Error, Debug)]
 pub enum TransactionsBackupError {
@@ -651,6 +1163,9 @@ pub enum TransactionsBackupError {
 
 /// Task which manages saving local transactions to the persistent file in case of shutdown.
 /// Reloads the transactions from the file on the boot up and inserts them into the pool.
+///
+/// If [`LocalTransactionBackupConfig::pool_transactions_path`] is set, the propagated
+/// transactions of the pool are saved and reloaded as well.
 pub async fn backup_local_transactions_task<P>(
     shutdown: reth_tasks::shutdown::GracefulShutdown,
     pool: P,
@@ -658,19 +1173,33 @@ pub async fn backup_local_transactions_task<P>(
 ) where
     P: TransactionPool + Clone,
 {
-    let Some(transactions_path) = config.transactions_path else {
+    let LocalTransactionBackupConfig { transactions_path, pool_transactions_path } = config;
+    if transactions_path.is_none() && pool_transactions_path.is_none() {
         // nothing to do
         return
-    };
+    }
 
-    if let Err(err) = load_and_reinsert_transactions(pool.clone(), &transactions_path).await {
-        error!(target: "txpool", "{}", err)
+    if let Some(transactions_path) = &transactions_path {
+        if let Err(err) = load_and_reinsert_transactions(pool.clone(), transactions_path).await {
+            error!(target: "txpool", "{}", err)
+        }
+    }
+    if let Some(pool_transactions_path) = &pool_transactions_path {
+        if let Err(err) =
+            load_and_reinsert_pool_transactions(pool.clone(), pool_transactions_path).await
+        {
+            error!(target: "txpool", "{}", err)
+        }
     }
 
     let graceful_guard = shutdown.await;
 
-    // write transactions to disk
-    save_local_txs_backup(pool, &transactions_path);
+    // write transactions to disk, the persisted pool transactions include the local ones
+    if let Some(pool_transactions_path) = &pool_transactions_path {
+        save_pool_transactions(pool, pool_transactions_path);
+    } else if let Some(transactions_path) = &transactions_path {
+        save_local_txs_backup(pool, transactions_path);
+    }
 
     drop(graceful_guard)
 }
@@ -679,12 +1208,17 @@ pub async fn backup_local_transactions_task<P>(
 mod tests {
     use super::*;
     use crate::{
-        blobstore::InMemoryBlobStore, validate::EthTransactionValidatorBuilder,
+        blobstore::InMemoryBlobStore, noop::MockTransactionValidator,
+        test_utils::TransactionGenerator, validate::EthTransactionValidatorBuilder,
         CoinbaseTipOrdering, EthPooledTransaction, Pool, PoolTransaction, TransactionOrigin,
     };
     use reth_chainspec::MAINNET;
+    use reth_execution_types::Chain;
     use reth_fs_util as fs;
-    use reth_primitives::{hex, PooledTransactionsElement, U256};
+    use reth_primitives::{
+        hex, BlobTransaction, BlobTransactionSidecar, Header, PooledTransactionsElement,
+        SealedBlock, SealedBlockWithSenders, B256, U256,
+    };
     use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
     use reth_tasks::TaskManager;
 
@@ -696,6 +1230,34 @@ mod tests {
         assert!(changed_acc.eq(&ChangedAccountEntry(copy)));
     }
 
+    #[test]
+    fn merge_canonical_state_changes_batch() {
+        let chain = |number: u64, parent_hash| {
+            let header = Header { number, parent_hash, ..Default::default() }.seal_slow();
+            let block = SealedBlock { header, ..Default::default() };
+            let block = SealedBlockWithSenders { block, senders: vec![] };
+            Arc::new(Chain::new([block], Default::default(), None))
+        };
+        let first = chain(1, B256::ZERO);
+        let second = chain(2, first.tip().hash());
+        let fork = chain(2, B256::random());
+
+        let mut batch = CanonStateNotification::Commit { new: first.clone() };
+        assert!(merge_canonical_state_changes(
+            &mut batch,
+            CanonStateNotification::Commit { new: second.clone() }
+        )
+        .is_none());
+        assert_eq!(batch.committed().range(), 1..=2);
+
+        // commits that don't extend the batch and reorgs are not merged
+        let next = CanonStateNotification::Commit { new: fork.clone() };
+        assert_eq!(merge_canonical_state_changes(&mut batch, next.clone()), Some(next));
+        let next = CanonStateNotification::Reorg { old: second, new: fork };
+        assert_eq!(merge_canonical_state_changes(&mut batch, next.clone()), Some(next));
+        assert_eq!(batch.committed().range(), 1..=2);
+    }
+
     const EXTENSION: &str = "rlp";
     const FILENAME: &str = "test_transactions_backup";
 
@@ -748,4 +1310,70 @@ mod tests {
 
         temp_dir.close().unwrap();
     }
+
+    #[tokio::test]
+    async fn persist_pool_transactions() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(FILENAME).with_extension(EXTENSION);
+        let new_pool = || {
+            Pool::new(
+                MockTransactionValidator::<EthPooledTransaction>::default(),
+                CoinbaseTipOrdering::default(),
+                InMemoryBlobStore::default(),
+                Default::default(),
+            )
+        };
+        let mut gen = TransactionGenerator::new(rand::thread_rng());
+        let blob = BlobTransaction::try_from_signed(
+            gen.transaction().signer(B256::random()).into_eip4844(),
+            BlobTransactionSidecar::default(),
+        )
+        .unwrap();
+        let blob = EthPooledTransaction::from_recovered_pooled_transaction(
+            PooledTransactionsElement::BlobTransaction(blob).try_into_ecrecovered().unwrap(),
+        );
+        let mut transaction = || {
+            let tx = gen.transaction().signer(B256::random()).into_eip1559();
+            EthPooledTransaction::try_from_recovered_transaction(tx.into_ecrecovered().unwrap())
+                .unwrap()
+        };
+        let (local, external, private) = (transaction(), transaction(), transaction());
+        let conditions =
+            TransactionConditional { block_number_max: Some(10), ..Default::default() };
+        let conditional = transaction().with_conditional(conditions.clone()).unwrap();
+
+        let txpool = new_pool();
+        txpool.add_transaction(TransactionOrigin::Local, local.clone()).await.unwrap();
+        txpool.add_transaction(TransactionOrigin::External, external.clone()).await.unwrap();
+        txpool.add_transaction(TransactionOrigin::Private, private.clone()).await.unwrap();
+        txpool.add_transaction(TransactionOrigin::Private, conditional.clone()).await.unwrap();
+        // the blob fee cap of the blob transaction is too low, so it's parked in the blob sub-pool
+        txpool.set_block_info(BlockInfo { pending_blob_fee: Some(1), ..txpool.block_info() });
+        txpool.add_transaction(TransactionOrigin::External, blob.clone()).await.unwrap();
+        assert_eq!(txpool.pool_size().blob, 1);
+        save_pool_transactions(txpool, &path);
+
+        let txpool = new_pool();
+        load_and_reinsert_pool_transactions(txpool.clone(), &path).await.unwrap();
+        assert!(!path.exists());
+
+        // private transactions are not propagated and therefore not persisted
+        assert!(txpool.get(local.hash()).unwrap().is_local());
+        assert!(!txpool.get(external.hash()).unwrap().is_local());
+        assert!(!txpool.contains(private.hash()));
+
+        // blob transactions are persisted with their sidecars
+        assert!(txpool.contains(blob.hash()));
+        assert_eq!(txpool.get_blob(*blob.hash()).unwrap(), Some(BlobTransactionSidecar::default()));
+
+        // conditional transactions are persisted with their conditions
+        let conditional = txpool.get(conditional.hash()).unwrap();
+        assert_eq!(conditional.origin, TransactionOrigin::Private);
+        assert_eq!(conditional.transaction.conditional(), Some(&conditions));
+        let indexed = txpool.get_conditional_transactions();
+        assert_eq!(
+            indexed.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(),
+            vec![*conditional.hash()]
+        );
+    }
 }
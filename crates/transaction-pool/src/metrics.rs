@@ -1,9 +1,10 @@
 //! Transaction pool metrics.
 
 use reth_metrics::{
-    metrics::{Counter, Gauge},
+    metrics::{Counter, Gauge, Histogram},
     Metrics,
 };
+use std::time::Duration;
 
 /// Transaction pool metrics
 #[derive(Metrics)]
@@ -68,8 +69,19 @@ pub struct MaintainPoolMetrics {
     pub(crate) drift_count: Counter,
     /// Number of transaction reinserted into the pool after reorg.
     pub(crate) reinserted_transactions: Counter,
+    /// Number of reorged blob transactions that could not be reinserted because their sidecar is
+    /// not in the blob store.
+    pub(crate) missing_reorged_blob_sidecars: Counter,
     /// Number of transactions finalized blob transactions we were tracking.
     pub(crate) deleted_tracked_finalized_blobs: Counter,
+    /// Time it takes to apply a canonical state change to the pool.
+    pub(crate) canonical_update_duration_seconds: Histogram,
+    /// Number of canonical state changes that were merged into a previous update of the pool.
+    pub(crate) batched_canonical_updates: Counter,
+    /// Number of reorgs that were too deep to be applied incrementally.
+    pub(crate) deep_reorgs: Counter,
+    /// Number of conditional transactions removed because their conditions can't be met anymore.
+    pub(crate) expired_conditional_transactions: Counter,
 }
 
 impl MaintainPoolMetrics {
@@ -83,6 +95,11 @@ impl MaintainPoolMetrics {
         self.reinserted_transactions.increment(count as u64);
     }
 
+    #[inline]
+    pub(crate) fn inc_missing_reorged_blob_sidecars(&self, count: usize) {
+        self.missing_reorged_blob_sidecars.increment(count as u64);
+    }
+
     #[inline]
     pub(crate) fn inc_deleted_tracked_blobs(&self, count: usize) {
         self.deleted_tracked_finalized_blobs.increment(count as u64);
@@ -92,6 +109,53 @@ impl MaintainPoolMetrics {
     pub(crate) fn inc_drift(&self) {
         self.drift_count.increment(1);
     }
+
+    #[inline]
+    pub(crate) fn record_update_duration(&self, duration: Duration) {
+        self.canonical_update_duration_seconds.record(duration.as_secs_f64());
+    }
+
+    #[inline]
+    pub(crate) fn inc_batched_updates(&self, count: usize) {
+        self.batched_canonical_updates.increment(count as u64);
+    }
+
+    #[inline]
+    pub(crate) fn inc_deep_reorgs(&self) {
+        self.deep_reorgs.increment(1);
+    }
+
+    #[inline]
+    pub(crate) fn inc_expired_conditional_transactions(&self, count: usize) {
+        self.expired_conditional_transactions.increment(count as u64);
+    }
+}
+
+/// Metrics of prefetching the trie paths of pending transactions
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.trie_prefetch")]
+pub struct TriePrefetchMetrics {
+    /// Number of accounts whose trie paths were prefetched
+    pub(crate) prefetched_accounts: Counter,
+    /// Number of storage slots whose trie paths were prefetched
+    pub(crate) prefetched_storage_slots: Counter,
+    /// Time it takes to prefetch the trie paths after a new block
+    pub(crate) prefetch_duration_seconds: Histogram,
+}
+
+/// Metrics of rebroadcasting the tracked local transactions
+#[derive(Metrics)]
+#[metrics(scope = "transaction_pool.locals")]
+pub struct LocalRebroadcastMetrics {
+    /// Number of tracked local transactions
+    pub(crate) tracked_transactions: Gauge,
+    /// Number of senders of tracked local transactions with a nonce gap
+    pub(crate) senders_with_nonce_gap: Gauge,
+    /// Number of tracked local transactions that were reinserted into the pool
+    pub(crate) reinserted_transactions: Counter,
+    /// Number of local transactions that are no longer tracked because they were mined or
+    /// became invalid
+    pub(crate) untracked_transactions: Counter,
 }
 
 /// All Transactions metrics
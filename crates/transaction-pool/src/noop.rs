@@ -17,7 +17,10 @@ use crate::{
     TransactionPool, TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
 };
 use reth_eth_wire_types::HandleMempoolData;
-use reth_primitives::{Address, BlobTransactionSidecar, TxHash, U256};
+use reth_primitives::{
+    Address, BlobAndProofV1, BlobTransactionSidecar, PooledTransactionsElementEcRecovered, TxHash,
+    B256, U256,
+};
 use std::{collections::HashSet, marker::PhantomData, sync::Arc};
 use tokio::sync::{mpsc, mpsc::Receiver};
 
@@ -203,6 +206,21 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn get_all_transactions_by_sender(
+        &self,
+        _sender: Address,
+    ) -> AllPoolTransactions<Self::Transaction> {
+        AllPoolTransactions::default()
+    }
+
+    fn get_transactions_page(
+        &self,
+        _start: Option<Address>,
+        _limit: usize,
+    ) -> (AllPoolTransactions<Self::Transaction>, Option<Address>) {
+        (AllPoolTransactions::default(), None)
+    }
+
     fn get_transactions_by_sender_and_nonce(
         &self,
         _sender: Address,
@@ -218,6 +236,21 @@ impl TransactionPool for NoopTransactionPool {
         vec![]
     }
 
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>> {
+        vec![]
+    }
+
+    fn tracked_local_transactions(&self) -> Vec<PooledTransactionsElementEcRecovered> {
+        vec![]
+    }
+
+    fn untrack_local_transaction(
+        &self,
+        _tx_hash: TxHash,
+    ) -> Option<PooledTransactionsElementEcRecovered> {
+        None
+    }
+
     fn unique_senders(&self) -> HashSet<Address> {
         Default::default()
     }
@@ -242,6 +275,13 @@ impl TransactionPool for NoopTransactionPool {
         }
         Err(BlobStoreError::MissingSidecar(tx_hashes[0]))
     }
+
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError> {
+        Ok(vec![None; versioned_hashes.len()])
+    }
 }
 
 /// A [`TransactionValidator`] that does nothing.
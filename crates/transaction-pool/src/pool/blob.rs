@@ -1,11 +1,14 @@
 use super::txpool::PendingFees;
 use crate::{
-    identifier::TransactionId, pool::size::SizeTracker, traits::BestTransactionsAttributes,
+    identifier::{SenderId, TransactionId},
+    pool::size::SizeTracker,
+    traits::BestTransactionsAttributes,
     PoolTransaction, SubPoolLimit, ValidPoolTransaction,
 };
+use rustc_hash::FxHashMap;
 use std::{
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
+    collections::{hash_map::Entry, BTreeMap, BTreeSet},
     sync::Arc,
 };
 
@@ -25,6 +28,8 @@ pub(crate) struct BlobTransactions<T: PoolTransaction> {
     by_id: BTreeMap<TransactionId, BlobTransaction<T>>,
     /// _All_ transactions sorted by blob priority.
     all: BTreeSet<BlobTransaction<T>>,
+    /// Keeps track of the number of transactions in the pool by the sender.
+    sender_transaction_count: FxHashMap<SenderId, usize>,
     /// Keeps track of the current fees, so transaction priority can be calculated on insertion.
     pending_fees: PendingFees,
     /// Keeps track of the size of this pool.
@@ -56,6 +61,7 @@ impl<T: PoolTransaction> BlobTransactions<T> {
 
         self.by_id.insert(id, transaction.clone());
         self.all.insert(transaction);
+        *self.sender_transaction_count.entry(id.sender).or_default() += 1;
     }
 
     fn next_id(&mut self) -> u64 {
@@ -73,6 +79,12 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         let tx = self.by_id.remove(id)?;
 
         self.all.remove(&tx);
+        if let Entry::Occupied(mut entry) = self.sender_transaction_count.entry(id.sender) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
 
         // keep track of size
         self.size_of -= tx.transaction.size();
@@ -238,6 +250,20 @@ impl<T: PoolTransaction> BlobTransactions<T> {
         self.by_id.contains_key(id)
     }
 
+    /// Returns the transactions with the highest nonce of each sender in the pool.
+    pub(crate) fn highest_nonces(&self) -> impl Iterator<Item = TransactionId> + '_ {
+        self.sender_transaction_count.keys().filter_map(|sender| self.highest_nonce(*sender))
+    }
+
+    /// Returns the transaction with the highest nonce of the sender in the pool.
+    pub(crate) fn highest_nonce(&self, sender: SenderId) -> Option<TransactionId> {
+        self.by_id
+            .range(..=TransactionId::new(sender, u64::MAX))
+            .next_back()
+            .map(|(id, _)| *id)
+            .filter(|id| id.sender == sender)
+    }
+
     /// Retrieves a transaction with the given ID from the pool, if it exists.
     fn get(&self, id: &TransactionId) -> Option<&BlobTransaction<T>> {
         self.by_id.get(id)
@@ -256,6 +282,7 @@ impl<T: PoolTransaction> Default for BlobTransactions<T> {
             submission_id: 0,
             by_id: Default::default(),
             all: Default::default(),
+            sender_transaction_count: Default::default(),
             size_of: Default::default(),
             pending_fees: Default::default(),
         }
@@ -5,6 +5,29 @@ use std::sync::Arc;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+/// An event of the pool with its sequence number.
+///
+/// The pool numbers all events in the order they happen, starting at zero, so every event of a
+/// [`AllTransactionsEvents`](crate::AllTransactionsEvents) stream has the successor of the
+/// sequence number of the previous event, unless events were missed. The events of a
+/// [`TransactionEvents`](crate::TransactionEvents) stream share the same numbering, but skip the
+/// events of other transactions.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SequencedEvent<E> {
+    /// The sequence number of the event.
+    pub sequence: u64,
+    /// The event.
+    pub event: E,
+}
+
+impl<E> SequencedEvent<E> {
+    /// Creates a new event with the sequence number.
+    pub const fn new(sequence: u64, event: E) -> Self {
+        Self { sequence, event }
+    }
+}
+
 /// An event that happened to a transaction and contains its full body where possible.
 #[derive(Debug)]
 pub enum FullTransactionEvent<T: PoolTransaction> {
@@ -0,0 +1,177 @@
+//! Journal of the local transactions.
+
+use crate::SubPoolLimit;
+use parking_lot::RwLock;
+use reth_primitives::{Address, PooledTransactionsElementEcRecovered, TxHash};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+/// Keeps the local transactions until they are mined, even if they are dropped from the pool.
+///
+/// There's at most one transaction per sender and nonce: a local replacement transaction replaces
+/// the tracked transaction. Transactions are no longer tracked once they are older than the
+/// configured lifetime, and new transactions aren't tracked while the journal is full.
+#[derive(Debug)]
+pub(crate) struct LocalTransactionJournal {
+    inner: RwLock<JournalInner>,
+    /// Max number and combined size of the tracked transactions.
+    limit: SubPoolLimit,
+    /// How long a transaction is tracked.
+    lifetime: Duration,
+}
+
+impl LocalTransactionJournal {
+    /// Creates a new journal with the given limits.
+    pub(crate) fn new(limit: SubPoolLimit, lifetime: Duration) -> Self {
+        Self { inner: Default::default(), limit, lifetime }
+    }
+
+    /// Tracks the transaction, replacing the tracked transaction of the same sender and nonce.
+    ///
+    /// Returns `false` if the transaction isn't tracked because the journal is full.
+    pub(crate) fn track(&self, transaction: PooledTransactionsElementEcRecovered) -> bool {
+        let key = (transaction.signer(), transaction.nonce());
+        let size = transaction.length_without_header();
+
+        let mut inner = self.inner.write();
+        let replaced = inner.transactions.get(&key).map(|tracked| tracked.size);
+        let count = inner.transactions.len() + usize::from(replaced.is_none());
+        let total_size = inner.size - replaced.unwrap_or_default() + size;
+        if self.limit.is_exceeded(count, total_size) {
+            return false
+        }
+
+        inner.remove(&key);
+        inner.by_hash.insert(*transaction.hash(), key);
+        inner.size += size;
+        inner.transactions.insert(key, TrackedTransaction::new(transaction, size));
+        true
+    }
+
+    /// Stops tracking the transaction, returns it if it was tracked.
+    pub(crate) fn untrack(&self, hash: &TxHash) -> Option<PooledTransactionsElementEcRecovered> {
+        let mut inner = self.inner.write();
+        let key = *inner.by_hash.get(hash)?;
+        inner.remove(&key)
+    }
+
+    /// Stops tracking the transactions that are tracked for longer than the configured lifetime.
+    ///
+    /// Returns the number of untracked transactions.
+    pub(crate) fn remove_expired(&self) -> usize {
+        let mut inner = self.inner.write();
+        let expired = inner
+            .transactions
+            .iter()
+            .filter(|(_, tracked)| tracked.tracked_at.elapsed() > self.lifetime)
+            .map(|(key, _)| *key)
+            .collect::<Vec<_>>();
+        for key in &expired {
+            inner.remove(key);
+        }
+        expired.len()
+    }
+
+    /// Returns all tracked transactions, sorted by sender and nonce.
+    pub(crate) fn transactions(&self) -> Vec<PooledTransactionsElementEcRecovered> {
+        self.inner.read().transactions.values().map(|tracked| tracked.transaction.clone()).collect()
+    }
+}
+
+/// The tracked transactions, indexed by sender and nonce and by hash.
+#[derive(Debug, Default)]
+struct JournalInner {
+    transactions: BTreeMap<(Address, u64), TrackedTransaction>,
+    by_hash: HashMap<TxHash, (Address, u64)>,
+    /// Combined size of the tracked transactions.
+    size: usize,
+}
+
+impl JournalInner {
+    fn remove(&mut self, key: &(Address, u64)) -> Option<PooledTransactionsElementEcRecovered> {
+        let tracked = self.transactions.remove(key)?;
+        self.by_hash.remove(tracked.transaction.hash());
+        self.size -= tracked.size;
+        Some(tracked.transaction)
+    }
+}
+
+/// A tracked transaction.
+#[derive(Debug)]
+struct TrackedTransaction {
+    transaction: PooledTransactionsElementEcRecovered,
+    /// Encoded size of the transaction.
+    size: usize,
+    /// When the transaction was tracked.
+    tracked_at: Instant,
+}
+
+impl TrackedTransaction {
+    fn new(transaction: PooledTransactionsElementEcRecovered, size: usize) -> Self {
+        Self { transaction, size, tracked_at: Instant::now() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TransactionGenerator;
+    use reth_primitives::B256;
+
+    fn journal() -> LocalTransactionJournal {
+        LocalTransactionJournal::new(SubPoolLimit::default(), Duration::from_secs(3600))
+    }
+
+    #[test]
+    fn tracks_one_transaction_per_nonce() {
+        let mut gen = TransactionGenerator::new(rand::thread_rng());
+        let signer = B256::random();
+        let mut transaction = |max_fee| -> PooledTransactionsElementEcRecovered {
+            let tx = gen.transaction().signer(signer).max_fee_per_gas(max_fee).into_eip1559();
+            tx.into_ecrecovered().unwrap().try_into().unwrap()
+        };
+        let journal = journal();
+
+        let tx = transaction(100);
+        assert!(journal.track(tx.clone()));
+        assert_eq!(journal.transactions(), vec![tx.clone()]);
+
+        // a replacement replaces the tracked transaction
+        let replacement = transaction(200);
+        assert!(journal.track(replacement.clone()));
+        assert_eq!(journal.transactions(), vec![replacement.clone()]);
+
+        assert!(journal.untrack(tx.hash()).is_none());
+        assert_eq!(journal.untrack(replacement.hash()), Some(replacement));
+        assert!(journal.transactions().is_empty());
+        assert_eq!(journal.inner.read().size, 0);
+    }
+
+    #[test]
+    fn respects_limits() {
+        let mut gen = TransactionGenerator::new(rand::thread_rng());
+        let signer = B256::random();
+        let mut transaction = |nonce| -> PooledTransactionsElementEcRecovered {
+            let tx = gen.transaction().signer(signer).nonce(nonce).into_eip1559();
+            tx.into_ecrecovered().unwrap().try_into().unwrap()
+        };
+
+        let journal = LocalTransactionJournal::new(SubPoolLimit::new(1, usize::MAX), Duration::MAX);
+        let first = transaction(0);
+        assert!(journal.track(first.clone()));
+        // the journal is full
+        assert!(!journal.track(transaction(1)));
+        // but a replacement is still tracked
+        let replacement = transaction(0);
+        assert!(journal.track(replacement.clone()));
+        assert_eq!(journal.transactions(), vec![replacement]);
+
+        let journal = LocalTransactionJournal::new(SubPoolLimit::default(), Duration::ZERO);
+        assert!(journal.track(first));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(journal.remove_expired(), 1);
+        assert!(journal.transactions().is_empty());
+    }
+}
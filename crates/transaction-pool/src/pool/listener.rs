@@ -1,17 +1,19 @@
 //! Listeners for the transaction-pool
 
 use crate::{
-    pool::events::{FullTransactionEvent, TransactionEvent},
+    pool::events::{FullTransactionEvent, SequencedEvent, TransactionEvent},
     traits::PropagateKind,
     PoolTransaction, ValidPoolTransaction,
 };
 use futures_util::Stream;
+use parking_lot::Mutex;
 use reth_primitives::{TxHash, B256};
 use std::{
-    collections::{hash_map::Entry, HashMap},
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    ops::Range,
     pin::Pin,
     sync::Arc,
-    task::{Context, Poll},
+    task::{ready, Context, Poll},
 };
 use tokio::sync::mpsc::{
     error::TrySendError, Receiver, Sender, UnboundedReceiver, UnboundedSender,
@@ -20,12 +22,16 @@ use tokio::sync::mpsc::{
 /// The size of the event channel used to propagate transaction events.
 const TX_POOL_EVENT_CHANNEL_SIZE: usize = 1024;
 
+/// The number of the most recent events that are kept to replay them to the listeners for all
+/// transactions that lagged behind.
+const TX_POOL_EVENT_REPLAY_BUFFER_SIZE: usize = 4 * TX_POOL_EVENT_CHANNEL_SIZE;
+
 /// A Stream that receives [`TransactionEvent`] only for the transaction with the given hash.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
 pub struct TransactionEvents {
     hash: TxHash,
-    events: UnboundedReceiver<TransactionEvent>,
+    events: UnboundedReceiver<SequencedEvent<TransactionEvent>>,
 }
 
 impl TransactionEvents {
@@ -33,6 +39,19 @@ impl TransactionEvents {
     pub const fn hash(&self) -> TxHash {
         self.hash
     }
+
+    /// Polls the next event with its sequence number.
+    pub fn poll_next_sequenced(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<SequencedEvent<TransactionEvent>>> {
+        self.events.poll_recv(cx)
+    }
+
+    /// Converts this into a stream that yields the events with their sequence numbers.
+    pub fn sequenced(mut self) -> impl Stream<Item = SequencedEvent<TransactionEvent>> + Unpin {
+        futures_util::stream::poll_fn(move |cx| self.poll_next_sequenced(cx))
+    }
 }
 
 impl Stream for TransactionEvents {
@@ -42,21 +61,79 @@ impl Stream for TransactionEvents {
         self: std::pin::Pin<&mut Self>,
         cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Option<Self::Item>> {
-        self.get_mut().events.poll_recv(cx)
+        self.get_mut().poll_next_sequenced(cx).map(|event| event.map(|event| event.event))
     }
 }
 
 /// A Stream that receives [`FullTransactionEvent`] for _all_ transaction.
+///
+/// The pool doesn't wait for slow listeners: events that don't fit into the channel of the
+/// listener are dropped. When the listener receives the next event, the events it missed are
+/// replayed first from a buffer of the most recent events of the pool. The events that are no
+/// longer buffered are lost, which leaves a gap in the sequence numbers of the events and is
+/// counted by [`AllTransactionsEvents::missed`].
+///
+/// The stream yields the events without their sequence numbers, see
+/// [`AllTransactionsEvents::sequenced`] for a stream that includes them.
 #[derive(Debug)]
 #[must_use = "streams do nothing unless polled"]
 pub struct AllTransactionsEvents<T: PoolTransaction> {
-    pub(crate) events: Receiver<FullTransactionEvent<T>>,
+    events: Receiver<SequencedEvent<FullTransactionEvent<T>>>,
+    /// The most recent events of the pool.
+    replay: EventReplayBuffer<T>,
+    /// The events to yield before receiving the next event.
+    queued: VecDeque<SequencedEvent<FullTransactionEvent<T>>>,
+    /// The sequence number of the next event, if any event was received.
+    next_sequence: Option<u64>,
+    /// The number of events that were missed and couldn't be replayed.
+    missed: u64,
 }
 
 impl<T: PoolTransaction> AllTransactionsEvents<T> {
-    /// Create a new instance of this stream.
-    pub const fn new(events: Receiver<FullTransactionEvent<T>>) -> Self {
-        Self { events }
+    /// Create a new instance of this stream, without replaying missed events.
+    pub fn new(events: Receiver<SequencedEvent<FullTransactionEvent<T>>>) -> Self {
+        Self::with_replay(events, EventReplayBuffer::default())
+    }
+
+    /// Creates a new instance of this stream that replays missed events from the buffer.
+    pub(crate) fn with_replay(
+        events: Receiver<SequencedEvent<FullTransactionEvent<T>>>,
+        replay: EventReplayBuffer<T>,
+    ) -> Self {
+        Self { events, replay, queued: VecDeque::new(), next_sequence: None, missed: 0 }
+    }
+
+    /// Returns the number of events this listener missed and that couldn't be replayed, because
+    /// the listener lagged too far behind.
+    pub const fn missed(&self) -> u64 {
+        self.missed
+    }
+
+    /// Polls the next event with its sequence number, replaying the missed events first.
+    pub fn poll_next_sequenced(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<SequencedEvent<FullTransactionEvent<T>>>> {
+        if self.queued.is_empty() {
+            let Some(event) = ready!(self.events.poll_recv(cx)) else { return Poll::Ready(None) };
+            if let Some(next) = self.next_sequence.filter(|next| event.sequence > *next) {
+                // the listener lagged, replay the missed events that are still buffered
+                self.queued = self.replay.events(next..event.sequence);
+                self.missed += event.sequence - next - self.queued.len() as u64;
+            }
+            self.queued.push_back(event);
+        }
+
+        let event = self.queued.pop_front().expect("queued is not empty");
+        self.next_sequence = Some(event.sequence + 1);
+        Poll::Ready(Some(event))
+    }
+
+    /// Converts this into a stream that yields the events with their sequence numbers.
+    pub fn sequenced(
+        mut self,
+    ) -> impl Stream<Item = SequencedEvent<FullTransactionEvent<T>>> + Unpin {
+        futures_util::stream::poll_fn(move |cx| self.poll_next_sequenced(cx))
     }
 }
 
@@ -64,7 +141,47 @@ impl<T: PoolTransaction> Stream for AllTransactionsEvents<T> {
     type Item = FullTransactionEvent<T>;
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        self.get_mut().events.poll_recv(cx)
+        self.get_mut().poll_next_sequenced(cx).map(|event| event.map(|event| event.event))
+    }
+}
+
+/// The most recent events of the pool, shared between the pool and the listeners for all
+/// transactions.
+#[derive(Debug)]
+pub(crate) struct EventReplayBuffer<T: PoolTransaction> {
+    events: Arc<Mutex<VecDeque<SequencedEvent<FullTransactionEvent<T>>>>>,
+}
+
+impl<T: PoolTransaction> EventReplayBuffer<T> {
+    /// Adds the event, evicting the oldest event if the buffer is full.
+    fn push(&self, event: SequencedEvent<FullTransactionEvent<T>>) {
+        let mut events = self.events.lock();
+        if events.len() == TX_POOL_EVENT_REPLAY_BUFFER_SIZE {
+            events.pop_front();
+        }
+        events.push_back(event);
+    }
+
+    /// Returns the buffered events with a sequence number in the range.
+    fn events(&self, sequences: Range<u64>) -> VecDeque<SequencedEvent<FullTransactionEvent<T>>> {
+        self.events
+            .lock()
+            .iter()
+            .filter(|event| sequences.contains(&event.sequence))
+            .cloned()
+            .collect()
+    }
+}
+
+impl<T: PoolTransaction> Clone for EventReplayBuffer<T> {
+    fn clone(&self) -> Self {
+        Self { events: Arc::clone(&self.events) }
+    }
+}
+
+impl<T: PoolTransaction> Default for EventReplayBuffer<T> {
+    fn default() -> Self {
+        Self { events: Default::default() }
     }
 }
 
@@ -78,6 +195,8 @@ pub(crate) struct PoolEventBroadcast<T: PoolTransaction> {
     all_events_broadcaster: AllPoolEventsBroadcaster<T>,
     /// All listeners for events for a certain transaction hash.
     broadcasters_by_hash: HashMap<TxHash, PoolEventBroadcaster>,
+    /// The sequence number of the next event.
+    next_sequence: u64,
 }
 
 impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
@@ -85,6 +204,7 @@ impl<T: PoolTransaction> Default for PoolEventBroadcast<T> {
         Self {
             all_events_broadcaster: AllPoolEventsBroadcaster::default(),
             broadcasters_by_hash: HashMap::default(),
+            next_sequence: 0,
         }
     }
 }
@@ -97,17 +217,21 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
         event: TransactionEvent,
         pool_event: FullTransactionEvent<T>,
     ) {
+        let sequence = self.next_sequence;
+        self.next_sequence += 1;
+
         // Broadcast to all listeners for the transaction hash.
         if let Entry::Occupied(mut sink) = self.broadcasters_by_hash.entry(*hash) {
-            sink.get_mut().broadcast(event.clone());
+            let is_final = event.is_final();
+            sink.get_mut().broadcast(SequencedEvent::new(sequence, event));
 
-            if sink.get().is_empty() || event.is_final() {
+            if sink.get().is_empty() || is_final {
                 sink.remove();
             }
         }
 
         // Broadcast to all listeners for all transactions.
-        self.all_events_broadcaster.broadcast(pool_event);
+        self.all_events_broadcaster.broadcast(SequencedEvent::new(sequence, pool_event));
     }
 
     /// Create a new subscription for the given transaction hash.
@@ -129,7 +253,7 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
     pub(crate) fn subscribe_all(&mut self) -> AllTransactionsEvents<T> {
         let (tx, rx) = tokio::sync::mpsc::channel(TX_POOL_EVENT_CHANNEL_SIZE);
         self.all_events_broadcaster.senders.push(tx);
-        AllTransactionsEvents::new(rx)
+        AllTransactionsEvents::with_replay(rx, self.all_events_broadcaster.replay.clone())
     }
 
     /// Notify listeners about a transaction that was added to the pending queue.
@@ -188,18 +312,27 @@ impl<T: PoolTransaction> PoolEventBroadcast<T> {
 #[derive(Debug)]
 struct AllPoolEventsBroadcaster<T: PoolTransaction> {
     /// Corresponding sender half(s) for event listener channel
-    senders: Vec<Sender<FullTransactionEvent<T>>>,
+    senders: Vec<Sender<SequencedEvent<FullTransactionEvent<T>>>>,
+    /// The most recent events, to replay the events a listener missed because its channel was
+    /// full.
+    replay: EventReplayBuffer<T>,
 }
 
 impl<T: PoolTransaction> Default for AllPoolEventsBroadcaster<T> {
     fn default() -> Self {
-        Self { senders: Vec::new() }
+        Self { senders: Vec::new(), replay: EventReplayBuffer::default() }
     }
 }
 
 impl<T: PoolTransaction> AllPoolEventsBroadcaster<T> {
     // Broadcast an event to all listeners. Dropped listeners are silently evicted.
-    fn broadcast(&mut self, event: FullTransactionEvent<T>) {
+    fn broadcast(&mut self, event: SequencedEvent<FullTransactionEvent<T>>) {
+        if self.senders.is_empty() {
+            return
+        }
+        // buffered before it's sent, so that it can be replayed as soon as a listener receives a
+        // later event
+        self.replay.push(event.clone());
         self.senders.retain(|sender| match sender.try_send(event.clone()) {
             Ok(_) | Err(TrySendError::Full(_)) => true,
             Err(TrySendError::Closed(_)) => false,
@@ -213,7 +346,7 @@ impl<T: PoolTransaction> AllPoolEventsBroadcaster<T> {
 #[derive(Default, Debug)]
 struct PoolEventBroadcaster {
     /// Corresponding sender half(s) for event listener channel
-    senders: Vec<UnboundedSender<TransactionEvent>>,
+    senders: Vec<UnboundedSender<SequencedEvent<TransactionEvent>>>,
 }
 
 impl PoolEventBroadcaster {
@@ -223,7 +356,56 @@ impl PoolEventBroadcaster {
     }
 
     // Broadcast an event to all listeners. Dropped listeners are silently evicted.
-    fn broadcast(&mut self, event: TransactionEvent) {
+    fn broadcast(&mut self, event: SequencedEvent<TransactionEvent>) {
         self.senders.retain(|sender| sender.send(event.clone()).is_ok())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use futures_util::FutureExt;
+    use std::future::poll_fn;
+
+    fn drain(events: &mut AllTransactionsEvents<MockTransaction>) -> Vec<u64> {
+        let mut sequences = Vec::new();
+        while let Some(Some(event)) = poll_fn(|cx| events.poll_next_sequenced(cx)).now_or_never() {
+            sequences.push(event.sequence);
+        }
+        sequences
+    }
+
+    #[test]
+    fn replays_missed_events() {
+        let mut broadcast = PoolEventBroadcast::<MockTransaction>::default();
+        let mut events = broadcast.subscribe_all();
+        let hash = B256::random();
+
+        // the events that don't fit into the channel are replayed with the next event
+        let lagged = TX_POOL_EVENT_CHANNEL_SIZE as u64 + 100;
+        for _ in 0..lagged {
+            broadcast.queued(&hash);
+        }
+        assert_eq!(drain(&mut events), (0..TX_POOL_EVENT_CHANNEL_SIZE as u64).collect::<Vec<_>>());
+        broadcast.queued(&hash);
+        assert_eq!(
+            drain(&mut events),
+            (TX_POOL_EVENT_CHANNEL_SIZE as u64..=lagged).collect::<Vec<_>>()
+        );
+        assert_eq!(events.missed(), 0);
+
+        // the events that are no longer buffered leave a gap, the buffer also holds the event that
+        // is received after the gap
+        let lost = 1_000;
+        for _ in 0..TX_POOL_EVENT_CHANNEL_SIZE + lost + TX_POOL_EVENT_REPLAY_BUFFER_SIZE - 1 {
+            broadcast.queued(&hash);
+        }
+        broadcast.queued(&hash);
+        let sequences = drain(&mut events);
+        assert!(sequences.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*sequences.last().unwrap(), broadcast.next_sequence - 1);
+        assert_eq!(events.missed(), lost as u64);
+        assert_eq!(sequences.len() as u64 + events.missed(), broadcast.next_sequence - lagged - 1);
+    }
+}
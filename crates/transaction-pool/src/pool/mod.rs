@@ -69,6 +69,7 @@ use crate::{
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     pool::{
+        journal::LocalTransactionJournal,
         listener::PoolEventBroadcast,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
@@ -85,7 +86,8 @@ use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{
     Address, BlobTransaction, BlobTransactionSidecar, IntoRecoveredTransaction,
-    PooledTransactionsElement, TransactionSigned, TxHash, B256,
+    PooledTransactionsElement, PooledTransactionsElementEcRecovered, TransactionSigned, TxHash,
+    B256,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -105,13 +107,14 @@ use crate::{
 };
 pub use best::BestTransactionFilter;
 pub use blob::{blob_tx_priority, fee_delta};
-pub use events::{FullTransactionEvent, TransactionEvent};
+pub use events::{FullTransactionEvent, SequencedEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
 
 mod best;
 mod blob;
+mod journal;
 mod listener;
 mod parked;
 pub(crate) mod pending;
@@ -149,6 +152,8 @@ where
     blob_transaction_sidecar_listener: Mutex<Vec<BlobTransactionSidecarListener>>,
     /// Metrics for the blob store
     blob_store_metrics: BlobStoreMetrics,
+    /// The local transactions that are tracked until they are mined.
+    local_journal: LocalTransactionJournal,
 }
 
 // === impl PoolInner ===
@@ -161,6 +166,10 @@ where
 {
     /// Create a new transaction pool instance.
     pub(crate) fn new(validator: V, ordering: T, blob_store: S, config: PoolConfig) -> Self {
+        let local_journal = LocalTransactionJournal::new(
+            config.local_transactions_config.journal_limit,
+            config.local_transactions_config.journal_lifetime,
+        );
         Self {
             identifiers: Default::default(),
             validator,
@@ -172,6 +181,7 @@ where
             config,
             blob_store,
             blob_store_metrics: Default::default(),
+            local_journal,
         }
     }
 
@@ -376,6 +386,14 @@ where
 
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
+        // mined local transactions and the ones tracked for too long are no longer tracked
+        if self.config.local_transactions_config.journal {
+            for hash in &mined_transactions {
+                self.local_journal.untrack(hash);
+            }
+            self.local_journal.remove_expired();
+        }
+
         // update the pool
         let outcome = self.pool.write().on_canonical_state_change(
             block_info,
@@ -423,9 +441,18 @@ where
                 transaction,
                 propagate,
             } => {
-                let sender_id = self.get_sender_id(transaction.sender());
+                let sender = transaction.sender();
+                let sender_id = self.get_sender_id(sender);
                 let transaction_id = TransactionId::new(sender_id, transaction.nonce());
 
+                // transactions with conditions are never tracked, a reinserted transaction would
+                // lose its conditions
+                let track = origin.is_local() &&
+                    self.config.local_transactions_config.journal &&
+                    transaction.transaction().conditional().is_none();
+                // peers would receive conditional transactions without their conditions
+                let propagate = propagate && transaction.transaction().conditional().is_none();
+
                 // split the valid transaction and the blob sidecar if it has any
                 let (transaction, maybe_sidecar) = match transaction {
                     ValidTransaction::Valid(tx) => (tx, None),
@@ -462,6 +489,20 @@ where
                     self.delete_blob(replaced);
                 }
 
+                // track local transactions, so they can be reinserted if they are dropped
+                if track {
+                    if let Some(pooled) = self.get_pooled_transaction_element(hash) {
+                        let tracked = self.local_journal.track(
+                            PooledTransactionsElementEcRecovered::from_signed_transaction(
+                                pooled, sender,
+                            ),
+                        );
+                        if !tracked {
+                            debug!(target: "txpool", %hash, "local transaction journal is full");
+                        }
+                    }
+                }
+
                 // Notify about new pending transactions
                 if let Some(pending) = added.as_pending() {
                     self.on_new_pending_transaction(pending);
@@ -689,6 +730,11 @@ where
         }
         let removed = self.pool.write().remove_transactions(hashes);
 
+        // removed transactions must not be reinserted by the rebroadcast of local transactions
+        for tx in &removed {
+            self.local_journal.untrack(tx.hash());
+        }
+
         let mut listener = self.event_listener.write();
 
         removed.iter().for_each(|tx| listener.discarded(tx.hash()));
@@ -725,6 +771,25 @@ where
         self.get_pool_data().get_transactions_by_sender(sender_id)
     }
 
+    /// Returns all transactions of the address grouped by whether they are pending or queued
+    pub(crate) fn get_all_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> AllPoolTransactions<T::Transaction> {
+        let sender_id = self.get_sender_id(sender);
+        self.get_pool_data().get_all_transactions_by_sender(sender_id)
+    }
+
+    /// Returns a page of the transactions of the pool, see [`TxPool::get_transactions_page`].
+    pub(crate) fn get_transactions_page(
+        &self,
+        start: Option<Address>,
+        limit: usize,
+    ) -> (AllPoolTransactions<T::Transaction>, Option<Address>) {
+        let start = start.map_or(SenderId::from(0), |start| self.get_sender_id(start));
+        self.get_pool_data().get_transactions_page(start, limit)
+    }
+
     /// Returns all transactions that where submitted with the given [`TransactionOrigin`]
     pub(crate) fn get_transactions_by_origin(
         &self,
@@ -733,6 +798,26 @@ where
         self.get_pool_data().all().transactions_iter().filter(|tx| tx.origin == origin).collect()
     }
 
+    /// Returns all transactions that were submitted with conditions.
+    pub(crate) fn get_conditional_transactions(
+        &self,
+    ) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
+        self.get_pool_data().all().conditional_transactions_iter().collect()
+    }
+
+    /// Returns the local transactions that are tracked until they are mined.
+    pub(crate) fn tracked_local_transactions(&self) -> Vec<PooledTransactionsElementEcRecovered> {
+        self.local_journal.transactions()
+    }
+
+    /// Stops tracking the local transaction.
+    pub(crate) fn untrack_local_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<PooledTransactionsElementEcRecovered> {
+        self.local_journal.untrack(&tx_hash)
+    }
+
     /// Returns all the transactions belonging to the hashes.
     ///
     /// If no transaction exists, it is skipped.
@@ -164,6 +164,20 @@ impl<T: ParkedOrd> ParkedPool<T> {
             .collect()
     }
 
+    /// Returns the transactions with the highest nonce of each sender in the pool.
+    pub(crate) fn highest_nonces(&self) -> impl Iterator<Item = TransactionId> + '_ {
+        self.sender_transaction_count.keys().filter_map(|sender| self.highest_nonce(*sender))
+    }
+
+    /// Returns the transaction with the highest nonce of the sender in the pool.
+    pub(crate) fn highest_nonce(&self, sender: SenderId) -> Option<TransactionId> {
+        self.by_id
+            .range(..=TransactionId::new(sender, u64::MAX))
+            .next_back()
+            .map(|(id, _)| *id)
+            .filter(|id| id.sender == sender)
+    }
+
     #[cfg(test)]
     pub(crate) fn get_senders_by_submission_id(
         &self,
@@ -578,6 +592,30 @@ mod tests {
         assert!(pool.is_empty());
     }
 
+    #[test]
+    fn test_parked_highest_nonces() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = ParkedPool::<BasefeeOrd<_>>::default();
+        let t = MockTransaction::eip1559();
+        let root_tx = f.validated_arc(t.clone());
+        let descendant_tx = f.validated_arc(t.inc_nonce());
+        let other_tx = f.validated_arc(MockTransaction::eip1559());
+        pool.add_transaction(root_tx.clone());
+        pool.add_transaction(descendant_tx.clone());
+        pool.add_transaction(other_tx.clone());
+
+        let mut highest = pool.highest_nonces().collect::<Vec<_>>();
+        highest.sort();
+        assert_eq!(highest, vec![*descendant_tx.id(), *other_tx.id()]);
+
+        // the ancestor becomes the highest nonce of the sender
+        pool.remove_transaction(descendant_tx.id());
+        assert_eq!(pool.highest_nonce(root_tx.sender_id()), Some(*root_tx.id()));
+        pool.remove_transaction(root_tx.id());
+        assert_eq!(pool.highest_nonce(root_tx.sender_id()), None);
+        assert_eq!(pool.highest_nonces().collect::<Vec<_>>(), vec![*other_tx.id()]);
+    }
+
     #[test]
     fn truncate_parked_by_submission_id() {
         // this test ensures that we evict from the pending pool by sender
@@ -512,6 +512,20 @@ impl<T: TransactionOrdering> PendingPool<T> {
             .collect()
     }
 
+    /// Returns the transactions with the highest nonce of each sender in the pool.
+    pub(crate) fn highest_nonces(&self) -> impl Iterator<Item = TransactionId> + '_ {
+        self.highest_nonces.iter().map(|tx| *tx.transaction.id())
+    }
+
+    /// Returns the transaction with the highest nonce of the sender in the pool.
+    pub(crate) fn highest_nonce(&self, sender: SenderId) -> Option<TransactionId> {
+        self.by_id
+            .range(..=TransactionId::new(sender, u64::MAX))
+            .next_back()
+            .map(|(id, _)| *id)
+            .filter(|id| id.sender == sender)
+    }
+
     /// Retrieves a transaction with the given ID from the pool, if it exists.
     fn get(&self, id: &TransactionId) -> Option<&PendingTransaction<T>> {
         self.by_id.get(id)
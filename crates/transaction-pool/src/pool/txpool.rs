@@ -3,6 +3,7 @@
 use crate::{
     config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{Eip4844PoolTransactionError, InvalidPoolTransactionError, PoolError, PoolErrorKind},
+    eviction::{EvictionCandidate, EvictionPolicy},
     identifier::{SenderId, TransactionId},
     metrics::{AllTransactionsMetrics, TxPoolMetrics},
     pool::{
@@ -14,7 +15,7 @@ use crate::{
         update::{Destination, PoolUpdate},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
+    traits::{AllPoolTransactions, BestTransactionsAttributes, BlockInfo, PoolSize},
     PoolConfig, PoolResult, PoolTransaction, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -27,8 +28,8 @@ use reth_primitives::{
 use rustc_hash::FxHashMap;
 use smallvec::SmallVec;
 use std::{
-    cmp::Ordering,
-    collections::{btree_map::Entry, hash_map, BTreeMap, HashMap, HashSet},
+    cmp::{Ordering, Reverse},
+    collections::{btree_map::Entry, hash_map, BTreeMap, BinaryHeap, HashMap, HashSet},
     fmt,
     ops::Bound::{Excluded, Unbounded},
     sync::Arc,
@@ -381,6 +382,52 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.txs_iter(sender).map(|(_, tx)| Arc::clone(&tx.transaction)).collect()
     }
 
+    /// Returns the transactions sent from the given sender, grouped like
+    /// [`Self::pending_transactions`] and [`Self::queued_transactions`].
+    pub(crate) fn get_all_transactions_by_sender(
+        &self,
+        sender: SenderId,
+    ) -> AllPoolTransactions<T::Transaction> {
+        let mut all = AllPoolTransactions::default();
+        for (_, tx) in self.all_transactions.txs_iter(sender) {
+            match tx.subpool {
+                SubPool::Pending => all.pending.push(Arc::clone(&tx.transaction)),
+                SubPool::BaseFee | SubPool::Queued => all.queued.push(Arc::clone(&tx.transaction)),
+                SubPool::Blob => {}
+            }
+        }
+        all
+    }
+
+    /// Returns the transactions of the senders starting with the given sender, in the order of
+    /// their [`SenderId`]s, grouped like [`Self::get_all_transactions_by_sender`].
+    ///
+    /// All transactions of a sender are on the same page, senders are added as long as the page
+    /// has at most `limit` transactions. Also returns the sender the next page starts with, if
+    /// there are more senders.
+    pub(crate) fn get_transactions_page(
+        &self,
+        start: SenderId,
+        limit: usize,
+    ) -> (AllPoolTransactions<T::Transaction>, Option<Address>) {
+        let mut page = AllPoolTransactions::default();
+        let mut len = 0;
+        let mut next = start.start_bound();
+        while let Some((id, tx)) = self.all_transactions.txs.range((next, Unbounded)).next() {
+            let AllPoolTransactions { pending, queued } =
+                self.get_all_transactions_by_sender(id.sender);
+            // the first sender is always included, so that every page makes progress
+            if len > 0 && len + pending.len() + queued.len() > limit {
+                return (page, Some(tx.transaction.sender()))
+            }
+            len += pending.len() + queued.len();
+            page.pending.extend(pending);
+            page.queued.extend(queued);
+            next = Excluded(TransactionId::new(id.sender, u64::MAX));
+        }
+        (page, None)
+    }
+
     /// Updates the transactions for the changed senders.
     pub(crate) fn update_accounts(
         &mut self,
@@ -758,6 +805,13 @@ impl<T: TransactionOrdering> TxPool<T> {
     pub(crate) fn discard_worst(&mut self) -> Vec<Arc<ValidPoolTransaction<T::Transaction>>> {
         let mut removed = Vec::new();
 
+        if let Some(policy) = self.config.eviction_policy.clone() {
+            for pool in [SubPool::Pending, SubPool::BaseFee, SubPool::Blob, SubPool::Queued] {
+                self.evict_with_policy(pool, &*policy, &mut removed);
+            }
+            return removed
+        }
+
         // Helper macro that discards the worst transactions for the pools
         macro_rules! discard_worst {
             ($this:ident, $removed:ident, [$($limit:ident => $pool:ident),* $(,)*]) => {
@@ -816,6 +870,104 @@ impl<T: TransactionOrdering> TxPool<T> {
         removed
     }
 
+    /// Evicts transactions from the sub-pool until it's within its limit, in the order of the
+    /// [`EvictionPolicy`].
+    ///
+    /// The candidates are the transactions with the highest nonce of each sender in the sub-pool,
+    /// the candidates of local senders are evicted last. The evicted transactions and their
+    /// descendants are added to `removed`.
+    fn evict_with_policy(
+        &mut self,
+        pool: SubPool,
+        policy: &dyn EvictionPolicy,
+        removed: &mut Vec<Arc<ValidPoolTransaction<T::Transaction>>>,
+    ) {
+        if !self.exceeds_limit(pool) {
+            return
+        }
+        trace!(target: "txpool", ?pool, ?policy, "evicting transactions with eviction policy");
+
+        let mut candidates = self
+            .highest_nonces(pool)
+            .into_iter()
+            .filter_map(|id| self.eviction_key(&id, policy))
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+
+        while self.exceeds_limit(pool) {
+            let Some(Reverse((_, _, id))) = candidates.pop() else { break };
+            let Some(tx) = self.remove_transaction(&id) else { continue };
+            removed.push(tx);
+            self.remove_descendants(&id, removed);
+
+            // the previous transaction of the sender in the sub-pool is the next candidate
+            let previous = self.highest_nonce(pool, id.sender);
+            if let Some(key) = previous.and_then(|id| self.eviction_key(&id, policy)) {
+                candidates.push(Reverse(key));
+            }
+        }
+        self.update_size_metrics();
+    }
+
+    /// Returns the key that orders the candidates for eviction: non-local transactions before
+    /// local transactions, then by the score of the [`EvictionPolicy`].
+    fn eviction_key(
+        &self,
+        id: &TransactionId,
+        policy: &dyn EvictionPolicy,
+    ) -> Option<(bool, u128, TransactionId)> {
+        let tx = &self.all_transactions.txs.get(id)?.transaction;
+        let candidate = EvictionCandidate {
+            sender: tx.sender(),
+            nonce: tx.nonce(),
+            max_fee_per_gas: tx.max_fee_per_gas(),
+            effective_tip_per_gas: tx
+                .effective_tip_per_gas(self.all_transactions.pending_fees.base_fee),
+            age: tx.timestamp.elapsed(),
+            size: tx.size(),
+            sender_transactions: self
+                .all_transactions
+                .tx_counter
+                .get(&id.sender)
+                .copied()
+                .unwrap_or_default(),
+            origin: tx.origin,
+        };
+        let is_local =
+            self.all_transactions.local_transactions_config.is_local(tx.origin, tx.sender());
+        Some((is_local, policy.score(&candidate), *id))
+    }
+
+    /// Returns the transactions with the highest nonce of each sender in the sub-pool.
+    fn highest_nonces(&self, pool: SubPool) -> Vec<TransactionId> {
+        match pool {
+            SubPool::Queued => self.queued_pool.highest_nonces().collect(),
+            SubPool::Pending => self.pending_pool.highest_nonces().collect(),
+            SubPool::BaseFee => self.basefee_pool.highest_nonces().collect(),
+            SubPool::Blob => self.blob_pool.highest_nonces().collect(),
+        }
+    }
+
+    /// Returns the transaction with the highest nonce of the sender in the sub-pool.
+    fn highest_nonce(&self, pool: SubPool, sender: SenderId) -> Option<TransactionId> {
+        match pool {
+            SubPool::Queued => self.queued_pool.highest_nonce(sender),
+            SubPool::Pending => self.pending_pool.highest_nonce(sender),
+            SubPool::BaseFee => self.basefee_pool.highest_nonce(sender),
+            SubPool::Blob => self.blob_pool.highest_nonce(sender),
+        }
+    }
+
+    /// Returns true if the sub-pool exceeds its configured limit.
+    fn exceeds_limit(&self, pool: SubPool) -> bool {
+        match pool {
+            SubPool::Queued => self.queued_pool.exceeds(&self.config.queued_limit),
+            SubPool::Pending => self.pending_pool.exceeds(&self.config.pending_limit),
+            SubPool::BaseFee => self.basefee_pool.exceeds(&self.config.basefee_limit),
+            SubPool::Blob => self.blob_pool.exceeds(&self.config.blob_limit),
+        }
+    }
+
     /// Number of transactions in the entire pool
     pub(crate) fn len(&self) -> usize {
         self.all_transactions.len()
@@ -897,8 +1049,12 @@ pub(crate) struct AllTransactions<T: PoolTransaction> {
     block_gas_limit: u64,
     /// Max number of executable transaction slots guaranteed per account
     max_account_slots: usize,
+    /// Max combined size of the transactions of an account, unlimited if unset
+    max_account_size: Option<usize>,
     /// _All_ transactions identified by their hash.
     by_hash: HashMap<TxHash, Arc<ValidPoolTransaction<T>>>,
+    /// Hashes of the transactions that were submitted with conditions.
+    conditional: HashSet<TxHash>,
     /// _All_ transaction in the pool sorted by their sender and nonce pair.
     txs: BTreeMap<TransactionId, PoolInternalTransaction<T>>,
     /// Tracks the number of transactions by sender that are currently in the pool.
@@ -922,6 +1078,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     fn new(config: &PoolConfig) -> Self {
         Self {
             max_account_slots: config.max_account_slots,
+            max_account_size: config.max_account_size,
             price_bumps: config.price_bumps,
             local_transactions_config: config.local_transactions_config.clone(),
             ..Default::default()
@@ -941,6 +1098,13 @@ impl<T: PoolTransaction> AllTransactions<T> {
         self.by_hash.values().cloned()
     }
 
+    /// Returns an iterator over all transactions in the pool that were submitted with conditions.
+    pub(crate) fn conditional_transactions_iter(
+        &self,
+    ) -> impl Iterator<Item = Arc<ValidPoolTransaction<T>>> + '_ {
+        self.conditional.iter().filter_map(|hash| self.by_hash.get(hash)).cloned()
+    }
+
     /// Returns if the transaction for the given hash is already included in this pool
     pub(crate) fn contains(&self, tx_hash: &TxHash) -> bool {
         self.by_hash.contains_key(tx_hash)
@@ -1240,6 +1404,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         tx_hash: &B256,
     ) -> Option<(Arc<ValidPoolTransaction<T>>, SubPool)> {
         let tx = self.by_hash.remove(tx_hash)?;
+        self.conditional.remove(tx_hash);
         let internal = self.txs.remove(&tx.transaction_id)?;
         // decrement the counter for the sender.
         self.tx_decr(tx.sender_id());
@@ -1261,6 +1426,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
         // decrement the counter for the sender.
         self.tx_decr(internal.transaction.sender_id());
 
+        self.conditional.remove(internal.transaction.hash());
         let result =
             self.by_hash.remove(internal.transaction.hash()).map(|tx| (tx, internal.subpool));
 
@@ -1288,7 +1454,7 @@ impl<T: PoolTransaction> AllTransactions<T> {
     ///
     /// This will enforce all additional rules in the context of this pool, such as:
     ///   - Spam protection: reject new non-local transaction from a sender that exhausted its slot
-    ///     capacity.
+    ///     or size capacity.
     ///   - Gas limit: reject transactions if they exceed a block's maximum gas.
     ///   - Ensures transaction types are not conflicting for the sender: blob vs normal
     ///     transactions are mutually exclusive for the same sender.
@@ -1297,9 +1463,20 @@ impl<T: PoolTransaction> AllTransactions<T> {
         transaction: ValidPoolTransaction<T>,
     ) -> Result<ValidPoolTransaction<T>, InsertErr<T>> {
         if !self.local_transactions_config.is_local(transaction.origin, transaction.sender()) {
+            // a replacement doesn't take an additional slot, and the size of the replaced
+            // transaction doesn't count
+            let is_replacement = self.txs.contains_key(transaction.id());
             let current_txs =
                 self.tx_counter.get(&transaction.sender_id()).copied().unwrap_or_default();
-            if current_txs >= self.max_account_slots {
+            let exceeds_size = self.max_account_size.is_some_and(|max_size| {
+                let current_size = self
+                    .txs_iter(transaction.sender_id())
+                    .filter(|(id, _)| *id != transaction.id())
+                    .map(|(_, tx)| tx.transaction.size())
+                    .sum::<usize>();
+                current_size + transaction.size() > max_size
+            });
+            if (!is_replacement && current_txs >= self.max_account_slots) || exceeds_size {
                 return Err(InsertErr::ExceededSenderTransactionsCapacity {
                     transaction: Arc::new(transaction),
                 })
@@ -1530,6 +1707,9 @@ impl<T: PoolTransaction> AllTransactions<T> {
         match self.txs.entry(*transaction.id()) {
             Entry::Vacant(entry) => {
                 // Insert the transaction in both maps
+                if pool_tx.transaction.transaction.conditional().is_some() {
+                    self.conditional.insert(*pool_tx.transaction.hash());
+                }
                 self.by_hash.insert(*pool_tx.transaction.hash(), pool_tx.transaction.clone());
                 entry.insert(pool_tx);
             }
@@ -1550,6 +1730,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
                 let new_transaction = pool_tx.transaction.clone();
                 let replaced = entry.insert(pool_tx);
                 self.by_hash.remove(replaced.transaction.hash());
+                self.conditional.remove(replaced.transaction.hash());
+                if new_transaction.transaction.conditional().is_some() {
+                    self.conditional.insert(new_hash);
+                }
                 self.by_hash.insert(new_hash, new_transaction);
                 // also remove the hash
                 replaced_tx = Some((replaced.transaction, replaced.subpool));
@@ -1660,6 +1844,10 @@ impl<T: PoolTransaction> AllTransactions<T> {
     #[cfg(any(test, feature = "test-utils"))]
     pub(crate) fn assert_invariants(&self) {
         assert_eq!(self.by_hash.len(), self.txs.len(), "by_hash.len() != txs.len()");
+        assert!(
+            self.conditional.iter().all(|hash| self.by_hash.contains_key(hash)),
+            "conditional transaction not in by_hash"
+        );
     }
 }
 
@@ -1677,9 +1865,11 @@ impl<T: PoolTransaction> Default for AllTransactions<T> {
     fn default() -> Self {
         Self {
             max_account_slots: TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
+            max_account_size: None,
             minimal_protocol_basefee: MIN_PROTOCOL_BASE_FEE,
             block_gas_limit: ETHEREUM_BLOCK_GAS_LIMIT,
             by_hash: Default::default(),
+            conditional: Default::default(),
             txs: Default::default(),
             tx_counter: Default::default(),
             last_seen_block_number: Default::default(),
@@ -1825,7 +2015,7 @@ mod tests {
     use crate::{
         test_utils::{MockOrdering, MockTransaction, MockTransactionFactory, MockTransactionSet},
         traits::TransactionOrigin,
-        SubPoolLimit,
+        FeeEvictionPolicy, SubPoolLimit,
     };
 
     #[test]
@@ -2548,6 +2738,31 @@ mod tests {
         .unwrap();
     }
 
+    #[test]
+    fn rejects_sender_over_size_limit() {
+        let on_chain_balance = U256::from(1_000);
+        let on_chain_nonce = 0;
+        let mut f = MockTransactionFactory::default();
+        let mut pool = AllTransactions::default();
+        pool.max_account_size = Some(100);
+
+        let mut tx = MockTransaction::eip1559();
+        tx.set_size(60);
+        pool.insert_tx(f.validated(tx.clone()), on_chain_balance, on_chain_nonce).unwrap();
+
+        let err =
+            pool.insert_tx(f.validated(tx.next()), on_chain_balance, on_chain_nonce).unwrap_err();
+        assert!(matches!(err, InsertErr::ExceededSenderTransactionsCapacity { .. }));
+
+        // local transactions are exempt
+        pool.insert_tx(
+            f.validated_with_origin(TransactionOrigin::Local, tx.next()),
+            on_chain_balance,
+            on_chain_nonce,
+        )
+        .unwrap();
+    }
+
     #[test]
     fn reject_tx_over_gas_limit() {
         let on_chain_balance = U256::from(1_000);
@@ -2652,6 +2867,49 @@ mod tests {
         assert_eq!(highest_tx.as_ref().transaction, tx1);
     }
 
+    #[test]
+    fn get_all_transactions_by_sender() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        // the second transaction has a nonce gap
+        let tx = MockTransaction::eip1559();
+        let gapped = tx.next().next();
+        pool.add_transaction(f.validated(tx.clone()), U256::from(1_000), 0).unwrap();
+        pool.add_transaction(f.validated(gapped.clone()), U256::from(1_000), 0).unwrap();
+        pool.add_transaction(f.validated(MockTransaction::eip1559()), U256::from(1_000), 0)
+            .unwrap();
+
+        let all = pool.get_all_transactions_by_sender(f.ids.sender_id(&tx.sender()).unwrap());
+        assert_eq!(all.pending.iter().map(|tx| tx.transaction.clone()).collect::<Vec<_>>(), [tx]);
+        assert_eq!(
+            all.queued.iter().map(|tx| tx.transaction.clone()).collect::<Vec<_>>(),
+            [gapped]
+        );
+    }
+
+    #[test]
+    fn get_transactions_page() {
+        let mut f = MockTransactionFactory::default();
+        let mut pool = TxPool::new(MockOrdering::default(), Default::default());
+
+        let first = MockTransaction::eip1559();
+        let second = MockTransaction::eip1559();
+        for tx in [first.clone(), first.next(), second.clone()] {
+            pool.add_transaction(f.validated(tx), U256::from(1_000), 0).unwrap();
+        }
+        let first_id = f.ids.sender_id(&first.sender()).unwrap();
+
+        // the first sender is included even if it exceeds the limit
+        let (page, next) = pool.get_transactions_page(first_id, 1);
+        assert_eq!(page.pending.len(), 2);
+        assert_eq!(next, Some(second.sender()));
+
+        let (page, next) = pool.get_transactions_page(first_id, 3);
+        assert_eq!(page.pending.len(), 3);
+        assert_eq!(next, None);
+    }
+
     #[test]
     fn discard_nonce_too_low() {
         let mut f = MockTransactionFactory::default();
@@ -2818,6 +3076,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn discard_spammer_with_eviction_policy() {
+        let mut f = MockTransactionFactory::default();
+        let pending_limit = SubPoolLimit::new(4, usize::MAX);
+        let policy = FeeEvictionPolicy::new(1).with_half_life(None);
+        let mut pool = TxPool::new(
+            MockOrdering::default(),
+            PoolConfig {
+                pending_limit,
+                eviction_policy: Some(Arc::new(policy)),
+                ..Default::default()
+            },
+        );
+
+        // a well-paying transaction, and a wave of transactions of a spammer that pays a bit more
+        let tx = MockTransaction::eip1559().with_max_fee(100);
+        let honest = *tx.hash();
+        pool.add_transaction(f.validated(tx), U256::MAX, 0).unwrap();
+        let mut tx = MockTransaction::eip1559().with_max_fee(150);
+        let mut spam = Vec::new();
+        for _ in 0..4 {
+            spam.push(*tx.hash());
+            pool.add_transaction(f.validated(tx.clone()), U256::MAX, 0).unwrap();
+            tx = tx.next();
+        }
+        assert_eq!(pool.size().pending, 5);
+
+        // the last transaction of the spammer has the lowest score, 150 / 4
+        let removed = pool.discard_worst();
+        assert_eq!(removed.iter().map(|tx| *tx.hash()).collect::<Vec<_>>(), vec![spam[3]]);
+        assert!(pool.contains(&honest));
+        assert_eq!(pool.size().pending, 4);
+    }
+
     #[test]
     fn account_updates_nonce_gap() {
         let on_chain_balance = U256::from(10_000);
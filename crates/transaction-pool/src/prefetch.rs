@@ -0,0 +1,202 @@
+//! Speculative prefetching of the state trie paths the next block is likely going to touch.
+//!
+//! Computing the state root of a block reads the trie nodes on the paths of all accounts and
+//! storage slots the block changed. Between blocks, the prefetcher walks the paths of the accounts
+//! and storage slots that the best pending transactions of the pool touch, by computing their
+//! proofs on the latest state. This loads the trie nodes into the page cache of the database, so
+//! computing the state root reads them from memory once those transactions are included.
+
+use crate::{
+    metrics::TriePrefetchMetrics,
+    traits::{PoolTransaction, TransactionPool},
+};
+use futures_util::{future::BoxFuture, FutureExt, Stream, StreamExt};
+use reth_primitives::{Address, B256};
+use reth_provider::{CanonStateNotification, ProviderResult, StateProviderFactory};
+use reth_tasks::TaskSpawner;
+use std::{
+    collections::{HashMap, HashSet},
+    time::Instant,
+};
+use tokio::sync::oneshot;
+use tracing::debug;
+
+/// Settings for prefetching the trie paths of the best pending transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriePrefetchConfig {
+    /// Maximum number of best pending transactions whose trie paths are prefetched after every
+    /// new canonical block.
+    pub max_transactions: usize,
+}
+
+impl TriePrefetchConfig {
+    /// Creates a new config that prefetches the trie paths of up to `max_transactions` of the best
+    /// pending transactions.
+    pub const fn new(max_transactions: usize) -> Self {
+        Self { max_transactions }
+    }
+}
+
+/// Returns a spawnable future that prefetches the trie paths of the best pending transactions
+/// after every new canonical block, see [`prefetch_trie`].
+pub fn prefetch_trie_future<Client, P, St, Tasks>(
+    client: Client,
+    pool: P,
+    events: St,
+    task_spawner: Tasks,
+    config: TriePrefetchConfig,
+) -> BoxFuture<'static, ()>
+where
+    Client: StateProviderFactory + Clone + 'static,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    async move {
+        prefetch_trie(client, pool, events, task_spawner, config).await;
+    }
+    .boxed()
+}
+
+/// Prefetches the trie paths of the accounts and storage slots the best pending transactions touch
+/// after every new canonical block.
+///
+/// The touched state is the sender and recipient of a transaction and the accounts and storage
+/// slots of its access list. Notifications that arrive while the trie is prefetched are merged,
+/// only the state of the latest block is prefetched.
+pub async fn prefetch_trie<Client, P, St, Tasks>(
+    client: Client,
+    pool: P,
+    mut events: St,
+    task_spawner: Tasks,
+    config: TriePrefetchConfig,
+) where
+    Client: StateProviderFactory + Clone + 'static,
+    P: TransactionPool + 'static,
+    St: Stream<Item = CanonStateNotification> + Send + Unpin + 'static,
+    Tasks: TaskSpawner + 'static,
+{
+    let metrics = TriePrefetchMetrics::default();
+
+    while let Some(mut event) = events.next().await {
+        // skip to the latest block if blocks arrived while prefetching
+        while let Some(Some(next)) = events.next().now_or_never() {
+            event = next;
+        }
+
+        // the pool may not have removed the transactions of the new block yet
+        let mined = event.tip().body.iter().map(|tx| tx.hash()).collect::<HashSet<_>>();
+        let targets = prefetch_targets(
+            pool.best_transactions()
+                .filter(|tx| !mined.contains(tx.hash()))
+                .take(config.max_transactions)
+                .collect::<Vec<_>>()
+                .iter()
+                .map(|tx| &tx.transaction),
+        );
+        if targets.is_empty() {
+            continue
+        }
+
+        let accounts = targets.len();
+        let storage_slots = targets.iter().map(|(_, slots)| slots.len()).sum::<usize>();
+        let (tx, rx) = oneshot::channel();
+        let client = client.clone();
+        task_spawner.spawn_blocking(Box::pin(async move {
+            let start = Instant::now();
+            let res = prefetch(&client, targets).map(|_| start.elapsed());
+            let _ = tx.send(res);
+        }));
+
+        match rx.await {
+            Ok(Ok(elapsed)) => {
+                metrics.prefetched_accounts.increment(accounts as u64);
+                metrics.prefetched_storage_slots.increment(storage_slots as u64);
+                metrics.prefetch_duration_seconds.record(elapsed.as_secs_f64());
+                debug!(
+                    target: "txpool::prefetch",
+                    block = event.tip().number,
+                    accounts,
+                    storage_slots,
+                    ?elapsed,
+                    "Prefetched trie paths of pending transactions"
+                );
+            }
+            Ok(Err(err)) => {
+                debug!(target: "txpool::prefetch", %err, "Failed to prefetch trie paths")
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/// Walks the trie paths of the accounts and their storage slots on the latest state.
+fn prefetch<Client: StateProviderFactory>(
+    client: &Client,
+    targets: Vec<(Address, Vec<B256>)>,
+) -> ProviderResult<()> {
+    client.latest()?.multiproof(&targets)?;
+    Ok(())
+}
+
+/// Returns the accounts and storage slots the transactions touch, in the order of the
+/// transactions.
+fn prefetch_targets<'a, T: PoolTransaction + 'a>(
+    transactions: impl IntoIterator<Item = &'a T>,
+) -> Vec<(Address, Vec<B256>)> {
+    let mut targets = Vec::<(Address, Vec<B256>)>::new();
+    let mut indices = HashMap::new();
+    let mut slots = HashSet::new();
+    let mut push = |address: Address, slot: Option<B256>| {
+        let index = *indices.entry(address).or_insert_with(|| {
+            targets.push((address, Vec::new()));
+            targets.len() - 1
+        });
+        if let Some(slot) = slot.filter(|slot| slots.insert((address, *slot))) {
+            targets[index].1.push(slot);
+        }
+    };
+
+    for transaction in transactions {
+        push(transaction.sender(), None);
+        if let Some(to) = transaction.to() {
+            push(to, None);
+        }
+        for item in transaction.access_list().iter().flat_map(|access_list| access_list.0.iter()) {
+            push(item.address, None);
+            for key in &item.storage_keys {
+                push(item.address, Some(*key));
+            }
+        }
+    }
+
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+    use reth_primitives::{AccessList, AccessListItem};
+
+    #[test]
+    fn prefetch_targets_deduplicated() {
+        let contract = Address::with_last_byte(1);
+        let mut first = MockTransaction::eip2930();
+        first.set_accesslist(AccessList(vec![AccessListItem {
+            address: contract,
+            storage_keys: vec![B256::with_last_byte(1), B256::with_last_byte(1)],
+        }]));
+        let second = MockTransaction::eip1559().with_sender(first.get_sender());
+
+        assert_eq!(
+            prefetch_targets([&first, &second]),
+            vec![
+                (first.get_sender(), vec![]),
+                (first.to().unwrap(), vec![]),
+                (contract, vec![B256::with_last_byte(1)]),
+                (second.to().unwrap(), vec![]),
+            ]
+        );
+    }
+}
@@ -11,11 +11,13 @@ use futures_util::{ready, Stream};
 use reth_eth_wire_types::HandleMempoolData;
 use reth_primitives::{
     kzg::KzgSettings, transaction::TryFromRecoveredTransactionError, AccessList, Address,
-    BlobTransactionSidecar, BlobTransactionValidationError, FromRecoveredPooledTransaction,
-    IntoRecoveredTransaction, PooledTransactionsElement, PooledTransactionsElementEcRecovered,
-    SealedBlock, Transaction, TransactionSignedEcRecovered, TryFromRecoveredTransaction, TxHash,
-    TxKind, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID, U256,
+    BlobAndProofV1, BlobTransactionSidecar, BlobTransactionValidationError,
+    FromRecoveredPooledTransaction, IntoRecoveredTransaction, PooledTransactionsElement,
+    PooledTransactionsElementEcRecovered, SealedBlock, Transaction, TransactionSignedEcRecovered,
+    TryFromRecoveredTransaction, TxHash, TxKind, B256, EIP1559_TX_TYPE_ID, EIP4844_TX_TYPE_ID,
+    U256,
 };
+use reth_rpc_types::TransactionConditional;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::{
@@ -332,6 +334,29 @@ pub trait TransactionPool: Send + Sync + Clone {
         sender: Address,
     ) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
 
+    /// Returns all transactions sent by a given user grouped by whether they are ready for
+    /// inclusion in the next block or not, like [Self::all_transactions].
+    ///
+    /// Consumer: RPC
+    fn get_all_transactions_by_sender(
+        &self,
+        sender: Address,
+    ) -> AllPoolTransactions<Self::Transaction>;
+
+    /// Returns the transactions of the senders starting with the given sender, grouped like
+    /// [Self::all_transactions], and the sender the next page starts with, if any.
+    ///
+    /// Senders are ordered by when they were first seen by the pool. All transactions of a sender
+    /// are on the same page, and senders are added as long as the page has at most `limit`
+    /// transactions.
+    ///
+    /// Consumer: RPC
+    fn get_transactions_page(
+        &self,
+        start: Option<Address>,
+        limit: usize,
+    ) -> (AllPoolTransactions<Self::Transaction>, Option<Address>);
+
     /// Returns a transaction sent by a given user with a given nonce
     fn get_transactions_by_sender_and_nonce(
         &self,
@@ -360,6 +385,25 @@ pub trait TransactionPool: Send + Sync + Clone {
         self.get_transactions_by_origin(TransactionOrigin::External)
     }
 
+    /// Returns all transactions that were submitted with conditions, see
+    /// [`PoolTransaction::conditional`].
+    fn get_conditional_transactions(&self) -> Vec<Arc<ValidPoolTransaction<Self::Transaction>>>;
+
+    /// Returns the local transactions that are tracked until they are mined, sorted by sender and
+    /// nonce, including the ones that were dropped from the pool.
+    ///
+    /// This is empty unless the journal of the
+    /// [`LocalTransactionConfig`](crate::LocalTransactionConfig) is enabled.
+    fn tracked_local_transactions(&self) -> Vec<PooledTransactionsElementEcRecovered>;
+
+    /// Stops tracking the local transaction with the given hash, returns it if it was tracked.
+    ///
+    /// This does not remove the transaction from the pool.
+    fn untrack_local_transaction(
+        &self,
+        tx_hash: TxHash,
+    ) -> Option<PooledTransactionsElementEcRecovered>;
+
     /// Returns a set of all senders of transactions in the pool
     fn unique_senders(&self) -> HashSet<Address>;
 
@@ -385,6 +429,15 @@ pub trait TransactionPool: Send + Sync + Clone {
         &self,
         tx_hashes: Vec<TxHash>,
     ) -> Result<Vec<BlobTransactionSidecar>, BlobStoreError>;
+
+    /// Returns the [BlobAndProofV1] for the given versioned hashes in the order they were
+    /// requested.
+    ///
+    /// The entry of a versioned hash is `None` if the blob is not in the blob store.
+    fn get_blobs_for_versioned_hashes(
+        &self,
+        versioned_hashes: &[B256],
+    ) -> Result<Vec<Option<BlobAndProofV1>>, BlobStoreError>;
 }
 
 /// Extension for [TransactionPool] trait that allows to set the current block info.
@@ -849,6 +902,25 @@ pub trait PoolTransaction:
 
     /// Returns `chain_id`
     fn chain_id(&self) -> Option<u64>;
+
+    /// Returns the conditions under which the transaction may be included in a block, if it was
+    /// submitted with any, see `eth_sendRawTransactionConditional`.
+    ///
+    /// Conditional transactions are not propagated, since peers would receive them without their
+    /// conditions.
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        None
+    }
+
+    /// Attaches the conditions under which the transaction may be included in a block.
+    ///
+    /// Returns `None` if the transaction type doesn't support conditions.
+    fn with_conditional(self, _conditional: TransactionConditional) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// An extension trait that provides additional interfaces for the
@@ -889,6 +961,9 @@ pub struct EthPooledTransaction {
 
     /// The blob side car for this transaction
     pub(crate) blob_sidecar: EthBlobTransactionSidecar,
+
+    /// The conditions the transaction was submitted with, if any.
+    pub(crate) conditional: Option<Box<TransactionConditional>>,
 }
 
 /// Represents the blob sidecar of the [`EthPooledTransaction`].
@@ -950,7 +1025,7 @@ impl EthPooledTransaction {
             ));
         }
 
-        Self { transaction, cost, encoded_length, blob_sidecar }
+        Self { transaction, cost, encoded_length, blob_sidecar, conditional: None }
     }
 
     /// Return the reference to the underlying transaction.
@@ -1092,6 +1167,15 @@ impl PoolTransaction for EthPooledTransaction {
     fn chain_id(&self) -> Option<u64> {
         self.transaction.chain_id()
     }
+
+    fn conditional(&self) -> Option<&TransactionConditional> {
+        self.conditional.as_deref()
+    }
+
+    fn with_conditional(mut self, conditional: TransactionConditional) -> Option<Self> {
+        self.conditional = Some(Box::new(conditional));
+        Some(self)
+    }
 }
 
 impl EthPoolTransaction for EthPooledTransaction {
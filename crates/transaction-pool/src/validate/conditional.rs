@@ -0,0 +1,159 @@
+//! Validation of the conditions of transactions submitted with `eth_sendRawTransactionConditional`.
+
+use crate::error::ConditionalTransactionError;
+use reth_primitives::U256;
+use reth_provider::{ProviderResult, StateProvider};
+use reth_rpc_types::{KnownAccount, TransactionConditional};
+
+/// Maximum number of storage lookups that checking the known accounts of a conditional
+/// transaction may require, see [`TransactionConditional::cost`].
+pub const MAX_CONDITIONAL_KNOWN_ACCOUNT_LOOKUPS: usize = 1000;
+
+/// Ensures that checking the known accounts of the conditions doesn't require more than
+/// [`MAX_CONDITIONAL_KNOWN_ACCOUNT_LOOKUPS`] storage lookups.
+pub fn ensure_conditional_cost(
+    conditional: &TransactionConditional,
+) -> Result<(), ConditionalTransactionError> {
+    let cost = conditional.cost();
+    if cost > MAX_CONDITIONAL_KNOWN_ACCOUNT_LOOKUPS {
+        return Err(ConditionalTransactionError::TooManyKnownAccountLookups {
+            have: cost,
+            permitted: MAX_CONDITIONAL_KNOWN_ACCOUNT_LOOKUPS,
+        })
+    }
+    Ok(())
+}
+
+/// Ensures that a block with the given number and timestamp is within the ranges of the
+/// conditions.
+pub fn ensure_conditional_block(
+    conditional: &TransactionConditional,
+    number: u64,
+    timestamp: u64,
+) -> Result<(), ConditionalTransactionError> {
+    if !conditional.is_block_number_valid(number) {
+        return Err(ConditionalTransactionError::BlockNumberOutOfRange(number))
+    }
+    if !conditional.is_timestamp_valid(timestamp) {
+        return Err(ConditionalTransactionError::TimestampOutOfRange(timestamp))
+    }
+    Ok(())
+}
+
+/// Ensures that the conditions can still be met by a block that follows the block with the given
+/// number and timestamp, i.e. that neither range has ended.
+pub fn ensure_conditional_not_expired(
+    conditional: &TransactionConditional,
+    number: u64,
+    timestamp: u64,
+) -> Result<(), ConditionalTransactionError> {
+    let (next_number, next_timestamp) = (number + 1, timestamp + 1);
+    if conditional.has_exceeded_block_number(next_number) {
+        return Err(ConditionalTransactionError::BlockNumberOutOfRange(next_number))
+    }
+    if conditional.has_exceeded_timestamp(next_timestamp) {
+        return Err(ConditionalTransactionError::TimestampOutOfRange(next_timestamp))
+    }
+    Ok(())
+}
+
+/// Ensures that the storage of the known accounts of the conditions matches the given state.
+///
+/// Returns an error if the state couldn't be read, and the mismatch otherwise.
+pub fn ensure_known_accounts<S: StateProvider + ?Sized>(
+    conditional: &TransactionConditional,
+    state: &S,
+) -> ProviderResult<Result<(), ConditionalTransactionError>> {
+    for (address, account) in &conditional.known_accounts {
+        match account {
+            KnownAccount::StorageRoot(expected) => {
+                if state.proof(*address, &[])?.storage_root != *expected {
+                    return Ok(Err(ConditionalTransactionError::StorageRootMismatch(*address)))
+                }
+            }
+            KnownAccount::Slots(slots) => {
+                for (slot, expected) in slots {
+                    let value = state.storage(*address, *slot)?.unwrap_or_default();
+                    if value != U256::from_be_bytes(expected.0) {
+                        return Ok(Err(ConditionalTransactionError::StorageSlotMismatch(
+                            *address, *slot,
+                        )))
+                    }
+                }
+            }
+        }
+    }
+    Ok(Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, B256};
+    use reth_provider::{
+        test_utils::{ExtendedAccount, MockEthProvider},
+        StateProviderFactory,
+    };
+    use std::collections::HashMap;
+
+    #[test]
+    fn known_account_slots() {
+        let provider = MockEthProvider::default();
+        let address = Address::with_last_byte(1);
+        provider.add_account(
+            address,
+            ExtendedAccount::new(0, U256::ZERO)
+                .extend_storage([(B256::with_last_byte(1), U256::from(2))]),
+        );
+        let state = provider.latest().unwrap();
+
+        let conditional = |value: u8| TransactionConditional {
+            known_accounts: HashMap::from([(
+                address,
+                KnownAccount::Slots(HashMap::from([
+                    (B256::with_last_byte(1), B256::with_last_byte(value)),
+                    (B256::with_last_byte(2), B256::ZERO),
+                ])),
+            )]),
+            ..Default::default()
+        };
+        assert_eq!(ensure_known_accounts(&conditional(2), &state).unwrap(), Ok(()));
+        assert_eq!(
+            ensure_known_accounts(&conditional(3), &state).unwrap(),
+            Err(ConditionalTransactionError::StorageSlotMismatch(address, B256::with_last_byte(1)))
+        );
+    }
+
+    #[test]
+    fn conditional_block_range() {
+        let conditional = TransactionConditional {
+            block_number_min: Some(10),
+            block_number_max: Some(20),
+            timestamp_max: Some(100),
+            ..Default::default()
+        };
+        assert_eq!(ensure_conditional_block(&conditional, 10, 100), Ok(()));
+        assert_eq!(
+            ensure_conditional_block(&conditional, 9, 100),
+            Err(ConditionalTransactionError::BlockNumberOutOfRange(9))
+        );
+        assert_eq!(
+            ensure_conditional_block(&conditional, 21, 100),
+            Err(ConditionalTransactionError::BlockNumberOutOfRange(21))
+        );
+        assert_eq!(
+            ensure_conditional_block(&conditional, 20, 101),
+            Err(ConditionalTransactionError::TimestampOutOfRange(101))
+        );
+
+        assert_eq!(ensure_conditional_not_expired(&conditional, 19, 99), Ok(()));
+        assert_eq!(
+            ensure_conditional_not_expired(&conditional, 20, 99),
+            Err(ConditionalTransactionError::BlockNumberOutOfRange(21))
+        );
+        assert_eq!(
+            ensure_conditional_not_expired(&conditional, 19, 100),
+            Err(ConditionalTransactionError::TimestampOutOfRange(101))
+        );
+    }
+}
@@ -11,13 +11,27 @@ use reth_primitives::{
 };
 use std::{fmt, future::Future, time::Instant};
 
+mod conditional;
 mod constants;
 mod eth;
+mod stack;
 mod task;
 
+/// Validation of the conditions of conditional transactions.
+pub use conditional::{
+    ensure_conditional_block, ensure_conditional_cost, ensure_conditional_not_expired,
+    ensure_known_accounts, MAX_CONDITIONAL_KNOWN_ACCOUNT_LOOKUPS,
+};
+
 /// A `TransactionValidator` implementation that validates ethereum transaction.
 pub use eth::*;
 
+/// A `TransactionValidator` implementation that screens transactions before and after another
+/// validator.
+pub use stack::{
+    AddressDenyList, MinPriorityFee, TransactionScreen, TransactionScreens, ValidatorStack,
+};
+
 /// A spawnable task that performs transaction validation.
 pub use task::{TransactionValidationTaskExecutor, ValidationTask};
 
@@ -0,0 +1,441 @@
+//! A [`TransactionValidator`] that screens transactions before and after another validator.
+
+use crate::{
+    error::{InvalidPoolTransactionError, ScreenedTransactionError},
+    traits::{PoolTransaction, TransactionOrigin},
+    validate::{TransactionValidationOutcome, TransactionValidator},
+};
+use reth_primitives::{Address, SealedBlock};
+use std::{collections::HashSet, fmt, sync::Arc};
+
+/// A check that decides whether a transaction may be inserted into the pool, as part of a
+/// [`ValidatorStack`].
+///
+/// This is implemented for closures with the same signature as [`TransactionScreen::screen`].
+pub trait TransactionScreen<T: PoolTransaction>: Send + Sync {
+    /// Returns an error if the transaction must not be inserted into the pool.
+    fn screen(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError>;
+}
+
+impl<T, F> TransactionScreen<T> for F
+where
+    T: PoolTransaction,
+    F: Fn(TransactionOrigin, &T) -> Result<(), InvalidPoolTransactionError> + Send + Sync,
+{
+    fn screen(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        self(origin, transaction)
+    }
+}
+
+/// A [`TransactionValidator`] that runs [`TransactionScreen`]s before and after another
+/// validator, e.g. the [`EthTransactionValidator`](crate::EthTransactionValidator).
+///
+/// The screens run in the order they were added. The screens before the validator see every
+/// transaction and can reject it before the more expensive validation of the validator, the
+/// screens after the validator only see the transactions the validator considers valid. The first
+/// screen that rejects a transaction makes it invalid.
+///
+/// The validator of a task executor is stacked with
+/// [`TransactionValidationTaskExecutor::map`](super::TransactionValidationTaskExecutor::map).
+///
+/// ```
+/// use reth_transaction_pool::{
+///     error::{InvalidPoolTransactionError, ScreenedTransactionError},
+///     noop::MockTransactionValidator,
+///     validate::{AddressDenyList, MinPriorityFee, ValidatorStack},
+///     EthPooledTransaction, PoolTransaction, TransactionOrigin,
+/// };
+/// # use reth_primitives::Address;
+/// # let denied = Address::ZERO;
+///
+/// let validator = ValidatorStack::new(MockTransactionValidator::<EthPooledTransaction>::default())
+///     .with_screen_before(AddressDenyList::new([denied]))
+///     .with_screen_before(MinPriorityFee::new(1_000_000_000))
+///     .with_screen_after(
+///         |_: TransactionOrigin,
+///          tx: &EthPooledTransaction|
+///          -> Result<(), InvalidPoolTransactionError> {
+///             if tx.gas_limit() < 50_000 {
+///                 let err = ScreenedTransactionError::Rejected("gas limit too low".into());
+///                 return Err(err.into())
+///             }
+///             Ok(())
+///         },
+///     );
+/// ```
+pub struct ValidatorStack<V: TransactionValidator> {
+    /// The screens that run before the validator.
+    before: Vec<Arc<dyn TransactionScreen<V::Transaction>>>,
+    /// The validator.
+    validator: V,
+    /// The screens that run after the validator.
+    after: Vec<Arc<dyn TransactionScreen<V::Transaction>>>,
+}
+
+impl<V: TransactionValidator> ValidatorStack<V> {
+    /// Creates a new stack of the validator without any screens.
+    pub const fn new(validator: V) -> Self {
+        Self { before: Vec::new(), validator, after: Vec::new() }
+    }
+
+    /// Adds a screen that runs before the validator, after the screens that were added before.
+    pub fn with_screen_before(
+        mut self,
+        screen: impl TransactionScreen<V::Transaction> + 'static,
+    ) -> Self {
+        self.before.push(Arc::new(screen));
+        self
+    }
+
+    /// Adds a screen that runs after the validator, after the screens that were added before.
+    pub fn with_screen_after(
+        mut self,
+        screen: impl TransactionScreen<V::Transaction> + 'static,
+    ) -> Self {
+        self.after.push(Arc::new(screen));
+        self
+    }
+
+    /// Adds the screens, after the screens that were added before.
+    pub fn with_screens(mut self, screens: TransactionScreens<V::Transaction>) -> Self {
+        self.before.extend(screens.before);
+        self.after.extend(screens.after);
+        self
+    }
+
+    /// Returns the validator of the stack.
+    pub const fn validator(&self) -> &V {
+        &self.validator
+    }
+
+    /// Runs the screens before the validator, returns the invalid outcome if one of them rejects
+    /// the transaction.
+    fn screen_before(
+        &self,
+        origin: TransactionOrigin,
+        transaction: V::Transaction,
+    ) -> Result<V::Transaction, TransactionValidationOutcome<V::Transaction>> {
+        match screen(&self.before, origin, &transaction) {
+            Ok(()) => Ok(transaction),
+            Err(err) => Err(TransactionValidationOutcome::Invalid(transaction, err)),
+        }
+    }
+
+    /// Runs the screens after the validator if the validator considers the transaction valid.
+    fn screen_after(
+        &self,
+        origin: TransactionOrigin,
+        outcome: TransactionValidationOutcome<V::Transaction>,
+    ) -> TransactionValidationOutcome<V::Transaction> {
+        match outcome {
+            TransactionValidationOutcome::Valid {
+                balance,
+                state_nonce,
+                transaction,
+                propagate,
+            } => match screen(&self.after, origin, transaction.transaction()) {
+                Ok(()) => TransactionValidationOutcome::Valid {
+                    balance,
+                    state_nonce,
+                    transaction,
+                    propagate,
+                },
+                Err(err) => {
+                    TransactionValidationOutcome::Invalid(transaction.into_transaction(), err)
+                }
+            },
+            outcome => outcome,
+        }
+    }
+}
+
+/// Runs the screens in order and returns the error of the first one that rejects the transaction.
+fn screen<T: PoolTransaction>(
+    screens: &[Arc<dyn TransactionScreen<T>>],
+    origin: TransactionOrigin,
+    transaction: &T,
+) -> Result<(), InvalidPoolTransactionError> {
+    screens.iter().try_for_each(|screen| screen.screen(origin, transaction))
+}
+
+impl<V: TransactionValidator + Clone> Clone for ValidatorStack<V> {
+    fn clone(&self) -> Self {
+        Self {
+            before: self.before.clone(),
+            validator: self.validator.clone(),
+            after: self.after.clone(),
+        }
+    }
+}
+
+impl<V: TransactionValidator + fmt::Debug> fmt::Debug for ValidatorStack<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ValidatorStack")
+            .field("before", &self.before.len())
+            .field("validator", &self.validator)
+            .field("after", &self.after.len())
+            .finish()
+    }
+}
+
+impl<V: TransactionValidator> TransactionValidator for ValidatorStack<V> {
+    type Transaction = V::Transaction;
+
+    async fn validate_transaction(
+        &self,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        let transaction = match self.screen_before(origin, transaction) {
+            Ok(transaction) => transaction,
+            Err(outcome) => return outcome,
+        };
+        let outcome = self.validator.validate_transaction(origin, transaction).await;
+        self.screen_after(origin, outcome)
+    }
+
+    async fn validate_transactions(
+        &self,
+        transactions: Vec<(TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<TransactionValidationOutcome<Self::Transaction>> {
+        // the transactions that pass the screens before the validator are validated as a batch,
+        // the outcomes of the others are kept at their position
+        let mut outcomes = Vec::with_capacity(transactions.len());
+        let mut screened = Vec::new();
+        for (origin, transaction) in transactions {
+            match self.screen_before(origin, transaction) {
+                Ok(transaction) => {
+                    outcomes.push(None);
+                    screened.push((origin, transaction));
+                }
+                Err(outcome) => outcomes.push(Some(outcome)),
+            }
+        }
+
+        let origins = screened.iter().map(|(origin, _)| *origin).collect::<Vec<_>>();
+        let mut validated = self
+            .validator
+            .validate_transactions(screened)
+            .await
+            .into_iter()
+            .zip(origins)
+            .map(|(outcome, origin)| self.screen_after(origin, outcome));
+
+        outcomes
+            .into_iter()
+            .map(|outcome| {
+                outcome.unwrap_or_else(|| validated.next().expect("one outcome per transaction"))
+            })
+            .collect()
+    }
+
+    fn on_new_head_block(&self, new_tip_block: &SealedBlock) {
+        self.validator.on_new_head_block(new_tip_block)
+    }
+}
+
+/// The [`TransactionScreen`]s of a [`ValidatorStack`], collected before the validator is built.
+///
+/// This is how node components take screens, e.g. the pool builders of the nodes.
+pub struct TransactionScreens<T> {
+    /// The screens that run before the validator.
+    before: Vec<Arc<dyn TransactionScreen<T>>>,
+    /// The screens that run after the validator.
+    after: Vec<Arc<dyn TransactionScreen<T>>>,
+}
+
+impl<T: PoolTransaction> TransactionScreens<T> {
+    /// Adds a screen that runs before the validator, after the screens that were added before.
+    pub fn with_screen_before(mut self, screen: impl TransactionScreen<T> + 'static) -> Self {
+        self.before.push(Arc::new(screen));
+        self
+    }
+
+    /// Adds a screen that runs after the validator, after the screens that were added before.
+    pub fn with_screen_after(mut self, screen: impl TransactionScreen<T> + 'static) -> Self {
+        self.after.push(Arc::new(screen));
+        self
+    }
+
+    /// Adds the screens, after the screens that were added before.
+    pub fn with_screens(mut self, screens: Self) -> Self {
+        self.before.extend(screens.before);
+        self.after.extend(screens.after);
+        self
+    }
+
+    /// Returns `true` if there are no screens.
+    pub fn is_empty(&self) -> bool {
+        self.before.is_empty() && self.after.is_empty()
+    }
+}
+
+impl<T> Default for TransactionScreens<T> {
+    fn default() -> Self {
+        Self { before: Vec::new(), after: Vec::new() }
+    }
+}
+
+impl<T> Clone for TransactionScreens<T> {
+    fn clone(&self) -> Self {
+        Self { before: self.before.clone(), after: self.after.clone() }
+    }
+}
+
+impl<T> fmt::Debug for TransactionScreens<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionScreens")
+            .field("before", &self.before.len())
+            .field("after", &self.after.len())
+            .finish()
+    }
+}
+
+/// A [`TransactionScreen`] that rejects the transactions from or to any of the denied addresses,
+/// e.g. sanctioned accounts or blacklisted contracts.
+#[derive(Debug, Clone, Default)]
+pub struct AddressDenyList {
+    addresses: HashSet<Address>,
+}
+
+impl AddressDenyList {
+    /// Creates a new deny list of the addresses.
+    pub fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self { addresses: addresses.into_iter().collect() }
+    }
+
+    /// Returns `true` if the address is denied.
+    pub fn contains(&self, address: &Address) -> bool {
+        self.addresses.contains(address)
+    }
+}
+
+impl<T: PoolTransaction> TransactionScreen<T> for AddressDenyList {
+    fn screen(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        let sender = transaction.sender();
+        if self.contains(&sender) {
+            return Err(ScreenedTransactionError::DeniedSender(sender).into())
+        }
+        if let Some(to) = transaction.to().filter(|to| self.contains(to)) {
+            return Err(ScreenedTransactionError::DeniedRecipient(to).into())
+        }
+        Ok(())
+    }
+}
+
+/// A [`TransactionScreen`] that rejects external transactions whose priority fee, or gas price
+/// for legacy transactions, is below a minimum.
+///
+/// Unlike the minimum priority fee of the
+/// [`EthTransactionValidatorBuilder`](crate::validate::EthTransactionValidatorBuilder), this also
+/// applies to legacy transactions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MinPriorityFee {
+    min: u128,
+}
+
+impl MinPriorityFee {
+    /// Creates a new screen with the minimum priority fee in wei.
+    pub const fn new(min: u128) -> Self {
+        Self { min }
+    }
+}
+
+impl<T: PoolTransaction> TransactionScreen<T> for MinPriorityFee {
+    fn screen(
+        &self,
+        origin: TransactionOrigin,
+        transaction: &T,
+    ) -> Result<(), InvalidPoolTransactionError> {
+        if origin.is_external() && transaction.priority_fee_or_price() < self.min {
+            return Err(InvalidPoolTransactionError::Underpriced)
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{noop::MockTransactionValidator, test_utils::MockTransaction};
+
+    #[tokio::test]
+    async fn screens_transactions_in_order() {
+        let denied = Address::random();
+        let validator = ValidatorStack::new(MockTransactionValidator::<MockTransaction>::default())
+            .with_screen_before(AddressDenyList::new([denied]))
+            .with_screen_after(MinPriorityFee::new(10));
+
+        let transactions = vec![
+            MockTransaction::eip1559().with_priority_fee(10),
+            MockTransaction::eip1559().with_priority_fee(10).with_sender(denied),
+            MockTransaction::eip1559().with_priority_fee(9),
+            MockTransaction::legacy().with_gas_price(9),
+        ];
+        let outcomes = validator
+            .validate_transactions(
+                transactions.iter().cloned().map(|tx| (TransactionOrigin::External, tx)).collect(),
+            )
+            .await;
+
+        assert_eq!(outcomes.len(), transactions.len());
+        for (outcome, tx) in outcomes.iter().zip(&transactions) {
+            assert_eq!(outcome.tx_hash(), *tx.hash());
+        }
+        assert!(outcomes[0].is_valid());
+        assert!(matches!(
+            outcomes[1],
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Screened(ScreenedTransactionError::DeniedSender(_))
+            )
+        ));
+        assert!(matches!(
+            outcomes[2],
+            TransactionValidationOutcome::Invalid(_, InvalidPoolTransactionError::Underpriced)
+        ));
+        assert!(outcomes[3].is_invalid());
+
+        // local transactions are exempt from the minimum priority fee
+        let tx = MockTransaction::eip1559().with_priority_fee(9);
+        assert!(validator.validate_transaction(TransactionOrigin::Local, tx).await.is_valid());
+    }
+
+    #[tokio::test]
+    async fn stacks_collected_screens() {
+        let denied = Address::random();
+        let screens = TransactionScreens::default()
+            .with_screen_before(AddressDenyList::new([denied]))
+            .with_screen_after(MinPriorityFee::new(10));
+        assert!(!screens.is_empty());
+
+        let validator = ValidatorStack::new(MockTransactionValidator::<MockTransaction>::default())
+            .with_screen_before(MinPriorityFee::new(5))
+            .with_screens(screens);
+
+        let tx = MockTransaction::eip1559().with_priority_fee(10).with_sender(denied);
+        assert!(matches!(
+            validator.validate_transaction(TransactionOrigin::External, tx).await,
+            TransactionValidationOutcome::Invalid(
+                _,
+                InvalidPoolTransactionError::Screened(ScreenedTransactionError::DeniedSender(_))
+            )
+        ));
+        let tx = MockTransaction::eip1559().with_priority_fee(9);
+        assert!(validator.validate_transaction(TransactionOrigin::External, tx).await.is_invalid());
+        let tx = MockTransaction::eip1559().with_priority_fee(10);
+        assert!(validator.validate_transaction(TransactionOrigin::External, tx).await.is_valid());
+    }
+}
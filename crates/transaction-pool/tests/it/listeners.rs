@@ -2,8 +2,8 @@ use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
-    FullTransactionEvent, TransactionEvent, TransactionListenerKind, TransactionOrigin,
-    TransactionPool,
+    FullTransactionEvent, SequencedEvent, TransactionEvent, TransactionListenerKind,
+    TransactionOrigin, TransactionPool,
 };
 use std::{future::poll_fn, task::Poll};
 use tokio_stream::StreamExt;
@@ -41,6 +41,36 @@ async fn txpool_listener_all() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_listener_sequenced() {
+    let txpool = TestPoolBuilder::default();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let mut all_tx_events = txpool.all_transactions_event_listener().sequenced();
+
+    for sequence in 0..2 {
+        let transaction = mock_tx_factory.create_eip1559();
+        let mut events = txpool
+            .add_transaction_and_subscribe(
+                TransactionOrigin::External,
+                transaction.transaction.clone(),
+            )
+            .await
+            .unwrap()
+            .sequenced();
+
+        assert_eq!(
+            events.next().await,
+            Some(SequencedEvent::new(sequence, TransactionEvent::Pending))
+        );
+        assert_matches!(
+            all_tx_events.next().await,
+            Some(SequencedEvent { sequence: s, event: FullTransactionEvent::Pending(hash) })
+                if s == sequence && hash == transaction.transaction.get_hash()
+        );
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_listener_propagate_only() {
     let txpool =
@@ -0,0 +1,61 @@
+//! Local transaction journal tests.
+
+use reth_primitives::SealedBlock;
+use reth_transaction_pool::{
+    test_utils::{MockTransaction, TestPool, TestPoolBuilder},
+    CanonicalStateUpdate, FromRecoveredPooledTransaction, LocalTransactionConfig, PoolConfig,
+    PoolTransaction, SubPoolLimit, TransactionOrigin, TransactionPool, TransactionPoolExt,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reinserted_local_transaction_is_untracked_when_mined() {
+    let pool: TestPool = TestPoolBuilder::default()
+        .with_config(PoolConfig {
+            pending_limit: SubPoolLimit::new(1, usize::MAX),
+            local_transactions_config: LocalTransactionConfig {
+                journal: true,
+                no_exemptions: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .into();
+
+    let evicted = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(1);
+    let kept = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(50);
+    pool.add_transaction(TransactionOrigin::Local, evicted.clone()).await.unwrap();
+    pool.add_transaction(TransactionOrigin::Local, kept.clone()).await.unwrap();
+
+    // the cheaper local transaction is evicted, but still tracked
+    assert!(!pool.contains(evicted.hash()));
+    assert!(pool.contains(kept.hash()));
+    let tracked = pool.tracked_local_transactions();
+    assert_eq!(tracked.len(), 2);
+
+    // the kept transaction is mined, so the evicted one can be reinserted
+    let tip = SealedBlock::default();
+    pool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &tip,
+        pending_block_base_fee: 0,
+        pending_block_blob_fee: None,
+        changed_accounts: vec![],
+        mined_transactions: vec![*kept.hash()],
+    });
+    let tracked = pool.tracked_local_transactions();
+    assert_eq!(tracked.len(), 1);
+    assert_eq!(tracked[0].hash(), evicted.hash());
+
+    let reinserted = MockTransaction::from_recovered_pooled_transaction(tracked[0].clone());
+    pool.add_transaction(TransactionOrigin::Local, reinserted).await.unwrap();
+    assert!(pool.contains(evicted.hash()));
+
+    // once mined, the reinserted transaction is no longer tracked
+    pool.on_canonical_state_change(CanonicalStateUpdate {
+        new_tip: &tip,
+        pending_block_base_fee: 0,
+        pending_block_blob_fee: None,
+        changed_accounts: vec![],
+        mined_transactions: vec![*evicted.hash()],
+    });
+    assert!(pool.tracked_local_transactions().is_empty());
+}
@@ -7,6 +7,8 @@ mod evict;
 #[cfg(feature = "test-utils")]
 mod listeners;
 #[cfg(feature = "test-utils")]
+mod locals;
+#[cfg(feature = "test-utils")]
 mod pending;
 
 const fn main() {}
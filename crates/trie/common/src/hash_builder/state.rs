@@ -6,6 +6,9 @@ use nybbles::Nibbles;
 use reth_codecs::{derive_arbitrary, Compact};
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// The hash builder state for storing in the database.
 /// Check the `reth-trie` crate for more info on hash builder.
 #[derive_arbitrary(compact)]
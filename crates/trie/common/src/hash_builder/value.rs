@@ -3,6 +3,9 @@ use alloy_trie::hash_builder::HashBuilderValue;
 use bytes::Buf;
 use reth_codecs::Compact;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// A wrapper around `HashBuilderValue` that implements `Compact`.
 pub(crate) struct StoredHashBuilderValue(pub(crate) HashBuilderValue);
 
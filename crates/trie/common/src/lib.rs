@@ -1,4 +1,14 @@
 //! Commonly used types for trie usage.
+//!
+//! The trie encodings, nibbles, branch nodes and proof verification don't depend on the standard
+//! library, so they can be reused by light clients and in zk circuits.
+//!
+//! ## Feature Flags
+//!
+//! - `std` (default): Uses the standard library. Disable it to build for `no_std` targets, which
+//!   only requires `alloc`.
+//! - `arbitrary`: Adds `proptest` and `arbitrary` support for trie types.
+//! - `test-utils`: Export utilities for testing, requires `std`.
 
 #![doc(
     html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
@@ -9,6 +19,10 @@
 // TODO: remove when https://github.com/proptest-rs/proptest/pull/427 is merged
 #![allow(unknown_lints, non_local_definitions)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 
 /// The implementation of hash builder.
 pub mod hash_builder;
@@ -3,6 +3,9 @@ use derive_more::Deref;
 use reth_codecs::Compact;
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 pub use nybbles::Nibbles;
 
 /// The representation of nibbles of the merkle trie stored in the database.
@@ -44,7 +47,7 @@ impl PartialEq<[u8]> for StoredNibbles {
 
 impl PartialOrd<[u8]> for StoredNibbles {
     #[inline]
-    fn partial_cmp(&self, other: &[u8]) -> Option<std::cmp::Ordering> {
+    fn partial_cmp(&self, other: &[u8]) -> Option<core::cmp::Ordering> {
         self.0.as_slice().partial_cmp(other)
     }
 }
@@ -5,6 +5,9 @@ use bytes::Buf;
 use reth_codecs::Compact;
 use serde::{Deserialize, Serialize};
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// Wrapper around `BranchNodeCompact` that implements `Compact`.
 #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StoredBranchNode(pub BranchNodeCompact);
@@ -7,6 +7,9 @@ use alloy_trie::HashBuilder;
 use itertools::Itertools;
 use nybbles::Nibbles;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// Adjust the index of an item for rlp encoding.
 pub const fn adjust_index_for_rlp(i: usize, len: usize) -> usize {
     if i > 0x7f {
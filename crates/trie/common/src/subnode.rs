@@ -2,6 +2,9 @@ use super::{BranchNodeCompact, StoredBranchNode};
 use bytes::Buf;
 use reth_codecs::Compact;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
 /// Walker sub node for storing intermediate state root calculation state in the database.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub struct StoredSubNode {
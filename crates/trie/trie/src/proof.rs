@@ -12,6 +12,7 @@ use reth_db_api::transaction::DbTx;
 use reth_execution_errors::{StateRootError, StorageRootError};
 use reth_primitives::{constants::EMPTY_ROOT_HASH, keccak256, Address, B256};
 use reth_trie_common::{proof::ProofRetainer, AccountProof, StorageProof, TrieAccount};
+use std::collections::HashMap;
 /// A struct for generating merkle proofs.
 ///
 /// Proof generator adds the target address and slots to the prefix set, enables the proof retainer
@@ -43,21 +44,42 @@ where
         address: Address,
         slots: &[B256],
     ) -> Result<AccountProof, StateRootError> {
-        let target_hashed_address = keccak256(address);
-        let target_nibbles = Nibbles::unpack(target_hashed_address);
-        let mut account_proof = AccountProof::new(address);
+        let mut proofs = self.multiproof(&[(address, slots.to_vec())])?;
+        Ok(proofs.pop().expect("one proof per target"))
+    }
+
+    /// Generate the account proofs of multiple accounts and their storage slots in a single walk
+    /// of the account trie.
+    ///
+    /// The trie nodes that the paths to the accounts share are only read once. Returns one proof
+    /// per target, in the order of the targets.
+    pub fn multiproof(
+        &self,
+        targets: &[(Address, Vec<B256>)],
+    ) -> Result<Vec<AccountProof>, StateRootError> {
+        let mut account_proofs =
+            targets.iter().map(|(address, _)| AccountProof::new(*address)).collect::<Vec<_>>();
+        let target_nibbles = targets
+            .iter()
+            .map(|(address, _)| Nibbles::unpack(keccak256(address)))
+            .collect::<Vec<_>>();
+
+        // the indices of the targets by hashed address, an account may be requested repeatedly
+        let mut target_indices = HashMap::<B256, Vec<usize>>::new();
+        for (index, (address, _)) in targets.iter().enumerate() {
+            target_indices.entry(keccak256(address)).or_default().push(index);
+        }
 
         let hashed_account_cursor = self.hashed_cursor_factory.hashed_account_cursor()?;
         let trie_cursor =
             DatabaseAccountTrieCursor::new(self.tx.cursor_read::<tables::AccountsTrie>()?);
 
         // Create the walker.
-        let mut prefix_set = PrefixSetMut::default();
-        prefix_set.insert(target_nibbles.clone());
-        let walker = TrieWalker::new(trie_cursor, prefix_set.freeze());
+        let prefix_set = PrefixSetMut::from(target_nibbles.clone()).freeze();
+        let walker = TrieWalker::new(trie_cursor, prefix_set);
 
         // Create a hash builder to rebuild the root node since it is not available in the database.
-        let retainer = ProofRetainer::from_iter([target_nibbles]);
+        let retainer = ProofRetainer::from_iter(target_nibbles.clone());
         let mut hash_builder = HashBuilder::default().with_proof_retainer(retainer);
 
         let mut account_rlp = Vec::with_capacity(128);
@@ -68,13 +90,19 @@ where
                     hash_builder.add_branch(node.key, node.value, node.children_are_in_trie);
                 }
                 TrieElement::Leaf(hashed_address, account) => {
-                    let storage_root = if hashed_address == target_hashed_address {
-                        let (storage_root, storage_proofs) =
-                            self.storage_root_with_proofs(hashed_address, slots)?;
-                        account_proof.set_account(account, storage_root, storage_proofs);
-                        storage_root
-                    } else {
-                        self.storage_root(hashed_address)?
+                    let storage_root = match target_indices.get(&hashed_address) {
+                        Some(indices) => {
+                            let mut storage_root = EMPTY_ROOT_HASH;
+                            for &index in indices {
+                                let slots = &targets[index].1;
+                                let (root, storage_proofs) =
+                                    self.storage_root_with_proofs(hashed_address, slots)?;
+                                account_proofs[index].set_account(account, root, storage_proofs);
+                                storage_root = root;
+                            }
+                            storage_root
+                        }
+                        None => self.storage_root(hashed_address)?,
                     };
 
                     account_rlp.clear();
@@ -88,10 +116,17 @@ where
 
         let _ = hash_builder.root();
 
-        let proofs = hash_builder.take_proofs();
-        account_proof.set_proof(proofs.values().cloned().collect());
+        let all_proof_nodes = hash_builder.take_proofs();
+        for (account_proof, nibbles) in account_proofs.iter_mut().zip(&target_nibbles) {
+            // The filtered results are guaranteed to be in order.
+            let matching_proof_nodes = all_proof_nodes
+                .iter()
+                .filter(|(path, _)| nibbles.starts_with(path))
+                .map(|(_, node)| node.clone());
+            account_proof.set_proof(matching_proof_nodes.collect());
+        }
 
-        Ok(account_proof)
+        Ok(account_proofs)
     }
 
     /// Compute storage root.
@@ -291,6 +326,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn holesky_multiproof() {
+        // Create test database and insert genesis accounts.
+        let factory = create_test_provider_factory();
+        let root = insert_genesis(&factory, HOLESKY.clone()).unwrap();
+
+        let deposit_contract =
+            Address::from_str("0x4242424242424242424242424242424242424242").unwrap();
+        let targets = Vec::from([
+            (deposit_contract, Vec::from([B256::with_last_byte(0x22), B256::with_last_byte(0xff)])),
+            (Address::from_str("0x000d836201318ec6899a67540690382780743281").unwrap(), Vec::new()),
+            (deposit_contract, Vec::from([B256::with_last_byte(0x23)])),
+        ]);
+
+        let provider = factory.provider().unwrap();
+        let proof = Proof::new(provider.tx_ref());
+        let multiproof = proof.multiproof(&targets).unwrap();
+        assert_eq!(multiproof.len(), targets.len());
+        for ((address, slots), account_proof) in targets.iter().zip(multiproof) {
+            similar_asserts::assert_eq!(
+                account_proof,
+                proof.account_proof(*address, slots).unwrap()
+            );
+            assert_eq!(account_proof.verify(root), Ok(()));
+        }
+    }
+
     #[test]
     fn testspec_empty_storage_proof() {
         // Create test database and insert genesis accounts.
@@ -0,0 +1,15 @@
+use prost::Message;
+use std::{env, fs, path::PathBuf};
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // the schema is parsed with `protox`, so that building doesn't require `protoc`
+    let file_descriptors = protox::compile(["proto/exex.proto"], ["proto"])?;
+    let file_descriptor_path = PathBuf::from(env::var_os("OUT_DIR").unwrap()).join("exex.bin");
+    fs::write(&file_descriptor_path, file_descriptors.encode_to_vec())?;
+
+    tonic_build::configure()
+        .skip_protoc_run()
+        .file_descriptor_set_path(&file_descriptor_path)
+        .compile(&["proto/exex.proto"], &["proto"])?;
+    Ok(())
+}
@@ -0,0 +1,245 @@
+//! Conversions of the notifications into their protobuf representation.
+
+use crate::{proto, traces::ChainTraces};
+use reth_execution_types::Chain;
+use reth_exex::ExExNotification;
+use reth_primitives::{Log, Receipt, SealedBlockWithSenders};
+use reth_rpc_types::trace::parity::{Action, CallType, LocalizedTransactionTrace, TraceOutput};
+
+impl From<&ExExNotification> for proto::ExExNotification {
+    fn from(notification: &ExExNotification) -> Self {
+        let notification = match notification {
+            ExExNotification::ChainCommitted { new } => {
+                proto::ex_ex_notification::Notification::ChainCommitted(proto::ChainCommitted {
+                    new: Some(new.as_ref().into()),
+                })
+            }
+            ExExNotification::ChainReorged { old, new } => {
+                proto::ex_ex_notification::Notification::ChainReorged(proto::ChainReorged {
+                    old: Some(old.as_ref().into()),
+                    new: Some(new.as_ref().into()),
+                })
+            }
+            ExExNotification::ChainReverted { old } => {
+                proto::ex_ex_notification::Notification::ChainReverted(proto::ChainReverted {
+                    old: Some(old.as_ref().into()),
+                })
+            }
+        };
+        Self { notification: Some(notification) }
+    }
+}
+
+impl proto::ExExNotification {
+    /// Sets the call traces of the transactions of the committed chain, see
+    /// [`trace_chain`](crate::traces::trace_chain).
+    pub(crate) fn set_traces(&mut self, traces: ChainTraces) {
+        let chain = match &mut self.notification {
+            Some(proto::ex_ex_notification::Notification::ChainCommitted(committed)) => {
+                committed.new.as_mut()
+            }
+            Some(proto::ex_ex_notification::Notification::ChainReorged(reorged)) => {
+                reorged.new.as_mut()
+            }
+            _ => None,
+        };
+        let Some(chain) = chain else { return };
+
+        // the traces are produced from the same chain, so they match the blocks and transactions
+        debug_assert_eq!(chain.blocks.len(), traces.len());
+        for (block, traces) in chain.blocks.iter_mut().zip(traces) {
+            debug_assert_eq!(block.transactions.len(), traces.len());
+            for (transaction, traces) in block.transactions.iter_mut().zip(traces) {
+                transaction.traces = traces.iter().map(trace_to_proto).collect();
+            }
+        }
+    }
+}
+
+impl From<&Chain> for proto::Chain {
+    fn from(chain: &Chain) -> Self {
+        let blocks =
+            chain.blocks_and_receipts().map(|(block, receipts)| block_to_proto(block, receipts));
+        let state_changes =
+            chain.execution_outcome().bundle_accounts_iter().map(|(address, account)| {
+                proto::AccountChange {
+                    address: address.to_vec(),
+                    info: account.info.as_ref().map(|info| proto::AccountInfo {
+                        balance: info.balance.to_be_bytes::<32>().to_vec(),
+                        nonce: info.nonce,
+                        code_hash: info.code_hash.to_vec(),
+                    }),
+                    storage: account
+                        .storage
+                        .iter()
+                        .map(|(slot, value)| proto::StorageChange {
+                            slot: slot.to_be_bytes::<32>().to_vec(),
+                            value: value.present_value.to_be_bytes::<32>().to_vec(),
+                        })
+                        .collect(),
+                }
+            });
+        Self { blocks: blocks.collect(), state_changes: state_changes.collect() }
+    }
+}
+
+fn block_to_proto(block: &SealedBlockWithSenders, receipts: &[Option<Receipt>]) -> proto::Block {
+    let transactions = block
+        .body
+        .iter()
+        .zip(&block.senders)
+        .enumerate()
+        .map(|(index, (transaction, sender))| proto::Transaction {
+            hash: transaction.hash().to_vec(),
+            sender: sender.to_vec(),
+            receipt: receipts.get(index).and_then(Option::as_ref).map(receipt_to_proto),
+        })
+        .collect();
+
+    proto::Block {
+        number: block.number,
+        hash: block.hash().to_vec(),
+        parent_hash: block.parent_hash.to_vec(),
+        timestamp: block.timestamp,
+        gas_used: block.gas_used,
+        transactions,
+    }
+}
+
+fn receipt_to_proto(receipt: &Receipt) -> proto::Receipt {
+    proto::Receipt {
+        tx_type: u8::from(receipt.tx_type).into(),
+        success: receipt.success,
+        cumulative_gas_used: receipt.cumulative_gas_used,
+        logs: receipt.logs.iter().map(log_to_proto).collect(),
+    }
+}
+
+fn log_to_proto(log: &Log) -> proto::Log {
+    proto::Log {
+        address: log.address.to_vec(),
+        topics: log.topics().iter().map(|topic| topic.to_vec()).collect(),
+        data: log.data.data.to_vec(),
+    }
+}
+
+fn trace_to_proto(trace: &LocalizedTransactionTrace) -> proto::Trace {
+    let trace = &trace.trace;
+    let action = match &trace.action {
+        Action::Call(call) => Some(proto::trace::Action::Call(proto::CallAction {
+            from: call.from.to_vec(),
+            to: call.to.to_vec(),
+            call_type: call_type_to_proto(call.call_type).into(),
+            gas: call.gas.to(),
+            input: call.input.to_vec(),
+            value: call.value.to_be_bytes::<32>().to_vec(),
+        })),
+        Action::Create(create) => Some(proto::trace::Action::Create(proto::CreateAction {
+            from: create.from.to_vec(),
+            gas: create.gas.to(),
+            init: create.init.to_vec(),
+            value: create.value.to_be_bytes::<32>().to_vec(),
+        })),
+        Action::Selfdestruct(selfdestruct) => {
+            Some(proto::trace::Action::Selfdestruct(proto::SelfdestructAction {
+                address: selfdestruct.address.to_vec(),
+                refund_address: selfdestruct.refund_address.to_vec(),
+                balance: selfdestruct.balance.to_be_bytes::<32>().to_vec(),
+            }))
+        }
+        // block rewards aren't part of the traces of a transaction
+        Action::Reward(_) => None,
+    };
+    let output = trace.result.as_ref().map(|output| match output {
+        TraceOutput::Call(call) => proto::trace::Output::CallOutput(proto::CallOutput {
+            gas_used: call.gas_used.to(),
+            output: call.output.to_vec(),
+        }),
+        TraceOutput::Create(create) => proto::trace::Output::CreateOutput(proto::CreateOutput {
+            address: create.address.to_vec(),
+            gas_used: create.gas_used.to(),
+            code: create.code.to_vec(),
+        }),
+    });
+
+    proto::Trace {
+        trace_address: trace.trace_address.iter().map(|index| *index as u64).collect(),
+        subtraces: trace.subtraces as u64,
+        action,
+        error: trace.error.clone(),
+        output,
+    }
+}
+
+const fn call_type_to_proto(call_type: CallType) -> proto::CallType {
+    match call_type {
+        CallType::None => proto::CallType::None,
+        CallType::Call => proto::CallType::Call,
+        CallType::CallCode => proto::CallType::CallCode,
+        CallType::DelegateCall => proto::CallType::DelegateCall,
+        CallType::StaticCall => proto::CallType::StaticCall,
+        CallType::AuthCall => proto::CallType::AuthCall,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_primitives::{Address, TransactionSigned, U64};
+    use reth_rpc_types::trace::parity::{CallAction, CallOutput, TransactionTrace};
+    use std::sync::Arc;
+
+    #[test]
+    fn sets_traces_of_committed_chain() {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.body.push(TransactionSigned::default());
+        block.senders.push(Address::ZERO);
+        let chain = Arc::new(Chain::new([block], Default::default(), None));
+
+        let trace = LocalizedTransactionTrace {
+            trace: TransactionTrace {
+                action: Action::Call(CallAction {
+                    call_type: CallType::DelegateCall,
+                    gas: U64::from(30_000),
+                    ..Default::default()
+                }),
+                result: Some(TraceOutput::Call(CallOutput {
+                    gas_used: U64::from(21_000),
+                    output: Default::default(),
+                })),
+                ..Default::default()
+            },
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_position: None,
+        };
+
+        let mut committed =
+            proto::ExExNotification::from(&ExExNotification::ChainCommitted { new: chain.clone() });
+        committed.set_traces(vec![vec![vec![trace]]]);
+        let Some(proto::ex_ex_notification::Notification::ChainCommitted(committed)) =
+            committed.notification
+        else {
+            panic!("expected a committed chain")
+        };
+        let traces = &committed.new.unwrap().blocks[0].transactions[0].traces;
+        assert_eq!(traces.len(), 1);
+        let Some(proto::trace::Action::Call(call)) = &traces[0].action else {
+            panic!("expected a call")
+        };
+        assert_eq!(call.call_type(), proto::CallType::DelegateCall);
+        assert_eq!(call.gas, 30_000);
+        let Some(proto::trace::Output::CallOutput(output)) = &traces[0].output else {
+            panic!("expected a call output")
+        };
+        assert_eq!(output.gas_used, 21_000);
+
+        // reverted chains don't have traces
+        let reverted =
+            proto::ExExNotification::from(&ExExNotification::ChainReverted { old: chain });
+        let mut traced = reverted.clone();
+        traced.set_traces(vec![vec![Vec::new()]]);
+        assert_eq!(traced, reverted);
+    }
+}
@@ -0,0 +1,151 @@
+//! Example of an `ExEx` that streams the chain notifications of the node over gRPC.
+//!
+//! Consumers in any language can subscribe to the notifications with a client generated from the
+//! protobuf schema in `proto/exex.proto`, without writing an `ExEx` themselves. Every notification
+//! is reorg-aware: it contains the reverted and the committed chains, with their blocks,
+//! transactions, senders, receipts and logs, and the state changes of the chains. With
+//! `--exex.grpc.traces`, the call traces of the committed transactions are included as well, which
+//! requires re-executing every committed block.
+//!
+//! Run with
+//!
+//! ```not_rust
+//! cargo run -p example-exex-grpc -- node --exex.grpc.addr 127.0.0.1:10000
+//! ```
+
+use clap::Parser;
+use futures::FutureExt;
+use reth_execution_types::Chain;
+use reth_exex::{ExExContext, ExExEvent};
+use reth_node_api::FullNodeComponents;
+use reth_node_ethereum::EthereumNode;
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{future::Future, net::SocketAddr, sync::Arc};
+use tokio::{net::TcpListener, sync::broadcast};
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+mod codec;
+mod service;
+mod traces;
+
+use service::ExExService;
+use traces::{trace_chain, ChainTraces};
+
+/// The generated protobuf types, and the gRPC server and client.
+#[allow(missing_docs, unreachable_pub, clippy::all)]
+mod proto {
+    tonic::include_proto!("exex");
+}
+
+/// The default address the gRPC server listens on.
+const DEFAULT_ADDR: &str = "127.0.0.1:10000";
+
+/// The default number of notifications a subscriber can fall behind before it's disconnected.
+const DEFAULT_BUFFER: usize = 64;
+
+/// Parameters for the gRPC `ExEx`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::Args)]
+struct ExExGrpcArgs {
+    /// Serve the chain notifications over gRPC on the given address.
+    #[arg(long = "exex.grpc.addr", value_name = "ADDR", default_value = DEFAULT_ADDR)]
+    addr: SocketAddr,
+
+    /// The number of notifications a subscriber can fall behind before it's disconnected.
+    #[arg(long = "exex.grpc.buffer", default_value_t = DEFAULT_BUFFER)]
+    buffer: usize,
+
+    /// Include the call traces of the committed transactions, by re-executing the committed
+    /// blocks.
+    #[arg(long = "exex.grpc.traces")]
+    traces: bool,
+}
+
+/// Starts the gRPC server, and returns the `ExEx` that forwards the chain notifications to its
+/// subscribers.
+///
+/// The server is shut down gracefully with the node. If it fails, the node keeps running and
+/// only the subscribers are disconnected.
+async fn init<Node: FullNodeComponents>(
+    ctx: ExExContext<Node>,
+    args: ExExGrpcArgs,
+) -> eyre::Result<impl Future<Output = eyre::Result<()>>> {
+    let service = ExExService::new(args.buffer);
+    let notifications = service.sender();
+
+    let listener = TcpListener::bind(args.addr).await?;
+    info!(target: "exex::grpc", local_addr = %listener.local_addr()?, "gRPC server started");
+    ctx.task_executor().spawn_with_graceful_shutdown_signal(|shutdown| async move {
+        let mut guard = None;
+        let server = Server::builder()
+            .add_service(proto::ex_ex_server::ExExServer::new(service))
+            .serve_with_incoming_shutdown(
+                TcpListenerStream::new(listener),
+                shutdown.map(|shutdown_guard| guard = Some(shutdown_guard)),
+            );
+        if let Err(err) = server.await {
+            error!(target: "exex::grpc", %err, "gRPC server failed");
+        }
+        drop(guard);
+    });
+
+    Ok(grpc_exex(ctx, notifications, args.traces))
+}
+
+/// Forwards all chain notifications to the subscribers of the gRPC server.
+async fn grpc_exex<Node: FullNodeComponents>(
+    mut ctx: ExExContext<Node>,
+    notifications: broadcast::Sender<proto::ExExNotification>,
+    traces: bool,
+) -> eyre::Result<()> {
+    while let Some(notification) = ctx.notifications.recv().await {
+        // encoding the notification is skipped if there are no subscribers
+        if notifications.receiver_count() > 0 {
+            let mut encoded = proto::ExExNotification::from(&notification);
+            if traces {
+                if let Some(chain) = notification.committed_chain() {
+                    match trace_committed_chain(&ctx, chain).await {
+                        Ok(chain_traces) => encoded.set_traces(chain_traces),
+                        Err(err) => {
+                            warn!(target: "exex::grpc", %err, "Failed to trace committed chain")
+                        }
+                    }
+                }
+            }
+            let _ = notifications.send(encoded);
+        }
+
+        if let Some(committed_chain) = notification.committed_chain() {
+            ctx.events.send(ExExEvent::FinishedHeight(committed_chain.tip().number))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Replays the committed chain on a blocking thread, and returns the call traces of its
+/// transactions.
+async fn trace_committed_chain<Node: FullNodeComponents>(
+    ctx: &ExExContext<Node>,
+    chain: Arc<Chain>,
+) -> eyre::Result<ChainTraces> {
+    let provider = ctx.provider().clone();
+    let evm_config = ctx.evm_config().clone();
+    let range = chain.range();
+    let chain_traces =
+        tokio::task::spawn_blocking(move || trace_chain(&provider, &evm_config, &chain)).await??;
+    debug!(target: "exex::grpc", ?range, "Traced committed chain");
+    Ok(chain_traces)
+}
+
+fn main() -> eyre::Result<()> {
+    reth::cli::Cli::<ExExGrpcArgs>::parse().run(|builder, args| async move {
+        let handle = builder
+            .node(EthereumNode::default())
+            .install_exex("gRPC", move |ctx| init(ctx, args))
+            .launch()
+            .await?;
+
+        handle.wait_for_node_exit().await
+    })
+}
@@ -0,0 +1,91 @@
+//! The gRPC service that streams the notifications to its subscribers.
+
+use crate::proto::{self, ex_ex_server::ExEx};
+use futures::Stream;
+use std::pin::Pin;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tonic::{Request, Response, Status};
+
+/// Implements the `ExEx` gRPC service, by streaming the notifications sent to [`Self::sender`] to
+/// all subscribers.
+#[derive(Debug, Clone)]
+pub struct ExExService {
+    notifications: broadcast::Sender<proto::ExExNotification>,
+}
+
+impl ExExService {
+    /// Creates a new service, which disconnects subscribers that fall more than `buffer`
+    /// notifications behind.
+    pub fn new(buffer: usize) -> Self {
+        let (notifications, _) = broadcast::channel(buffer);
+        Self { notifications }
+    }
+
+    /// Returns the sender of the notifications that are streamed to the subscribers.
+    pub fn sender(&self) -> broadcast::Sender<proto::ExExNotification> {
+        self.notifications.clone()
+    }
+}
+
+#[tonic::async_trait]
+impl ExEx for ExExService {
+    type SubscribeStream =
+        Pin<Box<dyn Stream<Item = Result<proto::ExExNotification, Status>> + Send>>;
+
+    async fn subscribe(
+        &self,
+        _request: Request<proto::SubscribeRequest>,
+    ) -> Result<Response<Self::SubscribeStream>, Status> {
+        let receiver = self.notifications.subscribe();
+        let stream = futures::stream::unfold(Some(receiver), |receiver| async move {
+            let mut receiver = receiver?;
+            match receiver.recv().await {
+                Ok(notification) => Some((Ok(notification), Some(receiver))),
+                // a subscriber that missed notifications can't follow reorgs anymore, so the
+                // stream is terminated
+                Err(RecvError::Lagged(skipped)) => Some((
+                    Err(Status::data_loss(format!("subscriber missed {skipped} notifications"))),
+                    None,
+                )),
+                Err(RecvError::Closed) => None,
+            }
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+    use reth_execution_types::Chain;
+    use reth_exex::ExExNotification;
+    use reth_primitives::SealedBlockWithSenders;
+    use std::sync::Arc;
+    use tonic::Code;
+
+    fn notification(number: u64) -> proto::ExExNotification {
+        let mut block = SealedBlockWithSenders::default();
+        block.block.header.set_block_number(number);
+        let chain = Chain::new([block], Default::default(), None);
+        (&ExExNotification::ChainCommitted { new: Arc::new(chain) }).into()
+    }
+
+    #[tokio::test]
+    async fn streams_notifications() {
+        let service = ExExService::new(2);
+        let mut stream =
+            service.subscribe(Request::new(proto::SubscribeRequest {})).await.unwrap().into_inner();
+
+        let sender = service.sender();
+        sender.send(notification(1)).unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), notification(1));
+
+        // the subscriber is disconnected once it falls behind
+        for number in 2..5 {
+            sender.send(notification(number)).unwrap();
+        }
+        assert_eq!(stream.next().await.unwrap().unwrap_err().code(), Code::DataLoss);
+        assert!(stream.next().await.is_none());
+    }
+}
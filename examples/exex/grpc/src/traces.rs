@@ -0,0 +1,81 @@
+//! Call traces of the committed chains, produced by replaying their blocks.
+
+use reth_evm::ConfigureEvm;
+use reth_execution_types::Chain;
+use reth_primitives::SealedBlockWithSenders;
+use reth_provider::{EvmEnvProvider, StateProviderFactory};
+use reth_revm::{
+    database::StateProviderDatabase,
+    db::CacheDB,
+    primitives::{EnvWithHandlerCfg, ResultAndState, TxEnv},
+    DatabaseCommit,
+};
+use reth_rpc_types::{trace::parity::LocalizedTransactionTrace, TransactionInfo};
+use revm_inspectors::tracing::{TracingInspector, TracingInspectorConfig};
+
+/// The call traces of the transactions, for every block of a chain.
+pub(crate) type ChainTraces = Vec<Vec<Vec<LocalizedTransactionTrace>>>;
+
+/// Replays the blocks of the chain on top of the state of their parents, and returns the call
+/// traces of their transactions.
+///
+/// This re-executes the whole chain, so it should be called on a blocking thread.
+pub(crate) fn trace_chain<P, E>(
+    provider: &P,
+    evm_config: &E,
+    chain: &Chain,
+) -> eyre::Result<ChainTraces>
+where
+    P: StateProviderFactory + EvmEnvProvider,
+    E: ConfigureEvm,
+{
+    chain.blocks_iter().map(|block| trace_block(provider, evm_config, block)).collect()
+}
+
+/// Replays the block on top of the state of its parent, and returns the call traces of its
+/// transactions.
+fn trace_block<P, E>(
+    provider: &P,
+    evm_config: &E,
+    block: &SealedBlockWithSenders,
+) -> eyre::Result<Vec<Vec<LocalizedTransactionTrace>>>
+where
+    P: StateProviderFactory + EvmEnvProvider,
+    E: ConfigureEvm,
+{
+    let (cfg, block_env) = provider.env_with_header(&block.header, evm_config.clone())?;
+    let state_provider = provider.state_by_block_hash(block.parent_hash)?;
+    let mut db = CacheDB::new(StateProviderDatabase::new(state_provider));
+
+    let mut traces = Vec::with_capacity(block.body.len());
+    for (index, (sender, transaction)) in block.transactions_with_sender().enumerate() {
+        let mut tx_env = TxEnv::default();
+        E::fill_tx_env(&mut tx_env, transaction, *sender);
+        let env = EnvWithHandlerCfg::new_with_cfg_env(cfg.clone(), block_env.clone(), tx_env);
+
+        let mut inspector = TracingInspector::new(TracingInspectorConfig::default_parity());
+        let ResultAndState { result, state } = evm_config
+            .evm_with_env_and_inspector(&mut db, env, &mut inspector)
+            .transact()
+            .map_err(|err| {
+                eyre::eyre!("failed to replay transaction {}: {err}", transaction.hash())
+            })?;
+        db.commit(state);
+
+        let info = TransactionInfo {
+            hash: Some(transaction.hash()),
+            index: Some(index as u64),
+            block_hash: Some(block.hash()),
+            block_number: Some(block.number),
+            base_fee: block.base_fee_per_gas.map(u128::from),
+        };
+        traces.push(
+            inspector
+                .with_transaction_gas_used(result.gas_used())
+                .into_parity_builder()
+                .into_localized_transaction_traces(info),
+        );
+    }
+
+    Ok(traces)
+}
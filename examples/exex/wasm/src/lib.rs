@@ -0,0 +1,93 @@
+//! Example of an `ExEx` compiled to WASM, that logs the committed and reverted blocks.
+//!
+//! Build the module, and run it with a node built with the `wasm-exex` feature:
+//!
+//! ```not_rust
+//! cargo build --manifest-path examples/exex/wasm/Cargo.toml --target wasm32-wasip1 --release
+//! cargo run --features wasm-exex -- node \
+//!     --exex.wasm.module examples/exex/wasm/target/wasm32-wasip1/release/example_exex_wasm.wasm
+//! ```
+
+use serde::Deserialize;
+
+#[link(wasm_import_module = "reth")]
+extern "C" {
+    fn log(ptr: *const u8, len: u32);
+    fn latest_block_number() -> u64;
+}
+
+/// A notification, as passed by the host.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Notification {
+    ChainCommitted { new: Chain },
+    ChainReorged { old: Chain, new: Chain },
+    ChainReverted { old: Chain },
+}
+
+#[derive(Deserialize)]
+struct Chain {
+    blocks: Vec<Block>,
+}
+
+/// The fields of a block this `ExEx` is interested in, the others are ignored.
+#[derive(Deserialize)]
+struct Block {
+    number: u64,
+    hash: String,
+    transactions: Vec<String>,
+    logs: Vec<serde::de::IgnoredAny>,
+}
+
+fn info(message: &str) {
+    // SAFETY: the host only reads `len` bytes at `ptr`
+    unsafe { log(message.as_ptr(), message.len() as u32) }
+}
+
+fn log_blocks(action: &str, chain: &Chain) {
+    // SAFETY: the host function takes no arguments
+    let latest = unsafe { latest_block_number() };
+    for block in &chain.blocks {
+        info(&format!(
+            "{action} block {} ({}) with {} transactions and {} logs, latest block is {latest}",
+            block.number,
+            block.hash,
+            block.transactions.len(),
+            block.logs.len()
+        ));
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn reth_exex_abi_version() -> u32 {
+    1
+}
+
+#[no_mangle]
+pub extern "C" fn reth_exex_alloc(len: u32) -> *mut u8 {
+    Box::into_raw(vec![0u8; len as usize].into_boxed_slice()).cast()
+}
+
+#[no_mangle]
+pub extern "C" fn reth_exex_notification(ptr: *mut u8, len: u32) -> i64 {
+    // SAFETY: the buffer was allocated by `reth_exex_alloc` with the same length, and its
+    // ownership is passed back to us
+    let payload = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len as usize)) };
+    let Ok(notification) = serde_json::from_slice::<Notification>(&payload) else { return -2 };
+
+    match notification {
+        Notification::ChainCommitted { new } => {
+            log_blocks("Committed", &new);
+            new.blocks.last().map_or(-1, |block| block.number as i64)
+        }
+        Notification::ChainReorged { old, new } => {
+            log_blocks("Reverted", &old);
+            log_blocks("Committed", &new);
+            new.blocks.last().map_or(-1, |block| block.number as i64)
+        }
+        Notification::ChainReverted { old } => {
+            log_blocks("Reverted", &old);
+            -1
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use reth_chainspec::{BaseFeeParams, Chain, ChainSpec, ForkCondition, Hardfork};
 use reth_discv4::NodeRecord;
-use reth_primitives::{b256, Head, B256};
+use reth_primitives::{
+    b256,
+    constants::{ALLOWED_FUTURE_BLOCK_TIME_SECONDS, MAXIMUM_EXTRA_DATA_SIZE},
+    Head, B256,
+};
 
 use std::{collections::BTreeMap, sync::Arc};
 
@@ -26,6 +30,8 @@ pub(crate) fn polygon_chain_spec() -> Arc<ChainSpec> {
         deposit_contract: None,
         base_fee_params: reth_chainspec::BaseFeeParamsKind::Constant(BaseFeeParams::ethereum()),
         prune_delete_limit: 0,
+        max_extra_data_size: MAXIMUM_EXTRA_DATA_SIZE,
+        max_future_timestamp_drift: ALLOWED_FUTURE_BLOCK_TIME_SECONDS,
     }
     .into()
 }